@@ -21,10 +21,43 @@ use util::ResultExt;
 
 pub const MAX_WORD_DIFF_LINE_COUNT: usize = 5;
 
+/// Which whitespace differences are disregarded when computing hunks.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum IgnoreWhitespace {
+    /// Every whitespace difference produces a hunk.
+    #[default]
+    None,
+    /// Whitespace at the end of lines is ignored.
+    Trailing,
+    /// All whitespace differences are ignored, including indentation changes.
+    All,
+}
+
+impl IgnoreWhitespace {
+    /// The mode after this one when cycling through them, from `None` to `All` and back.
+    pub fn next(self) -> Self {
+        match self {
+            Self::None => Self::Trailing,
+            Self::Trailing => Self::All,
+            Self::All => Self::None,
+        }
+    }
+
+    /// A short description of the mode, used as a tooltip for the button that cycles it.
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::None => "Ignore Whitespace",
+            Self::Trailing => "Ignoring Trailing Whitespace",
+            Self::All => "Ignoring All Whitespace",
+        }
+    }
+}
+
 pub struct BufferDiff {
     pub buffer_id: BufferId,
     inner: BufferDiffInner<Entity<language::Buffer>>,
     secondary_diff: Option<Entity<BufferDiff>>,
+    ignore_whitespace: IgnoreWhitespace,
 }
 
 #[derive(Clone)]
@@ -1091,10 +1124,64 @@ fn build_diff_options(
         })
 }
 
+/// Applies the changes between `index_text` and `buffer_text` that touch the given buffer rows to
+/// `index_text`. Within a change, each selected buffer line replaces the index line at the same
+/// offset, and index lines beyond the end of the buffer lines are removed along with the change's
+/// last line. A pure deletion is staged when the row below it is selected, or the last row when it
+/// is at the end of the file.
+fn stage_lines(index_text: &str, buffer_text: &str, rows: &[Range<u32>]) -> String {
+    let index_lines = index_text.split_inclusive('\n').collect::<Vec<_>>();
+    let buffer_lines = buffer_text.split_inclusive('\n').collect::<Vec<_>>();
+    let buffer_row_count = buffer_lines.len() as u32;
+    let is_selected = |row: u32| rows.iter().any(|range| range.contains(&row));
+    let line = |lines: &[&str], row: u32| lines.get(row as usize).copied().unwrap_or_default();
+
+    let mut new_index_text = String::with_capacity(index_text.len());
+    let mut index_row = 0;
+    for (old_rows, new_rows) in language::line_diff(index_text, buffer_text) {
+        for row in index_row..old_rows.start {
+            new_index_text.push_str(line(&index_lines, row));
+        }
+        index_row = old_rows.end;
+
+        let old_len = old_rows.end - old_rows.start;
+        let new_len = new_rows.end - new_rows.start;
+        if new_len == 0 {
+            let staged = is_selected(new_rows.start)
+                || (new_rows.start == buffer_row_count
+                    && rows.iter().any(|range| range.end >= buffer_row_count));
+            if !staged {
+                for row in old_rows {
+                    new_index_text.push_str(line(&index_lines, row));
+                }
+            }
+            continue;
+        }
+
+        for offset in 0..new_len {
+            if is_selected(new_rows.start + offset) {
+                new_index_text.push_str(line(&buffer_lines, new_rows.start + offset));
+            } else if offset < old_len {
+                new_index_text.push_str(line(&index_lines, old_rows.start + offset));
+            }
+        }
+        if old_len > new_len && !is_selected(new_rows.end - 1) {
+            for row in old_rows.start + new_len..old_rows.end {
+                new_index_text.push_str(line(&index_lines, row));
+            }
+        }
+    }
+    for row in index_row..index_lines.len() as u32 {
+        new_index_text.push_str(line(&index_lines, row));
+    }
+    new_index_text
+}
+
 fn compute_hunks(
     diff_base: Option<(Arc<str>, Rope)>,
     buffer: &text::BufferSnapshot,
     diff_options: Option<DiffOptions>,
+    ignore_whitespace: IgnoreWhitespace,
 ) -> SumTree<InternalDiffHunk> {
     let mut tree = SumTree::new(buffer);
 
@@ -1103,6 +1190,15 @@ fn compute_hunks(
 
         let mut options = GitOptions::default();
         options.context_lines(0);
+        match ignore_whitespace {
+            IgnoreWhitespace::None => {}
+            IgnoreWhitespace::Trailing => {
+                options.ignore_whitespace_eol(true);
+            }
+            IgnoreWhitespace::All => {
+                options.ignore_whitespace(true);
+            }
+        }
         let patch = GitPatch::from_buffers(
             diff_base.as_bytes(),
             None,
@@ -1151,6 +1247,7 @@ fn compute_hunks(
                 buffer_word_diffs: Vec::default(),
             },
             buffer,
+            IgnoreWhitespace::None,
         );
     }
 
@@ -1508,6 +1605,7 @@ impl BufferDiff {
                 buffer_snapshot: buffer.clone(),
             },
             secondary_diff: None,
+            ignore_whitespace: IgnoreWhitespace::None,
         }
     }
 
@@ -1529,6 +1627,7 @@ impl BufferDiff {
                 buffer_snapshot: buffer.clone(),
             },
             secondary_diff: None,
+            ignore_whitespace: IgnoreWhitespace::None,
         }
     }
 
@@ -1560,6 +1659,15 @@ impl BufferDiff {
         self.secondary_diff.clone()
     }
 
+    /// Sets which whitespace differences are ignored by subsequent diff updates.
+    pub fn set_ignore_whitespace(&mut self, ignore_whitespace: IgnoreWhitespace) {
+        self.ignore_whitespace = ignore_whitespace;
+    }
+
+    pub fn ignore_whitespace(&self) -> IgnoreWhitespace {
+        self.ignore_whitespace
+    }
+
     pub fn clear_pending_hunks(&mut self, cx: &mut Context<Self>) {
         if self.secondary_diff.is_some() {
             self.inner.pending_hunks = SumTree::from_summary(DiffHunkSummary {
@@ -1614,6 +1722,27 @@ impl BufferDiff {
         new_index_text
     }
 
+    /// Stages the changes on the given buffer rows, leaving the other lines of the hunks they fall
+    /// in unstaged. Returns the new index text.
+    pub fn stage_lines(
+        &mut self,
+        rows: &[Range<u32>],
+        buffer: &text::BufferSnapshot,
+        cx: &mut Context<Self>,
+    ) -> Option<Rope> {
+        let secondary_diff = self.secondary_diff.as_ref()?.read(cx);
+        let index_text = if secondary_diff.inner.base_text_exists {
+            secondary_diff.inner.base_text.read(cx).text()
+        } else {
+            String::new()
+        };
+        let new_index_text = Rope::from(stage_lines(&index_text, &buffer.text(), rows).as_str());
+        cx.emit(BufferDiffEvent::HunksStagedOrUnstaged(Some(
+            new_index_text.clone(),
+        )));
+        Some(new_index_text)
+    }
+
     pub fn stage_or_unstage_all_hunks(
         &mut self,
         stage: bool,
@@ -1659,6 +1788,7 @@ impl BufferDiff {
             language.as_ref().map(|l| l.default_scope()),
             cx,
         );
+        let ignore_whitespace = self.ignore_whitespace;
         let buffer_snapshot = buffer.clone();
 
         let base_text_diff_task = if base_text_changed && compute_base_text_edits {
@@ -1688,6 +1818,7 @@ impl BufferDiff {
                         .map(|base_text| (base_text, base_text_rope.clone())),
                     &buffer,
                     diff_options,
+                    ignore_whitespace,
                 );
                 let base_text = base_text.unwrap_or_default();
                 BufferDiffInner {
@@ -2200,6 +2331,48 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_compute_hunks_ignoring_whitespace() {
+        let diff_base = "
+            fn main() {
+                one();
+                two();
+                three();
+            }
+        "
+        .unindent();
+
+        let buffer_text = "
+            fn main() {
+                one();\t
+              two();
+                THREE();
+            }
+        "
+        .unindent();
+
+        let buffer = Buffer::new(ReplicaId::LOCAL, BufferId::new(1).unwrap(), buffer_text);
+        let hunk_rows = |ignore_whitespace| {
+            compute_hunks(
+                Some((
+                    Arc::from(diff_base.as_str()),
+                    Rope::from(diff_base.as_str()),
+                )),
+                buffer.snapshot(),
+                None,
+                ignore_whitespace,
+            )
+            .iter()
+            .map(|hunk| hunk.buffer_range.to_point(buffer.snapshot()))
+            .map(|range| range.start.row..range.end.row)
+            .collect::<Vec<_>>()
+        };
+
+        assert_eq!(hunk_rows(IgnoreWhitespace::None), vec![1..4]);
+        assert_eq!(hunk_rows(IgnoreWhitespace::Trailing), vec![2..4]);
+        assert_eq!(hunk_rows(IgnoreWhitespace::All), vec![3..4]);
+    }
+
     #[gpui::test]
     async fn test_buffer_diff_with_secondary(cx: &mut gpui::TestAppContext) {
         let head_text = "
@@ -3486,6 +3659,7 @@ mod tests {
             Some((Arc::from(initial_base), Rope::from(initial_base))),
             buffer.snapshot(),
             None,
+            IgnoreWhitespace::None,
         );
 
         // Insert "XXX\n" after "aaa\n" in the base text.
@@ -3497,6 +3671,7 @@ mod tests {
             Some((new_base_str_1.clone(), Rope::from(new_base_str_1.as_ref()))),
             buffer.snapshot(),
             None,
+            IgnoreWhitespace::None,
         );
 
         let DiffChanged {
@@ -3545,6 +3720,7 @@ mod tests {
             Some((Arc::from(simple_base), Rope::from(simple_base))),
             buffer_2.snapshot(),
             None,
+            IgnoreWhitespace::None,
         );
 
         // The base text is edited so "two" becomes "TWO", now matching the buffer.
@@ -3556,6 +3732,7 @@ mod tests {
             Some((new_base_str_2.clone(), Rope::from(new_base_str_2.as_ref()))),
             buffer_2.snapshot(),
             None,
+            IgnoreWhitespace::None,
         );
 
         let DiffChanged {
@@ -3612,6 +3789,7 @@ mod tests {
             Some((Arc::from(base_3), Rope::from(base_3))),
             buffer_3.snapshot(),
             None,
+            IgnoreWhitespace::None,
         );
 
         // Change "ddd" to "DDD" in the base text so that hunk disappears,
@@ -3624,6 +3802,7 @@ mod tests {
             Some((new_base_str_3.clone(), Rope::from(new_base_str_3.as_ref()))),
             buffer_3.snapshot(),
             None,
+            IgnoreWhitespace::None,
         );
 
         let DiffChanged {
@@ -3679,6 +3858,7 @@ mod tests {
             Some((Arc::from(base_4), Rope::from(base_4))),
             buffer_4.snapshot(),
             None,
+            IgnoreWhitespace::None,
         );
 
         // Edit the buffer: change "delta" to "DELTA" (new modification hunk).
@@ -3701,6 +3881,7 @@ mod tests {
             Some((new_base_str_4.clone(), Rope::from(new_base_str_4.as_ref()))),
             buffer_4.snapshot(),
             None,
+            IgnoreWhitespace::None,
         );
 
         let DiffChanged {
@@ -3947,4 +4128,39 @@ mod tests {
             }
         }
     }
+
+    #[test]
+    fn test_stage_lines() {
+        let index_text = "one\ntwo\nthree\nfour\nfive\n";
+        let buffer_text = "ONE\nTWO\nthree\nfour\nNEW\nfive\n";
+
+        // Only the selected line of a modified group is staged.
+        assert_eq!(
+            stage_lines(index_text, buffer_text, &[1..2]),
+            "one\nTWO\nthree\nfour\nfive\n"
+        );
+        // Changes outside the selection are left alone.
+        assert_eq!(
+            stage_lines(index_text, buffer_text, &[4..5]),
+            "one\ntwo\nthree\nfour\nNEW\nfive\n"
+        );
+        assert_eq!(
+            stage_lines(index_text, buffer_text, &[0..2, 4..5]),
+            buffer_text
+        );
+        assert_eq!(stage_lines(index_text, buffer_text, &[2..4]), index_text);
+
+        // Deletions are staged with the row below them, or the last row at the end of the file.
+        let buffer_text = "one\nfour\n";
+        assert_eq!(stage_lines(index_text, buffer_text, &[0..1]), index_text);
+        assert_eq!(stage_lines(index_text, buffer_text, &[1..2]), buffer_text);
+        assert_eq!(
+            stage_lines(index_text, "one\ntwo\nfour\nfive\n", &[2..3]),
+            "one\ntwo\nfour\nfive\n"
+        );
+        assert_eq!(
+            stage_lines(index_text, "one\ntwo\nfour\nfive\n", &[1..2]),
+            index_text
+        );
+    }
 }
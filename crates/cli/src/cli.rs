@@ -15,6 +15,8 @@ pub enum CliRequest {
         urls: Vec<String>,
         diff_paths: Vec<[String; 2]>,
         diff_all: bool,
+        /// The base, local, remote, and output files of a `git mergetool` invocation.
+        merge_paths: Option<[String; 4]>,
         wsl: Option<String>,
         wait: bool,
         open_new_workspace: Option<bool>,
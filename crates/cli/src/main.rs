@@ -122,6 +122,11 @@ struct Args {
     /// When directories are provided, recurses into them and shows all changed files in a single multi-diff view.
     #[arg(long, action = clap::ArgAction::Append, num_args = 2, value_names = ["OLD_PATH", "NEW_PATH"])]
     diff: Vec<String>,
+    /// Resolve a merge conflict between the local and remote versions of a file, as done by
+    /// `git mergetool`. Waits until the result is saved to the output file, or the merge is
+    /// closed, and exits with a non-zero status if it was not saved.
+    #[arg(long, num_args = 4, value_names = ["BASE", "LOCAL", "REMOTE", "OUTPUT"], conflicts_with = "diff")]
+    merge: Vec<String>,
     /// Uninstall Zed from user system
     #[cfg(all(
         any(target_os = "linux", target_os = "macos"),
@@ -590,6 +595,16 @@ fn main() -> Result<()> {
         ]);
     }
 
+    let merge_paths = match args.merge.as_slice() {
+        [base, local, remote, output] => Some([
+            parse_path_with_position(base)?,
+            parse_path_with_position(local)?,
+            parse_path_with_position(remote)?,
+            parse_path_with_position(output)?,
+        ]),
+        _ => None,
+    };
+
     let (expanded_diff_paths, temp_dirs) = expand_directory_diff_pairs(diff_paths)?;
     diff_paths = expanded_diff_paths;
     // Prevent automatic cleanup of temp directories containing empty stub files
@@ -664,6 +679,7 @@ fn main() -> Result<()> {
                     urls,
                     diff_paths,
                     diff_all: diff_all_mode,
+                    merge_paths,
                     wsl,
                     wait: args.wait,
                     open_new_workspace,
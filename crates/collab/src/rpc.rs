@@ -421,8 +421,32 @@ impl Server {
             .add_request_handler(forward_mutating_project_request::<proto::Commit>)
             .add_request_handler(forward_mutating_project_request::<proto::RunGitHook>)
             .add_request_handler(forward_mutating_project_request::<proto::GitInit>)
+            .add_request_handler(
+                forward_mutating_project_request::<proto::GitResolveInProgressOperation>,
+            )
+            .add_request_handler(forward_mutating_project_request::<proto::GitCherryPick>)
+            .add_request_handler(forward_mutating_project_request::<proto::GitRevertCommit>)
+            .add_request_handler(forward_mutating_project_request::<proto::GitBisectStart>)
+            .add_request_handler(forward_mutating_project_request::<proto::GitBisectMark>)
+            .add_request_handler(forward_mutating_project_request::<proto::GitBisectReset>)
+            .add_request_handler(forward_read_only_project_request::<proto::GitReflog>)
+            .add_request_handler(forward_read_only_project_request::<proto::GitCommitLog>)
+            .add_request_handler(forward_read_only_project_request::<proto::GitCommitsBetween>)
+            .add_request_handler(forward_mutating_project_request::<proto::GitCheckoutRevision>)
+            .add_request_handler(forward_mutating_project_request::<proto::GitStashBranch>)
+            .add_request_handler(forward_mutating_project_request::<proto::GitSetBranchUpstream>)
+            .add_request_handler(forward_read_only_project_request::<proto::GitGetTags>)
+            .add_request_handler(forward_mutating_project_request::<proto::GitCreateTag>)
+            .add_request_handler(forward_mutating_project_request::<proto::GitDeleteTag>)
             .add_request_handler(forward_read_only_project_request::<proto::GetRemotes>)
             .add_request_handler(forward_read_only_project_request::<proto::GitShow>)
+            .add_request_handler(forward_read_only_project_request::<proto::GitLoadTextAtRevision>)
+            .add_request_handler(forward_read_only_project_request::<proto::GitLoadConflictStages>)
+            .add_request_handler(forward_read_only_project_request::<proto::GitLoadCommitTemplate>)
+            .add_request_handler(forward_read_only_project_request::<proto::GitRecentCommitAuthors>)
+            .add_request_handler(
+                forward_read_only_project_request::<proto::GitVerifyCommitSignatures>,
+            )
             .add_request_handler(forward_read_only_project_request::<proto::LoadCommitDiff>)
             .add_request_handler(forward_read_only_project_request::<proto::GitReset>)
             .add_request_handler(forward_read_only_project_request::<proto::GitCheckoutFiles>)
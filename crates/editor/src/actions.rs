@@ -466,6 +466,9 @@ actions!(
         CopyFileName,
         /// Copies the file name without extension to the clipboard.
         CopyFileNameWithoutExtension,
+        /// Copies a link to the current line on the remote's HEAD, its default branch, which
+        /// keeps following the file rather than pinning the checked out commit.
+        CopyPermalinkAtHead,
         /// Copies a permalink to the current line.
         CopyPermalinkToLine,
         /// Cuts selected text to the clipboard.
@@ -819,8 +822,12 @@ actions!(
         ToggleAutoSignatureHelp,
         /// Toggles inline git blame display.
         ToggleGitBlameInline,
+        /// Toggles coloring the git blame column by the age of each line's commit.
+        ToggleGitBlameHeatMap,
         /// Opens the git commit for the blame at cursor.
         OpenGitBlameCommit,
+        /// Blames the file as it was before the commit blamed for the line at the cursor.
+        OpenGitBlameBeforeCommit,
         /// Toggles the diagnostics panel.
         ToggleDiagnostics,
         /// Toggles indent guides display.
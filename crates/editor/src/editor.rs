@@ -68,7 +68,7 @@ pub use element::{
     CursorLayout, EditorElement, HighlightedRange, HighlightedRangeLine, PointForPosition,
     render_breadcrumb_text,
 };
-pub use git::blame::BlameRenderer;
+pub use git::blame::{BlameRenderer, BlameRevision};
 pub use hover_popover::hover_markdown_style;
 pub use inlays::Inlay;
 pub use items::MAX_TAB_TITLE_LEN;
@@ -1066,6 +1066,20 @@ impl DiffReviewDragState {
     }
 }
 
+/// The working-tree text of a hunk that was restored to its base text, kept so that the restore
+/// can be undone.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DiscardedHunk {
+    pub buffer: WeakEntity<Buffer>,
+    /// Where the restored base text now is in the buffer.
+    pub range: Range<text::Anchor>,
+    pub discarded_text: String,
+    /// The base text the hunk was restored to, to tell whether it was edited since.
+    pub restored_text: String,
+    /// The file's index text from before the restore unstaged the hunk, if it had one.
+    pub index_text: Option<Arc<str>>,
+}
+
 /// Identifies a specific hunk in the diff buffer.
 /// Used as a key to group comments by their location.
 #[derive(Clone, Debug)]
@@ -1274,6 +1288,7 @@ pub struct Editor {
     show_git_blame_inline: bool,
     show_git_blame_inline_delay_task: Option<Task<()>>,
     git_blame_inline_enabled: bool,
+    show_git_blame_heat_map: Option<bool>,
     render_diff_hunk_controls: RenderDiffHunkControlsFn,
     buffer_serialization: Option<BufferSerialization>,
     show_selection_menu: Option<bool>,
@@ -2510,6 +2525,7 @@ impl Editor {
             show_git_blame_inline_delay_task: None,
             git_blame_inline_enabled: full_mode
                 && ProjectSettings::get_global(cx).git.inline_blame.enabled,
+            show_git_blame_heat_map: None,
             render_diff_hunk_controls: Arc::new(render_diff_hunk_controls),
             buffer_serialization: is_minimap.not().then(|| {
                 BufferSerialization::new(
@@ -11747,29 +11763,63 @@ impl Editor {
             return;
         }
         let hunks = self.snapshot(window, cx).hunks_for_ranges(ranges);
+        let mut discarded_hunks = Vec::new();
         self.transact(window, cx, |editor, window, cx| {
-            editor.restore_diff_hunks(hunks, cx);
+            discarded_hunks = editor.restore_diff_hunks(hunks, cx);
             editor.change_selections(SelectionEffects::no_scroll(), window, cx, |selections| {
                 selections.refresh()
             });
         });
+        if !discarded_hunks.is_empty() {
+            cx.emit(EditorEvent::HunksDiscarded {
+                hunks: discarded_hunks,
+            });
+        }
     }
 
-    pub(crate) fn restore_diff_hunks(&self, hunks: Vec<MultiBufferDiffHunk>, cx: &mut App) {
+    pub(crate) fn restore_diff_hunks(
+        &self,
+        hunks: Vec<MultiBufferDiffHunk>,
+        cx: &mut App,
+    ) -> Vec<DiscardedHunk> {
         let mut revert_changes = HashMap::default();
+        let mut index_texts = HashMap::default();
         let chunk_by = hunks.into_iter().chunk_by(|hunk| hunk.buffer_id);
         for (buffer_id, hunks) in &chunk_by {
             let hunks = hunks.collect::<Vec<_>>();
             for hunk in &hunks {
                 self.prepare_restore_change(&mut revert_changes, hunk, cx);
             }
+            index_texts.entry(buffer_id).or_insert_with(|| {
+                self.buffer
+                    .read(cx)
+                    .diff_for(buffer_id)
+                    .and_then(|diff| diff.read(cx).secondary_diff())
+                    .and_then(|unstaged_diff| unstaged_diff.read(cx).base_text_string(cx))
+                    .map(Arc::<str>::from)
+            });
             self.do_stage_or_unstage(false, buffer_id, hunks.into_iter(), cx);
         }
+        let mut discarded_hunks = Vec::new();
         if !revert_changes.is_empty() {
             self.buffer().update(cx, |multi_buffer, cx| {
                 for (buffer_id, changes) in revert_changes {
                     if let Some(buffer) = multi_buffer.buffer(buffer_id) {
                         buffer.update(cx, |buffer, cx| {
+                            // The changes are sorted, so each restored range is shifted by the
+                            // length difference of the changes before it.
+                            let mut delta = 0isize;
+                            let restored_ranges = changes
+                                .iter()
+                                .map(|(range, text)| {
+                                    let range = range.to_offset(buffer);
+                                    let discarded_text =
+                                        buffer.text_for_range(range.clone()).collect::<String>();
+                                    let start = range.start.saturating_add_signed(delta);
+                                    delta += text.len() as isize - range.len() as isize;
+                                    (start..start + text.len(), discarded_text, text.to_string())
+                                })
+                                .collect::<Vec<_>>();
                             buffer.edit(
                                 changes
                                     .into_iter()
@@ -11777,11 +11827,23 @@ impl Editor {
                                 None,
                                 cx,
                             );
+                            let index_text = index_texts.get(&buffer_id).cloned().flatten();
+                            discarded_hunks.extend(restored_ranges.into_iter().map(
+                                |(range, discarded_text, restored_text)| DiscardedHunk {
+                                    buffer: cx.weak_entity(),
+                                    range: buffer.anchor_before(range.start)
+                                        ..buffer.anchor_after(range.end),
+                                    discarded_text,
+                                    restored_text,
+                                    index_text: index_text.clone(),
+                                },
+                            ));
                         });
                     }
                 }
             });
         }
+        discarded_hunks
     }
 
     pub fn status_for_buffer_id(&self, buffer_id: BufferId, cx: &App) -> Option<FileStatus> {
@@ -20874,6 +20936,58 @@ impl Editor {
         .detach_and_log_err(cx);
     }
 
+    pub fn stage_selected_lines(
+        &mut self,
+        _: &::git::StageSelectedLines,
+        _: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        if self.delegate_stage_and_restore {
+            return;
+        }
+        let ranges = self.selections.disjoint_anchor_ranges().collect::<Vec<_>>();
+        let task = self.save_buffers_for_ranges_if_needed(&ranges, cx);
+        cx.spawn(async move |this, cx| {
+            task.await?;
+            this.update(cx, |this, cx| {
+                let snapshot = this.buffer.read(cx).snapshot(cx);
+                let mut rows_by_buffer = BTreeMap::<BufferId, Vec<Range<u32>>>::new();
+                for range in &ranges {
+                    for (buffer, buffer_range, _) in
+                        snapshot.range_to_buffer_ranges(range.start..=range.end)
+                    {
+                        let start = buffer.offset_to_point(buffer_range.start.0);
+                        let end = buffer.offset_to_point(buffer_range.end.0);
+                        // A selection ending at the start of a line doesn't include that line.
+                        let end_row = if end.column == 0 && end.row > start.row {
+                            end.row
+                        } else {
+                            end.row + 1
+                        };
+                        rows_by_buffer
+                            .entry(buffer.remote_id())
+                            .or_default()
+                            .push(start.row..end_row);
+                    }
+                }
+                for (buffer_id, rows) in rows_by_buffer {
+                    let multi_buffer = this.buffer.read(cx);
+                    let Some((buffer, diff)) = multi_buffer
+                        .buffer(buffer_id)
+                        .zip(multi_buffer.diff_for(buffer_id))
+                    else {
+                        continue;
+                    };
+                    let buffer_snapshot = buffer.read(cx).text_snapshot();
+                    diff.update(cx, |diff, cx| {
+                        diff.stage_lines(&rows, &buffer_snapshot, cx);
+                    });
+                }
+            })
+        })
+        .detach_and_log_err(cx);
+    }
+
     fn save_buffers_for_ranges_if_needed(
         &mut self,
         ranges: &[Range<Anchor>],
@@ -23100,11 +23214,21 @@ impl Editor {
         window: &mut Window,
         cx: &mut Context<Self>,
     ) {
-        self.open_git_blame_commit_internal(window, cx);
+        self.open_git_blame_commit_internal(false, window, cx);
+    }
+
+    pub fn open_git_blame_before_commit(
+        &mut self,
+        _: &OpenGitBlameBeforeCommit,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.open_git_blame_commit_internal(true, window, cx);
     }
 
     fn open_git_blame_commit_internal(
         &mut self,
+        before_commit: bool,
         window: &mut Window,
         cx: &mut Context<Self>,
     ) -> Option<()> {
@@ -23132,7 +23256,11 @@ impl Editor {
         let renderer = cx.global::<GlobalBlameRenderer>().0.clone();
         let repo = blame.read(cx).repository(cx, buffer.remote_id())?;
         let workspace = self.workspace()?.downgrade();
-        renderer.open_blame_commit(blame_entry, repo, workspace, window, cx);
+        if before_commit {
+            renderer.open_blame_before_commit(blame_entry, repo, workspace, window, cx);
+        } else {
+            renderer.open_blame_commit(blame_entry, repo, workspace, window, cx);
+        }
         None
     }
 
@@ -23140,6 +23268,21 @@ impl Editor {
         self.git_blame_inline_enabled
     }
 
+    pub fn toggle_git_blame_heat_map(
+        &mut self,
+        _: &ToggleGitBlameHeatMap,
+        _: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.show_git_blame_heat_map = Some(!self.git_blame_heat_map_enabled(cx));
+        cx.notify();
+    }
+
+    pub fn git_blame_heat_map_enabled(&self, cx: &App) -> bool {
+        self.show_git_blame_heat_map
+            .unwrap_or_else(|| ProjectSettings::get_global(cx).git.blame.heat_map)
+    }
+
     pub fn toggle_selection_menu(
         &mut self,
         _: &ToggleSelectionMenu,
@@ -23177,6 +23320,18 @@ impl Editor {
             let project = project.clone();
             let blame = cx
                 .new(|cx| GitBlame::new(self.buffer.clone(), project, user_triggered, focused, cx));
+            if let Some(buffer) = self.buffer().read(cx).as_singleton() {
+                let buffer_id = buffer.read(cx).remote_id();
+                let display_snapshot = self.display_snapshot(cx);
+                let cursor_row = self
+                    .selections
+                    .newest::<Point>(&display_snapshot)
+                    .head()
+                    .row;
+                blame.update(cx, |blame, _| {
+                    blame.set_visible_rows(buffer_id, cursor_row..cursor_row + 1)
+                });
+            }
             self.blame_subscription =
                 Some(cx.observe_in(&blame, window, |_, _, _, cx| cx.notify()));
             self.blame = Some(blame);
@@ -23220,6 +23375,24 @@ impl Editor {
         }
     }
 
+    /// Shows the blame of a past revision of a file in the gutter, for an editor whose buffer holds
+    /// the file's contents at that revision.
+    pub fn show_git_blame_at_revision(
+        &mut self,
+        revision: BlameRevision,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let Some(project) = self.project().cloned() else {
+            return;
+        };
+        let blame = cx.new(|cx| GitBlame::at_revision(self.buffer.clone(), project, revision, cx));
+        self.blame_subscription = Some(cx.observe_in(&blame, window, |_, _, _, cx| cx.notify()));
+        self.blame = Some(blame);
+        self.show_git_blame_gutter = true;
+        cx.notify();
+    }
+
     pub fn blame(&self) -> Option<&Entity<GitBlame>> {
         self.blame.as_ref()
     }
@@ -23253,7 +23426,11 @@ impl Editor {
         snapshot.line_len(buffer_row) == 0
     }
 
-    fn get_permalink_to_line(&self, cx: &mut Context<Self>) -> Task<Result<url::Url>> {
+    fn get_permalink_to_line(
+        &self,
+        at_head: bool,
+        cx: &mut Context<Self>,
+    ) -> Task<Result<url::Url>> {
         let buffer_and_selection = maybe!({
             let selection = self.selections.newest::<Point>(&self.display_snapshot(cx));
             let selection_range = selection.range();
@@ -23299,7 +23476,7 @@ impl Editor {
         };
 
         project.update(cx, |project, cx| {
-            project.get_permalink_to_line(&buffer, selection, cx)
+            project.get_permalink_to_line(&buffer, selection, at_head, cx)
         })
     }
 
@@ -23309,7 +23486,20 @@ impl Editor {
         window: &mut Window,
         cx: &mut Context<Self>,
     ) {
-        let permalink_task = self.get_permalink_to_line(cx);
+        self.copy_permalink(false, window, cx);
+    }
+
+    pub fn copy_permalink_at_head(
+        &mut self,
+        _: &CopyPermalinkAtHead,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.copy_permalink(true, window, cx);
+    }
+
+    fn copy_permalink(&mut self, at_head: bool, window: &mut Window, cx: &mut Context<Self>) {
+        let permalink_task = self.get_permalink_to_line(at_head, cx);
         let workspace = self.workspace();
 
         cx.spawn_in(window, async move |_, cx| match permalink_task.await {
@@ -23383,7 +23573,7 @@ impl Editor {
         window: &mut Window,
         cx: &mut Context<Self>,
     ) {
-        let permalink_task = self.get_permalink_to_line(cx);
+        let permalink_task = self.get_permalink_to_line(false, cx);
         let workspace = self.workspace();
 
         cx.spawn_in(window, async move |_, cx| match permalink_task.await {
@@ -28205,6 +28395,10 @@ pub enum EditorEvent {
     RestoreRequested {
         hunks: Vec<MultiBufferDiffHunk>,
     },
+    /// Diff hunks were restored to their base text, discarding the working-tree changes.
+    HunksDiscarded {
+        hunks: Vec<DiscardedHunk>,
+    },
     BufferEdited,
     Edited {
         transaction_id: clock::Lamport,
@@ -24723,6 +24723,65 @@ async fn test_display_diff_hunks(cx: &mut TestAppContext) {
     );
 }
 
+#[gpui::test]
+async fn test_stage_selected_lines(cx: &mut TestAppContext) {
+    init_test(cx, |_| {});
+
+    let mut cx = EditorTestContext::new(cx).await;
+    let original_text = indoc! { "
+        one
+        two
+        three
+        four
+        five
+        "
+    };
+    cx.set_head_text(original_text);
+    cx.set_index_text(original_text);
+    cx.set_state(indoc! {"
+        one
+        TWO
+        «THREE
+        FOURˇ»
+        five
+    "});
+    cx.run_until_parked();
+    cx.update_editor(|editor, window, cx| {
+        editor.stage_selected_lines(&Default::default(), window, cx);
+    });
+    cx.run_until_parked();
+    cx.assert_index_text(Some(indoc! {"
+        one
+        two
+        THREE
+        FOUR
+        five
+    "}));
+
+    cx.set_state(indoc! {"
+        one
+        TWO
+        THREE
+        FOUR
+        five
+        «sixˇ»
+        seven
+    "});
+    cx.run_until_parked();
+    cx.update_editor(|editor, window, cx| {
+        editor.stage_selected_lines(&Default::default(), window, cx);
+    });
+    cx.run_until_parked();
+    cx.assert_index_text(Some(indoc! {"
+        one
+        two
+        THREE
+        FOUR
+        five
+        six
+    "}));
+}
+
 #[gpui::test]
 async fn test_partially_staged_hunk(cx: &mut TestAppContext) {
     init_test(cx, |_| {});
@@ -21,7 +21,7 @@ use crate::{
         MinimapThumb, MinimapThumbBorder, ScrollBeyondLastLine, ScrollbarAxes,
         ScrollbarDiagnostics, ShowMinimap,
     },
-    git::blame::{BlameRenderer, GitBlame, GlobalBlameRenderer},
+    git::blame::{BlameRenderer, GitBlame, GlobalBlameRenderer, commit_heat},
     hover_popover::{
         self, HOVER_POPOVER_GAP, MIN_POPOVER_CHARACTER_WIDTH, MIN_POPOVER_LINE_HEIGHT,
         POPOVER_RIGHT_OFFSET, hover_at,
@@ -516,15 +516,19 @@ impl EditorElement {
         register_action(editor, window, Editor::copy_file_name_without_extension);
         register_action(editor, window, Editor::copy_highlight_json);
         register_action(editor, window, Editor::copy_permalink_to_line);
+        register_action(editor, window, Editor::copy_permalink_at_head);
         register_action(editor, window, Editor::open_permalink_to_line);
         register_action(editor, window, Editor::copy_file_location);
         register_action(editor, window, Editor::toggle_git_blame);
         register_action(editor, window, Editor::toggle_git_blame_inline);
+        register_action(editor, window, Editor::toggle_git_blame_heat_map);
         register_action(editor, window, Editor::open_git_blame_commit);
+        register_action(editor, window, Editor::open_git_blame_before_commit);
         register_action(editor, window, Editor::toggle_selected_diff_hunks);
         register_action(editor, window, Editor::toggle_staged_selected_diff_hunks);
         register_action(editor, window, Editor::stage_and_next);
         register_action(editor, window, Editor::unstage_and_next);
+        register_action(editor, window, Editor::stage_selected_lines);
         register_action(editor, window, Editor::expand_all_diff_hunks);
         register_action(editor, window, Editor::collapse_all_diff_hunks);
         register_action(editor, window, Editor::toggle_review_comments_expanded);
@@ -2904,16 +2908,37 @@ impl EditorElement {
 
         let mut last_used_color: Option<(Hsla, Oid)> = None;
         let blame_renderer = cx.global::<GlobalBlameRenderer>().0.clone();
+        let heat_map = self.editor.read(cx).git_blame_heat_map_enabled(cx);
+        let mut commit_time_ranges = HashMap::default();
+        // The legend takes the bottom row of the gutter, so no entry is painted beneath it.
+        let legend_top = heat_map.then(|| gutter_hitbox.size.height - line_height);
 
-        let shaped_lines = blamed_rows
+        let mut shaped_lines: Vec<_> = blamed_rows
             .into_iter()
             .enumerate()
             .flat_map(|(ix, blame_entry)| {
                 let (buffer_id, blame_entry) = blame_entry?;
+                let start_y = ix as f32 * line_height
+                    - Pixels::from(scroll_top % ScrollPixelOffset::from(line_height));
+                if legend_top.is_some_and(|legend_top| start_y + line_height > legend_top) {
+                    return None;
+                }
+                let heat = if heat_map {
+                    let commit_time_range = commit_time_ranges
+                        .entry(buffer_id)
+                        .or_insert_with(|| blame.read(cx).commit_time_range(buffer_id));
+                    commit_time_range
+                        .as_ref()
+                        .zip(blame_entry.author_time)
+                        .map(|(range, author_time)| commit_heat(author_time, range))
+                } else {
+                    None
+                };
                 let mut element = render_blame_entry(
                     ix,
                     &blame,
                     blame_entry,
+                    heat,
                     &self.style,
                     &mut last_used_color,
                     self.editor.clone(),
@@ -2924,8 +2949,6 @@ impl EditorElement {
                     cx,
                 )?;
 
-                let start_y = ix as f32 * line_height
-                    - Pixels::from(scroll_top % ScrollPixelOffset::from(line_height));
                 let absolute_offset = gutter_hitbox.origin + point(start_x, start_y);
 
                 element.prepaint_as_root(
@@ -2939,6 +2962,20 @@ impl EditorElement {
             })
             .collect();
 
+        if let Some(legend_top) = legend_top
+            && commit_time_ranges.values().any(Option::is_some)
+        {
+            let mut legend = render_blame_heat_map_legend(&self.style, line_height, cx);
+            let legend_origin = gutter_hitbox.origin + point(start_x, legend_top);
+            legend.prepaint_as_root(
+                legend_origin,
+                size(width, AvailableSpace::MinContent),
+                window,
+                cx,
+            );
+            shaped_lines.push(legend);
+        }
+
         Some(shaped_lines)
     }
 
@@ -8691,6 +8728,7 @@ fn render_blame_entry(
     ix: usize,
     blame: &Entity<GitBlame>,
     blame_entry: BlameEntry,
+    heat: Option<f32>,
     style: &EditorStyle,
     last_used_color: &mut Option<(Hsla, Oid)>,
     editor: Entity<Editor>,
@@ -8716,7 +8754,7 @@ fn render_blame_entry(
     let blame = blame.read(cx);
     let details = blame.details_for_entry(buffer, &blame_entry);
     let repository = blame.repository(cx, buffer)?;
-    renderer.render_blame_entry(
+    let element = renderer.render_blame_entry(
         &style.text,
         blame_entry,
         details,
@@ -8727,7 +8765,42 @@ fn render_blame_entry(
         sha_color,
         window,
         cx,
-    )
+    )?;
+    match heat {
+        Some(heat) => Some(
+            div()
+                .bg(blame_heat_color(heat, cx))
+                .child(element)
+                .into_any_element(),
+        ),
+        None => Some(element),
+    }
+}
+
+fn blame_heat_color(heat: f32, cx: &App) -> Hsla {
+    cx.theme()
+        .colors()
+        .version_control_modified
+        .alpha(0.05 + 0.35 * heat)
+}
+
+fn render_blame_heat_map_legend(style: &EditorStyle, line_height: Pixels, cx: &App) -> AnyElement {
+    h_flex()
+        .gap_1()
+        .px_1()
+        .font(style.text.font())
+        .line_height(style.text.line_height)
+        .text_color(cx.theme().status().hint)
+        .bg(cx.theme().colors().editor_gutter_background)
+        .child("Older")
+        .children([0., 0.25, 0.5, 0.75, 1.].map(|heat| {
+            div()
+                .size(line_height * 0.6)
+                .rounded_xs()
+                .bg(blame_heat_color(heat, cx))
+        }))
+        .child("Newer")
+        .into_any_element()
 }
 
 #[derive(Debug)]
@@ -6,13 +6,14 @@ use git::{
     GitHostingProviderRegistry, Oid,
     blame::{Blame, BlameEntry},
     commit::ParsedCommitMessage,
+    repository::RepoPath,
 };
 use gpui::{
     AnyElement, App, AppContext as _, Context, Entity, Hsla, ScrollHandle, Subscription, Task,
     TextStyle, WeakEntity, Window,
 };
 use itertools::Itertools;
-use language::{Bias, BufferSnapshot, Edit};
+use language::{Bias, Buffer, BufferSnapshot, Edit};
 use markdown::Markdown;
 use multi_buffer::{MultiBuffer, RowInfo};
 use project::{
@@ -20,7 +21,12 @@ use project::{
     git_store::{GitStoreEvent, Repository},
 };
 use smallvec::SmallVec;
-use std::{sync::Arc, time::Duration};
+use std::{
+    future::Future,
+    ops::{Range, RangeInclusive},
+    sync::Arc,
+    time::Duration,
+};
 use sum_tree::SumTree;
 use text::BufferId;
 use workspace::Workspace;
@@ -69,12 +75,25 @@ struct GitBlameBuffer {
     buffer_snapshot: BufferSnapshot,
     buffer_edits: text::Subscription<usize>,
     commit_details: HashMap<Oid, ParsedCommitMessage>,
+    /// The times of the oldest and newest commits blamed for the buffer when it was blamed.
+    commit_time_range: Option<RangeInclusive<i64>>,
+}
+
+/// A past revision of a file, which a [`GitBlame`] blames instead of the buffers' files as they
+/// are in the working tree.
+#[derive(Clone)]
+pub struct BlameRevision {
+    pub repository: Entity<Repository>,
+    pub revision: String,
+    pub path: RepoPath,
 }
 
 pub struct GitBlame {
     project: Entity<Project>,
     multi_buffer: WeakEntity<MultiBuffer>,
+    revision: Option<BlameRevision>,
     buffers: HashMap<BufferId, GitBlameBuffer>,
+    visible_rows: HashMap<BufferId, Range<u32>>,
     task: Task<Result<()>>,
     focused: bool,
     changed_while_blurred: bool,
@@ -127,6 +146,15 @@ pub trait BlameRenderer {
         _: &mut Window,
         _: &mut App,
     );
+
+    fn open_blame_before_commit(
+        &self,
+        _: BlameEntry,
+        _: Entity<Repository>,
+        _: WeakEntity<Workspace>,
+        _: &mut Window,
+        _: &mut App,
+    );
 }
 
 impl BlameRenderer for () {
@@ -182,12 +210,33 @@ impl BlameRenderer for () {
         _: &mut App,
     ) {
     }
+
+    fn open_blame_before_commit(
+        &self,
+        _: BlameEntry,
+        _: Entity<Repository>,
+        _: WeakEntity<Workspace>,
+        _: &mut Window,
+        _: &mut App,
+    ) {
+    }
 }
 
 pub(crate) struct GlobalBlameRenderer(pub Arc<dyn BlameRenderer>);
 
 impl gpui::Global for GlobalBlameRenderer {}
 
+/// How recent a commit is within the commits blamed for a buffer, from 0 for the oldest to 1 for
+/// the newest.
+pub fn commit_heat(commit_time: i64, commit_time_range: &RangeInclusive<i64>) -> f32 {
+    let span = commit_time_range.end() - commit_time_range.start();
+    if span <= 0 {
+        return 1.;
+    }
+    let age = (commit_time - commit_time_range.start()).clamp(0, span);
+    (age as f64 / span as f64) as f32
+}
+
 impl GitBlame {
     pub fn new(
         multi_buffer: Entity<MultiBuffer>,
@@ -195,6 +244,27 @@ impl GitBlame {
         user_triggered: bool,
         focused: bool,
         cx: &mut Context<Self>,
+    ) -> Self {
+        Self::build(multi_buffer, project, None, user_triggered, focused, cx)
+    }
+
+    /// Blames the contents of `multi_buffer` as the file at a past revision.
+    pub fn at_revision(
+        multi_buffer: Entity<MultiBuffer>,
+        project: Entity<Project>,
+        revision: BlameRevision,
+        cx: &mut Context<Self>,
+    ) -> Self {
+        Self::build(multi_buffer, project, Some(revision), true, true, cx)
+    }
+
+    fn build(
+        multi_buffer: Entity<MultiBuffer>,
+        project: Entity<Project>,
+        revision: Option<BlameRevision>,
+        user_triggered: bool,
+        focused: bool,
+        cx: &mut Context<Self>,
     ) -> Self {
         let multi_buffer_subscription = cx.subscribe(
             &multi_buffer,
@@ -248,7 +318,9 @@ impl GitBlame {
         let mut this = Self {
             project,
             multi_buffer: multi_buffer.downgrade(),
+            revision,
             buffers: HashMap::default(),
+            visible_rows: HashMap::default(),
             user_triggered,
             focused,
             changed_while_blurred: false,
@@ -265,6 +337,9 @@ impl GitBlame {
     }
 
     pub fn repository(&self, cx: &App, id: BufferId) -> Option<Entity<Repository>> {
+        if let Some(revision) = &self.revision {
+            return Some(revision.repository.clone());
+        }
         self.project
             .read(cx)
             .git_store()
@@ -294,6 +369,17 @@ impl GitBlame {
         rows: &'a [RowInfo],
         cx: &'a mut App,
     ) -> impl Iterator<Item = Option<(BufferId, BlameEntry)>> + use<'a> {
+        let mut visible_rows = HashMap::<BufferId, Range<u32>>::default();
+        for info in rows {
+            if let Some((buffer_id, buffer_row)) = info.buffer_id.zip(info.buffer_row) {
+                let rows = visible_rows
+                    .entry(buffer_id)
+                    .or_insert(buffer_row..buffer_row + 1);
+                rows.start = rows.start.min(buffer_row);
+                rows.end = rows.end.max(buffer_row + 1);
+            }
+        }
+        self.visible_rows.extend(visible_rows);
         rows.iter().map(move |info| {
             let buffer_id = info.buffer_id?;
             self.sync(cx, buffer_id);
@@ -305,6 +391,12 @@ impl GitBlame {
         })
     }
 
+    /// The rows of the buffer that are blamed first when the buffer is too long to blame all at
+    /// once, before anything has been rendered.
+    pub fn set_visible_rows(&mut self, buffer_id: BufferId, rows: Range<u32>) {
+        self.visible_rows.insert(buffer_id, rows);
+    }
+
     pub fn max_author_length(&mut self, cx: &mut App) -> usize {
         let mut max_author_length = 0;
         self.sync_all(cx);
@@ -327,6 +419,12 @@ impl GitBlame {
         max_author_length
     }
 
+    /// The times of the oldest and newest commits blamed for lines of the buffer, which the
+    /// heat map colors the rest of the buffer's commits between.
+    pub fn commit_time_range(&self, buffer: BufferId) -> Option<RangeInclusive<i64>> {
+        self.buffers.get(&buffer)?.commit_time_range.clone()
+    }
+
     pub fn blur(&mut self, _: &mut Context<Self>) {
         self.focused = false;
     }
@@ -506,8 +604,66 @@ impl GitBlame {
             })
             .unwrap_or_default();
         let project = self.project.downgrade();
+        let revision = self.revision.clone();
 
         self.task = cx.spawn(async move |this, cx| {
+            let provider_registry = cx.update(|cx| GitHostingProviderRegistry::default_global(cx));
+
+            // Blaming all of a long file takes a while, so blame the rows in view first.
+            let visible_blames = this.update(cx, |this, cx| {
+                let Some(project) = project.upgrade().filter(|_| revision.is_none()) else {
+                    return Vec::new();
+                };
+                buffers_to_blame
+                    .iter()
+                    .filter_map(|buffer| {
+                        let buffer = buffer.upgrade()?;
+                        let buffer_id = buffer.read(cx).remote_id();
+                        let max_point = buffer.read(cx).max_point();
+                        // Git doesn't count the empty line after a trailing newline.
+                        let line_count = if max_point.column == 0 {
+                            max_point.row
+                        } else {
+                            max_point.row + 1
+                        };
+                        if line_count < INCREMENTAL_BLAME_MIN_ROWS
+                            || this.buffers.contains_key(&buffer_id)
+                            || project
+                                .read(cx)
+                                .git_store()
+                                .read(cx)
+                                .has_cached_blame(&buffer, cx)
+                        {
+                            return None;
+                        }
+                        let visible_rows =
+                            this.visible_rows.get(&buffer_id).cloned().unwrap_or(0..1);
+                        let rows = visible_rows
+                            .start
+                            .saturating_sub(VISIBLE_BLAME_MARGIN_ROWS)
+                            .min(line_count - 1)
+                            ..(visible_rows.end + VISIBLE_BLAME_MARGIN_ROWS).min(line_count);
+                        Some(request_blame(&buffer, &project, None, Some(rows), cx))
+                    })
+                    .collect::<Vec<_>>()
+            })?;
+            if !visible_blames.is_empty() {
+                let (visible_results, _) = cx
+                    .background_spawn(build_blame_buffers(
+                        visible_blames,
+                        provider_registry.clone(),
+                    ))
+                    .await;
+                this.update(cx, |this, cx| {
+                    for (id, blame_buffer) in visible_results {
+                        if let Some(blame_buffer) = blame_buffer {
+                            this.buffers.insert(id, blame_buffer);
+                        }
+                    }
+                    cx.notify();
+                })?;
+            }
+
             let mut all_results = Vec::new();
             let mut all_errors = Vec::new();
 
@@ -520,72 +676,18 @@ impl GitBlame {
                         .map(|buffer| {
                             let buffer = buffer.upgrade().context("buffer was dropped")?;
                             let project = project.upgrade().context("project was dropped")?;
-                            let id = buffer.read(cx).remote_id();
-                            let snapshot = buffer.read(cx).snapshot();
-                            let buffer_edits = buffer.update(cx, |buffer, _| buffer.subscribe());
-                            let remote_url = project
-                                .read(cx)
-                                .git_store()
-                                .read(cx)
-                                .repository_and_path_for_buffer_id(buffer.read(cx).remote_id(), cx)
-                                .and_then(|(repo, _)| repo.read(cx).default_remote_url());
-                            let blame_buffer = project
-                                .update(cx, |project, cx| project.blame_buffer(&buffer, None, cx));
-                            Ok(async move {
-                                (id, snapshot, buffer_edits, blame_buffer.await, remote_url)
-                            })
+                            Ok(request_blame(
+                                &buffer,
+                                &project,
+                                revision.as_ref(),
+                                None,
+                                cx,
+                            ))
                         })
                         .collect::<Result<Vec<_>>>()
                 })?;
-                let provider_registry =
-                    cx.update(|cx| GitHostingProviderRegistry::default_global(cx));
                 let (results, errors) = cx
-                    .background_spawn({
-                        async move {
-                            let blame = futures::future::join_all(blame).await;
-                            let mut res = vec![];
-                            let mut errors = vec![];
-                            for (id, snapshot, buffer_edits, blame, remote_url) in blame {
-                                match blame {
-                                    Ok(Some(Blame { entries, messages })) => {
-                                        let entries = build_blame_entry_sum_tree(
-                                            entries,
-                                            snapshot.max_point().row,
-                                        );
-                                        let commit_details = messages
-                                            .into_iter()
-                                            .map(|(oid, message)| {
-                                                let parsed_commit_message =
-                                                    ParsedCommitMessage::parse(
-                                                        oid.to_string(),
-                                                        message,
-                                                        remote_url.as_deref(),
-                                                        Some(provider_registry.clone()),
-                                                    );
-                                                (oid, parsed_commit_message)
-                                            })
-                                            .collect();
-                                        res.push((
-                                            id,
-                                            snapshot,
-                                            buffer_edits,
-                                            Some(entries),
-                                            commit_details,
-                                        ));
-                                    }
-                                    Ok(None) => res.push((
-                                        id,
-                                        snapshot,
-                                        buffer_edits,
-                                        None,
-                                        Default::default(),
-                                    )),
-                                    Err(e) => errors.push(e),
-                                }
-                            }
-                            (res, errors)
-                        }
-                    })
+                    .background_spawn(build_blame_buffers(blame, provider_registry.clone()))
                     .await;
                 all_results.extend(results);
                 all_errors.extend(errors)
@@ -593,19 +695,11 @@ impl GitBlame {
 
             this.update(cx, |this, cx| {
                 this.buffers.clear();
-                for (id, snapshot, buffer_edits, entries, commit_details) in all_results {
-                    let Some(entries) = entries else {
+                for (id, blame_buffer) in all_results {
+                    let Some(blame_buffer) = blame_buffer else {
                         continue;
                     };
-                    this.buffers.insert(
-                        id,
-                        GitBlameBuffer {
-                            buffer_edits,
-                            buffer_snapshot: snapshot,
-                            entries,
-                            commit_details,
-                        },
-                    );
+                    this.buffers.insert(id, blame_buffer);
                 }
                 cx.notify();
                 if !all_errors.is_empty() {
@@ -650,6 +744,119 @@ impl GitBlame {
 
 const REGENERATE_ON_EDIT_DEBOUNCE_INTERVAL: Duration = Duration::from_secs(2);
 
+/// Buffers with more rows than this are blamed around the rows in view before being blamed
+/// whole, so that their blame shows up without waiting for all of it.
+const INCREMENTAL_BLAME_MIN_ROWS: u32 = 5_000;
+const VISIBLE_BLAME_MARGIN_ROWS: u32 = 200;
+
+struct BufferBlame {
+    buffer_id: BufferId,
+    snapshot: BufferSnapshot,
+    buffer_edits: text::Subscription<usize>,
+    blame: Result<Option<Blame>>,
+    remote_url: Option<String>,
+}
+
+fn request_blame(
+    buffer: &Entity<Buffer>,
+    project: &Entity<Project>,
+    revision: Option<&BlameRevision>,
+    rows: Option<Range<u32>>,
+    cx: &mut App,
+) -> impl Future<Output = BufferBlame> + use<> {
+    let buffer_id = buffer.read(cx).remote_id();
+    let snapshot = buffer.read(cx).snapshot();
+    let buffer_edits = buffer.update(cx, |buffer, _| buffer.subscribe());
+    let (remote_url, blame) = match revision {
+        Some(revision) => (
+            revision.repository.read(cx).default_remote_url(),
+            revision.repository.update(cx, |repository, cx| {
+                let blame = repository.blame_at_revision(
+                    revision.revision.clone(),
+                    revision.path.clone(),
+                    cx,
+                );
+                cx.background_spawn(async move { blame.await.map(Some) })
+            }),
+        ),
+        None => (
+            project
+                .read(cx)
+                .git_store()
+                .read(cx)
+                .repository_and_path_for_buffer_id(buffer_id, cx)
+                .and_then(|(repo, _)| repo.read(cx).default_remote_url()),
+            project.update(cx, |project, cx| match rows {
+                Some(rows) => project.blame_buffer_rows(buffer, rows, cx),
+                None => project.blame_buffer(buffer, None, cx),
+            }),
+        ),
+    };
+    async move {
+        BufferBlame {
+            buffer_id,
+            snapshot,
+            buffer_edits,
+            blame: blame.await,
+            remote_url,
+        }
+    }
+}
+
+async fn build_blame_buffers(
+    blames: Vec<impl Future<Output = BufferBlame>>,
+    provider_registry: Arc<GitHostingProviderRegistry>,
+) -> (Vec<(BufferId, Option<GitBlameBuffer>)>, Vec<anyhow::Error>) {
+    let blames = futures::future::join_all(blames).await;
+    let mut results = Vec::new();
+    let mut errors = Vec::new();
+    for BufferBlame {
+        buffer_id,
+        snapshot,
+        buffer_edits,
+        blame,
+        remote_url,
+    } in blames
+    {
+        match blame {
+            Ok(Some(Blame { entries, messages })) => {
+                let commit_time_range = entries
+                    .iter()
+                    .filter_map(|entry| entry.author_time)
+                    .minmax()
+                    .into_option()
+                    .map(|(oldest, newest)| oldest..=newest);
+                let entries = build_blame_entry_sum_tree(entries, snapshot.max_point().row);
+                let commit_details = messages
+                    .into_iter()
+                    .map(|(oid, message)| {
+                        let parsed_commit_message = ParsedCommitMessage::parse(
+                            oid.to_string(),
+                            message,
+                            remote_url.as_deref(),
+                            Some(provider_registry.clone()),
+                        );
+                        (oid, parsed_commit_message)
+                    })
+                    .collect();
+                results.push((
+                    buffer_id,
+                    Some(GitBlameBuffer {
+                        entries,
+                        buffer_snapshot: snapshot,
+                        buffer_edits,
+                        commit_details,
+                        commit_time_range,
+                    }),
+                ));
+            }
+            Ok(None) => results.push((buffer_id, None)),
+            Err(error) => errors.push(error),
+        }
+    }
+    (results, errors)
+}
+
 fn build_blame_entry_sum_tree(entries: Vec<BlameEntry>, max_row: u32) -> SumTree<GitBlameEntry> {
     let mut current_row = 0;
     let mut entries = SumTree::from_iter(
@@ -934,6 +1141,103 @@ mod tests {
         });
     }
 
+    #[gpui::test]
+    async fn test_blame_for_rows_of_long_buffer(cx: &mut gpui::TestAppContext) {
+        init_test(cx);
+
+        let row_count = INCREMENTAL_BLAME_MIN_ROWS + 1_000;
+        let fs = FakeFs::new(cx.executor());
+        fs.insert_tree(
+            "/my-repo",
+            json!({
+                ".git": {},
+                "file.txt": "line\n".repeat(row_count as usize),
+            }),
+        )
+        .await;
+        fs.set_blame_for_repo(
+            Path::new("/my-repo/.git"),
+            vec![(
+                repo_path("file.txt"),
+                Blame {
+                    entries: vec![
+                        blame_entry("1b1b1b", 0..3_000),
+                        blame_entry("0d0d0d", 3_000..row_count),
+                    ],
+                    ..Default::default()
+                },
+            )],
+        );
+        let project = Project::test(fs, ["/my-repo".as_ref()], cx).await;
+        let buffer = project
+            .update(cx, |project, cx| {
+                project.open_local_buffer("/my-repo/file.txt", cx)
+            })
+            .await
+            .unwrap();
+        let buffer_id = buffer.read_with(cx, |buffer, _| buffer.remote_id());
+
+        let blame = project
+            .update(cx, |project, cx| {
+                project.blame_buffer_rows(&buffer, 2_990..3_010, cx)
+            })
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(
+            blame.entries,
+            vec![
+                BlameEntry {
+                    original_line_number: 2_990,
+                    ..blame_entry("1b1b1b", 2_990..3_000)
+                },
+                blame_entry("0d0d0d", 3_000..3_010),
+            ]
+        );
+
+        let multi_buffer = cx.new(|cx| MultiBuffer::singleton(buffer, cx));
+        let git_blame = cx.new(|cx| {
+            let mut blame = GitBlame::new(multi_buffer, project.clone(), false, true, cx);
+            blame.set_visible_rows(buffer_id, 3_000..3_050);
+            blame
+        });
+        cx.executor().run_until_parked();
+
+        git_blame.update(cx, |blame, cx| {
+            assert_blame_rows(
+                blame,
+                buffer_id,
+                0..1,
+                vec![Some(blame_entry("1b1b1b", 0..3_000))],
+                cx,
+            );
+            assert_blame_rows(
+                blame,
+                buffer_id,
+                row_count - 1..row_count,
+                vec![Some(blame_entry("0d0d0d", 3_000..row_count))],
+                cx,
+            );
+        });
+
+        // The whole-file blame stays cached after the buffer is released.
+        drop(git_blame);
+        cx.executor().run_until_parked();
+        let buffer = project
+            .update(cx, |project, cx| {
+                project.open_local_buffer("/my-repo/file.txt", cx)
+            })
+            .await
+            .unwrap();
+        assert_ne!(
+            buffer.read_with(cx, |buffer, _| buffer.remote_id()),
+            buffer_id
+        );
+        project.read_with(cx, |project, cx| {
+            assert!(project.git_store().read(cx).has_cached_blame(&buffer, cx));
+        });
+    }
+
     #[gpui::test]
     async fn test_blame_for_rows_with_edits(cx: &mut gpui::TestAppContext) {
         init_test(cx);
@@ -1200,6 +1504,60 @@ mod tests {
         blame_entries
     }
 
+    #[gpui::test]
+    async fn test_commit_heat(cx: &mut gpui::TestAppContext) {
+        init_test(cx);
+
+        let fs = FakeFs::new(cx.executor());
+        fs.insert_tree(
+            "/my-repo",
+            json!({
+                ".git": {},
+                "file.txt": "old\nnewest\nmiddle\nuncommitted\n",
+            }),
+        )
+        .await;
+        let authored_at = |sha, range, author_time| BlameEntry {
+            author_time: Some(author_time),
+            ..blame_entry(sha, range)
+        };
+        fs.set_blame_for_repo(
+            Path::new("/my-repo/.git"),
+            vec![(
+                repo_path("file.txt"),
+                Blame {
+                    entries: vec![
+                        authored_at("1b1b1b", 0..1, 1_000),
+                        authored_at("0d0d0d", 1..2, 5_000),
+                        authored_at("3a3a3a", 2..3, 2_000),
+                    ],
+                    ..Default::default()
+                },
+            )],
+        );
+        let project = Project::test(fs, ["/my-repo".as_ref()], cx).await;
+        let buffer = project
+            .update(cx, |project, cx| {
+                project.open_local_buffer("/my-repo/file.txt", cx)
+            })
+            .await
+            .unwrap();
+        let buffer_id = buffer.read_with(cx, |buffer, _| buffer.remote_id());
+        let buffer = cx.new(|cx| MultiBuffer::singleton(buffer, cx));
+        let git_blame = cx.new(|cx| GitBlame::new(buffer.clone(), project, false, true, cx));
+        cx.executor().run_until_parked();
+
+        let commit_time_range = git_blame
+            .read_with(cx, |blame, _| blame.commit_time_range(buffer_id))
+            .unwrap();
+        assert_eq!(commit_time_range, 1_000..=5_000);
+        assert_eq!(commit_heat(1_000, &commit_time_range), 0.);
+        assert_eq!(commit_heat(2_000, &commit_time_range), 0.25);
+        assert_eq!(commit_heat(5_000, &commit_time_range), 1.);
+        assert_eq!(commit_heat(9_000, &commit_time_range), 1.);
+        assert_eq!(commit_heat(1_000, &(1_000..=1_000)), 1.);
+    }
+
     fn blame_entry(sha: &str, range: Range<u32>) -> BlameEntry {
         BlameEntry {
             sha: sha.parse().unwrap(),
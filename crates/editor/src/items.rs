@@ -39,7 +39,7 @@ use std::{
     sync::Arc,
 };
 use text::{BufferId, BufferSnapshot, Selection};
-use ui::{IconDecorationKind, prelude::*};
+use ui::{Chip, IconDecorationKind, prelude::*};
 use util::{ResultExt, TryFutureExt, paths::PathExt};
 use workspace::item::{Dedup, ItemSettings, SerializableItem, TabContentParams};
 use workspace::{
@@ -740,6 +740,9 @@ impl Item for Editor {
                 .when(params.preview, |this| this.italic())
                 .when(was_deleted, |this| this.strikethrough()),
             )
+            .when(shows_lfs_pointer(&self.buffer, cx), |this| {
+                this.child(Chip::new("LFS").label_color(Color::Muted))
+            })
             .when_some(description, |this, description| {
                 this.child(
                     Label::new(description)
@@ -1985,6 +1988,16 @@ fn path_for_buffer<'a>(
     path_for_file(file, height, include_filename, cx)
 }
 
+/// Whether the buffer holds a Git LFS pointer whose object hasn't been downloaded.
+pub(crate) fn shows_lfs_pointer(buffer: &Entity<MultiBuffer>, cx: &App) -> bool {
+    buffer.read(cx).as_singleton().is_some_and(|buffer| {
+        let buffer = buffer.read(cx);
+        buffer.file().is_some()
+            && buffer.len() <= git::lfs::MAX_LFS_POINTER_LEN
+            && git::lfs::LfsPointer::parse(&buffer.text()).is_some()
+    })
+}
+
 fn path_for_file<'a>(
     file: &'a Arc<dyn language::File>,
     mut height: usize,
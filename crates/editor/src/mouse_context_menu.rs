@@ -1,13 +1,18 @@
 use crate::{
-    Copy, CopyAndTrim, CopyPermalinkToLine, Cut, DisplayPoint, DisplaySnapshot, Editor,
-    EvaluateSelectedText, FindAllReferences, GoToDeclaration, GoToDefinition, GoToImplementation,
-    GoToTypeDefinition, Paste, Rename, RevealInFileManager, RunToCursor, SelectMode,
-    SelectionEffects, SelectionExt, ToDisplayPoint, ToggleCodeActions,
-    actions::{Format, FormatSelections},
+    Copy, CopyAndTrim, CopyPermalinkAtHead, CopyPermalinkToLine, Cut, DisplayPoint,
+    DisplaySnapshot, Editor, EvaluateSelectedText, FindAllReferences, GoToDeclaration,
+    GoToDefinition, GoToImplementation, GoToTypeDefinition, OpenPermalinkToLine, Paste, Rename,
+    RevealInFileManager, RunToCursor, SelectMode, SelectionEffects, SelectionExt, ToDisplayPoint,
+    ToggleCodeActions,
+    actions::{DiffClipboardWithSelection, Format, FormatSelections},
+    items::shows_lfs_pointer,
     selections_collection::SelectionsCollection,
 };
 use gpui::prelude::FluentBuilder;
-use gpui::{Context, DismissEvent, Entity, Focusable as _, Pixels, Point, Subscription, Window};
+use gpui::{
+    ClipboardEntry, Context, DismissEvent, Entity, Focusable as _, Pixels, Point, Subscription,
+    Window,
+};
 use project::DisableAiSettings;
 use std::ops::Range;
 use text::PointUtf16;
@@ -216,6 +221,12 @@ pub fn deploy_context_menu(
                     .is_some()
             });
 
+        let has_clipboard_text = cx.read_from_clipboard().is_some_and(|item| {
+            item.entries()
+                .iter()
+                .any(|entry| matches!(entry, ClipboardEntry::String(_)))
+        });
+
         let evaluate_selection = window.is_action_available(&EvaluateSelectedText, cx);
         let run_to_cursor = window.is_action_available(&RunToCursor, cx);
         let disable_ai = DisableAiSettings::is_ai_disabled_for_buffer(
@@ -230,6 +241,8 @@ pub fn deploy_context_menu(
             .and_then(|buffer| buffer.read(cx).language())
             .is_some_and(|language| language.name().as_ref() == "Markdown");
 
+        let is_lfs_pointer = has_git_repo && shows_lfs_pointer(editor.buffer(), cx);
+
         let is_svg = editor
             .buffer()
             .read(cx)
@@ -283,6 +296,15 @@ pub fn deploy_context_menu(
                 .action("Copy", Box::new(Copy))
                 .action("Copy and Trim", Box::new(CopyAndTrim))
                 .action("Paste", Box::new(Paste))
+                .action_disabled_when(
+                    !has_clipboard_text,
+                    if has_selections {
+                        "Compare Selection with Clipboard"
+                    } else {
+                        "Compare with Clipboard"
+                    },
+                    Box::new(DiffClipboardWithSelection),
+                )
                 .separator()
                 .action_disabled_when(
                     !has_reveal_target,
@@ -305,11 +327,39 @@ pub fn deploy_context_menu(
                     "Copy Permalink",
                     Box::new(CopyPermalinkToLine),
                 )
+                .action_disabled_when(
+                    !has_git_repo,
+                    "Copy Permalink at HEAD",
+                    Box::new(CopyPermalinkAtHead),
+                )
+                .action_disabled_when(
+                    !has_git_repo,
+                    "Open Permalink",
+                    Box::new(OpenPermalinkToLine),
+                )
+                .action_disabled_when(
+                    !has_git_repo,
+                    "Stage Selected Lines",
+                    Box::new(git::StageSelectedLines),
+                )
                 .action_disabled_when(
                     !has_git_repo,
                     "View File History",
                     Box::new(git::FileHistory),
-                );
+                )
+                .action_disabled_when(
+                    !has_git_repo,
+                    "Restore from Revision…",
+                    Box::new(git::RestoreFromRevision),
+                )
+                .action_disabled_when(
+                    !has_git_repo,
+                    "View Line History",
+                    Box::new(git::LineHistory),
+                )
+                .when(is_lfs_pointer, |builder| {
+                    builder.action("Fetch LFS Object", Box::new(git::FetchLfsObject))
+                });
             match focus {
                 Some(focus) => builder.context(focus),
                 None => builder,
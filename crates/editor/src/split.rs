@@ -583,7 +583,10 @@ impl SplittableEditor {
                         let translated = translate_lhs_hunks_to_rhs(hunks, this, cx);
                         if !translated.is_empty() {
                             this.rhs_editor.update(cx, |editor, cx| {
-                                editor.restore_diff_hunks(translated, cx);
+                                let hunks = editor.restore_diff_hunks(translated, cx);
+                                if !hunks.is_empty() {
+                                    cx.emit(EditorEvent::HunksDiscarded { hunks });
+                                }
                             });
                         }
                     }
@@ -1,7 +1,10 @@
 #[cfg(test)]
 mod file_finder_tests;
 
-use futures::future::join_all;
+use futures::{
+    FutureExt as _,
+    future::{Shared, join_all},
+};
 pub use open_path_prompt::OpenPathDelegate;
 
 use channel::ChannelStore;
@@ -10,10 +13,14 @@ use collections::HashMap;
 use editor::Editor;
 use file_icons::FileIcons;
 use fuzzy::{CharBag, PathMatch, PathMatchCandidate, StringMatch, StringMatchCandidate};
+use git::{
+    repository::RepoPath,
+    status::{DiffTreeType, TreeDiffStatus},
+};
 use gpui::{
-    Action, AnyElement, App, Context, DismissEvent, Entity, EventEmitter, FocusHandle, Focusable,
-    KeyContext, Modifiers, ModifiersChangedEvent, ParentElement, Render, Styled, Task, WeakEntity,
-    Window, actions, rems,
+    Action, AnyElement, App, AsyncApp, Context, DismissEvent, Entity, EventEmitter, FocusHandle,
+    Focusable, KeyContext, Modifiers, ModifiersChangedEvent, ParentElement, Render, Styled, Task,
+    WeakEntity, Window, actions, rems,
 };
 use open_path_prompt::{
     OpenPathPrompt,
@@ -21,7 +28,8 @@ use open_path_prompt::{
 };
 use picker::{Picker, PickerDelegate};
 use project::{
-    PathMatchCandidateSet, Project, ProjectPath, WorktreeId, worktree_store::WorktreeStore,
+    PathMatchCandidateSet, Project, ProjectPath, WorktreeId, git_store::Repository,
+    worktree_store::WorktreeStore,
 };
 use project_panel::project_panel_settings::ProjectPanelSettings;
 use settings::Settings;
@@ -59,7 +67,17 @@ actions!(
         /// Toggles the file filter menu.
         ToggleFilterMenu,
         /// Toggles the split direction menu.
-        ToggleSplitMenu
+        ToggleSplitMenu,
+        /// Toggles whether only files with uncommitted changes are searched.
+        ToggleChangedFilesOnly,
+        /// Toggles whether only files changed on the current branch, since it diverged from the
+        /// default branch, or with uncommitted changes are searched.
+        ToggleBranchChangesOnly,
+        /// Opens the file finder, searching only files with uncommitted changes.
+        OpenChangedFile,
+        /// Opens the file finder, searching only files changed on the current branch or with
+        /// uncommitted changes.
+        OpenBranchChangedFile
     ]
 );
 
@@ -104,7 +122,7 @@ impl FileFinder {
         workspace.register_action(
             |workspace, action: &workspace::ToggleFileFinder, window, cx| {
                 let Some(file_finder) = workspace.active_modal::<Self>(cx) else {
-                    Self::open(workspace, action.separate_history, window, cx).detach();
+                    Self::open(workspace, action.separate_history, None, window, cx).detach();
                     return;
                 };
 
@@ -116,11 +134,39 @@ impl FileFinder {
                 });
             },
         );
+        workspace.register_action(|workspace, _: &OpenChangedFile, window, cx| {
+            Self::open_changed_files(workspace, ChangedFiles::Uncommitted, window, cx);
+        });
+        workspace.register_action(|workspace, _: &OpenBranchChangedFile, window, cx| {
+            Self::open_changed_files(workspace, ChangedFiles::SinceDefaultBranch, window, cx);
+        });
+    }
+
+    fn open_changed_files(
+        workspace: &mut Workspace,
+        changed_files: ChangedFiles,
+        window: &mut Window,
+        cx: &mut Context<Workspace>,
+    ) {
+        let Some(file_finder) = workspace.active_modal::<Self>(cx) else {
+            Self::open(workspace, false, Some(changed_files), window, cx).detach();
+            return;
+        };
+
+        file_finder.update(cx, |file_finder, cx| {
+            file_finder.picker.update(cx, |picker, cx| {
+                if picker.delegate.changed_files != Some(changed_files) {
+                    picker.delegate.changed_files = Some(changed_files);
+                    picker.refresh(window, cx);
+                }
+            });
+        });
     }
 
     fn open(
         workspace: &mut Workspace,
         separate_history: bool,
+        changed_files: Option<ChangedFiles>,
         window: &mut Window,
         cx: &mut Context<Workspace>,
     ) -> Task<()> {
@@ -173,6 +219,7 @@ impl FileFinder {
                             currently_opened_path,
                             history_items.collect(),
                             separate_history,
+                            changed_files,
                             window,
                             cx,
                         );
@@ -275,6 +322,42 @@ impl FileFinder {
         });
     }
 
+    fn handle_toggle_changed_files_only(
+        &mut self,
+        _: &ToggleChangedFilesOnly,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.toggle_changed_files(ChangedFiles::Uncommitted, window, cx);
+    }
+
+    fn handle_toggle_branch_changes_only(
+        &mut self,
+        _: &ToggleBranchChangesOnly,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.toggle_changed_files(ChangedFiles::SinceDefaultBranch, window, cx);
+    }
+
+    fn toggle_changed_files(
+        &mut self,
+        changed_files: ChangedFiles,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.picker.update(cx, |picker, cx| {
+            picker.delegate.changed_files = if picker.delegate.changed_files == Some(changed_files)
+            {
+                None
+            } else {
+                Some(changed_files)
+            };
+            picker.delegate.changed_files_refresh =
+                picker.delegate.update_matches(picker.query(cx), window, cx);
+        });
+    }
+
     fn go_to_file_split_left(
         &mut self,
         _: &pane::SplitLeft,
@@ -382,6 +465,8 @@ impl Render for FileFinder {
             .on_action(cx.listener(Self::handle_filter_toggle_menu))
             .on_action(cx.listener(Self::handle_split_toggle_menu))
             .on_action(cx.listener(Self::handle_toggle_ignored))
+            .on_action(cx.listener(Self::handle_toggle_changed_files_only))
+            .on_action(cx.listener(Self::handle_toggle_branch_changes_only))
             .on_action(cx.listener(Self::go_to_file_split_left))
             .on_action(cx.listener(Self::go_to_file_split_right))
             .on_action(cx.listener(Self::go_to_file_split_up))
@@ -412,6 +497,20 @@ pub struct FileFinderDelegate {
     focus_handle: FocusHandle,
     include_ignored: Option<bool>,
     include_ignored_refresh: Task<()>,
+    /// Which changed files are searched, and listed for an empty query, instead of all files.
+    changed_files: Option<ChangedFiles>,
+    changed_files_refresh: Task<()>,
+    /// The files changed on the current branch, loaded once when first searched.
+    branch_changes: Option<Shared<Task<Vec<ProjectPath>>>>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum ChangedFiles {
+    /// Files with uncommitted changes.
+    Uncommitted,
+    /// Files with uncommitted changes, or changed on the current branch since it diverged from
+    /// the default branch.
+    SinceDefaultBranch,
 }
 
 /// Use a custom ordering for file finder: the regular one
@@ -799,6 +898,89 @@ fn matching_history_items<'a>(
     matching_history_paths
 }
 
+/// Matches the changed files of a worktree against the query, listing all of them for an empty
+/// query.
+fn match_changed_files(
+    worktree_id: WorktreeId,
+    root_name: Option<Arc<RelPath>>,
+    paths: &[Arc<RelPath>],
+    query: &str,
+    path_style: PathStyle,
+) -> Vec<PathMatch> {
+    if query.is_empty() {
+        let path_prefix = root_name.unwrap_or_else(|| RelPath::empty().into());
+        return paths
+            .iter()
+            .map(|path| PathMatch {
+                score: 1.0,
+                positions: Vec::new(),
+                worktree_id: worktree_id.to_usize(),
+                path: path.clone(),
+                path_prefix: path_prefix.clone(),
+                is_dir: false,
+                distance_to_relative_ancestor: usize::MAX,
+            })
+            .collect();
+    }
+
+    let root_name_chars = root_name
+        .as_ref()
+        .map(|root_name| root_name.as_unix_str().to_lowercase())
+        .unwrap_or_default();
+    let candidates = paths
+        .iter()
+        .map(|path| PathMatchCandidate {
+            is_dir: false,
+            path,
+            char_bag: CharBag::from_iter(
+                root_name_chars
+                    .chars()
+                    .chain(path.as_unix_str().to_lowercase().chars()),
+            ),
+        })
+        .collect();
+    fuzzy::match_fixed_path_set(
+        candidates,
+        worktree_id.to_usize(),
+        root_name,
+        query,
+        false,
+        100,
+        path_style,
+    )
+}
+
+/// Loads the paths changed on a repository's current branch since it diverged from the
+/// repository's default branch.
+async fn load_branch_changes(
+    repository: &Entity<Repository>,
+    cx: &mut AsyncApp,
+) -> anyhow::Result<Vec<RepoPath>> {
+    let default_branch = repository
+        .update(cx, |repository, _| repository.default_branch(true))
+        .await??;
+    let Some(base) = default_branch else {
+        return Ok(Vec::new());
+    };
+    let tree_diff = repository
+        .update(cx, |repository, cx| {
+            repository.diff_tree(
+                DiffTreeType::MergeBase {
+                    base,
+                    head: "HEAD".into(),
+                },
+                cx,
+            )
+        })
+        .await??;
+    Ok(tree_diff
+        .entries
+        .into_iter()
+        .filter(|(_, status)| !matches!(status, TreeDiffStatus::Deleted { .. }))
+        .map(|(repo_path, _)| repo_path)
+        .collect())
+}
+
 fn should_hide_root_in_entry_path(worktree_store: &Entity<WorktreeStore>, cx: &App) -> bool {
     let multiple_worktrees = worktree_store
         .read(cx)
@@ -852,6 +1034,7 @@ impl FileFinderDelegate {
         currently_opened_path: Option<FoundPath>,
         history_items: Vec<FoundPath>,
         separate_history: bool,
+        changed_files: Option<ChangedFiles>,
         window: &mut Window,
         cx: &mut Context<FileFinder>,
     ) -> Self {
@@ -883,6 +1066,9 @@ impl FileFinderDelegate {
             focus_handle: cx.focus_handle(),
             include_ignored: FileFinderSettings::get_global(cx).include_ignored,
             include_ignored_refresh: Task::ready(()),
+            changed_files,
+            changed_files_refresh: Task::ready(()),
+            branch_changes: None,
         }
     }
 
@@ -910,6 +1096,9 @@ impl FileFinderDelegate {
         window: &mut Window,
         cx: &mut Context<Picker<Self>>,
     ) -> Task<()> {
+        if let Some(changed_files) = self.changed_files {
+            return self.spawn_changed_files_search(changed_files, query, window, cx);
+        }
         let relative_to = self
             .currently_opened_path
             .as_ref()
@@ -963,6 +1152,146 @@ impl FileFinderDelegate {
         })
     }
 
+    /// The files with uncommitted changes in the project's repositories, by worktree. Deleted
+    /// files are left out, as there is nothing left to open.
+    fn uncommitted_files(&self, cx: &App) -> HashMap<WorktreeId, Vec<Arc<RelPath>>> {
+        let mut changed_files = HashMap::<WorktreeId, Vec<Arc<RelPath>>>::default();
+        let git_store = self.project.read(cx).git_store().read(cx);
+        for repository in git_store.repositories().values() {
+            let repository = repository.read(cx);
+            for entry in repository.cached_status() {
+                if entry.status.is_deleted() {
+                    continue;
+                }
+                if let Some(project_path) =
+                    repository.repo_path_to_project_path(&entry.repo_path, cx)
+                {
+                    changed_files
+                        .entry(project_path.worktree_id)
+                        .or_default()
+                        .push(project_path.path);
+                }
+            }
+        }
+        changed_files
+    }
+
+    /// The files changed on the current branch since it diverged from the default branch of
+    /// their repository, and that still exist in the project.
+    fn branch_changes(&mut self, cx: &mut Context<Picker<Self>>) -> Shared<Task<Vec<ProjectPath>>> {
+        self.branch_changes
+            .get_or_insert_with(|| {
+                let project = self.project.clone();
+                let repositories = project
+                    .read(cx)
+                    .git_store()
+                    .read(cx)
+                    .repositories()
+                    .values()
+                    .cloned()
+                    .collect::<Vec<_>>();
+                cx.spawn(async move |_, cx| {
+                    let mut branch_changes = Vec::new();
+                    for repository in repositories {
+                        let Some(repo_paths) = load_branch_changes(&repository, cx).await.log_err()
+                        else {
+                            continue;
+                        };
+                        branch_changes.extend(project.read_with(cx, |project, cx| {
+                            repo_paths
+                                .iter()
+                                .filter_map(|repo_path| {
+                                    repository.read(cx).repo_path_to_project_path(repo_path, cx)
+                                })
+                                .filter(|project_path| {
+                                    project.entry_for_path(project_path, cx).is_some()
+                                })
+                                .collect::<Vec<_>>()
+                        }));
+                    }
+                    branch_changes
+                })
+                .shared()
+            })
+            .clone()
+    }
+
+    fn spawn_changed_files_search(
+        &mut self,
+        changed_files: ChangedFiles,
+        query: FileSearchQuery,
+        window: &mut Window,
+        cx: &mut Context<Picker<Self>>,
+    ) -> Task<()> {
+        let uncommitted_files = self.uncommitted_files(cx);
+        let branch_changes =
+            (changed_files == ChangedFiles::SinceDefaultBranch).then(|| self.branch_changes(cx));
+        let worktree_store = self.project.read(cx).worktree_store();
+        let include_root_name = !should_hide_root_in_entry_path(&worktree_store, cx);
+        let path_style = self.project.read(cx).path_style(cx);
+        let worktrees = worktree_store
+            .read(cx)
+            .visible_worktrees(cx)
+            .map(|worktree| {
+                let worktree = worktree.read(cx);
+                let root_name: Option<Arc<RelPath>> =
+                    include_root_name.then(|| worktree.root_name().into());
+                (worktree.id(), root_name)
+            })
+            .collect::<Vec<_>>();
+
+        let search_id = util::post_inc(&mut self.search_count);
+        cx.spawn_in(window, async move |picker, cx| {
+            let mut changed_files = uncommitted_files;
+            if let Some(branch_changes) = branch_changes {
+                for project_path in branch_changes.await {
+                    changed_files
+                        .entry(project_path.worktree_id)
+                        .or_default()
+                        .push(project_path.path);
+                }
+                for paths in changed_files.values_mut() {
+                    paths.sort();
+                    paths.dedup();
+                }
+            }
+            let changed_files = worktrees
+                .into_iter()
+                .filter_map(|(worktree_id, root_name)| {
+                    let paths = changed_files.remove(&worktree_id)?;
+                    Some((worktree_id, root_name, paths))
+                })
+                .collect::<Vec<_>>();
+
+            let path_query = query.path_query().to_owned();
+            let matches = cx
+                .background_executor()
+                .spawn(async move {
+                    changed_files
+                        .into_iter()
+                        .flat_map(|(worktree_id, root_name, paths)| {
+                            match_changed_files(
+                                worktree_id,
+                                root_name,
+                                &paths,
+                                &path_query,
+                                path_style,
+                            )
+                        })
+                        .map(ProjectPanelOrdMatch)
+                        .collect::<Vec<_>>()
+                })
+                .await;
+            picker
+                .update(cx, |picker, cx| {
+                    picker
+                        .delegate
+                        .set_search_matches(search_id, false, query, matches, cx)
+                })
+                .log_err();
+        })
+    }
+
     fn set_search_matches(
         &mut self,
         search_id: usize,
@@ -986,12 +1315,22 @@ impl FileFinderDelegate {
                 self.matches.get(self.selected_index).cloned()
             };
 
+            // History items are matched regardless of their changes, so they are left out while
+            // only changed files are searched.
+            let (history_items, currently_opened_path) = if self.changed_files.is_some() {
+                (&[][..], None)
+            } else {
+                (
+                    self.history_items.as_slice(),
+                    self.currently_opened_path.as_ref(),
+                )
+            };
             let path_style = self.project.read(cx).path_style(cx);
             self.matches.push_new_matches(
                 self.project.read(cx).worktree_store(),
                 cx,
-                &self.history_items,
-                self.currently_opened_path.as_ref(),
+                history_items,
+                currently_opened_path,
                 Some(&query),
                 matches.into_iter(),
                 extend_old_matches,
@@ -999,7 +1338,9 @@ impl FileFinderDelegate {
             );
 
             // Add channel matches
-            if let Some(channel_store) = &self.channel_store {
+            if let Some(channel_store) = &self.channel_store
+                && self.changed_files.is_none()
+            {
                 let channel_store = channel_store.read(cx);
                 let channels: Vec<_> = channel_store.channels().cloned().collect();
                 if !channels.is_empty() {
@@ -1061,7 +1402,9 @@ impl FileFinderDelegate {
             }
 
             let query_path = query.raw_query.as_str();
-            if let Ok(mut query_path) = RelPath::new(Path::new(query_path), path_style) {
+            if self.changed_files.is_none()
+                && let Ok(mut query_path) = RelPath::new(Path::new(query_path), path_style)
+            {
                 let available_worktree = self
                     .project
                     .read(cx)
@@ -1411,7 +1754,11 @@ impl PickerDelegate for FileFinderDelegate {
     type ListItem = ListItem;
 
     fn placeholder_text(&self, _window: &mut Window, _cx: &mut App) -> Arc<str> {
-        "Search project files...".into()
+        if self.changed_files.is_some() {
+            "Search changed files...".into()
+        } else {
+            "Search project files...".into()
+        }
     }
 
     fn match_count(&self) -> usize {
@@ -1478,7 +1825,7 @@ impl PickerDelegate for FileFinderDelegate {
             _ => raw_query,
         };
 
-        if raw_query.is_empty() {
+        if raw_query.is_empty() && self.changed_files.is_none() {
             // if there was no query before, and we already have some (history) matches
             // there's no need to update anything, since nothing has changed.
             // We also want to populate matches set from history entries on the first update.
@@ -1806,10 +2153,14 @@ impl PickerDelegate for FileFinderDelegate {
                             IconButton::new("filter-trigger", IconName::Sliders)
                                 .icon_size(IconSize::Small)
                                 .icon_size(IconSize::Small)
-                                .toggle_state(self.include_ignored.unwrap_or(false))
-                                .when(self.include_ignored.is_some(), |this| {
-                                    this.indicator(Indicator::dot().color(Color::Info))
-                                }),
+                                .toggle_state(
+                                    self.include_ignored.unwrap_or(false)
+                                        || self.changed_files.is_some(),
+                                )
+                                .when(
+                                    self.include_ignored.is_some() || self.changed_files.is_some(),
+                                    |this| this.indicator(Indicator::dot().color(Color::Info)),
+                                ),
                             {
                                 let focus_handle = focus_handle.clone();
                                 move |_window, cx| {
@@ -1825,6 +2176,7 @@ impl PickerDelegate for FileFinderDelegate {
                         .menu({
                             let focus_handle = focus_handle.clone();
                             let include_ignored = self.include_ignored;
+                            let changed_files = self.changed_files;
 
                             move |window, cx| {
                                 Some(ContextMenu::build(window, cx, {
@@ -1837,10 +2189,43 @@ impl PickerDelegate for FileFinderDelegate {
                                                 include_ignored.unwrap_or(false),
                                                 ui::IconPosition::End,
                                                 Some(ToggleIncludeIgnored.boxed_clone()),
+                                                {
+                                                    let focus_handle = focus_handle.clone();
+                                                    move |window, cx| {
+                                                        window.focus(&focus_handle, cx);
+                                                        window.dispatch_action(
+                                                            ToggleIncludeIgnored.boxed_clone(),
+                                                            cx,
+                                                        );
+                                                    }
+                                                },
+                                            )
+                                            .toggleable_entry(
+                                                "Changed Files Only",
+                                                changed_files == Some(ChangedFiles::Uncommitted),
+                                                ui::IconPosition::End,
+                                                Some(ToggleChangedFilesOnly.boxed_clone()),
+                                                {
+                                                    let focus_handle = focus_handle.clone();
+                                                    move |window, cx| {
+                                                        window.focus(&focus_handle, cx);
+                                                        window.dispatch_action(
+                                                            ToggleChangedFilesOnly.boxed_clone(),
+                                                            cx,
+                                                        );
+                                                    }
+                                                },
+                                            )
+                                            .toggleable_entry(
+                                                "Branch Changes Only",
+                                                changed_files
+                                                    == Some(ChangedFiles::SinceDefaultBranch),
+                                                ui::IconPosition::End,
+                                                Some(ToggleBranchChangesOnly.boxed_clone()),
                                                 move |window, cx| {
                                                     window.focus(&focus_handle, cx);
                                                     window.dispatch_action(
-                                                        ToggleIncludeIgnored.boxed_clone(),
+                                                        ToggleBranchChangesOnly.boxed_clone(),
                                                         cx,
                                                     );
                                                 },
@@ -1117,6 +1117,165 @@ async fn test_ignored_root(cx: &mut TestAppContext) {
     });
 }
 
+#[gpui::test]
+async fn test_changed_files_only(cx: &mut TestAppContext) {
+    let app_state = init_test(cx);
+    app_state
+        .fs
+        .as_fake()
+        .insert_tree(
+            path!("/root"),
+            json!({
+                ".git": {},
+                "src": {
+                    "main.rs": "fn main() {}\n",
+                    "lib.rs": "pub mod changed;\n",
+                    "changed.rs": "",
+                },
+                "README.md": "# Changed\n",
+            }),
+        )
+        .await;
+    app_state.fs.as_fake().set_head_and_index_for_repo(
+        path!("/root/.git").as_ref(),
+        &[
+            ("src/main.rs", "fn main() {}\n".into()),
+            ("src/lib.rs", "pub mod changed;\n".into()),
+            ("README.md", "# Unchanged\n".into()),
+        ],
+    );
+
+    let project = Project::test(app_state.fs.clone(), [path!("/root").as_ref()], cx).await;
+    let (multi_workspace, cx) =
+        cx.add_window_view(|window, cx| MultiWorkspace::test_new(project, window, cx));
+    let workspace = multi_workspace.read_with(cx, |mw, _| mw.workspace().clone());
+    cx.run_until_parked();
+
+    cx.dispatch_action(OpenChangedFile);
+    cx.run_until_parked();
+    let picker = active_file_picker(&workspace, cx);
+    picker.update(cx, |picker, _| {
+        let mut paths = collect_search_matches(picker).search_paths_only();
+        paths.sort();
+        assert_eq!(
+            paths,
+            vec![
+                rel_path("README.md").into(),
+                rel_path("src/changed.rs").into(),
+            ],
+            "An empty query should list all changed files"
+        );
+    });
+
+    picker
+        .update_in(cx, |picker, window, cx| {
+            picker.delegate.update_matches("rs".to_string(), window, cx)
+        })
+        .await;
+    picker.update(cx, |picker, _| {
+        assert_eq!(
+            collect_search_matches(picker).search_paths_only(),
+            vec![rel_path("src/changed.rs").into()],
+            "Unchanged files should not match"
+        );
+    });
+
+    cx.dispatch_action(ToggleChangedFilesOnly);
+    cx.run_until_parked();
+    picker.update(cx, |picker, _| {
+        let mut paths = collect_search_matches(picker).search_paths_only();
+        paths.sort();
+        assert_eq!(
+            paths,
+            vec![
+                rel_path("src/changed.rs").into(),
+                rel_path("src/lib.rs").into(),
+                rel_path("src/main.rs").into(),
+            ],
+            "All files should match once the filter is toggled off"
+        );
+    });
+}
+
+#[gpui::test]
+async fn test_branch_changes_only(cx: &mut TestAppContext) {
+    let app_state = init_test(cx);
+    app_state
+        .fs
+        .as_fake()
+        .insert_tree(
+            path!("/root"),
+            json!({
+                ".git": {},
+                "src": {
+                    "main.rs": "fn main() {}\n",
+                    "lib.rs": "pub mod branch;\n",
+                    "branch.rs": "",
+                    "changed.rs": "",
+                },
+                "README.md": "# Changed\n",
+            }),
+        )
+        .await;
+    app_state.fs.as_fake().set_head_and_index_for_repo(
+        path!("/root/.git").as_ref(),
+        &[
+            ("src/main.rs", "fn main() {}\n".into()),
+            ("src/lib.rs", "pub mod branch;\n".into()),
+            ("src/branch.rs", "".into()),
+            ("README.md", "# Unchanged\n".into()),
+        ],
+    );
+    app_state.fs.as_fake().set_merge_base_content_for_repo(
+        path!("/root/.git").as_ref(),
+        &[
+            ("src/main.rs", "fn main() {}\n".into()),
+            ("src/lib.rs", "".into()),
+            ("src/removed.rs", "".into()),
+            ("README.md", "# Unchanged\n".into()),
+        ],
+    );
+
+    let project = Project::test(app_state.fs.clone(), [path!("/root").as_ref()], cx).await;
+    let (multi_workspace, cx) =
+        cx.add_window_view(|window, cx| MultiWorkspace::test_new(project, window, cx));
+    let workspace = multi_workspace.read_with(cx, |mw, _| mw.workspace().clone());
+    cx.run_until_parked();
+
+    cx.dispatch_action(OpenBranchChangedFile);
+    cx.run_until_parked();
+    let picker = active_file_picker(&workspace, cx);
+    picker.update(cx, |picker, _| {
+        let mut paths = collect_search_matches(picker).search_paths_only();
+        paths.sort();
+        assert_eq!(
+            paths,
+            vec![
+                rel_path("README.md").into(),
+                rel_path("src/branch.rs").into(),
+                rel_path("src/changed.rs").into(),
+                rel_path("src/lib.rs").into(),
+            ],
+            "Files changed on the branch and files with uncommitted changes should be listed"
+        );
+    });
+
+    cx.dispatch_action(ToggleChangedFilesOnly);
+    cx.run_until_parked();
+    picker.update(cx, |picker, _| {
+        let mut paths = collect_search_matches(picker).search_paths_only();
+        paths.sort();
+        assert_eq!(
+            paths,
+            vec![
+                rel_path("README.md").into(),
+                rel_path("src/changed.rs").into(),
+            ],
+            "Switching to uncommitted changes should leave out the branch's committed changes"
+        );
+    });
+}
+
 #[gpui::test]
 async fn test_single_file_worktrees(cx: &mut TestAppContext) {
     let app_state = init_test(cx);
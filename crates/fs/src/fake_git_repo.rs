@@ -1,26 +1,38 @@
 use crate::{FakeFs, FakeFsEntry, Fs, RemoveOptions, RenameOptions};
 use anyhow::{Context as _, Result, bail};
-use collections::{HashMap, HashSet};
+use collections::{BTreeMap, BTreeSet, HashMap, HashSet};
 use futures::future::{self, BoxFuture, join_all};
 use git::{
     Oid, RunHook,
-    blame::Blame,
+    blame::{Blame, BlameOptions},
+    commit::CommitOverview,
+    gitignore::IgnoreRule,
+    index_flags::{FlaggedFile, IndexFlag},
+    line_history::LineHistoryEntry,
+    remote_progress::RemoteProgressDelegate,
     repository::{
-        AskPassDelegate, Branch, CommitDataReader, CommitDetails, CommitOptions, FetchOptions,
-        GRAPH_CHUNK_SIZE, GitRepository, GitRepositoryCheckpoint, InitialGraphCommitData, LogOrder,
-        LogSource, PushOptions, Remote, RepoPath, ResetMode, Worktree,
+        AskPassDelegate, BRANCH_NOT_FULLY_MERGED, BisectMark, BisectStatus, Branch,
+        CommitDataReader, CommitDetails, CommitLogEntry, CommitOptions, CommitSignature,
+        CommitSummary, ConflictStages, FetchOptions, GRAPH_CHUNK_SIZE, GitRepository,
+        GitRepositoryCheckpoint, InProgressOperation, InProgressOperationKind,
+        InitialGraphCommitData, LogOrder, LogSource, OperationResolution, PullStrategy,
+        PushOptions, RebaseAction, RebaseTodoEntry, ReflogEntry, Remote, RemoteInfo, RepoPath,
+        ResetMode, STASH_APPLY_CONFLICTS, StashOptions, Upstream, UpstreamTrackingStatus, Worktree,
     },
+    sparse_checkout::SparseCheckout,
+    stash::StashEntry,
     status::{
-        DiffTreeType, FileStatus, GitStatus, StatusCode, TrackedStatus, TreeDiff, TreeDiffStatus,
-        UnmergedStatus,
+        DiffTreeType, FileRename, FileStatus, GitStatus, StatusCode, TrackedStatus, TreeDiff,
+        TreeDiffStatus, UnmergedStatus,
     },
+    submodule::{Submodule, SubmoduleState},
 };
 use gpui::{AsyncApp, BackgroundExecutor, SharedString, Task};
 use ignore::gitignore::GitignoreBuilder;
 use parking_lot::Mutex;
 use rope::Rope;
 use smol::{channel::Sender, future::FutureExt as _};
-use std::{path::PathBuf, sync::Arc, sync::atomic::AtomicBool};
+use std::{ops::RangeInclusive, path::PathBuf, sync::Arc, sync::atomic::AtomicBool};
 use text::LineEnding;
 use util::{paths::PathStyle, rel_path::RelPath};
 
@@ -45,15 +57,112 @@ pub struct FakeGitRepositoryState {
     pub merge_base_contents: HashMap<RepoPath, Oid>,
     pub oids: HashMap<Oid, String>,
     pub blames: HashMap<RepoPath, Blame>,
+    /// Blames and contents of files at revisions other than HEAD, keyed by revision and path.
+    pub revision_blames: HashMap<(String, RepoPath), Blame>,
+    pub revision_contents: HashMap<(String, RepoPath), String>,
+    /// The commits reported for any lines of a file.
+    pub line_histories: HashMap<RepoPath, Vec<LineHistoryEntry>>,
+    /// The committers, parents, refs and changed files of commits, keyed by SHA.
+    pub commit_overviews: HashMap<String, CommitOverview>,
+    /// The common ancestors of pairs of revisions, in either order.
+    pub merge_bases: HashMap<(String, String), String>,
     pub current_branch_name: Option<String>,
     pub branches: HashSet<String>,
     /// List of remotes, keys are names and values are URLs
     pub remotes: HashMap<String, String>,
+    /// The push URLs of the remotes that push somewhere other than their fetch URL.
+    pub remote_push_urls: HashMap<String, String>,
+    pub default_remote: Option<String>,
     pub simulated_index_write_error_message: Option<String>,
     pub simulated_create_worktree_error: Option<String>,
     pub refs: HashMap<String, String>,
     pub graph_commits: Vec<Arc<InitialGraphCommitData>>,
     pub worktrees: Vec<Worktree>,
+    /// Remote branches that already contain HEAD.
+    pub pushed_to: Vec<String>,
+    /// Contents of the template configured through `commit.template`.
+    pub commit_template: Option<String>,
+    /// Names and emails of the authors of recent commits, most recent first.
+    pub recent_commit_authors: Vec<(String, String)>,
+    /// Signatures of signed commits, keyed by SHA.
+    pub commit_signatures: HashMap<String, CommitSignature>,
+    /// The text `search_commits` matches against for each commit, such as its author and message.
+    pub commit_search_text: HashMap<Oid, String>,
+    /// The index stages of conflicted files.
+    pub conflict_stages: HashMap<RepoPath, ConflictStages>,
+    /// The commits `commits_between` lists for each base and head, newest first.
+    pub commits_between: HashMap<(String, String), Vec<CommitSummary>>,
+    /// The commits `rebase_todo` lists, oldest first.
+    pub rebase_todo: Vec<RebaseTodoEntry>,
+    /// The todo lists of the interactive rebases that were run.
+    pub rebased_todos: Vec<Vec<RebaseTodoEntry>>,
+    /// The commits of each cherry-pick that was run, and whether it recorded their origin.
+    pub cherry_picks: Vec<(Vec<String>, bool)>,
+    /// Commits whose cherry-pick stops on conflicts.
+    pub conflicting_cherry_picks: HashSet<String>,
+    /// The commits that were reverted, and whether the revert was left uncommitted.
+    pub reverted_commits: Vec<(String, bool)>,
+    /// The operation that stopped and waits to be continued, skipped or aborted.
+    pub in_progress_operation: Option<InProgressOperation>,
+    /// The commits a bisect searches by SHA, newest first, as if the history was linear.
+    pub bisect_history: Vec<String>,
+    pub bisect: Option<FakeBisect>,
+    /// The reflog of each reference, newest first.
+    pub reflogs: HashMap<String, Vec<ReflogEntry>>,
+    /// The commits reachable from HEAD, newest first. Searches match their subjects and authors.
+    pub commit_log: Vec<CommitLogEntry>,
+    /// The commits the current branch was reset to, and how.
+    pub resets: Vec<(String, ResetMode)>,
+    /// Stashes, newest first.
+    pub stash_entries: Vec<StashEntry>,
+    /// The paths and options of each stash push, oldest first.
+    pub stash_pushes: Vec<(Vec<RepoPath>, StashOptions)>,
+    /// Stashes whose apply or pop stops on conflicts.
+    pub conflicting_stashes: HashSet<Oid>,
+    /// The revision each branch was created from, when one was given.
+    pub branch_bases: HashMap<String, String>,
+    /// The branch each branch tracks.
+    pub branch_upstreams: HashMap<String, String>,
+    /// Tag names, most recently created first.
+    pub tags: Vec<String>,
+    /// The message of each annotated tag.
+    pub tag_messages: HashMap<String, String>,
+    /// The revision each tag was created at, when one was given.
+    pub tag_targets: HashMap<String, String>,
+    /// Branches with commits that aren't merged anywhere else, so deleting them needs `force`.
+    pub unmerged_branches: HashSet<String>,
+    /// Lines printed by hooks when they run.
+    pub hook_output: Vec<String>,
+    /// When set, hooks fail with this message after printing `hook_output`.
+    pub simulated_hook_failure: Option<String>,
+    /// The options of each fetch that was run, oldest first.
+    pub fetches: Vec<FetchOptions>,
+    /// When set, fetches fail with this message.
+    pub simulated_fetch_error: Option<String>,
+    /// The remote, remote ref and local branch of each fetch into a branch, oldest first.
+    pub branch_fetches: Vec<(String, String, String)>,
+    /// The remote and strategy of each pull that was run, oldest first.
+    pub pulls: Vec<(String, Option<PullStrategy>)>,
+    /// Messages the next pulls fail with, one per pull.
+    pub simulated_pull_errors: Vec<String>,
+    /// The remote branch, remote and options of each push that was run, oldest first.
+    pub pushes: Vec<(String, String, Option<PushOptions>)>,
+    /// Messages the next pushes fail with, one per push.
+    pub simulated_push_errors: Vec<String>,
+    /// Paths whose `filter` attribute is `lfs`.
+    pub lfs_paths: HashSet<RepoPath>,
+    /// The paths of each LFS fetch that was run, oldest first.
+    pub lfs_fetches: Vec<Vec<RepoPath>>,
+    pub submodules: Vec<Submodule>,
+    /// The paths of each submodule update that was run and whether it initialized them, oldest
+    /// first.
+    pub submodule_updates: Vec<(Vec<RepoPath>, bool)>,
+    /// The paths of each submodule sync that was run, oldest first.
+    pub submodule_syncs: Vec<Vec<RepoPath>>,
+    pub sparse_checkout: Option<SparseCheckout>,
+    pub ignore_rules: HashMap<RepoPath, IgnoreRule>,
+    pub renames: Vec<FileRename>,
+    pub flagged_files: Vec<FlaggedFile>,
 }
 
 impl FakeGitRepositoryState {
@@ -64,6 +173,11 @@ impl FakeGitRepositoryState {
             index_contents: Default::default(),
             unmerged_paths: Default::default(),
             blames: Default::default(),
+            revision_blames: Default::default(),
+            revision_contents: Default::default(),
+            line_histories: Default::default(),
+            commit_overviews: Default::default(),
+            merge_bases: Default::default(),
             current_branch_name: Default::default(),
             branches: Default::default(),
             simulated_index_write_error_message: Default::default(),
@@ -72,10 +186,118 @@ impl FakeGitRepositoryState {
             merge_base_contents: Default::default(),
             oids: Default::default(),
             remotes: HashMap::default(),
+            remote_push_urls: HashMap::default(),
+            default_remote: None,
             graph_commits: Vec::new(),
             worktrees: Vec::new(),
+            pushed_to: Vec::new(),
+            commit_template: None,
+            recent_commit_authors: Vec::new(),
+            commit_signatures: HashMap::default(),
+            commit_search_text: HashMap::default(),
+            conflict_stages: HashMap::default(),
+            commits_between: Default::default(),
+            rebase_todo: Vec::new(),
+            rebased_todos: Vec::new(),
+            cherry_picks: Vec::new(),
+            conflicting_cherry_picks: HashSet::default(),
+            reverted_commits: Vec::new(),
+            in_progress_operation: None,
+            bisect_history: Vec::new(),
+            bisect: None,
+            reflogs: HashMap::default(),
+            commit_log: Vec::new(),
+            resets: Vec::new(),
+            stash_entries: Vec::new(),
+            stash_pushes: Vec::new(),
+            conflicting_stashes: HashSet::default(),
+            branch_bases: HashMap::default(),
+            branch_upstreams: HashMap::default(),
+            tags: Vec::new(),
+            tag_messages: HashMap::default(),
+            tag_targets: HashMap::default(),
+            unmerged_branches: HashSet::default(),
+            hook_output: Vec::new(),
+            simulated_hook_failure: None,
+            fetches: Vec::new(),
+            simulated_fetch_error: None,
+            branch_fetches: Vec::new(),
+            pulls: Vec::new(),
+            simulated_pull_errors: Vec::new(),
+            pushes: Vec::new(),
+            simulated_push_errors: Vec::new(),
+            lfs_paths: HashSet::default(),
+            lfs_fetches: Vec::new(),
+            submodules: Vec::new(),
+            submodule_updates: Vec::new(),
+            submodule_syncs: Vec::new(),
+            sparse_checkout: None,
+            ignore_rules: HashMap::default(),
+            renames: Vec::new(),
+            flagged_files: Vec::new(),
         }
     }
+
+    /// The contents of every file at `revision`, from `head_contents` for HEAD and from
+    /// `revision_contents` otherwise.
+    fn contents_at_revision(&self, revision: &str) -> BTreeMap<RepoPath, String> {
+        if revision == "HEAD" || self.refs.get("HEAD").is_some_and(|sha| sha == revision) {
+            return self.head_contents.clone().into_iter().collect();
+        }
+        self.revision_contents
+            .iter()
+            .filter(|((content_revision, _), _)| content_revision == revision)
+            .map(|((_, path), content)| (path.clone(), content.clone()))
+            .collect()
+    }
+
+    fn apply_stash(&self, index: usize) -> Result<()> {
+        let Some(entry) = self.stash_entries.get(index) else {
+            bail!("no stash at index {index}");
+        };
+        if self.conflicting_stashes.contains(&entry.oid) {
+            bail!("{STASH_APPLY_CONFLICTS}\nCONFLICT (content): Merge conflict");
+        }
+        Ok(())
+    }
+}
+
+/// A bisect in progress over [`FakeGitRepositoryState::bisect_history`].
+#[derive(Clone, Debug, Default)]
+pub struct FakeBisect {
+    /// The commits that can still be the first bad one, newest first. The first is known to be
+    /// bad.
+    pub suspects: Vec<String>,
+    pub skipped: HashSet<String>,
+    /// Every mark given so far, in order.
+    pub marks: Vec<BisectMark>,
+}
+
+impl FakeBisect {
+    /// The suspect closest to the middle that wasn't skipped.
+    fn candidate(&self) -> Option<usize> {
+        let middle = self.suspects.len() / 2;
+        (1..self.suspects.len())
+            .filter(|index| !self.skipped.contains(&self.suspects[*index]))
+            .min_by_key(|index| index.abs_diff(middle))
+    }
+
+    fn status(&self) -> Result<BisectStatus> {
+        if let [culprit] = self.suspects.as_slice() {
+            return Ok(BisectStatus::Found {
+                sha: culprit.clone(),
+                subject: String::new(),
+            });
+        }
+        let candidate = self
+            .candidate()
+            .context("only skipped commits are left to test")?;
+        Ok(BisectStatus::Testing {
+            sha: self.suspects[candidate].clone(),
+            subject: String::new(),
+            remaining_steps: (self.suspects.len() - 1).ilog2() as usize,
+        })
+    }
 }
 
 impl FakeGitRepository {
@@ -109,6 +331,16 @@ impl GitRepository for FakeGitRepository {
         self.executor.spawn(async move { fut.await.ok() }).boxed()
     }
 
+    fn load_conflict_stages(&self, path: RepoPath) -> BoxFuture<'_, Result<ConflictStages>> {
+        self.with_state_async(false, move |state| {
+            state
+                .conflict_stages
+                .get(&path)
+                .cloned()
+                .context("not conflicted")
+        })
+    }
+
     fn load_committed_text(&self, path: RepoPath) -> BoxFuture<'_, Option<String>> {
         let fut = self.with_state_async(false, move |state| {
             state
@@ -120,6 +352,22 @@ impl GitRepository for FakeGitRepository {
         self.executor.spawn(async move { fut.await.ok() }).boxed()
     }
 
+    fn load_text_at_revision(
+        &self,
+        revision: String,
+        path: RepoPath,
+    ) -> BoxFuture<'_, Result<Option<String>>> {
+        self.with_state_async(false, move |state| {
+            if revision == "HEAD" || state.refs.get("HEAD") == Some(&revision) {
+                return Ok(state.head_contents.get(&path).cloned());
+            }
+            if let Some(content) = state.revision_contents.get(&(revision.clone(), path)) {
+                return Ok(Some(content.clone()));
+            }
+            bail!("unknown revision {revision}")
+        })
+    }
+
     fn load_blob_content(&self, oid: git::Oid) -> BoxFuture<'_, Result<String>> {
         self.with_state_async(false, move |state| {
             state.oids.get(&oid).cloned().context("oid does not exist")
@@ -135,6 +383,46 @@ impl GitRepository for FakeGitRepository {
         unimplemented!()
     }
 
+    fn compare_revisions(
+        &self,
+        base: String,
+        target: String,
+    ) -> BoxFuture<'_, Result<git::repository::CommitDiff>> {
+        self.with_state_async(false, move |state| {
+            let mut old_contents = state.contents_at_revision(&base);
+            let mut new_contents = state.contents_at_revision(&target);
+            let paths = old_contents
+                .keys()
+                .chain(new_contents.keys())
+                .cloned()
+                .collect::<BTreeSet<_>>();
+            let files = paths
+                .into_iter()
+                .filter_map(|path| {
+                    let old_text = old_contents.remove(&path);
+                    let new_text = new_contents.remove(&path);
+                    (old_text != new_text).then(|| git::repository::CommitFile {
+                        path,
+                        old_text,
+                        new_text,
+                        is_binary: false,
+                    })
+                })
+                .collect();
+            Ok(git::repository::CommitDiff { files })
+        })
+    }
+
+    fn merge_base(&self, first: String, second: String) -> BoxFuture<'_, Result<Option<String>>> {
+        self.with_state_async(false, move |state| {
+            Ok(state
+                .merge_bases
+                .get(&(first.clone(), second.clone()))
+                .or_else(|| state.merge_bases.get(&(second, first)))
+                .cloned())
+        })
+    }
+
     fn set_index_text(
         &self,
         path: RepoPath,
@@ -166,9 +454,33 @@ impl GitRepository for FakeGitRepository {
         async move { fut.await.ok() }.boxed()
     }
 
-    fn diff_tree(&self, _request: DiffTreeType) -> BoxFuture<'_, Result<TreeDiff>> {
+    fn diff_tree(&self, request: DiffTreeType) -> BoxFuture<'_, Result<TreeDiff>> {
         let mut entries = HashMap::default();
-        self.with_state_async(false, |state| {
+        self.with_state_async(false, move |state| {
+            if let DiffTreeType::Since { base, head } = &request {
+                // Revisions other than HEAD only have contents, so their blobs get a placeholder
+                // oid.
+                let old_contents = state.contents_at_revision(base);
+                let new_contents = state.contents_at_revision(head);
+                for (path, old_content) in &old_contents {
+                    let status = match new_contents.get(path) {
+                        Some(new_content) if new_content == old_content => continue,
+                        Some(_) => TreeDiffStatus::Modified {
+                            old: git::Oid::default(),
+                        },
+                        None => TreeDiffStatus::Deleted {
+                            old: git::Oid::default(),
+                        },
+                    };
+                    entries.insert(path.clone(), status);
+                }
+                for path in new_contents.keys() {
+                    if !old_contents.contains_key(path) {
+                        entries.insert(path.clone(), TreeDiffStatus::Added);
+                    }
+                }
+                return Ok(TreeDiff { entries });
+            }
             for (path, content) in &state.head_contents {
                 let status = if let Some((oid, original)) = state
                     .merge_base_contents
@@ -214,13 +526,27 @@ impl GitRepository for FakeGitRepository {
         .boxed()
     }
 
+    fn commit_overview(&self, commit: String) -> BoxFuture<'_, Result<CommitOverview>> {
+        self.with_state_async(false, move |state| {
+            state
+                .commit_overviews
+                .get(&commit)
+                .cloned()
+                .with_context(|| format!("no overview for commit {commit:?}"))
+        })
+    }
+
     fn reset(
         &self,
-        _commit: String,
-        _mode: ResetMode,
+        commit: String,
+        mode: ResetMode,
         _env: Arc<HashMap<String, String>>,
     ) -> BoxFuture<'_, Result<()>> {
-        unimplemented!()
+        self.with_state_async(true, move |state| {
+            state.refs.insert("HEAD".into(), commit.clone());
+            state.resets.push((commit, mode));
+            Ok(())
+        })
     }
 
     fn checkout_files(
@@ -232,6 +558,48 @@ impl GitRepository for FakeGitRepository {
         unimplemented!()
     }
 
+    fn checkout_revision(
+        &self,
+        revision: String,
+        _env: Arc<HashMap<String, String>>,
+    ) -> BoxFuture<'_, Result<()>> {
+        self.with_state_async(true, move |state| {
+            state.current_branch_name = None;
+            state.refs.insert("HEAD".into(), revision);
+            Ok(())
+        })
+    }
+
+    fn reflog(&self, reference: String, limit: usize) -> BoxFuture<'_, Result<Vec<ReflogEntry>>> {
+        self.with_state_async(false, move |state| {
+            let mut entries = state.reflogs.get(&reference).cloned().unwrap_or_default();
+            entries.truncate(limit);
+            Ok(entries)
+        })
+    }
+
+    fn commit_log(
+        &self,
+        query: Option<String>,
+        skip: usize,
+        limit: usize,
+    ) -> BoxFuture<'_, Result<Vec<CommitLogEntry>>> {
+        self.with_state_async(false, move |state| {
+            let query = query.unwrap_or_default().to_lowercase();
+            Ok(state
+                .commit_log
+                .iter()
+                .filter(|entry| {
+                    entry.subject.to_lowercase().contains(&query)
+                        || entry.author_name.to_lowercase().contains(&query)
+                })
+                .skip(skip)
+                .take(limit)
+                .cloned()
+                .collect())
+        })
+    }
+
     fn path(&self) -> PathBuf {
         self.repository_dir_path.clone()
     }
@@ -380,7 +748,11 @@ impl GitRepository for FakeGitRepository {
     }
 
     fn stash_entries(&self) -> BoxFuture<'_, Result<git::stash::GitStash>> {
-        async { Ok(git::stash::GitStash::default()) }.boxed()
+        self.with_state_async(false, |state| {
+            Ok(git::stash::GitStash {
+                entries: state.stash_entries.clone().into(),
+            })
+        })
     }
 
     fn branches(&self) -> BoxFuture<'_, Result<Vec<Branch>>> {
@@ -397,11 +769,26 @@ impl GitRepository for FakeGitRepository {
                     } else {
                         format!("refs/heads/{branch_name}").into()
                     };
+                    let upstream = state.branch_upstreams.get(branch_name).map(|upstream| {
+                        let ref_name = if upstream.contains('/') {
+                            format!("refs/remotes/{upstream}")
+                        } else {
+                            format!("refs/heads/{upstream}")
+                        };
+                        Upstream {
+                            ref_name: ref_name.into(),
+                            tracking: UpstreamTrackingStatus {
+                                ahead: 0,
+                                behind: 0,
+                            }
+                            .into(),
+                        }
+                    });
                     Branch {
                         is_head: Some(branch_name) == current_branch.as_ref(),
                         ref_name,
                         most_recent_commit: None,
-                        upstream: None,
+                        upstream,
                     }
                 })
                 .collect())
@@ -557,7 +944,11 @@ impl GitRepository for FakeGitRepository {
         .boxed()
     }
 
-    fn change_branch(&self, name: String) -> BoxFuture<'_, Result<()>> {
+    fn change_branch(
+        &self,
+        name: String,
+        _progress: RemoteProgressDelegate,
+    ) -> BoxFuture<'_, Result<()>> {
         self.with_state_async(true, |state| {
             state.current_branch_name = Some(name);
             Ok(())
@@ -567,7 +958,7 @@ impl GitRepository for FakeGitRepository {
     fn create_branch(
         &self,
         name: String,
-        _base_branch: Option<String>,
+        base_branch: Option<String>,
     ) -> BoxFuture<'_, Result<()>> {
         self.with_state_async(true, move |state| {
             if let Some((remote, _)) = name.split_once('/')
@@ -575,6 +966,9 @@ impl GitRepository for FakeGitRepository {
             {
                 state.remotes.insert(remote.to_owned(), "".to_owned());
             }
+            if let Some(base_branch) = base_branch {
+                state.branch_bases.insert(name.clone(), base_branch);
+            }
             state.branches.insert(name);
             Ok(())
         })
@@ -593,11 +987,77 @@ impl GitRepository for FakeGitRepository {
         })
     }
 
-    fn delete_branch(&self, _is_remote: bool, name: String) -> BoxFuture<'_, Result<()>> {
+    fn delete_branch(
+        &self,
+        _is_remote: bool,
+        name: String,
+        force: bool,
+    ) -> BoxFuture<'_, Result<()>> {
         self.with_state_async(true, move |state| {
-            if !state.branches.remove(&name) {
+            if !state.branches.contains(&name) {
                 bail!("no such branch: {name}");
             }
+            if !force && state.unmerged_branches.contains(&name) {
+                bail!("{BRANCH_NOT_FULLY_MERGED}\nerror: the branch '{name}' is not fully merged");
+            }
+            state.branches.remove(&name);
+            state.branch_upstreams.remove(&name);
+            Ok(())
+        })
+    }
+
+    fn set_branch_upstream(
+        &self,
+        branch: String,
+        upstream: Option<String>,
+    ) -> BoxFuture<'_, Result<()>> {
+        self.with_state_async(true, move |state| {
+            if !state.branches.contains(&branch) {
+                bail!("no such branch: {branch}");
+            }
+            match upstream {
+                Some(upstream) => state.branch_upstreams.insert(branch, upstream),
+                None => state.branch_upstreams.remove(&branch),
+            };
+            Ok(())
+        })
+    }
+
+    fn tags(&self) -> BoxFuture<'_, Result<Vec<SharedString>>> {
+        self.with_state_async(false, |state| {
+            Ok(state.tags.iter().cloned().map(SharedString::from).collect())
+        })
+    }
+
+    fn create_tag(
+        &self,
+        name: String,
+        message: Option<String>,
+        target: Option<String>,
+    ) -> BoxFuture<'_, Result<()>> {
+        self.with_state_async(true, move |state| {
+            if state.tags.contains(&name) {
+                bail!("fatal: tag '{name}' already exists");
+            }
+            if let Some(message) = message {
+                state.tag_messages.insert(name.clone(), message);
+            }
+            if let Some(target) = target {
+                state.tag_targets.insert(name.clone(), target);
+            }
+            state.tags.insert(0, name);
+            Ok(())
+        })
+    }
+
+    fn delete_tag(&self, name: String) -> BoxFuture<'_, Result<()>> {
+        self.with_state_async(true, move |state| {
+            let Some(index) = state.tags.iter().position(|tag| *tag == name) else {
+                bail!("error: tag '{name}' not found.");
+            };
+            state.tags.remove(index);
+            state.tag_messages.remove(&name);
+            state.tag_targets.remove(&name);
             Ok(())
         })
     }
@@ -607,12 +1067,36 @@ impl GitRepository for FakeGitRepository {
         path: RepoPath,
         _content: Rope,
         _line_ending: LineEnding,
+        options: BlameOptions,
     ) -> BoxFuture<'_, Result<git::blame::Blame>> {
         self.with_state_async(false, move |state| {
-            state
+            let mut blame = state
                 .blames
                 .get(&path)
                 .with_context(|| format!("failed to get blame for {:?}", path))
+                .cloned()?;
+            if let Some(rows) = options.rows {
+                blame.entries.retain_mut(|entry| {
+                    let start = entry.range.start.max(rows.start);
+                    entry.original_line_number += start.saturating_sub(entry.range.start);
+                    entry.range = start..entry.range.end.min(rows.end);
+                    !entry.range.is_empty()
+                });
+            }
+            Ok(blame)
+        })
+    }
+
+    fn blame_at_revision(
+        &self,
+        revision: String,
+        path: RepoPath,
+    ) -> BoxFuture<'_, Result<git::blame::Blame>> {
+        self.with_state_async(false, move |state| {
+            state
+                .revision_blames
+                .get(&(revision.clone(), path.clone()))
+                .with_context(|| format!("failed to get blame for {path:?} at {revision}"))
                 .cloned()
         })
     }
@@ -636,6 +1120,16 @@ impl GitRepository for FakeGitRepository {
         .boxed()
     }
 
+    fn line_history(
+        &self,
+        path: RepoPath,
+        _lines: RangeInclusive<u32>,
+    ) -> BoxFuture<'_, Result<Vec<LineHistoryEntry>>> {
+        self.with_state_async(false, move |state| {
+            Ok(state.line_histories.get(&path).cloned().unwrap_or_default())
+        })
+    }
+
     fn stage_paths(
         &self,
         paths: Vec<RepoPath>,
@@ -686,34 +1180,97 @@ impl GitRepository for FakeGitRepository {
 
     fn stash_paths(
         &self,
-        _paths: Vec<RepoPath>,
+        paths: Vec<RepoPath>,
+        options: StashOptions,
         _env: Arc<HashMap<String, String>>,
     ) -> BoxFuture<'_, Result<()>> {
-        unimplemented!()
+        self.with_state_async(true, move |state| {
+            let branch = state.current_branch_name.clone();
+            let message = options.message.clone().unwrap_or_else(|| {
+                format!("WIP on {}", branch.as_deref().unwrap_or("(no branch)"))
+            });
+            state.stash_pushes.push((paths, options));
+            let oid = Oid::from_bytes(&[state.stash_pushes.len() as u8; 20])?;
+            state.stash_entries.insert(
+                0,
+                StashEntry {
+                    index: 0,
+                    oid,
+                    message,
+                    branch,
+                    timestamp: 0,
+                },
+            );
+            for (index, entry) in state.stash_entries.iter_mut().enumerate() {
+                entry.index = index;
+            }
+            Ok(())
+        })
     }
 
     fn stash_pop(
         &self,
-        _index: Option<usize>,
+        index: Option<usize>,
         _env: Arc<HashMap<String, String>>,
     ) -> BoxFuture<'_, Result<()>> {
-        unimplemented!()
+        self.with_state_async(true, move |state| {
+            let index = index.unwrap_or(0);
+            state.apply_stash(index)?;
+            state.stash_entries.remove(index);
+            for (index, entry) in state.stash_entries.iter_mut().enumerate() {
+                entry.index = index;
+            }
+            Ok(())
+        })
     }
 
     fn stash_apply(
         &self,
-        _index: Option<usize>,
+        index: Option<usize>,
         _env: Arc<HashMap<String, String>>,
     ) -> BoxFuture<'_, Result<()>> {
-        unimplemented!()
+        self.with_state_async(true, move |state| state.apply_stash(index.unwrap_or(0)))
     }
 
     fn stash_drop(
         &self,
-        _index: Option<usize>,
+        index: Option<usize>,
         _env: Arc<HashMap<String, String>>,
     ) -> BoxFuture<'_, Result<()>> {
-        unimplemented!()
+        self.with_state_async(true, move |state| {
+            let index = index.unwrap_or(0);
+            if index >= state.stash_entries.len() {
+                bail!("no stash at index {index}");
+            }
+            state.stash_entries.remove(index);
+            for (index, entry) in state.stash_entries.iter_mut().enumerate() {
+                entry.index = index;
+            }
+            Ok(())
+        })
+    }
+
+    fn stash_branch(
+        &self,
+        branch_name: String,
+        index: Option<usize>,
+        _env: Arc<HashMap<String, String>>,
+    ) -> BoxFuture<'_, Result<()>> {
+        self.with_state_async(true, move |state| {
+            let index = index.unwrap_or(0);
+            if index >= state.stash_entries.len() {
+                bail!("no stash at index {index}");
+            }
+            if !state.branches.insert(branch_name.clone()) {
+                bail!("a branch named '{branch_name}' already exists");
+            }
+            state.current_branch_name = Some(branch_name);
+            state.stash_entries.remove(index);
+            for (index, entry) in state.stash_entries.iter_mut().enumerate() {
+                entry.index = index;
+            }
+            Ok(())
+        })
     }
 
     fn commit(
@@ -727,47 +1284,305 @@ impl GitRepository for FakeGitRepository {
         async { Ok(()) }.boxed()
     }
 
+    fn load_commit_template(
+        &self,
+        _configured_path: Option<String>,
+    ) -> BoxFuture<'_, Result<Option<String>>> {
+        self.with_state_async(false, |state| Ok(state.commit_template.clone()))
+    }
+
+    fn recent_commit_authors(
+        &self,
+        max_commits: usize,
+    ) -> BoxFuture<'_, Result<Vec<(gpui::SharedString, gpui::SharedString)>>> {
+        self.with_state_async(false, move |state| {
+            Ok(state
+                .recent_commit_authors
+                .iter()
+                .take(max_commits)
+                .map(|(name, email)| (name.clone().into(), email.clone().into()))
+                .collect())
+        })
+    }
+
+    fn verify_commit_signatures(
+        &self,
+        shas: Vec<SharedString>,
+    ) -> BoxFuture<'_, Result<HashMap<SharedString, CommitSignature>>> {
+        self.with_state_async(false, move |state| {
+            Ok(shas
+                .into_iter()
+                .filter_map(|sha| {
+                    let signature = state.commit_signatures.get(&*sha)?.clone();
+                    Some((sha, signature))
+                })
+                .collect())
+        })
+    }
+
     fn run_hook(
         &self,
         _hook: RunHook,
         _env: Arc<HashMap<String, String>>,
+        output: Sender<String>,
     ) -> BoxFuture<'_, Result<()>> {
-        async { Ok(()) }.boxed()
+        let state = self.with_state_async(false, |state| {
+            Ok((
+                state.hook_output.clone(),
+                state.simulated_hook_failure.clone(),
+            ))
+        });
+        async move {
+            let (lines, failure) = state.await?;
+            for line in lines {
+                output.send(line).await.ok();
+            }
+            match failure {
+                Some(message) => bail!("{message}"),
+                None => Ok(()),
+            }
+        }
+        .boxed()
     }
 
     fn push(
         &self,
         _branch: String,
-        _remote_branch: String,
-        _remote: String,
-        _options: Option<PushOptions>,
+        remote_branch: String,
+        remote: String,
+        options: Option<PushOptions>,
+        _follow_tags: bool,
         _askpass: AskPassDelegate,
+        _progress: RemoteProgressDelegate,
         _env: Arc<HashMap<String, String>>,
         _cx: AsyncApp,
     ) -> BoxFuture<'_, Result<git::repository::RemoteCommandOutput>> {
-        unimplemented!()
+        self.with_state_async(true, move |state| {
+            state.pushes.push((remote_branch, remote, options));
+            if !state.simulated_push_errors.is_empty() {
+                bail!("{}", state.simulated_push_errors.remove(0));
+            }
+            Ok(git::repository::RemoteCommandOutput {
+                stdout: String::new(),
+                stderr: String::new(),
+            })
+        })
     }
 
     fn pull(
         &self,
         _branch: Option<String>,
-        _remote: String,
-        _rebase: bool,
+        remote: String,
+        strategy: Option<PullStrategy>,
         _askpass: AskPassDelegate,
+        _progress: RemoteProgressDelegate,
         _env: Arc<HashMap<String, String>>,
         _cx: AsyncApp,
     ) -> BoxFuture<'_, Result<git::repository::RemoteCommandOutput>> {
-        unimplemented!()
+        self.with_state_async(true, move |state| {
+            state.pulls.push((remote, strategy));
+            if !state.simulated_pull_errors.is_empty() {
+                bail!("{}", state.simulated_pull_errors.remove(0));
+            }
+            Ok(git::repository::RemoteCommandOutput {
+                stdout: String::new(),
+                stderr: String::new(),
+            })
+        })
     }
 
     fn fetch(
         &self,
-        _fetch_options: FetchOptions,
+        fetch_options: FetchOptions,
         _askpass: AskPassDelegate,
+        _progress: RemoteProgressDelegate,
         _env: Arc<HashMap<String, String>>,
         _cx: AsyncApp,
     ) -> BoxFuture<'_, Result<git::repository::RemoteCommandOutput>> {
-        unimplemented!()
+        self.with_state_async(false, move |state| {
+            state.fetches.push(fetch_options);
+            if let Some(message) = &state.simulated_fetch_error {
+                bail!("{message}");
+            }
+            Ok(git::repository::RemoteCommandOutput {
+                stdout: String::new(),
+                stderr: String::new(),
+            })
+        })
+    }
+
+    fn fetch_into_branch(
+        &self,
+        remote_name: String,
+        remote_ref: String,
+        branch_name: String,
+        _askpass: AskPassDelegate,
+        _progress: RemoteProgressDelegate,
+        _env: Arc<HashMap<String, String>>,
+        _cx: AsyncApp,
+    ) -> BoxFuture<'_, Result<git::repository::RemoteCommandOutput>> {
+        self.with_state_async(true, move |state| {
+            if let Some(message) = &state.simulated_fetch_error {
+                bail!("{message}");
+            }
+            if state.current_branch_name.as_ref() == Some(&branch_name) {
+                bail!("refusing to fetch into branch '{branch_name}' checked out");
+            }
+            state.branches.insert(branch_name.clone());
+            state
+                .branch_fetches
+                .push((remote_name, remote_ref, branch_name));
+            Ok(git::repository::RemoteCommandOutput {
+                stdout: String::new(),
+                stderr: String::new(),
+            })
+        })
+    }
+
+    fn lfs_tracked_paths(
+        &self,
+        paths: Vec<RepoPath>,
+    ) -> BoxFuture<'_, Result<std::collections::HashSet<RepoPath>>> {
+        self.with_state_async(false, move |state| {
+            Ok(paths
+                .into_iter()
+                .filter(|path| state.lfs_paths.contains(path))
+                .collect())
+        })
+    }
+
+    fn fetch_lfs_objects(
+        &self,
+        paths: Vec<RepoPath>,
+        _askpass: AskPassDelegate,
+        _progress: RemoteProgressDelegate,
+        _env: Arc<HashMap<String, String>>,
+        _cx: AsyncApp,
+    ) -> BoxFuture<'_, Result<git::repository::RemoteCommandOutput>> {
+        self.with_state_async(false, move |state| {
+            state.lfs_fetches.push(paths);
+            Ok(git::repository::RemoteCommandOutput {
+                stdout: String::new(),
+                stderr: String::new(),
+            })
+        })
+    }
+
+    fn submodules(&self) -> BoxFuture<'_, Result<Vec<Submodule>>> {
+        self.with_state_async(false, |state| Ok(state.submodules.clone()))
+    }
+
+    fn update_submodules(
+        &self,
+        paths: Vec<RepoPath>,
+        init: bool,
+        _askpass: AskPassDelegate,
+        _progress: RemoteProgressDelegate,
+        _env: Arc<HashMap<String, String>>,
+        _cx: AsyncApp,
+    ) -> BoxFuture<'_, Result<git::repository::RemoteCommandOutput>> {
+        self.with_state_async(true, move |state| {
+            for submodule in &mut state.submodules {
+                let selected = paths.is_empty() || paths.contains(&submodule.path);
+                if selected && (init || submodule.state != SubmoduleState::Uninitialized) {
+                    submodule.state = SubmoduleState::UpToDate;
+                }
+            }
+            state.submodule_updates.push((paths, init));
+            Ok(git::repository::RemoteCommandOutput {
+                stdout: String::new(),
+                stderr: String::new(),
+            })
+        })
+    }
+
+    fn sync_submodules(&self, paths: Vec<RepoPath>) -> BoxFuture<'_, Result<()>> {
+        self.with_state_async(false, move |state| {
+            state.submodule_syncs.push(paths);
+            Ok(())
+        })
+    }
+
+    fn sparse_checkout(&self) -> BoxFuture<'_, Result<Option<SparseCheckout>>> {
+        self.with_state_async(false, |state| Ok(state.sparse_checkout.clone()))
+    }
+
+    fn set_sparse_checkout(&self, cone: bool, patterns: Vec<String>) -> BoxFuture<'_, Result<()>> {
+        self.with_state_async(true, move |state| {
+            // Directories the new patterns check out stop being excluded.
+            let excluded_directories = state
+                .sparse_checkout
+                .take()
+                .map(|sparse_checkout| sparse_checkout.excluded_directories)
+                .unwrap_or_default()
+                .into_iter()
+                .filter(|directory| {
+                    !patterns.iter().any(|pattern| {
+                        RepoPath::new(pattern).is_ok_and(|pattern| directory.starts_with(&pattern))
+                    })
+                })
+                .collect();
+            state.sparse_checkout = Some(SparseCheckout {
+                cone,
+                patterns,
+                excluded_directories,
+            });
+            Ok(())
+        })
+    }
+
+    fn disable_sparse_checkout(&self) -> BoxFuture<'_, Result<()>> {
+        self.with_state_async(true, |state| {
+            state.sparse_checkout = None;
+            Ok(())
+        })
+    }
+
+    fn ignore_rule(&self, path: RepoPath) -> BoxFuture<'_, Result<Option<IgnoreRule>>> {
+        self.with_state_async(false, move |state| {
+            Ok(state.ignore_rules.get(&path).cloned())
+        })
+    }
+
+    fn renames(&self) -> BoxFuture<'_, Result<Vec<FileRename>>> {
+        self.with_state_async(false, |state| Ok(state.renames.clone()))
+    }
+
+    fn flagged_files(&self) -> BoxFuture<'_, Result<Vec<FlaggedFile>>> {
+        self.with_state_async(false, |state| Ok(state.flagged_files.clone()))
+    }
+
+    fn set_index_flag(
+        &self,
+        paths: Vec<RepoPath>,
+        flag: IndexFlag,
+        value: bool,
+    ) -> BoxFuture<'_, Result<()>> {
+        self.with_state_async(true, move |state| {
+            for path in paths {
+                let index = match state
+                    .flagged_files
+                    .iter()
+                    .position(|flagged_file| flagged_file.path == path)
+                {
+                    Some(index) => index,
+                    None => {
+                        state.flagged_files.push(FlaggedFile {
+                            path,
+                            assume_unchanged: false,
+                            skip_worktree: false,
+                        });
+                        state.flagged_files.len() - 1
+                    }
+                };
+                let flagged_file = &mut state.flagged_files[index];
+                flagged_file.set(flag, value);
+                if flagged_file.flags().next().is_none() {
+                    state.flagged_files.remove(index);
+                }
+            }
+            Ok(())
+        })
     }
 
     fn get_all_remotes(&self) -> BoxFuture<'_, Result<Vec<Remote>>> {
@@ -783,16 +1598,26 @@ impl GitRepository for FakeGitRepository {
         })
     }
 
-    fn get_push_remote(&self, _branch: String) -> BoxFuture<'_, Result<Option<Remote>>> {
-        unimplemented!()
+    fn get_push_remote(&self, branch: String) -> BoxFuture<'_, Result<Option<Remote>>> {
+        self.get_branch_remote(branch)
     }
 
-    fn get_branch_remote(&self, _branch: String) -> BoxFuture<'_, Result<Option<Remote>>> {
-        unimplemented!()
+    fn get_branch_remote(&self, branch: String) -> BoxFuture<'_, Result<Option<Remote>>> {
+        self.with_state_async(false, move |state| {
+            Ok(state
+                .branch_upstreams
+                .get(&branch)
+                .and_then(|upstream| upstream.split_once('/'))
+                .map(|(remote, _)| Remote {
+                    name: remote.to_string().into(),
+                }))
+        })
     }
 
     fn check_for_pushed_commit(&self) -> BoxFuture<'_, Result<Vec<gpui::SharedString>>> {
-        future::ready(Ok(Vec::new())).boxed()
+        self.with_state_async(false, |state| {
+            Ok(state.pushed_to.iter().cloned().map(Into::into).collect())
+        })
     }
 
     fn diff(&self, _diff: git::repository::DiffType) -> BoxFuture<'_, Result<String>> {
@@ -993,6 +1818,75 @@ impl GitRepository for FakeGitRepository {
                     .is_none_or(|(remote, _)| remote != name)
             });
             state.remotes.remove(&name);
+            state.remote_push_urls.remove(&name);
+            if state.default_remote.as_ref() == Some(&name) {
+                state.default_remote = None;
+            }
+            Ok(())
+        })
+    }
+
+    fn remote_infos(&self) -> BoxFuture<'_, Result<Vec<RemoteInfo>>> {
+        self.with_state_async(false, |state| {
+            let mut remotes = state
+                .remotes
+                .iter()
+                .map(|(name, url)| RemoteInfo {
+                    name: name.clone().into(),
+                    fetch_url: Some(url.clone().into()),
+                    push_url: state.remote_push_urls.get(name).cloned().map(Into::into),
+                    is_default: state.default_remote.as_ref() == Some(name),
+                })
+                .collect::<Vec<_>>();
+            remotes.sort_by(|a, b| a.name.cmp(&b.name));
+            Ok(remotes)
+        })
+    }
+
+    fn rename_remote(&self, old_name: String, new_name: String) -> BoxFuture<'_, Result<()>> {
+        self.with_state_async(true, move |state| {
+            if state.remotes.contains_key(&new_name) {
+                bail!("remote {new_name} already exists");
+            }
+            let url = state
+                .remotes
+                .remove(&old_name)
+                .with_context(|| format!("no such remote: {old_name}"))?;
+            state.remotes.insert(new_name.clone(), url);
+            if let Some(push_url) = state.remote_push_urls.remove(&old_name) {
+                state.remote_push_urls.insert(new_name.clone(), push_url);
+            }
+            if state.default_remote.as_ref() == Some(&old_name) {
+                state.default_remote = Some(new_name.clone());
+            }
+            state.branches = state
+                .branches
+                .drain()
+                .map(|branch| match branch.split_once('/') {
+                    Some((remote, rest)) if remote == old_name => format!("{new_name}/{rest}"),
+                    _ => branch,
+                })
+                .collect();
+            Ok(())
+        })
+    }
+
+    fn set_remote_push_url(&self, name: String, url: Option<String>) -> BoxFuture<'_, Result<()>> {
+        self.with_state_async(true, move |state| {
+            if !state.remotes.contains_key(&name) {
+                bail!("no such remote: {name}");
+            }
+            match url {
+                Some(url) => state.remote_push_urls.insert(name, url),
+                None => state.remote_push_urls.remove(&name),
+            };
+            Ok(())
+        })
+    }
+
+    fn set_default_remote(&self, name: Option<String>) -> BoxFuture<'_, Result<()>> {
+        self.with_state_async(true, move |state| {
+            state.default_remote = name;
             Ok(())
         })
     }
@@ -1021,6 +1915,178 @@ impl GitRepository for FakeGitRepository {
         anyhow::bail!("commit_data_reader not supported for FakeGitRepository")
     }
 
+    fn search_commits(
+        &self,
+        _log_source: LogSource,
+        query: String,
+    ) -> BoxFuture<'_, Result<Vec<Oid>>> {
+        self.with_state_async(false, move |state| {
+            let query = query.to_lowercase();
+            Ok(state
+                .commit_search_text
+                .iter()
+                .filter(|(_, text)| text.to_lowercase().contains(&query))
+                .map(|(oid, _)| *oid)
+                .collect())
+        })
+    }
+
+    fn commits_between(
+        &self,
+        base: String,
+        head: String,
+    ) -> BoxFuture<'_, Result<Vec<CommitSummary>>> {
+        self.with_state_async(false, move |state| {
+            Ok(state
+                .commits_between
+                .get(&(base, head))
+                .cloned()
+                .unwrap_or_default())
+        })
+    }
+
+    fn rebase_todo(&self, _base: String) -> BoxFuture<'_, Result<Vec<RebaseTodoEntry>>> {
+        self.with_state_async(false, |state| Ok(state.rebase_todo.clone()))
+    }
+
+    fn interactive_rebase(
+        &self,
+        _base: String,
+        todo: Vec<RebaseTodoEntry>,
+        _env: Arc<HashMap<String, String>>,
+    ) -> BoxFuture<'_, Result<bool>> {
+        self.with_state_async(true, move |state| {
+            if state.in_progress_operation.is_some() {
+                bail!("an operation is already in progress");
+            }
+            let stops = todo.iter().any(|entry| entry.action == RebaseAction::Edit);
+            state.in_progress_operation = stops.then_some(InProgressOperation {
+                kind: InProgressOperationKind::Rebase,
+                progress: None,
+            });
+            state.rebased_todos.push(todo);
+            Ok(stops)
+        })
+    }
+
+    fn cherry_pick(
+        &self,
+        shas: Vec<String>,
+        record_origin: bool,
+        _env: Arc<HashMap<String, String>>,
+    ) -> BoxFuture<'_, Result<bool>> {
+        self.with_state_async(true, move |state| {
+            if state.in_progress_operation.is_some() {
+                bail!("an operation is already in progress");
+            }
+            let stops = shas
+                .iter()
+                .any(|sha| state.conflicting_cherry_picks.contains(sha));
+            state.in_progress_operation = stops.then_some(InProgressOperation {
+                kind: InProgressOperationKind::CherryPick,
+                progress: None,
+            });
+            state.cherry_picks.push((shas, record_origin));
+            Ok(stops)
+        })
+    }
+
+    fn revert_commit(
+        &self,
+        sha: String,
+        no_commit: bool,
+        _env: Arc<HashMap<String, String>>,
+    ) -> BoxFuture<'_, Result<Option<String>>> {
+        self.with_state_async(true, move |state| {
+            if state.in_progress_operation.is_some() {
+                bail!("an operation is already in progress");
+            }
+            let message = no_commit.then(|| format!("Revert {sha}\n\nThis reverts commit {sha}."));
+            state.reverted_commits.push((sha, no_commit));
+            Ok(message)
+        })
+    }
+
+    fn bisect_start(
+        &self,
+        bad: String,
+        good: String,
+        _env: Arc<HashMap<String, String>>,
+    ) -> BoxFuture<'_, Result<BisectStatus>> {
+        self.with_state_async(true, move |state| {
+            if state.bisect.is_some() {
+                bail!("already bisecting");
+            }
+            let position = |sha: &str| {
+                state
+                    .bisect_history
+                    .iter()
+                    .position(|commit| commit == sha)
+                    .with_context(|| format!("unknown revision {sha}"))
+            };
+            let (bad_index, good_index) = (position(&bad)?, position(&good)?);
+            if good_index <= bad_index {
+                bail!("{good} is not an ancestor of {bad}");
+            }
+            let bisect = FakeBisect {
+                suspects: state.bisect_history[bad_index..good_index].to_vec(),
+                ..FakeBisect::default()
+            };
+            let status = bisect.status()?;
+            state.bisect = Some(bisect);
+            Ok(status)
+        })
+    }
+
+    fn bisect_mark(
+        &self,
+        mark: BisectMark,
+        _env: Arc<HashMap<String, String>>,
+    ) -> BoxFuture<'_, Result<BisectStatus>> {
+        self.with_state_async(true, move |state| {
+            let bisect = state.bisect.as_mut().context("not bisecting")?;
+            let candidate = bisect.candidate().context("nothing left to test")?;
+            match mark {
+                BisectMark::Good => bisect.suspects.truncate(candidate),
+                BisectMark::Bad => {
+                    bisect.suspects.drain(..candidate);
+                }
+                BisectMark::Skip => {
+                    bisect.skipped.insert(bisect.suspects[candidate].clone());
+                }
+            }
+            bisect.marks.push(mark);
+            bisect.status()
+        })
+    }
+
+    fn bisect_reset(&self, _env: Arc<HashMap<String, String>>) -> BoxFuture<'_, Result<()>> {
+        self.with_state_async(true, |state| {
+            state.bisect.take().context("not bisecting")?;
+            Ok(())
+        })
+    }
+
+    fn in_progress_operation(&self) -> BoxFuture<'_, Option<InProgressOperation>> {
+        let operation = self.with_state_async(false, |state| Ok(state.in_progress_operation));
+        async move { operation.await.ok().flatten() }.boxed()
+    }
+
+    fn resolve_in_progress_operation(
+        &self,
+        kind: InProgressOperationKind,
+        _resolution: OperationResolution,
+        _env: Arc<HashMap<String, String>>,
+    ) -> BoxFuture<'_, Result<bool>> {
+        self.with_state_async(true, move |state| {
+            if state.in_progress_operation.map(|operation| operation.kind) != Some(kind) {
+                bail!("no {} in progress", kind.command());
+            }
+            state.in_progress_operation = None;
+            Ok(false)
+        })
+    }
+
     fn set_trusted(&self, trusted: bool) {
         self.is_trusted
             .store(trusted, std::sync::atomic::Ordering::Release);
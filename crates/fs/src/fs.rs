@@ -32,7 +32,8 @@ use std::mem::MaybeUninit;
 
 use async_tar::Archive;
 use futures::{AsyncRead, Stream, StreamExt, future::BoxFuture};
-use git::repository::{GitRepository, RealGitRepository};
+use git::remote_progress::RemoteProgressDelegate;
+use git::repository::{GitRepository, RealGitRepository, run_remote_command};
 use is_executable::IsExecutable;
 use rope::Rope;
 use serde::{Deserialize, Serialize};
@@ -153,7 +154,12 @@ pub trait Fs: Send + Sync {
     ) -> Result<Arc<dyn GitRepository>>;
     async fn git_init(&self, abs_work_directory: &Path, fallback_branch_name: String)
     -> Result<()>;
-    async fn git_clone(&self, repo_url: &str, abs_work_directory: &Path) -> Result<()>;
+    async fn git_clone(
+        &self,
+        repo_url: &str,
+        abs_work_directory: &Path,
+        progress: Option<RemoteProgressDelegate>,
+    ) -> Result<()>;
     fn is_fake(&self) -> bool;
     async fn is_case_sensitive(&self) -> bool;
     fn subscribe_to_jobs(&self) -> JobEventReceiver;
@@ -1154,7 +1160,12 @@ impl Fs for RealFs {
         Ok(())
     }
 
-    async fn git_clone(&self, repo_url: &str, abs_work_directory: &Path) -> Result<()> {
+    async fn git_clone(
+        &self,
+        repo_url: &str,
+        abs_work_directory: &Path,
+        progress: Option<RemoteProgressDelegate>,
+    ) -> Result<()> {
         let job_id = self.next_job_id.fetch_add(1, Ordering::SeqCst);
         let job_info = JobInfo {
             id: job_id,
@@ -1164,18 +1175,13 @@ impl Fs for RealFs {
 
         let _job_tracker = JobTracker::new(job_info, self.job_event_subscribers.clone());
 
-        let output = new_command("git")
+        let mut command = new_command("git");
+        command
             .current_dir(abs_work_directory)
-            .args(&["clone", repo_url])
-            .output()
-            .await?;
-
-        if !output.status.success() {
-            anyhow::bail!(
-                "git clone failed: {}",
-                String::from_utf8_lossy(&output.stderr)
-            );
-        }
+            .args(&["clone", "--progress", repo_url]);
+        run_remote_command(command, progress)
+            .await
+            .context("git clone failed")?;
 
         Ok(())
     }
@@ -2863,7 +2869,12 @@ impl Fs for FakeFs {
         self.create_dir(&abs_work_directory_path.join(".git")).await
     }
 
-    async fn git_clone(&self, _repo_url: &str, _abs_work_directory: &Path) -> Result<()> {
+    async fn git_clone(
+        &self,
+        _repo_url: &str,
+        _abs_work_directory: &Path,
+        _progress: Option<RemoteProgressDelegate>,
+    ) -> Result<()> {
         anyhow::bail!("Git clone is not supported in fake Fs")
     }
 
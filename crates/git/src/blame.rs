@@ -5,6 +5,7 @@ use anyhow::{Context as _, Result};
 use collections::{HashMap, HashSet};
 use futures::AsyncWriteExt;
 use serde::{Deserialize, Serialize};
+use smol::io::{AsyncBufReadExt, AsyncReadExt, BufReader};
 use std::ops::Range;
 use text::{LineEnding, Rope};
 use time::OffsetDateTime;
@@ -20,15 +21,92 @@ pub struct Blame {
     pub messages: HashMap<Oid, String>,
 }
 
+/// Which revisions and lines of a file to blame.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct BlameOptions {
+    /// Revisions for blame to look past, in addition to those in `blame.ignoreRevsFile`.
+    pub ignore_revs: Vec<String>,
+    /// The rows to blame, starting at 0, or `None` to blame the whole file. Blaming a few rows
+    /// of a long file is much faster than blaming all of it.
+    pub rows: Option<Range<u32>>,
+}
+
 impl Blame {
+    /// Blames the file with `content`. `has_ignore_revs_file` tells whether the repository
+    /// configures `blame.ignoreRevsFile`, which git applies by itself.
     pub(crate) async fn for_path(
         git: &GitBinary,
         path: &RepoPath,
         content: &Rope,
         line_ending: LineEnding,
+        options: &BlameOptions,
+        has_ignore_revs_file: bool,
+    ) -> Result<Self> {
+        let line_range = options
+            .rows
+            .as_ref()
+            .map(|rows| format!("{},{}", rows.start + 1, rows.end.max(rows.start + 1)));
+        let mut args = Vec::new();
+        if let Some(line_range) = &line_range {
+            args.extend(["-L", line_range.as_str()]);
+        }
+        // A revision that doesn't name a commit would make blame fail, so it is left out.
+        let mut ignore_revs = Vec::new();
+        for revision in &options.ignore_revs {
+            let commit = format!("{revision}^{{commit}}");
+            if git
+                .run(&["rev-parse", "--verify", "--quiet", &commit])
+                .await
+                .is_ok()
+            {
+                ignore_revs.push(revision.as_str());
+            }
+        }
+        args.extend(
+            ignore_revs
+                .iter()
+                .flat_map(|revision| ["--ignore-rev", revision]),
+        );
+        let output = run_git_blame(git, path, content, line_ending, &args).await?;
+        let mut blame = Self::from_output(git, &output).await?;
+
+        if !ignore_revs.is_empty() || has_ignore_revs_file {
+            // Git doesn't report which lines it blamed past an ignored revision, so only the
+            // lines that differ from the ones they're attributed to are blamed again, without
+            // ignoring any revision, to find the revisions that changed them.
+            let rows = rows_differing_from_blamed_lines(git, &blame.entries, content).await?;
+            if !rows.is_empty() {
+                let line_ranges = rows
+                    .iter()
+                    .map(|rows| format!("{},{}", rows.start + 1, rows.end))
+                    .collect::<Vec<_>>();
+                let mut args = vec!["--ignore-revs-file", ""];
+                for line_range in &line_ranges {
+                    args.extend(["-L", line_range.as_str()]);
+                }
+                let output = run_git_blame(git, path, content, line_ending, &args).await?;
+                let unignored_entries = parse_git_blame(&output)?;
+                blame.entries = mark_ignored_revisions(blame.entries, &unignored_entries);
+            }
+        }
+        Ok(blame)
+    }
+
+    /// Blames `path` as it was at `revision`, rather than as it is in the working tree.
+    pub(crate) async fn at_revision(
+        git: &GitBinary,
+        revision: &str,
+        path: &RepoPath,
     ) -> Result<Self> {
-        let output = run_git_blame(git, path, content, line_ending).await?;
-        let mut entries = parse_git_blame(&output)?;
+        let output = git
+            .run(&["blame", "--incremental", revision, "--", path.as_unix_str()])
+            .await
+            .with_context(|| format!("blaming {} at {revision}", path.as_unix_str()))?;
+        Self::from_output(git, &output).await
+    }
+
+    async fn from_output(git: &GitBinary, output: &str) -> Result<Self> {
+        let mut entries = parse_git_blame(output)?;
         entries.sort_unstable_by(|a, b| a.range.start.cmp(&b.range.start));
 
         let mut unique_shas = HashSet::default();
@@ -46,6 +124,95 @@ impl Blame {
     }
 }
 
+/// Finds the rows whose text differs from the line of the commit that blame attributes them to,
+/// which blame only does when it looked past an ignored revision that changed them.
+async fn rows_differing_from_blamed_lines(
+    git: &GitBinary,
+    entries: &[BlameEntry],
+    content: &Rope,
+) -> Result<Vec<Range<u32>>> {
+    let mut cat_file_process = git
+        .build_command(&["--no-optional-locks", "cat-file", "--batch"])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .context("starting git cat-file process")?;
+    let mut stdin = cat_file_process
+        .stdin
+        .take()
+        .context("no stdin for git cat-file")?;
+    let mut stdout = BufReader::new(
+        cat_file_process
+            .stdout
+            .take()
+            .context("no stdout for git cat-file")?,
+    );
+
+    let text = content.to_string();
+    let lines = text.lines().collect::<Vec<_>>();
+    let mut blob_lines = HashMap::<(Oid, &str), Option<Vec<String>>>::default();
+    let mut rows = Vec::<Range<u32>>::new();
+    for entry in entries {
+        // Uncommitted lines aren't attributed to any commit.
+        if entry.sha.is_zero() {
+            continue;
+        }
+        let key = (entry.sha, entry.filename.as_str());
+        if !blob_lines.contains_key(&key) {
+            stdin
+                .write_all(format!("{}:{}\n", entry.sha, entry.filename).as_bytes())
+                .await?;
+            stdin.flush().await?;
+            let mut header = String::new();
+            stdout.read_line(&mut header).await?;
+            let blob = if header.trim_end().ends_with(" missing") {
+                None
+            } else {
+                let size = header
+                    .split_whitespace()
+                    .nth(2)
+                    .and_then(|size| size.parse::<usize>().ok())
+                    .with_context(|| format!("invalid cat-file header: {header}"))?;
+                // The object is followed by a newline.
+                let mut bytes = vec![0; size + 1];
+                stdout.read_exact(&mut bytes).await?;
+                bytes.pop();
+                Some(
+                    String::from_utf8_lossy(&bytes)
+                        .lines()
+                        .map(str::to_string)
+                        .collect(),
+                )
+            };
+            blob_lines.insert(key, blob);
+        }
+        let Some(Some(blob)) = blob_lines.get(&key) else {
+            continue;
+        };
+
+        for row in entry.range.clone() {
+            let original_row = (entry.original_line_number + row - entry.range.start) as usize;
+            let current_line = lines.get(row as usize).copied();
+            let original_line = original_row
+                .checked_sub(1)
+                .and_then(|original_row| blob.get(original_row))
+                .map(String::as_str);
+            if current_line == original_line {
+                continue;
+            }
+            match rows.last_mut() {
+                Some(last) if last.end == row => last.end += 1,
+                _ => rows.push(row..row + 1),
+            }
+        }
+    }
+
+    drop(stdin);
+    cat_file_process.kill().ok();
+    Ok(rows)
+}
+
 const GIT_BLAME_NO_COMMIT_ERROR: &str = "fatal: no such ref: HEAD";
 const GIT_BLAME_NO_PATH: &str = "fatal: no such path";
 
@@ -54,11 +221,13 @@ async fn run_git_blame(
     path: &RepoPath,
     contents: &Rope,
     line_ending: LineEnding,
+    extra_args: &[&str],
 ) -> Result<String> {
     let mut child = {
         let span = ztracing::debug_span!("spawning git-blame command", path = path.as_unix_str());
         let _enter = span.enter();
         git.build_command(&["blame", "--incremental", "--contents", "-"])
+            .args(extra_args)
             .arg(path.as_unix_str())
             .stdin(Stdio::piped())
             .stdout(Stdio::piped())
@@ -113,6 +282,11 @@ pub struct BlameEntry {
 
     pub previous: Option<String>,
     pub filename: String,
+
+    /// The ignored revision that last changed these lines, which blame looked past to find the
+    /// commit they're attributed to.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ignored_revision: Option<Oid>,
 }
 
 impl BlameEntry {
@@ -154,6 +328,13 @@ impl BlameEntry {
         })
     }
 
+    /// The parent of the blamed commit and the path the lines had there, which is where to look
+    /// for the lines as they were before this commit. `None` when the commit added the lines.
+    pub fn previous_revision(&self) -> Option<(&str, RepoPath)> {
+        let (sha, path) = self.previous.as_deref()?.split_once(' ')?;
+        Some((sha, RepoPath::new(path).ok()?))
+    }
+
     pub fn author_offset_date_time(&self) -> Result<time::OffsetDateTime> {
         if let (Some(author_time), Some(author_tz)) = (self.author_time, &self.author_tz) {
             let format = format_description!("[offset_hour][offset_minute]");
@@ -168,6 +349,46 @@ impl BlameEntry {
     }
 }
 
+/// Splits the entries into runs of lines that blame did and didn't attribute past an ignored
+/// revision, going by which commit `unignored_entries` blames each line on.
+fn mark_ignored_revisions(
+    entries: Vec<BlameEntry>,
+    unignored_entries: &[BlameEntry],
+) -> Vec<BlameEntry> {
+    let mut unignored_shas = HashMap::default();
+    for entry in unignored_entries {
+        for line in entry.range.clone() {
+            unignored_shas.insert(line, entry.sha);
+        }
+    }
+
+    let mut marked_entries = Vec::with_capacity(entries.len());
+    for entry in entries {
+        let ignored_revision_at = |line: u32| {
+            unignored_shas
+                .get(&line)
+                .copied()
+                .filter(|sha| *sha != entry.sha)
+        };
+        let mut start = entry.range.start;
+        while start < entry.range.end {
+            let ignored_revision = ignored_revision_at(start);
+            let mut end = start + 1;
+            while end < entry.range.end && ignored_revision_at(end) == ignored_revision {
+                end += 1;
+            }
+            marked_entries.push(BlameEntry {
+                range: start..end,
+                original_line_number: entry.original_line_number + (start - entry.range.start),
+                ignored_revision,
+                ..entry.clone()
+            });
+            start = end;
+        }
+    }
+    marked_entries
+}
+
 // parse_git_blame parses the output of `git blame --incremental`, which returns
 // all the blame-entries for a given path incrementally, as it finds them.
 //
@@ -291,7 +512,9 @@ mod tests {
     use std::path::PathBuf;
 
     use super::BlameEntry;
-    use super::parse_git_blame;
+    use super::{mark_ignored_revisions, parse_git_blame};
+    use crate::Oid;
+    use crate::repository::RepoPath;
 
     fn read_test_data(filename: &str) -> String {
         let mut path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
@@ -345,6 +568,55 @@ mod tests {
         assert_eq_golden(&entries, "blame_incremental_simple");
     }
 
+    #[test]
+    fn test_previous_revision() {
+        let entry = BlameEntry {
+            previous: Some("486c2409237a2c627230589e567024a96751d475 src/old name.js".into()),
+            ..Default::default()
+        };
+        assert_eq!(
+            entry.previous_revision(),
+            Some((
+                "486c2409237a2c627230589e567024a96751d475",
+                RepoPath::new("src/old name.js").unwrap()
+            ))
+        );
+        assert_eq!(BlameEntry::default().previous_revision(), None);
+    }
+
+    #[test]
+    fn test_mark_ignored_revisions() {
+        let formatting = Oid::from_bytes(&[1; 20]).unwrap();
+        let feature = Oid::from_bytes(&[2; 20]).unwrap();
+        let fix = Oid::from_bytes(&[3; 20]).unwrap();
+        let entry = |sha, range, original_line_number| BlameEntry {
+            sha,
+            range,
+            original_line_number,
+            ..Default::default()
+        };
+
+        let entries = vec![entry(feature, 0..4, 10), entry(fix, 4..5, 1)];
+        let unignored_entries = vec![
+            entry(feature, 0..1, 10),
+            entry(formatting, 1..3, 2),
+            entry(feature, 3..4, 13),
+            entry(fix, 4..5, 1),
+        ];
+        assert_eq!(
+            mark_ignored_revisions(entries, &unignored_entries),
+            [
+                entry(feature, 0..1, 10),
+                BlameEntry {
+                    ignored_revision: Some(formatting),
+                    ..entry(feature, 1..3, 11)
+                },
+                entry(feature, 3..4, 13),
+                entry(fix, 4..5, 1),
+            ]
+        );
+    }
+
     #[test]
     fn test_parse_git_blame_complex() {
         let output = read_test_data("blame_incremental_complex");
@@ -1,11 +1,13 @@
 use crate::{
     BuildCommitPermalinkParams, GitHostingProviderRegistry, GitRemote, Oid, parse_git_remote_url,
-    repository::GitBinary, status::StatusCode,
+    repository::{CommitFileStatus, GitBinary, RepoPath},
+    status::StatusCode,
 };
 use anyhow::{Context as _, Result};
 use collections::HashMap;
 use gpui::SharedString;
 use std::sync::Arc;
+use util::rel_path::RelPath;
 
 #[derive(Clone, Debug, Default)]
 pub struct ParsedCommitMessage {
@@ -118,6 +120,68 @@ pub fn parse_git_diff_name_status(content: &str) -> impl Iterator<Item = (&str,
     })
 }
 
+/// What's known about a commit beyond its author and message: who committed it, what it builds
+/// on, what points at it and the files it changed.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct CommitOverview {
+    pub committer_name: SharedString,
+    pub committer_email: SharedString,
+    pub parents: Vec<SharedString>,
+    /// The branches and tags pointing at the commit.
+    pub refs: Vec<SharedString>,
+    /// The files the commit changed compared to its first parent.
+    pub files: Vec<(RepoPath, CommitFileStatus)>,
+}
+
+/// The `git show --no-patch` format that [`parse_commit_overview`] expects.
+pub const COMMIT_OVERVIEW_FORMAT: &str = "--format=%cn%x00%ce%x00%P%x00%D";
+
+/// Parses a commit's header, printed with [`COMMIT_OVERVIEW_FORMAT`], and the files it changed, as
+/// printed by `git show --name-status -z`.
+pub fn parse_commit_overview(header: &str, name_status: &str) -> Result<CommitOverview> {
+    let header = header.trim_end();
+    let mut fields = header.split('\0');
+    let mut field = |name: &str| {
+        fields
+            .next()
+            .with_context(|| format!("expected the {name} of a commit in {header:?}"))
+    };
+    let committer_name = field("committer name")?.to_string().into();
+    let committer_email = field("committer email")?.to_string().into();
+    let parents = field("parents")?
+        .split_whitespace()
+        .map(|parent| parent.to_string().into())
+        .collect();
+    let refs = field("refs")?
+        .split(", ")
+        .filter(|name| !name.is_empty())
+        .map(|name| {
+            name.strip_prefix("HEAD -> ")
+                .unwrap_or(name)
+                .to_string()
+                .into()
+        })
+        .collect();
+    let files = parse_git_diff_name_status(name_status)
+        .map(|(path, status)| {
+            let status = match status {
+                StatusCode::Added => CommitFileStatus::Added,
+                StatusCode::Deleted => CommitFileStatus::Deleted,
+                _ => CommitFileStatus::Modified,
+            };
+            // git-show outputs `/`-delimited paths even on Windows.
+            Ok((RepoPath::from_rel_path(RelPath::unix(path)?), status))
+        })
+        .collect::<Result<_>>()?;
+    Ok(CommitOverview {
+        committer_name,
+        committer_email,
+        parents,
+        refs,
+        files,
+    })
+}
+
 #[cfg(test)]
 mod tests {
 
@@ -159,4 +223,39 @@ mod tests {
             ]
         );
     }
+
+    #[test]
+    fn test_parse_commit_overview() {
+        let header =
+            "Jane Doe\0jane@example.com\x001b1b1b 0d0d0d\0HEAD -> main, tag: v1.0, origin/main\n";
+        let name_status = "M\0src/main.rs\0A\0src/lib.rs\0D\0README.md\0";
+        assert_eq!(
+            parse_commit_overview(header, name_status).unwrap(),
+            CommitOverview {
+                committer_name: "Jane Doe".into(),
+                committer_email: "jane@example.com".into(),
+                parents: vec!["1b1b1b".into(), "0d0d0d".into()],
+                refs: vec!["main".into(), "tag: v1.0".into(), "origin/main".into()],
+                files: vec![
+                    (
+                        RepoPath::new("src/main.rs").unwrap(),
+                        CommitFileStatus::Modified
+                    ),
+                    (
+                        RepoPath::new("src/lib.rs").unwrap(),
+                        CommitFileStatus::Added
+                    ),
+                    (
+                        RepoPath::new("README.md").unwrap(),
+                        CommitFileStatus::Deleted
+                    ),
+                ],
+            }
+        );
+
+        let overview = parse_commit_overview("Jane Doe\0jane@example.com\0\0", "").unwrap();
+        assert!(overview.parents.is_empty());
+        assert!(overview.refs.is_empty());
+        assert!(overview.files.is_empty());
+    }
 }
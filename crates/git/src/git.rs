@@ -1,10 +1,17 @@
 pub mod blame;
 pub mod commit;
+pub mod gitignore;
 mod hosting_provider;
+pub mod index_flags;
+pub mod lfs;
+pub mod line_history;
 mod remote;
+pub mod remote_progress;
 pub mod repository;
+pub mod sparse_checkout;
 pub mod stash;
 pub mod status;
+pub mod submodule;
 
 pub use crate::hosting_provider::*;
 pub use crate::remote::*;
@@ -37,18 +44,110 @@ actions!(
         StageAndNext,
         /// Unstages the current hunk and moves to the next one.
         UnstageAndNext,
+        /// Stages only the selected lines of the hunks under the selection.
+        StageSelectedLines,
         /// Restores the selected hunks to their original state.
         #[action(deprecated_aliases = ["editor::RevertSelectedHunks"])]
         Restore,
         /// Restores the selected hunks to their original state and moves to the
         /// next one.
         RestoreAndNext,
+        /// Brings back the working-tree changes discarded by the most recent hunk restore.
+        UndoDiscard,
         // per-file
         /// Shows git blame information for the current file.
         #[action(deprecated_aliases = ["editor::ToggleGitBlame"])]
         Blame,
         /// Shows the git history for the current file.
         FileHistory,
+        /// Shows the commits that changed the selected lines of the current file.
+        LineHistory,
+        /// Downloads the Git LFS object of the current file in place of its pointer.
+        FetchLfsObject,
+        /// Clones the submodules that aren't checked out yet.
+        InitSubmodules,
+        /// Checks out the commits the repository records for its submodules.
+        UpdateSubmodules,
+        /// Copies the submodule URLs from `.gitmodules` into the git config.
+        SyncSubmodules,
+        /// Compares the current file against another file.
+        CompareWithFile,
+        /// Compares the current file against its content at a git revision.
+        CompareWithRevision,
+        /// Restores the current file to its content at a commit, after previewing the diff.
+        RestoreFromRevision,
+        /// Compares two files picked one after the other.
+        CompareFiles,
+        /// Compares two branches picked one after the other, across all of the files that
+        /// differ between them.
+        CompareBranches,
+        /// Compares the selected folder at two revisions picked one after the other.
+        CompareDirectoryRevisions,
+        /// Compares the current branch with its upstream branch.
+        DiffAgainstUpstream,
+        /// Compares the current branch with where it branched off the default base branch.
+        DiffAgainstMergeBase,
+        /// Steps through the commits of a range such as main..feature, one diff at a time.
+        ReviewCommitRange,
+        /// Marks or unmarks the selected file in the comparison picker, so that a diff is opened
+        /// for each marked file.
+        ToggleComparisonMark,
+        /// Reopens the most recently opened file comparison.
+        RepeatLastComparison,
+        /// Compares the current file's unsaved contents against the file on disk.
+        CompareWithSaved,
+        /// Swaps the old and new sides of the active file diff.
+        SwapDiffSides,
+        /// Cycles which whitespace differences are ignored in the active diff: none, trailing, or all.
+        ToggleIgnoreWhitespace,
+        /// Replaces the selected hunks on the new side of the file diff with the old side's text.
+        CopyHunkToNewSide,
+        /// Replaces the selected hunks on the old side of the file diff with the new side's text.
+        CopyHunkToOldSide,
+        /// Copies the selected hunks of the file diff to the clipboard as a unified diff.
+        CopyHunkAsPatch,
+        /// Copies the whole file diff to the clipboard as a unified diff.
+        CopyDiffAsPatch,
+        /// Saves the whole file diff to a patch file.
+        SaveDiffAs,
+        /// Opens the active `.patch` or `.diff` file as a rendered diff.
+        ViewPatch,
+        /// Applies the patch shown in the active patch view to the files in the project.
+        ApplyPatch,
+        /// Compares a base file against two files derived from it, picked one after the other.
+        CompareThreeWay,
+        /// Resolves the change under the cursor in the three-way diff with the left side's text.
+        TakeLeftSide,
+        /// Resolves the change under the cursor in the three-way diff with the right side's text.
+        TakeRightSide,
+        /// Resolves the change under the cursor in the three-way diff with both sides' text.
+        TakeBothSides,
+        /// Opens the selected conflicted file in a three-way view to resolve its conflicts.
+        ResolveConflicts,
+        /// Moves the cursor to the next merge conflict in the editor.
+        GoToNextConflict,
+        /// Moves the cursor to the previous merge conflict in the editor.
+        GoToPreviousConflict,
+        /// Starts bisecting the history to find the commit that introduced a bug.
+        StartBisect,
+        /// Marks the commit being bisected as good.
+        BisectGood,
+        /// Marks the commit being bisected as bad.
+        BisectBad,
+        /// Skips the commit being bisected when it can't be tested.
+        BisectSkip,
+        /// Ends the bisect and checks out the branch it was started from.
+        ResetBisect,
+        /// Opens the reflog to restore the repository to an earlier state.
+        OpenReflog,
+        /// Opens the commit log of the repository, to browse and search its history.
+        OpenCommitLog,
+        /// Opens the tag picker to create, delete, push or check out tags.
+        ViewTags,
+        /// Opens the list of remotes to add, rename or remove them and change their URLs.
+        ManageRemotes,
+        /// Edits the patterns that limit which files of the repository are checked out.
+        ConfigureSparseCheckout,
         /// Stages the current file.
         StageFile,
         /// Unstages the current file.
@@ -60,6 +159,11 @@ actions!(
         UnstageAll,
         /// Stashes all changes in the repository, including untracked files.
         StashAll,
+        /// Stashes changes with a message, optionally leaving untracked files in place.
+        StashWithMessage,
+        /// Stashes the file selected in the git panel, along with the other marked files when it
+        /// is marked.
+        StashSelected,
         /// Pops the most recent stash.
         StashPop,
         /// Apply the most recent stash.
@@ -76,6 +180,10 @@ actions!(
         PushTo,
         /// Force pushes commits to the remote repository.
         ForcePush,
+        /// Pushes commits to a remote branch and makes it the upstream of the current branch.
+        PushSetUpstream,
+        /// Pushes commits along with the annotated tags that point at them.
+        PushWithTags,
         /// Pulls changes from the remote repository.
         Pull,
         /// Pulls changes from the remote repository with rebase.
@@ -84,10 +192,20 @@ actions!(
         Fetch,
         /// Fetches changes from a specific remote.
         FetchFrom,
+        /// Fetches, pulls and then pushes the current branch, stopping at the first step that fails.
+        Sync,
         /// Creates a new commit with staged changes.
         Commit,
         /// Amends the last commit with staged changes.
         Amend,
+        /// Commits only the files marked in the git panel, leaving anything else staged in the index.
+        CommitSelected,
+        /// Commits without running the pre-commit hook, after confirming. Requires the
+        /// `git.allow_no_verify` setting.
+        CommitNoVerify,
+        /// Picks a recent commit author or collaborator and adds a `Co-authored-by` trailer for them
+        /// to the commit message.
+        AddCoAuthor,
         /// Enable the --signoff option.
         Signoff,
         /// Cancels the current git operation.
@@ -96,14 +214,25 @@ actions!(
         ExpandCommitEditor,
         /// Generates a commit message using AI.
         GenerateCommitMessage,
+        /// Fills in a commit subject summarizing the staged changes, without using AI.
+        SuggestCommitMessage,
         /// Initializes a new git repository.
         Init,
         /// Opens all modified files in the editor.
         OpenModifiedFiles,
-        /// Clones a repository.
+        /// Clones a repository from a URL into a chosen directory and opens it.
         Clone,
-        /// Adds a file to .gitignore.
-        AddToGitignore,
+        /// Opens the .gitignore line whose pattern makes git ignore the current path.
+        ExplainIgnore,
+        /// Sets or clears the assume-unchanged flag on the selected file, which hides its local
+        /// changes until git needs to touch the file.
+        ToggleAssumeUnchanged,
+        /// Sets or clears the skip-worktree flag on the selected file, which hides its local
+        /// changes until the flag is cleared.
+        ToggleSkipWorktree,
+        /// Creates a branch at the current commit and switches to it, so that commits made on a
+        /// detached HEAD belong to a branch.
+        CreateBranchHere,
     ]
 );
 
@@ -119,6 +248,18 @@ pub struct RenameBranch {
     pub branch: Option<String>,
 }
 
+/// Opens an editor for interactively rebasing the commits after a base commit.
+#[derive(Clone, Debug, Default, PartialEq, Deserialize, JsonSchema, Action)]
+#[action(namespace = git)]
+#[serde(deny_unknown_fields)]
+pub struct InteractiveRebase {
+    /// The commit or branch to rebase onto.
+    ///
+    /// Default: the upstream of the current branch.
+    #[serde(default)]
+    pub base: Option<String>,
+}
+
 /// Restores a file to its last committed state, discarding local changes.
 #[derive(Clone, Debug, Default, PartialEq, Deserialize, JsonSchema, Action)]
 #[action(namespace = git, deprecated_aliases = ["editor::RevertFile"])]
@@ -128,6 +269,18 @@ pub struct RestoreFile {
     pub skip_prompt: bool,
 }
 
+/// Adds an entry ignoring the selected untracked path to the nearest .gitignore.
+#[derive(Clone, Debug, Default, PartialEq, Deserialize, JsonSchema, Action)]
+#[action(namespace = git)]
+#[serde(deny_unknown_fields)]
+pub struct AddToGitignore {
+    /// Which files the entry ignores.
+    ///
+    /// Default: exact_path
+    #[serde(default)]
+    pub pattern: gitignore::GitignorePatternKind,
+}
+
 /// The length of a Git short SHA.
 pub const SHORT_SHA_LENGTH: usize = 7;
 
@@ -0,0 +1,192 @@
+use std::path::PathBuf;
+
+use schemars::JsonSchema;
+use serde::Deserialize;
+use util::rel_path::RelPath;
+
+/// Which files the `.gitignore` entry added for a path matches.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum GitignorePatternKind {
+    /// Only the path itself.
+    #[default]
+    ExactPath,
+    /// Every file with the same extension as the path.
+    Extension,
+    /// The directory the path is in, or the path itself when it's a directory.
+    Directory,
+}
+
+/// The entry to add to the `.gitignore` in `gitignore_directory` so that it ignores `path`, both
+/// relative to the root of the repository. Returns `None` when the kind doesn't apply to the
+/// path, such as an extension pattern for a file without an extension.
+pub fn gitignore_pattern(
+    path: &RelPath,
+    is_dir: bool,
+    gitignore_directory: &RelPath,
+    kind: GitignorePatternKind,
+) -> Option<String> {
+    let path = path.strip_prefix(gitignore_directory).ok()?;
+    match kind {
+        GitignorePatternKind::ExactPath => {
+            let suffix = if is_dir { "/" } else { "" };
+            Some(format!("/{}{suffix}", escape_glob(path.as_unix_str())))
+        }
+        GitignorePatternKind::Extension => {
+            if is_dir {
+                return None;
+            }
+            let extension = path.extension()?;
+            Some(format!("*.{}", escape_glob(extension)))
+        }
+        GitignorePatternKind::Directory => {
+            let directory = if is_dir { path } else { path.parent()? };
+            if directory.is_empty() {
+                return None;
+            }
+            Some(format!("/{}/", escape_glob(directory.as_unix_str())))
+        }
+    }
+}
+
+/// Escapes the characters gitignore patterns treat as wildcards, and the trailing spaces git
+/// strips from patterns, so that they match themselves.
+fn escape_glob(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    let trailing_spaces_start = text.trim_end_matches(' ').len();
+    for (index, character) in text.char_indices() {
+        if matches!(character, '*' | '?' | '[' | '\\') || index >= trailing_spaces_start {
+            escaped.push('\\');
+        }
+        escaped.push(character);
+    }
+    escaped
+}
+
+/// The pattern that makes git ignore a path, as `git check-ignore -v` reports it.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct IgnoreRule {
+    /// The file the pattern is in, relative to the working directory unless it's outside of it,
+    /// like the global excludes file.
+    pub source: PathBuf,
+    /// The line of the pattern in the source, starting at 1.
+    pub line: u32,
+    pub pattern: String,
+}
+
+/// Parses the output of `git check-ignore -v -z` for a single path, whose fields are the source,
+/// line and pattern that matched, then the path. A negated pattern matching means the path isn't
+/// ignored, so it's not reported.
+pub fn parse_check_ignore(output: &str) -> Option<IgnoreRule> {
+    let mut fields = output.split('\0');
+    let source = fields.next().filter(|source| !source.is_empty())?;
+    let line = fields.next()?.parse().ok()?;
+    let pattern = fields.next()?;
+    if pattern.starts_with('!') {
+        return None;
+    }
+    Some(IgnoreRule {
+        source: PathBuf::from(source),
+        line,
+        pattern: pattern.to_string(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_gitignore_pattern() {
+        let path = RelPath::unix("crates/app/build/out[1].log").unwrap();
+        assert_eq!(
+            gitignore_pattern(
+                path,
+                false,
+                RelPath::empty(),
+                GitignorePatternKind::ExactPath
+            ),
+            Some("/crates/app/build/out\\[1].log".to_string())
+        );
+        assert_eq!(
+            gitignore_pattern(
+                path,
+                false,
+                RelPath::unix("crates/app").unwrap(),
+                GitignorePatternKind::ExactPath
+            ),
+            Some("/build/out\\[1].log".to_string())
+        );
+        assert_eq!(
+            gitignore_pattern(
+                path,
+                false,
+                RelPath::unix("crates/app").unwrap(),
+                GitignorePatternKind::Extension
+            ),
+            Some("*.log".to_string())
+        );
+        assert_eq!(
+            gitignore_pattern(
+                path,
+                false,
+                RelPath::unix("crates/app").unwrap(),
+                GitignorePatternKind::Directory
+            ),
+            Some("/build/".to_string())
+        );
+        assert_eq!(
+            gitignore_pattern(
+                RelPath::unix("target").unwrap(),
+                true,
+                RelPath::empty(),
+                GitignorePatternKind::Directory
+            ),
+            Some("/target/".to_string())
+        );
+        assert_eq!(
+            gitignore_pattern(
+                RelPath::unix("notes/draft  ").unwrap(),
+                false,
+                RelPath::empty(),
+                GitignorePatternKind::ExactPath
+            ),
+            Some("/notes/draft\\ \\ ".to_string())
+        );
+        assert_eq!(
+            gitignore_pattern(
+                RelPath::unix("Makefile").unwrap(),
+                false,
+                RelPath::empty(),
+                GitignorePatternKind::Extension
+            ),
+            None
+        );
+        assert_eq!(
+            gitignore_pattern(
+                RelPath::unix("Makefile").unwrap(),
+                false,
+                RelPath::empty(),
+                GitignorePatternKind::Directory
+            ),
+            None
+        );
+    }
+
+    #[test]
+    fn test_parse_check_ignore() {
+        assert_eq!(
+            parse_check_ignore("crates/app/.gitignore\x003\0*.log\0crates/app/out.log\0"),
+            Some(IgnoreRule {
+                source: PathBuf::from("crates/app/.gitignore"),
+                line: 3,
+                pattern: "*.log".to_string(),
+            })
+        );
+        assert_eq!(
+            parse_check_ignore(".gitignore\x002\0!keep.log\0keep.log\0"),
+            None
+        );
+        assert_eq!(parse_check_ignore(""), None);
+    }
+}
@@ -17,6 +17,75 @@ pub struct PullRequest {
     pub url: Url,
 }
 
+/// The progress of a check, such as a CI job, run against a pull request.
+///
+/// Ordered by how much attention it needs, so that the status of several checks is their maximum.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum CheckStatus {
+    Success,
+    Pending,
+    Failure,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct PullRequestCheck {
+    pub name: SharedString,
+    pub status: CheckStatus,
+    pub url: Option<Url>,
+}
+
+/// Where the reviews of a pull request stand, going by the latest review of each reviewer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReviewState {
+    Approved,
+    ChangesRequested,
+}
+
+/// The checks and reviews of an open pull request.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PullRequestStatus {
+    pub number: u32,
+    pub title: SharedString,
+    pub url: Url,
+    pub checks: Vec<PullRequestCheck>,
+    /// `None` while no reviewer has approved or requested changes.
+    pub review: Option<ReviewState>,
+}
+
+impl PullRequestStatus {
+    /// The status of all checks together, or `None` when there are no checks.
+    pub fn checks_status(&self) -> Option<CheckStatus> {
+        self.checks.iter().map(|check| check.status).max()
+    }
+
+    pub fn failing_checks(&self) -> impl Iterator<Item = &PullRequestCheck> {
+        self.checks
+            .iter()
+            .filter(|check| check.status == CheckStatus::Failure)
+    }
+}
+
+/// An open pull request, as listed by a hosting provider.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PullRequestSummary {
+    pub number: u32,
+    pub title: SharedString,
+    pub author: SharedString,
+    /// The branch the pull request was opened from, prefixed with its owner when it lives on a
+    /// fork.
+    pub source_branch: SharedString,
+    pub url: Url,
+}
+
+/// An issue, or a pull request, that commit messages can reference by its number as in `#123`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Issue {
+    pub number: u32,
+    pub title: SharedString,
+    pub url: Url,
+    pub is_pull_request: bool,
+}
+
 #[derive(Clone)]
 pub struct GitRemote {
     pub host: Arc<dyn GitHostingProvider + Send + Sync + 'static>,
@@ -53,6 +122,17 @@ impl GitRemote {
     }
 }
 
+/// A pull request to open through the API of a hosting provider.
+#[derive(Debug, Clone)]
+pub struct CreatePullRequestParams {
+    /// The branch on the remote whose changes are to be merged.
+    pub source_branch: String,
+    /// The branch the changes are to be merged into.
+    pub target_branch: String,
+    pub title: String,
+    pub body: String,
+}
+
 pub struct BuildCommitPermalinkParams<'a> {
     pub sha: &'a str,
 }
@@ -102,6 +182,77 @@ pub trait GitHostingProvider {
         None
     }
 
+    /// Returns whether [`GitHostingProvider::create_pull_request`] can be used, which requires
+    /// credentials for the provider's API.
+    fn can_create_pull_requests(&self) -> bool {
+        false
+    }
+
+    /// Creates a pull request through the API of this hosting provider.
+    async fn create_pull_request(
+        &self,
+        _remote: &ParsedGitRemote,
+        _params: CreatePullRequestParams,
+        _http_client: Arc<dyn HttpClient>,
+    ) -> Result<PullRequest> {
+        anyhow::bail!("{} does not support creating pull requests", self.name())
+    }
+
+    /// Returns the status of the open pull request against `remote` from the given branch, if
+    /// there is one. `head_owner` owns the repository the branch lives on, which is a fork of
+    /// `remote` when the pull request comes from one.
+    async fn pull_request_status(
+        &self,
+        _remote: &ParsedGitRemote,
+        _head_owner: &str,
+        _source_branch: &str,
+        _http_client: Arc<dyn HttpClient>,
+    ) -> Result<Option<PullRequestStatus>> {
+        Ok(None)
+    }
+
+    /// Returns the open pull requests of the repository, most recently created first.
+    async fn list_pull_requests(
+        &self,
+        _remote: &ParsedGitRemote,
+        _http_client: Arc<dyn HttpClient>,
+    ) -> Result<Vec<PullRequestSummary>> {
+        anyhow::bail!("{} does not support listing pull requests", self.name())
+    }
+
+    /// Returns the ref of the repository that points at the head of the given pull request.
+    ///
+    /// The ref lives on the repository the pull request was opened against, so fetching it works
+    /// the same for pull requests opened from forks.
+    fn pull_request_head_ref(&self, _number: u32) -> Option<String> {
+        None
+    }
+
+    /// Returns the issues of the repository that `#` references in commit messages can point
+    /// to, most recently updated first.
+    async fn list_issues(
+        &self,
+        _remote: &ParsedGitRemote,
+        _http_client: Arc<dyn HttpClient>,
+    ) -> Result<Vec<Issue>> {
+        Ok(Vec::new())
+    }
+
+    /// Returns the issue that a `#` reference with the given number points to, if there is one.
+    async fn issue(
+        &self,
+        _remote: &ParsedGitRemote,
+        _number: u32,
+        _http_client: Arc<dyn HttpClient>,
+    ) -> Result<Option<Issue>> {
+        Ok(None)
+    }
+
+    /// Returns a URL to browse the given branch on this hosting provider.
+    fn build_branch_url(&self, _remote: &ParsedGitRemote, _branch: &str) -> Option<Url> {
+        None
+    }
+
     /// Returns whether this provider supports avatars.
     fn supports_avatars(&self) -> bool;
 
@@ -231,7 +382,7 @@ impl GitHostingProviderRegistry {
     }
 }
 
-#[derive(Debug, PartialEq)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct ParsedGitRemote {
     pub owner: Arc<str>,
     pub repo: Arc<str>,
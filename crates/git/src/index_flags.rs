@@ -0,0 +1,120 @@
+use anyhow::{Context as _, Result};
+use util::rel_path::RelPath;
+
+use crate::repository::RepoPath;
+
+/// A bit on an index entry that makes git stop looking at the file in the working tree, which
+/// leaves the file's local changes out of the status.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum IndexFlag {
+    /// Meant for files that are slow to check, and dropped when git needs to touch the file.
+    AssumeUnchanged,
+    /// Meant for files changed on purpose, such as local configuration, and kept until cleared.
+    SkipWorktree,
+}
+
+impl IndexFlag {
+    /// The `git update-index` option that sets or clears the flag.
+    pub fn update_index_option(self, value: bool) -> &'static str {
+        match (self, value) {
+            (IndexFlag::AssumeUnchanged, true) => "--assume-unchanged",
+            (IndexFlag::AssumeUnchanged, false) => "--no-assume-unchanged",
+            (IndexFlag::SkipWorktree, true) => "--skip-worktree",
+            (IndexFlag::SkipWorktree, false) => "--no-skip-worktree",
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            IndexFlag::AssumeUnchanged => "Assume Unchanged",
+            IndexFlag::SkipWorktree => "Skip Worktree",
+        }
+    }
+}
+
+/// A file in the index with at least one of the flags set.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct FlaggedFile {
+    pub path: RepoPath,
+    pub assume_unchanged: bool,
+    pub skip_worktree: bool,
+}
+
+impl FlaggedFile {
+    pub fn has(&self, flag: IndexFlag) -> bool {
+        match flag {
+            IndexFlag::AssumeUnchanged => self.assume_unchanged,
+            IndexFlag::SkipWorktree => self.skip_worktree,
+        }
+    }
+
+    pub fn set(&mut self, flag: IndexFlag, value: bool) {
+        match flag {
+            IndexFlag::AssumeUnchanged => self.assume_unchanged = value,
+            IndexFlag::SkipWorktree => self.skip_worktree = value,
+        }
+    }
+
+    /// The flags that are set, assume-unchanged first.
+    pub fn flags(&self) -> impl Iterator<Item = IndexFlag> {
+        [IndexFlag::AssumeUnchanged, IndexFlag::SkipWorktree]
+            .into_iter()
+            .filter(|flag| self.has(*flag))
+    }
+}
+
+/// Parses the flagged files out of `git ls-files -v -z`, which tags every file with a letter that
+/// is `S` for skip-worktree and lowercase for assume-unchanged:
+///
+/// ```text
+/// H README.md\0h slow.bin\0S config.local\0
+/// ```
+pub fn parse_flagged_files(output: &str) -> Result<Vec<FlaggedFile>> {
+    let mut flagged_files = Vec::new();
+    for entry in output.split('\0').filter(|entry| !entry.is_empty()) {
+        let (tag, path) = entry
+            .split_once(' ')
+            .with_context(|| format!("expected a tag and a path in {entry:?}"))?;
+        let assume_unchanged = tag.chars().all(|tag| tag.is_ascii_lowercase());
+        let skip_worktree = tag.eq_ignore_ascii_case("s");
+        if assume_unchanged || skip_worktree {
+            flagged_files.push(FlaggedFile {
+                path: RepoPath::from_rel_path(RelPath::unix(path)?),
+                assume_unchanged,
+                skip_worktree,
+            });
+        }
+    }
+    Ok(flagged_files)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_flagged_files() {
+        let output = "H README.md\0h slow.bin\0S config/local.toml\0s both.txt\0";
+        assert_eq!(
+            parse_flagged_files(output).unwrap(),
+            [
+                FlaggedFile {
+                    path: RepoPath::new("slow.bin").unwrap(),
+                    assume_unchanged: true,
+                    skip_worktree: false,
+                },
+                FlaggedFile {
+                    path: RepoPath::new("config/local.toml").unwrap(),
+                    assume_unchanged: false,
+                    skip_worktree: true,
+                },
+                FlaggedFile {
+                    path: RepoPath::new("both.txt").unwrap(),
+                    assume_unchanged: true,
+                    skip_worktree: true,
+                },
+            ]
+        );
+        assert_eq!(parse_flagged_files("").unwrap(), []);
+    }
+}
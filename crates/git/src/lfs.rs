@@ -0,0 +1,105 @@
+use std::collections::HashSet;
+
+use gpui::SharedString;
+
+use crate::repository::RepoPath;
+
+/// The first line of every Git LFS pointer file.
+pub const LFS_POINTER_VERSION: &str = "version https://git-lfs.github.com/spec/v1";
+
+/// Pointer files are small by design, so anything larger is real content.
+pub const MAX_LFS_POINTER_LEN: usize = 1024;
+
+/// What git-lfs leaves in the working tree in place of a file whose object hasn't been downloaded
+/// yet, such as after cloning with `GIT_LFS_SKIP_SMUDGE=1`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct LfsPointer {
+    /// The object's id, such as `sha256:4d7a…`.
+    pub oid: SharedString,
+    /// The size of the object in bytes.
+    pub size: u64,
+}
+
+impl LfsPointer {
+    /// Parses the contents of a pointer file, returning `None` for any other content.
+    pub fn parse(text: &str) -> Option<Self> {
+        if text.len() > MAX_LFS_POINTER_LEN {
+            return None;
+        }
+        let mut lines = text.lines();
+        if lines.next()?.trim_end() != LFS_POINTER_VERSION {
+            return None;
+        }
+
+        let mut oid = None;
+        let mut size = None;
+        for line in lines {
+            let Some((key, value)) = line.split_once(' ') else {
+                continue;
+            };
+            match key {
+                "oid" => oid = Some(value.trim()),
+                "size" => size = value.trim().parse().ok(),
+                _ => {}
+            }
+        }
+
+        Some(Self {
+            oid: oid?.to_string().into(),
+            size: size?,
+        })
+    }
+}
+
+/// Parses the output of `git check-attr -z filter -- <paths>` into the paths git-lfs filters.
+pub fn parse_lfs_tracked_paths(output: &str) -> HashSet<RepoPath> {
+    let mut fields = output.split('\0');
+    let mut paths = HashSet::default();
+    while let (Some(path), Some(_attribute), Some(value)) =
+        (fields.next(), fields.next(), fields.next())
+    {
+        if value == "lfs"
+            && let Ok(path) = RepoPath::new(path)
+        {
+            paths.insert(path);
+        }
+    }
+    paths
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_lfs_pointer() {
+        assert_eq!(
+            LfsPointer::parse(
+                "version https://git-lfs.github.com/spec/v1\n\
+                 oid sha256:4d7a214614ab2935c943f9e0ff69d22eadbb8f32b1258daaa5e2ca24d17e2393\n\
+                 size 12345\n"
+            ),
+            Some(LfsPointer {
+                oid: "sha256:4d7a214614ab2935c943f9e0ff69d22eadbb8f32b1258daaa5e2ca24d17e2393"
+                    .into(),
+                size: 12345,
+            })
+        );
+        assert_eq!(
+            LfsPointer::parse("version https://git-lfs.github.com/spec/v1\nsize 12345\n"),
+            None
+        );
+        assert_eq!(LfsPointer::parse("fn main() {}\n"), None);
+    }
+
+    #[test]
+    fn test_parse_lfs_tracked_paths() {
+        let paths = parse_lfs_tracked_paths(
+            "assets/logo.png\0filter\0lfs\0src/main.rs\0filter\0unspecified\0",
+        );
+        assert_eq!(
+            paths,
+            HashSet::from_iter([RepoPath::new("assets/logo.png").unwrap()])
+        );
+    }
+}
@@ -0,0 +1,104 @@
+use anyhow::{Context as _, Result};
+use gpui::SharedString;
+
+/// A commit that changed some of the lines of a file, as `git log -L` reports it.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct LineHistoryEntry {
+    pub sha: SharedString,
+    pub subject: SharedString,
+    pub commit_timestamp: i64,
+    pub author_name: SharedString,
+    /// The commit's changes to the lines, as a unified diff of the file.
+    pub diff: String,
+}
+
+/// The `git log` format that [`parse_line_history`] expects, which starts every commit with a
+/// record separator so that commits split apart from the diffs that follow them.
+pub const LINE_HISTORY_FORMAT: &str = "--format=%x1e%H%x00%s%x00%at%x00%an";
+
+/// Parses the output of `git log -L` run with [`LINE_HISTORY_FORMAT`], newest commit first.
+pub fn parse_line_history(output: &str) -> Result<Vec<LineHistoryEntry>> {
+    output
+        .split('\x1e')
+        .filter(|record| !record.trim().is_empty())
+        .map(|record| {
+            let (header, diff) = record.split_once('\n').unwrap_or((record, ""));
+            let mut fields = header.split('\0');
+            let mut field = |name: &str| {
+                fields
+                    .next()
+                    .with_context(|| format!("expected the {name} of a commit in {header:?}"))
+            };
+            Ok(LineHistoryEntry {
+                sha: field("sha")?.to_string().into(),
+                subject: field("subject")?.to_string().into(),
+                commit_timestamp: field("timestamp")?
+                    .parse()
+                    .context("parsing commit timestamp")?,
+                author_name: field("author")?.to_string().into(),
+                diff: diff.trim_start_matches('\n').to_string(),
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_line_history() {
+        let output = concat!(
+            "\x1e3a3a3a\0Rename the greeting\x001709741400\0Joe Schmoe\n",
+            "\n",
+            "diff --git a/src/main.rs b/src/main.rs\n",
+            "--- a/src/main.rs\n",
+            "+++ b/src/main.rs\n",
+            "@@ -2,1 +2,1 @@\n",
+            "-    println!(\"hello\");\n",
+            "+    println!(\"hi\");\n",
+            "\x1e1b1b1b\0Add main\x001709740000\0Jane Doe\n",
+            "\n",
+            "diff --git a/src/main.rs b/src/main.rs\n",
+            "--- /dev/null\n",
+            "+++ b/src/main.rs\n",
+            "@@ -0,0 +2,1 @@\n",
+            "+    println!(\"hello\");\n",
+        );
+        assert_eq!(
+            parse_line_history(output).unwrap(),
+            [
+                LineHistoryEntry {
+                    sha: "3a3a3a".into(),
+                    subject: "Rename the greeting".into(),
+                    commit_timestamp: 1709741400,
+                    author_name: "Joe Schmoe".into(),
+                    diff: concat!(
+                        "diff --git a/src/main.rs b/src/main.rs\n",
+                        "--- a/src/main.rs\n",
+                        "+++ b/src/main.rs\n",
+                        "@@ -2,1 +2,1 @@\n",
+                        "-    println!(\"hello\");\n",
+                        "+    println!(\"hi\");\n",
+                    )
+                    .to_string(),
+                },
+                LineHistoryEntry {
+                    sha: "1b1b1b".into(),
+                    subject: "Add main".into(),
+                    commit_timestamp: 1709740000,
+                    author_name: "Jane Doe".into(),
+                    diff: concat!(
+                        "diff --git a/src/main.rs b/src/main.rs\n",
+                        "--- /dev/null\n",
+                        "+++ b/src/main.rs\n",
+                        "@@ -0,0 +2,1 @@\n",
+                        "+    println!(\"hello\");\n",
+                    )
+                    .to_string(),
+                },
+            ]
+        );
+        assert_eq!(parse_line_history("").unwrap(), []);
+    }
+}
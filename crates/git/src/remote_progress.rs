@@ -0,0 +1,128 @@
+use futures::channel::oneshot;
+use gpui::SharedString;
+use smol::channel::Sender;
+
+/// How far a clone, push, pull, fetch or checkout has come, as last reported by git or git-lfs.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RemoteProgress {
+    /// What git is doing, such as "Receiving objects" or "Downloading LFS objects".
+    pub phase: SharedString,
+    pub percent: u8,
+    /// The number of objects done so far, and the number there are in this phase.
+    pub objects: Option<(u64, u64)>,
+    /// How much has been transferred and how fast, such as "1.20 MiB | 2.00 MiB/s".
+    pub transfer: Option<SharedString>,
+}
+
+impl RemoteProgress {
+    /// Parses a line of the progress git prints on stderr, such as
+    /// `Receiving objects:  45% (450/1000), 1.20 MiB | 2.00 MiB/s` or
+    /// `remote: Compressing objects: 100% (20/20), done.`.
+    pub fn parse(line: &str) -> Option<Self> {
+        let line = line.trim();
+        let line = line.strip_prefix("remote:").unwrap_or(line).trim_start();
+        let (phase, rest) = line.split_once(": ")?;
+        let (percent, rest) = rest.split_once('%')?;
+        let percent = percent.trim().parse::<u8>().ok()?.min(100);
+
+        let rest = rest.trim_start();
+        let (objects, rest) = match rest.strip_prefix('(') {
+            Some(rest) => {
+                let (counts, rest) = rest.split_once(')')?;
+                let (current, total) = counts.split_once('/')?;
+                (
+                    Some((current.trim().parse().ok()?, total.trim().parse().ok()?)),
+                    rest,
+                )
+            }
+            None => (None, rest),
+        };
+
+        let transfer = rest
+            .trim_start_matches(',')
+            .trim()
+            .trim_end_matches("done.")
+            .trim_end()
+            .trim_end_matches(',');
+
+        Some(Self {
+            phase: phase.trim().to_string().into(),
+            percent,
+            objects,
+            transfer: (!transfer.is_empty()).then(|| transfer.to_string().into()),
+        })
+    }
+}
+
+/// Receives the progress of a clone, push, pull, fetch or checkout while it runs, and lets it be
+/// cancelled, which kills the git process.
+pub struct RemoteProgressDelegate {
+    pub(crate) progress_tx: Sender<RemoteProgress>,
+    pub(crate) cancel_rx: oneshot::Receiver<()>,
+}
+
+impl RemoteProgressDelegate {
+    pub fn new(progress_tx: Sender<RemoteProgress>, cancel_rx: oneshot::Receiver<()>) -> Self {
+        Self {
+            progress_tx,
+            cancel_rx,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_remote_progress() {
+        assert_eq!(
+            RemoteProgress::parse("Receiving objects:  45% (450/1000), 1.20 MiB | 2.00 MiB/s"),
+            Some(RemoteProgress {
+                phase: "Receiving objects".into(),
+                percent: 45,
+                objects: Some((450, 1000)),
+                transfer: Some("1.20 MiB | 2.00 MiB/s".into()),
+            })
+        );
+        assert_eq!(
+            RemoteProgress::parse("remote: Compressing objects: 100% (20/20), done."),
+            Some(RemoteProgress {
+                phase: "Compressing objects".into(),
+                percent: 100,
+                objects: Some((20, 20)),
+                transfer: None,
+            })
+        );
+        assert_eq!(
+            RemoteProgress::parse("Writing objects: 100% (3/3), 310 bytes | 310.00 KiB/s, done."),
+            Some(RemoteProgress {
+                phase: "Writing objects".into(),
+                percent: 100,
+                objects: Some((3, 3)),
+                transfer: Some("310 bytes | 310.00 KiB/s".into()),
+            })
+        );
+        assert_eq!(
+            RemoteProgress::parse("Downloading LFS objects:  45% (9/20), 1.2 MB | 2.0 MB/s"),
+            Some(RemoteProgress {
+                phase: "Downloading LFS objects".into(),
+                percent: 45,
+                objects: Some((9, 20)),
+                transfer: Some("1.2 MB | 2.0 MB/s".into()),
+            })
+        );
+        assert_eq!(
+            RemoteProgress::parse("To github.com:zed-industries/zed.git"),
+            None
+        );
+        assert_eq!(
+            RemoteProgress::parse("remote: Counting objects: 5, done."),
+            None
+        );
+        assert_eq!(
+            RemoteProgress::parse("error: failed to push some refs to 'origin'"),
+            None
+        );
+    }
+}
@@ -1,6 +1,15 @@
-use crate::commit::parse_git_diff_name_status;
+use crate::blame::BlameOptions;
+use crate::commit::{
+    COMMIT_OVERVIEW_FORMAT, CommitOverview, parse_commit_overview, parse_git_diff_name_status,
+};
+use crate::gitignore::{IgnoreRule, parse_check_ignore};
+use crate::index_flags::{FlaggedFile, IndexFlag, parse_flagged_files};
+use crate::line_history::{LINE_HISTORY_FORMAT, LineHistoryEntry, parse_line_history};
+use crate::remote_progress::{RemoteProgress, RemoteProgressDelegate};
+use crate::sparse_checkout::{SparseCheckout, cone_parent_directories, excluded_cone_directories};
 use crate::stash::GitStash;
-use crate::status::{DiffTreeType, GitStatus, StatusCode, TreeDiff};
+use crate::status::{DiffTreeType, FileRename, GitStatus, StatusCode, TreeDiff, parse_renames};
+use crate::submodule::{Submodule, parse_submodules};
 use crate::{Oid, RunHook, SHORT_SHA_LENGTH};
 use anyhow::{Context as _, Result, anyhow, bail};
 use collections::HashMap;
@@ -28,6 +37,7 @@ use std::str::FromStr;
 use std::{
     cmp::Ordering,
     future,
+    ops::RangeInclusive,
     path::{Path, PathBuf},
     sync::Arc,
 };
@@ -40,9 +50,33 @@ use util::{ResultExt, paths};
 use uuid::Uuid;
 
 pub use askpass::{AskPassDelegate, AskPassResult, AskPassSession};
+pub use settings_content::PullStrategy;
 
 pub const REMOTE_CANCELLED_BY_USER: &str = "Operation cancelled by user";
 
+/// Prefix of the error returned when a commit fails because it could not be signed.
+pub const COMMIT_SIGNING_FAILED: &str = "Failed to sign the commit";
+
+/// Prefix of the error returned when applying or popping a stash leaves conflicts in the working
+/// tree. The stash itself is kept.
+pub const STASH_APPLY_CONFLICTS: &str = "Applying the stash stopped on conflicts";
+
+/// Prefix of the error returned when a branch isn't deleted because its commits aren't merged
+/// anywhere else. Deleting it with `force` discards them.
+pub const BRANCH_NOT_FULLY_MERGED: &str = "The branch is not fully merged";
+
+/// Prefix of the error returned when a fast-forward-only pull fails because the branch and its
+/// upstream have diverged. Pulling again with a rebase or a merge integrates them.
+pub const PULL_NOT_FAST_FORWARD: &str = "The branch can't be fast-forwarded";
+
+/// Prefix of the error returned when the remote rejects a push because its branch has commits
+/// the pushed one doesn't.
+pub const PUSH_REJECTED: &str = "The remote rejected the push";
+
+/// git-lfs only reports its download progress to a terminal unless this is set, and the git
+/// processes that print progress run with stderr piped.
+const GIT_LFS_FORCE_PROGRESS: &str = "GIT_LFS_FORCE_PROGRESS";
+
 /// Format string used in graph log to get initial data for the git graph
 /// %H - Full commit hash
 /// %P - Parent hashes
@@ -50,6 +84,11 @@ pub const REMOTE_CANCELLED_BY_USER: &str = "Operation cancelled by user";
 /// %x00 - Null byte separator, used to split up commit data
 static GRAPH_COMMIT_FORMAT: &str = "--format=%H%x00%P%x00%D";
 
+/// Format of the commits listed in the commit log. The message is only printed so that searches
+/// can match it, and each commit ends with [`COMMIT_LOG_SEPARATOR`].
+static COMMIT_LOG_FORMAT: &str = "--format=%H%x00%D%x00%at%x00%an%x00%ae%x00%s%x00%B%x1e";
+const COMMIT_LOG_SEPARATOR: u8 = 0x1e;
+
 /// Number of commits to load per chunk for the git graph.
 pub const GRAPH_CHUNK_SIZE: usize = 1000;
 
@@ -298,10 +337,23 @@ impl Upstream {
     }
 }
 
-#[derive(Clone, Copy, Default)]
+#[derive(Clone, Default)]
 pub struct CommitOptions {
     pub amend: bool,
     pub signoff: bool,
+    /// When non-empty, only these paths are committed, leaving anything else
+    /// in the index staged (`git commit --only`).
+    pub only_paths: Vec<RepoPath>,
+    /// Skips the pre-commit hook, like `git commit --no-verify`.
+    pub no_verify: bool,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct StashOptions {
+    /// Replaces the default `WIP on <branch>` description of the stash.
+    pub message: Option<String>,
+    /// Stashes untracked files too, like `git stash push --include-untracked`.
+    pub include_untracked: bool,
 }
 
 #[derive(Clone, Copy, Debug, Hash, PartialEq, Eq)]
@@ -368,6 +420,31 @@ pub struct CommitDetails {
     pub author_name: SharedString,
 }
 
+/// A position a reference was at, recorded in its reflog whenever an operation moved it.
+#[derive(Clone, Debug, Hash, PartialEq, Eq)]
+pub struct ReflogEntry {
+    pub sha: SharedString,
+    /// How git addresses the entry, such as `HEAD@{2}`.
+    pub selector: SharedString,
+    /// What moved the reference, such as `rebase (finish): returning to refs/heads/main`.
+    pub message: SharedString,
+    /// When the reference was moved, in seconds since the Unix epoch.
+    pub timestamp: i64,
+}
+
+/// A commit listed in the repository's log.
+#[derive(Clone, Debug, Hash, PartialEq, Eq)]
+pub struct CommitLogEntry {
+    pub sha: SharedString,
+    pub subject: SharedString,
+    /// When the commit was made, in seconds since the Unix epoch.
+    pub commit_timestamp: i64,
+    pub author_name: SharedString,
+    pub author_email: SharedString,
+    /// The branches and tags pointing at the commit, such as `HEAD -> main` and `tag: v1.0`.
+    pub refs: Vec<SharedString>,
+}
+
 #[derive(Clone, Debug, Hash, PartialEq, Eq)]
 pub struct FileHistoryEntry {
     pub sha: SharedString,
@@ -376,6 +453,177 @@ pub struct FileHistoryEntry {
     pub commit_timestamp: i64,
     pub author_name: SharedString,
     pub author_email: SharedString,
+    /// Whether the commit carries a GPG, SSH or X.509 signature.
+    pub signed: bool,
+    /// The file's path as of the commit, which differs from the history's path before a rename.
+    pub path: Option<RepoPath>,
+    /// The file's path before the commit, when the commit renamed or copied it.
+    pub old_path: Option<RepoPath>,
+}
+
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq)]
+pub enum SignatureStatus {
+    /// The signature is good and was made by a key git could check.
+    Verified,
+    /// The signature is bad or was made by a revoked key.
+    Unverified,
+    /// The signature can't be checked because the signing key isn't available.
+    UnknownKey,
+    /// The signature is good, but either it or the key that made it has expired.
+    Expired,
+}
+
+#[derive(Clone, Debug, Hash, PartialEq, Eq)]
+pub struct CommitSignature {
+    pub status: SignatureStatus,
+    /// The identity of the signer, if git could determine it.
+    pub signer: Option<SharedString>,
+    /// The fingerprint or ID of the signing key.
+    pub key: Option<SharedString>,
+}
+
+/// The versions of a conflicted file, each missing when that side doesn't have the file.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ConflictStages {
+    /// The common ancestor's version.
+    pub base: Option<String>,
+    /// The version on the current branch.
+    pub ours: Option<String>,
+    /// The version being merged in.
+    pub theirs: Option<String>,
+}
+
+/// What an interactive rebase does with a commit.
+#[derive(Clone, Copy, Debug, Default, Hash, PartialEq, Eq)]
+pub enum RebaseAction {
+    #[default]
+    Pick,
+    Reword,
+    Edit,
+    Squash,
+    Fixup,
+    Drop,
+}
+
+impl RebaseAction {
+    pub const ALL: [RebaseAction; 6] = [
+        RebaseAction::Pick,
+        RebaseAction::Reword,
+        RebaseAction::Edit,
+        RebaseAction::Squash,
+        RebaseAction::Fixup,
+        RebaseAction::Drop,
+    ];
+
+    /// The command for this action in a rebase todo list.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            RebaseAction::Pick => "pick",
+            RebaseAction::Reword => "reword",
+            RebaseAction::Edit => "edit",
+            RebaseAction::Squash => "squash",
+            RebaseAction::Fixup => "fixup",
+            RebaseAction::Drop => "drop",
+        }
+    }
+
+    /// Whether the action melds the commit into the one before it, so it can't apply to the
+    /// first commit of a rebase.
+    pub fn melds_into_previous(self) -> bool {
+        matches!(self, RebaseAction::Squash | RebaseAction::Fixup)
+    }
+}
+
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq)]
+pub enum InProgressOperationKind {
+    Merge,
+    CherryPick,
+    Revert,
+    Rebase,
+}
+
+impl InProgressOperationKind {
+    /// The git subcommand that started the operation.
+    pub fn command(self) -> &'static str {
+        match self {
+            InProgressOperationKind::Merge => "merge",
+            InProgressOperationKind::CherryPick => "cherry-pick",
+            InProgressOperationKind::Revert => "revert",
+            InProgressOperationKind::Rebase => "rebase",
+        }
+    }
+
+    /// Whether the operation applies several commits, one of which can be skipped.
+    pub fn can_skip(self) -> bool {
+        self != InProgressOperationKind::Merge
+    }
+}
+
+/// A multi-step operation that stopped, usually on conflicts, and waits to be resolved.
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq)]
+pub struct InProgressOperation {
+    pub kind: InProgressOperationKind,
+    /// The 1-based step being applied and the number of steps, when git records them.
+    pub progress: Option<(usize, usize)>,
+}
+
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq)]
+pub enum OperationResolution {
+    Continue,
+    Skip,
+    Abort,
+}
+
+impl OperationResolution {
+    pub fn flag(self) -> &'static str {
+        match self {
+            OperationResolution::Continue => "--continue",
+            OperationResolution::Skip => "--skip",
+            OperationResolution::Abort => "--abort",
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq)]
+pub enum BisectMark {
+    Good,
+    Bad,
+    Skip,
+}
+
+impl BisectMark {
+    /// The `git bisect` subcommand that marks the commit being tested.
+    pub fn command(self) -> &'static str {
+        match self {
+            BisectMark::Good => "good",
+            BisectMark::Bad => "bad",
+            BisectMark::Skip => "skip",
+        }
+    }
+}
+
+/// Where a bisect stands after it was started or the tested commit was marked.
+#[derive(Clone, Debug, Hash, PartialEq, Eq)]
+pub enum BisectStatus {
+    /// `sha` is checked out and waits to be marked good, bad or skipped.
+    Testing {
+        sha: String,
+        subject: String,
+        /// Roughly how many more commits need to be tested after this one.
+        remaining_steps: usize,
+    },
+    /// `sha` is the first bad commit.
+    Found { sha: String, subject: String },
+}
+
+/// A commit replayed by an interactive rebase, in the order it is replayed.
+#[derive(Clone, Debug, Hash, PartialEq, Eq)]
+pub struct RebaseTodoEntry {
+    pub action: RebaseAction,
+    pub sha: SharedString,
+    pub subject: SharedString,
+    /// The full commit message, which replaces the original one when the commit is reworded.
+    pub message: SharedString,
 }
 
 #[derive(Debug, Clone)]
@@ -432,6 +680,19 @@ pub struct Remote {
     pub name: SharedString,
 }
 
+/// A remote along with where it fetches from and pushes to.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RemoteInfo {
+    pub name: SharedString,
+    pub fetch_url: Option<SharedString>,
+    /// Only set when pushes go somewhere other than the fetch URL.
+    pub push_url: Option<SharedString>,
+    /// Whether this is `remote.pushDefault`, which push and pull use when the branch doesn't
+    /// name a remote of its own.
+    pub is_default: bool,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum ResetMode {
     /// Reset the branch pointer, leave index and worktree unchanged (this will make it look like things that were
     /// committed are now staged).
@@ -439,6 +700,11 @@ pub enum ResetMode {
     /// Reset the branch pointer and index, leave worktree unchanged (this makes it look as though things that were
     /// committed are now unstaged).
     Mixed,
+    /// Reset the branch pointer, index and worktree, discarding uncommitted changes.
+    Hard,
+    /// Reset the branch pointer, index and the worktree files that differ from it, keeping other
+    /// local changes. This undoes a merge or stash apply that stopped on conflicts.
+    Merge,
 }
 
 #[derive(Debug, Clone, Hash, PartialEq, Eq)]
@@ -635,8 +901,20 @@ pub trait GitRepository: Send + Sync {
     ///
     /// Also returns `None` for symlinks.
     fn load_committed_text(&self, path: RepoPath) -> BoxFuture<'_, Option<String>>;
+
+    /// Returns the contents of an entry in the tree of the given revision, or None if the revision has no entry for the given path.
+    ///
+    /// Also returns `None` for symlinks.
+    fn load_text_at_revision(
+        &self,
+        revision: String,
+        path: RepoPath,
+    ) -> BoxFuture<'_, Result<Option<String>>>;
     fn load_blob_content(&self, oid: Oid) -> BoxFuture<'_, Result<String>>;
 
+    /// Returns the versions of a conflicted file recorded in the index.
+    fn load_conflict_stages(&self, path: RepoPath) -> BoxFuture<'_, Result<ConflictStages>>;
+
     fn set_index_text(
         &self,
         path: RepoPath,
@@ -672,12 +950,46 @@ pub trait GitRepository: Send + Sync {
 
     fn branches(&self) -> BoxFuture<'_, Result<Vec<Branch>>>;
 
-    fn change_branch(&self, name: String) -> BoxFuture<'_, Result<()>>;
+    /// Checks out the branch, reporting the progress of the checkout, including the LFS objects
+    /// it downloads.
+    fn change_branch(
+        &self,
+        name: String,
+        progress: RemoteProgressDelegate,
+    ) -> BoxFuture<'_, Result<()>>;
     fn create_branch(&self, name: String, base_branch: Option<String>)
     -> BoxFuture<'_, Result<()>>;
     fn rename_branch(&self, branch: String, new_name: String) -> BoxFuture<'_, Result<()>>;
 
-    fn delete_branch(&self, is_remote: bool, name: String) -> BoxFuture<'_, Result<()>>;
+    /// Deletes a branch. Unless `force` is set, a local branch whose commits aren't merged is
+    /// kept and [`BRANCH_NOT_FULLY_MERGED`] is returned.
+    fn delete_branch(
+        &self,
+        is_remote: bool,
+        name: String,
+        force: bool,
+    ) -> BoxFuture<'_, Result<()>>;
+
+    /// Makes `branch` track `upstream`, or stop tracking a branch when `upstream` is `None`.
+    fn set_branch_upstream(
+        &self,
+        branch: String,
+        upstream: Option<String>,
+    ) -> BoxFuture<'_, Result<()>>;
+
+    /// Returns the names of the repository's tags, most recently created first.
+    fn tags(&self) -> BoxFuture<'_, Result<Vec<SharedString>>>;
+
+    /// Creates a tag at `target`, or at HEAD when `target` is `None`. The tag is annotated when
+    /// a `message` is given and lightweight otherwise.
+    fn create_tag(
+        &self,
+        name: String,
+        message: Option<String>,
+        target: Option<String>,
+    ) -> BoxFuture<'_, Result<()>>;
+
+    fn delete_tag(&self, name: String) -> BoxFuture<'_, Result<()>>;
 
     fn worktrees(&self) -> BoxFuture<'_, Result<Vec<Worktree>>>;
 
@@ -706,14 +1018,45 @@ pub trait GitRepository: Send + Sync {
         env: Arc<HashMap<String, String>>,
     ) -> BoxFuture<'_, Result<()>>;
 
+    /// Checks out `revision` without a branch, detaching HEAD.
+    fn checkout_revision(
+        &self,
+        revision: String,
+        env: Arc<HashMap<String, String>>,
+    ) -> BoxFuture<'_, Result<()>>;
+
+    /// Returns the `limit` most recent entries of the reflog of `reference`, newest first.
+    fn reflog(&self, reference: String, limit: usize) -> BoxFuture<'_, Result<Vec<ReflogEntry>>>;
+
+    /// Returns up to `limit` of the commits reachable from HEAD, newest first, after skipping
+    /// `skip` of them. With a query, only the commits whose message or author contains it,
+    /// ignoring case, are listed and skipped.
+    fn commit_log(
+        &self,
+        query: Option<String>,
+        skip: usize,
+        limit: usize,
+    ) -> BoxFuture<'_, Result<Vec<CommitLogEntry>>>;
+
     fn show(&self, commit: String) -> BoxFuture<'_, Result<CommitDetails>>;
+    fn commit_overview(&self, commit: String) -> BoxFuture<'_, Result<CommitOverview>>;
 
     fn load_commit(&self, commit: String, cx: AsyncApp) -> BoxFuture<'_, Result<CommitDiff>>;
+    /// The files that differ between two revisions, with their contents at both.
+    fn compare_revisions(&self, base: String, target: String) -> BoxFuture<'_, Result<CommitDiff>>;
+    /// The best common ancestor of two revisions, or `None` when their histories never meet.
+    fn merge_base(&self, first: String, second: String) -> BoxFuture<'_, Result<Option<String>>>;
     fn blame(
         &self,
         path: RepoPath,
         content: Rope,
         line_ending: LineEnding,
+        options: BlameOptions,
+    ) -> BoxFuture<'_, Result<crate::blame::Blame>>;
+    fn blame_at_revision(
+        &self,
+        revision: String,
+        path: RepoPath,
     ) -> BoxFuture<'_, Result<crate::blame::Blame>>;
     fn file_history(&self, path: RepoPath) -> BoxFuture<'_, Result<FileHistory>>;
     fn file_history_paginated(
@@ -722,6 +1065,13 @@ pub trait GitRepository: Send + Sync {
         skip: usize,
         limit: Option<usize>,
     ) -> BoxFuture<'_, Result<FileHistory>>;
+    /// The commits that changed the given lines of a file, newest first. The lines start at 1
+    /// and are counted in the file at HEAD.
+    fn line_history(
+        &self,
+        path: RepoPath,
+        lines: RangeInclusive<u32>,
+    ) -> BoxFuture<'_, Result<Vec<LineHistoryEntry>>>;
 
     /// Returns the absolute path to the repository. For worktrees, this will be the path to the
     /// worktree's gitdir within the main repository (typically `.git/worktrees/<name>`).
@@ -746,10 +1096,12 @@ pub trait GitRepository: Send + Sync {
         env: Arc<HashMap<String, String>>,
     ) -> BoxFuture<'_, Result<()>>;
 
+    /// Runs the given hook, sending each line it prints to `output` while it runs.
     fn run_hook(
         &self,
         hook: RunHook,
         env: Arc<HashMap<String, String>>,
+        output: Sender<String>,
     ) -> BoxFuture<'_, Result<()>>;
 
     fn commit(
@@ -761,9 +1113,30 @@ pub trait GitRepository: Send + Sync {
         env: Arc<HashMap<String, String>>,
     ) -> BoxFuture<'_, Result<()>>;
 
+    /// Returns the contents of the commit message template, read from `configured_path` when
+    /// given and from the `commit.template` git config otherwise.
+    fn load_commit_template(
+        &self,
+        configured_path: Option<String>,
+    ) -> BoxFuture<'_, Result<Option<String>>>;
+
+    /// Returns the distinct names and emails of the authors of the last `max_commits` commits,
+    /// most recent first.
+    fn recent_commit_authors(
+        &self,
+        max_commits: usize,
+    ) -> BoxFuture<'_, Result<Vec<(SharedString, SharedString)>>>;
+
+    /// Verifies the signatures of the given commits. Unsigned commits are left out of the result.
+    fn verify_commit_signatures(
+        &self,
+        shas: Vec<SharedString>,
+    ) -> BoxFuture<'_, Result<HashMap<SharedString, CommitSignature>>>;
+
     fn stash_paths(
         &self,
         paths: Vec<RepoPath>,
+        options: StashOptions,
         env: Arc<HashMap<String, String>>,
     ) -> BoxFuture<'_, Result<()>>;
 
@@ -785,25 +1158,41 @@ pub trait GitRepository: Send + Sync {
         env: Arc<HashMap<String, String>>,
     ) -> BoxFuture<'_, Result<()>>;
 
+    /// Creates and checks out a branch at the commit the stash was made on, applies the stash
+    /// there and drops it.
+    fn stash_branch(
+        &self,
+        branch_name: String,
+        index: Option<usize>,
+        env: Arc<HashMap<String, String>>,
+    ) -> BoxFuture<'_, Result<()>>;
+
+    /// Pushes the branch. A push the remote rejects returns [`PUSH_REJECTED`].
     fn push(
         &self,
         branch_name: String,
         remote_branch_name: String,
         upstream_name: String,
         options: Option<PushOptions>,
+        follow_tags: bool,
         askpass: AskPassDelegate,
+        progress: RemoteProgressDelegate,
         env: Arc<HashMap<String, String>>,
         // This method takes an AsyncApp to ensure it's invoked on the main thread,
         // otherwise git-credentials-manager won't work.
         cx: AsyncApp,
     ) -> BoxFuture<'_, Result<RemoteCommandOutput>>;
 
+    /// Pulls with the given strategy, or with the one the `pull.rebase` and `pull.ff` git config
+    /// picks when there's none. A fast-forward-only pull of diverged branches returns
+    /// [`PULL_NOT_FAST_FORWARD`].
     fn pull(
         &self,
         branch_name: Option<String>,
         upstream_name: String,
-        rebase: bool,
+        strategy: Option<PullStrategy>,
         askpass: AskPassDelegate,
+        progress: RemoteProgressDelegate,
         env: Arc<HashMap<String, String>>,
         // This method takes an AsyncApp to ensure it's invoked on the main thread,
         // otherwise git-credentials-manager won't work.
@@ -814,12 +1203,89 @@ pub trait GitRepository: Send + Sync {
         &self,
         fetch_options: FetchOptions,
         askpass: AskPassDelegate,
+        progress: RemoteProgressDelegate,
+        env: Arc<HashMap<String, String>>,
+        // This method takes an AsyncApp to ensure it's invoked on the main thread,
+        // otherwise git-credentials-manager won't work.
+        cx: AsyncApp,
+    ) -> BoxFuture<'_, Result<RemoteCommandOutput>>;
+
+    /// Fetches a ref of the remote into a local branch, creating the branch or moving it to the
+    /// fetched commit. Fails when the branch is checked out.
+    fn fetch_into_branch(
+        &self,
+        remote_name: String,
+        remote_ref: String,
+        branch_name: String,
+        askpass: AskPassDelegate,
+        progress: RemoteProgressDelegate,
+        env: Arc<HashMap<String, String>>,
+        // This method takes an AsyncApp to ensure it's invoked on the main thread,
+        // otherwise git-credentials-manager won't work.
+        cx: AsyncApp,
+    ) -> BoxFuture<'_, Result<RemoteCommandOutput>>;
+
+    /// Picks out the paths that git-lfs filters, going by their `filter` attribute.
+    fn lfs_tracked_paths(&self, paths: Vec<RepoPath>) -> BoxFuture<'_, Result<HashSet<RepoPath>>>;
+
+    /// Downloads the LFS objects of the paths and writes their content over the pointer files in
+    /// the working tree.
+    fn fetch_lfs_objects(
+        &self,
+        paths: Vec<RepoPath>,
+        askpass: AskPassDelegate,
+        progress: RemoteProgressDelegate,
+        env: Arc<HashMap<String, String>>,
+        // This method takes an AsyncApp to ensure it's invoked on the main thread,
+        // otherwise git-credentials-manager won't work.
+        cx: AsyncApp,
+    ) -> BoxFuture<'_, Result<RemoteCommandOutput>>;
+
+    /// Lists the submodules directly inside the repository, in the order of their paths.
+    fn submodules(&self) -> BoxFuture<'_, Result<Vec<Submodule>>>;
+
+    /// Checks out the commits the repository records for the submodules, cloning the ones that
+    /// aren't yet when `init` is set. No paths means all submodules.
+    fn update_submodules(
+        &self,
+        paths: Vec<RepoPath>,
+        init: bool,
+        askpass: AskPassDelegate,
+        progress: RemoteProgressDelegate,
         env: Arc<HashMap<String, String>>,
         // This method takes an AsyncApp to ensure it's invoked on the main thread,
         // otherwise git-credentials-manager won't work.
         cx: AsyncApp,
     ) -> BoxFuture<'_, Result<RemoteCommandOutput>>;
 
+    /// Copies the submodule URLs from `.gitmodules` into the git config. No paths means all
+    /// submodules.
+    fn sync_submodules(&self, paths: Vec<RepoPath>) -> BoxFuture<'_, Result<()>>;
+
+    /// The sparse checkout of the working tree, or `None` when all of HEAD is checked out.
+    fn sparse_checkout(&self) -> BoxFuture<'_, Result<Option<SparseCheckout>>>;
+
+    /// Limits the working tree to the patterns, turning on sparse checkout if it's off.
+    fn set_sparse_checkout(&self, cone: bool, patterns: Vec<String>) -> BoxFuture<'_, Result<()>>;
+
+    /// Checks out all of HEAD again.
+    fn disable_sparse_checkout(&self) -> BoxFuture<'_, Result<()>>;
+
+    /// The pattern that makes git ignore the path, or `None` when it isn't ignored.
+    fn ignore_rule(&self, path: RepoPath) -> BoxFuture<'_, Result<Option<IgnoreRule>>>;
+
+    /// The files in the working tree whose index entries have the assume-unchanged or
+    /// skip-worktree flag set.
+    fn flagged_files(&self) -> BoxFuture<'_, Result<Vec<FlaggedFile>>>;
+
+    /// Sets or clears the flag on the index entries of the paths.
+    fn set_index_flag(
+        &self,
+        paths: Vec<RepoPath>,
+        flag: IndexFlag,
+        value: bool,
+    ) -> BoxFuture<'_, Result<()>>;
+
     fn get_push_remote(&self, branch: String) -> BoxFuture<'_, Result<Option<Remote>>>;
 
     fn get_branch_remote(&self, branch: String) -> BoxFuture<'_, Result<Option<Remote>>>;
@@ -830,6 +1296,17 @@ pub trait GitRepository: Send + Sync {
 
     fn create_remote(&self, name: String, url: String) -> BoxFuture<'_, Result<()>>;
 
+    /// Lists the remotes with their URLs, sorted by name.
+    fn remote_infos(&self) -> BoxFuture<'_, Result<Vec<RemoteInfo>>>;
+
+    fn rename_remote(&self, old_name: String, new_name: String) -> BoxFuture<'_, Result<()>>;
+
+    /// Sets where pushes to the remote go, or with `None` makes them go to the fetch URL again.
+    fn set_remote_push_url(&self, name: String, url: Option<String>) -> BoxFuture<'_, Result<()>>;
+
+    /// Sets `remote.pushDefault`, or unsets it with `None`.
+    fn set_default_remote(&self, name: Option<String>) -> BoxFuture<'_, Result<()>>;
+
     /// returns a list of remote branches that contain HEAD
     fn check_for_pushed_commit(&self) -> BoxFuture<'_, Result<Vec<SharedString>>>;
 
@@ -841,6 +1318,10 @@ pub trait GitRepository: Send + Sync {
         path_prefixes: &[RepoPath],
     ) -> BoxFuture<'_, Result<crate::status::GitDiffStat>>;
 
+    /// The renames and copies that git's similarity detection finds between HEAD and the working
+    /// tree, which leaves out untracked files.
+    fn renames(&self) -> BoxFuture<'_, Result<Vec<FileRename>>>;
+
     /// Creates a checkpoint for the repository.
     fn checkpoint(&self) -> BoxFuture<'static, Result<GitRepositoryCheckpoint>>;
 
@@ -877,6 +1358,86 @@ pub trait GitRepository: Send + Sync {
 
     fn commit_data_reader(&self) -> Result<CommitDataReader>;
 
+    /// Lists the commits reachable from `head` but not from `base`, newest first.
+    fn commits_between(
+        &self,
+        base: String,
+        head: String,
+    ) -> BoxFuture<'_, Result<Vec<CommitSummary>>>;
+
+    /// Lists the commits an interactive rebase onto `base` would replay, oldest first.
+    fn rebase_todo(&self, base: String) -> BoxFuture<'_, Result<Vec<RebaseTodoEntry>>>;
+
+    /// Rebases the current branch onto `base`, replaying `todo` instead of asking for a todo list
+    /// in an editor. Returns whether the rebase stopped before finishing, either for an `edit`
+    /// step or because of conflicts.
+    fn interactive_rebase(
+        &self,
+        base: String,
+        todo: Vec<RebaseTodoEntry>,
+        env: Arc<HashMap<String, String>>,
+    ) -> BoxFuture<'_, Result<bool>>;
+
+    /// Applies the changes of `shas` onto the current branch, oldest first. With `record_origin`,
+    /// each new commit message notes the commit it was cherry-picked from. Returns whether the
+    /// cherry-pick stopped on conflicts.
+    fn cherry_pick(
+        &self,
+        shas: Vec<String>,
+        record_origin: bool,
+        env: Arc<HashMap<String, String>>,
+    ) -> BoxFuture<'_, Result<bool>>;
+
+    /// Reverts the changes of `sha` on the current branch, staging them instead of committing
+    /// with `no_commit`. Returns the generated revert message when the revert was left to be
+    /// committed, either because of `no_commit` or because it stopped on conflicts.
+    fn revert_commit(
+        &self,
+        sha: String,
+        no_commit: bool,
+        env: Arc<HashMap<String, String>>,
+    ) -> BoxFuture<'_, Result<Option<String>>>;
+
+    /// Starts bisecting the commits between the `good` and `bad` revisions, checking out the
+    /// first one to test.
+    fn bisect_start(
+        &self,
+        bad: String,
+        good: String,
+        env: Arc<HashMap<String, String>>,
+    ) -> BoxFuture<'_, Result<BisectStatus>>;
+
+    /// Marks the commit being bisected, checking out the next one to test unless the first bad
+    /// commit was found.
+    fn bisect_mark(
+        &self,
+        mark: BisectMark,
+        env: Arc<HashMap<String, String>>,
+    ) -> BoxFuture<'_, Result<BisectStatus>>;
+
+    /// Ends the bisect and checks out the branch it was started from.
+    fn bisect_reset(&self, env: Arc<HashMap<String, String>>) -> BoxFuture<'_, Result<()>>;
+
+    /// Returns the merge, cherry-pick, revert or rebase that stopped and waits to be resolved.
+    fn in_progress_operation(&self) -> BoxFuture<'_, Option<InProgressOperation>>;
+
+    /// Continues, skips the current step of, or aborts the stopped operation. Returns whether an
+    /// operation is still in progress afterwards, for example because the next step conflicts.
+    fn resolve_in_progress_operation(
+        &self,
+        kind: InProgressOperationKind,
+        resolution: OperationResolution,
+        env: Arc<HashMap<String, String>>,
+    ) -> BoxFuture<'_, Result<bool>>;
+
+    /// Returns the commits reachable from `log_source` whose message or author contains `query`,
+    /// ignoring case, in no particular order.
+    fn search_commits(
+        &self,
+        log_source: LogSource,
+        query: String,
+    ) -> BoxFuture<'_, Result<Vec<Oid>>>;
+
     fn set_trusted(&self, trusted: bool);
     fn is_trusted(&self) -> bool;
 }
@@ -954,6 +1515,17 @@ impl RealGitRepository {
         ))
     }
 
+    /// What starts the comment lines of the messages git prepares. With `auto`, git only picks
+    /// another character when committing, so prepared messages still use `#`.
+    fn comment_prefix(&self) -> String {
+        let config = self.repository.lock().config();
+        ["core.commentString", "core.commentChar"]
+            .into_iter()
+            .find_map(|key| config.as_ref().ok()?.get_string(key).ok())
+            .filter(|prefix| !prefix.is_empty() && prefix != "auto")
+            .unwrap_or_else(|| "#".to_string())
+    }
+
     async fn any_git_binary_help_output(&self) -> SharedString {
         if let Some(output) = self.any_git_binary_help_output.lock().clone() {
             return output;
@@ -981,6 +1553,115 @@ pub struct GitCommitter {
     pub email: Option<String>,
 }
 
+/// Loads the old and new contents of the files changed between two revisions, as listed by
+/// `git diff --name-status -z`.
+async fn load_changed_files(
+    git: &GitBinary,
+    changes: impl Iterator<Item = (&str, StatusCode)>,
+    new_revision: &str,
+    old_revision: &str,
+) -> Result<CommitDiff> {
+    let mut cat_file_process = git
+        .build_command(&["--no-optional-locks", "cat-file", "--batch=%(objectsize)"])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .context("starting git cat-file process")?;
+
+    let mut files = Vec::<CommitFile>::new();
+    let mut stdin = BufWriter::with_capacity(512, cat_file_process.stdin.take().unwrap());
+    let mut stdout = BufReader::new(cat_file_process.stdout.take().unwrap());
+    let mut info_line = String::new();
+    let mut newline = [b'\0'];
+    for (path, status_code) in changes {
+        // git outputs `/`-delimited paths even on Windows.
+        let Some(rel_path) = RelPath::unix(path).log_err() else {
+            continue;
+        };
+
+        match status_code {
+            StatusCode::Modified => {
+                stdin.write_all(new_revision.as_bytes()).await?;
+                stdin.write_all(b":").await?;
+                stdin.write_all(path.as_bytes()).await?;
+                stdin.write_all(b"\n").await?;
+                stdin.write_all(old_revision.as_bytes()).await?;
+                stdin.write_all(b":").await?;
+                stdin.write_all(path.as_bytes()).await?;
+                stdin.write_all(b"\n").await?;
+            }
+            StatusCode::Added => {
+                stdin.write_all(new_revision.as_bytes()).await?;
+                stdin.write_all(b":").await?;
+                stdin.write_all(path.as_bytes()).await?;
+                stdin.write_all(b"\n").await?;
+            }
+            StatusCode::Deleted => {
+                stdin.write_all(old_revision.as_bytes()).await?;
+                stdin.write_all(b":").await?;
+                stdin.write_all(path.as_bytes()).await?;
+                stdin.write_all(b"\n").await?;
+            }
+            _ => continue,
+        }
+        stdin.flush().await?;
+
+        info_line.clear();
+        stdout.read_line(&mut info_line).await?;
+
+        let len = info_line
+            .trim_end()
+            .parse()
+            .with_context(|| format!("invalid object size output from cat-file {info_line}"))?;
+        let mut text_bytes = vec![0; len];
+        stdout.read_exact(&mut text_bytes).await?;
+        stdout.read_exact(&mut newline).await?;
+
+        let mut old_text = None;
+        let mut new_text = None;
+        let mut is_binary = is_binary_content(&text_bytes);
+        let text = if is_binary {
+            String::new()
+        } else {
+            String::from_utf8_lossy(&text_bytes).to_string()
+        };
+
+        match status_code {
+            StatusCode::Modified => {
+                info_line.clear();
+                stdout.read_line(&mut info_line).await?;
+                let len = info_line.trim_end().parse().with_context(|| {
+                    format!("invalid object size output from cat-file {}", info_line)
+                })?;
+                let mut parent_bytes = vec![0; len];
+                stdout.read_exact(&mut parent_bytes).await?;
+                stdout.read_exact(&mut newline).await?;
+                is_binary = is_binary || is_binary_content(&parent_bytes);
+                if is_binary {
+                    old_text = Some(String::new());
+                    new_text = Some(String::new());
+                } else {
+                    old_text = Some(String::from_utf8_lossy(&parent_bytes).to_string());
+                    new_text = Some(text);
+                }
+            }
+            StatusCode::Added => new_text = Some(text),
+            StatusCode::Deleted => old_text = Some(text),
+            _ => continue,
+        }
+
+        files.push(CommitFile {
+            path: RepoPath(Arc::from(rel_path)),
+            old_text,
+            new_text,
+            is_binary,
+        })
+    }
+
+    Ok(CommitDiff { files })
+}
+
 pub async fn get_git_committer(cx: &AsyncApp) -> GitCommitter {
     if cfg!(any(feature = "test-support", test)) {
         return GitCommitter {
@@ -1039,22 +1720,148 @@ impl GitRepository for RealGitRepository {
         repo.commondir().into()
     }
 
-    fn show(&self, commit: String) -> BoxFuture<'_, Result<CommitDetails>> {
+    fn checkout_revision(
+        &self,
+        revision: String,
+        env: Arc<HashMap<String, String>>,
+    ) -> BoxFuture<'_, Result<()>> {
         let git_binary = self.git_binary();
-        self.executor
-            .spawn(async move {
-                let git = git_binary?;
-                let output = git
-                    .build_command(&[
-                        "--no-optional-locks",
-                        "show",
-                        "--no-patch",
-                        "--format=%H%x00%B%x00%at%x00%ae%x00%an%x00",
-                        &commit,
-                    ])
-                    .output()
-                    .await?;
-                let output = std::str::from_utf8(&output.stdout)?;
+        async move {
+            let git = git_binary?;
+            let output = git
+                .build_command(&["switch", "--detach", &revision])
+                .envs(env.iter())
+                .output()
+                .await?;
+            anyhow::ensure!(
+                output.status.success(),
+                "Failed to check out {revision}:\n{}",
+                String::from_utf8_lossy(&output.stderr),
+            );
+            Ok(())
+        }
+        .boxed()
+    }
+
+    fn reflog(&self, reference: String, limit: usize) -> BoxFuture<'_, Result<Vec<ReflogEntry>>> {
+        let git_binary = self.git_binary();
+        self.executor
+            .spawn(async move {
+                // The reference can come from collab guests, so it must not be read as an option.
+                anyhow::ensure!(
+                    !reference.starts_with('-'),
+                    "invalid reflog reference {reference:?}"
+                );
+                let git = git_binary?;
+                let limit = limit.to_string();
+                // With `--date=unix`, `%gd` prints the time of the entry instead of its index.
+                let output = git
+                    .build_command(&[
+                        "--no-optional-locks",
+                        "log",
+                        "--walk-reflogs",
+                        "--date=unix",
+                        "--format=%H%x00%gd%x00%gs",
+                        "-n",
+                        &limit,
+                        &reference,
+                        "--",
+                    ])
+                    .output()
+                    .await?;
+                anyhow::ensure!(
+                    output.status.success(),
+                    "Failed to read the reflog of {reference}:\n{}",
+                    String::from_utf8_lossy(&output.stderr),
+                );
+                Ok(parse_reflog(&String::from_utf8_lossy(&output.stdout)))
+            })
+            .boxed()
+    }
+
+    fn commit_log(
+        &self,
+        query: Option<String>,
+        skip: usize,
+        limit: usize,
+    ) -> BoxFuture<'_, Result<Vec<CommitLogEntry>>> {
+        let git_binary = self.git_binary();
+        self.executor
+            .spawn(async move {
+                let git = git_binary?;
+                let query = query
+                    .map(|query| query.to_lowercase())
+                    .filter(|query| !query.is_empty());
+                let mut command =
+                    git.build_command(&["--no-optional-locks", "log", COMMIT_LOG_FORMAT]);
+                // Git can only combine `--grep` and `--author` so that both must match, so
+                // searches are matched here instead, and git only pages through the whole log.
+                if query.is_none() {
+                    command.arg(format!("--skip={skip}"));
+                    command.arg(format!("--max-count={limit}"));
+                }
+                command
+                    .arg("--")
+                    .stdout(Stdio::piped())
+                    .stderr(Stdio::null())
+                    .kill_on_drop(true);
+                let mut child = command.spawn()?;
+                let stdout = child.stdout.take().context("failed to get stdout")?;
+                let mut reader = BufReader::new(stdout);
+
+                let mut entries = Vec::new();
+                let mut skipped = 0;
+                let mut record = Vec::new();
+                while entries.len() < limit {
+                    record.clear();
+                    if reader.read_until(COMMIT_LOG_SEPARATOR, &mut record).await? == 0 {
+                        // An unborn branch has no log, which git reports as an error.
+                        let status = child.status().await?;
+                        anyhow::ensure!(
+                            status.success() || entries.is_empty(),
+                            "Failed to read the commit log"
+                        );
+                        break;
+                    }
+                    let record = String::from_utf8_lossy(&record);
+                    let Some((entry, message)) = parse_commit_log_record(&record) else {
+                        continue;
+                    };
+                    if let Some(query) = &query {
+                        let matches = message.to_lowercase().contains(query)
+                            || entry.author_name.to_lowercase().contains(query)
+                            || entry.author_email.to_lowercase().contains(query);
+                        if !matches {
+                            continue;
+                        }
+                        if skipped < skip {
+                            skipped += 1;
+                            continue;
+                        }
+                    }
+                    entries.push(entry);
+                }
+                Ok(entries)
+            })
+            .boxed()
+    }
+
+    fn show(&self, commit: String) -> BoxFuture<'_, Result<CommitDetails>> {
+        let git_binary = self.git_binary();
+        self.executor
+            .spawn(async move {
+                let git = git_binary?;
+                let output = git
+                    .build_command(&[
+                        "--no-optional-locks",
+                        "show",
+                        "--no-patch",
+                        "--format=%H%x00%B%x00%at%x00%ae%x00%an%x00",
+                        &commit,
+                    ])
+                    .output()
+                    .await?;
+                let output = std::str::from_utf8(&output.stdout)?;
                 let fields = output.split('\0').collect::<Vec<_>>();
                 if fields.len() != 6 {
                     bail!("unexpected git-show output for {commit:?}: {output:?}")
@@ -1075,6 +1882,37 @@ impl GitRepository for RealGitRepository {
             .boxed()
     }
 
+    fn commit_overview(&self, commit: String) -> BoxFuture<'_, Result<CommitOverview>> {
+        let git_binary = self.git_binary();
+        self.executor
+            .spawn(async move {
+                let git = git_binary?;
+                let header = git
+                    .run(&[
+                        "--no-optional-locks",
+                        "show",
+                        "--no-patch",
+                        COMMIT_OVERVIEW_FORMAT,
+                        &commit,
+                    ])
+                    .await?;
+                let name_status = git
+                    .run(&[
+                        "--no-optional-locks",
+                        "show",
+                        "--format=",
+                        "-z",
+                        "--no-renames",
+                        "--name-status",
+                        "--first-parent",
+                        &commit,
+                    ])
+                    .await?;
+                parse_commit_overview(&header, &name_status)
+            })
+            .boxed()
+    }
+
     fn load_commit(&self, commit: String, cx: AsyncApp) -> BoxFuture<'_, Result<CommitDiff>> {
         if self.repository.lock().workdir().is_none() {
             return future::ready(Err(anyhow!("no working directory"))).boxed();
@@ -1103,107 +1941,58 @@ impl GitRepository for RealGitRepository {
             let show_stdout = String::from_utf8_lossy(&show_output.stdout);
             let changes = parse_git_diff_name_status(&show_stdout);
             let parent_sha = format!("{}^", commit);
+            load_changed_files(&git, changes, &commit, &parent_sha).await
+        })
+        .boxed()
+    }
 
-            let mut cat_file_process = git
-                .build_command(&["--no-optional-locks", "cat-file", "--batch=%(objectsize)"])
-                .stdin(Stdio::piped())
-                .stdout(Stdio::piped())
-                .stderr(Stdio::piped())
-                .spawn()
-                .context("starting git cat-file process")?;
-
-            let mut files = Vec::<CommitFile>::new();
-            let mut stdin = BufWriter::with_capacity(512, cat_file_process.stdin.take().unwrap());
-            let mut stdout = BufReader::new(cat_file_process.stdout.take().unwrap());
-            let mut info_line = String::new();
-            let mut newline = [b'\0'];
-            for (path, status_code) in changes {
-                // git-show outputs `/`-delimited paths even on Windows.
-                let Some(rel_path) = RelPath::unix(path).log_err() else {
-                    continue;
-                };
-
-                match status_code {
-                    StatusCode::Modified => {
-                        stdin.write_all(commit.as_bytes()).await?;
-                        stdin.write_all(b":").await?;
-                        stdin.write_all(path.as_bytes()).await?;
-                        stdin.write_all(b"\n").await?;
-                        stdin.write_all(parent_sha.as_bytes()).await?;
-                        stdin.write_all(b":").await?;
-                        stdin.write_all(path.as_bytes()).await?;
-                        stdin.write_all(b"\n").await?;
-                    }
-                    StatusCode::Added => {
-                        stdin.write_all(commit.as_bytes()).await?;
-                        stdin.write_all(b":").await?;
-                        stdin.write_all(path.as_bytes()).await?;
-                        stdin.write_all(b"\n").await?;
-                    }
-                    StatusCode::Deleted => {
-                        stdin.write_all(parent_sha.as_bytes()).await?;
-                        stdin.write_all(b":").await?;
-                        stdin.write_all(path.as_bytes()).await?;
-                        stdin.write_all(b"\n").await?;
-                    }
-                    _ => continue,
-                }
-                stdin.flush().await?;
-
-                info_line.clear();
-                stdout.read_line(&mut info_line).await?;
-
-                let len = info_line.trim_end().parse().with_context(|| {
-                    format!("invalid object size output from cat-file {info_line}")
-                })?;
-                let mut text_bytes = vec![0; len];
-                stdout.read_exact(&mut text_bytes).await?;
-                stdout.read_exact(&mut newline).await?;
-
-                let mut old_text = None;
-                let mut new_text = None;
-                let mut is_binary = is_binary_content(&text_bytes);
-                let text = if is_binary {
-                    String::new()
-                } else {
-                    String::from_utf8_lossy(&text_bytes).to_string()
-                };
+    fn compare_revisions(&self, base: String, target: String) -> BoxFuture<'_, Result<CommitDiff>> {
+        if self.repository.lock().workdir().is_none() {
+            return future::ready(Err(anyhow!("no working directory"))).boxed();
+        }
+        let git_binary = self.git_binary();
+        self.executor
+            .spawn(async move {
+                let git = git_binary?;
+                let output = git
+                    .run(&[
+                        "--no-optional-locks",
+                        "diff",
+                        "-z",
+                        "--no-renames",
+                        "--name-status",
+                        &base,
+                        &target,
+                        "--",
+                    ])
+                    .await?;
+                let changes = parse_git_diff_name_status(&output);
+                load_changed_files(&git, changes, &target, &base).await
+            })
+            .boxed()
+    }
 
-                match status_code {
-                    StatusCode::Modified => {
-                        info_line.clear();
-                        stdout.read_line(&mut info_line).await?;
-                        let len = info_line.trim_end().parse().with_context(|| {
-                            format!("invalid object size output from cat-file {}", info_line)
-                        })?;
-                        let mut parent_bytes = vec![0; len];
-                        stdout.read_exact(&mut parent_bytes).await?;
-                        stdout.read_exact(&mut newline).await?;
-                        is_binary = is_binary || is_binary_content(&parent_bytes);
-                        if is_binary {
-                            old_text = Some(String::new());
-                            new_text = Some(String::new());
-                        } else {
-                            old_text = Some(String::from_utf8_lossy(&parent_bytes).to_string());
-                            new_text = Some(text);
-                        }
-                    }
-                    StatusCode::Added => new_text = Some(text),
-                    StatusCode::Deleted => old_text = Some(text),
-                    _ => continue,
+    fn merge_base(&self, first: String, second: String) -> BoxFuture<'_, Result<Option<String>>> {
+        let git_binary = self.git_binary();
+        self.executor
+            .spawn(async move {
+                let git = git_binary?;
+                let output = git
+                    .build_command(&["merge-base", &first, &second])
+                    .output()
+                    .await?;
+                // Exits with 1 and no error when the revisions have no common ancestor.
+                if output.status.code() == Some(1) && output.stderr.is_empty() {
+                    return Ok(None);
                 }
-
-                files.push(CommitFile {
-                    path: RepoPath(Arc::from(rel_path)),
-                    old_text,
-                    new_text,
-                    is_binary,
-                })
-            }
-
-            Ok(CommitDiff { files })
-        })
-        .boxed()
+                anyhow::ensure!(
+                    output.status.success(),
+                    "failed to find the merge base of {first} and {second}: {}",
+                    String::from_utf8_lossy(&output.stderr).trim()
+                );
+                Ok(Some(String::from_utf8(output.stdout)?.trim().to_string()))
+            })
+            .boxed()
     }
 
     fn reset(
@@ -1217,6 +2006,8 @@ impl GitRepository for RealGitRepository {
             let mode_flag = match mode {
                 ResetMode::Mixed => "--mixed",
                 ResetMode::Soft => "--soft",
+                ResetMode::Hard => "--hard",
+                ResetMode::Merge => "--merge",
             };
 
             let git = git_binary?;
@@ -1299,6 +2090,42 @@ impl GitRepository for RealGitRepository {
             .boxed()
     }
 
+    fn load_conflict_stages(&self, path: RepoPath) -> BoxFuture<'_, Result<ConflictStages>> {
+        const STAGE_BASE: i32 = 1;
+        const STAGE_OURS: i32 = 2;
+        const STAGE_THEIRS: i32 = 3;
+
+        let repo = self.repository.clone();
+        self.executor
+            .spawn(async move {
+                fn logic(repo: &git2::Repository, path: &RepoPath) -> Result<ConflictStages> {
+                    let mut index = repo.index()?;
+                    index.read(false)?;
+                    // git2 unwraps internally on empty paths or `.`
+                    if path.is_empty() {
+                        bail!("empty path has no conflict stages");
+                    }
+                    let load_stage = |stage| -> Result<Option<String>> {
+                        let Some(entry) = index.get_path(path.as_std_path(), stage) else {
+                            return Ok(None);
+                        };
+                        let content = repo.find_blob(entry.id)?.content().to_owned();
+                        Ok(Some(
+                            String::from_utf8(content).context("conflicted file is not UTF-8")?,
+                        ))
+                    };
+                    Ok(ConflictStages {
+                        base: load_stage(STAGE_BASE)?,
+                        ours: load_stage(STAGE_OURS)?,
+                        theirs: load_stage(STAGE_THEIRS)?,
+                    })
+                }
+
+                logic(&repo.lock(), &path).context("loading conflict stages")
+            })
+            .boxed()
+    }
+
     fn load_committed_text(&self, path: RepoPath) -> BoxFuture<'_, Option<String>> {
         let repo = self.repository.clone();
         self.executor
@@ -1327,6 +2154,35 @@ impl GitRepository for RealGitRepository {
             .boxed()
     }
 
+    fn load_text_at_revision(
+        &self,
+        revision: String,
+        path: RepoPath,
+    ) -> BoxFuture<'_, Result<Option<String>>> {
+        let repo = self.repository.clone();
+        self.executor
+            .spawn(async move {
+                let repo = repo.lock();
+                let tree = repo
+                    .revparse_single(&revision)
+                    .with_context(|| format!("resolving revision {revision}"))?
+                    .peel_to_tree()?;
+                // git2 unwraps internally on empty paths or `.`
+                if path.is_empty() {
+                    bail!("empty path has no text at revision {revision}");
+                }
+                let Some(entry) = tree.get_path(path.as_std_path()).ok() else {
+                    return Ok(None);
+                };
+                if entry.filemode() == i32::from(git2::FileMode::Link) {
+                    return Ok(None);
+                }
+                let content = repo.find_blob(entry.id())?.content().to_owned();
+                Ok(String::from_utf8(content).ok())
+            })
+            .boxed()
+    }
+
     fn load_blob_content(&self, oid: Oid) -> BoxFuture<'_, Result<String>> {
         let repo = self.repository.clone();
         self.executor
@@ -1703,7 +2559,11 @@ impl GitRepository for RealGitRepository {
             .boxed()
     }
 
-    fn change_branch(&self, name: String) -> BoxFuture<'_, Result<()>> {
+    fn change_branch(
+        &self,
+        name: String,
+        progress: RemoteProgressDelegate,
+    ) -> BoxFuture<'_, Result<()>> {
         let repo = self.repository.clone();
         let git_binary = self.git_binary();
         let branch = self.executor.spawn(async move {
@@ -1740,7 +2600,12 @@ impl GitRepository for RealGitRepository {
         self.executor
             .spawn(async move {
                 let branch = branch.await?;
-                git_binary?.run(&["checkout", &branch]).await?;
+                let mut command = git_binary?.build_command(&["checkout", "--progress", &branch]);
+                command
+                    .env(GIT_LFS_FORCE_PROGRESS, "1")
+                    .stdout(Stdio::piped())
+                    .stderr(Stdio::piped());
+                run_remote_process(command.spawn()?, Some(progress)).await?;
                 anyhow::Ok(())
             })
             .boxed()
@@ -1781,65 +2646,195 @@ impl GitRepository for RealGitRepository {
             .boxed()
     }
 
-    fn delete_branch(&self, is_remote: bool, name: String) -> BoxFuture<'_, Result<()>> {
+    fn delete_branch(
+        &self,
+        is_remote: bool,
+        name: String,
+        force: bool,
+    ) -> BoxFuture<'_, Result<()>> {
         let git_binary = self.git_binary();
 
         self.executor
             .spawn(async move {
-                git_binary?
-                    .run(&["branch", if is_remote { "-dr" } else { "-d" }, &name])
+                let flag = match (is_remote, force) {
+                    (true, _) => "-dr",
+                    (false, false) => "-d",
+                    (false, true) => "-D",
+                };
+                let output = git_binary?
+                    .build_command(&["branch", flag, &name])
+                    // The error is checked for unmerged commits, so it mustn't be translated.
+                    .env("LC_ALL", "C")
+                    .output()
                     .await?;
+                if !output.status.success() {
+                    let stderr = String::from_utf8_lossy(&output.stderr);
+                    if stderr.contains("not fully merged") {
+                        bail!("{BRANCH_NOT_FULLY_MERGED}\n{}", stderr.trim());
+                    }
+                    bail!("Failed to delete branch {name}:\n{stderr}");
+                }
                 anyhow::Ok(())
             })
             .boxed()
     }
 
-    fn blame(
+    fn set_branch_upstream(
         &self,
-        path: RepoPath,
-        content: Rope,
-        line_ending: LineEnding,
-    ) -> BoxFuture<'_, Result<crate::blame::Blame>> {
-        let git = self.git_binary();
+        branch: String,
+        upstream: Option<String>,
+    ) -> BoxFuture<'_, Result<()>> {
+        let git_binary = self.git_binary();
 
         self.executor
             .spawn(async move {
-                crate::blame::Blame::for_path(&git?, &path, &content, line_ending).await
+                let git = git_binary?;
+                match upstream {
+                    Some(upstream) => {
+                        git.run(&["branch", &format!("--set-upstream-to={upstream}"), &branch])
+                            .await?;
+                    }
+                    None => {
+                        git.run(&["branch", "--unset-upstream", &branch]).await?;
+                    }
+                }
+                anyhow::Ok(())
             })
             .boxed()
     }
 
-    fn file_history(&self, path: RepoPath) -> BoxFuture<'_, Result<FileHistory>> {
-        self.file_history_paginated(path, 0, None)
+    fn tags(&self) -> BoxFuture<'_, Result<Vec<SharedString>>> {
+        let git_binary = self.git_binary();
+
+        self.executor
+            .spawn(async move {
+                let output = git_binary?
+                    .run(&["tag", "--list", "--sort=-creatordate"])
+                    .await?;
+                Ok(output
+                    .lines()
+                    .filter(|line| !line.is_empty())
+                    .map(|line| SharedString::from(line.to_string()))
+                    .collect())
+            })
+            .boxed()
     }
 
-    fn file_history_paginated(
+    fn create_tag(
         &self,
-        path: RepoPath,
-        skip: usize,
-        limit: Option<usize>,
-    ) -> BoxFuture<'_, Result<FileHistory>> {
+        name: String,
+        message: Option<String>,
+        target: Option<String>,
+    ) -> BoxFuture<'_, Result<()>> {
         let git_binary = self.git_binary();
+
         self.executor
             .spawn(async move {
-                let git = git_binary?;
-                // Use a unique delimiter with a hardcoded UUID to separate commits
-                // This essentially eliminates any chance of encountering the delimiter in actual commit data
-                let commit_delimiter =
-                    concat!("<<COMMIT_END-", "3f8a9c2e-7d4b-4e1a-9f6c-8b5d2a1e4c3f>>",);
+                let mut args = vec!["tag".to_string()];
+                if let Some(message) = message {
+                    args.extend(["-a".to_string(), "-m".to_string(), message]);
+                }
+                args.push(name);
+                args.extend(target);
+                git_binary?.run(&args).await?;
+                anyhow::Ok(())
+            })
+            .boxed()
+    }
 
-                let format_string = format!(
-                    "--pretty=format:%H%x00%s%x00%B%x00%at%x00%an%x00%ae{}",
-                    commit_delimiter
-                );
+    fn delete_tag(&self, name: String) -> BoxFuture<'_, Result<()>> {
+        let git_binary = self.git_binary();
 
-                let mut args = vec!["--no-optional-locks", "log", "--follow", &format_string];
+        self.executor
+            .spawn(async move {
+                git_binary?.run(&["tag", "-d", &name]).await?;
+                anyhow::Ok(())
+            })
+            .boxed()
+    }
 
-                let skip_str;
-                let limit_str;
-                if skip > 0 {
-                    skip_str = skip.to_string();
-                    args.push("--skip");
+    fn blame(
+        &self,
+        path: RepoPath,
+        content: Rope,
+        line_ending: LineEnding,
+        options: BlameOptions,
+    ) -> BoxFuture<'_, Result<crate::blame::Blame>> {
+        let git = self.git_binary();
+        let has_ignore_revs_file = self
+            .repository
+            .lock()
+            .config()
+            .and_then(|config| config.get_string("blame.ignoreRevsFile"))
+            .is_ok_and(|file| !file.is_empty());
+
+        self.executor
+            .spawn(async move {
+                crate::blame::Blame::for_path(
+                    &git?,
+                    &path,
+                    &content,
+                    line_ending,
+                    &options,
+                    has_ignore_revs_file,
+                )
+                .await
+            })
+            .boxed()
+    }
+
+    fn blame_at_revision(
+        &self,
+        revision: String,
+        path: RepoPath,
+    ) -> BoxFuture<'_, Result<crate::blame::Blame>> {
+        let git = self.git_binary();
+
+        self.executor
+            .spawn(async move { crate::blame::Blame::at_revision(&git?, &revision, &path).await })
+            .boxed()
+    }
+
+    fn file_history(&self, path: RepoPath) -> BoxFuture<'_, Result<FileHistory>> {
+        self.file_history_paginated(path, 0, None)
+    }
+
+    fn file_history_paginated(
+        &self,
+        path: RepoPath,
+        skip: usize,
+        limit: Option<usize>,
+    ) -> BoxFuture<'_, Result<FileHistory>> {
+        let git_binary = self.git_binary();
+        self.executor
+            .spawn(async move {
+                let git = git_binary?;
+                // Use a unique delimiter with a hardcoded UUID to separate commits
+                // This essentially eliminates any chance of encountering the delimiter in actual commit data
+                let commit_delimiter =
+                    concat!("<<COMMIT_END-", "3f8a9c2e-7d4b-4e1a-9f6c-8b5d2a1e4c3f>>",);
+
+                // The delimiter starts each commit so that the `--name-status` fields, which git
+                // prints after the formatted commit, stay in the same block as the commit.
+                let format_string = format!(
+                    "--pretty=format:{}%H%x00%s%x00%B%x00%at%x00%an%x00%ae%x00%G?%x00",
+                    commit_delimiter
+                );
+
+                let mut args = vec![
+                    "--no-optional-locks",
+                    "log",
+                    "--follow",
+                    "--name-status",
+                    "-z",
+                    &format_string,
+                ];
+
+                let skip_str;
+                let limit_str;
+                if skip > 0 {
+                    skip_str = skip.to_string();
+                    args.push("--skip");
                     args.push(&skip_str);
                 }
                 if let Some(n) = limit {
@@ -1871,13 +2866,15 @@ impl GitRepository for RealGitRepository {
                     }
 
                     let fields: Vec<&str> = commit_block.split('\0').collect();
-                    if fields.len() >= 6 {
+                    if fields.len() >= 7 {
                         let sha = fields[0].trim().to_string().into();
                         let subject = fields[1].trim().to_string().into();
                         let message = fields[2].trim().to_string().into();
                         let commit_timestamp = fields[3].trim().parse().unwrap_or(0);
                         let author_name = fields[4].trim().to_string().into();
                         let author_email = fields[5].trim().to_string().into();
+                        let signed = !matches!(fields[6].trim(), "" | "N");
+                        let (path, old_path) = parse_file_history_name_status(&fields[7..]);
 
                         entries.push(FileHistoryEntry {
                             sha,
@@ -1886,6 +2883,9 @@ impl GitRepository for RealGitRepository {
                             commit_timestamp,
                             author_name,
                             author_email,
+                            signed,
+                            path,
+                            old_path,
                         });
                     }
                 }
@@ -1895,6 +2895,30 @@ impl GitRepository for RealGitRepository {
             .boxed()
     }
 
+    fn line_history(
+        &self,
+        path: RepoPath,
+        lines: RangeInclusive<u32>,
+    ) -> BoxFuture<'_, Result<Vec<LineHistoryEntry>>> {
+        let git_binary = self.git_binary();
+        self.executor
+            .spawn(async move {
+                let git = git_binary?;
+                let line_range =
+                    format!("-L{},{}:{}", lines.start(), lines.end(), path.as_unix_str());
+                let output = git
+                    .run(&[
+                        "--no-optional-locks",
+                        "log",
+                        LINE_HISTORY_FORMAT,
+                        &line_range,
+                    ])
+                    .await?;
+                parse_line_history(&output)
+            })
+            .boxed()
+    }
+
     fn diff(&self, diff: DiffType) -> BoxFuture<'_, Result<String>> {
         let git_binary = self.git_binary();
         self.executor
@@ -1952,6 +2976,18 @@ impl GitRepository for RealGitRepository {
             .boxed()
     }
 
+    fn renames(&self) -> BoxFuture<'_, Result<Vec<FileRename>>> {
+        let git_binary = self.git_binary();
+        self.executor
+            .spawn(async move {
+                let output = git_binary?
+                    .run(&["diff", "--name-status", "-z", "-M", "-C", "HEAD"])
+                    .await?;
+                parse_renames(&output)
+            })
+            .boxed()
+    }
+
     fn stage_paths(
         &self,
         paths: Vec<RepoPath>,
@@ -2011,14 +3047,22 @@ impl GitRepository for RealGitRepository {
     fn stash_paths(
         &self,
         paths: Vec<RepoPath>,
+        options: StashOptions,
         env: Arc<HashMap<String, String>>,
     ) -> BoxFuture<'_, Result<()>> {
         let git_binary = self.git_binary();
         self.executor
             .spawn(async move {
                 let git = git_binary?;
+                let mut args = vec!["stash", "push", "--quiet"];
+                if options.include_untracked {
+                    args.push("--include-untracked");
+                }
+                if let Some(message) = &options.message {
+                    args.extend(["--message", message.as_str()]);
+                }
                 let output = git
-                    .build_command(&["stash", "push", "--quiet", "--include-untracked"])
+                    .build_command(&args)
                     .envs(env.iter())
                     .args(paths.iter().map(|p| p.as_unix_str()))
                     .output()
@@ -2047,13 +3091,24 @@ impl GitRepository for RealGitRepository {
                 if let Some(index) = index {
                     args.push(format!("stash@{{{}}}", index));
                 }
-                let output = git.build_command(&args).envs(env.iter()).output().await?;
+                // The output is checked for conflicts, so it mustn't be translated.
+                let output = git
+                    .build_command(&args)
+                    .envs(env.iter())
+                    .env("LC_ALL", "C")
+                    .output()
+                    .await?;
 
-                anyhow::ensure!(
-                    output.status.success(),
-                    "Failed to stash pop:\n{}",
-                    String::from_utf8_lossy(&output.stderr)
-                );
+                if !output.status.success() {
+                    let stdout = String::from_utf8_lossy(&output.stdout);
+                    if stdout.contains("CONFLICT") {
+                        bail!("{STASH_APPLY_CONFLICTS}\n{}", stdout.trim());
+                    }
+                    bail!(
+                        "Failed to stash pop:\n{}",
+                        String::from_utf8_lossy(&output.stderr)
+                    );
+                }
                 Ok(())
             })
             .boxed()
@@ -2072,11 +3127,46 @@ impl GitRepository for RealGitRepository {
                 if let Some(index) = index {
                     args.push(format!("stash@{{{}}}", index));
                 }
+                let output = git
+                    .build_command(&args)
+                    .envs(env.iter())
+                    .env("LC_ALL", "C")
+                    .output()
+                    .await?;
+
+                if !output.status.success() {
+                    let stdout = String::from_utf8_lossy(&output.stdout);
+                    if stdout.contains("CONFLICT") {
+                        bail!("{STASH_APPLY_CONFLICTS}\n{}", stdout.trim());
+                    }
+                    bail!(
+                        "Failed to apply stash:\n{}",
+                        String::from_utf8_lossy(&output.stderr)
+                    );
+                }
+                Ok(())
+            })
+            .boxed()
+    }
+
+    fn stash_drop(
+        &self,
+        index: Option<usize>,
+        env: Arc<HashMap<String, String>>,
+    ) -> BoxFuture<'_, Result<()>> {
+        let git_binary = self.git_binary();
+        self.executor
+            .spawn(async move {
+                let git = git_binary?;
+                let mut args = vec!["stash".to_string(), "drop".to_string()];
+                if let Some(index) = index {
+                    args.push(format!("stash@{{{}}}", index));
+                }
                 let output = git.build_command(&args).envs(env.iter()).output().await?;
 
                 anyhow::ensure!(
                     output.status.success(),
-                    "Failed to apply stash:\n{}",
+                    "Failed to stash drop:\n{}",
                     String::from_utf8_lossy(&output.stderr)
                 );
                 Ok(())
@@ -2084,8 +3174,9 @@ impl GitRepository for RealGitRepository {
             .boxed()
     }
 
-    fn stash_drop(
+    fn stash_branch(
         &self,
+        branch_name: String,
         index: Option<usize>,
         env: Arc<HashMap<String, String>>,
     ) -> BoxFuture<'_, Result<()>> {
@@ -2093,7 +3184,7 @@ impl GitRepository for RealGitRepository {
         self.executor
             .spawn(async move {
                 let git = git_binary?;
-                let mut args = vec!["stash".to_string(), "drop".to_string()];
+                let mut args = vec!["stash".to_string(), "branch".to_string(), branch_name];
                 if let Some(index) = index {
                     args.push(format!("stash@{{{}}}", index));
                 }
@@ -2101,7 +3192,7 @@ impl GitRepository for RealGitRepository {
 
                 anyhow::ensure!(
                     output.status.success(),
-                    "Failed to stash drop:\n{}",
+                    "Failed to create a branch from the stash:\n{}",
                     String::from_utf8_lossy(&output.stderr)
                 );
                 Ok(())
@@ -2143,20 +3234,136 @@ impl GitRepository for RealGitRepository {
                 cmd.arg("--author").arg(&format!("{name} <{email}>"));
             }
 
-            run_git_command(env, ask_pass, cmd, executor).await?;
+            if !options.only_paths.is_empty() {
+                cmd.arg("--only")
+                    .arg("--")
+                    .args(options.only_paths.iter().map(|path| path.as_unix_str()));
+            }
+
+            run_git_command(env, ask_pass, None, cmd, executor)
+                .await
+                .map_err(
+                    |error| match commit_signing_failure_hint(&error.to_string()) {
+                        Some(hint) => anyhow!("{COMMIT_SIGNING_FAILED}. {hint}\n\n{error}"),
+                        None => error,
+                    },
+                )?;
 
             Ok(())
         }
         .boxed()
     }
 
+    fn load_commit_template(
+        &self,
+        configured_path: Option<String>,
+    ) -> BoxFuture<'_, Result<Option<String>>> {
+        let git_binary = self.git_binary();
+        let working_directory = self.working_directory();
+        self.executor
+            .spawn(async move {
+                let template_path = match configured_path {
+                    Some(path) => path,
+                    None => {
+                        let git = git_binary?;
+                        let output = git
+                            .build_command(&["config", "--get", "--path", "commit.template"])
+                            .output()
+                            .await?;
+                        if !output.status.success() {
+                            return Ok(None);
+                        }
+                        String::from_utf8_lossy(&output.stdout).trim().to_string()
+                    }
+                };
+                if template_path.is_empty() {
+                    return Ok(None);
+                }
+
+                let template_path = match template_path.strip_prefix("~/") {
+                    Some(relative_to_home) => paths::home_dir().join(relative_to_home),
+                    None => working_directory?.join(template_path),
+                };
+                let template = smol::fs::read_to_string(&template_path)
+                    .await
+                    .with_context(|| {
+                        format!("reading commit template {}", template_path.display())
+                    })?;
+                Ok(Some(template))
+            })
+            .boxed()
+    }
+
+    fn recent_commit_authors(
+        &self,
+        max_commits: usize,
+    ) -> BoxFuture<'_, Result<Vec<(SharedString, SharedString)>>> {
+        let git_binary = self.git_binary();
+        self.executor
+            .spawn(async move {
+                let git = git_binary?;
+                let output = git
+                    .build_command(&["--no-optional-locks", "log", "--format=%an%x00%ae"])
+                    .arg(format!("--max-count={max_commits}"))
+                    .output()
+                    .await?;
+                // Fails in repositories without commits, which have no authors.
+                if !output.status.success() {
+                    return Ok(Vec::new());
+                }
+                Ok(parse_commit_authors(&String::from_utf8_lossy(
+                    &output.stdout,
+                )))
+            })
+            .boxed()
+    }
+
+    fn verify_commit_signatures(
+        &self,
+        shas: Vec<SharedString>,
+    ) -> BoxFuture<'_, Result<HashMap<SharedString, CommitSignature>>> {
+        let git_binary = self.git_binary();
+        self.executor
+            .spawn(async move {
+                if shas.is_empty() {
+                    return Ok(HashMap::default());
+                }
+                // The shas can come from collab guests, so nothing but object ids reaches git.
+                for sha in &shas {
+                    Oid::from_str(sha).with_context(|| format!("invalid commit sha {sha:?}"))?;
+                }
+                let git = git_binary?;
+                let output = git
+                    .build_command(&[
+                        "--no-optional-locks",
+                        "show",
+                        "--no-patch",
+                        "--format=%H%x00%G?%x00%GS%x00%GK",
+                    ])
+                    .args(shas.iter().map(|sha| sha.as_ref()))
+                    .output()
+                    .await?;
+                anyhow::ensure!(
+                    output.status.success(),
+                    "failed to verify commit signatures: {}",
+                    String::from_utf8_lossy(&output.stderr)
+                );
+                Ok(parse_commit_signatures(&String::from_utf8_lossy(
+                    &output.stdout,
+                )))
+            })
+            .boxed()
+    }
+
     fn push(
         &self,
         branch_name: String,
         remote_branch_name: String,
         remote_name: String,
         options: Option<PushOptions>,
+        follow_tags: bool,
         ask_pass: AskPassDelegate,
+        progress: RemoteProgressDelegate,
         env: Arc<HashMap<String, String>>,
         cx: AsyncApp,
     ) -> BoxFuture<'_, Result<RemoteCommandOutput>> {
@@ -2177,20 +3384,29 @@ impl GitRepository for RealGitRepository {
                 executor.clone(),
                 is_trusted,
             );
-            let mut command = git.build_command(&["push"]);
+            let mut command = git.build_command(&["push", "--progress"]);
             command
                 .envs(env.iter())
                 .args(options.map(|option| match option {
                     PushOptions::SetUpstream => "--set-upstream",
                     PushOptions::Force => "--force-with-lease",
                 }))
+                .args(follow_tags.then_some("--follow-tags"))
                 .arg(remote_name)
                 .arg(format!("{}:{}", branch_name, remote_branch_name))
                 .stdin(Stdio::null())
                 .stdout(Stdio::piped())
                 .stderr(Stdio::piped());
 
-            run_git_command(env, ask_pass, command, executor).await
+            run_git_command(env, ask_pass, Some(progress), command, executor)
+                .await
+                .map_err(|error| {
+                    if error.to_string().contains("[rejected]") {
+                        anyhow!("{PUSH_REJECTED}\n{error}")
+                    } else {
+                        error
+                    }
+                })
         }
         .boxed()
     }
@@ -2199,8 +3415,9 @@ impl GitRepository for RealGitRepository {
         &self,
         branch_name: Option<String>,
         remote_name: String,
-        rebase: bool,
+        strategy: Option<PullStrategy>,
         ask_pass: AskPassDelegate,
+        progress: RemoteProgressDelegate,
         env: Arc<HashMap<String, String>>,
         cx: AsyncApp,
     ) -> BoxFuture<'_, Result<RemoteCommandOutput>> {
@@ -2221,20 +3438,32 @@ impl GitRepository for RealGitRepository {
                 executor.clone(),
                 is_trusted,
             );
-            let mut command = git.build_command(&["pull"]);
-            command.envs(env.iter());
-
-            if rebase {
-                command.arg("--rebase");
-            }
-
+            let mut command = git.build_command(&["pull", "--progress"]);
             command
+                .envs(env.iter())
+                .env(GIT_LFS_FORCE_PROGRESS, "1")
+                // A failed fast-forward is told apart by its message, so it mustn't be translated.
+                .env("LC_ALL", "C")
+                .args(strategy.map(|strategy| match strategy {
+                    PullStrategy::Merge => "--no-rebase",
+                    PullStrategy::Rebase => "--rebase",
+                    PullStrategy::FastForwardOnly => "--ff-only",
+                }))
                 .arg(remote_name)
                 .args(branch_name)
                 .stdout(Stdio::piped())
                 .stderr(Stdio::piped());
 
-            run_git_command(env, ask_pass, command, executor).await
+            run_git_command(env, ask_pass, Some(progress), command, executor)
+                .await
+                .map_err(|error| {
+                    // `pull.ff = only` in the git config fails the same way as `--ff-only`.
+                    if error.to_string().contains("Not possible to fast-forward") {
+                        anyhow!("{PULL_NOT_FAST_FORWARD}\n{error}")
+                    } else {
+                        error
+                    }
+                })
         }
         .boxed()
     }
@@ -2243,6 +3472,7 @@ impl GitRepository for RealGitRepository {
         &self,
         fetch_options: FetchOptions,
         ask_pass: AskPassDelegate,
+        progress: RemoteProgressDelegate,
         env: Arc<HashMap<String, String>>,
         cx: AsyncApp,
     ) -> BoxFuture<'_, Result<RemoteCommandOutput>> {
@@ -2264,17 +3494,410 @@ impl GitRepository for RealGitRepository {
                 executor.clone(),
                 is_trusted,
             );
-            let mut command = git.build_command(&["fetch", &remote_name]);
+            let mut command = git.build_command(&["fetch", "--progress", &remote_name]);
             command
                 .envs(env.iter())
                 .stdout(Stdio::piped())
                 .stderr(Stdio::piped());
 
-            run_git_command(env, ask_pass, command, executor).await
+            run_git_command(env, ask_pass, Some(progress), command, executor).await
         }
         .boxed()
     }
 
+    fn fetch_into_branch(
+        &self,
+        remote_name: String,
+        remote_ref: String,
+        branch_name: String,
+        ask_pass: AskPassDelegate,
+        progress: RemoteProgressDelegate,
+        env: Arc<HashMap<String, String>>,
+        cx: AsyncApp,
+    ) -> BoxFuture<'_, Result<RemoteCommandOutput>> {
+        let working_directory = self.working_directory();
+        let git_directory = self.path();
+        let git_binary_path = self.system_git_binary_path.clone();
+        let executor = cx.background_executor().clone();
+        let is_trusted = self.is_trusted();
+        // Note: Do not spawn this command on the background thread, it might pop open the credential helper
+        // which we want to block on.
+        async move {
+            let git_binary_path = git_binary_path.context("git not found on $PATH, can't fetch")?;
+            let working_directory = working_directory?;
+            let git = GitBinary::new(
+                git_binary_path,
+                working_directory,
+                git_directory,
+                executor.clone(),
+                is_trusted,
+            );
+            let local_ref = format!("refs/heads/{branch_name}");
+            let head = git
+                .build_command(&["symbolic-ref", "--quiet", "HEAD"])
+                .output()
+                .await?;
+            anyhow::ensure!(
+                String::from_utf8_lossy(&head.stdout).trim() != local_ref,
+                "{branch_name} is checked out, pull into it to update it instead"
+            );
+
+            // The head is fetched on its own and only moved onto the branch when that is a
+            // fast-forward, so that local commits on the branch are never discarded.
+            let mut command =
+                git.build_command(&["fetch", "--progress", "--", &remote_name, &remote_ref]);
+            command
+                .envs(env.iter())
+                .stdout(Stdio::piped())
+                .stderr(Stdio::piped());
+            let output = run_git_command(env, ask_pass, Some(progress), command, executor).await?;
+
+            let fetched_sha = git
+                .run(&["rev-parse", "--verify", "FETCH_HEAD^{commit}"])
+                .await?;
+            let branch_sha = git
+                .run(&["rev-parse", "--verify", "--quiet", &local_ref])
+                .await
+                .ok();
+            if let Some(branch_sha) = &branch_sha {
+                let is_fast_forward = git
+                    .build_command(&["merge-base", "--is-ancestor", branch_sha, &fetched_sha])
+                    .output()
+                    .await?
+                    .status
+                    .success();
+                anyhow::ensure!(
+                    is_fast_forward,
+                    "{branch_name} has commits the pull request doesn't, \
+                    rename or delete it to check the pull request out again"
+                );
+            }
+            git.run(&[
+                "update-ref",
+                &local_ref,
+                &fetched_sha,
+                branch_sha.as_deref().unwrap_or(""),
+            ])
+            .await?;
+            Ok(output)
+        }
+        .boxed()
+    }
+
+    fn lfs_tracked_paths(&self, paths: Vec<RepoPath>) -> BoxFuture<'_, Result<HashSet<RepoPath>>> {
+        let git_binary = self.git_binary();
+        self.executor
+            .spawn(async move {
+                if paths.is_empty() {
+                    return Ok(HashSet::default());
+                }
+                let git = git_binary?;
+                let mut child = git
+                    .build_command(&["check-attr", "-z", "--stdin", "filter"])
+                    .stdin(Stdio::piped())
+                    .stdout(Stdio::piped())
+                    .stderr(Stdio::piped())
+                    .spawn()?;
+                let mut stdin = child.stdin.take().context("failed to get stdin")?;
+                for path in &paths {
+                    stdin.write_all(path.as_unix_str().as_bytes()).await?;
+                    stdin.write_all(b"\0").await?;
+                }
+                stdin.flush().await?;
+                drop(stdin);
+                let output = child.output().await?;
+                anyhow::ensure!(
+                    output.status.success(),
+                    "Failed to check LFS attributes:\n{}",
+                    String::from_utf8_lossy(&output.stderr)
+                );
+                Ok(crate::lfs::parse_lfs_tracked_paths(
+                    &String::from_utf8_lossy(&output.stdout),
+                ))
+            })
+            .boxed()
+    }
+
+    fn fetch_lfs_objects(
+        &self,
+        paths: Vec<RepoPath>,
+        ask_pass: AskPassDelegate,
+        progress: RemoteProgressDelegate,
+        env: Arc<HashMap<String, String>>,
+        cx: AsyncApp,
+    ) -> BoxFuture<'_, Result<RemoteCommandOutput>> {
+        let working_directory = self.working_directory();
+        let git_directory = self.path();
+        let git_binary_path = self.system_git_binary_path.clone();
+        let executor = cx.background_executor().clone();
+        let is_trusted = self.is_trusted();
+        // Note: Do not spawn this command on the background thread, it might pop open the credential helper
+        // which we want to block on.
+        async move {
+            let git_binary_path =
+                git_binary_path.context("git not found on $PATH, can't fetch LFS objects")?;
+            let working_directory = working_directory?;
+            let git = GitBinary::new(
+                git_binary_path,
+                working_directory,
+                git_directory,
+                executor.clone(),
+                is_trusted,
+            );
+            let mut args = vec!["lfs".to_string(), "pull".to_string()];
+            for path in &paths {
+                args.push("--include".to_string());
+                args.push(lfs_include_pattern(path.as_unix_str()));
+            }
+            let mut command = git.build_command(&args);
+            command
+                .envs(env.iter())
+                .env(GIT_LFS_FORCE_PROGRESS, "1")
+                .stdout(Stdio::piped())
+                .stderr(Stdio::piped());
+
+            run_git_command(env, ask_pass, Some(progress), command, executor).await
+        }
+        .boxed()
+    }
+
+    fn submodules(&self) -> BoxFuture<'_, Result<Vec<Submodule>>> {
+        let git_binary = self.git_binary();
+        self.executor
+            .spawn(async move {
+                let output = git_binary?.run(&["submodule", "status"]).await?;
+                Ok(parse_submodules(&output))
+            })
+            .boxed()
+    }
+
+    fn update_submodules(
+        &self,
+        paths: Vec<RepoPath>,
+        init: bool,
+        ask_pass: AskPassDelegate,
+        progress: RemoteProgressDelegate,
+        env: Arc<HashMap<String, String>>,
+        cx: AsyncApp,
+    ) -> BoxFuture<'_, Result<RemoteCommandOutput>> {
+        let working_directory = self.working_directory();
+        let git_directory = self.path();
+        let git_binary_path = self.system_git_binary_path.clone();
+        let executor = cx.background_executor().clone();
+        let is_trusted = self.is_trusted();
+        // Note: Do not spawn this command on the background thread, it might pop open the credential helper
+        // which we want to block on.
+        async move {
+            let git_binary_path =
+                git_binary_path.context("git not found on $PATH, can't update submodules")?;
+            let working_directory = working_directory?;
+            let git = GitBinary::new(
+                git_binary_path,
+                working_directory,
+                git_directory,
+                executor.clone(),
+                is_trusted,
+            );
+            let mut command = git.build_command(&["submodule", "update", "--progress"]);
+            command
+                .envs(env.iter())
+                .env(GIT_LFS_FORCE_PROGRESS, "1")
+                .args(init.then_some("--init"))
+                .arg("--")
+                .args(paths.iter().map(|path| path.as_unix_str()))
+                .stdout(Stdio::piped())
+                .stderr(Stdio::piped());
+
+            run_git_command(env, ask_pass, Some(progress), command, executor).await
+        }
+        .boxed()
+    }
+
+    fn sync_submodules(&self, paths: Vec<RepoPath>) -> BoxFuture<'_, Result<()>> {
+        let git_binary = self.git_binary();
+        self.executor
+            .spawn(async move {
+                let git = git_binary?;
+                let output = git
+                    .build_command(&["submodule", "sync", "--"])
+                    .args(paths.iter().map(|path| path.as_unix_str()))
+                    .output()
+                    .await?;
+                anyhow::ensure!(
+                    output.status.success(),
+                    "Failed to sync submodules:\n{}",
+                    String::from_utf8_lossy(&output.stderr)
+                );
+                Ok(())
+            })
+            .boxed()
+    }
+
+    fn sparse_checkout(&self) -> BoxFuture<'_, Result<Option<SparseCheckout>>> {
+        let git_binary = self.git_binary();
+        self.executor
+            .spawn(async move {
+                let git = git_binary?;
+                if !config_is_enabled(&git, "core.sparseCheckout").await {
+                    return Ok(None);
+                }
+                let cone = config_is_enabled(&git, "core.sparseCheckoutCone").await;
+                let patterns = git
+                    .run(&["sparse-checkout", "list"])
+                    .await?
+                    .lines()
+                    .map(|pattern| {
+                        let pattern = pattern.trim();
+                        // Cone mode lists directories, which may come with slashes around them.
+                        if cone {
+                            pattern.trim_matches('/').to_string()
+                        } else {
+                            pattern.to_string()
+                        }
+                    })
+                    .filter(|pattern| !pattern.is_empty())
+                    .collect::<Vec<_>>();
+
+                let mut excluded_directories = Vec::new();
+                if cone {
+                    let mut subdirectories = Vec::new();
+                    for parent in cone_parent_directories(&patterns) {
+                        let tree = if parent.is_empty() {
+                            "HEAD".to_string()
+                        } else {
+                            format!("HEAD:{parent}")
+                        };
+                        let output = git
+                            .run_raw(&["ls-tree", "-d", "--name-only", "-z", &tree])
+                            .await?;
+                        subdirectories.extend(
+                            output
+                                .split('\0')
+                                .filter(|name| !name.is_empty())
+                                .map(|name| {
+                                    if parent.is_empty() {
+                                        name.to_string()
+                                    } else {
+                                        format!("{parent}/{name}")
+                                    }
+                                }),
+                        );
+                    }
+                    excluded_directories = excluded_cone_directories(&patterns, subdirectories);
+                }
+
+                Ok(Some(SparseCheckout {
+                    cone,
+                    patterns,
+                    excluded_directories,
+                }))
+            })
+            .boxed()
+    }
+
+    fn set_sparse_checkout(&self, cone: bool, patterns: Vec<String>) -> BoxFuture<'_, Result<()>> {
+        let git_binary = self.git_binary();
+        self.executor
+            .spawn(async move {
+                let git = git_binary?;
+                let output = git
+                    .build_command(&[
+                        "sparse-checkout",
+                        "set",
+                        if cone { "--cone" } else { "--no-cone" },
+                        "--",
+                    ])
+                    .args(&patterns)
+                    .output()
+                    .await?;
+                anyhow::ensure!(
+                    output.status.success(),
+                    "Failed to set the sparse checkout:\n{}",
+                    String::from_utf8_lossy(&output.stderr)
+                );
+                Ok(())
+            })
+            .boxed()
+    }
+
+    fn disable_sparse_checkout(&self) -> BoxFuture<'_, Result<()>> {
+        let git_binary = self.git_binary();
+        self.executor
+            .spawn(async move {
+                git_binary?.run(&["sparse-checkout", "disable"]).await?;
+                Ok(())
+            })
+            .boxed()
+    }
+
+    fn ignore_rule(&self, path: RepoPath) -> BoxFuture<'_, Result<Option<IgnoreRule>>> {
+        let git_binary = self.git_binary();
+        self.executor
+            .spawn(async move {
+                let output = git_binary?
+                    .build_command(&["check-ignore", "-v", "-z", "--"])
+                    .arg(path.as_unix_str())
+                    .output()
+                    .await?;
+                // Exiting with 1 means that no pattern matched.
+                if output.status.code() == Some(1) {
+                    return Ok(None);
+                }
+                anyhow::ensure!(
+                    output.status.success(),
+                    "Failed to check ignore rules:\n{}",
+                    String::from_utf8_lossy(&output.stderr)
+                );
+                Ok(parse_check_ignore(&String::from_utf8_lossy(&output.stdout)))
+            })
+            .boxed()
+    }
+
+    fn flagged_files(&self) -> BoxFuture<'_, Result<Vec<FlaggedFile>>> {
+        let git_binary = self.git_binary();
+        let working_directory = self.working_directory();
+        self.executor
+            .spawn(async move {
+                let working_directory = working_directory?;
+                let output = git_binary?.run(&["ls-files", "-v", "-z"]).await?;
+                let mut flagged_files = Vec::new();
+                for flagged_file in parse_flagged_files(&output)? {
+                    // A sparse checkout sets skip-worktree on the files it leaves out, which
+                    // aren't in the working tree.
+                    let abs_path = working_directory.join(flagged_file.path.as_std_path());
+                    if smol::fs::symlink_metadata(&abs_path).await.is_ok() {
+                        flagged_files.push(flagged_file);
+                    }
+                }
+                Ok(flagged_files)
+            })
+            .boxed()
+    }
+
+    fn set_index_flag(
+        &self,
+        paths: Vec<RepoPath>,
+        flag: IndexFlag,
+        value: bool,
+    ) -> BoxFuture<'_, Result<()>> {
+        let git_binary = self.git_binary();
+        self.executor
+            .spawn(async move {
+                let output = git_binary?
+                    .build_command(&["update-index", flag.update_index_option(value), "--"])
+                    .args(paths.iter().map(|path| path.as_unix_str()))
+                    .output()
+                    .await?;
+                anyhow::ensure!(
+                    output.status.success(),
+                    "Failed to update the index flags:\n{}",
+                    String::from_utf8_lossy(&output.stderr)
+                );
+                Ok(())
+            })
+            .boxed()
+    }
+
     fn get_push_remote(&self, branch: String) -> BoxFuture<'_, Result<Option<Remote>>> {
         let git_binary = self.git_binary();
         self.executor
@@ -2352,24 +3975,133 @@ impl GitRepository for RealGitRepository {
             .boxed()
     }
 
-    fn remove_remote(&self, name: String) -> BoxFuture<'_, Result<()>> {
-        let repo = self.repository.clone();
+    fn remove_remote(&self, name: String) -> BoxFuture<'_, Result<()>> {
+        let repo = self.repository.clone();
+        self.executor
+            .spawn(async move {
+                let repo = repo.lock();
+                repo.remote_delete(&name)?;
+
+                Ok(())
+            })
+            .boxed()
+    }
+
+    fn create_remote(&self, name: String, url: String) -> BoxFuture<'_, Result<()>> {
+        let repo = self.repository.clone();
+        self.executor
+            .spawn(async move {
+                let repo = repo.lock();
+                repo.remote(&name, url.as_ref())?;
+                Ok(())
+            })
+            .boxed()
+    }
+
+    fn remote_infos(&self) -> BoxFuture<'_, Result<Vec<RemoteInfo>>> {
+        let git_binary = self.git_binary();
+        self.executor
+            .spawn(async move {
+                let git = git_binary?;
+                let output = git.build_command(&["remote", "-v"]).output().await?;
+                anyhow::ensure!(
+                    output.status.success(),
+                    "Failed to list remotes:\n{}",
+                    String::from_utf8_lossy(&output.stderr)
+                );
+                let default_remote = git
+                    .build_command(&["config", "--get", "remote.pushDefault"])
+                    .output()
+                    .await?;
+                let default_remote = String::from_utf8_lossy(&default_remote.stdout);
+                Ok(parse_remote_infos(
+                    &String::from_utf8_lossy(&output.stdout),
+                    Some(default_remote.trim()).filter(|name| !name.is_empty()),
+                ))
+            })
+            .boxed()
+    }
+
+    fn rename_remote(&self, old_name: String, new_name: String) -> BoxFuture<'_, Result<()>> {
+        let git_binary = self.git_binary();
+        self.executor
+            .spawn(async move {
+                let git = git_binary?;
+                let output = git
+                    .build_command(&["remote", "rename", &old_name, &new_name])
+                    .output()
+                    .await?;
+                anyhow::ensure!(
+                    output.status.success(),
+                    "Failed to rename remote {old_name}:\n{}",
+                    String::from_utf8_lossy(&output.stderr)
+                );
+                Ok(())
+            })
+            .boxed()
+    }
+
+    fn set_remote_push_url(&self, name: String, url: Option<String>) -> BoxFuture<'_, Result<()>> {
+        let git_binary = self.git_binary();
         self.executor
             .spawn(async move {
-                let repo = repo.lock();
-                repo.remote_delete(&name)?;
-
+                let git = git_binary?;
+                let output = match url {
+                    Some(url) => {
+                        git.build_command(&["remote", "set-url", "--push", &name, &url])
+                            .output()
+                            .await?
+                    }
+                    None => {
+                        let output = git
+                            .build_command(&["config", "--unset-all"])
+                            .arg(format!("remote.{name}.pushurl"))
+                            .output()
+                            .await?;
+                        // Exit code 5 means there was no push URL to unset.
+                        if output.status.code() == Some(5) {
+                            return Ok(());
+                        }
+                        output
+                    }
+                };
+                anyhow::ensure!(
+                    output.status.success(),
+                    "Failed to set the push URL of remote {name}:\n{}",
+                    String::from_utf8_lossy(&output.stderr)
+                );
                 Ok(())
             })
             .boxed()
     }
 
-    fn create_remote(&self, name: String, url: String) -> BoxFuture<'_, Result<()>> {
-        let repo = self.repository.clone();
+    fn set_default_remote(&self, name: Option<String>) -> BoxFuture<'_, Result<()>> {
+        let git_binary = self.git_binary();
         self.executor
             .spawn(async move {
-                let repo = repo.lock();
-                repo.remote(&name, url.as_ref())?;
+                let git = git_binary?;
+                let output = match &name {
+                    Some(name) => {
+                        git.build_command(&["config", "remote.pushDefault", name])
+                            .output()
+                            .await?
+                    }
+                    None => {
+                        let output = git
+                            .build_command(&["config", "--unset", "remote.pushDefault"])
+                            .output()
+                            .await?;
+                        if output.status.code() == Some(5) {
+                            return Ok(());
+                        }
+                        output
+                    }
+                };
+                anyhow::ensure!(
+                    output.status.success(),
+                    "Failed to set the default remote:\n{}",
+                    String::from_utf8_lossy(&output.stderr)
+                );
                 Ok(())
             })
             .boxed()
@@ -2599,6 +4331,7 @@ impl GitRepository for RealGitRepository {
         &self,
         hook: RunHook,
         env: Arc<HashMap<String, String>>,
+        output: Sender<String>,
     ) -> BoxFuture<'_, Result<()>> {
         let git_binary = self.git_binary();
         let repository = self.repository.clone();
@@ -2617,20 +4350,9 @@ impl GitRepository for RealGitRepository {
                 let hook_abs_path = repository.lock().path().join("hooks").join(hook.as_str());
                 if hook_abs_path.is_file() && git_binary.is_trusted {
                     #[allow(clippy::disallowed_methods)]
-                    let output = new_command(&hook_abs_path)
-                        .envs(env.iter())
-                        .current_dir(&working_directory)
-                        .output()
-                        .await?;
-
-                    if !output.status.success() {
-                        return Err(GitBinaryCommandError {
-                            stdout: String::from_utf8_lossy(&output.stdout).into_owned(),
-                            stderr: String::from_utf8_lossy(&output.stderr).into_owned(),
-                            status: output.status,
-                        }
-                        .into());
-                    }
+                    let mut command = new_command(&hook_abs_path);
+                    command.envs(env.iter()).current_dir(&working_directory);
+                    run_streaming_command(command, output).await?;
                 }
 
                 return Ok(());
@@ -2638,9 +4360,9 @@ impl GitRepository for RealGitRepository {
 
             if git_binary.is_trusted {
                 let git_binary = git_binary.envs(HashMap::clone(&env));
-                git_binary
-                    .run(&["hook", "run", "--ignore-missing", hook.as_str()])
-                    .await?;
+                let command =
+                    git_binary.build_command(&["hook", "run", "--ignore-missing", hook.as_str()]);
+                run_streaming_command(command, output).await?;
             }
             Ok(())
         }
@@ -2726,6 +4448,325 @@ impl GitRepository for RealGitRepository {
         })
     }
 
+    fn search_commits(
+        &self,
+        log_source: LogSource,
+        query: String,
+    ) -> BoxFuture<'_, Result<Vec<Oid>>> {
+        let git_binary = self.git_binary();
+        self.executor
+            .spawn(async move {
+                let git = git_binary?;
+                let mut matches = HashSet::new();
+                // Combining `--grep` and `--author` in one invocation only yields commits that
+                // match both, so messages and authors are searched separately.
+                for filter in ["--grep", "--author"] {
+                    let output = git
+                        .build_command(&[
+                            "--no-optional-locks",
+                            "log",
+                            "--format=%H",
+                            "--regexp-ignore-case",
+                            "--fixed-strings",
+                            log_source.get_arg()?,
+                        ])
+                        .arg(format!("{filter}={query}"))
+                        .output()
+                        .await?;
+                    anyhow::ensure!(
+                        output.status.success(),
+                        "failed to search commits: {}",
+                        String::from_utf8_lossy(&output.stderr)
+                    );
+                    matches.extend(
+                        String::from_utf8_lossy(&output.stdout)
+                            .lines()
+                            .filter_map(|line| line.parse::<Oid>().ok()),
+                    );
+                }
+                Ok(matches.into_iter().collect())
+            })
+            .boxed()
+    }
+
+    fn commits_between(
+        &self,
+        base: String,
+        head: String,
+    ) -> BoxFuture<'_, Result<Vec<CommitSummary>>> {
+        let git_binary = self.git_binary();
+        self.executor
+            .spawn(async move {
+                let git = git_binary?;
+                let output = git
+                    .build_command(&[
+                        "--no-optional-locks",
+                        "log",
+                        "--format=%H%x00%s%x00%ct%x00%an%x00%P",
+                    ])
+                    .arg(format!("{base}..{head}"))
+                    .arg("--")
+                    .output()
+                    .await?;
+                anyhow::ensure!(
+                    output.status.success(),
+                    "failed to list commits between {base} and {head}: {}",
+                    String::from_utf8_lossy(&output.stderr)
+                );
+                Ok(parse_commit_summaries(&String::from_utf8_lossy(
+                    &output.stdout,
+                )))
+            })
+            .boxed()
+    }
+
+    fn rebase_todo(&self, base: String) -> BoxFuture<'_, Result<Vec<RebaseTodoEntry>>> {
+        let git_binary = self.git_binary();
+        self.executor
+            .spawn(async move {
+                let git = git_binary?;
+                let output = git
+                    .build_command(&[
+                        "--no-optional-locks",
+                        "log",
+                        "--reverse",
+                        "--topo-order",
+                        "--no-merges",
+                        "--format=%x1e%H%x00%B",
+                    ])
+                    .arg(format!("{base}..HEAD"))
+                    .output()
+                    .await?;
+                anyhow::ensure!(
+                    output.status.success(),
+                    "failed to list commits after {base}: {}",
+                    String::from_utf8_lossy(&output.stderr)
+                );
+                Ok(parse_rebase_todo(&String::from_utf8_lossy(&output.stdout)))
+            })
+            .boxed()
+    }
+
+    fn interactive_rebase(
+        &self,
+        base: String,
+        todo: Vec<RebaseTodoEntry>,
+        env: Arc<HashMap<String, String>>,
+    ) -> BoxFuture<'_, Result<bool>> {
+        let git_binary = self.git_binary();
+        let git_directory = self.path();
+        async move {
+            let git = git_binary?;
+            // Reworded messages are applied by `exec` steps that may run after the rebase stops
+            // and is continued, so they are kept in the git directory rather than a temporary one.
+            let messages_directory = git_directory.join(REBASE_MESSAGES_DIRECTORY);
+            if smol::fs::metadata(&messages_directory).await.is_ok() {
+                smol::fs::remove_dir_all(&messages_directory).await?;
+            }
+            smol::fs::create_dir_all(&messages_directory).await?;
+            for (index, entry) in todo.iter().enumerate() {
+                if entry.action == RebaseAction::Reword {
+                    smol::fs::write(
+                        messages_directory.join(index.to_string()),
+                        entry.message.as_bytes(),
+                    )
+                    .await?;
+                }
+            }
+            let todo_path = git_directory.join(REBASE_TODO_FILE);
+            smol::fs::write(&todo_path, rebase_todo_script(&todo, &messages_directory)).await?;
+
+            let output = git
+                .build_command(&["rebase", "--interactive", &base])
+                .envs(env.iter())
+                .env(
+                    "GIT_SEQUENCE_EDITOR",
+                    format!("cp {}", shell_quote(&todo_path.to_string_lossy())),
+                )
+                .env("GIT_EDITOR", "true")
+                .output()
+                .await?;
+            smol::fs::remove_file(&todo_path).await.log_err();
+            let stopped = read_in_progress_operation(&git_directory).await.is_some();
+            anyhow::ensure!(
+                output.status.success() || stopped,
+                "Failed to rebase:\n{}",
+                String::from_utf8_lossy(&output.stderr),
+            );
+            Ok(stopped)
+        }
+        .boxed()
+    }
+
+    fn cherry_pick(
+        &self,
+        shas: Vec<String>,
+        record_origin: bool,
+        env: Arc<HashMap<String, String>>,
+    ) -> BoxFuture<'_, Result<bool>> {
+        let git_binary = self.git_binary();
+        let git_directory = self.path();
+        async move {
+            let git = git_binary?;
+            anyhow::ensure!(!shas.is_empty(), "no commits to cherry-pick");
+            for sha in &shas {
+                Oid::from_str(sha).with_context(|| format!("invalid commit sha {sha:?}"))?;
+            }
+            let mut args = vec!["cherry-pick".to_string()];
+            if record_origin {
+                args.push("-x".to_string());
+            }
+            args.extend(shas);
+            let output = git
+                .build_command(&args)
+                .envs(env.iter())
+                .env("GIT_EDITOR", "true")
+                .output()
+                .await?;
+            let stopped = read_in_progress_operation(&git_directory).await.is_some();
+            anyhow::ensure!(
+                output.status.success() || stopped,
+                "Failed to cherry-pick:\n{}",
+                String::from_utf8_lossy(&output.stderr),
+            );
+            Ok(stopped)
+        }
+        .boxed()
+    }
+
+    fn revert_commit(
+        &self,
+        sha: String,
+        no_commit: bool,
+        env: Arc<HashMap<String, String>>,
+    ) -> BoxFuture<'_, Result<Option<String>>> {
+        let git_binary = self.git_binary();
+        let git_directory = self.path();
+        let comment_prefix = self.comment_prefix();
+        async move {
+            let git = git_binary?;
+            let mut args = vec!["revert", "--no-edit"];
+            if no_commit {
+                args.push("--no-commit");
+            }
+            args.push(&sha);
+            let output = git.build_command(&args).envs(env.iter()).output().await?;
+            let stopped = read_in_progress_operation(&git_directory).await.is_some();
+            anyhow::ensure!(
+                output.status.success() || stopped,
+                "Failed to revert {sha}:\n{}",
+                String::from_utf8_lossy(&output.stderr),
+            );
+            if !no_commit && !stopped {
+                return Ok(None);
+            }
+            let message = smol::fs::read_to_string(git_directory.join("MERGE_MSG"))
+                .await
+                .ok();
+            Ok(message.map(|message| strip_message_comments(&message, &comment_prefix)))
+        }
+        .boxed()
+    }
+
+    fn bisect_start(
+        &self,
+        bad: String,
+        good: String,
+        env: Arc<HashMap<String, String>>,
+    ) -> BoxFuture<'_, Result<BisectStatus>> {
+        let git_binary = self.git_binary();
+        async move {
+            let git = git_binary?;
+            let output = git
+                .build_command(&["bisect", "start", &bad, &good, "--"])
+                .envs(env.iter())
+                // The output is parsed, so it mustn't be translated.
+                .env("LC_ALL", "C")
+                .output()
+                .await?;
+            parse_bisect_command_output("start bisecting", &output)
+        }
+        .boxed()
+    }
+
+    fn bisect_mark(
+        &self,
+        mark: BisectMark,
+        env: Arc<HashMap<String, String>>,
+    ) -> BoxFuture<'_, Result<BisectStatus>> {
+        let git_binary = self.git_binary();
+        async move {
+            let git = git_binary?;
+            let output = git
+                .build_command(&["bisect", mark.command()])
+                .envs(env.iter())
+                .env("LC_ALL", "C")
+                .output()
+                .await?;
+            parse_bisect_command_output(&format!("mark the commit {}", mark.command()), &output)
+        }
+        .boxed()
+    }
+
+    fn bisect_reset(&self, env: Arc<HashMap<String, String>>) -> BoxFuture<'_, Result<()>> {
+        let git_binary = self.git_binary();
+        async move {
+            let git = git_binary?;
+            let output = git
+                .build_command(&["bisect", "reset"])
+                .envs(env.iter())
+                .output()
+                .await?;
+            anyhow::ensure!(
+                output.status.success(),
+                "Failed to reset the bisect:\n{}",
+                String::from_utf8_lossy(&output.stderr),
+            );
+            Ok(())
+        }
+        .boxed()
+    }
+
+    fn in_progress_operation(&self) -> BoxFuture<'_, Option<InProgressOperation>> {
+        let git_directory = self.path();
+        async move { read_in_progress_operation(&git_directory).await }.boxed()
+    }
+
+    fn resolve_in_progress_operation(
+        &self,
+        kind: InProgressOperationKind,
+        resolution: OperationResolution,
+        env: Arc<HashMap<String, String>>,
+    ) -> BoxFuture<'_, Result<bool>> {
+        let git_binary = self.git_binary();
+        let git_directory = self.path();
+        async move {
+            let git = git_binary?;
+            let before = read_in_progress_operation(&git_directory).await;
+            let head_before = read_in_progress_operation_head(&git_directory, kind).await;
+            let output = git
+                .build_command(&[kind.command(), resolution.flag()])
+                .envs(env.iter())
+                .env("GIT_EDITOR", "true")
+                .output()
+                .await?;
+            let after = read_in_progress_operation(&git_directory).await;
+            let head_after = read_in_progress_operation_head(&git_directory, kind).await;
+            // Git exits with an error when the next step conflicts too, which is only a failure
+            // if the operation didn't move on.
+            let moved_on = after != before || head_after != head_before;
+            anyhow::ensure!(
+                output.status.success() || (after.is_some() && moved_on),
+                "Failed to {} the {}:\n{}",
+                resolution.flag().trim_start_matches('-'),
+                kind.command(),
+                String::from_utf8_lossy(&output.stderr),
+            );
+            Ok(after.is_some())
+        }
+        .boxed()
+    }
+
     fn set_trusted(&self, trusted: bool) {
         self.is_trusted
             .store(trusted, std::sync::atomic::Ordering::Release);
@@ -3057,40 +5098,99 @@ impl GitBinary {
             command.arg("--no-ext-diff");
         }
 
-        if let Some(index_file_path) = self.index_file_path.as_ref() {
-            command.env("GIT_INDEX_FILE", index_file_path);
-        }
-        command.envs(&self.envs);
-        command
-    }
+        if let Some(index_file_path) = self.index_file_path.as_ref() {
+            command.env("GIT_INDEX_FILE", index_file_path);
+        }
+        command.envs(&self.envs);
+        command
+    }
+}
+
+#[derive(Error, Debug)]
+#[error("Git command failed:\n{stdout}{stderr}\n")]
+struct GitBinaryCommandError {
+    stdout: String,
+    stderr: String,
+    status: ExitStatus,
+}
+
+/// Runs a command, sending each line it prints to stdout or stderr to `output` as it's printed.
+async fn run_streaming_command(
+    mut command: util::command::Command,
+    output: Sender<String>,
+) -> Result<()> {
+    command.stdout(Stdio::piped()).stderr(Stdio::piped());
+    let mut child = command.spawn()?;
+    let stdout = child.stdout.take().context("failed to get stdout")?;
+    let stderr = child.stderr.take().context("failed to get stderr")?;
+    let (stdout, stderr) = futures::future::join(
+        forward_lines(stdout, output.clone()),
+        forward_lines(stderr, output),
+    )
+    .await;
+    let status = child.status().await?;
+    anyhow::ensure!(
+        status.success(),
+        GitBinaryCommandError {
+            stdout: stdout?,
+            stderr: stderr?,
+            status,
+        }
+    );
+    Ok(())
+}
+
+async fn forward_lines(
+    stream: impl smol::io::AsyncRead + Unpin,
+    output: Sender<String>,
+) -> Result<String> {
+    let mut reader = BufReader::new(stream);
+    let mut collected = String::new();
+    let mut line = String::new();
+    while reader.read_line(&mut line).await? > 0 {
+        // The receiver going away only means nobody is watching the output anymore.
+        output.send(line.clone()).await.ok();
+        collected.push_str(&line);
+        line.clear();
+    }
+    Ok(collected)
+}
+
+/// Whether a boolean git config value is set to true. Reading a value that isn't set fails, so
+/// unset values count as false.
+async fn config_is_enabled(git: &GitBinary, key: &str) -> bool {
+    git.run(&["config", "--type=bool", key])
+        .await
+        .is_ok_and(|value| value == "true")
 }
 
-#[derive(Error, Debug)]
-#[error("Git command failed:\n{stdout}{stderr}\n")]
-struct GitBinaryCommandError {
-    stdout: String,
-    stderr: String,
-    status: ExitStatus,
+/// Runs a git command that talks to a remote without a repository to run in, such as
+/// `git clone`, reporting its progress until it exits or is cancelled.
+pub async fn run_remote_command(
+    mut command: util::command::Command,
+    progress: Option<RemoteProgressDelegate>,
+) -> Result<RemoteCommandOutput> {
+    command
+        .kill_on_drop(true)
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped());
+    let git_process = command.spawn()?;
+    run_remote_process(git_process, progress).await
 }
 
 async fn run_git_command(
     env: Arc<HashMap<String, String>>,
     ask_pass: AskPassDelegate,
+    progress: Option<RemoteProgressDelegate>,
     mut command: util::command::Command,
     executor: BackgroundExecutor,
 ) -> Result<RemoteCommandOutput> {
+    // Cancelling drops the process, which has to stop git rather than leave it running.
+    command.kill_on_drop(true);
     if env.contains_key("GIT_ASKPASS") {
         let git_process = command.spawn()?;
-        let output = git_process.output().await?;
-        anyhow::ensure!(
-            output.status.success(),
-            "{}",
-            String::from_utf8_lossy(&output.stderr)
-        );
-        Ok(RemoteCommandOutput {
-            stdout: String::from_utf8_lossy(&output.stdout).to_string(),
-            stderr: String::from_utf8_lossy(&output.stderr).to_string(),
-        })
+        run_remote_process(git_process, progress).await
     } else {
         let ask_pass = AskPassSession::new(executor, ask_pass).await?;
         command
@@ -3099,13 +5199,14 @@ async fn run_git_command(
             .env("SSH_ASKPASS_REQUIRE", "force");
         let git_process = command.spawn()?;
 
-        run_askpass_command(ask_pass, git_process).await
+        run_askpass_command(ask_pass, git_process, progress).await
     }
 }
 
 async fn run_askpass_command(
     mut ask_pass: AskPassSession,
     git_process: util::command::Child,
+    progress: Option<RemoteProgressDelegate>,
 ) -> anyhow::Result<RemoteCommandOutput> {
     select_biased! {
         result = ask_pass.run().fuse() => {
@@ -3118,19 +5219,92 @@ async fn run_askpass_command(
                 }
             }
         }
-        output = git_process.output().fuse() => {
-            let output = output?;
-            anyhow::ensure!(
-                output.status.success(),
-                "{}",
-                String::from_utf8_lossy(&output.stderr)
-            );
-            Ok(RemoteCommandOutput {
-                stdout: String::from_utf8_lossy(&output.stdout).to_string(),
-                stderr: String::from_utf8_lossy(&output.stderr).to_string(),
-            })
+        output = run_remote_process(git_process, progress).fuse() => output,
+    }
+}
+
+/// Waits for a git process that talks to a remote, forwarding the progress it prints on stderr
+/// until it exits or is cancelled.
+async fn run_remote_process(
+    mut git_process: util::command::Child,
+    progress: Option<RemoteProgressDelegate>,
+) -> Result<RemoteCommandOutput> {
+    let (progress_tx, cancel_rx) = match progress {
+        Some(progress) => (Some(progress.progress_tx), Some(progress.cancel_rx)),
+        None => (None, None),
+    };
+    let cancelled = async move {
+        match cancel_rx {
+            Some(cancel_rx) if cancel_rx.await.is_ok() => {}
+            // Nobody can cancel anymore, so the process runs to completion.
+            _ => futures::future::pending::<()>().await,
+        }
+    };
+
+    let output = async move {
+        let mut stdout = git_process.stdout.take().context("failed to get stdout")?;
+        let stderr = git_process.stderr.take().context("failed to get stderr")?;
+        let read_stdout = async {
+            let mut stdout_bytes = Vec::new();
+            stdout.read_to_end(&mut stdout_bytes).await?;
+            anyhow::Ok(String::from_utf8_lossy(&stdout_bytes).to_string())
+        };
+        let (stdout, stderr) =
+            futures::future::try_join(read_stdout, forward_progress(stderr, progress_tx)).await?;
+        let status = git_process.status().await?;
+        anyhow::ensure!(status.success(), "{stderr}");
+        Ok(RemoteCommandOutput { stdout, stderr })
+    };
+
+    select_biased! {
+        _ = cancelled.fuse() => Err(anyhow!(REMOTE_CANCELLED_BY_USER)),
+        output = output.fuse() => output,
+    }
+}
+
+/// Reads git's stderr, reporting each progress update to `progress_tx`. Progress lines are
+/// redrawn in place with a carriage return, so only the lines that end in a newline are kept.
+async fn forward_progress(
+    mut stream: impl smol::io::AsyncRead + Unpin,
+    progress_tx: Option<Sender<RemoteProgress>>,
+) -> Result<String> {
+    let mut collected = String::new();
+    let mut line = Vec::new();
+    let mut redrawn_line = Vec::new();
+    let mut chunk = [0; 1024];
+    loop {
+        let len = stream.read(&mut chunk).await?;
+        if len == 0 {
+            break;
+        }
+        for &byte in &chunk[..len] {
+            if byte != b'\r' && byte != b'\n' {
+                line.push(byte);
+                continue;
+            }
+            if byte == b'\n' && line.is_empty() {
+                // The newline of a `\r\n` line ending.
+                line = std::mem::take(&mut redrawn_line);
+            }
+            let text = String::from_utf8_lossy(&line).to_string();
+            if let Some(progress_tx) = &progress_tx
+                && let Some(progress) = RemoteProgress::parse(&text)
+            {
+                // The receiver going away only means nobody is watching the progress anymore.
+                progress_tx.send(progress).await.ok();
+            }
+            if byte == b'\n' {
+                collected.push_str(&text);
+                collected.push('\n');
+                redrawn_line.clear();
+                line.clear();
+            } else {
+                redrawn_line = std::mem::take(&mut line);
+            }
         }
     }
+    collected.push_str(&String::from_utf8_lossy(&line));
+    Ok(collected)
 }
 
 #[derive(Clone, Ord, Hash, PartialOrd, Eq, PartialEq)]
@@ -3205,6 +5379,392 @@ impl MapSeekTarget<RepoPath> for RepoPathDescendants<'_> {
     }
 }
 
+/// Recognizes failures of the signing program configured by `commit.gpgsign` and `gpg.format`,
+/// returning a suggestion for how to fix them.
+fn commit_signing_failure_hint(stderr: &str) -> Option<&'static str> {
+    let stderr = stderr.to_lowercase();
+    if stderr.contains("inappropriate ioctl for device") || stderr.contains("no pinentry") {
+        Some(
+            "GPG could not ask for the key's passphrase. Configure a graphical pinentry program in gpg-agent.conf, or unlock the key in a terminal first.",
+        )
+    } else if stderr.contains("no secret key") || stderr.contains("unusable secret key") {
+        Some(
+            "The signing key was not found. Check that `user.signingkey` names a key in your keyring.",
+        )
+    } else if stderr.contains("cannot run gpg") || stderr.contains("cannot run ssh-keygen") {
+        Some(
+            "The signing program could not be started. Check the `gpg.program` or `gpg.ssh.program` setting.",
+        )
+    } else if stderr.contains("incorrect passphrase") || stderr.contains("no private key found") {
+        Some(
+            "The SSH signing key could not be unlocked. Check `user.signingkey` and the key's passphrase.",
+        )
+    } else if stderr.contains("failed to sign the data") || stderr.contains("signing failed") {
+        Some("Check the `commit.gpgsign`, `gpg.format` and `user.signingkey` settings.")
+    } else {
+        None
+    }
+}
+
+const REBASE_TODO_FILE: &str = "zed-rebase-todo";
+const REBASE_MESSAGES_DIRECTORY: &str = "zed-rebase-messages";
+
+/// Parses the output of `git remote -v`, which has a fetch and a push line for every remote.
+fn parse_remote_infos(input: &str, default_remote: Option<&str>) -> Vec<RemoteInfo> {
+    let mut remotes: Vec<RemoteInfo> = Vec::new();
+    for line in input.lines() {
+        let mut fields = line.split_whitespace();
+        let (Some(name), Some(url), Some(kind)) = (fields.next(), fields.next(), fields.next())
+        else {
+            continue;
+        };
+        let ix = match remotes.iter().position(|remote| remote.name == name) {
+            Some(ix) => ix,
+            None => {
+                remotes.push(RemoteInfo {
+                    name: name.to_string().into(),
+                    fetch_url: None,
+                    push_url: None,
+                    is_default: default_remote == Some(name),
+                });
+                remotes.len() - 1
+            }
+        };
+        let url = SharedString::from(url.to_string());
+        match kind {
+            "(fetch)" => remotes[ix].fetch_url = Some(url),
+            "(push)" => remotes[ix].push_url = Some(url),
+            _ => {}
+        }
+    }
+    for remote in &mut remotes {
+        if remote.push_url == remote.fetch_url {
+            remote.push_url = None;
+        }
+    }
+    remotes.sort_by(|a, b| a.name.cmp(&b.name));
+    remotes
+}
+
+/// Parses lines of `%H%x00%s%x00%ct%x00%an%x00%P` into commit summaries.
+fn parse_commit_summaries(input: &str) -> Vec<CommitSummary> {
+    input
+        .lines()
+        .filter_map(|line| {
+            let mut fields = line.split('\0');
+            let sha = fields.next()?;
+            let subject = fields.next()?;
+            let commit_timestamp = fields.next()?.parse().ok()?;
+            let author_name = fields.next()?;
+            let parents = fields.next()?;
+            Some(CommitSummary {
+                sha: sha.to_string().into(),
+                subject: subject.to_string().into(),
+                commit_timestamp,
+                author_name: author_name.to_string().into(),
+                has_parent: !parents.trim().is_empty(),
+            })
+        })
+        .collect()
+}
+
+/// Parses records of `%x1e%H%x00%B` into todo entries that pick every commit.
+fn parse_rebase_todo(input: &str) -> Vec<RebaseTodoEntry> {
+    input
+        .split('\x1e')
+        .filter_map(|record| {
+            let (sha, message) = record.split_once('\0')?;
+            let message = message.trim_end();
+            Some(RebaseTodoEntry {
+                action: RebaseAction::Pick,
+                sha: sha.trim().to_string().into(),
+                subject: message
+                    .lines()
+                    .next()
+                    .unwrap_or_default()
+                    .to_string()
+                    .into(),
+                message: message.to_string().into(),
+            })
+        })
+        .collect()
+}
+
+/// Builds the todo list git replays. Reworded commits are picked and then amended with the
+/// message stored in `messages_directory`, because `reword` would open an editor.
+fn rebase_todo_script(todo: &[RebaseTodoEntry], messages_directory: &Path) -> String {
+    let mut script = String::new();
+    for (index, entry) in todo.iter().enumerate() {
+        let subject = &entry.subject;
+        let sha = &entry.sha;
+        if entry.action == RebaseAction::Reword {
+            let message_path = messages_directory.join(index.to_string());
+            script.push_str(&format!("pick {sha} {subject}\n"));
+            script.push_str(&format!(
+                "exec git commit --amend --allow-empty --no-verify --quiet --cleanup=strip -F {}\n",
+                shell_quote(&message_path.to_string_lossy())
+            ));
+        } else {
+            script.push_str(&format!("{} {sha} {subject}\n", entry.action.as_str()));
+        }
+    }
+    script
+}
+
+/// Parses the `--name-status -z` fields git prints for the file after each commit of its
+/// history, returning the file's path as of the commit and, for renames and copies, before it.
+fn parse_file_history_name_status(fields: &[&str]) -> (Option<RepoPath>, Option<RepoPath>) {
+    let repo_path = |field: Option<&&str>| field.and_then(|path| RepoPath::new(path).ok());
+    match fields.first().map(|status| status.trim()) {
+        Some(status) if status.starts_with(['R', 'C']) => {
+            (repo_path(fields.get(2)), repo_path(fields.get(1)))
+        }
+        Some(status) if !status.is_empty() => (repo_path(fields.get(1)), None),
+        _ => (None, None),
+    }
+}
+
+/// Quotes `argument` for the POSIX shell git runs editors and `exec` steps with.
+fn shell_quote(argument: &str) -> String {
+    format!("'{}'", argument.replace('\'', "'\\''"))
+}
+
+/// Escapes the glob metacharacters in `path`, and the commas git-lfs splits patterns on, so that
+/// `git lfs pull --include` matches exactly that path.
+fn lfs_include_pattern(path: &str) -> String {
+    let mut pattern = String::with_capacity(path.len());
+    for character in path.chars() {
+        if matches!(
+            character,
+            '\\' | '*' | '?' | '[' | ']' | '{' | '}' | '!' | ','
+        ) {
+            pattern.push('\\');
+        }
+        pattern.push(character);
+    }
+    pattern
+}
+
+/// Removes the comment lines git adds to messages it prepares, such as the list of conflicted
+/// files, which start with `comment_prefix`.
+fn strip_message_comments(message: &str, comment_prefix: &str) -> String {
+    message
+        .lines()
+        .filter(|line| !line.starts_with(comment_prefix))
+        .collect::<Vec<_>>()
+        .join("\n")
+        .trim_end()
+        .to_string()
+}
+
+/// Parses the lines printed for `--format=%H%x00%gd%x00%gs` with `--date=unix`, where the
+/// selectors carry times, such as `HEAD@{1700000000}`. The selectors are rewritten to the
+/// entries' indices, which is how they are usually addressed.
+fn parse_reflog(output: &str) -> Vec<ReflogEntry> {
+    output
+        .lines()
+        .filter_map(|line| {
+            let mut fields = line.split('\0');
+            let sha = fields.next()?;
+            let (reference, time) = fields.next()?.strip_suffix('}')?.rsplit_once("@{")?;
+            let message = fields.next().unwrap_or_default();
+            Some((sha, reference, time.parse().ok()?, message))
+        })
+        .enumerate()
+        .map(
+            |(index, (sha, reference, timestamp, message))| ReflogEntry {
+                sha: sha.to_string().into(),
+                selector: format!("{reference}@{{{index}}}").into(),
+                message: message.to_string().into(),
+                timestamp,
+            },
+        )
+        .collect()
+}
+
+/// Parses a commit printed with [`COMMIT_LOG_FORMAT`] into its entry and its message.
+fn parse_commit_log_record(record: &str) -> Option<(CommitLogEntry, &str)> {
+    let record = record.trim_start_matches('\n');
+    let record = record
+        .strip_suffix(COMMIT_LOG_SEPARATOR as char)
+        .unwrap_or(record);
+    let mut fields = record.splitn(7, '\0');
+    let sha = fields.next().filter(|sha| !sha.is_empty())?;
+    let refs = fields.next()?;
+    let commit_timestamp = fields.next()?.parse().ok()?;
+    let author_name = fields.next()?;
+    let author_email = fields.next()?;
+    let subject = fields.next()?;
+    let message = fields.next().unwrap_or_default();
+    let entry = CommitLogEntry {
+        sha: sha.to_string().into(),
+        subject: subject.to_string().into(),
+        commit_timestamp,
+        author_name: author_name.to_string().into(),
+        author_email: author_email.to_string().into(),
+        refs: refs
+            .split(", ")
+            .filter(|name| !name.is_empty())
+            .map(|name| name.to_string().into())
+            .collect(),
+    };
+    Some((entry, message))
+}
+
+fn parse_bisect_command_output(
+    action: &str,
+    output: &std::process::Output,
+) -> Result<BisectStatus> {
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    anyhow::ensure!(
+        output.status.success(),
+        "Failed to {action}:\n{}{}",
+        stdout,
+        String::from_utf8_lossy(&output.stderr),
+    );
+    parse_bisect_output(&stdout)
+        .with_context(|| format!("Unexpected output from git bisect:\n{stdout}"))
+}
+
+/// Reads the commit to test next, or the first bad commit, from what `git bisect` printed.
+fn parse_bisect_output(output: &str) -> Option<BisectStatus> {
+    let mut lines = output.lines();
+    while let Some(line) = lines.next() {
+        if let Some(sha) = line.strip_suffix(" is the first bad commit") {
+            // The culprit is described like `git show` does, with an indented message.
+            let subject = lines
+                .skip_while(|line| !line.is_empty())
+                .find(|line| !line.trim().is_empty())
+                .map(|line| line.trim().to_string())
+                .unwrap_or_default();
+            return Some(BisectStatus::Found {
+                sha: sha.trim().to_string(),
+                subject,
+            });
+        }
+        if let Some(progress) = line.strip_prefix("Bisecting: ") {
+            let remaining_steps = progress
+                .split_once("(roughly ")
+                .and_then(|(_, steps)| steps.split_whitespace().next()?.parse().ok())
+                .unwrap_or(0);
+            let (sha, subject) = lines.next()?.strip_prefix('[')?.split_once(']')?;
+            return Some(BisectStatus::Testing {
+                sha: sha.to_string(),
+                subject: subject.trim().to_string(),
+                remaining_steps,
+            });
+        }
+    }
+    None
+}
+
+/// Detects a stopped operation from the state files git keeps in the git directory.
+async fn read_in_progress_operation(git_directory: &Path) -> Option<InProgressOperation> {
+    async fn read_number(path: PathBuf) -> Option<usize> {
+        smol::fs::read_to_string(path)
+            .await
+            .ok()?
+            .trim()
+            .parse()
+            .ok()
+    }
+    async fn exists(path: PathBuf) -> bool {
+        smol::fs::metadata(path).await.is_ok()
+    }
+
+    for (directory, step_file, total_file) in [
+        ("rebase-merge", "msgnum", "end"),
+        ("rebase-apply", "next", "last"),
+    ] {
+        let directory = git_directory.join(directory);
+        if exists(directory.clone()).await {
+            let step = read_number(directory.join(step_file)).await;
+            let total = read_number(directory.join(total_file)).await;
+            return Some(InProgressOperation {
+                kind: InProgressOperationKind::Rebase,
+                progress: step.zip(total),
+            });
+        }
+    }
+    for (head, kind) in [
+        ("MERGE_HEAD", InProgressOperationKind::Merge),
+        ("CHERRY_PICK_HEAD", InProgressOperationKind::CherryPick),
+        ("REVERT_HEAD", InProgressOperationKind::Revert),
+    ] {
+        if exists(git_directory.join(head)).await {
+            return Some(InProgressOperation {
+                kind,
+                progress: None,
+            });
+        }
+    }
+    None
+}
+
+/// The commit a stopped merge, cherry-pick or revert is applying. A multi-commit cherry-pick or
+/// revert that stops again does so at a different commit, with nothing else to tell it moved on.
+async fn read_in_progress_operation_head(
+    git_directory: &Path,
+    kind: InProgressOperationKind,
+) -> Option<String> {
+    let head = match kind {
+        InProgressOperationKind::Merge => "MERGE_HEAD",
+        InProgressOperationKind::CherryPick => "CHERRY_PICK_HEAD",
+        InProgressOperationKind::Revert => "REVERT_HEAD",
+        InProgressOperationKind::Rebase => return None,
+    };
+    smol::fs::read_to_string(git_directory.join(head))
+        .await
+        .ok()
+}
+
+fn parse_commit_signatures(input: &str) -> HashMap<SharedString, CommitSignature> {
+    let non_empty =
+        |field: &str| (!field.is_empty()).then(|| SharedString::from(field.to_string()));
+    let mut signatures = HashMap::default();
+    for line in input.lines() {
+        let mut fields = line.split('\0');
+        let (Some(sha), Some(status)) = (fields.next(), fields.next()) else {
+            continue;
+        };
+        // See the description of `%G?` in git-log(1).
+        let status = match status {
+            "G" | "U" => SignatureStatus::Verified,
+            "X" | "Y" => SignatureStatus::Expired,
+            "B" | "R" => SignatureStatus::Unverified,
+            "E" => SignatureStatus::UnknownKey,
+            _ => continue,
+        };
+        signatures.insert(
+            SharedString::from(sha.to_string()),
+            CommitSignature {
+                status,
+                signer: fields.next().and_then(non_empty),
+                key: fields.next().and_then(non_empty),
+            },
+        );
+    }
+    signatures
+}
+
+fn parse_commit_authors(input: &str) -> Vec<(SharedString, SharedString)> {
+    let mut seen_emails = HashSet::new();
+    input
+        .lines()
+        .filter_map(|line| {
+            let (name, email) = line.split_once('\0')?;
+            let (name, email) = (name.trim(), email.trim());
+            if email.is_empty() || !seen_emails.insert(email.to_lowercase()) {
+                return None;
+            }
+            Some((
+                SharedString::from(name.to_string()),
+                SharedString::from(email.to_string()),
+            ))
+        })
+        .collect()
+}
+
 fn parse_branch_input(input: &str) -> Result<Vec<Branch>> {
     let mut branches = Vec::new();
     for line in input.split('\n') {
@@ -3317,6 +5877,28 @@ mod tests {
         }
     }
 
+    #[gpui::test]
+    async fn test_forward_progress_keeps_finished_lines(_cx: &mut TestAppContext) {
+        let (progress_tx, progress_rx) = smol::channel::unbounded();
+        let stderr = "Receiving objects:  50% (1/2)\rReceiving objects: 100% (2/2), done.\r\n\
+            To github.com:zed-industries/zed.git\n";
+        let collected = forward_progress(
+            futures::io::Cursor::new(stderr.as_bytes()),
+            Some(progress_tx),
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(
+            collected,
+            "Receiving objects: 100% (2/2), done.\nTo github.com:zed-industries/zed.git\n"
+        );
+        let percents = std::iter::from_fn(|| progress_rx.try_recv().ok())
+            .map(|progress| progress.percent)
+            .collect::<Vec<_>>();
+        assert_eq!(percents, [50, 100, 100]);
+    }
+
     #[gpui::test]
     async fn test_build_command_untrusted_includes_both_safety_args(cx: &mut TestAppContext) {
         cx.executor().allow_parking();
@@ -3786,6 +6368,289 @@ mod tests {
         assert_eq!(upstream.branch_name(), Some("feature/git-pull-request"));
     }
 
+    #[test]
+    fn test_commit_signing_failure_hint() {
+        let missing_pinentry = "error: gpg failed to sign the data:\n\
+                                gpg: signing failed: Inappropriate ioctl for device\n\
+                                fatal: failed to write commit object";
+        assert!(
+            commit_signing_failure_hint(missing_pinentry)
+                .is_some_and(|hint| hint.contains("pinentry"))
+        );
+
+        let missing_key = "gpg: skipped \"ABCDEF\": No secret key\n\
+                           error: gpg failed to sign the data";
+        assert!(
+            commit_signing_failure_hint(missing_key)
+                .is_some_and(|hint| hint.contains("user.signingkey"))
+        );
+
+        let other_failure =
+            "error: gpg failed to sign the data\nfatal: failed to write commit object";
+        assert!(commit_signing_failure_hint(other_failure).is_some());
+
+        assert_eq!(
+            commit_signing_failure_hint("error: pathspec 'foo' did not match any file(s)"),
+            None
+        );
+    }
+
+    #[test]
+    fn test_parse_commit_signatures() {
+        let input = "aaa\0G\0Ada Lovelace <ada@example.com>\0ABCD1234\n\
+                     bbb\0N\0\0\n\
+                     ccc\0E\0\0EF567890\n\
+                     ddd\0B\0Mallory\0DEADBEEF\n\
+                     eee\0Y\0Ada Lovelace <ada@example.com>\0ABCD1234\n";
+        let signatures = parse_commit_signatures(input);
+        assert_eq!(signatures.len(), 4);
+        assert_eq!(
+            signatures.get("aaa"),
+            Some(&CommitSignature {
+                status: SignatureStatus::Verified,
+                signer: Some("Ada Lovelace <ada@example.com>".into()),
+                key: Some("ABCD1234".into()),
+            })
+        );
+        assert_eq!(signatures.get("bbb"), None);
+        assert_eq!(
+            signatures.get("ccc"),
+            Some(&CommitSignature {
+                status: SignatureStatus::UnknownKey,
+                signer: None,
+                key: Some("EF567890".into()),
+            })
+        );
+        assert_eq!(
+            signatures.get("ddd").map(|signature| signature.status),
+            Some(SignatureStatus::Unverified)
+        );
+        assert_eq!(
+            signatures.get("eee").map(|signature| signature.status),
+            Some(SignatureStatus::Expired)
+        );
+    }
+
+    #[test]
+    fn test_rebase_todo_script() {
+        let entries = parse_rebase_todo(
+            "\x1eaaa\0First\n\nBody\n\x1ebbb\0Second\n\x1eccc\0Third\n\x1eddd\0Fourth\n",
+        );
+        assert_eq!(entries.len(), 4);
+        assert_eq!(entries[0].subject, "First");
+        assert_eq!(entries[0].message, "First\n\nBody");
+
+        let mut todo = entries;
+        todo[1].action = RebaseAction::Reword;
+        todo[1].message = "Second, reworded".into();
+        todo[2].action = RebaseAction::Fixup;
+        todo[3].action = RebaseAction::Drop;
+        assert_eq!(
+            rebase_todo_script(&todo, Path::new("/repo/.git/zed-rebase-messages")),
+            "pick aaa First\n\
+             pick bbb Second\n\
+             exec git commit --amend --allow-empty --no-verify --quiet --cleanup=strip -F '/repo/.git/zed-rebase-messages/1'\n\
+             fixup ccc Third\n\
+             drop ddd Fourth\n"
+        );
+        assert_eq!(shell_quote("it's"), "'it'\\''s'");
+    }
+
+    #[test]
+    fn test_parse_file_history_name_status() {
+        assert_eq!(
+            parse_file_history_name_status(&["\nR066", "old.txt", "new.txt", "", ""]),
+            (Some(repo_path("new.txt")), Some(repo_path("old.txt")))
+        );
+        assert_eq!(
+            parse_file_history_name_status(&["\nM", "new.txt", "", ""]),
+            (Some(repo_path("new.txt")), None)
+        );
+        assert_eq!(parse_file_history_name_status(&[""]), (None, None));
+    }
+
+    #[test]
+    fn test_lfs_include_pattern() {
+        assert_eq!(lfs_include_pattern("assets/logo.png"), "assets/logo.png");
+        assert_eq!(
+            lfs_include_pattern("assets/[draft] a*b?, c.psd"),
+            "assets/\\[draft\\] a\\*b\\?\\, c.psd"
+        );
+    }
+
+    #[test]
+    fn test_parse_remote_infos() {
+        let remotes = parse_remote_infos(
+            "upstream\thttps://github.com/zed-industries/zed.git (fetch)\n\
+             upstream\thttps://github.com/zed-industries/zed.git (push)\n\
+             origin\thttps://github.com/me/zed.git (fetch)\n\
+             origin\tgit@github.com:me/zed.git (push)\n",
+            Some("upstream"),
+        );
+        assert_eq!(
+            remotes,
+            [
+                RemoteInfo {
+                    name: "origin".into(),
+                    fetch_url: Some("https://github.com/me/zed.git".into()),
+                    push_url: Some("git@github.com:me/zed.git".into()),
+                    is_default: false,
+                },
+                RemoteInfo {
+                    name: "upstream".into(),
+                    fetch_url: Some("https://github.com/zed-industries/zed.git".into()),
+                    push_url: None,
+                    is_default: true,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_commit_summaries() {
+        let summaries = parse_commit_summaries(
+            "bbb\0Second\01700000100\0Alice\0aaa\naaa\0Root commit\01700000000\0Bob\0\n",
+        );
+        assert_eq!(
+            summaries,
+            vec![
+                CommitSummary {
+                    sha: "bbb".into(),
+                    subject: "Second".into(),
+                    commit_timestamp: 1700000100,
+                    author_name: "Alice".into(),
+                    has_parent: true,
+                },
+                CommitSummary {
+                    sha: "aaa".into(),
+                    subject: "Root commit".into(),
+                    commit_timestamp: 1700000000,
+                    author_name: "Bob".into(),
+                    has_parent: false,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_strip_message_comments() {
+        let message = "Revert \"Add a feature\"\n\n\
+                       This reverts commit abc123.\n\n\
+                       # Conflicts:\n\
+                       #\tsrc/main.rs\n";
+        assert_eq!(
+            strip_message_comments(message, "#"),
+            "Revert \"Add a feature\"\n\nThis reverts commit abc123."
+        );
+
+        let message = "Revert \"Add a feature\"\n\n\
+                       #1 was fixed by this commit.\n\n\
+                       ; Conflicts:\n\
+                       ;\tsrc/main.rs\n";
+        assert_eq!(
+            strip_message_comments(message, ";"),
+            "Revert \"Add a feature\"\n\n#1 was fixed by this commit."
+        );
+    }
+
+    #[test]
+    fn test_parse_reflog() {
+        let output = "1111\0HEAD@{1700000200}\0rebase (finish): returning to refs/heads/main\n\
+                      2222\0HEAD@{1700000100}\0commit: Add a feature\n";
+        assert_eq!(
+            parse_reflog(output),
+            [
+                ReflogEntry {
+                    sha: "1111".into(),
+                    selector: "HEAD@{0}".into(),
+                    message: "rebase (finish): returning to refs/heads/main".into(),
+                    timestamp: 1700000200,
+                },
+                ReflogEntry {
+                    sha: "2222".into(),
+                    selector: "HEAD@{1}".into(),
+                    message: "commit: Add a feature".into(),
+                    timestamp: 1700000100,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_commit_log_record() {
+        let record = "\n1111\0HEAD -> main, tag: v1.0\01700000000\0Ada\0ada@example.com\0\
+                      Add a feature\0Add a feature\n\nFixes #12.\n\x1e";
+        let (entry, message) = parse_commit_log_record(record).unwrap();
+        assert_eq!(
+            entry,
+            CommitLogEntry {
+                sha: "1111".into(),
+                subject: "Add a feature".into(),
+                commit_timestamp: 1700000000,
+                author_name: "Ada".into(),
+                author_email: "ada@example.com".into(),
+                refs: vec!["HEAD -> main".into(), "tag: v1.0".into()],
+            }
+        );
+        assert_eq!(message, "Add a feature\n\nFixes #12.\n");
+
+        let (entry, _) =
+            parse_commit_log_record("2222\0\01700000000\0Ada\0ada@example.com\0Fix\0Fix\x1e")
+                .unwrap();
+        assert!(entry.refs.is_empty());
+        assert_eq!(parse_commit_log_record("\n"), None);
+    }
+
+    #[test]
+    fn test_parse_bisect_output() {
+        let testing = "Bisecting: 12 revisions left to test after this (roughly 4 steps)\n\
+                       [0123abcd] Add a feature\n";
+        assert_eq!(
+            parse_bisect_output(testing),
+            Some(BisectStatus::Testing {
+                sha: "0123abcd".to_string(),
+                subject: "Add a feature".to_string(),
+                remaining_steps: 4,
+            })
+        );
+
+        let found = "0123abcd is the first bad commit\n\
+                     commit 0123abcd\n\
+                     Author: Ada Lovelace <ada@example.com>\n\
+                     Date:   Mon Jan 1 00:00:00 2024 +0000\n\
+                     \n\
+                     \x20   Break everything\n\
+                     \n\
+                     \x20src/main.rs | 2 +-\n";
+        assert_eq!(
+            parse_bisect_output(found),
+            Some(BisectStatus::Found {
+                sha: "0123abcd".to_string(),
+                subject: "Break everything".to_string(),
+            })
+        );
+
+        assert_eq!(
+            parse_bisect_output("status: waiting for both good and bad commits\n"),
+            None
+        );
+    }
+
+    #[test]
+    fn test_parse_commit_authors() {
+        let input = "Ada Lovelace\0ada@example.com\n\
+                     Grace Hopper\0grace@example.com\n\
+                     ada\0ADA@example.com\n\
+                     Nobody\0\n";
+        assert_eq!(
+            parse_commit_authors(input),
+            [
+                ("Ada Lovelace".into(), "ada@example.com".into()),
+                ("Grace Hopper".into(), "grace@example.com".into()),
+            ]
+        );
+    }
+
     #[test]
     fn test_parse_worktrees_from_str() {
         // Empty input
@@ -0,0 +1,77 @@
+use std::collections::BTreeSet;
+
+use crate::repository::RepoPath;
+
+/// How a repository limits its working tree to some of the files in HEAD.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SparseCheckout {
+    /// Whether the patterns are directories to check out, rather than gitignore-style patterns.
+    pub cone: bool,
+    pub patterns: Vec<String>,
+    /// The directories of HEAD left out of the working tree, each directly inside a directory
+    /// that is checked out. Only known in cone mode.
+    pub excluded_directories: Vec<RepoPath>,
+}
+
+/// The directories whose files cone-mode patterns check out without checking out their
+/// subdirectories: the root, written as an empty string, and every ancestor of a pattern.
+pub fn cone_parent_directories(patterns: &[String]) -> BTreeSet<String> {
+    let mut parents = BTreeSet::from([String::new()]);
+    for pattern in patterns {
+        let mut ancestor = pattern.as_str();
+        while let Some((parent, _)) = ancestor.rsplit_once('/') {
+            parents.insert(parent.to_string());
+            ancestor = parent;
+        }
+    }
+    parents
+}
+
+/// Picks out the directories that cone-mode patterns leave out, given the subdirectories of
+/// [`cone_parent_directories`].
+pub fn excluded_cone_directories(
+    patterns: &[String],
+    subdirectories: impl IntoIterator<Item = String>,
+) -> Vec<RepoPath> {
+    let parents = cone_parent_directories(patterns);
+    let mut excluded = subdirectories
+        .into_iter()
+        .filter(|directory| {
+            !parents.contains(directory) && !patterns.iter().any(|pattern| pattern == directory)
+        })
+        .filter_map(|directory| RepoPath::new(&directory).ok())
+        .collect::<Vec<_>>();
+    excluded.sort();
+    excluded.dedup();
+    excluded
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_excluded_cone_directories() {
+        let patterns = vec!["crates/git".to_string(), "docs".to_string()];
+        assert_eq!(
+            cone_parent_directories(&patterns),
+            BTreeSet::from([String::new(), "crates".to_string()])
+        );
+        assert_eq!(
+            excluded_cone_directories(
+                &patterns,
+                [
+                    "crates".to_string(),
+                    "docs".to_string(),
+                    "script".to_string(),
+                    "crates/editor".to_string(),
+                    "crates/git".to_string(),
+                ]
+            ),
+            [
+                RepoPath::new("crates/editor").unwrap(),
+                RepoPath::new("script").unwrap(),
+            ]
+        );
+    }
+}
@@ -628,6 +628,48 @@ pub fn parse_numstat(output: &str) -> GitDiffStat {
     }
 }
 
+/// A path that git's similarity detection matched with the path its content came from.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct FileRename {
+    pub old_path: RepoPath,
+    pub new_path: RepoPath,
+    /// Whether the old path is still there, making the new one a copy rather than a rename.
+    pub is_copy: bool,
+}
+
+/// Parses the renames and copies out of `git diff --name-status -z -M -C`, whose entries for them
+/// list the status with a similarity score, then the old path and the new path:
+///
+/// ```text
+/// R087\0old.rs\0new.rs\0M\0other.rs\0
+/// ```
+pub fn parse_renames(output: &str) -> Result<Vec<FileRename>> {
+    let mut fields = output.split('\0');
+    let mut renames = Vec::new();
+    while let Some(status) = fields.next() {
+        let is_copy = match status.as_bytes().first() {
+            Some(b'R') => false,
+            Some(b'C') => true,
+            Some(_) => {
+                fields.next();
+                continue;
+            }
+            None => continue,
+        };
+        let (Some(old_path), Some(new_path)) = (fields.next(), fields.next()) else {
+            return Err(anyhow!(
+                "expected the old and new paths of a {status} entry"
+            ));
+        };
+        renames.push(FileRename {
+            old_path: RepoPath::from_rel_path(RelPath::unix(old_path)?),
+            new_path: RepoPath::from_rel_path(RelPath::unix(new_path)?),
+            is_copy,
+        });
+    }
+    Ok(renames)
+}
+
 #[cfg(test)]
 mod tests {
 
@@ -636,7 +678,7 @@ mod tests {
         status::{FileStatus, GitStatus, TreeDiff, TreeDiffStatus},
     };
 
-    use super::{DiffStat, parse_numstat};
+    use super::{DiffStat, FileRename, parse_numstat, parse_renames};
 
     fn lookup<'a>(entries: &'a [(RepoPath, DiffStat)], path: &str) -> Option<&'a DiffStat> {
         let path = RepoPath::new(path).unwrap();
@@ -772,4 +814,26 @@ mod tests {
             }
         )
     }
+
+    #[test]
+    fn test_parse_renames() {
+        let input =
+            "R087\0src/old.rs\0src/new.rs\0M\0Cargo.toml\0C100\0a.txt\0b.txt\0D\0gone.txt\0";
+        assert_eq!(
+            parse_renames(input).unwrap(),
+            [
+                FileRename {
+                    old_path: RepoPath::new("src/old.rs").unwrap(),
+                    new_path: RepoPath::new("src/new.rs").unwrap(),
+                    is_copy: false,
+                },
+                FileRename {
+                    old_path: RepoPath::new("a.txt").unwrap(),
+                    new_path: RepoPath::new("b.txt").unwrap(),
+                    is_copy: true,
+                },
+            ]
+        );
+        assert_eq!(parse_renames("").unwrap(), []);
+    }
 }
@@ -0,0 +1,92 @@
+use gpui::SharedString;
+
+use crate::repository::RepoPath;
+
+/// A submodule of a repository, as `git submodule status` reports it.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Submodule {
+    pub path: RepoPath,
+    /// The commit the submodule is checked out at, or the one the superproject records for it
+    /// when it isn't checked out.
+    pub sha: SharedString,
+    pub state: SubmoduleState,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SubmoduleState {
+    /// Listed in `.gitmodules` but not cloned yet.
+    Uninitialized,
+    /// Checked out at the commit the superproject records.
+    UpToDate,
+    /// Checked out at another commit than the one the superproject records.
+    CommitChanged,
+    /// Conflicted in a merge of the superproject.
+    Conflicted,
+}
+
+/// Parses the output of `git submodule status`, where each line is a state marker, a SHA and a
+/// path, followed by the `git describe` of the SHA when the submodule is checked out.
+pub fn parse_submodules(output: &str) -> Vec<Submodule> {
+    output
+        .lines()
+        .filter_map(|line| {
+            let mut chars = line.chars();
+            let state = match chars.next()? {
+                ' ' => SubmoduleState::UpToDate,
+                '-' => SubmoduleState::Uninitialized,
+                '+' => SubmoduleState::CommitChanged,
+                'U' => SubmoduleState::Conflicted,
+                _ => return None,
+            };
+            let (sha, path) = chars.as_str().split_once(' ')?;
+            let path = match path.rsplit_once(" (") {
+                Some((path, describe)) if describe.ends_with(')') => path,
+                _ => path,
+            };
+            Some(Submodule {
+                path: RepoPath::new(path).ok()?,
+                sha: sha.to_string().into(),
+                state,
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_submodules() {
+        let output = "\
+ 3f786850e387550fdab836ed7e6dc881de23001b vendor/lib (v1.2.0)
+-89e6c98d92887913cadf06b2adb97f26cde4849b third_party/docs
++e69de29bb2d1d6434b8b29ae775ad8c2e48c5391 tools/gen (heads/main)
+Ue69de29bb2d1d6434b8b29ae775ad8c2e48c5391 tools/conflicted";
+        assert_eq!(
+            parse_submodules(output),
+            [
+                Submodule {
+                    path: RepoPath::new("vendor/lib").unwrap(),
+                    sha: "3f786850e387550fdab836ed7e6dc881de23001b".into(),
+                    state: SubmoduleState::UpToDate,
+                },
+                Submodule {
+                    path: RepoPath::new("third_party/docs").unwrap(),
+                    sha: "89e6c98d92887913cadf06b2adb97f26cde4849b".into(),
+                    state: SubmoduleState::Uninitialized,
+                },
+                Submodule {
+                    path: RepoPath::new("tools/gen").unwrap(),
+                    sha: "e69de29bb2d1d6434b8b29ae775ad8c2e48c5391".into(),
+                    state: SubmoduleState::CommitChanged,
+                },
+                Submodule {
+                    path: RepoPath::new("tools/conflicted").unwrap(),
+                    sha: "e69de29bb2d1d6434b8b29ae775ad8c2e48c5391".into(),
+                    state: SubmoduleState::Conflicted,
+                },
+            ]
+        );
+    }
+}
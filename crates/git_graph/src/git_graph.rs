@@ -1,4 +1,5 @@
-use collections::{BTreeMap, HashMap};
+use collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+use editor::{Editor, EditorEvent};
 use feature_flags::{FeatureFlagAppExt as _, GitGraphFeatureFlag};
 use git::{
     BuildCommitPermalinkParams, GitHostingProviderRegistry, GitRemote, Oid, ParsedGitRemote,
@@ -6,13 +7,16 @@ use git::{
     repository::{CommitDiff, CommitFile, InitialGraphCommitData, LogOrder, LogSource, RepoPath},
     status::{FileStatus, StatusCode, TrackedStatus},
 };
-use git_ui::{commit_tooltip::CommitAvatar, commit_view::CommitView, git_status_icon};
+use git_ui::{
+    commit_tooltip::CommitAvatar, commit_view::CommitView, git_panel, git_status_icon,
+    reset_modal::ResetModal,
+};
 use gpui::{
-    AnyElement, App, Bounds, ClickEvent, ClipboardItem, Corner, DefiniteLength, DragMoveEvent,
-    ElementId, Empty, Entity, EventEmitter, FocusHandle, Focusable, Hsla, PathBuilder, Pixels,
-    Point, ScrollStrategy, ScrollWheelEvent, SharedString, Subscription, Task,
-    UniformListScrollHandle, WeakEntity, Window, actions, anchored, deferred, point, prelude::*,
-    px, uniform_list,
+    AnyElement, App, Bounds, ClickEvent, ClipboardItem, Corner, DefiniteLength, DismissEvent,
+    DragMoveEvent, ElementId, Empty, Entity, EventEmitter, FocusHandle, Focusable, Hsla,
+    MouseButton, PathBuilder, Pixels, Point, ScrollStrategy, ScrollWheelEvent, SharedString,
+    Subscription, Task, UniformListScrollHandle, WeakEntity, Window, actions, anchored, deferred,
+    point, prelude::*, px, uniform_list,
 };
 use language::line_diff;
 use menu::{Cancel, SelectFirst, SelectLast, SelectNext, SelectPrevious};
@@ -41,8 +45,9 @@ use ui::{
     prelude::*,
 };
 use workspace::{
-    Workspace,
+    Toast, Workspace,
     item::{Item, ItemEvent, SerializableItem, TabTooltipContent},
+    notifications::NotificationId,
 };
 
 const COMMIT_CIRCLE_RADIUS: Pixels = px(3.5);
@@ -52,6 +57,7 @@ const LEFT_PADDING: Pixels = px(12.0);
 const LINE_WIDTH: Pixels = px(1.5);
 const RESIZE_HANDLE_WIDTH: f32 = 8.0;
 const COPIED_STATE_DURATION: Duration = Duration::from_secs(2);
+const SEARCH_DEBOUNCE: Duration = Duration::from_millis(150);
 
 struct CopiedState {
     copied_at: Option<Instant>,
@@ -246,6 +252,12 @@ actions!(
     [
         /// Opens the commit view for the selected commit.
         OpenCommitView,
+        /// Focuses the field for searching commit messages and authors.
+        FocusSearch,
+        /// Selects the next commit matching the search.
+        SelectNextMatch,
+        /// Selects the previous commit matching the search.
+        SelectPreviousMatch,
     ]
 );
 
@@ -787,10 +799,23 @@ fn to_row_center(
     bounds.origin.y + to_row as f32 * row_height + row_height / 2.0 - scroll_offset
 }
 
-fn draw_commit_circle(center_x: Pixels, center_y: Pixels, color: Hsla, window: &mut Window) {
-    let radius = COMMIT_CIRCLE_RADIUS;
-
-    let mut builder = PathBuilder::fill();
+/// Draws a commit as a filled circle, or as a ring for merge commits so that merges stand out
+/// from the commits of the branches they join.
+fn draw_commit_circle(
+    center_x: Pixels,
+    center_y: Pixels,
+    color: Hsla,
+    is_merge: bool,
+    window: &mut Window,
+) {
+    let (radius, mut builder) = if is_merge {
+        (
+            COMMIT_CIRCLE_RADIUS - COMMIT_CIRCLE_STROKE_WIDTH / 2.,
+            PathBuilder::stroke(COMMIT_CIRCLE_STROKE_WIDTH),
+        )
+    } else {
+        (COMMIT_CIRCLE_RADIUS, PathBuilder::fill())
+    };
 
     // Start at the rightmost point of the circle
     builder.move_to(point(center_x + radius, center_y));
@@ -845,6 +870,9 @@ pub struct GitGraph {
     horizontal_scroll_offset: Pixels,
     graph_viewport_width: Pixels,
     selected_entry_idx: Option<usize>,
+    /// Commits added to the selection with a secondary click, which actions like cherry-picking
+    /// apply to along with the selected commit.
+    marked_entry_indices: BTreeSet<usize>,
     hovered_entry_idx: Option<usize>,
     graph_canvas_bounds: Rc<Cell<Option<Bounds<Pixels>>>>,
     log_source: LogSource,
@@ -856,6 +884,10 @@ pub struct GitGraph {
     selected_repo_id: Option<RepositoryId>,
     changed_files_scroll_handle: UniformListScrollHandle,
     pending_select_sha: Option<Oid>,
+    search_editor: Entity<Editor>,
+    /// Commits whose message or author contains the search query, once the search has finished.
+    search_matches: Option<HashSet<Oid>>,
+    _search_task: Option<Task<()>>,
 }
 
 impl GitGraph {
@@ -902,6 +934,8 @@ impl GitGraph {
                 if this.selected_repo_id != *changed_repo_id {
                     this.selected_repo_id = *changed_repo_id;
                     this.graph_data.clear();
+                    this.marked_entry_indices.clear();
+                    this.search_changed(cx);
                     cx.notify();
                 }
             }
@@ -914,6 +948,18 @@ impl GitGraph {
             .active_repository(cx)
             .map(|repo| repo.read(cx).id);
 
+        let search_editor = cx.new(|cx| {
+            let mut editor = Editor::single_line(window, cx);
+            editor.set_placeholder_text("Search commit messages and authors…", window, cx);
+            editor
+        });
+        cx.subscribe(&search_editor, |this, _, event: &EditorEvent, cx| {
+            if let EditorEvent::BufferEdited = event {
+                this.search_changed(cx);
+            }
+        })
+        .detach();
+
         let table_interaction_state = cx.new(|cx| TableInteractionState::new(cx));
         let table_column_widths = cx.new(|cx| TableColumnWidths::new(4, cx));
         let mut row_height = Self::row_height(cx);
@@ -944,6 +990,7 @@ impl GitGraph {
             horizontal_scroll_offset: px(0.),
             graph_viewport_width: px(88.),
             selected_entry_idx: None,
+            marked_entry_indices: BTreeSet::new(),
             hovered_entry_idx: None,
             graph_canvas_bounds: Rc::new(Cell::new(None)),
             selected_commit_diff: None,
@@ -954,6 +1001,9 @@ impl GitGraph {
             selected_repo_id: active_repository,
             changed_files_scroll_handle: UniformListScrollHandle::new(),
             pending_select_sha: None,
+            search_editor,
+            search_matches: None,
+            _search_task: None,
         };
 
         this.fetch_initial_graph_data(cx);
@@ -1031,6 +1081,7 @@ impl GitGraph {
                 // NOTE: this fixes an loading performance regression
                 if repository.read(cx).scan_id > 1 {
                     self.graph_data.clear();
+                    self.marked_entry_indices.clear();
                     cx.notify();
                 }
             }
@@ -1050,6 +1101,151 @@ impl GitGraph {
         }
     }
 
+    fn search_changed(&mut self, cx: &mut Context<Self>) {
+        let query = self.search_editor.read(cx).text(cx).trim().to_string();
+        if query.is_empty() {
+            self.search_matches = None;
+            self._search_task = None;
+            cx.notify();
+            return;
+        }
+        let Some(repository) = self.get_selected_repository(cx) else {
+            return;
+        };
+        let log_source = self.log_source.clone();
+
+        self._search_task = Some(cx.spawn(async move |this, cx| {
+            cx.background_executor().timer(SEARCH_DEBOUNCE).await;
+            let search = repository.update(cx, |repository, _| {
+                repository.search_commits(log_source, query)
+            });
+            let matches = match search
+                .await
+                .map_err(anyhow::Error::from)
+                .and_then(|result| result)
+            {
+                Ok(matches) => matches.into_iter().collect(),
+                Err(error) => {
+                    log::error!("failed to search commits: {error:?}");
+                    HashSet::default()
+                }
+            };
+
+            this.update(cx, |this, cx| {
+                this.search_matches = Some(matches);
+                let selection_matches = this
+                    .selected_entry_idx
+                    .and_then(|idx| this.graph_data.commits.get(idx))
+                    .is_some_and(|commit| this.is_search_match(commit.data.sha));
+                if !selection_matches {
+                    this.select_adjacent_match(true, cx);
+                }
+                cx.notify();
+            })
+            .ok();
+        }));
+    }
+
+    fn is_search_match(&self, sha: Oid) -> bool {
+        self.search_matches
+            .as_ref()
+            .is_some_and(|matches| matches.contains(&sha))
+    }
+
+    fn select_adjacent_match(&mut self, forward: bool, cx: &mut Context<Self>) {
+        let match_rows = self
+            .graph_data
+            .commits
+            .iter()
+            .enumerate()
+            .filter(|(_, commit)| self.is_search_match(commit.data.sha))
+            .map(|(idx, _)| idx)
+            .collect::<Vec<_>>();
+        let target = match (forward, self.selected_entry_idx) {
+            (true, Some(selected)) => match_rows
+                .iter()
+                .find(|row| **row > selected)
+                .or(match_rows.first()),
+            (false, Some(selected)) => match_rows
+                .iter()
+                .rev()
+                .find(|row| **row < selected)
+                .or(match_rows.last()),
+            (true, None) => match_rows.first(),
+            (false, None) => match_rows.last(),
+        };
+        if let Some(row) = target.copied() {
+            self.select_entry(row, cx);
+        }
+    }
+
+    fn focus_search(&mut self, _: &FocusSearch, window: &mut Window, cx: &mut Context<Self>) {
+        window.focus(&self.search_editor.focus_handle(cx), cx);
+    }
+
+    fn select_next_match(&mut self, _: &SelectNextMatch, _: &mut Window, cx: &mut Context<Self>) {
+        self.select_adjacent_match(true, cx);
+    }
+
+    fn select_previous_match(
+        &mut self,
+        _: &SelectPreviousMatch,
+        _: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.select_adjacent_match(false, cx);
+    }
+
+    fn render_search_bar(&self, cx: &mut Context<Self>) -> impl IntoElement {
+        let match_count = self.search_matches.as_ref().map(|matches| matches.len());
+
+        h_flex()
+            .key_context("GitGraphSearch")
+            .on_action(cx.listener(Self::select_next_match))
+            .on_action(cx.listener(Self::select_previous_match))
+            .h_8()
+            .px_2()
+            .gap_2()
+            .flex_none()
+            .border_b_1()
+            .border_color(cx.theme().colors().border)
+            .child(
+                Icon::new(IconName::MagnifyingGlass)
+                    .size(IconSize::Small)
+                    .color(Color::Muted),
+            )
+            .child(div().flex_1().child(self.search_editor.clone()))
+            .children(match_count.map(|count| {
+                let label = if count == 1 {
+                    "1 match".to_string()
+                } else {
+                    format!("{count} matches")
+                };
+                Label::new(label).size(LabelSize::Small).color(Color::Muted)
+            }))
+            .when(match_count.is_some_and(|count| count > 0), |this| {
+                this.child(
+                    IconButton::new("previous-match", IconName::ChevronUp)
+                        .icon_size(IconSize::Small)
+                        .tooltip(Tooltip::for_action_title(
+                            "Previous Match",
+                            &SelectPreviousMatch,
+                        ))
+                        .on_click(cx.listener(|this, _, _, cx| {
+                            this.select_adjacent_match(false, cx);
+                        })),
+                )
+                .child(
+                    IconButton::new("next-match", IconName::ChevronDown)
+                        .icon_size(IconSize::Small)
+                        .tooltip(Tooltip::for_action_title("Next Match", &SelectNextMatch))
+                        .on_click(cx.listener(|this, _, _, cx| {
+                            this.select_adjacent_match(true, cx);
+                        })),
+                )
+            })
+    }
+
     fn get_selected_repository(&self, cx: &App) -> Option<Entity<Repository>> {
         let project = self.project.read(cx);
         self.selected_repo_id
@@ -1128,9 +1324,16 @@ impl GitGraph {
                     .unwrap_or_else(|| accent_colors.0.first().copied().unwrap_or_default());
 
                 let is_selected = self.selected_entry_idx == Some(idx);
+                let label_color = match &self.search_matches {
+                    Some(matches) if !matches.contains(&commit.data.sha) => Some(Color::Disabled),
+                    Some(_) => None,
+                    None => Some(Color::Muted),
+                };
                 let column_label = |label: SharedString| {
                     Label::new(label)
-                        .when(!is_selected, |c| c.color(Color::Muted))
+                        .when_some(label_color.filter(|_| !is_selected), |c, color| {
+                            c.color(color)
+                        })
                         .truncate()
                         .into_any_element()
                 };
@@ -1166,6 +1369,7 @@ impl GitGraph {
 
     fn cancel(&mut self, _: &Cancel, _window: &mut Window, cx: &mut Context<Self>) {
         self.selected_entry_idx = None;
+        self.marked_entry_indices.clear();
         self.selected_commit_diff = None;
         self.selected_commit_diff_stats = None;
         cx.notify();
@@ -1248,6 +1452,203 @@ impl GitGraph {
         cx.notify();
     }
 
+    fn click_entry(&mut self, idx: usize, toggle_mark: bool, cx: &mut Context<Self>) {
+        if toggle_mark {
+            if let Some(selected_entry_idx) = self.selected_entry_idx {
+                self.marked_entry_indices.insert(selected_entry_idx);
+            }
+            if self.marked_entry_indices.remove(&idx) {
+                // The selected commit counts as marked, so an unmarked one can't stay selected.
+                if let Some(&marked_idx) = self.marked_entry_indices.last() {
+                    self.marked_entry_indices.remove(&marked_idx);
+                    self.select_entry(marked_idx, cx);
+                }
+                cx.notify();
+                return;
+            }
+            self.marked_entry_indices.insert(idx);
+        } else {
+            self.marked_entry_indices.clear();
+        }
+        self.select_entry(idx, cx);
+        cx.notify();
+    }
+
+    /// The selected and marked commits, oldest first.
+    fn selected_commit_shas(&self) -> Vec<String> {
+        let mut indices = self.marked_entry_indices.clone();
+        indices.extend(self.selected_entry_idx);
+        // Rows are ordered newest first.
+        indices
+            .into_iter()
+            .rev()
+            .filter_map(|idx| self.graph_data.commits.get(idx))
+            .map(|commit| commit.data.sha.to_string())
+            .collect()
+    }
+
+    fn deploy_context_menu(
+        &mut self,
+        position: Point<Pixels>,
+        idx: usize,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        if !self.marked_entry_indices.contains(&idx) {
+            self.marked_entry_indices.clear();
+        }
+        self.select_entry(idx, cx);
+
+        let commit_count = self.selected_commit_shas().len();
+        let cherry_pick_label = if commit_count == 1 {
+            "Cherry-Pick Commit".to_string()
+        } else {
+            format!("Cherry-Pick {commit_count} Commits")
+        };
+        let revert_target = match self.selected_commit_shas().as_slice() {
+            [sha] => self
+                .get_selected_repository(cx)
+                .map(|repository| (repository, sha.clone())),
+            _ => None,
+        };
+        let workspace = self.workspace.clone();
+        let this = cx.weak_entity();
+        let context_menu = ContextMenu::build(window, cx, |context_menu, _, _| {
+            context_menu
+                .entry(cherry_pick_label, None, {
+                    let this = this.clone();
+                    move |_, cx| {
+                        this.update(cx, |this, cx| this.cherry_pick(false, cx)).ok();
+                    }
+                })
+                .entry(
+                    "Cherry-Pick with Reference to Original",
+                    None,
+                    move |_, cx| {
+                        this.update(cx, |this, cx| this.cherry_pick(true, cx)).ok();
+                    },
+                )
+                .when_some(revert_target, |context_menu, (repository, sha)| {
+                    context_menu
+                        .separator()
+                        .entry("Revert Commit", None, {
+                            let workspace = workspace.clone();
+                            let repository = repository.clone();
+                            let sha = sha.clone();
+                            move |window, cx| {
+                                git_panel::revert_commit(
+                                    workspace.clone(),
+                                    repository.clone(),
+                                    sha.clone(),
+                                    false,
+                                    window,
+                                    cx,
+                                )
+                            }
+                        })
+                        .entry("Revert Commit Without Committing", None, {
+                            let workspace = workspace.clone();
+                            let repository = repository.clone();
+                            let sha = sha.clone();
+                            move |window, cx| {
+                                git_panel::revert_commit(
+                                    workspace.clone(),
+                                    repository.clone(),
+                                    sha.clone(),
+                                    true,
+                                    window,
+                                    cx,
+                                )
+                            }
+                        })
+                        .separator()
+                        .entry(
+                            "Reset Current Branch to Here…",
+                            None,
+                            move |window, cx| {
+                                workspace
+                                    .update(cx, |workspace, cx| {
+                                        ResetModal::toggle(
+                                            workspace,
+                                            repository.clone(),
+                                            sha.clone(),
+                                            window,
+                                            cx,
+                                        )
+                                    })
+                                    .ok();
+                            },
+                        )
+                })
+        });
+        let subscription = cx.subscribe_in(
+            &context_menu,
+            window,
+            |this, _, _: &DismissEvent, window, cx| {
+                if this.context_menu.as_ref().is_some_and(|context_menu| {
+                    context_menu.0.focus_handle(cx).contains_focused(window, cx)
+                }) {
+                    cx.focus_self(window);
+                }
+                this.context_menu.take();
+                cx.notify();
+            },
+        );
+        self.context_menu = Some((context_menu, position, subscription));
+        cx.notify();
+    }
+
+    /// Cherry-picks the selected and marked commits onto the current branch. With
+    /// `record_origin`, each new commit message notes the commit it came from, as `-x` does.
+    fn cherry_pick(&mut self, record_origin: bool, cx: &mut Context<Self>) {
+        let shas = self.selected_commit_shas();
+        let Some(repository) = self.get_selected_repository(cx) else {
+            return;
+        };
+        if shas.is_empty() {
+            return;
+        }
+        let cherry_pick = repository.update(cx, |repository, _| {
+            repository.cherry_pick(shas, record_origin)
+        });
+        let workspace = self.workspace.clone();
+        cx.spawn(async move |this, cx| {
+            let result = cherry_pick
+                .await
+                .map_err(anyhow::Error::from)
+                .and_then(|result| result);
+            this.update(cx, |this, cx| {
+                this.marked_entry_indices.clear();
+                cx.notify();
+            })
+            .ok();
+            workspace
+                .update(cx, |workspace, cx| match result {
+                    Ok(true) => {
+                        struct CherryPickStopped;
+                        workspace.show_toast(
+                            Toast::new(
+                                NotificationId::unique::<CherryPickStopped>(),
+                                "The cherry-pick stopped on conflicts. Resolve them, then \
+                                continue or abort it from the Git panel.",
+                            )
+                            .on_click(
+                                "Open Git Panel",
+                                |window, cx| {
+                                    window.dispatch_action(Box::new(git_panel::ToggleFocus), cx);
+                                },
+                            ),
+                            cx,
+                        );
+                    }
+                    Ok(false) => {}
+                    Err(error) => workspace.show_error(&error, cx),
+                })
+                .ok();
+        })
+        .detach();
+    }
+
     pub fn select_commit_by_sha(&mut self, sha: &str, cx: &mut Context<Self>) {
         let Ok(oid) = sha.parse::<Oid>() else {
             return;
@@ -1612,6 +2013,26 @@ impl GitGraph {
                                         },
                                     ),
                                 )
+                            })
+                            .child({
+                                let base = full_sha.to_string();
+                                Button::new("rebase-after-commit", "Rebase Commits After This")
+                                    .start_icon(
+                                        Icon::new(IconName::GitBranch)
+                                            .size(IconSize::Small)
+                                            .color(Color::Muted),
+                                    )
+                                    .label_size(LabelSize::Small)
+                                    .truncate(true)
+                                    .color(Color::Muted)
+                                    .on_click(move |_, window, cx| {
+                                        window.dispatch_action(
+                                            Box::new(git::InteractiveRebase {
+                                                base: Some(base.clone()),
+                                            }),
+                                            cx,
+                                        );
+                                    })
                             }),
                     ),
             )
@@ -1779,7 +2200,13 @@ impl GitGraph {
                         let commit_x =
                             lane_center_x(bounds, row.lane as f32, horizontal_scroll_offset);
 
-                        draw_commit_circle(commit_x, row_y_center, row_color, window);
+                        draw_commit_circle(
+                            commit_x,
+                            row_y_center,
+                            row_color,
+                            row.data.parents.len() > 1,
+                            window,
+                        );
                     }
 
                     for line in commit_lines {
@@ -2158,6 +2585,7 @@ impl Render for GitGraph {
                 .child({
                     let row_height = self.row_height;
                     let selected_entry_idx = self.selected_entry_idx;
+                    let marked_entry_indices = self.marked_entry_indices.clone();
                     let hovered_entry_idx = self.hovered_entry_idx;
                     let weak_self = cx.weak_entity();
                     let focus_handle = self.focus_handle.clone();
@@ -2194,7 +2622,8 @@ impl Render for GitGraph {
                                 cx,
                             )
                             .map_row(move |(index, row), window, cx| {
-                                let is_selected = selected_entry_idx == Some(index);
+                                let is_selected = selected_entry_idx == Some(index)
+                                    || marked_entry_indices.contains(&index);
                                 let is_hovered = hovered_entry_idx == Some(index);
                                 let is_focused = focus_handle.is_focused(window);
                                 let weak = weak_self.clone();
@@ -2226,10 +2655,26 @@ impl Render for GitGraph {
                                             })
                                             .ok();
                                     })
+                                    .on_mouse_down(MouseButton::Right, {
+                                        let weak = weak.clone();
+                                        move |event, window, cx| {
+                                            weak.update(cx, |this, cx| {
+                                                this.deploy_context_menu(
+                                                    event.position,
+                                                    index,
+                                                    window,
+                                                    cx,
+                                                );
+                                            })
+                                            .ok();
+                                            cx.stop_propagation();
+                                        }
+                                    })
                                     .on_click(move |event, window, cx| {
                                         let click_count = event.click_count();
+                                        let toggle_mark = event.modifiers().secondary();
                                         weak.update(cx, |this, cx| {
-                                            this.select_entry(index, cx);
+                                            this.click_entry(index, toggle_mark, cx);
                                             if click_count >= 2 {
                                                 this.open_commit_view(index, window, cx);
                                             }
@@ -2261,7 +2706,7 @@ impl Render for GitGraph {
                 })
         };
 
-        div()
+        v_flex()
             .key_context("GitGraph")
             .track_focus(&self.focus_handle)
             .size_full()
@@ -2275,7 +2720,9 @@ impl Render for GitGraph {
             .on_action(cx.listener(Self::select_next))
             .on_action(cx.listener(Self::select_last))
             .on_action(cx.listener(Self::confirm))
-            .child(content)
+            .on_action(cx.listener(Self::focus_search))
+            .child(self.render_search_bar(cx))
+            .child(div().flex_1().min_h_0().child(content))
             .children(self.context_menu.as_ref().map(|(menu, position, _)| {
                 deferred(
                     anchored()
@@ -2466,7 +2913,7 @@ mod tests {
     use collections::{HashMap, HashSet};
     use fs::FakeFs;
     use git::Oid;
-    use git::repository::InitialGraphCommitData;
+    use git::repository::{InProgressOperationKind, InitialGraphCommitData};
     use gpui::TestAppContext;
     use project::Project;
     use project::git_store::{GitStoreEvent, RepositoryEvent};
@@ -3200,6 +3647,145 @@ mod tests {
         );
     }
 
+    #[gpui::test]
+    async fn test_search_selects_matching_commits(cx: &mut TestAppContext) {
+        init_test_with_theme(cx);
+
+        let fs = FakeFs::new(cx.executor());
+        fs.insert_tree(
+            Path::new("/project"),
+            json!({
+                ".git": {},
+                "file.txt": "content",
+            }),
+        )
+        .await;
+
+        let mut rng = StdRng::seed_from_u64(42);
+        let commits = generate_random_commit_dag(&mut rng, 10, false);
+        fs.set_graph_commits(Path::new("/project/.git"), commits.clone());
+        fs.with_git_state(Path::new("/project/.git"), true, |state| {
+            for (index, commit) in commits.iter().enumerate() {
+                let text = if index == 3 || index == 7 {
+                    "Jane Doe: Fix the search bar"
+                } else {
+                    "John Doe: Update dependencies"
+                };
+                state
+                    .commit_search_text
+                    .insert(commit.sha, text.to_string());
+            }
+        })
+        .unwrap();
+
+        let project = Project::test(fs.clone(), [Path::new("/project")], cx).await;
+        cx.run_until_parked();
+
+        let (multi_workspace, cx) =
+            cx.add_window_view(|window, cx| MultiWorkspace::test_new(project.clone(), window, cx));
+        let workspace_weak =
+            multi_workspace.read_with(&*cx, |multi, _| multi.workspace().downgrade());
+        let git_graph = cx.new_window_entity(|window, cx| {
+            GitGraph::new(project.clone(), workspace_weak, window, cx)
+        });
+        cx.run_until_parked();
+
+        git_graph.update_in(cx, |graph, window, cx| {
+            graph
+                .search_editor
+                .update(cx, |editor, cx| editor.set_text("SEARCH", window, cx));
+        });
+        cx.executor().advance_clock(SEARCH_DEBOUNCE);
+        cx.run_until_parked();
+
+        git_graph.update(cx, |graph, cx| {
+            assert_eq!(
+                graph.search_matches.as_ref().map(|matches| matches.len()),
+                Some(2)
+            );
+            assert_eq!(graph.selected_entry_idx, Some(3));
+            graph.select_adjacent_match(true, cx);
+            assert_eq!(graph.selected_entry_idx, Some(7));
+            graph.select_adjacent_match(true, cx);
+            assert_eq!(graph.selected_entry_idx, Some(3));
+            graph.select_adjacent_match(false, cx);
+            assert_eq!(graph.selected_entry_idx, Some(7));
+        });
+
+        git_graph.update_in(cx, |graph, window, cx| {
+            graph
+                .search_editor
+                .update(cx, |editor, cx| editor.set_text("", window, cx));
+        });
+        cx.run_until_parked();
+        git_graph.read_with(cx, |graph, _| assert_eq!(graph.search_matches, None));
+    }
+
+    #[gpui::test]
+    async fn test_cherry_pick_marked_commits(cx: &mut TestAppContext) {
+        init_test_with_theme(cx);
+
+        let fs = FakeFs::new(cx.executor());
+        fs.insert_tree(
+            Path::new("/project"),
+            json!({
+                ".git": {},
+                "file.txt": "content",
+            }),
+        )
+        .await;
+        let mut rng = StdRng::seed_from_u64(42);
+        let commits = generate_random_commit_dag(&mut rng, 10, false);
+        fs.set_graph_commits(Path::new("/project/.git"), commits);
+
+        let project = Project::test(fs.clone(), [Path::new("/project")], cx).await;
+        cx.run_until_parked();
+
+        let (multi_workspace, cx) =
+            cx.add_window_view(|window, cx| MultiWorkspace::test_new(project.clone(), window, cx));
+        let workspace_weak =
+            multi_workspace.read_with(&*cx, |multi, _| multi.workspace().downgrade());
+        let git_graph = cx.new_window_entity(|window, cx| {
+            GitGraph::new(project.clone(), workspace_weak, window, cx)
+        });
+        cx.run_until_parked();
+
+        let expected_shas = git_graph.update(cx, |graph, cx| {
+            graph.click_entry(5, false, cx);
+            graph.click_entry(2, true, cx);
+            graph.click_entry(7, true, cx);
+            graph.click_entry(2, true, cx);
+            let sha = |idx: usize| graph.graph_data.commits[idx].data.sha.to_string();
+            let expected_shas = vec![sha(7), sha(5)];
+            assert_eq!(graph.selected_entry_idx, Some(7));
+            assert_eq!(graph.selected_commit_shas(), expected_shas);
+            expected_shas
+        });
+        fs.with_git_state(Path::new("/project/.git"), false, |state| {
+            state
+                .conflicting_cherry_picks
+                .insert(expected_shas[1].clone());
+        })
+        .unwrap();
+
+        git_graph.update(cx, |graph, cx| graph.cherry_pick(true, cx));
+        cx.run_until_parked();
+
+        let (cherry_picks, operation) = fs
+            .with_git_state(Path::new("/project/.git"), false, |state| {
+                (state.cherry_picks.clone(), state.in_progress_operation)
+            })
+            .unwrap();
+        assert_eq!(cherry_picks, vec![(expected_shas, true)]);
+        assert_eq!(
+            operation.map(|operation| operation.kind),
+            Some(InProgressOperationKind::CherryPick)
+        );
+        git_graph.read_with(cx, |graph, _| {
+            assert!(graph.marked_entry_indices.is_empty())
+        });
+    }
+
     #[gpui::test]
     async fn test_graph_data_repopulated_from_cache_after_repo_switch(cx: &mut TestAppContext) {
         init_test_with_theme(cx);
@@ -1,6 +1,7 @@
 mod azure;
 mod bitbucket;
 mod chromium;
+mod custom;
 mod forgejo;
 mod gitea;
 mod gitee;
@@ -11,6 +12,7 @@ mod sourcehut;
 pub use azure::*;
 pub use bitbucket::*;
 pub use chromium::*;
+pub use custom::*;
 pub use forgejo::*;
 pub use gitea::*;
 pub use gitee::*;
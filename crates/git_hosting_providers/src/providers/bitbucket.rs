@@ -255,6 +255,22 @@ impl GitHostingProvider for Bitbucket {
         permalink
     }
 
+    fn build_branch_url(&self, remote: &ParsedGitRemote, branch: &str) -> Option<Url> {
+        let ParsedGitRemote { owner, repo } = remote;
+        let branch = urlencoding::encode(branch);
+        if self.is_self_hosted() {
+            let mut url = self
+                .base_url()
+                .join(&format!("projects/{owner}/repos/{repo}/browse"))
+                .ok()?;
+            url.set_query(Some(&format!("at=refs%2Fheads%2F{branch}")));
+            return Some(url);
+        }
+        self.base_url()
+            .join(&format!("{owner}/{repo}/branch/{branch}"))
+            .ok()
+    }
+
     fn extract_pull_request(&self, remote: &ParsedGitRemote, message: &str) -> Option<PullRequest> {
         // Check first line of commit message for PR references
         let first_line = message.lines().next()?;
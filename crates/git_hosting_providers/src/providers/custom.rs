@@ -0,0 +1,360 @@
+use std::str::FromStr;
+
+use anyhow::{Context as _, Result, anyhow};
+use settings::GitHostingUrlTemplates;
+use url::Url;
+use urlencoding::encode;
+use util::ResultExt as _;
+
+use git::{
+    BuildCommitPermalinkParams, BuildPermalinkParams, GitHostingProvider, ParsedGitRemote,
+    RemoteUrl,
+};
+
+const DEFAULT_COMMIT_TEMPLATE: &str = "{base_url}/{owner}/{repo}/commit/{sha}";
+const DEFAULT_FILE_TEMPLATE: &str = "{base_url}/{owner}/{repo}/blob/{sha}/{path}";
+const DEFAULT_BRANCH_TEMPLATE: &str = "{base_url}/{owner}/{repo}/tree/{branch}";
+const DEFAULT_LINE_TEMPLATE: &str = "L{line}";
+const DEFAULT_LINE_RANGE_TEMPLATE: &str = "L{start_line}-L{end_line}";
+
+/// A self-hosted forge whose URLs are built from user-provided templates.
+#[derive(Debug)]
+pub struct Custom {
+    name: String,
+    base_url: Url,
+    templates: GitHostingUrlTemplates,
+}
+
+impl Custom {
+    /// Fails when one of the URL templates doesn't render to a URL, so that a typo in the
+    /// settings is reported rather than every link silently pointing at the base URL.
+    pub fn new(name: &str, base_url: Url, templates: GitHostingUrlTemplates) -> Result<Self> {
+        let provider = Self {
+            name: name.to_string(),
+            base_url,
+            templates,
+        };
+        let remote = ParsedGitRemote {
+            owner: "owner".into(),
+            repo: "repo".into(),
+        };
+        for (kind, template, replacements) in [
+            (
+                "commit",
+                &provider.templates.commit,
+                &[("sha", "0000000")][..],
+            ),
+            (
+                "file",
+                &provider.templates.file,
+                &[("sha", "0000000"), ("path", "src/main.rs")][..],
+            ),
+            (
+                "branch",
+                &provider.templates.branch,
+                &[("branch", "main")][..],
+            ),
+        ] {
+            let Some(template) = template else {
+                continue;
+            };
+            let url = provider.render(template, &remote, replacements);
+            let url = if let Some(start) = url.find('{')
+                && let Some(len) = url[start..].find('}')
+            {
+                Err(anyhow!(
+                    "unknown placeholder {} in \"{template}\"",
+                    &url[start..=start + len]
+                ))
+            } else {
+                Url::parse(&url).with_context(|| format!("\"{template}\" renders to \"{url}\""))
+            };
+            url.with_context(|| {
+                format!("invalid {kind} URL template of git hosting provider \"{name}\"")
+            })?;
+        }
+        Ok(provider)
+    }
+
+    fn render(
+        &self,
+        template: &str,
+        remote: &ParsedGitRemote,
+        replacements: &[(&str, &str)],
+    ) -> String {
+        let base_url = self.base_url.as_str().trim_end_matches('/');
+        render_template(
+            template,
+            [
+                ("base_url", base_url),
+                ("owner", remote.owner.as_ref()),
+                ("repo", remote.repo.as_ref()),
+            ]
+            .iter()
+            .chain(replacements),
+        )
+    }
+
+    fn render_url(
+        &self,
+        template: &str,
+        remote: &ParsedGitRemote,
+        replacements: &[(&str, &str)],
+    ) -> Url {
+        let url = self.render(template, remote, replacements);
+        Url::parse(&url)
+            .with_context(|| {
+                format!(
+                    "URL template \"{template}\" of git hosting provider \"{}\" renders to \"{url}\"",
+                    self.name
+                )
+            })
+            .log_err()
+            .unwrap_or_else(|| self.base_url())
+    }
+}
+
+fn render_template<'a>(
+    template: &str,
+    replacements: impl IntoIterator<Item = &'a (&'a str, &'a str)>,
+) -> String {
+    replacements
+        .into_iter()
+        .fold(template.to_string(), |text, (name, value)| {
+            text.replace(&format!("{{{name}}}"), value)
+        })
+}
+
+impl GitHostingProvider for Custom {
+    fn name(&self) -> String {
+        self.name.clone()
+    }
+
+    fn base_url(&self) -> Url {
+        self.base_url.clone()
+    }
+
+    fn supports_avatars(&self) -> bool {
+        false
+    }
+
+    fn format_line_number(&self, line: u32) -> String {
+        let template = self
+            .templates
+            .line
+            .as_deref()
+            .unwrap_or(DEFAULT_LINE_TEMPLATE);
+        render_template(template, &[("line", line.to_string().as_str())])
+    }
+
+    fn format_line_numbers(&self, start_line: u32, end_line: u32) -> String {
+        let template = self
+            .templates
+            .line_range
+            .as_deref()
+            .unwrap_or(DEFAULT_LINE_RANGE_TEMPLATE);
+        render_template(
+            template,
+            &[
+                ("start_line", start_line.to_string().as_str()),
+                ("end_line", end_line.to_string().as_str()),
+            ],
+        )
+    }
+
+    fn parse_remote_url(&self, url: &str) -> Option<ParsedGitRemote> {
+        let url = RemoteUrl::from_str(url).ok()?;
+
+        let host = url.host_str()?;
+        if host != self.base_url.host_str()? {
+            return None;
+        }
+
+        let mut path_segments = url.path_segments()?.collect::<Vec<_>>();
+        let repo = path_segments.pop()?.trim_end_matches(".git");
+        let owner = path_segments.join("/");
+
+        Some(ParsedGitRemote {
+            owner: owner.into(),
+            repo: repo.into(),
+        })
+    }
+
+    fn build_commit_permalink(
+        &self,
+        remote: &ParsedGitRemote,
+        params: BuildCommitPermalinkParams,
+    ) -> Url {
+        let template = self
+            .templates
+            .commit
+            .as_deref()
+            .unwrap_or(DEFAULT_COMMIT_TEMPLATE);
+        self.render_url(template, remote, &[("sha", params.sha)])
+    }
+
+    fn build_permalink(&self, remote: ParsedGitRemote, params: BuildPermalinkParams) -> Url {
+        let BuildPermalinkParams {
+            sha,
+            path,
+            selection,
+        } = params;
+
+        let template = self
+            .templates
+            .file
+            .as_deref()
+            .unwrap_or(DEFAULT_FILE_TEMPLATE);
+        let mut permalink = self.render_url(template, &remote, &[("sha", sha), ("path", &path)]);
+        permalink.set_fragment(
+            selection
+                .map(|selection| self.line_fragment(&selection))
+                .as_deref(),
+        );
+        permalink
+    }
+
+    fn build_branch_url(&self, remote: &ParsedGitRemote, branch: &str) -> Option<Url> {
+        let template = self
+            .templates
+            .branch
+            .as_deref()
+            .unwrap_or(DEFAULT_BRANCH_TEMPLATE);
+        Some(self.render_url(template, remote, &[("branch", &encode(branch))]))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use git::repository::repo_path;
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    fn custom_provider(templates: GitHostingUrlTemplates) -> Custom {
+        Custom::new(
+            "Corp Forge",
+            Url::parse("https://code.corp.com").unwrap(),
+            templates,
+        )
+        .unwrap()
+    }
+
+    fn zed_remote() -> ParsedGitRemote {
+        ParsedGitRemote {
+            owner: "zed-industries".into(),
+            repo: "zed".into(),
+        }
+    }
+
+    #[test]
+    fn test_parse_remote_url() {
+        let provider = custom_provider(GitHostingUrlTemplates::default());
+        assert_eq!(
+            provider.parse_remote_url("git@code.corp.com:platform/tools/zed.git"),
+            Some(ParsedGitRemote {
+                owner: "platform/tools".into(),
+                repo: "zed".into(),
+            })
+        );
+        assert_eq!(
+            provider.parse_remote_url("https://github.com/zed-industries/zed.git"),
+            None
+        );
+    }
+
+    #[test]
+    fn test_build_urls_with_default_templates() {
+        let provider = custom_provider(GitHostingUrlTemplates::default());
+
+        let permalink = provider.build_permalink(
+            zed_remote(),
+            BuildPermalinkParams::new(
+                "faa6f979be417239b2e070dbbf6392b909224e0b",
+                &repo_path("crates/editor/src/git/permalink.rs"),
+                Some(6..9),
+            ),
+        );
+        assert_eq!(
+            permalink.as_str(),
+            "https://code.corp.com/zed-industries/zed/blob/faa6f979be417239b2e070dbbf6392b909224e0b/crates/editor/src/git/permalink.rs#L7-L10"
+        );
+
+        let branch_url = provider
+            .build_branch_url(&zed_remote(), "feature/new-feature")
+            .unwrap();
+        assert_eq!(
+            branch_url.as_str(),
+            "https://code.corp.com/zed-industries/zed/tree/feature%2Fnew-feature"
+        );
+    }
+
+    #[test]
+    fn test_build_urls_with_custom_templates() {
+        let provider = custom_provider(GitHostingUrlTemplates {
+            commit: Some("{base_url}/{owner}/{repo}/+/{sha}".to_string()),
+            file: Some("{base_url}/{owner}/{repo}/+/{sha}/{path}".to_string()),
+            branch: Some("{base_url}/{owner}/{repo}/+/refs/heads/{branch}".to_string()),
+            line: Some("{line}".to_string()),
+            line_range: Some("{start_line}:{end_line}".to_string()),
+        });
+
+        let commit = provider.build_commit_permalink(
+            &zed_remote(),
+            BuildCommitPermalinkParams {
+                sha: "faa6f979be417239b2e070dbbf6392b909224e0b",
+            },
+        );
+        assert_eq!(
+            commit.as_str(),
+            "https://code.corp.com/zed-industries/zed/+/faa6f979be417239b2e070dbbf6392b909224e0b"
+        );
+
+        let permalink = provider.build_permalink(
+            zed_remote(),
+            BuildPermalinkParams::new(
+                "faa6f979be417239b2e070dbbf6392b909224e0b",
+                &repo_path("README.md"),
+                Some(2..2),
+            ),
+        );
+        assert_eq!(
+            permalink.as_str(),
+            "https://code.corp.com/zed-industries/zed/+/faa6f979be417239b2e070dbbf6392b909224e0b/README.md#3"
+        );
+
+        let branch_url = provider.build_branch_url(&zed_remote(), "main").unwrap();
+        assert_eq!(
+            branch_url.as_str(),
+            "https://code.corp.com/zed-industries/zed/+/refs/heads/main"
+        );
+    }
+
+    #[test]
+    fn test_invalid_templates() {
+        let new_provider = |templates| {
+            Custom::new(
+                "Corp Forge",
+                Url::parse("https://code.corp.com").unwrap(),
+                templates,
+            )
+        };
+        let error = new_provider(GitHostingUrlTemplates {
+            commit: Some("{base_url}/{owner}/{repo}/commit/{commit}".to_string()),
+            ..Default::default()
+        })
+        .unwrap_err();
+        assert_eq!(
+            format!("{error:#}"),
+            "invalid commit URL template of git hosting provider \"Corp Forge\": \
+             unknown placeholder {commit} in \"{base_url}/{owner}/{repo}/commit/{commit}\""
+        );
+        assert!(
+            new_provider(GitHostingUrlTemplates {
+                branch: Some("{owner}/{repo}/tree/{branch}".to_string()),
+                ..Default::default()
+            })
+            .is_err()
+        );
+    }
+}
@@ -157,6 +157,16 @@ impl GitHostingProvider for Gitea {
             .unwrap()
     }
 
+    fn build_branch_url(&self, remote: &ParsedGitRemote, branch: &str) -> Option<Url> {
+        let ParsedGitRemote { owner, repo } = remote;
+        self.base_url()
+            .join(&format!(
+                "{owner}/{repo}/src/branch/{}",
+                urlencoding::encode(branch)
+            ))
+            .ok()
+    }
+
     fn build_permalink(&self, remote: ParsedGitRemote, params: BuildPermalinkParams) -> Url {
         let ParsedGitRemote { owner, repo } = remote;
         let BuildPermalinkParams {
@@ -1,3 +1,4 @@
+use std::collections::HashMap;
 use std::str::FromStr;
 use std::sync::{Arc, LazyLock};
 
@@ -5,19 +6,25 @@ use anyhow::{Context as _, Result, bail};
 use async_trait::async_trait;
 use futures::AsyncReadExt;
 use gpui::SharedString;
-use http_client::{AsyncBody, HttpClient, HttpRequestExt, Request};
+use http_client::{AsyncBody, Builder, HttpClient, HttpRequestExt, Request};
 use regex::Regex;
-use serde::Deserialize;
+use serde::{Deserialize, Serialize, de::DeserializeOwned};
 use url::Url;
 use urlencoding::encode;
 
 use git::{
-    BuildCommitPermalinkParams, BuildPermalinkParams, GitHostingProvider, ParsedGitRemote,
-    PullRequest, RemoteUrl,
+    BuildCommitPermalinkParams, BuildPermalinkParams, CheckStatus, CreatePullRequestParams,
+    GitHostingProvider, Issue, ParsedGitRemote, PullRequest, PullRequestCheck, PullRequestStatus,
+    PullRequestSummary, RemoteUrl, ReviewState,
 };
 
 use crate::get_host_from_git_remote_url;
 
+/// The most items GitHub returns in one page of a list.
+const PAGE_SIZE: usize = 100;
+/// How many pages of a list are fetched at most, to bound the requests made for one update.
+const MAX_PAGES: usize = 10;
+
 fn pull_request_number_regex() -> &'static Regex {
     static PULL_REQUEST_NUMBER_REGEX: LazyLock<Regex> =
         LazyLock::new(|| Regex::new(r"\(#(\d+)\)$").unwrap());
@@ -62,6 +69,184 @@ struct User {
     pub avatar_url: String,
 }
 
+#[derive(Debug, Serialize)]
+struct NewPullRequest<'a> {
+    title: &'a str,
+    body: &'a str,
+    head: &'a str,
+    base: &'a str,
+}
+
+#[derive(Debug, Deserialize)]
+struct CreatedPullRequest {
+    number: u32,
+    html_url: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct OpenPullRequest {
+    number: u32,
+    title: String,
+    html_url: String,
+    user: Option<UserLogin>,
+    head: PullRequestHead,
+}
+
+impl OpenPullRequest {
+    fn summary(self, remote_owner: &str) -> Result<PullRequestSummary> {
+        // The label of the head is `owner:branch`, naming the fork the branch lives on.
+        let source_branch = match self.head.label.split_once(':') {
+            Some((owner, _)) if !owner.eq_ignore_ascii_case(remote_owner) => self.head.label,
+            _ => self.head.branch,
+        };
+        Ok(PullRequestSummary {
+            number: self.number,
+            title: self.title.into(),
+            author: self.user.map(|user| user.login.into()).unwrap_or_default(),
+            source_branch: source_branch.into(),
+            url: Url::parse(&self.html_url)?,
+        })
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct PullRequestHead {
+    sha: String,
+    #[serde(rename = "ref")]
+    branch: String,
+    label: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct GithubIssue {
+    number: u32,
+    title: String,
+    html_url: String,
+    /// Only set for issues that are pull requests.
+    pull_request: Option<serde::de::IgnoredAny>,
+}
+
+impl GithubIssue {
+    fn issue(self) -> Result<Issue> {
+        Ok(Issue {
+            number: self.number,
+            title: self.title.into(),
+            url: Url::parse(&self.html_url)?,
+            is_pull_request: self.pull_request.is_some(),
+        })
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct CheckRuns {
+    check_runs: Vec<CheckRun>,
+}
+
+#[derive(Debug, Deserialize)]
+struct CheckRun {
+    name: String,
+    status: String,
+    conclusion: Option<String>,
+    html_url: Option<String>,
+}
+
+impl CheckRun {
+    fn check(self) -> PullRequestCheck {
+        PullRequestCheck {
+            status: self.check_status(),
+            url: self
+                .html_url
+                .as_deref()
+                .and_then(|url| Url::parse(url).ok()),
+            name: self.name.into(),
+        }
+    }
+
+    fn check_status(&self) -> CheckStatus {
+        if self.status != "completed" {
+            return CheckStatus::Pending;
+        }
+        match self.conclusion.as_deref() {
+            Some("success" | "neutral" | "skipped") => CheckStatus::Success,
+            _ => CheckStatus::Failure,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct CombinedStatus {
+    statuses: Vec<CommitStatus>,
+}
+
+#[derive(Debug, Deserialize)]
+struct CommitStatus {
+    context: String,
+    state: String,
+    target_url: Option<String>,
+}
+
+impl CommitStatus {
+    fn check(self) -> PullRequestCheck {
+        let status = match self.state.as_str() {
+            "success" => CheckStatus::Success,
+            "pending" => CheckStatus::Pending,
+            _ => CheckStatus::Failure,
+        };
+        PullRequestCheck {
+            status,
+            url: self
+                .target_url
+                .as_deref()
+                .and_then(|url| Url::parse(url).ok()),
+            name: self.context.into(),
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct Review {
+    user: Option<UserLogin>,
+    state: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct UserLogin {
+    login: String,
+}
+
+/// GitHub lists every review of a pull request, oldest first, so only the latest approval or
+/// request for changes of each reviewer counts.
+fn review_state(reviews: &[Review]) -> Option<ReviewState> {
+    let mut latest_reviews = HashMap::new();
+    for review in reviews {
+        let Some(user) = &review.user else {
+            continue;
+        };
+        match review.state.as_str() {
+            "APPROVED" => {
+                latest_reviews.insert(user.login.as_str(), ReviewState::Approved);
+            }
+            "CHANGES_REQUESTED" => {
+                latest_reviews.insert(user.login.as_str(), ReviewState::ChangesRequested);
+            }
+            "DISMISSED" => {
+                latest_reviews.remove(user.login.as_str());
+            }
+            _ => {}
+        }
+    }
+    if latest_reviews
+        .values()
+        .any(|state| *state == ReviewState::ChangesRequested)
+    {
+        Some(ReviewState::ChangesRequested)
+    } else if latest_reviews.is_empty() {
+        None
+    } else {
+        Some(ReviewState::Approved)
+    }
+}
+
 #[derive(Debug)]
 pub struct Github {
     name: String,
@@ -108,6 +293,34 @@ impl Github {
         ))
     }
 
+    /// The URL of a REST API endpoint: `api.github.com` for GitHub itself, and `/api/v3` on the
+    /// instance's own host for GitHub Enterprise.
+    fn api_url(&self, path: &str) -> Result<String> {
+        let Some(host) = self.base_url.host_str() else {
+            bail!("failed to get host from github base url");
+        };
+        if host == "github.com" {
+            Ok(format!("https://api.github.com/{path}"))
+        } else {
+            Ok(format!("https://{host}/api/v3/{path}"))
+        }
+    }
+
+    /// The token to send to this instance. Like the `gh` CLI, `GITHUB_TOKEN` is only sent to
+    /// github.com, and `GH_ENTERPRISE_TOKEN` only to the GitHub Enterprise host that `GH_HOST`
+    /// names, so that no token leaks to other hosts.
+    fn api_token(&self) -> Option<String> {
+        let host = self.base_url.host_str()?;
+        let token = if host == "github.com" {
+            std::env::var("GITHUB_TOKEN").ok()
+        } else if std::env::var("GH_HOST").is_ok_and(|gh_host| gh_host == host) {
+            std::env::var("GH_ENTERPRISE_TOKEN").ok()
+        } else {
+            None
+        };
+        token.filter(|token| !token.is_empty())
+    }
+
     async fn fetch_github_commit_author(
         &self,
         repo_owner: &str,
@@ -115,18 +328,8 @@ impl Github {
         commit: &str,
         client: &Arc<dyn HttpClient>,
     ) -> Result<Option<User>> {
-        let Some(host) = self.base_url.host_str() else {
-            bail!("failed to get host from github base url");
-        };
-        let url = format!("https://api.{host}/repos/{repo_owner}/{repo}/commits/{commit}");
-
-        let mut request = Request::get(&url)
-            .header("Content-Type", "application/json")
-            .follow_redirects(http_client::RedirectPolicy::FollowAll);
-
-        if let Ok(github_token) = std::env::var("GITHUB_TOKEN") {
-            request = request.header("Authorization", format!("Bearer {}", github_token));
-        }
+        let url = self.api_url(&format!("repos/{repo_owner}/{repo}/commits/{commit}"))?;
+        let request = self.api_request(&url);
 
         let mut response = client
             .send(request.body(AsyncBody::default())?)
@@ -150,6 +353,47 @@ impl Github {
             .map(|commit| commit.author)
             .context("failed to deserialize GitHub commit details")
     }
+
+    /// A GET request to the API, authenticated when there is a token for this instance.
+    /// Redirects are only followed without a token, since they could carry it to another host.
+    fn api_request(&self, url: &str) -> Builder {
+        let request = Request::get(url)
+            .header("Content-Type", "application/json")
+            .header("Accept", "application/vnd.github+json");
+        match self.api_token() {
+            Some(github_token) => request
+                .header("Authorization", format!("Bearer {}", github_token))
+                .follow_redirects(http_client::RedirectPolicy::NoFollow),
+            None => request.follow_redirects(http_client::RedirectPolicy::FollowAll),
+        }
+    }
+
+    async fn fetch_github_api<T: DeserializeOwned>(
+        &self,
+        path: &str,
+        client: &Arc<dyn HttpClient>,
+    ) -> Result<T> {
+        let url = self.api_url(path)?;
+        let request = self.api_request(&url);
+
+        let mut response = client
+            .send(request.body(AsyncBody::default())?)
+            .await
+            .with_context(|| format!("error fetching {:?}", url))?;
+
+        let mut body = Vec::new();
+        response.body_mut().read_to_end(&mut body).await?;
+
+        if !response.status().is_success() {
+            let text = String::from_utf8_lossy(body.as_slice());
+            bail!(
+                "status error {}, response: {text:?}",
+                response.status().as_u16()
+            );
+        }
+
+        serde_json::from_slice(&body).with_context(|| format!("failed to deserialize {:?}", url))
+    }
 }
 
 #[async_trait]
@@ -247,6 +491,194 @@ impl GitHostingProvider for Github {
             .ok()
     }
 
+    fn can_create_pull_requests(&self) -> bool {
+        self.api_token().is_some()
+    }
+
+    async fn create_pull_request(
+        &self,
+        remote: &ParsedGitRemote,
+        params: CreatePullRequestParams,
+        http_client: Arc<dyn HttpClient>,
+    ) -> Result<PullRequest> {
+        let Some(github_token) = self.api_token() else {
+            bail!("no GitHub token is set for {}", self.base_url);
+        };
+        let ParsedGitRemote { owner, repo } = remote;
+        let url = self.api_url(&format!("repos/{owner}/{repo}/pulls"))?;
+        let body = serde_json::to_string(&NewPullRequest {
+            title: &params.title,
+            body: &params.body,
+            head: &params.source_branch,
+            base: &params.target_branch,
+        })?;
+
+        let request = Request::post(&url)
+            .header("Content-Type", "application/json")
+            .header("Accept", "application/vnd.github+json")
+            .header("Authorization", format!("Bearer {}", github_token));
+
+        let mut response = http_client
+            .send(request.body(AsyncBody::from(body))?)
+            .await
+            .with_context(|| format!("error creating GitHub pull request at {:?}", url))?;
+
+        let mut body = Vec::new();
+        response.body_mut().read_to_end(&mut body).await?;
+
+        if !response.status().is_success() {
+            let text = String::from_utf8_lossy(body.as_slice());
+            bail!(
+                "status error {}, response: {text:?}",
+                response.status().as_u16()
+            );
+        }
+
+        let pull_request = serde_json::from_slice::<CreatedPullRequest>(&body)
+            .context("failed to deserialize GitHub pull request")?;
+        Ok(PullRequest {
+            number: pull_request.number,
+            url: Url::parse(&pull_request.html_url)?,
+        })
+    }
+
+    async fn pull_request_status(
+        &self,
+        remote: &ParsedGitRemote,
+        head_owner: &str,
+        source_branch: &str,
+        http_client: Arc<dyn HttpClient>,
+    ) -> Result<Option<PullRequestStatus>> {
+        // Polling without a token would quickly run into GitHub's rate limit.
+        if self.api_token().is_none() {
+            return Ok(None);
+        }
+        let ParsedGitRemote { owner, repo } = remote;
+        let pull_requests: Vec<OpenPullRequest> = self
+            .fetch_github_api(
+                &format!(
+                    "repos/{owner}/{repo}/pulls?state=open&head={}",
+                    encode(&format!("{head_owner}:{source_branch}"))
+                ),
+                &http_client,
+            )
+            .await?;
+        let Some(pull_request) = pull_requests.into_iter().next() else {
+            return Ok(None);
+        };
+        let head_sha = &pull_request.head.sha;
+
+        let mut checks = Vec::new();
+        for page in 1..=MAX_PAGES {
+            let query = format!("per_page={PAGE_SIZE}&page={page}");
+            let check_runs: CheckRuns = self
+                .fetch_github_api(
+                    &format!("repos/{owner}/{repo}/commits/{head_sha}/check-runs?{query}"),
+                    &http_client,
+                )
+                .await?;
+            let is_last_page = check_runs.check_runs.len() < PAGE_SIZE;
+            checks.extend(check_runs.check_runs.into_iter().map(CheckRun::check));
+            if is_last_page {
+                break;
+            }
+        }
+        // CI services that predate check runs report through commit statuses instead.
+        let combined_status: CombinedStatus = self
+            .fetch_github_api(
+                &format!("repos/{owner}/{repo}/commits/{head_sha}/status?per_page={PAGE_SIZE}"),
+                &http_client,
+            )
+            .await?;
+        checks.extend(
+            combined_status
+                .statuses
+                .into_iter()
+                .map(CommitStatus::check),
+        );
+
+        let reviews: Vec<Review> = self
+            .fetch_github_api(
+                &format!("repos/{owner}/{repo}/pulls/{}/reviews", pull_request.number),
+                &http_client,
+            )
+            .await?;
+
+        Ok(Some(PullRequestStatus {
+            number: pull_request.number,
+            title: pull_request.title.into(),
+            url: Url::parse(&pull_request.html_url)?,
+            checks,
+            review: review_state(&reviews),
+        }))
+    }
+
+    async fn list_pull_requests(
+        &self,
+        remote: &ParsedGitRemote,
+        http_client: Arc<dyn HttpClient>,
+    ) -> Result<Vec<PullRequestSummary>> {
+        let ParsedGitRemote { owner, repo } = remote;
+        let mut pull_requests = Vec::new();
+        for page in 1..=MAX_PAGES {
+            let query = format!("state=open&per_page={PAGE_SIZE}&page={page}");
+            let page_pull_requests: Vec<OpenPullRequest> = self
+                .fetch_github_api(&format!("repos/{owner}/{repo}/pulls?{query}"), &http_client)
+                .await?;
+            let is_last_page = page_pull_requests.len() < PAGE_SIZE;
+            pull_requests.extend(page_pull_requests);
+            if is_last_page {
+                break;
+            }
+        }
+        pull_requests
+            .into_iter()
+            .map(|pull_request| pull_request.summary(owner))
+            .collect()
+    }
+
+    fn pull_request_head_ref(&self, number: u32) -> Option<String> {
+        Some(format!("refs/pull/{number}/head"))
+    }
+
+    async fn list_issues(
+        &self,
+        remote: &ParsedGitRemote,
+        http_client: Arc<dyn HttpClient>,
+    ) -> Result<Vec<Issue>> {
+        let ParsedGitRemote { owner, repo } = remote;
+        let issues: Vec<GithubIssue> = self
+            .fetch_github_api(
+                &format!("repos/{owner}/{repo}/issues?state=all&sort=updated&per_page=100"),
+                &http_client,
+            )
+            .await?;
+        issues.into_iter().map(GithubIssue::issue).collect()
+    }
+
+    async fn issue(
+        &self,
+        remote: &ParsedGitRemote,
+        number: u32,
+        http_client: Arc<dyn HttpClient>,
+    ) -> Result<Option<Issue>> {
+        let ParsedGitRemote { owner, repo } = remote;
+        let issue: GithubIssue = self
+            .fetch_github_api(
+                &format!("repos/{owner}/{repo}/issues/{number}"),
+                &http_client,
+            )
+            .await?;
+        issue.issue().map(Some)
+    }
+
+    fn build_branch_url(&self, remote: &ParsedGitRemote, branch: &str) -> Option<Url> {
+        let ParsedGitRemote { owner, repo } = remote;
+        self.base_url()
+            .join(&format!("{owner}/{repo}/tree/{}", encode(branch)))
+            .ok()
+    }
+
     fn extract_pull_request(&self, remote: &ParsedGitRemote, message: &str) -> Option<PullRequest> {
         let line = message.lines().next()?;
         let capture = pull_request_number_regex().captures(line)?;
@@ -574,6 +1006,23 @@ mod tests {
         assert_eq!(permalink.to_string(), expected_url.to_string())
     }
 
+    #[test]
+    fn test_api_url() {
+        assert_eq!(
+            Github::public_instance()
+                .api_url("repos/zed-industries/zed/pulls")
+                .unwrap(),
+            "https://api.github.com/repos/zed-industries/zed/pulls"
+        );
+        assert_eq!(
+            Github::from_remote_url("https://github.my-enterprise.com/zed-industries/zed")
+                .unwrap()
+                .api_url("repos/zed-industries/zed/pulls")
+                .unwrap(),
+            "https://github.my-enterprise.com/api/v3/repos/zed-industries/zed/pulls"
+        );
+    }
+
     #[test]
     fn test_build_create_pull_request_url() {
         let remote = ParsedGitRemote {
@@ -603,6 +1052,193 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_build_branch_url() {
+        let remote = ParsedGitRemote {
+            owner: "zed-industries".into(),
+            repo: "zed".into(),
+        };
+
+        let url = Github::public_instance()
+            .build_branch_url(&remote, "feature/new-feature")
+            .unwrap();
+        assert_eq!(
+            url.as_str(),
+            "https://github.com/zed-industries/zed/tree/feature%2Fnew-feature"
+        );
+    }
+
+    #[test]
+    fn test_pull_request_review_state() {
+        let reviews: Vec<Review> = serde_json::from_str(indoc! {r#"
+            [
+                { "user": { "login": "alice" }, "state": "CHANGES_REQUESTED" },
+                { "user": { "login": "bob" }, "state": "COMMENTED" },
+                { "user": { "login": "alice" }, "state": "APPROVED" }
+            ]
+        "#})
+        .unwrap();
+        assert_eq!(review_state(&reviews), Some(ReviewState::Approved));
+        assert_eq!(review_state(&reviews[1..2]), None);
+
+        let reviews: Vec<Review> = serde_json::from_str(indoc! {r#"
+            [
+                { "user": { "login": "alice" }, "state": "APPROVED" },
+                { "user": { "login": "bob" }, "state": "CHANGES_REQUESTED" },
+                { "user": { "login": "carol" }, "state": "CHANGES_REQUESTED" },
+                { "user": { "login": "carol" }, "state": "DISMISSED" }
+            ]
+        "#})
+        .unwrap();
+        assert_eq!(review_state(&reviews), Some(ReviewState::ChangesRequested));
+        assert_eq!(review_state(&reviews[2..]), None);
+    }
+
+    #[test]
+    fn test_pull_request_summary() {
+        let pull_requests: Vec<OpenPullRequest> = serde_json::from_str(indoc! {r#"
+            [
+                {
+                    "number": 42,
+                    "title": "Add a checkout picker",
+                    "html_url": "https://github.com/zed-industries/zed/pull/42",
+                    "user": { "login": "alice" },
+                    "head": { "sha": "abc", "ref": "checkout-picker", "label": "zed-industries:checkout-picker" }
+                },
+                {
+                    "number": 43,
+                    "title": "Fix typo",
+                    "html_url": "https://github.com/zed-industries/zed/pull/43",
+                    "user": null,
+                    "head": { "sha": "def", "ref": "main", "label": "bob:main" }
+                }
+            ]
+        "#})
+        .unwrap();
+        let summaries = pull_requests
+            .into_iter()
+            .map(|pull_request| pull_request.summary("zed-industries").unwrap())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            summaries,
+            [
+                PullRequestSummary {
+                    number: 42,
+                    title: "Add a checkout picker".into(),
+                    author: "alice".into(),
+                    source_branch: "checkout-picker".into(),
+                    url: Url::parse("https://github.com/zed-industries/zed/pull/42").unwrap(),
+                },
+                PullRequestSummary {
+                    number: 43,
+                    title: "Fix typo".into(),
+                    author: "".into(),
+                    source_branch: "bob:main".into(),
+                    url: Url::parse("https://github.com/zed-industries/zed/pull/43").unwrap(),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_github_issues() {
+        let issues: Vec<GithubIssue> = serde_json::from_str(indoc! {r#"
+            [
+                {
+                    "number": 12,
+                    "title": "Blame is wrong for renamed files",
+                    "html_url": "https://github.com/zed-industries/zed/issues/12",
+                    "pull_request": null
+                },
+                {
+                    "number": 13,
+                    "title": "Fix blame for renamed files",
+                    "html_url": "https://github.com/zed-industries/zed/pull/13",
+                    "pull_request": { "url": "https://api.github.com/repos/zed-industries/zed/pulls/13" }
+                }
+            ]
+        "#})
+        .unwrap();
+        let issues = issues
+            .into_iter()
+            .map(|issue| issue.issue().unwrap())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            issues,
+            [
+                Issue {
+                    number: 12,
+                    title: "Blame is wrong for renamed files".into(),
+                    url: Url::parse("https://github.com/zed-industries/zed/issues/12").unwrap(),
+                    is_pull_request: false,
+                },
+                Issue {
+                    number: 13,
+                    title: "Fix blame for renamed files".into(),
+                    url: Url::parse("https://github.com/zed-industries/zed/pull/13").unwrap(),
+                    is_pull_request: true,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_commit_status_checks() {
+        let combined_status: CombinedStatus = serde_json::from_str(indoc! {r#"
+            {
+                "statuses": [
+                    { "context": "ci/jenkins", "state": "success", "target_url": "https://ci.example.com/1" },
+                    { "context": "ci/travis", "state": "pending", "target_url": null },
+                    { "context": "ci/circle", "state": "error", "target_url": null }
+                ]
+            }
+        "#})
+        .unwrap();
+        let checks = combined_status
+            .statuses
+            .into_iter()
+            .map(CommitStatus::check)
+            .map(|check| (check.name, check.status))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            checks,
+            [
+                ("ci/jenkins".into(), CheckStatus::Success),
+                ("ci/travis".into(), CheckStatus::Pending),
+                ("ci/circle".into(), CheckStatus::Failure),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_check_run_status() {
+        let check_runs: CheckRuns = serde_json::from_str(indoc! {r#"
+            {
+                "check_runs": [
+                    { "name": "lint", "status": "completed", "conclusion": "success", "html_url": null },
+                    { "name": "docs", "status": "completed", "conclusion": "skipped", "html_url": null },
+                    { "name": "test", "status": "in_progress", "conclusion": null, "html_url": null },
+                    { "name": "build", "status": "completed", "conclusion": "timed_out", "html_url": null }
+                ]
+            }
+        "#})
+        .unwrap();
+        let statuses = check_runs
+            .check_runs
+            .iter()
+            .map(CheckRun::check_status)
+            .collect::<Vec<_>>();
+        assert_eq!(
+            statuses,
+            [
+                CheckStatus::Success,
+                CheckStatus::Success,
+                CheckStatus::Pending,
+                CheckStatus::Failure
+            ]
+        );
+    }
+
     #[test]
     fn test_build_cdn_avatar_url_simple_email() {
         let url = build_cdn_avatar_url("user@example.com").unwrap();
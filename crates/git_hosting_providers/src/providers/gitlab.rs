@@ -7,13 +7,13 @@ use futures::AsyncReadExt;
 use gpui::SharedString;
 use http_client::{AsyncBody, HttpClient, HttpRequestExt, Request};
 use regex::Regex;
-use serde::Deserialize;
+use serde::{Deserialize, de::DeserializeOwned};
 use url::Url;
 use urlencoding::encode;
 
 use git::{
-    BuildCommitPermalinkParams, BuildPermalinkParams, GitHostingProvider, ParsedGitRemote,
-    PullRequest, RemoteUrl,
+    BuildCommitPermalinkParams, BuildPermalinkParams, GitHostingProvider, Issue, ParsedGitRemote,
+    PullRequest, PullRequestSummary, RemoteUrl,
 };
 
 fn merge_request_number_regex() -> &'static Regex {
@@ -28,6 +28,11 @@ fn merge_request_number_regex() -> &'static Regex {
 
 use crate::get_host_from_git_remote_url;
 
+/// The most items GitLab returns in one page of a list.
+const PAGE_SIZE: usize = 100;
+/// How many pages of a list are fetched at most, to bound the requests made for one update.
+const MAX_PAGES: usize = 10;
+
 #[derive(Debug, Deserialize)]
 struct CommitDetails {
     author_email: String,
@@ -38,6 +43,62 @@ struct AvatarInfo {
     avatar_url: String,
 }
 
+#[derive(Debug, Deserialize)]
+struct MergeRequest {
+    iid: u32,
+    title: String,
+    web_url: String,
+    author: Option<MergeRequestAuthor>,
+    source_branch: String,
+    source_project_id: u64,
+    target_project_id: u64,
+}
+
+#[derive(Debug, Deserialize)]
+struct MergeRequestAuthor {
+    username: String,
+}
+
+impl MergeRequest {
+    fn summary(self) -> Result<PullRequestSummary> {
+        let author = self
+            .author
+            .map(|author| author.username)
+            .unwrap_or_default();
+        // Merge requests only name the fork by its ID, and forks usually belong to their author.
+        let source_branch = if self.source_project_id == self.target_project_id {
+            self.source_branch
+        } else {
+            format!("{author}:{}", self.source_branch)
+        };
+        Ok(PullRequestSummary {
+            number: self.iid,
+            title: self.title.into(),
+            author: author.into(),
+            source_branch: source_branch.into(),
+            url: Url::parse(&self.web_url)?,
+        })
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct GitlabIssue {
+    iid: u32,
+    title: String,
+    web_url: String,
+}
+
+impl GitlabIssue {
+    fn issue(self) -> Result<Issue> {
+        Ok(Issue {
+            number: self.iid,
+            title: self.title.into(),
+            url: Url::parse(&self.web_url)?,
+            is_pull_request: false,
+        })
+    }
+}
+
 #[derive(Debug)]
 pub struct Gitlab {
     name: String,
@@ -144,6 +205,69 @@ impl Gitlab {
         serde_json::from_str::<Option<AvatarInfo>>(body_str)
             .context("failed to deserialize GitLab avatar info")
     }
+
+    /// The token to send to this instance. Like the `glab` CLI, `GITLAB_TOKEN` is only sent to
+    /// the host that `GITLAB_HOST` names, which defaults to gitlab.com, so that no token leaks to
+    /// other hosts.
+    fn api_token(&self) -> Option<String> {
+        let host = self.base_url.host_str()?;
+        let token_host = std::env::var("GITLAB_HOST")
+            .ok()
+            .filter(|gitlab_host| !gitlab_host.is_empty())
+            .unwrap_or_else(|| "gitlab.com".to_string());
+        let token_host = token_host
+            .strip_prefix("https://")
+            .unwrap_or(&token_host)
+            .trim_end_matches('/');
+        if token_host != host {
+            return None;
+        }
+        std::env::var("GITLAB_TOKEN")
+            .ok()
+            .filter(|token| !token.is_empty())
+    }
+
+    /// Requests a path below the API endpoint of the remote's project.
+    async fn fetch_gitlab_project_api<T: DeserializeOwned>(
+        &self,
+        remote: &ParsedGitRemote,
+        path: &str,
+        client: &Arc<dyn HttpClient>,
+    ) -> Result<T> {
+        let Some(host) = self.base_url.host_str() else {
+            bail!("failed to get host from gitlab base url");
+        };
+        let project_path = format!("{}/{}", remote.owner, remote.repo);
+        let project_path_encoded = urlencoding::encode(&project_path);
+        let url = format!("https://{host}/api/v4/projects/{project_path_encoded}/{path}");
+
+        // Redirects are only followed without a token, since they could carry it to another host.
+        let request = Request::get(&url).header("Content-Type", "application/json");
+        let request = match self.api_token() {
+            Some(gitlab_token) => request
+                .header("PRIVATE-TOKEN", gitlab_token)
+                .follow_redirects(http_client::RedirectPolicy::NoFollow),
+            None => request.follow_redirects(http_client::RedirectPolicy::FollowAll),
+        };
+
+        let mut response = client
+            .send(request.body(AsyncBody::default())?)
+            .await
+            .with_context(|| format!("error fetching {:?}", url))?;
+
+        let mut body = Vec::new();
+        response.body_mut().read_to_end(&mut body).await?;
+
+        if !response.status().is_success() {
+            let text = String::from_utf8_lossy(body.as_slice());
+            bail!(
+                "status error {}, response: {text:?}",
+                response.status().as_u16()
+            );
+        }
+
+        serde_json::from_slice(&body).with_context(|| format!("failed to deserialize {:?}", url))
+    }
 }
 
 #[async_trait]
@@ -241,6 +365,68 @@ impl GitHostingProvider for Gitlab {
         Some(url)
     }
 
+    async fn list_pull_requests(
+        &self,
+        remote: &ParsedGitRemote,
+        http_client: Arc<dyn HttpClient>,
+    ) -> Result<Vec<PullRequestSummary>> {
+        let mut merge_requests = Vec::new();
+        for page in 1..=MAX_PAGES {
+            let query = format!("state=opened&per_page={PAGE_SIZE}&page={page}");
+            let page_merge_requests: Vec<MergeRequest> = self
+                .fetch_gitlab_project_api(remote, &format!("merge_requests?{query}"), &http_client)
+                .await?;
+            let is_last_page = page_merge_requests.len() < PAGE_SIZE;
+            merge_requests.extend(page_merge_requests);
+            if is_last_page {
+                break;
+            }
+        }
+        merge_requests
+            .into_iter()
+            .map(MergeRequest::summary)
+            .collect()
+    }
+
+    fn pull_request_head_ref(&self, number: u32) -> Option<String> {
+        Some(format!("refs/merge-requests/{number}/head"))
+    }
+
+    async fn list_issues(
+        &self,
+        remote: &ParsedGitRemote,
+        http_client: Arc<dyn HttpClient>,
+    ) -> Result<Vec<Issue>> {
+        // Merge requests are referenced with `!` rather than `#`, so only issues are listed.
+        let issues: Vec<GitlabIssue> = self
+            .fetch_gitlab_project_api(
+                remote,
+                "issues?order_by=updated_at&per_page=100",
+                &http_client,
+            )
+            .await?;
+        issues.into_iter().map(GitlabIssue::issue).collect()
+    }
+
+    async fn issue(
+        &self,
+        remote: &ParsedGitRemote,
+        number: u32,
+        http_client: Arc<dyn HttpClient>,
+    ) -> Result<Option<Issue>> {
+        let issue: GitlabIssue = self
+            .fetch_gitlab_project_api(remote, &format!("issues/{number}"), &http_client)
+            .await?;
+        issue.issue().map(Some)
+    }
+
+    fn build_branch_url(&self, remote: &ParsedGitRemote, branch: &str) -> Option<Url> {
+        let ParsedGitRemote { owner, repo } = remote;
+        self.base_url()
+            .join(&format!("{owner}/{repo}/-/tree/{}", encode(branch)))
+            .ok()
+    }
+
     fn extract_pull_request(&self, remote: &ParsedGitRemote, message: &str) -> Option<PullRequest> {
         // Check commit message for GitLab MR references
         let capture = merge_request_number_regex().captures(message)?;
@@ -300,6 +486,7 @@ impl GitHostingProvider for Gitlab {
 #[cfg(test)]
 mod tests {
     use git::repository::repo_path;
+    use indoc::indoc;
     use pretty_assertions::assert_eq;
 
     use super::*;
@@ -520,6 +707,22 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_build_branch_url() {
+        let remote = ParsedGitRemote {
+            owner: "zed-industries".into(),
+            repo: "zed".into(),
+        };
+
+        let url = Gitlab::public_instance()
+            .build_branch_url(&remote, "feature/new-feature")
+            .unwrap();
+        assert_eq!(
+            url.as_str(),
+            "https://gitlab.com/zed-industries/zed/-/tree/feature%2Fnew-feature"
+        );
+    }
+
     #[test]
     fn test_extract_merge_request_from_squash_commit() {
         let remote = ParsedGitRemote {
@@ -596,4 +799,56 @@ mod tests {
 
         assert!(pull_request.is_none());
     }
+
+    #[test]
+    fn test_merge_request_summary() {
+        let merge_requests: Vec<MergeRequest> = serde_json::from_str(indoc! {r#"
+            [
+                {
+                    "iid": 7,
+                    "title": "Add a checkout picker",
+                    "web_url": "https://gitlab.com/zed-industries/zed/-/merge_requests/7",
+                    "author": { "username": "alice" },
+                    "source_branch": "checkout-picker",
+                    "source_project_id": 1,
+                    "target_project_id": 1
+                },
+                {
+                    "iid": 8,
+                    "title": "Fix typo",
+                    "web_url": "https://gitlab.com/zed-industries/zed/-/merge_requests/8",
+                    "author": { "username": "bob" },
+                    "source_branch": "main",
+                    "source_project_id": 2,
+                    "target_project_id": 1
+                }
+            ]
+        "#})
+        .unwrap();
+        let summaries = merge_requests
+            .into_iter()
+            .map(|merge_request| merge_request.summary().unwrap())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            summaries,
+            [
+                PullRequestSummary {
+                    number: 7,
+                    title: "Add a checkout picker".into(),
+                    author: "alice".into(),
+                    source_branch: "checkout-picker".into(),
+                    url: Url::parse("https://gitlab.com/zed-industries/zed/-/merge_requests/7")
+                        .unwrap(),
+                },
+                PullRequestSummary {
+                    number: 8,
+                    title: "Fix typo".into(),
+                    author: "bob".into(),
+                    source_branch: "bob:main".into(),
+                    url: Url::parse("https://gitlab.com/zed-industries/zed/-/merge_requests/8")
+                        .unwrap(),
+                },
+            ]
+        );
+    }
 }
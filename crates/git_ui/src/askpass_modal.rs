@@ -1,21 +1,146 @@
 use askpass::EncryptedPassword;
+use collections::HashMap;
+use db::kvp::KeyValueStore;
 use editor::Editor;
 use futures::channel::oneshot;
-use gpui::{AppContext, DismissEvent, Entity, EventEmitter, Focusable, Styled};
+use gpui::{AppContext, DismissEvent, Entity, EventEmitter, Focusable, Global, Styled};
 use ui::{
-    ActiveTheme, AnyElement, App, Button, Clickable, Color, Context, DynamicSpacing, Headline,
-    HeadlineSize, Icon, IconName, IconSize, InteractiveElement, IntoElement, Label, LabelCommon,
-    LabelSize, ParentElement, Render, SharedString, StyledExt, StyledTypography, Window, div,
-    h_flex, v_flex,
+    ActiveTheme, AnyElement, App, Button, Checkbox, Clickable, Color, Context, DynamicSpacing,
+    FluentBuilder, Headline, HeadlineSize, Icon, IconName, IconSize, InteractiveElement,
+    IntoElement, Label, LabelCommon, LabelSize, ParentElement, Render, SharedString, StyledExt,
+    StyledTypography, ToggleState, Window, div, h_flex, v_flex,
 };
-use util::maybe;
+use util::{ResultExt as _, maybe};
 use workspace::ModalView;
 use zeroize::Zeroize;
 
+const ASKPASS_USERNAMES_NAMESPACE: &str = "git_askpass_usernames";
+
+/// What git or ssh asks for, recognized from the prompt so that the modal can say who is asking.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(crate) enum CredentialRequest {
+    Username {
+        host: SharedString,
+    },
+    Password {
+        host: SharedString,
+        username: Option<SharedString>,
+    },
+    Passphrase {
+        key: SharedString,
+    },
+    Other,
+}
+
+impl CredentialRequest {
+    /// Recognizes the prompts of git's credential helpers, such as `Username for
+    /// 'https://github.com': `, and of ssh, such as `Enter passphrase for key '~/.ssh/id_rsa': `
+    /// and `git@example.com's password: `.
+    pub(crate) fn parse(prompt: &str) -> Self {
+        let prompt = prompt.trim();
+        let quoted = |prefix: &str| {
+            let rest = prompt
+                .strip_prefix(prefix)?
+                .trim_start()
+                .strip_prefix('\'')?;
+            rest.split_once('\'').map(|(quoted, _)| quoted)
+        };
+        if let Some(url) = quoted("Username for") {
+            let (_, host) = split_url_authority(url);
+            return Self::Username { host: host.into() };
+        }
+        if let Some(url) = quoted("Password for") {
+            let (username, host) = split_url_authority(url);
+            return Self::Password {
+                host: host.into(),
+                username: username.map(Into::into),
+            };
+        }
+        if let Some(key) = quoted("Enter passphrase for key") {
+            return Self::Passphrase {
+                key: key.to_string().into(),
+            };
+        }
+        if let Some(destination) = prompt.strip_suffix("'s password:") {
+            let (username, host) = match destination.split_once('@') {
+                Some((username, host)) => (Some(username.to_string().into()), host),
+                None => (None, destination),
+            };
+            return Self::Password {
+                host: host.to_string().into(),
+                username,
+            };
+        }
+        Self::Other
+    }
+
+    fn description(&self) -> Option<String> {
+        match self {
+            Self::Username { host } => Some(format!("{host} is asking for your username.")),
+            Self::Password {
+                host,
+                username: Some(username),
+            } => Some(format!("{host} is asking for the password of {username}.")),
+            Self::Password {
+                host,
+                username: None,
+            } => Some(format!("{host} is asking for your password.")),
+            Self::Passphrase { key } => Some(format!("Unlocking the SSH key {key}.")),
+            Self::Other => None,
+        }
+    }
+}
+
+/// Splits `https://user@host:port/path` into the user and `host:port`.
+fn split_url_authority(url: &str) -> (Option<String>, String) {
+    let without_scheme = url.split_once("://").map_or(url, |(_, rest)| rest);
+    let authority = without_scheme
+        .split_once('/')
+        .map_or(without_scheme, |(authority, _)| authority);
+    match authority.rsplit_once('@') {
+        Some((username, host)) => (Some(username.to_string()), host.to_string()),
+        None => (None, authority.to_string()),
+    }
+}
+
+/// An SSH key file on a particular machine, since the same path can hold a different key on each.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub(crate) struct PassphraseKey {
+    /// The machine the key is on, or `None` for the one Zed runs on.
+    pub machine: Option<SharedString>,
+    pub path: SharedString,
+}
+
+/// SSH key passphrases the user chose to remember until Zed quits.
+#[derive(Default)]
+pub(crate) struct SessionPassphrases(HashMap<PassphraseKey, EncryptedPassword>);
+
+impl Global for SessionPassphrases {}
+
+impl SessionPassphrases {
+    pub(crate) fn get(key: &PassphraseKey, cx: &App) -> Option<EncryptedPassword> {
+        cx.try_global::<Self>()?.0.get(key).cloned()
+    }
+
+    pub(crate) fn forget(key: &PassphraseKey, cx: &mut App) {
+        if cx.has_global::<Self>() {
+            cx.global_mut::<Self>().0.remove(key);
+        }
+    }
+
+    fn remember(key: PassphraseKey, passphrase: EncryptedPassword, cx: &mut App) {
+        cx.default_global::<Self>().0.insert(key, passphrase);
+    }
+}
+
 pub(crate) struct AskPassModal {
     operation: SharedString,
     prompt: SharedString,
+    request: CredentialRequest,
     editor: Entity<Editor>,
+    remember_passphrase: ToggleState,
+    /// The machine the prompt comes from, when passphrases asked for there may be remembered.
+    passphrase_machine: Option<Option<SharedString>>,
     tx: Option<oneshot::Sender<EncryptedPassword>>,
 }
 
@@ -31,10 +156,20 @@ impl AskPassModal {
     pub fn new(
         operation: SharedString,
         prompt: SharedString,
+        passphrase_machine: Option<Option<SharedString>>,
         tx: oneshot::Sender<EncryptedPassword>,
         window: &mut Window,
         cx: &mut Context<Self>,
     ) -> Self {
+        let request = CredentialRequest::parse(&prompt);
+        let last_username = match &request {
+            CredentialRequest::Username { host } => KeyValueStore::global(cx)
+                .scoped(ASKPASS_USERNAMES_NAMESPACE)
+                .read(host)
+                .log_err()
+                .flatten(),
+            _ => None,
+        };
         let editor = cx.new(|cx| {
             let mut editor = Editor::single_line(window, cx);
             if prompt.contains("yes/no") || prompt.contains("Username") {
@@ -42,12 +177,18 @@ impl AskPassModal {
             } else {
                 editor.set_masked(true, cx);
             }
+            if let Some(last_username) = last_username {
+                editor.set_text(last_username, window, cx);
+            }
             editor
         });
         Self {
             operation,
             prompt,
+            request,
             editor,
+            remember_passphrase: ToggleState::Unselected,
+            passphrase_machine,
             tx: Some(tx),
         }
     }
@@ -65,6 +206,28 @@ impl AskPassModal {
                 text
             });
             let pw = askpass::EncryptedPassword::try_from(text.as_ref()).ok()?;
+            match &self.request {
+                CredentialRequest::Username { host } => {
+                    let kvp = KeyValueStore::global(cx);
+                    let host = host.to_string();
+                    let username = text.clone();
+                    db::write_and_log(cx, move || async move {
+                        kvp.scoped(ASKPASS_USERNAMES_NAMESPACE)
+                            .write(host, username)
+                            .await
+                    });
+                }
+                CredentialRequest::Passphrase { key } if self.remember_passphrase.selected() => {
+                    if let Some(machine) = self.passphrase_machine.clone() {
+                        let key = PassphraseKey {
+                            machine,
+                            path: key.clone(),
+                        };
+                        SessionPassphrases::remember(key, pw.clone(), cx);
+                    }
+                }
+                _ => {}
+            }
             text.zeroize();
             tx.send(pw).ok();
             Some(())
@@ -139,9 +302,75 @@ impl Render for AskPassModal {
                     .border_color(cx.theme().colors().border_variant)
                     .size_full()
                     .overflow_hidden()
+                    .when_some(self.request.description(), |this, description| {
+                        this.child(
+                            Label::new(description)
+                                .size(LabelSize::Small)
+                                .color(Color::Muted),
+                        )
+                    })
                     .child(self.prompt.clone())
-                    .child(self.editor.clone()),
+                    .child(self.editor.clone())
+                    .when(
+                        matches!(self.request, CredentialRequest::Passphrase { .. })
+                            && self.passphrase_machine.is_some(),
+                        |this| {
+                            this.child(
+                                div().pt_2().child(
+                                    Checkbox::new("remember-passphrase", self.remember_passphrase)
+                                        .label("Remember until Zed quits")
+                                        .on_click(cx.listener(
+                                            |this, state: &ToggleState, _, cx| {
+                                                this.remember_passphrase = *state;
+                                                cx.notify();
+                                            },
+                                        )),
+                                ),
+                            )
+                        },
+                    ),
             )
             .children(self.render_hint(cx))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_credential_request() {
+        assert_eq!(
+            CredentialRequest::parse("Username for 'https://github.com': "),
+            CredentialRequest::Username {
+                host: "github.com".into()
+            }
+        );
+        assert_eq!(
+            CredentialRequest::parse("Password for 'https://alice@git.example.com:8443': "),
+            CredentialRequest::Password {
+                host: "git.example.com:8443".into(),
+                username: Some("alice".into()),
+            }
+        );
+        assert_eq!(
+            CredentialRequest::parse("Enter passphrase for key '/home/alice/.ssh/id_ed25519': "),
+            CredentialRequest::Passphrase {
+                key: "/home/alice/.ssh/id_ed25519".into()
+            }
+        );
+        assert_eq!(
+            CredentialRequest::parse("git@example.com's password: "),
+            CredentialRequest::Password {
+                host: "example.com".into(),
+                username: Some("git".into()),
+            }
+        );
+        assert_eq!(
+            CredentialRequest::parse(
+                "Are you sure you want to continue connecting (yes/no/[fingerprint])? "
+            ),
+            CredentialRequest::Other
+        );
+    }
+}
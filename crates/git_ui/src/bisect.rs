@@ -0,0 +1,421 @@
+use anyhow::{Context as _, Result, bail};
+use editor::Editor;
+use git::repository::{BisectMark, BisectStatus};
+use gpui::{App, DismissEvent, Entity, EventEmitter, FocusHandle, Focusable, WeakEntity};
+use menu::{Cancel, Confirm};
+use project::git_store::Repository;
+use task::{TaskContext, TaskTemplate};
+use ui::prelude::*;
+use workspace::{ModalView, Workspace};
+
+use crate::{
+    git_panel::{GitPanel, show_error_toast},
+    resolve_active_repository,
+};
+
+/// The exit code with which a test command reports that a commit can't be tested, as for
+/// `git bisect run`.
+const SKIP_EXIT_CODE: i32 = 125;
+
+pub fn register(workspace: &mut Workspace) {
+    workspace.register_action(|workspace, _: &git::StartBisect, window, cx| {
+        let Some(repository) = resolve_active_repository(workspace, cx) else {
+            return;
+        };
+        let workspace_handle = workspace.weak_handle();
+        workspace.toggle_modal(window, cx, |window, cx| {
+            BisectModal::new(repository, workspace_handle, window, cx)
+        });
+    });
+    workspace.register_action(|workspace, _: &git::BisectGood, _, cx| {
+        mark_active_repository(workspace, BisectMark::Good, cx);
+    });
+    workspace.register_action(|workspace, _: &git::BisectBad, _, cx| {
+        mark_active_repository(workspace, BisectMark::Bad, cx);
+    });
+    workspace.register_action(|workspace, _: &git::BisectSkip, _, cx| {
+        mark_active_repository(workspace, BisectMark::Skip, cx);
+    });
+    workspace.register_action(|workspace, _: &git::ResetBisect, _, cx| {
+        if let Some(repository) = resolve_active_repository(workspace, cx) {
+            reset_bisect(workspace.weak_handle(), repository, cx);
+        }
+    });
+}
+
+fn mark_active_repository(workspace: &mut Workspace, mark: BisectMark, cx: &mut App) {
+    if let Some(repository) = resolve_active_repository(workspace, cx) {
+        mark_bisect(workspace.weak_handle(), repository, mark, cx);
+    }
+}
+
+/// Marks the commit being bisected in `repository`, which checks out the next one to test.
+pub fn mark_bisect(
+    workspace: WeakEntity<Workspace>,
+    repository: Entity<Repository>,
+    mark: BisectMark,
+    cx: &mut App,
+) {
+    let job = repository.update(cx, |repository, cx| repository.bisect_mark(mark, cx));
+    cx.spawn(async move |cx| {
+        let result = job
+            .await
+            .map_err(anyhow::Error::from)
+            .and_then(|result| result);
+        if let Err(error) = result
+            && let Some(workspace) = workspace.upgrade()
+        {
+            cx.update(|cx| show_error_toast(workspace, "bisect", error, cx));
+        }
+    })
+    .detach();
+}
+
+pub fn reset_bisect(
+    workspace: WeakEntity<Workspace>,
+    repository: Entity<Repository>,
+    cx: &mut App,
+) {
+    let job = repository.update(cx, |repository, cx| repository.bisect_reset(cx));
+    cx.spawn(async move |cx| {
+        let result = job
+            .await
+            .map_err(anyhow::Error::from)
+            .and_then(|result| result);
+        if let Err(error) = result
+            && let Some(workspace) = workspace.upgrade()
+        {
+            cx.update(|cx| show_error_toast(workspace, "bisect reset", error, cx));
+        }
+    })
+    .detach();
+}
+
+/// Tests every commit the bisect checks out by running `command` as a task in the repository,
+/// until the first bad commit is found. As with `git bisect run`, a commit is good when the
+/// command succeeds, skipped when it exits with 125 and bad for other exit codes below 128.
+pub fn run_bisect_task(
+    workspace: WeakEntity<Workspace>,
+    repository: Entity<Repository>,
+    command: String,
+    window: &mut Window,
+    cx: &mut App,
+) {
+    let work_directory = repository.read(cx).work_directory_abs_path.clone();
+    let template = TaskTemplate {
+        label: format!("git bisect: {command}"),
+        command,
+        cwd: Some(work_directory.to_string_lossy().into_owned()),
+        ..TaskTemplate::default()
+    };
+    window
+        .spawn(cx, async move |cx| {
+            let result: Result<()> = async {
+                loop {
+                    let status = repository
+                        .read_with(cx, |repository, _| repository.bisect_status().cloned());
+                    let Some(BisectStatus::Testing { sha, .. }) = status else {
+                        return Ok(());
+                    };
+                    let resolved_task = template
+                        .resolve_task(&format!("git-bisect-{sha}"), &TaskContext::default())
+                        .context("Invalid bisect command")?;
+                    let run = workspace.update_in(cx, |workspace, window, cx| {
+                        workspace.spawn_in_terminal(resolved_task.resolved, window, cx)
+                    })?;
+                    let Some(exit_status) = run.await else {
+                        return Ok(());
+                    };
+                    let exit_status = exit_status?;
+                    let mark = match exit_status.code() {
+                        Some(0) => BisectMark::Good,
+                        Some(SKIP_EXIT_CODE) => BisectMark::Skip,
+                        Some(code) if code < 128 => BisectMark::Bad,
+                        _ => bail!("The bisect command was interrupted ({exit_status})"),
+                    };
+                    repository
+                        .update(cx, |repository, cx| repository.bisect_mark(mark, cx))
+                        .await??;
+                }
+            }
+            .await;
+            if let Err(error) = result
+                && let Some(workspace) = workspace.upgrade()
+            {
+                cx.update(|_, cx| show_error_toast(workspace, "bisect run", error, cx))
+                    .ok();
+            }
+        })
+        .detach();
+}
+
+/// Asks for the revisions to bisect between, and optionally a command that tests each commit.
+struct BisectModal {
+    repository: Entity<Repository>,
+    workspace: WeakEntity<Workspace>,
+    bad_editor: Entity<Editor>,
+    good_editor: Entity<Editor>,
+    command_editor: Entity<Editor>,
+}
+
+impl BisectModal {
+    fn new(
+        repository: Entity<Repository>,
+        workspace: WeakEntity<Workspace>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Self {
+        let bad_editor = cx.new(|cx| {
+            let mut editor = Editor::single_line(window, cx);
+            editor.set_text("HEAD", window, cx);
+            editor
+        });
+        let good_editor = cx.new(|cx| {
+            let mut editor = Editor::single_line(window, cx);
+            editor.set_placeholder_text("A tag, branch or SHA without the bug", window, cx);
+            editor
+        });
+        let command_editor = cx.new(|cx| {
+            let mut editor = Editor::single_line(window, cx);
+            editor.set_placeholder_text("Optional, e.g. cargo test", window, cx);
+            editor
+        });
+        Self {
+            repository,
+            workspace,
+            bad_editor,
+            good_editor,
+            command_editor,
+        }
+    }
+
+    fn cancel(&mut self, _: &Cancel, _window: &mut Window, cx: &mut Context<Self>) {
+        cx.emit(DismissEvent);
+    }
+
+    fn confirm(&mut self, _: &Confirm, window: &mut Window, cx: &mut Context<Self>) {
+        let bad = self.bad_editor.read(cx).text(cx).trim().to_string();
+        let good = self.good_editor.read(cx).text(cx).trim().to_string();
+        let command = self.command_editor.read(cx).text(cx).trim().to_string();
+        if bad.is_empty() || good.is_empty() {
+            return;
+        }
+
+        let start = self
+            .repository
+            .update(cx, |repository, cx| repository.bisect_start(bad, good, cx));
+        let repository = self.repository.clone();
+        let workspace = self.workspace.clone();
+        window
+            .spawn(cx, async move |cx| {
+                let result = start
+                    .await
+                    .map_err(anyhow::Error::from)
+                    .and_then(|result| result);
+                let Some(workspace_entity) = workspace.upgrade() else {
+                    return;
+                };
+                cx.update(|window, cx| match result {
+                    Ok(_) => {
+                        workspace_entity.update(cx, |workspace, cx| {
+                            workspace.open_panel::<GitPanel>(window, cx)
+                        });
+                        if !command.is_empty() {
+                            run_bisect_task(workspace, repository, command, window, cx);
+                        }
+                    }
+                    Err(error) => show_error_toast(workspace_entity, "bisect start", error, cx),
+                })
+                .ok();
+            })
+            .detach();
+        cx.emit(DismissEvent);
+    }
+
+    fn render_field(label: &'static str, editor: &Entity<Editor>) -> impl IntoElement {
+        v_flex()
+            .gap_0p5()
+            .child(Label::new(label).size(LabelSize::Small).color(Color::Muted))
+            .child(editor.clone())
+    }
+}
+
+impl EventEmitter<DismissEvent> for BisectModal {}
+impl ModalView for BisectModal {}
+impl Focusable for BisectModal {
+    fn focus_handle(&self, cx: &App) -> FocusHandle {
+        self.good_editor.focus_handle(cx)
+    }
+}
+
+impl Render for BisectModal {
+    fn render(&mut self, _: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        v_flex()
+            .key_context("BisectModal")
+            .on_action(cx.listener(Self::cancel))
+            .on_action(cx.listener(Self::confirm))
+            .elevation_2(cx)
+            .w(rems(34.))
+            .child(
+                h_flex()
+                    .px_3()
+                    .pt_2()
+                    .pb_1()
+                    .w_full()
+                    .gap_1p5()
+                    .child(Icon::new(IconName::MagnifyingGlass).size(IconSize::XSmall))
+                    .child(Headline::new("Start Bisect").size(HeadlineSize::XSmall)),
+            )
+            .child(
+                v_flex()
+                    .px_3()
+                    .pb_3()
+                    .w_full()
+                    .gap_2()
+                    .child(Self::render_field("Bad revision", &self.bad_editor))
+                    .child(Self::render_field("Good revision", &self.good_editor))
+                    .child(Self::render_field("Test command", &self.command_editor))
+                    .child(
+                        Label::new(
+                            "The command runs as a task on every commit to test. Exiting with 0 \
+                            marks the commit good, 125 skips it and other codes mark it bad.",
+                        )
+                        .size(LabelSize::Small)
+                        .color(Color::Muted),
+                    ),
+            )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{cell::RefCell, collections::VecDeque, path::Path, process::ExitStatus, rc::Rc};
+
+    use gpui::{Task, TestAppContext, VisualTestContext};
+    use project::{FakeFs, Project};
+    use serde_json::json;
+    use settings::SettingsStore;
+    use task::SpawnInTerminal;
+    use theme::LoadThemes;
+    use util::path;
+    use workspace::{MultiWorkspace, TerminalProvider};
+
+    use super::*;
+
+    struct ScriptedTerminalProvider {
+        exit_codes: Rc<RefCell<VecDeque<i32>>>,
+    }
+
+    impl TerminalProvider for ScriptedTerminalProvider {
+        fn spawn(
+            &self,
+            _task: SpawnInTerminal,
+            _window: &mut Window,
+            _cx: &mut App,
+        ) -> Task<Option<Result<ExitStatus>>> {
+            let code = self.exit_codes.borrow_mut().pop_front();
+            Task::ready(code.map(|code| Ok(exit_status(code))))
+        }
+    }
+
+    fn exit_status(code: i32) -> ExitStatus {
+        #[cfg(unix)]
+        {
+            std::os::unix::process::ExitStatusExt::from_raw(code << 8)
+        }
+        #[cfg(windows)]
+        {
+            std::os::windows::process::ExitStatusExt::from_raw(code as u32)
+        }
+    }
+
+    #[gpui::test]
+    async fn test_bisect_with_task(cx: &mut TestAppContext) {
+        zlog::init_test();
+        cx.update(|cx| {
+            let settings_store = SettingsStore::test(cx);
+            cx.set_global(settings_store);
+            theme::init(LoadThemes::JustBase, cx);
+            editor::init(cx);
+            crate::init(cx);
+        });
+
+        let fs = FakeFs::new(cx.background_executor.clone());
+        fs.insert_tree(
+            "/root",
+            json!({
+                "project": {
+                    ".git": {},
+                    "file.txt": "content",
+                }
+            }),
+        )
+        .await;
+        fs.with_git_state(Path::new(path!("/root/project/.git")), false, |state| {
+            state.bisect_history = ["e", "d", "c", "b", "a"].map(String::from).to_vec();
+        })
+        .unwrap();
+
+        let project = Project::test(fs.clone(), [Path::new(path!("/root/project"))], cx).await;
+        let window_handle =
+            cx.add_window(|window, cx| MultiWorkspace::test_new(project.clone(), window, cx));
+        let workspace = window_handle
+            .read_with(cx, |mw, _| mw.workspace().clone())
+            .unwrap();
+        let cx = &mut VisualTestContext::from_window(window_handle.into(), cx);
+        cx.run_until_parked();
+
+        // "c" is tested first and passes, then "d" fails.
+        let exit_codes = Rc::new(RefCell::new(VecDeque::from([0, 1])));
+        workspace.update(cx, |workspace, _| {
+            workspace.set_terminal_provider(ScriptedTerminalProvider {
+                exit_codes: exit_codes.clone(),
+            })
+        });
+
+        let repository =
+            project.read_with(cx, |project, cx| project.active_repository(cx).unwrap());
+        let status = repository
+            .update(cx, |repository, cx| {
+                repository.bisect_start("e".to_string(), "a".to_string(), cx)
+            })
+            .await
+            .unwrap()
+            .unwrap();
+        assert!(matches!(status, BisectStatus::Testing { sha, .. } if sha == "c"));
+
+        cx.update(|window, cx| {
+            run_bisect_task(
+                workspace.downgrade(),
+                repository.clone(),
+                "cargo test".to_string(),
+                window,
+                cx,
+            )
+        });
+        cx.run_until_parked();
+
+        assert!(exit_codes.borrow().is_empty());
+        repository.read_with(cx, |repository, _| {
+            assert_eq!(
+                repository.bisect_status(),
+                Some(&BisectStatus::Found {
+                    sha: "d".to_string(),
+                    subject: String::new(),
+                })
+            );
+        });
+        let marks = fs
+            .with_git_state(Path::new(path!("/root/project/.git")), false, |state| {
+                state.bisect.as_ref().map(|bisect| bisect.marks.clone())
+            })
+            .unwrap();
+        assert_eq!(marks, Some(vec![BisectMark::Good, BisectMark::Bad]));
+
+        cx.update(|_, cx| reset_bisect(workspace.downgrade(), repository.clone(), cx));
+        cx.run_until_parked();
+        repository.read_with(cx, |repository, _| {
+            assert_eq!(repository.bisect_status(), None)
+        });
+    }
+}
@@ -1,20 +1,23 @@
 use crate::{
     commit_tooltip::{CommitAvatar, CommitTooltip},
     commit_view::CommitView,
+    git_panel,
 };
-use editor::{BlameRenderer, Editor, hover_markdown_style};
+use anyhow::Context as _;
+use editor::{BlameRenderer, BlameRevision, Editor, MultiBuffer, hover_markdown_style};
 use git::{blame::BlameEntry, commit::ParsedCommitMessage, repository::CommitSummary};
 use gpui::{
     ClipboardItem, Entity, Hsla, MouseButton, ScrollHandle, Subscription, TextStyle,
     TextStyleRefinement, UnderlineStyle, WeakEntity, prelude::*,
 };
+use language::{Buffer, Point};
 use markdown::{Markdown, MarkdownElement};
 use project::{git_store::Repository, project_settings::ProjectSettings};
 use settings::Settings as _;
 use theme::ThemeSettings;
 use time::OffsetDateTime;
-use ui::{ContextMenu, CopyButton, Divider, prelude::*, tooltip_container};
-use workspace::Workspace;
+use ui::{ContextMenu, CopyButton, Divider, Tooltip, prelude::*, tooltip_container};
+use workspace::{Workspace, notifications::DetachAndPromptErr};
 
 const GIT_BLAME_MAX_AUTHOR_CHARS_DISPLAYED: usize = 20;
 
@@ -89,6 +92,8 @@ impl BlameRenderer for GitBlameRenderer {
                         .on_mouse_down(MouseButton::Right, {
                             let blame_entry = blame_entry.clone();
                             let details = details.clone();
+                            let repository = repository.clone();
+                            let workspace = workspace.clone();
                             let editor = editor.clone();
                             move |event, window, cx| {
                                 cx.stop_propagation();
@@ -96,6 +101,8 @@ impl BlameRenderer for GitBlameRenderer {
                                 deploy_blame_entry_context_menu(
                                     &blame_entry,
                                     details.as_ref(),
+                                    repository.clone(),
+                                    workspace.clone(),
                                     editor.clone(),
                                     event.position,
                                     window,
@@ -167,6 +174,14 @@ impl BlameRenderer for GitBlameRenderer {
                 .line_height(style.line_height)
                 .child(Icon::new(IconName::FileGit).color(Color::Hint))
                 .child(text)
+                .when_some(blame_entry.ignored_revision, |this, ignored_revision| {
+                    this.child(
+                        h_flex()
+                            .gap_1()
+                            .child(Icon::new(IconName::EyeOff).color(Color::Hint))
+                            .child(format!("past {}", ignored_revision.display_short())),
+                    )
+                })
                 .gap_2()
                 .into_any(),
         )
@@ -244,6 +259,9 @@ impl BlameRenderer for GitBlameRenderer {
         let pull_request = details
             .as_ref()
             .and_then(|details| details.pull_request.clone());
+        let permalink = details
+            .as_ref()
+            .and_then(|details| details.permalink.clone());
 
         let ui_font_size = ThemeSettings::get_global(cx).ui_font_size(cx);
         let message_max_height = window.line_height() * 12 + (ui_font_size / 0.4);
@@ -294,6 +312,46 @@ impl BlameRenderer for GitBlameRenderer {
                                         )
                                     }),
                             )
+                            .when_some(blame.ignored_revision, |this, ignored_revision| {
+                                this.child(
+                                    h_flex()
+                                        .pt_1()
+                                        .gap_1()
+                                        .text_color(cx.theme().colors().text_muted)
+                                        .child(
+                                            Icon::new(IconName::EyeOff)
+                                                .size(IconSize::Small)
+                                                .color(Color::Muted),
+                                        )
+                                        .child("Blamed past the ignored revision")
+                                        .child(
+                                            Button::new(
+                                                "ignored-revision-button",
+                                                ignored_revision.display_short(),
+                                            )
+                                            .color(Color::Muted)
+                                            .tooltip(Tooltip::text("View Ignored Commit"))
+                                            .on_click(
+                                                {
+                                                    let repository = repository.clone();
+                                                    let workspace = workspace.clone();
+                                                    move |_, window, cx| {
+                                                        CommitView::open(
+                                                            ignored_revision.to_string(),
+                                                            repository.downgrade(),
+                                                            workspace.clone(),
+                                                            None,
+                                                            None,
+                                                            window,
+                                                            cx,
+                                                        );
+                                                        cx.stop_propagation();
+                                                    }
+                                                },
+                                            ),
+                                        ),
+                                )
+                            })
                             .child(
                                 div()
                                     .id("inline-blame-commit-message")
@@ -345,24 +403,99 @@ impl BlameRenderer for GitBlameRenderer {
                                                         .size(IconSize::Small)
                                                         .color(Color::Muted),
                                                 )
-                                                .on_click(move |_, window, cx| {
-                                                    CommitView::open(
-                                                        commit_summary.sha.clone().into(),
-                                                        repository.downgrade(),
-                                                        workspace.clone(),
-                                                        None,
-                                                        None,
-                                                        window,
-                                                        cx,
-                                                    );
-                                                    cx.stop_propagation();
+                                                .tooltip(Tooltip::text("View Commit"))
+                                                .on_click({
+                                                    let repository = repository.clone();
+                                                    let workspace = workspace.clone();
+                                                    move |_, window, cx| {
+                                                        CommitView::open(
+                                                            commit_summary.sha.clone().into(),
+                                                            repository.downgrade(),
+                                                            workspace.clone(),
+                                                            None,
+                                                            None,
+                                                            window,
+                                                            cx,
+                                                        );
+                                                        cx.stop_propagation();
+                                                    }
+                                                }),
+                                            )
+                                            .when(blame.previous_revision().is_some(), |this| {
+                                                this.child(Divider::vertical()).child(
+                                                    IconButton::new(
+                                                        "blame-before-commit",
+                                                        IconName::HistoryRerun,
+                                                    )
+                                                    .icon_size(IconSize::Small)
+                                                    .icon_color(Color::Muted)
+                                                    .tooltip(Tooltip::text(
+                                                        "Blame Before This Commit",
+                                                    ))
+                                                    .on_click({
+                                                        let blame = blame.clone();
+                                                        let repository = repository.clone();
+                                                        let workspace = workspace.clone();
+                                                        move |_, window, cx| {
+                                                            cx.stop_propagation();
+                                                            open_blame_before_commit(
+                                                                blame.clone(),
+                                                                repository.clone(),
+                                                                workspace.clone(),
+                                                                window,
+                                                                cx,
+                                                            );
+                                                        }
+                                                    }),
+                                                )
+                                            })
+                                            .child(Divider::vertical())
+                                            .child(
+                                                IconButton::new(
+                                                    "revert-blame-commit",
+                                                    IconName::Undo,
+                                                )
+                                                .icon_size(IconSize::Small)
+                                                .icon_color(Color::Muted)
+                                                .tooltip(Tooltip::text("Revert This Commit"))
+                                                .on_click({
+                                                    let sha = sha.to_string();
+                                                    move |_, window, cx| {
+                                                        cx.stop_propagation();
+                                                        git_panel::confirm_revert_commit(
+                                                            workspace.clone(),
+                                                            repository.clone(),
+                                                            sha.clone(),
+                                                            window,
+                                                            cx,
+                                                        );
+                                                    }
                                                 }),
                                             )
                                             .child(Divider::vertical())
                                             .child(
                                                 CopyButton::new("copy-blame-sha", sha.to_string())
                                                     .tooltip_label("Copy SHA"),
-                                            ),
+                                            )
+                                            .when_some(permalink, |this, permalink| {
+                                                this.child(
+                                                    IconButton::new(
+                                                        "copy-blame-permalink",
+                                                        IconName::Link,
+                                                    )
+                                                    .icon_size(IconSize::Small)
+                                                    .icon_color(Color::Muted)
+                                                    .tooltip(Tooltip::text("Copy Permalink"))
+                                                    .on_click(move |_, _, cx| {
+                                                        cx.stop_propagation();
+                                                        cx.write_to_clipboard(
+                                                            ClipboardItem::new_string(
+                                                                permalink.to_string(),
+                                                            ),
+                                                        );
+                                                    }),
+                                                )
+                                            }),
                                     ),
                             ),
                     )
@@ -389,11 +522,100 @@ impl BlameRenderer for GitBlameRenderer {
             cx,
         )
     }
+
+    fn open_blame_before_commit(
+        &self,
+        blame_entry: BlameEntry,
+        repository: Entity<Repository>,
+        workspace: WeakEntity<Workspace>,
+        window: &mut Window,
+        cx: &mut App,
+    ) {
+        open_blame_before_commit(blame_entry, repository, workspace, window, cx)
+    }
+}
+
+/// Opens the file as it was in the parent of the commit blamed for `blame_entry`, with that
+/// revision's blame in the gutter, so that blaming again from there walks further back through
+/// the history of the line.
+fn open_blame_before_commit(
+    blame_entry: BlameEntry,
+    repository: Entity<Repository>,
+    workspace: WeakEntity<Workspace>,
+    window: &mut Window,
+    cx: &mut App,
+) {
+    let Some((parent_sha, path)) = blame_entry.previous_revision() else {
+        return;
+    };
+    let parent_sha = parent_sha.to_string();
+    let load_text = repository.update(cx, |repository, cx| {
+        repository.load_text_at_revision(parent_sha.clone(), path.clone(), cx)
+    });
+    // The line was at its original line number right after the commit, which is the closest
+    // place to it in the parent.
+    let row = blame_entry.original_line_number.saturating_sub(1);
+
+    window
+        .spawn(cx, async move |cx| {
+            let text = load_text
+                .await?
+                .with_context(|| format!("{} doesn't exist at {parent_sha}", path.as_unix_str()))?;
+            let (project, languages) = workspace.read_with(cx, |workspace, cx| {
+                let project = workspace.project().clone();
+                let languages = project.read(cx).languages().clone();
+                (project, languages)
+            })?;
+            let language = languages
+                .load_language_for_file_path(path.as_std_path())
+                .await
+                .ok();
+
+            workspace.update_in(cx, |workspace, window, cx| {
+                let short_sha = parent_sha
+                    .get(..git::SHORT_SHA_LENGTH)
+                    .unwrap_or(&parent_sha);
+                let file_name = path.file_name().unwrap_or(path.as_unix_str());
+                let title = format!("{file_name} @ {short_sha}");
+                let buffer = cx.new(|cx| {
+                    let mut buffer = Buffer::local(text, cx);
+                    buffer.set_language(language, cx);
+                    buffer
+                });
+                let multibuffer = cx.new(|cx| MultiBuffer::singleton(buffer, cx).with_title(title));
+                let editor = cx.new(|cx| {
+                    let mut editor =
+                        Editor::for_multibuffer(multibuffer, Some(project), window, cx);
+                    editor.set_read_only(true);
+                    editor.show_git_blame_at_revision(
+                        BlameRevision {
+                            repository,
+                            revision: parent_sha,
+                            path,
+                        },
+                        window,
+                        cx,
+                    );
+                    editor.go_to_singleton_buffer_point(Point::new(row, 0), window, cx);
+                    editor
+                });
+                workspace.add_item_to_active_pane(Box::new(editor), None, true, window, cx);
+            })?;
+            anyhow::Ok(())
+        })
+        .detach_and_prompt_err(
+            "Failed to blame the file before this commit",
+            window,
+            cx,
+            |_, _, _| None,
+        );
 }
 
 fn deploy_blame_entry_context_menu(
     blame_entry: &BlameEntry,
     details: Option<&ParsedCommitMessage>,
+    repository: Entity<Repository>,
+    workspace: WeakEntity<Workspace>,
     editor: Entity<Editor>,
     position: gpui::Point<Pixels>,
     window: &mut Window,
@@ -402,17 +624,72 @@ fn deploy_blame_entry_context_menu(
     let context_menu = ContextMenu::build(window, cx, move |menu, _, _| {
         let sha = format!("{}", blame_entry.sha);
         menu.on_blur_subscription(Subscription::new(|| {}))
-            .entry("Copy Commit SHA", None, move |_, cx| {
-                cx.write_to_clipboard(ClipboardItem::new_string(sha.clone()));
+            .entry("Copy Commit SHA", None, {
+                let sha = sha.clone();
+                move |_, cx| {
+                    cx.write_to_clipboard(ClipboardItem::new_string(sha.clone()));
+                }
             })
             .when_some(
                 details.and_then(|details| details.permalink.clone()),
                 |this, url| {
-                    this.entry("Open Permalink", None, move |_, cx| {
+                    this.entry("Copy Permalink", None, {
+                        let url = url.clone();
+                        move |_, cx| {
+                            cx.write_to_clipboard(ClipboardItem::new_string(url.to_string()))
+                        }
+                    })
+                    .entry("Open Permalink", None, move |_, cx| {
                         cx.open_url(url.as_str())
                     })
                 },
             )
+            .when(blame_entry.previous_revision().is_some(), |this| {
+                this.entry("Blame Before This Commit", None, {
+                    let blame_entry = blame_entry.clone();
+                    let repository = repository.clone();
+                    let workspace = workspace.clone();
+                    move |window, cx| {
+                        open_blame_before_commit(
+                            blame_entry.clone(),
+                            repository.clone(),
+                            workspace.clone(),
+                            window,
+                            cx,
+                        )
+                    }
+                })
+            })
+            .separator()
+            .entry("Revert Commit", None, {
+                let sha = sha.clone();
+                let repository = repository.clone();
+                let workspace = workspace.clone();
+                move |window, cx| {
+                    git_panel::revert_commit(
+                        workspace.clone(),
+                        repository.clone(),
+                        sha.clone(),
+                        false,
+                        window,
+                        cx,
+                    )
+                }
+            })
+            .entry(
+                "Revert Commit Without Committing",
+                None,
+                move |window, cx| {
+                    git_panel::revert_commit(
+                        workspace.clone(),
+                        repository.clone(),
+                        sha.clone(),
+                        true,
+                        window,
+                        cx,
+                    )
+                },
+            )
     });
 
     editor.update(cx, move |editor, cx| {
@@ -437,3 +714,101 @@ fn blame_entry_relative_timestamp(blame_entry: &BlameEntry) -> String {
         Err(_) => "Error parsing date".to_string(),
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use super::*;
+    use git::{blame::Blame, repository::repo_path};
+    use gpui::{TestAppContext, VisualTestContext};
+    use project::{FakeFs, Project};
+    use serde_json::json;
+    use settings::SettingsStore;
+    use util::path;
+    use workspace::MultiWorkspace;
+
+    fn init_test(cx: &mut TestAppContext) {
+        cx.update(|cx| {
+            let settings_store = SettingsStore::test(cx);
+            cx.set_global(settings_store);
+
+            theme::init(theme::LoadThemes::JustBase, cx);
+            editor::init(cx);
+        })
+    }
+
+    #[gpui::test]
+    async fn test_open_blame_before_commit(cx: &mut TestAppContext) {
+        init_test(cx);
+
+        let fs = FakeFs::new(cx.executor());
+        fs.insert_tree(
+            path!("/project"),
+            json!({
+                ".git": {},
+                "file.txt": "one\ntwo, changed\nthree\n",
+            }),
+        )
+        .await;
+        let parent_sha = "1b1b1b1b1b1b1b1b1b1b1b1b1b1b1b1b1b1b1b1b";
+        let revision = (parent_sha.to_string(), repo_path("old.txt"));
+        fs.with_git_state(Path::new(path!("/project/.git")), true, |state| {
+            state
+                .revision_contents
+                .insert(revision.clone(), "one\ntwo\nthree\n".to_string());
+            state.revision_blames.insert(
+                revision.clone(),
+                Blame {
+                    entries: vec![BlameEntry {
+                        sha: parent_sha.parse().unwrap(),
+                        range: 0..3,
+                        original_line_number: 1,
+                        ..Default::default()
+                    }],
+                    ..Default::default()
+                },
+            );
+        })
+        .unwrap();
+        let project = Project::test(fs.clone(), [Path::new(path!("/project"))], cx).await;
+        let multi_workspace =
+            cx.add_window(|window, cx| MultiWorkspace::test_new(project.clone(), window, cx));
+        let cx = &mut VisualTestContext::from_window(*multi_workspace, cx);
+        let workspace = multi_workspace
+            .update(cx, |workspace, _, _| workspace.workspace().clone())
+            .unwrap();
+        cx.run_until_parked();
+
+        let repository =
+            project.read_with(cx, |project, cx| project.active_repository(cx).unwrap());
+        let blame_entry = BlameEntry {
+            sha: "3a3a3a3a3a3a3a3a3a3a3a3a3a3a3a3a3a3a3a3a".parse().unwrap(),
+            range: 1..2,
+            original_line_number: 2,
+            previous: Some(format!("{parent_sha} old.txt")),
+            filename: "file.txt".to_string(),
+            ..Default::default()
+        };
+        workspace.update_in(cx, |workspace, window, cx| {
+            let workspace_handle = workspace.weak_handle();
+            open_blame_before_commit(blame_entry, repository, workspace_handle, window, cx);
+        });
+        cx.run_until_parked();
+
+        let editor = workspace
+            .update(cx, |workspace, cx| workspace.active_item_as::<Editor>(cx))
+            .unwrap();
+        editor.update(cx, |editor, cx| {
+            assert_eq!(editor.text(cx), "one\ntwo\nthree\n");
+            assert!(editor.read_only(cx));
+            assert!(editor.show_git_blame_gutter());
+            assert!(editor.blame().unwrap().read(cx).has_generated_entries());
+            let display_snapshot = editor.display_snapshot(cx);
+            assert_eq!(
+                editor.selections.newest::<Point>(&display_snapshot).head(),
+                Point::new(1, 0)
+            );
+        });
+    }
+}
@@ -0,0 +1,343 @@
+use std::sync::Arc;
+
+use fuzzy::StringMatchCandidate;
+use git::repository::RepoPath;
+use gpui::{App, DismissEvent, Entity, EventEmitter, FocusHandle, Focusable, Task, WeakEntity};
+use picker::{Picker, PickerDelegate};
+use project::{ProjectPath, git_store::Repository};
+use ui::{HighlightedLabel, ListItem, ListItemSpacing, prelude::*};
+use util::ResultExt;
+use workspace::{ModalView, Workspace, notifications::DetachAndPromptErr};
+
+use crate::{
+    branch_comparison_view::BranchComparisonView, directory_diff_view::DirectoryDiffView,
+    resolve_active_repository,
+};
+
+pub fn register(workspace: &mut Workspace) {
+    workspace.register_action(|workspace, _: &git::CompareBranches, window, cx| {
+        let Some(repository) = resolve_active_repository(workspace, cx) else {
+            return;
+        };
+        let weak_workspace = workspace.weak_handle();
+        workspace.toggle_modal(window, cx, |window, cx| {
+            BranchComparisonPicker::new(repository, None, weak_workspace, window, cx)
+        });
+    });
+}
+
+/// Opens the picker to compare the directory at `project_path` between two revisions, such as for
+/// a folder selected in the project panel.
+pub fn compare_directory_revisions(
+    workspace: &mut Workspace,
+    project_path: ProjectPath,
+    window: &mut Window,
+    cx: &mut Context<Workspace>,
+) {
+    let Some((repository, directory)) = workspace
+        .project()
+        .read(cx)
+        .git_store()
+        .read(cx)
+        .repository_and_path_for_project_path(&project_path, cx)
+    else {
+        return;
+    };
+    let weak_workspace = workspace.weak_handle();
+    workspace.toggle_modal(window, cx, |window, cx| {
+        BranchComparisonPicker::new(repository, Some(directory), weak_workspace, window, cx)
+    });
+}
+
+/// Picks the base and then the target of a comparison between two refs.
+pub struct BranchComparisonPicker {
+    picker: Entity<Picker<BranchComparisonDelegate>>,
+    _load_branches: Task<()>,
+}
+
+impl BranchComparisonPicker {
+    fn new(
+        repository: Entity<Repository>,
+        directory: Option<RepoPath>,
+        workspace: WeakEntity<Workspace>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Self {
+        let branches_request = repository.update(cx, |repository, _| repository.branches());
+        let delegate = BranchComparisonDelegate {
+            picker: cx.entity().downgrade(),
+            workspace,
+            repository,
+            directory,
+            base: None,
+            all_refs: Vec::new(),
+            matches: Vec::new(),
+            selected_index: 0,
+        };
+        let picker = cx.new(|cx| {
+            Picker::uniform_list(delegate, window, cx)
+                .max_height(Some(rems(20.).into()))
+                .show_scrollbar(true)
+        });
+
+        let load_branches = cx.spawn_in(window, async move |this, cx| {
+            let Some(branches) = branches_request
+                .await
+                .ok()
+                .and_then(|result| result.log_err())
+            else {
+                return;
+            };
+            let refs = branches
+                .into_iter()
+                .map(|branch| RefCandidate {
+                    name: branch.name().to_string().into(),
+                    detail: branch
+                        .most_recent_commit
+                        .map(|commit_summary| commit_summary.subject),
+                })
+                .collect();
+            this.update_in(cx, |this, window, cx| {
+                this.picker.update(cx, |picker, cx| {
+                    picker.delegate.all_refs = refs;
+                    picker.refresh(window, cx);
+                });
+            })
+            .log_err();
+        });
+
+        Self {
+            picker,
+            _load_branches: load_branches,
+        }
+    }
+}
+
+impl EventEmitter<DismissEvent> for BranchComparisonPicker {}
+
+impl Focusable for BranchComparisonPicker {
+    fn focus_handle(&self, cx: &App) -> FocusHandle {
+        self.picker.focus_handle(cx)
+    }
+}
+
+impl ModalView for BranchComparisonPicker {}
+
+impl Render for BranchComparisonPicker {
+    fn render(&mut self, _window: &mut Window, _cx: &mut Context<Self>) -> impl IntoElement {
+        v_flex()
+            .key_context("BranchComparisonPicker")
+            .w(rems(34.))
+            .child(self.picker.clone())
+    }
+}
+
+#[derive(Clone)]
+struct RefCandidate {
+    /// A branch name, or any other ref understood by git when typed in.
+    name: SharedString,
+    detail: Option<SharedString>,
+}
+
+struct RefMatch {
+    candidate: RefCandidate,
+    positions: Vec<usize>,
+}
+
+pub struct BranchComparisonDelegate {
+    picker: WeakEntity<BranchComparisonPicker>,
+    workspace: WeakEntity<Workspace>,
+    repository: Entity<Repository>,
+    /// The directory to compare, instead of every file that differs.
+    directory: Option<RepoPath>,
+    /// The ref picked first, which the second one is compared against.
+    base: Option<SharedString>,
+    all_refs: Vec<RefCandidate>,
+    matches: Vec<RefMatch>,
+    selected_index: usize,
+}
+
+impl PickerDelegate for BranchComparisonDelegate {
+    type ListItem = ListItem;
+
+    fn match_count(&self) -> usize {
+        self.matches.len()
+    }
+
+    fn selected_index(&self) -> usize {
+        self.selected_index
+    }
+
+    fn set_selected_index(
+        &mut self,
+        ix: usize,
+        _window: &mut Window,
+        cx: &mut Context<Picker<Self>>,
+    ) {
+        self.selected_index = ix;
+        cx.notify();
+    }
+
+    fn placeholder_text(&self, _window: &mut Window, _cx: &mut App) -> Arc<str> {
+        match &self.base {
+            None => "Select the base branch…".into(),
+            Some(base) => format!("Compare {base} with…").into(),
+        }
+    }
+
+    fn no_matches_text(&self, _window: &mut Window, _cx: &mut App) -> Option<SharedString> {
+        Some("No branches found".into())
+    }
+
+    fn update_matches(
+        &mut self,
+        query: String,
+        window: &mut Window,
+        cx: &mut Context<Picker<Self>>,
+    ) -> Task<()> {
+        let all_refs = self
+            .all_refs
+            .iter()
+            .filter(|candidate| Some(&candidate.name) != self.base.as_ref())
+            .cloned()
+            .collect::<Vec<_>>();
+        cx.spawn_in(window, async move |picker, cx| {
+            let mut matches: Vec<RefMatch> = if query.is_empty() {
+                all_refs
+                    .into_iter()
+                    .map(|candidate| RefMatch {
+                        candidate,
+                        positions: Vec::new(),
+                    })
+                    .collect()
+            } else {
+                let candidates = all_refs
+                    .iter()
+                    .enumerate()
+                    .map(|(ix, candidate)| StringMatchCandidate::new(ix, &candidate.name))
+                    .collect::<Vec<_>>();
+                fuzzy::match_strings(
+                    &candidates,
+                    &query,
+                    true,
+                    true,
+                    10000,
+                    &Default::default(),
+                    cx.background_executor().clone(),
+                )
+                .await
+                .into_iter()
+                .filter_map(|string_match| {
+                    Some(RefMatch {
+                        candidate: all_refs.get(string_match.candidate_id)?.clone(),
+                        positions: string_match.positions,
+                    })
+                })
+                .collect()
+            };
+
+            // Tags and commits aren't listed, so let the query itself be used as a ref.
+            let query = query.trim();
+            if matches.is_empty() && !query.is_empty() {
+                matches.push(RefMatch {
+                    candidate: RefCandidate {
+                        name: query.to_string().into(),
+                        detail: None,
+                    },
+                    positions: Vec::new(),
+                });
+            }
+
+            picker
+                .update(cx, |picker, cx| {
+                    let delegate = &mut picker.delegate;
+                    delegate.matches = matches;
+                    delegate.selected_index = delegate
+                        .selected_index
+                        .min(delegate.matches.len().saturating_sub(1));
+                    cx.notify();
+                })
+                .log_err();
+        })
+    }
+
+    fn confirm(&mut self, _secondary: bool, window: &mut Window, cx: &mut Context<Picker<Self>>) {
+        let Some(selected) = self.matches.get(self.selected_index) else {
+            return;
+        };
+        let name = selected.candidate.name.clone();
+        let Some(base) = self.base.clone() else {
+            self.base = Some(name);
+            self.matches.clear();
+            self.selected_index = 0;
+            cx.defer_in(window, |picker, window, cx| {
+                picker.refresh_placeholder(window, cx);
+                picker.set_query("", window, cx);
+                cx.notify();
+            });
+            return;
+        };
+
+        let Some(workspace) = self.workspace.upgrade() else {
+            return;
+        };
+        let repository = self.repository.clone();
+        if let Some(directory) = self.directory.clone() {
+            DirectoryDiffView::open_revisions(
+                repository,
+                directory,
+                base,
+                name,
+                workspace.downgrade(),
+                window,
+                cx,
+            )
+            .detach_and_prompt_err(
+                "Failed to compare directory",
+                window,
+                cx,
+                |_, _, _| None,
+            );
+        } else {
+            workspace
+                .update(cx, |workspace, cx| {
+                    BranchComparisonView::open(repository, base, name, workspace, window, cx)
+                })
+                .detach_and_prompt_err("Failed to compare branches", window, cx, |_, _, _| None);
+        }
+        self.dismissed(window, cx);
+    }
+
+    fn dismissed(&mut self, _window: &mut Window, cx: &mut Context<Picker<Self>>) {
+        self.picker
+            .update(cx, |_, cx| cx.emit(DismissEvent))
+            .log_err();
+    }
+
+    fn render_match(
+        &self,
+        ix: usize,
+        selected: bool,
+        _window: &mut Window,
+        _cx: &mut Context<Picker<Self>>,
+    ) -> Option<Self::ListItem> {
+        let ref_match = self.matches.get(ix)?;
+        Some(
+            ListItem::new(ix)
+                .inset(true)
+                .spacing(ListItemSpacing::Sparse)
+                .toggle_state(selected)
+                .child(
+                    h_flex()
+                        .gap_2()
+                        .child(HighlightedLabel::new(
+                            ref_match.candidate.name.clone(),
+                            ref_match.positions.clone(),
+                        ))
+                        .when_some(ref_match.candidate.detail.clone(), |this, detail| {
+                            this.child(Label::new(detail).color(Color::Muted).truncate())
+                        }),
+                ),
+        )
+    }
+}
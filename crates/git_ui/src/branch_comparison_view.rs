@@ -0,0 +1,798 @@
+//! BranchComparisonView shows every file that differs between two refs in one multibuffer.
+
+use anyhow::{Context as _, Result, anyhow};
+use buffer_diff::BufferDiff;
+use collections::HashMap;
+use editor::{
+    Editor, EditorEvent, MultiBuffer, PathKey, SelectionEffects, multibuffer_context_lines,
+    scroll::Autoscroll,
+};
+use git::{
+    repository::{CommitFileStatus, RepoPath, is_binary_content},
+    status::{DiffTreeType, FileStatus, StatusCode, TrackedStatus, TreeDiffStatus},
+};
+use gpui::{
+    AnyElement, App, AppContext as _, AsyncWindowContext, Context, Entity, EventEmitter,
+    FocusHandle, Focusable, Font, IntoElement, Render, SharedString, Task, WeakEntity, Window,
+};
+use language::{Buffer, Capability, HighlightedText, OffsetRangeExt as _, Point};
+use project::{Project, git_store::Repository};
+use std::{
+    any::{Any, TypeId},
+    sync::Arc,
+};
+use ui::{ContextMenu, PopoverMenu, Tooltip, prelude::*};
+use util::{ResultExt as _, paths::PathStyle};
+use workspace::{
+    Item, ItemNavHistory, ToolbarItemLocation, Workspace,
+    item::{ItemEvent, TabContentParams},
+    notifications::DetachAndPromptErr,
+    searchable::SearchableItemHandle,
+};
+
+use crate::{
+    commit_view::{CommitDiffAddon, GitBlob, build_buffer, build_buffer_diff},
+    repository_settings, resolve_active_repository,
+};
+
+pub struct BranchComparisonView {
+    editor: Entity<Editor>,
+    multibuffer: Entity<MultiBuffer>,
+    base: SharedString,
+    target: SharedString,
+    /// Whether the target is compared with the point where it branched off the base, rather
+    /// than with the base itself.
+    against_merge_base: bool,
+    files: Vec<(RepoPath, CommitFileStatus)>,
+    _load_files: Task<Result<()>>,
+}
+
+pub fn register(workspace: &mut Workspace) {
+    workspace.register_action(|workspace, _: &git::DiffAgainstUpstream, window, cx| {
+        let Some(repository) = resolve_active_repository(workspace, cx) else {
+            return;
+        };
+        BranchComparisonView::open_against_upstream(repository, workspace, window, cx)
+            .detach_and_prompt_err("Failed to diff against upstream", window, cx, |_, _, _| {
+                None
+            });
+    });
+    workspace.register_action(|workspace, _: &git::DiffAgainstMergeBase, window, cx| {
+        let Some(repository) = resolve_active_repository(workspace, cx) else {
+            return;
+        };
+        BranchComparisonView::open_against_merge_base(repository, workspace, window, cx)
+            .detach_and_prompt_err(
+                "Failed to diff against merge base",
+                window,
+                cx,
+                |_, _, _| None,
+            );
+    });
+}
+
+impl BranchComparisonView {
+    /// Opens the changes that `target` makes on top of `base`, one file after another.
+    pub fn open(
+        repository: Entity<Repository>,
+        base: SharedString,
+        target: SharedString,
+        workspace: &Workspace,
+        window: &mut Window,
+        cx: &mut App,
+    ) -> Task<Result<Entity<Self>>> {
+        let base_revision = base.to_string();
+        Self::open_comparison(
+            repository,
+            base_revision,
+            base,
+            target,
+            false,
+            workspace,
+            window,
+            cx,
+        )
+    }
+
+    /// Opens the changes that the current branch makes on top of its upstream branch.
+    pub fn open_against_upstream(
+        repository: Entity<Repository>,
+        workspace: &Workspace,
+        window: &mut Window,
+        cx: &mut App,
+    ) -> Task<Result<Entity<Self>>> {
+        let Some(branch) = repository.read(cx).branch.clone() else {
+            return Task::ready(Err(anyhow!("HEAD isn't on a branch")));
+        };
+        let Some(upstream) = branch.upstream.as_ref() else {
+            return Task::ready(Err(anyhow!("{} has no upstream branch", branch.name())));
+        };
+        let base = upstream
+            .stripped_ref_name()
+            .or_else(|| upstream.ref_name.strip_prefix("refs/heads/"))
+            .unwrap_or(&upstream.ref_name)
+            .to_string();
+        let target = branch.name().to_string();
+        Self::open(
+            repository,
+            base.into(),
+            target.into(),
+            workspace,
+            window,
+            cx,
+        )
+    }
+
+    /// Opens the changes made since HEAD branched off the `git.default_base_branch` of its
+    /// repository, which is the default branch of its remote when unset.
+    pub fn open_against_merge_base(
+        repository: Entity<Repository>,
+        workspace: &Workspace,
+        window: &mut Window,
+        cx: &mut App,
+    ) -> Task<Result<Entity<Self>>> {
+        let target: SharedString = match &repository.read(cx).branch {
+            Some(branch) => branch.name().to_string().into(),
+            None => "HEAD".into(),
+        };
+        let configured_base_branch = repository_settings(workspace.project(), &repository, cx)
+            .git
+            .default_base_branch
+            .clone();
+        let default_branch = repository.update(cx, |repository, _| repository.default_branch(true));
+        let workspace = workspace.weak_handle();
+
+        window.spawn(cx, async move |cx| {
+            let base: SharedString = match configured_base_branch {
+                Some(base) => base.into(),
+                None => default_branch.await??.unwrap_or_else(|| "main".into()),
+            };
+            let merge_base = repository
+                .update(cx, |repository, cx| {
+                    repository.merge_base(base.to_string(), target.to_string(), cx)
+                })
+                .await?
+                .with_context(|| format!("{target} and {base} have no common history"))?;
+            workspace
+                .update_in(cx, |workspace, window, cx| {
+                    Self::open_comparison(
+                        repository, merge_base, base, target, true, workspace, window, cx,
+                    )
+                })?
+                .await
+        })
+    }
+
+    fn open_comparison(
+        repository: Entity<Repository>,
+        base_revision: String,
+        base: SharedString,
+        target: SharedString,
+        against_merge_base: bool,
+        workspace: &Workspace,
+        window: &mut Window,
+        cx: &mut App,
+    ) -> Task<Result<Entity<Self>>> {
+        let project = workspace.project().clone();
+        let tree_diff = repository.update(cx, |repository, cx| {
+            repository.diff_tree(
+                DiffTreeType::Since {
+                    base: base_revision.clone().into(),
+                    head: target.clone(),
+                },
+                cx,
+            )
+        });
+        let workspace = workspace.weak_handle();
+
+        window.spawn(cx, async move |cx| {
+            let tree_diff = tree_diff.await??;
+            if tree_diff.entries.is_empty() {
+                if against_merge_base {
+                    anyhow::bail!("{target} has no changes since it branched off {base}");
+                }
+                anyhow::bail!("{base} and {target} have no differences");
+            }
+            let mut files = tree_diff
+                .entries
+                .into_iter()
+                .map(|(path, status)| {
+                    let status = match status {
+                        TreeDiffStatus::Added => CommitFileStatus::Added,
+                        TreeDiffStatus::Modified { .. } => CommitFileStatus::Modified,
+                        TreeDiffStatus::Deleted { .. } => CommitFileStatus::Deleted,
+                    };
+                    (path, status)
+                })
+                .collect::<Vec<_>>();
+            files.sort_by(|(path, _), (other_path, _)| path.cmp(other_path));
+
+            workspace.update_in(cx, |workspace, window, cx| {
+                let view = cx.new(|cx| {
+                    Self::new(
+                        repository,
+                        project,
+                        base_revision,
+                        base,
+                        target,
+                        against_merge_base,
+                        files,
+                        window,
+                        cx,
+                    )
+                });
+                workspace.active_pane().update(cx, |pane, cx| {
+                    pane.add_item(Box::new(view.clone()), true, true, None, window, cx);
+                });
+                view
+            })
+        })
+    }
+
+    fn new(
+        repository: Entity<Repository>,
+        project: Entity<Project>,
+        base_revision: String,
+        base: SharedString,
+        target: SharedString,
+        against_merge_base: bool,
+        files: Vec<(RepoPath, CommitFileStatus)>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Self {
+        let multibuffer = cx.new(|cx| {
+            let mut multibuffer = MultiBuffer::new(Capability::ReadOnly);
+            multibuffer.set_all_diff_hunks_expanded(cx);
+            multibuffer
+        });
+        let editor = cx.new(|cx| {
+            let mut editor =
+                Editor::for_multibuffer(multibuffer.clone(), Some(project.clone()), window, cx);
+            editor.set_read_only(true);
+            editor.disable_diagnostics(cx);
+            editor.set_expand_all_diff_hunks(cx);
+            editor.set_render_diff_hunk_controls(
+                Arc::new(|_, _, _, _, _, _, _, _| gpui::Empty.into_any_element()),
+                cx,
+            );
+            editor.register_addon(CommitDiffAddon {
+                file_statuses: HashMap::default(),
+            });
+            editor
+        });
+        let load_files = cx.spawn_in(window, {
+            let files = files.clone();
+            let target_revision = target.to_string();
+            async move |this, cx| {
+                Self::load_files(
+                    this,
+                    repository,
+                    project,
+                    base_revision,
+                    target_revision,
+                    files,
+                    cx,
+                )
+                .await
+            }
+        });
+
+        Self {
+            editor,
+            multibuffer,
+            base,
+            target,
+            against_merge_base,
+            files,
+            _load_files: load_files,
+        }
+    }
+
+    /// Loads the files one after another, adding each file's excerpts as soon as its contents
+    /// are read, so that only the file being loaded is held in memory outside of its buffer.
+    async fn load_files(
+        this: WeakEntity<Self>,
+        repository: Entity<Repository>,
+        project: Entity<Project>,
+        base_revision: String,
+        target_revision: String,
+        files: Vec<(RepoPath, CommitFileStatus)>,
+        cx: &mut AsyncWindowContext,
+    ) -> Result<()> {
+        let (languages, first_worktree_id) = project.read_with(cx, |project, cx| {
+            let first_worktree_id = project
+                .worktrees(cx)
+                .next()
+                .map(|worktree| worktree.read(cx).id());
+            (project.languages().clone(), first_worktree_id)
+        });
+
+        for (path, status) in files {
+            let load_text = |revision: &String, cx: &mut AsyncWindowContext| {
+                let revision = revision.clone();
+                let path = path.clone();
+                repository.update(cx, |repository, cx| {
+                    repository.load_text_at_revision(revision, path, cx)
+                })
+            };
+            let old_text = match status {
+                CommitFileStatus::Added => Task::ready(Ok(None)),
+                _ => load_text(&base_revision, cx),
+            };
+            let new_text = match status {
+                CommitFileStatus::Deleted => Task::ready(Ok(None)),
+                _ => load_text(&target_revision, cx),
+            };
+            let (Some(old_text), Some(new_text)) =
+                (old_text.await.log_err(), new_text.await.log_err())
+            else {
+                continue;
+            };
+
+            let is_binary = new_text
+                .iter()
+                .chain(&old_text)
+                .any(|text| is_binary_content(text.as_bytes()));
+            let (new_text, old_text) = if is_binary {
+                ("(binary file not shown)".to_string(), None)
+            } else {
+                (new_text.unwrap_or_default(), old_text)
+            };
+            let worktree_id = repository
+                .update(cx, |repository, cx| {
+                    repository
+                        .repo_path_to_project_path(&path, cx)
+                        .map(|path| path.worktree_id)
+                        .or(first_worktree_id)
+                })
+                .context("project has no worktrees")?;
+            let display_name = path
+                .file_name()
+                .map(|name| name.to_string())
+                .unwrap_or_else(|| path.display(PathStyle::local()).to_string());
+            let blob = Arc::new(GitBlob {
+                path: path.clone(),
+                worktree_id,
+                is_deleted: status == CommitFileStatus::Deleted,
+                is_binary,
+                display_name,
+            }) as Arc<dyn language::File>;
+
+            let buffer = build_buffer(new_text, blob, &languages, cx).await?;
+            let diff = if is_binary {
+                None
+            } else {
+                Some(build_buffer_diff(old_text, &buffer, &languages, cx).await?)
+            };
+            this.update(cx, |this, cx| this.add_file(path, status, buffer, diff, cx))?;
+        }
+        Ok(())
+    }
+
+    fn add_file(
+        &mut self,
+        path: RepoPath,
+        status: CommitFileStatus,
+        buffer: Entity<Buffer>,
+        diff: Option<Entity<BufferDiff>>,
+        cx: &mut Context<Self>,
+    ) {
+        let snapshot = buffer.read(cx).snapshot();
+        let mut ranges = diff
+            .as_ref()
+            .map(|diff| {
+                diff.read(cx)
+                    .snapshot(cx)
+                    .hunks(&snapshot)
+                    .map(|hunk| hunk.buffer_range.to_point(&snapshot))
+                    .collect::<Vec<_>>()
+            })
+            .unwrap_or_default();
+        if ranges.is_empty() {
+            ranges.push(Point::zero()..snapshot.max_point());
+        }
+        let status_code = match status {
+            CommitFileStatus::Added => StatusCode::Added,
+            CommitFileStatus::Modified => StatusCode::Modified,
+            CommitFileStatus::Deleted => StatusCode::Deleted,
+        };
+        self.editor.update(cx, |editor, _| {
+            if let Some(addon) = editor.addon_mut::<CommitDiffAddon>() {
+                addon.file_statuses.insert(
+                    snapshot.remote_id(),
+                    FileStatus::Tracked(TrackedStatus {
+                        index_status: status_code,
+                        worktree_status: StatusCode::Unmodified,
+                    }),
+                );
+            }
+        });
+        let context_lines = multibuffer_context_lines(cx);
+        self.multibuffer.update(cx, |multibuffer, cx| {
+            multibuffer.set_excerpts_for_path(
+                PathKey::with_sort_prefix(0, path.as_ref().clone()),
+                buffer,
+                ranges,
+                context_lines,
+                cx,
+            );
+            if let Some(diff) = diff {
+                multibuffer.add_diff(diff, cx);
+            }
+        });
+    }
+
+    fn move_to_file(&mut self, path: &RepoPath, window: &mut Window, cx: &mut Context<Self>) {
+        let path_key = PathKey::with_sort_prefix(0, path.as_ref().clone());
+        let Some(position) = self.multibuffer.read(cx).location_for_path(&path_key, cx) else {
+            return;
+        };
+        self.editor.update(cx, |editor, cx| {
+            editor.change_selections(
+                SelectionEffects::scroll(Autoscroll::top_relative(0)),
+                window,
+                cx,
+                |selections| selections.select_ranges([position..position]),
+            );
+        });
+        window.focus(&self.editor.focus_handle(cx), cx);
+    }
+
+    fn title(&self) -> SharedString {
+        // Git's notation, where three dots compare with the merge base.
+        let separator = if self.against_merge_base { "..." } else { ".." };
+        format!("{}{separator}{}", self.base, self.target).into()
+    }
+
+    fn render_file_menu(&self, cx: &mut Context<Self>) -> impl IntoElement {
+        let view = cx.weak_entity();
+        let files = self.files.clone();
+        let file_count = match files.len() {
+            1 => "1 file".to_string(),
+            count => format!("{count} files"),
+        };
+
+        PopoverMenu::new("branch-comparison-files")
+            .trigger_with_tooltip(
+                Button::new("branch-comparison-files-trigger", file_count)
+                    .label_size(LabelSize::Small)
+                    .end_icon(Icon::new(IconName::ChevronDown).size(IconSize::XSmall)),
+                Tooltip::text("Go to File"),
+            )
+            .menu(move |window, cx| {
+                let view = view.clone();
+                let files = files.clone();
+                Some(ContextMenu::build(window, cx, move |mut menu, _, _| {
+                    for (path, status) in files {
+                        let label = match status {
+                            CommitFileStatus::Added => format!("{} (added)", path.as_unix_str()),
+                            CommitFileStatus::Modified => path.as_unix_str().to_string(),
+                            CommitFileStatus::Deleted => {
+                                format!("{} (deleted)", path.as_unix_str())
+                            }
+                        };
+                        let view: WeakEntity<Self> = view.clone();
+                        menu = menu.entry(label, None, move |window, cx| {
+                            view.update(cx, |view, cx| view.move_to_file(&path, window, cx))
+                                .ok();
+                        });
+                    }
+                    menu
+                }))
+            })
+    }
+}
+
+impl EventEmitter<EditorEvent> for BranchComparisonView {}
+
+impl Focusable for BranchComparisonView {
+    fn focus_handle(&self, cx: &App) -> FocusHandle {
+        self.editor.focus_handle(cx)
+    }
+}
+
+impl Item for BranchComparisonView {
+    type Event = EditorEvent;
+
+    fn tab_icon(&self, _window: &Window, _cx: &App) -> Option<Icon> {
+        Some(Icon::new(IconName::GitBranch).color(Color::Muted))
+    }
+
+    fn tab_content(&self, params: TabContentParams, _window: &Window, _cx: &App) -> AnyElement {
+        Label::new(self.title())
+            .color(if params.selected {
+                Color::Default
+            } else {
+                Color::Muted
+            })
+            .into_any_element()
+    }
+
+    fn tab_content_text(&self, _detail: usize, _cx: &App) -> SharedString {
+        self.title()
+    }
+
+    fn tab_tooltip_text(&self, _cx: &App) -> Option<SharedString> {
+        let tooltip = if self.against_merge_base {
+            format!(
+                "Changes in {} since it branched off {}",
+                self.target, self.base
+            )
+        } else {
+            format!("Changes in {} since {}", self.target, self.base)
+        };
+        Some(tooltip.into())
+    }
+
+    fn to_item_events(event: &EditorEvent, f: &mut dyn FnMut(ItemEvent)) {
+        Editor::to_item_events(event, f)
+    }
+
+    fn telemetry_event_text(&self) -> Option<&'static str> {
+        Some("Branch Comparison Opened")
+    }
+
+    fn deactivated(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        self.editor
+            .update(cx, |editor, cx| editor.deactivated(window, cx));
+    }
+
+    fn act_as_type<'a>(
+        &'a self,
+        type_id: TypeId,
+        self_handle: &'a Entity<Self>,
+        _: &'a App,
+    ) -> Option<gpui::AnyEntity> {
+        if type_id == TypeId::of::<Self>() {
+            Some(self_handle.clone().into())
+        } else if type_id == TypeId::of::<Editor>() {
+            Some(self.editor.clone().into())
+        } else {
+            None
+        }
+    }
+
+    fn as_searchable(&self, _: &Entity<Self>, _: &App) -> Option<Box<dyn SearchableItemHandle>> {
+        Some(Box::new(self.editor.clone()))
+    }
+
+    fn set_nav_history(
+        &mut self,
+        nav_history: ItemNavHistory,
+        _: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.editor.update(cx, |editor, _| {
+            editor.set_nav_history(Some(nav_history));
+        });
+    }
+
+    fn navigate(
+        &mut self,
+        data: Arc<dyn Any + Send>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> bool {
+        self.editor
+            .update(cx, |editor, cx| editor.navigate(data, window, cx))
+    }
+
+    fn breadcrumb_location(&self, _: &App) -> ToolbarItemLocation {
+        ToolbarItemLocation::PrimaryLeft
+    }
+
+    fn breadcrumbs(&self, cx: &App) -> Option<(Vec<HighlightedText>, Option<Font>)> {
+        self.editor.breadcrumbs(cx)
+    }
+
+    fn added_to_workspace(
+        &mut self,
+        workspace: &mut Workspace,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.editor.update(cx, |editor, cx| {
+            editor.added_to_workspace(workspace, window, cx)
+        });
+    }
+}
+
+impl Render for BranchComparisonView {
+    fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        v_flex()
+            .size_full()
+            .bg(cx.theme().colors().editor_background)
+            .child(
+                h_flex()
+                    .px_2()
+                    .py_1()
+                    .gap_2()
+                    .justify_between()
+                    .border_b_1()
+                    .border_color(cx.theme().colors().border_variant)
+                    .child(
+                        h_flex()
+                            .gap_1()
+                            .child(Label::new(self.base.clone()).size(LabelSize::Small))
+                            .when(self.against_merge_base, |this| {
+                                this.child(
+                                    Label::new("(merge base)")
+                                        .size(LabelSize::Small)
+                                        .color(Color::Muted),
+                                )
+                            })
+                            .child(
+                                Icon::new(IconName::ArrowRight)
+                                    .size(IconSize::XSmall)
+                                    .color(Color::Muted),
+                            )
+                            .child(Label::new(self.target.clone()).size(LabelSize::Small)),
+                    )
+                    .child(self.render_file_menu(cx)),
+            )
+            .child(div().flex_grow().child(self.editor.clone()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use super::*;
+    use git::repository::repo_path;
+    use gpui::{TestAppContext, VisualTestContext};
+    use project::{FakeFs, Project};
+    use serde_json::json;
+    use settings::SettingsStore;
+    use util::path;
+    use workspace::MultiWorkspace;
+
+    fn init_test(cx: &mut TestAppContext) {
+        cx.update(|cx| {
+            let settings_store = SettingsStore::test(cx);
+            cx.set_global(settings_store);
+            theme::init(theme::LoadThemes::JustBase, cx);
+            editor::init(cx);
+        });
+    }
+
+    #[gpui::test]
+    async fn test_compare_branches(cx: &mut TestAppContext) {
+        init_test(cx);
+
+        let fs = FakeFs::new(cx.executor());
+        fs.insert_tree(
+            path!("/project"),
+            json!({ ".git": {}, "a.txt": "a\n", "b.txt": "b\n" }),
+        )
+        .await;
+        fs.with_git_state(Path::new(path!("/project/.git")), true, |state| {
+            for (revision, path, content) in [
+                ("main", "a.txt", "a\n"),
+                ("main", "b.txt", "b\n"),
+                ("main", "d.txt", "d\n"),
+                ("feature", "a.txt", "a\nmore a\n"),
+                ("feature", "b.txt", "b\n"),
+                ("feature", "c.txt", "c\n"),
+            ] {
+                state
+                    .revision_contents
+                    .insert((revision.to_string(), repo_path(path)), content.to_string());
+            }
+        })
+        .unwrap();
+        let project = Project::test(fs.clone(), [Path::new(path!("/project"))], cx).await;
+        let multi_workspace =
+            cx.add_window(|window, cx| MultiWorkspace::test_new(project.clone(), window, cx));
+        let cx = &mut VisualTestContext::from_window(*multi_workspace, cx);
+        let workspace = multi_workspace
+            .update(cx, |workspace, _, _| workspace.workspace().clone())
+            .unwrap();
+        cx.run_until_parked();
+
+        let repository =
+            project.read_with(cx, |project, cx| project.active_repository(cx).unwrap());
+        let view = workspace
+            .update_in(cx, |workspace, window, cx| {
+                BranchComparisonView::open(
+                    repository,
+                    "main".into(),
+                    "feature".into(),
+                    workspace,
+                    window,
+                    cx,
+                )
+            })
+            .await
+            .unwrap();
+        cx.run_until_parked();
+
+        view.update(cx, |view, cx| {
+            assert_eq!(view.title(), "main..feature");
+            assert_eq!(
+                view.files,
+                [
+                    (repo_path("a.txt"), CommitFileStatus::Modified),
+                    (repo_path("c.txt"), CommitFileStatus::Added),
+                    (repo_path("d.txt"), CommitFileStatus::Deleted),
+                ]
+            );
+            assert_eq!(view.multibuffer.read(cx).all_buffer_ids().len(), 3);
+        });
+        workspace.update(cx, |workspace, cx| {
+            assert_eq!(
+                workspace
+                    .active_item_as::<BranchComparisonView>(cx)
+                    .map(|item| item.entity_id()),
+                Some(view.entity_id())
+            );
+        });
+    }
+
+    #[gpui::test]
+    async fn test_diff_against_merge_base(cx: &mut TestAppContext) {
+        init_test(cx);
+
+        let fs = FakeFs::new(cx.executor());
+        fs.insert_tree(
+            path!("/project"),
+            json!({
+                ".git": {},
+                ".zed": {
+                    "settings.json": r#"{ "git": { "default_base_branch": "develop" } }"#
+                },
+                "a.txt": "a\n",
+            }),
+        )
+        .await;
+        fs.with_git_state(Path::new(path!("/project/.git")), true, |state| {
+            state.current_branch_name = Some("feature".to_string());
+            state.branches.insert("feature".to_string());
+            state.branches.insert("develop".to_string());
+            state.merge_bases.insert(
+                ("develop".to_string(), "feature".to_string()),
+                "fork-point".to_string(),
+            );
+            for (revision, content) in [
+                ("fork-point", "a\n"),
+                ("develop", "a\nchanged on develop\n"),
+                ("feature", "a\nchanged on feature\n"),
+            ] {
+                state.revision_contents.insert(
+                    (revision.to_string(), repo_path("a.txt")),
+                    content.to_string(),
+                );
+            }
+        })
+        .unwrap();
+        let project = Project::test(fs.clone(), [Path::new(path!("/project"))], cx).await;
+        let multi_workspace =
+            cx.add_window(|window, cx| MultiWorkspace::test_new(project.clone(), window, cx));
+        let cx = &mut VisualTestContext::from_window(*multi_workspace, cx);
+        let workspace = multi_workspace
+            .update(cx, |workspace, _, _| workspace.workspace().clone())
+            .unwrap();
+        cx.run_until_parked();
+
+        let repository =
+            project.read_with(cx, |project, cx| project.active_repository(cx).unwrap());
+        let view = workspace
+            .update_in(cx, |workspace, window, cx| {
+                BranchComparisonView::open_against_merge_base(repository, workspace, window, cx)
+            })
+            .await
+            .unwrap();
+        cx.run_until_parked();
+
+        view.update(cx, |view, cx| {
+            assert_eq!(view.title(), "develop...feature");
+            assert_eq!(
+                view.files,
+                [(repo_path("a.txt"), CommitFileStatus::Modified)]
+            );
+            let buffer_id = view.multibuffer.read(cx).all_buffer_ids()[0];
+            let buffer = view.multibuffer.read(cx).buffer(buffer_id).unwrap();
+            assert_eq!(buffer.read(cx).text(), "a\nchanged on feature\n");
+        });
+    }
+}
@@ -3,12 +3,12 @@ use editor::Editor;
 use fuzzy::StringMatchCandidate;
 
 use collections::HashSet;
-use git::repository::Branch;
+use git::repository::{BRANCH_NOT_FULLY_MERGED, Branch};
 use gpui::http_client::Url;
 use gpui::{
     Action, App, Context, DismissEvent, Entity, EventEmitter, FocusHandle, Focusable,
-    InteractiveElement, IntoElement, Modifiers, ModifiersChangedEvent, ParentElement, Render,
-    SharedString, Styled, Subscription, Task, WeakEntity, Window, actions, rems,
+    InteractiveElement, IntoElement, Modifiers, ModifiersChangedEvent, ParentElement, PromptLevel,
+    Render, SharedString, Styled, Subscription, Task, WeakEntity, Window, actions, rems,
 };
 use picker::{Picker, PickerDelegate, PickerEditorPosition};
 use project::git_store::Repository;
@@ -22,7 +22,10 @@ use util::ResultExt;
 use workspace::notifications::DetachAndPromptErr;
 use workspace::{ModalView, Workspace};
 
-use crate::{branch_picker, git_panel::show_error_toast, resolve_active_repository};
+use crate::{
+    RenameBranchModal, branch_picker, commit_tooltip::CommitShaTooltip,
+    git_panel::show_error_toast, resolve_active_repository,
+};
 
 actions!(
     branch_picker,
@@ -30,7 +33,10 @@ actions!(
         /// Deletes the selected git branch or remote.
         DeleteBranch,
         /// Filter the list of remotes
-        FilterRemotes
+        FilterRemotes,
+        /// Creates the new branch from a branch, tag or commit you choose instead of the current
+        /// branch.
+        CreateBranchFrom,
     ]
 );
 
@@ -150,8 +156,12 @@ impl BranchList {
             repository.update(cx, |repository, _| repository.default_branch(false))
         });
 
+        let tags_request = repository
+            .clone()
+            .map(|repository| repository.update(cx, |repository, _| repository.tags()));
+
         cx.spawn_in(window, async move |this, cx| {
-            let mut all_branches = all_branches_request
+            let all_branches = all_branches_request
                 .context("No active repository")?
                 .await??;
             let default_branch = default_branch_request
@@ -161,40 +171,24 @@ impl BranchList {
                 .ok()
                 .flatten()
                 .flatten();
+            let tags = match tags_request {
+                Some(request) => request
+                    .await
+                    .ok()
+                    .and_then(|tags| tags.log_err())
+                    .unwrap_or_default(),
+                None => Vec::new(),
+            };
 
             let all_branches = cx
-                .background_spawn(async move {
-                    let remote_upstreams: HashSet<_> = all_branches
-                        .iter()
-                        .filter_map(|branch| {
-                            branch
-                                .upstream
-                                .as_ref()
-                                .filter(|upstream| upstream.is_remote())
-                                .map(|upstream| upstream.ref_name.clone())
-                        })
-                        .collect();
-
-                    all_branches.retain(|branch| !remote_upstreams.contains(&branch.ref_name));
-
-                    all_branches.sort_by_key(|branch| {
-                        (
-                            !branch.is_head, // Current branch (is_head=true) comes first
-                            branch
-                                .most_recent_commit
-                                .as_ref()
-                                .map(|commit| 0 - commit.commit_timestamp),
-                        )
-                    });
-
-                    all_branches
-                })
+                .background_spawn(async move { prepare_branches(all_branches) })
                 .await;
 
             let _ = this.update_in(cx, |this, window, cx| {
                 this.picker.update(cx, |picker, cx| {
                     picker.delegate.default_branch = default_branch;
                     picker.delegate.all_branches = Some(all_branches);
+                    picker.delegate.tags = tags;
                     picker.refresh(window, cx);
                 })
             });
@@ -203,7 +197,13 @@ impl BranchList {
         })
         .detach_and_log_err(cx);
 
-        let delegate = BranchListDelegate::new(workspace, repository, style, cx);
+        // Show the branches listed last time right away, and update them once they're listed again.
+        let cached_branches = repository
+            .as_ref()
+            .and_then(|repository| repository.read(cx).cached_branches())
+            .map(|branches| prepare_branches(branches.to_vec()));
+        let mut delegate = BranchListDelegate::new(workspace, repository, style, cx);
+        delegate.all_branches = cached_branches;
         let picker = cx.new(|cx| {
             Picker::uniform_list(delegate, window, cx)
                 .show_scrollbar(true)
@@ -269,6 +269,19 @@ impl BranchList {
         })
     }
 
+    pub fn handle_create_branch_from(
+        &mut self,
+        _: &branch_picker::CreateBranchFrom,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.picker.update(cx, |picker, cx| {
+            picker
+                .delegate
+                .choose_base_at(picker.delegate.selected_index, window, cx)
+        })
+    }
+
     pub fn handle_filter(
         &mut self,
         _: &branch_picker::FilterRemotes,
@@ -300,6 +313,7 @@ impl Render for BranchList {
             .on_modifiers_changed(cx.listener(Self::handle_modifiers_changed))
             .on_action(cx.listener(Self::handle_delete))
             .on_action(cx.listener(Self::handle_filter))
+            .on_action(cx.listener(Self::handle_create_branch_from))
             .child(self.picker.clone())
             .when(!self.embedded, |this| {
                 this.on_mouse_down_out({
@@ -313,6 +327,35 @@ impl Render for BranchList {
     }
 }
 
+/// Hides the remote branches that local branches track, and sorts the current branch first and
+/// the rest by their latest commit.
+fn prepare_branches(mut all_branches: Vec<Branch>) -> Vec<Branch> {
+    let remote_upstreams: HashSet<_> = all_branches
+        .iter()
+        .filter_map(|branch| {
+            branch
+                .upstream
+                .as_ref()
+                .filter(|upstream| upstream.is_remote())
+                .map(|upstream| upstream.ref_name.clone())
+        })
+        .collect();
+
+    all_branches.retain(|branch| !remote_upstreams.contains(&branch.ref_name));
+
+    all_branches.sort_by_key(|branch| {
+        (
+            !branch.is_head, // Current branch (is_head=true) comes first
+            branch
+                .most_recent_commit
+                .as_ref()
+                .map(|commit| 0 - commit.commit_timestamp),
+        )
+    });
+
+    all_branches
+}
+
 #[derive(Debug, Clone, PartialEq)]
 enum Entry {
     Branch {
@@ -329,6 +372,107 @@ enum Entry {
         name: String,
         url: SharedString,
     },
+    Base {
+        base: BranchBase,
+        positions: Vec<usize>,
+    },
+    Manage {
+        action: BranchAction,
+        positions: Vec<usize>,
+    },
+}
+
+/// What a new branch can be created from, or an existing branch can track.
+#[derive(Debug, Clone, PartialEq)]
+enum BranchBase {
+    Branch(Branch),
+    Tag(SharedString),
+    /// A commit SHA typed or pasted into the query.
+    Commit(SharedString),
+}
+
+impl BranchBase {
+    fn name(&self) -> &str {
+        match self {
+            BranchBase::Branch(branch) => branch.name(),
+            BranchBase::Tag(name) | BranchBase::Commit(name) => name.as_ref(),
+        }
+    }
+}
+
+/// What can be done to a branch from the menu that secondary confirmation opens.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum BranchAction {
+    Rename,
+    Delete,
+    SetUpstream,
+    UnsetUpstream,
+}
+
+impl BranchAction {
+    fn available_for(branch: &Branch) -> Vec<Self> {
+        let mut actions = Vec::new();
+        if !branch.is_remote() {
+            actions.push(BranchAction::Rename);
+        }
+        if !branch.is_head {
+            actions.push(BranchAction::Delete);
+        }
+        if !branch.is_remote() {
+            actions.push(BranchAction::SetUpstream);
+            if branch.upstream.is_some() {
+                actions.push(BranchAction::UnsetUpstream);
+            }
+        }
+        actions
+    }
+
+    fn label(&self) -> &'static str {
+        match self {
+            BranchAction::Rename => "Rename…",
+            BranchAction::Delete => "Delete",
+            BranchAction::SetUpstream => "Set Upstream…",
+            BranchAction::UnsetUpstream => "Stop Tracking",
+        }
+    }
+
+    fn icon(&self) -> IconName {
+        match self {
+            BranchAction::Rename => IconName::Pencil,
+            BranchAction::Delete => IconName::Trash,
+            BranchAction::SetUpstream => IconName::ArrowUpRight,
+            BranchAction::UnsetUpstream => IconName::Close,
+        }
+    }
+
+    fn description(&self, branch: &Branch) -> String {
+        match self {
+            BranchAction::Rename => format!("Give {} a new name", branch.name()),
+            BranchAction::Delete if branch.is_remote() => "Delete the remote branch".to_string(),
+            BranchAction::Delete => "Delete the local branch".to_string(),
+            BranchAction::SetUpstream => match upstream_name(branch) {
+                Some(upstream) => format!("Tracking {upstream}"),
+                None => "Not tracking a branch".to_string(),
+            },
+            BranchAction::UnsetUpstream => match upstream_name(branch) {
+                Some(upstream) => format!("Stop tracking {upstream}"),
+                None => "Stop tracking".to_string(),
+            },
+        }
+    }
+}
+
+fn upstream_name(branch: &Branch) -> Option<&str> {
+    branch.upstream.as_ref().map(|upstream| {
+        upstream
+            .stripped_ref_name()
+            .or_else(|| upstream.ref_name.strip_prefix("refs/heads/"))
+            .unwrap_or(&upstream.ref_name)
+    })
+}
+
+fn is_commit_sha(query: &str) -> bool {
+    (4..=40).contains(&query.len()) && query.chars().all(|c| c.is_ascii_hexdigit())
 }
 
 impl Entry {
@@ -345,6 +489,8 @@ impl Entry {
             Entry::NewUrl { url, .. } => url.as_str(),
             Entry::NewBranch { name, .. } => name.as_str(),
             Entry::NewRemoteName { name, .. } => name.as_str(),
+            Entry::Base { base, .. } => base.name(),
+            Entry::Manage { action, .. } => action.label(),
         }
     }
 
@@ -380,6 +526,7 @@ pub struct BranchListDelegate {
     workspace: WeakEntity<Workspace>,
     matches: Vec<Entry>,
     all_branches: Option<Vec<Branch>>,
+    tags: Vec<SharedString>,
     default_branch: Option<SharedString>,
     repo: Option<Entity<Repository>>,
     style: BranchListStyle,
@@ -401,6 +548,15 @@ enum PickerState {
     CreateRemote(SharedString),
     /// When we set a new branch to create
     NewBranch,
+    /// When we pick the base of the new branch with this name (after NewBranch)
+    ChooseBase(SharedString),
+    /// When we pick what to do with this branch (after secondary confirming it in List)
+    ManageBranch(Branch),
+    /// When we pick the branch this branch should track (after ManageBranch)
+    ChooseUpstream(Branch),
+    /// When we name the local branch that tracks this remote branch, because a local branch
+    /// already has its name (after List)
+    NameTrackingBranch(Branch),
 }
 
 impl BranchListDelegate {
@@ -416,6 +572,7 @@ impl BranchListDelegate {
             repo,
             style,
             all_branches: None,
+            tags: Vec::new(),
             default_branch: None,
             selected_index: 0,
             last_query: Default::default(),
@@ -430,6 +587,7 @@ impl BranchListDelegate {
         &self,
         from_branch: Option<SharedString>,
         new_branch_name: SharedString,
+        upstream: Option<String>,
         window: &mut Window,
         cx: &mut Context<Picker<Self>>,
     ) {
@@ -440,9 +598,15 @@ impl BranchListDelegate {
         let base_branch = from_branch.map(|b| b.to_string());
         cx.spawn(async move |_, cx| {
             repo.update(cx, |repo, _| {
-                repo.create_branch(new_branch_name, base_branch)
+                repo.create_branch(new_branch_name.clone(), base_branch)
             })
             .await??;
+            if let Some(upstream) = upstream {
+                repo.update(cx, |repo, _| {
+                    repo.set_branch_upstream(new_branch_name, Some(upstream))
+                })
+                .await??;
+            }
 
             Ok(())
         })
@@ -472,10 +636,328 @@ impl BranchListDelegate {
         cx.emit(DismissEvent);
     }
 
+    fn choose_base_at(&mut self, idx: usize, window: &mut Window, cx: &mut Context<Picker<Self>>) {
+        let Some(Entry::NewBranch { name }) = self.matches.get(idx) else {
+            return;
+        };
+        let name = name.clone().into();
+        self.enter_state(PickerState::ChooseBase(name), window, cx);
+    }
+
+    fn update_base_matches(
+        &mut self,
+        query: String,
+        window: &mut Window,
+        cx: &mut Context<Picker<Self>>,
+    ) -> Task<()> {
+        // Only other branches can be tracked, while new branches can also start at a tag or commit.
+        let tracking_branch = match &self.state {
+            PickerState::ChooseUpstream(branch) => Some(branch.ref_name.clone()),
+            _ => None,
+        };
+        let mut bases = self
+            .all_branches
+            .iter()
+            .flatten()
+            .filter(|branch| tracking_branch.as_ref() != Some(&branch.ref_name))
+            .cloned()
+            .map(BranchBase::Branch)
+            .collect::<Vec<_>>();
+        if tracking_branch.is_none() {
+            bases.extend(self.tags.iter().cloned().map(BranchBase::Tag));
+        }
+
+        cx.spawn_in(window, async move |picker, cx| {
+            let mut matches: Vec<Entry> = if query.is_empty() {
+                bases
+                    .into_iter()
+                    .map(|base| Entry::Base {
+                        base,
+                        positions: Vec::new(),
+                    })
+                    .collect()
+            } else {
+                let candidates = bases
+                    .iter()
+                    .enumerate()
+                    .map(|(ix, base)| StringMatchCandidate::new(ix, base.name()))
+                    .collect::<Vec<StringMatchCandidate>>();
+                fuzzy::match_strings(
+                    &candidates,
+                    &query,
+                    true,
+                    true,
+                    10000,
+                    &Default::default(),
+                    cx.background_executor().clone(),
+                )
+                .await
+                .into_iter()
+                .map(|candidate| Entry::Base {
+                    base: bases[candidate.candidate_id].clone(),
+                    positions: candidate.positions,
+                })
+                .collect()
+            };
+            if tracking_branch.is_none()
+                && is_commit_sha(&query)
+                && !matches.iter().any(|entry| entry.name() == query)
+            {
+                matches.push(Entry::Base {
+                    base: BranchBase::Commit(query.clone().into()),
+                    positions: Vec::new(),
+                });
+            }
+
+            picker
+                .update(cx, |picker, _| {
+                    let delegate = &mut picker.delegate;
+                    delegate.matches = matches;
+                    delegate.selected_index = 0;
+                    delegate.last_query = query;
+                })
+                .log_err();
+        })
+    }
+
+    /// Checks out a local branch that tracks the remote branch, creating it unless a local branch
+    /// already tracks it. When another local branch has its name, asks whether to switch to that
+    /// branch or to name the new one.
+    fn check_out_remote_branch(
+        &self,
+        remote_branch: Branch,
+        window: &mut Window,
+        cx: &mut Context<Picker<Self>>,
+    ) {
+        let Some(repo) = self.repo.clone() else {
+            return;
+        };
+        let remote_branch_name = remote_branch.name().to_string();
+        let Some((remote_name, local_name)) = remote_branch_name.split_once('/') else {
+            return;
+        };
+        let local_name = local_name.to_string();
+        let suggested_name = format!("{remote_name}-{local_name}");
+
+        let existing_branch = self
+            .all_branches
+            .iter()
+            .flatten()
+            .find(|branch| !branch.is_remote() && branch.name() == local_name);
+        let Some(existing_branch) = existing_branch else {
+            self.create_branch(
+                Some(remote_branch_name.clone().into()),
+                local_name.into(),
+                Some(remote_branch_name),
+                window,
+                cx,
+            );
+            return;
+        };
+        if existing_branch
+            .upstream
+            .as_ref()
+            .is_some_and(|upstream| upstream.ref_name == remote_branch.ref_name)
+        {
+            self.change_branch(repo, local_name, window, cx);
+            cx.emit(DismissEvent);
+            return;
+        }
+
+        let prompt = window.prompt(
+            PromptLevel::Info,
+            &format!("A local branch named \"{local_name}\" already exists"),
+            Some(&format!(
+                "It doesn't track {remote_branch_name}. Switch to it, or check out \
+                 {remote_branch_name} as a new branch with another name."
+            )),
+            &["Switch to Existing", "Use Another Name…", "Cancel"],
+            cx,
+        );
+        cx.spawn_in(window, async move |picker, cx| match prompt.await {
+            Ok(0) => picker.update_in(cx, |picker, window, cx| {
+                picker.delegate.change_branch(repo, local_name, window, cx);
+                cx.emit(DismissEvent);
+            }),
+            Ok(1) => picker.update_in(cx, |picker, window, cx| {
+                picker.delegate.state = PickerState::NameTrackingBranch(remote_branch);
+                picker.delegate.matches = Vec::new();
+                picker.delegate.selected_index = 0;
+                picker.refresh_placeholder(window, cx);
+                picker.set_query(&suggested_name, window, cx);
+                cx.notify();
+            }),
+            _ => Ok(()),
+        })
+        .detach_and_log_err(cx);
+    }
+
+    fn change_branch(
+        &self,
+        repo: Entity<Repository>,
+        branch_name: String,
+        window: &mut Window,
+        cx: &mut Context<Picker<Self>>,
+    ) {
+        cx.spawn(async move |_, cx| {
+            repo.update(cx, |repo, _| repo.change_branch(branch_name))
+                .await??;
+
+            anyhow::Ok(())
+        })
+        .detach_and_prompt_err("Failed to change branch", window, cx, |_, _, _| None);
+    }
+
+    fn update_tracking_name_matches(&mut self, query: String) -> Task<()> {
+        let name = query.trim().replace(' ', "-");
+        let name_taken = self
+            .all_branches
+            .iter()
+            .flatten()
+            .any(|branch| !branch.is_remote() && branch.name() == name);
+        self.matches = if name.is_empty() || name_taken {
+            Vec::new()
+        } else {
+            vec![Entry::NewBranch { name }]
+        };
+        self.selected_index = 0;
+        self.last_query = query;
+        Task::ready(())
+    }
+
+    fn manage_at(&mut self, idx: usize, window: &mut Window, cx: &mut Context<Picker<Self>>) {
+        let Some(Entry::Branch { branch, .. }) = self.matches.get(idx) else {
+            return;
+        };
+        self.enter_state(PickerState::ManageBranch(branch.clone()), window, cx);
+    }
+
+    fn enter_state(
+        &mut self,
+        state: PickerState,
+        window: &mut Window,
+        cx: &mut Context<Picker<Self>>,
+    ) {
+        self.state = state;
+        self.matches = Vec::new();
+        self.selected_index = 0;
+
+        cx.defer_in(window, |picker, window, cx| {
+            picker.refresh_placeholder(window, cx);
+            picker.set_query("", window, cx);
+            cx.notify();
+        });
+    }
+
+    fn update_manage_matches(
+        &mut self,
+        query: String,
+        window: &mut Window,
+        cx: &mut Context<Picker<Self>>,
+    ) -> Task<()> {
+        let PickerState::ManageBranch(branch) = &self.state else {
+            return Task::ready(());
+        };
+        let actions = BranchAction::available_for(branch);
+
+        cx.spawn_in(window, async move |picker, cx| {
+            let matches: Vec<Entry> = if query.is_empty() {
+                actions
+                    .into_iter()
+                    .map(|action| Entry::Manage {
+                        action,
+                        positions: Vec::new(),
+                    })
+                    .collect()
+            } else {
+                let candidates = actions
+                    .iter()
+                    .enumerate()
+                    .map(|(ix, action)| StringMatchCandidate::new(ix, action.label()))
+                    .collect::<Vec<StringMatchCandidate>>();
+                fuzzy::match_strings(
+                    &candidates,
+                    &query,
+                    true,
+                    true,
+                    10000,
+                    &Default::default(),
+                    cx.background_executor().clone(),
+                )
+                .await
+                .into_iter()
+                .map(|candidate| Entry::Manage {
+                    action: actions[candidate.candidate_id],
+                    positions: candidate.positions,
+                })
+                .collect()
+            };
+
+            picker
+                .update(cx, |picker, _| {
+                    let delegate = &mut picker.delegate;
+                    delegate.matches = matches;
+                    delegate.selected_index = 0;
+                    delegate.last_query = query;
+                })
+                .log_err();
+        })
+    }
+
+    fn rename_branch(&self, branch: &Branch, window: &mut Window, cx: &mut Context<Picker<Self>>) {
+        let Some(repo) = self.repo.clone() else {
+            return;
+        };
+        let workspace = self.workspace.clone();
+        let branch_name = branch.name().to_string();
+        window.defer(cx, move |window, cx| {
+            workspace
+                .update(cx, |workspace, cx| {
+                    workspace.toggle_modal(window, cx, |window, cx| {
+                        RenameBranchModal::new(branch_name, repo, window, cx)
+                    })
+                })
+                .ok();
+        });
+    }
+
+    fn set_upstream(
+        &self,
+        branch_name: String,
+        upstream: Option<String>,
+        window: &mut Window,
+        cx: &mut Context<Picker<Self>>,
+    ) {
+        let Some(repo) = self.repo.clone() else {
+            return;
+        };
+        cx.spawn(async move |_, cx| {
+            repo.update(cx, |repo, _| {
+                repo.set_branch_upstream(branch_name, upstream)
+            })
+            .await??;
+
+            anyhow::Ok(())
+        })
+        .detach_and_prompt_err("Failed to set upstream branch", window, cx, |e, _, _| {
+            Some(e.to_string())
+        });
+    }
+
     fn delete_at(&self, idx: usize, window: &mut Window, cx: &mut Context<Picker<Self>>) {
-        let Some(entry) = self.matches.get(idx).cloned() else {
+        let Some(entry) = self.matches.get(idx) else {
+            return;
+        };
+        let Some(branch) = entry.as_branch() else {
+            log::error!("Failed to delete entry: wrong entry to delete");
             return;
         };
+        self.delete_branch(branch.clone(), window, cx);
+    }
+
+    /// Deletes the branch, asking before force deleting a local branch whose commits aren't
+    /// merged anywhere else.
+    fn delete_branch(&self, branch: Branch, window: &mut Window, cx: &mut Context<Picker<Self>>) {
         let Some(repo) = self.repo.clone() else {
             return;
         };
@@ -483,20 +965,38 @@ impl BranchListDelegate {
         let workspace = self.workspace.clone();
 
         cx.spawn_in(window, async move |picker, cx| {
-            let is_remote;
-            let result = match &entry {
-                Entry::Branch { branch, .. } => {
-                    is_remote = branch.is_remote();
-                    repo.update(cx, |repo, _| {
-                        repo.delete_branch(is_remote, branch.name().to_string())
-                    })
-                    .await?
-                }
-                _ => {
-                    log::error!("Failed to delete entry: wrong entry to delete");
+            let is_remote = branch.is_remote();
+            let branch_name = branch.name().to_string();
+            let mut result = repo
+                .update(cx, |repo, _| {
+                    repo.delete_branch(is_remote, branch_name.clone(), false)
+                })
+                .await?;
+
+            if let Err(error) = &result
+                && !is_remote
+                && format!("{error:#}").contains(BRANCH_NOT_FULLY_MERGED)
+            {
+                let answer = cx
+                    .update(|window, cx| {
+                        window.prompt(
+                            PromptLevel::Warning,
+                            &format!("\"{branch_name}\" is not fully merged"),
+                            Some("Force deleting it discards the commits only it contains."),
+                            &["Force Delete", "Cancel"],
+                            cx,
+                        )
+                    })?
+                    .await;
+                if answer.ok() != Some(0) {
                     return Ok(());
                 }
-            };
+                result = repo
+                    .update(cx, |repo, _| {
+                        repo.delete_branch(false, branch_name.clone(), true)
+                    })
+                    .await?;
+            }
 
             if let Err(e) = result {
                 if is_remote {
@@ -508,19 +1008,9 @@ impl BranchListDelegate {
                 if let Some(workspace) = workspace.upgrade() {
                     cx.update(|_window, cx| {
                         if is_remote {
-                            show_error_toast(
-                                workspace,
-                                format!("branch -dr {}", entry.name()),
-                                e,
-                                cx,
-                            )
+                            show_error_toast(workspace, format!("branch -dr {branch_name}"), e, cx)
                         } else {
-                            show_error_toast(
-                                workspace,
-                                format!("branch -d {}", entry.name()),
-                                e,
-                                cx,
-                            )
+                            show_error_toast(workspace, format!("branch -d {branch_name}"), e, cx)
                         }
                     })?;
                 }
@@ -529,12 +1019,14 @@ impl BranchListDelegate {
             }
 
             picker.update_in(cx, |picker, _, cx| {
-                picker.delegate.matches.retain(|e| e != &entry);
+                picker.delegate.matches.retain(|entry| {
+                    entry
+                        .as_branch()
+                        .is_none_or(|deleted| deleted.ref_name != branch.ref_name)
+                });
 
-                if let Entry::Branch { branch, .. } = &entry {
-                    if let Some(all_branches) = &mut picker.delegate.all_branches {
-                        all_branches.retain(|e| e.ref_name != branch.ref_name);
-                    }
+                if let Some(all_branches) = &mut picker.delegate.all_branches {
+                    all_branches.retain(|e| e.ref_name != branch.ref_name);
                 }
 
                 if picker.delegate.matches.is_empty() {
@@ -556,15 +1048,28 @@ impl PickerDelegate for BranchListDelegate {
     type ListItem = ListItem;
 
     fn placeholder_text(&self, _window: &mut Window, _cx: &mut App) -> Arc<str> {
-        match self.state {
+        match &self.state {
             PickerState::List | PickerState::NewRemote | PickerState::NewBranch => {
                 match self.branch_filter {
-                    BranchFilter::All | BranchFilter::Remote => "Select branch…",
+                    BranchFilter::All | BranchFilter::Remote => "Select branch…".into(),
                 }
             }
-            PickerState::CreateRemote(_) => "Enter a name for this remote…",
+            PickerState::CreateRemote(_) => "Enter a name for this remote…".into(),
+            PickerState::ChooseBase(name) => {
+                format!("Select a branch, tag or commit to base \"{name}\" on…").into()
+            }
+            PickerState::ManageBranch(branch) => {
+                format!("Choose what to do with \"{}\"…", branch.name()).into()
+            }
+            PickerState::ChooseUpstream(branch) => {
+                format!("Select the branch \"{}\" should track…", branch.name()).into()
+            }
+            PickerState::NameTrackingBranch(remote_branch) => format!(
+                "Name the local branch that tracks \"{}\"…",
+                remote_branch.name()
+            )
+            .into(),
         }
-        .into()
     }
 
     fn no_matches_text(&self, _window: &mut Window, _cx: &mut App) -> Option<SharedString> {
@@ -572,6 +1077,13 @@ impl PickerDelegate for BranchListDelegate {
             PickerState::CreateRemote(_) => {
                 Some(SharedString::new_static("Remote name can't be empty"))
             }
+            PickerState::ChooseBase(_) => Some(SharedString::new_static(
+                "No matching branch or tag. Paste a commit SHA to use it.",
+            )),
+            PickerState::ChooseUpstream(_) => Some(SharedString::new_static("No matching branch")),
+            PickerState::NameTrackingBranch(_) => Some(SharedString::new_static(
+                "Enter a name that no local branch has yet",
+            )),
             _ => None,
         }
     }
@@ -662,6 +1174,17 @@ impl PickerDelegate for BranchListDelegate {
         window: &mut Window,
         cx: &mut Context<Picker<Self>>,
     ) -> Task<()> {
+        match self.state {
+            PickerState::ChooseBase(_) | PickerState::ChooseUpstream(_) => {
+                return self.update_base_matches(query, window, cx);
+            }
+            PickerState::ManageBranch(_) => return self.update_manage_matches(query, window, cx),
+            PickerState::NameTrackingBranch(_) => return self.update_tracking_name_matches(query),
+            PickerState::List
+            | PickerState::NewRemote
+            | PickerState::CreateRemote(_)
+            | PickerState::NewBranch => {}
+        }
         let Some(all_branches) = self.all_branches.clone() else {
             return Task::ready(());
         };
@@ -782,6 +1305,10 @@ impl PickerDelegate for BranchListDelegate {
         };
 
         match entry {
+            Entry::Branch { .. } if secondary => {
+                self.manage_at(self.selected_index, window, cx);
+                return;
+            }
             Entry::Branch { branch, .. } => {
                 let current_branch = self.repo.as_ref().map(|repo| {
                     repo.read_with(cx, |repo, _| {
@@ -797,34 +1324,19 @@ impl PickerDelegate for BranchListDelegate {
                     return;
                 }
 
+                if branch.is_remote() {
+                    self.check_out_remote_branch(branch.clone(), window, cx);
+                    return;
+                }
+
                 let Some(repo) = self.repo.clone() else {
                     return;
                 };
-
-                let branch = branch.clone();
-                cx.spawn(async move |_, cx| {
-                    repo.update(cx, |repo, _| repo.change_branch(branch.name().to_string()))
-                        .await??;
-
-                    anyhow::Ok(())
-                })
-                .detach_and_prompt_err(
-                    "Failed to change branch",
-                    window,
-                    cx,
-                    |_, _, _| None,
-                );
+                self.change_branch(repo, branch.name().to_string(), window, cx);
             }
             Entry::NewUrl { url } => {
-                self.state = PickerState::CreateRemote(url.clone().into());
-                self.matches = Vec::new();
-                self.selected_index = 0;
-
-                cx.defer_in(window, |picker, window, cx| {
-                    picker.refresh_placeholder(window, cx);
-                    picker.set_query("", window, cx);
-                    cx.notify();
-                });
+                let url = url.clone().into();
+                self.enter_state(PickerState::CreateRemote(url), window, cx);
 
                 // returning early to prevent dismissing the modal, so a user can enter
                 // a remote name first.
@@ -834,12 +1346,69 @@ impl PickerDelegate for BranchListDelegate {
                 self.create_remote(name.clone(), url.to_string(), window, cx);
             }
             Entry::NewBranch { name } => {
+                if let PickerState::NameTrackingBranch(remote_branch) = &self.state {
+                    let remote_branch_name = remote_branch.name().to_string();
+                    self.create_branch(
+                        Some(remote_branch_name.clone().into()),
+                        name.into(),
+                        Some(remote_branch_name),
+                        window,
+                        cx,
+                    );
+                    return;
+                }
                 let from_branch = if secondary {
                     self.default_branch.clone()
                 } else {
                     None
                 };
-                self.create_branch(from_branch, name.into(), window, cx);
+                self.create_branch(from_branch, name.into(), None, window, cx);
+            }
+            Entry::Base { base, .. } => match &self.state {
+                PickerState::ChooseBase(new_branch_name) => {
+                    // Secondary confirmation also makes the new branch track the one it starts from.
+                    let upstream = match base {
+                        BranchBase::Branch(branch) if secondary => Some(branch.name().to_string()),
+                        _ => None,
+                    };
+                    self.create_branch(
+                        Some(base.name().to_string().into()),
+                        new_branch_name.clone(),
+                        upstream,
+                        window,
+                        cx,
+                    );
+                }
+                PickerState::ChooseUpstream(branch) => {
+                    self.set_upstream(
+                        branch.name().to_string(),
+                        Some(base.name().to_string()),
+                        window,
+                        cx,
+                    );
+                }
+                _ => return,
+            },
+            Entry::Manage { action, .. } => {
+                let PickerState::ManageBranch(branch) = &self.state else {
+                    return;
+                };
+                let branch = branch.clone();
+                match *action {
+                    BranchAction::Rename => self.rename_branch(&branch, window, cx),
+                    BranchAction::Delete => {
+                        self.delete_branch(branch, window, cx);
+                        self.enter_state(PickerState::List, window, cx);
+                        return;
+                    }
+                    BranchAction::SetUpstream => {
+                        self.enter_state(PickerState::ChooseUpstream(branch), window, cx);
+                        return;
+                    }
+                    BranchAction::UnsetUpstream => {
+                        self.set_upstream(branch.name().to_string(), None, window, cx)
+                    }
+                }
             }
         }
 
@@ -860,6 +1429,15 @@ impl PickerDelegate for BranchListDelegate {
     ) -> Option<Self::ListItem> {
         let entry = &self.matches.get(ix)?;
 
+        let upstream = entry.as_branch().and_then(|branch| {
+            let name = upstream_name(branch)?.to_string();
+            Some((name, branch.upstream.as_ref()?.tracking))
+        });
+
+        let commit_sha = entry
+            .as_branch()
+            .and_then(|branch| branch.most_recent_commit.as_ref())
+            .map(|commit| commit.sha.clone());
         let (commit_time, author_name, subject) = entry
             .as_branch()
             .and_then(|branch| {
@@ -885,13 +1463,26 @@ impl PickerDelegate for BranchListDelegate {
             Entry::NewUrl { .. } | Entry::NewBranch { .. } | Entry::NewRemoteName { .. } => {
                 IconName::Plus
             }
-            Entry::Branch { branch, .. } => {
+            Entry::Branch { branch, .. }
+            | Entry::Base {
+                base: BranchBase::Branch(branch),
+                ..
+            } => {
                 if branch.is_remote() {
                     IconName::Screen
                 } else {
                     IconName::GitBranchAlt
                 }
             }
+            Entry::Base {
+                base: BranchBase::Tag(_),
+                ..
+            } => IconName::Hash,
+            Entry::Base {
+                base: BranchBase::Commit(_),
+                ..
+            } => IconName::GitCommit,
+            Entry::Manage { action, .. } => action.icon(),
         };
 
         let entry_title = match entry {
@@ -913,6 +1504,25 @@ impl PickerDelegate for BranchListDelegate {
                     .truncate()
                     .into_any_element()
             }
+            Entry::Base {
+                base: BranchBase::Commit(sha),
+                ..
+            } => Label::new(format!("Commit {sha}"))
+                .single_line()
+                .truncate()
+                .into_any_element(),
+            Entry::Base { base, positions } => {
+                HighlightedLabel::new(base.name().to_string(), positions.clone())
+                    .single_line()
+                    .truncate()
+                    .into_any_element()
+            }
+            Entry::Manage { action, positions } => {
+                HighlightedLabel::new(action.label(), positions.clone())
+                    .single_line()
+                    .truncate()
+                    .into_any_element()
+            }
         };
 
         let focus_handle = self.focus_handle.clone();
@@ -922,6 +1532,7 @@ impl PickerDelegate for BranchListDelegate {
         );
 
         let is_head_branch = entry.as_branch().is_some_and(|branch| branch.is_head);
+        let is_picked_option = matches!(entry, Entry::Base { .. } | Entry::Manage { .. });
 
         let deleted_branch_icon = |entry_ix: usize| {
             IconButton::new(("delete", entry_ix), IconName::Trash)
@@ -986,7 +1597,11 @@ impl PickerDelegate for BranchListDelegate {
                                             format!("Based off {url}")
                                         }
                                         Entry::NewBranch { .. } => {
-                                            if let Some(current_branch) =
+                                            if let PickerState::NameTrackingBranch(remote_branch) =
+                                                &self.state
+                                            {
+                                                format!("Tracking {}", remote_branch.name())
+                                            } else if let Some(current_branch) =
                                                 self.repo.as_ref().and_then(|repo| {
                                                     repo.read(cx).branch.as_ref().map(|b| b.name())
                                                 })
@@ -997,6 +1612,20 @@ impl PickerDelegate for BranchListDelegate {
                                             }
                                         }
                                         Entry::Branch { .. } => String::new(),
+                                        Entry::Base { base, .. } => match base {
+                                            BranchBase::Branch(branch) if branch.is_remote() => {
+                                                "Remote branch".to_string()
+                                            }
+                                            BranchBase::Branch(_) => "Local branch".to_string(),
+                                            BranchBase::Tag(_) => "Tag".to_string(),
+                                            BranchBase::Commit(_) => "Commit".to_string(),
+                                        },
+                                        Entry::Manage { action, .. } => match &self.state {
+                                            PickerState::ManageBranch(branch) => {
+                                                action.description(branch)
+                                            }
+                                            _ => String::new(),
+                                        },
                                     };
 
                                     if matches!(entry, Entry::Branch { .. }) {
@@ -1035,14 +1664,82 @@ impl PickerDelegate for BranchListDelegate {
                                                             .size(LabelSize::Small),
                                                     )
                                             })
+                                            .when_some(upstream, |this, (name, tracking)| {
+                                                let tracking_count = |icon, count: u32| {
+                                                    h_flex()
+                                                        .gap_0p5()
+                                                        .child(
+                                                            Icon::new(icon)
+                                                                .size(IconSize::XSmall)
+                                                                .color(Color::Muted),
+                                                        )
+                                                        .child(
+                                                            Label::new(count.to_string())
+                                                                .color(Color::Muted)
+                                                                .size(LabelSize::Small),
+                                                        )
+                                                };
+                                                this.when(has_author || has_commit, |this| {
+                                                    this.child(dot())
+                                                })
+                                                .child(
+                                                    Label::new(name)
+                                                        .color(Color::Muted)
+                                                        .size(LabelSize::Small),
+                                                )
+                                                .map(|this| match tracking.status() {
+                                                    Some(status) => this
+                                                        .when(status.ahead > 0, |this| {
+                                                            this.child(tracking_count(
+                                                                IconName::ArrowUp,
+                                                                status.ahead,
+                                                            ))
+                                                        })
+                                                        .when(status.behind > 0, |this| {
+                                                            this.child(tracking_count(
+                                                                IconName::ArrowDown,
+                                                                status.behind,
+                                                            ))
+                                                        }),
+                                                    None => this.child(
+                                                        Label::new("(gone)")
+                                                            .color(Color::Muted)
+                                                            .size(LabelSize::Small),
+                                                    ),
+                                                })
+                                            })
                                             .when_some(subject, |this, subj| {
                                                 this.when(has_commit, |this| this.child(dot()))
                                                     .child(
-                                                        Label::new(subj.to_string())
-                                                            .color(Color::Muted)
-                                                            .size(LabelSize::Small)
-                                                            .truncate()
-                                                            .flex_1(),
+                                                        div()
+                                                            .id(("branch-commit-subject", ix))
+                                                            .flex_1()
+                                                            .min_w_0()
+                                                            .child(
+                                                                Label::new(subj.to_string())
+                                                                    .color(Color::Muted)
+                                                                    .size(LabelSize::Small)
+                                                                    .truncate(),
+                                                            )
+                                                            .when_some(
+                                                                commit_sha.zip(self.repo.clone()),
+                                                                |this, (sha, repository)| {
+                                                                    let workspace =
+                                                                        self.workspace.clone();
+                                                                    this.hoverable_tooltip(
+                                                                        move |window, cx| {
+                                                                            CommitShaTooltip::new(
+                                                                                sha.clone(),
+                                                                                repository.clone(),
+                                                                                workspace.clone(),
+                                                                                window,
+                                                                                cx,
+                                                                            )
+                                                                            .into()
+                                                                        },
+                                                                    )
+                                                                },
+                                                            ),
                                                     )
                                             })
                                             .when(!has_commit, |this| {
@@ -1082,17 +1779,20 @@ impl PickerDelegate for BranchListDelegate {
                                 ),
                         ),
                 )
-                .when(!is_new_items && !is_head_branch, |this| {
-                    this.map(|this| {
-                        if self.selected_index() == ix {
-                            this.end_slot(deleted_branch_icon(ix))
-                        } else {
-                            this.end_hover_slot(deleted_branch_icon(ix))
-                        }
-                    })
-                })
+                .when(
+                    !is_new_items && !is_picked_option && !is_head_branch,
+                    |this| {
+                        this.map(|this| {
+                            if self.selected_index() == ix {
+                                this.end_slot(deleted_branch_icon(ix))
+                            } else {
+                                this.end_hover_slot(deleted_branch_icon(ix))
+                            }
+                        })
+                    },
+                )
                 .when_some(
-                    if is_new_items {
+                    if is_new_items && !matches!(self.state, PickerState::NameTrackingBranch(_)) {
                         create_from_default_button
                     } else {
                         None
@@ -1124,14 +1824,27 @@ impl PickerDelegate for BranchListDelegate {
                 .border_color(cx.theme().colors().border_variant)
         };
 
-        match self.state {
+        let create_from_button = || {
+            Button::new("create-branch-from", "Create From…")
+                .key_binding(
+                    KeyBinding::for_action_in(&branch_picker::CreateBranchFrom, &focus_handle, cx)
+                        .map(|kb| kb.size(rems_from_px(12.))),
+                )
+                .on_click(|_, window, cx| {
+                    window.dispatch_action(branch_picker::CreateBranchFrom.boxed_clone(), cx);
+                })
+        };
+
+        match &self.state {
             PickerState::List => {
                 let selected_entry = self.matches.get(self.selected_index);
+                let new_branch_selected = matches!(selected_entry, Some(Entry::NewBranch { .. }));
+                let branch_selected = matches!(selected_entry, Some(Entry::Branch { .. }));
 
                 let branch_from_default_button = self
                     .default_branch
                     .as_ref()
-                    .filter(|_| matches!(selected_entry, Some(Entry::NewBranch { .. })))
+                    .filter(|_| new_branch_selected)
                     .map(|default_branch| {
                         let button_label = format!("Create New From: {default_branch}");
 
@@ -1151,6 +1864,22 @@ impl PickerDelegate for BranchListDelegate {
 
                 let delete_and_select_btns = h_flex()
                     .gap_1()
+                    .when(branch_selected, |this| {
+                        this.child(
+                            Button::new("manage-branch", "Manage…")
+                                .key_binding(
+                                    KeyBinding::for_action_in(
+                                        &menu::SecondaryConfirm,
+                                        &focus_handle,
+                                        cx,
+                                    )
+                                    .map(|kb| kb.size(rems_from_px(12.))),
+                                )
+                                .on_click(cx.listener(|this, _, window, cx| {
+                                    this.delegate.confirm(true, window, cx);
+                                })),
+                        )
+                    })
                     .child(
                         Button::new("delete-branch", "Delete")
                             .key_binding(
@@ -1180,26 +1909,27 @@ impl PickerDelegate for BranchListDelegate {
                 Some(
                     footer_container()
                         .map(|this| {
-                            if branch_from_default_button.is_some() {
-                                this.justify_end().when_some(
-                                    branch_from_default_button,
-                                    |this, button| {
-                                        this.child(button).child(
-                                            Button::new("create", "Create")
-                                                .key_binding(
-                                                    KeyBinding::for_action_in(
-                                                        &menu::Confirm,
-                                                        &focus_handle,
-                                                        cx,
-                                                    )
-                                                    .map(|kb| kb.size(rems_from_px(12.))),
+                            if new_branch_selected {
+                                this.justify_end()
+                                    .gap_1()
+                                    .child(create_from_button())
+                                    .when_some(branch_from_default_button, |this, button| {
+                                        this.child(button)
+                                    })
+                                    .child(
+                                        Button::new("create", "Create")
+                                            .key_binding(
+                                                KeyBinding::for_action_in(
+                                                    &menu::Confirm,
+                                                    &focus_handle,
+                                                    cx,
                                                 )
-                                                .on_click(cx.listener(|this, _, window, cx| {
-                                                    this.delegate.confirm(false, window, cx);
-                                                })),
-                                        )
-                                    },
-                                )
+                                                .map(|kb| kb.size(rems_from_px(12.))),
+                                            )
+                                            .on_click(cx.listener(|this, _, window, cx| {
+                                                this.delegate.confirm(false, window, cx);
+                                            })),
+                                    )
                             } else {
                                 this.justify_between()
                                     .child({
@@ -1241,43 +1971,103 @@ impl PickerDelegate for BranchListDelegate {
 
                         Button::new("branch-from-default", button_label)
                             .key_binding(
-                                KeyBinding::for_action_in(
-                                    &menu::SecondaryConfirm,
-                                    &focus_handle,
-                                    cx,
-                                )
-                                .map(|kb| kb.size(rems_from_px(12.))),
+                                KeyBinding::for_action_in(
+                                    &menu::SecondaryConfirm,
+                                    &focus_handle,
+                                    cx,
+                                )
+                                .map(|kb| kb.size(rems_from_px(12.))),
+                            )
+                            .on_click(cx.listener(|this, _, window, cx| {
+                                this.delegate.confirm(true, window, cx);
+                            }))
+                    });
+
+                Some(
+                    footer_container()
+                        .gap_1()
+                        .justify_end()
+                        .child(create_from_button())
+                        .when_some(branch_from_default_button, |this, button| {
+                            this.child(button)
+                        })
+                        .child(
+                            Button::new("branch-from-default", "Create")
+                                .key_binding(
+                                    KeyBinding::for_action_in(&menu::Confirm, &focus_handle, cx)
+                                        .map(|kb| kb.size(rems_from_px(12.))),
+                                )
+                                .on_click(cx.listener(|this, _, window, cx| {
+                                    this.delegate.confirm(false, window, cx);
+                                })),
+                        )
+                        .into_any_element(),
+                )
+            }
+            PickerState::CreateRemote(_) => Some(
+                footer_container()
+                    .justify_end()
+                    .child(
+                        Button::new("branch-from-default", "Confirm")
+                            .key_binding(
+                                KeyBinding::for_action_in(&menu::Confirm, &focus_handle, cx)
+                                    .map(|kb| kb.size(rems_from_px(12.))),
                             )
                             .on_click(cx.listener(|this, _, window, cx| {
-                                this.delegate.confirm(true, window, cx);
+                                this.delegate.confirm(false, window, cx);
                             }))
-                    });
+                            .disabled(self.last_query.is_empty()),
+                    )
+                    .into_any_element(),
+            ),
+            PickerState::ChooseBase(_) => {
+                let selected_branch = matches!(
+                    self.matches.get(self.selected_index),
+                    Some(Entry::Base {
+                        base: BranchBase::Branch(_),
+                        ..
+                    })
+                );
 
                 Some(
                     footer_container()
                         .gap_1()
                         .justify_end()
-                        .when_some(branch_from_default_button, |this, button| {
-                            this.child(button)
+                        .when(selected_branch, |this| {
+                            this.child(
+                                Button::new("create-and-track", "Create and Track")
+                                    .key_binding(
+                                        KeyBinding::for_action_in(
+                                            &menu::SecondaryConfirm,
+                                            &focus_handle,
+                                            cx,
+                                        )
+                                        .map(|kb| kb.size(rems_from_px(12.))),
+                                    )
+                                    .on_click(cx.listener(|this, _, window, cx| {
+                                        this.delegate.confirm(true, window, cx);
+                                    })),
+                            )
                         })
                         .child(
-                            Button::new("branch-from-default", "Create")
+                            Button::new("create-from-base", "Create")
                                 .key_binding(
                                     KeyBinding::for_action_in(&menu::Confirm, &focus_handle, cx)
                                         .map(|kb| kb.size(rems_from_px(12.))),
                                 )
                                 .on_click(cx.listener(|this, _, window, cx| {
                                     this.delegate.confirm(false, window, cx);
-                                })),
+                                }))
+                                .disabled(self.matches.is_empty()),
                         )
                         .into_any_element(),
                 )
             }
-            PickerState::CreateRemote(_) => Some(
+            PickerState::ChooseUpstream(_) => Some(
                 footer_container()
                     .justify_end()
                     .child(
-                        Button::new("branch-from-default", "Confirm")
+                        Button::new("track-upstream", "Track")
                             .key_binding(
                                 KeyBinding::for_action_in(&menu::Confirm, &focus_handle, cx)
                                     .map(|kb| kb.size(rems_from_px(12.))),
@@ -1285,11 +2075,27 @@ impl PickerDelegate for BranchListDelegate {
                             .on_click(cx.listener(|this, _, window, cx| {
                                 this.delegate.confirm(false, window, cx);
                             }))
-                            .disabled(self.last_query.is_empty()),
+                            .disabled(self.matches.is_empty()),
+                    )
+                    .into_any_element(),
+            ),
+            PickerState::NameTrackingBranch(_) => Some(
+                footer_container()
+                    .justify_end()
+                    .child(
+                        Button::new("create-tracking-branch", "Create and Track")
+                            .key_binding(
+                                KeyBinding::for_action_in(&menu::Confirm, &focus_handle, cx)
+                                    .map(|kb| kb.size(rems_from_px(12.))),
+                            )
+                            .on_click(cx.listener(|this, _, window, cx| {
+                                this.delegate.confirm(false, window, cx);
+                            }))
+                            .disabled(self.matches.is_empty()),
                     )
                     .into_any_element(),
             ),
-            PickerState::NewRemote => None,
+            PickerState::NewRemote | PickerState::ManageBranch(_) => None,
         }
     }
 }
@@ -1829,6 +2635,408 @@ mod tests {
         );
     }
 
+    #[gpui::test]
+    async fn test_create_branch_from_chosen_base(test_cx: &mut TestAppContext) {
+        init_test(test_cx);
+        let fs = FakeFs::new(test_cx.executor());
+        fs.insert_tree(path!("/dir"), json!({ ".git": {}, "file.txt": "text" }))
+            .await;
+        fs.with_git_state(path!("/dir/.git").as_ref(), true, |state| {
+            state.branches.insert("main".to_string());
+            state.branches.insert("origin/main".to_string());
+        })
+        .unwrap();
+        let project = Project::test(fs.clone(), [path!("/dir").as_ref()], test_cx).await;
+        let repository = test_cx.read(|cx| project.read(cx).active_repository(cx));
+
+        let branches = vec![
+            create_test_branch("main", true, None, Some(1000)),
+            create_test_branch("main", false, Some("origin"), Some(900)),
+        ];
+        let (branch_list, mut ctx) = init_branch_list_test(repository, branches, test_cx).await;
+        let cx = &mut ctx;
+        branch_list.update(cx, |branch_list, cx| {
+            branch_list.picker.update(cx, |picker, _| {
+                picker.delegate.tags = vec!["v1.0".into()];
+            })
+        });
+
+        branch_list
+            .update_in(cx, |branch_list, window, cx| {
+                branch_list.picker.update(cx, |picker, cx| {
+                    picker
+                        .delegate
+                        .update_matches("topic".to_string(), window, cx)
+                })
+            })
+            .await;
+        branch_list.update_in(cx, |branch_list, window, cx| {
+            branch_list.handle_create_branch_from(&CreateBranchFrom, window, cx);
+        });
+        cx.run_until_parked();
+
+        branch_list.update(cx, |branch_list, cx| {
+            let picker = branch_list.picker.read(cx);
+            assert!(matches!(
+                &picker.delegate.state,
+                PickerState::ChooseBase(name) if name == "topic"
+            ));
+            let bases = picker
+                .delegate
+                .matches
+                .iter()
+                .map(Entry::name)
+                .collect::<Vec<_>>();
+            assert_eq!(bases, ["main", "origin/main", "v1.0"]);
+        });
+
+        // A commit SHA that matches nothing else is offered as a base.
+        branch_list
+            .update_in(cx, |branch_list, window, cx| {
+                branch_list.picker.update(cx, |picker, cx| {
+                    picker
+                        .delegate
+                        .update_matches("deadbeef".to_string(), window, cx)
+                })
+            })
+            .await;
+        branch_list.update(cx, |branch_list, cx| {
+            let matches = &branch_list.picker.read(cx).delegate.matches;
+            assert!(matches!(
+                matches.last(),
+                Some(Entry::Base {
+                    base: BranchBase::Commit(sha),
+                    ..
+                }) if sha == "deadbeef"
+            ));
+        });
+
+        branch_list
+            .update_in(cx, |branch_list, window, cx| {
+                branch_list.picker.update(cx, |picker, cx| {
+                    picker
+                        .delegate
+                        .update_matches("origin/main".to_string(), window, cx)
+                })
+            })
+            .await;
+        branch_list.update_in(cx, |branch_list, window, cx| {
+            branch_list.picker.update(cx, |picker, cx| {
+                let ix = picker
+                    .delegate
+                    .matches
+                    .iter()
+                    .position(|entry| entry.name() == "origin/main")
+                    .unwrap();
+                picker.delegate.set_selected_index(ix, window, cx);
+                picker.delegate.confirm(true, window, cx);
+            })
+        });
+        cx.run_until_parked();
+
+        let (base, upstream) = fs
+            .with_git_state(path!("/dir/.git").as_ref(), false, |state| {
+                (
+                    state.branch_bases.get("topic").cloned(),
+                    state.branch_upstreams.get("topic").cloned(),
+                )
+            })
+            .unwrap();
+        assert_eq!(base.as_deref(), Some("origin/main"));
+        assert_eq!(upstream.as_deref(), Some("origin/main"));
+    }
+
+    #[gpui::test]
+    async fn test_branches_listed_before_are_shown_immediately(cx: &mut TestAppContext) {
+        init_test(cx);
+        let fs = FakeFs::new(cx.executor());
+        fs.insert_tree(path!("/dir"), json!({ ".git": {}, "file.txt": "text" }))
+            .await;
+        fs.with_git_state(path!("/dir/.git").as_ref(), true, |state| {
+            state.branches.insert("main".to_string());
+            state.branches.insert("feature".to_string());
+            state.current_branch_name = Some("main".to_string());
+            state
+                .branch_upstreams
+                .insert("feature".to_string(), "origin/feature".to_string());
+        })
+        .unwrap();
+        let project = Project::test(fs.clone(), [path!("/dir").as_ref()], cx).await;
+        let repository = cx
+            .read(|cx| project.read(cx).active_repository(cx))
+            .unwrap();
+        repository
+            .update(cx, |repository, _| repository.branches())
+            .await
+            .unwrap()
+            .unwrap();
+
+        let window_handle =
+            cx.add_window(|window, cx| MultiWorkspace::test_new(project.clone(), window, cx));
+        let workspace = window_handle
+            .read_with(cx, |multi_workspace, _| multi_workspace.workspace().clone())
+            .unwrap();
+        let branch_list = window_handle
+            .update(cx, |_, window, cx| {
+                cx.new(|cx| {
+                    BranchList::new(
+                        workspace.downgrade(),
+                        Some(repository.clone()),
+                        BranchListStyle::Modal,
+                        rems(34.),
+                        window,
+                        cx,
+                    )
+                })
+            })
+            .unwrap();
+
+        // The branches are there before they're listed again, along with what they track.
+        branch_list.read_with(cx, |branch_list, cx| {
+            let all_branches = branch_list.picker.read(cx).delegate.all_branches.clone();
+            let all_branches = all_branches.expect("cached branches should be shown");
+            assert_eq!(all_branches[0].name(), "main");
+            let feature = all_branches
+                .iter()
+                .find(|branch| branch.name() == "feature")
+                .unwrap();
+            assert_eq!(
+                feature
+                    .upstream
+                    .as_ref()
+                    .and_then(|upstream| upstream.stripped_ref_name()),
+                Some("origin/feature")
+            );
+        });
+    }
+
+    #[gpui::test]
+    async fn test_manage_branch_upstream(test_cx: &mut TestAppContext) {
+        init_test(test_cx);
+        let fs = FakeFs::new(test_cx.executor());
+        fs.insert_tree(path!("/dir"), json!({ ".git": {}, "file.txt": "text" }))
+            .await;
+        fs.with_git_state(path!("/dir/.git").as_ref(), true, |state| {
+            state.branches.insert("main".to_string());
+            state.branches.insert("feature".to_string());
+            state.branches.insert("origin/feature".to_string());
+        })
+        .unwrap();
+        let project = Project::test(fs.clone(), [path!("/dir").as_ref()], test_cx).await;
+        let repository = test_cx.read(|cx| project.read(cx).active_repository(cx));
+
+        let branches = vec![
+            create_test_branch("main", true, None, Some(1000)),
+            create_test_branch("feature", false, None, Some(900)),
+            create_test_branch("feature", false, Some("origin"), Some(800)),
+        ];
+        let (branch_list, mut ctx) = init_branch_list_test(repository, branches, test_cx).await;
+        let cx = &mut ctx;
+        update_branch_list_matches_with_empty_query(&branch_list, cx).await;
+
+        branch_list.update_in(cx, |branch_list, window, cx| {
+            branch_list.picker.update(cx, |picker, cx| {
+                let ix = picker
+                    .delegate
+                    .matches
+                    .iter()
+                    .position(|entry| entry.name() == "feature")
+                    .unwrap();
+                picker.delegate.set_selected_index(ix, window, cx);
+                picker.delegate.confirm(true, window, cx);
+            })
+        });
+        cx.run_until_parked();
+
+        branch_list.update_in(cx, |branch_list, window, cx| {
+            branch_list.picker.update(cx, |picker, cx| {
+                let actions = picker
+                    .delegate
+                    .matches
+                    .iter()
+                    .map(Entry::name)
+                    .collect::<Vec<_>>();
+                assert_eq!(actions, ["Rename…", "Delete", "Set Upstream…"]);
+                picker.delegate.set_selected_index(2, window, cx);
+                picker.delegate.confirm(false, window, cx);
+            })
+        });
+        cx.run_until_parked();
+
+        // The branch itself isn't offered, and neither are tags or commits.
+        branch_list.update_in(cx, |branch_list, window, cx| {
+            branch_list.picker.update(cx, |picker, cx| {
+                assert!(matches!(
+                    &picker.delegate.state,
+                    PickerState::ChooseUpstream(branch) if branch.name() == "feature"
+                ));
+                let candidates = picker
+                    .delegate
+                    .matches
+                    .iter()
+                    .map(Entry::name)
+                    .collect::<Vec<_>>();
+                assert_eq!(candidates, ["main", "origin/feature"]);
+                picker.delegate.set_selected_index(1, window, cx);
+                picker.delegate.confirm(false, window, cx);
+            })
+        });
+        cx.run_until_parked();
+
+        let upstream = fs
+            .with_git_state(path!("/dir/.git").as_ref(), false, |state| {
+                state.branch_upstreams.get("feature").cloned()
+            })
+            .unwrap();
+        assert_eq!(upstream.as_deref(), Some("origin/feature"));
+    }
+
+    #[gpui::test]
+    async fn test_force_delete_unmerged_branch(test_cx: &mut TestAppContext) {
+        init_test(test_cx);
+        let fs = FakeFs::new(test_cx.executor());
+        fs.insert_tree(path!("/dir"), json!({ ".git": {}, "file.txt": "text" }))
+            .await;
+        fs.with_git_state(path!("/dir/.git").as_ref(), true, |state| {
+            state.branches.insert("main".to_string());
+            state.branches.insert("experiment".to_string());
+            state.unmerged_branches.insert("experiment".to_string());
+        })
+        .unwrap();
+        let project = Project::test(fs.clone(), [path!("/dir").as_ref()], test_cx).await;
+        let repository = test_cx.read(|cx| project.read(cx).active_repository(cx));
+
+        let branches = vec![
+            create_test_branch("main", true, None, Some(1000)),
+            create_test_branch("experiment", false, None, Some(900)),
+        ];
+        let (branch_list, mut ctx) = init_branch_list_test(repository, branches, test_cx).await;
+        let cx = &mut ctx;
+        update_branch_list_matches_with_empty_query(&branch_list, cx).await;
+
+        let has_experiment = || {
+            fs.with_git_state(path!("/dir/.git").as_ref(), false, |state| {
+                state.branches.contains("experiment")
+            })
+            .unwrap()
+        };
+
+        // Cancelling the force delete keeps the branch.
+        branch_list.update_in(cx, |branch_list, window, cx| {
+            branch_list.picker.update(cx, |picker, cx| {
+                picker.delegate.delete_at(1, window, cx);
+            })
+        });
+        cx.run_until_parked();
+        cx.simulate_prompt_answer("Cancel");
+        cx.run_until_parked();
+        assert!(has_experiment());
+
+        branch_list.update_in(cx, |branch_list, window, cx| {
+            branch_list.picker.update(cx, |picker, cx| {
+                picker.delegate.delete_at(1, window, cx);
+            })
+        });
+        cx.run_until_parked();
+        cx.simulate_prompt_answer("Force Delete");
+        cx.run_until_parked();
+        assert!(!has_experiment());
+        branch_list.update(cx, |branch_list, cx| {
+            let matches = &branch_list.picker.read(cx).delegate.matches;
+            assert_eq!(
+                matches.iter().map(Entry::name).collect::<Vec<_>>(),
+                ["main"]
+            );
+        });
+    }
+
+    #[gpui::test]
+    async fn test_check_out_remote_branch_as_tracking_branch(test_cx: &mut TestAppContext) {
+        init_test(test_cx);
+        let fs = FakeFs::new(test_cx.executor());
+        fs.insert_tree(path!("/dir"), json!({ ".git": {}, "file.txt": "text" }))
+            .await;
+        fs.with_git_state(path!("/dir/.git").as_ref(), true, |state| {
+            state.branches.insert("main".to_string());
+            state.branches.insert("feature".to_string());
+            state.branches.insert("origin/feature".to_string());
+            state.branches.insert("origin/fix".to_string());
+        })
+        .unwrap();
+        let project = Project::test(fs.clone(), [path!("/dir").as_ref()], test_cx).await;
+        let repository = test_cx.read(|cx| project.read(cx).active_repository(cx));
+
+        let branches = vec![
+            create_test_branch("main", true, None, Some(1000)),
+            create_test_branch("feature", false, None, Some(900)),
+            create_test_branch("feature", false, Some("origin"), Some(800)),
+            create_test_branch("fix", false, Some("origin"), Some(700)),
+        ];
+        let (branch_list, mut ctx) = init_branch_list_test(repository, branches, test_cx).await;
+        let cx = &mut ctx;
+        let select_and_confirm = |name: &str, cx: &mut VisualTestContext| {
+            branch_list.update_in(cx, |branch_list, window, cx| {
+                branch_list.picker.update(cx, |picker, cx| {
+                    let ix = picker
+                        .delegate
+                        .matches
+                        .iter()
+                        .position(|entry| entry.name() == name)
+                        .unwrap();
+                    picker.delegate.set_selected_index(ix, window, cx);
+                    picker.delegate.confirm(false, window, cx);
+                })
+            });
+            cx.run_until_parked();
+        };
+        let tracking = |name: &str| {
+            fs.with_git_state(path!("/dir/.git").as_ref(), false, |state| {
+                (
+                    state.branch_bases.get(name).cloned(),
+                    state.branch_upstreams.get(name).cloned(),
+                )
+            })
+            .unwrap()
+        };
+
+        // Without a local branch of the same name, one is created to track the remote branch.
+        update_branch_list_matches_with_empty_query(&branch_list, cx).await;
+        select_and_confirm("origin/fix", cx);
+        assert_eq!(
+            tracking("fix"),
+            (
+                Some("origin/fix".to_string()),
+                Some("origin/fix".to_string())
+            )
+        );
+
+        // A local branch that doesn't track it already has the name, so another one is chosen.
+        update_branch_list_matches_with_empty_query(&branch_list, cx).await;
+        select_and_confirm("origin/feature", cx);
+        cx.simulate_prompt_answer("Use Another Name…");
+        cx.run_until_parked();
+        branch_list.update(cx, |branch_list, cx| {
+            let delegate = &branch_list.picker.read(cx).delegate;
+            assert!(matches!(
+                &delegate.state,
+                PickerState::NameTrackingBranch(branch) if branch.name() == "origin/feature"
+            ));
+            assert_eq!(
+                delegate.matches.iter().map(Entry::name).collect::<Vec<_>>(),
+                ["origin-feature"]
+            );
+        });
+        select_and_confirm("origin-feature", cx);
+        assert_eq!(
+            tracking("origin-feature"),
+            (
+                Some("origin/feature".to_string()),
+                Some("origin/feature".to_string())
+            )
+        );
+        assert_eq!(tracking("feature"), (None, None));
+    }
+
     #[gpui::test]
     async fn test_remote_url_detection_https(cx: &mut TestAppContext) {
         init_test(cx);
@@ -1,8 +1,18 @@
-use gpui::{App, Context, WeakEntity, Window};
+use futures::channel::oneshot;
+use git::{
+    remote_progress::{RemoteProgress, RemoteProgressDelegate},
+    repository::REMOTE_CANCELLED_BY_USER,
+};
+use gpui::{
+    App, Context, DismissEvent, EventEmitter, FocusHandle, Focusable, IntoElement, ParentElement,
+    Render, Styled, WeakEntity, Window,
+};
 use notifications::status_toast::{StatusToast, ToastIcon};
+use project::RemoveOptions;
 use std::sync::Arc;
-use ui::{Color, IconName, SharedString};
+use ui::{Color, IconName, Label, LabelSize, ProgressBar, SharedString, v_flex};
 use util::ResultExt;
+use workspace::notifications::{Notification, NotificationFrame, NotificationId, SuppressEvent};
 use workspace::{self, Workspace};
 
 pub fn clone_and_open(
@@ -33,18 +43,72 @@ pub fn clone_and_open(
                 .unwrap_or("repository")
                 .to_owned();
 
+            let notification_id =
+                NotificationId::composite::<CloneProgressNotification>(SharedString::from(
+                    destination_dir
+                        .join(&repo_name)
+                        .to_string_lossy()
+                        .into_owned(),
+                ));
             let clone_task = workspace
                 .update(cx, |workspace, cx| {
                     let fs = workspace.app_state().fs.clone();
                     let destination_dir = destination_dir.clone();
                     let repo_url = repo_url.clone();
+                    let (progress_tx, progress_rx) = smol::channel::unbounded();
+                    let (cancel_tx, cancel_rx) = oneshot::channel();
+                    let notification = cx.new(|cx| CloneProgressNotification {
+                        focus_handle: cx.focus_handle(),
+                        repo_name: repo_name.clone().into(),
+                        progress: None,
+                        cancel_tx: Some(cancel_tx),
+                    });
+                    workspace
+                        .show_notification(notification_id.clone(), cx, |_| notification.clone());
+                    cx.spawn(async move |_, cx| {
+                        // The channel closes once the clone is done reporting.
+                        while let Ok(progress) = progress_rx.recv().await {
+                            notification.update(cx, |notification, cx| {
+                                notification.progress = Some(progress);
+                                cx.notify();
+                            });
+                        }
+                    })
+                    .detach();
+                    let clone_dir = destination_dir.join(&repo_name);
                     cx.spawn(async move |_workspace, _cx| {
-                        fs.git_clone(&repo_url, destination_dir.as_path()).await
+                        let existed = fs.is_dir(&clone_dir).await;
+                        let progress = RemoteProgressDelegate::new(progress_tx, cancel_rx);
+                        let result = fs
+                            .git_clone(&repo_url, destination_dir.as_path(), Some(progress))
+                            .await;
+                        // Cancelling kills git before it can clean up the half-cloned repository.
+                        if result.as_ref().is_err_and(is_cancelled) && !existed {
+                            fs.remove_dir(
+                                &clone_dir,
+                                RemoveOptions {
+                                    recursive: true,
+                                    ignore_if_not_exists: true,
+                                },
+                            )
+                            .await
+                            .log_err();
+                        }
+                        result
                     })
                 })
                 .ok()?;
 
-            if let Err(error) = clone_task.await {
+            let result = clone_task.await;
+            workspace
+                .update(cx, |workspace, cx| {
+                    workspace.dismiss_notification(&notification_id, cx)
+                })
+                .ok();
+            if let Err(error) = result {
+                if is_cancelled(&error) {
+                    return None;
+                }
                 workspace
                     .update(cx, |workspace, cx| {
                         let toast = StatusToast::new(error.to_string(), cx, |this, _| {
@@ -153,3 +217,67 @@ pub fn clone_and_open(
         })
         .detach();
 }
+
+fn is_cancelled(error: &anyhow::Error) -> bool {
+    error.root_cause().to_string() == REMOTE_CANCELLED_BY_USER
+}
+
+/// Shows how far a clone has come, and cancels it when closed.
+struct CloneProgressNotification {
+    focus_handle: FocusHandle,
+    repo_name: SharedString,
+    progress: Option<RemoteProgress>,
+    cancel_tx: Option<oneshot::Sender<()>>,
+}
+
+impl Focusable for CloneProgressNotification {
+    fn focus_handle(&self, _cx: &App) -> FocusHandle {
+        self.focus_handle.clone()
+    }
+}
+
+impl EventEmitter<DismissEvent> for CloneProgressNotification {}
+impl EventEmitter<SuppressEvent> for CloneProgressNotification {}
+impl Notification for CloneProgressNotification {}
+
+impl Render for CloneProgressNotification {
+    fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let (status, percent) = match &self.progress {
+            Some(progress) => (
+                format!("{} {}%", progress.phase, progress.percent),
+                progress.percent as f32,
+            ),
+            None => ("Connecting…".to_string(), 0.),
+        };
+        let transfer = self
+            .progress
+            .as_ref()
+            .and_then(|progress| progress.transfer.clone());
+
+        NotificationFrame::new()
+            .with_title(Some(format!("Cloning {}", self.repo_name)))
+            .show_suppress_button(false)
+            .on_close(cx.listener(|this, _, _, cx| {
+                if let Some(cancel_tx) = this.cancel_tx.take() {
+                    cancel_tx.send(()).ok();
+                }
+                cx.emit(DismissEvent);
+            }))
+            .with_content(
+                v_flex()
+                    .gap_1()
+                    .w_80()
+                    .child(
+                        Label::new(status)
+                            .size(LabelSize::Small)
+                            .color(Color::Muted),
+                    )
+                    .child(ProgressBar::new("clone-progress", percent, 100., cx))
+                    .children(transfer.map(|transfer| {
+                        Label::new(transfer)
+                            .size(LabelSize::XSmall)
+                            .color(Color::Muted)
+                    })),
+            )
+    }
+}
@@ -0,0 +1,78 @@
+use std::path::Path;
+
+use anyhow::Result;
+use db::kvp::KeyValueStore;
+use serde::{Deserialize, Serialize};
+
+const COMMIT_MESSAGES_NAMESPACE: &str = "git_commit_messages";
+
+/// Number of commit messages remembered for each repository.
+pub(crate) const MAX_COMMIT_MESSAGE_HISTORY: usize = 20;
+
+/// The unsent draft and the recently committed messages of a repository, persisted across
+/// restarts.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub(crate) struct CommitMessages {
+    #[serde(default)]
+    pub draft: Option<String>,
+    /// Most recent first.
+    #[serde(default)]
+    pub history: Vec<String>,
+}
+
+impl CommitMessages {
+    pub(crate) fn load(kvp: &KeyValueStore, work_directory: &Path) -> Result<Self> {
+        let Some(serialized) = kvp
+            .scoped(COMMIT_MESSAGES_NAMESPACE)
+            .read(&work_directory.to_string_lossy())?
+        else {
+            return Ok(Self::default());
+        };
+        Ok(serde_json::from_str(&serialized)?)
+    }
+
+    pub(crate) async fn save(&self, kvp: &KeyValueStore, work_directory: &Path) -> Result<()> {
+        kvp.scoped(COMMIT_MESSAGES_NAMESPACE)
+            .write(
+                work_directory.to_string_lossy().into_owned(),
+                serde_json::to_string(self)?,
+            )
+            .await
+    }
+
+    /// Records a committed message, moving it to the front if it was committed before.
+    pub(crate) fn push_history(&mut self, message: String) {
+        self.history.retain(|previous| *previous != message);
+        self.history.insert(0, message);
+        self.history.truncate(MAX_COMMIT_MESSAGE_HISTORY);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_push_history() {
+        let mut messages = CommitMessages::default();
+        for index in 0..MAX_COMMIT_MESSAGE_HISTORY + 5 {
+            messages.push_history(format!("Message {index}"));
+        }
+        assert_eq!(messages.history.len(), MAX_COMMIT_MESSAGE_HISTORY);
+        assert_eq!(
+            messages.history[0],
+            format!("Message {}", MAX_COMMIT_MESSAGE_HISTORY + 4)
+        );
+
+        messages.push_history("Message 10".to_string());
+        assert_eq!(messages.history[0], "Message 10");
+        assert_eq!(
+            messages
+                .history
+                .iter()
+                .filter(|message| *message == "Message 10")
+                .count(),
+            1
+        );
+    }
+}
@@ -0,0 +1,267 @@
+use git::repository::RepoPath;
+use itertools::Itertools as _;
+
+/// Files listed by name in a suggested subject before they are summarized as a count.
+const MAX_NAMED_FILES: usize = 3;
+
+/// Suggested subjects longer than this are shortened by counting files instead of naming them.
+const MAX_SUBJECT_LENGTH: usize = 72;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum ChangeKind {
+    Created,
+    Deleted,
+    Modified,
+}
+
+/// One part of a suggested subject, such as "update 2 files" or "move main.rs to src".
+struct Clause {
+    verb: &'static str,
+    paths: Vec<String>,
+    /// Appended after the file names or count, e.g. " to src".
+    suffix: String,
+}
+
+impl Clause {
+    fn describe(&self, max_named_files: usize) -> (String, bool) {
+        if self.paths.len() <= max_named_files {
+            (
+                format!("{} {}{}", self.verb, self.paths.join(", "), self.suffix),
+                false,
+            )
+        } else {
+            let noun = if self.paths.len() == 1 {
+                "file"
+            } else {
+                "files"
+            };
+            (
+                format!("{} {} {noun}{}", self.verb, self.paths.len(), self.suffix),
+                true,
+            )
+        }
+    }
+}
+
+/// Summarizes changed files into a commit subject such as "Update 4 files in crates/git_ui" or
+/// "Move main.rs to src", without consulting a language model.
+///
+/// A deleted and a created file with the same name are reported as a move, and a single deleted
+/// and created file in the same directory as a rename.
+pub(crate) fn summarize_changes(changes: &[(RepoPath, ChangeKind)]) -> Option<String> {
+    if changes.is_empty() {
+        return None;
+    }
+
+    let mut created = Vec::new();
+    let mut deleted = Vec::new();
+    let mut modified = Vec::new();
+    for (path, kind) in changes {
+        match kind {
+            ChangeKind::Created => created.push(path),
+            ChangeKind::Deleted => deleted.push(path),
+            ChangeKind::Modified => modified.push(path),
+        }
+    }
+
+    let mut moves = Vec::new();
+    deleted.retain(|deleted_path| {
+        let Some(index) = created.iter().position(|created_path| {
+            created_path.file_name() == deleted_path.file_name()
+                && created_path.parent() != deleted_path.parent()
+        }) else {
+            return true;
+        };
+        moves.push((*deleted_path, created.remove(index)));
+        false
+    });
+
+    let mut clauses = Vec::new();
+    clauses.push(Clause {
+        verb: "update",
+        paths: file_names(&modified),
+        suffix: String::new(),
+    });
+    clauses.push(Clause {
+        verb: "create",
+        paths: file_names(&created),
+        suffix: String::new(),
+    });
+    clauses.push(Clause {
+        verb: "delete",
+        paths: file_names(&deleted),
+        suffix: String::new(),
+    });
+    if let [created_path] = created.as_slice()
+        && let [deleted_path] = deleted.as_slice()
+        && created_path.parent() == deleted_path.parent()
+    {
+        clauses.truncate(1);
+        clauses.push(Clause {
+            verb: "rename",
+            paths: file_names(&deleted),
+            suffix: format!(" to {}", file_name(created_path)),
+        });
+    }
+    let move_destinations = moves
+        .iter()
+        .map(|(_, destination)| directory_name(destination))
+        .unique()
+        .collect::<Vec<_>>();
+    clauses.push(Clause {
+        verb: "move",
+        paths: moves.iter().map(|(source, _)| file_name(source)).collect(),
+        suffix: match move_destinations.as_slice() {
+            [destination] => format!(" to {destination}"),
+            _ => String::new(),
+        },
+    });
+    clauses.retain(|clause| !clause.paths.is_empty());
+    // The predominant kind of change leads the subject; ties keep the order above.
+    clauses.sort_by_key(|clause| std::cmp::Reverse(clause.paths.len()));
+
+    let directory = common_directory(changes.iter().map(|(path, _)| path));
+    let subject = compose_subject(&clauses, directory.as_deref(), MAX_NAMED_FILES);
+    if subject.len() > MAX_SUBJECT_LENGTH {
+        Some(compose_subject(&clauses, directory.as_deref(), 0))
+    } else {
+        Some(subject)
+    }
+}
+
+fn compose_subject(clauses: &[Clause], directory: Option<&str>, max_named_files: usize) -> String {
+    let mut any_counted = false;
+    let parts = clauses
+        .iter()
+        .map(|clause| {
+            let (description, counted) = clause.describe(max_named_files);
+            any_counted |= counted;
+            description
+        })
+        .collect::<Vec<_>>();
+
+    let mut subject = match parts.as_slice() {
+        [] => String::new(),
+        [only] => only.clone(),
+        [rest @ .., last] => format!("{} and {last}", rest.join(", ")),
+    };
+    if any_counted && let Some(directory) = directory {
+        subject.push_str(" in ");
+        subject.push_str(directory);
+    }
+
+    let mut characters = subject.chars();
+    match characters.next() {
+        Some(first) => first.to_uppercase().chain(characters).collect(),
+        None => subject,
+    }
+}
+
+fn file_name(path: &RepoPath) -> String {
+    path.file_name().unwrap_or_default().to_string()
+}
+
+fn file_names(paths: &[&RepoPath]) -> Vec<String> {
+    paths.iter().map(|path| file_name(path)).collect()
+}
+
+fn directory_name(path: &RepoPath) -> String {
+    match path.parent() {
+        Some(parent) if !parent.is_empty() => parent.components().join("/"),
+        _ => "the repository root".to_string(),
+    }
+}
+
+/// The deepest directory containing every path, if it is not the repository root.
+fn common_directory<'a>(paths: impl IntoIterator<Item = &'a RepoPath>) -> Option<String> {
+    let mut common: Option<Vec<&str>> = None;
+    for path in paths {
+        let directories = path
+            .parent()
+            .map(|parent| parent.components().collect::<Vec<_>>())
+            .unwrap_or_default();
+        common = Some(match common {
+            None => directories,
+            Some(common) => common
+                .into_iter()
+                .zip(directories)
+                .take_while(|(left, right)| left == right)
+                .map(|(directory, _)| directory)
+                .collect(),
+        });
+    }
+    common
+        .filter(|common| !common.is_empty())
+        .map(|common| common.join("/"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn summarize(changes: &[(&str, ChangeKind)]) -> Option<String> {
+        let changes = changes
+            .iter()
+            .map(|(path, kind)| (RepoPath::new(path).unwrap(), *kind))
+            .collect::<Vec<_>>();
+        summarize_changes(&changes)
+    }
+
+    #[test]
+    fn test_summarize_changes() {
+        use ChangeKind::*;
+
+        assert_eq!(summarize(&[]), None);
+        assert_eq!(
+            summarize(&[("src/main.rs", Modified)]).as_deref(),
+            Some("Update main.rs")
+        );
+        assert_eq!(
+            summarize(&[
+                ("src/lib.rs", Modified),
+                ("src/main.rs", Modified),
+                ("src/cli.rs", Created),
+            ])
+            .as_deref(),
+            Some("Update lib.rs, main.rs and create cli.rs")
+        );
+        assert_eq!(
+            summarize(&[
+                ("crates/git_ui/src/a.rs", Modified),
+                ("crates/git_ui/src/b.rs", Modified),
+                ("crates/git_ui/src/c.rs", Modified),
+                ("crates/git_ui/src/d.rs", Modified),
+                ("crates/git_ui/Cargo.toml", Deleted),
+            ])
+            .as_deref(),
+            Some("Update 4 files and delete Cargo.toml in crates/git_ui")
+        );
+        assert_eq!(
+            summarize(&[("src/old/foo.rs", Deleted), ("src/new/foo.rs", Created)]).as_deref(),
+            Some("Move foo.rs to src/new")
+        );
+        assert_eq!(
+            summarize(&[("src/foo/a.rs", Deleted), ("a.rs", Created)]).as_deref(),
+            Some("Move a.rs to the repository root")
+        );
+        assert_eq!(
+            summarize(&[("src/a.rs", Deleted), ("src/b.rs", Created)]).as_deref(),
+            Some("Rename a.rs to b.rs")
+        );
+    }
+
+    #[test]
+    fn test_long_summaries_count_files() {
+        use ChangeKind::*;
+
+        assert_eq!(
+            summarize(&[
+                ("docs/a_very_long_file_name.md", Modified),
+                ("docs/another_very_long_file_name.md", Modified),
+                ("docs/yet_another_long_file_name.md", Created),
+            ])
+            .as_deref(),
+            Some("Update 2 files and create 1 file in docs")
+        );
+    }
+}
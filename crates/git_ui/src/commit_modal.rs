@@ -1,7 +1,7 @@
 use crate::branch_picker::{self, BranchList};
 use crate::git_panel::{GitPanel, commit_message_editor, panel_editor_style};
 use git::repository::CommitOptions;
-use git::{Amend, Commit, GenerateCommitMessage, Signoff};
+use git::{Amend, Commit, GenerateCommitMessage, Signoff, SuggestCommitMessage};
 use panel::panel_button;
 use project::DisableAiSettings;
 use settings::Settings;
@@ -334,6 +334,7 @@ impl CommitModal {
             commit_label,
             co_authors,
             generate_commit_message,
+            subject_length_warning,
             active_repo,
             is_amend_pending,
             is_signoff_enabled,
@@ -343,6 +344,7 @@ impl CommitModal {
             let title = git_panel.commit_button_title();
             let co_authors = git_panel.render_co_authors(cx);
             let generate_commit_message = git_panel.render_generate_commit_message_button(cx);
+            let subject_length_warning = git_panel.render_subject_length_warning(cx);
             let active_repo = git_panel.active_repository.clone();
             let is_amend_pending = git_panel.amend_pending();
             let is_signoff_enabled = git_panel.signoff_enabled();
@@ -352,6 +354,7 @@ impl CommitModal {
                 title,
                 co_authors,
                 generate_commit_message,
+                subject_length_warning,
                 active_repo,
                 is_amend_pending,
                 is_signoff_enabled,
@@ -424,7 +427,8 @@ impl CommitModal {
                             .child(branch_picker),
                     )
                     .children(generate_commit_message)
-                    .children(co_authors),
+                    .children(co_authors)
+                    .children(subject_length_warning),
             )
             .child(div().flex_1())
             .child(
@@ -453,6 +457,12 @@ impl CommitModal {
                                     CommitOptions {
                                         amend: is_amend_pending,
                                         signoff: is_signoff_enabled,
+                                        only_paths: if is_amend_pending {
+                                            Vec::new()
+                                        } else {
+                                            git_panel.marked_paths()
+                                        },
+                                        no_verify: false,
                                     },
                                     window,
                                     cx,
@@ -551,6 +561,11 @@ impl Render for CommitModal {
                     })
                 }))
             })
+            .on_action(cx.listener(|this, _: &SuggestCommitMessage, window, cx| {
+                this.git_panel.update(cx, |panel, cx| {
+                    panel.fill_suggested_commit_message(window, cx);
+                })
+            }))
             .on_action(
                 cx.listener(|this, _: &zed_actions::git::Branch, window, cx| {
                     this.toggle_branch_selector(window, cx);
@@ -0,0 +1,461 @@
+//! CommitRangeView steps through the commits of a range such as `main..feature`, showing the
+//! diff of one commit at a time.
+
+use anyhow::{Context as _, Result};
+use editor::Editor;
+use git::repository::CommitSummary;
+use gpui::{
+    App, DismissEvent, Entity, EventEmitter, FocusHandle, Focusable, Task, WeakEntity, actions,
+};
+use menu::{Cancel, Confirm};
+use project::{Project, git_store::Repository};
+use ui::{Tooltip, prelude::*};
+use util::ResultExt as _;
+use workspace::{Item, ModalView, Workspace, item::ItemEvent, notifications::DetachAndPromptErr};
+
+use crate::{commit_view::CommitView, repository_settings, resolve_active_repository};
+
+actions!(
+    git,
+    [
+        /// Shows the next commit of the range being reviewed.
+        NextCommitInRange,
+        /// Shows the previous commit of the range being reviewed.
+        PreviousCommitInRange,
+    ]
+);
+
+pub fn register(workspace: &mut Workspace) {
+    workspace.register_action(|workspace, _: &git::ReviewCommitRange, window, cx| {
+        let Some(repository) = resolve_active_repository(workspace, cx) else {
+            return;
+        };
+        let default_base_branch = repository_settings(workspace.project(), &repository, cx)
+            .git
+            .default_base_branch
+            .clone();
+        let weak_workspace = workspace.weak_handle();
+        workspace.toggle_modal(window, cx, |window, cx| {
+            CommitRangeModal::new(repository, weak_workspace, default_base_branch, window, cx)
+        });
+    });
+}
+
+/// Splits a range such as `main..feature` into its base and head, either of which is HEAD when
+/// left out, as with `git log`.
+pub fn parse_commit_range(range: &str) -> Result<(String, String)> {
+    let range = range.trim();
+    anyhow::ensure!(
+        !range.contains("..."),
+        "{range} is a symmetric difference, use a range like main..feature instead"
+    );
+    let (base, head) = range
+        .split_once("..")
+        .with_context(|| format!("{range:?} isn't a range like main..feature"))?;
+    let revision_or_head = |revision: &str| match revision.trim() {
+        "" => "HEAD".to_string(),
+        revision => revision.to_string(),
+    };
+    Ok((revision_or_head(base), revision_or_head(head)))
+}
+
+/// Asks for the range of commits to review.
+struct CommitRangeModal {
+    editor: Entity<Editor>,
+    repository: Entity<Repository>,
+    workspace: WeakEntity<Workspace>,
+    error: Option<SharedString>,
+}
+
+impl CommitRangeModal {
+    fn new(
+        repository: Entity<Repository>,
+        workspace: WeakEntity<Workspace>,
+        default_base_branch: Option<String>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Self {
+        let editor = cx.new(|cx| {
+            let mut editor = Editor::single_line(window, cx);
+            editor.set_placeholder_text("A range of commits, e.g. main..feature", window, cx);
+            if let Some(base) = default_base_branch {
+                editor.set_text(format!("{base}..HEAD"), window, cx);
+            }
+            editor
+        });
+        Self {
+            editor,
+            repository,
+            workspace,
+            error: None,
+        }
+    }
+
+    fn cancel(&mut self, _: &Cancel, _window: &mut Window, cx: &mut Context<Self>) {
+        cx.emit(DismissEvent);
+    }
+
+    fn confirm(&mut self, _: &Confirm, window: &mut Window, cx: &mut Context<Self>) {
+        let (base, head) = match parse_commit_range(&self.editor.read(cx).text(cx)) {
+            Ok(range) => range,
+            Err(error) => {
+                self.error = Some(error.to_string().into());
+                cx.notify();
+                return;
+            }
+        };
+        let Some(workspace) = self.workspace.upgrade() else {
+            return;
+        };
+        let repository = self.repository.clone();
+        workspace
+            .update(cx, |workspace, cx| {
+                CommitRangeView::open(repository, base, head, workspace, window, cx)
+            })
+            .detach_and_prompt_err("Failed to review commits", window, cx, |_, _, _| None);
+        cx.emit(DismissEvent);
+    }
+}
+
+impl EventEmitter<DismissEvent> for CommitRangeModal {}
+impl ModalView for CommitRangeModal {}
+impl Focusable for CommitRangeModal {
+    fn focus_handle(&self, cx: &App) -> FocusHandle {
+        self.editor.focus_handle(cx)
+    }
+}
+
+impl Render for CommitRangeModal {
+    fn render(&mut self, _: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        v_flex()
+            .key_context("CommitRangeModal")
+            .on_action(cx.listener(Self::cancel))
+            .on_action(cx.listener(Self::confirm))
+            .elevation_2(cx)
+            .w(rems(34.))
+            .child(
+                h_flex()
+                    .px_3()
+                    .pt_2()
+                    .pb_1()
+                    .w_full()
+                    .gap_1p5()
+                    .child(Icon::new(IconName::GitCommit).size(IconSize::XSmall))
+                    .child(Headline::new("Review Commits").size(HeadlineSize::XSmall)),
+            )
+            .child(
+                v_flex()
+                    .px_3()
+                    .pb_3()
+                    .w_full()
+                    .gap_1()
+                    .child(self.editor.clone())
+                    .when_some(self.error.clone(), |this, error| {
+                        this.child(Label::new(error).size(LabelSize::Small).color(Color::Error))
+                    }),
+            )
+    }
+}
+
+/// Shows the commits that `head` has and `base` doesn't, one after another, oldest first.
+pub struct CommitRangeView {
+    repository: Entity<Repository>,
+    project: Entity<Project>,
+    base: SharedString,
+    head: SharedString,
+    /// Oldest first, in the order they were made.
+    commits: Vec<CommitSummary>,
+    selected_index: usize,
+    commit_view: Option<Entity<CommitView>>,
+    error: Option<SharedString>,
+    focus_handle: FocusHandle,
+    _load_commit: Task<()>,
+}
+
+impl CommitRangeView {
+    /// Opens the first of the commits in `base..head`.
+    pub fn open(
+        repository: Entity<Repository>,
+        base: String,
+        head: String,
+        workspace: &Workspace,
+        window: &mut Window,
+        cx: &mut App,
+    ) -> Task<Result<Entity<Self>>> {
+        let commits = repository.update(cx, |repository, _| {
+            repository.commits_between(base.clone(), head.clone())
+        });
+        let project = workspace.project().clone();
+        let workspace = workspace.weak_handle();
+
+        window.spawn(cx, async move |cx| {
+            let mut commits = commits.await??;
+            anyhow::ensure!(
+                !commits.is_empty(),
+                "{head} has no commits that {base} lacks"
+            );
+            commits.reverse();
+            workspace.update_in(cx, |workspace, window, cx| {
+                let view = cx.new(|cx| {
+                    Self::new(
+                        repository,
+                        project,
+                        base.into(),
+                        head.into(),
+                        commits,
+                        window,
+                        cx,
+                    )
+                });
+                workspace.active_pane().update(cx, |pane, cx| {
+                    pane.add_item(Box::new(view.clone()), true, true, None, window, cx);
+                });
+                view
+            })
+        })
+    }
+
+    fn new(
+        repository: Entity<Repository>,
+        project: Entity<Project>,
+        base: SharedString,
+        head: SharedString,
+        commits: Vec<CommitSummary>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Self {
+        let mut this = Self {
+            repository,
+            project,
+            base,
+            head,
+            commits,
+            selected_index: 0,
+            commit_view: None,
+            error: None,
+            focus_handle: cx.focus_handle(),
+            _load_commit: Task::ready(()),
+        };
+        this.select_commit(0, window, cx);
+        this
+    }
+
+    fn title(&self) -> SharedString {
+        format!("{}..{}", self.base, self.head).into()
+    }
+
+    fn select_commit(&mut self, index: usize, window: &mut Window, cx: &mut Context<Self>) {
+        let Some(commit) = self.commits.get(index) else {
+            return;
+        };
+        self.selected_index = index;
+        let sha = commit.sha.to_string();
+        let (details, diff) = self.repository.update(cx, |repository, _| {
+            (
+                repository.show(sha.clone()),
+                repository.load_commit_diff(sha),
+            )
+        });
+        self._load_commit = cx.spawn_in(window, async move |this, cx| {
+            let result = async { anyhow::Ok((details.await??, diff.await??)) }.await;
+            this.update_in(cx, |this, window, cx| {
+                match result {
+                    Ok((details, diff)) => {
+                        let had_focus = this.focus_handle.contains_focused(window, cx);
+                        let commit_view = cx.new(|cx| {
+                            CommitView::new(
+                                details,
+                                diff,
+                                this.repository.clone(),
+                                this.project.clone(),
+                                None,
+                                window,
+                                cx,
+                            )
+                        });
+                        if had_focus {
+                            window.focus(&commit_view.focus_handle(cx), cx);
+                        }
+                        this.commit_view = Some(commit_view);
+                        this.error = None;
+                    }
+                    Err(error) => {
+                        this.commit_view = None;
+                        this.error = Some(error.to_string().into());
+                    }
+                }
+                cx.notify();
+            })
+            .log_err();
+        });
+        cx.notify();
+    }
+
+    fn next_commit(&mut self, _: &NextCommitInRange, window: &mut Window, cx: &mut Context<Self>) {
+        if self.selected_index + 1 < self.commits.len() {
+            self.select_commit(self.selected_index + 1, window, cx);
+        }
+    }
+
+    fn previous_commit(
+        &mut self,
+        _: &PreviousCommitInRange,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        if let Some(index) = self.selected_index.checked_sub(1) {
+            self.select_commit(index, window, cx);
+        }
+    }
+
+    fn render_navigation(&self, cx: &mut Context<Self>) -> impl IntoElement {
+        let commit = self.commits.get(self.selected_index);
+        let focus_handle = self.focus_handle.clone();
+        h_flex()
+            .px_2()
+            .py_1()
+            .gap_2()
+            .justify_between()
+            .border_b_1()
+            .border_color(cx.theme().colors().border_variant)
+            .child(
+                h_flex()
+                    .min_w_0()
+                    .gap_2()
+                    .child(Label::new(self.title()).size(LabelSize::Small))
+                    .child(
+                        Label::new(format!(
+                            "Commit {} of {}",
+                            self.selected_index + 1,
+                            self.commits.len()
+                        ))
+                        .size(LabelSize::Small)
+                        .color(Color::Muted),
+                    )
+                    .when_some(commit, |this, commit| {
+                        this.child(
+                            Label::new(commit.subject.clone())
+                                .size(LabelSize::Small)
+                                .color(Color::Muted)
+                                .truncate(),
+                        )
+                    }),
+            )
+            .child(
+                h_flex()
+                    .gap_0p5()
+                    .child(
+                        IconButton::new("previous-commit", IconName::ArrowLeft)
+                            .icon_size(IconSize::Small)
+                            .disabled(self.selected_index == 0)
+                            .tooltip(Tooltip::for_action_title_in(
+                                "Previous Commit",
+                                &PreviousCommitInRange,
+                                &focus_handle,
+                            ))
+                            .on_click(cx.listener(|this, _, window, cx| {
+                                this.previous_commit(&PreviousCommitInRange, window, cx)
+                            })),
+                    )
+                    .child(
+                        IconButton::new("next-commit", IconName::ArrowRight)
+                            .icon_size(IconSize::Small)
+                            .disabled(self.selected_index + 1 >= self.commits.len())
+                            .tooltip(Tooltip::for_action_title_in(
+                                "Next Commit",
+                                &NextCommitInRange,
+                                &focus_handle,
+                            ))
+                            .on_click(cx.listener(|this, _, window, cx| {
+                                this.next_commit(&NextCommitInRange, window, cx)
+                            })),
+                    ),
+            )
+    }
+}
+
+impl EventEmitter<ItemEvent> for CommitRangeView {}
+
+impl Focusable for CommitRangeView {
+    fn focus_handle(&self, cx: &App) -> FocusHandle {
+        match &self.commit_view {
+            Some(commit_view) => commit_view.focus_handle(cx),
+            None => self.focus_handle.clone(),
+        }
+    }
+}
+
+impl Item for CommitRangeView {
+    type Event = ItemEvent;
+
+    fn to_item_events(event: &Self::Event, f: &mut dyn FnMut(ItemEvent)) {
+        f(*event)
+    }
+
+    fn tab_content_text(&self, _detail: usize, _cx: &App) -> SharedString {
+        format!("Review {}", self.title()).into()
+    }
+
+    fn tab_tooltip_text(&self, _cx: &App) -> Option<SharedString> {
+        Some(format!("Commits in {} that aren't in {}", self.head, self.base).into())
+    }
+
+    fn tab_icon(&self, _window: &Window, _cx: &App) -> Option<Icon> {
+        Some(Icon::new(IconName::GitCommit).color(Color::Muted))
+    }
+
+    fn telemetry_event_text(&self) -> Option<&'static str> {
+        Some("Commit Range Review Opened")
+    }
+}
+
+impl Render for CommitRangeView {
+    fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        v_flex()
+            .key_context("CommitRangeView")
+            .track_focus(&self.focus_handle)
+            .on_action(cx.listener(Self::next_commit))
+            .on_action(cx.listener(Self::previous_commit))
+            .size_full()
+            .bg(cx.theme().colors().editor_background)
+            .child(self.render_navigation(cx))
+            .map(|this| match (&self.commit_view, &self.error) {
+                (Some(commit_view), _) => this.child(div().flex_1().child(commit_view.clone())),
+                (None, Some(error)) => this.child(
+                    h_flex()
+                        .flex_1()
+                        .justify_center()
+                        .child(Label::new(error.clone()).color(Color::Error)),
+                ),
+                (None, None) => this.child(
+                    h_flex()
+                        .flex_1()
+                        .justify_center()
+                        .child(Label::new("Loading commit…").color(Color::Muted)),
+                ),
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_commit_range() {
+        assert_eq!(
+            parse_commit_range("main..feature").unwrap(),
+            ("main".to_string(), "feature".to_string())
+        );
+        assert_eq!(
+            parse_commit_range(" origin/main.. ").unwrap(),
+            ("origin/main".to_string(), "HEAD".to_string())
+        );
+        assert_eq!(
+            parse_commit_range("..feature").unwrap(),
+            ("HEAD".to_string(), "feature".to_string())
+        );
+        assert!(parse_commit_range("feature").is_err());
+        assert!(parse_commit_range("main...feature").is_err());
+    }
+}
@@ -1,12 +1,15 @@
-use crate::commit_view::CommitView;
+use crate::{commit_view::CommitView, git_panel};
 use editor::hover_markdown_style;
 use futures::Future;
 use git::blame::BlameEntry;
-use git::repository::CommitSummary;
-use git::{GitRemote, commit::ParsedCommitMessage};
+use git::repository::{CommitFileStatus, CommitSignature, CommitSummary, SignatureStatus};
+use git::{
+    GitHostingProviderRegistry, GitRemote,
+    commit::{CommitOverview, ParsedCommitMessage},
+};
 use gpui::{
-    AbsoluteLength, App, Asset, Element, Entity, MouseButton, ParentElement, Render, ScrollHandle,
-    StatefulInteractiveElement, WeakEntity, prelude::*,
+    AbsoluteLength, App, Asset, ClipboardItem, Element, Entity, MouseButton, ParentElement, Render,
+    ScrollHandle, StatefulInteractiveElement, Task, WeakEntity, prelude::*,
 };
 use markdown::{Markdown, MarkdownElement};
 use project::git_store::Repository;
@@ -14,7 +17,8 @@ use settings::Settings;
 use std::hash::Hash;
 use theme::ThemeSettings;
 use time::{OffsetDateTime, UtcOffset};
-use ui::{Avatar, CopyButton, Divider, prelude::*, tooltip_container};
+use ui::{Avatar, Chip, CopyButton, Disclosure, Divider, Tooltip, prelude::*, tooltip_container};
+use util::ResultExt;
 use workspace::Workspace;
 
 #[derive(Clone, Debug)]
@@ -151,12 +155,105 @@ impl Asset for CommitAvatarAsset {
     }
 }
 
+/// Renders the result of verifying a commit's signature, with the signer in the tooltip.
+pub(crate) fn render_signature_badge(signature: &CommitSignature) -> Chip {
+    let (label, color) = match signature.status {
+        SignatureStatus::Verified => ("Verified", Color::Success),
+        SignatureStatus::Unverified => ("Unverified", Color::Error),
+        SignatureStatus::UnknownKey => ("Unknown key", Color::Warning),
+        SignatureStatus::Expired => ("Expired", Color::Warning),
+    };
+    let signer = signature
+        .signer
+        .as_ref()
+        .or(signature.key.as_ref())
+        .map_or_else(|| "an unknown signer".into(), |signer| signer.clone());
+    let tooltip = match signature.status {
+        SignatureStatus::UnknownKey => format!("Signed by {signer}, whose key is not available"),
+        SignatureStatus::Expired => {
+            format!("Signed by {signer}, but the signature or its key has expired")
+        }
+        _ => format!("Signed by {signer}"),
+    };
+    Chip::new(label)
+        .label_color(color)
+        .tooltip(Tooltip::text(tooltip))
+}
+
+/// A [`CommitTooltip`] for a commit known only by its SHA, shown once the commit's details are
+/// loaded.
+pub(crate) struct CommitShaTooltip {
+    commit_tooltip: Option<Entity<CommitTooltip>>,
+}
+
+impl CommitShaTooltip {
+    pub(crate) fn new(
+        sha: SharedString,
+        repository: Entity<Repository>,
+        workspace: WeakEntity<Workspace>,
+        window: &mut Window,
+        cx: &mut App,
+    ) -> Entity<Self> {
+        let remote_url = repository.read(cx).default_remote_url();
+        let details = repository.update(cx, |repository, _| repository.show(sha.to_string()));
+        cx.new(|cx| {
+            cx.spawn_in(window, async move |this, cx| {
+                let details = details.await??;
+                let provider_registry = cx
+                    .update(|_, app| GitHostingProviderRegistry::default_global(app))
+                    .ok();
+
+                let commit_details = CommitDetails {
+                    sha: details.sha.clone(),
+                    author_name: details.author_name.clone(),
+                    author_email: details.author_email.clone(),
+                    commit_time: OffsetDateTime::from_unix_timestamp(details.commit_timestamp)?,
+                    message: Some(ParsedCommitMessage::parse(
+                        details.sha.to_string(),
+                        details.message.to_string(),
+                        remote_url.as_deref(),
+                        provider_registry,
+                    )),
+                };
+
+                this.update(cx, |this: &mut CommitShaTooltip, cx| {
+                    this.commit_tooltip = Some(cx.new(move |cx| {
+                        CommitTooltip::new(commit_details, repository, workspace, cx)
+                    }));
+                    cx.notify();
+                })
+            })
+            .detach();
+
+            Self {
+                commit_tooltip: None,
+            }
+        })
+    }
+}
+
+impl Render for CommitShaTooltip {
+    fn render(&mut self, _window: &mut Window, _cx: &mut Context<Self>) -> impl IntoElement {
+        if let Some(commit_tooltip) = &self.commit_tooltip {
+            commit_tooltip.clone().into_any_element()
+        } else {
+            gpui::Empty.into_any_element()
+        }
+    }
+}
+
 pub struct CommitTooltip {
     commit: CommitDetails,
+    is_committed: bool,
+    signature: Option<CommitSignature>,
     scroll_handle: ScrollHandle,
     markdown: Entity<Markdown>,
     repository: Entity<Repository>,
     workspace: WeakEntity<Workspace>,
+    details_expanded: bool,
+    overview: Option<CommitOverview>,
+    _verify_signature: Task<()>,
+    _load_overview: Task<()>,
 }
 
 impl CommitTooltip {
@@ -208,14 +305,207 @@ impl CommitTooltip {
                 cx,
             )
         });
+        // Lines that haven't been committed yet are blamed on the all-zero SHA.
+        let is_committed = commit.sha.chars().any(|character| character != '0');
+        let verify_signature = is_committed.then(|| {
+            repository.update(cx, |repository, cx| {
+                repository.verify_commit_signatures(vec![commit.sha.clone()], cx)
+            })
+        });
+        let _verify_signature = cx.spawn(async move |this, cx| {
+            let Some(verify_signature) = verify_signature else {
+                return;
+            };
+            let Some(signatures) = verify_signature.await.log_err() else {
+                return;
+            };
+            this.update(cx, |this, cx| {
+                this.signature = signatures.get(&this.commit.sha).cloned();
+                cx.notify();
+            })
+            .ok();
+        });
         Self {
             commit,
+            is_committed,
+            signature: None,
             repository,
             workspace,
             scroll_handle: ScrollHandle::new(),
             markdown,
+            details_expanded: false,
+            overview: None,
+            _verify_signature,
+            _load_overview: Task::ready(()),
         }
     }
+
+    fn toggle_details(&mut self, cx: &mut Context<Self>) {
+        self.details_expanded = !self.details_expanded;
+        if self.details_expanded && self.overview.is_none() {
+            let overview = self.repository.update(cx, |repository, cx| {
+                repository.commit_overview(self.commit.sha.to_string(), cx)
+            });
+            self._load_overview = cx.spawn(async move |this, cx| {
+                let Some(overview) = overview.await.log_err() else {
+                    return;
+                };
+                this.update(cx, |this, cx| {
+                    this.overview = Some(overview);
+                    cx.notify();
+                })
+                .ok();
+            });
+        }
+        cx.notify();
+    }
+
+    /// The committer, parents, refs and changed files of the commit, each file with a button
+    /// that opens its diff.
+    fn render_details(&self) -> AnyElement {
+        let Some(overview) = &self.overview else {
+            return Label::new("Loading…")
+                .size(LabelSize::Small)
+                .color(Color::Muted)
+                .into_any_element();
+        };
+        let sha = self.commit.sha.to_string();
+        let repository = self.repository.downgrade();
+        let workspace = self.workspace.clone();
+
+        v_flex()
+            .gap_1p5()
+            .child(
+                h_flex()
+                    .gap_1()
+                    .child(
+                        Label::new("Committed by")
+                            .size(LabelSize::Small)
+                            .color(Color::Muted),
+                    )
+                    .child(Label::new(overview.committer_name.clone()).size(LabelSize::Small))
+                    .when(!overview.committer_email.is_empty(), |this| {
+                        this.child(
+                            Label::new(overview.committer_email.clone())
+                                .size(LabelSize::Small)
+                                .color(Color::Muted),
+                        )
+                    }),
+            )
+            .when(!overview.parents.is_empty(), |this| {
+                this.child(
+                    h_flex()
+                        .gap_1()
+                        .child(
+                            Label::new(if overview.parents.len() == 1 {
+                                "Parent"
+                            } else {
+                                "Parents"
+                            })
+                            .size(LabelSize::Small)
+                            .color(Color::Muted),
+                        )
+                        .children(overview.parents.iter().enumerate().map(|(ix, parent)| {
+                            let short_sha = parent
+                                .get(0..git::SHORT_SHA_LENGTH)
+                                .unwrap_or(parent.as_ref())
+                                .to_string();
+                            let parent = parent.to_string();
+                            let repository = repository.clone();
+                            let workspace = workspace.clone();
+                            Button::new(("commit-parent", ix), short_sha)
+                                .style(ButtonStyle::Subtle)
+                                .label_size(LabelSize::Small)
+                                .tooltip(Tooltip::text("View Parent Commit"))
+                                .on_click(move |_, window, cx| {
+                                    cx.stop_propagation();
+                                    CommitView::open(
+                                        parent.clone(),
+                                        repository.clone(),
+                                        workspace.clone(),
+                                        None,
+                                        None,
+                                        window,
+                                        cx,
+                                    );
+                                })
+                        })),
+                )
+            })
+            .when(!overview.refs.is_empty(), |this| {
+                this.child(
+                    h_flex()
+                        .gap_1()
+                        .flex_wrap()
+                        .children(overview.refs.iter().map(|name| Chip::new(name.clone()))),
+                )
+            })
+            .child(
+                v_flex()
+                    .id("commit-files")
+                    .max_h(rems(12.))
+                    .overflow_y_scroll()
+                    .children(
+                        overview
+                            .files
+                            .iter()
+                            .enumerate()
+                            .map(|(ix, (path, status))| {
+                                let (status_label, status_color) = match status {
+                                    CommitFileStatus::Added => ("A", Color::Created),
+                                    CommitFileStatus::Modified => ("M", Color::Modified),
+                                    CommitFileStatus::Deleted => ("D", Color::Deleted),
+                                };
+                                let sha = sha.clone();
+                                let path = path.clone();
+                                let repository = repository.clone();
+                                let workspace = workspace.clone();
+                                h_flex()
+                                    .gap_1p5()
+                                    .justify_between()
+                                    .child(
+                                        h_flex()
+                                            .gap_1p5()
+                                            .min_w_0()
+                                            .child(
+                                                Label::new(status_label)
+                                                    .size(LabelSize::Small)
+                                                    .color(status_color),
+                                            )
+                                            .child(
+                                                Label::new(path.as_unix_str().to_string())
+                                                    .size(LabelSize::Small)
+                                                    .truncate(),
+                                            ),
+                                    )
+                                    .child(
+                                        IconButton::new(
+                                            ("open-commit-file-diff", ix),
+                                            IconName::Diff,
+                                        )
+                                        .icon_size(IconSize::Small)
+                                        .icon_color(Color::Muted)
+                                        .tooltip(Tooltip::text("Open Diff"))
+                                        .on_click(
+                                            move |_, window, cx| {
+                                                cx.stop_propagation();
+                                                CommitView::open(
+                                                    sha.clone(),
+                                                    repository.clone(),
+                                                    workspace.clone(),
+                                                    None,
+                                                    Some(path.clone()),
+                                                    window,
+                                                    cx,
+                                                );
+                                            },
+                                        ),
+                                    )
+                            }),
+                    ),
+            )
+            .into_any_element()
+    }
 }
 
 impl Render for CommitTooltip {
@@ -226,10 +516,12 @@ impl Render for CommitTooltip {
 
         let author_email = self.commit.author_email.clone();
 
+        let signature_badge = self.signature.as_ref().map(render_signature_badge);
+
         let short_commit_id = self
             .commit
             .sha
-            .get(0..8)
+            .get(0..git::SHORT_SHA_LENGTH)
             .map(|sha| sha.to_string().into())
             .unwrap_or_else(|| self.commit.sha.clone());
         let full_sha = self.commit.sha.to_string();
@@ -257,6 +549,18 @@ impl Render for CommitTooltip {
             .message
             .as_ref()
             .and_then(|details| details.pull_request.clone());
+        let permalink = self
+            .commit
+            .message
+            .as_ref()
+            .and_then(|details| details.permalink.clone());
+
+        let details_expanded = self.details_expanded;
+        let details = (self.is_committed && details_expanded).then(|| self.render_details());
+        let toggle_details = cx.listener(|this, _, _, cx| {
+            cx.stop_propagation();
+            this.toggle_details(cx);
+        });
 
         let ui_font_size = ThemeSettings::get_global(cx).ui_font_size(cx);
         let message_max_height = window.line_height() * 12 + (ui_font_size / 0.4);
@@ -306,6 +610,7 @@ impl Render for CommitTooltip {
                                             .child(author_email),
                                     )
                                 })
+                                .children(signature_badge)
                                 .border_b_1()
                                 .border_color(cx.theme().colors().border_variant),
                         )
@@ -317,6 +622,25 @@ impl Render for CommitTooltip {
                                 .overflow_y_scroll()
                                 .track_scroll(&self.scroll_handle),
                         )
+                        .when(self.is_committed, |this| {
+                            this.child(
+                                h_flex()
+                                    .gap_1()
+                                    .child(
+                                        Disclosure::new(
+                                            "commit-details-disclosure",
+                                            details_expanded,
+                                        )
+                                        .on_click(toggle_details),
+                                    )
+                                    .child(
+                                        Label::new("Details")
+                                            .size(LabelSize::Small)
+                                            .color(Color::Muted),
+                                    ),
+                            )
+                        })
+                        .children(details)
                         .child(
                             h_flex()
                                 .text_color(cx.theme().colors().text_muted)
@@ -358,26 +682,69 @@ impl Render for CommitTooltip {
                                             .start_icon(
                                                 Icon::new(IconName::FileGit).color(Color::Muted),
                                             )
+                                            .tooltip(Tooltip::text("View Commit"))
                                             .on_click(
-                                                move |_, window, cx| {
-                                                    CommitView::open(
-                                                        commit_summary.sha.to_string(),
-                                                        repo.downgrade(),
-                                                        workspace.clone(),
-                                                        None,
-                                                        None,
-                                                        window,
-                                                        cx,
-                                                    );
-                                                    cx.stop_propagation();
+                                                {
+                                                    let repo = repo.clone();
+                                                    let workspace = workspace.clone();
+                                                    move |_, window, cx| {
+                                                        CommitView::open(
+                                                            commit_summary.sha.to_string(),
+                                                            repo.downgrade(),
+                                                            workspace.clone(),
+                                                            None,
+                                                            None,
+                                                            window,
+                                                            cx,
+                                                        );
+                                                        cx.stop_propagation();
+                                                    }
                                                 },
                                             ),
                                         )
+                                        .when(self.is_committed, |this| {
+                                            this.child(Divider::vertical()).child(
+                                                IconButton::new("revert-commit", IconName::Undo)
+                                                    .tooltip(Tooltip::text("Revert This Commit"))
+                                                    .icon_color(Color::Muted)
+                                                    .on_click({
+                                                        let sha = full_sha.clone();
+                                                        move |_, window, cx| {
+                                                            cx.stop_propagation();
+                                                            git_panel::confirm_revert_commit(
+                                                                workspace.clone(),
+                                                                repo.clone(),
+                                                                sha.clone(),
+                                                                window,
+                                                                cx,
+                                                            );
+                                                        }
+                                                    }),
+                                            )
+                                        })
                                         .child(Divider::vertical())
                                         .child(
                                             CopyButton::new("copy-commit-sha", full_sha)
                                                 .tooltip_label("Copy SHA"),
-                                        ),
+                                        )
+                                        .when_some(permalink, |this, permalink| {
+                                            this.child(
+                                                IconButton::new(
+                                                    "copy-commit-permalink",
+                                                    IconName::Link,
+                                                )
+                                                .tooltip(Tooltip::text("Copy Permalink"))
+                                                .icon_color(Color::Muted)
+                                                .on_click(move |_, _, cx| {
+                                                    cx.stop_propagation();
+                                                    cx.write_to_clipboard(
+                                                        ClipboardItem::new_string(
+                                                            permalink.to_string(),
+                                                        ),
+                                                    );
+                                                }),
+                                            )
+                                        }),
                                 ),
                         ),
                 )
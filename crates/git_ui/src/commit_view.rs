@@ -69,16 +69,16 @@ pub struct CommitView {
     remote: Option<GitRemote>,
 }
 
-struct GitBlob {
-    path: RepoPath,
-    worktree_id: WorktreeId,
-    is_deleted: bool,
-    is_binary: bool,
-    display_name: String,
+pub(crate) struct GitBlob {
+    pub(crate) path: RepoPath,
+    pub(crate) worktree_id: WorktreeId,
+    pub(crate) is_deleted: bool,
+    pub(crate) is_binary: bool,
+    pub(crate) display_name: String,
 }
 
-struct CommitDiffAddon {
-    file_statuses: HashMap<language::BufferId, FileStatus>,
+pub(crate) struct CommitDiffAddon {
+    pub(crate) file_statuses: HashMap<language::BufferId, FileStatus>,
 }
 
 impl Addon for CommitDiffAddon {
@@ -162,7 +162,7 @@ impl CommitView {
             .detach();
     }
 
-    fn new(
+    pub(crate) fn new(
         commit: CommitDetails,
         commit_diff: CommitDiff,
         repository: Entity<Repository>,
@@ -749,7 +749,7 @@ impl language::File for GitBlob {
     }
 }
 
-async fn build_buffer(
+pub(crate) async fn build_buffer(
     mut text: String,
     blob: Arc<dyn File>,
     language_registry: &Arc<language::LanguageRegistry>,
@@ -782,7 +782,7 @@ async fn build_buffer(
     Ok(buffer)
 }
 
-async fn build_buffer_diff(
+pub(crate) async fn build_buffer_diff(
     mut old_text: Option<String>,
     buffer: &Entity<Buffer>,
     language_registry: &Arc<LanguageRegistry>,
@@ -2,12 +2,13 @@ use agent_settings::AgentSettings;
 use collections::{HashMap, HashSet};
 use editor::{
     ConflictsOurs, ConflictsOursMarker, ConflictsOuter, ConflictsTheirs, ConflictsTheirsMarker,
-    Editor, EditorEvent, ExcerptId, MultiBuffer, RowHighlightOptions,
+    Editor, EditorEvent, ExcerptId, MultiBuffer, RowHighlightOptions, SelectionEffects,
     display_map::{BlockContext, BlockPlacement, BlockProperties, BlockStyle, CustomBlockId},
+    scroll::Autoscroll,
 };
 use gpui::{
-    App, Context, DismissEvent, Entity, InteractiveElement as _, ParentElement as _, Subscription,
-    Task, WeakEntity,
+    App, Context, DismissEvent, Entity, EventEmitter, Focusable as _, InteractiveElement as _,
+    ParentElement as _, Render, Subscription, Task, WeakEntity,
 };
 use language::{Anchor, Buffer, BufferId};
 use project::{
@@ -16,15 +17,19 @@ use project::{
 };
 use settings::Settings;
 use std::{cell::RefCell, ops::Range, rc::Rc, sync::Arc};
-use ui::{ActiveTheme, Divider, Element as _, Styled, Window, prelude::*};
+use ui::{ActiveTheme, Divider, Element as _, Styled, Tooltip, Window, prelude::*};
 use util::{ResultExt as _, debug_panic, maybe};
-use workspace::{Workspace, notifications::simple_message_notification::MessageNotification};
+use workspace::{
+    ItemHandle, ToolbarItemEvent, ToolbarItemLocation, ToolbarItemView, Workspace,
+    notifications::simple_message_notification::MessageNotification,
+};
 use zed_actions::agent::{
     ConflictContent, ResolveConflictedFilesWithAgent, ResolveConflictsWithAgent,
 };
 
 pub(crate) struct ConflictAddon {
     buffers: HashMap<BufferId, BufferConflicts>,
+    _action_subscriptions: Vec<Subscription>,
 }
 
 impl ConflictAddon {
@@ -33,6 +38,14 @@ impl ConflictAddon {
             .get(&buffer_id)
             .map(|entry| entry.conflict_set.clone())
     }
+
+    /// The number of conflicts whose markers remain in the editor's buffers.
+    pub(crate) fn conflict_count(&self, cx: &App) -> usize {
+        self.buffers
+            .values()
+            .map(|buffer| buffer.conflict_set.read(cx).snapshot().conflicts.len())
+            .sum()
+    }
 }
 
 struct BufferConflicts {
@@ -61,8 +74,25 @@ pub fn register_editor(editor: &mut Editor, buffer: Entity<MultiBuffer>, cx: &mu
         return;
     }
 
+    let editor_handle = cx.weak_entity();
+    let action_subscriptions = vec![
+        editor.register_action({
+            let editor_handle = editor_handle.clone();
+            move |_: &git::GoToNextConflict, window, cx| {
+                editor_handle
+                    .update(cx, |editor, cx| go_to_conflict(editor, true, window, cx))
+                    .ok();
+            }
+        }),
+        editor.register_action(move |_: &git::GoToPreviousConflict, window, cx| {
+            editor_handle
+                .update(cx, |editor, cx| go_to_conflict(editor, false, window, cx))
+                .ok();
+        }),
+    ];
     editor.register_addon(ConflictAddon {
         buffers: Default::default(),
+        _action_subscriptions: action_subscriptions,
     });
 
     let buffers = buffer.read(cx).all_buffers();
@@ -93,6 +123,60 @@ pub fn register_editor(editor: &mut Editor, buffer: Entity<MultiBuffer>, cx: &mu
     .detach();
 }
 
+/// Moves the cursor to the start of the next or previous conflict, wrapping around at either end
+/// of the editor.
+fn go_to_conflict(
+    editor: &mut Editor,
+    forward: bool,
+    window: &mut Window,
+    cx: &mut Context<Editor>,
+) {
+    let Some(addon) = editor.addon::<ConflictAddon>() else {
+        return;
+    };
+    let multibuffer = editor.buffer().read(cx);
+    let snapshot = multibuffer.snapshot(cx);
+    let mut conflict_starts = Vec::new();
+    for (buffer_id, buffer_conflicts) in &addon.buffers {
+        let excerpts = multibuffer.excerpts_for_buffer(*buffer_id, cx);
+        for conflict in buffer_conflicts
+            .conflict_set
+            .read(cx)
+            .snapshot()
+            .conflicts
+            .iter()
+        {
+            conflict_starts.extend(excerpts.iter().find_map(|(excerpt_id, _, _)| {
+                snapshot.anchor_in_excerpt(*excerpt_id, conflict.range.start)
+            }));
+        }
+    }
+    conflict_starts.sort_by(|left, right| left.cmp(right, &snapshot));
+
+    let cursor = editor.selections.newest_anchor().head();
+    let target = if forward {
+        conflict_starts
+            .iter()
+            .find(|start| start.cmp(&cursor, &snapshot).is_gt())
+            .or(conflict_starts.first())
+    } else {
+        conflict_starts
+            .iter()
+            .rev()
+            .find(|start| start.cmp(&cursor, &snapshot).is_lt())
+            .or(conflict_starts.last())
+    };
+    let Some(target) = target.copied() else {
+        return;
+    };
+    editor.change_selections(
+        SelectionEffects::scroll(Autoscroll::center()),
+        window,
+        cx,
+        |selections| selections.select_anchor_ranges([target..target]),
+    );
+}
+
 fn excerpt_for_buffer_updated(
     editor: &mut Editor,
     conflict_set: Entity<ConflictSet>,
@@ -497,6 +581,127 @@ fn render_conflict_buttons(
         .into_any()
 }
 
+/// Shows how many conflicts remain in the active editor, with buttons to move between them.
+pub struct ConflictToolbar {
+    editor: Option<WeakEntity<Editor>>,
+    location: ToolbarItemLocation,
+    _editor_subscription: Option<Subscription>,
+}
+
+impl ConflictToolbar {
+    pub fn new() -> Self {
+        Self {
+            editor: None,
+            location: ToolbarItemLocation::Hidden,
+            _editor_subscription: None,
+        }
+    }
+
+    fn conflict_count(&self, cx: &App) -> usize {
+        self.editor
+            .as_ref()
+            .and_then(|editor| editor.upgrade())
+            .and_then(|editor| Some(editor.read(cx).addon::<ConflictAddon>()?.conflict_count(cx)))
+            .unwrap_or(0)
+    }
+
+    fn desired_location(&self, cx: &App) -> ToolbarItemLocation {
+        if self.conflict_count(cx) > 0 {
+            ToolbarItemLocation::PrimaryRight
+        } else {
+            ToolbarItemLocation::Hidden
+        }
+    }
+
+    fn go_to_conflict(&self, forward: bool, window: &mut Window, cx: &mut App) {
+        let Some(editor) = self.editor.as_ref().and_then(|editor| editor.upgrade()) else {
+            return;
+        };
+        editor.update(cx, |editor, cx| {
+            go_to_conflict(editor, forward, window, cx);
+            window.focus(&editor.focus_handle(cx), cx);
+        });
+    }
+}
+
+impl EventEmitter<ToolbarItemEvent> for ConflictToolbar {}
+
+impl Render for ConflictToolbar {
+    fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let conflict_count = self.conflict_count(cx);
+        if conflict_count == 0 {
+            return div();
+        }
+        let label = if conflict_count == 1 {
+            "1 conflict remaining".to_string()
+        } else {
+            format!("{conflict_count} conflicts remaining")
+        };
+
+        div().child(
+            h_flex()
+                .gap_1()
+                .child(
+                    IconButton::new("previous-conflict", IconName::ArrowUp)
+                        .icon_size(IconSize::Small)
+                        .tooltip(Tooltip::for_action_title(
+                            "Previous Conflict",
+                            &git::GoToPreviousConflict,
+                        ))
+                        .on_click(cx.listener(|this, _, window, cx| {
+                            this.go_to_conflict(false, window, cx)
+                        })),
+                )
+                .child(
+                    Label::new(label)
+                        .size(LabelSize::Small)
+                        .color(Color::Warning),
+                )
+                .child(
+                    IconButton::new("next-conflict", IconName::ArrowDown)
+                        .icon_size(IconSize::Small)
+                        .tooltip(Tooltip::for_action_title(
+                            "Next Conflict",
+                            &git::GoToNextConflict,
+                        ))
+                        .on_click(
+                            cx.listener(|this, _, window, cx| {
+                                this.go_to_conflict(true, window, cx)
+                            }),
+                        ),
+                ),
+        )
+    }
+}
+
+impl ToolbarItemView for ConflictToolbar {
+    fn set_active_pane_item(
+        &mut self,
+        active_pane_item: Option<&dyn ItemHandle>,
+        _: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> ToolbarItemLocation {
+        let editor = active_pane_item
+            .and_then(|item| item.act_as::<Editor>(cx))
+            .filter(|editor| editor.read(cx).addon::<ConflictAddon>().is_some());
+        // Conflicts are re-parsed as the buffer changes, which re-renders the editor, so
+        // observing it is enough to keep the count current.
+        self._editor_subscription = editor.as_ref().map(|editor| {
+            cx.observe(editor, |this, _, cx| {
+                let location = this.desired_location(cx);
+                if location != this.location {
+                    this.location = location;
+                    cx.emit(ToolbarItemEvent::ChangeLocation(location));
+                }
+                cx.notify();
+            })
+        });
+        self.editor = editor.map(|editor| editor.downgrade());
+        self.location = self.desired_location(cx);
+        self.location
+    }
+}
+
 fn collect_conflicted_file_paths(project: &Project, cx: &App) -> Vec<String> {
     let git_store = project.git_store().read(cx);
     let mut paths = Vec::new();
@@ -0,0 +1,243 @@
+use std::rc::Rc;
+
+use anyhow::Result;
+use collections::BTreeSet;
+use editor::{CompletionProvider, Editor, ExcerptId};
+use git::repository::RepoPath;
+use gpui::{App, Context, Entity, Task, Window};
+use language::{Buffer, CodeLabel, ToPoint};
+use project::{
+    Completion, CompletionDisplayOptions, CompletionResponse, CompletionSource, Project,
+    lsp_store::CompletionDocumentation,
+};
+
+use crate::issue_references::IssueReferences;
+
+/// Commit types from the Conventional Commits specification, with the summary shown next to each
+/// completion.
+const COMMIT_TYPES: &[(&str, &str)] = &[
+    ("feat", "A new feature"),
+    ("fix", "A bug fix"),
+    ("docs", "Documentation only changes"),
+    (
+        "style",
+        "Formatting changes that do not affect the meaning of the code",
+    ),
+    (
+        "refactor",
+        "A code change that neither fixes a bug nor adds a feature",
+    ),
+    ("perf", "A code change that improves performance"),
+    ("test", "Adding missing tests or correcting existing tests"),
+    (
+        "build",
+        "Changes to the build system or external dependencies",
+    ),
+    ("ci", "Changes to the CI configuration files and scripts"),
+    (
+        "chore",
+        "Other changes that don't modify source or test files",
+    ),
+    ("revert", "Reverts a previous commit"),
+];
+
+/// Directories that only group packages, so the scope is taken from the directory below them.
+const CONTAINER_DIRECTORIES: &[&str] = &[
+    "crates", "packages", "apps", "libs", "modules", "plugins", "src", "lib",
+];
+
+#[derive(Debug, PartialEq, Eq)]
+enum CompletionQuery<'a> {
+    Type(&'a str),
+    Scope(&'a str),
+}
+
+/// Determines what is being typed at the end of `line_prefix`, the subject line up to the cursor.
+fn completion_query(line_prefix: &str) -> Option<CompletionQuery<'_>> {
+    match line_prefix.split_once('(') {
+        None => line_prefix
+            .chars()
+            .all(|character| character.is_ascii_alphabetic())
+            .then_some(CompletionQuery::Type(line_prefix)),
+        Some((commit_type, scope)) => {
+            let is_type = !commit_type.is_empty()
+                && commit_type
+                    .chars()
+                    .all(|character| character.is_ascii_alphabetic());
+            let is_scope = scope.chars().all(is_scope_character);
+            (is_type && is_scope).then_some(CompletionQuery::Scope(scope))
+        }
+    }
+}
+
+fn is_scope_character(character: char) -> bool {
+    character.is_ascii_alphanumeric() || matches!(character, '-' | '_' | '/' | '.')
+}
+
+/// Infers commit scopes from the changed paths: the top-level directory of each path, or the
+/// directory below it for directories like `crates/` that only contain packages.
+pub(crate) fn scopes_for_paths<'a>(paths: impl IntoIterator<Item = &'a RepoPath>) -> Vec<String> {
+    let mut scopes = BTreeSet::new();
+    for path in paths {
+        let directories = path
+            .parent()
+            .map(|parent| parent.components().collect::<Vec<_>>())
+            .unwrap_or_default();
+        let scope = match directories.as_slice() {
+            [] => continue,
+            [container, package, ..] if CONTAINER_DIRECTORIES.contains(container) => package,
+            [directory, ..] => directory,
+        };
+        scopes.insert(scope.to_string());
+    }
+    scopes.into_iter().collect()
+}
+
+/// Offers Conventional Commits types and scopes while typing the subject line of a commit message,
+/// and issues after a `#` anywhere in it.
+pub(crate) struct CommitMessageCompletionProvider {
+    project: Entity<Project>,
+    issue_references: Rc<IssueReferences>,
+}
+
+impl CommitMessageCompletionProvider {
+    pub(crate) fn new(project: Entity<Project>, issue_references: Rc<IssueReferences>) -> Self {
+        Self {
+            project,
+            issue_references,
+        }
+    }
+
+    fn changed_scopes(&self, cx: &App) -> Vec<String> {
+        let Some(repository) = self.project.read(cx).active_repository(cx) else {
+            return Vec::new();
+        };
+        let statuses = repository.read(cx).cached_status().collect::<Vec<_>>();
+        scopes_for_paths(statuses.iter().map(|entry| &entry.repo_path))
+    }
+}
+
+impl CompletionProvider for CommitMessageCompletionProvider {
+    fn completions(
+        &self,
+        _excerpt_id: ExcerptId,
+        buffer: &Entity<Buffer>,
+        buffer_position: language::Anchor,
+        _trigger: editor::CompletionContext,
+        _window: &mut Window,
+        cx: &mut Context<Editor>,
+    ) -> Task<Result<Vec<CompletionResponse>>> {
+        if let Some(completions) = self
+            .issue_references
+            .completions(buffer, buffer_position, cx)
+        {
+            return completions;
+        }
+
+        let snapshot = buffer.read(cx).snapshot();
+        let position = buffer_position.to_point(&snapshot);
+        if position.row != 0 {
+            return Task::ready(Ok(Vec::new()));
+        }
+        let line_prefix = snapshot
+            .text_for_range(language::Point::zero()..position)
+            .collect::<String>();
+
+        let (query, candidates) = match completion_query(&line_prefix) {
+            Some(CompletionQuery::Type(query)) => (
+                query,
+                COMMIT_TYPES
+                    .iter()
+                    .map(|(commit_type, description)| (commit_type.to_string(), Some(*description)))
+                    .collect::<Vec<_>>(),
+            ),
+            Some(CompletionQuery::Scope(query)) => (
+                query,
+                self.changed_scopes(cx)
+                    .into_iter()
+                    .map(|scope| (scope, None))
+                    .collect(),
+            ),
+            None => return Task::ready(Ok(Vec::new())),
+        };
+
+        let query_start = language::Point::new(0, position.column - query.len() as u32);
+        let replace_range = snapshot.anchor_before(query_start)..buffer_position;
+        let completions = candidates
+            .into_iter()
+            .map(|(text, description)| Completion {
+                replace_range: replace_range.clone(),
+                label: CodeLabel::plain(text.clone(), None),
+                new_text: text,
+                documentation: description
+                    .map(|description| CompletionDocumentation::SingleLine(description.into())),
+                source: CompletionSource::Custom,
+                icon_path: None,
+                match_start: None,
+                snippet_deduplication_key: None,
+                insert_text_mode: None,
+                confirm: None,
+            })
+            .collect();
+
+        Task::ready(Ok(vec![CompletionResponse {
+            completions,
+            display_options: CompletionDisplayOptions::default(),
+            is_incomplete: false,
+        }]))
+    }
+
+    fn is_completion_trigger(
+        &self,
+        buffer: &Entity<Buffer>,
+        position: language::Anchor,
+        text: &str,
+        _trigger_in_words: bool,
+        cx: &mut Context<Editor>,
+    ) -> bool {
+        let Some(last_character) = text.chars().last() else {
+            return false;
+        };
+        if (last_character == '#' || last_character.is_alphanumeric())
+            && self
+                .issue_references
+                .is_completion_position(buffer, position, cx)
+        {
+            return true;
+        }
+        let is_trigger_character = last_character.is_ascii_alphabetic() || last_character == '(';
+        is_trigger_character && position.to_point(&buffer.read(cx).snapshot()).row == 0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_completion_query() {
+        assert_eq!(completion_query(""), Some(CompletionQuery::Type("")));
+        assert_eq!(completion_query("fe"), Some(CompletionQuery::Type("fe")));
+        assert_eq!(completion_query("feat("), Some(CompletionQuery::Scope("")));
+        assert_eq!(
+            completion_query("fix(git_u"),
+            Some(CompletionQuery::Scope("git_u"))
+        );
+        assert_eq!(completion_query("feat: add"), None);
+        assert_eq!(completion_query("feat(ui): x"), None);
+        assert_eq!(completion_query("(ui"), None);
+    }
+
+    #[test]
+    fn test_scopes_for_paths() {
+        let paths = [
+            "crates/git_ui/src/git_panel.rs",
+            "crates/git_ui/src/commit_modal.rs",
+            "crates/editor/src/editor.rs",
+            "docs/src/git.md",
+            "README.md",
+        ]
+        .map(|path| RepoPath::new(path).unwrap());
+        assert_eq!(scopes_for_paths(&paths), ["docs", "editor", "git_ui"]);
+    }
+}
@@ -0,0 +1,318 @@
+//! The filter bar of the project diff, which narrows it down to some of its files and hunks.
+
+use anyhow::Result;
+use collections::HashSet;
+use editor::{Editor, EditorEvent};
+use git::{repository::RepoPath, status::FileStatus};
+use gpui::{Entity, EventEmitter, FocusHandle, Focusable, Subscription, Task};
+use menu::Cancel;
+use std::time::Duration;
+use ui::{Tooltip, prelude::*};
+use util::paths::{PathMatcher, PathStyle};
+
+/// How long typing in the filter bar must pause before the project diff is filtered again.
+pub(crate) const FILTER_DEBOUNCE: Duration = Duration::from_millis(200);
+
+/// A kind of change that the project diff can be narrowed down to.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum StatusFilter {
+    Staged,
+    Unstaged,
+    Untracked,
+    Conflicted,
+}
+
+impl StatusFilter {
+    pub const ALL: [StatusFilter; 4] = [
+        StatusFilter::Staged,
+        StatusFilter::Unstaged,
+        StatusFilter::Untracked,
+        StatusFilter::Conflicted,
+    ];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            StatusFilter::Staged => "Staged",
+            StatusFilter::Unstaged => "Unstaged",
+            StatusFilter::Untracked => "Untracked",
+            StatusFilter::Conflicted => "Conflicted",
+        }
+    }
+
+    /// Partially staged files are both staged and unstaged.
+    pub fn matches(self, status: FileStatus) -> bool {
+        match self {
+            StatusFilter::Staged => !status.is_conflicted() && status.staging().has_staged(),
+            StatusFilter::Unstaged => {
+                !status.is_conflicted() && !status.is_untracked() && status.staging().has_unstaged()
+            }
+            StatusFilter::Untracked => status.is_untracked(),
+            StatusFilter::Conflicted => status.is_conflicted(),
+        }
+    }
+}
+
+/// Which files and hunks of a project diff are shown. An empty filter shows everything.
+#[derive(Clone, Debug, Default)]
+pub struct DiffFilter {
+    paths: Option<PathMatcher>,
+    statuses: HashSet<StatusFilter>,
+    /// Lowercase, as hunks are searched regardless of case.
+    query: Option<String>,
+}
+
+impl DiffFilter {
+    /// Builds a filter from comma-separated path globs and a search query, failing when one of
+    /// the globs is invalid.
+    pub fn new(
+        paths: &str,
+        statuses: HashSet<StatusFilter>,
+        query: &str,
+        path_style: PathStyle,
+    ) -> Result<Self> {
+        let globs = paths
+            .split(',')
+            .map(str::trim)
+            .filter(|glob| !glob.is_empty())
+            .collect::<Vec<_>>();
+        let paths = if globs.is_empty() {
+            None
+        } else {
+            Some(PathMatcher::new(globs, path_style)?)
+        };
+        let query = query.trim();
+        Ok(Self {
+            paths,
+            statuses,
+            query: (!query.is_empty()).then(|| query.to_lowercase()),
+        })
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.paths.is_none() && self.statuses.is_empty() && self.query.is_none()
+    }
+
+    pub fn has_query(&self) -> bool {
+        self.query.is_some()
+    }
+
+    pub fn matches_file(&self, repo_path: &RepoPath, status: FileStatus) -> bool {
+        self.paths
+            .as_ref()
+            .is_none_or(|paths| paths.is_match(repo_path.as_ref()))
+            && (self.statuses.is_empty()
+                || self.statuses.iter().any(|filter| filter.matches(status)))
+    }
+
+    /// Whether the text of a hunk, old or new, contains the query.
+    pub fn matches_hunk(&self, texts: &[&str]) -> bool {
+        self.query.as_ref().is_none_or(|query| {
+            texts
+                .iter()
+                .any(|text| text.to_lowercase().contains(query.as_str()))
+        })
+    }
+}
+
+pub enum DiffFilterEvent {
+    Changed,
+    Closed,
+}
+
+/// Edits the [`DiffFilter`] of a project diff, telling it whenever the filter changes.
+pub struct DiffFilterBar {
+    pub(crate) path_editor: Entity<Editor>,
+    pub(crate) query_editor: Entity<Editor>,
+    statuses: HashSet<StatusFilter>,
+    path_style: PathStyle,
+    filter: DiffFilter,
+    has_invalid_paths: bool,
+    update_filter_task: Task<()>,
+    _subscriptions: [Subscription; 2],
+}
+
+impl DiffFilterBar {
+    pub fn new(path_style: PathStyle, window: &mut Window, cx: &mut Context<Self>) -> Self {
+        let path_editor = cx.new(|cx| {
+            let mut editor = Editor::single_line(window, cx);
+            editor.set_placeholder_text("Paths, e.g. src/**/*.rs, *.toml", window, cx);
+            editor
+        });
+        let query_editor = cx.new(|cx| {
+            let mut editor = Editor::single_line(window, cx);
+            editor.set_placeholder_text("Search changes…", window, cx);
+            editor
+        });
+        let subscriptions = [&path_editor, &query_editor].map(|editor| {
+            cx.subscribe(editor, |this, _, event: &EditorEvent, cx| {
+                if let EditorEvent::BufferEdited = event {
+                    this.update_filter_task = cx.spawn(async move |this, cx| {
+                        cx.background_executor().timer(FILTER_DEBOUNCE).await;
+                        this.update(cx, |this, cx| this.update_filter(cx)).ok();
+                    });
+                }
+            })
+        });
+        Self {
+            path_editor,
+            query_editor,
+            statuses: HashSet::default(),
+            path_style,
+            filter: DiffFilter::default(),
+            has_invalid_paths: false,
+            update_filter_task: Task::ready(()),
+            _subscriptions: subscriptions,
+        }
+    }
+
+    pub fn filter(&self) -> &DiffFilter {
+        &self.filter
+    }
+
+    pub fn toggle_status(&mut self, status: StatusFilter, cx: &mut Context<Self>) {
+        if !self.statuses.remove(&status) {
+            self.statuses.insert(status);
+        }
+        // Whatever was typed is picked up now, so there's nothing left to wait for.
+        self.update_filter_task = Task::ready(());
+        self.update_filter(cx);
+    }
+
+    fn update_filter(&mut self, cx: &mut Context<Self>) {
+        let paths = self.path_editor.read(cx).text(cx);
+        let query = self.query_editor.read(cx).text(cx);
+        match DiffFilter::new(&paths, self.statuses.clone(), &query, self.path_style) {
+            Ok(filter) => {
+                self.has_invalid_paths = false;
+                self.filter = filter;
+                cx.emit(DiffFilterEvent::Changed);
+            }
+            // Keep showing what the last valid globs matched while the paths are being typed.
+            Err(_) => self.has_invalid_paths = true,
+        }
+        cx.notify();
+    }
+
+    fn cancel(&mut self, _: &Cancel, _window: &mut Window, cx: &mut Context<Self>) {
+        cx.emit(DiffFilterEvent::Closed);
+    }
+
+    fn render_input(&self, editor: &Entity<Editor>, is_invalid: bool, cx: &App) -> Div {
+        let border_color = if is_invalid {
+            Color::Error.color(cx)
+        } else {
+            cx.theme().colors().border
+        };
+        h_flex()
+            .flex_1()
+            .min_w_32()
+            .h_7()
+            .px_2()
+            .border_1()
+            .border_color(border_color)
+            .rounded_md()
+            .child(editor.clone())
+    }
+}
+
+impl EventEmitter<DiffFilterEvent> for DiffFilterBar {}
+
+impl Focusable for DiffFilterBar {
+    fn focus_handle(&self, cx: &App) -> FocusHandle {
+        self.path_editor.focus_handle(cx)
+    }
+}
+
+impl Render for DiffFilterBar {
+    fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        h_flex()
+            .key_context("DiffFilterBar")
+            .on_action(cx.listener(Self::cancel))
+            .w_full()
+            .px_2()
+            .py_1()
+            .gap_2()
+            .border_b_1()
+            .border_color(cx.theme().colors().border_variant)
+            .child(
+                h_flex()
+                    .flex_1()
+                    .gap_1()
+                    .child(Icon::new(IconName::Filter).color(Color::Muted))
+                    .child(self.render_input(&self.path_editor, self.has_invalid_paths, cx))
+                    .child(self.render_input(&self.query_editor, false, cx)),
+            )
+            .child(
+                h_flex()
+                    .gap_0p5()
+                    .children(StatusFilter::ALL.into_iter().map(|status| {
+                        Button::new(status.label(), status.label())
+                            .label_size(LabelSize::Small)
+                            .toggle_state(self.statuses.contains(&status))
+                            .on_click(
+                                cx.listener(move |this, _, _, cx| this.toggle_status(status, cx)),
+                            )
+                    })),
+            )
+            .child(
+                IconButton::new("close-diff-filter", IconName::Close)
+                    .icon_size(IconSize::Small)
+                    .tooltip(Tooltip::text("Close Filter"))
+                    .on_click(cx.listener(|_, _, _, cx| cx.emit(DiffFilterEvent::Closed))),
+            )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use git::{
+        repository::repo_path,
+        status::{StatusCode, TrackedStatus, UnmergedStatus, UnmergedStatusCode},
+    };
+
+    fn tracked(index_status: StatusCode, worktree_status: StatusCode) -> FileStatus {
+        FileStatus::Tracked(TrackedStatus {
+            index_status,
+            worktree_status,
+        })
+    }
+
+    #[test]
+    fn test_diff_filter() {
+        let staged = tracked(StatusCode::Modified, StatusCode::Unmodified);
+        let unstaged = tracked(StatusCode::Unmodified, StatusCode::Modified);
+        let partially_staged = tracked(StatusCode::Modified, StatusCode::Modified);
+        let conflicted = FileStatus::Unmerged(UnmergedStatus {
+            first_head: UnmergedStatusCode::Updated,
+            second_head: UnmergedStatusCode::Updated,
+        });
+
+        let filter = DiffFilter::new("", HashSet::default(), "", PathStyle::Posix).unwrap();
+        assert!(filter.is_empty());
+        assert!(filter.matches_file(&repo_path("src/main.rs"), staged));
+        assert!(filter.matches_hunk(&["anything"]));
+
+        let filter = DiffFilter::new(
+            "src/**/*.rs, *.toml",
+            HashSet::from_iter([StatusFilter::Unstaged, StatusFilter::Conflicted]),
+            "",
+            PathStyle::Posix,
+        )
+        .unwrap();
+        assert!(filter.matches_file(&repo_path("src/git/status.rs"), unstaged));
+        assert!(filter.matches_file(&repo_path("Cargo.toml"), partially_staged));
+        assert!(filter.matches_file(&repo_path("src/lib.rs"), conflicted));
+        assert!(!filter.matches_file(&repo_path("src/lib.rs"), staged));
+        assert!(!filter.matches_file(&repo_path("src/lib.rs"), FileStatus::Untracked));
+        assert!(!filter.matches_file(&repo_path("docs/git.md"), unstaged));
+
+        let filter = DiffFilter::new("", HashSet::default(), " Blame ", PathStyle::Posix).unwrap();
+        assert!(filter.has_query());
+        assert!(filter.matches_hunk(&["fn render_blame()", ""]));
+        assert!(filter.matches_hunk(&["", "// BLAME"]));
+        assert!(!filter.matches_hunk(&["fn render()", "// history"]));
+
+        assert!(DiffFilter::new("src/{", HashSet::default(), "", PathStyle::Posix).is_err());
+    }
+}
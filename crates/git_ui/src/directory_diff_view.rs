@@ -0,0 +1,726 @@
+//! DirectoryDiffView lists the files that differ between two directories.
+
+use anyhow::{Context as _, Result};
+use collections::BTreeSet;
+use futures::StreamExt as _;
+use git::repository::{CommitFile, CommitFileStatus, RepoPath};
+use gpui::{
+    AnyElement, App, AsyncWindowContext, Context, Entity, EventEmitter, FocusHandle, Focusable,
+    ListSizingBehavior, Task, UniformListScrollHandle, WeakEntity, Window, uniform_list,
+};
+use language::Buffer;
+use project::{Fs, git_store::Repository, project_settings::ProjectSettings};
+use settings::Settings as _;
+use std::{
+    ops::Range,
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+use ui::{ListItem, ListItemSpacing, prelude::*};
+use util::{ResultExt as _, paths::PathExt as _};
+use workspace::{
+    Item, Workspace,
+    item::{ItemEvent, TabContentParams},
+    notifications::DetachAndPromptErr,
+};
+
+use crate::{
+    commit_view::{GitBlob, build_buffer},
+    file_diff_view::FileDiffView,
+};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DirectoryDiffStatus {
+    Added,
+    Removed,
+    Modified,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DirectoryDiffEntry {
+    /// The path of the file, relative to both compared directories.
+    pub path: PathBuf,
+    pub status: DirectoryDiffStatus,
+}
+
+enum DirectoryDiffRow {
+    Directory { path: PathBuf, depth: usize },
+    File { entry_ix: usize, depth: usize },
+}
+
+/// What a [`DirectoryDiffView`] compares.
+enum DirectoryDiffSource {
+    /// Two directories on disk.
+    Directories {
+        old_path: PathBuf,
+        new_path: PathBuf,
+    },
+    /// A directory of a repository at two revisions, with the files that differ between them in
+    /// the order of the entries.
+    Revisions {
+        repository: Entity<Repository>,
+        directory: RepoPath,
+        old_revision: SharedString,
+        new_revision: SharedString,
+        files: Vec<CommitFile>,
+    },
+}
+
+pub struct DirectoryDiffView {
+    source: DirectoryDiffSource,
+    entries: Vec<DirectoryDiffEntry>,
+    rows: Vec<DirectoryDiffRow>,
+    workspace: WeakEntity<Workspace>,
+    focus_handle: FocusHandle,
+    scroll_handle: UniformListScrollHandle,
+}
+
+impl DirectoryDiffView {
+    pub fn open(
+        old_path: PathBuf,
+        new_path: PathBuf,
+        workspace: WeakEntity<Workspace>,
+        window: &mut Window,
+        cx: &mut App,
+    ) -> Task<Result<Entity<Self>>> {
+        window.spawn(cx, async move |cx| {
+            let fs = workspace.update(cx, |workspace, cx| {
+                workspace.project().read(cx).fs().clone()
+            })?;
+            let entries = diff_directories(fs.as_ref(), &old_path, &new_path).await?;
+            let source = DirectoryDiffSource::Directories { old_path, new_path };
+            Self::add_to_workspace(source, entries, workspace, cx)
+        })
+    }
+
+    /// Opens the files of `directory` that differ between two revisions of its repository.
+    pub fn open_revisions(
+        repository: Entity<Repository>,
+        directory: RepoPath,
+        old_revision: SharedString,
+        new_revision: SharedString,
+        workspace: WeakEntity<Workspace>,
+        window: &mut Window,
+        cx: &mut App,
+    ) -> Task<Result<Entity<Self>>> {
+        let comparison = repository.update(cx, |repository, cx| {
+            repository.compare_revisions(old_revision.to_string(), new_revision.to_string(), cx)
+        });
+        window.spawn(cx, async move |cx| {
+            let mut files = comparison
+                .await?
+                .files
+                .into_iter()
+                .filter_map(|file| {
+                    let path = file.path.strip_prefix(&directory).ok()?.as_std_path();
+                    Some((path.to_path_buf(), file))
+                })
+                .collect::<Vec<_>>();
+            files.sort_by(|(path, _), (other_path, _)| path.cmp(other_path));
+            let entries = files
+                .iter()
+                .map(|(path, file)| DirectoryDiffEntry {
+                    path: path.clone(),
+                    status: match file.status() {
+                        CommitFileStatus::Added => DirectoryDiffStatus::Added,
+                        CommitFileStatus::Deleted => DirectoryDiffStatus::Removed,
+                        CommitFileStatus::Modified => DirectoryDiffStatus::Modified,
+                    },
+                })
+                .collect();
+            let source = DirectoryDiffSource::Revisions {
+                repository,
+                directory,
+                old_revision,
+                new_revision,
+                files: files.into_iter().map(|(_, file)| file).collect(),
+            };
+            Self::add_to_workspace(source, entries, workspace, cx)
+        })
+    }
+
+    fn add_to_workspace(
+        source: DirectoryDiffSource,
+        entries: Vec<DirectoryDiffEntry>,
+        workspace: WeakEntity<Workspace>,
+        cx: &mut AsyncWindowContext,
+    ) -> Result<Entity<Self>> {
+        workspace.update_in(cx, |workspace, window, cx| {
+            let diff_view =
+                cx.new(|cx| DirectoryDiffView::new(source, entries, workspace.weak_handle(), cx));
+
+            let pane = workspace.active_pane();
+            pane.update(cx, |pane, cx| {
+                pane.add_item(Box::new(diff_view.clone()), true, true, None, window, cx);
+            });
+
+            diff_view
+        })
+    }
+
+    fn new(
+        source: DirectoryDiffSource,
+        entries: Vec<DirectoryDiffEntry>,
+        workspace: WeakEntity<Workspace>,
+        cx: &mut Context<Self>,
+    ) -> Self {
+        Self {
+            source,
+            rows: build_rows(&entries),
+            entries,
+            workspace,
+            focus_handle: cx.focus_handle(),
+            scroll_handle: UniformListScrollHandle::new(),
+        }
+    }
+
+    pub fn entries(&self) -> &[DirectoryDiffEntry] {
+        &self.entries
+    }
+
+    pub fn open_entry(&self, entry_ix: usize, window: &mut Window, cx: &mut App) {
+        let Some(entry) = self.entries.get(entry_ix) else {
+            return;
+        };
+        let workspace = self.workspace.clone();
+        let task = match &self.source {
+            DirectoryDiffSource::Directories { old_path, new_path } => {
+                let old_path = old_path.join(&entry.path);
+                let new_path = new_path.join(&entry.path);
+                match entry.status {
+                    DirectoryDiffStatus::Modified => {
+                        FileDiffView::open(old_path, new_path, workspace, window, cx)
+                    }
+                    DirectoryDiffStatus::Added => window.spawn(cx, async move |cx| {
+                        open_against_empty_buffer(new_path, false, workspace, cx).await
+                    }),
+                    DirectoryDiffStatus::Removed => window.spawn(cx, async move |cx| {
+                        open_against_empty_buffer(old_path, true, workspace, cx).await
+                    }),
+                }
+            }
+            DirectoryDiffSource::Revisions {
+                repository, files, ..
+            } => {
+                let Some(file) = files.get(entry_ix) else {
+                    return;
+                };
+                let repository = repository.clone();
+                let path = file.path.clone();
+                let old_text = file.old_text.clone();
+                let new_text = file.new_text.clone();
+                let is_binary = file.is_binary;
+                window.spawn(cx, async move |cx| {
+                    anyhow::ensure!(!is_binary, "binary files can't be compared");
+                    open_revision_texts(repository, path, old_text, new_text, workspace, cx).await
+                })
+            }
+        };
+        task.detach_and_prompt_err("Failed to open diff", window, cx, |_, _, _| None);
+    }
+
+    /// The names of the compared sides, and the longer descriptions shown in the tooltip.
+    fn titles(&self) -> (String, String) {
+        match &self.source {
+            DirectoryDiffSource::Directories { old_path, new_path } => (
+                format!("{} ↔ {}", file_name(old_path), file_name(new_path)),
+                format!(
+                    "{} ↔ {}",
+                    old_path.compact().to_string_lossy(),
+                    new_path.compact().to_string_lossy()
+                ),
+            ),
+            DirectoryDiffSource::Revisions {
+                directory,
+                old_revision,
+                new_revision,
+                ..
+            } => {
+                let name = directory
+                    .file_name()
+                    .map(|name| format!("{name} @ "))
+                    .unwrap_or_default();
+                let path = if directory.is_empty() {
+                    String::new()
+                } else {
+                    format!("{} @ ", directory.as_unix_str())
+                };
+                (
+                    format!("{name}{old_revision} ↔ {new_revision}"),
+                    format!("{path}{old_revision} ↔ {new_revision}"),
+                )
+            }
+        }
+    }
+
+    fn render_rows(
+        &mut self,
+        range: Range<usize>,
+        _window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Vec<AnyElement> {
+        range
+            .filter_map(|ix| {
+                let row = self.rows.get(ix)?;
+                let item = match row {
+                    DirectoryDiffRow::Directory { path, depth } => ListItem::new(ix)
+                        .indent_level(*depth)
+                        .spacing(ListItemSpacing::Dense)
+                        .start_slot(Icon::new(IconName::Folder).color(Color::Muted))
+                        .child(Label::new(file_name(path))),
+                    DirectoryDiffRow::File { entry_ix, depth } => {
+                        let entry = self.entries.get(*entry_ix)?;
+                        let (icon, color) = match entry.status {
+                            DirectoryDiffStatus::Added => (IconName::SquarePlus, Color::Created),
+                            DirectoryDiffStatus::Removed => (IconName::SquareMinus, Color::Deleted),
+                            DirectoryDiffStatus::Modified => (IconName::SquareDot, Color::Modified),
+                        };
+                        let entry_ix = *entry_ix;
+                        ListItem::new(ix)
+                            .indent_level(*depth)
+                            .spacing(ListItemSpacing::Dense)
+                            .start_slot(Icon::new(icon).color(color))
+                            .child(Label::new(file_name(&entry.path)).color(color))
+                            .on_click(cx.listener(move |this, _, window, cx| {
+                                this.open_entry(entry_ix, window, cx);
+                            }))
+                    }
+                };
+                Some(item.into_any_element())
+            })
+            .collect()
+    }
+}
+
+fn file_name(path: &Path) -> SharedString {
+    path.file_name()
+        .map(|file_name| file_name.to_string_lossy().into_owned().into())
+        .unwrap_or_default()
+}
+
+/// Opens a diff of the file at `path` against an empty buffer, so that the whole file shows up as
+/// added or removed.
+async fn open_against_empty_buffer(
+    path: PathBuf,
+    removed: bool,
+    workspace: WeakEntity<Workspace>,
+    cx: &mut AsyncWindowContext,
+) -> Result<Entity<FileDiffView>> {
+    let project = workspace.update(cx, |workspace, _| workspace.project().clone())?;
+    let buffer = project
+        .update(cx, |project, cx| project.open_local_buffer(&path, cx))
+        .await?;
+    let empty_buffer = cx.new(|cx| Buffer::local("", cx));
+    let (old_buffer, new_buffer) = if removed {
+        (buffer, empty_buffer)
+    } else {
+        (empty_buffer, buffer)
+    };
+    cx.update(|window, cx| {
+        FileDiffView::open_buffers(
+            old_buffer,
+            new_buffer,
+            ProjectSettings::get_global(cx).git.diff.placement,
+            workspace,
+            window,
+            cx,
+        )
+    })?
+    .await
+}
+
+/// Opens a diff of a file's texts at two revisions, where `None` is a side without the file.
+async fn open_revision_texts(
+    repository: Entity<Repository>,
+    path: RepoPath,
+    old_text: Option<String>,
+    new_text: Option<String>,
+    workspace: WeakEntity<Workspace>,
+    cx: &mut AsyncWindowContext,
+) -> Result<Entity<FileDiffView>> {
+    let project = workspace.update(cx, |workspace, _| workspace.project().clone())?;
+    let languages = project.read_with(cx, |project, _| project.languages().clone());
+    let first_worktree_id = project.read_with(cx, |project, cx| {
+        project
+            .worktrees(cx)
+            .next()
+            .map(|worktree| worktree.read(cx).id())
+    });
+    let worktree_id = repository
+        .update(cx, |repository, cx| {
+            repository
+                .repo_path_to_project_path(&path, cx)
+                .map(|project_path| project_path.worktree_id)
+        })
+        .or(first_worktree_id)
+        .context("project has no worktrees")?;
+    let display_name = path.file_name().unwrap_or_default().to_string();
+    let blob = |is_deleted| {
+        Arc::new(GitBlob {
+            path: path.clone(),
+            worktree_id,
+            is_deleted,
+            is_binary: false,
+            display_name: display_name.clone(),
+        }) as Arc<dyn language::File>
+    };
+    let is_deleted = new_text.is_none();
+    let old_buffer =
+        build_buffer(old_text.unwrap_or_default(), blob(false), &languages, cx).await?;
+    let new_buffer = build_buffer(
+        new_text.unwrap_or_default(),
+        blob(is_deleted),
+        &languages,
+        cx,
+    )
+    .await?;
+    cx.update(|window, cx| {
+        FileDiffView::open_buffers(
+            old_buffer,
+            new_buffer,
+            ProjectSettings::get_global(cx).git.diff.placement,
+            workspace,
+            window,
+            cx,
+        )
+    })?
+    .await
+}
+
+pub async fn diff_directories(
+    fs: &dyn Fs,
+    old_path: &Path,
+    new_path: &Path,
+) -> Result<Vec<DirectoryDiffEntry>> {
+    let old_files = collect_files(fs, old_path).await?;
+    let new_files = collect_files(fs, new_path).await?;
+
+    let mut entries = Vec::new();
+    for path in old_files.union(&new_files) {
+        let status = match (old_files.contains(path), new_files.contains(path)) {
+            (true, false) => DirectoryDiffStatus::Removed,
+            (false, true) => DirectoryDiffStatus::Added,
+            _ => {
+                let old_content = fs.load_bytes(&old_path.join(path)).await?;
+                let new_content = fs.load_bytes(&new_path.join(path)).await?;
+                if old_content == new_content {
+                    continue;
+                }
+                DirectoryDiffStatus::Modified
+            }
+        };
+        entries.push(DirectoryDiffEntry {
+            path: path.clone(),
+            status,
+        });
+    }
+    Ok(entries)
+}
+
+/// Returns the paths of all files below `root`, relative to it.
+async fn collect_files(fs: &dyn Fs, root: &Path) -> Result<BTreeSet<PathBuf>> {
+    let mut files = BTreeSet::default();
+    let mut pending_directories = vec![root.to_path_buf()];
+    while let Some(directory) = pending_directories.pop() {
+        let mut children = fs.read_dir(&directory).await?;
+        while let Some(child) = children.next().await {
+            let child = child?;
+            if child.file_name().is_some_and(|name| name == ".git") {
+                continue;
+            }
+            let Some(metadata) = fs.metadata(&child).await? else {
+                continue;
+            };
+            if metadata.is_dir {
+                // A link to a directory could lead back to one of its ancestors.
+                if !metadata.is_symlink {
+                    pending_directories.push(child);
+                }
+            } else if let Some(relative_path) = child.strip_prefix(root).log_err() {
+                files.insert(relative_path.to_path_buf());
+            }
+        }
+    }
+    Ok(files)
+}
+
+/// Lays out the entries as a tree, inserting a row for each directory that contains changes.
+fn build_rows(entries: &[DirectoryDiffEntry]) -> Vec<DirectoryDiffRow> {
+    let mut rows = Vec::new();
+    let mut current_directories: Vec<PathBuf> = Vec::new();
+    for (entry_ix, entry) in entries.iter().enumerate() {
+        let ancestors = entry
+            .path
+            .ancestors()
+            .skip(1)
+            .filter(|ancestor| !ancestor.as_os_str().is_empty())
+            .map(Path::to_path_buf)
+            .collect::<Vec<_>>();
+        let depth = ancestors.len();
+        let shared_depth = ancestors
+            .iter()
+            .rev()
+            .zip(&current_directories)
+            .take_while(|(ancestor, directory)| ancestor == directory)
+            .count();
+        current_directories.truncate(shared_depth);
+        for (directory_depth, ancestor) in
+            ancestors.into_iter().rev().enumerate().skip(shared_depth)
+        {
+            current_directories.push(ancestor.clone());
+            rows.push(DirectoryDiffRow::Directory {
+                path: ancestor,
+                depth: directory_depth,
+            });
+        }
+        rows.push(DirectoryDiffRow::File { entry_ix, depth });
+    }
+    rows
+}
+
+impl EventEmitter<ItemEvent> for DirectoryDiffView {}
+
+impl Focusable for DirectoryDiffView {
+    fn focus_handle(&self, _: &App) -> FocusHandle {
+        self.focus_handle.clone()
+    }
+}
+
+impl Item for DirectoryDiffView {
+    type Event = ItemEvent;
+
+    fn tab_icon(&self, _window: &Window, _cx: &App) -> Option<Icon> {
+        Some(Icon::new(IconName::Diff).color(Color::Muted))
+    }
+
+    fn tab_content(&self, params: TabContentParams, _window: &Window, cx: &App) -> AnyElement {
+        Label::new(self.tab_content_text(params.detail.unwrap_or_default(), cx))
+            .color(if params.selected {
+                Color::Default
+            } else {
+                Color::Muted
+            })
+            .into_any_element()
+    }
+
+    fn tab_content_text(&self, _detail: usize, _cx: &App) -> SharedString {
+        self.titles().0.into()
+    }
+
+    fn tab_tooltip_text(&self, _cx: &App) -> Option<SharedString> {
+        Some(self.titles().1.into())
+    }
+
+    fn to_item_events(event: &ItemEvent, f: &mut dyn FnMut(ItemEvent)) {
+        f(*event)
+    }
+
+    fn telemetry_event_text(&self) -> Option<&'static str> {
+        Some("Directory Diff View Opened")
+    }
+}
+
+impl Render for DirectoryDiffView {
+    fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let content = if self.rows.is_empty() {
+            h_flex()
+                .size_full()
+                .justify_center()
+                .child(Label::new("No differences").color(Color::Muted))
+                .into_any_element()
+        } else {
+            uniform_list(
+                "directory-diff-entries",
+                self.rows.len(),
+                cx.processor(Self::render_rows),
+            )
+            .with_sizing_behavior(ListSizingBehavior::Auto)
+            .track_scroll(&self.scroll_handle)
+            .size_full()
+            .into_any_element()
+        };
+
+        v_flex()
+            .key_context("DirectoryDiffView")
+            .track_focus(&self.focus_handle)
+            .size_full()
+            .p_2()
+            .bg(cx.theme().colors().editor_background)
+            .child(content)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use git::repository::repo_path;
+    use gpui::{TestAppContext, VisualTestContext};
+    use project::{FakeFs, Project};
+    use serde_json::json;
+    use settings::SettingsStore;
+    use util::path;
+    use workspace::MultiWorkspace;
+
+    #[gpui::test]
+    async fn test_diff_directories(cx: &mut TestAppContext) {
+        let fs = FakeFs::new(cx.executor());
+        fs.insert_tree(
+            path!("/test"),
+            json!({
+                "old": {
+                    "same.txt": "same\n",
+                    "changed.txt": "old\n",
+                    "removed.txt": "removed\n",
+                    "nested": {
+                        "changed.rs": "fn old() {}\n",
+                    },
+                },
+                "new": {
+                    "same.txt": "same\n",
+                    "changed.txt": "new\n",
+                    "added.txt": "added\n",
+                    "nested": {
+                        "changed.rs": "fn new() {}\n",
+                    },
+                },
+            }),
+        )
+        .await;
+        fs.insert_symlink(path!("/test/new/nested/loop"), path!("/test/new").into())
+            .await;
+
+        let entries = diff_directories(
+            fs.as_ref(),
+            path!("/test/old").as_ref(),
+            path!("/test/new").as_ref(),
+        )
+        .await
+        .unwrap();
+        assert_eq!(
+            entries,
+            vec![
+                DirectoryDiffEntry {
+                    path: "added.txt".into(),
+                    status: DirectoryDiffStatus::Added,
+                },
+                DirectoryDiffEntry {
+                    path: "changed.txt".into(),
+                    status: DirectoryDiffStatus::Modified,
+                },
+                DirectoryDiffEntry {
+                    path: PathBuf::from("nested").join("changed.rs"),
+                    status: DirectoryDiffStatus::Modified,
+                },
+                DirectoryDiffEntry {
+                    path: "removed.txt".into(),
+                    status: DirectoryDiffStatus::Removed,
+                },
+            ]
+        );
+
+        let rows = build_rows(&entries)
+            .into_iter()
+            .map(|row| match row {
+                DirectoryDiffRow::Directory { path, depth } => (path, depth),
+                DirectoryDiffRow::File { entry_ix, depth } => {
+                    (entries[entry_ix].path.clone(), depth)
+                }
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            rows,
+            vec![
+                (PathBuf::from("added.txt"), 0),
+                (PathBuf::from("changed.txt"), 0),
+                (PathBuf::from("nested"), 0),
+                (PathBuf::from("nested").join("changed.rs"), 1),
+                (PathBuf::from("removed.txt"), 0),
+            ]
+        );
+    }
+
+    #[gpui::test]
+    async fn test_diff_directory_revisions(cx: &mut TestAppContext) {
+        cx.update(|cx| {
+            let settings_store = SettingsStore::test(cx);
+            cx.set_global(settings_store);
+            theme::init(theme::LoadThemes::JustBase, cx);
+            editor::init(cx);
+        });
+
+        let fs = FakeFs::new(cx.executor());
+        fs.insert_tree(
+            path!("/project"),
+            json!({ ".git": {}, "src": { "a.txt": "a\n" } }),
+        )
+        .await;
+        fs.with_git_state(Path::new(path!("/project/.git")), true, |state| {
+            for (revision, path, content) in [
+                ("v1", "src/a.txt", "a\n"),
+                ("v1", "src/nested/b.txt", "b\n"),
+                ("v1", "other.txt", "other\n"),
+                ("v2", "src/a.txt", "a\nmore a\n"),
+                ("v2", "src/c.txt", "c\n"),
+                ("v2", "other.txt", "changed\n"),
+            ] {
+                state
+                    .revision_contents
+                    .insert((revision.to_string(), repo_path(path)), content.to_string());
+            }
+        })
+        .unwrap();
+        let project = Project::test(fs.clone(), [Path::new(path!("/project"))], cx).await;
+        let multi_workspace =
+            cx.add_window(|window, cx| MultiWorkspace::test_new(project.clone(), window, cx));
+        let cx = &mut VisualTestContext::from_window(*multi_workspace, cx);
+        let workspace = multi_workspace
+            .update(cx, |workspace, _, _| workspace.workspace().clone())
+            .unwrap();
+        cx.run_until_parked();
+
+        let repository =
+            project.read_with(cx, |project, cx| project.active_repository(cx).unwrap());
+        let view = workspace
+            .update_in(cx, |workspace, window, cx| {
+                DirectoryDiffView::open_revisions(
+                    repository,
+                    repo_path("src"),
+                    "v1".into(),
+                    "v2".into(),
+                    workspace.weak_handle(),
+                    window,
+                    cx,
+                )
+            })
+            .await
+            .unwrap();
+        cx.run_until_parked();
+
+        view.update(cx, |view, _| {
+            assert_eq!(view.titles().0, "src @ v1 ↔ v2");
+            assert_eq!(
+                view.entries(),
+                [
+                    DirectoryDiffEntry {
+                        path: "a.txt".into(),
+                        status: DirectoryDiffStatus::Modified,
+                    },
+                    DirectoryDiffEntry {
+                        path: "c.txt".into(),
+                        status: DirectoryDiffStatus::Added,
+                    },
+                    DirectoryDiffEntry {
+                        path: PathBuf::from("nested").join("b.txt"),
+                        status: DirectoryDiffStatus::Removed,
+                    },
+                ]
+            );
+        });
+
+        cx.update(|window, cx| view.read(cx).open_entry(0, window, cx));
+        cx.run_until_parked();
+        workspace.update(cx, |workspace, cx| {
+            assert!(workspace.active_item_as::<FileDiffView>(cx).is_some());
+        });
+    }
+}
@@ -0,0 +1,2025 @@
+use std::{
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+
+use anyhow::Context as _;
+use collections::{HashMap, HashSet};
+use editor::Editor;
+use file_icons::FileIcons;
+use fuzzy::StringMatchCandidate;
+use gpui::{
+    App, AsyncWindowContext, DismissEvent, Entity, EventEmitter, FocusHandle, Focusable, Task,
+    WeakEntity,
+};
+use language::Buffer;
+use picker::{Picker, PickerDelegate};
+use project::{
+    Candidates, PathMatchCandidateSet, Project, ProjectItem as _, ProjectPath, WorktreeId,
+    project_settings::ProjectSettings,
+};
+use settings::{DiffPlacement, Settings as _};
+use ui::{HighlightedLabel, ListItem, ListItemSpacing, prelude::*};
+use util::ResultExt;
+use workspace::{ModalView, MultiWorkspace, Pane, Workspace, notifications::DetachAndPromptErr};
+
+use crate::{file_diff_view::FileDiffView, three_way_diff_view::ThreeWayDiffView};
+
+pub fn register(workspace: &mut Workspace) {
+    workspace.register_action(open);
+    workspace.register_action(open_two_step);
+    workspace.register_action(repeat_last_comparison);
+    workspace.register_action(open_three_way);
+}
+
+const MAX_RECENT_COMPARISONS: usize = 5;
+
+/// A previously opened comparison, stored by absolute path so that it can be resolved again in a
+/// later session.
+#[derive(Clone, Debug, PartialEq)]
+struct RecentComparison {
+    old_path: PathBuf,
+    new_path: PathBuf,
+}
+
+impl RecentComparison {
+    fn label(&self) -> SharedString {
+        let file_name = |path: &PathBuf| {
+            path.file_name()
+                .unwrap_or(path.as_os_str())
+                .to_string_lossy()
+                .into_owned()
+        };
+        format!(
+            "{} ↔ {}",
+            file_name(&self.old_path),
+            file_name(&self.new_path)
+        )
+        .into()
+    }
+}
+
+pub fn open(
+    workspace: &mut Workspace,
+    _: &git::CompareWithFile,
+    window: &mut Window,
+    cx: &mut Context<Workspace>,
+) {
+    let Some(active_buffer) = workspace
+        .active_item_as::<Editor>(cx)
+        .and_then(|editor| editor.read(cx).buffer().read(cx).as_singleton())
+    else {
+        return;
+    };
+    toggle_against_buffer(workspace, active_buffer, window, cx);
+}
+
+/// Opens the picker to compare a project file with another file, loading the file when it isn't
+/// open in an editor.
+pub fn open_against_path(
+    workspace: &mut Workspace,
+    project_path: ProjectPath,
+    window: &mut Window,
+    cx: &mut Context<Workspace>,
+) {
+    let open_buffer = workspace
+        .project()
+        .update(cx, |project, cx| project.open_buffer(project_path, cx));
+    cx.spawn_in(window, async move |workspace, cx| {
+        let buffer = open_buffer.await?;
+        workspace.update_in(cx, |workspace, window, cx| {
+            toggle_against_buffer(workspace, buffer, window, cx)
+        })
+    })
+    .detach_and_prompt_err("Failed to open file", window, cx, |_, _, _| None);
+}
+
+fn toggle_against_buffer(
+    workspace: &mut Workspace,
+    buffer: Entity<Buffer>,
+    window: &mut Window,
+    cx: &mut Context<Workspace>,
+) {
+    let candidates = open_buffers(workspace, cx);
+    let recent_comparisons = load_recent_comparisons(workspace, cx);
+    let project = workspace.project().clone();
+    let weak_workspace = workspace.weak_handle();
+    workspace.toggle_modal(window, cx, |window, cx| {
+        FileComparisonPicker::new(
+            ComparisonStage::AgainstBuffer(buffer),
+            candidates,
+            recent_comparisons,
+            project,
+            weak_workspace,
+            window,
+            cx,
+        )
+    });
+}
+
+pub fn open_two_step(
+    workspace: &mut Workspace,
+    _: &git::CompareFiles,
+    window: &mut Window,
+    cx: &mut Context<Workspace>,
+) {
+    let candidates = open_buffers(workspace, cx);
+    let recent_comparisons = load_recent_comparisons(workspace, cx);
+    let project = workspace.project().clone();
+    let weak_workspace = workspace.weak_handle();
+    workspace.toggle_modal(window, cx, |window, cx| {
+        FileComparisonPicker::new(
+            ComparisonStage::PickLeft,
+            candidates,
+            recent_comparisons,
+            project,
+            weak_workspace,
+            window,
+            cx,
+        )
+    });
+}
+
+pub fn open_three_way(
+    workspace: &mut Workspace,
+    _: &git::CompareThreeWay,
+    window: &mut Window,
+    cx: &mut Context<Workspace>,
+) {
+    let candidates = open_buffers(workspace, cx);
+    let project = workspace.project().clone();
+    let weak_workspace = workspace.weak_handle();
+    workspace.toggle_modal(window, cx, |window, cx| {
+        FileComparisonPicker::new(
+            ComparisonStage::PickThreeWay(Vec::new()),
+            candidates,
+            Task::ready(Vec::new()),
+            project,
+            weak_workspace,
+            window,
+            cx,
+        )
+    });
+}
+
+pub fn repeat_last_comparison(
+    workspace: &mut Workspace,
+    _: &git::RepeatLastComparison,
+    window: &mut Window,
+    cx: &mut Context<Workspace>,
+) {
+    let recent_comparisons = load_recent_comparisons(workspace, cx);
+    let project = workspace.project().clone();
+    let weak_workspace = workspace.weak_handle();
+    let placement = diff_placement(false, cx);
+    window
+        .spawn(cx, async move |cx| {
+            let comparison = recent_comparisons
+                .await
+                .into_iter()
+                .next()
+                .context("no comparison has been opened yet")?;
+            open_recent_comparison(comparison, project, placement, weak_workspace, cx).await
+        })
+        .detach_and_prompt_err("Failed to open diff", window, cx, |_, _, _| None);
+}
+
+fn load_recent_comparisons(workspace: &Workspace, cx: &App) -> Task<Vec<RecentComparison>> {
+    let Some(workspace_id) = workspace.database_id() else {
+        return Task::ready(Vec::new());
+    };
+    let db = persistence::FileComparisonDb::global(cx);
+    cx.background_spawn(async move {
+        db.recent_comparisons(workspace_id)
+            .log_err()
+            .unwrap_or_default()
+            .into_iter()
+            .map(|(old_path, new_path)| RecentComparison { old_path, new_path })
+            .collect()
+    })
+}
+
+fn record_comparison(
+    old_buffer: &Entity<Buffer>,
+    new_buffer: &Entity<Buffer>,
+    workspace: &Workspace,
+    cx: &App,
+) {
+    let project = workspace.project().read(cx);
+    let abs_path = |buffer: &Entity<Buffer>| {
+        let project_path = buffer.read(cx).project_path(cx)?;
+        project.absolute_path(&project_path, cx)
+    };
+    let (Some(old_path), Some(new_path), Some(workspace_id)) = (
+        abs_path(old_buffer),
+        abs_path(new_buffer),
+        workspace.database_id(),
+    ) else {
+        return;
+    };
+    let db = persistence::FileComparisonDb::global(cx);
+    cx.background_spawn(
+        async move { db.record_comparison(workspace_id, old_path, new_path).await },
+    )
+    .detach_and_log_err(cx);
+}
+
+async fn open_recent_comparison(
+    comparison: RecentComparison,
+    project: Entity<Project>,
+    placement: DiffPlacement,
+    workspace: WeakEntity<Workspace>,
+    cx: &mut AsyncWindowContext,
+) -> anyhow::Result<()> {
+    let old_buffer = open_abs_path(&comparison.old_path, &project, cx).await?;
+    let new_buffer = open_abs_path(&comparison.new_path, &project, cx).await?;
+    open_comparison(old_buffer, new_buffer, placement, workspace, cx).await
+}
+
+/// The configured placement for new diffs, or the other one when `secondary` is set.
+fn diff_placement(secondary: bool, cx: &App) -> DiffPlacement {
+    let placement = ProjectSettings::get_global(cx).git.diff.placement;
+    match (placement, secondary) {
+        (placement, false) => placement,
+        (DiffPlacement::ActivePane, true) => DiffPlacement::Split,
+        (DiffPlacement::Split, true) => DiffPlacement::ActivePane,
+    }
+}
+
+async fn open_abs_path(
+    abs_path: &Path,
+    project: &Entity<Project>,
+    cx: &mut AsyncWindowContext,
+) -> anyhow::Result<Entity<Buffer>> {
+    let project_path = project
+        .read_with(cx, |project, cx| {
+            project.project_path_for_absolute_path(abs_path, cx)
+        })
+        .with_context(|| format!("{} is not part of the project", abs_path.display()))?;
+    open_target(ComparisonTarget::ProjectFile(project_path), project, cx).await
+}
+
+async fn open_comparison(
+    old_buffer: Entity<Buffer>,
+    new_buffer: Entity<Buffer>,
+    placement: DiffPlacement,
+    workspace: WeakEntity<Workspace>,
+    cx: &mut AsyncWindowContext,
+) -> anyhow::Result<()> {
+    workspace.read_with(cx, |workspace, cx| {
+        record_comparison(&old_buffer, &new_buffer, workspace, cx)
+    })?;
+    cx.update(|window, cx| {
+        FileDiffView::open_buffers(old_buffer, new_buffer, placement, workspace, window, cx)
+    })?
+    .await?;
+    Ok(())
+}
+
+/// Collects the file buffers open in editors across every pane of every workspace window, starting
+/// with the active pane so that its buffers are listed first. Other items, such as diff views and
+/// terminals, aren't editors and so are skipped. Comparisons are made within this workspace's
+/// project, so a buffer of another window's project is listed as this project's copy of its file,
+/// and left out when its file isn't part of this project.
+fn open_buffers(workspace: &Workspace, cx: &App) -> Vec<ComparisonCandidate> {
+    let this_project = workspace.project();
+    let mut seen_paths = HashSet::default();
+    let mut candidates = Vec::new();
+    let mut add_pane =
+        |pane: &Entity<Pane>, pane_project: &Entity<Project>, location: Option<SharedString>| {
+            for editor in pane.read(cx).items_of_type::<Editor>() {
+                let Some(buffer) = editor.read(cx).buffer().read(cx).as_singleton() else {
+                    continue;
+                };
+                let Some(buffer_path) = buffer.read(cx).project_path(cx) else {
+                    continue;
+                };
+                let (target, project_path) = if pane_project == this_project {
+                    (ComparisonTarget::OpenBuffer(buffer), buffer_path)
+                } else {
+                    let Some(project_path) = pane_project
+                        .read(cx)
+                        .absolute_path(&buffer_path, cx)
+                        .and_then(|abs_path| {
+                            this_project
+                                .read(cx)
+                                .project_path_for_absolute_path(&abs_path, cx)
+                        })
+                    else {
+                        continue;
+                    };
+                    (
+                        ComparisonTarget::ProjectFile(project_path.clone()),
+                        project_path,
+                    )
+                };
+                let Some(worktree) = this_project
+                    .read(cx)
+                    .worktree_for_id(project_path.worktree_id, cx)
+                else {
+                    continue;
+                };
+                let path = worktree
+                    .read(cx)
+                    .full_path(&project_path.path)
+                    .to_string_lossy()
+                    .into_owned()
+                    .into();
+                if !seen_paths.insert(project_path) {
+                    continue;
+                }
+                candidates.push(ComparisonCandidate {
+                    target,
+                    path,
+                    location: location.clone(),
+                });
+            }
+        };
+
+    let active_pane = workspace.active_pane();
+    add_pane(active_pane, this_project, None);
+    for (ix, pane) in workspace.panes().iter().enumerate() {
+        if pane != active_pane {
+            add_pane(pane, this_project, Some(format!("Pane {}", ix + 1).into()));
+        }
+    }
+
+    let current_workspace_id = workspace.weak_handle().entity_id();
+    let other_workspaces = cx
+        .windows()
+        .into_iter()
+        .filter_map(|window| window.downcast::<MultiWorkspace>())
+        .filter_map(|window| window.read(cx).ok())
+        .flat_map(|multi_workspace| multi_workspace.workspaces())
+        .filter(|other| other.entity_id() != current_workspace_id)
+        .collect::<Vec<_>>();
+    for other_workspace in other_workspaces {
+        let other_workspace = other_workspace.read(cx);
+        let other_project = other_workspace.project();
+        // Absolute paths only name the same file when both projects are on this machine.
+        if other_project != this_project
+            && !(other_project.read(cx).is_local() && this_project.read(cx).is_local())
+        {
+            continue;
+        }
+        let location: SharedString = other_project
+            .read(cx)
+            .visible_worktrees(cx)
+            .next()
+            .map(|worktree| worktree.read(cx).root_name_str().to_string().into())
+            .unwrap_or_else(|| "Other window".into());
+        for pane in other_workspace.panes() {
+            add_pane(pane, other_project, Some(location.clone()));
+        }
+    }
+    candidates
+}
+
+pub struct FileComparisonPicker {
+    picker: Entity<Picker<FileComparisonDelegate>>,
+}
+
+impl FileComparisonPicker {
+    fn new(
+        stage: ComparisonStage,
+        candidates: Vec<ComparisonCandidate>,
+        recent_comparisons: Task<Vec<RecentComparison>>,
+        project: Entity<Project>,
+        workspace: WeakEntity<Workspace>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Self {
+        let diff_stats = compute_diff_stats(&stage, &candidates, cx);
+        let delegate = FileComparisonDelegate {
+            picker: cx.entity().downgrade(),
+            project,
+            workspace,
+            stage,
+            all_matches: candidates,
+            matches: Vec::new(),
+            recent_comparisons: Vec::new(),
+            recent_matches: Vec::new(),
+            marked: Vec::new(),
+            diff_stats: HashMap::default(),
+            selected_index: 0,
+        };
+        let picker = cx.new(|cx| {
+            Picker::uniform_list(delegate, window, cx)
+                .max_height(Some(rems(20.).into()))
+                .show_scrollbar(true)
+        });
+        cx.spawn_in(window, {
+            let picker = picker.clone();
+            async move |_, cx| {
+                let recent_comparisons = recent_comparisons.await;
+                if recent_comparisons.is_empty() {
+                    return;
+                }
+                picker
+                    .update_in(cx, |picker, window, cx| {
+                        picker.delegate.recent_comparisons = recent_comparisons;
+                        picker.refresh(window, cx);
+                    })
+                    .log_err();
+            }
+        })
+        .detach();
+        cx.spawn_in(window, {
+            let picker = picker.clone();
+            async move |_, cx| {
+                let diff_stats = diff_stats.await;
+                picker.update(cx, |picker, cx| {
+                    picker.delegate.diff_stats = diff_stats;
+                    cx.notify();
+                });
+            }
+        })
+        .detach();
+        Self { picker }
+    }
+}
+
+/// The number of lines a comparison against the active buffer would show as added and removed.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+struct DiffStat {
+    added: u32,
+    removed: u32,
+}
+
+impl DiffStat {
+    fn between(old_text: &str, new_text: &str) -> Self {
+        language::line_diff(old_text, new_text).into_iter().fold(
+            Self::default(),
+            |stat, (old_rows, new_rows)| Self {
+                added: stat.added + (new_rows.end - new_rows.start),
+                removed: stat.removed + (old_rows.end - old_rows.start),
+            },
+        )
+    }
+}
+
+/// Diffs each open candidate against the active buffer in the background. Unopened project files
+/// aren't loaded just to compute their statistics.
+fn compute_diff_stats(
+    stage: &ComparisonStage,
+    candidates: &[ComparisonCandidate],
+    cx: &App,
+) -> Task<HashMap<SharedString, DiffStat>> {
+    let ComparisonStage::AgainstBuffer(active_buffer) = stage else {
+        return Task::ready(HashMap::default());
+    };
+    let active_snapshot = active_buffer.read(cx).snapshot();
+    let candidate_snapshots = candidates
+        .iter()
+        .filter_map(|candidate| match &candidate.target {
+            ComparisonTarget::OpenBuffer(buffer) => {
+                Some((candidate.path.clone(), buffer.read(cx).snapshot()))
+            }
+            ComparisonTarget::ProjectFile(_) => None,
+        })
+        .collect::<Vec<_>>();
+    cx.background_spawn(async move {
+        let active_text = active_snapshot.text();
+        candidate_snapshots
+            .into_iter()
+            .map(|(path, snapshot)| (path, DiffStat::between(&snapshot.text(), &active_text)))
+            .collect()
+    })
+}
+
+impl EventEmitter<DismissEvent> for FileComparisonPicker {}
+
+impl Focusable for FileComparisonPicker {
+    fn focus_handle(&self, cx: &App) -> FocusHandle {
+        self.picker.focus_handle(cx)
+    }
+}
+
+impl ModalView for FileComparisonPicker {}
+
+impl Render for FileComparisonPicker {
+    fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        v_flex()
+            .key_context("FileComparisonPicker")
+            .on_action(cx.listener(|this, _: &git::ToggleComparisonMark, _, cx| {
+                this.picker
+                    .update(cx, |picker, cx| picker.delegate.toggle_marked(cx));
+            }))
+            .w(rems(34.))
+            .child(self.picker.clone())
+    }
+}
+
+#[derive(Clone)]
+struct ComparisonCandidate {
+    target: ComparisonTarget,
+    path: SharedString,
+    /// Where the buffer is open, when that isn't the active pane.
+    location: Option<SharedString>,
+}
+
+#[derive(Clone)]
+enum ComparisonTarget {
+    OpenBuffer(Entity<Buffer>),
+    /// A project file that isn't open yet, loaded when the comparison is confirmed.
+    ProjectFile(ProjectPath),
+}
+
+enum ComparisonStage {
+    /// The picked file is compared against this buffer.
+    AgainstBuffer(Entity<Buffer>),
+    /// The picked file becomes the left side, and the right side is picked next.
+    PickLeft,
+    /// The picked file becomes the right side, compared against this left side.
+    PickRight(ComparisonCandidate),
+    /// The base, left and right files of a three-way comparison are picked in turn.
+    PickThreeWay(Vec<ComparisonCandidate>),
+}
+
+struct ComparisonMatch {
+    candidate: ComparisonCandidate,
+    positions: Vec<usize>,
+}
+
+pub struct FileComparisonDelegate {
+    picker: WeakEntity<FileComparisonPicker>,
+    project: Entity<Project>,
+    workspace: WeakEntity<Workspace>,
+    stage: ComparisonStage,
+    all_matches: Vec<ComparisonCandidate>,
+    matches: Vec<ComparisonMatch>,
+    recent_comparisons: Vec<RecentComparison>,
+    /// Recent comparisons listed under a header above `matches`, only while the query is empty.
+    recent_matches: Vec<RecentComparison>,
+    /// Candidates to open a diff for each against the active buffer on confirm.
+    marked: Vec<ComparisonCandidate>,
+    diff_stats: HashMap<SharedString, DiffStat>,
+    selected_index: usize,
+}
+
+enum PickerEntry<'a> {
+    RecentHeader,
+    Recent(&'a RecentComparison),
+    Candidate(&'a ComparisonMatch),
+}
+
+impl FileComparisonDelegate {
+    fn is_marked(&self, candidate: &ComparisonCandidate) -> bool {
+        self.marked
+            .iter()
+            .any(|marked| marked.path == candidate.path)
+    }
+
+    fn toggle_marked(&mut self, cx: &mut Context<Picker<Self>>) {
+        if !matches!(self.stage, ComparisonStage::AgainstBuffer(_)) {
+            return;
+        }
+        let Some(PickerEntry::Candidate(selected)) = self.entry(self.selected_index) else {
+            return;
+        };
+        let candidate = selected.candidate.clone();
+        if let Some(ix) = self
+            .marked
+            .iter()
+            .position(|marked| marked.path == candidate.path)
+        {
+            self.marked.remove(ix);
+        } else {
+            self.marked.push(candidate);
+        }
+        cx.notify();
+    }
+
+    /// Paths that are already part of the comparison, and so can't be picked again.
+    fn excluded_paths(&self, cx: &App) -> HashSet<SharedString> {
+        match &self.stage {
+            ComparisonStage::AgainstBuffer(buffer) => buffer
+                .read(cx)
+                .file()
+                .map(|file| file.full_path(cx).to_string_lossy().into_owned().into())
+                .into_iter()
+                .collect(),
+            ComparisonStage::PickLeft => HashSet::default(),
+            ComparisonStage::PickRight(left) => HashSet::from_iter([left.path.clone()]),
+            ComparisonStage::PickThreeWay(picked) => picked
+                .iter()
+                .map(|candidate| candidate.path.clone())
+                .collect(),
+        }
+    }
+
+    fn recent_entry_count(&self) -> usize {
+        if self.recent_matches.is_empty() {
+            0
+        } else {
+            self.recent_matches.len() + 1
+        }
+    }
+
+    fn entry(&self, ix: usize) -> Option<PickerEntry<'_>> {
+        let recent_entry_count = self.recent_entry_count();
+        if ix >= recent_entry_count {
+            self.matches
+                .get(ix - recent_entry_count)
+                .map(PickerEntry::Candidate)
+        } else if ix == 0 {
+            Some(PickerEntry::RecentHeader)
+        } else {
+            self.recent_matches.get(ix - 1).map(PickerEntry::Recent)
+        }
+    }
+}
+
+impl PickerDelegate for FileComparisonDelegate {
+    type ListItem = ListItem;
+
+    fn match_count(&self) -> usize {
+        self.recent_entry_count() + self.matches.len()
+    }
+
+    fn separators_after_indices(&self) -> Vec<usize> {
+        match self.recent_entry_count() {
+            0 => Vec::new(),
+            count => vec![count - 1],
+        }
+    }
+
+    fn can_select(&self, ix: usize, _window: &mut Window, _cx: &mut Context<Picker<Self>>) -> bool {
+        !matches!(self.entry(ix), Some(PickerEntry::RecentHeader))
+    }
+
+    fn selected_index(&self) -> usize {
+        self.selected_index
+    }
+
+    fn set_selected_index(
+        &mut self,
+        ix: usize,
+        _window: &mut Window,
+        cx: &mut Context<Picker<Self>>,
+    ) {
+        self.selected_index = ix;
+        cx.notify();
+    }
+
+    fn placeholder_text(&self, _window: &mut Window, _cx: &mut App) -> Arc<str> {
+        match &self.stage {
+            ComparisonStage::AgainstBuffer(_) => "Compare with…".into(),
+            ComparisonStage::PickLeft => "Select the left file…".into(),
+            ComparisonStage::PickRight(left) => format!("Compare {} with…", left.path).into(),
+            ComparisonStage::PickThreeWay(picked) => match picked.len() {
+                0 => "Select the base file…".into(),
+                1 => "Select the left file…".into(),
+                _ => "Select the right file…".into(),
+            },
+        }
+    }
+
+    fn no_matches_text(&self, _window: &mut Window, _cx: &mut App) -> Option<SharedString> {
+        Some("No files to compare with".into())
+    }
+
+    fn update_matches(
+        &mut self,
+        query: String,
+        window: &mut Window,
+        cx: &mut Context<Picker<Self>>,
+    ) -> Task<()> {
+        let excluded_paths = self.excluded_paths(cx);
+        let all_matches = self
+            .all_matches
+            .iter()
+            .filter(|candidate| !excluded_paths.contains(&candidate.path))
+            .cloned()
+            .collect::<Vec<_>>();
+        // Project files are skipped when they are already listed as open buffers.
+        let mut skipped_paths = excluded_paths;
+        skipped_paths.extend(all_matches.iter().map(|candidate| candidate.path.clone()));
+        let project = self.project.read(cx);
+        let path_style = project.path_style(cx);
+        let candidate_sets = project
+            .visible_worktrees(cx)
+            .map(|worktree| {
+                let worktree = worktree.read(cx);
+                PathMatchCandidateSet {
+                    snapshot: worktree.snapshot(),
+                    include_ignored: false,
+                    include_root_name: true,
+                    candidates: Candidates::Files,
+                }
+            })
+            .collect::<Vec<_>>();
+
+        cx.spawn_in(window, async move |picker, cx| {
+            let mut matches: Vec<ComparisonMatch> = if query.is_empty() {
+                all_matches
+                    .into_iter()
+                    .map(|candidate| ComparisonMatch {
+                        candidate,
+                        positions: Vec::new(),
+                    })
+                    .collect()
+            } else {
+                let candidates = all_matches
+                    .iter()
+                    .enumerate()
+                    .map(|(ix, candidate)| StringMatchCandidate::new(ix, &candidate.path))
+                    .collect::<Vec<_>>();
+                fuzzy::match_strings(
+                    &candidates,
+                    &query,
+                    true,
+                    true,
+                    10000,
+                    &Default::default(),
+                    cx.background_executor().clone(),
+                )
+                .await
+                .into_iter()
+                .filter_map(|string_match| {
+                    Some(ComparisonMatch {
+                        candidate: all_matches.get(string_match.candidate_id)?.clone(),
+                        positions: string_match.positions,
+                    })
+                })
+                .collect()
+            };
+
+            // Files that aren't open yet are only searched once there is a query, to avoid listing
+            // the whole project up front.
+            if !query.is_empty() {
+                let project_matches = fuzzy::match_path_sets(
+                    candidate_sets.as_slice(),
+                    &query,
+                    &None,
+                    true,
+                    100,
+                    &Default::default(),
+                    cx.background_executor().clone(),
+                )
+                .await;
+                matches.extend(project_matches.into_iter().filter_map(|path_match| {
+                    let path: SharedString = path_match
+                        .path_prefix
+                        .join(&path_match.path)
+                        .display(path_style)
+                        .into_owned()
+                        .into();
+                    if skipped_paths.contains(&path) {
+                        return None;
+                    }
+                    Some(ComparisonMatch {
+                        candidate: ComparisonCandidate {
+                            target: ComparisonTarget::ProjectFile(ProjectPath {
+                                worktree_id: WorktreeId::from_usize(path_match.worktree_id),
+                                path: path_match.path,
+                            }),
+                            path,
+                            location: None,
+                        },
+                        positions: path_match.positions,
+                    })
+                }));
+            }
+
+            picker
+                .update(cx, |picker, cx| {
+                    let delegate = &mut picker.delegate;
+                    delegate.matches = matches;
+                    delegate.recent_matches = match delegate.stage {
+                        ComparisonStage::PickRight(_) | ComparisonStage::PickThreeWay(_) => {
+                            Vec::new()
+                        }
+                        _ if !query.is_empty() => Vec::new(),
+                        _ => delegate.recent_comparisons.clone(),
+                    };
+                    let recent_entry_count = delegate.recent_entry_count();
+                    delegate.selected_index = delegate
+                        .selected_index
+                        .min(delegate.match_count().saturating_sub(1))
+                        .max(recent_entry_count.min(1));
+                    cx.notify();
+                })
+                .log_err();
+        })
+    }
+
+    fn confirm(&mut self, secondary: bool, window: &mut Window, cx: &mut Context<Picker<Self>>) {
+        let placement = diff_placement(secondary, cx);
+        let selected = match self.entry(self.selected_index) {
+            Some(PickerEntry::Candidate(selected)) => selected,
+            Some(PickerEntry::Recent(comparison)) => {
+                let comparison = comparison.clone();
+                let project = self.project.clone();
+                let workspace = self.workspace.clone();
+                window
+                    .spawn(cx, async move |cx| {
+                        open_recent_comparison(comparison, project, placement, workspace, cx).await
+                    })
+                    .detach_and_prompt_err("Failed to open diff", window, cx, |_, _, _| None);
+                self.dismissed(window, cx);
+                return;
+            }
+            Some(PickerEntry::RecentHeader) | None => return,
+        };
+        let target = selected.candidate.target.clone();
+        let (left, right) = match &self.stage {
+            ComparisonStage::AgainstBuffer(buffer) if !self.marked.is_empty() => {
+                let buffer = buffer.clone();
+                let targets = self
+                    .marked
+                    .drain(..)
+                    .map(|candidate| candidate.target)
+                    .collect::<Vec<_>>();
+                let project = self.project.clone();
+                let workspace = self.workspace.clone();
+                window
+                    .spawn(cx, async move |cx| {
+                        // Only the first diff gets a new split; the rest join it as the split's
+                        // pane becomes the active one.
+                        let mut placement = placement;
+                        for target in targets {
+                            let old_buffer = open_target(target, &project, cx).await?;
+                            open_comparison(
+                                old_buffer,
+                                buffer.clone(),
+                                placement,
+                                workspace.clone(),
+                                cx,
+                            )
+                            .await?;
+                            placement = DiffPlacement::ActivePane;
+                        }
+                        anyhow::Ok(())
+                    })
+                    .detach_and_prompt_err("Failed to open diff", window, cx, |_, _, _| None);
+                self.dismissed(window, cx);
+                return;
+            }
+            ComparisonStage::AgainstBuffer(buffer) => {
+                (target, ComparisonTarget::OpenBuffer(buffer.clone()))
+            }
+            ComparisonStage::PickLeft => {
+                self.stage = ComparisonStage::PickRight(selected.candidate.clone());
+                self.matches.clear();
+                self.recent_matches.clear();
+                self.selected_index = 0;
+                cx.defer_in(window, |picker, window, cx| {
+                    picker.refresh_placeholder(window, cx);
+                    picker.set_query("", window, cx);
+                    cx.notify();
+                });
+                return;
+            }
+            ComparisonStage::PickRight(left) => (left.target.clone(), target),
+            ComparisonStage::PickThreeWay(picked) => {
+                let mut picked = picked.clone();
+                picked.push(selected.candidate.clone());
+                if picked.len() < 3 {
+                    self.stage = ComparisonStage::PickThreeWay(picked);
+                    self.matches.clear();
+                    self.selected_index = 0;
+                    cx.defer_in(window, |picker, window, cx| {
+                        picker.refresh_placeholder(window, cx);
+                        picker.set_query("", window, cx);
+                        cx.notify();
+                    });
+                    return;
+                }
+                let project = self.project.clone();
+                let workspace = self.workspace.clone();
+                window
+                    .spawn(cx, async move |cx| {
+                        let mut buffers = Vec::new();
+                        for candidate in picked {
+                            buffers.push(open_target(candidate.target, &project, cx).await?);
+                        }
+                        let [base_buffer, left_buffer, right_buffer] = <[_; 3]>::try_from(buffers)
+                            .map_err(|_| anyhow::anyhow!("expected three files to compare"))?;
+                        cx.update(|window, cx| {
+                            ThreeWayDiffView::open(
+                                base_buffer,
+                                left_buffer,
+                                right_buffer,
+                                workspace,
+                                window,
+                                cx,
+                            )
+                        })?
+                        .await?;
+                        anyhow::Ok(())
+                    })
+                    .detach_and_prompt_err("Failed to open diff", window, cx, |_, _, _| None);
+                self.dismissed(window, cx);
+                return;
+            }
+        };
+        let project = self.project.clone();
+        let workspace = self.workspace.clone();
+        window
+            .spawn(cx, async move |cx| {
+                let old_buffer = open_target(left, &project, cx).await?;
+                let new_buffer = open_target(right, &project, cx).await?;
+                open_comparison(old_buffer, new_buffer, placement, workspace, cx).await
+            })
+            .detach_and_prompt_err("Failed to open diff", window, cx, |_, _, _| None);
+        self.dismissed(window, cx);
+    }
+
+    fn dismissed(&mut self, _window: &mut Window, cx: &mut Context<Picker<Self>>) {
+        self.picker
+            .update(cx, |_, cx| cx.emit(DismissEvent))
+            .log_err();
+    }
+
+    fn render_match(
+        &self,
+        ix: usize,
+        selected: bool,
+        _window: &mut Window,
+        cx: &mut Context<Picker<Self>>,
+    ) -> Option<Self::ListItem> {
+        let comparison_match = match self.entry(ix)? {
+            PickerEntry::Candidate(comparison_match) => comparison_match,
+            PickerEntry::RecentHeader => {
+                return Some(
+                    ListItem::new(ix).inset(true).selectable(false).child(
+                        Label::new("Recent comparisons")
+                            .size(LabelSize::Small)
+                            .color(Color::Muted),
+                    ),
+                );
+            }
+            PickerEntry::Recent(comparison) => {
+                return Some(
+                    ListItem::new(ix)
+                        .inset(true)
+                        .spacing(ListItemSpacing::Sparse)
+                        .toggle_state(selected)
+                        .start_slot(Icon::new(IconName::HistoryRerun).color(Color::Muted))
+                        .child(Label::new(comparison.label())),
+                );
+            }
+        };
+        let labels = PathLabels::new(
+            &comparison_match.candidate.path,
+            &comparison_match.positions,
+        );
+        let file_icon = FileIcons::get_icon(Path::new(labels.file_name.as_ref()), cx)
+            .map(|icon| Icon::from_path(icon).color(Color::Muted));
+        Some(
+            ListItem::new(ix)
+                .inset(true)
+                .spacing(ListItemSpacing::Sparse)
+                .toggle_state(selected)
+                .start_slot::<Icon>(file_icon)
+                .child(
+                    h_flex()
+                        .gap_2()
+                        .child(HighlightedLabel::new(
+                            labels.file_name,
+                            labels.file_name_positions,
+                        ))
+                        .child(
+                            HighlightedLabel::new(labels.directory, labels.directory_positions)
+                                .size(LabelSize::Small)
+                                .color(Color::Muted),
+                        ),
+                )
+                .end_slot(
+                    h_flex()
+                        .gap_1()
+                        .children(comparison_match.candidate.location.clone().map(|location| {
+                            Label::new(location)
+                                .size(LabelSize::Small)
+                                .color(Color::Muted)
+                        }))
+                        .children(
+                            self.diff_stats
+                                .get(&comparison_match.candidate.path)
+                                .map(|stat| render_diff_stat(*stat)),
+                        )
+                        .when(self.is_marked(&comparison_match.candidate), |this| {
+                            this.child(
+                                Icon::new(IconName::Check)
+                                    .size(IconSize::Small)
+                                    .color(Color::Accent),
+                            )
+                        }),
+                ),
+        )
+    }
+}
+
+fn render_diff_stat(stat: DiffStat) -> AnyElement {
+    if stat == DiffStat::default() {
+        return Label::new("Identical")
+            .size(LabelSize::Small)
+            .color(Color::Muted)
+            .into_any_element();
+    }
+    h_flex()
+        .gap_0p5()
+        .child(
+            Label::new(format!("+{}", stat.added))
+                .size(LabelSize::Small)
+                .color(Color::Created),
+        )
+        .child(
+            Label::new(format!("−{}", stat.removed))
+                .size(LabelSize::Small)
+                .color(Color::Deleted),
+        )
+        .into_any_element()
+}
+
+/// A candidate's path split into its file name and the directory containing it, so that files with
+/// the same name are told apart by their dimmed directory.
+struct PathLabels {
+    file_name: SharedString,
+    file_name_positions: Vec<usize>,
+    directory: SharedString,
+    directory_positions: Vec<usize>,
+}
+
+impl PathLabels {
+    fn new(path: &str, positions: &[usize]) -> Self {
+        let file_name_start = path.rfind(['/', '\\']).map_or(0, |ix| ix + 1);
+        let directory_end = file_name_start.saturating_sub(1);
+        let (directory_positions, file_name_positions): (Vec<_>, Vec<_>) = positions
+            .iter()
+            .copied()
+            .filter(|&position| position != directory_end || file_name_start == 0)
+            .partition(|&position| position < file_name_start);
+        Self {
+            file_name: path[file_name_start..].to_string().into(),
+            file_name_positions: file_name_positions
+                .into_iter()
+                .map(|position| position - file_name_start)
+                .collect(),
+            directory: path[..directory_end].to_string().into(),
+            directory_positions,
+        }
+    }
+}
+
+async fn open_target(
+    target: ComparisonTarget,
+    project: &Entity<Project>,
+    cx: &mut AsyncWindowContext,
+) -> anyhow::Result<Entity<Buffer>> {
+    match target {
+        ComparisonTarget::OpenBuffer(buffer) => Ok(buffer),
+        ComparisonTarget::ProjectFile(project_path) => {
+            project
+                .update(cx, |project, cx| project.open_buffer(project_path, cx))
+                .await
+        }
+    }
+}
+
+mod persistence {
+    use std::path::PathBuf;
+
+    use db::{
+        sqlez::{domain::Domain, thread_safe_connection::ThreadSafeConnection},
+        sqlez_macros::sql,
+    };
+    use workspace::{WorkspaceDb, WorkspaceId};
+
+    use super::MAX_RECENT_COMPARISONS;
+
+    pub struct FileComparisonDb(ThreadSafeConnection);
+
+    impl Domain for FileComparisonDb {
+        const NAME: &str = stringify!(FileComparisonDb);
+
+        const MIGRATIONS: &[&str] = &[sql!(
+                CREATE TABLE file_comparison_recent(
+                    workspace_id INTEGER,
+                    old_path BLOB NOT NULL,
+                    new_path BLOB NOT NULL,
+                    opened_at INTEGER NOT NULL,
+
+                    PRIMARY KEY(workspace_id, old_path, new_path),
+                    FOREIGN KEY(workspace_id) REFERENCES workspaces(workspace_id)
+                    ON DELETE CASCADE
+                ) STRICT;
+        )];
+    }
+
+    db::static_connection!(FileComparisonDb, [WorkspaceDb]);
+
+    impl FileComparisonDb {
+        /// Moves the comparison to the front of the workspace's recent comparisons, keeping at
+        /// most [`MAX_RECENT_COMPARISONS`]. Both steps run on the database's write queue, so
+        /// concurrent windows can't drop each other's entries.
+        pub async fn record_comparison(
+            &self,
+            workspace_id: WorkspaceId,
+            old_path: PathBuf,
+            new_path: PathBuf,
+        ) -> anyhow::Result<()> {
+            self.write(move |connection| {
+                connection.exec_bound::<(WorkspaceId, PathBuf, PathBuf)>(sql!(
+                    INSERT OR REPLACE INTO file_comparison_recent(
+                        workspace_id, old_path, new_path, opened_at
+                    )
+                    VALUES (
+                        ?1, ?2, ?3,
+                        (SELECT COALESCE(MAX(opened_at), 0) + 1
+                         FROM file_comparison_recent
+                         WHERE workspace_id = ?1)
+                    );
+                ))?((workspace_id, old_path, new_path))?;
+                connection.exec_bound::<(WorkspaceId, i64)>(sql!(
+                    DELETE FROM file_comparison_recent
+                    WHERE workspace_id = ?1 AND opened_at NOT IN (
+                        SELECT opened_at FROM file_comparison_recent
+                        WHERE workspace_id = ?1
+                        ORDER BY opened_at DESC
+                        LIMIT ?2
+                    );
+                ))?((workspace_id, MAX_RECENT_COMPARISONS as i64))
+            })
+            .await
+        }
+
+        /// The workspace's recent comparisons, most recent first.
+        pub fn recent_comparisons(
+            &self,
+            workspace_id: WorkspaceId,
+        ) -> anyhow::Result<Vec<(PathBuf, PathBuf)>> {
+            self.select_bound::<WorkspaceId, (PathBuf, PathBuf)>(sql!(
+                SELECT old_path, new_path FROM file_comparison_recent
+                WHERE workspace_id = ?
+                ORDER BY opened_at DESC
+            ))?(workspace_id)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use gpui::{TestAppContext, VisualTestContext};
+    use project::{FakeFs, Project};
+    use serde_json::json;
+    use settings::SettingsStore;
+    use util::{path, rel_path::rel_path};
+    use workspace::{Item as _, SplitDirection};
+
+    fn init_test(cx: &mut TestAppContext) {
+        cx.update(|cx| {
+            let settings_store = SettingsStore::test(cx);
+            cx.set_global(settings_store);
+            theme::init(theme::LoadThemes::JustBase, cx);
+            editor::init(cx);
+        });
+    }
+
+    #[gpui::test]
+    async fn test_fuzzy_matches_are_ranked_and_highlighted(cx: &mut TestAppContext) {
+        init_test(cx);
+
+        let fs = FakeFs::new(cx.executor());
+        fs.insert_tree(
+            path!("/project"),
+            json!({
+                "main.rs": "fn main() {}\n",
+                "readme.md": "# readme\n",
+                "src": {
+                    "mars.rs": "struct Mars;\n",
+                },
+            }),
+        )
+        .await;
+        let project = Project::test(fs, [path!("/project").as_ref()], cx).await;
+        let multi_workspace =
+            cx.add_window(|window, cx| MultiWorkspace::test_new(project.clone(), window, cx));
+        let cx = &mut VisualTestContext::from_window(*multi_workspace, cx);
+        let workspace = multi_workspace
+            .update(cx, |multi_workspace, _, _| {
+                multi_workspace.workspace().clone()
+            })
+            .unwrap();
+
+        let mut candidates = Vec::new();
+        for file_path in [
+            path!("/project/main.rs"),
+            path!("/project/readme.md"),
+            path!("/project/src/mars.rs"),
+        ] {
+            let buffer = project
+                .update(cx, |project, cx| project.open_local_buffer(file_path, cx))
+                .await
+                .unwrap();
+            let path = buffer.read_with(cx, |buffer, cx| {
+                buffer
+                    .file()
+                    .unwrap()
+                    .full_path(cx)
+                    .to_string_lossy()
+                    .into_owned()
+            });
+            candidates.push(ComparisonCandidate {
+                target: ComparisonTarget::OpenBuffer(buffer),
+                path: path.into(),
+                location: None,
+            });
+        }
+        let ComparisonTarget::OpenBuffer(active_buffer) = candidates[1].target.clone() else {
+            unreachable!()
+        };
+
+        let comparison_picker = workspace.update_in(cx, |workspace, window, cx| {
+            let weak_workspace = workspace.weak_handle();
+            workspace.toggle_modal(window, cx, |window, cx| {
+                FileComparisonPicker::new(
+                    ComparisonStage::AgainstBuffer(active_buffer),
+                    candidates,
+                    Task::ready(Vec::new()),
+                    project.clone(),
+                    weak_workspace,
+                    window,
+                    cx,
+                )
+            });
+            workspace.active_modal::<FileComparisonPicker>(cx).unwrap()
+        });
+        let picker = comparison_picker.read_with(cx, |this, _| this.picker.clone());
+
+        picker
+            .update_in(cx, |picker, window, cx| {
+                picker
+                    .delegate
+                    .update_matches("mrs".to_string(), window, cx)
+            })
+            .await;
+
+        picker.read_with(cx, |picker, _| {
+            let mut paths = picker
+                .delegate
+                .matches
+                .iter()
+                .map(|comparison_match| comparison_match.candidate.path.to_string())
+                .collect::<Vec<_>>();
+            paths.sort();
+            assert_eq!(
+                paths,
+                vec![
+                    path!("project/main.rs").to_string(),
+                    path!("project/src/mars.rs").to_string(),
+                ]
+            );
+            assert!(
+                picker
+                    .delegate
+                    .matches
+                    .iter()
+                    .all(|comparison_match| comparison_match.positions.len() == 3)
+            );
+        });
+    }
+
+    #[gpui::test]
+    async fn test_active_buffer_is_not_a_candidate(cx: &mut TestAppContext) {
+        init_test(cx);
+
+        let fs = FakeFs::new(cx.executor());
+        fs.insert_tree(
+            path!("/project"),
+            json!({
+                "a.txt": "one\n",
+                "b.txt": "two\n",
+            }),
+        )
+        .await;
+        let project = Project::test(fs, [path!("/project").as_ref()], cx).await;
+        let multi_workspace =
+            cx.add_window(|window, cx| MultiWorkspace::test_new(project.clone(), window, cx));
+        let cx = &mut VisualTestContext::from_window(*multi_workspace, cx);
+        let workspace = multi_workspace
+            .update(cx, |multi_workspace, _, _| {
+                multi_workspace.workspace().clone()
+            })
+            .unwrap();
+        cx.run_until_parked();
+
+        let active_buffer = project
+            .update(cx, |project, cx| {
+                project.open_local_buffer(path!("/project/a.txt"), cx)
+            })
+            .await
+            .unwrap();
+        let candidates = vec![ComparisonCandidate {
+            target: ComparisonTarget::OpenBuffer(active_buffer.clone()),
+            path: path!("project/a.txt").into(),
+            location: None,
+        }];
+
+        let comparison_picker = workspace.update_in(cx, |workspace, window, cx| {
+            let weak_workspace = workspace.weak_handle();
+            workspace.toggle_modal(window, cx, |window, cx| {
+                FileComparisonPicker::new(
+                    ComparisonStage::AgainstBuffer(active_buffer),
+                    candidates,
+                    Task::ready(Vec::new()),
+                    project.clone(),
+                    weak_workspace,
+                    window,
+                    cx,
+                )
+            });
+            workspace.active_modal::<FileComparisonPicker>(cx).unwrap()
+        });
+        let picker = comparison_picker.read_with(cx, |this, _| this.picker.clone());
+
+        picker
+            .update_in(cx, |picker, window, cx| {
+                picker
+                    .delegate
+                    .update_matches("txt".to_string(), window, cx)
+            })
+            .await;
+        picker.read_with(cx, |picker, _| {
+            let paths = picker
+                .delegate
+                .matches
+                .iter()
+                .map(|comparison_match| comparison_match.candidate.path.to_string())
+                .collect::<Vec<_>>();
+            assert_eq!(paths, vec![path!("project/b.txt").to_string()]);
+        });
+    }
+
+    #[gpui::test]
+    async fn test_open_diff_for_each_marked_file(cx: &mut TestAppContext) {
+        init_test(cx);
+
+        let fs = FakeFs::new(cx.executor());
+        fs.insert_tree(
+            path!("/project"),
+            json!({
+                "a.txt": "one\n",
+                "b.txt": "two\n",
+                "c.txt": "three\n",
+            }),
+        )
+        .await;
+        let project = Project::test(fs, [path!("/project").as_ref()], cx).await;
+        let multi_workspace =
+            cx.add_window(|window, cx| MultiWorkspace::test_new(project.clone(), window, cx));
+        let cx = &mut VisualTestContext::from_window(*multi_workspace, cx);
+        let workspace = multi_workspace
+            .update(cx, |multi_workspace, _, _| {
+                multi_workspace.workspace().clone()
+            })
+            .unwrap();
+        cx.run_until_parked();
+
+        let mut candidates = Vec::new();
+        for (file_path, path) in [
+            (path!("/project/b.txt"), path!("project/b.txt")),
+            (path!("/project/c.txt"), path!("project/c.txt")),
+        ] {
+            let buffer = project
+                .update(cx, |project, cx| project.open_local_buffer(file_path, cx))
+                .await
+                .unwrap();
+            candidates.push(ComparisonCandidate {
+                target: ComparisonTarget::OpenBuffer(buffer),
+                path: path.into(),
+                location: None,
+            });
+        }
+        let active_buffer = project
+            .update(cx, |project, cx| {
+                project.open_local_buffer(path!("/project/a.txt"), cx)
+            })
+            .await
+            .unwrap();
+
+        let comparison_picker = workspace.update_in(cx, |workspace, window, cx| {
+            let weak_workspace = workspace.weak_handle();
+            workspace.toggle_modal(window, cx, |window, cx| {
+                FileComparisonPicker::new(
+                    ComparisonStage::AgainstBuffer(active_buffer),
+                    candidates,
+                    Task::ready(Vec::new()),
+                    project.clone(),
+                    weak_workspace,
+                    window,
+                    cx,
+                )
+            });
+            workspace.active_modal::<FileComparisonPicker>(cx).unwrap()
+        });
+        let picker = comparison_picker.read_with(cx, |this, _| this.picker.clone());
+        picker
+            .update_in(cx, |picker, window, cx| {
+                picker.delegate.update_matches(String::new(), window, cx)
+            })
+            .await;
+
+        picker.update_in(cx, |picker, window, cx| {
+            for ix in 0..2 {
+                picker.delegate.set_selected_index(ix, window, cx);
+                picker.delegate.toggle_marked(cx);
+            }
+            assert_eq!(picker.delegate.marked.len(), 2);
+            picker.delegate.confirm(false, window, cx);
+        });
+        cx.run_until_parked();
+
+        workspace.read_with(cx, |workspace, cx| {
+            assert!(workspace.active_modal::<FileComparisonPicker>(cx).is_none());
+            let mut titles = workspace
+                .active_pane()
+                .read(cx)
+                .items_of_type::<FileDiffView>()
+                .map(|diff_view| diff_view.read(cx).tab_content_text(0, cx).to_string())
+                .collect::<Vec<_>>();
+            titles.sort();
+            assert_eq!(titles, vec!["b.txt ↔ a.txt", "c.txt ↔ a.txt"]);
+        });
+    }
+
+    #[gpui::test]
+    async fn test_secondary_confirm_opens_diff_in_split(cx: &mut TestAppContext) {
+        init_test(cx);
+
+        let fs = FakeFs::new(cx.executor());
+        fs.insert_tree(
+            path!("/project"),
+            json!({
+                "a.txt": "one\n",
+                "b.txt": "two\n",
+            }),
+        )
+        .await;
+        let project = Project::test(fs, [path!("/project").as_ref()], cx).await;
+        let multi_workspace =
+            cx.add_window(|window, cx| MultiWorkspace::test_new(project.clone(), window, cx));
+        let cx = &mut VisualTestContext::from_window(*multi_workspace, cx);
+        let workspace = multi_workspace
+            .update(cx, |multi_workspace, _, _| {
+                multi_workspace.workspace().clone()
+            })
+            .unwrap();
+        cx.run_until_parked();
+
+        let other_buffer = project
+            .update(cx, |project, cx| {
+                project.open_local_buffer(path!("/project/b.txt"), cx)
+            })
+            .await
+            .unwrap();
+        let active_buffer = project
+            .update(cx, |project, cx| {
+                project.open_local_buffer(path!("/project/a.txt"), cx)
+            })
+            .await
+            .unwrap();
+        let original_pane = workspace.read_with(cx, |workspace, _| workspace.active_pane().clone());
+
+        let comparison_picker = workspace.update_in(cx, |workspace, window, cx| {
+            let weak_workspace = workspace.weak_handle();
+            workspace.toggle_modal(window, cx, |window, cx| {
+                FileComparisonPicker::new(
+                    ComparisonStage::AgainstBuffer(active_buffer),
+                    vec![ComparisonCandidate {
+                        target: ComparisonTarget::OpenBuffer(other_buffer),
+                        path: path!("project/b.txt").into(),
+                        location: None,
+                    }],
+                    Task::ready(Vec::new()),
+                    project.clone(),
+                    weak_workspace,
+                    window,
+                    cx,
+                )
+            });
+            workspace.active_modal::<FileComparisonPicker>(cx).unwrap()
+        });
+        let picker = comparison_picker.read_with(cx, |this, _| this.picker.clone());
+        picker
+            .update_in(cx, |picker, window, cx| {
+                picker.delegate.update_matches(String::new(), window, cx)
+            })
+            .await;
+        picker.update_in(cx, |picker, window, cx| {
+            picker.delegate.confirm(true, window, cx);
+        });
+        cx.run_until_parked();
+
+        workspace.read_with(cx, |workspace, cx| {
+            assert_eq!(workspace.panes().len(), 2);
+            assert_ne!(workspace.active_pane(), &original_pane);
+            assert_eq!(
+                workspace
+                    .active_pane()
+                    .read(cx)
+                    .items_of_type::<FileDiffView>()
+                    .count(),
+                1
+            );
+            assert_eq!(
+                original_pane
+                    .read(cx)
+                    .items_of_type::<FileDiffView>()
+                    .count(),
+                0
+            );
+        });
+    }
+
+    #[gpui::test]
+    async fn test_compare_with_unopened_project_file(cx: &mut TestAppContext) {
+        init_test(cx);
+
+        let fs = FakeFs::new(cx.executor());
+        fs.insert_tree(
+            path!("/project"),
+            json!({
+                "a.txt": "one\ntwo\nthree\n",
+                "b.txt": "one\n2\nthree\n",
+            }),
+        )
+        .await;
+        let project = Project::test(fs, [path!("/project").as_ref()], cx).await;
+        let multi_workspace =
+            cx.add_window(|window, cx| MultiWorkspace::test_new(project.clone(), window, cx));
+        let cx = &mut VisualTestContext::from_window(*multi_workspace, cx);
+        let workspace = multi_workspace
+            .update(cx, |multi_workspace, _, _| {
+                multi_workspace.workspace().clone()
+            })
+            .unwrap();
+        cx.run_until_parked();
+
+        let active_buffer = project
+            .update(cx, |project, cx| {
+                project.open_local_buffer(path!("/project/a.txt"), cx)
+            })
+            .await
+            .unwrap();
+
+        let comparison_picker = workspace.update_in(cx, |workspace, window, cx| {
+            let weak_workspace = workspace.weak_handle();
+            workspace.toggle_modal(window, cx, |window, cx| {
+                FileComparisonPicker::new(
+                    ComparisonStage::AgainstBuffer(active_buffer),
+                    Vec::new(),
+                    Task::ready(Vec::new()),
+                    project.clone(),
+                    weak_workspace,
+                    window,
+                    cx,
+                )
+            });
+            workspace.active_modal::<FileComparisonPicker>(cx).unwrap()
+        });
+        let picker = comparison_picker.read_with(cx, |this, _| this.picker.clone());
+
+        picker
+            .update_in(cx, |picker, window, cx| {
+                picker
+                    .delegate
+                    .update_matches("b.txt".to_string(), window, cx)
+            })
+            .await;
+        picker.read_with(cx, |picker, _| {
+            assert_eq!(picker.delegate.matches.len(), 1);
+            assert!(matches!(
+                picker.delegate.matches[0].candidate.target,
+                ComparisonTarget::ProjectFile(_)
+            ));
+        });
+
+        picker.update_in(cx, |picker, window, cx| {
+            picker.delegate.confirm(false, window, cx);
+        });
+        cx.run_until_parked();
+
+        workspace.read_with(cx, |workspace, cx| {
+            assert!(workspace.active_modal::<FileComparisonPicker>(cx).is_none());
+            let diff_view = workspace.active_item_as::<FileDiffView>(cx).unwrap();
+            assert_eq!(diff_view.read(cx).tab_content_text(0, cx), "b.txt ↔ a.txt");
+        });
+    }
+
+    #[gpui::test]
+    async fn test_compare_two_picked_files(cx: &mut TestAppContext) {
+        init_test(cx);
+
+        let fs = FakeFs::new(cx.executor());
+        fs.insert_tree(
+            path!("/project"),
+            json!({
+                "a.txt": "one\ntwo\nthree\n",
+                "b.txt": "one\n2\nthree\n",
+            }),
+        )
+        .await;
+        let project = Project::test(fs, [path!("/project").as_ref()], cx).await;
+        let multi_workspace =
+            cx.add_window(|window, cx| MultiWorkspace::test_new(project.clone(), window, cx));
+        let cx = &mut VisualTestContext::from_window(*multi_workspace, cx);
+        let workspace = multi_workspace
+            .update(cx, |multi_workspace, _, _| {
+                multi_workspace.workspace().clone()
+            })
+            .unwrap();
+        cx.run_until_parked();
+
+        let comparison_picker = workspace.update_in(cx, |workspace, window, cx| {
+            open_two_step(workspace, &git::CompareFiles, window, cx);
+            workspace.active_modal::<FileComparisonPicker>(cx).unwrap()
+        });
+        let picker = comparison_picker.read_with(cx, |this, _| this.picker.clone());
+
+        for query in ["a.txt", "b.txt"] {
+            picker
+                .update_in(cx, |picker, window, cx| {
+                    picker
+                        .delegate
+                        .update_matches(query.to_string(), window, cx)
+                })
+                .await;
+            picker.update_in(cx, |picker, window, cx| {
+                assert_eq!(picker.delegate.matches.len(), 1);
+                picker.delegate.confirm(false, window, cx);
+            });
+            cx.run_until_parked();
+        }
+
+        workspace.read_with(cx, |workspace, cx| {
+            assert!(workspace.active_modal::<FileComparisonPicker>(cx).is_none());
+            let diff_view = workspace.active_item_as::<FileDiffView>(cx).unwrap();
+            assert_eq!(diff_view.read(cx).tab_content_text(0, cx), "a.txt ↔ b.txt");
+        });
+    }
+
+    #[gpui::test]
+    async fn test_compare_unopened_file_with(cx: &mut TestAppContext) {
+        init_test(cx);
+
+        let fs = FakeFs::new(cx.executor());
+        fs.insert_tree(
+            path!("/project"),
+            json!({
+                "a.txt": "one\ntwo\nthree\n",
+                "b.txt": "one\n2\nthree\n",
+            }),
+        )
+        .await;
+        let project = Project::test(fs, [path!("/project").as_ref()], cx).await;
+        let multi_workspace =
+            cx.add_window(|window, cx| MultiWorkspace::test_new(project.clone(), window, cx));
+        let cx = &mut VisualTestContext::from_window(*multi_workspace, cx);
+        let workspace = multi_workspace
+            .update(cx, |multi_workspace, _, _| {
+                multi_workspace.workspace().clone()
+            })
+            .unwrap();
+        cx.run_until_parked();
+
+        let worktree_id = project.read_with(cx, |project, cx| {
+            project.worktrees(cx).next().unwrap().read(cx).id()
+        });
+        workspace.update_in(cx, |workspace, window, cx| {
+            open_against_path(
+                workspace,
+                ProjectPath {
+                    worktree_id,
+                    path: rel_path("b.txt").into(),
+                },
+                window,
+                cx,
+            );
+        });
+        cx.run_until_parked();
+        let comparison_picker = workspace.read_with(cx, |workspace, cx| {
+            workspace.active_modal::<FileComparisonPicker>(cx).unwrap()
+        });
+        let picker = comparison_picker.read_with(cx, |this, _| this.picker.clone());
+
+        picker
+            .update_in(cx, |picker, window, cx| {
+                picker
+                    .delegate
+                    .update_matches("txt".to_string(), window, cx)
+            })
+            .await;
+        picker.update_in(cx, |picker, window, cx| {
+            assert_eq!(
+                picker
+                    .delegate
+                    .matches
+                    .iter()
+                    .map(|comparison_match| comparison_match.candidate.path.to_string())
+                    .collect::<Vec<_>>(),
+                vec![path!("project/a.txt").to_string()]
+            );
+            picker.delegate.confirm(false, window, cx);
+        });
+        cx.run_until_parked();
+
+        workspace.read_with(cx, |workspace, cx| {
+            assert!(workspace.active_modal::<FileComparisonPicker>(cx).is_none());
+            let diff_view = workspace.active_item_as::<FileDiffView>(cx).unwrap();
+            assert_eq!(diff_view.read(cx).tab_content_text(0, cx), "a.txt ↔ b.txt");
+        });
+    }
+
+    #[gpui::test]
+    async fn test_candidates_include_buffers_from_other_panes(cx: &mut TestAppContext) {
+        init_test(cx);
+
+        let fs = FakeFs::new(cx.executor());
+        fs.insert_tree(
+            path!("/project"),
+            json!({
+                "a.txt": "one\n",
+                "b.txt": "two\n",
+            }),
+        )
+        .await;
+        let project = Project::test(fs, [path!("/project").as_ref()], cx).await;
+        let multi_workspace =
+            cx.add_window(|window, cx| MultiWorkspace::test_new(project.clone(), window, cx));
+        let cx = &mut VisualTestContext::from_window(*multi_workspace, cx);
+        let workspace = multi_workspace
+            .update(cx, |multi_workspace, _, _| {
+                multi_workspace.workspace().clone()
+            })
+            .unwrap();
+        cx.run_until_parked();
+
+        let mut buffers = Vec::new();
+        for file_path in [path!("/project/a.txt"), path!("/project/b.txt")] {
+            let buffer = project
+                .update(cx, |project, cx| project.open_local_buffer(file_path, cx))
+                .await
+                .unwrap();
+            buffers.push(buffer);
+        }
+
+        workspace.update_in(cx, |workspace, window, cx| {
+            let first_editor =
+                cx.new(|cx| Editor::for_buffer(buffers[0].clone(), None, window, cx));
+            workspace.add_item_to_active_pane(Box::new(first_editor), None, true, window, cx);
+            let first_pane = workspace.active_pane().clone();
+            let second_pane = workspace.split_pane(first_pane, SplitDirection::Right, window, cx);
+            let second_editor =
+                cx.new(|cx| Editor::for_buffer(buffers[1].clone(), None, window, cx));
+            workspace.add_item(
+                second_pane,
+                Box::new(second_editor),
+                None,
+                false,
+                false,
+                window,
+                cx,
+            );
+        });
+
+        workspace.read_with(cx, |workspace, cx| {
+            let candidates = open_buffers(workspace, cx);
+            assert_eq!(candidates.len(), 2);
+            assert_eq!(
+                candidates
+                    .iter()
+                    .filter(|candidate| candidate.location.is_none())
+                    .count(),
+                1
+            );
+            assert!(candidates.iter().any(|candidate| {
+                candidate
+                    .location
+                    .as_ref()
+                    .is_some_and(|location| location.starts_with("Pane "))
+            }));
+        });
+    }
+
+    #[gpui::test]
+    async fn test_candidates_from_other_windows_are_files_of_this_project(cx: &mut TestAppContext) {
+        init_test(cx);
+
+        let fs = FakeFs::new(cx.executor());
+        fs.insert_tree(path!("/project"), json!({ "a.txt": "one\n" }))
+            .await;
+        fs.insert_tree(path!("/elsewhere"), json!({ "c.txt": "three\n" }))
+            .await;
+        let other_project = Project::test(
+            fs.clone(),
+            [path!("/project").as_ref(), path!("/elsewhere").as_ref()],
+            cx,
+        )
+        .await;
+        let other_window =
+            cx.add_window(|window, cx| MultiWorkspace::test_new(other_project.clone(), window, cx));
+        {
+            let cx = &mut VisualTestContext::from_window(*other_window, cx);
+            let other_workspace = other_window
+                .update(cx, |multi_workspace, _, _| {
+                    multi_workspace.workspace().clone()
+                })
+                .unwrap();
+            for file_path in [path!("/project/a.txt"), path!("/elsewhere/c.txt")] {
+                let buffer = other_project
+                    .update(cx, |project, cx| project.open_local_buffer(file_path, cx))
+                    .await
+                    .unwrap();
+                other_workspace.update_in(cx, |workspace, window, cx| {
+                    let editor = cx.new(|cx| Editor::for_buffer(buffer, None, window, cx));
+                    workspace.add_item_to_active_pane(Box::new(editor), None, true, window, cx);
+                });
+            }
+        }
+
+        let project = Project::test(fs, [path!("/project").as_ref()], cx).await;
+        let multi_workspace =
+            cx.add_window(|window, cx| MultiWorkspace::test_new(project.clone(), window, cx));
+        let cx = &mut VisualTestContext::from_window(*multi_workspace, cx);
+        let workspace = multi_workspace
+            .update(cx, |multi_workspace, _, _| {
+                multi_workspace.workspace().clone()
+            })
+            .unwrap();
+        cx.run_until_parked();
+
+        workspace.read_with(cx, |workspace, cx| {
+            let candidates = open_buffers(workspace, cx);
+            assert_eq!(candidates.len(), 1);
+            assert_eq!(candidates[0].path.as_ref(), path!("project/a.txt"));
+            let ComparisonTarget::ProjectFile(project_path) = &candidates[0].target else {
+                panic!("a buffer of another project was listed");
+            };
+            assert_eq!(project_path.path.as_ref(), rel_path("a.txt"));
+        });
+    }
+
+    #[gpui::test]
+    async fn test_recent_comparisons_are_listed_first(cx: &mut TestAppContext) {
+        init_test(cx);
+
+        let fs = FakeFs::new(cx.executor());
+        fs.insert_tree(
+            path!("/project"),
+            json!({
+                "a.txt": "one\ntwo\n",
+                "b.txt": "one\n2\n",
+            }),
+        )
+        .await;
+        let project = Project::test(fs, [path!("/project").as_ref()], cx).await;
+        let multi_workspace =
+            cx.add_window(|window, cx| MultiWorkspace::test_new(project.clone(), window, cx));
+        let cx = &mut VisualTestContext::from_window(*multi_workspace, cx);
+        let workspace = multi_workspace
+            .update(cx, |multi_workspace, _, _| {
+                multi_workspace.workspace().clone()
+            })
+            .unwrap();
+        cx.run_until_parked();
+
+        let recent_comparisons = vec![RecentComparison {
+            old_path: PathBuf::from(path!("/project/a.txt")),
+            new_path: PathBuf::from(path!("/project/b.txt")),
+        }];
+        let comparison_picker = workspace.update_in(cx, |workspace, window, cx| {
+            let weak_workspace = workspace.weak_handle();
+            workspace.toggle_modal(window, cx, |window, cx| {
+                FileComparisonPicker::new(
+                    ComparisonStage::PickLeft,
+                    Vec::new(),
+                    Task::ready(recent_comparisons),
+                    project.clone(),
+                    weak_workspace,
+                    window,
+                    cx,
+                )
+            });
+            workspace.active_modal::<FileComparisonPicker>(cx).unwrap()
+        });
+        let picker = comparison_picker.read_with(cx, |this, _| this.picker.clone());
+        cx.run_until_parked();
+
+        picker.update_in(cx, |picker, window, cx| {
+            let delegate = &picker.delegate;
+            assert_eq!(delegate.match_count(), 2);
+            assert!(matches!(delegate.entry(0), Some(PickerEntry::RecentHeader)));
+            assert!(!delegate.can_select(0, window, cx));
+            assert_eq!(delegate.selected_index(), 1);
+            let Some(PickerEntry::Recent(comparison)) = delegate.entry(1) else {
+                panic!("expected a recent comparison");
+            };
+            assert_eq!(comparison.label(), "a.txt ↔ b.txt");
+        });
+
+        picker
+            .update_in(cx, |picker, window, cx| {
+                picker
+                    .delegate
+                    .update_matches("a.txt".to_string(), window, cx)
+            })
+            .await;
+        picker.read_with(cx, |picker, _| {
+            assert_eq!(picker.delegate.recent_entry_count(), 0);
+        });
+
+        picker
+            .update_in(cx, |picker, window, cx| {
+                picker.delegate.update_matches(String::new(), window, cx)
+            })
+            .await;
+        picker.update_in(cx, |picker, window, cx| {
+            picker.delegate.confirm(false, window, cx);
+        });
+        cx.run_until_parked();
+
+        workspace.read_with(cx, |workspace, cx| {
+            assert!(workspace.active_modal::<FileComparisonPicker>(cx).is_none());
+            let diff_view = workspace.active_item_as::<FileDiffView>(cx).unwrap();
+            assert_eq!(diff_view.read(cx).tab_content_text(0, cx), "a.txt ↔ b.txt");
+        });
+    }
+
+    #[gpui::test]
+    async fn test_record_comparison(cx: &mut TestAppContext) {
+        let workspace_db = cx.update(|cx| workspace::WorkspaceDb::global(cx));
+        let workspace_id = workspace_db.next_id().await.unwrap();
+        let db = cx.update(|cx| persistence::FileComparisonDb::global(cx));
+
+        let paths = |ix: usize| {
+            (
+                PathBuf::from(format!("/{ix}.old")),
+                PathBuf::from(format!("/{ix}.new")),
+            )
+        };
+        for ix in 0..MAX_RECENT_COMPARISONS + 2 {
+            let (old_path, new_path) = paths(ix);
+            db.record_comparison(workspace_id, old_path, new_path)
+                .await
+                .unwrap();
+        }
+        let recent_comparisons = db.recent_comparisons(workspace_id).unwrap();
+        assert_eq!(recent_comparisons.len(), MAX_RECENT_COMPARISONS);
+        assert_eq!(recent_comparisons[0], paths(MAX_RECENT_COMPARISONS + 1));
+
+        let repeated = recent_comparisons[2].clone();
+        db.record_comparison(workspace_id, repeated.0.clone(), repeated.1.clone())
+            .await
+            .unwrap();
+        let recent_comparisons = db.recent_comparisons(workspace_id).unwrap();
+        assert_eq!(recent_comparisons.len(), MAX_RECENT_COMPARISONS);
+        assert_eq!(recent_comparisons[0], repeated);
+        assert_eq!(
+            recent_comparisons
+                .iter()
+                .filter(|recent| **recent == repeated)
+                .count(),
+            1
+        );
+    }
+
+    #[test]
+    fn test_diff_stat_between() {
+        assert_eq!(
+            DiffStat::between("a\nb\nc\n", "a\nb\nc\n"),
+            DiffStat::default()
+        );
+        assert_eq!(
+            DiffStat::between("a\nb\nc\n", "a\nB\nc\nd\n"),
+            DiffStat {
+                added: 2,
+                removed: 1
+            }
+        );
+        assert_eq!(
+            DiffStat::between("a\nb\nc\n", "c\n"),
+            DiffStat {
+                added: 0,
+                removed: 2
+            }
+        );
+    }
+
+    #[test]
+    fn test_path_labels() {
+        let labels = PathLabels::new("project/src/mod.rs", &[0, 8, 12, 16]);
+        assert_eq!(labels.file_name, "mod.rs");
+        assert_eq!(labels.file_name_positions, vec![0, 4]);
+        assert_eq!(labels.directory, "project/src");
+        assert_eq!(labels.directory_positions, vec![0, 8]);
+
+        let labels = PathLabels::new("readme.md", &[0, 1]);
+        assert_eq!(labels.file_name, "readme.md");
+        assert_eq!(labels.file_name_positions, vec![0, 1]);
+        assert_eq!(labels.directory, "");
+        assert!(labels.directory_positions.is_empty());
+    }
+}
@@ -1,36 +1,60 @@
 //! FileDiffView provides a UI for displaying differences between two buffers.
 
-use anyhow::Result;
-use buffer_diff::BufferDiff;
-use editor::{Editor, EditorEvent, MultiBuffer};
+use anyhow::{Context as _, Result};
+use buffer_diff::{BufferDiff, IgnoreWhitespace};
+use editor::{Anchor, Editor, EditorEvent, EditorSettings, MultiBuffer, SplittableEditor};
 use futures::{FutureExt, select_biased};
 use gpui::{
-    AnyElement, App, AppContext as _, AsyncApp, Context, Entity, EventEmitter, FocusHandle,
-    Focusable, Font, IntoElement, Render, Task, WeakEntity, Window,
+    AnyElement, App, AppContext as _, AsyncApp, AsyncWindowContext, ClipboardItem, Context, Entity,
+    EventEmitter, FocusHandle, Focusable, Font, IntoElement, Render, Subscription, Task,
+    WeakEntity, Window,
 };
-use language::{Buffer, HighlightedText, LanguageRegistry};
-use project::Project;
+use language::{Buffer, HighlightedText, LanguageRegistry, LocalFile as _};
+use project::{DirectoryLister, Fs as _, Project, ProjectPath, project_settings::ProjectSettings};
+use settings::{DiffPlacement, DiffViewStyle, Settings as _};
 use std::{
     any::{Any, TypeId},
+    ops::Range,
     path::PathBuf,
     pin::pin,
     sync::Arc,
     time::Duration,
 };
-use ui::{Color, Icon, IconName, Label, LabelCommon as _, SharedString};
+use ui::{Tooltip, prelude::*};
 use util::paths::PathExt as _;
 use workspace::{
-    Item, ItemHandle as _, ItemNavHistory, ToolbarItemLocation, Workspace,
+    Item, ItemHandle, ItemNavHistory, SplitDirection, ToolbarItemEvent, ToolbarItemLocation,
+    ToolbarItemView, Workspace,
     item::{ItemEvent, SaveOptions, TabContentParams},
+    notifications::DetachAndPromptErr,
     searchable::SearchableItemHandle,
 };
 
+pub fn register(workspace: &mut Workspace) {
+    workspace.register_action(|workspace, _: &git::CompareWithSaved, window, cx| {
+        let Some(buffer) = workspace
+            .active_item_as::<Editor>(cx)
+            .and_then(|editor| editor.read(cx).buffer().read(cx).as_singleton())
+        else {
+            return;
+        };
+        FileDiffView::open_against_disk(buffer, workspace.weak_handle(), window, cx)
+            .detach_and_prompt_err("Failed to open diff", window, cx, |_, _, _| None);
+    });
+}
+
 pub struct FileDiffView {
-    editor: Entity<Editor>,
+    diff_editor: Entity<SplittableEditor>,
+    workspace: WeakEntity<Workspace>,
     old_buffer: Entity<Buffer>,
     new_buffer: Entity<Buffer>,
+    diff: Entity<BufferDiff>,
+    /// When set, the side without a file holds another version of the other side's file, such as
+    /// its content at this git revision, and is titled after it.
+    version: Option<SharedString>,
     buffer_changes_tx: watch::Sender<()>,
     _recalculate_diff_task: Task<Result<()>>,
+    _subscriptions: Vec<Subscription>,
 }
 
 const RECALCULATE_DIFF_DEBOUNCE: Duration = Duration::from_millis(250);
@@ -44,6 +68,7 @@ impl FileDiffView {
         window: &mut Window,
         cx: &mut App,
     ) -> Task<Result<Entity<Self>>> {
+        let placement = ProjectSettings::get_global(cx).git.diff.placement;
         window.spawn(cx, async move |cx| {
             let project = workspace.update(cx, |workspace, _| workspace.project().clone())?;
             let old_buffer = project
@@ -52,29 +77,134 @@ impl FileDiffView {
             let new_buffer = project
                 .update(cx, |project, cx| project.open_local_buffer(&new_path, cx))
                 .await?;
-            let languages = project.update(cx, |project, _| project.languages().clone());
+            Self::open_buffers_in(old_buffer, new_buffer, None, placement, workspace, cx).await
+        })
+    }
 
-            let buffer_diff = build_buffer_diff(&old_buffer, &new_buffer, languages, cx).await?;
+    /// Opens a diff view for two buffers that are already loaded in the project.
+    pub fn open_buffers(
+        old_buffer: Entity<Buffer>,
+        new_buffer: Entity<Buffer>,
+        placement: DiffPlacement,
+        workspace: WeakEntity<Workspace>,
+        window: &mut Window,
+        cx: &mut App,
+    ) -> Task<Result<Entity<Self>>> {
+        window.spawn(cx, async move |cx| {
+            Self::open_buffers_in(old_buffer, new_buffer, None, placement, workspace, cx).await
+        })
+    }
 
-            workspace.update_in(cx, |workspace, window, cx| {
-                let diff_view = cx.new(|cx| {
-                    FileDiffView::new(
-                        old_buffer,
-                        new_buffer,
-                        buffer_diff,
-                        project.clone(),
-                        window,
-                        cx,
-                    )
-                });
+    /// Opens a diff view between `new_buffer` and its content at a git revision, which has been
+    /// loaded into `old_buffer`.
+    pub fn open_at_revision(
+        old_buffer: Entity<Buffer>,
+        revision: SharedString,
+        new_buffer: Entity<Buffer>,
+        workspace: WeakEntity<Workspace>,
+        window: &mut Window,
+        cx: &mut App,
+    ) -> Task<Result<Entity<Self>>> {
+        let placement = ProjectSettings::get_global(cx).git.diff.placement;
+        window.spawn(cx, async move |cx| {
+            Self::open_buffers_in(
+                old_buffer,
+                new_buffer,
+                Some(revision),
+                placement,
+                workspace,
+                cx,
+            )
+            .await
+        })
+    }
 
-                let pane = workspace.active_pane();
-                pane.update(cx, |pane, cx| {
-                    pane.add_item(Box::new(diff_view.clone()), true, true, None, window, cx);
-                });
+    /// Opens a diff view between the file's contents on disk and the unsaved contents of `buffer`.
+    pub fn open_against_disk(
+        buffer: Entity<Buffer>,
+        workspace: WeakEntity<Workspace>,
+        window: &mut Window,
+        cx: &mut App,
+    ) -> Task<Result<Entity<Self>>> {
+        let load_text = buffer
+            .read(cx)
+            .file()
+            .and_then(|file| file.as_local())
+            .map(|file| file.load(cx));
+        let language = buffer.read(cx).language().cloned();
+        let placement = ProjectSettings::get_global(cx).git.diff.placement;
+        window.spawn(cx, async move |cx| {
+            let text = load_text.context("file is not on disk")?.await?;
+            let disk_buffer = cx.new(|cx| {
+                let mut disk_buffer = Buffer::local(text, cx);
+                disk_buffer.set_language(language, cx);
+                disk_buffer
+            });
+            Self::open_buffers_in(
+                disk_buffer,
+                buffer,
+                Some("disk".into()),
+                placement,
+                workspace,
+                cx,
+            )
+            .await
+        })
+    }
 
+    async fn open_buffers_in(
+        old_buffer: Entity<Buffer>,
+        new_buffer: Entity<Buffer>,
+        version: Option<SharedString>,
+        placement: DiffPlacement,
+        workspace: WeakEntity<Workspace>,
+        cx: &mut AsyncWindowContext,
+    ) -> Result<Entity<Self>> {
+        let (project, ignore_whitespace) = workspace.update(cx, |workspace, cx| {
+            (
+                workspace.project().clone(),
+                ProjectSettings::get_global(cx).git.diff.ignore_whitespace,
+            )
+        })?;
+        let languages = project.update(cx, |project, _| project.languages().clone());
+
+        let buffer_diff =
+            build_buffer_diff(&old_buffer, &new_buffer, languages, ignore_whitespace, cx).await?;
+
+        workspace.update_in(cx, |workspace, window, cx| {
+            let workspace_entity = cx.entity();
+            let diff_view = cx.new(|cx| {
+                let mut diff_view = FileDiffView::new(
+                    old_buffer,
+                    new_buffer,
+                    buffer_diff,
+                    project.clone(),
+                    workspace_entity,
+                    EditorSettings::get_global(cx).diff_view_style,
+                    window,
+                    cx,
+                );
+                diff_view.version = version;
                 diff_view
-            })
+            });
+
+            match placement {
+                DiffPlacement::ActivePane => {
+                    workspace.active_pane().update(cx, |pane, cx| {
+                        pane.add_item(Box::new(diff_view.clone()), true, true, None, window, cx);
+                    });
+                }
+                DiffPlacement::Split => {
+                    workspace.split_item(
+                        SplitDirection::Right,
+                        Box::new(diff_view.clone()),
+                        window,
+                        cx,
+                    );
+                }
+            }
+
+            diff_view
         })
     }
 
@@ -83,6 +213,8 @@ impl FileDiffView {
         new_buffer: Entity<Buffer>,
         diff: Entity<BufferDiff>,
         project: Entity<Project>,
+        workspace: Entity<Workspace>,
+        style: DiffViewStyle,
         window: &mut Window,
         cx: &mut Context<Self>,
     ) -> Self {
@@ -91,38 +223,49 @@ impl FileDiffView {
             multibuffer.add_diff(diff.clone(), cx);
             multibuffer
         });
-        let editor = cx.new(|cx| {
-            let mut editor =
-                Editor::for_multibuffer(multibuffer.clone(), Some(project.clone()), window, cx);
-            editor.start_temporary_diff_override();
-            editor.disable_diagnostics(cx);
-            editor.set_expand_all_diff_hunks(cx);
-            editor.set_render_diff_hunk_controls(
-                Arc::new(|_, _, _, _, _, _, _, _| gpui::Empty.into_any_element()),
+        let workspace_handle = workspace.downgrade();
+        let this = cx.weak_entity();
+        let diff_editor = cx.new(|cx| {
+            let splittable =
+                SplittableEditor::new(style, multibuffer, project, workspace, window, cx);
+            splittable.set_render_diff_hunk_controls(
+                Arc::new(move |row, _, hunk_range, _, line_height, editor, _, cx| {
+                    render_diff_hunk_controls(row, hunk_range, line_height, &this, editor, cx)
+                }),
                 cx,
             );
-            editor
+            splittable.rhs_editor().update(cx, |editor, cx| {
+                editor.start_temporary_diff_override();
+                editor.disable_diagnostics(cx);
+                editor.set_expand_all_diff_hunks(cx);
+            });
+            splittable
         });
 
         let (buffer_changes_tx, mut buffer_changes_rx) = watch::channel(());
 
-        for buffer in [&old_buffer, &new_buffer] {
-            cx.subscribe(buffer, move |this, _, event, _| match event {
-                language::BufferEvent::Edited { .. }
-                | language::BufferEvent::LanguageChanged(_)
-                | language::BufferEvent::Reparsed => {
-                    this.buffer_changes_tx.send(()).ok();
-                }
-                _ => {}
+        let subscriptions = [&old_buffer, &new_buffer]
+            .into_iter()
+            .map(|buffer| {
+                cx.subscribe(buffer, move |this, _, event, _| match event {
+                    language::BufferEvent::Edited { .. }
+                    | language::BufferEvent::LanguageChanged(_)
+                    | language::BufferEvent::Reparsed => {
+                        this.buffer_changes_tx.send(()).ok();
+                    }
+                    _ => {}
+                })
             })
-            .detach();
-        }
+            .collect();
 
         Self {
-            editor,
+            diff_editor,
+            workspace: workspace_handle,
             buffer_changes_tx,
             old_buffer,
             new_buffer,
+            diff: diff.clone(),
+            version: None,
             _recalculate_diff_task: cx.spawn(async move |this, cx| {
                 while buffer_changes_rx.recv().await.is_ok() {
                     loop {
@@ -158,8 +301,245 @@ impl FileDiffView {
                 }
                 Ok(())
             }),
+            _subscriptions: subscriptions,
         }
     }
+
+    /// Swaps which buffer is treated as the old side of the diff and which as the new one.
+    pub fn swap_sides(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let Some(workspace) = self.workspace.upgrade() else {
+            return;
+        };
+        let project = workspace.read(cx).project().clone();
+        let style = if self.diff_editor.read(cx).is_split() {
+            DiffViewStyle::Split
+        } else {
+            DiffViewStyle::Unified
+        };
+        let languages = project.read(cx).languages().clone();
+        let ignore_whitespace = self.diff.read(cx).ignore_whitespace();
+        let old_buffer = self.new_buffer.clone();
+        let new_buffer = self.old_buffer.clone();
+        cx.spawn_in(window, async move |this, cx| {
+            let diff =
+                build_buffer_diff(&old_buffer, &new_buffer, languages, ignore_whitespace, cx)
+                    .await?;
+            this.update_in(cx, |this, window, cx| {
+                let version = this.version.take();
+                let was_focused = this
+                    .diff_editor
+                    .focus_handle(cx)
+                    .contains_focused(window, cx);
+                *this = FileDiffView::new(
+                    old_buffer, new_buffer, diff, project, workspace, style, window, cx,
+                );
+                this.version = version;
+                if was_focused {
+                    this.diff_editor.focus_handle(cx).focus(window, cx);
+                }
+                cx.emit(EditorEvent::TitleChanged);
+                cx.notify();
+            })
+        })
+        .detach_and_log_err(cx);
+    }
+
+    /// Replaces each hunk intersecting `ranges` in the new buffer with the old buffer's text.
+    pub fn copy_hunks_to_new_side(&mut self, ranges: &[Range<Anchor>], cx: &mut Context<Self>) {
+        let base_text = self.diff.read(cx).base_text(cx);
+        let edits = self
+            .hunks_in_ranges(ranges, cx)
+            .into_iter()
+            .map(|(buffer_range, diff_base_byte_range)| {
+                let old_text = base_text
+                    .text_for_range(diff_base_byte_range)
+                    .collect::<String>();
+                (buffer_range, old_text)
+            })
+            .collect::<Vec<_>>();
+        if edits.is_empty() {
+            return;
+        }
+        self.new_buffer.update(cx, |buffer, cx| {
+            buffer.edit(edits, None, cx);
+        });
+    }
+
+    /// Replaces each hunk intersecting `ranges` in the old buffer with the new buffer's text.
+    pub fn copy_hunks_to_old_side(&mut self, ranges: &[Range<Anchor>], cx: &mut Context<Self>) {
+        let base_text = self.diff.read(cx).base_text(cx);
+        let old_snapshot = self.old_buffer.read(cx).snapshot();
+        // Hunk offsets refer to the diff's copy of the old text, which lags behind edits to the
+        // old buffer until the diff is recalculated.
+        if old_snapshot.text() != base_text.text() {
+            return;
+        }
+        let new_snapshot = self.new_buffer.read(cx).snapshot();
+        let edits = self
+            .hunks_in_ranges(ranges, cx)
+            .into_iter()
+            .map(|(buffer_range, diff_base_byte_range)| {
+                let new_text = new_snapshot
+                    .text_for_range(buffer_range)
+                    .collect::<String>();
+                (diff_base_byte_range, new_text)
+            })
+            .collect::<Vec<_>>();
+        if edits.is_empty() {
+            return;
+        }
+        self.old_buffer.update(cx, |buffer, cx| {
+            buffer.edit(edits, None, cx);
+        });
+    }
+
+    fn hunks_in_ranges(
+        &self,
+        ranges: &[Range<Anchor>],
+        cx: &App,
+    ) -> Vec<(Range<language::Anchor>, Range<usize>)> {
+        let editor = self.diff_editor.read(cx).rhs_editor().read(cx);
+        let snapshot = editor.buffer().read(cx).snapshot(cx);
+        let mut hunks = editor
+            .diff_hunks_in_ranges(ranges, &snapshot)
+            .map(|hunk| {
+                (
+                    hunk.buffer_range,
+                    hunk.diff_base_byte_range.start.0..hunk.diff_base_byte_range.end.0,
+                )
+            })
+            .collect::<Vec<_>>();
+        hunks.dedup_by(|(_, a), (_, b)| a == b);
+        hunks
+    }
+
+    /// Writes the hunks intersecting `ranges` to the clipboard as a unified diff.
+    pub fn copy_hunks_as_patch(&mut self, ranges: &[Range<Anchor>], cx: &mut Context<Self>) {
+        let mut hunks = self.hunks_in_ranges(ranges, cx);
+        if hunks.is_empty() {
+            return;
+        }
+        hunks.sort_by_key(|(_, diff_base_byte_range)| diff_base_byte_range.start);
+        // Diffing the old text against it with only these hunks applied gives them the context
+        // lines of the old text around them, which is where the patch will be applied.
+        let old_text = self.diff.read(cx).base_text(cx).text();
+        let new_snapshot = self.new_buffer.read(cx).snapshot();
+        let mut patched_text = String::with_capacity(old_text.len());
+        let mut old_offset = 0;
+        for (buffer_range, diff_base_byte_range) in hunks {
+            patched_text.push_str(&old_text[old_offset..diff_base_byte_range.start]);
+            patched_text.extend(new_snapshot.text_for_range(buffer_range));
+            old_offset = diff_base_byte_range.end;
+        }
+        patched_text.push_str(&old_text[old_offset..]);
+        let mut patch = self.patch_header(cx);
+        patch.push_str(&language::unified_diff(&old_text, &patched_text));
+        cx.write_to_clipboard(ClipboardItem::new_string(patch));
+    }
+
+    /// Writes the whole diff to the clipboard as a unified diff.
+    pub fn copy_diff_as_patch(&mut self, cx: &mut Context<Self>) {
+        cx.write_to_clipboard(ClipboardItem::new_string(self.patch_text(cx)));
+    }
+
+    /// Prompts for a path and writes the whole diff to it as a patch file.
+    pub fn save_diff_as(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let Some(workspace) = self.workspace.upgrade() else {
+            return;
+        };
+        let patch = self.patch_text(cx);
+        let file_stem = self
+            .new_buffer
+            .read(cx)
+            .file()
+            .and_then(|file| Some(file.path().file_stem()?.to_string()));
+        let suggested_name = format!("{}.patch", file_stem.as_deref().unwrap_or("diff"));
+        let (project, new_path) = workspace.update(cx, |workspace, cx| {
+            let project = workspace.project().clone();
+            let lister = if project.read(cx).is_local() {
+                DirectoryLister::Local(project.clone(), workspace.app_state().fs.clone())
+            } else {
+                DirectoryLister::Project(project.clone())
+            };
+            let new_path = workspace.prompt_for_new_path(lister, Some(suggested_name), window, cx);
+            (project, new_path)
+        });
+        cx.spawn(async move |_, cx| {
+            let Some(path) = new_path.await.ok().flatten().into_iter().flatten().next() else {
+                return Ok(());
+            };
+            if project.read_with(cx, |project, _| project.is_local()) {
+                let fs = project.read_with(cx, |project, _| project.fs().clone());
+                return fs.atomic_write(path, patch).await;
+            }
+            // The path is on the remote host, which only a buffer saved there can write to.
+            let (worktree, path) = project
+                .update(cx, |project, cx| {
+                    project.find_or_create_worktree(path, false, cx)
+                })
+                .await?;
+            let project_path = ProjectPath {
+                worktree_id: worktree.read_with(cx, |worktree, _| worktree.id()),
+                path,
+            };
+            let buffer = project
+                .update(cx, |project, cx| project.create_buffer(None, false, cx))
+                .await?;
+            buffer.update(cx, |buffer, cx| buffer.set_text(patch, cx));
+            project
+                .update(cx, |project, cx| {
+                    project.save_buffer_as(buffer, project_path, cx)
+                })
+                .await
+        })
+        .detach_and_prompt_err("Failed to save diff", window, cx, |_, _, _| None);
+    }
+
+    /// Returns the whole diff in unified diff format, with `---`/`+++` headers naming both sides.
+    pub fn patch_text(&self, cx: &App) -> String {
+        let old_text = self.old_buffer.read(cx).text();
+        let new_text = self.new_buffer.read(cx).text();
+        let mut patch = self.patch_header(cx);
+        patch.push_str(&language::unified_diff(&old_text, &new_text));
+        patch
+    }
+
+    fn patch_header(&self, cx: &App) -> String {
+        // A side without a file holds another version of the other side's file.
+        let patch_path = |buffer: &Entity<Buffer>, other_buffer: &Entity<Buffer>| {
+            [buffer, other_buffer]
+                .into_iter()
+                .find_map(|buffer| Some(buffer.read(cx).file()?.path().as_unix_str().to_string()))
+                .unwrap_or_else(|| "untitled".into())
+        };
+        let old_path = patch_path(&self.old_buffer, &self.new_buffer);
+        let new_path = patch_path(&self.new_buffer, &self.old_buffer);
+        format!("--- a/{old_path}\n+++ b/{new_path}\n")
+    }
+
+    fn selected_ranges(&self, cx: &App) -> Vec<Range<Anchor>> {
+        self.diff_editor
+            .read(cx)
+            .rhs_editor()
+            .read(cx)
+            .selections
+            .disjoint_anchor_ranges()
+            .collect()
+    }
+
+    pub fn ignore_whitespace(&self, cx: &App) -> IgnoreWhitespace {
+        self.diff.read(cx).ignore_whitespace()
+    }
+
+    /// Cycles between showing every whitespace difference, ignoring trailing whitespace, and
+    /// ignoring all of it.
+    pub fn toggle_ignore_whitespace(&mut self, cx: &mut Context<Self>) {
+        self.diff.update(cx, |diff, _| {
+            diff.set_ignore_whitespace(diff.ignore_whitespace().next());
+        });
+        self.buffer_changes_tx.send(()).ok();
+        cx.notify();
+    }
 }
 
 #[ztracing::instrument(skip_all)]
@@ -167,12 +547,17 @@ async fn build_buffer_diff(
     old_buffer: &Entity<Buffer>,
     new_buffer: &Entity<Buffer>,
     language_registry: Arc<LanguageRegistry>,
+    ignore_whitespace: IgnoreWhitespace,
     cx: &mut AsyncApp,
 ) -> Result<Entity<BufferDiff>> {
     let old_buffer_snapshot = old_buffer.read_with(cx, |buffer, _| buffer.snapshot());
     let new_buffer_snapshot = new_buffer.read_with(cx, |buffer, _| buffer.snapshot());
 
-    let diff = cx.new(|cx| BufferDiff::new(&new_buffer_snapshot.text, cx));
+    let diff = cx.new(|cx| {
+        let mut diff = BufferDiff::new(&new_buffer_snapshot.text, cx);
+        diff.set_ignore_whitespace(ignore_whitespace);
+        diff
+    });
 
     let update = diff
         .update(cx, |diff, cx| {
@@ -203,7 +588,7 @@ impl EventEmitter<EditorEvent> for FileDiffView {}
 
 impl Focusable for FileDiffView {
     fn focus_handle(&self, cx: &App) -> FocusHandle {
-        self.editor.focus_handle(cx)
+        self.diff_editor.focus_handle(cx)
     }
 }
 
@@ -225,36 +610,53 @@ impl Item for FileDiffView {
     }
 
     fn tab_content_text(&self, _detail: usize, cx: &App) -> SharedString {
-        let title_text = |buffer: &Entity<Buffer>| {
-            buffer
-                .read(cx)
-                .file()
-                .and_then(|file| {
-                    Some(
-                        file.full_path(cx)
-                            .file_name()?
-                            .to_string_lossy()
-                            .to_string(),
-                    )
+        let file_name = |buffer: &Entity<Buffer>| {
+            Some(
+                buffer
+                    .read(cx)
+                    .file()?
+                    .full_path(cx)
+                    .file_name()?
+                    .to_string_lossy()
+                    .to_string(),
+            )
+        };
+        let title_text = |buffer: &Entity<Buffer>, other_buffer: &Entity<Buffer>| {
+            file_name(buffer)
+                .or_else(|| {
+                    let version = self.version.as_ref()?;
+                    Some(format!("{} @ {version}", file_name(other_buffer)?))
                 })
                 .unwrap_or_else(|| "untitled".into())
         };
-        let old_filename = title_text(&self.old_buffer);
-        let new_filename = title_text(&self.new_buffer);
+        let old_filename = title_text(&self.old_buffer, &self.new_buffer);
+        let new_filename = title_text(&self.new_buffer, &self.old_buffer);
 
         format!("{old_filename} ↔ {new_filename}").into()
     }
 
     fn tab_tooltip_text(&self, cx: &App) -> Option<ui::SharedString> {
-        let path = |buffer: &Entity<Buffer>| {
-            buffer
-                .read(cx)
-                .file()
-                .map(|file| file.full_path(cx).compact().to_string_lossy().into_owned())
+        let file_path = |buffer: &Entity<Buffer>| {
+            Some(
+                buffer
+                    .read(cx)
+                    .file()?
+                    .full_path(cx)
+                    .compact()
+                    .to_string_lossy()
+                    .into_owned(),
+            )
+        };
+        let path = |buffer: &Entity<Buffer>, other_buffer: &Entity<Buffer>| {
+            file_path(buffer)
+                .or_else(|| {
+                    let version = self.version.as_ref()?;
+                    Some(format!("{} @ {version}", file_path(other_buffer)?))
+                })
                 .unwrap_or_else(|| "untitled".into())
         };
-        let old_path = path(&self.old_buffer);
-        let new_path = path(&self.new_buffer);
+        let old_path = path(&self.old_buffer, &self.new_buffer);
+        let new_path = path(&self.new_buffer, &self.old_buffer);
 
         Some(format!("{old_path} ↔ {new_path}").into())
     }
@@ -268,7 +670,7 @@ impl Item for FileDiffView {
     }
 
     fn deactivated(&mut self, window: &mut Window, cx: &mut Context<Self>) {
-        self.editor
+        self.diff_editor
             .update(cx, |editor, cx| editor.deactivated(window, cx));
     }
 
@@ -276,19 +678,21 @@ impl Item for FileDiffView {
         &'a self,
         type_id: TypeId,
         self_handle: &'a Entity<Self>,
-        _: &'a App,
+        cx: &'a App,
     ) -> Option<gpui::AnyEntity> {
         if type_id == TypeId::of::<Self>() {
             Some(self_handle.clone().into())
+        } else if type_id == TypeId::of::<SplittableEditor>() {
+            Some(self.diff_editor.clone().into())
         } else if type_id == TypeId::of::<Editor>() {
-            Some(self.editor.clone().into())
+            Some(self.diff_editor.read(cx).rhs_editor().clone().into())
         } else {
             None
         }
     }
 
     fn as_searchable(&self, _: &Entity<Self>, _: &App) -> Option<Box<dyn SearchableItemHandle>> {
-        Some(Box::new(self.editor.clone()))
+        Some(Box::new(self.diff_editor.clone()))
     }
 
     fn for_each_project_item(
@@ -296,7 +700,7 @@ impl Item for FileDiffView {
         cx: &App,
         f: &mut dyn FnMut(gpui::EntityId, &dyn project::ProjectItem),
     ) {
-        self.editor.for_each_project_item(cx, f)
+        self.diff_editor.read(cx).for_each_project_item(cx, f)
     }
 
     fn set_nav_history(
@@ -305,7 +709,8 @@ impl Item for FileDiffView {
         _: &mut Window,
         cx: &mut Context<Self>,
     ) {
-        self.editor.update(cx, |editor, _| {
+        let rhs = self.diff_editor.read(cx).rhs_editor().clone();
+        rhs.update(cx, |editor, _| {
             editor.set_nav_history(Some(nav_history));
         });
     }
@@ -316,7 +721,7 @@ impl Item for FileDiffView {
         window: &mut Window,
         cx: &mut Context<Self>,
     ) -> bool {
-        self.editor
+        self.diff_editor
             .update(cx, |editor, cx| editor.navigate(data, window, cx))
     }
 
@@ -325,7 +730,7 @@ impl Item for FileDiffView {
     }
 
     fn breadcrumbs(&self, cx: &App) -> Option<(Vec<HighlightedText>, Option<Font>)> {
-        self.editor.breadcrumbs(cx)
+        self.diff_editor.breadcrumbs(cx)
     }
 
     fn added_to_workspace(
@@ -334,14 +739,14 @@ impl Item for FileDiffView {
         window: &mut Window,
         cx: &mut Context<Self>,
     ) {
-        self.editor.update(cx, |editor, cx| {
+        self.diff_editor.update(cx, |editor, cx| {
             editor.added_to_workspace(workspace, window, cx)
         });
     }
 
     fn can_save(&self, cx: &App) -> bool {
         // The editor handles the new buffer, so delegate to it
-        self.editor.read(cx).can_save(cx)
+        self.diff_editor.read(cx).can_save(cx)
     }
 
     fn save(
@@ -352,22 +757,239 @@ impl Item for FileDiffView {
         cx: &mut Context<Self>,
     ) -> Task<Result<()>> {
         // Delegate saving to the editor, which manages the new buffer
-        self.editor
+        self.diff_editor
             .update(cx, |editor, cx| editor.save(options, project, window, cx))
     }
 }
 
 impl Render for FileDiffView {
-    fn render(&mut self, _: &mut Window, _: &mut Context<Self>) -> impl IntoElement {
-        self.editor.clone()
+    fn render(&mut self, _: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        div()
+            .size_full()
+            .on_action(
+                cx.listener(|this, _: &git::SwapDiffSides, window, cx| this.swap_sides(window, cx)),
+            )
+            .on_action(cx.listener(|this, _: &git::ToggleIgnoreWhitespace, _, cx| {
+                this.toggle_ignore_whitespace(cx)
+            }))
+            .on_action(cx.listener(|this, _: &git::CopyHunkToNewSide, _, cx| {
+                let ranges = this.selected_ranges(cx);
+                this.copy_hunks_to_new_side(&ranges, cx)
+            }))
+            .on_action(cx.listener(|this, _: &git::CopyHunkToOldSide, _, cx| {
+                let ranges = this.selected_ranges(cx);
+                this.copy_hunks_to_old_side(&ranges, cx)
+            }))
+            .on_action(cx.listener(|this, _: &git::CopyHunkAsPatch, _, cx| {
+                let ranges = this.selected_ranges(cx);
+                this.copy_hunks_as_patch(&ranges, cx)
+            }))
+            .on_action(
+                cx.listener(|this, _: &git::CopyDiffAsPatch, _, cx| this.copy_diff_as_patch(cx)),
+            )
+            .on_action(
+                cx.listener(|this, _: &git::SaveDiffAs, window, cx| this.save_diff_as(window, cx)),
+            )
+            .child(self.diff_editor.clone())
+    }
+}
+
+fn render_diff_hunk_controls(
+    row: u32,
+    hunk_range: Range<Anchor>,
+    line_height: Pixels,
+    file_diff_view: &WeakEntity<FileDiffView>,
+    editor: &Entity<Editor>,
+    cx: &mut App,
+) -> AnyElement {
+    let Some(old_side_has_file) = file_diff_view
+        .upgrade()
+        .map(|file_diff_view| file_diff_view.read(cx).old_buffer.read(cx).file().is_some())
+    else {
+        return gpui::Empty.into_any_element();
+    };
+    let focus_handle = editor.focus_handle(cx);
+
+    h_flex()
+        .h(line_height)
+        .mr_1()
+        .gap_1()
+        .px_0p5()
+        .pb_1()
+        .border_x_1()
+        .border_b_1()
+        .border_color(cx.theme().colors().border_variant)
+        .rounded_b_lg()
+        .bg(cx.theme().colors().editor_background)
+        .block_mouse_except_scroll()
+        .shadow_md()
+        .child(
+            IconButton::new(("copy-as-patch", row as u64), IconName::Copy)
+                .icon_size(IconSize::Small)
+                .tooltip({
+                    let focus_handle = focus_handle.clone();
+                    move |_window, cx| {
+                        Tooltip::for_action_in(
+                            "Copy Hunk as Patch",
+                            &git::CopyHunkAsPatch,
+                            &focus_handle,
+                            cx,
+                        )
+                    }
+                })
+                .on_click({
+                    let file_diff_view = file_diff_view.clone();
+                    let hunk_range = hunk_range.clone();
+                    move |_event, _window, cx| {
+                        file_diff_view
+                            .update(cx, |file_diff_view, cx| {
+                                file_diff_view
+                                    .copy_hunks_as_patch(&[hunk_range.start..hunk_range.start], cx)
+                            })
+                            .ok();
+                    }
+                }),
+        )
+        .when(old_side_has_file, |controls| {
+            controls.child(
+                Button::new(("copy-to-old", row as u64), "Use New")
+                    .tooltip({
+                        let focus_handle = focus_handle.clone();
+                        move |_window, cx| {
+                            Tooltip::for_action_in(
+                                "Copy Hunk to Old Side",
+                                &git::CopyHunkToOldSide,
+                                &focus_handle,
+                                cx,
+                            )
+                        }
+                    })
+                    .on_click({
+                        let file_diff_view = file_diff_view.clone();
+                        let hunk_range = hunk_range.clone();
+                        move |_event, _window, cx| {
+                            file_diff_view
+                                .update(cx, |file_diff_view, cx| {
+                                    file_diff_view.copy_hunks_to_old_side(
+                                        &[hunk_range.start..hunk_range.start],
+                                        cx,
+                                    )
+                                })
+                                .ok();
+                        }
+                    }),
+            )
+        })
+        .child(
+            Button::new(("copy-to-new", row as u64), "Use Old")
+                .tooltip(move |_window, cx| {
+                    Tooltip::for_action_in(
+                        "Copy Hunk to New Side",
+                        &git::CopyHunkToNewSide,
+                        &focus_handle,
+                        cx,
+                    )
+                })
+                .on_click({
+                    let file_diff_view = file_diff_view.clone();
+                    move |_event, _window, cx| {
+                        file_diff_view
+                            .update(cx, |file_diff_view, cx| {
+                                file_diff_view.copy_hunks_to_new_side(
+                                    &[hunk_range.start..hunk_range.start],
+                                    cx,
+                                )
+                            })
+                            .ok();
+                    }
+                }),
+        )
+        .into_any_element()
+}
+
+pub struct FileDiffViewToolbar {
+    file_diff_view: Option<WeakEntity<FileDiffView>>,
+}
+
+impl FileDiffViewToolbar {
+    pub fn new() -> Self {
+        Self {
+            file_diff_view: None,
+        }
+    }
+}
+
+impl EventEmitter<ToolbarItemEvent> for FileDiffViewToolbar {}
+
+impl Render for FileDiffViewToolbar {
+    fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let Some(file_diff_view) = self.file_diff_view.clone() else {
+            return div();
+        };
+        let ignore_whitespace = file_diff_view
+            .upgrade()
+            .map_or(IgnoreWhitespace::None, |file_diff_view| {
+                file_diff_view.read(cx).ignore_whitespace(cx)
+            });
+
+        h_flex()
+            .gap_1()
+            .child(
+                IconButton::new("toggle_ignore_whitespace", IconName::Space)
+                    .icon_size(IconSize::Small)
+                    .toggle_state(ignore_whitespace != IgnoreWhitespace::None)
+                    .tooltip(Tooltip::for_action_title(
+                        ignore_whitespace.label(),
+                        &git::ToggleIgnoreWhitespace,
+                    ))
+                    .on_click({
+                        let file_diff_view = file_diff_view.clone();
+                        move |_, _, cx| {
+                            file_diff_view
+                                .update(cx, |file_diff_view, cx| {
+                                    file_diff_view.toggle_ignore_whitespace(cx)
+                                })
+                                .ok();
+                        }
+                    }),
+            )
+            .child(
+                IconButton::new("swap_diff_sides", IconName::ArrowRightLeft)
+                    .icon_size(IconSize::Small)
+                    .tooltip(Tooltip::for_action_title("Swap Sides", &git::SwapDiffSides))
+                    .on_click(move |_, window, cx| {
+                        file_diff_view
+                            .update(cx, |file_diff_view, cx| {
+                                file_diff_view.swap_sides(window, cx)
+                            })
+                            .ok();
+                    }),
+            )
+    }
+}
+
+impl ToolbarItemView for FileDiffViewToolbar {
+    fn set_active_pane_item(
+        &mut self,
+        active_pane_item: Option<&dyn ItemHandle>,
+        _: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> ToolbarItemLocation {
+        if let Some(entity) = active_pane_item.and_then(|item| item.act_as::<FileDiffView>(cx)) {
+            self.file_diff_view = Some(entity.downgrade());
+            return ToolbarItemLocation::PrimaryRight;
+        }
+        self.file_diff_view = None;
+        ToolbarItemLocation::Hidden
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use editor::test::editor_test_context::assert_state_with_diff;
-    use gpui::TestAppContext;
+    use editor::{MultiBufferOffset, test::editor_test_context::assert_state_with_diff};
+    use gpui::{BorrowAppContext, TestAppContext, VisualTestContext};
+    use language::Point;
     use project::{FakeFs, Fs, Project};
     use settings::SettingsStore;
     use std::path::PathBuf;
@@ -379,6 +1001,11 @@ mod tests {
         cx.update(|cx| {
             let settings_store = SettingsStore::test(cx);
             cx.set_global(settings_store);
+            cx.update_global::<SettingsStore, _>(|store, cx| {
+                store.update_user_settings(cx, |settings| {
+                    settings.editor.diff_view_style = Some(DiffViewStyle::Unified);
+                });
+            });
             theme::init(theme::LoadThemes::JustBase, cx);
         });
     }
@@ -418,7 +1045,9 @@ mod tests {
 
         // Verify initial diff
         assert_state_with_diff(
-            &diff_view.read_with(cx, |diff_view, _| diff_view.editor.clone()),
+            &diff_view.read_with(cx, |diff_view, cx| {
+                diff_view.diff_editor.read(cx).rhs_editor().clone()
+            }),
             cx,
             &unindent(
                 "
@@ -453,7 +1082,9 @@ mod tests {
         // The diff now reflects the changes to the new file
         cx.executor().advance_clock(RECALCULATE_DIFF_DEBOUNCE);
         assert_state_with_diff(
-            &diff_view.read_with(cx, |diff_view, _| diff_view.editor.clone()),
+            &diff_view.read_with(cx, |diff_view, cx| {
+                diff_view.diff_editor.read(cx).rhs_editor().clone()
+            }),
             cx,
             &unindent(
                 "
@@ -488,7 +1119,9 @@ mod tests {
         // The diff now reflects the changes to the new file
         cx.executor().advance_clock(RECALCULATE_DIFF_DEBOUNCE);
         assert_state_with_diff(
-            &diff_view.read_with(cx, |diff_view, _| diff_view.editor.clone()),
+            &diff_view.read_with(cx, |diff_view, cx| {
+                diff_view.diff_editor.read(cx).rhs_editor().clone()
+            }),
             cx,
             &unindent(
                 "
@@ -518,6 +1151,483 @@ mod tests {
         })
     }
 
+    #[gpui::test]
+    async fn test_diff_against_disk(cx: &mut TestAppContext) {
+        init_test(cx);
+
+        let fs = FakeFs::new(cx.executor());
+        fs.insert_tree(
+            path!("/test"),
+            serde_json::json!({
+                "file.txt": "line 1\nline 2\nline 3\n",
+            }),
+        )
+        .await;
+
+        let project = Project::test(fs.clone(), [path!("/test").as_ref()], cx).await;
+
+        let (multi_workspace, cx) =
+            cx.add_window_view(|window, cx| MultiWorkspace::test_new(project.clone(), window, cx));
+        let workspace = multi_workspace.read_with(cx, |mw, _| mw.workspace().clone());
+
+        let buffer = project
+            .update(cx, |project, cx| {
+                project.open_local_buffer(path!("/test/file.txt"), cx)
+            })
+            .await
+            .unwrap();
+        buffer.update(cx, |buffer, cx| {
+            buffer.edit([(Point::new(1, 0)..Point::new(1, 6), "unsaved")], None, cx);
+        });
+
+        let diff_view = workspace
+            .update_in(cx, |workspace, window, cx| {
+                FileDiffView::open_against_disk(buffer, workspace.weak_handle(), window, cx)
+            })
+            .await
+            .unwrap();
+
+        assert_state_with_diff(
+            &diff_view.read_with(cx, |diff_view, cx| {
+                diff_view.diff_editor.read(cx).rhs_editor().clone()
+            }),
+            cx,
+            &unindent(
+                "
+                  ˇline 1
+                - line 2
+                + unsaved
+                  line 3
+                ",
+            ),
+        );
+        diff_view.read_with(cx, |diff_view, cx| {
+            assert_eq!(
+                diff_view.tab_content_text(0, cx),
+                "file.txt @ disk ↔ file.txt"
+            );
+        });
+    }
+
+    #[gpui::test]
+    async fn test_split_diff_view(cx: &mut TestAppContext) {
+        init_test(cx);
+        cx.update(|cx| {
+            cx.update_global::<SettingsStore, _>(|store, cx| {
+                store.update_user_settings(cx, |settings| {
+                    settings.editor.diff_view_style = Some(DiffViewStyle::Split);
+                });
+            });
+        });
+
+        let fs = FakeFs::new(cx.executor());
+        fs.insert_tree(
+            path!("/test"),
+            serde_json::json!({
+                "old_file.txt": "old line 1\nline 2\n",
+                "new_file.txt": "new line 1\nline 2\n"
+            }),
+        )
+        .await;
+
+        let project = Project::test(fs.clone(), [path!("/test").as_ref()], cx).await;
+
+        let (multi_workspace, cx) =
+            cx.add_window_view(|window, cx| MultiWorkspace::test_new(project.clone(), window, cx));
+        let workspace = multi_workspace.read_with(cx, |mw, _| mw.workspace().clone());
+
+        let diff_view = workspace
+            .update_in(cx, |workspace, window, cx| {
+                FileDiffView::open(
+                    path!("/test/old_file.txt").into(),
+                    path!("/test/new_file.txt").into(),
+                    workspace.weak_handle(),
+                    window,
+                    cx,
+                )
+            })
+            .await
+            .unwrap();
+        cx.run_until_parked();
+
+        diff_view.read_with(cx, |diff_view, cx| {
+            let diff_editor = diff_view.diff_editor.read(cx);
+            assert!(diff_editor.is_split());
+            assert_eq!(
+                diff_editor.rhs_editor().read(cx).text(cx),
+                "new line 1\nline 2\n"
+            );
+        });
+    }
+
+    #[gpui::test]
+    async fn test_swap_sides(cx: &mut TestAppContext) {
+        init_test(cx);
+
+        let fs = FakeFs::new(cx.executor());
+        fs.insert_tree(
+            path!("/test"),
+            serde_json::json!({
+                "old_file.txt": "old line 1\nline 2\n",
+                "new_file.txt": "new line 1\nline 2\n"
+            }),
+        )
+        .await;
+
+        let project = Project::test(fs.clone(), [path!("/test").as_ref()], cx).await;
+
+        let (multi_workspace, cx) =
+            cx.add_window_view(|window, cx| MultiWorkspace::test_new(project.clone(), window, cx));
+        let workspace = multi_workspace.read_with(cx, |mw, _| mw.workspace().clone());
+
+        let diff_view = workspace
+            .update_in(cx, |workspace, window, cx| {
+                FileDiffView::open(
+                    path!("/test/old_file.txt").into(),
+                    path!("/test/new_file.txt").into(),
+                    workspace.weak_handle(),
+                    window,
+                    cx,
+                )
+            })
+            .await
+            .unwrap();
+
+        diff_view.update_in(cx, |diff_view, window, cx| diff_view.swap_sides(window, cx));
+        cx.run_until_parked();
+
+        assert_state_with_diff(
+            &diff_view.read_with(cx, |diff_view, cx| {
+                diff_view.diff_editor.read(cx).rhs_editor().clone()
+            }),
+            cx,
+            &unindent(
+                "
+                - new line 1
+                + ˇold line 1
+                  line 2
+                ",
+            ),
+        );
+        diff_view.read_with(cx, |diff_view, cx| {
+            assert_eq!(
+                diff_view.tab_content_text(0, cx),
+                "new_file.txt ↔ old_file.txt"
+            );
+        });
+    }
+
+    #[gpui::test]
+    async fn test_toggle_ignore_whitespace(cx: &mut TestAppContext) {
+        init_test(cx);
+
+        let fs = FakeFs::new(cx.executor());
+        fs.insert_tree(
+            path!("/test"),
+            serde_json::json!({
+                "old_file.txt": "fn main() {\none();\n}\n",
+                "new_file.txt": "fn main() {\n    one();\n}\n"
+            }),
+        )
+        .await;
+
+        let project = Project::test(fs.clone(), [path!("/test").as_ref()], cx).await;
+
+        let (multi_workspace, cx) =
+            cx.add_window_view(|window, cx| MultiWorkspace::test_new(project.clone(), window, cx));
+        let workspace = multi_workspace.read_with(cx, |mw, _| mw.workspace().clone());
+
+        let diff_view = workspace
+            .update_in(cx, |workspace, window, cx| {
+                FileDiffView::open(
+                    path!("/test/old_file.txt").into(),
+                    path!("/test/new_file.txt").into(),
+                    workspace.weak_handle(),
+                    window,
+                    cx,
+                )
+            })
+            .await
+            .unwrap();
+
+        let editor = diff_view.read_with(cx, |diff_view, cx| {
+            diff_view.diff_editor.read(cx).rhs_editor().clone()
+        });
+        assert_state_with_diff(
+            &editor,
+            cx,
+            &unindent(
+                "
+                  ˇfn main() {
+                - one();
+                +     one();
+                  }
+                ",
+            ),
+        );
+
+        // Ignoring trailing whitespace still shows the indentation change
+        diff_view.update(cx, |diff_view, cx| diff_view.toggle_ignore_whitespace(cx));
+        cx.executor().advance_clock(RECALCULATE_DIFF_DEBOUNCE);
+        cx.run_until_parked();
+
+        assert_eq!(
+            diff_view.read_with(cx, |diff_view, cx| diff_view.ignore_whitespace(cx)),
+            IgnoreWhitespace::Trailing
+        );
+        assert_state_with_diff(
+            &editor,
+            cx,
+            &unindent(
+                "
+                  ˇfn main() {
+                - one();
+                +     one();
+                  }
+                ",
+            ),
+        );
+
+        diff_view.update(cx, |diff_view, cx| diff_view.toggle_ignore_whitespace(cx));
+        cx.executor().advance_clock(RECALCULATE_DIFF_DEBOUNCE);
+        cx.run_until_parked();
+
+        assert_eq!(
+            diff_view.read_with(cx, |diff_view, cx| diff_view.ignore_whitespace(cx)),
+            IgnoreWhitespace::All
+        );
+        assert_state_with_diff(
+            &editor,
+            cx,
+            &unindent(
+                "
+                  ˇfn main() {
+                      one();
+                  }
+                ",
+            ),
+        );
+    }
+
+    #[gpui::test]
+    async fn test_copy_hunks_between_sides(cx: &mut TestAppContext) {
+        init_test(cx);
+
+        let fs = FakeFs::new(cx.executor());
+        fs.insert_tree(
+            path!("/test"),
+            serde_json::json!({
+                "old_file.txt": "line 1\nold line 2\nline 3\nold line 4\n",
+                "new_file.txt": "line 1\nnew line 2\nline 3\nnew line 4\n"
+            }),
+        )
+        .await;
+
+        let project = Project::test(fs.clone(), [path!("/test").as_ref()], cx).await;
+
+        let (multi_workspace, cx) =
+            cx.add_window_view(|window, cx| MultiWorkspace::test_new(project.clone(), window, cx));
+        let workspace = multi_workspace.read_with(cx, |mw, _| mw.workspace().clone());
+
+        let diff_view = workspace
+            .update_in(cx, |workspace, window, cx| {
+                FileDiffView::open(
+                    path!("/test/old_file.txt").into(),
+                    path!("/test/new_file.txt").into(),
+                    workspace.weak_handle(),
+                    window,
+                    cx,
+                )
+            })
+            .await
+            .unwrap();
+
+        let anchor_at = |text: &str, cx: &mut VisualTestContext| {
+            diff_view.read_with(cx, |diff_view, cx| {
+                let editor = diff_view.diff_editor.read(cx).rhs_editor().read(cx);
+                let snapshot = editor.buffer().read(cx).snapshot(cx);
+                let offset = snapshot.text().find(text).unwrap();
+                let anchor = snapshot.anchor_before(MultiBufferOffset(offset));
+                anchor..anchor
+            })
+        };
+
+        let range = anchor_at("new line 2", cx);
+        diff_view.update(cx, |diff_view, cx| {
+            diff_view.copy_hunks_to_new_side(&[range], cx)
+        });
+        cx.executor().advance_clock(RECALCULATE_DIFF_DEBOUNCE);
+        cx.run_until_parked();
+
+        let range = anchor_at("new line 4", cx);
+        diff_view.update(cx, |diff_view, cx| {
+            diff_view.copy_hunks_to_old_side(&[range], cx)
+        });
+        cx.executor().advance_clock(RECALCULATE_DIFF_DEBOUNCE);
+        cx.run_until_parked();
+
+        diff_view.read_with(cx, |diff_view, cx| {
+            assert_eq!(
+                diff_view.new_buffer.read(cx).text(),
+                "line 1\nold line 2\nline 3\nnew line 4\n"
+            );
+            assert_eq!(
+                diff_view.old_buffer.read(cx).text(),
+                "line 1\nold line 2\nline 3\nnew line 4\n"
+            );
+        });
+        assert_state_with_diff(
+            &diff_view.read_with(cx, |diff_view, cx| {
+                diff_view.diff_editor.read(cx).rhs_editor().clone()
+            }),
+            cx,
+            &unindent(
+                "
+                  ˇline 1
+                  old line 2
+                  line 3
+                  new line 4
+                ",
+            ),
+        );
+    }
+
+    #[gpui::test]
+    async fn test_copy_as_patch(cx: &mut TestAppContext) {
+        init_test(cx);
+
+        let fs = FakeFs::new(cx.executor());
+        fs.insert_tree(
+            path!("/test"),
+            serde_json::json!({
+                "old_file.txt": "line 1\nold line 2\nline 3\nline 4\nline 5\nline 6\nline 7\nold line 8\n",
+                "new_file.txt": "line 1\nnew line 2\nline 3\nline 4\nline 5\nline 6\nline 7\nnew line 8\n"
+            }),
+        )
+        .await;
+
+        let project = Project::test(fs.clone(), [path!("/test").as_ref()], cx).await;
+
+        let (multi_workspace, cx) =
+            cx.add_window_view(|window, cx| MultiWorkspace::test_new(project.clone(), window, cx));
+        let workspace = multi_workspace.read_with(cx, |mw, _| mw.workspace().clone());
+
+        let diff_view = workspace
+            .update_in(cx, |workspace, window, cx| {
+                FileDiffView::open(
+                    path!("/test/old_file.txt").into(),
+                    path!("/test/new_file.txt").into(),
+                    workspace.weak_handle(),
+                    window,
+                    cx,
+                )
+            })
+            .await
+            .unwrap();
+
+        diff_view.update(cx, |diff_view, cx| {
+            let editor = diff_view.diff_editor.read(cx).rhs_editor().read(cx);
+            let snapshot = editor.buffer().read(cx).snapshot(cx);
+            let offset = snapshot.text().find("new line 8").unwrap();
+            let anchor = snapshot.anchor_before(MultiBufferOffset(offset));
+            diff_view.copy_hunks_as_patch(&[anchor..anchor], cx);
+        });
+        assert_eq!(
+            cx.read_from_clipboard().and_then(|item| item.text()),
+            Some(unindent(
+                "
+                    --- a/old_file.txt
+                    +++ b/new_file.txt
+                    @@ -5,4 +5,4 @@
+                     line 5
+                     line 6
+                     line 7
+                    -old line 8
+                    +new line 8
+                    "
+            ))
+        );
+
+        diff_view.update(cx, |diff_view, cx| diff_view.copy_diff_as_patch(cx));
+        assert_eq!(
+            cx.read_from_clipboard().and_then(|item| item.text()),
+            Some(unindent(
+                "
+                    --- a/old_file.txt
+                    +++ b/new_file.txt
+                    @@ -1,8 +1,8 @@
+                     line 1
+                    -old line 2
+                    +new line 2
+                     line 3
+                     line 4
+                     line 5
+                     line 6
+                     line 7
+                    -old line 8
+                    +new line 8
+                    "
+            ))
+        );
+    }
+
+    #[gpui::test]
+    async fn test_save_diff_as(cx: &mut TestAppContext) {
+        init_test(cx);
+
+        let fs = FakeFs::new(cx.executor());
+        fs.insert_tree(
+            path!("/test"),
+            serde_json::json!({
+                "old_file.txt": "old line 1\nline 2\n",
+                "new_file.txt": "new line 1\nline 2\n"
+            }),
+        )
+        .await;
+
+        let project = Project::test(fs.clone(), [path!("/test").as_ref()], cx).await;
+
+        let (multi_workspace, cx) =
+            cx.add_window_view(|window, cx| MultiWorkspace::test_new(project.clone(), window, cx));
+        let workspace = multi_workspace.read_with(cx, |mw, _| mw.workspace().clone());
+
+        let diff_view = workspace
+            .update_in(cx, |workspace, window, cx| {
+                FileDiffView::open(
+                    path!("/test/old_file.txt").into(),
+                    path!("/test/new_file.txt").into(),
+                    workspace.weak_handle(),
+                    window,
+                    cx,
+                )
+            })
+            .await
+            .unwrap();
+
+        diff_view.update_in(cx, |diff_view, window, cx| {
+            diff_view.save_diff_as(window, cx)
+        });
+        cx.run_until_parked();
+        cx.simulate_new_path_selection(|_| Some(PathBuf::from(path!("/test/changes.patch"))));
+        cx.run_until_parked();
+
+        assert_eq!(
+            fs.load(path!("/test/changes.patch").as_ref())
+                .await
+                .unwrap(),
+            unindent(
+                "
+                --- a/old_file.txt
+                +++ b/new_file.txt
+                @@ -1,2 +1,2 @@
+                -old line 1
+                +new line 1
+                 line 2
+                "
+            )
+        );
+    }
+
     #[gpui::test]
     async fn test_save_changes_in_diff_view(cx: &mut TestAppContext) {
         init_test(cx);
@@ -552,7 +1662,8 @@ mod tests {
             .unwrap();
 
         diff_view.update_in(cx, |diff_view, window, cx| {
-            diff_view.editor.update(cx, |editor, cx| {
+            let editor = diff_view.diff_editor.read(cx).rhs_editor().clone();
+            editor.update(cx, |editor, cx| {
                 editor.insert("modified ", window, cx);
             });
         });
@@ -1,10 +1,11 @@
 use anyhow::Result;
 
-use git::repository::{FileHistory, FileHistoryEntry, RepoPath};
+use collections::HashMap;
+use git::repository::{CommitSignature, FileHistory, FileHistoryEntry, RepoPath};
 use git::{GitHostingProviderRegistry, GitRemote, parse_git_remote_url};
 use gpui::{
     AnyElement, AnyEntity, App, Context, Entity, EventEmitter, FocusHandle, Focusable, IntoElement,
-    Render, ScrollStrategy, Task, UniformListScrollHandle, WeakEntity, Window, uniform_list,
+    ListAlignment, ListState, Render, Task, WeakEntity, Window, list,
 };
 use project::{
     Project, ProjectPath,
@@ -14,18 +15,36 @@ use std::any::{Any, TypeId};
 use std::sync::Arc;
 
 use time::OffsetDateTime;
-use ui::{Chip, Divider, ListItem, WithScrollbar, prelude::*};
+use ui::{Chip, Disclosure, Divider, ListItem, Tooltip, WithScrollbar, prelude::*};
 use util::ResultExt;
 use workspace::{
     Item, Workspace,
     item::{ItemEvent, SaveOptions},
 };
 
-use crate::commit_tooltip::CommitAvatar;
+use crate::commit_tooltip::{CommitAvatar, render_signature_badge};
 use crate::commit_view::CommitView;
 
 const PAGE_SIZE: usize = 50;
 
+/// Lines of an expanded diff shown inline; the full diff is available in the commit view.
+const MAX_INLINE_DIFF_LINES: usize = 400;
+
+/// The change a commit made to the file, shown below the commit when it is expanded.
+enum InlineDiff {
+    Loading {
+        _load: Task<()>,
+    },
+    Loaded {
+        /// The file's path before the commit, when the commit renamed it.
+        renamed_from: Option<SharedString>,
+        lines: Vec<SharedString>,
+    },
+    Binary,
+    Unchanged,
+    Failed,
+}
+
 pub struct FileHistoryView {
     history: FileHistory,
     repository: WeakEntity<Repository>,
@@ -33,10 +52,12 @@ pub struct FileHistoryView {
     workspace: WeakEntity<Workspace>,
     remote: Option<GitRemote>,
     selected_entry: Option<usize>,
-    scroll_handle: UniformListScrollHandle,
+    list_state: ListState,
     focus_handle: FocusHandle,
     loading_more: bool,
     has_more: bool,
+    signatures: HashMap<SharedString, CommitSignature>,
+    inline_diffs: HashMap<SharedString, InlineDiff>,
 }
 
 impl FileHistoryView {
@@ -105,7 +126,7 @@ impl FileHistoryView {
         cx: &mut Context<Self>,
     ) -> Self {
         let focus_handle = cx.focus_handle();
-        let scroll_handle = UniformListScrollHandle::new();
+        let list_state = ListState::new(history.entries.len(), ListAlignment::Top, px(1000.));
         let has_more = history.entries.len() >= PAGE_SIZE;
 
         let snapshot = repository.read(cx).snapshot();
@@ -123,18 +144,43 @@ impl FileHistoryView {
             })
         });
 
-        Self {
+        let signed_shas = signed_shas(&history.entries);
+        let mut this = Self {
             history,
             git_store,
             repository: repository.downgrade(),
             workspace,
             remote,
             selected_entry: None,
-            scroll_handle,
+            list_state,
             focus_handle,
             loading_more: false,
             has_more,
+            signatures: HashMap::default(),
+            inline_diffs: HashMap::default(),
+        };
+        this.verify_signatures(signed_shas, cx);
+        this
+    }
+
+    fn verify_signatures(&mut self, shas: Vec<SharedString>, cx: &mut Context<Self>) {
+        if shas.is_empty() {
+            return;
         }
+        let Some(repository) = self.repository.upgrade() else {
+            return;
+        };
+        let verify_signatures = repository.update(cx, |repository, cx| {
+            repository.verify_commit_signatures(shas, cx)
+        });
+        cx.spawn(async move |this, cx| {
+            let signatures = verify_signatures.await?;
+            this.update(cx, |this, cx| {
+                this.signatures.extend(signatures);
+                cx.notify();
+            })
+        })
+        .detach_and_log_err(cx);
     }
 
     fn load_more(&mut self, window: &mut Window, cx: &mut Context<Self>) {
@@ -172,6 +218,10 @@ impl FileHistoryView {
                     this.update(cx, |this, cx| {
                         this.loading_more = false;
                         this.has_more = more_history.entries.len() >= PAGE_SIZE;
+                        this.verify_signatures(signed_shas(&more_history.entries), cx);
+                        let previous_count = this.history.entries.len();
+                        this.list_state
+                            .splice(previous_count..previous_count, more_history.entries.len());
                         this.history.entries.extend(more_history.entries);
                         cx.notify();
                     })
@@ -239,7 +289,7 @@ impl FileHistoryView {
     fn select_ix(&mut self, ix: Option<usize>, cx: &mut Context<Self>) {
         self.selected_entry = ix;
         if let Some(ix) = ix {
-            self.scroll_handle.scroll_to_item(ix, ScrollStrategy::Top);
+            self.list_state.scroll_to_reveal_item(ix);
         }
         cx.notify();
     }
@@ -270,6 +320,148 @@ impl FileHistoryView {
         }
     }
 
+    fn toggle_inline_diff(&mut self, ix: usize, cx: &mut Context<Self>) {
+        let Some(entry) = self.history.entries.get(ix) else {
+            return;
+        };
+        let sha = entry.sha.clone();
+        if self.inline_diffs.remove(&sha).is_none() {
+            let load = self.load_inline_diff(entry, cx);
+            self.inline_diffs
+                .insert(sha, InlineDiff::Loading { _load: load });
+        }
+        self.list_state.splice(ix..ix + 1, 1);
+        cx.notify();
+    }
+
+    /// Loads the file on both sides of the commit, following it across renames, rather than the
+    /// whole commit's diff.
+    fn load_inline_diff(&self, entry: &FileHistoryEntry, cx: &mut Context<Self>) -> Task<()> {
+        let Some(repository) = self.repository.upgrade() else {
+            return Task::ready(());
+        };
+        let sha = entry.sha.clone();
+        let path = entry
+            .path
+            .clone()
+            .unwrap_or_else(|| self.history.path.clone());
+        let renamed_from = entry.old_path.clone().filter(|old_path| *old_path != path);
+        let (old_text, new_text) = repository.update(cx, |repository, cx| {
+            (
+                repository.load_text_at_revision(
+                    format!("{sha}^"),
+                    renamed_from.clone().unwrap_or_else(|| path.clone()),
+                    cx,
+                ),
+                repository.load_text_at_revision(sha.to_string(), path, cx),
+            )
+        });
+
+        cx.spawn(async move |this, cx| {
+            // The first commit has no parent to compare with.
+            let old_text = old_text.await.ok().flatten();
+            let diff = match new_text.await.log_err() {
+                Some(None) if old_text.is_none() => InlineDiff::Binary,
+                Some(new_text) if renamed_from.is_none() && new_text == old_text => {
+                    InlineDiff::Unchanged
+                }
+                Some(new_text) => {
+                    let diff = language::unified_diff(
+                        old_text.as_deref().unwrap_or_default(),
+                        new_text.as_deref().unwrap_or_default(),
+                    );
+                    InlineDiff::Loaded {
+                        renamed_from: renamed_from
+                            .map(|old_path| old_path.as_unix_str().to_string().into()),
+                        lines: diff.lines().map(|line| line.to_string().into()).collect(),
+                    }
+                }
+                None => InlineDiff::Failed,
+            };
+
+            this.update(cx, |this, cx| {
+                let Some(inline_diff) = this.inline_diffs.get_mut(&sha) else {
+                    return;
+                };
+                *inline_diff = diff;
+                if let Some(ix) = this
+                    .history
+                    .entries
+                    .iter()
+                    .position(|entry| entry.sha == sha)
+                {
+                    this.list_state.splice(ix..ix + 1, 1);
+                }
+                cx.notify();
+            })
+            .ok();
+        })
+    }
+
+    fn render_inline_diff(&self, inline_diff: &InlineDiff, cx: &App) -> AnyElement {
+        let container = v_flex()
+            .w_full()
+            .py_1()
+            .pl_8()
+            .pr_2p5()
+            .bg(cx.theme().colors().editor_subheader_background)
+            .border_y_1()
+            .border_color(cx.theme().colors().border_variant);
+        let message = |text: &'static str| {
+            Label::new(text)
+                .size(LabelSize::Small)
+                .color(Color::Muted)
+                .into_any_element()
+        };
+
+        let inline_diff = match inline_diff {
+            InlineDiff::Loading { .. } => container.child(message("Loading diff…")),
+            InlineDiff::Binary => container.child(message("Binary file changed")),
+            InlineDiff::Unchanged => {
+                container.child(message("This commit did not change the file's contents"))
+            }
+            InlineDiff::Failed => container.child(message("Failed to load the diff")),
+            InlineDiff::Loaded {
+                renamed_from,
+                lines,
+            } => container
+                .when_some(renamed_from.clone(), |this, renamed_from| {
+                    this.child(
+                        Label::new(format!("Renamed from {renamed_from}"))
+                            .size(LabelSize::Small)
+                            .color(Color::Muted),
+                    )
+                })
+                .children(lines.iter().take(MAX_INLINE_DIFF_LINES).map(|line| {
+                    let color = if line.starts_with("@@") {
+                        Color::Accent
+                    } else if line.starts_with('+') {
+                        Color::Created
+                    } else if line.starts_with('-') {
+                        Color::Deleted
+                    } else {
+                        Color::Muted
+                    };
+                    Label::new(line.clone())
+                        .size(LabelSize::Small)
+                        .buffer_font(cx)
+                        .color(color)
+                        .into_any_element()
+                }))
+                .when(lines.len() > MAX_INLINE_DIFF_LINES, |this| {
+                    this.child(
+                        Label::new(format!(
+                            "{} more lines; open the commit to see the full diff",
+                            lines.len() - MAX_INLINE_DIFF_LINES
+                        ))
+                        .size(LabelSize::Small)
+                        .color(Color::Muted),
+                    )
+                }),
+        };
+        inline_diff.into_any_element()
+    }
+
     fn render_commit_avatar(
         &self,
         sha: &SharedString,
@@ -315,7 +507,9 @@ impl FileHistoryView {
             time_format::TimestampFormat::Relative,
         );
 
-        ListItem::new(("commit", ix))
+        let inline_diff = self.inline_diffs.get(&entry.sha);
+
+        let commit = ListItem::new(("commit", ix))
             .toggle_state(Some(ix) == self.selected_entry)
             .child(
                 h_flex()
@@ -324,6 +518,14 @@ impl FileHistoryView {
                     .pl_0p5()
                     .pr_2p5()
                     .gap_2()
+                    .child(
+                        Disclosure::new(("inline-diff", ix), inline_diff.is_some()).on_click(
+                            cx.listener(move |this, _, _, cx| {
+                                cx.stop_propagation();
+                                this.toggle_inline_diff(ix, cx);
+                            }),
+                        ),
+                    )
                     .child(
                         div()
                             .w(rems_from_px(52.))
@@ -360,11 +562,21 @@ impl FileHistoryView {
                                     ),
                             )
                             .child(
-                                h_flex().flex_none().child(
-                                    Label::new(relative_timestamp)
-                                        .size(LabelSize::Small)
-                                        .color(Color::Muted),
-                                ),
+                                h_flex()
+                                    .flex_none()
+                                    .gap_1()
+                                    .when(entry.signed, |this| {
+                                        this.child(match self.signatures.get(&entry.sha) {
+                                            Some(signature) => render_signature_badge(signature),
+                                            None => Chip::new("Signed")
+                                                .tooltip(Tooltip::text("This commit is signed")),
+                                        })
+                                    })
+                                    .child(
+                                        Label::new(relative_timestamp)
+                                            .size(LabelSize::Small)
+                                            .color(Color::Muted),
+                                    ),
                             ),
                     ),
             )
@@ -373,11 +585,24 @@ impl FileHistoryView {
                 cx.notify();
 
                 this.open_commit_view(window, cx);
-            }))
+            }));
+
+        v_flex()
+            .w_full()
+            .child(commit)
+            .children(inline_diff.map(|inline_diff| self.render_inline_diff(inline_diff, cx)))
             .into_any_element()
     }
 }
 
+fn signed_shas(entries: &[FileHistoryEntry]) -> Vec<SharedString> {
+    entries
+        .iter()
+        .filter(|entry| entry.signed)
+        .map(|entry| entry.sha.clone())
+        .collect()
+}
+
 impl EventEmitter<ItemEvent> for FileHistoryView {}
 
 impl Focusable for FileHistoryView {
@@ -447,31 +672,19 @@ impl Render for FileHistoryView {
                     .size_full()
                     .child({
                         let view = cx.weak_entity();
-                        uniform_list(
-                            "file-history-list",
-                            entry_count,
-                            move |range, window, cx| {
-                                let Some(view) = view.upgrade() else {
-                                    return Vec::new();
-                                };
-                                view.update(cx, |this, cx| {
-                                    let mut items = Vec::with_capacity(range.end - range.start);
-                                    for ix in range {
-                                        if let Some(entry) = this.history.entries.get(ix) {
-                                            items.push(
-                                                this.render_commit_entry(ix, entry, window, cx),
-                                            );
-                                        }
-                                    }
-                                    items
-                                })
-                            },
-                        )
+                        list(self.list_state.clone(), move |ix, window, cx| {
+                            let Some(view) = view.upgrade() else {
+                                return div().into_any_element();
+                            };
+                            view.update(cx, |this, cx| match this.history.entries.get(ix) {
+                                Some(entry) => this.render_commit_entry(ix, entry, window, cx),
+                                None => div().into_any_element(),
+                            })
+                        })
                         .flex_1()
                         .size_full()
-                        .track_scroll(&self.scroll_handle)
                     })
-                    .vertical_scrollbar_for(&self.scroll_handle, window, cx),
+                    .vertical_scrollbar_for(&self.list_state, window, cx),
             )
     }
 }
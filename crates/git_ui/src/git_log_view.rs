@@ -0,0 +1,561 @@
+//! GitLogView lists the commits of the repository, newest first. More of them are loaded as the
+//! list is scrolled, and a search narrows them down to the ones whose message or author matches.
+
+use std::time::Duration;
+
+use editor::{Editor, EditorEvent};
+use git::repository::CommitLogEntry;
+use gpui::{
+    AnyElement, App, Context, Entity, EventEmitter, FocusHandle, Focusable, IntoElement,
+    ListAlignment, ListScrollEvent, ListState, Render, SharedString, Subscription, Task,
+    WeakEntity, Window, list,
+};
+use project::git_store::{Repository, RepositoryEvent};
+use time::OffsetDateTime;
+use ui::{Chip, ListItem, WithScrollbar, prelude::*};
+use workspace::{Item, Workspace, item::ItemEvent};
+
+use crate::{commit_tooltip::CommitShaTooltip, commit_view::CommitView, resolve_active_repository};
+
+/// Commits loaded at a time.
+const PAGE_SIZE: usize = 100;
+
+/// How close to the end of the loaded commits the list can get before more are loaded.
+const LOAD_MORE_THRESHOLD: usize = 20;
+
+const SEARCH_DEBOUNCE: Duration = Duration::from_millis(150);
+
+pub fn register(workspace: &mut Workspace) {
+    workspace.register_action(|workspace, _: &git::OpenCommitLog, window, cx| {
+        let Some(repository) = resolve_active_repository(workspace, cx) else {
+            return;
+        };
+        GitLogView::open(repository, workspace, window, cx);
+    });
+}
+
+pub struct GitLogView {
+    repository: WeakEntity<Repository>,
+    workspace: WeakEntity<Workspace>,
+    entries: Vec<CommitLogEntry>,
+    search_editor: Entity<Editor>,
+    /// The search the entries are listed for, empty to list every commit.
+    query: String,
+    list_state: ListState,
+    selected_entry: Option<usize>,
+    loading: bool,
+    has_more: bool,
+    error: Option<SharedString>,
+    _load: Task<()>,
+    _search: Task<()>,
+    _subscriptions: [Subscription; 2],
+}
+
+impl GitLogView {
+    fn open(
+        repository: Entity<Repository>,
+        workspace: &mut Workspace,
+        window: &mut Window,
+        cx: &mut Context<Workspace>,
+    ) {
+        let existing = workspace
+            .active_pane()
+            .read(cx)
+            .items()
+            .find_map(|item| item.downcast::<GitLogView>())
+            .filter(|view| view.read(cx).repository == repository.downgrade());
+        if let Some(view) = existing {
+            workspace.activate_item(&view, true, true, window, cx);
+            return;
+        }
+
+        let workspace_handle = workspace.weak_handle();
+        let view = cx.new(|cx| GitLogView::new(repository, workspace_handle, window, cx));
+        workspace.active_pane().update(cx, |pane, cx| {
+            pane.add_item(Box::new(view), true, true, None, window, cx);
+        });
+    }
+
+    fn new(
+        repository: Entity<Repository>,
+        workspace: WeakEntity<Workspace>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Self {
+        let search_editor = cx.new(|cx| {
+            let mut editor = Editor::single_line(window, cx);
+            editor.set_placeholder_text("Search commit messages and authors…", window, cx);
+            editor
+        });
+        let list_state = ListState::new(0, ListAlignment::Top, px(1000.));
+        list_state.set_scroll_handler(cx.listener(|this, event: &ListScrollEvent, _, cx| {
+            if event.count.saturating_sub(event.visible_range.end) < LOAD_MORE_THRESHOLD {
+                this.load_more(cx);
+            }
+        }));
+        let subscriptions = [
+            cx.subscribe(&search_editor, |this, _, event: &EditorEvent, cx| {
+                if let EditorEvent::BufferEdited = event {
+                    this.search_changed(cx);
+                }
+            }),
+            cx.subscribe(&repository, |this, _, event: &RepositoryEvent, cx| {
+                if let RepositoryEvent::BranchChanged = event {
+                    this.reload(cx);
+                }
+            }),
+        ];
+
+        let mut this = Self {
+            repository: repository.downgrade(),
+            workspace,
+            entries: Vec::new(),
+            search_editor,
+            query: String::new(),
+            list_state,
+            selected_entry: None,
+            loading: false,
+            has_more: true,
+            error: None,
+            _load: Task::ready(()),
+            _search: Task::ready(()),
+            _subscriptions: subscriptions,
+        };
+        this.load_more(cx);
+        this
+    }
+
+    fn search_changed(&mut self, cx: &mut Context<Self>) {
+        let query = self.search_editor.read(cx).text(cx).trim().to_string();
+        self._search = cx.spawn(async move |this, cx| {
+            cx.background_executor().timer(SEARCH_DEBOUNCE).await;
+            this.update(cx, |this, cx| {
+                if this.query != query {
+                    this.query = query;
+                    this.reload(cx);
+                }
+            })
+            .ok();
+        });
+    }
+
+    /// Lists the commits again from the newest one, dropping any page that is still loading.
+    fn reload(&mut self, cx: &mut Context<Self>) {
+        self.entries.clear();
+        self.list_state.reset(0);
+        self.selected_entry = None;
+        self.loading = false;
+        self.has_more = true;
+        self.load_more(cx);
+    }
+
+    fn load_more(&mut self, cx: &mut Context<Self>) {
+        if self.loading || !self.has_more {
+            return;
+        }
+        let Some(repository) = self.repository.upgrade() else {
+            return;
+        };
+        let query = Some(self.query.clone()).filter(|query| !query.is_empty());
+        let skip = self.entries.len();
+        let commit_log = repository.update(cx, |repository, _| {
+            repository.commit_log(query, skip, PAGE_SIZE)
+        });
+        self.loading = true;
+        self._load = cx.spawn(async move |this, cx| {
+            let commit_log = commit_log
+                .await
+                .map_err(anyhow::Error::from)
+                .and_then(|result| result);
+            this.update(cx, |this, cx| {
+                this.loading = false;
+                match commit_log {
+                    Ok(entries) => {
+                        this.has_more = entries.len() >= PAGE_SIZE;
+                        let count = this.entries.len();
+                        this.list_state.splice(count..count, entries.len());
+                        this.entries.extend(entries);
+                        this.error = None;
+                    }
+                    Err(error) => {
+                        this.has_more = false;
+                        this.error = Some(error.to_string().into());
+                    }
+                }
+                cx.notify();
+            })
+            .ok();
+        });
+        cx.notify();
+    }
+
+    fn select_next(&mut self, _: &menu::SelectNext, _: &mut Window, cx: &mut Context<Self>) {
+        let ix = match self.selected_entry {
+            _ if self.entries.is_empty() => None,
+            None => Some(0),
+            Some(ix) => Some((ix + 1).min(self.entries.len() - 1)),
+        };
+        self.select_ix(ix, cx);
+    }
+
+    fn select_previous(
+        &mut self,
+        _: &menu::SelectPrevious,
+        _: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let ix = match self.selected_entry {
+            _ if self.entries.is_empty() => None,
+            None => Some(0),
+            Some(ix) => Some(ix.saturating_sub(1)),
+        };
+        self.select_ix(ix, cx);
+    }
+
+    fn select_first(&mut self, _: &menu::SelectFirst, _: &mut Window, cx: &mut Context<Self>) {
+        let ix = (!self.entries.is_empty()).then_some(0);
+        self.select_ix(ix, cx);
+    }
+
+    fn select_last(&mut self, _: &menu::SelectLast, _: &mut Window, cx: &mut Context<Self>) {
+        let ix = self.entries.len().checked_sub(1);
+        self.select_ix(ix, cx);
+    }
+
+    fn select_ix(&mut self, ix: Option<usize>, cx: &mut Context<Self>) {
+        self.selected_entry = ix;
+        if let Some(ix) = ix {
+            self.list_state.scroll_to_reveal_item(ix);
+            if ix + LOAD_MORE_THRESHOLD >= self.entries.len() {
+                self.load_more(cx);
+            }
+        }
+        cx.notify();
+    }
+
+    fn confirm(&mut self, _: &menu::Confirm, window: &mut Window, cx: &mut Context<Self>) {
+        if let Some(ix) = self.selected_entry {
+            self.open_commit(ix, window, cx);
+        }
+    }
+
+    fn open_commit(&self, ix: usize, window: &mut Window, cx: &mut App) {
+        if let Some(entry) = self.entries.get(ix) {
+            CommitView::open(
+                entry.sha.to_string(),
+                self.repository.clone(),
+                self.workspace.clone(),
+                None,
+                None,
+                window,
+                cx,
+            );
+        }
+    }
+
+    fn render_entry(&self, ix: usize, entry: &CommitLogEntry, cx: &Context<Self>) -> AnyElement {
+        let short_sha: String = entry.sha.chars().take(git::SHORT_SHA_LENGTH).collect();
+        let commit_time = OffsetDateTime::from_unix_timestamp(entry.commit_timestamp)
+            .unwrap_or(OffsetDateTime::UNIX_EPOCH);
+        let relative_time = time_format::format_localized_timestamp(
+            commit_time,
+            OffsetDateTime::now_utc(),
+            time::UtcOffset::current_local_offset().unwrap_or(time::UtcOffset::UTC),
+            time_format::TimestampFormat::Relative,
+        );
+
+        ListItem::new(("commit", ix))
+            .toggle_state(Some(ix) == self.selected_entry)
+            .child(
+                h_flex()
+                    .h_8()
+                    .w_full()
+                    .px_2()
+                    .gap_2()
+                    .child(
+                        div()
+                            .id(("commit-sha", ix))
+                            .child(
+                                Label::new(short_sha)
+                                    .size(LabelSize::Small)
+                                    .color(Color::Muted)
+                                    .buffer_font(cx),
+                            )
+                            .when_some(self.repository.upgrade(), |this, repository| {
+                                let sha = entry.sha.clone();
+                                let workspace = self.workspace.clone();
+                                this.hoverable_tooltip(move |window, cx| {
+                                    CommitShaTooltip::new(
+                                        sha.clone(),
+                                        repository.clone(),
+                                        workspace.clone(),
+                                        window,
+                                        cx,
+                                    )
+                                    .into()
+                                })
+                            }),
+                    )
+                    .children(entry.refs.iter().map(|name| Chip::new(name.clone())))
+                    .child(
+                        div().flex_1().min_w_0().child(
+                            Label::new(entry.subject.clone())
+                                .size(LabelSize::Small)
+                                .single_line()
+                                .truncate(),
+                        ),
+                    )
+                    .child(
+                        Label::new(entry.author_name.clone())
+                            .size(LabelSize::Small)
+                            .color(Color::Muted)
+                            .single_line(),
+                    )
+                    .child(
+                        Label::new(relative_time)
+                            .size(LabelSize::Small)
+                            .color(Color::Muted),
+                    ),
+            )
+            .on_click(cx.listener(move |this, _, window, cx| {
+                this.selected_entry = Some(ix);
+                cx.notify();
+                this.open_commit(ix, window, cx);
+            }))
+            .into_any_element()
+    }
+}
+
+impl EventEmitter<ItemEvent> for GitLogView {}
+
+impl Focusable for GitLogView {
+    fn focus_handle(&self, cx: &App) -> FocusHandle {
+        self.search_editor.focus_handle(cx)
+    }
+}
+
+impl Render for GitLogView {
+    fn render(&mut self, window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let empty_message = if self.query.is_empty() {
+            "The repository has no commits"
+        } else {
+            "No commits match the search"
+        };
+
+        v_flex()
+            .key_context("GitLogView")
+            .on_action(cx.listener(Self::select_next))
+            .on_action(cx.listener(Self::select_previous))
+            .on_action(cx.listener(Self::select_first))
+            .on_action(cx.listener(Self::select_last))
+            .on_action(cx.listener(Self::confirm))
+            .size_full()
+            .bg(cx.theme().colors().editor_background)
+            .child(
+                h_flex()
+                    .h(rems_from_px(41.))
+                    .px_2()
+                    .gap_2()
+                    .border_b_1()
+                    .border_color(cx.theme().colors().border_variant)
+                    .child(
+                        Icon::new(IconName::MagnifyingGlass)
+                            .size(IconSize::Small)
+                            .color(Color::Muted),
+                    )
+                    .child(div().flex_1().child(self.search_editor.clone()))
+                    .when(self.loading, |this| {
+                        this.child(
+                            Label::new("Loading…")
+                                .size(LabelSize::Small)
+                                .color(Color::Muted),
+                        )
+                    }),
+            )
+            .children(self.error.clone().map(|error| {
+                h_flex().px_2().py_1().child(
+                    Label::new(error)
+                        .size(LabelSize::Small)
+                        .color(Color::Error)
+                        .single_line()
+                        .truncate(),
+                )
+            }))
+            .when(
+                self.entries.is_empty() && !self.loading && self.error.is_none(),
+                |this| {
+                    this.child(
+                        h_flex()
+                            .p_4()
+                            .justify_center()
+                            .child(Label::new(empty_message).color(Color::Muted)),
+                    )
+                },
+            )
+            .child(
+                v_flex()
+                    .flex_1()
+                    .size_full()
+                    .child({
+                        let view = cx.weak_entity();
+                        list(self.list_state.clone(), move |ix, _, cx| {
+                            let Some(view) = view.upgrade() else {
+                                return div().into_any_element();
+                            };
+                            view.update(cx, |this, cx| match this.entries.get(ix) {
+                                Some(entry) => this.render_entry(ix, entry, cx),
+                                None => div().into_any_element(),
+                            })
+                        })
+                        .flex_1()
+                        .size_full()
+                    })
+                    .vertical_scrollbar_for(&self.list_state, window, cx),
+            )
+    }
+}
+
+impl Item for GitLogView {
+    type Event = ItemEvent;
+
+    fn to_item_events(event: &Self::Event, f: &mut dyn FnMut(ItemEvent)) {
+        f(*event)
+    }
+
+    fn tab_content_text(&self, _detail: usize, _cx: &App) -> SharedString {
+        "Commit Log".into()
+    }
+
+    fn tab_icon(&self, _window: &Window, _cx: &App) -> Option<Icon> {
+        Some(Icon::new(IconName::GitBranch))
+    }
+
+    fn telemetry_event_text(&self) -> Option<&'static str> {
+        Some("commit log")
+    }
+
+    fn added_to_workspace(
+        &mut self,
+        _workspace: &mut Workspace,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        window.focus(&self.search_editor.focus_handle(cx), cx);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use gpui::{TestAppContext, VisualTestContext};
+    use project::{FakeFs, Project};
+    use serde_json::json;
+    use settings::SettingsStore;
+    use theme::LoadThemes;
+    use util::path;
+    use workspace::MultiWorkspace;
+
+    use super::*;
+
+    fn entry(index: usize, subject: &str, author_name: &str) -> CommitLogEntry {
+        CommitLogEntry {
+            sha: format!("{index:040x}").into(),
+            subject: subject.to_string().into(),
+            commit_timestamp: 1_700_000_000 - index as i64 * 60,
+            author_name: author_name.to_string().into(),
+            author_email: format!("{}@example.com", author_name.to_lowercase()).into(),
+            refs: Vec::new(),
+        }
+    }
+
+    #[gpui::test]
+    async fn test_commit_log_pages_and_search(cx: &mut TestAppContext) {
+        zlog::init_test();
+        cx.update(|cx| {
+            let settings_store = SettingsStore::test(cx);
+            cx.set_global(settings_store);
+            theme::init(LoadThemes::JustBase, cx);
+            editor::init(cx);
+            crate::init(cx);
+        });
+
+        let fs = FakeFs::new(cx.background_executor.clone());
+        fs.insert_tree(
+            "/root",
+            json!({
+                "project": {
+                    ".git": {},
+                    "file.txt": "content",
+                }
+            }),
+        )
+        .await;
+        fs.with_git_state(Path::new(path!("/root/project/.git")), false, |state| {
+            state.commit_log = (0..PAGE_SIZE + 10)
+                .map(|index| {
+                    if index % 50 == 0 {
+                        entry(index, &format!("Fix the parser, part {index}"), "Grace")
+                    } else {
+                        entry(index, &format!("Change {index}"), "Ada")
+                    }
+                })
+                .collect();
+        })
+        .unwrap();
+
+        let project = Project::test(fs.clone(), [Path::new(path!("/root/project"))], cx).await;
+        let window_handle =
+            cx.add_window(|window, cx| MultiWorkspace::test_new(project.clone(), window, cx));
+        let workspace = window_handle
+            .read_with(cx, |mw, _| mw.workspace().clone())
+            .unwrap();
+        let cx = &mut VisualTestContext::from_window(window_handle.into(), cx);
+        cx.run_until_parked();
+
+        let repository =
+            project.read_with(cx, |project, cx| project.active_repository(cx).unwrap());
+        workspace.update_in(cx, |workspace, window, cx| {
+            GitLogView::open(repository, workspace, window, cx)
+        });
+        cx.run_until_parked();
+        let view = workspace
+            .read_with(cx, |workspace, cx| {
+                workspace.active_item_as::<GitLogView>(cx)
+            })
+            .unwrap();
+        view.read_with(cx, |view, _| {
+            assert_eq!(view.entries.len(), PAGE_SIZE);
+            assert!(view.has_more);
+        });
+
+        view.update(cx, |view, cx| view.load_more(cx));
+        cx.run_until_parked();
+        view.read_with(cx, |view, _| {
+            assert_eq!(view.entries.len(), PAGE_SIZE + 10);
+            assert!(!view.has_more);
+        });
+
+        let search_editor = view.read_with(cx, |view, _| view.search_editor.clone());
+        search_editor.update_in(cx, |editor, window, cx| {
+            editor.set_text("grace", window, cx)
+        });
+        cx.executor().advance_clock(SEARCH_DEBOUNCE);
+        cx.run_until_parked();
+        view.read_with(cx, |view, _| {
+            let subjects = view
+                .entries
+                .iter()
+                .map(|entry| entry.subject.as_ref())
+                .collect::<Vec<_>>();
+            assert_eq!(
+                subjects,
+                [
+                    "Fix the parser, part 0",
+                    "Fix the parser, part 50",
+                    "Fix the parser, part 100"
+                ]
+            );
+        });
+    }
+}
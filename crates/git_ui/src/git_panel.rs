@@ -1,13 +1,21 @@
-use crate::askpass_modal::AskPassModal;
+use crate::askpass_modal::{AskPassModal, CredentialRequest, PassphraseKey, SessionPassphrases};
+use crate::bisect;
+use crate::commit_message_history::CommitMessages;
+use crate::commit_message_suggestion::{ChangeKind, summarize_changes};
 use crate::commit_modal::CommitModal;
-use crate::commit_tooltip::CommitTooltip;
+use crate::commit_tooltip::CommitShaTooltip;
 use crate::commit_view::CommitView;
+use crate::conventional_commits::CommitMessageCompletionProvider;
+use crate::issue_references::IssueReferences;
 use crate::project_diff::{self, BranchDiff, Diff, ProjectDiff};
+use crate::pull_request_modal::{PullRequestModal, PullRequestTarget};
+use crate::pull_request_picker::{PullRequestPicker, PullRequestRemote};
 use crate::remote_output::{self, RemoteAction, SuccessMessage};
-use crate::{branch_picker, picker_prompt, render_remote_button};
+use crate::stash_picker::{StashModal, show_stash_apply_error};
+use crate::{branch_picker, picker_prompt, render_remote_button, repository_settings};
 use crate::{
     file_history_view::FileHistoryView, git_panel_settings::GitPanelSettings, git_status_icon,
-    repository_selector::RepositorySelector,
+    repository_selector::RepositorySelector, three_way_diff_view::ThreeWayDiffView,
 };
 use agent_settings::AgentSettings;
 use anyhow::Context as _;
@@ -15,31 +23,35 @@ use askpass::AskPassDelegate;
 use collections::{BTreeMap, HashMap, HashSet};
 use db::kvp::KeyValueStore;
 use editor::{
-    Direction, Editor, EditorElement, EditorMode, MultiBuffer, MultiBufferOffset,
-    actions::ExpandAllDiffHunks,
+    Direction, DiscardedHunk, Editor, EditorElement, EditorEvent, EditorMode, MultiBuffer,
+    MultiBufferOffset, actions::ExpandAllDiffHunks,
 };
 use editor::{EditorStyle, RewrapOptions};
 use feature_flags::{FeatureFlagAppExt as _, GitGraphFeatureFlag};
 use file_icons::FileIcons;
 use futures::StreamExt as _;
-use git::commit::ParsedCommitMessage;
+use git::index_flags::{FlaggedFile, IndexFlag};
 use git::repository::{
-    Branch, CommitDetails, CommitOptions, CommitSummary, DiffType, FetchOptions, GitCommitter,
-    PushOptions, Remote, RemoteCommandOutput, ResetMode, Upstream, UpstreamTracking,
-    UpstreamTrackingStatus, get_git_committer,
+    BisectMark, BisectStatus, Branch, CommitDetails, CommitOptions, CommitSummary, DiffType,
+    FetchOptions, GitCommitter, InProgressOperation, InProgressOperationKind, OperationResolution,
+    PULL_NOT_FAST_FORWARD, PUSH_REJECTED, PullStrategy, PushOptions, Remote, RemoteCommandOutput,
+    RemoteInfo, ResetMode, StashOptions, Upstream, UpstreamTracking, UpstreamTrackingStatus,
+    get_git_committer,
 };
 use git::stash::GitStash;
 use git::status::{DiffStat, StageStatus};
+use git::submodule::{Submodule, SubmoduleState};
 use git::{Amend, Signoff, ToggleStaged, repository::RepoPath, status::FileStatus};
 use git::{
-    ExpandCommitEditor, GitHostingProviderRegistry, RestoreTrackedFiles, StageAll, StashAll,
-    StashApply, StashPop, TrashUntrackedFiles, UnstageAll,
+    ExpandCommitEditor, GitHostingProvider, GitHostingProviderRegistry, ParsedGitRemote,
+    RestoreTrackedFiles, StageAll, StashAll, StashApply, StashPop, StashWithMessage,
+    TrashUntrackedFiles, UndoDiscard, UnstageAll,
 };
 use gpui::{
     Action, AsyncApp, AsyncWindowContext, Bounds, ClickEvent, Corner, DismissEvent, Empty, Entity,
     EventEmitter, FocusHandle, Focusable, KeyContext, MouseButton, MouseDownEvent, Point,
-    PromptLevel, ScrollStrategy, Subscription, Task, TextStyle, UniformListScrollHandle,
-    WeakEntity, actions, anchored, deferred, point, size, uniform_list,
+    PromptLevel, ScrollHandle, ScrollStrategy, Subscription, Task, TextStyle,
+    UniformListScrollHandle, WeakEntity, actions, anchored, deferred, point, size, uniform_list,
 };
 use itertools::Itertools;
 use language::{Buffer, File};
@@ -52,8 +64,8 @@ use multi_buffer::ExcerptInfo;
 use notifications::status_toast::{StatusToast, ToastIcon};
 use panel::{PanelHeader, panel_button, panel_filled_button, panel_icon_button};
 use project::{
-    Fs, Project, ProjectPath,
-    git_store::{GitStoreEvent, Repository, RepositoryEvent, RepositoryId, pending_op},
+    ConflictSet, Fs, Project, ProjectPath,
+    git_store::{GitStoreEvent, HookStatus, Repository, RepositoryEvent, RepositoryId, pending_op},
     project_settings::{GitPathStyle, ProjectSettings},
 };
 use prompt_store::{BuiltInPrompt, PromptId, PromptStore, RULES_FILE_NAMES};
@@ -64,14 +76,14 @@ use smallvec::SmallVec;
 use std::future::Future;
 use std::ops::Range;
 use std::path::Path;
+use std::rc::Rc;
 use std::{sync::Arc, time::Duration, usize};
 use strum::{IntoEnumIterator, VariantNames};
 use theme::ThemeSettings;
-use time::OffsetDateTime;
 use ui::{
-    ButtonLike, Checkbox, CommonAnimationExt, ContextMenu, ElevationIndex, IndentGuideColors,
-    PopoverMenu, RenderedIndentGuide, ScrollAxes, Scrollbars, SplitButton, Tooltip, WithScrollbar,
-    prelude::*,
+    ButtonLike, Checkbox, Chip, CommonAnimationExt, ContextMenu, Disclosure, ElevationIndex,
+    IndentGuideColors, PopoverMenu, ProgressBar, RenderedIndentGuide, ScrollAxes, Scrollbars,
+    SplitButton, Tooltip, WithScrollbar, prelude::*,
 };
 use util::paths::PathStyle;
 use util::{ResultExt, TryFutureExt, maybe, rel_path::RelPath};
@@ -133,6 +145,72 @@ pub struct OpenAtCommit {
     pub sha: String,
 }
 
+/// Returns the text to append to `message` so that it ends with `Co-authored-by` trailers for
+/// each of the given co-authors it doesn't mention yet.
+fn co_author_trailers(
+    message: &str,
+    co_authors: impl IntoIterator<Item = (String, String)>,
+) -> Option<String> {
+    const CO_AUTHOR_PREFIX: &str = "Co-authored-by: ";
+
+    let existing_text = message.to_ascii_lowercase();
+    let lowercase_co_author_prefix = CO_AUTHOR_PREFIX.to_lowercase();
+    let mut ends_with_co_authors = false;
+    let existing_co_authors = existing_text
+        .lines()
+        .filter_map(|line| {
+            let line = line.trim();
+            if line.starts_with(&lowercase_co_author_prefix) {
+                ends_with_co_authors = true;
+                Some(line)
+            } else {
+                ends_with_co_authors = false;
+                None
+            }
+        })
+        .collect::<HashSet<_>>();
+
+    let new_co_authors = co_authors
+        .into_iter()
+        .filter(|(_, email)| {
+            let email = email.to_ascii_lowercase();
+            !existing_co_authors
+                .iter()
+                .any(|existing| existing.contains(email.as_str()))
+        })
+        .collect::<Vec<_>>();
+
+    if new_co_authors.is_empty() {
+        return None;
+    }
+
+    let mut trailers = String::new();
+    if !ends_with_co_authors {
+        trailers.push('\n');
+    }
+    for (name, email) in new_co_authors {
+        trailers.push('\n');
+        trailers.push_str(CO_AUTHOR_PREFIX);
+        trailers.push_str(&name);
+        trailers.push_str(" <");
+        trailers.push_str(&email);
+        trailers.push('>');
+    }
+    trailers.push('\n');
+    Some(trailers)
+}
+
+/// Drops comment and blank lines, so that messages which only differ in what
+/// `git commit --cleanup=strip` removes compare equal.
+fn strip_commit_message_comments(message: &str) -> String {
+    message
+        .lines()
+        .filter(|line| !line.starts_with('#'))
+        .map(str::trim_end)
+        .filter(|line| !line.is_empty())
+        .join("\n")
+}
+
 fn prompt<T>(
     msg: &str,
     detail: Option<&str>,
@@ -153,6 +231,18 @@ enum TrashCancel {
     Cancel,
 }
 
+/// How a push should behave, as picked from the push dropdown.
+#[derive(Clone, Copy, Debug, Default)]
+pub(crate) struct PushBehavior {
+    pub force: bool,
+    /// Ask which remote to push to instead of using the branch's.
+    pub select_remote: bool,
+    /// Make the pushed branch the upstream even when the branch tracks one already.
+    pub set_upstream: bool,
+    /// Also push the annotated tags that point at the pushed commits.
+    pub follow_tags: bool,
+}
+
 struct GitMenuState {
     has_tracked_changes: bool,
     has_staged_changes: bool,
@@ -160,7 +250,12 @@ struct GitMenuState {
     has_new_changes: bool,
     sort_by_path: bool,
     has_stash_items: bool,
+    has_discarded_hunks: bool,
     tree_view: bool,
+    repository: Option<Entity<Repository>>,
+    remotes: Option<Arc<[RemoteInfo]>>,
+    /// Remotes can't be managed over collab yet, so guests don't get the remotes section.
+    can_manage_remotes: bool,
 }
 
 fn git_panel_context_menu(
@@ -169,7 +264,12 @@ fn git_panel_context_menu(
     window: &mut Window,
     cx: &mut App,
 ) -> Entity<ContextMenu> {
-    ContextMenu::build(window, cx, move |context_menu, _, _| {
+    ContextMenu::build(window, cx, move |context_menu, _, cx| {
+        let has_upstream = state
+            .repository
+            .as_ref()
+            .and_then(|repository| repository.read(cx).branch.as_ref())
+            .is_some_and(|branch| branch.upstream.is_some());
         context_menu
             .context(focus_handle)
             .action_disabled_when(
@@ -188,10 +288,24 @@ fn git_panel_context_menu(
                 "Stash All",
                 StashAll.boxed_clone(),
             )
+            .action_disabled_when(
+                !(state.has_new_changes || state.has_tracked_changes),
+                "Stash With Message…",
+                StashWithMessage.boxed_clone(),
+            )
             .action_disabled_when(!state.has_stash_items, "Stash Pop", StashPop.boxed_clone())
             .action("View Stash", zed_actions::git::ViewStash.boxed_clone())
             .separator()
             .action("Open Diff", project_diff::Diff.boxed_clone())
+            .action_disabled_when(
+                !has_upstream,
+                "Diff Against Upstream",
+                git::DiffAgainstUpstream.boxed_clone(),
+            )
+            .action(
+                "Diff Against Merge Base",
+                git::DiffAgainstMergeBase.boxed_clone(),
+            )
             .separator()
             .action_disabled_when(
                 !state.has_tracked_changes,
@@ -203,6 +317,11 @@ fn git_panel_context_menu(
                 "Trash Untracked Files",
                 TrashUntrackedFiles.boxed_clone(),
             )
+            .action_disabled_when(
+                !state.has_discarded_hunks,
+                "Undo Discard",
+                UndoDiscard.boxed_clone(),
+            )
             .separator()
             .entry(
                 if state.tree_view {
@@ -224,12 +343,130 @@ fn git_panel_context_menu(
                     move |window, cx| window.dispatch_action(Box::new(ToggleSortByPath), cx),
                 )
             })
+            .when(state.can_manage_remotes, |menu| {
+                menu.separator()
+                    .header("Remotes")
+                    .map(|mut menu| {
+                        let remotes = state.remotes.clone().unwrap_or_default();
+                        // With a single remote there is nothing to pick, so it is only listed.
+                        let can_pick = remotes.len() > 1;
+                        for remote in remotes.iter().cloned() {
+                            let render = {
+                                let remote = remote.clone();
+                                move |_: &mut Window, _: &mut App| {
+                                    render_remote_menu_entry(&remote, can_pick).into_any_element()
+                                }
+                            };
+                            menu = match state.repository.clone().filter(|_| can_pick) {
+                                Some(repository) => menu.custom_entry(render, move |_, cx| {
+                                    set_default_remote(&repository, remote.name.to_string(), cx)
+                                }),
+                                None => menu.custom_row(render),
+                            };
+                        }
+                        menu
+                    })
+                    .action("Manage Remotes…", git::ManageRemotes.boxed_clone())
+            })
+    })
+}
+
+fn render_remote_menu_entry(remote: &RemoteInfo, show_default: bool) -> impl IntoElement {
+    let url = remote.push_url.clone().or_else(|| remote.fetch_url.clone());
+    h_flex()
+        .w_full()
+        .gap_1p5()
+        .when(show_default, |this| {
+            this.child(Icon::new(IconName::Check).size(IconSize::Small).color(
+                if remote.is_default {
+                    Color::Accent
+                } else {
+                    Color::Hidden
+                },
+            ))
+        })
+        .child(
+            v_flex()
+                .min_w_0()
+                .child(Label::new(remote.name.clone()))
+                .when_some(url, |this, url| {
+                    this.child(
+                        Label::new(url)
+                            .size(LabelSize::Small)
+                            .color(Color::Muted)
+                            .truncate(),
+                    )
+                }),
+        )
+}
+
+/// Describes the checkout of a submodule, going by its own repository once it's checked out.
+fn submodule_status(submodule: &Submodule, repository: Option<&Repository>) -> (String, Color) {
+    let changed_commit = match submodule.state {
+        SubmoduleState::Uninitialized => return ("Not initialized".into(), Color::Muted),
+        SubmoduleState::Conflicted => {
+            return ("Conflicted".into(), Color::VersionControlConflict);
+        }
+        SubmoduleState::UpToDate => false,
+        SubmoduleState::CommitChanged => true,
+    };
+
+    let mut parts = Vec::new();
+    if changed_commit {
+        parts.push("new commits".to_string());
+    }
+    if let Some(repository) = repository {
+        let changes = repository.status_summary().count;
+        if changes > 0 {
+            parts.push(format!("{changes} changed"));
+        }
+        if let Some(tracking) = repository
+            .branch
+            .as_ref()
+            .and_then(|branch| branch.upstream.as_ref())
+            .and_then(|upstream| upstream.tracking.status())
+        {
+            if tracking.ahead > 0 {
+                parts.push(format!("↑{}", tracking.ahead));
+            }
+            if tracking.behind > 0 {
+                parts.push(format!("↓{}", tracking.behind));
+            }
+        }
+    }
+
+    if parts.is_empty() {
+        ("Up to date".into(), Color::Muted)
+    } else {
+        (parts.join(" · "), Color::VersionControlModified)
+    }
+}
+
+/// Makes push and pull target the remote when the branch doesn't track one, then relists the
+/// remotes so that the menu shows the new default.
+fn set_default_remote(repository: &Entity<Repository>, remote_name: String, cx: &mut App) {
+    let update = repository.update(cx, |repository, _| {
+        repository.set_default_remote(Some(remote_name))
+    });
+    let repository = repository.clone();
+    cx.spawn(async move |cx| {
+        update.await.ok()?.log_err()?;
+        repository
+            .update(cx, |repository, _| repository.remote_infos())
+            .await
+            .ok();
+        Some(())
     })
+    .detach();
 }
 
 const GIT_PANEL_KEY: &str = "GitPanel";
 
 const UPDATE_DEBOUNCE: Duration = Duration::from_millis(50);
+const MAX_DISCARD_HISTORY: usize = 50;
+const MAX_CO_AUTHOR_CANDIDATE_COMMITS: usize = 200;
+/// Conflicted files listed by name in the banner shown while an operation is in progress.
+const MAX_BANNER_CONFLICTS: usize = 5;
 // TODO: We should revise this part. It seems the indentation width is not aligned with the one in project panel
 const TREE_INDENT: f32 = 16.0;
 
@@ -250,6 +487,31 @@ pub fn register(workspace: &mut Workspace) {
             panel.update(cx, |panel, cx| panel.git_init(window, cx));
         }
     });
+    workspace.register_action(|workspace, _: &UndoDiscard, _, cx| {
+        if let Some(panel) = workspace.panel::<GitPanel>(cx) {
+            panel.update(cx, |panel, cx| panel.undo_discard(cx));
+        }
+    });
+}
+
+/// Records the hunks `editor` discards in its workspace's git panel, so that they can be brought
+/// back with [`UndoDiscard`].
+pub(crate) fn register_editor(cx: &mut Context<Editor>) {
+    cx.subscribe(&cx.entity(), |editor, _, event, cx| {
+        let EditorEvent::HunksDiscarded { hunks } = event else {
+            return;
+        };
+        let Some(panel) = editor
+            .workspace()
+            .and_then(|workspace| workspace.read(cx).panel::<GitPanel>(cx))
+        else {
+            return;
+        };
+        panel.update(cx, |panel, cx| {
+            panel.record_discarded_hunks(hunks.clone(), cx)
+        });
+    })
+    .detach();
 }
 
 #[derive(Debug, Clone)]
@@ -620,6 +882,8 @@ pub struct GitPanel {
     conflicted_count: usize,
     conflicted_staged_count: usize,
     add_coauthors: bool,
+    hook_output_expanded: bool,
+    hook_output_scroll_handle: ScrollHandle,
     generate_commit_message_task: Option<Task<Option<()>>>,
     entries: Vec<GitListEntry>,
     view_mode: GitPanelViewMode,
@@ -635,13 +899,18 @@ pub struct GitPanel {
     pending_commit: Option<Task<()>>,
     amend_pending: bool,
     original_commit_message: Option<String>,
+    commit_template: Option<String>,
     signoff_enabled: bool,
     pending_serialization: Task<()>,
+    /// Draft and history of the active repository, once loaded, with its work directory.
+    commit_messages: Option<(Arc<Path>, CommitMessages)>,
+    pending_draft_serialization: Task<()>,
+    _commit_buffer_subscription: Option<Subscription>,
     pub(crate) project: Entity<Project>,
     scroll_handle: UniformListScrollHandle,
     max_width_item_index: Option<usize>,
     selected_entry: Option<usize>,
-    marked_entries: Vec<usize>,
+    marked_entries: HashSet<RepoPath>,
     tracked_count: usize,
     tracked_staged_count: usize,
     update_visible_entries_task: Task<()>,
@@ -653,10 +922,47 @@ pub struct GitPanel {
     local_committer_task: Option<Task<()>>,
     bulk_staging: Option<BulkStaging>,
     stash_entries: GitStash,
+    /// The working-tree text of recently discarded hunks, most recent last, kept for the session.
+    discarded_hunks: Vec<Vec<DiscardedHunk>>,
+    /// The entries git-lfs filters, as of the last update of the entries.
+    lfs_paths: std::collections::HashSet<RepoPath>,
+    load_lfs_paths_task: Task<()>,
+    /// The submodules of the active repository, as of the last update of the entries.
+    submodules: Vec<Submodule>,
+    /// What `submodules` were last loaded for, so that they're only reloaded when they could
+    /// have changed.
+    submodules_key: Option<SubmodulesKey>,
+    submodules_expanded: bool,
+    load_submodules_task: Task<()>,
 
     _settings_subscription: Subscription,
 }
 
+/// The state of a repository that its submodules depend on: its HEAD, and the statuses of
+/// `.gitmodules` and of the submodules' paths, which change when a submodule is added, removed or
+/// checked out at another commit.
+#[derive(Clone, Debug, PartialEq)]
+struct SubmodulesKey {
+    repo_id: RepositoryId,
+    head_sha: Option<SharedString>,
+    statuses: Vec<Option<FileStatus>>,
+}
+
+impl SubmodulesKey {
+    fn new(submodules: &[Submodule], repo: &Repository) -> Self {
+        let gitmodules = RepoPath::new(".gitmodules").expect(".gitmodules is a valid path");
+        let statuses = std::iter::once(&gitmodules)
+            .chain(submodules.iter().map(|submodule| &submodule.path))
+            .map(|path| repo.status_for_path(path).map(|entry| entry.status))
+            .collect();
+        Self {
+            repo_id: repo.id,
+            head_sha: repo.head_commit.as_ref().map(|commit| commit.sha.clone()),
+            statuses,
+        }
+    }
+}
+
 #[derive(Clone, Debug, PartialEq, Eq)]
 struct BulkStaging {
     repo_id: RepositoryId,
@@ -685,11 +991,16 @@ pub(crate) fn commit_message_editor(
         window,
         cx,
     );
+    let issue_references = Rc::new(IssueReferences::new(project.clone()));
+    commit_editor.set_completion_provider(Some(Rc::new(CommitMessageCompletionProvider::new(
+        project.clone(),
+        issue_references.clone(),
+    ))));
+    commit_editor.set_semantics_provider(Some(issue_references));
     commit_editor.set_collaboration_hub(Box::new(project));
     commit_editor.set_use_autoclose(false);
     commit_editor.set_show_gutter(false, cx);
     commit_editor.set_use_modal_editing(true);
-    commit_editor.set_show_wrap_guides(false, cx);
     commit_editor.set_show_indent_guides(false, cx);
     let placeholder = placeholder.unwrap_or("Enter commit message".into());
     commit_editor.set_placeholder_text(&placeholder, window, cx);
@@ -780,12 +1091,17 @@ impl GitPanel {
                 move |this, _git_store, event, window, cx| match event {
                     GitStoreEvent::RepositoryUpdated(
                         _,
-                        RepositoryEvent::StatusesChanged | RepositoryEvent::BranchChanged,
+                        RepositoryEvent::StatusesChanged
+                        | RepositoryEvent::BranchChanged
+                        | RepositoryEvent::RenamesChanged,
                         true,
                     )
                     | GitStoreEvent::RepositoryAdded
-                    | GitStoreEvent::RepositoryRemoved(_)
-                    | GitStoreEvent::ActiveRepositoryChanged(_) => {
+                    | GitStoreEvent::RepositoryRemoved(_) => {
+                        this.schedule_update(window, cx);
+                    }
+                    GitStoreEvent::ActiveRepositoryChanged(_) => {
+                        this.load_remotes(cx);
                         this.schedule_update(window, cx);
                     }
                     GitStoreEvent::IndexWriteError(error) => {
@@ -795,6 +1111,30 @@ impl GitPanel {
                             })
                             .ok();
                     }
+                    GitStoreEvent::RepositoryUpdated(
+                        _,
+                        RepositoryEvent::HookOutputChanged,
+                        true,
+                    ) => {
+                        this.hook_output_changed(cx);
+                    }
+                    GitStoreEvent::RepositoryUpdated(
+                        _,
+                        RepositoryEvent::StatusesChanged | RepositoryEvent::BranchChanged,
+                        false,
+                    ) if !this.submodules.is_empty() => {
+                        // Submodules show the changes and branch of their own repositories.
+                        cx.notify();
+                    }
+                    GitStoreEvent::RepositoryUpdated(
+                        _,
+                        RepositoryEvent::BisectChanged
+                        | RepositoryEvent::RemoteProgressChanged
+                        | RepositoryEvent::IndexFlagsChanged,
+                        true,
+                    ) => {
+                        cx.notify();
+                    }
                     GitStoreEvent::RepositoryUpdated(_, _, _) => {}
                     GitStoreEvent::JobsUpdated | GitStoreEvent::ConflictsUpdated => {}
                 },
@@ -807,6 +1147,8 @@ impl GitPanel {
                 conflicted_count: 0,
                 conflicted_staged_count: 0,
                 add_coauthors: true,
+                hook_output_expanded: false,
+                hook_output_scroll_handle: ScrollHandle::new(),
                 generate_commit_message_task: None,
                 entries: Vec::new(),
                 view_mode: GitPanelViewMode::from_settings(cx),
@@ -819,15 +1161,19 @@ impl GitPanel {
                 pending_commit: None,
                 amend_pending: false,
                 original_commit_message: None,
+                commit_template: None,
                 signoff_enabled: false,
                 pending_serialization: Task::ready(()),
+                commit_messages: None,
+                pending_draft_serialization: Task::ready(()),
+                _commit_buffer_subscription: None,
                 single_staged_entry: None,
                 single_tracked_entry: None,
                 project,
                 scroll_handle,
                 max_width_item_index: None,
                 selected_entry: None,
-                marked_entries: Vec::new(),
+                marked_entries: HashSet::default(),
                 tracked_count: 0,
                 tracked_staged_count: 0,
                 update_visible_entries_task: Task::ready(()),
@@ -840,6 +1186,13 @@ impl GitPanel {
                 entry_count: 0,
                 bulk_staging: None,
                 stash_entries: Default::default(),
+                discarded_hunks: Vec::new(),
+                lfs_paths: Default::default(),
+                load_lfs_paths_task: Task::ready(()),
+                submodules: Vec::new(),
+                submodules_key: None,
+                submodules_expanded: true,
+                load_submodules_task: Task::ready(()),
                 _settings_subscription,
             };
 
@@ -1320,6 +1673,71 @@ impl GitPanel {
         });
     }
 
+    fn fetch_lfs_object(
+        &mut self,
+        _: &git::FetchLfsObject,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        maybe!({
+            let entry = self.entries.get(self.selected_entry?)?.status_entry()?;
+            let repo = self.active_repository.clone()?;
+            self.fetch_lfs_objects(repo, vec![entry.repo_path.clone()], window, cx);
+            Some(())
+        });
+    }
+
+    /// Downloads the LFS objects of the paths, replacing their pointer files in the working tree.
+    pub(crate) fn fetch_lfs_objects(
+        &mut self,
+        repo: Entity<Repository>,
+        paths: Vec<RepoPath>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let askpass = self.askpass_delegate("git lfs pull", window, cx);
+        let fetch = repo.update(cx, |repo, cx| repo.fetch_lfs_objects(paths, askpass, cx));
+        cx.spawn(async move |this, cx| {
+            let result = fetch.await?;
+            this.update(cx, |this, cx| {
+                if let Err(error) = result {
+                    this.show_error_toast("lfs pull", error, cx);
+                }
+            })
+        })
+        .detach_and_log_err(cx);
+    }
+
+    fn resolve_conflicts(
+        &mut self,
+        _: &git::ResolveConflicts,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        maybe!({
+            let entry = self.entries.get(self.selected_entry?)?.status_entry()?;
+            if !entry.status.is_conflicted() {
+                return None;
+            }
+            let active_repo = self.active_repository.clone()?;
+            ThreeWayDiffView::open_conflict(
+                active_repo,
+                entry.repo_path.clone(),
+                self.workspace.clone(),
+                window,
+                cx,
+            )
+            .detach_and_prompt_err(
+                "Failed to resolve conflicts",
+                window,
+                cx,
+                |_, _, _| None,
+            );
+
+            Some(())
+        });
+    }
+
     fn open_file(
         &mut self,
         _: &menu::SecondaryConfirm,
@@ -1433,7 +1851,7 @@ impl GitPanel {
 
     fn add_to_gitignore(
         &mut self,
-        _: &git::AddToGitignore,
+        action: &git::AddToGitignore,
         _window: &mut Window,
         cx: &mut Context<Self>,
     ) {
@@ -1445,61 +1863,72 @@ impl GitPanel {
                 return Some(());
             }
 
-            let project = self.project.downgrade();
-            let repo_path = entry.repo_path;
-            let active_repository = self.active_repository.as_ref()?.downgrade();
-
-            cx.spawn(async move |_, cx| {
-                let file_path_str = repo_path.as_ref().display(PathStyle::Posix);
-
-                let repo_root = active_repository.read_with(cx, |repository, _| {
-                    repository.snapshot().work_directory_abs_path
-                })?;
-
-                let gitignore_abs_path = repo_root.join(".gitignore");
-
-                let buffer: Entity<Buffer> = project
-                    .update(cx, |project, cx| {
-                        project.open_local_buffer(gitignore_abs_path, cx)
-                    })?
-                    .await?;
+            crate::gitignore::add_to_gitignore(
+                self.project.clone(),
+                self.active_repository.clone()?,
+                entry.repo_path,
+                false,
+                action.pattern,
+                cx,
+            )
+            .detach_and_log_err(cx);
 
-                let mut should_save = false;
-                buffer.update(cx, |buffer, cx| {
-                    let existing_content = buffer.text();
+            Some(())
+        });
+    }
 
-                    if existing_content
-                        .lines()
-                        .any(|line: &str| line.trim() == file_path_str)
-                    {
-                        return;
-                    }
+    fn toggle_assume_unchanged(
+        &mut self,
+        _: &git::ToggleAssumeUnchanged,
+        _window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.toggle_index_flag(IndexFlag::AssumeUnchanged, cx);
+    }
 
-                    let insert_position = existing_content.len();
-                    let new_entry = if existing_content.is_empty() {
-                        format!("{}\n", file_path_str)
-                    } else if existing_content.ends_with('\n') {
-                        format!("{}\n", file_path_str)
-                    } else {
-                        format!("\n{}\n", file_path_str)
-                    };
+    fn toggle_skip_worktree(
+        &mut self,
+        _: &git::ToggleSkipWorktree,
+        _window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.toggle_index_flag(IndexFlag::SkipWorktree, cx);
+    }
 
-                    buffer.edit([(insert_position..insert_position, new_entry)], None, cx);
-                    should_save = true;
-                });
+    fn toggle_index_flag(&mut self, flag: IndexFlag, cx: &mut Context<Self>) {
+        maybe!({
+            let entry = self.entries.get(self.selected_entry?)?.status_entry()?;
+            if entry.status.is_created() {
+                return Some(());
+            }
+            let repo = self.active_repository.clone()?;
+            let value = !repo
+                .read(cx)
+                .flagged_file(&entry.repo_path)
+                .is_some_and(|flagged_file| flagged_file.has(flag));
+            self.set_index_flag(repo, vec![entry.repo_path.clone()], flag, value, cx);
+            Some(())
+        });
+    }
 
-                if should_save {
-                    project
-                        .update(cx, |project, cx| project.save_buffer(buffer, cx))?
-                        .await?;
+    pub(crate) fn set_index_flag(
+        &mut self,
+        repo: Entity<Repository>,
+        paths: Vec<RepoPath>,
+        flag: IndexFlag,
+        value: bool,
+        cx: &mut Context<Self>,
+    ) {
+        let update = repo.update(cx, |repo, _| repo.set_index_flag(paths, flag, value));
+        cx.spawn(async move |this, cx| {
+            let result = update.await?;
+            this.update(cx, |this, cx| {
+                if let Err(error) = result {
+                    this.show_error_toast("update-index", error, cx);
                 }
-
-                anyhow::Ok(())
             })
-            .detach_and_log_err(cx);
-
-            Some(())
-        });
+        })
+        .detach_and_log_err(cx);
     }
 
     fn revert_entry(
@@ -1849,8 +2278,86 @@ impl GitPanel {
         }
     }
 
-    pub fn stage_all(&mut self, _: &StageAll, _window: &mut Window, cx: &mut Context<Self>) {
-        self.change_all_files_stage(true, cx);
+    fn record_discarded_hunks(&mut self, hunks: Vec<DiscardedHunk>, cx: &mut Context<Self>) {
+        if self.discarded_hunks.len() == MAX_DISCARD_HISTORY {
+            self.discarded_hunks.remove(0);
+        }
+        self.discarded_hunks.push(hunks);
+        cx.notify();
+    }
+
+    /// Puts the most recently discarded hunks' text back in place of their restored base text, and
+    /// the index entries the discard unstaged back in the index. Nothing is undone if any of the
+    /// restored hunks was edited since, as undoing would overwrite those edits.
+    fn undo_discard(&mut self, cx: &mut Context<Self>) {
+        let Some(hunks) = self.discarded_hunks.pop() else {
+            return;
+        };
+        let edited_since = hunks.iter().any(|hunk| {
+            hunk.buffer.upgrade().is_none_or(|buffer| {
+                let restored_text = buffer
+                    .read(cx)
+                    .text_for_range(hunk.range.clone())
+                    .collect::<String>();
+                restored_text != hunk.restored_text
+            })
+        });
+        if edited_since {
+            if let Some(workspace) = self.workspace.upgrade() {
+                workspace.update(cx, |workspace, cx| {
+                    let toast = StatusToast::new(
+                        "Can't undo the discard, the discarded lines were edited since",
+                        cx,
+                        |this, _| {
+                            this.icon(ToastIcon::new(IconName::Warning).color(Color::Warning))
+                                .dismiss_button(true)
+                        },
+                    );
+                    workspace.toggle_status_toast(toast, cx);
+                });
+            }
+            cx.notify();
+            return;
+        }
+
+        let git_store = self.project.read(cx).git_store().clone();
+        let mut restored_index_buffers = HashSet::default();
+        for hunk in hunks {
+            let Some(buffer) = hunk.buffer.upgrade() else {
+                continue;
+            };
+            buffer.update(cx, |buffer, cx| {
+                buffer.edit([(hunk.range, hunk.discarded_text)], None, cx);
+            });
+            let buffer_id = buffer.read(cx).remote_id();
+            let Some(index_text) = hunk.index_text else {
+                continue;
+            };
+            if !restored_index_buffers.insert(buffer_id) {
+                continue;
+            }
+            let Some((repository, path)) = git_store
+                .read(cx)
+                .repository_and_path_for_buffer_id(buffer_id, cx)
+            else {
+                continue;
+            };
+            let write = repository.update(cx, |repository, cx| {
+                repository.set_index_text(path, Some(index_text.to_string()), cx)
+            });
+            cx.spawn(async move |this, cx| {
+                if let Ok(Err(error)) = write.await {
+                    this.update(cx, |this, cx| this.show_error_toast("add", error, cx))
+                        .ok();
+                }
+            })
+            .detach();
+        }
+        cx.notify();
+    }
+
+    pub fn stage_all(&mut self, _: &StageAll, window: &mut Window, cx: &mut Context<Self>) {
+        self.stage_all_checking_conflict_markers(window, cx);
     }
 
     pub fn unstage_all(&mut self, _: &UnstageAll, _window: &mut Window, cx: &mut Context<Self>) {
@@ -1860,7 +2367,7 @@ impl GitPanel {
     fn toggle_staged_for_entry(
         &mut self,
         entry: &GitListEntry,
-        _window: &mut Window,
+        window: &mut Window,
         cx: &mut Context<Self>,
     ) {
         let Some(active_repository) = self.active_repository.clone() else {
@@ -1958,23 +2465,146 @@ impl GitPanel {
             self.set_bulk_staging_anchor(anchor, cx);
         }
 
-        self.change_file_stage(stage, repo_paths, cx);
+        if stage {
+            self.stage_checking_conflict_markers(repo_paths, window, cx);
+        } else {
+            self.change_file_stage(false, repo_paths, cx);
+        }
     }
 
-    fn change_file_stage(
+    /// Stages `entries`, asking for confirmation first if staging would mark a conflicted file
+    /// as resolved while it still contains conflict markers.
+    fn stage_checking_conflict_markers(
         &mut self,
-        stage: bool,
         entries: Vec<GitStatusEntry>,
+        window: &mut Window,
         cx: &mut Context<Self>,
     ) {
-        let Some(active_repository) = self.active_repository.clone() else {
+        let conflicted_paths = entries
+            .iter()
+            .filter(|entry| entry.status.is_conflicted())
+            .map(|entry| entry.repo_path.clone())
+            .collect();
+        let Some(confirmation) = self.confirm_staging_conflicts(conflicted_paths, window, cx)
+        else {
+            self.change_file_stage(true, entries, cx);
             return;
         };
-        cx.spawn({
-            async move |this, cx| {
-                let result = this
-                    .update(cx, |this, cx| {
-                        let task = active_repository.update(cx, |repo, cx| {
+        cx.spawn(async move |this, cx| {
+            if confirmation.await? {
+                this.update(cx, |this, cx| this.change_file_stage(true, entries, cx))?;
+            }
+            anyhow::Ok(())
+        })
+        .detach_and_log_err(cx);
+    }
+
+    /// Stages every file, with the same confirmation as [`Self::stage_checking_conflict_markers`]
+    /// for the conflicted ones.
+    fn stage_all_checking_conflict_markers(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let Some(active_repository) = self.active_repository.clone() else {
+            return;
+        };
+        let conflicted_paths = active_repository
+            .read(cx)
+            .cached_status()
+            .filter(|entry| entry.status.is_conflicted())
+            .map(|entry| entry.repo_path)
+            .collect();
+        let Some(confirmation) = self.confirm_staging_conflicts(conflicted_paths, window, cx)
+        else {
+            self.change_all_files_stage(true, cx);
+            return;
+        };
+        cx.spawn(async move |this, cx| {
+            if confirmation.await? {
+                this.update(cx, |this, cx| this.change_all_files_stage(true, cx))?;
+            }
+            anyhow::Ok(())
+        })
+        .detach_and_log_err(cx);
+    }
+
+    /// Resolves to whether to go ahead with staging `conflicted_paths`, asking first if any of
+    /// them still contains conflict markers. Returns `None` when there's nothing to check.
+    fn confirm_staging_conflicts(
+        &self,
+        conflicted_paths: Vec<RepoPath>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Option<Task<anyhow::Result<bool>>> {
+        let active_repository = self.active_repository.clone()?;
+        let conflicted_paths = conflicted_paths
+            .iter()
+            .filter_map(|repo_path| {
+                active_repository
+                    .read(cx)
+                    .repo_path_to_project_path(repo_path, cx)
+            })
+            .collect::<Vec<_>>();
+        if conflicted_paths.is_empty() {
+            return None;
+        }
+
+        let project = self.project.clone();
+        Some(cx.spawn_in(window, async move |this, cx| {
+            let mut files_with_markers = Vec::new();
+            for project_path in conflicted_paths {
+                let buffer = project
+                    .update(cx, |project, cx| {
+                        project.open_buffer(project_path.clone(), cx)
+                    })
+                    .await?;
+                let has_markers = buffer.read_with(cx, |buffer, _| {
+                    !ConflictSet::parse(&buffer.text_snapshot())
+                        .conflicts
+                        .is_empty()
+                });
+                if has_markers {
+                    files_with_markers.push(project_path);
+                }
+            }
+            if files_with_markers.is_empty() {
+                return Ok(true);
+            }
+
+            let message = match files_with_markers.as_slice() {
+                [project_path] => format!(
+                    "{} still contains conflict markers. Stage it anyway?",
+                    project_path.path.file_name().unwrap_or_default()
+                ),
+                _ => format!(
+                    "{} files still contain conflict markers. Stage them anyway?",
+                    files_with_markers.len()
+                ),
+            };
+            let answer = this.update_in(cx, |_, window, cx| {
+                window.prompt(
+                    PromptLevel::Warning,
+                    &message,
+                    Some("Staging a conflicted file marks its conflicts as resolved."),
+                    &["Stage Anyway", "Cancel"],
+                    cx,
+                )
+            })?;
+            Ok(answer.await == Ok(0))
+        }))
+    }
+
+    fn change_file_stage(
+        &mut self,
+        stage: bool,
+        entries: Vec<GitStatusEntry>,
+        cx: &mut Context<Self>,
+    ) {
+        let Some(active_repository) = self.active_repository.clone() else {
+            return;
+        };
+        cx.spawn({
+            async move |this, cx| {
+                let result = this
+                    .update(cx, |this, cx| {
+                        let task = active_repository.update(cx, |repo, cx| {
                             let repo_paths = entries
                                 .iter()
                                 .map(|entry| entry.repo_path.clone())
@@ -2017,11 +2647,18 @@ impl GitPanel {
                     .update(cx, |repo, cx| repo.stash_pop(None, cx))
                     .await;
                 this.update(cx, |this, cx| {
-                    stash_task
-                        .map_err(|e| {
-                            this.show_error_toast("stash pop", e, cx);
-                        })
-                        .ok();
+                    if let Err(error) = stash_task
+                        && let Some(workspace) = this.workspace.upgrade()
+                    {
+                        show_stash_apply_error(
+                            workspace,
+                            active_repository,
+                            None,
+                            "stash pop",
+                            error,
+                            cx,
+                        );
+                    }
                     cx.notify();
                 })
             }
@@ -2040,11 +2677,18 @@ impl GitPanel {
                     .update(cx, |repo, cx| repo.stash_apply(None, cx))
                     .await;
                 this.update(cx, |this, cx| {
-                    stash_task
-                        .map_err(|e| {
-                            this.show_error_toast("stash apply", e, cx);
-                        })
-                        .ok();
+                    if let Err(error) = stash_task
+                        && let Some(workspace) = this.workspace.upgrade()
+                    {
+                        show_stash_apply_error(
+                            workspace,
+                            active_repository,
+                            None,
+                            "stash apply",
+                            error,
+                            cx,
+                        );
+                    }
                     cx.notify();
                 })
             }
@@ -2060,7 +2704,15 @@ impl GitPanel {
         cx.spawn({
             async move |this, cx| {
                 let stash_task = active_repository
-                    .update(cx, |repo, cx| repo.stash_all(cx))
+                    .update(cx, |repo, cx| {
+                        repo.stash_all(
+                            StashOptions {
+                                message: None,
+                                include_untracked: true,
+                            },
+                            cx,
+                        )
+                    })
                     .await;
                 this.update(cx, |this, cx| {
                     stash_task
@@ -2075,6 +2727,45 @@ impl GitPanel {
         .detach();
     }
 
+    fn stash_selected(
+        &mut self,
+        _: &git::StashSelected,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let paths = self.stash_selected_paths();
+        if paths.is_empty() {
+            return;
+        }
+        let (Some(repository), Some(workspace)) =
+            (self.active_repository.clone(), self.workspace.upgrade())
+        else {
+            return;
+        };
+        let weak_workspace = self.workspace.clone();
+        workspace.update(cx, |workspace, cx| {
+            workspace.toggle_modal(window, cx, |window, cx| {
+                StashModal::new(repository, paths, weak_workspace, window, cx)
+            });
+        });
+    }
+
+    /// The files [`git::StashSelected`] stashes: every marked file when the selected file is one
+    /// of them, and otherwise only the selected file.
+    fn stash_selected_paths(&self) -> Vec<RepoPath> {
+        let Some(entry) = self
+            .get_selected_entry()
+            .and_then(|entry| entry.status_entry())
+        else {
+            return Vec::new();
+        };
+        if self.marked_entries.contains(&entry.repo_path) {
+            self.marked_paths()
+        } else {
+            vec![entry.repo_path.clone()]
+        }
+    }
+
     pub fn commit_message_buffer(&self, cx: &App) -> Entity<Buffer> {
         self.commit_editor
             .read(cx)
@@ -2102,15 +2793,14 @@ impl GitPanel {
         self.stage_bulk(index, cx);
     }
 
-    fn stage_selected(&mut self, _: &git::StageFile, _window: &mut Window, cx: &mut Context<Self>) {
-        let Some(selected_entry) = self.get_selected_entry() else {
-            return;
-        };
-        let Some(status_entry) = selected_entry.status_entry() else {
-            return;
-        };
-        if status_entry.staging != StageStatus::Staged {
-            self.change_file_stage(true, vec![status_entry.clone()], cx);
+    fn stage_selected(&mut self, _: &git::StageFile, window: &mut Window, cx: &mut Context<Self>) {
+        let entries = self
+            .selected_status_entries()
+            .into_iter()
+            .filter(|status_entry| status_entry.staging != StageStatus::Staged)
+            .collect::<Vec<_>>();
+        if !entries.is_empty() {
+            self.stage_checking_conflict_markers(entries, window, cx);
         }
     }
 
@@ -2120,14 +2810,27 @@ impl GitPanel {
         _window: &mut Window,
         cx: &mut Context<Self>,
     ) {
-        let Some(selected_entry) = self.get_selected_entry() else {
-            return;
-        };
-        let Some(status_entry) = selected_entry.status_entry() else {
-            return;
-        };
-        if status_entry.staging != StageStatus::Unstaged {
-            self.change_file_stage(false, vec![status_entry.clone()], cx);
+        let entries = self
+            .selected_status_entries()
+            .into_iter()
+            .filter(|status_entry| status_entry.staging != StageStatus::Unstaged)
+            .collect::<Vec<_>>();
+        if !entries.is_empty() {
+            self.change_file_stage(false, entries, cx);
+        }
+    }
+
+    /// The selected file, or every file under the selected directory in tree view.
+    fn selected_status_entries(&self) -> Vec<GitStatusEntry> {
+        match self.get_selected_entry() {
+            Some(GitListEntry::Directory(entry)) => self
+                .view_mode
+                .tree_state()
+                .and_then(|state| state.directory_descendants.get(&entry.key))
+                .cloned()
+                .unwrap_or_default(),
+            Some(entry) => entry.status_entry().cloned().into_iter().collect(),
+            None => Vec::new(),
         }
     }
 
@@ -2155,6 +2858,7 @@ impl GitPanel {
                 CommitOptions {
                     amend: false,
                     signoff: self.signoff_enabled,
+                    ..Default::default()
                 },
                 window,
                 cx,
@@ -2166,6 +2870,102 @@ impl GitPanel {
         }
     }
 
+    fn commit_selected(
+        &mut self,
+        _: &git::CommitSelected,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        if self.amend_pending || self.marked_entries.is_empty() {
+            return;
+        }
+        telemetry::event!("Git Committed", source = "Git Panel Selection");
+        self.commit_changes(
+            CommitOptions {
+                amend: false,
+                signoff: self.signoff_enabled,
+                only_paths: self.marked_paths(),
+                no_verify: false,
+            },
+            window,
+            cx,
+        );
+    }
+
+    fn commit_no_verify(
+        &mut self,
+        _: &git::CommitNoVerify,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        if !ProjectSettings::get_global(cx).git.allow_no_verify || self.pending_commit.is_some() {
+            return;
+        }
+
+        let amend = self.amend_pending;
+        let options = CommitOptions {
+            amend,
+            signoff: self.signoff_enabled,
+            only_paths: if amend {
+                Vec::new()
+            } else {
+                self.marked_paths()
+            },
+            no_verify: true,
+        };
+        let confirmation = window.prompt(
+            PromptLevel::Warning,
+            "Commit without running the pre-commit hook?",
+            Some("Hooks often catch failing checks before they're committed."),
+            &["Commit Without Hooks", "Cancel"],
+            cx,
+        );
+        let task = cx.spawn_in(window, async move |this, cx| {
+            let answer = confirmation.await;
+            this.update_in(cx, |this, window, cx| {
+                this.pending_commit.take();
+                if answer == Ok(0) {
+                    telemetry::event!("Git Committed", source = "Git Panel Without Hooks");
+                    this.commit_changes(options, window, cx);
+                }
+            })
+            .ok();
+        });
+
+        self.pending_commit = Some(task);
+    }
+
+    pub(crate) fn marked_paths(&self) -> Vec<RepoPath> {
+        self.marked_entries.iter().cloned().sorted().collect()
+    }
+
+    fn toggle_marked_entry(&mut self, ix: usize, cx: &mut Context<Self>) {
+        let Some(entry) = self.entries.get(ix).and_then(|entry| entry.status_entry()) else {
+            return;
+        };
+        let repo_path = entry.repo_path.clone();
+        if !self.marked_entries.remove(&repo_path) {
+            self.marked_entries.insert(repo_path);
+        }
+        self.selected_entry = Some(ix);
+        cx.notify();
+    }
+
+    /// Marks every file between the selected entry and `ix`, replacing the previous marks.
+    /// The selection is left in place so that further shift-clicks extend from the same anchor.
+    fn mark_entries_through(&mut self, ix: usize, cx: &mut Context<Self>) {
+        let anchor = self.selected_entry.unwrap_or(ix);
+        self.marked_entries = self
+            .entries
+            .get(anchor.min(ix)..=anchor.max(ix))
+            .into_iter()
+            .flatten()
+            .filter_map(|entry| entry.status_entry())
+            .map(|entry| entry.repo_path.clone())
+            .collect();
+        cx.notify();
+    }
+
     fn on_amend(&mut self, _: &git::Amend, window: &mut Window, cx: &mut Context<Self>) {
         if self.amend(&self.commit_editor.focus_handle(cx), window, cx) {
             telemetry::event!("Git Amended", source = "Git Panel");
@@ -2195,6 +2995,7 @@ impl GitPanel {
                         CommitOptions {
                             amend: true,
                             signoff: self.signoff_enabled,
+                            ..Default::default()
                         },
                         window,
                         cx,
@@ -2248,11 +3049,13 @@ impl GitPanel {
             .read(cx)
             .language_at(MultiBufferOffset(0), cx);
         let message = self.commit_editor.read(cx).text(cx);
-        if message.is_empty() {
+        if message.is_empty()
+            || (self.commit_template.is_some() && self.is_unedited_commit_message(&message))
+        {
             return self
                 .suggest_commit_message(cx)
                 .filter(|message| !message.trim().is_empty());
-        } else if message.trim().is_empty() {
+        } else if self.is_unedited_commit_message(&message) {
             return None;
         }
         let buffer = cx.new(|cx| {
@@ -2281,9 +3084,10 @@ impl GitPanel {
 
     fn has_commit_message(&self, cx: &mut Context<Self>) -> bool {
         let text = self.commit_editor.read(cx).text(cx);
-        if !text.trim().is_empty() {
+        let is_template = self.commit_template.is_some() && self.is_unedited_commit_message(&text);
+        if !self.is_unedited_commit_message(&text) {
             true
-        } else if text.is_empty() {
+        } else if text.is_empty() || is_template {
             self.suggest_commit_message(cx)
                 .is_some_and(|text| !text.trim().is_empty())
         } else {
@@ -2291,11 +3095,83 @@ impl GitPanel {
         }
     }
 
+    /// Commits with the given options, first asking for confirmation when HEAD is detached or
+    /// when amending a commit that has already been pushed.
     pub(crate) fn commit_changes(
         &mut self,
         options: CommitOptions,
         window: &mut Window,
         cx: &mut Context<Self>,
+    ) {
+        // Rebases and bisects detach HEAD on purpose, so only warn outside of them.
+        let commits_on_detached_head = self.active_repository.as_ref().is_some_and(|repo| {
+            let repo = repo.read(cx);
+            repo.is_head_detached() && repo.merge.operation.is_none()
+        });
+        if !commits_on_detached_head {
+            self.confirm_amend_and_commit(options, window, cx);
+            return;
+        }
+
+        let confirmation = window.prompt(
+            PromptLevel::Warning,
+            "HEAD is detached",
+            Some(
+                "The commit won't belong to any branch and is easy to lose after switching \
+                 branches. Create a branch here first to keep it.",
+            ),
+            &["Create Branch Here…", "Commit Anyway", "Cancel"],
+            cx,
+        );
+        let task = cx.spawn_in(window, async move |this, cx| {
+            let answer = confirmation.await;
+            this.update_in(cx, |this, window, cx| {
+                this.pending_commit.take();
+                match answer {
+                    Ok(0) => window.dispatch_action(git::CreateBranchHere.boxed_clone(), cx),
+                    Ok(1) => this.confirm_amend_and_commit(options, window, cx),
+                    _ => {}
+                }
+            })
+            .ok();
+        });
+
+        self.pending_commit = Some(task);
+    }
+
+    fn confirm_amend_and_commit(
+        &mut self,
+        options: CommitOptions,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        if !options.amend {
+            self.perform_commit(options, window, cx);
+            return;
+        }
+
+        let confirmation = self.check_for_pushed_commits("Amend", window, cx);
+        let task = cx.spawn_in(window, async move |this, cx| {
+            let confirmed = confirmation.await;
+            this.update_in(cx, |this, window, cx| {
+                this.pending_commit.take();
+                match confirmed {
+                    Ok(true) => this.perform_commit(options, window, cx),
+                    Ok(false) => {}
+                    Err(e) => this.show_error_toast("commit", e, cx),
+                }
+            })
+            .ok();
+        });
+
+        self.pending_commit = Some(task);
+    }
+
+    fn perform_commit(
+        &mut self,
+        options: CommitOptions,
+        window: &mut Window,
+        cx: &mut Context<Self>,
     ) {
         let Some(active_repository) = self.active_repository.clone() else {
             return;
@@ -2328,11 +3204,26 @@ impl GitPanel {
             return;
         };
 
+        let committed_message = message.clone();
         if self.add_coauthors {
             self.fill_co_authors(&mut message, cx);
         }
 
-        let task = if self.has_staged_changes() {
+        let amend = options.amend;
+        let commits_selection = !options.only_paths.is_empty();
+        let task = if commits_selection {
+            // `git commit --only` refuses untracked paths, so the selection is staged first.
+            let stage_task = active_repository.update(cx, |repo, cx| {
+                repo.stage_entries(options.only_paths.clone(), cx)
+            });
+            cx.spawn(async move |_, cx| {
+                stage_task.await?;
+                let commit_task = active_repository.update(cx, |repo, cx| {
+                    repo.commit(message.into(), None, options, askpass, cx)
+                });
+                commit_task.await?
+            })
+        } else if self.has_staged_changes() {
             // Repository serializes all git operations, so we can just send a commit immediately
             let commit_task = active_repository.update(cx, |repo, cx| {
                 repo.commit(message.into(), None, options, askpass, cx)
@@ -2369,15 +3260,25 @@ impl GitPanel {
 
                 match result {
                     Ok(()) => {
-                        if options.amend {
+                        if commits_selection {
+                            this.marked_entries.clear();
+                        }
+                        this.record_committed_message(committed_message, cx);
+                        if amend {
                             this.set_amend_pending(false, cx);
                         } else {
                             this.commit_editor
                                 .update(cx, |editor, cx| editor.clear(window, cx));
                             this.original_commit_message = None;
+                            this.apply_commit_template(window, cx);
                         }
                     }
-                    Err(e) => this.show_error_toast("commit", e, cx),
+                    Err(e) => {
+                        // The message stays in the editor, but can also be recalled after
+                        // trying something else, such as fixing what a hook rejected.
+                        this.push_commit_message_history(committed_message, cx);
+                        this.show_error_toast("commit", e, cx);
+                    }
                 }
             })
             .ok();
@@ -2392,7 +3293,7 @@ impl GitPanel {
         };
         telemetry::event!("Git Uncommitted");
 
-        let confirmation = self.check_for_pushed_commits(window, cx);
+        let confirmation = self.check_for_pushed_commits("Uncommit", window, cx);
         let prior_head = self.load_commit_details("HEAD".to_string(), cx);
 
         let task = cx.spawn_in(window, async move |this, cx| {
@@ -2430,8 +3331,11 @@ impl GitPanel {
         self.pending_commit = Some(task);
     }
 
+    /// Resolves to `true` when HEAD hasn't been pushed yet, or the user confirmed rewriting it
+    /// with `confirm_label`.
     fn check_for_pushed_commits(
         &mut self,
+        confirm_label: &'static str,
         window: &mut Window,
         cx: &mut Context<Self>,
     ) -> impl Future<Output = anyhow::Result<bool>> + use<> {
@@ -2448,24 +3352,23 @@ impl GitPanel {
             if pushed_to.is_empty() {
                 Ok(true)
             } else {
-                #[derive(strum::EnumIter, strum::VariantNames)]
-                #[strum(serialize_all = "title_case")]
-                enum CancelUncommit {
-                    Uncommit,
-                    Cancel,
-                }
                 let detail = format!(
                     "This commit was already pushed to {}.",
                     pushed_to.into_iter().join(", ")
                 );
-                let result = cx
-                    .update(|window, cx| prompt("Are you sure?", Some(&detail), window, cx))?
+                let answer = cx
+                    .update(|window, cx| {
+                        window.prompt(
+                            PromptLevel::Warning,
+                            "Are you sure?",
+                            Some(&detail),
+                            &[confirm_label, "Cancel"],
+                            cx,
+                        )
+                    })?
                     .await?;
 
-                match result {
-                    CancelUncommit::Cancel => Ok(false),
-                    CancelUncommit::Uncommit => Ok(true),
-                }
+                Ok(answer == 0)
             }
         }
     }
@@ -2518,19 +3421,95 @@ impl GitPanel {
         self.generate_commit_message(cx);
     }
 
-    fn split_patch(patch: &str) -> Vec<String> {
-        let mut result = Vec::new();
-        let mut current_patch = String::new();
-
-        for line in patch.lines() {
-            if line.starts_with("---") && !current_patch.is_empty() {
-                result.push(current_patch.trim_end_matches('\n').into());
-                current_patch = String::new();
-            }
-            current_patch.push_str(line);
-            current_patch.push('\n');
-        }
-
+    /// Summarizes the staged changes, or every change when nothing is staged, into a subject line.
+    pub(crate) fn summarize_staged_changes(&self, cx: &App) -> Option<String> {
+        let repo = self.active_repository.as_ref()?.read(cx);
+        let only_staged = self.has_staged_changes();
+        let changes = self
+            .entries
+            .iter()
+            .filter_map(|entry| entry.status_entry())
+            .filter(|entry| {
+                !only_staged
+                    || GitPanel::stage_status_for_entry(entry, repo)
+                        .as_bool()
+                        .unwrap_or(true)
+            })
+            .filter(|entry| !entry.status.is_conflicted())
+            .map(|entry| {
+                let kind = if entry.status.is_deleted() {
+                    ChangeKind::Deleted
+                } else if entry.status.is_created() || entry.status.is_untracked() {
+                    ChangeKind::Created
+                } else {
+                    ChangeKind::Modified
+                };
+                (entry.repo_path.clone(), kind)
+            })
+            .collect::<Vec<_>>();
+        summarize_changes(&changes)
+    }
+
+    fn suggest_commit_message_action(
+        &mut self,
+        _: &git::SuggestCommitMessage,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.fill_suggested_commit_message(window, cx);
+    }
+
+    /// Replaces the commit message when `repository` is the one being committed to.
+    fn set_commit_message_for(
+        &mut self,
+        repository: &Entity<Repository>,
+        message: String,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        if self.active_repository.as_ref() != Some(repository) {
+            return;
+        }
+        self.commit_editor
+            .update(cx, |editor, cx| editor.set_text(message, window, cx));
+    }
+
+    /// Replaces the subject line of the commit message with a summary of the changes, keeping
+    /// the rest of the message.
+    pub(crate) fn fill_suggested_commit_message(
+        &mut self,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let Some(subject) = self.summarize_staged_changes(cx) else {
+            return;
+        };
+        let message = self.commit_editor.read(cx).text(cx);
+        let message = match message.split_once('\n') {
+            Some((_, body)) => format!("{subject}\n{body}"),
+            None => subject,
+        };
+        self.commit_editor.update(cx, |editor, cx| {
+            editor.set_text(message, window, cx);
+            editor.move_to_beginning(&Default::default(), window, cx);
+            editor.move_to_end_of_line(&Default::default(), window, cx);
+        });
+        window.focus(&self.commit_editor.focus_handle(cx), cx);
+    }
+
+    fn split_patch(patch: &str) -> Vec<String> {
+        let mut result = Vec::new();
+        let mut current_patch = String::new();
+
+        for line in patch.lines() {
+            if line.starts_with("---") && !current_patch.is_empty() {
+                result.push(current_patch.trim_end_matches('\n').into());
+                current_patch = String::new();
+            }
+            current_patch.push_str(line);
+            current_patch.push('\n');
+        }
+
         if !current_patch.is_empty() {
             result.push(current_patch.trim_end_matches('\n').into());
         }
@@ -2908,18 +3887,6 @@ impl GitPanel {
             .detach_and_log_err(cx);
     }
 
-    pub(crate) fn git_clone(&mut self, repo: String, window: &mut Window, cx: &mut Context<Self>) {
-        let workspace = self.workspace.clone();
-
-        crate::clone::clone_and_open(
-            repo.into(),
-            workspace,
-            window,
-            cx,
-            Arc::new(|_workspace: &mut workspace::Workspace, _window, _cx| {}),
-        );
-    }
-
     pub(crate) fn git_init(&mut self, window: &mut Window, cx: &mut Context<Self>) {
         let worktrees = self
             .project
@@ -3002,7 +3969,13 @@ impl GitPanel {
         .detach();
     }
 
-    pub(crate) fn pull(&mut self, rebase: bool, window: &mut Window, cx: &mut Context<Self>) {
+    /// Pulls with `strategy`, or with the one configured for the repository when there's none.
+    pub(crate) fn pull(
+        &mut self,
+        strategy: Option<PullStrategy>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
         if !self.can_push_and_pull(cx) {
             return;
         }
@@ -3014,6 +3987,7 @@ impl GitPanel {
         };
         telemetry::event!("Git Pulled");
         let branch = branch.clone();
+        let strategy = strategy.or_else(|| self.configured_pull_strategy(&repo, cx));
         let remote = self.get_remote(false, false, window, cx);
         cx.spawn_in(window, async move |this, cx| {
             let remote = match remote.await {
@@ -3039,11 +4013,24 @@ impl GitPanel {
                 .then(|| branch.name().to_owned().into());
 
             let pull = repo.update(cx, |repo, cx| {
-                repo.pull(branch_name, remote.name.clone(), rebase, askpass, cx)
+                repo.pull(branch_name, remote.name.clone(), strategy, askpass, cx)
             });
 
             let remote_message = pull.await?;
 
+            if let Err(error) = &remote_message
+                && format!("{error:#}").contains(PULL_NOT_FAST_FORWARD)
+            {
+                let strategy = this.update_in(cx, |_, window, cx| {
+                    prompt_for_diverged_pull(branch.name(), &remote.name, window, cx)
+                })?;
+                let Some(strategy) = strategy.await else {
+                    return Ok(());
+                };
+                this.update_in(cx, |this, window, cx| this.pull(Some(strategy), window, cx))?;
+                return Ok(());
+            }
+
             let action = RemoteAction::Pull(remote);
             this.update(cx, |this, cx| match remote_message {
                 Ok(remote_message) => this.show_remote_output(action, remote_message, cx),
@@ -3059,10 +4046,20 @@ impl GitPanel {
         .detach_and_log_err(cx);
     }
 
+    /// The `git.pull_strategy` setting of the worktree containing the repository, so a project
+    /// can pick a strategy for its own repositories.
+    fn configured_pull_strategy(
+        &self,
+        repo: &Entity<Repository>,
+        cx: &App,
+    ) -> Option<PullStrategy> {
+        let settings = repository_settings(&self.project, repo, cx);
+        settings.git.pull_strategy
+    }
+
     pub(crate) fn push(
         &mut self,
-        force_push: bool,
-        select_remote: bool,
+        behavior: PushBehavior,
         window: &mut Window,
         cx: &mut Context<Self>,
     ) {
@@ -3078,8 +4075,10 @@ impl GitPanel {
         telemetry::event!("Git Pushed");
         let branch = branch.clone();
 
-        let options = if force_push {
+        let options = if behavior.force {
             Some(PushOptions::Force)
+        } else if behavior.set_upstream {
+            Some(PushOptions::SetUpstream)
         } else {
             match branch.upstream {
                 Some(Upstream {
@@ -3090,7 +4089,7 @@ impl GitPanel {
                 _ => None,
             }
         };
-        let remote = self.get_remote(select_remote, true, window, cx);
+        let remote = self.get_remote(behavior.select_remote, true, window, cx);
 
         cx.spawn_in(window, async move |this, cx| {
             let remote = match remote.await {
@@ -3106,6 +4105,23 @@ impl GitPanel {
                 }
             };
 
+            let remote_branch_name: SharedString = branch
+                .upstream
+                .as_ref()
+                .filter(|u| matches!(u.tracking, UpstreamTracking::Tracked(_)))
+                .and_then(|u| u.branch_name())
+                .unwrap_or_else(|| branch.name())
+                .to_owned()
+                .into();
+
+            if behavior.force {
+                let remote_branch = format!("{}/{}", remote.name, remote_branch_name);
+                let title = format!("Force push {} to {remote_branch}?", branch.name());
+                if !confirm_force_push(&this, &repo, &title, &remote_branch, cx).await? {
+                    return Ok(());
+                }
+            }
+
             let askpass_delegate = this.update_in(cx, |this, window, cx| {
                 this.askpass_delegate(format!("git push {}", remote.name), window, cx)
             })?;
@@ -3113,16 +4129,10 @@ impl GitPanel {
             let push = repo.update(cx, |repo, cx| {
                 repo.push(
                     branch.name().to_owned().into(),
-                    branch
-                        .upstream
-                        .as_ref()
-                        .filter(|u| matches!(u.tracking, UpstreamTracking::Tracked(_)))
-                        .and_then(|u| u.branch_name())
-                        .unwrap_or_else(|| branch.name())
-                        .to_owned()
-                        .into(),
+                    remote_branch_name,
                     remote.name.clone(),
                     options,
+                    behavior.follow_tags,
                     askpass_delegate,
                     cx,
                 )
@@ -3144,59 +4154,265 @@ impl GitPanel {
         .detach_and_log_err(cx);
     }
 
-    pub fn create_pull_request(&self, window: &mut Window, cx: &mut Context<Self>) {
-        let result = (|| -> anyhow::Result<()> {
-            let repo = self
-                .active_repository
-                .clone()
-                .ok_or_else(|| anyhow::anyhow!("No active repository"))?;
+    /// Fetches, integrates the upstream changes with the configured pull strategy and pushes,
+    /// stopping at the first step that fails. Diverged branches ask how to integrate, conflicts
+    /// are left to the in-progress operation banner, and a rejected push offers to force push.
+    pub(crate) fn sync(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        if !self.can_push_and_pull(cx) {
+            return;
+        }
+        let Some(repo) = self.active_repository.clone() else {
+            return;
+        };
+        let Some(branch) = repo.read(cx).branch.clone() else {
+            return;
+        };
+        telemetry::event!("Git Synced");
+        let mut strategy = self.configured_pull_strategy(&repo, cx);
+        let remote = self.get_remote(false, false, window, cx);
 
-            let (branch, remote_origin, remote_upstream) = {
-                let repository = repo.read(cx);
-                (
-                    repository.branch.clone(),
-                    repository.remote_origin_url.clone(),
-                    repository.remote_upstream_url.clone(),
-                )
+        cx.spawn_in(window, async move |this, cx| {
+            let remote = match remote.await {
+                Ok(Some(remote)) => remote,
+                Ok(None) => {
+                    return Ok(());
+                }
+                Err(e) => {
+                    log::error!("Failed to get current remote: {}", e);
+                    this.update(cx, |this, cx| this.show_error_toast("sync", e, cx))
+                        .ok();
+                    return Ok(());
+                }
             };
 
-            let branch = branch.ok_or_else(|| anyhow::anyhow!("No active branch"))?;
-            let source_branch = branch
+            let askpass = this.update_in(cx, |this, window, cx| {
+                this.askpass_delegate(format!("git fetch {}", remote.name), window, cx)
+            })?;
+            let fetch = repo.update(cx, |repo, cx| {
+                repo.fetch(FetchOptions::Remote(remote.clone()), askpass, cx)
+            });
+            if let Err(error) = fetch.await? {
+                log::error!("Error while fetching {:?}", error);
+                this.update(cx, |this, cx| this.show_error_toast("fetch", error, cx))?;
+                return Ok(());
+            }
+
+            let tracked_upstream = branch
                 .upstream
                 .as_ref()
-                .filter(|upstream| matches!(upstream.tracking, UpstreamTracking::Tracked(_)))
+                .filter(|upstream| matches!(upstream.tracking, UpstreamTracking::Tracked(_)));
+            // A branch that was never pushed has nothing to pull, so it is only published.
+            if tracked_upstream.is_some() {
+                loop {
+                    let askpass = this.update_in(cx, |this, window, cx| {
+                        this.askpass_delegate(format!("git pull {}", remote.name), window, cx)
+                    })?;
+                    let pull = repo.update(cx, |repo, cx| {
+                        repo.pull(None, remote.name.clone(), strategy, askpass, cx)
+                    });
+                    let Err(error) = pull.await? else {
+                        break;
+                    };
+                    if format!("{error:#}").contains(PULL_NOT_FAST_FORWARD) {
+                        let choice = this.update_in(cx, |_, window, cx| {
+                            prompt_for_diverged_pull(branch.name(), &remote.name, window, cx)
+                        })?;
+                        match choice.await {
+                            Some(choice) => {
+                                strategy = Some(choice);
+                                continue;
+                            }
+                            None => return Ok(()),
+                        }
+                    }
+                    log::error!("Error while pulling {:?}", error);
+                    this.update(cx, |this, cx| this.show_error_toast("pull", error, cx))?;
+                    return Ok(());
+                }
+            }
+
+            let remote_branch_name: SharedString = tracked_upstream
                 .and_then(|upstream| upstream.branch_name())
-                .ok_or_else(|| anyhow::anyhow!("No remote configured for repository"))?;
-            let source_branch = source_branch.to_string();
+                .unwrap_or_else(|| branch.name())
+                .to_owned()
+                .into();
+            let mut options = tracked_upstream
+                .is_none()
+                .then_some(PushOptions::SetUpstream);
+            loop {
+                let askpass = this.update_in(cx, |this, window, cx| {
+                    this.askpass_delegate(format!("git push {}", remote.name), window, cx)
+                })?;
+                let push = repo.update(cx, |repo, cx| {
+                    repo.push(
+                        branch.name().to_owned().into(),
+                        remote_branch_name.clone(),
+                        remote.name.clone(),
+                        options,
+                        false,
+                        askpass,
+                        cx,
+                    )
+                });
+                match push.await? {
+                    Ok(remote_message) => {
+                        let action = RemoteAction::Push(branch.name().to_owned().into(), remote);
+                        this.update(cx, |this, cx| {
+                            this.show_remote_output(action, remote_message, cx)
+                        })?;
+                        return Ok(());
+                    }
+                    Err(error)
+                        if options != Some(PushOptions::Force)
+                            && format!("{error:#}").contains(PUSH_REJECTED) =>
+                    {
+                        let remote_branch = format!("{}/{}", remote.name, remote_branch_name);
+                        let title = format!(
+                            "{remote_branch} rejected the push. Force push {}?",
+                            branch.name()
+                        );
+                        if !confirm_force_push(&this, &repo, &title, &remote_branch, cx).await? {
+                            return Ok(());
+                        }
+                        options = Some(PushOptions::Force);
+                    }
+                    Err(error) => {
+                        log::error!("Error while pushing {:?}", error);
+                        this.update(cx, |this, cx| this.show_error_toast("push", error, cx))?;
+                        return Ok(());
+                    }
+                }
+            }
+        })
+        .detach_and_log_err(cx);
+    }
 
-            let remote_url = branch
-                .upstream
-                .as_ref()
-                .and_then(|upstream| match upstream.remote_name() {
-                    Some("upstream") => remote_upstream.as_deref(),
-                    Some(_) => remote_origin.as_deref(),
-                    None => None,
-                })
-                .or(remote_origin.as_deref())
-                .or(remote_upstream.as_deref())
-                .ok_or_else(|| anyhow::anyhow!("No remote configured for repository"))?;
-            let remote_url = remote_url.to_string();
-
-            let provider_registry = GitHostingProviderRegistry::global(cx);
-            let Some((provider, parsed_remote)) =
-                git::parse_git_remote_url(provider_registry, &remote_url)
-            else {
-                return Err(anyhow::anyhow!("Unsupported remote URL: {}", remote_url));
-            };
+    pub(crate) fn push_tag(
+        &mut self,
+        tag_name: SharedString,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        if !self.can_push_and_pull(cx) {
+            return;
+        }
+        let Some(repo) = self.active_repository.clone() else {
+            return;
+        };
+        telemetry::event!("Git Tag Pushed");
+        let remote = self.get_remote(true, true, window, cx);
 
-            let Some(url) = provider.build_create_pull_request_url(&parsed_remote, &source_branch)
-            else {
-                return Err(anyhow::anyhow!("Unable to construct pull request URL"));
+        cx.spawn_in(window, async move |this, cx| {
+            let remote = match remote.await {
+                Ok(Some(remote)) => remote,
+                Ok(None) => {
+                    return Ok(());
+                }
+                Err(e) => {
+                    log::error!("Failed to get current remote: {}", e);
+                    this.update(cx, |this, cx| this.show_error_toast("push", e, cx))
+                        .ok();
+                    return Ok(());
+                }
             };
 
-            cx.open_url(url.as_str());
-            Ok(())
-        })();
+            let askpass_delegate = this.update_in(cx, |this, window, cx| {
+                this.askpass_delegate(format!("git push {}", remote.name), window, cx)
+            })?;
+
+            let tag_ref: SharedString = format!("refs/tags/{tag_name}").into();
+            let push = repo.update(cx, |repo, cx| {
+                repo.push(
+                    tag_ref.clone(),
+                    tag_ref,
+                    remote.name.clone(),
+                    None,
+                    false,
+                    askpass_delegate,
+                    cx,
+                )
+            });
+
+            let remote_output = push.await?;
+
+            let action = RemoteAction::Push(tag_name, remote);
+            this.update(cx, |this, cx| match remote_output {
+                Ok(remote_message) => this.show_remote_output(action, remote_message, cx),
+                Err(e) => {
+                    log::error!("Error while pushing {:?}", e);
+                    this.show_error_toast(action.name(), e, cx)
+                }
+            })?;
+
+            anyhow::Ok(())
+        })
+        .detach_and_log_err(cx);
+    }
+
+    /// The hosting provider of the remote with the given name, which falls back to `origin` and
+    /// then `upstream` when the repository doesn't know its URL.
+    fn hosting_remote(
+        &self,
+        remote_name: Option<&str>,
+        cx: &App,
+    ) -> anyhow::Result<(
+        Arc<dyn GitHostingProvider + Send + Sync + 'static>,
+        ParsedGitRemote,
+    )> {
+        let repo = self
+            .active_repository
+            .as_ref()
+            .ok_or_else(|| anyhow::anyhow!("No active repository"))?;
+        let repository = repo.read(cx);
+        let remote_origin = repository.remote_origin_url.as_deref();
+        let remote_upstream = repository.remote_upstream_url.as_deref();
+
+        let remote_url = match remote_name {
+            Some("upstream") => remote_upstream,
+            Some(_) => remote_origin,
+            None => None,
+        }
+        .or(remote_origin)
+        .or(remote_upstream)
+        .ok_or_else(|| anyhow::anyhow!("No remote configured for repository"))?;
+
+        let provider_registry = GitHostingProviderRegistry::global(cx);
+        git::parse_git_remote_url(provider_registry, remote_url)
+            .ok_or_else(|| anyhow::anyhow!("Unsupported remote URL: {}", remote_url))
+    }
+
+    /// The branch the current branch tracks, on the hosting provider of its remote.
+    fn tracked_remote(&self, cx: &App) -> anyhow::Result<PullRequestTarget> {
+        let branch = self
+            .active_repository
+            .as_ref()
+            .ok_or_else(|| anyhow::anyhow!("No active repository"))?
+            .read(cx)
+            .branch
+            .clone()
+            .ok_or_else(|| anyhow::anyhow!("No active branch"))?;
+        let upstream = branch
+            .upstream
+            .as_ref()
+            .filter(|upstream| matches!(upstream.tracking, UpstreamTracking::Tracked(_)));
+        let source_branch = upstream
+            .and_then(|upstream| upstream.branch_name())
+            .ok_or_else(|| anyhow::anyhow!("No remote configured for repository"))?
+            .to_string();
+
+        let (provider, remote) =
+            self.hosting_remote(upstream.and_then(|upstream| upstream.remote_name()), cx)?;
+        Ok(PullRequestTarget {
+            provider,
+            remote,
+            source_branch,
+        })
+    }
+
+    pub fn create_pull_request(&self, window: &mut Window, cx: &mut Context<Self>) {
+        let result = self
+            .tracked_remote(cx)
+            .and_then(|target| self.open_pull_request(target, window, cx));
 
         if let Err(err) = result {
             log::error!("Error while creating pull request {:?}", err);
@@ -3206,33 +4422,199 @@ impl GitPanel {
         }
     }
 
-    fn askpass_delegate(
+    /// Opens a pull request for the branch, through the API of its hosting provider when
+    /// possible and otherwise on its web page.
+    fn open_pull_request(
         &self,
-        operation: impl Into<SharedString>,
+        target: PullRequestTarget,
         window: &mut Window,
         cx: &mut Context<Self>,
-    ) -> AskPassDelegate {
+    ) -> anyhow::Result<()> {
+        if !target.provider.can_create_pull_requests() {
+            let Some(url) = target.compare_url() else {
+                return Err(anyhow::anyhow!("Unable to construct pull request URL"));
+            };
+            cx.open_url(&url);
+            return Ok(());
+        }
+
+        let repository = self
+            .active_repository
+            .clone()
+            .ok_or_else(|| anyhow::anyhow!("No active repository"))?;
+        let commit_message = self.commit_editor.read(cx).text(cx);
+        let commit_message = if commit_message.trim().is_empty() {
+            repository
+                .read(cx)
+                .head_commit
+                .as_ref()
+                .map(|commit| commit.message.to_string())
+                .unwrap_or_default()
+        } else {
+            commit_message
+        };
         let workspace = self.workspace.clone();
-        let operation = operation.into();
-        let window = window.window_handle();
-        AskPassDelegate::new(&mut cx.to_async(), move |prompt, tx, cx| {
-            window
-                .update(cx, |_, window, cx| {
-                    workspace.update(cx, |workspace, cx| {
-                        workspace.toggle_modal(window, cx, |window, cx| {
-                            AskPassModal::new(operation.clone(), prompt.into(), tx, window, cx)
-                        });
-                    })
-                })
-                .ok();
+        self.workspace.update(cx, |workspace, cx| {
+            workspace.toggle_modal(window, cx, |window, cx| {
+                PullRequestModal::new(target, &commit_message, repository, workspace, window, cx)
+            });
         })
     }
 
-    fn can_push_and_pull(&self, cx: &App) -> bool {
-        !self.project.read(cx).is_via_collab()
-    }
+    /// Lists the open pull requests of the repository's remote, to check one of them out.
+    pub fn open_pull_request_picker(&self, window: &mut Window, cx: &mut Context<Self>) {
+        let result = (|| -> anyhow::Result<()> {
+            let repository = self
+                .active_repository
+                .as_ref()
+                .ok_or_else(|| anyhow::anyhow!("No active repository"))?;
+            // In a fork, pull requests are opened against the repository it was forked from.
+            let remote_name = if repository.read(cx).remote_upstream_url.is_some() {
+                "upstream"
+            } else {
+                "origin"
+            };
+            let (provider, remote) = self.hosting_remote(Some(remote_name), cx)?;
+            let pull_request_remote = PullRequestRemote {
+                name: remote_name.to_string(),
+                provider,
+                remote,
+            };
+            let git_panel = cx.weak_entity();
+            self.workspace.update(cx, |workspace, cx| {
+                workspace.toggle_modal(window, cx, |window, cx| {
+                    PullRequestPicker::new(pull_request_remote, git_panel, window, cx)
+                });
+            })
+        })();
 
-    fn get_remote(
+        if let Err(err) = result {
+            log::error!("Error while listing pull requests {:?}", err);
+            cx.defer_in(window, |panel, _window, cx| {
+                panel.show_error_toast("checkout pull request", err, cx);
+            });
+        }
+    }
+
+    /// Fetches the head of a pull request into a local branch named after its number and
+    /// switches to that branch.
+    pub(crate) fn checkout_pull_request(
+        &self,
+        remote_name: String,
+        head_ref: String,
+        number: u32,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let Some(repo) = self.active_repository.clone() else {
+            return;
+        };
+        let branch_name = format!("pr/{number}");
+        let askpass = self.askpass_delegate(format!("git fetch {remote_name}"), window, cx);
+        cx.spawn_in(window, async move |this, cx| {
+            let fetch = repo.update(cx, |repo, cx| {
+                repo.fetch_into_branch(remote_name, head_ref, branch_name.clone(), askpass, cx)
+            });
+            if let Err(error) = fetch.await? {
+                log::error!("Error while fetching pull request {:?}", error);
+                this.update(cx, |this, cx| this.show_error_toast("fetch", error, cx))?;
+                return Ok(());
+            }
+
+            let change_branch = repo.update(cx, |repo, _| repo.change_branch(branch_name));
+            if let Err(error) = change_branch.await? {
+                log::error!("Error while switching to pull request {:?}", error);
+                this.update(cx, |this, cx| this.show_error_toast("switch", error, cx))?;
+            }
+            anyhow::Ok(())
+        })
+        .detach_and_log_err(cx);
+    }
+
+    pub fn open_branch_on_remote(&self, window: &mut Window, cx: &mut Context<Self>) {
+        let result = (|| -> anyhow::Result<()> {
+            let PullRequestTarget {
+                provider,
+                remote,
+                source_branch,
+            } = self.tracked_remote(cx)?;
+            let Some(url) = provider.build_branch_url(&remote, &source_branch) else {
+                return Err(anyhow::anyhow!(
+                    "{} does not support opening branches",
+                    provider.name()
+                ));
+            };
+
+            cx.open_url(url.as_str());
+            Ok(())
+        })();
+
+        if let Err(err) = result {
+            log::error!("Error while opening branch on remote {:?}", err);
+            cx.defer_in(window, |panel, _window, cx| {
+                panel.show_error_toast("open branch on remote", err, cx);
+            });
+        }
+    }
+
+    fn askpass_delegate(
+        &self,
+        operation: impl Into<SharedString>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> AskPassDelegate {
+        let workspace = self.workspace.clone();
+        let operation = operation.into();
+        let window = window.window_handle();
+        let asked_passphrases = Arc::new(std::sync::Mutex::new(HashSet::default()));
+        // Prompts of remote repositories are relayed by the host, which must not be able to learn
+        // the passphrases of local keys, so passphrases are only remembered for local ones.
+        let passphrase_machine = self.project.read(cx).is_local().then_some(None);
+        AskPassDelegate::new(&mut cx.to_async(), move |prompt, tx, cx| {
+            window
+                .update(cx, |_, window, cx| {
+                    // A remembered passphrase is only used the first time ssh asks for it, since
+                    // being asked again means it was wrong.
+                    if let CredentialRequest::Passphrase { key } = CredentialRequest::parse(&prompt)
+                        && let Some(machine) = passphrase_machine.clone()
+                    {
+                        let key = PassphraseKey { machine, path: key };
+                        let first_ask = asked_passphrases
+                            .lock()
+                            .is_ok_and(|mut asked| asked.insert(key.clone()));
+                        match SessionPassphrases::get(&key, cx) {
+                            Some(passphrase) if first_ask => {
+                                tx.send(passphrase).ok();
+                                return;
+                            }
+                            Some(_) => SessionPassphrases::forget(&key, cx),
+                            None => {}
+                        }
+                    }
+                    workspace
+                        .update(cx, |workspace, cx| {
+                            workspace.toggle_modal(window, cx, |window, cx| {
+                                AskPassModal::new(
+                                    operation.clone(),
+                                    prompt.into(),
+                                    passphrase_machine.clone(),
+                                    tx,
+                                    window,
+                                    cx,
+                                )
+                            });
+                        })
+                        .ok();
+                })
+                .ok();
+        })
+    }
+
+    fn can_push_and_pull(&self, cx: &App) -> bool {
+        !self.project.read(cx).is_via_collab()
+    }
+
+    fn get_remote(
         &mut self,
         always_select: bool,
         is_push: bool,
@@ -3398,51 +4780,60 @@ impl GitPanel {
     }
 
     fn fill_co_authors(&mut self, message: &mut String, cx: &mut Context<Self>) {
-        const CO_AUTHOR_PREFIX: &str = "Co-authored-by: ";
+        if let Some(trailers) = co_author_trailers(message, self.potential_co_authors(cx)) {
+            message.push_str(&trailers);
+        }
+    }
 
-        let existing_text = message.to_ascii_lowercase();
-        let lowercase_co_author_prefix = CO_AUTHOR_PREFIX.to_lowercase();
-        let mut ends_with_co_authors = false;
-        let existing_co_authors = existing_text
-            .lines()
-            .filter_map(|line| {
-                let line = line.trim();
-                if line.starts_with(&lowercase_co_author_prefix) {
-                    ends_with_co_authors = true;
-                    Some(line)
-                } else {
-                    ends_with_co_authors = false;
-                    None
+    fn add_co_author(&mut self, _: &git::AddCoAuthor, window: &mut Window, cx: &mut Context<Self>) {
+        let Some(repo) = self.active_repository.clone() else {
+            return;
+        };
+        let mut co_authors = self.potential_co_authors(cx);
+        let own_email = self
+            .local_committer
+            .as_ref()
+            .and_then(|committer| committer.email.clone());
+        let recent_authors = repo.update(cx, |repo, cx| {
+            repo.recent_commit_authors(MAX_CO_AUTHOR_CANDIDATE_COMMITS, cx)
+        });
+        let workspace = self.workspace.clone();
+
+        cx.spawn_in(window, async move |this, cx| {
+            for (name, email) in recent_authors.await? {
+                let is_known = own_email.as_deref() == Some(&*email)
+                    || co_authors
+                        .iter()
+                        .any(|(_, co_author_email)| co_author_email.eq_ignore_ascii_case(&email));
+                if !is_known {
+                    co_authors.push((name.to_string(), email.to_string()));
                 }
-            })
-            .collect::<HashSet<_>>();
+            }
+            let options = co_authors
+                .iter()
+                .map(|(name, email)| SharedString::from(format!("{name} <{email}>")))
+                .collect();
+            let selection = cx
+                .update(|window, cx| {
+                    picker_prompt::prompt("Add a co-author", options, workspace, window, cx)
+                })?
+                .await;
+            let Some(co_author) = selection.and_then(|index| co_authors.get(index).cloned()) else {
+                return Ok(());
+            };
 
-        let new_co_authors = self
-            .potential_co_authors(cx)
-            .into_iter()
-            .filter(|(_, email)| {
-                !existing_co_authors
-                    .iter()
-                    .any(|existing| existing.contains(email.as_str()))
+            this.update(cx, |this, cx| {
+                let buffer = this.commit_message_buffer(cx);
+                let message = buffer.read(cx).text();
+                if let Some(trailers) = co_author_trailers(&message, [co_author]) {
+                    buffer.update(cx, |buffer, cx| {
+                        let end = buffer.len();
+                        buffer.edit([(end..end, trailers)], None, cx);
+                    });
+                }
             })
-            .collect::<Vec<_>>();
-
-        if new_co_authors.is_empty() {
-            return;
-        }
-
-        if !ends_with_co_authors {
-            message.push('\n');
-        }
-        for (name, email) in new_co_authors {
-            message.push('\n');
-            message.push_str(CO_AUTHOR_PREFIX);
-            message.push_str(&name);
-            message.push_str(" <");
-            message.push_str(&email);
-            message.push('>');
-        }
-        message.push('\n');
+        })
+        .detach_and_log_err(cx);
     }
 
     fn schedule_update(&mut self, window: &mut Window, cx: &mut Context<Self>) {
@@ -3485,6 +4876,12 @@ impl GitPanel {
                     .as_ref()
                     != Some(&buffer)
                 {
+                    git_panel._commit_buffer_subscription =
+                        Some(cx.subscribe(&buffer, |git_panel, _, event, cx| {
+                            if let language::BufferEvent::Edited { .. } = event {
+                                git_panel.serialize_commit_message_draft(cx);
+                            }
+                        }));
                     git_panel.commit_editor = cx.new(|cx| {
                         commit_message_editor(
                             buffer,
@@ -3495,12 +4892,174 @@ impl GitPanel {
                             cx,
                         )
                     });
+                    git_panel.load_commit_messages(window, cx);
+                }
+            })
+        })
+        .detach_and_log_err(cx);
+    }
+
+    /// Loads the persisted draft and message history of the active repository, restoring the
+    /// draft before the commit template is applied.
+    fn load_commit_messages(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let Some(active_repo) = self.active_repository.as_ref() else {
+            return;
+        };
+        let repo_id = active_repo.read(cx).id;
+        let work_directory = active_repo.read(cx).work_directory_abs_path.clone();
+        self.commit_messages = None;
+        let kvp = self.commit_messages_store(cx);
+        let load_messages = cx.background_spawn({
+            let work_directory = work_directory.clone();
+            async move {
+                match kvp {
+                    Some(kvp) => CommitMessages::load(&kvp, &work_directory),
+                    None => Ok(CommitMessages::default()),
+                }
+            }
+        });
+
+        cx.spawn_in(window, async move |git_panel, cx| {
+            let commit_messages = load_messages.await.log_err().unwrap_or_default();
+            git_panel.update_in(cx, |git_panel, window, cx| {
+                // Another repository may have become active while loading.
+                if git_panel
+                    .active_repository
+                    .as_ref()
+                    .is_none_or(|active_repo| active_repo.read(cx).id != repo_id)
+                {
+                    return;
+                }
+                if let Some(draft) = &commit_messages.draft
+                    && git_panel.commit_editor.read(cx).text(cx).is_empty()
+                {
+                    git_panel.commit_editor.update(cx, |editor, cx| {
+                        editor.set_text(draft.as_str(), window, cx);
+                    });
                 }
+                git_panel.commit_messages = Some((work_directory, commit_messages));
+                git_panel.load_commit_template(window, cx);
+            })
+        })
+        .detach_and_log_err(cx);
+    }
+
+    fn serialize_commit_message_draft(&mut self, cx: &mut Context<Self>) {
+        let message = self.commit_editor.read(cx).text(cx);
+        let draft = (!self.is_unedited_commit_message(&message)).then_some(message);
+        let Some((_, commit_messages)) = &mut self.commit_messages else {
+            return;
+        };
+        if commit_messages.draft == draft {
+            return;
+        }
+        commit_messages.draft = draft;
+
+        self.pending_draft_serialization = cx.spawn(async move |git_panel, cx| {
+            cx.background_executor()
+                .timer(SERIALIZATION_THROTTLE_TIME)
+                .await;
+            git_panel
+                .update(cx, |git_panel, cx| git_panel.save_commit_messages(cx))
+                .ok();
+        });
+    }
+
+    /// Commit messages are only persisted for workspaces that are themselves persisted.
+    fn commit_messages_store(&self, cx: &App) -> Option<KeyValueStore> {
+        self.workspace
+            .read_with(cx, |workspace, _| workspace.database_id())
+            .ok()
+            .flatten()
+            .map(|_| KeyValueStore::global(cx))
+    }
+
+    fn save_commit_messages(&mut self, cx: &mut Context<Self>) {
+        let Some((work_directory, commit_messages)) = self.commit_messages.clone() else {
+            return;
+        };
+        let Some(kvp) = self.commit_messages_store(cx) else {
+            return;
+        };
+        cx.background_spawn(async move { commit_messages.save(&kvp, &work_directory).await })
+            .detach_and_log_err(cx);
+    }
+
+    /// Moves a committed message into the history of the active repository and drops the draft.
+    fn record_committed_message(&mut self, message: String, cx: &mut Context<Self>) {
+        let Some((_, commit_messages)) = &mut self.commit_messages else {
+            return;
+        };
+        commit_messages.draft = None;
+        self.pending_draft_serialization = Task::ready(());
+        self.push_commit_message_history(message, cx);
+    }
+
+    /// Adds a message to the history of the active repository, keeping the draft.
+    fn push_commit_message_history(&mut self, message: String, cx: &mut Context<Self>) {
+        let Some((_, commit_messages)) = &mut self.commit_messages else {
+            return;
+        };
+        commit_messages.push_history(message);
+        self.save_commit_messages(cx);
+    }
+
+    fn recall_commit_message(
+        &mut self,
+        message: String,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.commit_editor.update(cx, |editor, cx| {
+            editor.set_text(message, window, cx);
+            editor.move_to_end(&Default::default(), window, cx);
+        });
+        window.focus(&self.commit_editor.focus_handle(cx), cx);
+    }
+
+    fn load_commit_template(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let Some(active_repo) = self.active_repository.as_ref() else {
+            return;
+        };
+        let configured_path = ProjectSettings::get_global(cx).git.commit_template.clone();
+        let load_template = active_repo.update(cx, |active_repo, cx| {
+            active_repo.load_commit_template(configured_path, cx)
+        });
+
+        cx.spawn_in(window, async move |git_panel, cx| {
+            let template = load_template.await?;
+            git_panel.update_in(cx, |git_panel, window, cx| {
+                git_panel.commit_template = template;
+                git_panel.apply_commit_template(window, cx);
             })
         })
         .detach_and_log_err(cx);
     }
 
+    /// Fills the commit message editor with the commit template, unless a message was already written.
+    fn apply_commit_template(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let Some(template) = self.commit_template.clone() else {
+            return;
+        };
+        if self.commit_editor.read(cx).text(cx).is_empty() {
+            self.commit_editor.update(cx, |editor, cx| {
+                editor.set_text(template, window, cx);
+                editor.move_to_beginning(&Default::default(), window, cx);
+            });
+        }
+    }
+
+    /// Whether the message only consists of the commit template or of lines that `--cleanup=strip`
+    /// removes, in which case it is treated like an empty message.
+    fn is_unedited_commit_message(&self, message: &str) -> bool {
+        let stripped = strip_commit_message_comments(message);
+        stripped.is_empty()
+            || self
+                .commit_template
+                .as_deref()
+                .is_some_and(|template| strip_commit_message_comments(template) == stripped)
+    }
+
     fn update_visible_entries(&mut self, window: &mut Window, cx: &mut Context<Self>) {
         let path_style = self.project.read(cx).path_style(cx);
         let bulk_staging = self.bulk_staging.take();
@@ -3547,6 +5106,10 @@ impl GitPanel {
         self.stash_entries = repo.cached_stash();
 
         for entry in repo.cached_status() {
+            // A renamed file's entry stands for the deletion of the path it was renamed from.
+            if repo.rename_from(&entry.repo_path).is_some() {
+                continue;
+            }
             self.changes_count += 1;
             let is_conflict = repo.had_conflict_on_last_merge_head_change(&entry.repo_path);
             let is_new = entry.status.is_created();
@@ -3710,6 +5273,8 @@ impl GitPanel {
         self.max_width_item_index = max_width_item_index;
 
         self.update_counts(repo);
+        self.marked_entries
+            .retain(|repo_path| repo.status_for_path(repo_path).is_some());
 
         let bulk_staging_anchor_new_index = bulk_staging
             .as_ref()
@@ -3735,63 +5300,202 @@ impl GitPanel {
             editor.set_placeholder_text(&placeholder_text, window, cx)
         });
 
+        self.load_lfs_paths(cx);
+        let submodules_key = self
+            .active_repository
+            .as_ref()
+            .map(|repo| SubmodulesKey::new(&self.submodules, repo.read(cx)));
+        if submodules_key != self.submodules_key {
+            self.submodules_key = submodules_key;
+            self.load_submodules(cx);
+        }
         cx.notify();
     }
 
-    fn header_state(&self, header_type: Section) -> ToggleState {
-        let (staged_count, count) = match header_type {
-            Section::New => (self.new_staged_count, self.new_count),
-            Section::Tracked => (self.tracked_staged_count, self.tracked_count),
-            Section::Conflict => (self.conflicted_staged_count, self.conflicted_count),
+    fn load_submodules(&mut self, cx: &mut Context<Self>) {
+        let Some(repo) = self
+            .active_repository
+            .clone()
+            .filter(|_| !self.project.read(cx).is_via_collab())
+        else {
+            self.submodules.clear();
+            return;
         };
-        if staged_count == 0 {
-            ToggleState::Unselected
-        } else if count == staged_count {
-            ToggleState::Selected
-        } else {
-            ToggleState::Indeterminate
-        }
-    }
-
-    fn update_counts(&mut self, repo: &Repository) {
-        self.show_placeholders = false;
-        self.conflicted_count = 0;
-        self.conflicted_staged_count = 0;
-        self.new_count = 0;
-        self.tracked_count = 0;
-        self.new_staged_count = 0;
-        self.tracked_staged_count = 0;
-        self.entry_count = 0;
-
-        for status_entry in self.entries.iter().filter_map(|entry| entry.status_entry()) {
-            self.entry_count += 1;
-            let is_staging_or_staged = GitPanel::stage_status_for_entry(status_entry, repo)
-                .as_bool()
-                .unwrap_or(true);
-
-            if repo.had_conflict_on_last_merge_head_change(&status_entry.repo_path) {
-                self.conflicted_count += 1;
-                if is_staging_or_staged {
-                    self.conflicted_staged_count += 1;
-                }
-            } else if status_entry.status.is_created() {
-                self.new_count += 1;
-                if is_staging_or_staged {
-                    self.new_staged_count += 1;
-                }
-            } else {
-                self.tracked_count += 1;
-                if is_staging_or_staged {
-                    self.tracked_staged_count += 1;
+        let submodules = repo.update(cx, |repo, _| repo.submodules());
+        self.load_submodules_task = cx.spawn(async move |this, cx| {
+            let Some(submodules) = submodules
+                .await
+                .ok()
+                .and_then(|submodules| submodules.log_err())
+            else {
+                return;
+            };
+            this.update(cx, |this, cx| {
+                if this.submodules != submodules {
+                    this.submodules = submodules;
+                    cx.notify();
                 }
-            }
-        }
+            })
+            .ok();
+        });
     }
 
-    pub(crate) fn has_staged_changes(&self) -> bool {
-        self.tracked_staged_count > 0
-            || self.new_staged_count > 0
-            || self.conflicted_staged_count > 0
+    pub(crate) fn init_submodules(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let paths = self
+            .submodules
+            .iter()
+            .filter(|submodule| submodule.state == SubmoduleState::Uninitialized)
+            .map(|submodule| submodule.path.clone())
+            .collect::<Vec<_>>();
+        if !paths.is_empty() {
+            self.update_submodules(paths, true, window, cx);
+        }
+    }
+
+    /// Checks out the commits recorded for the submodules at the paths, or for all of them when
+    /// there are none, cloning them first when `init` is set.
+    pub(crate) fn update_submodules(
+        &mut self,
+        paths: Vec<RepoPath>,
+        init: bool,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let Some(repo) = self.active_repository.clone() else {
+            return;
+        };
+        let askpass = self.askpass_delegate("git submodule update", window, cx);
+        let update = repo.update(cx, |repo, cx| {
+            repo.update_submodules(paths, init, askpass, cx)
+        });
+        cx.spawn(async move |this, cx| {
+            let result = update.await?;
+            this.update(cx, |this, cx| match result {
+                Ok(_) => this.load_submodules(cx),
+                Err(error) => this.show_error_toast("submodule update", error, cx),
+            })
+        })
+        .detach_and_log_err(cx);
+    }
+
+    pub(crate) fn sync_submodules(&mut self, paths: Vec<RepoPath>, cx: &mut Context<Self>) {
+        let Some(repo) = self.active_repository.clone() else {
+            return;
+        };
+        let sync = repo.update(cx, |repo, _| repo.sync_submodules(paths));
+        cx.spawn(async move |this, cx| {
+            if let Err(error) = sync.await? {
+                this.update(cx, |this, cx| {
+                    this.show_error_toast("submodule sync", error, cx)
+                })?;
+            }
+            anyhow::Ok(())
+        })
+        .detach_and_log_err(cx);
+    }
+
+    /// The repository of a checked-out submodule, which the git store discovers like any other.
+    fn submodule_repository(&self, submodule: &Submodule, cx: &App) -> Option<Entity<Repository>> {
+        let abs_path = self
+            .active_repository
+            .as_ref()?
+            .read(cx)
+            .work_directory_abs_path
+            .join(submodule.path.as_std_path());
+        self.project
+            .read(cx)
+            .git_store()
+            .read(cx)
+            .repositories()
+            .values()
+            .find(|repository| *repository.read(cx).work_directory_abs_path == *abs_path)
+            .cloned()
+    }
+
+    fn load_lfs_paths(&mut self, cx: &mut Context<Self>) {
+        let Some(repo) = self
+            .active_repository
+            .clone()
+            .filter(|_| !self.project.read(cx).is_via_collab())
+        else {
+            self.lfs_paths.clear();
+            return;
+        };
+        let paths = self
+            .entries
+            .iter()
+            .filter_map(|entry| entry.status_entry())
+            .map(|entry| entry.repo_path.clone())
+            .collect();
+        let lfs_paths = repo.update(cx, |repo, _| repo.lfs_tracked_paths(paths));
+        self.load_lfs_paths_task = cx.spawn(async move |this, cx| {
+            let Some(lfs_paths) = lfs_paths.await.ok().and_then(|paths| paths.log_err()) else {
+                return;
+            };
+            this.update(cx, |this, cx| {
+                if this.lfs_paths != lfs_paths {
+                    this.lfs_paths = lfs_paths;
+                    cx.notify();
+                }
+            })
+            .ok();
+        });
+    }
+
+    fn header_state(&self, header_type: Section) -> ToggleState {
+        let (staged_count, count) = match header_type {
+            Section::New => (self.new_staged_count, self.new_count),
+            Section::Tracked => (self.tracked_staged_count, self.tracked_count),
+            Section::Conflict => (self.conflicted_staged_count, self.conflicted_count),
+        };
+        if staged_count == 0 {
+            ToggleState::Unselected
+        } else if count == staged_count {
+            ToggleState::Selected
+        } else {
+            ToggleState::Indeterminate
+        }
+    }
+
+    fn update_counts(&mut self, repo: &Repository) {
+        self.show_placeholders = false;
+        self.conflicted_count = 0;
+        self.conflicted_staged_count = 0;
+        self.new_count = 0;
+        self.tracked_count = 0;
+        self.new_staged_count = 0;
+        self.tracked_staged_count = 0;
+        self.entry_count = 0;
+
+        for status_entry in self.entries.iter().filter_map(|entry| entry.status_entry()) {
+            self.entry_count += 1;
+            let is_staging_or_staged = GitPanel::stage_status_for_entry(status_entry, repo)
+                .as_bool()
+                .unwrap_or(true);
+
+            if repo.had_conflict_on_last_merge_head_change(&status_entry.repo_path) {
+                self.conflicted_count += 1;
+                if is_staging_or_staged {
+                    self.conflicted_staged_count += 1;
+                }
+            } else if status_entry.status.is_created() {
+                self.new_count += 1;
+                if is_staging_or_staged {
+                    self.new_staged_count += 1;
+                }
+            } else {
+                self.tracked_count += 1;
+                if is_staging_or_staged {
+                    self.tracked_staged_count += 1;
+                }
+            }
+        }
+    }
+
+    pub(crate) fn has_staged_changes(&self) -> bool {
+        self.tracked_staged_count > 0
+            || self.new_staged_count > 0
+            || self.conflicted_staged_count > 0
     }
 
     pub(crate) fn has_unstaged_changes(&self) -> bool {
@@ -3845,13 +5549,54 @@ impl GitPanel {
             return;
         };
 
+        let pushed_new_branch = remote_output::pushed_new_branch(&info);
+        let SuccessMessage { message, mut style } = remote_output::format_output(&action, info);
+        // Offer a pull request for new branches even when the remote doesn't suggest one.
+        let mut pull_request_target = None;
+        if pushed_new_branch
+            && let RemoteAction::Push(branch_name, remote) = &action
+            && let Ok((provider, parsed_remote)) = self.hosting_remote(Some(&remote.name), cx)
+        {
+            let target = PullRequestTarget {
+                provider,
+                remote: parsed_remote,
+                source_branch: branch_name.to_string(),
+            };
+            if target.provider.can_create_pull_requests() {
+                pull_request_target = Some(target);
+            } else if !matches!(style, remote_output::SuccessStyle::PushPrLink { .. })
+                && let Some(link) = target.compare_url()
+            {
+                style = remote_output::SuccessStyle::PushPrLink {
+                    text: "Create Pull Request".to_string(),
+                    link,
+                };
+            }
+        }
+        let panel = cx.weak_entity();
+
         workspace.update(cx, |workspace, cx| {
-            let SuccessMessage { message, style } = remote_output::format_output(&action, info);
             let workspace_weak = cx.weak_entity();
             let operation = action.name();
 
             let status_toast = StatusToast::new(message, cx, move |this, _cx| {
                 use remote_output::SuccessStyle::*;
+                if let Some(target) = pull_request_target {
+                    return this
+                        .icon(ToastIcon::new(IconName::GitBranchAlt).color(Color::Muted))
+                        .action("Create Pull Request", move |window, cx| {
+                            panel
+                                .update(cx, |panel, cx| {
+                                    if let Err(err) =
+                                        panel.open_pull_request(target.clone(), window, cx)
+                                    {
+                                        panel.show_error_toast("create pull request", err, cx);
+                                    }
+                                })
+                                .ok();
+                        })
+                        .dismiss_button(true);
+                }
                 match style {
                     Toast => this.icon(ToastIcon::new(IconName::GitBranchAlt).color(Color::Muted)),
                     ToastWithLog { output } => this
@@ -3957,6 +5702,9 @@ impl GitPanel {
         let has_unstaged_changes = self.has_unstaged_changes();
         let has_new_changes = self.new_count > 0;
         let has_stash_items = self.stash_entries.entries.len() > 0;
+        let has_discarded_hunks = !self.discarded_hunks.is_empty();
+        let repository = self.active_repository.clone();
+        let project = self.project.clone();
 
         PopoverMenu::new(id.into())
             .trigger(
@@ -3974,7 +5722,13 @@ impl GitPanel {
                         has_new_changes,
                         sort_by_path: GitPanelSettings::get_global(cx).sort_by_path,
                         has_stash_items,
+                        has_discarded_hunks,
                         tree_view: GitPanelSettings::get_global(cx).tree_view,
+                        remotes: repository
+                            .as_ref()
+                            .and_then(|repository| repository.read(cx).cached_remotes()),
+                        repository: repository.clone(),
+                        can_manage_remotes: !project.read(cx).is_via_collab(),
                     },
                     window,
                     cx,
@@ -3983,2689 +5737,5150 @@ impl GitPanel {
             .anchor(Corner::TopRight)
     }
 
-    pub(crate) fn render_generate_commit_message_button(
-        &self,
-        cx: &Context<Self>,
-    ) -> Option<AnyElement> {
-        if !agent_settings::AgentSettings::get_global(cx).enabled(cx) {
-            return None;
+    /// Lists the remotes of the active repository for the overflow menu, which shows them as of
+    /// the last listing.
+    fn load_remotes(&self, cx: &mut Context<Self>) {
+        if self.project.read(cx).is_via_collab() {
+            return;
         }
+        let Some(repository) = self.project.read(cx).active_repository(cx) else {
+            return;
+        };
+        let remotes = repository.update(cx, |repository, _| repository.remote_infos());
+        cx.spawn(async move |_, _| {
+            remotes.await.ok();
+        })
+        .detach();
+    }
 
-        if self.generate_commit_message_task.is_some() {
-            return Some(
-                h_flex()
-                    .gap_1()
-                    .child(
-                        Icon::new(IconName::ArrowCircle)
-                            .size(IconSize::XSmall)
-                            .color(Color::Info)
-                            .with_rotate_animation(2),
-                    )
-                    .child(
-                        Label::new("Generating Commit…")
-                            .size(LabelSize::Small)
-                            .color(Color::Muted),
-                    )
-                    .into_any_element(),
-            );
+    fn hook_output_changed(&mut self, cx: &mut Context<Self>) {
+        let failed = self
+            .active_repository
+            .as_ref()
+            .and_then(|repository| repository.read(cx).hook_output())
+            .is_some_and(|hook_output| hook_output.status == HookStatus::Failed);
+        if failed {
+            self.hook_output_expanded = true;
         }
+        self.hook_output_scroll_handle.scroll_to_bottom();
+        cx.notify();
+    }
 
-        let model_registry = LanguageModelRegistry::read_global(cx);
-        let has_commit_model_configuration_error = model_registry
-            .configuration_error(model_registry.commit_message_model(), cx)
-            .is_some();
-        let can_commit = self.can_commit();
+    /// Shows what the last hook printed, expanded automatically when the hook fails.
+    fn render_hook_output(&self, cx: &mut Context<Self>) -> Option<impl IntoElement> {
+        let repository = self.active_repository.clone()?;
+        let hook_output = repository.read(cx).hook_output()?;
+        if hook_output.output.is_empty() {
+            return None;
+        }
 
-        let editor_focus_handle = self.commit_editor.focus_handle(cx);
+        let (status, icon, color) = match hook_output.status {
+            HookStatus::Running => ("running", IconName::ArrowCircle, Color::Muted),
+            HookStatus::Succeeded => ("passed", IconName::Check, Color::Success),
+            HookStatus::Failed => ("failed", IconName::XCircle, Color::Error),
+        };
+        let title = format!("{} hook {status}", hook_output.hook.as_str());
+        let output = hook_output.output.trim_end().to_string();
+        let expanded = self.hook_output_expanded;
 
         Some(
-            IconButton::new("generate-commit-message", IconName::AiEdit)
-                .shape(ui::IconButtonShape::Square)
-                .icon_color(if has_commit_model_configuration_error {
-                    Color::Disabled
-                } else {
-                    Color::Muted
-                })
-                .tooltip(move |_window, cx| {
-                    if !can_commit {
-                        Tooltip::simple("No Changes to Commit", cx)
-                    } else if has_commit_model_configuration_error {
-                        Tooltip::simple("Configure an LLM provider to generate commit messages", cx)
-                    } else {
-                        Tooltip::for_action_in(
-                            "Generate Commit Message",
-                            &git::GenerateCommitMessage,
-                            &editor_focus_handle,
-                            cx,
+            v_flex()
+                .border_t_1()
+                .border_color(cx.theme().colors().border)
+                .child(
+                    h_flex()
+                        .h_7()
+                        .px_2()
+                        .justify_between()
+                        .child(
+                            h_flex()
+                                .gap_1()
+                                .child(
+                                    Disclosure::new("hook-output-disclosure", expanded).on_click(
+                                        cx.listener(|this, _, _, cx| {
+                                            this.hook_output_expanded = !this.hook_output_expanded;
+                                            cx.notify();
+                                        }),
+                                    ),
+                                )
+                                .child(Icon::new(icon).size(IconSize::Small).color(color))
+                                .child(Label::new(title).size(LabelSize::Small)),
                         )
-                    }
-                })
-                .disabled(!can_commit || has_commit_model_configuration_error)
-                .on_click(cx.listener(move |this, _event, _window, cx| {
-                    this.generate_commit_message(cx);
-                }))
-                .into_any_element(),
+                        .child(
+                            IconButton::new("dismiss-hook-output", IconName::Close)
+                                .icon_size(IconSize::Small)
+                                .tooltip(Tooltip::text("Dismiss"))
+                                .on_click(move |_, _, cx| {
+                                    repository.update(cx, |repository, cx| {
+                                        repository.dismiss_hook_output(cx)
+                                    })
+                                }),
+                        ),
+                )
+                .when(expanded, |this| {
+                    this.child(
+                        div()
+                            .id("hook-output")
+                            .px_2()
+                            .pb_2()
+                            .max_h(rems(12.))
+                            .overflow_y_scroll()
+                            .track_scroll(&self.hook_output_scroll_handle)
+                            .font_buffer(cx)
+                            .text_xs()
+                            .text_color(cx.theme().colors().text_muted)
+                            .child(output),
+                    )
+                }),
         )
     }
 
-    pub(crate) fn render_co_authors(&self, cx: &Context<Self>) -> Option<AnyElement> {
-        let potential_co_authors = self.potential_co_authors(cx);
+    fn render_submodules(&self, cx: &mut Context<Self>) -> Option<impl IntoElement> {
+        if self.submodules.is_empty() {
+            return None;
+        }
+        let can_update = self.has_write_access(cx);
+        let expanded = self.submodules_expanded;
 
-        let (tooltip_label, icon) = if self.add_coauthors {
-            ("Remove co-authored-by", IconName::Person)
+        Some(
+            v_flex()
+                .pb_1()
+                .border_b_1()
+                .border_color(cx.theme().colors().border)
+                .child(
+                    h_flex()
+                        .h_7()
+                        .px_2()
+                        .justify_between()
+                        .child(
+                            h_flex()
+                                .gap_1()
+                                .child(Disclosure::new("submodules-disclosure", expanded).on_click(
+                                    cx.listener(|this, _, _, cx| {
+                                        this.submodules_expanded = !this.submodules_expanded;
+                                        cx.notify();
+                                    }),
+                                ))
+                                .child(Label::new("Submodules").size(LabelSize::Small)),
+                        )
+                        .child(
+                            h_flex()
+                                .gap_0p5()
+                                .child(
+                                    IconButton::new("sync-submodules", IconName::ArrowCircle)
+                                        .icon_size(IconSize::Small)
+                                        .disabled(!can_update)
+                                        .tooltip(Tooltip::for_action_title(
+                                            "Sync Submodule URLs",
+                                            &git::SyncSubmodules,
+                                        ))
+                                        .on_click(cx.listener(|this, _, _, cx| {
+                                            this.sync_submodules(Vec::new(), cx)
+                                        })),
+                                )
+                                .child(
+                                    IconButton::new("update-submodules", IconName::Download)
+                                        .icon_size(IconSize::Small)
+                                        .disabled(!can_update)
+                                        .tooltip(Tooltip::for_action_title(
+                                            "Update Submodules",
+                                            &git::UpdateSubmodules,
+                                        ))
+                                        .on_click(cx.listener(|this, _, window, cx| {
+                                            this.update_submodules(Vec::new(), false, window, cx)
+                                        })),
+                                ),
+                        ),
+                )
+                .when(expanded, |this| {
+                    this.children(self.submodules.iter().enumerate().map(|(ix, submodule)| {
+                        self.render_submodule(ix, submodule, can_update, cx)
+                    }))
+                }),
+        )
+    }
+
+    fn render_submodule(
+        &self,
+        ix: usize,
+        submodule: &Submodule,
+        can_update: bool,
+        cx: &mut Context<Self>,
+    ) -> AnyElement {
+        let repository = self.submodule_repository(submodule, cx);
+        let (status, status_color) =
+            submodule_status(submodule, repository.as_ref().map(|repo| repo.read(cx)));
+        let path = submodule.path.clone();
+        let action = if submodule.state == SubmoduleState::Uninitialized {
+            Button::new(("init-submodule", ix), "Init")
+                .label_size(LabelSize::Small)
+                .disabled(!can_update)
+                .tooltip(Tooltip::text("Clone and Check Out"))
+                .on_click(cx.listener(move |this, _, window, cx| {
+                    this.update_submodules(vec![path.clone()], true, window, cx)
+                }))
+                .into_any_element()
         } else {
-            ("Add co-authored-by", IconName::UserCheck)
+            IconButton::new(("update-submodule", ix), IconName::Download)
+                .icon_size(IconSize::Small)
+                .disabled(!can_update)
+                .tooltip(Tooltip::text("Update Submodule"))
+                .on_click(cx.listener(move |this, _, window, cx| {
+                    this.update_submodules(vec![path.clone()], false, window, cx)
+                }))
+                .into_any_element()
         };
 
-        if potential_co_authors.is_empty() {
-            None
-        } else {
-            Some(
-                IconButton::new("co-authors", icon)
-                    .shape(ui::IconButtonShape::Square)
-                    .icon_color(Color::Disabled)
-                    .selected_icon_color(Color::Selected)
-                    .toggle_state(self.add_coauthors)
-                    .tooltip(move |_, cx| {
-                        let title = format!(
-                            "{}:{}{}",
-                            tooltip_label,
-                            if potential_co_authors.len() == 1 {
-                                ""
-                            } else {
-                                "\n"
-                            },
-                            potential_co_authors
-                                .iter()
-                                .map(|(name, email)| format!(" {} <{}>", name, email))
-                                .join("\n")
-                        );
-                        Tooltip::simple(title, cx)
-                    })
-                    .on_click(cx.listener(|this, _, _, cx| {
-                        this.add_coauthors = !this.add_coauthors;
-                        cx.notify();
-                    }))
-                    .into_any_element(),
+        h_flex()
+            .id(("submodule", ix))
+            .h(self.list_item_height())
+            .px_3()
+            .gap_1p5()
+            .hover(|style| style.bg(cx.theme().colors().ghost_element_hover))
+            .child(
+                Icon::new(IconName::Folder)
+                    .size(IconSize::Small)
+                    .color(Color::Muted),
             )
-        }
-    }
-
-    fn render_git_commit_menu(
-        &self,
-        id: impl Into<ElementId>,
-        keybinding_target: Option<FocusHandle>,
-        cx: &mut Context<Self>,
-    ) -> impl IntoElement {
-        PopoverMenu::new(id.into())
-            .trigger(
-                ui::ButtonLike::new_rounded_right("commit-split-button-right")
-                    .layer(ui::ElevationIndex::ModalSurface)
-                    .size(ButtonSize::None)
+            .child(
+                h_flex()
+                    .min_w_0()
+                    .flex_1()
+                    .gap_1()
+                    .child(Label::new(submodule.path.as_unix_str().to_string()).truncate())
                     .child(
-                        h_flex()
-                            .px_1()
-                            .h_full()
-                            .justify_center()
-                            .border_l_1()
-                            .border_color(cx.theme().colors().border)
-                            .child(Icon::new(IconName::ChevronDown).size(IconSize::XSmall)),
+                        Label::new(status)
+                            .size(LabelSize::Small)
+                            .color(status_color),
                     ),
             )
-            .menu({
-                let git_panel = cx.entity();
-                let has_previous_commit = self.head_commit(cx).is_some();
-                let amend = self.amend_pending();
-                let signoff = self.signoff_enabled;
-
-                move |window, cx| {
-                    Some(ContextMenu::build(window, cx, |context_menu, _, _| {
-                        context_menu
-                            .when_some(keybinding_target.clone(), |el, keybinding_target| {
-                                el.context(keybinding_target)
-                            })
-                            .when(has_previous_commit, |this| {
-                                this.toggleable_entry(
-                                    "Amend",
-                                    amend,
-                                    IconPosition::Start,
-                                    Some(Box::new(Amend)),
-                                    {
-                                        let git_panel = git_panel.downgrade();
-                                        move |_, cx| {
-                                            git_panel
-                                                .update(cx, |git_panel, cx| {
-                                                    git_panel.toggle_amend_pending(cx);
-                                                })
-                                                .ok();
-                                        }
-                                    },
-                                )
-                            })
-                            .toggleable_entry(
-                                "Signoff",
-                                signoff,
-                                IconPosition::Start,
-                                Some(Box::new(Signoff)),
-                                move |window, cx| window.dispatch_action(Box::new(Signoff), cx),
-                            )
-                    }))
-                }
+            .child(div().flex_none().occlude().child(action))
+            .when_some(repository, |this, repository| {
+                this.cursor_pointer()
+                    .tooltip(Tooltip::text("Open Submodule"))
+                    .on_click(move |_, _, cx| {
+                        repository
+                            .update(cx, |repository, cx| repository.set_as_active_repository(cx))
+                    })
             })
-            .anchor(Corner::TopRight)
+            .into_any_element()
     }
 
-    pub fn configure_commit_button(&self, cx: &mut Context<Self>) -> (bool, &'static str) {
-        if self.has_unstaged_conflicts() {
-            (false, "You must resolve conflicts before committing")
-        } else if !self.has_staged_changes() && !self.has_tracked_changes() && !self.amend_pending {
-            (false, "No changes to commit")
-        } else if self.pending_commit.is_some() {
-            (false, "Commit in progress")
-        } else if !self.has_commit_message(cx) {
-            (false, "No commit message")
-        } else if !self.has_write_access(cx) {
-            (false, "You do not have write access to this project")
-        } else {
-            (true, self.commit_button_title())
-        }
-    }
+    /// Shows how far the running push, pull, fetch or checkout has come, with a way to stop it.
+    fn render_remote_progress(&self, cx: &mut Context<Self>) -> Option<impl IntoElement> {
+        let repository = self.active_repository.clone()?;
+        let progress = repository.read(cx).remote_progress()?.clone();
+        let can_cancel = repository.read(cx).can_cancel_remote_operation();
 
-    pub fn commit_button_title(&self) -> &'static str {
-        if self.amend_pending {
-            if self.has_staged_changes() {
-                "Amend"
-            } else if self.has_tracked_changes() {
-                "Amend Tracked"
-            } else {
-                "Amend"
-            }
-        } else if self.has_staged_changes() {
-            "Commit"
-        } else {
-            "Commit Tracked"
-        }
+        let details = progress
+            .objects
+            .map(|(current, total)| format!("{current}/{total} objects"))
+            .into_iter()
+            .chain(
+                progress
+                    .transfer
+                    .as_ref()
+                    .map(|transfer| transfer.to_string()),
+            )
+            .collect::<Vec<_>>()
+            .join(" • ");
+
+        Some(
+            v_flex()
+                .px_2()
+                .py_1p5()
+                .gap_1()
+                .border_t_1()
+                .border_color(cx.theme().colors().border)
+                .child(
+                    h_flex()
+                        .gap_1()
+                        .justify_between()
+                        .child(
+                            Label::new(format!("{} {}%", progress.phase, progress.percent))
+                                .size(LabelSize::Small),
+                        )
+                        .child(
+                            IconButton::new("cancel-remote-operation", IconName::Close)
+                                .icon_size(IconSize::Small)
+                                .disabled(!can_cancel)
+                                .tooltip(Tooltip::text("Cancel"))
+                                .on_click(move |_, _, cx| {
+                                    repository.update(cx, |repository, cx| {
+                                        repository.cancel_remote_operation(cx)
+                                    })
+                                }),
+                        ),
+                )
+                .child(ProgressBar::new(
+                    "remote-progress",
+                    progress.percent as f32,
+                    100.,
+                    cx,
+                ))
+                .when(!details.is_empty(), |this| {
+                    this.child(
+                        Label::new(details)
+                            .size(LabelSize::XSmall)
+                            .color(Color::Muted),
+                    )
+                }),
+        )
     }
 
-    fn expand_commit_editor(
+    fn resolve_in_progress_operation(
         &mut self,
-        _: &git::ExpandCommitEditor,
-        window: &mut Window,
+        kind: InProgressOperationKind,
+        resolution: OperationResolution,
         cx: &mut Context<Self>,
     ) {
-        let workspace = self.workspace.clone();
-        window.defer(cx, move |window, cx| {
-            workspace
-                .update(cx, |workspace, cx| {
-                    CommitModal::toggle(workspace, None, window, cx)
-                })
-                .ok();
+        let Some(repository) = self.active_repository.clone() else {
+            return;
+        };
+        let receiver = repository.update(cx, |repository, _| {
+            repository.resolve_in_progress_operation(kind, resolution)
+        });
+        cx.spawn(async move |this, cx| {
+            let result = receiver.await.map_err(anyhow::Error::from).and_then(|r| r);
+            this.update(cx, |this, cx| {
+                if let Err(error) = result {
+                    let action = format!("{} {}", kind.command(), resolution.flag());
+                    this.show_error_toast(action, error, cx);
+                }
+                cx.notify();
+            })
         })
+        .detach_and_log_err(cx);
     }
 
-    fn render_panel_header(
-        &self,
-        window: &mut Window,
-        cx: &mut Context<Self>,
-    ) -> Option<impl IntoElement> {
-        self.active_repository.as_ref()?;
-
-        let (text, action, stage, tooltip) =
-            if self.total_staged_count() == self.entry_count && self.entry_count > 0 {
-                ("Unstage All", UnstageAll.boxed_clone(), false, "git reset")
-            } else {
-                ("Stage All", StageAll.boxed_clone(), true, "git add --all")
-            };
-
-        let change_string = match self.changes_count {
-            0 => "No Changes".to_string(),
-            1 => "1 Change".to_string(),
-            count => format!("{} Changes", count),
+    fn open_conflicted_file(&self, path: &RepoPath, window: &mut Window, cx: &mut Context<Self>) {
+        let Some(project_path) = self
+            .active_repository
+            .as_ref()
+            .and_then(|repository| repository.read(cx).repo_path_to_project_path(path, cx))
+        else {
+            return;
         };
+        self.workspace
+            .update(cx, |workspace, cx| {
+                workspace
+                    .open_path(project_path, None, true, window, cx)
+                    .detach_and_log_err(cx);
+            })
+            .ok();
+    }
+
+    /// Shows the merge, cherry-pick, revert or rebase that stopped on conflicts, with controls to
+    /// resolve it.
+    fn render_in_progress_operation(&self, cx: &mut Context<Self>) -> Option<impl IntoElement> {
+        let repository = self.active_repository.as_ref()?.read(cx);
+        let operation = repository.merge.operation?;
+        let conflicted_paths = repository
+            .cached_status()
+            .filter(|entry| entry.status.is_conflicted())
+            .map(|entry| entry.repo_path)
+            .collect::<Vec<_>>();
+        let has_conflicts = !conflicted_paths.is_empty();
+        let can_resolve = self.has_write_access(cx);
+        let kind = operation.kind;
 
         Some(
-            self.panel_header_container(window, cx)
+            v_flex()
                 .px_2()
-                .justify_between()
+                .py_1p5()
+                .gap_1()
+                .border_b_1()
+                .border_color(cx.theme().colors().border)
+                .bg(cx.theme().status().warning_background)
                 .child(
-                    panel_button(change_string)
-                        .color(Color::Muted)
-                        .tooltip(Tooltip::for_action_title_in(
-                            "Open Diff",
-                            &Diff,
-                            &self.focus_handle,
-                        ))
-                        .on_click(|_, _, cx| {
-                            cx.defer(|cx| {
-                                cx.dispatch_action(&Diff);
-                            })
+                    h_flex()
+                        .gap_1()
+                        .child(
+                            Icon::new(IconName::Warning)
+                                .size(IconSize::Small)
+                                .color(Color::Warning),
+                        )
+                        .child(
+                            Label::new(in_progress_operation_title(operation))
+                                .size(LabelSize::Small),
+                        )
+                        .when(has_conflicts, |this| {
+                            let count = conflicted_paths.len();
+                            let noun = if count == 1 { "conflict" } else { "conflicts" };
+                            this.child(
+                                Label::new(format!("· {count} {noun}"))
+                                    .size(LabelSize::Small)
+                                    .color(Color::Muted),
+                            )
                         }),
                 )
+                .children(
+                    conflicted_paths
+                        .iter()
+                        .take(MAX_BANNER_CONFLICTS)
+                        .enumerate()
+                        .map(|(index, path)| {
+                            let path = path.clone();
+                            Button::new(
+                                ("in-progress-conflict", index),
+                                path.as_unix_str().to_string(),
+                            )
+                            .label_size(LabelSize::Small)
+                            .color(Color::Muted)
+                            .truncate(true)
+                            .start_icon(
+                                Icon::new(IconName::File)
+                                    .size(IconSize::Small)
+                                    .color(Color::Conflict),
+                            )
+                            .on_click(cx.listener(
+                                move |this, _, window, cx| {
+                                    this.open_conflicted_file(&path, window, cx);
+                                },
+                            ))
+                        }),
+                )
+                .when(conflicted_paths.len() > MAX_BANNER_CONFLICTS, |this| {
+                    this.child(
+                        Label::new(format!(
+                            "and {} more",
+                            conflicted_paths.len() - MAX_BANNER_CONFLICTS
+                        ))
+                        .size(LabelSize::Small)
+                        .color(Color::Muted),
+                    )
+                })
                 .child(
                     h_flex()
                         .gap_1()
-                        .child(self.render_overflow_menu("overflow_menu"))
+                        .justify_end()
                         .child(
-                            panel_filled_button(text)
-                                .tooltip(Tooltip::for_action_title_in(
-                                    tooltip,
-                                    action.as_ref(),
-                                    &self.focus_handle,
-                                ))
-                                .disabled(self.entry_count == 0)
-                                .on_click({
-                                    let git_panel = cx.weak_entity();
-                                    move |_, _, cx| {
-                                        git_panel
-                                            .update(cx, |git_panel, cx| {
-                                                git_panel.change_all_files_stage(stage, cx);
-                                            })
-                                            .ok();
-                                    }
-                                }),
+                            Button::new("abort-in-progress-operation", "Abort")
+                                .label_size(LabelSize::Small)
+                                .disabled(!can_resolve)
+                                .on_click(cx.listener(move |this, _, _, cx| {
+                                    this.resolve_in_progress_operation(
+                                        kind,
+                                        OperationResolution::Abort,
+                                        cx,
+                                    );
+                                })),
+                        )
+                        .when(kind.can_skip(), |this| {
+                            this.child(
+                                Button::new("skip-in-progress-operation", "Skip")
+                                    .label_size(LabelSize::Small)
+                                    .disabled(!can_resolve)
+                                    .tooltip(Tooltip::text("Skip the commit being applied"))
+                                    .on_click(cx.listener(move |this, _, _, cx| {
+                                        this.resolve_in_progress_operation(
+                                            kind,
+                                            OperationResolution::Skip,
+                                            cx,
+                                        );
+                                    })),
+                            )
+                        })
+                        .child(
+                            Button::new("continue-in-progress-operation", "Continue")
+                                .style(ButtonStyle::Filled)
+                                .label_size(LabelSize::Small)
+                                .disabled(!can_resolve || has_conflicts)
+                                .when(has_conflicts, |this| {
+                                    this.tooltip(Tooltip::text(
+                                        "Resolve and stage all conflicted files first",
+                                    ))
+                                })
+                                .on_click(cx.listener(move |this, _, _, cx| {
+                                    this.resolve_in_progress_operation(
+                                        kind,
+                                        OperationResolution::Continue,
+                                        cx,
+                                    );
+                                })),
                         ),
                 ),
         )
     }
 
-    pub(crate) fn render_remote_button(&self, cx: &mut Context<Self>) -> Option<AnyElement> {
-        let branch = self.active_repository.as_ref()?.read(cx).branch.clone();
-        if !self.can_push_and_pull(cx) {
-            return None;
-        }
-        Some(
-            h_flex()
-                .gap_1()
-                .flex_shrink_0()
-                .when_some(branch, |this, branch| {
-                    let focus_handle = Some(self.focus_handle(cx));
-
-                    this.children(render_remote_button(
-                        "remote-button",
-                        &branch,
-                        focus_handle,
-                        true,
-                    ))
+    /// Shows the commit being bisected with the buttons to mark it, or the first bad commit once
+    /// it was found.
+    fn render_bisect_status(&self, cx: &mut Context<Self>) -> Option<impl IntoElement> {
+        let repository = self.active_repository.clone()?;
+        let status = repository.read(cx).bisect_status()?.clone();
+        let can_mark = self.has_write_access(cx);
+        let (title, sha, subject, found) = match status {
+            BisectStatus::Testing {
+                sha,
+                subject,
+                remaining_steps,
+            } => {
+                let noun = if remaining_steps == 1 {
+                    "step"
+                } else {
+                    "steps"
+                };
+                (
+                    format!("Bisecting · roughly {remaining_steps} {noun} left"),
+                    sha,
+                    subject,
+                    false,
+                )
+            }
+            BisectStatus::Found { sha, subject } => {
+                ("Found the first bad commit".to_string(), sha, subject, true)
+            }
+        };
+        let short_sha = sha.get(..git::SHORT_SHA_LENGTH).unwrap_or(&sha).to_string();
+        let mark_button = |id: &'static str, label: &'static str, mark: BisectMark| {
+            let repository = repository.clone();
+            Button::new(id, label)
+                .label_size(LabelSize::Small)
+                .disabled(!can_mark)
+                .on_click(cx.listener(move |this, _, _, cx| {
+                    bisect::mark_bisect(this.workspace.clone(), repository.clone(), mark, cx);
+                }))
+        };
+
+        Some(
+            v_flex()
+                .px_2()
+                .py_1p5()
+                .gap_1()
+                .border_b_1()
+                .border_color(cx.theme().colors().border)
+                .bg(if found {
+                    cx.theme().status().info_background
+                } else {
+                    cx.theme().status().warning_background
                 })
-                .into_any_element(),
+                .child(
+                    h_flex()
+                        .gap_1()
+                        .child(
+                            Icon::new(IconName::MagnifyingGlass)
+                                .size(IconSize::Small)
+                                .color(if found { Color::Info } else { Color::Warning }),
+                        )
+                        .child(Label::new(title).size(LabelSize::Small)),
+                )
+                .child(
+                    Button::new("bisect-commit", format!("{short_sha} {subject}"))
+                        .label_size(LabelSize::Small)
+                        .color(Color::Muted)
+                        .truncate(true)
+                        .start_icon(
+                            Icon::new(IconName::FileGit)
+                                .size(IconSize::Small)
+                                .color(Color::Muted),
+                        )
+                        .tooltip(Tooltip::text("Open Commit"))
+                        .on_click(cx.listener({
+                            let repository = repository.downgrade();
+                            move |this, _, window, cx| {
+                                CommitView::open(
+                                    sha.clone(),
+                                    repository.clone(),
+                                    this.workspace.clone(),
+                                    None,
+                                    None,
+                                    window,
+                                    cx,
+                                );
+                            }
+                        })),
+                )
+                .child(
+                    h_flex()
+                        .gap_1()
+                        .justify_end()
+                        .child(
+                            Button::new("reset-bisect", if found { "Done" } else { "Reset" })
+                                .label_size(LabelSize::Small)
+                                .disabled(!can_mark)
+                                .tooltip(Tooltip::text(
+                                    "End the bisect and return to the original branch",
+                                ))
+                                .on_click(cx.listener({
+                                    let repository = repository.clone();
+                                    move |this, _, _, cx| {
+                                        bisect::reset_bisect(
+                                            this.workspace.clone(),
+                                            repository.clone(),
+                                            cx,
+                                        );
+                                    }
+                                })),
+                        )
+                        .when(!found, |this| {
+                            this.child(mark_button("bisect-skip", "Skip", BisectMark::Skip))
+                                .child(mark_button("bisect-bad", "Bad", BisectMark::Bad))
+                                .child(mark_button("bisect-good", "Good", BisectMark::Good))
+                        }),
+                ),
         )
     }
 
-    pub fn render_footer(
-        &self,
-        window: &mut Window,
-        cx: &mut Context<Self>,
-    ) -> Option<impl IntoElement> {
-        let active_repository = self.active_repository.clone()?;
-        let panel_editor_style = panel_editor_style(true, window, cx);
-        let enable_coauthors = self.render_co_authors(cx);
+    /// Warns when the first line of the commit message is longer than the configured limit.
+    pub(crate) fn render_subject_length_warning(&self, cx: &App) -> Option<AnyElement> {
+        let limit = GitPanelSettings::get_global(cx).max_subject_length;
+        let message = self.commit_editor.read(cx).text(cx);
+        let subject_length = message.lines().next().unwrap_or_default().chars().count();
+        if subject_length <= limit {
+            return None;
+        }
 
-        let editor_focus_handle = self.commit_editor.focus_handle(cx);
-        let expand_tooltip_focus_handle = editor_focus_handle;
+        Some(
+            h_flex()
+                .id("subject-length-warning")
+                .gap_0p5()
+                .child(
+                    Icon::new(IconName::Warning)
+                        .size(IconSize::XSmall)
+                        .color(Color::Warning),
+                )
+                .child(
+                    Label::new(format!("{subject_length}/{limit}"))
+                        .size(LabelSize::Small)
+                        .color(Color::Warning),
+                )
+                .tooltip(Tooltip::text(format!(
+                    "The first line is longer than {limit} characters"
+                )))
+                .into_any_element(),
+        )
+    }
 
-        let branch = active_repository.read(cx).branch.clone();
-        let head_commit = active_repository.read(cx).head_commit.clone();
+    /// Stands in for the generate button when AI features are disabled.
+    fn render_suggest_commit_message_button(&self, cx: &Context<Self>) -> AnyElement {
+        let can_commit = self.can_commit();
+        let editor_focus_handle = self.commit_editor.focus_handle(cx);
 
-        let footer_size = px(32.);
-        let gap = px(9.0);
-        let max_height = panel_editor_style
-            .text
-            .line_height_in_pixels(window.rem_size())
-            * MAX_PANEL_EDITOR_LINES
-            + gap;
+        IconButton::new("suggest-commit-message", IconName::Pencil)
+            .shape(ui::IconButtonShape::Square)
+            .icon_color(Color::Muted)
+            .tooltip(move |_window, cx| {
+                if can_commit {
+                    Tooltip::for_action_in(
+                        "Suggest Commit Message",
+                        &git::SuggestCommitMessage,
+                        &editor_focus_handle,
+                        cx,
+                    )
+                } else {
+                    Tooltip::simple("No Changes to Commit", cx)
+                }
+            })
+            .disabled(!can_commit)
+            .on_click(cx.listener(move |this, _event, window, cx| {
+                this.fill_suggested_commit_message(window, cx);
+            }))
+            .into_any_element()
+    }
 
-        let git_panel = cx.entity();
-        let display_name = SharedString::from(Arc::from(
-            active_repository
-                .read(cx)
-                .display_name()
-                .trim_end_matches("/"),
-        ));
-        let editor_is_long = self.commit_editor.update(cx, |editor, cx| {
-            editor.max_point(cx).row().0 >= MAX_PANEL_EDITOR_LINES as u32
-        });
+    pub(crate) fn render_generate_commit_message_button(
+        &self,
+        cx: &Context<Self>,
+    ) -> Option<AnyElement> {
+        if !agent_settings::AgentSettings::get_global(cx).enabled(cx) {
+            return Some(self.render_suggest_commit_message_button(cx));
+        }
 
-        let footer = v_flex()
-            .child(PanelRepoFooter::new(
-                display_name,
-                branch,
-                head_commit,
-                Some(git_panel),
-            ))
-            .child(
-                panel_editor_container(window, cx)
-                    .id("commit-editor-container")
-                    .relative()
-                    .w_full()
-                    .h(max_height + footer_size)
-                    .border_t_1()
-                    .border_color(cx.theme().colors().border)
-                    .cursor_text()
-                    .on_click(cx.listener(move |this, _: &ClickEvent, window, cx| {
-                        window.focus(&this.commit_editor.focus_handle(cx), cx);
-                    }))
+        if self.generate_commit_message_task.is_some() {
+            return Some(
+                h_flex()
+                    .gap_1()
                     .child(
-                        h_flex()
-                            .id("commit-footer")
-                            .border_t_1()
-                            .when(editor_is_long, |el| {
-                                el.border_color(cx.theme().colors().border_variant)
-                            })
-                            .absolute()
-                            .bottom_0()
-                            .left_0()
-                            .w_full()
-                            .px_2()
-                            .h(footer_size)
-                            .flex_none()
-                            .justify_between()
-                            .child(
-                                self.render_generate_commit_message_button(cx)
-                                    .unwrap_or_else(|| div().into_any_element()),
-                            )
-                            .child(
-                                h_flex()
-                                    .gap_0p5()
-                                    .children(enable_coauthors)
-                                    .child(self.render_commit_button(cx)),
-                            ),
+                        Icon::new(IconName::ArrowCircle)
+                            .size(IconSize::XSmall)
+                            .color(Color::Info)
+                            .with_rotate_animation(2),
                     )
                     .child(
-                        div()
-                            .pr_2p5()
-                            .on_action(|&zed_actions::editor::MoveUp, _, cx| {
-                                cx.stop_propagation();
-                            })
-                            .on_action(|&zed_actions::editor::MoveDown, _, cx| {
-                                cx.stop_propagation();
-                            })
-                            .child(EditorElement::new(&self.commit_editor, panel_editor_style)),
+                        Label::new("Generating Commit…")
+                            .size(LabelSize::Small)
+                            .color(Color::Muted),
                     )
-                    .child(
-                        h_flex()
-                            .absolute()
-                            .top_2()
-                            .right_2()
-                            .opacity(0.5)
-                            .hover(|this| this.opacity(1.0))
-                            .child(
-                                panel_icon_button("expand-commit-editor", IconName::Maximize)
-                                    .icon_size(IconSize::Small)
-                                    .size(ui::ButtonSize::Default)
-                                    .tooltip(move |_window, cx| {
-                                        Tooltip::for_action_in(
-                                            "Open Commit Modal",
-                                            &git::ExpandCommitEditor,
-                                            &expand_tooltip_focus_handle,
-                                            cx,
-                                        )
-                                    })
-                                    .on_click(cx.listener({
-                                        move |_, _, window, cx| {
-                                            window.dispatch_action(
-                                                git::ExpandCommitEditor.boxed_clone(),
-                                                cx,
-                                            )
-                                        }
-                                    })),
-                            ),
-                    ),
+                    .into_any_element(),
             );
+        }
 
-        Some(footer)
+        let model_registry = LanguageModelRegistry::read_global(cx);
+        let has_commit_model_configuration_error = model_registry
+            .configuration_error(model_registry.commit_message_model(), cx)
+            .is_some();
+        let can_commit = self.can_commit();
+
+        let editor_focus_handle = self.commit_editor.focus_handle(cx);
+
+        Some(
+            IconButton::new("generate-commit-message", IconName::AiEdit)
+                .shape(ui::IconButtonShape::Square)
+                .icon_color(if has_commit_model_configuration_error {
+                    Color::Disabled
+                } else {
+                    Color::Muted
+                })
+                .tooltip(move |_window, cx| {
+                    if !can_commit {
+                        Tooltip::simple("No Changes to Commit", cx)
+                    } else if has_commit_model_configuration_error {
+                        Tooltip::simple("Configure an LLM provider to generate commit messages", cx)
+                    } else {
+                        Tooltip::for_action_in(
+                            "Generate Commit Message",
+                            &git::GenerateCommitMessage,
+                            &editor_focus_handle,
+                            cx,
+                        )
+                    }
+                })
+                .disabled(!can_commit || has_commit_model_configuration_error)
+                .on_click(cx.listener(move |this, _event, _window, cx| {
+                    this.generate_commit_message(cx);
+                }))
+                .into_any_element(),
+        )
     }
 
-    fn render_commit_button(&self, cx: &mut Context<Self>) -> impl IntoElement {
-        let (can_commit, tooltip) = self.configure_commit_button(cx);
-        let title = self.commit_button_title();
-        let commit_tooltip_focus_handle = self.commit_editor.focus_handle(cx);
-        let amend = self.amend_pending();
-        let signoff = self.signoff_enabled;
+    fn render_add_co_author_button(&self, cx: &Context<Self>) -> impl IntoElement {
+        let editor_focus_handle = self.commit_editor.focus_handle(cx);
+        IconButton::new("add-co-author", IconName::UserRoundPen)
+            .shape(ui::IconButtonShape::Square)
+            .icon_color(Color::Muted)
+            .tooltip(move |_window, cx| {
+                Tooltip::for_action_in("Add Co-author", &git::AddCoAuthor, &editor_focus_handle, cx)
+            })
+            .on_click(cx.listener(|this, _, window, cx| {
+                this.add_co_author(&git::AddCoAuthor, window, cx);
+            }))
+    }
 
-        let label_color = if self.pending_commit.is_some() {
-            Color::Disabled
-        } else {
-            Color::Default
-        };
+    /// Offers the messages recently committed in this repository, to recall one after an aborted
+    /// commit.
+    fn render_commit_message_history_menu(&self, cx: &Context<Self>) -> Option<impl IntoElement> {
+        let history = self
+            .commit_messages
+            .as_ref()
+            .map(|(_, commit_messages)| commit_messages.history.clone())
+            .filter(|history| !history.is_empty())?;
+        let git_panel = cx.weak_entity();
 
-        div()
-            .id("commit-wrapper")
-            .on_hover(cx.listener(move |this, hovered, _, cx| {
-                this.show_placeholders =
-                    *hovered && !this.has_staged_changes() && !this.has_unstaged_conflicts();
-                cx.notify()
-            }))
-            .child(SplitButton::new(
-                ButtonLike::new_rounded_left(ElementId::Name(
-                    format!("split-button-left-{}", title).into(),
-                ))
-                .layer(ElevationIndex::ModalSurface)
-                .size(ButtonSize::Compact)
-                .child(
-                    Label::new(title)
-                        .size(LabelSize::Small)
-                        .color(label_color)
-                        .mr_0p5(),
+        Some(
+            PopoverMenu::new("commit-message-history")
+                .trigger_with_tooltip(
+                    IconButton::new("commit-message-history-button", IconName::HistoryRerun)
+                        .shape(ui::IconButtonShape::Square)
+                        .icon_color(Color::Muted),
+                    Tooltip::text("Recent Commit Messages"),
                 )
-                .on_click({
-                    let git_panel = cx.weak_entity();
-                    move |_, window, cx| {
-                        telemetry::event!("Git Committed", source = "Git Panel");
-                        git_panel
-                            .update(cx, |git_panel, cx| {
-                                git_panel.commit_changes(
-                                    CommitOptions { amend, signoff },
-                                    window,
-                                    cx,
-                                );
-                            })
-                            .ok();
-                    }
-                })
-                .disabled(!can_commit || self.modal_open)
-                .tooltip({
-                    let handle = commit_tooltip_focus_handle.clone();
-                    move |_window, cx| {
-                        if can_commit {
-                            Tooltip::with_meta_in(
-                                tooltip,
-                                Some(if amend { &git::Amend } else { &git::Commit }),
-                                format!(
-                                    "git commit{}{}",
-                                    if amend { " --amend" } else { "" },
-                                    if signoff { " --signoff" } else { "" }
-                                ),
-                                &handle.clone(),
-                                cx,
-                            )
-                        } else {
-                            Tooltip::simple(tooltip, cx)
+                .menu(move |window, cx| {
+                    let history = history.clone();
+                    let git_panel = git_panel.clone();
+                    Some(ContextMenu::build(window, cx, move |mut menu, _, _| {
+                        for message in history {
+                            let subject = message.lines().next().unwrap_or_default().to_string();
+                            let git_panel = git_panel.clone();
+                            menu = menu.entry(subject, None, move |window, cx| {
+                                git_panel
+                                    .update(cx, |git_panel, cx| {
+                                        git_panel.recall_commit_message(
+                                            message.clone(),
+                                            window,
+                                            cx,
+                                        );
+                                    })
+                                    .ok();
+                            });
                         }
-                    }
-                }),
-                self.render_git_commit_menu(
-                    ElementId::Name(format!("split-button-right-{}", title).into()),
-                    Some(commit_tooltip_focus_handle),
-                    cx,
-                )
-                .into_any_element(),
-            ))
+                        menu
+                    }))
+                })
+                .anchor(Corner::BottomLeft),
+        )
     }
 
-    fn render_pending_amend(&self, cx: &mut Context<Self>) -> impl IntoElement {
-        h_flex()
-            .py_1p5()
-            .px_2()
-            .gap_1p5()
-            .justify_between()
-            .border_t_1()
-            .border_color(cx.theme().colors().border.opacity(0.8))
-            .child(
-                div()
-                    .flex_grow()
-                    .overflow_hidden()
-                    .max_w(relative(0.85))
+    pub(crate) fn render_co_authors(&self, cx: &Context<Self>) -> Option<AnyElement> {
+        let potential_co_authors = self.potential_co_authors(cx);
+
+        let (tooltip_label, icon) = if self.add_coauthors {
+            ("Remove co-authored-by", IconName::Person)
+        } else {
+            ("Add co-authored-by", IconName::UserCheck)
+        };
+
+        if potential_co_authors.is_empty() {
+            None
+        } else {
+            Some(
+                IconButton::new("co-authors", icon)
+                    .shape(ui::IconButtonShape::Square)
+                    .icon_color(Color::Disabled)
+                    .selected_icon_color(Color::Selected)
+                    .toggle_state(self.add_coauthors)
+                    .tooltip(move |_, cx| {
+                        let title = format!(
+                            "{}:{}{}",
+                            tooltip_label,
+                            if potential_co_authors.len() == 1 {
+                                ""
+                            } else {
+                                "\n"
+                            },
+                            potential_co_authors
+                                .iter()
+                                .map(|(name, email)| format!(" {} <{}>", name, email))
+                                .join("\n")
+                        );
+                        Tooltip::simple(title, cx)
+                    })
+                    .on_click(cx.listener(|this, _, _, cx| {
+                        this.add_coauthors = !this.add_coauthors;
+                        cx.notify();
+                    }))
+                    .into_any_element(),
+            )
+        }
+    }
+
+    fn render_git_commit_menu(
+        &self,
+        id: impl Into<ElementId>,
+        keybinding_target: Option<FocusHandle>,
+        cx: &mut Context<Self>,
+    ) -> impl IntoElement {
+        PopoverMenu::new(id.into())
+            .trigger(
+                ui::ButtonLike::new_rounded_right("commit-split-button-right")
+                    .layer(ui::ElevationIndex::ModalSurface)
+                    .size(ButtonSize::None)
                     .child(
-                        Label::new("This will update your most recent commit.")
-                            .size(LabelSize::Small)
-                            .truncate(),
+                        h_flex()
+                            .px_1()
+                            .h_full()
+                            .justify_center()
+                            .border_l_1()
+                            .border_color(cx.theme().colors().border)
+                            .child(Icon::new(IconName::ChevronDown).size(IconSize::XSmall)),
                     ),
             )
-            .child(
-                panel_button("Cancel")
-                    .size(ButtonSize::Default)
-                    .on_click(cx.listener(|this, _, _, cx| this.set_amend_pending(false, cx))),
-            )
+            .menu({
+                let git_panel = cx.entity();
+                let has_previous_commit = self.head_commit(cx).is_some();
+                let amend = self.amend_pending();
+                let signoff = self.signoff_enabled;
+                let allow_no_verify = ProjectSettings::get_global(cx).git.allow_no_verify;
+
+                move |window, cx| {
+                    Some(ContextMenu::build(window, cx, |context_menu, _, _| {
+                        context_menu
+                            .when_some(keybinding_target.clone(), |el, keybinding_target| {
+                                el.context(keybinding_target)
+                            })
+                            .when(has_previous_commit, |this| {
+                                this.toggleable_entry(
+                                    "Amend",
+                                    amend,
+                                    IconPosition::Start,
+                                    Some(Box::new(Amend)),
+                                    {
+                                        let git_panel = git_panel.downgrade();
+                                        move |_, cx| {
+                                            git_panel
+                                                .update(cx, |git_panel, cx| {
+                                                    git_panel.toggle_amend_pending(cx);
+                                                })
+                                                .ok();
+                                        }
+                                    },
+                                )
+                            })
+                            .toggleable_entry(
+                                "Signoff",
+                                signoff,
+                                IconPosition::Start,
+                                Some(Box::new(Signoff)),
+                                move |window, cx| window.dispatch_action(Box::new(Signoff), cx),
+                            )
+                            .when(allow_no_verify, |this| {
+                                this.separator().entry(
+                                    "Commit Without Hooks",
+                                    Some(Box::new(git::CommitNoVerify)),
+                                    move |window, cx| {
+                                        window.dispatch_action(Box::new(git::CommitNoVerify), cx)
+                                    },
+                                )
+                            })
+                    }))
+                }
+            })
+            .anchor(Corner::TopRight)
     }
 
-    fn render_previous_commit(
+    pub fn configure_commit_button(&self, cx: &mut Context<Self>) -> (bool, &'static str) {
+        if self.has_unstaged_conflicts() {
+            (false, "You must resolve conflicts before committing")
+        } else if !self.has_staged_changes() && !self.has_tracked_changes() && !self.amend_pending {
+            (false, "No changes to commit")
+        } else if self.pending_commit.is_some() {
+            (false, "Commit in progress")
+        } else if !self.has_commit_message(cx) {
+            (false, "No commit message")
+        } else if !self.has_write_access(cx) {
+            (false, "You do not have write access to this project")
+        } else {
+            (true, self.commit_button_title())
+        }
+    }
+
+    pub fn commit_button_title(&self) -> &'static str {
+        if self.amend_pending {
+            if self.has_staged_changes() {
+                "Amend"
+            } else if self.has_tracked_changes() {
+                "Amend Tracked"
+            } else {
+                "Amend"
+            }
+        } else if !self.marked_entries.is_empty() {
+            "Commit Selected"
+        } else if self.has_staged_changes() {
+            "Commit"
+        } else {
+            "Commit Tracked"
+        }
+    }
+
+    fn expand_commit_editor(
+        &mut self,
+        _: &git::ExpandCommitEditor,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let workspace = self.workspace.clone();
+        window.defer(cx, move |window, cx| {
+            workspace
+                .update(cx, |workspace, cx| {
+                    CommitModal::toggle(workspace, None, window, cx)
+                })
+                .ok();
+        })
+    }
+
+    fn render_panel_header(
         &self,
-        _window: &mut Window,
+        window: &mut Window,
         cx: &mut Context<Self>,
     ) -> Option<impl IntoElement> {
-        let active_repository = self.active_repository.as_ref()?;
-        let branch = active_repository.read(cx).branch.as_ref()?;
-        let commit = branch.most_recent_commit.as_ref()?.clone();
-        let workspace = self.workspace.clone();
-        let this = cx.entity();
-        let can_open_git_graph = cx.has_flag::<GitGraphFeatureFlag>();
+        self.active_repository.as_ref()?;
 
-        Some(
-            h_flex()
-                .p_1p5()
-                .gap_1p5()
-                .justify_between()
-                .border_t_1()
-                .border_color(cx.theme().colors().border.opacity(0.8))
-                .child(
-                    div()
-                        .id("commit-msg-hover")
-                        .cursor_pointer()
-                        .px_1()
-                        .rounded_sm()
-                        .line_clamp(1)
-                        .hover(|s| s.bg(cx.theme().colors().element_hover))
-                        .child(
-                            Label::new(commit.subject.clone())
-                                .size(LabelSize::Small)
-                                .truncate(),
-                        )
-                        .on_click({
-                            let commit = commit.clone();
-                            let repo = active_repository.downgrade();
-                            move |_, window, cx| {
-                                CommitView::open(
-                                    commit.sha.to_string(),
-                                    repo.clone(),
-                                    workspace.clone(),
-                                    None,
-                                    None,
-                                    window,
-                                    cx,
-                                );
-                            }
-                        })
-                        .hoverable_tooltip({
-                            let repo = active_repository.clone();
-                            move |window, cx| {
-                                GitPanelMessageTooltip::new(
-                                    this.clone(),
-                                    commit.sha.clone(),
-                                    repo.clone(),
-                                    window,
-                                    cx,
-                                )
-                                .into()
-                            }
+        let (text, action, stage, tooltip) =
+            if self.total_staged_count() == self.entry_count && self.entry_count > 0 {
+                ("Unstage All", UnstageAll.boxed_clone(), false, "git reset")
+            } else {
+                ("Stage All", StageAll.boxed_clone(), true, "git add --all")
+            };
+
+        let change_string = match self.changes_count {
+            0 => "No Changes".to_string(),
+            1 => "1 Change".to_string(),
+            count => format!("{} Changes", count),
+        };
+
+        Some(
+            self.panel_header_container(window, cx)
+                .px_2()
+                .justify_between()
+                .child(
+                    panel_button(change_string)
+                        .color(Color::Muted)
+                        .tooltip(Tooltip::for_action_title_in(
+                            "Open Diff",
+                            &Diff,
+                            &self.focus_handle,
+                        ))
+                        .on_click(|_, _, cx| {
+                            cx.defer(|cx| {
+                                cx.dispatch_action(&Diff);
+                            })
                         }),
                 )
                 .child(
                     h_flex()
-                        .gap_0p5()
-                        .when(commit.has_parent, |this| {
-                            let has_unstaged = self.has_unstaged_changes();
-                            this.child(
-                                panel_icon_button("undo", IconName::Undo)
-                                    .icon_size(IconSize::Small)
-                                    .tooltip(move |_window, cx| {
-                                        Tooltip::with_meta(
-                                            "Uncommit",
-                                            Some(&git::Uncommit),
-                                            if has_unstaged {
-                                                "git reset HEAD^ --soft"
-                                            } else {
-                                                "git reset HEAD^"
-                                            },
-                                            cx,
-                                        )
-                                    })
-                                    .on_click(
-                                        cx.listener(|this, _, window, cx| {
-                                            this.uncommit(window, cx)
-                                        }),
-                                    ),
-                            )
-                        })
-                        .when(can_open_git_graph, |this| {
-                            this.child(
-                                panel_icon_button("git-graph-button", IconName::GitGraph)
-                                    .icon_size(IconSize::Small)
-                                    .tooltip(|_window, cx| {
-                                        Tooltip::for_action("Open Git Graph", &Open, cx)
-                                    })
-                                    .on_click(|_, window, cx| {
-                                        window.dispatch_action(Open.boxed_clone(), cx)
-                                    }),
-                            )
-                        }),
+                        .gap_1()
+                        .children(self.render_flagged_files_menu(cx))
+                        .child(self.render_tree_view_toggle(cx))
+                        .child(self.render_overflow_menu("overflow_menu"))
+                        .child(
+                            panel_filled_button(text)
+                                .tooltip(Tooltip::for_action_title_in(
+                                    tooltip,
+                                    action.as_ref(),
+                                    &self.focus_handle,
+                                ))
+                                .disabled(self.entry_count == 0)
+                                .on_click({
+                                    let git_panel = cx.weak_entity();
+                                    move |_, window, cx| {
+                                        git_panel
+                                            .update(cx, |git_panel, cx| {
+                                                if stage {
+                                                    git_panel.stage_all_checking_conflict_markers(
+                                                        window, cx,
+                                                    );
+                                                } else {
+                                                    git_panel.change_all_files_stage(false, cx);
+                                                }
+                                            })
+                                            .ok();
+                                    }
+                                }),
+                        ),
                 ),
         )
     }
 
-    fn render_empty_state(&self, cx: &mut Context<Self>) -> impl IntoElement {
-        let has_repo = self.active_repository.is_some();
-        let has_no_repo = self.active_repository.is_none();
-        let worktree_count = self.project.read(cx).visible_worktrees(cx).count();
-
-        let should_show_branch_diff =
-            has_repo && self.changes_count == 0 && !self.is_on_main_branch(cx);
-
-        let label = if has_repo {
-            "No changes to commit"
+    /// Lists the files whose changes git leaves out of the status because of their index flags,
+    /// which don't show up in the panel otherwise, so that the flags can be cleared.
+    fn render_flagged_files_menu(&self, cx: &Context<Self>) -> Option<impl IntoElement> {
+        let repo = self.active_repository.clone()?;
+        let count = repo.read(cx).flagged_files().len();
+        if count == 0 {
+            return None;
+        }
+        let tooltip = if count == 1 {
+            "1 file's changes are hidden by index flags".to_string()
         } else {
-            "No Git repositories"
+            format!("{count} files' changes are hidden by index flags")
         };
+        let path_style = self.project.read(cx).path_style(cx);
+        let git_panel = cx.weak_entity();
 
-        v_flex()
-            .gap_1p5()
-            .flex_1()
-            .items_center()
-            .justify_center()
-            .child(Label::new(label).size(LabelSize::Small).color(Color::Muted))
-            .when(has_no_repo && worktree_count > 0, |this| {
-                this.child(
-                    panel_filled_button("Initialize Repository")
-                        .tooltip(Tooltip::for_action_title_in(
-                            "git init",
-                            &git::Init,
-                            &self.focus_handle,
-                        ))
-                        .on_click(move |_, _, cx| {
-                            cx.defer(move |cx| {
-                                cx.dispatch_action(&git::Init);
-                            })
-                        }),
-                )
-            })
-            .when(should_show_branch_diff, |this| {
-                this.child(
-                    panel_filled_button("View Branch Diff")
-                        .tooltip(move |_, cx| {
-                            Tooltip::with_meta(
-                                "Branch Diff",
-                                Some(&BranchDiff),
-                                "Show diff between working directory and default branch",
-                                cx,
-                            )
-                        })
-                        .on_click(move |_, _, cx| {
-                            cx.defer(move |cx| {
-                                cx.dispatch_action(&BranchDiff);
-                            })
-                        }),
+        Some(
+            PopoverMenu::new("flagged-files")
+                .trigger_with_tooltip(
+                    IconButton::new("flagged-files-trigger", IconName::EyeOff)
+                        .icon_size(IconSize::Small)
+                        .icon_color(Color::Muted),
+                    Tooltip::text(tooltip),
                 )
-            })
+                .menu(move |window, cx| {
+                    let flagged_files = repo.read(cx).flagged_files().to_vec();
+                    let repo = repo.clone();
+                    let git_panel = git_panel.clone();
+                    Some(ContextMenu::build(window, cx, move |mut menu, _, _| {
+                        for flag in [IndexFlag::AssumeUnchanged, IndexFlag::SkipWorktree] {
+                            let paths = flagged_files
+                                .iter()
+                                .filter(|flagged_file| flagged_file.has(flag))
+                                .map(|flagged_file| flagged_file.path.clone())
+                                .collect::<Vec<_>>();
+                            if paths.is_empty() {
+                                continue;
+                            }
+                            menu = menu.header(format!("{} (Click to Clear)", flag.label()));
+                            for path in &paths {
+                                let clear_flag = {
+                                    let repo = repo.clone();
+                                    let git_panel = git_panel.clone();
+                                    let path = path.clone();
+                                    move |_: &mut Window, cx: &mut App| {
+                                        git_panel
+                                            .update(cx, |git_panel, cx| {
+                                                git_panel.set_index_flag(
+                                                    repo.clone(),
+                                                    vec![path.clone()],
+                                                    flag,
+                                                    false,
+                                                    cx,
+                                                )
+                                            })
+                                            .ok();
+                                    }
+                                };
+                                menu = menu.entry(
+                                    path.display(path_style).to_string(),
+                                    None,
+                                    clear_flag,
+                                );
+                            }
+                        }
+                        menu
+                    }))
+                })
+                .anchor(Corner::TopRight),
+        )
     }
 
-    fn is_on_main_branch(&self, cx: &Context<Self>) -> bool {
-        let Some(repo) = self.active_repository.as_ref() else {
-            return false;
-        };
+    fn render_tree_view_toggle(&self, cx: &Context<Self>) -> impl IntoElement {
+        let tree_view = GitPanelSettings::get_global(cx).tree_view;
+        IconButton::new("tree_view_toggle", IconName::ListTree)
+            .icon_size(IconSize::Small)
+            .toggle_state(tree_view)
+            .tooltip(Tooltip::for_action_title_in(
+                if tree_view { "Flat View" } else { "Tree View" },
+                &ToggleTreeView,
+                &self.focus_handle,
+            ))
+            .on_click(cx.listener(|this, _, window, cx| {
+                this.toggle_tree_view(&ToggleTreeView, window, cx);
+            }))
+    }
 
-        let Some(branch) = repo.read(cx).branch.as_ref() else {
-            return false;
-        };
+    pub(crate) fn render_remote_button(&self, cx: &mut Context<Self>) -> Option<AnyElement> {
+        let branch = self.active_repository.as_ref()?.read(cx).branch.clone();
+        if !self.can_push_and_pull(cx) {
+            return None;
+        }
+        Some(
+            h_flex()
+                .gap_1()
+                .flex_shrink_0()
+                .when_some(branch, |this, branch| {
+                    let focus_handle = Some(self.focus_handle(cx));
 
-        let branch_name = branch.name();
-        matches!(branch_name, "main" | "master")
+                    this.children(render_remote_button(
+                        "remote-button",
+                        &branch,
+                        focus_handle,
+                        true,
+                    ))
+                })
+                .into_any_element(),
+        )
     }
 
-    fn render_buffer_header_controls(
+    pub fn render_footer(
         &self,
-        entity: &Entity<Self>,
-        file: &Arc<dyn File>,
-        _: &Window,
-        cx: &App,
-    ) -> Option<AnyElement> {
-        let repo = self.active_repository.as_ref()?.read(cx);
-        let project_path = (file.worktree_id(cx), file.path().clone()).into();
-        let repo_path = repo.project_path_to_repo_path(&project_path, cx)?;
-        let ix = self.entry_by_path(&repo_path)?;
-        let entry = self.entries.get(ix)?;
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Option<impl IntoElement> {
+        let active_repository = self.active_repository.clone()?;
+        let panel_editor_style = panel_editor_style(true, window, cx);
+        let enable_coauthors = self.render_co_authors(cx);
 
-        let is_staging_or_staged = repo
-            .pending_ops_for_path(&repo_path)
-            .map(|ops| ops.staging() || ops.staged())
-            .or_else(|| {
-                repo.status_for_path(&repo_path)
-                    .and_then(|status| status.status.staging().as_bool())
-            })
-            .or_else(|| {
-                entry
-                    .status_entry()
-                    .and_then(|entry| entry.staging.as_bool())
-            });
+        let editor_focus_handle = self.commit_editor.focus_handle(cx);
+        let expand_tooltip_focus_handle = editor_focus_handle;
 
-        let checkbox = Checkbox::new("stage-file", is_staging_or_staged.into())
-            .disabled(!self.has_write_access(cx))
-            .fill()
-            .elevation(ElevationIndex::Surface)
-            .on_click({
-                let entry = entry.clone();
-                let git_panel = entity.downgrade();
-                move |_, window, cx| {
-                    git_panel
-                        .update(cx, |this, cx| {
-                            this.toggle_staged_for_entry(&entry, window, cx);
-                            cx.stop_propagation();
-                        })
-                        .ok();
-                }
-            });
-        Some(
-            h_flex()
-                .id("start-slot")
-                .text_lg()
-                .child(checkbox)
-                .on_mouse_down(MouseButton::Left, |_, _, cx| {
-                    // prevent the list item active state triggering when toggling checkbox
-                    cx.stop_propagation();
-                })
-                .into_any_element(),
-        )
-    }
+        let branch = active_repository.read(cx).branch.clone();
+        let head_commit = active_repository.read(cx).head_commit.clone();
 
-    fn render_entries(
-        &self,
-        has_write_access: bool,
-        repo: Entity<Repository>,
-        window: &mut Window,
-        cx: &mut Context<Self>,
-    ) -> impl IntoElement {
-        let (is_tree_view, entry_count) = match &self.view_mode {
-            GitPanelViewMode::Tree(state) => (true, state.logical_indices.len()),
-            GitPanelViewMode::Flat => (false, self.entries.len()),
-        };
-        let repo = repo.downgrade();
+        let footer_size = px(32.);
+        let gap = px(9.0);
+        let max_height = panel_editor_style
+            .text
+            .line_height_in_pixels(window.rem_size())
+            * MAX_PANEL_EDITOR_LINES
+            + gap;
 
-        v_flex()
-            .flex_1()
-            .size_full()
-            .overflow_hidden()
-            .relative()
+        let git_panel = cx.entity();
+        let display_name = SharedString::from(Arc::from(
+            active_repository
+                .read(cx)
+                .display_name()
+                .trim_end_matches("/"),
+        ));
+        let editor_is_long = self.commit_editor.update(cx, |editor, cx| {
+            editor.max_point(cx).row().0 >= MAX_PANEL_EDITOR_LINES as u32
+        });
+
+        let footer = v_flex()
+            .child(PanelRepoFooter::new(
+                display_name,
+                branch,
+                head_commit,
+                Some(git_panel),
+            ))
+            .children(self.render_hook_output(cx))
+            .children(self.render_remote_progress(cx))
             .child(
-                h_flex()
-                    .flex_1()
-                    .size_full()
+                panel_editor_container(window, cx)
+                    .id("commit-editor-container")
                     .relative()
-                    .overflow_hidden()
+                    .w_full()
+                    .h(max_height + footer_size)
+                    .border_t_1()
+                    .border_color(cx.theme().colors().border)
+                    .cursor_text()
+                    .on_click(cx.listener(move |this, _: &ClickEvent, window, cx| {
+                        window.focus(&this.commit_editor.focus_handle(cx), cx);
+                    }))
                     .child(
-                        uniform_list(
-                            "entries",
-                            entry_count,
-                            cx.processor(move |this, range: Range<usize>, window, cx| {
-                                let Some(repo) = repo.upgrade() else {
-                                    return Vec::new();
-                                };
-                                let repo = repo.read(cx);
-
-                                let mut items = Vec::with_capacity(range.end - range.start);
-
-                                for ix in range.into_iter().map(|ix| match &this.view_mode {
-                                    GitPanelViewMode::Tree(state) => state.logical_indices[ix],
-                                    GitPanelViewMode::Flat => ix,
-                                }) {
-                                    match &this.entries.get(ix) {
-                                        Some(GitListEntry::Status(entry)) => {
-                                            items.push(this.render_status_entry(
-                                                ix,
-                                                entry,
-                                                0,
-                                                has_write_access,
-                                                repo,
-                                                window,
-                                                cx,
-                                            ));
-                                        }
-                                        Some(GitListEntry::TreeStatus(entry)) => {
-                                            items.push(this.render_status_entry(
-                                                ix,
-                                                &entry.entry,
-                                                entry.depth,
-                                                has_write_access,
-                                                repo,
-                                                window,
-                                                cx,
-                                            ));
-                                        }
-                                        Some(GitListEntry::Directory(entry)) => {
-                                            items.push(this.render_directory_entry(
-                                                ix,
-                                                entry,
-                                                has_write_access,
-                                                window,
-                                                cx,
-                                            ));
-                                        }
-                                        Some(GitListEntry::Header(header)) => {
-                                            items.push(this.render_list_header(
-                                                ix,
-                                                header,
-                                                has_write_access,
-                                                window,
+                        h_flex()
+                            .id("commit-footer")
+                            .border_t_1()
+                            .when(editor_is_long, |el| {
+                                el.border_color(cx.theme().colors().border_variant)
+                            })
+                            .absolute()
+                            .bottom_0()
+                            .left_0()
+                            .w_full()
+                            .px_2()
+                            .h(footer_size)
+                            .flex_none()
+                            .justify_between()
+                            .child(
+                                h_flex()
+                                    .gap_1()
+                                    .children(self.render_generate_commit_message_button(cx))
+                                    .children(self.render_commit_message_history_menu(cx))
+                                    .children(self.render_subject_length_warning(cx)),
+                            )
+                            .child(
+                                h_flex()
+                                    .gap_0p5()
+                                    .child(self.render_add_co_author_button(cx))
+                                    .children(enable_coauthors)
+                                    .child(self.render_commit_button(cx)),
+                            ),
+                    )
+                    .child(
+                        div()
+                            .pr_2p5()
+                            .on_action(|&zed_actions::editor::MoveUp, _, cx| {
+                                cx.stop_propagation();
+                            })
+                            .on_action(|&zed_actions::editor::MoveDown, _, cx| {
+                                cx.stop_propagation();
+                            })
+                            .child(EditorElement::new(&self.commit_editor, panel_editor_style)),
+                    )
+                    .child(
+                        h_flex()
+                            .absolute()
+                            .top_2()
+                            .right_2()
+                            .opacity(0.5)
+                            .hover(|this| this.opacity(1.0))
+                            .child(
+                                panel_icon_button("expand-commit-editor", IconName::Maximize)
+                                    .icon_size(IconSize::Small)
+                                    .size(ui::ButtonSize::Default)
+                                    .tooltip(move |_window, cx| {
+                                        Tooltip::for_action_in(
+                                            "Open Commit Modal",
+                                            &git::ExpandCommitEditor,
+                                            &expand_tooltip_focus_handle,
+                                            cx,
+                                        )
+                                    })
+                                    .on_click(cx.listener({
+                                        move |_, _, window, cx| {
+                                            window.dispatch_action(
+                                                git::ExpandCommitEditor.boxed_clone(),
                                                 cx,
-                                            ));
+                                            )
                                         }
-                                        None => {}
-                                    }
-                                }
+                                    })),
+                            ),
+                    ),
+            );
 
-                                items
-                            }),
-                        )
-                        .when(is_tree_view, |list| {
-                            let indent_size = px(TREE_INDENT);
-                            list.with_decoration(
-                                ui::indent_guides(indent_size, IndentGuideColors::panel(cx))
-                                    .with_compute_indents_fn(
-                                        cx.entity(),
-                                        |this, range, _window, _cx| {
-                                            this.compute_visible_depths(range)
-                                        },
-                                    )
-                                    .with_render_fn(cx.entity(), |_, params, _, _| {
-                                        // Magic number to align the tree item is 3 here
-                                        // because we're using 12px as the left-side padding
-                                        // and 3 makes the alignment work with the bounding box of the icon
-                                        let left_offset = px(TREE_INDENT + 3_f32);
-                                        let indent_size = params.indent_size;
-                                        let item_height = params.item_height;
+        Some(footer)
+    }
 
-                                        params
-                                            .indent_guides
-                                            .into_iter()
-                                            .map(|layout| {
-                                                let bounds = Bounds::new(
-                                                    point(
-                                                        layout.offset.x * indent_size + left_offset,
-                                                        layout.offset.y * item_height,
-                                                    ),
-                                                    size(px(1.), layout.length * item_height),
-                                                );
-                                                RenderedIndentGuide {
-                                                    bounds,
-                                                    layout,
-                                                    is_active: false,
-                                                    hitbox: None,
-                                                }
-                                            })
-                                            .collect()
-                                    }),
-                            )
-                        })
-                        .size_full()
-                        .flex_grow()
-                        .with_width_from_item(self.max_width_item_index)
-                        .track_scroll(&self.scroll_handle),
-                    )
-                    .on_mouse_down(
-                        MouseButton::Right,
-                        cx.listener(move |this, event: &MouseDownEvent, window, cx| {
-                            this.deploy_panel_context_menu(event.position, window, cx)
-                        }),
-                    )
-                    .custom_scrollbars(
-                        Scrollbars::for_settings::<GitPanelSettings>()
-                            .tracked_scroll_handle(&self.scroll_handle)
-                            .with_track_along(
-                                ScrollAxes::Horizontal,
-                                cx.theme().colors().panel_background,
-                            ),
-                        window,
-                        cx,
-                    ),
-            )
-    }
+    fn render_commit_button(&self, cx: &mut Context<Self>) -> impl IntoElement {
+        let (can_commit, tooltip) = self.configure_commit_button(cx);
+        let title = self.commit_button_title();
+        let commit_tooltip_focus_handle = self.commit_editor.focus_handle(cx);
+        let amend = self.amend_pending();
+        let signoff = self.signoff_enabled;
+        let commit_selected = !amend && !self.marked_entries.is_empty();
 
-    fn entry_label(&self, label: impl Into<SharedString>, color: Color) -> Label {
-        Label::new(label.into()).color(color)
-    }
+        let label_color = if self.pending_commit.is_some() {
+            Color::Disabled
+        } else {
+            Color::Default
+        };
 
-    fn list_item_height(&self) -> Rems {
-        rems(1.75)
+        div()
+            .id("commit-wrapper")
+            .on_hover(cx.listener(move |this, hovered, _, cx| {
+                this.show_placeholders =
+                    *hovered && !this.has_staged_changes() && !this.has_unstaged_conflicts();
+                cx.notify()
+            }))
+            .child(SplitButton::new(
+                ButtonLike::new_rounded_left(ElementId::Name(
+                    format!("split-button-left-{}", title).into(),
+                ))
+                .layer(ElevationIndex::ModalSurface)
+                .size(ButtonSize::Compact)
+                .child(
+                    Label::new(title)
+                        .size(LabelSize::Small)
+                        .color(label_color)
+                        .mr_0p5(),
+                )
+                .on_click({
+                    let git_panel = cx.weak_entity();
+                    move |_, window, cx| {
+                        telemetry::event!("Git Committed", source = "Git Panel");
+                        git_panel
+                            .update(cx, |git_panel, cx| {
+                                if commit_selected {
+                                    git_panel.commit_selected(&git::CommitSelected, window, cx);
+                                } else {
+                                    git_panel.commit_changes(
+                                        CommitOptions {
+                                            amend,
+                                            signoff,
+                                            ..Default::default()
+                                        },
+                                        window,
+                                        cx,
+                                    );
+                                }
+                            })
+                            .ok();
+                    }
+                })
+                .disabled(!can_commit || self.modal_open)
+                .tooltip({
+                    let handle = commit_tooltip_focus_handle.clone();
+                    move |_window, cx| {
+                        if can_commit {
+                            Tooltip::with_meta_in(
+                                tooltip,
+                                Some(if amend {
+                                    &git::Amend
+                                } else if commit_selected {
+                                    &git::CommitSelected
+                                } else {
+                                    &git::Commit
+                                }),
+                                format!(
+                                    "git commit{}{}{}",
+                                    if amend { " --amend" } else { "" },
+                                    if signoff { " --signoff" } else { "" },
+                                    if commit_selected { " --only" } else { "" }
+                                ),
+                                &handle.clone(),
+                                cx,
+                            )
+                        } else {
+                            Tooltip::simple(tooltip, cx)
+                        }
+                    }
+                }),
+                self.render_git_commit_menu(
+                    ElementId::Name(format!("split-button-right-{}", title).into()),
+                    Some(commit_tooltip_focus_handle),
+                    cx,
+                )
+                .into_any_element(),
+            ))
     }
 
-    fn render_list_header(
-        &self,
-        ix: usize,
-        header: &GitHeaderEntry,
-        _: bool,
-        _: &Window,
-        _: &Context<Self>,
-    ) -> AnyElement {
-        let id: ElementId = ElementId::Name(format!("header_{}", ix).into());
-
+    fn render_pending_amend(&self, cx: &mut Context<Self>) -> impl IntoElement {
         h_flex()
-            .id(id)
-            .h(self.list_item_height())
-            .w_full()
-            .items_end()
-            .px_3()
-            .pb_1()
+            .py_1p5()
+            .px_2()
+            .gap_1p5()
+            .justify_between()
+            .border_t_1()
+            .border_color(cx.theme().colors().border.opacity(0.8))
             .child(
-                Label::new(header.title())
-                    .color(Color::Muted)
-                    .size(LabelSize::Small)
-                    .line_height_style(LineHeightStyle::UiLabel)
-                    .single_line(),
+                div()
+                    .flex_grow()
+                    .overflow_hidden()
+                    .max_w(relative(0.85))
+                    .child(
+                        Label::new("This will update your most recent commit.")
+                            .size(LabelSize::Small)
+                            .truncate(),
+                    ),
+            )
+            .child(
+                panel_button("Cancel")
+                    .size(ButtonSize::Default)
+                    .on_click(cx.listener(|this, _, _, cx| this.set_amend_pending(false, cx))),
             )
-            .into_any_element()
     }
 
-    pub fn load_commit_details(
+    fn render_previous_commit(
         &self,
-        sha: String,
+        _window: &mut Window,
         cx: &mut Context<Self>,
-    ) -> Task<anyhow::Result<CommitDetails>> {
-        let Some(repo) = self.active_repository.clone() else {
-            return Task::ready(Err(anyhow::anyhow!("no active repo")));
-        };
-        repo.update(cx, |repo, cx| {
-            let show = repo.show(sha);
-            cx.spawn(async move |_, _| show.await?)
-        })
-    }
+    ) -> Option<impl IntoElement> {
+        let active_repository = self.active_repository.as_ref()?;
+        let branch = active_repository.read(cx).branch.as_ref()?;
+        let commit = branch.most_recent_commit.as_ref()?.clone();
+        let workspace = self.workspace.clone();
+        let can_open_git_graph = cx.has_flag::<GitGraphFeatureFlag>();
 
-    fn deploy_entry_context_menu(
-        &mut self,
-        position: Point<Pixels>,
-        ix: usize,
-        window: &mut Window,
-        cx: &mut Context<Self>,
-    ) {
-        let Some(entry) = self.entries.get(ix).and_then(|e| e.status_entry()) else {
-            return;
-        };
-        let stage_title = if entry.status.staging().is_fully_staged() {
-            "Unstage File"
-        } else {
-            "Stage File"
-        };
-        let restore_title = if entry.status.is_created() {
-            "Trash File"
-        } else {
-            "Discard Changes"
-        };
-        let context_menu = ContextMenu::build(window, cx, |context_menu, _, _| {
-            let is_created = entry.status.is_created();
-            context_menu
-                .context(self.focus_handle.clone())
-                .action(stage_title, ToggleStaged.boxed_clone())
-                .action(restore_title, git::RestoreFile::default().boxed_clone())
-                .action_disabled_when(
-                    !is_created,
-                    "Add to .gitignore",
-                    git::AddToGitignore.boxed_clone(),
+        Some(
+            h_flex()
+                .p_1p5()
+                .gap_1p5()
+                .justify_between()
+                .border_t_1()
+                .border_color(cx.theme().colors().border.opacity(0.8))
+                .child(
+                    div()
+                        .id("commit-msg-hover")
+                        .cursor_pointer()
+                        .px_1()
+                        .rounded_sm()
+                        .line_clamp(1)
+                        .hover(|s| s.bg(cx.theme().colors().element_hover))
+                        .child(
+                            Label::new(commit.subject.clone())
+                                .size(LabelSize::Small)
+                                .truncate(),
+                        )
+                        .on_click({
+                            let commit = commit.clone();
+                            let repo = active_repository.downgrade();
+                            move |_, window, cx| {
+                                CommitView::open(
+                                    commit.sha.to_string(),
+                                    repo.clone(),
+                                    workspace.clone(),
+                                    None,
+                                    None,
+                                    window,
+                                    cx,
+                                );
+                            }
+                        })
+                        .hoverable_tooltip({
+                            let repo = active_repository.clone();
+                            move |window, cx| {
+                                CommitShaTooltip::new(
+                                    commit.sha.clone(),
+                                    repo.clone(),
+                                    workspace.clone(),
+                                    window,
+                                    cx,
+                                )
+                                .into()
+                            }
+                        }),
                 )
-                .separator()
-                .action("Open Diff", menu::Confirm.boxed_clone())
-                .action("Open File", menu::SecondaryConfirm.boxed_clone())
-                .separator()
-                .action_disabled_when(is_created, "View File History", Box::new(git::FileHistory))
-        });
-        self.selected_entry = Some(ix);
-        self.set_context_menu(context_menu, position, window, cx);
-    }
-
-    fn deploy_panel_context_menu(
-        &mut self,
-        position: Point<Pixels>,
-        window: &mut Window,
-        cx: &mut Context<Self>,
-    ) {
-        let context_menu = git_panel_context_menu(
-            self.focus_handle.clone(),
-            GitMenuState {
-                has_tracked_changes: self.has_tracked_changes(),
-                has_staged_changes: self.has_staged_changes(),
-                has_unstaged_changes: self.has_unstaged_changes(),
-                has_new_changes: self.new_count > 0,
-                sort_by_path: GitPanelSettings::get_global(cx).sort_by_path,
-                has_stash_items: self.stash_entries.entries.len() > 0,
-                tree_view: GitPanelSettings::get_global(cx).tree_view,
-            },
-            window,
-            cx,
-        );
-        self.set_context_menu(context_menu, position, window, cx);
+                .child(
+                    h_flex()
+                        .gap_0p5()
+                        .when(commit.has_parent, |this| {
+                            let has_unstaged = self.has_unstaged_changes();
+                            this.child(
+                                panel_icon_button("undo", IconName::Undo)
+                                    .icon_size(IconSize::Small)
+                                    .tooltip(move |_window, cx| {
+                                        Tooltip::with_meta(
+                                            "Uncommit",
+                                            Some(&git::Uncommit),
+                                            if has_unstaged {
+                                                "git reset HEAD^ --soft"
+                                            } else {
+                                                "git reset HEAD^"
+                                            },
+                                            cx,
+                                        )
+                                    })
+                                    .on_click(
+                                        cx.listener(|this, _, window, cx| {
+                                            this.uncommit(window, cx)
+                                        }),
+                                    ),
+                            )
+                        })
+                        .when(can_open_git_graph, |this| {
+                            this.child(
+                                panel_icon_button("git-graph-button", IconName::GitGraph)
+                                    .icon_size(IconSize::Small)
+                                    .tooltip(|_window, cx| {
+                                        Tooltip::for_action("Open Git Graph", &Open, cx)
+                                    })
+                                    .on_click(|_, window, cx| {
+                                        window.dispatch_action(Open.boxed_clone(), cx)
+                                    }),
+                            )
+                        }),
+                ),
+        )
     }
 
-    fn set_context_menu(
-        &mut self,
-        context_menu: Entity<ContextMenu>,
-        position: Point<Pixels>,
-        window: &Window,
-        cx: &mut Context<Self>,
-    ) {
-        let subscription = cx.subscribe_in(
-            &context_menu,
-            window,
-            |this, _, _: &DismissEvent, window, cx| {
-                if this.context_menu.as_ref().is_some_and(|context_menu| {
-                    context_menu.0.focus_handle(cx).contains_focused(window, cx)
-                }) {
-                    cx.focus_self(window);
-                }
-                this.context_menu.take();
-                cx.notify();
-            },
-        );
-        self.context_menu = Some((context_menu, position, subscription));
-        cx.notify();
-    }
+    fn render_empty_state(&self, cx: &mut Context<Self>) -> impl IntoElement {
+        let has_repo = self.active_repository.is_some();
+        let has_no_repo = self.active_repository.is_none();
+        let worktree_count = self.project.read(cx).visible_worktrees(cx).count();
 
-    fn render_status_entry(
-        &self,
-        ix: usize,
-        entry: &GitStatusEntry,
-        depth: usize,
-        has_write_access: bool,
-        repo: &Repository,
-        window: &Window,
-        cx: &Context<Self>,
-    ) -> AnyElement {
-        let settings = GitPanelSettings::get_global(cx);
-        let tree_view = settings.tree_view;
-        let path_style = self.project.read(cx).path_style(cx);
-        let git_path_style = ProjectSettings::get_global(cx).git.path_style;
-        let display_name = entry.display_name(path_style);
+        let should_show_branch_diff =
+            has_repo && self.changes_count == 0 && !self.is_on_main_branch(cx);
 
-        let selected = self.selected_entry == Some(ix);
-        let marked = self.marked_entries.contains(&ix);
-        let status_style = settings.status_style;
-        let status = entry.status;
-        let file_icon = if settings.file_icons {
-            FileIcons::get_icon(entry.repo_path.as_std_path(), cx)
+        let label = if has_repo {
+            "No changes to commit"
         } else {
-            None
+            "No Git repositories"
         };
 
-        let has_conflict = status.is_conflicted();
-        let is_modified = status.is_modified();
-        let is_deleted = status.is_deleted();
-        let is_created = status.is_created();
-
-        let label_color = if status_style == StatusStyle::LabelColor {
-            if has_conflict {
-                Color::VersionControlConflict
-            } else if is_created {
-                Color::VersionControlAdded
-            } else if is_modified {
-                Color::VersionControlModified
-            } else if is_deleted {
-                // We don't want a bunch of red labels in the list
-                Color::Disabled
-            } else {
-                Color::VersionControlAdded
-            }
-        } else {
-            Color::Default
-        };
+        v_flex()
+            .gap_1p5()
+            .flex_1()
+            .items_center()
+            .justify_center()
+            .child(Label::new(label).size(LabelSize::Small).color(Color::Muted))
+            .when(has_no_repo && worktree_count > 0, |this| {
+                this.child(
+                    panel_filled_button("Initialize Repository")
+                        .tooltip(Tooltip::for_action_title_in(
+                            "git init",
+                            &git::Init,
+                            &self.focus_handle,
+                        ))
+                        .on_click(move |_, _, cx| {
+                            cx.defer(move |cx| {
+                                cx.dispatch_action(&git::Init);
+                            })
+                        }),
+                )
+            })
+            .when(has_no_repo, |this| {
+                this.child(
+                    panel_filled_button("Clone Repository…")
+                        .tooltip(Tooltip::for_action_title_in(
+                            "git clone",
+                            &git::Clone,
+                            &self.focus_handle,
+                        ))
+                        .on_click(move |_, _, cx| {
+                            cx.defer(move |cx| {
+                                cx.dispatch_action(&git::Clone);
+                            })
+                        }),
+                )
+            })
+            .when(should_show_branch_diff, |this| {
+                this.child(
+                    panel_filled_button("View Branch Diff")
+                        .tooltip(move |_, cx| {
+                            Tooltip::with_meta(
+                                "Branch Diff",
+                                Some(&BranchDiff),
+                                "Show diff between working directory and default branch",
+                                cx,
+                            )
+                        })
+                        .on_click(move |_, _, cx| {
+                            cx.defer(move |cx| {
+                                cx.dispatch_action(&BranchDiff);
+                            })
+                        }),
+                )
+            })
+    }
 
-        let path_color = if status.is_deleted() {
-            Color::Disabled
-        } else {
-            Color::Muted
+    fn is_on_main_branch(&self, cx: &Context<Self>) -> bool {
+        let Some(repo) = self.active_repository.as_ref() else {
+            return false;
         };
 
-        let id: ElementId = ElementId::Name(format!("entry_{}_{}", display_name, ix).into());
-        let checkbox_wrapper_id: ElementId =
-            ElementId::Name(format!("entry_{}_{}_checkbox_wrapper", display_name, ix).into());
-        let checkbox_id: ElementId =
-            ElementId::Name(format!("entry_{}_{}_checkbox", display_name, ix).into());
-
-        let stage_status = GitPanel::stage_status_for_entry(entry, &repo);
-        let mut is_staged: ToggleState = match stage_status {
-            StageStatus::Staged => ToggleState::Selected,
-            StageStatus::Unstaged => ToggleState::Unselected,
-            StageStatus::PartiallyStaged => ToggleState::Indeterminate,
+        let Some(branch) = repo.read(cx).branch.as_ref() else {
+            return false;
         };
-        if self.show_placeholders && !self.has_staged_changes() && !entry.status.is_created() {
-            is_staged = ToggleState::Selected;
-        }
-
-        let handle = cx.weak_entity();
-
-        let selected_bg_alpha = 0.08;
-        let marked_bg_alpha = 0.12;
-        let state_opacity_step = 0.04;
-
-        let info_color = cx.theme().status().info;
 
-        let base_bg = match (selected, marked) {
-            (true, true) => info_color.alpha(selected_bg_alpha + marked_bg_alpha),
-            (true, false) => info_color.alpha(selected_bg_alpha),
-            (false, true) => info_color.alpha(marked_bg_alpha),
-            _ => cx.theme().colors().ghost_element_background,
-        };
+        let branch_name = branch.name();
+        matches!(branch_name, "main" | "master")
+    }
 
-        let (hover_bg, active_bg) = if selected {
-            (
-                info_color.alpha(selected_bg_alpha + state_opacity_step),
-                info_color.alpha(selected_bg_alpha + state_opacity_step * 2.0),
-            )
-        } else {
-            (
-                cx.theme().colors().ghost_element_hover,
-                cx.theme().colors().ghost_element_active,
-            )
-        };
+    fn render_buffer_header_controls(
+        &self,
+        entity: &Entity<Self>,
+        file: &Arc<dyn File>,
+        _: &Window,
+        cx: &App,
+    ) -> Option<AnyElement> {
+        let repo = self.active_repository.as_ref()?.read(cx);
+        let project_path = (file.worktree_id(cx), file.path().clone()).into();
+        let repo_path = repo.project_path_to_repo_path(&project_path, cx)?;
+        let ix = self.entry_by_path(&repo_path)?;
+        let entry = self.entries.get(ix)?;
+        let rename = repo.rename_to(&repo_path);
 
-        let name_row = h_flex()
-            .min_w_0()
-            .flex_1()
-            .gap_1()
-            .when(settings.file_icons, |this| {
-                this.child(
-                    file_icon
-                        .map(|file_icon| {
-                            Icon::from_path(file_icon)
-                                .size(IconSize::Small)
-                                .color(Color::Muted)
-                        })
-                        .unwrap_or_else(|| {
-                            Icon::new(IconName::File)
-                                .size(IconSize::Small)
-                                .color(Color::Muted)
-                        }),
-                )
-            })
-            .when(status_style != StatusStyle::LabelColor, |el| {
-                el.child(git_status_icon(status))
+        let is_staging_or_staged = repo
+            .pending_ops_for_path(&repo_path)
+            .map(|ops| ops.staging() || ops.staged())
+            .or_else(|| {
+                repo.status_for_path(&repo_path)
+                    .and_then(|status| status.status.staging().as_bool())
             })
-            .map(|this| {
-                if tree_view {
-                    this.pl(px(depth as f32 * TREE_INDENT)).child(
-                        self.entry_label(display_name, label_color)
-                            .when(status.is_deleted(), Label::strikethrough)
-                            .truncate(),
-                    )
-                } else {
-                    this.child(self.path_formatted(
-                        entry.parent_dir(path_style),
-                        path_color,
-                        display_name,
-                        label_color,
-                        path_style,
-                        git_path_style,
-                        status.is_deleted(),
-                    ))
-                }
+            .or_else(|| {
+                entry
+                    .status_entry()
+                    .and_then(|entry| entry.staging.as_bool())
             });
 
-        let id_for_diff_stat = id.clone();
-
-        h_flex()
-            .id(id)
-            .h(self.list_item_height())
-            .w_full()
-            .pl_3()
-            .pr_1()
-            .gap_1p5()
-            .border_1()
-            .border_r_2()
-            .when(selected && self.focus_handle.is_focused(window), |el| {
-                el.border_color(cx.theme().colors().panel_focused_border)
-            })
-            .bg(base_bg)
-            .hover(|s| s.bg(hover_bg))
-            .active(|s| s.bg(active_bg))
-            .child(name_row)
-            .when(GitPanelSettings::get_global(cx).diff_stats, |el| {
-                el.when_some(entry.diff_stat, move |this, stat| {
-                    let id = format!("diff-stat-{}", id_for_diff_stat);
-                    this.child(ui::DiffStat::new(
-                        id,
-                        stat.added as usize,
-                        stat.deleted as usize,
-                    ))
+        let checkbox = Checkbox::new("stage-file", is_staging_or_staged.into())
+            .disabled(!self.has_write_access(cx))
+            .fill()
+            .elevation(ElevationIndex::Surface)
+            .on_click({
+                let entry = entry.clone();
+                let git_panel = entity.downgrade();
+                move |_, window, cx| {
+                    git_panel
+                        .update(cx, |this, cx| {
+                            this.toggle_staged_for_entry(&entry, window, cx);
+                            cx.stop_propagation();
+                        })
+                        .ok();
+                }
+            });
+        Some(
+            h_flex()
+                .id("start-slot")
+                .gap_2()
+                .text_lg()
+                .when_some(rename, |this, rename| {
+                    let verb = if rename.is_copy { "Copied" } else { "Renamed" };
+                    let old_path = rename.old_path.display(repo.path_style);
+                    this.child(
+                        Label::new(format!("{verb} from {old_path}"))
+                            .size(LabelSize::Small)
+                            .color(Color::Muted),
+                    )
                 })
-            })
+                .child(checkbox)
+                .on_mouse_down(MouseButton::Left, |_, _, cx| {
+                    // prevent the list item active state triggering when toggling checkbox
+                    cx.stop_propagation();
+                })
+                .into_any_element(),
+        )
+    }
+
+    fn render_entries(
+        &self,
+        has_write_access: bool,
+        repo: Entity<Repository>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> impl IntoElement {
+        let (is_tree_view, entry_count) = match &self.view_mode {
+            GitPanelViewMode::Tree(state) => (true, state.logical_indices.len()),
+            GitPanelViewMode::Flat => (false, self.entries.len()),
+        };
+        let repo = repo.downgrade();
+
+        v_flex()
+            .flex_1()
+            .size_full()
+            .overflow_hidden()
+            .relative()
             .child(
-                div()
-                    .id(checkbox_wrapper_id)
-                    .flex_none()
-                    .occlude()
-                    .cursor_pointer()
+                h_flex()
+                    .flex_1()
+                    .size_full()
+                    .relative()
+                    .overflow_hidden()
                     .child(
-                        Checkbox::new(checkbox_id, is_staged)
-                            .disabled(!has_write_access)
-                            .fill()
-                            .elevation(ElevationIndex::Surface)
-                            .on_click_ext({
-                                let entry = entry.clone();
-                                let this = cx.weak_entity();
-                                move |_, click, window, cx| {
-                                    this.update(cx, |this, cx| {
-                                        if !has_write_access {
-                                            return;
+                        uniform_list(
+                            "entries",
+                            entry_count,
+                            cx.processor(move |this, range: Range<usize>, window, cx| {
+                                let Some(repo) = repo.upgrade() else {
+                                    return Vec::new();
+                                };
+                                let repo = repo.read(cx);
+
+                                let mut items = Vec::with_capacity(range.end - range.start);
+
+                                for ix in range.into_iter().map(|ix| match &this.view_mode {
+                                    GitPanelViewMode::Tree(state) => state.logical_indices[ix],
+                                    GitPanelViewMode::Flat => ix,
+                                }) {
+                                    match &this.entries.get(ix) {
+                                        Some(GitListEntry::Status(entry)) => {
+                                            items.push(this.render_status_entry(
+                                                ix,
+                                                entry,
+                                                0,
+                                                has_write_access,
+                                                repo,
+                                                window,
+                                                cx,
+                                            ));
                                         }
-                                        if click.modifiers().shift {
-                                            this.stage_bulk(ix, cx);
-                                        } else {
-                                            let list_entry =
-                                                if GitPanelSettings::get_global(cx).tree_view {
-                                                    GitListEntry::TreeStatus(GitTreeStatusEntry {
-                                                        entry: entry.clone(),
-                                                        depth,
-                                                    })
-                                                } else {
-                                                    GitListEntry::Status(entry.clone())
-                                                };
-                                            this.toggle_staged_for_entry(&list_entry, window, cx);
+                                        Some(GitListEntry::TreeStatus(entry)) => {
+                                            items.push(this.render_status_entry(
+                                                ix,
+                                                &entry.entry,
+                                                entry.depth,
+                                                has_write_access,
+                                                repo,
+                                                window,
+                                                cx,
+                                            ));
                                         }
-                                        cx.stop_propagation();
-                                    })
-                                    .ok();
+                                        Some(GitListEntry::Directory(entry)) => {
+                                            items.push(this.render_directory_entry(
+                                                ix,
+                                                entry,
+                                                has_write_access,
+                                                window,
+                                                cx,
+                                            ));
+                                        }
+                                        Some(GitListEntry::Header(header)) => {
+                                            items.push(this.render_list_header(
+                                                ix,
+                                                header,
+                                                has_write_access,
+                                                window,
+                                                cx,
+                                            ));
+                                        }
+                                        None => {}
+                                    }
                                 }
-                            })
-                            .tooltip(move |_window, cx| {
-                                let action = match stage_status {
-                                    StageStatus::Staged => "Unstage",
-                                    StageStatus::Unstaged | StageStatus::PartiallyStaged => "Stage",
-                                };
-                                let tooltip_name = action.to_string();
 
-                                Tooltip::for_action(tooltip_name, &ToggleStaged, cx)
+                                items
                             }),
+                        )
+                        .when(is_tree_view, |list| {
+                            let indent_size = px(TREE_INDENT);
+                            list.with_decoration(
+                                ui::indent_guides(indent_size, IndentGuideColors::panel(cx))
+                                    .with_compute_indents_fn(
+                                        cx.entity(),
+                                        |this, range, _window, _cx| {
+                                            this.compute_visible_depths(range)
+                                        },
+                                    )
+                                    .with_render_fn(cx.entity(), |_, params, _, _| {
+                                        // Magic number to align the tree item is 3 here
+                                        // because we're using 12px as the left-side padding
+                                        // and 3 makes the alignment work with the bounding box of the icon
+                                        let left_offset = px(TREE_INDENT + 3_f32);
+                                        let indent_size = params.indent_size;
+                                        let item_height = params.item_height;
+
+                                        params
+                                            .indent_guides
+                                            .into_iter()
+                                            .map(|layout| {
+                                                let bounds = Bounds::new(
+                                                    point(
+                                                        layout.offset.x * indent_size + left_offset,
+                                                        layout.offset.y * item_height,
+                                                    ),
+                                                    size(px(1.), layout.length * item_height),
+                                                );
+                                                RenderedIndentGuide {
+                                                    bounds,
+                                                    layout,
+                                                    is_active: false,
+                                                    hitbox: None,
+                                                }
+                                            })
+                                            .collect()
+                                    }),
+                            )
+                        })
+                        .size_full()
+                        .flex_grow()
+                        .with_width_from_item(self.max_width_item_index)
+                        .track_scroll(&self.scroll_handle),
+                    )
+                    .on_mouse_down(
+                        MouseButton::Right,
+                        cx.listener(move |this, event: &MouseDownEvent, window, cx| {
+                            this.deploy_panel_context_menu(event.position, window, cx)
+                        }),
+                    )
+                    .custom_scrollbars(
+                        Scrollbars::for_settings::<GitPanelSettings>()
+                            .tracked_scroll_handle(&self.scroll_handle)
+                            .with_track_along(
+                                ScrollAxes::Horizontal,
+                                cx.theme().colors().panel_background,
+                            ),
+                        window,
+                        cx,
                     ),
             )
-            .on_click({
-                cx.listener(move |this, event: &ClickEvent, window, cx| {
-                    this.selected_entry = Some(ix);
-                    cx.notify();
-                    if event.click_count() > 1 || event.modifiers().secondary() {
-                        this.open_file(&Default::default(), window, cx)
-                    } else {
-                        this.open_diff(&Default::default(), window, cx);
-                        this.focus_handle.focus(window, cx);
-                    }
-                })
-            })
-            .on_mouse_down(
-                MouseButton::Right,
-                move |event: &MouseDownEvent, window, cx| {
-                    // why isn't this happening automatically? we are passing MouseButton::Right to `on_mouse_down`?
-                    if event.button != MouseButton::Right {
-                        return;
-                    }
+    }
 
-                    let Some(this) = handle.upgrade() else {
-                        return;
-                    };
-                    this.update(cx, |this, cx| {
-                        this.deploy_entry_context_menu(event.position, ix, window, cx);
-                    });
-                    cx.stop_propagation();
-                },
+    fn entry_label(&self, label: impl Into<SharedString>, color: Color) -> Label {
+        Label::new(label.into()).color(color)
+    }
+
+    fn list_item_height(&self) -> Rems {
+        rems(1.75)
+    }
+
+    fn render_list_header(
+        &self,
+        ix: usize,
+        header: &GitHeaderEntry,
+        _: bool,
+        _: &Window,
+        _: &Context<Self>,
+    ) -> AnyElement {
+        let id: ElementId = ElementId::Name(format!("header_{}", ix).into());
+
+        h_flex()
+            .id(id)
+            .h(self.list_item_height())
+            .w_full()
+            .items_end()
+            .px_3()
+            .pb_1()
+            .child(
+                Label::new(header.title())
+                    .color(Color::Muted)
+                    .size(LabelSize::Small)
+                    .line_height_style(LineHeightStyle::UiLabel)
+                    .single_line(),
             )
             .into_any_element()
     }
 
-    fn render_directory_entry(
+    pub fn load_commit_details(
         &self,
+        sha: String,
+        cx: &mut Context<Self>,
+    ) -> Task<anyhow::Result<CommitDetails>> {
+        let Some(repo) = self.active_repository.clone() else {
+            return Task::ready(Err(anyhow::anyhow!("no active repo")));
+        };
+        repo.update(cx, |repo, cx| {
+            let show = repo.show(sha);
+            cx.spawn(async move |_, _| show.await?)
+        })
+    }
+
+    fn deploy_entry_context_menu(
+        &mut self,
+        position: Point<Pixels>,
         ix: usize,
-        entry: &GitTreeDirEntry,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let Some(entry) = self.entries.get(ix).and_then(|e| e.status_entry()) else {
+            return;
+        };
+        let stage_title = if entry.status.staging().is_fully_staged() {
+            "Unstage File"
+        } else {
+            "Stage File"
+        };
+        let restore_title = if entry.status.is_created() {
+            "Trash File"
+        } else {
+            "Discard Changes"
+        };
+        let is_conflicted = entry.status.is_conflicted();
+        let is_lfs = self.lfs_paths.contains(&entry.repo_path);
+        // The menu acts on the clicked file, along with the other marked files if it is marked.
+        let stash_title =
+            if self.marked_entries.len() > 1 && self.marked_entries.contains(&entry.repo_path) {
+                "Stash Marked Files…"
+            } else {
+                "Stash File…"
+            };
+        let extension = entry
+            .repo_path
+            .extension()
+            .map(|extension| SharedString::from(extension.to_string()));
+        let has_directory = entry
+            .repo_path
+            .parent()
+            .is_some_and(|parent| !parent.is_empty());
+        let flagged_file = self
+            .active_repository
+            .as_ref()
+            .and_then(|repo| repo.read(cx).flagged_file(&entry.repo_path).cloned());
+        let has_flag = |flag| {
+            flagged_file
+                .as_ref()
+                .is_some_and(|flagged_file| flagged_file.has(flag))
+        };
+        let assume_unchanged_title = if has_flag(IndexFlag::AssumeUnchanged) {
+            "Stop Assuming Unchanged"
+        } else {
+            "Assume Unchanged"
+        };
+        let skip_worktree_title = if has_flag(IndexFlag::SkipWorktree) {
+            "Stop Skipping Worktree"
+        } else {
+            "Skip Worktree"
+        };
+        let context_menu = ContextMenu::build(window, cx, |context_menu, _, _| {
+            let is_created = entry.status.is_created();
+            context_menu
+                .context(self.focus_handle.clone())
+                .when(is_conflicted, |menu| {
+                    menu.action("Resolve Conflicts…", git::ResolveConflicts.boxed_clone())
+                        .separator()
+                })
+                .action(stage_title, ToggleStaged.boxed_clone())
+                .action(restore_title, git::RestoreFile::default().boxed_clone())
+                .action(stash_title, git::StashSelected.boxed_clone())
+                .when(is_created, |menu| {
+                    let focus_handle = self.focus_handle.clone();
+                    menu.submenu("Add to .gitignore", move |menu, _, _| {
+                        crate::gitignore::gitignore_menu_entries(
+                            menu,
+                            focus_handle.clone(),
+                            extension.clone(),
+                            has_directory,
+                        )
+                    })
+                })
+                .when(!is_created, |menu| {
+                    menu.action(
+                        assume_unchanged_title,
+                        git::ToggleAssumeUnchanged.boxed_clone(),
+                    )
+                    .action(skip_worktree_title, git::ToggleSkipWorktree.boxed_clone())
+                })
+                .separator()
+                .action("Open Diff", menu::Confirm.boxed_clone())
+                .action("Open File", menu::SecondaryConfirm.boxed_clone())
+                .separator()
+                .action_disabled_when(is_created, "View File History", Box::new(git::FileHistory))
+                .when(is_lfs, |menu| {
+                    menu.action("Fetch LFS Object", git::FetchLfsObject.boxed_clone())
+                })
+        });
+        self.selected_entry = Some(ix);
+        self.set_context_menu(context_menu, position, window, cx);
+    }
+
+    fn deploy_directory_context_menu(
+        &mut self,
+        position: Point<Pixels>,
+        ix: usize,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let Some(GitListEntry::Directory(entry)) = self.entries.get(ix) else {
+            return;
+        };
+        let Some(repo) = self.active_repository.as_ref() else {
+            return;
+        };
+        let stage_status = self.stage_status_for_directory(entry, repo.read(cx));
+        let context_menu = ContextMenu::build(window, cx, |context_menu, _, _| {
+            context_menu
+                .context(self.focus_handle.clone())
+                .action_disabled_when(
+                    stage_status == StageStatus::Staged,
+                    "Stage Folder",
+                    git::StageFile.boxed_clone(),
+                )
+                .action_disabled_when(
+                    stage_status == StageStatus::Unstaged,
+                    "Unstage Folder",
+                    git::UnstageFile.boxed_clone(),
+                )
+        });
+        self.selected_entry = Some(ix);
+        self.set_context_menu(context_menu, position, window, cx);
+    }
+
+    fn deploy_panel_context_menu(
+        &mut self,
+        position: Point<Pixels>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let context_menu = git_panel_context_menu(
+            self.focus_handle.clone(),
+            GitMenuState {
+                has_tracked_changes: self.has_tracked_changes(),
+                has_staged_changes: self.has_staged_changes(),
+                has_unstaged_changes: self.has_unstaged_changes(),
+                has_new_changes: self.new_count > 0,
+                sort_by_path: GitPanelSettings::get_global(cx).sort_by_path,
+                has_stash_items: self.stash_entries.entries.len() > 0,
+                has_discarded_hunks: !self.discarded_hunks.is_empty(),
+                tree_view: GitPanelSettings::get_global(cx).tree_view,
+                repository: self.active_repository.clone(),
+                remotes: self
+                    .active_repository
+                    .as_ref()
+                    .and_then(|repository| repository.read(cx).cached_remotes()),
+                can_manage_remotes: !self.project.read(cx).is_via_collab(),
+            },
+            window,
+            cx,
+        );
+        self.set_context_menu(context_menu, position, window, cx);
+    }
+
+    fn set_context_menu(
+        &mut self,
+        context_menu: Entity<ContextMenu>,
+        position: Point<Pixels>,
+        window: &Window,
+        cx: &mut Context<Self>,
+    ) {
+        let subscription = cx.subscribe_in(
+            &context_menu,
+            window,
+            |this, _, _: &DismissEvent, window, cx| {
+                if this.context_menu.as_ref().is_some_and(|context_menu| {
+                    context_menu.0.focus_handle(cx).contains_focused(window, cx)
+                }) {
+                    cx.focus_self(window);
+                }
+                this.context_menu.take();
+                cx.notify();
+            },
+        );
+        self.context_menu = Some((context_menu, position, subscription));
+        cx.notify();
+    }
+
+    fn render_status_entry(
+        &self,
+        ix: usize,
+        entry: &GitStatusEntry,
+        depth: usize,
         has_write_access: bool,
+        repo: &Repository,
         window: &Window,
         cx: &Context<Self>,
     ) -> AnyElement {
-        // TODO: Have not yet plugin the self.marked_entries. Not sure when and why we need that
+        let settings = GitPanelSettings::get_global(cx);
+        let tree_view = settings.tree_view;
+        let path_style = self.project.read(cx).path_style(cx);
+        let git_path_style = ProjectSettings::get_global(cx).git.path_style;
+        let display_name = entry.display_name(path_style);
+        let renamed_from = repo.rename_to(&entry.repo_path).map(|rename| {
+            if rename.old_path.parent() == entry.repo_path.parent() {
+                rename.old_path.file_name().unwrap_or_default().to_string()
+            } else {
+                rename.old_path.display(path_style).to_string()
+            }
+        });
+
         let selected = self.selected_entry == Some(ix);
-        let label_color = Color::Muted;
+        let marked = self.marked_entries.contains(&entry.repo_path);
+        let status_style = settings.status_style;
+        let status = entry.status;
+        let file_icon = if settings.file_icons {
+            FileIcons::get_icon(entry.repo_path.as_std_path(), cx)
+        } else {
+            None
+        };
+
+        let has_conflict = status.is_conflicted();
+        let is_modified = status.is_modified();
+        let is_deleted = status.is_deleted();
+        let is_created = status.is_created();
+
+        let label_color = if status_style == StatusStyle::LabelColor {
+            if has_conflict {
+                Color::VersionControlConflict
+            } else if is_created {
+                Color::VersionControlAdded
+            } else if is_modified {
+                Color::VersionControlModified
+            } else if is_deleted {
+                // We don't want a bunch of red labels in the list
+                Color::Disabled
+            } else {
+                Color::VersionControlAdded
+            }
+        } else {
+            Color::Default
+        };
+
+        let path_color = if status.is_deleted() {
+            Color::Disabled
+        } else {
+            Color::Muted
+        };
+
+        let id: ElementId = ElementId::Name(format!("entry_{}_{}", display_name, ix).into());
+        let checkbox_wrapper_id: ElementId =
+            ElementId::Name(format!("entry_{}_{}_checkbox_wrapper", display_name, ix).into());
+        let checkbox_id: ElementId =
+            ElementId::Name(format!("entry_{}_{}_checkbox", display_name, ix).into());
+
+        let stage_status = GitPanel::stage_status_for_entry(entry, &repo);
+        let mut is_staged: ToggleState = match stage_status {
+            StageStatus::Staged => ToggleState::Selected,
+            StageStatus::Unstaged => ToggleState::Unselected,
+            StageStatus::PartiallyStaged => ToggleState::Indeterminate,
+        };
+        if self.show_placeholders && !self.has_staged_changes() && !entry.status.is_created() {
+            is_staged = ToggleState::Selected;
+        }
+
+        let handle = cx.weak_entity();
+
+        let selected_bg_alpha = 0.08;
+        let marked_bg_alpha = 0.12;
+        let state_opacity_step = 0.04;
+
+        let info_color = cx.theme().status().info;
+
+        let base_bg = match (selected, marked) {
+            (true, true) => info_color.alpha(selected_bg_alpha + marked_bg_alpha),
+            (true, false) => info_color.alpha(selected_bg_alpha),
+            (false, true) => info_color.alpha(marked_bg_alpha),
+            _ => cx.theme().colors().ghost_element_background,
+        };
+
+        let (hover_bg, active_bg) = if selected {
+            (
+                info_color.alpha(selected_bg_alpha + state_opacity_step),
+                info_color.alpha(selected_bg_alpha + state_opacity_step * 2.0),
+            )
+        } else {
+            (
+                cx.theme().colors().ghost_element_hover,
+                cx.theme().colors().ghost_element_active,
+            )
+        };
+
+        let name_row =
+            h_flex()
+                .min_w_0()
+                .flex_1()
+                .gap_1()
+                .when(settings.file_icons, |this| {
+                    this.child(
+                        file_icon
+                            .map(|file_icon| {
+                                Icon::from_path(file_icon)
+                                    .size(IconSize::Small)
+                                    .color(Color::Muted)
+                            })
+                            .unwrap_or_else(|| {
+                                Icon::new(IconName::File)
+                                    .size(IconSize::Small)
+                                    .color(Color::Muted)
+                            }),
+                    )
+                })
+                .when(status_style != StatusStyle::LabelColor, |el| {
+                    el.child(git_status_icon(status))
+                })
+                .when_some(renamed_from, |this, renamed_from| {
+                    this.child(
+                        div().flex_none().child(
+                            Label::new(format!("{renamed_from} →"))
+                                .color(Color::Muted)
+                                .single_line(),
+                        ),
+                    )
+                })
+                .map(|this| {
+                    if tree_view {
+                        this.pl(px(depth as f32 * TREE_INDENT)).child(
+                            self.entry_label(display_name, label_color)
+                                .when(status.is_deleted(), Label::strikethrough)
+                                .truncate(),
+                        )
+                    } else {
+                        this.child(self.path_formatted(
+                            entry.parent_dir(path_style),
+                            path_color,
+                            display_name,
+                            label_color,
+                            path_style,
+                            git_path_style,
+                            status.is_deleted(),
+                        ))
+                    }
+                })
+                .when(self.lfs_paths.contains(&entry.repo_path), |this| {
+                    this.child(
+                        Chip::new("LFS")
+                            .label_color(Color::Muted)
+                            .tooltip(Tooltip::text("Stored with Git LFS")),
+                    )
+                })
+                .children(
+                    repo.flagged_file(&entry.repo_path)
+                        .into_iter()
+                        .flat_map(FlaggedFile::flags)
+                        .map(|flag| {
+                            Chip::new(flag.label()).label_color(Color::Muted).tooltip(
+                                Tooltip::text("Git leaves this file's changes out of the status"),
+                            )
+                        }),
+                );
+
+        let id_for_diff_stat = id.clone();
+
+        h_flex()
+            .id(id)
+            .h(self.list_item_height())
+            .w_full()
+            .pl_3()
+            .pr_1()
+            .gap_1p5()
+            .border_1()
+            .border_r_2()
+            .when(selected && self.focus_handle.is_focused(window), |el| {
+                el.border_color(cx.theme().colors().panel_focused_border)
+            })
+            .bg(base_bg)
+            .hover(|s| s.bg(hover_bg))
+            .active(|s| s.bg(active_bg))
+            .child(name_row)
+            .when(GitPanelSettings::get_global(cx).diff_stats, |el| {
+                el.when_some(entry.diff_stat, move |this, stat| {
+                    let id = format!("diff-stat-{}", id_for_diff_stat);
+                    this.child(ui::DiffStat::new(
+                        id,
+                        stat.added as usize,
+                        stat.deleted as usize,
+                    ))
+                })
+            })
+            .child(
+                div()
+                    .id(checkbox_wrapper_id)
+                    .flex_none()
+                    .occlude()
+                    .cursor_pointer()
+                    .child(
+                        Checkbox::new(checkbox_id, is_staged)
+                            .disabled(!has_write_access)
+                            .fill()
+                            .elevation(ElevationIndex::Surface)
+                            .on_click_ext({
+                                let entry = entry.clone();
+                                let this = cx.weak_entity();
+                                move |_, click, window, cx| {
+                                    this.update(cx, |this, cx| {
+                                        if !has_write_access {
+                                            return;
+                                        }
+                                        if click.modifiers().shift {
+                                            this.stage_bulk(ix, cx);
+                                        } else {
+                                            let list_entry =
+                                                if GitPanelSettings::get_global(cx).tree_view {
+                                                    GitListEntry::TreeStatus(GitTreeStatusEntry {
+                                                        entry: entry.clone(),
+                                                        depth,
+                                                    })
+                                                } else {
+                                                    GitListEntry::Status(entry.clone())
+                                                };
+                                            this.toggle_staged_for_entry(&list_entry, window, cx);
+                                        }
+                                        cx.stop_propagation();
+                                    })
+                                    .ok();
+                                }
+                            })
+                            .tooltip(move |_window, cx| {
+                                let action = match stage_status {
+                                    StageStatus::Staged => "Unstage",
+                                    StageStatus::Unstaged | StageStatus::PartiallyStaged => "Stage",
+                                };
+                                let tooltip_name = action.to_string();
+
+                                Tooltip::for_action(tooltip_name, &ToggleStaged, cx)
+                            }),
+                    ),
+            )
+            .on_click({
+                cx.listener(move |this, event: &ClickEvent, window, cx| {
+                    if event.modifiers().shift {
+                        this.mark_entries_through(ix, cx);
+                        return;
+                    }
+                    if event.modifiers().alt {
+                        this.toggle_marked_entry(ix, cx);
+                        return;
+                    }
+                    this.selected_entry = Some(ix);
+                    cx.notify();
+                    if event.click_count() > 1 || event.modifiers().secondary() {
+                        this.open_file(&Default::default(), window, cx)
+                    } else {
+                        this.open_diff(&Default::default(), window, cx);
+                        this.focus_handle.focus(window, cx);
+                    }
+                })
+            })
+            .on_mouse_down(
+                MouseButton::Right,
+                move |event: &MouseDownEvent, window, cx| {
+                    // why isn't this happening automatically? we are passing MouseButton::Right to `on_mouse_down`?
+                    if event.button != MouseButton::Right {
+                        return;
+                    }
+
+                    let Some(this) = handle.upgrade() else {
+                        return;
+                    };
+                    this.update(cx, |this, cx| {
+                        this.deploy_entry_context_menu(event.position, ix, window, cx);
+                    });
+                    cx.stop_propagation();
+                },
+            )
+            .into_any_element()
+    }
+
+    fn render_directory_entry(
+        &self,
+        ix: usize,
+        entry: &GitTreeDirEntry,
+        has_write_access: bool,
+        window: &Window,
+        cx: &Context<Self>,
+    ) -> AnyElement {
+        // TODO: Have not yet plugin the self.marked_entries. Not sure when and why we need that
+        let selected = self.selected_entry == Some(ix);
+        let label_color = Color::Muted;
+
+        let id: ElementId = ElementId::Name(format!("dir_{}_{}", entry.name, ix).into());
+        let checkbox_id: ElementId =
+            ElementId::Name(format!("dir_checkbox_{}_{}", entry.name, ix).into());
+        let checkbox_wrapper_id: ElementId =
+            ElementId::Name(format!("dir_checkbox_wrapper_{}_{}", entry.name, ix).into());
+
+        let selected_bg_alpha = 0.08;
+        let state_opacity_step = 0.04;
+
+        let info_color = cx.theme().status().info;
+        let colors = cx.theme().colors();
+
+        let (base_bg, hover_bg, active_bg) = if selected {
+            (
+                info_color.alpha(selected_bg_alpha),
+                info_color.alpha(selected_bg_alpha + state_opacity_step),
+                info_color.alpha(selected_bg_alpha + state_opacity_step * 2.0),
+            )
+        } else {
+            (
+                colors.ghost_element_background,
+                colors.ghost_element_hover,
+                colors.ghost_element_active,
+            )
+        };
+
+        let settings = GitPanelSettings::get_global(cx);
+        let folder_icon = if settings.folder_icons {
+            FileIcons::get_folder_icon(entry.expanded, entry.key.path.as_std_path(), cx)
+        } else {
+            FileIcons::get_chevron_icon(entry.expanded, cx)
+        };
+        let fallback_folder_icon = if settings.folder_icons {
+            if entry.expanded {
+                IconName::FolderOpen
+            } else {
+                IconName::Folder
+            }
+        } else {
+            if entry.expanded {
+                IconName::ChevronDown
+            } else {
+                IconName::ChevronRight
+            }
+        };
+
+        let stage_status = if let Some(repo) = &self.active_repository {
+            self.stage_status_for_directory(entry, repo.read(cx))
+        } else {
+            util::debug_panic!(
+                "Won't have entries to render without an active repository in Git Panel"
+            );
+            StageStatus::PartiallyStaged
+        };
+
+        let toggle_state: ToggleState = match stage_status {
+            StageStatus::Staged => ToggleState::Selected,
+            StageStatus::Unstaged => ToggleState::Unselected,
+            StageStatus::PartiallyStaged => ToggleState::Indeterminate,
+        };
+
+        let name_row = h_flex()
+            .min_w_0()
+            .gap_1()
+            .pl(px(entry.depth as f32 * TREE_INDENT))
+            .child(
+                folder_icon
+                    .map(|folder_icon| {
+                        Icon::from_path(folder_icon)
+                            .size(IconSize::Small)
+                            .color(Color::Muted)
+                    })
+                    .unwrap_or_else(|| {
+                        Icon::new(fallback_folder_icon)
+                            .size(IconSize::Small)
+                            .color(Color::Muted)
+                    }),
+            )
+            .child(self.entry_label(entry.name.clone(), label_color).truncate());
+
+        h_flex()
+            .id(id)
+            .h(self.list_item_height())
+            .min_w_0()
+            .w_full()
+            .pl_3()
+            .pr_1()
+            .gap_1p5()
+            .justify_between()
+            .border_1()
+            .border_r_2()
+            .when(selected && self.focus_handle.is_focused(window), |el| {
+                el.border_color(cx.theme().colors().panel_focused_border)
+            })
+            .bg(base_bg)
+            .hover(|s| s.bg(hover_bg))
+            .active(|s| s.bg(active_bg))
+            .child(name_row)
+            .child(
+                div()
+                    .id(checkbox_wrapper_id)
+                    .flex_none()
+                    .occlude()
+                    .cursor_pointer()
+                    .child(
+                        Checkbox::new(checkbox_id, toggle_state)
+                            .disabled(!has_write_access)
+                            .fill()
+                            .elevation(ElevationIndex::Surface)
+                            .on_click({
+                                let entry = entry.clone();
+                                let this = cx.weak_entity();
+                                move |_, window, cx| {
+                                    this.update(cx, |this, cx| {
+                                        if !has_write_access {
+                                            return;
+                                        }
+                                        this.toggle_staged_for_entry(
+                                            &GitListEntry::Directory(entry.clone()),
+                                            window,
+                                            cx,
+                                        );
+                                        cx.stop_propagation();
+                                    })
+                                    .ok();
+                                }
+                            })
+                            .tooltip(move |_window, cx| {
+                                let action = match stage_status {
+                                    StageStatus::Staged => "Unstage",
+                                    StageStatus::Unstaged | StageStatus::PartiallyStaged => "Stage",
+                                };
+                                Tooltip::simple(format!("{action} folder"), cx)
+                            }),
+                    ),
+            )
+            .on_click({
+                let key = entry.key.clone();
+                cx.listener(move |this, _event: &ClickEvent, window, cx| {
+                    this.selected_entry = Some(ix);
+                    this.toggle_directory(&key, window, cx);
+                })
+            })
+            .on_mouse_down(
+                MouseButton::Right,
+                cx.listener(move |this, event: &MouseDownEvent, window, cx| {
+                    this.deploy_directory_context_menu(event.position, ix, window, cx);
+                    cx.stop_propagation();
+                }),
+            )
+            .into_any_element()
+    }
+
+    fn path_formatted(
+        &self,
+        directory: Option<String>,
+        path_color: Color,
+        file_name: String,
+        label_color: Color,
+        path_style: PathStyle,
+        git_path_style: GitPathStyle,
+        strikethrough: bool,
+    ) -> Div {
+        let file_name_first = git_path_style == GitPathStyle::FileNameFirst;
+        let file_path_first = git_path_style == GitPathStyle::FilePathFirst;
+
+        let file_name = format!("{} ", file_name);
+
+        h_flex()
+            .min_w_0()
+            .overflow_hidden()
+            .when(file_path_first, |this| this.flex_row_reverse())
+            .child(
+                div().flex_none().child(
+                    self.entry_label(file_name, label_color)
+                        .when(strikethrough, Label::strikethrough),
+                ),
+            )
+            .when_some(directory, |this, dir| {
+                let path_name = if file_name_first {
+                    dir
+                } else {
+                    format!("{dir}{}", path_style.primary_separator())
+                };
+
+                this.child(
+                    self.entry_label(path_name, path_color)
+                        .truncate_start()
+                        .when(strikethrough, Label::strikethrough),
+                )
+            })
+    }
+
+    fn has_write_access(&self, cx: &App) -> bool {
+        !self.project.read(cx).is_read_only(cx)
+    }
+
+    pub fn amend_pending(&self) -> bool {
+        self.amend_pending
+    }
+
+    /// Sets the pending amend state, ensuring that the original commit message
+    /// is either saved, when `value` is `true` and there's no pending amend, or
+    /// restored, when `value` is `false` and there's a pending amend.
+    pub fn set_amend_pending(&mut self, value: bool, cx: &mut Context<Self>) {
+        if value && !self.amend_pending {
+            let current_message = self.commit_message_buffer(cx).read(cx).text();
+            self.original_commit_message = if current_message.trim().is_empty() {
+                None
+            } else {
+                Some(current_message)
+            };
+        } else if !value && self.amend_pending {
+            let message = self.original_commit_message.take().unwrap_or_default();
+            self.commit_message_buffer(cx).update(cx, |buffer, cx| {
+                let start = buffer.anchor_before(0);
+                let end = buffer.anchor_after(buffer.len());
+                buffer.edit([(start..end, message)], None, cx);
+            });
+        }
+
+        self.amend_pending = value;
+        self.serialize(cx);
+        cx.notify();
+    }
+
+    pub fn signoff_enabled(&self) -> bool {
+        self.signoff_enabled
+    }
+
+    pub fn set_signoff_enabled(&mut self, value: bool, cx: &mut Context<Self>) {
+        self.signoff_enabled = value;
+        self.serialize(cx);
+        cx.notify();
+    }
+
+    pub fn toggle_signoff_enabled(
+        &mut self,
+        _: &Signoff,
+        _window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.set_signoff_enabled(!self.signoff_enabled, cx);
+    }
+
+    pub async fn load(
+        workspace: WeakEntity<Workspace>,
+        mut cx: AsyncWindowContext,
+    ) -> anyhow::Result<Entity<Self>> {
+        let serialized_panel = match workspace
+            .read_with(&cx, |workspace, cx| {
+                Self::serialization_key(workspace).map(|key| (key, KeyValueStore::global(cx)))
+            })
+            .ok()
+            .flatten()
+        {
+            Some((serialization_key, kvp)) => cx
+                .background_spawn(async move { kvp.read_kvp(&serialization_key) })
+                .await
+                .context("loading git panel")
+                .log_err()
+                .flatten()
+                .map(|panel| serde_json::from_str::<SerializedGitPanel>(&panel))
+                .transpose()
+                .log_err()
+                .flatten(),
+            None => None,
+        };
+
+        workspace.update_in(&mut cx, |workspace, window, cx| {
+            let panel = GitPanel::new(workspace, window, cx);
+
+            if let Some(serialized_panel) = serialized_panel {
+                panel.update(cx, |panel, cx| {
+                    panel.amend_pending = serialized_panel.amend_pending;
+                    panel.signoff_enabled = serialized_panel.signoff_enabled;
+                    cx.notify();
+                })
+            }
+
+            panel
+        })
+    }
+
+    fn stage_bulk(&mut self, mut index: usize, cx: &mut Context<'_, Self>) {
+        let Some(op) = self.bulk_staging.as_ref() else {
+            return;
+        };
+        let Some(mut anchor_index) = self.entry_by_path(&op.anchor) else {
+            return;
+        };
+        if let Some(entry) = self.entries.get(index)
+            && let Some(entry) = entry.status_entry()
+        {
+            self.set_bulk_staging_anchor(entry.repo_path.clone(), cx);
+        }
+        if index < anchor_index {
+            std::mem::swap(&mut index, &mut anchor_index);
+        }
+        let entries = self
+            .entries
+            .get(anchor_index..=index)
+            .unwrap_or_default()
+            .iter()
+            .filter_map(|entry| entry.status_entry().cloned())
+            .collect::<Vec<_>>();
+        self.change_file_stage(true, entries, cx);
+    }
+
+    fn set_bulk_staging_anchor(&mut self, path: RepoPath, cx: &mut Context<'_, GitPanel>) {
+        let Some(repo) = self.active_repository.as_ref() else {
+            return;
+        };
+        self.bulk_staging = Some(BulkStaging {
+            repo_id: repo.read(cx).id,
+            anchor: path,
+        });
+    }
+
+    pub(crate) fn toggle_amend_pending(&mut self, cx: &mut Context<Self>) {
+        self.set_amend_pending(!self.amend_pending, cx);
+        if self.amend_pending {
+            self.load_last_commit_message(cx);
+        }
+    }
+}
+
+#[cfg(any(test, feature = "test-support"))]
+impl GitPanel {
+    pub fn new_test(
+        workspace: &mut Workspace,
+        window: &mut Window,
+        cx: &mut Context<Workspace>,
+    ) -> Entity<Self> {
+        Self::new(workspace, window, cx)
+    }
+
+    pub fn active_repository(&self) -> Option<&Entity<Repository>> {
+        self.active_repository.as_ref()
+    }
+}
+
+impl Render for GitPanel {
+    fn render(&mut self, window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let project = self.project.read(cx);
+        let has_entries = !self.entries.is_empty();
+        let room = self.workspace.upgrade().and_then(|_workspace| {
+            call::ActiveCall::try_global(cx).and_then(|call| call.read(cx).room().cloned())
+        });
+
+        let has_write_access = self.has_write_access(cx);
+
+        let has_co_authors = room.is_some_and(|room| {
+            self.load_local_committer(cx);
+            let room = room.read(cx);
+            room.remote_participants()
+                .values()
+                .any(|remote_participant| remote_participant.can_write())
+        });
+
+        v_flex()
+            .id("git_panel")
+            .key_context(self.dispatch_context(window, cx))
+            .track_focus(&self.focus_handle)
+            .when(has_write_access && !project.is_read_only(cx), |this| {
+                this.on_action(cx.listener(Self::toggle_staged_for_selected))
+                    .on_action(cx.listener(Self::stage_range))
+                    .on_action(cx.listener(GitPanel::on_commit))
+                    .on_action(cx.listener(GitPanel::on_amend))
+                    .on_action(cx.listener(GitPanel::commit_selected))
+                    .on_action(cx.listener(GitPanel::commit_no_verify))
+                    .on_action(cx.listener(GitPanel::add_co_author))
+                    .on_action(cx.listener(GitPanel::toggle_signoff_enabled))
+                    .on_action(cx.listener(Self::stage_all))
+                    .on_action(cx.listener(Self::unstage_all))
+                    .on_action(cx.listener(Self::stage_selected))
+                    .on_action(cx.listener(Self::unstage_selected))
+                    .on_action(cx.listener(Self::restore_tracked_files))
+                    .on_action(cx.listener(Self::revert_selected))
+                    .on_action(cx.listener(Self::add_to_gitignore))
+                    .on_action(cx.listener(Self::toggle_assume_unchanged))
+                    .on_action(cx.listener(Self::toggle_skip_worktree))
+                    .on_action(cx.listener(Self::clean_all))
+                    .on_action(cx.listener(Self::generate_commit_message_action))
+                    .on_action(cx.listener(Self::suggest_commit_message_action))
+                    .on_action(cx.listener(Self::stash_all))
+                    .on_action(cx.listener(Self::stash_selected))
+                    .on_action(cx.listener(Self::stash_pop))
+                    .on_action(cx.listener(Self::resolve_conflicts))
+            })
+            .on_action(cx.listener(Self::collapse_selected_entry))
+            .on_action(cx.listener(Self::expand_selected_entry))
+            .on_action(cx.listener(Self::select_first))
+            .on_action(cx.listener(Self::select_next))
+            .on_action(cx.listener(Self::select_previous))
+            .on_action(cx.listener(Self::select_last))
+            .on_action(cx.listener(Self::first_entry))
+            .on_action(cx.listener(Self::next_entry))
+            .on_action(cx.listener(Self::previous_entry))
+            .on_action(cx.listener(Self::last_entry))
+            .on_action(cx.listener(Self::close_panel))
+            .on_action(cx.listener(Self::open_diff))
+            .on_action(cx.listener(Self::open_file))
+            .on_action(cx.listener(Self::file_history))
+            .on_action(cx.listener(Self::fetch_lfs_object))
+            .on_action(cx.listener(Self::focus_changes_list))
+            .on_action(cx.listener(Self::focus_editor))
+            .on_action(cx.listener(Self::expand_commit_editor))
+            .when(has_write_access && has_co_authors, |git_panel| {
+                git_panel.on_action(cx.listener(Self::toggle_fill_co_authors))
+            })
+            .on_action(cx.listener(Self::toggle_sort_by_path))
+            .on_action(cx.listener(Self::toggle_tree_view))
+            .size_full()
+            .overflow_hidden()
+            .bg(cx.theme().colors().panel_background)
+            .child(
+                v_flex()
+                    .size_full()
+                    .children(self.render_panel_header(window, cx))
+                    .children(self.render_in_progress_operation(cx))
+                    .children(self.render_bisect_status(cx))
+                    .children(self.render_submodules(cx))
+                    .map(|this| {
+                        if let Some(repo) = self.active_repository.clone()
+                            && has_entries
+                        {
+                            this.child(self.render_entries(has_write_access, repo, window, cx))
+                        } else {
+                            this.child(self.render_empty_state(cx).into_any_element())
+                        }
+                    })
+                    .children(self.render_footer(window, cx))
+                    .when(self.amend_pending, |this| {
+                        this.child(self.render_pending_amend(cx))
+                    })
+                    .when(!self.amend_pending, |this| {
+                        this.children(self.render_previous_commit(window, cx))
+                    })
+                    .into_any_element(),
+            )
+            .children(self.context_menu.as_ref().map(|(menu, position, _)| {
+                deferred(
+                    anchored()
+                        .position(*position)
+                        .anchor(Corner::TopLeft)
+                        .child(menu.clone()),
+                )
+                .with_priority(1)
+            }))
+    }
+}
+
+impl Focusable for GitPanel {
+    fn focus_handle(&self, cx: &App) -> gpui::FocusHandle {
+        if self.entries.is_empty() {
+            self.commit_editor.focus_handle(cx)
+        } else {
+            self.focus_handle.clone()
+        }
+    }
+}
+
+impl EventEmitter<Event> for GitPanel {}
+
+impl EventEmitter<PanelEvent> for GitPanel {}
+
+pub(crate) struct GitPanelAddon {
+    pub(crate) workspace: WeakEntity<Workspace>,
+}
+
+impl editor::Addon for GitPanelAddon {
+    fn to_any(&self) -> &dyn std::any::Any {
+        self
+    }
+
+    fn render_buffer_header_controls(
+        &self,
+        excerpt_info: &ExcerptInfo,
+        window: &Window,
+        cx: &App,
+    ) -> Option<AnyElement> {
+        let file = excerpt_info.buffer.file()?;
+        let git_panel = self.workspace.upgrade()?.read(cx).panel::<GitPanel>(cx)?;
+
+        git_panel
+            .read(cx)
+            .render_buffer_header_controls(&git_panel, file, window, cx)
+    }
+}
+
+impl Panel for GitPanel {
+    fn persistent_name() -> &'static str {
+        "GitPanel"
+    }
+
+    fn panel_key() -> &'static str {
+        GIT_PANEL_KEY
+    }
+
+    fn position(&self, _: &Window, cx: &App) -> DockPosition {
+        GitPanelSettings::get_global(cx).dock
+    }
+
+    fn position_is_valid(&self, position: DockPosition) -> bool {
+        matches!(position, DockPosition::Left | DockPosition::Right)
+    }
+
+    fn set_position(&mut self, position: DockPosition, _: &mut Window, cx: &mut Context<Self>) {
+        settings::update_settings_file(self.fs.clone(), cx, move |settings, _| {
+            settings.git_panel.get_or_insert_default().dock = Some(position.into())
+        });
+    }
+
+    fn default_size(&self, _: &Window, cx: &App) -> Pixels {
+        GitPanelSettings::get_global(cx).default_width
+    }
+
+    fn icon(&self, _: &Window, cx: &App) -> Option<ui::IconName> {
+        Some(ui::IconName::GitBranchAlt).filter(|_| GitPanelSettings::get_global(cx).button)
+    }
+
+    fn icon_tooltip(&self, _window: &Window, _cx: &App) -> Option<&'static str> {
+        Some("Git Panel")
+    }
+
+    fn icon_label(&self, _: &Window, cx: &App) -> Option<String> {
+        if !GitPanelSettings::get_global(cx).show_count_badge {
+            return None;
+        }
+        let total = self.changes_count;
+        (total > 0).then(|| total.to_string())
+    }
+
+    fn toggle_action(&self) -> Box<dyn Action> {
+        Box::new(ToggleFocus)
+    }
+
+    fn starts_open(&self, _: &Window, cx: &App) -> bool {
+        GitPanelSettings::get_global(cx).starts_open
+    }
+
+    fn activation_priority(&self) -> u32 {
+        3
+    }
+}
+
+impl PanelHeader for GitPanel {}
+
+pub fn panel_editor_container(_window: &mut Window, cx: &mut App) -> Div {
+    v_flex()
+        .size_full()
+        .gap(px(8.))
+        .p_2()
+        .bg(cx.theme().colors().editor_background)
+}
+
+pub(crate) fn panel_editor_style(monospace: bool, window: &Window, cx: &App) -> EditorStyle {
+    let settings = ThemeSettings::get_global(cx);
+
+    let font_size = TextSize::Small.rems(cx).to_pixels(window.rem_size());
+
+    let (font_family, font_fallbacks, font_features, font_weight, line_height) = if monospace {
+        (
+            settings.buffer_font.family.clone(),
+            settings.buffer_font.fallbacks.clone(),
+            settings.buffer_font.features.clone(),
+            settings.buffer_font.weight,
+            font_size * settings.buffer_line_height.value(),
+        )
+    } else {
+        (
+            settings.ui_font.family.clone(),
+            settings.ui_font.fallbacks.clone(),
+            settings.ui_font.features.clone(),
+            settings.ui_font.weight,
+            window.line_height(),
+        )
+    };
+
+    EditorStyle {
+        background: cx.theme().colors().editor_background,
+        local_player: cx.theme().players().local(),
+        text: TextStyle {
+            color: cx.theme().colors().text,
+            font_family,
+            font_fallbacks,
+            font_features,
+            font_size: TextSize::Small.rems(cx).into(),
+            font_weight,
+            line_height: line_height.into(),
+            ..Default::default()
+        },
+        syntax: cx.theme().syntax().clone(),
+        ..Default::default()
+    }
+}
+
+#[derive(IntoElement, RegisterComponent)]
+pub struct PanelRepoFooter {
+    active_repository: SharedString,
+    branch: Option<Branch>,
+    head_commit: Option<CommitDetails>,
+
+    // Getting a GitPanel in previews will be difficult.
+    //
+    // For now just take an option here, and we won't bind handlers to buttons in previews.
+    git_panel: Option<Entity<GitPanel>>,
+}
+
+impl PanelRepoFooter {
+    pub fn new(
+        active_repository: SharedString,
+        branch: Option<Branch>,
+        head_commit: Option<CommitDetails>,
+        git_panel: Option<Entity<GitPanel>>,
+    ) -> Self {
+        Self {
+            active_repository,
+            branch,
+            head_commit,
+            git_panel,
+        }
+    }
+
+    pub fn new_preview(active_repository: SharedString, branch: Option<Branch>) -> Self {
+        Self {
+            active_repository,
+            branch,
+            head_commit: None,
+            git_panel: None,
+        }
+    }
+}
+
+impl RenderOnce for PanelRepoFooter {
+    fn render(self, _window: &mut Window, cx: &mut App) -> impl IntoElement {
+        let project = self
+            .git_panel
+            .as_ref()
+            .map(|panel| panel.read(cx).project.clone());
+
+        let (workspace, repo) = self
+            .git_panel
+            .as_ref()
+            .map(|panel| {
+                let panel = panel.read(cx);
+                (panel.workspace.clone(), panel.active_repository.clone())
+            })
+            .unzip();
+
+        let single_repo = project
+            .as_ref()
+            .map(|project| project.read(cx).git_store().read(cx).repositories().len() == 1)
+            .unwrap_or(true);
+
+        const MAX_BRANCH_LEN: usize = 16;
+        const MAX_REPO_LEN: usize = 16;
+        const LABEL_CHARACTER_BUDGET: usize = MAX_BRANCH_LEN + MAX_REPO_LEN;
+        const MAX_SHORT_SHA_LEN: usize = 8;
+        let branch_name = self
+            .branch
+            .as_ref()
+            .map(|branch| branch.name().to_owned())
+            .or_else(|| {
+                self.head_commit.as_ref().map(|commit| {
+                    commit
+                        .sha
+                        .chars()
+                        .take(MAX_SHORT_SHA_LEN)
+                        .collect::<String>()
+                })
+            })
+            .unwrap_or_else(|| " (no branch)".to_owned());
+        let show_separator = self.branch.is_some() || self.head_commit.is_some();
+        let head_detached = self.branch.is_none() && self.head_commit.is_some();
+        let can_create_branch = self.git_panel.is_some();
+
+        let active_repo_name = self.active_repository.clone();
+
+        let branch_actual_len = branch_name.len();
+        let repo_actual_len = active_repo_name.len();
+
+        // ideally, show the whole branch and repo names but
+        // when we can't, use a budget to allocate space between the two
+        let (repo_display_len, branch_display_len) =
+            if branch_actual_len + repo_actual_len <= LABEL_CHARACTER_BUDGET {
+                (repo_actual_len, branch_actual_len)
+            } else if branch_actual_len <= MAX_BRANCH_LEN {
+                let repo_space = (LABEL_CHARACTER_BUDGET - branch_actual_len).min(MAX_REPO_LEN);
+                (repo_space, branch_actual_len)
+            } else if repo_actual_len <= MAX_REPO_LEN {
+                let branch_space = (LABEL_CHARACTER_BUDGET - repo_actual_len).min(MAX_BRANCH_LEN);
+                (repo_actual_len, branch_space)
+            } else {
+                (MAX_REPO_LEN, MAX_BRANCH_LEN)
+            };
+
+        let truncated_repo_name = if repo_actual_len <= repo_display_len {
+            active_repo_name.to_string()
+        } else {
+            util::truncate_and_trailoff(active_repo_name.trim_ascii(), repo_display_len)
+        };
+
+        let truncated_branch_name = if branch_actual_len <= branch_display_len {
+            branch_name
+        } else {
+            util::truncate_and_trailoff(branch_name.trim_ascii(), branch_display_len)
+        };
+
+        let repo_selector_trigger = Button::new("repo-selector", truncated_repo_name)
+            .size(ButtonSize::None)
+            .label_size(LabelSize::Small);
+
+        let repo_selector = PopoverMenu::new("repository-switcher")
+            .menu({
+                let project = project;
+                move |window, cx| {
+                    let project = project.clone()?;
+                    Some(cx.new(|cx| RepositorySelector::new(project, rems(20.), window, cx)))
+                }
+            })
+            .trigger_with_tooltip(
+                repo_selector_trigger
+                    .when(single_repo, |this| this.disabled(true).color(Color::Muted))
+                    .truncate(true),
+                move |_, cx| {
+                    if single_repo {
+                        cx.new(|_| Empty).into()
+                    } else {
+                        Tooltip::simple("Switch Active Repository", cx)
+                    }
+                },
+            )
+            .anchor(Corner::BottomLeft)
+            .offset(gpui::Point {
+                x: px(0.0),
+                y: px(-2.0),
+            })
+            .into_any_element();
+
+        let branch_selector_button = Button::new("branch-selector", truncated_branch_name)
+            .size(ButtonSize::None)
+            .label_size(LabelSize::Small)
+            .truncate(true)
+            .on_click(|_, window, cx| {
+                window.dispatch_action(zed_actions::git::Switch.boxed_clone(), cx);
+            });
+
+        let branch_selector = PopoverMenu::new("popover-button")
+            .menu(move |window, cx| {
+                let workspace = workspace.clone()?;
+                let repo = repo.clone().flatten();
+                Some(branch_picker::popover(workspace, false, repo, window, cx))
+            })
+            .trigger_with_tooltip(
+                branch_selector_button,
+                Tooltip::for_action_title("Switch Branch", &zed_actions::git::Switch),
+            )
+            .anchor(Corner::BottomLeft)
+            .offset(gpui::Point {
+                x: px(0.0),
+                y: px(-2.0),
+            });
+
+        h_flex()
+            .h(px(36.))
+            .w_full()
+            .px_2()
+            .justify_between()
+            .gap_1()
+            .child(
+                h_flex()
+                    .flex_1()
+                    .overflow_hidden()
+                    .gap_px()
+                    .child(
+                        Icon::new(IconName::GitBranchAlt)
+                            .size(IconSize::Small)
+                            .color(if single_repo {
+                                Color::Disabled
+                            } else {
+                                Color::Muted
+                            }),
+                    )
+                    .child(repo_selector)
+                    .when(show_separator, |this| {
+                        this.child(
+                            div()
+                                .text_sm()
+                                .text_color(cx.theme().colors().icon_muted.opacity(0.5))
+                                .child("/"),
+                        )
+                    })
+                    .child(branch_selector)
+                    .when(head_detached, |this| {
+                        this.child(
+                            Label::new("detached")
+                                .size(LabelSize::Small)
+                                .color(Color::Warning),
+                        )
+                        .when(can_create_branch, |this| {
+                            this.child(
+                                IconButton::new("create-branch-here", IconName::GitBranchPlus)
+                                    .icon_size(IconSize::Small)
+                                    .tooltip(Tooltip::for_action_title(
+                                        "Create Branch Here",
+                                        &git::CreateBranchHere,
+                                    ))
+                                    .on_click(|_, window, cx| {
+                                        window.dispatch_action(
+                                            git::CreateBranchHere.boxed_clone(),
+                                            cx,
+                                        );
+                                    }),
+                            )
+                        })
+                    }),
+            )
+            .children(if let Some(git_panel) = self.git_panel {
+                git_panel.update(cx, |git_panel, cx| git_panel.render_remote_button(cx))
+            } else {
+                None
+            })
+    }
+}
+
+impl Component for PanelRepoFooter {
+    fn scope() -> ComponentScope {
+        ComponentScope::VersionControl
+    }
+
+    fn preview(_window: &mut Window, _cx: &mut App) -> Option<AnyElement> {
+        let unknown_upstream = None;
+        let no_remote_upstream = Some(UpstreamTracking::Gone);
+        let ahead_of_upstream = Some(
+            UpstreamTrackingStatus {
+                ahead: 2,
+                behind: 0,
+            }
+            .into(),
+        );
+        let behind_upstream = Some(
+            UpstreamTrackingStatus {
+                ahead: 0,
+                behind: 2,
+            }
+            .into(),
+        );
+        let ahead_and_behind_upstream = Some(
+            UpstreamTrackingStatus {
+                ahead: 3,
+                behind: 1,
+            }
+            .into(),
+        );
+
+        let not_ahead_or_behind_upstream = Some(
+            UpstreamTrackingStatus {
+                ahead: 0,
+                behind: 0,
+            }
+            .into(),
+        );
+
+        fn branch(upstream: Option<UpstreamTracking>) -> Branch {
+            Branch {
+                is_head: true,
+                ref_name: "some-branch".into(),
+                upstream: upstream.map(|tracking| Upstream {
+                    ref_name: "origin/some-branch".into(),
+                    tracking,
+                }),
+                most_recent_commit: Some(CommitSummary {
+                    sha: "abc123".into(),
+                    subject: "Modify stuff".into(),
+                    commit_timestamp: 1710932954,
+                    author_name: "John Doe".into(),
+                    has_parent: true,
+                }),
+            }
+        }
+
+        fn custom(branch_name: &str, upstream: Option<UpstreamTracking>) -> Branch {
+            Branch {
+                is_head: true,
+                ref_name: branch_name.to_string().into(),
+                upstream: upstream.map(|tracking| Upstream {
+                    ref_name: format!("zed/{}", branch_name).into(),
+                    tracking,
+                }),
+                most_recent_commit: Some(CommitSummary {
+                    sha: "abc123".into(),
+                    subject: "Modify stuff".into(),
+                    commit_timestamp: 1710932954,
+                    author_name: "John Doe".into(),
+                    has_parent: true,
+                }),
+            }
+        }
+
+        fn active_repository(id: usize) -> SharedString {
+            format!("repo-{}", id).into()
+        }
+
+        let example_width = px(340.);
+        Some(
+            v_flex()
+                .gap_6()
+                .w_full()
+                .flex_none()
+                .children(vec![
+                    example_group_with_title(
+                        "Action Button States",
+                        vec![
+                            single_example(
+                                "No Branch",
+                                div()
+                                    .w(example_width)
+                                    .overflow_hidden()
+                                    .child(PanelRepoFooter::new_preview(active_repository(1), None))
+                                    .into_any_element(),
+                            ),
+                            single_example(
+                                "Remote status unknown",
+                                div()
+                                    .w(example_width)
+                                    .overflow_hidden()
+                                    .child(PanelRepoFooter::new_preview(
+                                        active_repository(2),
+                                        Some(branch(unknown_upstream)),
+                                    ))
+                                    .into_any_element(),
+                            ),
+                            single_example(
+                                "No Remote Upstream",
+                                div()
+                                    .w(example_width)
+                                    .overflow_hidden()
+                                    .child(PanelRepoFooter::new_preview(
+                                        active_repository(3),
+                                        Some(branch(no_remote_upstream)),
+                                    ))
+                                    .into_any_element(),
+                            ),
+                            single_example(
+                                "Not Ahead or Behind",
+                                div()
+                                    .w(example_width)
+                                    .overflow_hidden()
+                                    .child(PanelRepoFooter::new_preview(
+                                        active_repository(4),
+                                        Some(branch(not_ahead_or_behind_upstream)),
+                                    ))
+                                    .into_any_element(),
+                            ),
+                            single_example(
+                                "Behind remote",
+                                div()
+                                    .w(example_width)
+                                    .overflow_hidden()
+                                    .child(PanelRepoFooter::new_preview(
+                                        active_repository(5),
+                                        Some(branch(behind_upstream)),
+                                    ))
+                                    .into_any_element(),
+                            ),
+                            single_example(
+                                "Ahead of remote",
+                                div()
+                                    .w(example_width)
+                                    .overflow_hidden()
+                                    .child(PanelRepoFooter::new_preview(
+                                        active_repository(6),
+                                        Some(branch(ahead_of_upstream)),
+                                    ))
+                                    .into_any_element(),
+                            ),
+                            single_example(
+                                "Ahead and behind remote",
+                                div()
+                                    .w(example_width)
+                                    .overflow_hidden()
+                                    .child(PanelRepoFooter::new_preview(
+                                        active_repository(7),
+                                        Some(branch(ahead_and_behind_upstream)),
+                                    ))
+                                    .into_any_element(),
+                            ),
+                        ],
+                    )
+                    .grow()
+                    .vertical(),
+                ])
+                .children(vec![
+                    example_group_with_title(
+                        "Labels",
+                        vec![
+                            single_example(
+                                "Short Branch & Repo",
+                                div()
+                                    .w(example_width)
+                                    .overflow_hidden()
+                                    .child(PanelRepoFooter::new_preview(
+                                        SharedString::from("zed"),
+                                        Some(custom("main", behind_upstream)),
+                                    ))
+                                    .into_any_element(),
+                            ),
+                            single_example(
+                                "Long Branch",
+                                div()
+                                    .w(example_width)
+                                    .overflow_hidden()
+                                    .child(PanelRepoFooter::new_preview(
+                                        SharedString::from("zed"),
+                                        Some(custom(
+                                            "redesign-and-update-git-ui-list-entry-style",
+                                            behind_upstream,
+                                        )),
+                                    ))
+                                    .into_any_element(),
+                            ),
+                            single_example(
+                                "Long Repo",
+                                div()
+                                    .w(example_width)
+                                    .overflow_hidden()
+                                    .child(PanelRepoFooter::new_preview(
+                                        SharedString::from("zed-industries-community-examples"),
+                                        Some(custom("gpui", ahead_of_upstream)),
+                                    ))
+                                    .into_any_element(),
+                            ),
+                            single_example(
+                                "Long Repo & Branch",
+                                div()
+                                    .w(example_width)
+                                    .overflow_hidden()
+                                    .child(PanelRepoFooter::new_preview(
+                                        SharedString::from("zed-industries-community-examples"),
+                                        Some(custom(
+                                            "redesign-and-update-git-ui-list-entry-style",
+                                            behind_upstream,
+                                        )),
+                                    ))
+                                    .into_any_element(),
+                            ),
+                            single_example(
+                                "Uppercase Repo",
+                                div()
+                                    .w(example_width)
+                                    .overflow_hidden()
+                                    .child(PanelRepoFooter::new_preview(
+                                        SharedString::from("LICENSES"),
+                                        Some(custom("main", ahead_of_upstream)),
+                                    ))
+                                    .into_any_element(),
+                            ),
+                            single_example(
+                                "Uppercase Branch",
+                                div()
+                                    .w(example_width)
+                                    .overflow_hidden()
+                                    .child(PanelRepoFooter::new_preview(
+                                        SharedString::from("zed"),
+                                        Some(custom("update-README", behind_upstream)),
+                                    ))
+                                    .into_any_element(),
+                            ),
+                        ],
+                    )
+                    .grow()
+                    .vertical(),
+                ])
+                .into_any_element(),
+        )
+    }
+}
+
+fn open_output(
+    operation: impl Into<SharedString>,
+    workspace: &mut Workspace,
+    output: &str,
+    window: &mut Window,
+    cx: &mut Context<Workspace>,
+) {
+    let operation = operation.into();
+    let buffer = cx.new(|cx| Buffer::local(output, cx));
+    buffer.update(cx, |buffer, cx| {
+        buffer.set_capability(language::Capability::ReadOnly, cx);
+    });
+    let editor = cx.new(|cx| {
+        let mut editor = Editor::for_buffer(buffer, None, window, cx);
+        editor.buffer().update(cx, |buffer, cx| {
+            buffer.set_title(format!("Output from git {operation}"), cx);
+        });
+        editor.set_read_only(true);
+        editor
+    });
 
-        let id: ElementId = ElementId::Name(format!("dir_{}_{}", entry.name, ix).into());
-        let checkbox_id: ElementId =
-            ElementId::Name(format!("dir_checkbox_{}_{}", entry.name, ix).into());
-        let checkbox_wrapper_id: ElementId =
-            ElementId::Name(format!("dir_checkbox_wrapper_{}_{}", entry.name, ix).into());
+    workspace.add_item_to_center(Box::new(editor), window, cx);
+}
 
-        let selected_bg_alpha = 0.08;
-        let state_opacity_step = 0.04;
+/// Asks how to integrate a branch that can't be fast-forwarded to its upstream.
+fn prompt_for_diverged_pull(
+    branch_name: &str,
+    remote_name: &str,
+    window: &mut Window,
+    cx: &mut App,
+) -> impl Future<Output = Option<PullStrategy>> + use<> {
+    let answer = window.prompt(
+        PromptLevel::Warning,
+        &format!("Can't fast-forward {branch_name}"),
+        Some(&format!(
+            "{branch_name} and {remote_name} have diverged. Rebase your commits onto the \
+             upstream changes, or merge them with a merge commit."
+        )),
+        &["Rebase Instead", "Create Merge Commit", "Cancel"],
+        cx,
+    );
+    async move {
+        match answer.await {
+            Ok(0) => Some(PullStrategy::Rebase),
+            Ok(1) => Some(PullStrategy::Merge),
+            _ => None,
+        }
+    }
+}
 
-        let info_color = cx.theme().status().info;
-        let colors = cx.theme().colors();
+/// Asks before force pushing to `remote_branch`, listing the commits it would lose.
+async fn confirm_force_push(
+    this: &WeakEntity<GitPanel>,
+    repo: &Entity<Repository>,
+    title: &str,
+    remote_branch: &str,
+    cx: &mut AsyncWindowContext,
+) -> anyhow::Result<bool> {
+    // `--force-with-lease` only overwrites the remote branch if it still matches the
+    // remote-tracking ref, so the commits only that ref has are exactly what's lost.
+    let discarded_commits = repo
+        .update(cx, |repo, _| {
+            repo.commits_between("HEAD".to_string(), format!("refs/remotes/{remote_branch}"))
+        })
+        .await
+        .ok()
+        .and_then(|commits| commits.log_err());
+    let answer = this.update_in(cx, |_, window, cx| {
+        window.prompt(
+            PromptLevel::Warning,
+            title,
+            Some(&force_push_prompt_detail(
+                remote_branch,
+                discarded_commits.as_deref(),
+            )),
+            &["Force Push", "Cancel"],
+            cx,
+        )
+    })?;
+    Ok(answer.await == Ok(0))
+}
 
-        let (base_bg, hover_bg, active_bg) = if selected {
-            (
-                info_color.alpha(selected_bg_alpha),
-                info_color.alpha(selected_bg_alpha + state_opacity_step),
-                info_color.alpha(selected_bg_alpha + state_opacity_step * 2.0),
-            )
-        } else {
-            (
-                colors.ghost_element_background,
-                colors.ghost_element_hover,
-                colors.ghost_element_active,
-            )
-        };
+/// Describes the commits on `remote_branch` a force push would discard, or warns that they're
+/// unknown when they couldn't be listed.
+fn force_push_prompt_detail(
+    remote_branch: &str,
+    discarded_commits: Option<&[CommitSummary]>,
+) -> String {
+    const MAX_LISTED_COMMITS: usize = 10;
 
-        let settings = GitPanelSettings::get_global(cx);
-        let folder_icon = if settings.folder_icons {
-            FileIcons::get_folder_icon(entry.expanded, entry.key.path.as_std_path(), cx)
-        } else {
-            FileIcons::get_chevron_icon(entry.expanded, cx)
-        };
-        let fallback_folder_icon = if settings.folder_icons {
-            if entry.expanded {
-                IconName::FolderOpen
-            } else {
-                IconName::Folder
-            }
-        } else {
-            if entry.expanded {
-                IconName::ChevronDown
-            } else {
-                IconName::ChevronRight
-            }
-        };
+    let Some(discarded_commits) = discarded_commits else {
+        return format!("Any commits on {remote_branch} that aren't in your branch will be lost.");
+    };
+    if discarded_commits.is_empty() {
+        return format!("No commits on {remote_branch} will be lost.");
+    }
 
-        let stage_status = if let Some(repo) = &self.active_repository {
-            self.stage_status_for_directory(entry, repo.read(cx))
+    let mut detail = format!(
+        "{} on {remote_branch} will be lost:\n",
+        if discarded_commits.len() == 1 {
+            "1 commit".to_string()
         } else {
-            util::debug_panic!(
-                "Won't have entries to render without an active repository in Git Panel"
-            );
-            StageStatus::PartiallyStaged
-        };
+            format!("{} commits", discarded_commits.len())
+        }
+    );
+    for commit in discarded_commits.iter().take(MAX_LISTED_COMMITS) {
+        let short_sha = commit
+            .sha
+            .get(..git::SHORT_SHA_LENGTH)
+            .unwrap_or(&commit.sha);
+        detail.push_str(&format!("\n{short_sha} {}", commit.subject));
+    }
+    if discarded_commits.len() > MAX_LISTED_COMMITS {
+        detail.push_str(&format!(
+            "\n…and {} more",
+            discarded_commits.len() - MAX_LISTED_COMMITS
+        ));
+    }
+    detail
+}
 
-        let toggle_state: ToggleState = match stage_status {
-            StageStatus::Staged => ToggleState::Selected,
-            StageStatus::Unstaged => ToggleState::Unselected,
-            StageStatus::PartiallyStaged => ToggleState::Indeterminate,
-        };
+fn in_progress_operation_title(operation: InProgressOperation) -> String {
+    let verb = match operation.kind {
+        InProgressOperationKind::Merge => "Merging",
+        InProgressOperationKind::CherryPick => "Cherry-picking",
+        InProgressOperationKind::Revert => "Reverting",
+        InProgressOperationKind::Rebase => "Rebasing",
+    };
+    match operation.progress {
+        Some((step, total)) => format!("{verb} {step}/{total}"),
+        None => verb.to_string(),
+    }
+}
 
-        let name_row = h_flex()
-            .min_w_0()
-            .gap_1()
-            .pl(px(entry.depth as f32 * TREE_INDENT))
-            .child(
-                folder_icon
-                    .map(|folder_icon| {
-                        Icon::from_path(folder_icon)
-                            .size(IconSize::Small)
-                            .color(Color::Muted)
-                    })
-                    .unwrap_or_else(|| {
-                        Icon::new(fallback_folder_icon)
-                            .size(IconSize::Small)
-                            .color(Color::Muted)
-                    }),
-            )
-            .child(self.entry_label(entry.name.clone(), label_color).truncate());
+/// Reverts `sha` in `repository`. With `no_commit`, the reverting changes are only staged and the
+/// generated revert message is put in the Git panel's commit editor, ready to be committed.
+pub fn revert_commit(
+    workspace: WeakEntity<Workspace>,
+    repository: Entity<Repository>,
+    sha: String,
+    no_commit: bool,
+    window: &mut Window,
+    cx: &mut App,
+) {
+    let revert = repository.update(cx, |repository, _| repository.revert_commit(sha, no_commit));
+    window
+        .spawn(cx, async move |cx| {
+            let result = revert
+                .await
+                .map_err(anyhow::Error::from)
+                .and_then(|result| result);
+            let Some(workspace) = workspace.upgrade() else {
+                return;
+            };
+            cx.update(|window, cx| match result {
+                Ok(Some(message)) => workspace.update(cx, |workspace, cx| {
+                    if let Some(panel) = workspace.focus_panel::<GitPanel>(window, cx) {
+                        panel.update(cx, |panel, cx| {
+                            panel.set_commit_message_for(&repository, message, window, cx)
+                        });
+                    }
+                }),
+                Ok(None) => {}
+                Err(error) => show_error_toast(workspace, "revert", error, cx),
+            })
+            .ok();
+        })
+        .detach();
+}
 
-        h_flex()
-            .id(id)
-            .h(self.list_item_height())
-            .min_w_0()
-            .w_full()
-            .pl_3()
-            .pr_1()
-            .gap_1p5()
-            .justify_between()
-            .border_1()
-            .border_r_2()
-            .when(selected && self.focus_handle.is_focused(window), |el| {
-                el.border_color(cx.theme().colors().panel_focused_border)
+/// Asks before reverting `sha` with a new commit, for buttons that would otherwise commit the
+/// revert with a single click.
+pub fn confirm_revert_commit(
+    workspace: WeakEntity<Workspace>,
+    repository: Entity<Repository>,
+    sha: String,
+    window: &mut Window,
+    cx: &mut App,
+) {
+    let short_sha = sha.get(..git::SHORT_SHA_LENGTH).unwrap_or(&sha);
+    let answer = window.prompt(
+        PromptLevel::Warning,
+        &format!("Revert commit {short_sha}?"),
+        Some("This creates a new commit that undoes its changes."),
+        &["Revert", "Cancel"],
+        cx,
+    );
+    window
+        .spawn(cx, async move |cx| {
+            if answer.await != Ok(0) {
+                return;
+            }
+            cx.update(|window, cx| {
+                revert_commit(workspace, repository, sha, false, window, cx);
             })
-            .bg(base_bg)
-            .hover(|s| s.bg(hover_bg))
-            .active(|s| s.bg(active_bg))
-            .child(name_row)
-            .child(
-                div()
-                    .id(checkbox_wrapper_id)
-                    .flex_none()
-                    .occlude()
-                    .cursor_pointer()
-                    .child(
-                        Checkbox::new(checkbox_id, toggle_state)
-                            .disabled(!has_write_access)
-                            .fill()
-                            .elevation(ElevationIndex::Surface)
-                            .on_click({
-                                let entry = entry.clone();
-                                let this = cx.weak_entity();
-                                move |_, window, cx| {
-                                    this.update(cx, |this, cx| {
-                                        if !has_write_access {
-                                            return;
-                                        }
-                                        this.toggle_staged_for_entry(
-                                            &GitListEntry::Directory(entry.clone()),
-                                            window,
-                                            cx,
-                                        );
-                                        cx.stop_propagation();
-                                    })
-                                    .ok();
-                                }
+            .ok();
+        })
+        .detach();
+}
+
+pub(crate) fn show_error_toast(
+    workspace: Entity<Workspace>,
+    action: impl Into<SharedString>,
+    e: anyhow::Error,
+    cx: &mut App,
+) {
+    let action = action.into();
+    let message = format_git_error_toast_message(&e);
+    if message
+        .matches(git::repository::REMOTE_CANCELLED_BY_USER)
+        .next()
+        .is_some()
+    { // Hide the cancelled by user message
+    } else {
+        // Signing failures carry a suggested fix on their first line, so show it directly.
+        let title = message
+            .starts_with(git::repository::COMMIT_SIGNING_FAILED)
+            .then(|| message.lines().next())
+            .flatten()
+            .map(ToString::to_string)
+            .unwrap_or_else(|| format!("git {} failed", action));
+        workspace.update(cx, |workspace, cx| {
+            let workspace_weak = cx.weak_entity();
+            let toast = StatusToast::new(title, cx, |this, _cx| {
+                this.icon(ToastIcon::new(IconName::XCircle).color(Color::Error))
+                    .action("View Log", move |window, cx| {
+                        let message = message.clone();
+                        let action = action.clone();
+                        workspace_weak
+                            .update(cx, move |workspace, cx| {
+                                open_output(action, workspace, &message, window, cx)
                             })
-                            .tooltip(move |_window, cx| {
-                                let action = match stage_status {
-                                    StageStatus::Staged => "Unstage",
-                                    StageStatus::Unstaged | StageStatus::PartiallyStaged => "Stage",
-                                };
-                                Tooltip::simple(format!("{action} folder"), cx)
-                            }),
-                    ),
-            )
-            .on_click({
-                let key = entry.key.clone();
-                cx.listener(move |this, _event: &ClickEvent, window, cx| {
-                    this.selected_entry = Some(ix);
-                    this.toggle_directory(&key, window, cx);
-                })
-            })
-            .into_any_element()
+                            .ok();
+                    })
+            });
+            workspace.toggle_status_toast(toast, cx)
+        });
     }
+}
 
-    fn path_formatted(
-        &self,
-        directory: Option<String>,
-        path_color: Color,
-        file_name: String,
-        label_color: Color,
-        path_style: PathStyle,
-        git_path_style: GitPathStyle,
-        strikethrough: bool,
-    ) -> Div {
-        let file_name_first = git_path_style == GitPathStyle::FileNameFirst;
-        let file_path_first = git_path_style == GitPathStyle::FilePathFirst;
+fn rpc_error_raw_message_from_chain(error: &anyhow::Error) -> Option<&str> {
+    error
+        .chain()
+        .find_map(|cause| cause.downcast_ref::<RpcError>().map(RpcError::raw_message))
+}
+
+fn format_git_error_toast_message(error: &anyhow::Error) -> String {
+    if let Some(message) = rpc_error_raw_message_from_chain(error) {
+        message.trim().to_string()
+    } else {
+        error.to_string().trim().to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use git::{
+        gitignore::GitignorePatternKind,
+        repository::repo_path,
+        status::{FileRename, StatusCode, UnmergedStatus, UnmergedStatusCode},
+    };
+    use gpui::{TestAppContext, UpdateGlobal, VisualTestContext, px};
+    use indoc::indoc;
+    use project::FakeFs;
+    use serde_json::json;
+    use settings::SettingsStore;
+    use theme::LoadThemes;
+    use util::path;
+    use util::rel_path::rel_path;
 
-        let file_name = format!("{} ", file_name);
+    use workspace::MultiWorkspace;
 
-        h_flex()
-            .min_w_0()
-            .overflow_hidden()
-            .when(file_path_first, |this| this.flex_row_reverse())
-            .child(
-                div().flex_none().child(
-                    self.entry_label(file_name, label_color)
-                        .when(strikethrough, Label::strikethrough),
-                ),
-            )
-            .when_some(directory, |this, dir| {
-                let path_name = if file_name_first {
-                    dir
-                } else {
-                    format!("{dir}{}", path_style.primary_separator())
-                };
+    use super::*;
 
-                this.child(
-                    self.entry_label(path_name, path_color)
-                        .truncate_start()
-                        .when(strikethrough, Label::strikethrough),
-                )
-            })
-    }
+    fn init_test(cx: &mut gpui::TestAppContext) {
+        zlog::init_test();
 
-    fn has_write_access(&self, cx: &App) -> bool {
-        !self.project.read(cx).is_read_only(cx)
+        cx.update(|cx| {
+            let settings_store = SettingsStore::test(cx);
+            cx.set_global(settings_store);
+            theme::init(LoadThemes::JustBase, cx);
+            editor::init(cx);
+            crate::init(cx);
+        });
     }
 
-    pub fn amend_pending(&self) -> bool {
-        self.amend_pending
+    #[test]
+    fn test_format_git_error_toast_message_prefers_raw_rpc_message() {
+        let rpc_error = RpcError::from_proto(
+            &proto::Error {
+                message:
+                    "Your local changes to the following files would be overwritten by merge\n"
+                        .to_string(),
+                code: proto::ErrorCode::Internal as i32,
+                tags: Default::default(),
+            },
+            "Pull",
+        );
+
+        let message = format_git_error_toast_message(&rpc_error);
+        assert_eq!(
+            message,
+            "Your local changes to the following files would be overwritten by merge"
+        );
     }
 
-    /// Sets the pending amend state, ensuring that the original commit message
-    /// is either saved, when `value` is `true` and there's no pending amend, or
-    /// restored, when `value` is `false` and there's a pending amend.
-    pub fn set_amend_pending(&mut self, value: bool, cx: &mut Context<Self>) {
-        if value && !self.amend_pending {
-            let current_message = self.commit_message_buffer(cx).read(cx).text();
-            self.original_commit_message = if current_message.trim().is_empty() {
-                None
-            } else {
-                Some(current_message)
-            };
-        } else if !value && self.amend_pending {
-            let message = self.original_commit_message.take().unwrap_or_default();
-            self.commit_message_buffer(cx).update(cx, |buffer, cx| {
-                let start = buffer.anchor_before(0);
-                let end = buffer.anchor_after(buffer.len());
-                buffer.edit([(start..end, message)], None, cx);
-            });
-        }
+    #[test]
+    fn test_format_git_error_toast_message_prefers_raw_rpc_message_when_wrapped() {
+        let rpc_error = RpcError::from_proto(
+            &proto::Error {
+                message:
+                    "Your local changes to the following files would be overwritten by merge\n"
+                        .to_string(),
+                code: proto::ErrorCode::Internal as i32,
+                tags: Default::default(),
+            },
+            "Pull",
+        );
+        let wrapped = rpc_error.context("sending pull request");
 
-        self.amend_pending = value;
-        self.serialize(cx);
-        cx.notify();
+        let message = format_git_error_toast_message(&wrapped);
+        assert_eq!(
+            message,
+            "Your local changes to the following files would be overwritten by merge"
+        );
     }
 
-    pub fn signoff_enabled(&self) -> bool {
-        self.signoff_enabled
-    }
+    #[gpui::test]
+    async fn test_entry_worktree_paths(cx: &mut TestAppContext) {
+        init_test(cx);
+        let fs = FakeFs::new(cx.background_executor.clone());
+        fs.insert_tree(
+            "/root",
+            json!({
+                "zed": {
+                    ".git": {},
+                    "crates": {
+                        "gpui": {
+                            "gpui.rs": "fn main() {}"
+                        },
+                        "util": {
+                            "util.rs": "fn do_it() {}"
+                        }
+                    }
+                },
+            }),
+        )
+        .await;
 
-    pub fn set_signoff_enabled(&mut self, value: bool, cx: &mut Context<Self>) {
-        self.signoff_enabled = value;
-        self.serialize(cx);
-        cx.notify();
-    }
+        fs.set_status_for_repo(
+            Path::new(path!("/root/zed/.git")),
+            &[
+                ("crates/gpui/gpui.rs", StatusCode::Modified.worktree()),
+                ("crates/util/util.rs", StatusCode::Modified.worktree()),
+            ],
+        );
 
-    pub fn toggle_signoff_enabled(
-        &mut self,
-        _: &Signoff,
-        _window: &mut Window,
-        cx: &mut Context<Self>,
-    ) {
-        self.set_signoff_enabled(!self.signoff_enabled, cx);
-    }
+        let project =
+            Project::test(fs.clone(), [path!("/root/zed/crates/gpui").as_ref()], cx).await;
+        let window_handle =
+            cx.add_window(|window, cx| MultiWorkspace::test_new(project.clone(), window, cx));
+        let workspace = window_handle
+            .read_with(cx, |mw, _| mw.workspace().clone())
+            .unwrap();
+        let cx = &mut VisualTestContext::from_window(window_handle.into(), cx);
 
-    pub async fn load(
-        workspace: WeakEntity<Workspace>,
-        mut cx: AsyncWindowContext,
-    ) -> anyhow::Result<Entity<Self>> {
-        let serialized_panel = match workspace
-            .read_with(&cx, |workspace, cx| {
-                Self::serialization_key(workspace).map(|key| (key, KeyValueStore::global(cx)))
-            })
-            .ok()
-            .flatten()
-        {
-            Some((serialization_key, kvp)) => cx
-                .background_spawn(async move { kvp.read_kvp(&serialization_key) })
-                .await
-                .context("loading git panel")
-                .log_err()
-                .flatten()
-                .map(|panel| serde_json::from_str::<SerializedGitPanel>(&panel))
-                .transpose()
-                .log_err()
-                .flatten(),
-            None => None,
-        };
+        cx.read(|cx| {
+            project
+                .read(cx)
+                .worktrees(cx)
+                .next()
+                .unwrap()
+                .read(cx)
+                .as_local()
+                .unwrap()
+                .scan_complete()
+        })
+        .await;
 
-        workspace.update_in(&mut cx, |workspace, window, cx| {
-            let panel = GitPanel::new(workspace, window, cx);
+        cx.executor().run_until_parked();
+
+        let panel = workspace.update_in(cx, GitPanel::new);
+
+        let handle = cx.update_window_entity(&panel, |panel, _, _| {
+            std::mem::replace(&mut panel.update_visible_entries_task, Task::ready(()))
+        });
+        cx.executor().advance_clock(2 * UPDATE_DEBOUNCE);
+        handle.await;
+
+        let entries = panel.read_with(cx, |panel, _| panel.entries.clone());
+        pretty_assertions::assert_eq!(
+            entries,
+            [
+                GitListEntry::Header(GitHeaderEntry {
+                    header: Section::Tracked
+                }),
+                GitListEntry::Status(GitStatusEntry {
+                    repo_path: repo_path("crates/gpui/gpui.rs"),
+                    status: StatusCode::Modified.worktree(),
+                    staging: StageStatus::Unstaged,
+                    diff_stat: Some(DiffStat {
+                        added: 1,
+                        deleted: 1,
+                    }),
+                }),
+                GitListEntry::Status(GitStatusEntry {
+                    repo_path: repo_path("crates/util/util.rs"),
+                    status: StatusCode::Modified.worktree(),
+                    staging: StageStatus::Unstaged,
+                    diff_stat: Some(DiffStat {
+                        added: 1,
+                        deleted: 1,
+                    }),
+                },),
+            ],
+        );
+
+        let handle = cx.update_window_entity(&panel, |panel, _, _| {
+            std::mem::replace(&mut panel.update_visible_entries_task, Task::ready(()))
+        });
+        cx.executor().advance_clock(2 * UPDATE_DEBOUNCE);
+        handle.await;
+        let entries = panel.read_with(cx, |panel, _| panel.entries.clone());
+        pretty_assertions::assert_eq!(
+            entries,
+            [
+                GitListEntry::Header(GitHeaderEntry {
+                    header: Section::Tracked
+                }),
+                GitListEntry::Status(GitStatusEntry {
+                    repo_path: repo_path("crates/gpui/gpui.rs"),
+                    status: StatusCode::Modified.worktree(),
+                    staging: StageStatus::Unstaged,
+                    diff_stat: Some(DiffStat {
+                        added: 1,
+                        deleted: 1,
+                    }),
+                }),
+                GitListEntry::Status(GitStatusEntry {
+                    repo_path: repo_path("crates/util/util.rs"),
+                    status: StatusCode::Modified.worktree(),
+                    staging: StageStatus::Unstaged,
+                    diff_stat: Some(DiffStat {
+                        added: 1,
+                        deleted: 1,
+                    }),
+                },),
+            ],
+        );
+    }
 
-            if let Some(serialized_panel) = serialized_panel {
-                panel.update(cx, |panel, cx| {
-                    panel.amend_pending = serialized_panel.amend_pending;
-                    panel.signoff_enabled = serialized_panel.signoff_enabled;
-                    cx.notify();
-                })
-            }
+    #[gpui::test]
+    async fn test_bulk_staging(cx: &mut TestAppContext) {
+        use GitListEntry::*;
 
-            panel
-        })
-    }
+        init_test(cx);
+        let fs = FakeFs::new(cx.background_executor.clone());
+        fs.insert_tree(
+            "/root",
+            json!({
+                "project": {
+                    ".git": {},
+                    "src": {
+                        "main.rs": "fn main() {}",
+                        "lib.rs": "pub fn hello() {}",
+                        "utils.rs": "pub fn util() {}"
+                    },
+                    "tests": {
+                        "test.rs": "fn test() {}"
+                    },
+                    "new_file.txt": "new content",
+                    "another_new.rs": "// new file",
+                    "conflict.txt": "conflicted content"
+                }
+            }),
+        )
+        .await;
 
-    fn stage_bulk(&mut self, mut index: usize, cx: &mut Context<'_, Self>) {
-        let Some(op) = self.bulk_staging.as_ref() else {
-            return;
-        };
-        let Some(mut anchor_index) = self.entry_by_path(&op.anchor) else {
-            return;
-        };
-        if let Some(entry) = self.entries.get(index)
-            && let Some(entry) = entry.status_entry()
-        {
-            self.set_bulk_staging_anchor(entry.repo_path.clone(), cx);
-        }
-        if index < anchor_index {
-            std::mem::swap(&mut index, &mut anchor_index);
-        }
-        let entries = self
-            .entries
-            .get(anchor_index..=index)
-            .unwrap_or_default()
-            .iter()
-            .filter_map(|entry| entry.status_entry().cloned())
-            .collect::<Vec<_>>();
-        self.change_file_stage(true, entries, cx);
-    }
+        fs.set_status_for_repo(
+            Path::new(path!("/root/project/.git")),
+            &[
+                ("src/main.rs", StatusCode::Modified.worktree()),
+                ("src/lib.rs", StatusCode::Modified.worktree()),
+                ("tests/test.rs", StatusCode::Modified.worktree()),
+                ("new_file.txt", FileStatus::Untracked),
+                ("another_new.rs", FileStatus::Untracked),
+                ("src/utils.rs", FileStatus::Untracked),
+                (
+                    "conflict.txt",
+                    UnmergedStatus {
+                        first_head: UnmergedStatusCode::Updated,
+                        second_head: UnmergedStatusCode::Updated,
+                    }
+                    .into(),
+                ),
+            ],
+        );
 
-    fn set_bulk_staging_anchor(&mut self, path: RepoPath, cx: &mut Context<'_, GitPanel>) {
-        let Some(repo) = self.active_repository.as_ref() else {
-            return;
-        };
-        self.bulk_staging = Some(BulkStaging {
-            repo_id: repo.read(cx).id,
-            anchor: path,
-        });
-    }
+        let project = Project::test(fs.clone(), [Path::new(path!("/root/project"))], cx).await;
+        let window_handle =
+            cx.add_window(|window, cx| MultiWorkspace::test_new(project.clone(), window, cx));
+        let workspace = window_handle
+            .read_with(cx, |mw, _| mw.workspace().clone())
+            .unwrap();
+        let cx = &mut VisualTestContext::from_window(window_handle.into(), cx);
 
-    pub(crate) fn toggle_amend_pending(&mut self, cx: &mut Context<Self>) {
-        self.set_amend_pending(!self.amend_pending, cx);
-        if self.amend_pending {
-            self.load_last_commit_message(cx);
-        }
-    }
-}
+        cx.read(|cx| {
+            project
+                .read(cx)
+                .worktrees(cx)
+                .next()
+                .unwrap()
+                .read(cx)
+                .as_local()
+                .unwrap()
+                .scan_complete()
+        })
+        .await;
 
-#[cfg(any(test, feature = "test-support"))]
-impl GitPanel {
-    pub fn new_test(
-        workspace: &mut Workspace,
-        window: &mut Window,
-        cx: &mut Context<Workspace>,
-    ) -> Entity<Self> {
-        Self::new(workspace, window, cx)
-    }
+        cx.executor().run_until_parked();
 
-    pub fn active_repository(&self) -> Option<&Entity<Repository>> {
-        self.active_repository.as_ref()
-    }
-}
+        let panel = workspace.update_in(cx, GitPanel::new);
 
-impl Render for GitPanel {
-    fn render(&mut self, window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
-        let project = self.project.read(cx);
-        let has_entries = !self.entries.is_empty();
-        let room = self.workspace.upgrade().and_then(|_workspace| {
-            call::ActiveCall::try_global(cx).and_then(|call| call.read(cx).room().cloned())
+        let handle = cx.update_window_entity(&panel, |panel, _, _| {
+            std::mem::replace(&mut panel.update_visible_entries_task, Task::ready(()))
         });
+        cx.executor().advance_clock(2 * UPDATE_DEBOUNCE);
+        handle.await;
 
-        let has_write_access = self.has_write_access(cx);
+        let entries = panel.read_with(cx, |panel, _| panel.entries.clone());
+        #[rustfmt::skip]
+        pretty_assertions::assert_matches!(
+            entries.as_slice(),
+            &[
+                Header(GitHeaderEntry { header: Section::Conflict }),
+                Status(GitStatusEntry { staging: StageStatus::Unstaged, .. }),
+                Header(GitHeaderEntry { header: Section::Tracked }),
+                Status(GitStatusEntry { staging: StageStatus::Unstaged, .. }),
+                Status(GitStatusEntry { staging: StageStatus::Unstaged, .. }),
+                Status(GitStatusEntry { staging: StageStatus::Unstaged, .. }),
+                Header(GitHeaderEntry { header: Section::New }),
+                Status(GitStatusEntry { staging: StageStatus::Unstaged, .. }),
+                Status(GitStatusEntry { staging: StageStatus::Unstaged, .. }),
+                Status(GitStatusEntry { staging: StageStatus::Unstaged, .. }),
+            ],
+        );
 
-        let has_co_authors = room.is_some_and(|room| {
-            self.load_local_committer(cx);
-            let room = room.read(cx);
-            room.remote_participants()
-                .values()
-                .any(|remote_participant| remote_participant.can_write())
+        let second_status_entry = entries[3].clone();
+        panel.update_in(cx, |panel, window, cx| {
+            panel.toggle_staged_for_entry(&second_status_entry, window, cx);
         });
 
-        v_flex()
-            .id("git_panel")
-            .key_context(self.dispatch_context(window, cx))
-            .track_focus(&self.focus_handle)
-            .when(has_write_access && !project.is_read_only(cx), |this| {
-                this.on_action(cx.listener(Self::toggle_staged_for_selected))
-                    .on_action(cx.listener(Self::stage_range))
-                    .on_action(cx.listener(GitPanel::on_commit))
-                    .on_action(cx.listener(GitPanel::on_amend))
-                    .on_action(cx.listener(GitPanel::toggle_signoff_enabled))
-                    .on_action(cx.listener(Self::stage_all))
-                    .on_action(cx.listener(Self::unstage_all))
-                    .on_action(cx.listener(Self::stage_selected))
-                    .on_action(cx.listener(Self::unstage_selected))
-                    .on_action(cx.listener(Self::restore_tracked_files))
-                    .on_action(cx.listener(Self::revert_selected))
-                    .on_action(cx.listener(Self::add_to_gitignore))
-                    .on_action(cx.listener(Self::clean_all))
-                    .on_action(cx.listener(Self::generate_commit_message_action))
-                    .on_action(cx.listener(Self::stash_all))
-                    .on_action(cx.listener(Self::stash_pop))
-            })
-            .on_action(cx.listener(Self::collapse_selected_entry))
-            .on_action(cx.listener(Self::expand_selected_entry))
-            .on_action(cx.listener(Self::select_first))
-            .on_action(cx.listener(Self::select_next))
-            .on_action(cx.listener(Self::select_previous))
-            .on_action(cx.listener(Self::select_last))
-            .on_action(cx.listener(Self::first_entry))
-            .on_action(cx.listener(Self::next_entry))
-            .on_action(cx.listener(Self::previous_entry))
-            .on_action(cx.listener(Self::last_entry))
-            .on_action(cx.listener(Self::close_panel))
-            .on_action(cx.listener(Self::open_diff))
-            .on_action(cx.listener(Self::open_file))
-            .on_action(cx.listener(Self::file_history))
-            .on_action(cx.listener(Self::focus_changes_list))
-            .on_action(cx.listener(Self::focus_editor))
-            .on_action(cx.listener(Self::expand_commit_editor))
-            .when(has_write_access && has_co_authors, |git_panel| {
-                git_panel.on_action(cx.listener(Self::toggle_fill_co_authors))
-            })
-            .on_action(cx.listener(Self::toggle_sort_by_path))
-            .on_action(cx.listener(Self::toggle_tree_view))
-            .size_full()
-            .overflow_hidden()
-            .bg(cx.theme().colors().panel_background)
-            .child(
-                v_flex()
-                    .size_full()
-                    .children(self.render_panel_header(window, cx))
-                    .map(|this| {
-                        if let Some(repo) = self.active_repository.clone()
-                            && has_entries
-                        {
-                            this.child(self.render_entries(has_write_access, repo, window, cx))
-                        } else {
-                            this.child(self.render_empty_state(cx).into_any_element())
-                        }
-                    })
-                    .children(self.render_footer(window, cx))
-                    .when(self.amend_pending, |this| {
-                        this.child(self.render_pending_amend(cx))
-                    })
-                    .when(!self.amend_pending, |this| {
-                        this.children(self.render_previous_commit(window, cx))
-                    })
-                    .into_any_element(),
-            )
-            .children(self.context_menu.as_ref().map(|(menu, position, _)| {
-                deferred(
-                    anchored()
-                        .position(*position)
-                        .anchor(Corner::TopLeft)
-                        .child(menu.clone()),
-                )
-                .with_priority(1)
-            }))
-    }
-}
+        panel.update_in(cx, |panel, window, cx| {
+            panel.selected_entry = Some(7);
+            panel.stage_range(&git::StageRange, window, cx);
+        });
 
-impl Focusable for GitPanel {
-    fn focus_handle(&self, cx: &App) -> gpui::FocusHandle {
-        if self.entries.is_empty() {
-            self.commit_editor.focus_handle(cx)
-        } else {
-            self.focus_handle.clone()
-        }
-    }
-}
+        cx.read(|cx| {
+            project
+                .read(cx)
+                .worktrees(cx)
+                .next()
+                .unwrap()
+                .read(cx)
+                .as_local()
+                .unwrap()
+                .scan_complete()
+        })
+        .await;
 
-impl EventEmitter<Event> for GitPanel {}
+        cx.executor().run_until_parked();
 
-impl EventEmitter<PanelEvent> for GitPanel {}
+        let handle = cx.update_window_entity(&panel, |panel, _, _| {
+            std::mem::replace(&mut panel.update_visible_entries_task, Task::ready(()))
+        });
+        cx.executor().advance_clock(2 * UPDATE_DEBOUNCE);
+        handle.await;
 
-pub(crate) struct GitPanelAddon {
-    pub(crate) workspace: WeakEntity<Workspace>,
-}
+        let entries = panel.read_with(cx, |panel, _| panel.entries.clone());
+        #[rustfmt::skip]
+        pretty_assertions::assert_matches!(
+            entries.as_slice(),
+            &[
+                Header(GitHeaderEntry { header: Section::Conflict }),
+                Status(GitStatusEntry { staging: StageStatus::Unstaged, .. }),
+                Header(GitHeaderEntry { header: Section::Tracked }),
+                Status(GitStatusEntry { staging: StageStatus::Staged, .. }),
+                Status(GitStatusEntry { staging: StageStatus::Staged, .. }),
+                Status(GitStatusEntry { staging: StageStatus::Staged, .. }),
+                Header(GitHeaderEntry { header: Section::New }),
+                Status(GitStatusEntry { staging: StageStatus::Staged, .. }),
+                Status(GitStatusEntry { staging: StageStatus::Unstaged, .. }),
+                Status(GitStatusEntry { staging: StageStatus::Unstaged, .. }),
+            ],
+        );
 
-impl editor::Addon for GitPanelAddon {
-    fn to_any(&self) -> &dyn std::any::Any {
-        self
-    }
+        let third_status_entry = entries[4].clone();
+        panel.update_in(cx, |panel, window, cx| {
+            panel.toggle_staged_for_entry(&third_status_entry, window, cx);
+        });
 
-    fn render_buffer_header_controls(
-        &self,
-        excerpt_info: &ExcerptInfo,
-        window: &Window,
-        cx: &App,
-    ) -> Option<AnyElement> {
-        let file = excerpt_info.buffer.file()?;
-        let git_panel = self.workspace.upgrade()?.read(cx).panel::<GitPanel>(cx)?;
+        panel.update_in(cx, |panel, window, cx| {
+            panel.selected_entry = Some(9);
+            panel.stage_range(&git::StageRange, window, cx);
+        });
 
-        git_panel
-            .read(cx)
-            .render_buffer_header_controls(&git_panel, file, window, cx)
-    }
-}
+        cx.read(|cx| {
+            project
+                .read(cx)
+                .worktrees(cx)
+                .next()
+                .unwrap()
+                .read(cx)
+                .as_local()
+                .unwrap()
+                .scan_complete()
+        })
+        .await;
 
-impl Panel for GitPanel {
-    fn persistent_name() -> &'static str {
-        "GitPanel"
-    }
+        cx.executor().run_until_parked();
 
-    fn panel_key() -> &'static str {
-        GIT_PANEL_KEY
-    }
+        let handle = cx.update_window_entity(&panel, |panel, _, _| {
+            std::mem::replace(&mut panel.update_visible_entries_task, Task::ready(()))
+        });
+        cx.executor().advance_clock(2 * UPDATE_DEBOUNCE);
+        handle.await;
 
-    fn position(&self, _: &Window, cx: &App) -> DockPosition {
-        GitPanelSettings::get_global(cx).dock
+        let entries = panel.read_with(cx, |panel, _| panel.entries.clone());
+        #[rustfmt::skip]
+        pretty_assertions::assert_matches!(
+            entries.as_slice(),
+            &[
+                Header(GitHeaderEntry { header: Section::Conflict }),
+                Status(GitStatusEntry { staging: StageStatus::Unstaged, .. }),
+                Header(GitHeaderEntry { header: Section::Tracked }),
+                Status(GitStatusEntry { staging: StageStatus::Staged, .. }),
+                Status(GitStatusEntry { staging: StageStatus::Unstaged, .. }),
+                Status(GitStatusEntry { staging: StageStatus::Staged, .. }),
+                Header(GitHeaderEntry { header: Section::New }),
+                Status(GitStatusEntry { staging: StageStatus::Staged, .. }),
+                Status(GitStatusEntry { staging: StageStatus::Staged, .. }),
+                Status(GitStatusEntry { staging: StageStatus::Staged, .. }),
+            ],
+        );
     }
 
-    fn position_is_valid(&self, position: DockPosition) -> bool {
-        matches!(position, DockPosition::Left | DockPosition::Right)
-    }
+    #[gpui::test]
+    async fn test_bulk_staging_with_sort_by_paths(cx: &mut TestAppContext) {
+        use GitListEntry::*;
 
-    fn set_position(&mut self, position: DockPosition, _: &mut Window, cx: &mut Context<Self>) {
-        settings::update_settings_file(self.fs.clone(), cx, move |settings, _| {
-            settings.git_panel.get_or_insert_default().dock = Some(position.into())
+        init_test(cx);
+        let fs = FakeFs::new(cx.background_executor.clone());
+        fs.insert_tree(
+            "/root",
+            json!({
+                "project": {
+                    ".git": {},
+                    "src": {
+                        "main.rs": "fn main() {}",
+                        "lib.rs": "pub fn hello() {}",
+                        "utils.rs": "pub fn util() {}"
+                    },
+                    "tests": {
+                        "test.rs": "fn test() {}"
+                    },
+                    "new_file.txt": "new content",
+                    "another_new.rs": "// new file",
+                    "conflict.txt": "conflicted content"
+                }
+            }),
+        )
+        .await;
+
+        fs.set_status_for_repo(
+            Path::new(path!("/root/project/.git")),
+            &[
+                ("src/main.rs", StatusCode::Modified.worktree()),
+                ("src/lib.rs", StatusCode::Modified.worktree()),
+                ("tests/test.rs", StatusCode::Modified.worktree()),
+                ("new_file.txt", FileStatus::Untracked),
+                ("another_new.rs", FileStatus::Untracked),
+                ("src/utils.rs", FileStatus::Untracked),
+                (
+                    "conflict.txt",
+                    UnmergedStatus {
+                        first_head: UnmergedStatusCode::Updated,
+                        second_head: UnmergedStatusCode::Updated,
+                    }
+                    .into(),
+                ),
+            ],
+        );
+
+        let project = Project::test(fs.clone(), [Path::new(path!("/root/project"))], cx).await;
+        let window_handle =
+            cx.add_window(|window, cx| MultiWorkspace::test_new(project.clone(), window, cx));
+        let workspace = window_handle
+            .read_with(cx, |mw, _| mw.workspace().clone())
+            .unwrap();
+        let cx = &mut VisualTestContext::from_window(window_handle.into(), cx);
+
+        cx.read(|cx| {
+            project
+                .read(cx)
+                .worktrees(cx)
+                .next()
+                .unwrap()
+                .read(cx)
+                .as_local()
+                .unwrap()
+                .scan_complete()
+        })
+        .await;
+
+        cx.executor().run_until_parked();
+
+        let panel = workspace.update_in(cx, GitPanel::new);
+
+        let handle = cx.update_window_entity(&panel, |panel, _, _| {
+            std::mem::replace(&mut panel.update_visible_entries_task, Task::ready(()))
         });
-    }
+        cx.executor().advance_clock(2 * UPDATE_DEBOUNCE);
+        handle.await;
 
-    fn default_size(&self, _: &Window, cx: &App) -> Pixels {
-        GitPanelSettings::get_global(cx).default_width
-    }
+        let entries = panel.read_with(cx, |panel, _| panel.entries.clone());
+        #[rustfmt::skip]
+        pretty_assertions::assert_matches!(
+            entries.as_slice(),
+            &[
+                Header(GitHeaderEntry { header: Section::Conflict }),
+                Status(GitStatusEntry { staging: StageStatus::Unstaged, .. }),
+                Header(GitHeaderEntry { header: Section::Tracked }),
+                Status(GitStatusEntry { staging: StageStatus::Unstaged, .. }),
+                Status(GitStatusEntry { staging: StageStatus::Unstaged, .. }),
+                Status(GitStatusEntry { staging: StageStatus::Unstaged, .. }),
+                Header(GitHeaderEntry { header: Section::New }),
+                Status(GitStatusEntry { staging: StageStatus::Unstaged, .. }),
+                Status(GitStatusEntry { staging: StageStatus::Unstaged, .. }),
+                Status(GitStatusEntry { staging: StageStatus::Unstaged, .. }),
+            ],
+        );
+
+        assert_entry_paths(
+            &entries,
+            &[
+                None,
+                Some("conflict.txt"),
+                None,
+                Some("src/lib.rs"),
+                Some("src/main.rs"),
+                Some("tests/test.rs"),
+                None,
+                Some("another_new.rs"),
+                Some("new_file.txt"),
+                Some("src/utils.rs"),
+            ],
+        );
+
+        let second_status_entry = entries[3].clone();
+        panel.update_in(cx, |panel, window, cx| {
+            panel.toggle_staged_for_entry(&second_status_entry, window, cx);
+        });
+
+        cx.update(|_window, cx| {
+            SettingsStore::update_global(cx, |store, cx| {
+                store.update_user_settings(cx, |settings| {
+                    settings.git_panel.get_or_insert_default().sort_by_path = Some(true);
+                })
+            });
+        });
+
+        panel.update_in(cx, |panel, window, cx| {
+            panel.selected_entry = Some(7);
+            panel.stage_range(&git::StageRange, window, cx);
+        });
 
-    fn icon(&self, _: &Window, cx: &App) -> Option<ui::IconName> {
-        Some(ui::IconName::GitBranchAlt).filter(|_| GitPanelSettings::get_global(cx).button)
-    }
+        cx.read(|cx| {
+            project
+                .read(cx)
+                .worktrees(cx)
+                .next()
+                .unwrap()
+                .read(cx)
+                .as_local()
+                .unwrap()
+                .scan_complete()
+        })
+        .await;
 
-    fn icon_tooltip(&self, _window: &Window, _cx: &App) -> Option<&'static str> {
-        Some("Git Panel")
-    }
+        cx.executor().run_until_parked();
 
-    fn icon_label(&self, _: &Window, cx: &App) -> Option<String> {
-        if !GitPanelSettings::get_global(cx).show_count_badge {
-            return None;
-        }
-        let total = self.changes_count;
-        (total > 0).then(|| total.to_string())
-    }
+        let handle = cx.update_window_entity(&panel, |panel, _, _| {
+            std::mem::replace(&mut panel.update_visible_entries_task, Task::ready(()))
+        });
+        cx.executor().advance_clock(2 * UPDATE_DEBOUNCE);
+        handle.await;
 
-    fn toggle_action(&self) -> Box<dyn Action> {
-        Box::new(ToggleFocus)
-    }
+        let entries = panel.read_with(cx, |panel, _| panel.entries.clone());
+        #[rustfmt::skip]
+        pretty_assertions::assert_matches!(
+            entries.as_slice(),
+            &[
+                Status(GitStatusEntry { status: FileStatus::Untracked, staging: StageStatus::Unstaged, .. }),
+                Status(GitStatusEntry { status: FileStatus::Unmerged(..), staging: StageStatus::Unstaged, .. }),
+                Status(GitStatusEntry { status: FileStatus::Untracked, staging: StageStatus::Unstaged, .. }),
+                Status(GitStatusEntry { status: FileStatus::Tracked(..), staging: StageStatus::Staged, .. }),
+                Status(GitStatusEntry { status: FileStatus::Tracked(..), staging: StageStatus::Unstaged, .. }),
+                Status(GitStatusEntry { status: FileStatus::Untracked, staging: StageStatus::Unstaged, .. }),
+                Status(GitStatusEntry { status: FileStatus::Tracked(..), staging: StageStatus::Unstaged, .. }),
+            ],
+        );
 
-    fn starts_open(&self, _: &Window, cx: &App) -> bool {
-        GitPanelSettings::get_global(cx).starts_open
-    }
+        assert_entry_paths(
+            &entries,
+            &[
+                Some("another_new.rs"),
+                Some("conflict.txt"),
+                Some("new_file.txt"),
+                Some("src/lib.rs"),
+                Some("src/main.rs"),
+                Some("src/utils.rs"),
+                Some("tests/test.rs"),
+            ],
+        );
 
-    fn activation_priority(&self) -> u32 {
-        3
-    }
-}
+        let third_status_entry = entries[4].clone();
+        panel.update_in(cx, |panel, window, cx| {
+            panel.toggle_staged_for_entry(&third_status_entry, window, cx);
+        });
 
-impl PanelHeader for GitPanel {}
+        panel.update_in(cx, |panel, window, cx| {
+            panel.selected_entry = Some(9);
+            panel.stage_range(&git::StageRange, window, cx);
+        });
 
-pub fn panel_editor_container(_window: &mut Window, cx: &mut App) -> Div {
-    v_flex()
-        .size_full()
-        .gap(px(8.))
-        .p_2()
-        .bg(cx.theme().colors().editor_background)
-}
+        cx.read(|cx| {
+            project
+                .read(cx)
+                .worktrees(cx)
+                .next()
+                .unwrap()
+                .read(cx)
+                .as_local()
+                .unwrap()
+                .scan_complete()
+        })
+        .await;
 
-pub(crate) fn panel_editor_style(monospace: bool, window: &Window, cx: &App) -> EditorStyle {
-    let settings = ThemeSettings::get_global(cx);
+        cx.executor().run_until_parked();
 
-    let font_size = TextSize::Small.rems(cx).to_pixels(window.rem_size());
+        let handle = cx.update_window_entity(&panel, |panel, _, _| {
+            std::mem::replace(&mut panel.update_visible_entries_task, Task::ready(()))
+        });
+        cx.executor().advance_clock(2 * UPDATE_DEBOUNCE);
+        handle.await;
 
-    let (font_family, font_fallbacks, font_features, font_weight, line_height) = if monospace {
-        (
-            settings.buffer_font.family.clone(),
-            settings.buffer_font.fallbacks.clone(),
-            settings.buffer_font.features.clone(),
-            settings.buffer_font.weight,
-            font_size * settings.buffer_line_height.value(),
-        )
-    } else {
-        (
-            settings.ui_font.family.clone(),
-            settings.ui_font.fallbacks.clone(),
-            settings.ui_font.features.clone(),
-            settings.ui_font.weight,
-            window.line_height(),
-        )
-    };
+        let entries = panel.read_with(cx, |panel, _| panel.entries.clone());
+        #[rustfmt::skip]
+        pretty_assertions::assert_matches!(
+            entries.as_slice(),
+            &[
+                Status(GitStatusEntry { status: FileStatus::Untracked, staging: StageStatus::Unstaged, .. }),
+                Status(GitStatusEntry { status: FileStatus::Unmerged(..), staging: StageStatus::Unstaged, .. }),
+                Status(GitStatusEntry { status: FileStatus::Untracked, staging: StageStatus::Unstaged, .. }),
+                Status(GitStatusEntry { status: FileStatus::Tracked(..), staging: StageStatus::Staged, .. }),
+                Status(GitStatusEntry { status: FileStatus::Tracked(..), staging: StageStatus::Staged, .. }),
+                Status(GitStatusEntry { status: FileStatus::Untracked, staging: StageStatus::Unstaged, .. }),
+                Status(GitStatusEntry { status: FileStatus::Tracked(..), staging: StageStatus::Unstaged, .. }),
+            ],
+        );
 
-    EditorStyle {
-        background: cx.theme().colors().editor_background,
-        local_player: cx.theme().players().local(),
-        text: TextStyle {
-            color: cx.theme().colors().text,
-            font_family,
-            font_fallbacks,
-            font_features,
-            font_size: TextSize::Small.rems(cx).into(),
-            font_weight,
-            line_height: line_height.into(),
-            ..Default::default()
-        },
-        syntax: cx.theme().syntax().clone(),
-        ..Default::default()
+        assert_entry_paths(
+            &entries,
+            &[
+                Some("another_new.rs"),
+                Some("conflict.txt"),
+                Some("new_file.txt"),
+                Some("src/lib.rs"),
+                Some("src/main.rs"),
+                Some("src/utils.rs"),
+                Some("tests/test.rs"),
+            ],
+        );
     }
-}
-
-struct GitPanelMessageTooltip {
-    commit_tooltip: Option<Entity<CommitTooltip>>,
-}
 
-impl GitPanelMessageTooltip {
-    fn new(
-        git_panel: Entity<GitPanel>,
-        sha: SharedString,
-        repository: Entity<Repository>,
-        window: &mut Window,
-        cx: &mut App,
-    ) -> Entity<Self> {
-        let remote_url = repository.read(cx).default_remote_url();
-        cx.new(|cx| {
-            cx.spawn_in(window, async move |this, cx| {
-                let (details, workspace) = git_panel.update(cx, |git_panel, cx| {
-                    (
-                        git_panel.load_commit_details(sha.to_string(), cx),
-                        git_panel.workspace.clone(),
-                    )
-                });
-                let details = details.await?;
-                let provider_registry = cx
-                    .update(|_, app| GitHostingProviderRegistry::default_global(app))
-                    .ok();
+    #[gpui::test]
+    async fn test_amend_commit_message_handling(cx: &mut TestAppContext) {
+        init_test(cx);
+        let fs = FakeFs::new(cx.background_executor.clone());
+        fs.insert_tree(
+            "/root",
+            json!({
+                "project": {
+                    ".git": {},
+                    "src": {
+                        "main.rs": "fn main() {}"
+                    }
+                }
+            }),
+        )
+        .await;
 
-                let commit_details = crate::commit_tooltip::CommitDetails {
-                    sha: details.sha.clone(),
-                    author_name: details.author_name.clone(),
-                    author_email: details.author_email.clone(),
-                    commit_time: OffsetDateTime::from_unix_timestamp(details.commit_timestamp)?,
-                    message: Some(ParsedCommitMessage::parse(
-                        details.sha.to_string(),
-                        details.message.to_string(),
-                        remote_url.as_deref(),
-                        provider_registry,
-                    )),
-                };
+        fs.set_status_for_repo(
+            Path::new(path!("/root/project/.git")),
+            &[("src/main.rs", StatusCode::Modified.worktree())],
+        );
 
-                this.update(cx, |this: &mut GitPanelMessageTooltip, cx| {
-                    this.commit_tooltip = Some(cx.new(move |cx| {
-                        CommitTooltip::new(commit_details, repository, workspace, cx)
-                    }));
-                    cx.notify();
-                })
-            })
-            .detach();
+        let project = Project::test(fs.clone(), [Path::new(path!("/root/project"))], cx).await;
+        let window_handle =
+            cx.add_window(|window, cx| MultiWorkspace::test_new(project.clone(), window, cx));
+        let workspace = window_handle
+            .read_with(cx, |mw, _| mw.workspace().clone())
+            .unwrap();
+        let cx = &mut VisualTestContext::from_window(window_handle.into(), cx);
 
-            Self {
-                commit_tooltip: None,
-            }
-        })
-    }
-}
+        let panel = workspace.update_in(cx, GitPanel::new);
 
-impl Render for GitPanelMessageTooltip {
-    fn render(&mut self, _window: &mut Window, _cx: &mut Context<Self>) -> impl IntoElement {
-        if let Some(commit_tooltip) = &self.commit_tooltip {
-            commit_tooltip.clone().into_any_element()
-        } else {
-            gpui::Empty.into_any_element()
-        }
-    }
-}
+        // Test: User has commit message, enables amend (saves message), then disables (restores message)
+        panel.update(cx, |panel, cx| {
+            panel.commit_message_buffer(cx).update(cx, |buffer, cx| {
+                let start = buffer.anchor_before(0);
+                let end = buffer.anchor_after(buffer.len());
+                buffer.edit([(start..end, "Initial commit message")], None, cx);
+            });
 
-#[derive(IntoElement, RegisterComponent)]
-pub struct PanelRepoFooter {
-    active_repository: SharedString,
-    branch: Option<Branch>,
-    head_commit: Option<CommitDetails>,
+            panel.set_amend_pending(true, cx);
+            assert!(panel.original_commit_message.is_some());
 
-    // Getting a GitPanel in previews will be difficult.
-    //
-    // For now just take an option here, and we won't bind handlers to buttons in previews.
-    git_panel: Option<Entity<GitPanel>>,
-}
+            panel.set_amend_pending(false, cx);
+            let current_message = panel.commit_message_buffer(cx).read(cx).text();
+            assert_eq!(current_message, "Initial commit message");
+            assert!(panel.original_commit_message.is_none());
+        });
 
-impl PanelRepoFooter {
-    pub fn new(
-        active_repository: SharedString,
-        branch: Option<Branch>,
-        head_commit: Option<CommitDetails>,
-        git_panel: Option<Entity<GitPanel>>,
-    ) -> Self {
-        Self {
-            active_repository,
-            branch,
-            head_commit,
-            git_panel,
-        }
-    }
+        // Test: User has empty commit message, enables amend, then disables (clears message)
+        panel.update(cx, |panel, cx| {
+            panel.commit_message_buffer(cx).update(cx, |buffer, cx| {
+                let start = buffer.anchor_before(0);
+                let end = buffer.anchor_after(buffer.len());
+                buffer.edit([(start..end, "")], None, cx);
+            });
 
-    pub fn new_preview(active_repository: SharedString, branch: Option<Branch>) -> Self {
-        Self {
-            active_repository,
-            branch,
-            head_commit: None,
-            git_panel: None,
-        }
+            panel.set_amend_pending(true, cx);
+            assert!(panel.original_commit_message.is_none());
+
+            panel.commit_message_buffer(cx).update(cx, |buffer, cx| {
+                let start = buffer.anchor_before(0);
+                let end = buffer.anchor_after(buffer.len());
+                buffer.edit([(start..end, "Previous commit message")], None, cx);
+            });
+
+            panel.set_amend_pending(false, cx);
+            let current_message = panel.commit_message_buffer(cx).read(cx).text();
+            assert_eq!(current_message, "");
+        });
     }
-}
 
-impl RenderOnce for PanelRepoFooter {
-    fn render(self, _window: &mut Window, cx: &mut App) -> impl IntoElement {
-        let project = self
-            .git_panel
-            .as_ref()
-            .map(|panel| panel.read(cx).project.clone());
+    #[gpui::test]
+    async fn test_amend(cx: &mut TestAppContext) {
+        init_test(cx);
+        let fs = FakeFs::new(cx.background_executor.clone());
+        fs.insert_tree(
+            "/root",
+            json!({
+                "project": {
+                    ".git": {},
+                    "src": {
+                        "main.rs": "fn main() {}"
+                    }
+                }
+            }),
+        )
+        .await;
 
-        let (workspace, repo) = self
-            .git_panel
-            .as_ref()
-            .map(|panel| {
-                let panel = panel.read(cx);
-                (panel.workspace.clone(), panel.active_repository.clone())
-            })
-            .unzip();
+        fs.set_status_for_repo(
+            Path::new(path!("/root/project/.git")),
+            &[("src/main.rs", StatusCode::Modified.worktree())],
+        );
 
-        let single_repo = project
-            .as_ref()
-            .map(|project| project.read(cx).git_store().read(cx).repositories().len() == 1)
-            .unwrap_or(true);
+        let project = Project::test(fs.clone(), [Path::new(path!("/root/project"))], cx).await;
+        let window_handle =
+            cx.add_window(|window, cx| MultiWorkspace::test_new(project.clone(), window, cx));
+        let workspace = window_handle
+            .read_with(cx, |mw, _| mw.workspace().clone())
+            .unwrap();
+        let cx = &mut VisualTestContext::from_window(window_handle.into(), cx);
 
-        const MAX_BRANCH_LEN: usize = 16;
-        const MAX_REPO_LEN: usize = 16;
-        const LABEL_CHARACTER_BUDGET: usize = MAX_BRANCH_LEN + MAX_REPO_LEN;
-        const MAX_SHORT_SHA_LEN: usize = 8;
-        let branch_name = self
-            .branch
-            .as_ref()
-            .map(|branch| branch.name().to_owned())
-            .or_else(|| {
-                self.head_commit.as_ref().map(|commit| {
-                    commit
-                        .sha
-                        .chars()
-                        .take(MAX_SHORT_SHA_LEN)
-                        .collect::<String>()
-                })
-            })
-            .unwrap_or_else(|| " (no branch)".to_owned());
-        let show_separator = self.branch.is_some() || self.head_commit.is_some();
+        // Wait for the project scanning to finish so that `head_commit(cx)` is
+        // actually set, otherwise no head commit would be available from which
+        // to fetch the latest commit message from.
+        cx.executor().run_until_parked();
 
-        let active_repo_name = self.active_repository.clone();
+        let panel = workspace.update_in(cx, GitPanel::new);
+        panel.read_with(cx, |panel, cx| {
+            assert!(panel.active_repository.is_some());
+            assert!(panel.head_commit(cx).is_some());
+        });
 
-        let branch_actual_len = branch_name.len();
-        let repo_actual_len = active_repo_name.len();
+        panel.update_in(cx, |panel, window, cx| {
+            // Update the commit editor's message to ensure that its contents
+            // are later restored, after amending is finished.
+            panel.commit_message_buffer(cx).update(cx, |buffer, cx| {
+                buffer.set_text("refactor: update main.rs", cx);
+            });
 
-        // ideally, show the whole branch and repo names but
-        // when we can't, use a budget to allocate space between the two
-        let (repo_display_len, branch_display_len) =
-            if branch_actual_len + repo_actual_len <= LABEL_CHARACTER_BUDGET {
-                (repo_actual_len, branch_actual_len)
-            } else if branch_actual_len <= MAX_BRANCH_LEN {
-                let repo_space = (LABEL_CHARACTER_BUDGET - branch_actual_len).min(MAX_REPO_LEN);
-                (repo_space, branch_actual_len)
-            } else if repo_actual_len <= MAX_REPO_LEN {
-                let branch_space = (LABEL_CHARACTER_BUDGET - repo_actual_len).min(MAX_BRANCH_LEN);
-                (repo_actual_len, branch_space)
-            } else {
-                (MAX_REPO_LEN, MAX_BRANCH_LEN)
-            };
+            // Start amending the previous commit.
+            panel.focus_editor(&Default::default(), window, cx);
+            panel.on_amend(&Amend, window, cx);
+        });
 
-        let truncated_repo_name = if repo_actual_len <= repo_display_len {
-            active_repo_name.to_string()
-        } else {
-            util::truncate_and_trailoff(active_repo_name.trim_ascii(), repo_display_len)
-        };
+        // Since `GitPanel.amend` attempts to fetch the latest commit message in
+        // a background task, we need to wait for it to complete before being
+        // able to assert that the commit message editor's state has been
+        // updated.
+        cx.run_until_parked();
 
-        let truncated_branch_name = if branch_actual_len <= branch_display_len {
-            branch_name
-        } else {
-            util::truncate_and_trailoff(branch_name.trim_ascii(), branch_display_len)
-        };
+        panel.update_in(cx, |panel, window, cx| {
+            assert_eq!(
+                panel.commit_message_buffer(cx).read(cx).text(),
+                "initial commit"
+            );
+            assert_eq!(
+                panel.original_commit_message,
+                Some("refactor: update main.rs".to_string())
+            );
 
-        let repo_selector_trigger = Button::new("repo-selector", truncated_repo_name)
-            .size(ButtonSize::None)
-            .label_size(LabelSize::Small);
+            // Finish amending the previous commit.
+            panel.focus_editor(&Default::default(), window, cx);
+            panel.on_amend(&Amend, window, cx);
+        });
 
-        let repo_selector = PopoverMenu::new("repository-switcher")
-            .menu({
-                let project = project;
-                move |window, cx| {
-                    let project = project.clone()?;
-                    Some(cx.new(|cx| RepositorySelector::new(project, rems(20.), window, cx)))
-                }
-            })
-            .trigger_with_tooltip(
-                repo_selector_trigger
-                    .when(single_repo, |this| this.disabled(true).color(Color::Muted))
-                    .truncate(true),
-                move |_, cx| {
-                    if single_repo {
-                        cx.new(|_| Empty).into()
-                    } else {
-                        Tooltip::simple("Switch Active Repository", cx)
-                    }
-                },
-            )
-            .anchor(Corner::BottomLeft)
-            .offset(gpui::Point {
-                x: px(0.0),
-                y: px(-2.0),
-            })
-            .into_any_element();
+        // Since the actual commit logic is run in a background task, we need to
+        // await its completion to actually ensure that the commit message
+        // editor's contents are set to the original message and haven't been
+        // cleared.
+        cx.run_until_parked();
 
-        let branch_selector_button = Button::new("branch-selector", truncated_branch_name)
-            .size(ButtonSize::None)
-            .label_size(LabelSize::Small)
-            .truncate(true)
-            .on_click(|_, window, cx| {
-                window.dispatch_action(zed_actions::git::Switch.boxed_clone(), cx);
-            });
+        panel.update_in(cx, |panel, _window, cx| {
+            // After amending, the commit editor's message should be restored to
+            // the original message.
+            assert_eq!(
+                panel.commit_message_buffer(cx).read(cx).text(),
+                "refactor: update main.rs"
+            );
+            assert!(panel.original_commit_message.is_none());
+        });
+    }
 
-        let branch_selector = PopoverMenu::new("popover-button")
-            .menu(move |window, cx| {
-                let workspace = workspace.clone()?;
-                let repo = repo.clone().flatten();
-                Some(branch_picker::popover(workspace, false, repo, window, cx))
-            })
-            .trigger_with_tooltip(
-                branch_selector_button,
-                Tooltip::for_action_title("Switch Branch", &zed_actions::git::Switch),
-            )
-            .anchor(Corner::BottomLeft)
-            .offset(gpui::Point {
-                x: px(0.0),
-                y: px(-2.0),
-            });
+    #[test]
+    fn test_co_author_trailers() {
+        let ada = ("Ada Lovelace".to_string(), "ada@example.com".to_string());
+        let grace = ("Grace Hopper".to_string(), "Grace@example.com".to_string());
 
-        h_flex()
-            .h(px(36.))
-            .w_full()
-            .px_2()
-            .justify_between()
-            .gap_1()
-            .child(
-                h_flex()
-                    .flex_1()
-                    .overflow_hidden()
-                    .gap_px()
-                    .child(
-                        Icon::new(IconName::GitBranchAlt)
-                            .size(IconSize::Small)
-                            .color(if single_repo {
-                                Color::Disabled
-                            } else {
-                                Color::Muted
-                            }),
-                    )
-                    .child(repo_selector)
-                    .when(show_separator, |this| {
-                        this.child(
-                            div()
-                                .text_sm()
-                                .text_color(cx.theme().colors().icon_muted.opacity(0.5))
-                                .child("/"),
-                        )
-                    })
-                    .child(branch_selector),
-            )
-            .children(if let Some(git_panel) = self.git_panel {
-                git_panel.update(cx, |git_panel, cx| git_panel.render_remote_button(cx))
-            } else {
-                None
-            })
+        assert_eq!(
+            co_author_trailers("Fix startup", [ada.clone()]).as_deref(),
+            Some("\n\nCo-authored-by: Ada Lovelace <ada@example.com>\n")
+        );
+        assert_eq!(
+            co_author_trailers(
+                "Fix startup\n\nCo-authored-by: Ada Lovelace <ada@example.com>",
+                [ada.clone(), grace]
+            )
+            .as_deref(),
+            Some("\nCo-authored-by: Grace Hopper <Grace@example.com>\n")
+        );
+        assert_eq!(
+            co_author_trailers(
+                "Fix startup\n\nco-authored-by: Ada <ADA@example.com>\n",
+                [ada]
+            ),
+            None
+        );
     }
-}
 
-impl Component for PanelRepoFooter {
-    fn scope() -> ComponentScope {
-        ComponentScope::VersionControl
-    }
+    #[test]
+    fn test_force_push_prompt_detail() {
+        let commit = |sha: &str, subject: &str| CommitSummary {
+            sha: sha.to_string().into(),
+            subject: subject.to_string().into(),
+            commit_timestamp: 0,
+            author_name: "Ada Lovelace".into(),
+            has_parent: true,
+        };
 
-    fn preview(_window: &mut Window, _cx: &mut App) -> Option<AnyElement> {
-        let unknown_upstream = None;
-        let no_remote_upstream = Some(UpstreamTracking::Gone);
-        let ahead_of_upstream = Some(
-            UpstreamTrackingStatus {
-                ahead: 2,
-                behind: 0,
-            }
-            .into(),
+        assert_eq!(
+            force_push_prompt_detail("origin/main", None),
+            "Any commits on origin/main that aren't in your branch will be lost."
         );
-        let behind_upstream = Some(
-            UpstreamTrackingStatus {
-                ahead: 0,
-                behind: 2,
-            }
-            .into(),
+        assert_eq!(
+            force_push_prompt_detail("origin/main", Some(&[])),
+            "No commits on origin/main will be lost."
         );
-        let ahead_and_behind_upstream = Some(
-            UpstreamTrackingStatus {
-                ahead: 3,
-                behind: 1,
-            }
-            .into(),
+        assert_eq!(
+            force_push_prompt_detail(
+                "origin/main",
+                Some(&[
+                    commit("0123456789abcdef", "Fix the build"),
+                    commit("fedcba9876543210", "Add a feature"),
+                ])
+            ),
+            "2 commits on origin/main will be lost:\n\n0123456 Fix the build\nfedcba9 Add a feature"
         );
 
-        let not_ahead_or_behind_upstream = Some(
-            UpstreamTrackingStatus {
-                ahead: 0,
-                behind: 0,
-            }
-            .into(),
+        let many_commits = (0..12)
+            .map(|index| commit(&format!("{index:040}"), "Commit"))
+            .collect::<Vec<_>>();
+        assert!(
+            force_push_prompt_detail("origin/main", Some(&many_commits))
+                .ends_with("\n0000000 Commit\n…and 2 more")
         );
+    }
 
-        fn branch(upstream: Option<UpstreamTracking>) -> Branch {
-            Branch {
-                is_head: true,
-                ref_name: "some-branch".into(),
-                upstream: upstream.map(|tracking| Upstream {
-                    ref_name: "origin/some-branch".into(),
-                    tracking,
-                }),
-                most_recent_commit: Some(CommitSummary {
-                    sha: "abc123".into(),
-                    subject: "Modify stuff".into(),
-                    commit_timestamp: 1710932954,
-                    author_name: "John Doe".into(),
-                    has_parent: true,
-                }),
-            }
-        }
-
-        fn custom(branch_name: &str, upstream: Option<UpstreamTracking>) -> Branch {
-            Branch {
-                is_head: true,
-                ref_name: branch_name.to_string().into(),
-                upstream: upstream.map(|tracking| Upstream {
-                    ref_name: format!("zed/{}", branch_name).into(),
-                    tracking,
-                }),
-                most_recent_commit: Some(CommitSummary {
-                    sha: "abc123".into(),
-                    subject: "Modify stuff".into(),
-                    commit_timestamp: 1710932954,
-                    author_name: "John Doe".into(),
-                    has_parent: true,
-                }),
-            }
-        }
-
-        fn active_repository(id: usize) -> SharedString {
-            format!("repo-{}", id).into()
-        }
-
-        let example_width = px(340.);
-        Some(
-            v_flex()
-                .gap_6()
-                .w_full()
-                .flex_none()
-                .children(vec![
-                    example_group_with_title(
-                        "Action Button States",
-                        vec![
-                            single_example(
-                                "No Branch",
-                                div()
-                                    .w(example_width)
-                                    .overflow_hidden()
-                                    .child(PanelRepoFooter::new_preview(active_repository(1), None))
-                                    .into_any_element(),
-                            ),
-                            single_example(
-                                "Remote status unknown",
-                                div()
-                                    .w(example_width)
-                                    .overflow_hidden()
-                                    .child(PanelRepoFooter::new_preview(
-                                        active_repository(2),
-                                        Some(branch(unknown_upstream)),
-                                    ))
-                                    .into_any_element(),
-                            ),
-                            single_example(
-                                "No Remote Upstream",
-                                div()
-                                    .w(example_width)
-                                    .overflow_hidden()
-                                    .child(PanelRepoFooter::new_preview(
-                                        active_repository(3),
-                                        Some(branch(no_remote_upstream)),
-                                    ))
-                                    .into_any_element(),
-                            ),
-                            single_example(
-                                "Not Ahead or Behind",
-                                div()
-                                    .w(example_width)
-                                    .overflow_hidden()
-                                    .child(PanelRepoFooter::new_preview(
-                                        active_repository(4),
-                                        Some(branch(not_ahead_or_behind_upstream)),
-                                    ))
-                                    .into_any_element(),
-                            ),
-                            single_example(
-                                "Behind remote",
-                                div()
-                                    .w(example_width)
-                                    .overflow_hidden()
-                                    .child(PanelRepoFooter::new_preview(
-                                        active_repository(5),
-                                        Some(branch(behind_upstream)),
-                                    ))
-                                    .into_any_element(),
-                            ),
-                            single_example(
-                                "Ahead of remote",
-                                div()
-                                    .w(example_width)
-                                    .overflow_hidden()
-                                    .child(PanelRepoFooter::new_preview(
-                                        active_repository(6),
-                                        Some(branch(ahead_of_upstream)),
-                                    ))
-                                    .into_any_element(),
-                            ),
-                            single_example(
-                                "Ahead and behind remote",
-                                div()
-                                    .w(example_width)
-                                    .overflow_hidden()
-                                    .child(PanelRepoFooter::new_preview(
-                                        active_repository(7),
-                                        Some(branch(ahead_and_behind_upstream)),
-                                    ))
-                                    .into_any_element(),
-                            ),
-                        ],
-                    )
-                    .grow()
-                    .vertical(),
-                ])
-                .children(vec![
-                    example_group_with_title(
-                        "Labels",
-                        vec![
-                            single_example(
-                                "Short Branch & Repo",
-                                div()
-                                    .w(example_width)
-                                    .overflow_hidden()
-                                    .child(PanelRepoFooter::new_preview(
-                                        SharedString::from("zed"),
-                                        Some(custom("main", behind_upstream)),
-                                    ))
-                                    .into_any_element(),
-                            ),
-                            single_example(
-                                "Long Branch",
-                                div()
-                                    .w(example_width)
-                                    .overflow_hidden()
-                                    .child(PanelRepoFooter::new_preview(
-                                        SharedString::from("zed"),
-                                        Some(custom(
-                                            "redesign-and-update-git-ui-list-entry-style",
-                                            behind_upstream,
-                                        )),
-                                    ))
-                                    .into_any_element(),
-                            ),
-                            single_example(
-                                "Long Repo",
-                                div()
-                                    .w(example_width)
-                                    .overflow_hidden()
-                                    .child(PanelRepoFooter::new_preview(
-                                        SharedString::from("zed-industries-community-examples"),
-                                        Some(custom("gpui", ahead_of_upstream)),
-                                    ))
-                                    .into_any_element(),
-                            ),
-                            single_example(
-                                "Long Repo & Branch",
-                                div()
-                                    .w(example_width)
-                                    .overflow_hidden()
-                                    .child(PanelRepoFooter::new_preview(
-                                        SharedString::from("zed-industries-community-examples"),
-                                        Some(custom(
-                                            "redesign-and-update-git-ui-list-entry-style",
-                                            behind_upstream,
-                                        )),
-                                    ))
-                                    .into_any_element(),
-                            ),
-                            single_example(
-                                "Uppercase Repo",
-                                div()
-                                    .w(example_width)
-                                    .overflow_hidden()
-                                    .child(PanelRepoFooter::new_preview(
-                                        SharedString::from("LICENSES"),
-                                        Some(custom("main", ahead_of_upstream)),
-                                    ))
-                                    .into_any_element(),
-                            ),
-                            single_example(
-                                "Uppercase Branch",
-                                div()
-                                    .w(example_width)
-                                    .overflow_hidden()
-                                    .child(PanelRepoFooter::new_preview(
-                                        SharedString::from("zed"),
-                                        Some(custom("update-README", behind_upstream)),
-                                    ))
-                                    .into_any_element(),
-                            ),
-                        ],
-                    )
-                    .grow()
-                    .vertical(),
-                ])
-                .into_any_element(),
+    #[gpui::test]
+    async fn test_commit_template(cx: &mut TestAppContext) {
+        init_test(cx);
+        let fs = FakeFs::new(cx.background_executor.clone());
+        fs.insert_tree(
+            "/root",
+            json!({
+                "project": {
+                    ".git": {},
+                    "src": {
+                        "main.rs": "fn main() {}"
+                    }
+                }
+            }),
         )
-    }
-}
+        .await;
 
-fn open_output(
-    operation: impl Into<SharedString>,
-    workspace: &mut Workspace,
-    output: &str,
-    window: &mut Window,
-    cx: &mut Context<Workspace>,
-) {
-    let operation = operation.into();
-    let buffer = cx.new(|cx| Buffer::local(output, cx));
-    buffer.update(cx, |buffer, cx| {
-        buffer.set_capability(language::Capability::ReadOnly, cx);
-    });
-    let editor = cx.new(|cx| {
-        let mut editor = Editor::for_buffer(buffer, None, window, cx);
-        editor.buffer().update(cx, |buffer, cx| {
-            buffer.set_title(format!("Output from git {operation}"), cx);
+        fs.set_status_for_repo(
+            Path::new(path!("/root/project/.git")),
+            &[("src/main.rs", StatusCode::Modified.worktree())],
+        );
+        let template = "\n# Explain why this change is needed.\n";
+        fs.with_git_state(Path::new(path!("/root/project/.git")), true, |state| {
+            state.commit_template = Some(template.to_string());
+        })
+        .unwrap();
+
+        let project = Project::test(fs.clone(), [Path::new(path!("/root/project"))], cx).await;
+        let window_handle =
+            cx.add_window(|window, cx| MultiWorkspace::test_new(project.clone(), window, cx));
+        let workspace = window_handle
+            .read_with(cx, |mw, _| mw.workspace().clone())
+            .unwrap();
+        let cx = &mut VisualTestContext::from_window(window_handle.into(), cx);
+        cx.executor().run_until_parked();
+
+        let panel = workspace.update_in(cx, GitPanel::new);
+        cx.executor().advance_clock(2 * UPDATE_DEBOUNCE);
+        cx.run_until_parked();
+
+        panel.update_in(cx, |panel, window, cx| {
+            assert_eq!(panel.commit_message_buffer(cx).read(cx).text(), template);
+            assert_eq!(
+                panel.custom_or_suggested_commit_message(window, cx),
+                Some("Update main.rs".to_string())
+            );
+
+            panel.commit_message_buffer(cx).update(cx, |buffer, cx| {
+                buffer.edit([(0..0, "Fix startup")], None, cx);
+            });
+            assert!(
+                panel
+                    .custom_or_suggested_commit_message(window, cx)
+                    .is_some_and(|message| message.starts_with("Fix startup"))
+            );
+
+            panel
+                .commit_editor
+                .update(cx, |editor, cx| editor.clear(window, cx));
+            panel.apply_commit_template(window, cx);
+            assert_eq!(panel.commit_message_buffer(cx).read(cx).text(), template);
         });
-        editor.set_read_only(true);
-        editor
-    });
+    }
 
-    workspace.add_item_to_center(Box::new(editor), window, cx);
-}
+    #[gpui::test]
+    async fn test_configured_pull_strategy(cx: &mut TestAppContext) {
+        init_test(cx);
+        let fs = FakeFs::new(cx.background_executor.clone());
+        fs.insert_tree(
+            "/root",
+            json!({
+                "project": {
+                    ".git": {},
+                    ".zed": {
+                        "settings.json": r#"{ "git": { "pull_strategy": "fast_forward_only" } }"#
+                    },
+                    "src": {
+                        "main.rs": "fn main() {}"
+                    }
+                },
+                "other": {
+                    ".git": {},
+                    "lib.rs": ""
+                }
+            }),
+        )
+        .await;
 
-pub(crate) fn show_error_toast(
-    workspace: Entity<Workspace>,
-    action: impl Into<SharedString>,
-    e: anyhow::Error,
-    cx: &mut App,
-) {
-    let action = action.into();
-    let message = format_git_error_toast_message(&e);
-    if message
-        .matches(git::repository::REMOTE_CANCELLED_BY_USER)
-        .next()
-        .is_some()
-    { // Hide the cancelled by user message
-    } else {
-        workspace.update(cx, |workspace, cx| {
-            let workspace_weak = cx.weak_entity();
-            let toast = StatusToast::new(format!("git {} failed", action), cx, |this, _cx| {
-                this.icon(ToastIcon::new(IconName::XCircle).color(Color::Error))
-                    .action("View Log", move |window, cx| {
-                        let message = message.clone();
-                        let action = action.clone();
-                        workspace_weak
-                            .update(cx, move |workspace, cx| {
-                                open_output(action, workspace, &message, window, cx)
-                            })
-                            .ok();
+        let project = Project::test(
+            fs.clone(),
+            [
+                Path::new(path!("/root/project")),
+                Path::new(path!("/root/other")),
+            ],
+            cx,
+        )
+        .await;
+        let window_handle =
+            cx.add_window(|window, cx| MultiWorkspace::test_new(project.clone(), window, cx));
+        let workspace = window_handle
+            .read_with(cx, |mw, _| mw.workspace().clone())
+            .unwrap();
+        let cx = &mut VisualTestContext::from_window(window_handle.into(), cx);
+        cx.executor().run_until_parked();
+
+        let panel = workspace.update_in(cx, GitPanel::new);
+        cx.executor().advance_clock(2 * UPDATE_DEBOUNCE);
+        cx.run_until_parked();
+
+        let repository = |work_directory: &'static str, cx: &mut VisualTestContext| {
+            project.read_with(cx, |project, cx| {
+                project
+                    .repositories(cx)
+                    .values()
+                    .find(|repository| {
+                        repository.read(cx).work_directory_abs_path.as_ref()
+                            == Path::new(work_directory)
                     })
+                    .cloned()
+                    .unwrap()
+            })
+        };
+        let project_repository = repository(path!("/root/project"), cx);
+        let other_repository = repository(path!("/root/other"), cx);
+
+        panel.read_with(cx, |panel, cx| {
+            assert_eq!(
+                panel.configured_pull_strategy(&project_repository, cx),
+                Some(PullStrategy::FastForwardOnly)
+            );
+            assert_eq!(panel.configured_pull_strategy(&other_repository, cx), None);
+        });
+
+        cx.update(|_window, cx| {
+            SettingsStore::update_global(cx, |store, cx| {
+                store.update_user_settings(cx, |settings| {
+                    settings.git.get_or_insert_default().pull_strategy =
+                        Some(settings::PullStrategy::Rebase);
+                })
             });
-            workspace.toggle_status_toast(toast, cx)
+        });
+        panel.read_with(cx, |panel, cx| {
+            assert_eq!(
+                panel.configured_pull_strategy(&project_repository, cx),
+                Some(PullStrategy::FastForwardOnly)
+            );
+            assert_eq!(
+                panel.configured_pull_strategy(&other_repository, cx),
+                Some(PullStrategy::Rebase)
+            );
         });
     }
-}
 
-fn rpc_error_raw_message_from_chain(error: &anyhow::Error) -> Option<&str> {
-    error
-        .chain()
-        .find_map(|cause| cause.downcast_ref::<RpcError>().map(RpcError::raw_message))
-}
+    #[gpui::test]
+    async fn test_sync_asks_how_to_integrate_and_whether_to_force_push(cx: &mut TestAppContext) {
+        init_test(cx);
+        let fs = FakeFs::new(cx.background_executor.clone());
+        fs.insert_tree(
+            "/root",
+            json!({
+                "project": {
+                    ".git": {},
+                    "src": {
+                        "main.rs": "fn main() {}"
+                    }
+                }
+            }),
+        )
+        .await;
+        let dot_git = Path::new(path!("/root/project/.git"));
+        fs.with_git_state(dot_git, true, |state| {
+            state.branches.insert("main".to_string());
+            state.current_branch_name = Some("main".to_string());
+            state
+                .branch_upstreams
+                .insert("main".to_string(), "origin/main".to_string());
+            state.remotes.insert(
+                "origin".to_string(),
+                "https://github.com/me/project.git".to_string(),
+            );
+            state.simulated_pull_errors.push(format!(
+                "{PULL_NOT_FAST_FORWARD}\nfatal: Not possible to fast-forward"
+            ));
+            state.simulated_push_errors.push(format!(
+                "{PUSH_REJECTED}\n ! [rejected]        main -> main (fetch first)"
+            ));
+        })
+        .unwrap();
+
+        let project = Project::test(fs.clone(), [Path::new(path!("/root/project"))], cx).await;
+        let window_handle =
+            cx.add_window(|window, cx| MultiWorkspace::test_new(project.clone(), window, cx));
+        let workspace = window_handle
+            .read_with(cx, |mw, _| mw.workspace().clone())
+            .unwrap();
+        let cx = &mut VisualTestContext::from_window(window_handle.into(), cx);
+        cx.executor().run_until_parked();
+
+        let panel = workspace.update_in(cx, GitPanel::new);
+        cx.executor().advance_clock(2 * UPDATE_DEBOUNCE);
+        cx.run_until_parked();
+
+        panel.update_in(cx, |panel, window, cx| panel.sync(window, cx));
+        cx.run_until_parked();
+        cx.simulate_prompt_answer("Rebase Instead");
+        cx.run_until_parked();
+        cx.simulate_prompt_answer("Force Push");
+        cx.run_until_parked();
+
+        let (fetches, pulls, pushes) = fs
+            .with_git_state(dot_git, false, |state| {
+                (
+                    state.fetches.clone(),
+                    state.pulls.clone(),
+                    state.pushes.clone(),
+                )
+            })
+            .unwrap();
+        assert_eq!(
+            fetches,
+            [FetchOptions::Remote(Remote {
+                name: "origin".into()
+            })]
+        );
+        assert_eq!(
+            pulls,
+            [
+                ("origin".to_string(), None),
+                ("origin".to_string(), Some(PullStrategy::Rebase)),
+            ]
+        );
+        assert_eq!(
+            pushes,
+            [
+                ("main".to_string(), "origin".to_string(), None),
+                (
+                    "main".to_string(),
+                    "origin".to_string(),
+                    Some(PushOptions::Force)
+                ),
+            ]
+        );
+    }
+
+    #[gpui::test]
+    async fn test_checkout_pull_request(cx: &mut TestAppContext) {
+        init_test(cx);
+        let fs = FakeFs::new(cx.background_executor.clone());
+        fs.insert_tree(
+            "/root",
+            json!({
+                "project": {
+                    ".git": {},
+                    "src": {
+                        "main.rs": "fn main() {}"
+                    }
+                }
+            }),
+        )
+        .await;
+        let dot_git = Path::new(path!("/root/project/.git"));
+        fs.with_git_state(dot_git, true, |state| {
+            state.branches.insert("main".to_string());
+            state.current_branch_name = Some("main".to_string());
+        })
+        .unwrap();
+
+        let project = Project::test(fs.clone(), [Path::new(path!("/root/project"))], cx).await;
+        let window_handle =
+            cx.add_window(|window, cx| MultiWorkspace::test_new(project.clone(), window, cx));
+        let workspace = window_handle
+            .read_with(cx, |mw, _| mw.workspace().clone())
+            .unwrap();
+        let cx = &mut VisualTestContext::from_window(window_handle.into(), cx);
+        cx.executor().run_until_parked();
+
+        let panel = workspace.update_in(cx, GitPanel::new);
+        cx.executor().advance_clock(2 * UPDATE_DEBOUNCE);
+        cx.run_until_parked();
+
+        panel.update_in(cx, |panel, window, cx| {
+            panel.checkout_pull_request(
+                "origin".to_string(),
+                "refs/pull/42/head".to_string(),
+                42,
+                window,
+                cx,
+            )
+        });
+        cx.run_until_parked();
+
+        let (branch_fetches, current_branch) = fs
+            .with_git_state(dot_git, false, |state| {
+                (
+                    state.branch_fetches.clone(),
+                    state.current_branch_name.clone(),
+                )
+            })
+            .unwrap();
+        assert_eq!(
+            branch_fetches,
+            [(
+                "origin".to_string(),
+                "refs/pull/42/head".to_string(),
+                "pr/42".to_string()
+            )]
+        );
+        assert_eq!(current_branch.as_deref(), Some("pr/42"));
+    }
+
+    #[gpui::test]
+    async fn test_lfs_entries(cx: &mut TestAppContext) {
+        init_test(cx);
+        let fs = FakeFs::new(cx.background_executor.clone());
+        fs.insert_tree(
+            "/root",
+            json!({
+                "project": {
+                    ".git": {},
+                    "assets": {
+                        "logo.png": "version https://git-lfs.github.com/spec/v1\n"
+                    },
+                    "src": {
+                        "main.rs": "fn main() {}"
+                    }
+                }
+            }),
+        )
+        .await;
+        let dot_git = Path::new(path!("/root/project/.git"));
+        fs.set_status_for_repo(
+            dot_git,
+            &[
+                ("assets/logo.png", StatusCode::Modified.worktree()),
+                ("src/main.rs", StatusCode::Modified.worktree()),
+            ],
+        );
+        fs.with_git_state(dot_git, false, |state| {
+            state.lfs_paths.insert(repo_path("assets/logo.png"));
+        })
+        .unwrap();
+
+        let project = Project::test(fs.clone(), [Path::new(path!("/root/project"))], cx).await;
+        let window_handle =
+            cx.add_window(|window, cx| MultiWorkspace::test_new(project.clone(), window, cx));
+        let workspace = window_handle
+            .read_with(cx, |mw, _| mw.workspace().clone())
+            .unwrap();
+        let cx = &mut VisualTestContext::from_window(window_handle.into(), cx);
+        cx.executor().run_until_parked();
 
-fn format_git_error_toast_message(error: &anyhow::Error) -> String {
-    if let Some(message) = rpc_error_raw_message_from_chain(error) {
-        message.trim().to_string()
-    } else {
-        error.to_string().trim().to_string()
+        let panel = workspace.update_in(cx, GitPanel::new);
+        cx.executor().advance_clock(2 * UPDATE_DEBOUNCE);
+        cx.run_until_parked();
+
+        panel.update_in(cx, |panel, window, cx| {
+            assert_eq!(
+                panel.lfs_paths,
+                std::collections::HashSet::from_iter([repo_path("assets/logo.png")])
+            );
+            panel.selected_entry = panel.entry_by_path(&repo_path("assets/logo.png"));
+            panel.fetch_lfs_object(&git::FetchLfsObject, window, cx);
+        });
+        cx.run_until_parked();
+
+        let lfs_fetches = fs
+            .with_git_state(dot_git, false, |state| state.lfs_fetches.clone())
+            .unwrap();
+        assert_eq!(lfs_fetches, [vec![repo_path("assets/logo.png")]]);
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use git::{
-        repository::repo_path,
-        status::{StatusCode, UnmergedStatus, UnmergedStatusCode},
-    };
-    use gpui::{TestAppContext, UpdateGlobal, VisualTestContext, px};
-    use indoc::indoc;
-    use project::FakeFs;
-    use serde_json::json;
-    use settings::SettingsStore;
-    use theme::LoadThemes;
-    use util::path;
-    use util::rel_path::rel_path;
+    #[gpui::test]
+    async fn test_init_submodules(cx: &mut TestAppContext) {
+        init_test(cx);
+        let fs = FakeFs::new(cx.background_executor.clone());
+        fs.insert_tree(
+            "/root",
+            json!({
+                "project": {
+                    ".git": {},
+                    ".gitmodules": "",
+                    "src": {
+                        "main.rs": "fn main() {}"
+                    }
+                }
+            }),
+        )
+        .await;
+        let dot_git = Path::new(path!("/root/project/.git"));
+        fs.set_status_for_repo(dot_git, &[("src/main.rs", StatusCode::Modified.worktree())]);
+        let vendored = Submodule {
+            path: repo_path("vendor/lib"),
+            sha: "3f786850e387550fdab836ed7e6dc881de23001b".into(),
+            state: SubmoduleState::UpToDate,
+        };
+        let docs = Submodule {
+            path: repo_path("third_party/docs"),
+            sha: "89e6c98d92887913cadf06b2adb97f26cde4849b".into(),
+            state: SubmoduleState::Uninitialized,
+        };
+        fs.with_git_state(dot_git, false, |state| {
+            state.submodules = vec![vendored.clone(), docs.clone()];
+        })
+        .unwrap();
 
-    use workspace::MultiWorkspace;
+        let project = Project::test(fs.clone(), [Path::new(path!("/root/project"))], cx).await;
+        let window_handle =
+            cx.add_window(|window, cx| MultiWorkspace::test_new(project.clone(), window, cx));
+        let workspace = window_handle
+            .read_with(cx, |mw, _| mw.workspace().clone())
+            .unwrap();
+        let cx = &mut VisualTestContext::from_window(window_handle.into(), cx);
+        cx.executor().run_until_parked();
 
-    use super::*;
+        let panel = workspace.update_in(cx, GitPanel::new);
+        cx.executor().advance_clock(2 * UPDATE_DEBOUNCE);
+        cx.run_until_parked();
 
-    fn init_test(cx: &mut gpui::TestAppContext) {
-        zlog::init_test();
+        panel.update_in(cx, |panel, window, cx| {
+            assert_eq!(panel.submodules, [vendored.clone(), docs.clone()]);
+            assert_eq!(
+                submodule_status(&docs, None),
+                ("Not initialized".to_string(), Color::Muted)
+            );
+            panel.init_submodules(window, cx);
+        });
+        cx.run_until_parked();
 
-        cx.update(|cx| {
-            let settings_store = SettingsStore::test(cx);
-            cx.set_global(settings_store);
-            theme::init(LoadThemes::JustBase, cx);
-            editor::init(cx);
-            crate::init(cx);
+        let updates = fs
+            .with_git_state(dot_git, false, |state| state.submodule_updates.clone())
+            .unwrap();
+        assert_eq!(updates, [(vec![repo_path("third_party/docs")], true)]);
+        panel.update(cx, |panel, _| {
+            assert!(
+                panel
+                    .submodules
+                    .iter()
+                    .all(|submodule| submodule.state == SubmoduleState::UpToDate)
+            );
         });
     }
 
-    #[test]
-    fn test_format_git_error_toast_message_prefers_raw_rpc_message() {
-        let rpc_error = RpcError::from_proto(
-            &proto::Error {
-                message:
-                    "Your local changes to the following files would be overwritten by merge\n"
-                        .to_string(),
-                code: proto::ErrorCode::Internal as i32,
-                tags: Default::default(),
-            },
-            "Pull",
-        );
+    #[gpui::test]
+    async fn test_submodules_reload_when_gitmodules_changes(cx: &mut TestAppContext) {
+        init_test(cx);
+        let fs = FakeFs::new(cx.background_executor.clone());
+        fs.insert_tree(
+            "/root",
+            json!({
+                "project": {
+                    ".git": {},
+                    ".gitmodules": "",
+                    "src": {
+                        "main.rs": "fn main() {}"
+                    }
+                }
+            }),
+        )
+        .await;
+        let dot_git = Path::new(path!("/root/project/.git"));
+        fs.set_status_for_repo(dot_git, &[("src/main.rs", StatusCode::Modified.worktree())]);
+        let vendored = Submodule {
+            path: repo_path("vendor/lib"),
+            sha: "3f786850e387550fdab836ed7e6dc881de23001b".into(),
+            state: SubmoduleState::UpToDate,
+        };
+        fs.with_git_state(dot_git, false, |state| {
+            state.submodules = vec![vendored.clone()];
+        })
+        .unwrap();
 
-        let message = format_git_error_toast_message(&rpc_error);
-        assert_eq!(
-            message,
-            "Your local changes to the following files would be overwritten by merge"
-        );
-    }
+        let project = Project::test(fs.clone(), [Path::new(path!("/root/project"))], cx).await;
+        let window_handle =
+            cx.add_window(|window, cx| MultiWorkspace::test_new(project.clone(), window, cx));
+        let workspace = window_handle
+            .read_with(cx, |mw, _| mw.workspace().clone())
+            .unwrap();
+        let cx = &mut VisualTestContext::from_window(window_handle.into(), cx);
+        cx.executor().run_until_parked();
 
-    #[test]
-    fn test_format_git_error_toast_message_prefers_raw_rpc_message_when_wrapped() {
-        let rpc_error = RpcError::from_proto(
-            &proto::Error {
-                message:
-                    "Your local changes to the following files would be overwritten by merge\n"
-                        .to_string(),
-                code: proto::ErrorCode::Internal as i32,
-                tags: Default::default(),
-            },
-            "Pull",
-        );
-        let wrapped = rpc_error.context("sending pull request");
+        let panel = workspace.update_in(cx, GitPanel::new);
+        cx.executor().advance_clock(2 * UPDATE_DEBOUNCE);
+        cx.run_until_parked();
+        panel.update(cx, |panel, _| {
+            assert_eq!(panel.submodules, [vendored.clone()]);
+        });
 
-        let message = format_git_error_toast_message(&wrapped);
-        assert_eq!(
-            message,
-            "Your local changes to the following files would be overwritten by merge"
+        let docs = Submodule {
+            path: repo_path("third_party/docs"),
+            sha: "89e6c98d92887913cadf06b2adb97f26cde4849b".into(),
+            state: SubmoduleState::Uninitialized,
+        };
+        fs.with_git_state(dot_git, false, |state| {
+            state.submodules = vec![vendored.clone(), docs.clone()];
+        })
+        .unwrap();
+        fs.set_status_for_repo(dot_git, &[("src/main.rs", StatusCode::Deleted.worktree())]);
+        cx.executor().advance_clock(2 * UPDATE_DEBOUNCE);
+        cx.run_until_parked();
+        panel.update(cx, |panel, _| {
+            assert_eq!(
+                panel.submodules,
+                [vendored.clone()],
+                "unrelated changes don't reload the submodules"
+            );
+        });
+
+        fs.set_status_for_repo(
+            dot_git,
+            &[
+                ("src/main.rs", StatusCode::Deleted.worktree()),
+                (".gitmodules", StatusCode::Modified.worktree()),
+            ],
         );
+        cx.executor().advance_clock(2 * UPDATE_DEBOUNCE);
+        cx.run_until_parked();
+        panel.update(cx, |panel, _| {
+            assert_eq!(panel.submodules, [vendored.clone(), docs.clone()]);
+        });
     }
 
     #[gpui::test]
-    async fn test_entry_worktree_paths(cx: &mut TestAppContext) {
+    async fn test_resolve_in_progress_operation(cx: &mut TestAppContext) {
         init_test(cx);
         let fs = FakeFs::new(cx.background_executor.clone());
         fs.insert_tree(
             "/root",
             json!({
-                "zed": {
+                "project": {
                     ".git": {},
-                    "crates": {
-                        "gpui": {
-                            "gpui.rs": "fn main() {}"
-                        },
-                        "util": {
-                            "util.rs": "fn do_it() {}"
-                        }
+                    "src": {
+                        "main.rs": "fn main() {}"
                     }
-                },
+                }
             }),
         )
         .await;
 
         fs.set_status_for_repo(
-            Path::new(path!("/root/zed/.git")),
-            &[
-                ("crates/gpui/gpui.rs", StatusCode::Modified.worktree()),
-                ("crates/util/util.rs", StatusCode::Modified.worktree()),
-            ],
+            Path::new(path!("/root/project/.git")),
+            &[("src/main.rs", StatusCode::Modified.worktree())],
         );
+        let operation = InProgressOperation {
+            kind: InProgressOperationKind::Rebase,
+            progress: Some((3, 7)),
+        };
+        fs.with_git_state(Path::new(path!("/root/project/.git")), true, |state| {
+            state.in_progress_operation = Some(operation);
+        })
+        .unwrap();
 
-        let project =
-            Project::test(fs.clone(), [path!("/root/zed/crates/gpui").as_ref()], cx).await;
+        let project = Project::test(fs.clone(), [Path::new(path!("/root/project"))], cx).await;
         let window_handle =
             cx.add_window(|window, cx| MultiWorkspace::test_new(project.clone(), window, cx));
         let workspace = window_handle
             .read_with(cx, |mw, _| mw.workspace().clone())
             .unwrap();
         let cx = &mut VisualTestContext::from_window(window_handle.into(), cx);
+        cx.executor().run_until_parked();
 
-        cx.read(|cx| {
-            project
-                .read(cx)
-                .worktrees(cx)
-                .next()
-                .unwrap()
-                .read(cx)
-                .as_local()
-                .unwrap()
-                .scan_complete()
-        })
+        let panel = workspace.update_in(cx, GitPanel::new);
+        cx.executor().advance_clock(2 * UPDATE_DEBOUNCE);
+        cx.run_until_parked();
+
+        panel.update(cx, |panel, cx| {
+            let repository = panel.active_repository.clone().unwrap();
+            assert_eq!(repository.read(cx).merge.operation, Some(operation));
+            assert_eq!(in_progress_operation_title(operation), "Rebasing 3/7");
+            panel.resolve_in_progress_operation(
+                InProgressOperationKind::Rebase,
+                OperationResolution::Abort,
+                cx,
+            );
+        });
+        cx.run_until_parked();
+
+        panel.update(cx, |panel, cx| {
+            let repository = panel.active_repository.clone().unwrap();
+            assert_eq!(repository.read(cx).merge.operation, None);
+        });
+    }
+
+    #[gpui::test]
+    async fn test_amend_pushed_commit_asks_for_confirmation(cx: &mut TestAppContext) {
+        init_test(cx);
+        let fs = FakeFs::new(cx.background_executor.clone());
+        fs.insert_tree(
+            "/root",
+            json!({
+                "project": {
+                    ".git": {},
+                    "src": {
+                        "main.rs": "fn main() {}"
+                    }
+                }
+            }),
+        )
         .await;
 
+        fs.set_status_for_repo(
+            Path::new(path!("/root/project/.git")),
+            &[("src/main.rs", StatusCode::Modified.worktree())],
+        );
+        fs.with_git_state(Path::new(path!("/root/project/.git")), true, |state| {
+            state.pushed_to = vec!["origin/main".to_string()];
+        })
+        .unwrap();
+
+        let project = Project::test(fs.clone(), [Path::new(path!("/root/project"))], cx).await;
+        let window_handle =
+            cx.add_window(|window, cx| MultiWorkspace::test_new(project.clone(), window, cx));
+        let workspace = window_handle
+            .read_with(cx, |mw, _| mw.workspace().clone())
+            .unwrap();
+        let cx = &mut VisualTestContext::from_window(window_handle.into(), cx);
         cx.executor().run_until_parked();
 
         let panel = workspace.update_in(cx, GitPanel::new);
+        panel.update_in(cx, |panel, window, cx| {
+            panel.focus_editor(&Default::default(), window, cx);
+            panel.on_amend(&Amend, window, cx);
+        });
+        cx.run_until_parked();
 
-        let handle = cx.update_window_entity(&panel, |panel, _, _| {
-            std::mem::replace(&mut panel.update_visible_entries_task, Task::ready(()))
+        panel.update_in(cx, |panel, window, cx| {
+            panel.focus_editor(&Default::default(), window, cx);
+            panel.on_amend(&Amend, window, cx);
         });
-        cx.executor().advance_clock(2 * UPDATE_DEBOUNCE);
-        handle.await;
+        cx.run_until_parked();
 
-        let entries = panel.read_with(cx, |panel, _| panel.entries.clone());
-        pretty_assertions::assert_eq!(
-            entries,
-            [
-                GitListEntry::Header(GitHeaderEntry {
-                    header: Section::Tracked
-                }),
-                GitListEntry::Status(GitStatusEntry {
-                    repo_path: repo_path("crates/gpui/gpui.rs"),
-                    status: StatusCode::Modified.worktree(),
-                    staging: StageStatus::Unstaged,
-                    diff_stat: Some(DiffStat {
-                        added: 1,
-                        deleted: 1,
-                    }),
-                }),
-                GitListEntry::Status(GitStatusEntry {
-                    repo_path: repo_path("crates/util/util.rs"),
-                    status: StatusCode::Modified.worktree(),
-                    staging: StageStatus::Unstaged,
-                    diff_stat: Some(DiffStat {
-                        added: 1,
-                        deleted: 1,
-                    }),
-                },),
-            ],
-        );
+        assert!(cx.has_pending_prompt());
+        cx.simulate_prompt_answer("Cancel");
+        cx.run_until_parked();
+        panel.read_with(cx, |panel, _| assert!(panel.amend_pending()));
 
-        let handle = cx.update_window_entity(&panel, |panel, _, _| {
-            std::mem::replace(&mut panel.update_visible_entries_task, Task::ready(()))
+        panel.update_in(cx, |panel, window, cx| {
+            panel.focus_editor(&Default::default(), window, cx);
+            panel.on_amend(&Amend, window, cx);
         });
-        cx.executor().advance_clock(2 * UPDATE_DEBOUNCE);
-        handle.await;
-        let entries = panel.read_with(cx, |panel, _| panel.entries.clone());
-        pretty_assertions::assert_eq!(
-            entries,
-            [
-                GitListEntry::Header(GitHeaderEntry {
-                    header: Section::Tracked
-                }),
-                GitListEntry::Status(GitStatusEntry {
-                    repo_path: repo_path("crates/gpui/gpui.rs"),
-                    status: StatusCode::Modified.worktree(),
-                    staging: StageStatus::Unstaged,
-                    diff_stat: Some(DiffStat {
-                        added: 1,
-                        deleted: 1,
-                    }),
-                }),
-                GitListEntry::Status(GitStatusEntry {
-                    repo_path: repo_path("crates/util/util.rs"),
-                    status: StatusCode::Modified.worktree(),
-                    staging: StageStatus::Unstaged,
-                    diff_stat: Some(DiffStat {
-                        added: 1,
-                        deleted: 1,
-                    }),
-                },),
-            ],
-        );
+        cx.run_until_parked();
+
+        assert!(cx.has_pending_prompt());
+        cx.simulate_prompt_answer("Amend");
+        cx.run_until_parked();
+        panel.read_with(cx, |panel, _| assert!(!panel.amend_pending()));
     }
 
     #[gpui::test]
-    async fn test_bulk_staging(cx: &mut TestAppContext) {
-        use GitListEntry::*;
-
+    async fn test_staging_file_with_conflict_markers_asks_for_confirmation(
+        cx: &mut TestAppContext,
+    ) {
         init_test(cx);
         let fs = FakeFs::new(cx.background_executor.clone());
         fs.insert_tree(
@@ -6673,40 +10888,21 @@ mod tests {
             json!({
                 "project": {
                     ".git": {},
-                    "src": {
-                        "main.rs": "fn main() {}",
-                        "lib.rs": "pub fn hello() {}",
-                        "utils.rs": "pub fn util() {}"
-                    },
-                    "tests": {
-                        "test.rs": "fn test() {}"
-                    },
-                    "new_file.txt": "new content",
-                    "another_new.rs": "// new file",
-                    "conflict.txt": "conflicted content"
+                    "conflict.txt": "<<<<<<< HEAD\nours\n=======\ntheirs\n>>>>>>> topic\n",
                 }
             }),
         )
         .await;
-
         fs.set_status_for_repo(
             Path::new(path!("/root/project/.git")),
-            &[
-                ("src/main.rs", StatusCode::Modified.worktree()),
-                ("src/lib.rs", StatusCode::Modified.worktree()),
-                ("tests/test.rs", StatusCode::Modified.worktree()),
-                ("new_file.txt", FileStatus::Untracked),
-                ("another_new.rs", FileStatus::Untracked),
-                ("src/utils.rs", FileStatus::Untracked),
-                (
-                    "conflict.txt",
-                    UnmergedStatus {
-                        first_head: UnmergedStatusCode::Updated,
-                        second_head: UnmergedStatusCode::Updated,
-                    }
-                    .into(),
-                ),
-            ],
+            &[(
+                "conflict.txt",
+                UnmergedStatus {
+                    first_head: UnmergedStatusCode::Updated,
+                    second_head: UnmergedStatusCode::Updated,
+                }
+                .into(),
+            )],
         );
 
         let project = Project::test(fs.clone(), [Path::new(path!("/root/project"))], cx).await;
@@ -6716,151 +10912,167 @@ mod tests {
             .read_with(cx, |mw, _| mw.workspace().clone())
             .unwrap();
         let cx = &mut VisualTestContext::from_window(window_handle.into(), cx);
-
-        cx.read(|cx| {
-            project
-                .read(cx)
-                .worktrees(cx)
-                .next()
-                .unwrap()
-                .read(cx)
-                .as_local()
-                .unwrap()
-                .scan_complete()
-        })
-        .await;
-
         cx.executor().run_until_parked();
 
         let panel = workspace.update_in(cx, GitPanel::new);
-
-        let handle = cx.update_window_entity(&panel, |panel, _, _| {
-            std::mem::replace(&mut panel.update_visible_entries_task, Task::ready(()))
-        });
         cx.executor().advance_clock(2 * UPDATE_DEBOUNCE);
-        handle.await;
+        cx.run_until_parked();
 
-        let entries = panel.read_with(cx, |panel, _| panel.entries.clone());
-        #[rustfmt::skip]
-        pretty_assertions::assert_matches!(
-            entries.as_slice(),
-            &[
-                Header(GitHeaderEntry { header: Section::Conflict }),
-                Status(GitStatusEntry { staging: StageStatus::Unstaged, .. }),
-                Header(GitHeaderEntry { header: Section::Tracked }),
-                Status(GitStatusEntry { staging: StageStatus::Unstaged, .. }),
-                Status(GitStatusEntry { staging: StageStatus::Unstaged, .. }),
-                Status(GitStatusEntry { staging: StageStatus::Unstaged, .. }),
-                Header(GitHeaderEntry { header: Section::New }),
-                Status(GitStatusEntry { staging: StageStatus::Unstaged, .. }),
-                Status(GitStatusEntry { staging: StageStatus::Unstaged, .. }),
-                Status(GitStatusEntry { staging: StageStatus::Unstaged, .. }),
-            ],
-        );
+        let conflicted_entry = panel.read_with(cx, |panel, _| {
+            panel
+                .entries
+                .iter()
+                .find(|entry| entry.status_entry().is_some())
+                .cloned()
+                .unwrap()
+        });
+        let staged_contents = || {
+            fs.with_git_state(Path::new(path!("/root/project/.git")), false, |state| {
+                state
+                    .index_contents
+                    .get(&repo_path("conflict.txt"))
+                    .cloned()
+            })
+            .unwrap()
+        };
 
-        let second_status_entry = entries[3].clone();
         panel.update_in(cx, |panel, window, cx| {
-            panel.toggle_staged_for_entry(&second_status_entry, window, cx);
+            panel.toggle_staged_for_entry(&conflicted_entry, window, cx);
         });
+        cx.run_until_parked();
+        assert!(cx.has_pending_prompt());
+        cx.simulate_prompt_answer("Cancel");
+        cx.run_until_parked();
+        assert_eq!(staged_contents(), None);
 
         panel.update_in(cx, |panel, window, cx| {
-            panel.selected_entry = Some(7);
-            panel.stage_range(&git::StageRange, window, cx);
+            panel.toggle_staged_for_entry(&conflicted_entry, window, cx);
         });
+        cx.run_until_parked();
+        assert!(cx.has_pending_prompt());
+        cx.simulate_prompt_answer("Stage Anyway");
+        cx.run_until_parked();
+        assert!(staged_contents().is_some());
+    }
 
-        cx.read(|cx| {
-            project
-                .read(cx)
-                .worktrees(cx)
-                .next()
-                .unwrap()
-                .read(cx)
-                .as_local()
-                .unwrap()
-                .scan_complete()
-        })
+    #[gpui::test]
+    async fn test_stage_all_with_conflict_markers_asks_for_confirmation(cx: &mut TestAppContext) {
+        init_test(cx);
+        let fs = FakeFs::new(cx.background_executor.clone());
+        fs.insert_tree(
+            "/root",
+            json!({
+                "project": {
+                    ".git": {},
+                    "conflict.txt": "<<<<<<< HEAD\nours\n=======\ntheirs\n>>>>>>> topic\n",
+                }
+            }),
+        )
         .await;
+        fs.set_status_for_repo(
+            Path::new(path!("/root/project/.git")),
+            &[(
+                "conflict.txt",
+                UnmergedStatus {
+                    first_head: UnmergedStatusCode::Updated,
+                    second_head: UnmergedStatusCode::Updated,
+                }
+                .into(),
+            )],
+        );
 
+        let project = Project::test(fs.clone(), [Path::new(path!("/root/project"))], cx).await;
+        let window_handle =
+            cx.add_window(|window, cx| MultiWorkspace::test_new(project.clone(), window, cx));
+        let workspace = window_handle
+            .read_with(cx, |mw, _| mw.workspace().clone())
+            .unwrap();
+        let cx = &mut VisualTestContext::from_window(window_handle.into(), cx);
         cx.executor().run_until_parked();
 
-        let handle = cx.update_window_entity(&panel, |panel, _, _| {
-            std::mem::replace(&mut panel.update_visible_entries_task, Task::ready(()))
-        });
+        let panel = workspace.update_in(cx, GitPanel::new);
         cx.executor().advance_clock(2 * UPDATE_DEBOUNCE);
-        handle.await;
-
-        let entries = panel.read_with(cx, |panel, _| panel.entries.clone());
-        #[rustfmt::skip]
-        pretty_assertions::assert_matches!(
-            entries.as_slice(),
-            &[
-                Header(GitHeaderEntry { header: Section::Conflict }),
-                Status(GitStatusEntry { staging: StageStatus::Unstaged, .. }),
-                Header(GitHeaderEntry { header: Section::Tracked }),
-                Status(GitStatusEntry { staging: StageStatus::Staged, .. }),
-                Status(GitStatusEntry { staging: StageStatus::Staged, .. }),
-                Status(GitStatusEntry { staging: StageStatus::Staged, .. }),
-                Header(GitHeaderEntry { header: Section::New }),
-                Status(GitStatusEntry { staging: StageStatus::Staged, .. }),
-                Status(GitStatusEntry { staging: StageStatus::Unstaged, .. }),
-                Status(GitStatusEntry { staging: StageStatus::Unstaged, .. }),
-            ],
-        );
-
-        let third_status_entry = entries[4].clone();
-        panel.update_in(cx, |panel, window, cx| {
-            panel.toggle_staged_for_entry(&third_status_entry, window, cx);
-        });
+        cx.run_until_parked();
 
         panel.update_in(cx, |panel, window, cx| {
-            panel.selected_entry = Some(9);
-            panel.stage_range(&git::StageRange, window, cx);
+            panel.stage_all(&StageAll, window, cx);
         });
+        cx.run_until_parked();
+        assert!(cx.has_pending_prompt());
+        cx.simulate_prompt_answer("Cancel");
+        cx.run_until_parked();
+        let staged_contents = fs
+            .with_git_state(Path::new(path!("/root/project/.git")), false, |state| {
+                state
+                    .index_contents
+                    .get(&repo_path("conflict.txt"))
+                    .cloned()
+            })
+            .unwrap();
+        assert_eq!(staged_contents, None);
+    }
 
-        cx.read(|cx| {
-            project
-                .read(cx)
-                .worktrees(cx)
-                .next()
-                .unwrap()
-                .read(cx)
-                .as_local()
-                .unwrap()
-                .scan_complete()
-        })
+    #[gpui::test]
+    async fn test_revert_commit_without_committing_fills_commit_message(cx: &mut TestAppContext) {
+        init_test(cx);
+        let fs = FakeFs::new(cx.background_executor.clone());
+        fs.insert_tree(
+            "/root",
+            json!({
+                "project": {
+                    ".git": {},
+                    "file.txt": "content",
+                }
+            }),
+        )
         .await;
 
+        let project = Project::test(fs.clone(), [Path::new(path!("/root/project"))], cx).await;
+        let window_handle =
+            cx.add_window(|window, cx| MultiWorkspace::test_new(project.clone(), window, cx));
+        let workspace = window_handle
+            .read_with(cx, |mw, _| mw.workspace().clone())
+            .unwrap();
+        let cx = &mut VisualTestContext::from_window(window_handle.into(), cx);
         cx.executor().run_until_parked();
 
-        let handle = cx.update_window_entity(&panel, |panel, _, _| {
-            std::mem::replace(&mut panel.update_visible_entries_task, Task::ready(()))
+        let panel = workspace.update_in(cx, GitPanel::new);
+        workspace.update_in(cx, |workspace, window, cx| {
+            workspace.add_panel(panel.clone(), window, cx)
         });
         cx.executor().advance_clock(2 * UPDATE_DEBOUNCE);
-        handle.await;
+        cx.run_until_parked();
 
-        let entries = panel.read_with(cx, |panel, _| panel.entries.clone());
-        #[rustfmt::skip]
-        pretty_assertions::assert_matches!(
-            entries.as_slice(),
-            &[
-                Header(GitHeaderEntry { header: Section::Conflict }),
-                Status(GitStatusEntry { staging: StageStatus::Unstaged, .. }),
-                Header(GitHeaderEntry { header: Section::Tracked }),
-                Status(GitStatusEntry { staging: StageStatus::Staged, .. }),
-                Status(GitStatusEntry { staging: StageStatus::Unstaged, .. }),
-                Status(GitStatusEntry { staging: StageStatus::Staged, .. }),
-                Header(GitHeaderEntry { header: Section::New }),
-                Status(GitStatusEntry { staging: StageStatus::Staged, .. }),
-                Status(GitStatusEntry { staging: StageStatus::Staged, .. }),
-                Status(GitStatusEntry { staging: StageStatus::Staged, .. }),
-            ],
-        );
+        let repository =
+            project.read_with(cx, |project, cx| project.active_repository(cx).unwrap());
+        cx.update(|window, cx| {
+            revert_commit(
+                workspace.downgrade(),
+                repository,
+                "abc123".to_string(),
+                true,
+                window,
+                cx,
+            )
+        });
+        cx.run_until_parked();
+
+        let reverted_commits = fs
+            .with_git_state(Path::new(path!("/root/project/.git")), false, |state| {
+                state.reverted_commits.clone()
+            })
+            .unwrap();
+        assert_eq!(reverted_commits, vec![("abc123".to_string(), true)]);
+        panel.read_with(cx, |panel, cx| {
+            assert_eq!(
+                panel.commit_editor.read(cx).text(cx),
+                "Revert abc123\n\nThis reverts commit abc123."
+            );
+        });
     }
 
     #[gpui::test]
-    async fn test_bulk_staging_with_sort_by_paths(cx: &mut TestAppContext) {
-        use GitListEntry::*;
-
+    async fn test_failed_hook_output_keeps_commit_message(cx: &mut TestAppContext) {
         init_test(cx);
         let fs = FakeFs::new(cx.background_executor.clone());
         fs.insert_tree(
@@ -6869,16 +11081,8 @@ mod tests {
                 "project": {
                     ".git": {},
                     "src": {
-                        "main.rs": "fn main() {}",
-                        "lib.rs": "pub fn hello() {}",
-                        "utils.rs": "pub fn util() {}"
-                    },
-                    "tests": {
-                        "test.rs": "fn test() {}"
-                    },
-                    "new_file.txt": "new content",
-                    "another_new.rs": "// new file",
-                    "conflict.txt": "conflicted content"
+                        "main.rs": "fn main() {}"
+                    }
                 }
             }),
         )
@@ -6886,23 +11090,18 @@ mod tests {
 
         fs.set_status_for_repo(
             Path::new(path!("/root/project/.git")),
-            &[
-                ("src/main.rs", StatusCode::Modified.worktree()),
-                ("src/lib.rs", StatusCode::Modified.worktree()),
-                ("tests/test.rs", StatusCode::Modified.worktree()),
-                ("new_file.txt", FileStatus::Untracked),
-                ("another_new.rs", FileStatus::Untracked),
-                ("src/utils.rs", FileStatus::Untracked),
-                (
-                    "conflict.txt",
-                    UnmergedStatus {
-                        first_head: UnmergedStatusCode::Updated,
-                        second_head: UnmergedStatusCode::Updated,
-                    }
-                    .into(),
-                ),
-            ],
+            &[("src/main.rs", StatusCode::Modified.worktree())],
         );
+        fs.with_git_state(Path::new(path!("/root/project/.git")), true, |state| {
+            state.branches.insert("main".to_string());
+            state.current_branch_name = Some("main".to_string());
+            state.hook_output = vec![
+                "Running linters...\n".to_string(),
+                "src/main.rs: missing docs\n".to_string(),
+            ];
+            state.simulated_hook_failure = Some("pre-commit hook failed".to_string());
+        })
+        .unwrap();
 
         let project = Project::test(fs.clone(), [Path::new(path!("/root/project"))], cx).await;
         let window_handle =
@@ -6911,193 +11110,114 @@ mod tests {
             .read_with(cx, |mw, _| mw.workspace().clone())
             .unwrap();
         let cx = &mut VisualTestContext::from_window(window_handle.into(), cx);
-
-        cx.read(|cx| {
-            project
-                .read(cx)
-                .worktrees(cx)
-                .next()
-                .unwrap()
-                .read(cx)
-                .as_local()
-                .unwrap()
-                .scan_complete()
-        })
-        .await;
-
         cx.executor().run_until_parked();
 
         let panel = workspace.update_in(cx, GitPanel::new);
-
-        let handle = cx.update_window_entity(&panel, |panel, _, _| {
-            std::mem::replace(&mut panel.update_visible_entries_task, Task::ready(()))
-        });
         cx.executor().advance_clock(2 * UPDATE_DEBOUNCE);
-        handle.await;
-
-        let entries = panel.read_with(cx, |panel, _| panel.entries.clone());
-        #[rustfmt::skip]
-        pretty_assertions::assert_matches!(
-            entries.as_slice(),
-            &[
-                Header(GitHeaderEntry { header: Section::Conflict }),
-                Status(GitStatusEntry { staging: StageStatus::Unstaged, .. }),
-                Header(GitHeaderEntry { header: Section::Tracked }),
-                Status(GitStatusEntry { staging: StageStatus::Unstaged, .. }),
-                Status(GitStatusEntry { staging: StageStatus::Unstaged, .. }),
-                Status(GitStatusEntry { staging: StageStatus::Unstaged, .. }),
-                Header(GitHeaderEntry { header: Section::New }),
-                Status(GitStatusEntry { staging: StageStatus::Unstaged, .. }),
-                Status(GitStatusEntry { staging: StageStatus::Unstaged, .. }),
-                Status(GitStatusEntry { staging: StageStatus::Unstaged, .. }),
-            ],
-        );
-
-        assert_entry_paths(
-            &entries,
-            &[
-                None,
-                Some("conflict.txt"),
-                None,
-                Some("src/lib.rs"),
-                Some("src/main.rs"),
-                Some("tests/test.rs"),
-                None,
-                Some("another_new.rs"),
-                Some("new_file.txt"),
-                Some("src/utils.rs"),
-            ],
-        );
+        cx.run_until_parked();
 
-        let second_status_entry = entries[3].clone();
         panel.update_in(cx, |panel, window, cx| {
-            panel.toggle_staged_for_entry(&second_status_entry, window, cx);
-        });
-
-        cx.update(|_window, cx| {
-            SettingsStore::update_global(cx, |store, cx| {
-                store.update_user_settings(cx, |settings| {
-                    settings.git_panel.get_or_insert_default().sort_by_path = Some(true);
-                })
+            panel.commit_message_buffer(cx).update(cx, |buffer, cx| {
+                buffer.edit([(0..0, "Fix startup")], None, cx);
             });
+            panel.commit_changes(CommitOptions::default(), window, cx);
         });
+        cx.run_until_parked();
 
-        panel.update_in(cx, |panel, window, cx| {
-            panel.selected_entry = Some(7);
-            panel.stage_range(&git::StageRange, window, cx);
+        panel.update(cx, |panel, cx| {
+            assert_eq!(
+                panel.commit_message_buffer(cx).read(cx).text(),
+                "Fix startup"
+            );
+            assert!(panel.hook_output_expanded);
+            let (_, commit_messages) = panel.commit_messages.as_ref().unwrap();
+            assert_eq!(commit_messages.draft.as_deref(), Some("Fix startup"));
+            assert_eq!(commit_messages.history, ["Fix startup"]);
+            let repository = panel.active_repository.clone().unwrap();
+            let hook_output = repository.read(cx).hook_output().cloned().unwrap();
+            assert_eq!(hook_output.status, HookStatus::Failed);
+            assert_eq!(
+                hook_output.output,
+                "Running linters...\nsrc/main.rs: missing docs\n"
+            );
         });
+    }
 
-        cx.read(|cx| {
-            project
-                .read(cx)
-                .worktrees(cx)
-                .next()
-                .unwrap()
-                .read(cx)
-                .as_local()
-                .unwrap()
-                .scan_complete()
-        })
+    #[gpui::test]
+    async fn test_commit_no_verify_skips_hooks(cx: &mut TestAppContext) {
+        init_test(cx);
+        let fs = FakeFs::new(cx.background_executor.clone());
+        fs.insert_tree(
+            "/root",
+            json!({
+                "project": {
+                    ".git": {},
+                    "src": {
+                        "main.rs": "fn main() {}"
+                    }
+                }
+            }),
+        )
         .await;
 
-        cx.executor().run_until_parked();
-
-        let handle = cx.update_window_entity(&panel, |panel, _, _| {
-            std::mem::replace(&mut panel.update_visible_entries_task, Task::ready(()))
-        });
-        cx.executor().advance_clock(2 * UPDATE_DEBOUNCE);
-        handle.await;
-
-        let entries = panel.read_with(cx, |panel, _| panel.entries.clone());
-        #[rustfmt::skip]
-        pretty_assertions::assert_matches!(
-            entries.as_slice(),
-            &[
-                Status(GitStatusEntry { status: FileStatus::Untracked, staging: StageStatus::Unstaged, .. }),
-                Status(GitStatusEntry { status: FileStatus::Unmerged(..), staging: StageStatus::Unstaged, .. }),
-                Status(GitStatusEntry { status: FileStatus::Untracked, staging: StageStatus::Unstaged, .. }),
-                Status(GitStatusEntry { status: FileStatus::Tracked(..), staging: StageStatus::Staged, .. }),
-                Status(GitStatusEntry { status: FileStatus::Tracked(..), staging: StageStatus::Unstaged, .. }),
-                Status(GitStatusEntry { status: FileStatus::Untracked, staging: StageStatus::Unstaged, .. }),
-                Status(GitStatusEntry { status: FileStatus::Tracked(..), staging: StageStatus::Unstaged, .. }),
-            ],
+        fs.set_status_for_repo(
+            Path::new(path!("/root/project/.git")),
+            &[("src/main.rs", StatusCode::Modified.worktree())],
         );
+        fs.with_git_state(Path::new(path!("/root/project/.git")), true, |state| {
+            state.branches.insert("main".to_string());
+            state.current_branch_name = Some("main".to_string());
+            state.simulated_hook_failure = Some("pre-commit hook failed".to_string());
+        })
+        .unwrap();
 
-        assert_entry_paths(
-            &entries,
-            &[
-                Some("another_new.rs"),
-                Some("conflict.txt"),
-                Some("new_file.txt"),
-                Some("src/lib.rs"),
-                Some("src/main.rs"),
-                Some("src/utils.rs"),
-                Some("tests/test.rs"),
-            ],
-        );
+        let project = Project::test(fs.clone(), [Path::new(path!("/root/project"))], cx).await;
+        let window_handle =
+            cx.add_window(|window, cx| MultiWorkspace::test_new(project.clone(), window, cx));
+        let workspace = window_handle
+            .read_with(cx, |mw, _| mw.workspace().clone())
+            .unwrap();
+        let cx = &mut VisualTestContext::from_window(window_handle.into(), cx);
+        cx.executor().run_until_parked();
 
-        let third_status_entry = entries[4].clone();
-        panel.update_in(cx, |panel, window, cx| {
-            panel.toggle_staged_for_entry(&third_status_entry, window, cx);
-        });
+        let panel = workspace.update_in(cx, GitPanel::new);
+        cx.executor().advance_clock(2 * UPDATE_DEBOUNCE);
+        cx.run_until_parked();
 
         panel.update_in(cx, |panel, window, cx| {
-            panel.selected_entry = Some(9);
-            panel.stage_range(&git::StageRange, window, cx);
+            panel.commit_message_buffer(cx).update(cx, |buffer, cx| {
+                buffer.edit([(0..0, "Urgent fix")], None, cx);
+            });
+            panel.commit_no_verify(&git::CommitNoVerify, window, cx);
         });
+        cx.run_until_parked();
+        assert!(!cx.has_pending_prompt());
 
-        cx.read(|cx| {
-            project
-                .read(cx)
-                .worktrees(cx)
-                .next()
-                .unwrap()
-                .read(cx)
-                .as_local()
-                .unwrap()
-                .scan_complete()
-        })
-        .await;
-
-        cx.executor().run_until_parked();
-
-        let handle = cx.update_window_entity(&panel, |panel, _, _| {
-            std::mem::replace(&mut panel.update_visible_entries_task, Task::ready(()))
+        cx.update(|_window, cx| {
+            SettingsStore::update_global(cx, |store, cx| {
+                store.update_user_settings(cx, |settings| {
+                    settings.git.get_or_insert_default().allow_no_verify = Some(true);
+                })
+            });
         });
-        cx.executor().advance_clock(2 * UPDATE_DEBOUNCE);
-        handle.await;
+        panel.update_in(cx, |panel, window, cx| {
+            panel.commit_no_verify(&git::CommitNoVerify, window, cx);
+        });
+        cx.run_until_parked();
 
-        let entries = panel.read_with(cx, |panel, _| panel.entries.clone());
-        #[rustfmt::skip]
-        pretty_assertions::assert_matches!(
-            entries.as_slice(),
-            &[
-                Status(GitStatusEntry { status: FileStatus::Untracked, staging: StageStatus::Unstaged, .. }),
-                Status(GitStatusEntry { status: FileStatus::Unmerged(..), staging: StageStatus::Unstaged, .. }),
-                Status(GitStatusEntry { status: FileStatus::Untracked, staging: StageStatus::Unstaged, .. }),
-                Status(GitStatusEntry { status: FileStatus::Tracked(..), staging: StageStatus::Staged, .. }),
-                Status(GitStatusEntry { status: FileStatus::Tracked(..), staging: StageStatus::Staged, .. }),
-                Status(GitStatusEntry { status: FileStatus::Untracked, staging: StageStatus::Unstaged, .. }),
-                Status(GitStatusEntry { status: FileStatus::Tracked(..), staging: StageStatus::Unstaged, .. }),
-            ],
-        );
+        assert!(cx.has_pending_prompt());
+        cx.simulate_prompt_answer("Commit Without Hooks");
+        cx.run_until_parked();
 
-        assert_entry_paths(
-            &entries,
-            &[
-                Some("another_new.rs"),
-                Some("conflict.txt"),
-                Some("new_file.txt"),
-                Some("src/lib.rs"),
-                Some("src/main.rs"),
-                Some("src/utils.rs"),
-                Some("tests/test.rs"),
-            ],
-        );
+        panel.update(cx, |panel, cx| {
+            assert_eq!(panel.commit_message_buffer(cx).read(cx).text(), "");
+            let repository = panel.active_repository.clone().unwrap();
+            assert!(repository.read(cx).hook_output().is_none());
+        });
     }
 
     #[gpui::test]
-    async fn test_amend_commit_message_handling(cx: &mut TestAppContext) {
+    async fn test_commit_message_draft_and_history(cx: &mut TestAppContext) {
         init_test(cx);
         let fs = FakeFs::new(cx.background_executor.clone());
         fs.insert_tree(
@@ -7117,6 +11237,11 @@ mod tests {
             Path::new(path!("/root/project/.git")),
             &[("src/main.rs", StatusCode::Modified.worktree())],
         );
+        fs.with_git_state(Path::new(path!("/root/project/.git")), true, |state| {
+            state.branches.insert("main".to_string());
+            state.current_branch_name = Some("main".to_string());
+        })
+        .unwrap();
 
         let project = Project::test(fs.clone(), [Path::new(path!("/root/project"))], cx).await;
         let window_handle =
@@ -7125,51 +11250,44 @@ mod tests {
             .read_with(cx, |mw, _| mw.workspace().clone())
             .unwrap();
         let cx = &mut VisualTestContext::from_window(window_handle.into(), cx);
+        cx.executor().run_until_parked();
 
         let panel = workspace.update_in(cx, GitPanel::new);
+        cx.executor().advance_clock(2 * UPDATE_DEBOUNCE);
+        cx.run_until_parked();
 
-        // Test: User has commit message, enables amend (saves message), then disables (restores message)
         panel.update(cx, |panel, cx| {
             panel.commit_message_buffer(cx).update(cx, |buffer, cx| {
-                let start = buffer.anchor_before(0);
-                let end = buffer.anchor_after(buffer.len());
-                buffer.edit([(start..end, "Initial commit message")], None, cx);
+                buffer.edit([(0..0, "Fix startup")], None, cx);
             });
-
-            panel.set_amend_pending(true, cx);
-            assert!(panel.original_commit_message.is_some());
-
-            panel.set_amend_pending(false, cx);
-            let current_message = panel.commit_message_buffer(cx).read(cx).text();
-            assert_eq!(current_message, "Initial commit message");
-            assert!(panel.original_commit_message.is_none());
+        });
+        cx.run_until_parked();
+        panel.read_with(cx, |panel, _| {
+            let (_, commit_messages) = panel.commit_messages.as_ref().unwrap();
+            assert_eq!(commit_messages.draft.as_deref(), Some("Fix startup"));
         });
 
-        // Test: User has empty commit message, enables amend, then disables (clears message)
-        panel.update(cx, |panel, cx| {
-            panel.commit_message_buffer(cx).update(cx, |buffer, cx| {
-                let start = buffer.anchor_before(0);
-                let end = buffer.anchor_after(buffer.len());
-                buffer.edit([(start..end, "")], None, cx);
-            });
-
-            panel.set_amend_pending(true, cx);
-            assert!(panel.original_commit_message.is_none());
+        panel.update_in(cx, |panel, window, cx| {
+            panel.commit_changes(CommitOptions::default(), window, cx);
+        });
+        cx.run_until_parked();
 
-            panel.commit_message_buffer(cx).update(cx, |buffer, cx| {
-                let start = buffer.anchor_before(0);
-                let end = buffer.anchor_after(buffer.len());
-                buffer.edit([(start..end, "Previous commit message")], None, cx);
-            });
+        panel.update_in(cx, |panel, window, cx| {
+            let (_, commit_messages) = panel.commit_messages.as_ref().unwrap();
+            assert_eq!(commit_messages.draft, None);
+            assert_eq!(commit_messages.history, ["Fix startup"]);
+            assert_eq!(panel.commit_message_buffer(cx).read(cx).text(), "");
 
-            panel.set_amend_pending(false, cx);
-            let current_message = panel.commit_message_buffer(cx).read(cx).text();
-            assert_eq!(current_message, "");
+            panel.recall_commit_message("Fix startup".to_string(), window, cx);
+            assert_eq!(
+                panel.commit_message_buffer(cx).read(cx).text(),
+                "Fix startup"
+            );
         });
     }
 
     #[gpui::test]
-    async fn test_amend(cx: &mut TestAppContext) {
+    async fn test_commit_on_detached_head_asks_first(cx: &mut TestAppContext) {
         init_test(cx);
         let fs = FakeFs::new(cx.background_executor.clone());
         fs.insert_tree(
@@ -7184,7 +11302,6 @@ mod tests {
             }),
         )
         .await;
-
         fs.set_status_for_repo(
             Path::new(path!("/root/project/.git")),
             &[("src/main.rs", StatusCode::Modified.worktree())],
@@ -7197,65 +11314,40 @@ mod tests {
             .read_with(cx, |mw, _| mw.workspace().clone())
             .unwrap();
         let cx = &mut VisualTestContext::from_window(window_handle.into(), cx);
-
-        // Wait for the project scanning to finish so that `head_commit(cx)` is
-        // actually set, otherwise no head commit would be available from which
-        // to fetch the latest commit message from.
         cx.executor().run_until_parked();
 
         let panel = workspace.update_in(cx, GitPanel::new);
+        cx.executor().advance_clock(2 * UPDATE_DEBOUNCE);
+        cx.run_until_parked();
         panel.read_with(cx, |panel, cx| {
-            assert!(panel.active_repository.is_some());
-            assert!(panel.head_commit(cx).is_some());
+            let repo = panel.active_repository.as_ref().unwrap().read(cx);
+            assert!(repo.is_head_detached());
         });
 
         panel.update_in(cx, |panel, window, cx| {
-            // Update the commit editor's message to ensure that its contents
-            // are later restored, after amending is finished.
             panel.commit_message_buffer(cx).update(cx, |buffer, cx| {
-                buffer.set_text("refactor: update main.rs", cx);
+                buffer.edit([(0..0, "Fix startup")], None, cx);
             });
-
-            // Start amending the previous commit.
-            panel.focus_editor(&Default::default(), window, cx);
-            panel.on_amend(&Amend, window, cx);
+            panel.commit_changes(CommitOptions::default(), window, cx);
         });
-
-        // Since `GitPanel.amend` attempts to fetch the latest commit message in
-        // a background task, we need to wait for it to complete before being
-        // able to assert that the commit message editor's state has been
-        // updated.
         cx.run_until_parked();
-
-        panel.update_in(cx, |panel, window, cx| {
+        cx.simulate_prompt_answer("Cancel");
+        cx.run_until_parked();
+        panel.read_with(cx, |panel, cx| {
             assert_eq!(
                 panel.commit_message_buffer(cx).read(cx).text(),
-                "initial commit"
-            );
-            assert_eq!(
-                panel.original_commit_message,
-                Some("refactor: update main.rs".to_string())
+                "Fix startup"
             );
-
-            // Finish amending the previous commit.
-            panel.focus_editor(&Default::default(), window, cx);
-            panel.on_amend(&Amend, window, cx);
         });
 
-        // Since the actual commit logic is run in a background task, we need to
-        // await its completion to actually ensure that the commit message
-        // editor's contents are set to the original message and haven't been
-        // cleared.
+        panel.update_in(cx, |panel, window, cx| {
+            panel.commit_changes(CommitOptions::default(), window, cx);
+        });
         cx.run_until_parked();
-
-        panel.update_in(cx, |panel, _window, cx| {
-            // After amending, the commit editor's message should be restored to
-            // the original message.
-            assert_eq!(
-                panel.commit_message_buffer(cx).read(cx).text(),
-                "refactor: update main.rs"
-            );
-            assert!(panel.original_commit_message.is_none());
+        cx.simulate_prompt_answer("Commit Anyway");
+        cx.run_until_parked();
+        panel.read_with(cx, |panel, cx| {
+            assert_eq!(panel.commit_message_buffer(cx).read(cx).text(), "");
         });
     }
 
@@ -7437,16 +11529,211 @@ mod tests {
                 .expect("tree view state should exist");
             assert_eq!(state.expanded_dirs.get(&src_key).copied(), Some(true));
 
-            let selected_ix = panel.selected_entry.expect("selection should be set");
-            assert!(state.logical_indices.contains(&selected_ix));
+            let selected_ix = panel.selected_entry.expect("selection should be set");
+            assert!(state.logical_indices.contains(&selected_ix));
+
+            let selected_entry = panel
+                .entries
+                .get(selected_ix)
+                .and_then(|entry| entry.status_entry())
+                .expect("selected entry should be a status entry");
+            assert_eq!(selected_entry.repo_path, repo_path("src/a/foo.rs"));
+        });
+    }
+
+    #[gpui::test]
+    async fn test_tree_view_stage_and_unstage_selected_directory(cx: &mut TestAppContext) {
+        init_test(cx);
+
+        let fs = FakeFs::new(cx.background_executor.clone());
+        fs.insert_tree(
+            path!("/project"),
+            json!({
+                ".git": {},
+                "src": {
+                    "a": {
+                        "foo.rs": "fn foo() {}",
+                        "baz.rs": "fn baz() {}",
+                    },
+                    "b": {
+                        "bar.rs": "fn bar() {}",
+                    },
+                },
+            }),
+        )
+        .await;
+
+        fs.set_status_for_repo(
+            path!("/project/.git").as_ref(),
+            &[
+                ("src/a/foo.rs", StatusCode::Modified.worktree()),
+                ("src/a/baz.rs", StatusCode::Modified.worktree()),
+                ("src/b/bar.rs", StatusCode::Modified.worktree()),
+            ],
+        );
+
+        let project = Project::test(fs.clone(), [Path::new(path!("/project"))], cx).await;
+        let window_handle =
+            cx.add_window(|window, cx| MultiWorkspace::test_new(project.clone(), window, cx));
+        let workspace = window_handle
+            .read_with(cx, |mw, _| mw.workspace().clone())
+            .unwrap();
+        let cx = &mut VisualTestContext::from_window(window_handle.into(), cx);
+
+        cx.read(|cx| {
+            project
+                .read(cx)
+                .worktrees(cx)
+                .next()
+                .unwrap()
+                .read(cx)
+                .as_local()
+                .unwrap()
+                .scan_complete()
+        })
+        .await;
+
+        cx.executor().run_until_parked();
+
+        cx.update(|_window, cx| {
+            SettingsStore::update_global(cx, |store, cx| {
+                store.update_user_settings(cx, |settings| {
+                    settings.git_panel.get_or_insert_default().tree_view = Some(true);
+                })
+            });
+        });
+
+        let panel = workspace.update_in(cx, GitPanel::new);
+
+        let handle = cx.update_window_entity(&panel, |panel, _, _| {
+            std::mem::replace(&mut panel.update_visible_entries_task, Task::ready(()))
+        });
+        cx.executor().advance_clock(2 * UPDATE_DEBOUNCE);
+        handle.await;
+
+        let select_directory = |panel: &mut GitPanel, path: &str| {
+            panel.selected_entry = panel.entries.iter().position(|entry| {
+                matches!(entry, GitListEntry::Directory(dir) if dir.key.path == repo_path(path))
+            });
+            assert!(panel.selected_entry.is_some(), "{path} should be listed");
+        };
+        let staged_paths = |panel: &GitPanel, cx: &App| {
+            let repo = panel.active_repository.as_ref().unwrap().read(cx);
+            let mut paths = panel
+                .entries
+                .iter()
+                .filter_map(|entry| entry.status_entry())
+                .filter(|entry| {
+                    GitPanel::stage_status_for_entry(entry, repo) == StageStatus::Staged
+                })
+                .map(|entry| entry.repo_path.as_unix_str().to_string())
+                .collect::<Vec<_>>();
+            paths.sort();
+            paths
+        };
+
+        panel.update_in(cx, |panel, window, cx| {
+            select_directory(panel, "src/a");
+            panel.stage_selected(&git::StageFile, window, cx);
+        });
+        cx.executor().run_until_parked();
+        panel.read_with(cx, |panel, cx| {
+            assert_eq!(staged_paths(panel, cx), ["src/a/baz.rs", "src/a/foo.rs"]);
+        });
+
+        panel.update_in(cx, |panel, window, cx| {
+            select_directory(panel, "src");
+            panel.stage_selected(&git::StageFile, window, cx);
+        });
+        cx.executor().run_until_parked();
+        panel.update_in(cx, |panel, window, cx| {
+            select_directory(panel, "src/a");
+            panel.unstage_selected(&git::UnstageFile, window, cx);
+        });
+        cx.executor().run_until_parked();
+        panel.read_with(cx, |panel, cx| {
+            assert_eq!(staged_paths(panel, cx), ["src/b/bar.rs"]);
+        });
+    }
+
+    #[gpui::test]
+    async fn test_undo_discard(cx: &mut TestAppContext) {
+        init_test(cx);
+
+        let fs = FakeFs::new(cx.background_executor.clone());
+        fs.insert_tree(path!("/project"), json!({ ".git": {} }))
+            .await;
+        let project = Project::test(fs.clone(), [Path::new(path!("/project"))], cx).await;
+        let window_handle =
+            cx.add_window(|window, cx| MultiWorkspace::test_new(project.clone(), window, cx));
+        let workspace = window_handle
+            .read_with(cx, |mw, _| mw.workspace().clone())
+            .unwrap();
+        let cx = &mut VisualTestContext::from_window(window_handle.into(), cx);
+        let panel = workspace.update_in(cx, GitPanel::new);
+
+        let buffer = cx.new(|cx| Buffer::local("one\nTWO\nthree\nFOUR\n", cx));
+        // Replaces a line with its base text, as restoring a single-line hunk would.
+        let discard = |buffer: &mut Buffer, row: u32, base_text: &str, cx: &mut Context<Buffer>| {
+            let range = language::Point::new(row, 0)..language::Point::new(row + 1, 0);
+            let discarded_text = buffer.text_for_range(range.clone()).collect::<String>();
+            buffer.edit([(range.clone(), base_text)], None, cx);
+            DiscardedHunk {
+                buffer: cx.weak_entity(),
+                range: buffer.anchor_before(range.start)..buffer.anchor_after(range.end),
+                discarded_text,
+                restored_text: base_text.to_string(),
+                index_text: None,
+            }
+        };
+
+        let first = buffer.update(cx, |buffer, cx| discard(buffer, 1, "two\n", cx));
+        let second = buffer.update(cx, |buffer, cx| discard(buffer, 3, "four\n", cx));
+        panel.update(cx, |panel, cx| {
+            panel.record_discarded_hunks(vec![first], cx);
+            panel.record_discarded_hunks(vec![second], cx);
+        });
+        assert_eq!(
+            buffer.read_with(cx, |buffer, _| buffer.text()),
+            "one\ntwo\nthree\nfour\n"
+        );
 
-            let selected_entry = panel
-                .entries
-                .get(selected_ix)
-                .and_then(|entry| entry.status_entry())
-                .expect("selected entry should be a status entry");
-            assert_eq!(selected_entry.repo_path, repo_path("src/a/foo.rs"));
+        panel.update(cx, |panel, cx| panel.undo_discard(cx));
+        assert_eq!(
+            buffer.read_with(cx, |buffer, _| buffer.text()),
+            "one\ntwo\nthree\nFOUR\n"
+        );
+        panel.update(cx, |panel, cx| panel.undo_discard(cx));
+        assert_eq!(
+            buffer.read_with(cx, |buffer, _| buffer.text()),
+            "one\nTWO\nthree\nFOUR\n"
+        );
+        panel.update(cx, |panel, cx| {
+            panel.undo_discard(cx);
+            assert!(panel.discarded_hunks.is_empty());
+        });
+        assert_eq!(
+            buffer.read_with(cx, |buffer, _| buffer.text()),
+            "one\nTWO\nthree\nFOUR\n"
+        );
+
+        // Undoing a discard whose lines were edited since would lose those edits.
+        let third = buffer.update(cx, |buffer, cx| discard(buffer, 1, "two\n", cx));
+        panel.update(cx, |panel, cx| {
+            panel.record_discarded_hunks(vec![third], cx)
+        });
+        buffer.update(cx, |buffer, cx| {
+            buffer.edit(
+                [(language::Point::new(1, 3)..language::Point::new(1, 3), "!")],
+                None,
+                cx,
+            )
         });
+        panel.update(cx, |panel, cx| panel.undo_discard(cx));
+        assert_eq!(
+            buffer.read_with(cx, |buffer, _| buffer.text()),
+            "one\ntwo!\nthree\nFOUR\n"
+        );
     }
 
     #[gpui::test]
@@ -7839,44 +12126,359 @@ mod tests {
         })
         .await;
 
-        cx.executor().run_until_parked();
+        cx.executor().run_until_parked();
+
+        let handle = cx.update_window_entity(&panel, |panel, _, _| {
+            std::mem::replace(&mut panel.update_visible_entries_task, Task::ready(()))
+        });
+        cx.executor().advance_clock(2 * UPDATE_DEBOUNCE);
+        handle.await;
+
+        // GitPanel
+        // - Tracked:
+        // - [] tracked
+        // - Untracked
+        // - [] untracked
+        //
+        // The commit message should now read:
+        // "Update tracked"
+        let message = panel.update(cx, |panel, cx| panel.suggest_commit_message(cx));
+        assert_eq!(message, Some("Update tracked".to_string()));
+    }
+
+    #[gpui::test]
+    async fn test_dispatch_context_with_focus_states(cx: &mut TestAppContext) {
+        init_test(cx);
+
+        let fs = FakeFs::new(cx.background_executor.clone());
+        fs.insert_tree(
+            path!("/project"),
+            json!({
+                ".git": {},
+                "tracked": "tracked\n",
+            }),
+        )
+        .await;
+
+        fs.set_head_and_index_for_repo(
+            path!("/project/.git").as_ref(),
+            &[("tracked", "old tracked\n".into())],
+        );
+
+        let project = Project::test(fs.clone(), [Path::new(path!("/project"))], cx).await;
+        let window_handle =
+            cx.add_window(|window, cx| MultiWorkspace::test_new(project.clone(), window, cx));
+        let workspace = window_handle
+            .read_with(cx, |mw, _| mw.workspace().clone())
+            .unwrap();
+        let cx = &mut VisualTestContext::from_window(window_handle.into(), cx);
+        let panel = workspace.update_in(cx, GitPanel::new);
+
+        let handle = cx.update_window_entity(&panel, |panel, _, _| {
+            std::mem::replace(&mut panel.update_visible_entries_task, Task::ready(()))
+        });
+        cx.executor().advance_clock(2 * UPDATE_DEBOUNCE);
+        handle.await;
+
+        // Case 1: Focus the commit editor — should have "CommitEditor" but NOT "menu"/"ChangesList"
+        panel.update_in(cx, |panel, window, cx| {
+            panel.focus_editor(&FocusEditor, window, cx);
+            let editor_is_focused = panel.commit_editor.read(cx).is_focused(window);
+            assert!(
+                editor_is_focused,
+                "commit editor should be focused after focus_editor action"
+            );
+            let context = panel.dispatch_context(window, cx);
+            assert!(
+                context.contains("GitPanel"),
+                "should always have GitPanel context"
+            );
+            assert!(
+                context.contains("CommitEditor"),
+                "should have CommitEditor context when commit editor is focused"
+            );
+            assert!(
+                !context.contains("menu"),
+                "should not have menu context when commit editor is focused"
+            );
+            assert!(
+                !context.contains("ChangesList"),
+                "should not have ChangesList context when commit editor is focused"
+            );
+        });
+
+        // Case 2: Focus the panel's focus handle directly — should have "menu" and "ChangesList".
+        // We force a draw via simulate_resize to ensure the dispatch tree is populated,
+        // since contains_focused() depends on the rendered dispatch tree.
+        panel.update_in(cx, |panel, window, cx| {
+            panel.focus_handle.focus(window, cx);
+        });
+        cx.simulate_resize(gpui::size(px(800.), px(600.)));
+
+        panel.update_in(cx, |panel, window, cx| {
+            let context = panel.dispatch_context(window, cx);
+            assert!(
+                context.contains("GitPanel"),
+                "should always have GitPanel context"
+            );
+            assert!(
+                context.contains("menu"),
+                "should have menu context when changes list is focused"
+            );
+            assert!(
+                context.contains("ChangesList"),
+                "should have ChangesList context when changes list is focused"
+            );
+            assert!(
+                !context.contains("CommitEditor"),
+                "should not have CommitEditor context when changes list is focused"
+            );
+        });
+
+        // Case 3: Switch back to commit editor and verify context switches correctly
+        panel.update_in(cx, |panel, window, cx| {
+            panel.focus_editor(&FocusEditor, window, cx);
+        });
+
+        panel.update_in(cx, |panel, window, cx| {
+            let context = panel.dispatch_context(window, cx);
+            assert!(
+                context.contains("CommitEditor"),
+                "should have CommitEditor after switching focus back to editor"
+            );
+            assert!(
+                !context.contains("menu"),
+                "should not have menu after switching focus back to editor"
+            );
+        });
+
+        // Case 4: Re-focus changes list and verify it transitions back correctly
+        panel.update_in(cx, |panel, window, cx| {
+            panel.focus_handle.focus(window, cx);
+        });
+        cx.simulate_resize(gpui::size(px(800.), px(600.)));
+
+        panel.update_in(cx, |panel, window, cx| {
+            assert!(
+                panel.focus_handle.contains_focused(window, cx),
+                "panel focus handle should report contains_focused when directly focused"
+            );
+            let context = panel.dispatch_context(window, cx);
+            assert!(
+                context.contains("menu"),
+                "should have menu context after re-focusing changes list"
+            );
+            assert!(
+                context.contains("ChangesList"),
+                "should have ChangesList context after re-focusing changes list"
+            );
+        });
+    }
+
+    #[gpui::test]
+    async fn test_mark_entries_for_selected_commit(cx: &mut TestAppContext) {
+        init_test(cx);
+
+        let fs = FakeFs::new(cx.background_executor.clone());
+        fs.insert_tree(
+            path!("/project"),
+            json!({
+                ".git": {},
+                "a.rs": "fn a() {}",
+                "b.rs": "fn b() {}",
+                "c.rs": "fn c() {}",
+                "d.rs": "fn d() {}",
+            }),
+        )
+        .await;
+
+        fs.set_status_for_repo(
+            path!("/project/.git").as_ref(),
+            &[
+                ("a.rs", StatusCode::Modified.worktree()),
+                ("b.rs", StatusCode::Modified.worktree()),
+                ("c.rs", StatusCode::Modified.worktree()),
+                ("d.rs", StatusCode::Modified.worktree()),
+            ],
+        );
+
+        let project = Project::test(fs.clone(), [Path::new(path!("/project"))], cx).await;
+        let window_handle =
+            cx.add_window(|window, cx| MultiWorkspace::test_new(project.clone(), window, cx));
+        let workspace = window_handle
+            .read_with(cx, |mw, _| mw.workspace().clone())
+            .unwrap();
+        let cx = &mut VisualTestContext::from_window(window_handle.into(), cx);
+
+        cx.read(|cx| {
+            project
+                .read(cx)
+                .worktrees(cx)
+                .next()
+                .unwrap()
+                .read(cx)
+                .as_local()
+                .unwrap()
+                .scan_complete()
+        })
+        .await;
+
+        cx.executor().run_until_parked();
+
+        let panel = workspace.update_in(cx, GitPanel::new);
+
+        let handle = cx.update_window_entity(&panel, |panel, _, _| {
+            std::mem::replace(&mut panel.update_visible_entries_task, Task::ready(()))
+        });
+        cx.executor().advance_clock(2 * UPDATE_DEBOUNCE);
+        handle.await;
+
+        let marked = |panel: &GitPanel| {
+            panel
+                .marked_paths()
+                .iter()
+                .map(|path| path.as_unix_str().to_string())
+                .collect::<Vec<_>>()
+        };
+
+        panel.update(cx, |panel, cx| {
+            assert_eq!(panel.commit_button_title(), "Commit Tracked");
+
+            let b_index = panel.entry_by_path(&repo_path("b.rs")).unwrap();
+            let d_index = panel.entry_by_path(&repo_path("d.rs")).unwrap();
+            panel.selected_entry = Some(b_index);
+            panel.mark_entries_through(d_index, cx);
+            assert_eq!(marked(panel), ["b.rs", "c.rs", "d.rs"]);
+            assert_eq!(panel.selected_entry, Some(b_index));
+
+            let a_index = panel.entry_by_path(&repo_path("a.rs")).unwrap();
+            panel.mark_entries_through(a_index, cx);
+            assert_eq!(marked(panel), ["a.rs", "b.rs"]);
+
+            let c_index = panel.entry_by_path(&repo_path("c.rs")).unwrap();
+            panel.toggle_marked_entry(c_index, cx);
+            panel.toggle_marked_entry(a_index, cx);
+            assert_eq!(marked(panel), ["b.rs", "c.rs"]);
+            assert_eq!(panel.commit_button_title(), "Commit Selected");
+        });
+
+        fs.set_status_for_repo(
+            path!("/project/.git").as_ref(),
+            &[
+                ("a.rs", StatusCode::Modified.worktree()),
+                ("c.rs", StatusCode::Modified.worktree()),
+            ],
+        );
+        cx.executor().advance_clock(2 * UPDATE_DEBOUNCE);
+        cx.run_until_parked();
+
+        panel.update(cx, |panel, _| {
+            assert_eq!(marked(panel), ["c.rs"]);
+        });
+    }
+
+    #[gpui::test]
+    async fn test_stash_marked_entries(cx: &mut TestAppContext) {
+        init_test(cx);
+
+        let fs = FakeFs::new(cx.background_executor.clone());
+        fs.insert_tree(
+            path!("/project"),
+            json!({
+                ".git": {},
+                "a.rs": "fn a() {}",
+                "b.rs": "fn b() {}",
+                "c.rs": "fn c() {}",
+            }),
+        )
+        .await;
+        fs.set_status_for_repo(
+            path!("/project/.git").as_ref(),
+            &[
+                ("a.rs", StatusCode::Modified.worktree()),
+                ("b.rs", StatusCode::Modified.worktree()),
+                ("c.rs", FileStatus::Untracked),
+            ],
+        );
+
+        let project = Project::test(fs.clone(), [Path::new(path!("/project"))], cx).await;
+        let window_handle =
+            cx.add_window(|window, cx| MultiWorkspace::test_new(project.clone(), window, cx));
+        let workspace = window_handle
+            .read_with(cx, |mw, _| mw.workspace().clone())
+            .unwrap();
+        let cx = &mut VisualTestContext::from_window(window_handle.into(), cx);
+        cx.run_until_parked();
 
+        let panel = workspace.update_in(cx, GitPanel::new);
         let handle = cx.update_window_entity(&panel, |panel, _, _| {
             std::mem::replace(&mut panel.update_visible_entries_task, Task::ready(()))
         });
         cx.executor().advance_clock(2 * UPDATE_DEBOUNCE);
         handle.await;
 
-        // GitPanel
-        // - Tracked:
-        // - [] tracked
-        // - Untracked
-        // - [] untracked
-        //
-        // The commit message should now read:
-        // "Update tracked"
-        let message = panel.update(cx, |panel, cx| panel.suggest_commit_message(cx));
-        assert_eq!(message, Some("Update tracked".to_string()));
+        panel.update_in(cx, |panel, window, cx| {
+            let b_index = panel.entry_by_path(&repo_path("b.rs")).unwrap();
+            let c_index = panel.entry_by_path(&repo_path("c.rs")).unwrap();
+            panel.toggle_marked_entry(b_index, cx);
+            panel.toggle_marked_entry(c_index, cx);
+
+            // An unmarked file is stashed on its own.
+            let c_selected = panel.selected_entry;
+            panel.selected_entry = panel.entry_by_path(&repo_path("a.rs"));
+            assert_eq!(panel.stash_selected_paths(), [repo_path("a.rs")]);
+            panel.selected_entry = c_selected;
+
+            panel.stash_selected(&git::StashSelected, window, cx);
+        });
+        let modal = workspace.update(cx, |workspace, cx| {
+            workspace.active_modal::<StashModal>(cx).unwrap()
+        });
+        modal.update_in(cx, |modal, window, cx| {
+            modal.confirm(&menu::Confirm, window, cx)
+        });
+        cx.run_until_parked();
+
+        let pushes = fs
+            .with_git_state(path!("/project/.git").as_ref(), false, |state| {
+                state.stash_pushes.clone()
+            })
+            .unwrap();
+        assert_eq!(
+            pushes,
+            [(
+                vec![repo_path("b.rs"), repo_path("c.rs")],
+                StashOptions {
+                    message: None,
+                    include_untracked: true,
+                },
+            )]
+        );
     }
 
     #[gpui::test]
-    async fn test_dispatch_context_with_focus_states(cx: &mut TestAppContext) {
+    async fn test_renamed_file_is_one_entry(cx: &mut TestAppContext) {
         init_test(cx);
-
         let fs = FakeFs::new(cx.background_executor.clone());
         fs.insert_tree(
             path!("/project"),
             json!({
                 ".git": {},
-                "tracked": "tracked\n",
+                "new.txt": "one\ntwo\n",
             }),
         )
         .await;
-
-        fs.set_head_and_index_for_repo(
-            path!("/project/.git").as_ref(),
-            &[("tracked", "old tracked\n".into())],
-        );
+        let dot_git = Path::new(path!("/project/.git"));
+        fs.set_head_for_repo(dot_git, &[("old.txt", "one\n".into())], "deadbeef");
+        fs.set_index_for_repo(dot_git, &[("new.txt", "one\ntwo\n".into())]);
+        fs.with_git_state(dot_git, true, |state| {
+            state.renames = vec![FileRename {
+                old_path: repo_path("old.txt"),
+                new_path: repo_path("new.txt"),
+                is_copy: false,
+            }];
+        })
+        .unwrap();
 
         let project = Project::test(fs.clone(), [Path::new(path!("/project"))], cx).await;
         let window_handle =
@@ -7885,106 +12487,226 @@ mod tests {
             .read_with(cx, |mw, _| mw.workspace().clone())
             .unwrap();
         let cx = &mut VisualTestContext::from_window(window_handle.into(), cx);
+        cx.executor().run_until_parked();
+
         let panel = workspace.update_in(cx, GitPanel::new);
+        cx.executor().advance_clock(2 * UPDATE_DEBOUNCE);
+        cx.run_until_parked();
 
-        let handle = cx.update_window_entity(&panel, |panel, _, _| {
-            std::mem::replace(&mut panel.update_visible_entries_task, Task::ready(()))
+        panel.read_with(cx, |panel, _| {
+            assert!(panel.entry_by_path(&repo_path("new.txt")).is_some());
+            assert!(panel.entry_by_path(&repo_path("old.txt")).is_none());
         });
+
+        let repository =
+            project.read_with(cx, |project, cx| project.active_repository(cx).unwrap());
+        repository
+            .update(cx, |repository, cx| {
+                repository.unstage_entries(vec![repo_path("new.txt")], cx)
+            })
+            .await
+            .unwrap();
+        let index_paths = fs
+            .with_git_state(dot_git, false, |state| {
+                state.index_contents.keys().cloned().collect::<Vec<_>>()
+            })
+            .unwrap();
+        assert_eq!(index_paths, [repo_path("old.txt")]);
+    }
+
+    #[gpui::test]
+    async fn test_toggle_skip_worktree(cx: &mut TestAppContext) {
+        init_test(cx);
+        let fs = FakeFs::new(cx.background_executor.clone());
+        fs.insert_tree(
+            path!("/project"),
+            json!({
+                ".git": {},
+                "config.toml": "local = true\n",
+            }),
+        )
+        .await;
+        let dot_git = Path::new(path!("/project/.git"));
+        fs.set_head_and_index_for_repo(dot_git, &[("config.toml", "local = false\n".into())]);
+
+        let project = Project::test(fs.clone(), [Path::new(path!("/project"))], cx).await;
+        let window_handle =
+            cx.add_window(|window, cx| MultiWorkspace::test_new(project.clone(), window, cx));
+        let workspace = window_handle
+            .read_with(cx, |mw, _| mw.workspace().clone())
+            .unwrap();
+        let cx = &mut VisualTestContext::from_window(window_handle.into(), cx);
+        cx.executor().run_until_parked();
+
+        let panel = workspace.update_in(cx, GitPanel::new);
         cx.executor().advance_clock(2 * UPDATE_DEBOUNCE);
-        handle.await;
+        cx.run_until_parked();
 
-        // Case 1: Focus the commit editor — should have "CommitEditor" but NOT "menu"/"ChangesList"
         panel.update_in(cx, |panel, window, cx| {
-            panel.focus_editor(&FocusEditor, window, cx);
-            let editor_is_focused = panel.commit_editor.read(cx).is_focused(window);
-            assert!(
-                editor_is_focused,
-                "commit editor should be focused after focus_editor action"
-            );
-            let context = panel.dispatch_context(window, cx);
-            assert!(
-                context.contains("GitPanel"),
-                "should always have GitPanel context"
-            );
-            assert!(
-                context.contains("CommitEditor"),
-                "should have CommitEditor context when commit editor is focused"
-            );
-            assert!(
-                !context.contains("menu"),
-                "should not have menu context when commit editor is focused"
-            );
-            assert!(
-                !context.contains("ChangesList"),
-                "should not have ChangesList context when commit editor is focused"
-            );
+            panel.selected_entry = panel.entry_by_path(&repo_path("config.toml"));
+            panel.toggle_skip_worktree(&git::ToggleSkipWorktree, window, cx);
         });
+        cx.run_until_parked();
 
-        // Case 2: Focus the panel's focus handle directly — should have "menu" and "ChangesList".
-        // We force a draw via simulate_resize to ensure the dispatch tree is populated,
-        // since contains_focused() depends on the rendered dispatch tree.
-        panel.update_in(cx, |panel, window, cx| {
-            panel.focus_handle.focus(window, cx);
+        let expected = [FlaggedFile {
+            path: repo_path("config.toml"),
+            assume_unchanged: false,
+            skip_worktree: true,
+        }];
+        assert_eq!(
+            fs.with_git_state(dot_git, false, |state| state.flagged_files.clone())
+                .unwrap(),
+            expected
+        );
+        let repository =
+            project.read_with(cx, |project, cx| project.active_repository(cx).unwrap());
+        repository.read_with(cx, |repository, _| {
+            assert_eq!(repository.flagged_files(), expected)
         });
-        cx.simulate_resize(gpui::size(px(800.), px(600.)));
 
         panel.update_in(cx, |panel, window, cx| {
-            let context = panel.dispatch_context(window, cx);
-            assert!(
-                context.contains("GitPanel"),
-                "should always have GitPanel context"
-            );
-            assert!(
-                context.contains("menu"),
-                "should have menu context when changes list is focused"
-            );
-            assert!(
-                context.contains("ChangesList"),
-                "should have ChangesList context when changes list is focused"
-            );
-            assert!(
-                !context.contains("CommitEditor"),
-                "should not have CommitEditor context when changes list is focused"
-            );
+            panel.toggle_skip_worktree(&git::ToggleSkipWorktree, window, cx);
         });
-
-        // Case 3: Switch back to commit editor and verify context switches correctly
-        panel.update_in(cx, |panel, window, cx| {
-            panel.focus_editor(&FocusEditor, window, cx);
+        cx.run_until_parked();
+        repository.read_with(cx, |repository, _| {
+            assert!(repository.flagged_files().is_empty())
         });
+    }
+
+    #[gpui::test]
+    async fn test_add_to_nearest_gitignore(cx: &mut TestAppContext) {
+        init_test(cx);
+        let fs = FakeFs::new(cx.background_executor.clone());
+        fs.insert_tree(
+            "/root",
+            json!({
+                "project": {
+                    ".git": {},
+                    "crates": {
+                        "app": {
+                            ".gitignore": "/target/",
+                            "build": {
+                                "output.log": "done"
+                            }
+                        }
+                    }
+                }
+            }),
+        )
+        .await;
+        fs.set_status_for_repo(
+            Path::new(path!("/root/project/.git")),
+            &[("crates/app/build/output.log", FileStatus::Untracked)],
+        );
+
+        let project = Project::test(fs.clone(), [Path::new(path!("/root/project"))], cx).await;
+        let window_handle =
+            cx.add_window(|window, cx| MultiWorkspace::test_new(project.clone(), window, cx));
+        let workspace = window_handle
+            .read_with(cx, |mw, _| mw.workspace().clone())
+            .unwrap();
+        let cx = &mut VisualTestContext::from_window(window_handle.into(), cx);
+        cx.executor().run_until_parked();
+
+        let panel = workspace.update_in(cx, GitPanel::new);
+        cx.executor().advance_clock(2 * UPDATE_DEBOUNCE);
+        cx.run_until_parked();
 
         panel.update_in(cx, |panel, window, cx| {
-            let context = panel.dispatch_context(window, cx);
-            assert!(
-                context.contains("CommitEditor"),
-                "should have CommitEditor after switching focus back to editor"
-            );
-            assert!(
-                !context.contains("menu"),
-                "should not have menu after switching focus back to editor"
+            panel.selected_entry = panel.entry_by_path(&repo_path("crates/app/build/output.log"));
+            panel.add_to_gitignore(
+                &git::AddToGitignore {
+                    pattern: GitignorePatternKind::Extension,
+                },
+                window,
+                cx,
             );
         });
-
-        // Case 4: Re-focus changes list and verify it transitions back correctly
+        cx.run_until_parked();
         panel.update_in(cx, |panel, window, cx| {
-            panel.focus_handle.focus(window, cx);
+            panel.add_to_gitignore(
+                &git::AddToGitignore {
+                    pattern: GitignorePatternKind::Directory,
+                },
+                window,
+                cx,
+            );
         });
-        cx.simulate_resize(gpui::size(px(800.), px(600.)));
+        cx.run_until_parked();
+
+        assert_eq!(
+            fs.load(Path::new(path!("/root/project/crates/app/.gitignore")))
+                .await
+                .unwrap(),
+            "/target/\n*.log\n/build/\n"
+        );
+        assert!(
+            !fs.is_file(Path::new(path!("/root/project/.gitignore")))
+                .await
+        );
+    }
+
+    #[gpui::test]
+    async fn test_ignore_directory_above_its_gitignore(cx: &mut TestAppContext) {
+        init_test(cx);
+        let fs = FakeFs::new(cx.background_executor.clone());
+        fs.insert_tree(
+            "/root",
+            json!({
+                "project": {
+                    ".git": {},
+                    ".gitignore": "/target/\n",
+                    "crates": {
+                        "app": {
+                            ".gitignore": "*.log\n",
+                            "notes.txt": "draft"
+                        }
+                    }
+                }
+            }),
+        )
+        .await;
+        fs.set_status_for_repo(
+            Path::new(path!("/root/project/.git")),
+            &[("crates/app/notes.txt", FileStatus::Untracked)],
+        );
+
+        let project = Project::test(fs.clone(), [Path::new(path!("/root/project"))], cx).await;
+        let window_handle =
+            cx.add_window(|window, cx| MultiWorkspace::test_new(project.clone(), window, cx));
+        let workspace = window_handle
+            .read_with(cx, |mw, _| mw.workspace().clone())
+            .unwrap();
+        let cx = &mut VisualTestContext::from_window(window_handle.into(), cx);
+        cx.executor().run_until_parked();
+
+        let panel = workspace.update_in(cx, GitPanel::new);
+        cx.executor().advance_clock(2 * UPDATE_DEBOUNCE);
+        cx.run_until_parked();
 
         panel.update_in(cx, |panel, window, cx| {
-            assert!(
-                panel.focus_handle.contains_focused(window, cx),
-                "panel focus handle should report contains_focused when directly focused"
-            );
-            let context = panel.dispatch_context(window, cx);
-            assert!(
-                context.contains("menu"),
-                "should have menu context after re-focusing changes list"
-            );
-            assert!(
-                context.contains("ChangesList"),
-                "should have ChangesList context after re-focusing changes list"
+            panel.selected_entry = panel.entry_by_path(&repo_path("crates/app/notes.txt"));
+            panel.add_to_gitignore(
+                &git::AddToGitignore {
+                    pattern: GitignorePatternKind::Directory,
+                },
+                window,
+                cx,
             );
         });
+        cx.run_until_parked();
+
+        assert_eq!(
+            fs.load(Path::new(path!("/root/project/.gitignore")))
+                .await
+                .unwrap(),
+            "/target/\n/crates/app/\n"
+        );
+        assert_eq!(
+            fs.load(Path::new(path!("/root/project/crates/app/.gitignore")))
+                .await
+                .unwrap(),
+            "*.log\n"
+        );
     }
 }
@@ -30,6 +30,7 @@ pub struct GitPanelSettings {
     pub diff_stats: bool,
     pub show_count_badge: bool,
     pub starts_open: bool,
+    pub max_subject_length: usize,
 }
 
 impl ScrollbarVisibility for GitPanelSettings {
@@ -68,6 +69,7 @@ impl Settings for GitPanelSettings {
             diff_stats: git_panel.diff_stats.unwrap(),
             show_count_badge: git_panel.show_count_badge.unwrap(),
             starts_open: git_panel.starts_open.unwrap(),
+            max_subject_length: git_panel.max_subject_length.unwrap(),
         }
     }
 }
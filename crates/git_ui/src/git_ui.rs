@@ -2,7 +2,7 @@ use anyhow::anyhow;
 use commit_modal::CommitModal;
 use editor::{Editor, actions::DiffClipboardWithSelectionData};
 
-use project::ProjectPath;
+use project::{Project, ProjectPath, project_settings::ProjectSettings};
 use ui::{
     Headline, HeadlineSize, Icon, IconName, IconSize, IntoElement, ParentElement, Render, Styled,
     StyledExt, div, h_flex, rems, v_flex,
@@ -12,39 +12,68 @@ mod blame_ui;
 pub mod clone;
 
 use git::{
-    repository::{Branch, Upstream, UpstreamTracking, UpstreamTrackingStatus},
+    SHORT_SHA_LENGTH,
+    repository::{Branch, PullStrategy, Upstream, UpstreamTracking, UpstreamTrackingStatus},
     status::{FileStatus, StatusCode, UnmergedStatus, UnmergedStatusCode},
 };
 use gpui::{
-    App, Context, DismissEvent, Entity, EventEmitter, FocusHandle, Focusable, SharedString, Window,
+    App, Context, DismissEvent, Entity, EventEmitter, FocusHandle, Focusable, SharedString,
+    WeakEntity, Window,
 };
 use menu::{Cancel, Confirm};
 use project::git_store::Repository;
 use project_diff::ProjectDiff;
+use settings::{Settings as _, SettingsLocation};
+use std::sync::Arc;
 use ui::prelude::*;
 use workspace::{ModalView, Workspace, notifications::DetachAndPromptErr};
 use zed_actions;
 
-use crate::{git_panel::GitPanel, text_diff_view::TextDiffView};
+use crate::{git_panel::PushBehavior, text_diff_view::TextDiffView};
 
 mod askpass_modal;
+pub mod bisect;
+pub mod branch_comparison_picker;
+pub mod branch_comparison_view;
 pub mod branch_picker;
+mod commit_message_history;
+mod commit_message_suggestion;
 mod commit_modal;
+pub mod commit_range_view;
 pub mod commit_tooltip;
 pub mod commit_view;
-mod conflict_view;
+pub mod conflict_view;
+mod conventional_commits;
+pub mod diff_filter;
+pub mod directory_diff_view;
+pub mod file_comparison_picker;
 pub mod file_diff_view;
 pub mod file_history_view;
+pub mod git_log_view;
 pub mod git_panel;
 mod git_panel_settings;
 pub mod git_picker;
+pub mod gitignore;
+pub mod interactive_rebase_view;
+mod issue_references;
+pub mod line_history_view;
 pub mod multi_diff_view;
+pub mod patch_view;
 pub mod picker_prompt;
 pub mod project_diff;
+mod pull_request_modal;
+mod pull_request_picker;
+pub mod reflog_view;
+pub mod remote_list;
 pub(crate) mod remote_output;
 pub mod repository_selector;
+pub mod reset_modal;
+pub mod revision_comparison_picker;
+pub mod sparse_checkout_modal;
 pub mod stash_picker;
+pub mod tag_picker;
 pub mod text_diff_view;
+pub mod three_way_diff_view;
 pub mod worktree_picker;
 
 pub fn init(cx: &mut App) {
@@ -53,6 +82,7 @@ pub fn init(cx: &mut App) {
 
     cx.observe_new(|editor: &mut Editor, _, cx| {
         conflict_view::register_editor(editor, editor.buffer().clone(), cx);
+        git_panel::register_editor(cx);
     })
     .detach();
 
@@ -60,8 +90,17 @@ pub fn init(cx: &mut App) {
         ProjectDiff::register(workspace, cx);
         CommitModal::register(workspace);
         git_panel::register(workspace);
+        bisect::register(workspace);
+        branch_comparison_picker::register(workspace);
+        branch_comparison_view::register(workspace);
+        commit_range_view::register(workspace);
         repository_selector::register(workspace);
         git_picker::register(workspace);
+        file_comparison_picker::register(workspace);
+        file_diff_view::register(workspace);
+        line_history_view::register(workspace);
+        patch_view::register(workspace);
+        revision_comparison_picker::register(workspace);
         conflict_view::register_conflict_notification(workspace, cx);
 
         let project = workspace.project().read(cx);
@@ -78,6 +117,24 @@ pub fn init(cx: &mut App) {
                     }
                 },
             );
+            workspace.register_action(
+                |workspace, _: &zed_actions::git::CheckoutPullRequest, window, cx| {
+                    if let Some(panel) = workspace.panel::<git_panel::GitPanel>(cx) {
+                        panel.update(cx, |panel, cx| {
+                            panel.open_pull_request_picker(window, cx);
+                        });
+                    }
+                },
+            );
+            workspace.register_action(
+                |workspace, _: &zed_actions::git::OpenBranchOnRemote, window, cx| {
+                    if let Some(panel) = workspace.panel::<git_panel::GitPanel>(cx) {
+                        panel.update(cx, |panel, cx| {
+                            panel.open_branch_on_remote(window, cx);
+                        });
+                    }
+                },
+            );
             workspace.register_action(|workspace, _: &git::Fetch, window, cx| {
                 let Some(panel) = workspace.panel::<git_panel::GitPanel>(cx) else {
                     return;
@@ -99,7 +156,7 @@ pub fn init(cx: &mut App) {
                     return;
                 };
                 panel.update(cx, |panel, cx| {
-                    panel.push(false, false, window, cx);
+                    panel.push(PushBehavior::default(), window, cx);
                 });
             });
             workspace.register_action(|workspace, _: &git::PushTo, window, cx| {
@@ -107,7 +164,14 @@ pub fn init(cx: &mut App) {
                     return;
                 };
                 panel.update(cx, |panel, cx| {
-                    panel.push(false, true, window, cx);
+                    panel.push(
+                        PushBehavior {
+                            select_remote: true,
+                            ..PushBehavior::default()
+                        },
+                        window,
+                        cx,
+                    );
                 });
             });
             workspace.register_action(|workspace, _: &git::ForcePush, window, cx| {
@@ -115,7 +179,45 @@ pub fn init(cx: &mut App) {
                     return;
                 };
                 panel.update(cx, |panel, cx| {
-                    panel.push(true, false, window, cx);
+                    panel.push(
+                        PushBehavior {
+                            force: true,
+                            ..PushBehavior::default()
+                        },
+                        window,
+                        cx,
+                    );
+                });
+            });
+            workspace.register_action(|workspace, _: &git::PushSetUpstream, window, cx| {
+                let Some(panel) = workspace.panel::<git_panel::GitPanel>(cx) else {
+                    return;
+                };
+                panel.update(cx, |panel, cx| {
+                    panel.push(
+                        PushBehavior {
+                            select_remote: true,
+                            set_upstream: true,
+                            ..PushBehavior::default()
+                        },
+                        window,
+                        cx,
+                    );
+                });
+            });
+            workspace.register_action(|workspace, _: &git::PushWithTags, window, cx| {
+                let Some(panel) = workspace.panel::<git_panel::GitPanel>(cx) else {
+                    return;
+                };
+                panel.update(cx, |panel, cx| {
+                    panel.push(
+                        PushBehavior {
+                            follow_tags: true,
+                            ..PushBehavior::default()
+                        },
+                        window,
+                        cx,
+                    );
                 });
             });
             workspace.register_action(|workspace, _: &git::Pull, window, cx| {
@@ -123,7 +225,15 @@ pub fn init(cx: &mut App) {
                     return;
                 };
                 panel.update(cx, |panel, cx| {
-                    panel.pull(false, window, cx);
+                    panel.pull(None, window, cx);
+                });
+            });
+            workspace.register_action(|workspace, _: &git::Sync, window, cx| {
+                let Some(panel) = workspace.panel::<git_panel::GitPanel>(cx) else {
+                    return;
+                };
+                panel.update(cx, |panel, cx| {
+                    panel.sync(window, cx);
                 });
             });
             workspace.register_action(|workspace, _: &git::PullRebase, window, cx| {
@@ -131,10 +241,17 @@ pub fn init(cx: &mut App) {
                     return;
                 };
                 panel.update(cx, |panel, cx| {
-                    panel.pull(true, window, cx);
+                    panel.pull(Some(PullStrategy::Rebase), window, cx);
                 });
             });
+            interactive_rebase_view::register(workspace);
+            remote_list::register(workspace);
         }
+        git_log_view::register(workspace);
+        reflog_view::register(workspace);
+        sparse_checkout_modal::register(workspace);
+        stash_picker::register(workspace);
+        tag_picker::register(workspace);
         workspace.register_action(|workspace, action: &git::StashAll, window, cx| {
             let Some(panel) = workspace.panel::<git_panel::GitPanel>(cx) else {
                 return;
@@ -192,12 +309,9 @@ pub fn init(cx: &mut App) {
             });
         });
         workspace.register_action(|workspace, _action: &git::Clone, window, cx| {
-            let Some(panel) = workspace.panel::<git_panel::GitPanel>(cx) else {
-                return;
-            };
-
+            let weak_workspace = workspace.weak_handle();
             workspace.toggle_modal(window, cx, |window, cx| {
-                GitCloneModal::show(panel, window, cx)
+                GitCloneModal::show(weak_workspace, window, cx)
             });
         });
         workspace.register_action(|workspace, _: &git::OpenModifiedFiles, window, cx| {
@@ -206,6 +320,14 @@ pub fn init(cx: &mut App) {
         workspace.register_action(|workspace, _: &git::RenameBranch, window, cx| {
             rename_current_branch(workspace, window, cx);
         });
+        workspace.register_action(|workspace, _: &git::CreateBranchHere, window, cx| {
+            let Some(repo) = resolve_active_repository(workspace, cx) else {
+                return;
+            };
+            workspace.toggle_modal(window, cx, |window, cx| {
+                CreateBranchHereModal::new(repo, window, cx)
+            });
+        });
         workspace.register_action(
             |workspace, action: &DiffClipboardWithSelectionData, window, cx| {
                 if let Some(task) = TextDiffView::open(action, workspace, window, cx) {
@@ -248,6 +370,67 @@ pub fn init(cx: &mut App) {
                 cx,
             );
         });
+        workspace.register_action(|workspace, _: &git::InitSubmodules, window, cx| {
+            let Some(panel) = workspace.panel::<git_panel::GitPanel>(cx) else {
+                return;
+            };
+            panel.update(cx, |panel, cx| panel.init_submodules(window, cx));
+        });
+        workspace.register_action(|workspace, _: &git::UpdateSubmodules, window, cx| {
+            let Some(panel) = workspace.panel::<git_panel::GitPanel>(cx) else {
+                return;
+            };
+            panel.update(cx, |panel, cx| {
+                panel.update_submodules(Vec::new(), false, window, cx)
+            });
+        });
+        workspace.register_action(|workspace, _: &git::SyncSubmodules, _, cx| {
+            let Some(panel) = workspace.panel::<git_panel::GitPanel>(cx) else {
+                return;
+            };
+            panel.update(cx, |panel, cx| panel.sync_submodules(Vec::new(), cx));
+        });
+        workspace.register_action(|workspace, _: &git::FetchLfsObject, window, cx| {
+            let Some(panel) = workspace.panel::<git_panel::GitPanel>(cx) else {
+                return;
+            };
+            let Some(editor) = workspace.active_item_as::<Editor>(cx) else {
+                return;
+            };
+            let Some(buffer) = editor.read(cx).buffer().read(cx).as_singleton() else {
+                return;
+            };
+            let Some((repo, repo_path)) = workspace
+                .project()
+                .read(cx)
+                .git_store()
+                .read(cx)
+                .repository_and_path_for_buffer_id(buffer.read(cx).remote_id(), cx)
+            else {
+                return;
+            };
+            panel.update(cx, |panel, cx| {
+                panel.fetch_lfs_objects(repo, vec![repo_path], window, cx);
+            });
+        });
+        workspace.register_action(|workspace, _: &git::ExplainIgnore, window, cx| {
+            let Some(editor) = workspace.active_item_as::<Editor>(cx) else {
+                return;
+            };
+            let Some(buffer) = editor.read(cx).buffer().read(cx).as_singleton() else {
+                return;
+            };
+            let Some((repo, repo_path)) = workspace
+                .project()
+                .read(cx)
+                .git_store()
+                .read(cx)
+                .repository_and_path_for_buffer_id(buffer.read(cx).remote_id(), cx)
+            else {
+                return;
+            };
+            gitignore::explain_ignore(workspace, repo, repo_path, window, cx);
+        });
     })
     .detach();
 }
@@ -307,18 +490,38 @@ pub fn resolve_active_repository(workspace: &Workspace, cx: &App) -> Option<Enti
         .or_else(|| project.active_repository(cx))
 }
 
+/// The settings of the worktree containing the repository, so that a project can configure git
+/// for its own repositories.
+pub(crate) fn repository_settings<'a>(
+    project: &Entity<Project>,
+    repository: &Entity<Repository>,
+    cx: &'a App,
+) -> &'a ProjectSettings {
+    let work_directory = repository.read(cx).work_directory_abs_path.clone();
+    match project.read(cx).find_worktree(&work_directory, cx) {
+        Some((worktree, path)) => ProjectSettings::get(
+            Some(SettingsLocation {
+                worktree_id: worktree.read(cx).id(),
+                path: &path,
+            }),
+            cx,
+        ),
+        None => ProjectSettings::get_global(cx),
+    }
+}
+
 pub fn git_status_icon(status: FileStatus) -> impl IntoElement {
     GitStatusIcon::new(status)
 }
 
-struct RenameBranchModal {
+pub(crate) struct RenameBranchModal {
     current_branch: SharedString,
     editor: Entity<Editor>,
     repo: Entity<Repository>,
 }
 
 impl RenameBranchModal {
-    fn new(
+    pub(crate) fn new(
         current_branch: String,
         repo: Entity<Repository>,
         window: &mut Window,
@@ -399,6 +602,87 @@ impl Render for RenameBranchModal {
     }
 }
 
+/// Asks for the name of a branch to create at HEAD and switch to.
+struct CreateBranchHereModal {
+    editor: Entity<Editor>,
+    repo: Entity<Repository>,
+}
+
+impl CreateBranchHereModal {
+    fn new(repo: Entity<Repository>, window: &mut Window, cx: &mut Context<Self>) -> Self {
+        let editor = cx.new(|cx| {
+            let mut editor = Editor::single_line(window, cx);
+            editor.set_placeholder_text("New branch name", window, cx);
+            editor
+        });
+        Self { editor, repo }
+    }
+
+    fn cancel(&mut self, _: &Cancel, _window: &mut Window, cx: &mut Context<Self>) {
+        cx.emit(DismissEvent);
+    }
+
+    fn confirm(&mut self, _: &Confirm, window: &mut Window, cx: &mut Context<Self>) {
+        let branch_name = self.editor.read(cx).text(cx).trim().replace(' ', "-");
+        if branch_name.is_empty() {
+            return;
+        }
+
+        let repo = self.repo.clone();
+        cx.spawn(async move |_, cx| {
+            repo.update(cx, |repo, _| repo.create_branch(branch_name, None))
+                .await
+                .map_err(|_| anyhow!("Operation was canceled"))?
+        })
+        .detach_and_prompt_err("Failed to create branch", window, cx, |_, _, _| None);
+        cx.emit(DismissEvent);
+    }
+}
+
+impl EventEmitter<DismissEvent> for CreateBranchHereModal {}
+impl ModalView for CreateBranchHereModal {}
+impl Focusable for CreateBranchHereModal {
+    fn focus_handle(&self, cx: &App) -> FocusHandle {
+        self.editor.focus_handle(cx)
+    }
+}
+
+impl Render for CreateBranchHereModal {
+    fn render(&mut self, _: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let head_sha = self.repo.read(cx).head_commit.as_ref().map(|commit| {
+            commit
+                .sha
+                .chars()
+                .take(SHORT_SHA_LENGTH)
+                .collect::<String>()
+        });
+
+        v_flex()
+            .key_context("CreateBranchHereModal")
+            .on_action(cx.listener(Self::cancel))
+            .on_action(cx.listener(Self::confirm))
+            .elevation_2(cx)
+            .w(rems(34.))
+            .child(
+                h_flex()
+                    .px_3()
+                    .pt_2()
+                    .pb_1()
+                    .w_full()
+                    .gap_1p5()
+                    .child(Icon::new(IconName::GitBranchPlus).size(IconSize::XSmall))
+                    .child(
+                        Headline::new(match head_sha {
+                            Some(sha) => format!("Create Branch at {sha}"),
+                            None => "Create Branch Here".to_string(),
+                        })
+                        .size(HeadlineSize::XSmall),
+                    ),
+            )
+            .child(div().px_3().pb_3().w_full().child(self.editor.clone()))
+    }
+}
+
 fn rename_current_branch(
     workspace: &mut Workspace,
     window: &mut Window,
@@ -449,7 +733,13 @@ fn render_remote_button(
                 id,
                 ahead,
             )),
-            (ahead, behind) => Some(remote_button::render_pull_button(
+            (0, behind) => Some(remote_button::render_pull_button(
+                keybinding_target,
+                id,
+                0,
+                behind,
+            )),
+            (ahead, behind) => Some(remote_button::render_sync_button(
                 keybinding_target,
                 id,
                 ahead,
@@ -556,6 +846,34 @@ mod remote_button {
         )
     }
 
+    pub fn render_sync_button(
+        keybinding_target: Option<FocusHandle>,
+        id: SharedString,
+        ahead: u32,
+        behind: u32,
+    ) -> SplitButton {
+        split_button(
+            id,
+            "Sync",
+            ahead as usize,
+            behind as usize,
+            None,
+            keybinding_target.clone(),
+            move |_, window, cx| {
+                window.dispatch_action(Box::new(git::Sync), cx);
+            },
+            move |_window, cx| {
+                git_action_tooltip(
+                    "Pull remote changes, then push committed changes",
+                    &git::Sync,
+                    "git pull && git push",
+                    keybinding_target.clone(),
+                    cx,
+                )
+            },
+        )
+    }
+
     pub fn render_publish_button(
         keybinding_target: Option<FocusHandle>,
         id: SharedString,
@@ -646,6 +964,8 @@ mod remote_button {
                         .when_some(keybinding_target.clone(), |el, keybinding_target| {
                             el.context(keybinding_target)
                         })
+                        .action("Sync", git::Sync.boxed_clone())
+                        .separator()
                         .action("Fetch", git::Fetch.boxed_clone())
                         .action("Fetch From", git::FetchFrom.boxed_clone())
                         .action("Pull", git::Pull.boxed_clone())
@@ -653,6 +973,8 @@ mod remote_button {
                         .separator()
                         .action("Push", git::Push.boxed_clone())
                         .action("Push To", git::PushTo.boxed_clone())
+                        .action("Push with Tags", git::PushWithTags.boxed_clone())
+                        .action("Push and Set Upstream", git::PushSetUpstream.boxed_clone())
                         .action("Force Push", git::ForcePush.boxed_clone())
                 }))
             })
@@ -813,13 +1135,17 @@ impl Component for GitStatusIcon {
 }
 
 struct GitCloneModal {
-    panel: Entity<GitPanel>,
+    workspace: WeakEntity<Workspace>,
     repo_input: Entity<Editor>,
     focus_handle: FocusHandle,
 }
 
 impl GitCloneModal {
-    pub fn show(panel: Entity<GitPanel>, window: &mut Window, cx: &mut Context<Self>) -> Self {
+    pub fn show(
+        workspace: WeakEntity<Workspace>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Self {
         let repo_input = cx.new(|cx| {
             let mut editor = Editor::single_line(window, cx);
             editor.set_placeholder_text("Enter repository URL…", window, cx);
@@ -830,7 +1156,7 @@ impl GitCloneModal {
         window.focus(&focus_handle, cx);
 
         Self {
-            panel,
+            workspace,
             repo_input,
             focus_handle,
         }
@@ -884,9 +1210,13 @@ impl Render for GitCloneModal {
             }))
             .on_action(cx.listener(|this, _: &menu::Confirm, window, cx| {
                 let repo = this.repo_input.read(cx).text(cx);
-                this.panel.update(cx, |panel, cx| {
-                    panel.git_clone(repo, window, cx);
-                });
+                clone::clone_and_open(
+                    repo.trim().to_string().into(),
+                    this.workspace.clone(),
+                    window,
+                    cx,
+                    Arc::new(|_workspace: &mut Workspace, _window, _cx| {}),
+                );
                 cx.emit(DismissEvent);
             }))
     }
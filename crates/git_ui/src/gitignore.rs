@@ -0,0 +1,280 @@
+use std::sync::Arc;
+
+use anyhow::{Context as _, Result};
+use editor::Editor;
+use git::{
+    GITIGNORE,
+    gitignore::{GitignorePatternKind, gitignore_pattern},
+    repository::RepoPath,
+};
+use gpui::{Action as _, App, Entity, FocusHandle, SharedString, Task};
+use language::Point;
+use notifications::status_toast::{StatusToast, ToastIcon};
+use project::{Project, git_store::Repository};
+use ui::{ContextMenu, prelude::*};
+use util::rel_path::RelPath;
+use workspace::{OpenOptions, OpenVisible, Workspace, notifications::DetachAndPromptErr};
+
+/// Adds a choice of what to ignore for an untracked path, for the context menus of the git and
+/// project panels, which both handle [`git::AddToGitignore`] for their selected entry.
+pub fn gitignore_menu_entries(
+    menu: ContextMenu,
+    panel_focus_handle: FocusHandle,
+    extension: Option<SharedString>,
+    has_directory: bool,
+) -> ContextMenu {
+    let action = |pattern| git::AddToGitignore { pattern }.boxed_clone();
+    menu.context(panel_focus_handle)
+        .action("This Path", action(GitignorePatternKind::ExactPath))
+        .when_some(extension, |menu, extension| {
+            menu.action(
+                format!("All *.{extension} Files"),
+                action(GitignorePatternKind::Extension),
+            )
+        })
+        .when(has_directory, |menu| {
+            menu.action("Its Directory", action(GitignorePatternKind::Directory))
+        })
+}
+
+/// Adds an entry ignoring `repo_path` to the nearest `.gitignore` above it, creating one at the
+/// root of the repository when there is none, and rescans the repository so that the path drops
+/// out of its statuses right away.
+pub fn add_to_gitignore(
+    project: Entity<Project>,
+    repository: Entity<Repository>,
+    repo_path: RepoPath,
+    is_dir: bool,
+    kind: GitignorePatternKind,
+    cx: &mut App,
+) -> Task<Result<()>> {
+    let fs = project.read(cx).fs().clone();
+    // Only local projects can look for the files themselves.
+    let is_local = project.read(cx).is_local();
+    let work_directory = repository.read(cx).snapshot().work_directory_abs_path;
+
+    cx.spawn(async move |cx| {
+        // Ignoring the directory of a file takes a `.gitignore` above that directory, not in it.
+        let (ignored_path, is_dir): (Arc<RelPath>, _) = match kind {
+            GitignorePatternKind::Directory if !is_dir => (
+                repo_path
+                    .parent()
+                    .filter(|directory| !directory.is_empty())
+                    .context("the path isn't in a directory")?
+                    .into_arc(),
+                true,
+            ),
+            _ => (repo_path.as_ref().clone(), is_dir),
+        };
+        let mut gitignore_directory = RelPath::empty().into_arc();
+        if is_local {
+            for ancestor in ignored_path
+                .parent()
+                .into_iter()
+                .flat_map(RelPath::ancestors)
+            {
+                let gitignore_abs_path =
+                    work_directory.join(ancestor.as_std_path()).join(GITIGNORE);
+                if fs.is_file(&gitignore_abs_path).await {
+                    gitignore_directory = ancestor.into_arc();
+                    break;
+                }
+            }
+        }
+        let pattern = gitignore_pattern(&ignored_path, is_dir, &gitignore_directory, kind)
+            .context("no such pattern for this path")?;
+        let gitignore_abs_path = work_directory
+            .join(gitignore_directory.as_std_path())
+            .join(GITIGNORE);
+
+        let buffer = project
+            .update(cx, |project, cx| {
+                project.open_local_buffer(gitignore_abs_path, cx)
+            })
+            .await?;
+        let edited = buffer.update(cx, |buffer, cx| {
+            let text = buffer.text();
+            if text
+                .lines()
+                .any(|line| line == pattern || line.trim() == pattern)
+            {
+                return false;
+            }
+            let separator = if text.is_empty() || text.ends_with('\n') {
+                ""
+            } else {
+                "\n"
+            };
+            let end = text.len();
+            buffer.edit([(end..end, format!("{separator}{pattern}\n"))], None, cx);
+            true
+        });
+        if edited {
+            project
+                .update(cx, |project, cx| project.save_buffer(buffer, cx))
+                .await?;
+        }
+
+        repository.update(cx, |repository, cx| repository.rescan(cx));
+        Ok(())
+    })
+}
+
+/// Opens the file with the pattern that makes git ignore `repo_path` at the pattern's line, or
+/// says that git doesn't ignore the path.
+pub fn explain_ignore(
+    workspace: &mut Workspace,
+    repository: Entity<Repository>,
+    repo_path: RepoPath,
+    window: &mut Window,
+    cx: &mut Context<Workspace>,
+) {
+    let rule = repository.update(cx, |repository, _| {
+        repository.ignore_rule(repo_path.clone())
+    });
+    let work_directory = repository.read(cx).snapshot().work_directory_abs_path;
+
+    cx.spawn_in(window, async move |workspace, cx| {
+        let Some(rule) = rule.await?? else {
+            workspace.update(cx, |workspace, cx| {
+                let message = format!("{} isn't ignored", repo_path.as_unix_str());
+                let toast = StatusToast::new(message, cx, |this, _| {
+                    this.icon(ToastIcon::new(IconName::Info).color(Color::Muted))
+                        .dismiss_button(true)
+                });
+                workspace.toggle_status_toast(toast, cx);
+            })?;
+            return anyhow::Ok(());
+        };
+
+        // Sources outside of the working directory, like the global excludes file, are absolute.
+        let source_abs_path = work_directory.join(&rule.source);
+        let item = workspace
+            .update_in(cx, |workspace, window, cx| {
+                workspace.open_abs_path(
+                    source_abs_path,
+                    OpenOptions {
+                        visible: Some(OpenVisible::None),
+                        ..Default::default()
+                    },
+                    window,
+                    cx,
+                )
+            })?
+            .await?;
+        if let Some(editor) = item.downcast::<Editor>() {
+            editor.update_in(cx, |editor, window, cx| {
+                let row = rule.line.saturating_sub(1);
+                editor.go_to_singleton_buffer_point(Point::new(row, 0), window, cx);
+            })?;
+        }
+        workspace.update(cx, |workspace, cx| {
+            let message = format!(
+                "{} is ignored by {} on line {} of {}",
+                repo_path.as_unix_str(),
+                rule.pattern,
+                rule.line,
+                rule.source.display()
+            );
+            let toast = StatusToast::new(message, cx, |this, _| {
+                this.icon(ToastIcon::new(IconName::Info).color(Color::Muted))
+                    .dismiss_button(true)
+            });
+            workspace.toggle_status_toast(toast, cx);
+        })?;
+        anyhow::Ok(())
+    })
+    .detach_and_prompt_err(
+        "Failed to explain why the path is ignored",
+        window,
+        cx,
+        |_, _, _| None,
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::{Path, PathBuf};
+
+    use super::*;
+    use git::{gitignore::IgnoreRule, repository::repo_path};
+    use gpui::{TestAppContext, VisualTestContext};
+    use project::FakeFs;
+    use serde_json::json;
+    use settings::SettingsStore;
+    use util::path;
+    use workspace::MultiWorkspace;
+
+    fn init_test(cx: &mut TestAppContext) {
+        cx.update(|cx| {
+            let settings_store = SettingsStore::test(cx);
+            cx.set_global(settings_store);
+
+            theme::init(theme::LoadThemes::JustBase, cx);
+            editor::init(cx);
+        })
+    }
+
+    #[gpui::test]
+    async fn test_explain_ignore_opens_pattern_line(cx: &mut TestAppContext) {
+        init_test(cx);
+
+        let fs = FakeFs::new(cx.executor());
+        fs.insert_tree(
+            path!("/project"),
+            json!({
+                ".git": {},
+                ".gitignore": "/target/\n*.log\n",
+                "debug.log": "",
+            }),
+        )
+        .await;
+        fs.with_git_state(Path::new(path!("/project/.git")), false, |state| {
+            state.ignore_rules.insert(
+                repo_path("debug.log"),
+                IgnoreRule {
+                    source: PathBuf::from(".gitignore"),
+                    line: 2,
+                    pattern: "*.log".to_string(),
+                },
+            );
+        })
+        .unwrap();
+        let project = Project::test(fs.clone(), [Path::new(path!("/project"))], cx).await;
+        let multi_workspace =
+            cx.add_window(|window, cx| MultiWorkspace::test_new(project.clone(), window, cx));
+        let cx = &mut VisualTestContext::from_window(*multi_workspace, cx);
+        let workspace = multi_workspace
+            .update(cx, |workspace, _, _| workspace.workspace().clone())
+            .unwrap();
+        cx.run_until_parked();
+
+        let repository =
+            project.read_with(cx, |project, cx| project.active_repository(cx).unwrap());
+        workspace.update_in(cx, |workspace, window, cx| {
+            explain_ignore(workspace, repository, repo_path("debug.log"), window, cx);
+        });
+        cx.run_until_parked();
+
+        let editor = workspace
+            .update(cx, |workspace, cx| workspace.active_item_as::<Editor>(cx))
+            .unwrap();
+        editor.update(cx, |editor, cx| {
+            assert_eq!(
+                editor
+                    .buffer()
+                    .read(cx)
+                    .as_singleton()
+                    .unwrap()
+                    .read(cx)
+                    .text(),
+                "/target/\n*.log\n"
+            );
+            let display_snapshot = editor.display_snapshot(cx);
+            assert_eq!(
+                editor.selections.newest::<Point>(&display_snapshot).head(),
+                Point::new(1, 0)
+            );
+        });
+    }
+}
@@ -0,0 +1,585 @@
+//! InteractiveRebaseView edits the todo list of an interactive rebase and runs it without
+//! opening an editor for git.
+
+use anyhow::Result;
+use collections::HashMap;
+use editor::Editor;
+use git::repository::{
+    InProgressOperationKind, OperationResolution, RebaseAction, RebaseTodoEntry,
+};
+use gpui::{
+    AnyElement, App, Context, Entity, EventEmitter, FocusHandle, Focusable, IntoElement, Render,
+    SharedString, Task, WeakEntity, Window,
+};
+use project::git_store::Repository;
+use ui::{ContextMenu, PopoverMenu, Tooltip, prelude::*};
+use workspace::{Item, Workspace, item::ItemEvent};
+
+use crate::resolve_active_repository;
+
+pub fn register(workspace: &mut Workspace) {
+    workspace.register_action(|workspace, action: &git::InteractiveRebase, window, cx| {
+        let Some(repository) = resolve_active_repository(workspace, cx) else {
+            return;
+        };
+        let base = action.base.clone().or_else(|| {
+            let branch = repository.read(cx).branch.as_ref()?;
+            let upstream = branch.upstream.as_ref()?.stripped_ref_name()?;
+            Some(upstream.to_string())
+        });
+        InteractiveRebaseView::open(repository, base, workspace, window, cx);
+    });
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum RebaseStatus {
+    Idle,
+    Loading,
+    Running,
+    /// The rebase stopped for an `edit` step or a conflict and waits to be continued or aborted.
+    Stopped,
+}
+
+#[derive(Clone)]
+struct DraggedRebaseEntry {
+    index: usize,
+    subject: SharedString,
+}
+
+impl Render for DraggedRebaseEntry {
+    fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        h_flex()
+            .px_2()
+            .py_1()
+            .gap_1()
+            .rounded_sm()
+            .bg(cx.theme().colors().elevated_surface_background)
+            .border_1()
+            .border_color(cx.theme().colors().border)
+            .child(
+                Icon::new(IconName::Menu)
+                    .size(IconSize::Small)
+                    .color(Color::Muted),
+            )
+            .child(Label::new(self.subject.clone()).single_line())
+    }
+}
+
+pub struct InteractiveRebaseView {
+    repository: WeakEntity<Repository>,
+    base_editor: Entity<Editor>,
+    /// The base the todo list was loaded for.
+    base: Option<String>,
+    todo: Vec<RebaseTodoEntry>,
+    reword_editors: HashMap<SharedString, Entity<Editor>>,
+    status: RebaseStatus,
+    error: Option<SharedString>,
+    focus_handle: FocusHandle,
+    _task: Option<Task<()>>,
+}
+
+impl InteractiveRebaseView {
+    fn open(
+        repository: Entity<Repository>,
+        base: Option<String>,
+        workspace: &mut Workspace,
+        window: &mut Window,
+        cx: &mut Context<Workspace>,
+    ) {
+        let existing = workspace
+            .active_pane()
+            .read(cx)
+            .items()
+            .find_map(|item| item.downcast::<InteractiveRebaseView>())
+            .filter(|view| view.read(cx).repository == repository.downgrade());
+        if let Some(view) = existing {
+            if let Some(base) = base {
+                view.update(cx, |view, cx| view.set_base(&base, window, cx));
+            }
+            workspace.activate_item(&view, true, true, window, cx);
+            return;
+        }
+
+        let view = cx.new(|cx| InteractiveRebaseView::new(repository, base, window, cx));
+        workspace.active_pane().update(cx, |pane, cx| {
+            pane.add_item(Box::new(view), true, true, None, window, cx);
+        });
+    }
+
+    fn new(
+        repository: Entity<Repository>,
+        base: Option<String>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Self {
+        let base_editor = cx.new(|cx| {
+            let mut editor = Editor::single_line(window, cx);
+            editor.set_placeholder_text("Commit or branch to rebase onto", window, cx);
+            editor
+        });
+        let mut this = Self {
+            repository: repository.downgrade(),
+            base_editor,
+            base: None,
+            todo: Vec::new(),
+            reword_editors: HashMap::default(),
+            status: RebaseStatus::Idle,
+            error: None,
+            focus_handle: cx.focus_handle(),
+            _task: None,
+        };
+        if let Some(base) = base {
+            this.set_base(&base, window, cx);
+        }
+        this
+    }
+
+    fn set_base(&mut self, base: &str, window: &mut Window, cx: &mut Context<Self>) {
+        self.base_editor
+            .update(cx, |editor, cx| editor.set_text(base, window, cx));
+        self.load(cx);
+    }
+
+    fn load(&mut self, cx: &mut Context<Self>) {
+        if matches!(self.status, RebaseStatus::Running | RebaseStatus::Stopped) {
+            return;
+        }
+        let base = self.base_editor.read(cx).text(cx).trim().to_string();
+        let Some(repository) = self.repository.upgrade() else {
+            return;
+        };
+        if base.is_empty() {
+            self.error = Some("Enter a commit or branch to rebase onto".into());
+            cx.notify();
+            return;
+        }
+
+        let receiver = repository.update(cx, |repository, _| repository.rebase_todo(base.clone()));
+        self.status = RebaseStatus::Loading;
+        self.error = None;
+        self._task = Some(cx.spawn(async move |this, cx| {
+            let result = receiver.await.map_err(anyhow::Error::from).and_then(|r| r);
+            this.update(cx, |this, cx| {
+                this.status = RebaseStatus::Idle;
+                match result {
+                    Ok(todo) => {
+                        this.base = Some(base);
+                        this.todo = todo;
+                        this.reword_editors.clear();
+                    }
+                    Err(error) => this.error = Some(error.to_string().into()),
+                }
+                cx.notify();
+            })
+            .ok();
+        }));
+        cx.notify();
+    }
+
+    fn set_action(
+        &mut self,
+        index: usize,
+        action: RebaseAction,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let Some(entry) = self.todo.get_mut(index) else {
+            return;
+        };
+        entry.action = action;
+        if action == RebaseAction::Reword && !self.reword_editors.contains_key(&entry.sha) {
+            let message = entry.message.clone();
+            let editor = cx.new(|cx| {
+                let mut editor = Editor::auto_height(1, 8, window, cx);
+                editor.set_text(message.as_ref(), window, cx);
+                editor
+            });
+            self.reword_editors.insert(entry.sha.clone(), editor);
+        }
+        cx.notify();
+    }
+
+    fn move_entry(&mut self, from: usize, to: usize, cx: &mut Context<Self>) {
+        if from == to || from >= self.todo.len() || to >= self.todo.len() {
+            return;
+        }
+        let entry = self.todo.remove(from);
+        self.todo.insert(to, entry);
+        cx.notify();
+    }
+
+    /// The todo list to run, with reworded messages taken from their editors.
+    fn edited_todo(&self, cx: &App) -> Vec<RebaseTodoEntry> {
+        self.todo
+            .iter()
+            .map(|entry| {
+                let mut entry = entry.clone();
+                if entry.action == RebaseAction::Reword
+                    && let Some(editor) = self.reword_editors.get(&entry.sha)
+                {
+                    entry.message = editor.read(cx).text(cx).into();
+                }
+                entry
+            })
+            .collect()
+    }
+
+    fn start(&mut self, cx: &mut Context<Self>) {
+        let Some(base) = self.base.clone() else {
+            return;
+        };
+        let todo = self.edited_todo(cx);
+        if let Some(error) = todo_error(&todo) {
+            self.error = Some(error.into());
+            cx.notify();
+            return;
+        }
+        let Some(repository) = self.repository.upgrade() else {
+            return;
+        };
+        let receiver = repository.update(cx, |repository, _| {
+            repository.interactive_rebase(base, todo)
+        });
+        self.await_rebase(receiver, cx);
+    }
+
+    fn resolve_rebase(&mut self, resolution: OperationResolution, cx: &mut Context<Self>) {
+        let Some(repository) = self.repository.upgrade() else {
+            return;
+        };
+        let receiver = repository.update(cx, |repository, _| {
+            repository.resolve_in_progress_operation(InProgressOperationKind::Rebase, resolution)
+        });
+        self.await_rebase(receiver, cx);
+    }
+
+    fn await_rebase(
+        &mut self,
+        receiver: futures::channel::oneshot::Receiver<Result<bool>>,
+        cx: &mut Context<Self>,
+    ) {
+        let previous_status = self.status;
+        self.status = RebaseStatus::Running;
+        self.error = None;
+        self._task = Some(cx.spawn(async move |this, cx| {
+            let result = receiver.await.map_err(anyhow::Error::from).and_then(|r| r);
+            this.update(cx, |this, cx| {
+                match result {
+                    Ok(true) => this.status = RebaseStatus::Stopped,
+                    Ok(false) => {
+                        this.status = RebaseStatus::Idle;
+                        // The commits were rewritten, so list them again as they are now.
+                        this.load(cx);
+                    }
+                    Err(error) => {
+                        this.status = previous_status;
+                        this.error = Some(error.to_string().into());
+                    }
+                }
+                cx.notify();
+            })
+            .ok();
+        }));
+        cx.notify();
+    }
+
+    fn render_action_menu(
+        &self,
+        index: usize,
+        action: RebaseAction,
+        cx: &Context<Self>,
+    ) -> PopoverMenu<ContextMenu> {
+        let view = cx.weak_entity();
+        PopoverMenu::new(("rebase-action", index))
+            .trigger(
+                Button::new(("rebase-action-trigger", index), action.as_str())
+                    .label_size(LabelSize::Small)
+                    .end_icon(Icon::new(IconName::ChevronDown).size(IconSize::XSmall))
+                    .disabled(self.status != RebaseStatus::Idle),
+            )
+            .menu(move |window, cx| {
+                let view = view.clone();
+                Some(ContextMenu::build(window, cx, move |mut menu, _, _| {
+                    for action in RebaseAction::ALL {
+                        let view = view.clone();
+                        menu = menu.entry(action.as_str(), None, move |window, cx| {
+                            view.update(cx, |view, cx| view.set_action(index, action, window, cx))
+                                .ok();
+                        });
+                    }
+                    menu
+                }))
+            })
+    }
+
+    fn render_entry(
+        &self,
+        index: usize,
+        entry: &RebaseTodoEntry,
+        cx: &Context<Self>,
+    ) -> AnyElement {
+        let short_sha: String = entry.sha.chars().take(git::SHORT_SHA_LENGTH).collect();
+        let is_dropped = entry.action == RebaseAction::Drop;
+        let reword_editor = (entry.action == RebaseAction::Reword)
+            .then(|| self.reword_editors.get(&entry.sha).cloned())
+            .flatten();
+
+        v_flex()
+            .id(("rebase-entry", index))
+            .px_2()
+            .py_1()
+            .gap_1()
+            .border_b_1()
+            .border_color(cx.theme().colors().border_variant)
+            .when(self.status == RebaseStatus::Idle, |this| {
+                this.on_drag(
+                    DraggedRebaseEntry {
+                        index,
+                        subject: entry.subject.clone(),
+                    },
+                    |dragged, _, _, cx| cx.new(|_| dragged.clone()),
+                )
+                .drag_over::<DraggedRebaseEntry>(|style, _, _, cx| {
+                    style.bg(cx.theme().colors().drop_target_background)
+                })
+                .on_drop(cx.listener(
+                    move |this, dragged: &DraggedRebaseEntry, _, cx| {
+                        this.move_entry(dragged.index, index, cx);
+                    },
+                ))
+            })
+            .child(
+                h_flex()
+                    .gap_2()
+                    .child(
+                        Icon::new(IconName::Menu)
+                            .size(IconSize::Small)
+                            .color(Color::Muted),
+                    )
+                    .child(self.render_action_menu(index, entry.action, cx))
+                    .child(
+                        Label::new(short_sha)
+                            .size(LabelSize::Small)
+                            .color(Color::Muted)
+                            .buffer_font(cx),
+                    )
+                    .child(
+                        Label::new(entry.subject.clone())
+                            .single_line()
+                            .truncate()
+                            .when(is_dropped, |label| {
+                                label.strikethrough().color(Color::Disabled)
+                            }),
+                    ),
+            )
+            .children(reword_editor.map(|editor| {
+                div()
+                    .ml_6()
+                    .p_1()
+                    .rounded_sm()
+                    .border_1()
+                    .border_color(cx.theme().colors().border)
+                    .child(editor)
+            }))
+            .into_any_element()
+    }
+
+    fn render_controls(&self, cx: &Context<Self>) -> impl IntoElement {
+        let buttons = match self.status {
+            RebaseStatus::Stopped => h_flex()
+                .gap_1()
+                .child(
+                    Button::new("abort-rebase", "Abort")
+                        .label_size(LabelSize::Small)
+                        .on_click(cx.listener(|this, _, _, cx| {
+                            this.resolve_rebase(OperationResolution::Abort, cx)
+                        })),
+                )
+                .child(
+                    Button::new("continue-rebase", "Continue")
+                        .style(ButtonStyle::Filled)
+                        .label_size(LabelSize::Small)
+                        .tooltip(Tooltip::text(
+                            "Continue after editing the commit or resolving conflicts",
+                        ))
+                        .on_click(cx.listener(|this, _, _, cx| {
+                            this.resolve_rebase(OperationResolution::Continue, cx)
+                        })),
+                ),
+            _ => h_flex().child(
+                Button::new("start-rebase", "Start Rebase")
+                    .style(ButtonStyle::Filled)
+                    .label_size(LabelSize::Small)
+                    .disabled(self.status != RebaseStatus::Idle || self.todo.is_empty())
+                    .on_click(cx.listener(|this, _, _, cx| this.start(cx))),
+            ),
+        };
+        let status = match self.status {
+            RebaseStatus::Idle => None,
+            RebaseStatus::Loading => Some("Loading commits…"),
+            RebaseStatus::Running => Some("Rebasing…"),
+            RebaseStatus::Stopped => Some("Rebase stopped"),
+        };
+
+        h_flex()
+            .px_2()
+            .py_1()
+            .gap_2()
+            .justify_between()
+            .border_t_1()
+            .border_color(cx.theme().colors().border)
+            .child(
+                h_flex()
+                    .gap_2()
+                    .min_w_0()
+                    .children(status.map(|status| {
+                        Label::new(status)
+                            .size(LabelSize::Small)
+                            .color(Color::Muted)
+                    }))
+                    .children(self.error.clone().map(|error| {
+                        Label::new(error)
+                            .size(LabelSize::Small)
+                            .color(Color::Error)
+                            .single_line()
+                            .truncate()
+                    })),
+            )
+            .child(buttons)
+    }
+}
+
+/// Describes why git would refuse to run `todo`, if it would.
+fn todo_error(todo: &[RebaseTodoEntry]) -> Option<&'static str> {
+    if todo.is_empty() {
+        return Some("There are no commits to rebase");
+    }
+    let first_kept = todo
+        .iter()
+        .find(|entry| entry.action != RebaseAction::Drop)?;
+    if first_kept.action.melds_into_previous() {
+        return Some("The first commit can't be squashed or fixed up into a previous one");
+    }
+    let has_empty_message = todo
+        .iter()
+        .any(|entry| entry.action == RebaseAction::Reword && entry.message.trim().is_empty());
+    has_empty_message.then_some("Reworded commits need a message")
+}
+
+impl EventEmitter<ItemEvent> for InteractiveRebaseView {}
+
+impl Focusable for InteractiveRebaseView {
+    fn focus_handle(&self, _cx: &App) -> FocusHandle {
+        self.focus_handle.clone()
+    }
+}
+
+impl Render for InteractiveRebaseView {
+    fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let entries = self
+            .todo
+            .iter()
+            .enumerate()
+            .map(|(index, entry)| self.render_entry(index, entry, cx))
+            .collect::<Vec<_>>();
+
+        v_flex()
+            .key_context("InteractiveRebaseView")
+            .track_focus(&self.focus_handle)
+            .size_full()
+            .bg(cx.theme().colors().editor_background)
+            .child(
+                h_flex()
+                    .h(rems_from_px(41.))
+                    .px_2()
+                    .gap_2()
+                    .border_b_1()
+                    .border_color(cx.theme().colors().border_variant)
+                    .child(Label::new("Rebase onto").color(Color::Muted))
+                    .child(div().flex_1().child(self.base_editor.clone()))
+                    .child(
+                        Button::new("load-rebase-todo", "Load Commits")
+                            .label_size(LabelSize::Small)
+                            .disabled(self.status != RebaseStatus::Idle)
+                            .on_click(cx.listener(|this, _, _, cx| this.load(cx))),
+                    ),
+            )
+            .child(
+                v_flex()
+                    .id("rebase-todo")
+                    .flex_1()
+                    .overflow_y_scroll()
+                    .when(
+                        entries.is_empty() && self.status == RebaseStatus::Idle,
+                        |this| {
+                            this.child(
+                                h_flex()
+                                    .p_4()
+                                    .justify_center()
+                                    .child(Label::new("No commits to rebase").color(Color::Muted)),
+                            )
+                        },
+                    )
+                    .children(entries),
+            )
+            .child(self.render_controls(cx))
+    }
+}
+
+impl Item for InteractiveRebaseView {
+    type Event = ItemEvent;
+
+    fn to_item_events(event: &Self::Event, f: &mut dyn FnMut(ItemEvent)) {
+        f(*event)
+    }
+
+    fn tab_content_text(&self, _detail: usize, _cx: &App) -> SharedString {
+        match &self.base {
+            Some(base) => format!("Rebase onto {base}").into(),
+            None => "Interactive Rebase".into(),
+        }
+    }
+
+    fn tab_icon(&self, _window: &Window, _cx: &App) -> Option<Icon> {
+        Some(Icon::new(IconName::GitBranch))
+    }
+
+    fn telemetry_event_text(&self) -> Option<&'static str> {
+        Some("interactive rebase")
+    }
+
+    fn added_to_workspace(
+        &mut self,
+        _workspace: &mut Workspace,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        window.focus(&self.base_editor.focus_handle(cx), cx);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(action: RebaseAction, message: &str) -> RebaseTodoEntry {
+        RebaseTodoEntry {
+            action,
+            sha: message.to_string().into(),
+            subject: message.to_string().into(),
+            message: message.to_string().into(),
+        }
+    }
+
+    #[test]
+    fn test_todo_error() {
+        use RebaseAction::*;
+
+        assert!(todo_error(&[]).is_some());
+        assert_eq!(todo_error(&[entry(Pick, "a"), entry(Squash, "b")]), None);
+        assert!(todo_error(&[entry(Fixup, "a"), entry(Pick, "b")]).is_some());
+        assert!(todo_error(&[entry(Drop, "a"), entry(Squash, "b")]).is_some());
+        assert_eq!(todo_error(&[entry(Drop, "a"), entry(Drop, "b")]), None);
+        assert!(todo_error(&[entry(Pick, "a"), entry(Reword, " ")]).is_some());
+    }
+}
@@ -0,0 +1,419 @@
+use std::{
+    cell::RefCell,
+    ops::Range,
+    rc::Rc,
+    sync::Arc,
+    time::{Duration, Instant},
+};
+
+use anyhow::Result;
+use collections::{HashMap, HashSet};
+use editor::{GotoDefinitionKind, SemanticsProvider};
+use futures::{FutureExt as _, future::Shared};
+use git::{GitHostingProvider, GitHostingProviderRegistry, Issue, ParsedGitRemote};
+use gpui::{App, Entity, Task};
+use language::{Anchor, Buffer, BufferId, BufferRow, CodeLabel, Point, ToPoint};
+use project::{
+    Completion, CompletionDisplayOptions, CompletionResponse, CompletionSource, DocumentHighlight,
+    Hover, HoverBlock, HoverBlockKind, InlayHint, InvalidationStrategy, LocationLink, Project,
+    ProjectTransaction,
+    lsp_store::{BufferSemanticTokens, CacheInlayHints, CompletionDocumentation, RefreshForServer},
+};
+use util::ResultExt as _;
+
+/// How long listed issues are offered as completions before they are listed again. Issues looked
+/// up on their own, including references to issues that don't exist, are kept as long.
+const ISSUES_MAX_AGE: Duration = Duration::from_secs(5 * 60);
+
+/// How long the pointer has to rest on a reference that isn't listed before it is looked up.
+const LOOKUP_DEBOUNCE: Duration = Duration::from_millis(300);
+
+/// Finds the `#` reference being typed at the end of `line_prefix`, including the `#`.
+///
+/// Lines starting with `#` are comments that git strips from the message, so a reference has to
+/// follow other text.
+fn reference_query(line_prefix: &str) -> Option<&str> {
+    let start = line_prefix.rfind('#')?;
+    let (before, query) = line_prefix.split_at(start);
+    (is_reference_boundary(before) && query[1..].chars().all(char::is_alphanumeric))
+        .then_some(query)
+}
+
+fn is_reference_boundary(before: &str) -> bool {
+    before
+        .chars()
+        .last()
+        .is_some_and(|character| character.is_whitespace() || character == '(')
+}
+
+/// Finds the `#123` reference in `line` that touches the byte `offset`, returning its range and
+/// number.
+fn reference_at(line: &str, offset: usize) -> Option<(Range<usize>, u32)> {
+    line.match_indices('#').find_map(|(start, _)| {
+        let digits = line[start + 1..]
+            .chars()
+            .take_while(char::is_ascii_digit)
+            .count();
+        let end = start + 1 + digits;
+        let is_terminated = line[end..]
+            .chars()
+            .next()
+            .is_none_or(|character| !character.is_alphanumeric());
+        if digits == 0
+            || !is_terminated
+            || !is_reference_boundary(&line[..start])
+            || !(start..=end).contains(&offset)
+        {
+            return None;
+        }
+        let number = line[start + 1..end].parse().ok()?;
+        Some((start..end, number))
+    })
+}
+
+fn issue_kind(issue: &Issue) -> &'static str {
+    if issue.is_pull_request {
+        "Pull request"
+    } else {
+        "Issue"
+    }
+}
+
+struct ListedIssues {
+    remote_url: String,
+    listed_at: Instant,
+    issues: Shared<Task<Arc<[Issue]>>>,
+}
+
+struct LookedUpIssue {
+    looked_up_at: Instant,
+    issue: Shared<Task<Option<Issue>>>,
+}
+
+/// Looks up the issues that `#` references in a commit message point to, on the hosting provider
+/// of the active repository's remote. Completes references while they are typed, and describes
+/// existing ones on hover.
+pub(crate) struct IssueReferences {
+    project: Entity<Project>,
+    listed_issues: RefCell<Option<ListedIssues>>,
+    looked_up_issues: Rc<RefCell<HashMap<(String, u32), LookedUpIssue>>>,
+}
+
+impl IssueReferences {
+    pub(crate) fn new(project: Entity<Project>) -> Self {
+        Self {
+            project,
+            listed_issues: RefCell::new(None),
+            looked_up_issues: Rc::default(),
+        }
+    }
+
+    fn hosting_remote(
+        &self,
+        cx: &App,
+    ) -> Option<(
+        String,
+        Arc<dyn GitHostingProvider + Send + Sync + 'static>,
+        ParsedGitRemote,
+    )> {
+        let repository = self.project.read(cx).active_repository(cx)?;
+        let repository = repository.read(cx);
+        // Forks usually leave issues to the repository they were forked from.
+        let remote_url = repository
+            .remote_upstream_url
+            .clone()
+            .or_else(|| repository.remote_origin_url.clone())?;
+        let (provider, remote) =
+            git::parse_git_remote_url(GitHostingProviderRegistry::try_global(cx)?, &remote_url)?;
+        Some((remote_url, provider, remote))
+    }
+
+    fn issues(&self, cx: &App) -> Option<Shared<Task<Arc<[Issue]>>>> {
+        let (remote_url, provider, remote) = self.hosting_remote(cx)?;
+        let mut listed_issues = self.listed_issues.borrow_mut();
+        if let Some(listed) = listed_issues.as_ref()
+            && listed.remote_url == remote_url
+            && listed.listed_at.elapsed() < ISSUES_MAX_AGE
+        {
+            return Some(listed.issues.clone());
+        }
+
+        let http_client = cx.http_client();
+        let issues = cx
+            .background_spawn(async move {
+                provider
+                    .list_issues(&remote, http_client)
+                    .await
+                    .log_err()
+                    .map(Arc::from)
+                    .unwrap_or_default()
+            })
+            .shared();
+        *listed_issues = Some(ListedIssues {
+            remote_url,
+            listed_at: Instant::now(),
+            issues: issues.clone(),
+        });
+        Some(issues)
+    }
+
+    /// Returns whether `#` reference completions are offered at the position.
+    pub(crate) fn is_completion_position(
+        &self,
+        buffer: &Entity<Buffer>,
+        position: Anchor,
+        cx: &App,
+    ) -> bool {
+        let snapshot = buffer.read(cx).snapshot();
+        let position = position.to_point(&snapshot);
+        let line_prefix = snapshot
+            .text_for_range(Point::new(position.row, 0)..position)
+            .collect::<String>();
+        reference_query(&line_prefix).is_some()
+    }
+
+    /// Completes the `#` reference that is being typed, or returns `None` when there is none.
+    pub(crate) fn completions(
+        &self,
+        buffer: &Entity<Buffer>,
+        buffer_position: Anchor,
+        cx: &mut App,
+    ) -> Option<Task<Result<Vec<CompletionResponse>>>> {
+        let snapshot = buffer.read(cx).snapshot();
+        let position = buffer_position.to_point(&snapshot);
+        let line_prefix = snapshot
+            .text_for_range(Point::new(position.row, 0)..position)
+            .collect::<String>();
+        let query = reference_query(&line_prefix)?;
+        let query_start = Point::new(position.row, position.column - query.len() as u32);
+        let replace_range = snapshot.anchor_before(query_start)..buffer_position;
+        let issues = self.issues(cx)?;
+
+        Some(cx.spawn(async move |_| {
+            let completions = issues
+                .await
+                .iter()
+                .map(|issue| {
+                    let new_text = format!("#{}", issue.number);
+                    Completion {
+                        replace_range: replace_range.clone(),
+                        label: CodeLabel::plain(format!("{new_text} {}", issue.title), None),
+                        new_text,
+                        documentation: Some(CompletionDocumentation::SingleLine(
+                            issue_kind(issue).into(),
+                        )),
+                        source: CompletionSource::Custom,
+                        icon_path: None,
+                        match_start: None,
+                        snippet_deduplication_key: None,
+                        insert_text_mode: None,
+                        confirm: None,
+                    }
+                })
+                .collect();
+            Ok(vec![CompletionResponse {
+                completions,
+                display_options: CompletionDisplayOptions::default(),
+                is_incomplete: false,
+            }])
+        }))
+    }
+}
+
+impl SemanticsProvider for IssueReferences {
+    fn hover(
+        &self,
+        buffer: &Entity<Buffer>,
+        position: Anchor,
+        cx: &mut App,
+    ) -> Option<Task<Option<Vec<Hover>>>> {
+        let snapshot = buffer.read(cx).snapshot();
+        let point = position.to_point(&snapshot);
+        let line_start = Point::new(point.row, 0);
+        let line_end = Point::new(point.row, snapshot.line_len(point.row));
+        let line = snapshot
+            .text_for_range(line_start..line_end)
+            .collect::<String>();
+        let (range, number) = reference_at(&line, point.column as usize)?;
+        let range = snapshot.anchor_before(Point::new(point.row, range.start as u32))
+            ..snapshot.anchor_after(Point::new(point.row, range.end as u32));
+
+        let (remote_url, provider, remote) = self.hosting_remote(cx)?;
+        let listed_issues = self.issues(cx);
+        let looked_up_issues = self.looked_up_issues.clone();
+        Some(cx.spawn(async move |cx| {
+            let listed_issue = match listed_issues {
+                Some(issues) => issues
+                    .await
+                    .iter()
+                    .find(|issue| issue.number == number)
+                    .cloned(),
+                None => None,
+            };
+            let issue = match listed_issue {
+                Some(issue) => issue,
+                None => {
+                    let key = (remote_url, number);
+                    let cached_issue = looked_up_issues
+                        .borrow()
+                        .get(&key)
+                        .filter(|looked_up| looked_up.looked_up_at.elapsed() < ISSUES_MAX_AGE)
+                        .map(|looked_up| looked_up.issue.clone());
+                    let issue = match cached_issue {
+                        Some(issue) => issue,
+                        None => {
+                            // Passing over references on the way elsewhere shouldn't send requests.
+                            cx.background_executor().timer(LOOKUP_DEBOUNCE).await;
+                            let http_client = cx.update(|cx| cx.http_client());
+                            let issue = cx
+                                .background_spawn(async move {
+                                    provider
+                                        .issue(&remote, number, http_client)
+                                        .await
+                                        .log_err()
+                                        .flatten()
+                                })
+                                .shared();
+                            looked_up_issues.borrow_mut().insert(
+                                key,
+                                LookedUpIssue {
+                                    looked_up_at: Instant::now(),
+                                    issue: issue.clone(),
+                                },
+                            );
+                            issue
+                        }
+                    };
+                    issue.await?
+                }
+            };
+            Some(vec![Hover {
+                contents: vec![HoverBlock {
+                    text: format!(
+                        "{} [#{}]({}): {}",
+                        issue_kind(&issue),
+                        issue.number,
+                        issue.url,
+                        issue.title
+                    ),
+                    kind: HoverBlockKind::Markdown,
+                }],
+                range: Some(range),
+                language: None,
+            }])
+        }))
+    }
+
+    fn inline_values(
+        &self,
+        _buffer_handle: Entity<Buffer>,
+        _range: Range<Anchor>,
+        _cx: &mut App,
+    ) -> Option<Task<Result<Vec<InlayHint>>>> {
+        None
+    }
+
+    fn applicable_inlay_chunks(
+        &self,
+        _buffer: &Entity<Buffer>,
+        _ranges: &[Range<Anchor>],
+        _cx: &mut App,
+    ) -> Vec<Range<BufferRow>> {
+        Vec::new()
+    }
+
+    fn invalidate_inlay_hints(&self, _for_buffers: &HashSet<BufferId>, _cx: &mut App) {}
+
+    fn inlay_hints(
+        &self,
+        _invalidate: InvalidationStrategy,
+        _buffer: Entity<Buffer>,
+        _ranges: Vec<Range<Anchor>>,
+        _known_chunks: Option<(clock::Global, HashSet<Range<BufferRow>>)>,
+        _cx: &mut App,
+    ) -> Option<HashMap<Range<BufferRow>, Task<Result<CacheInlayHints>>>> {
+        None
+    }
+
+    fn semantic_tokens(
+        &self,
+        _buffer: Entity<Buffer>,
+        _refresh: Option<RefreshForServer>,
+        _cx: &mut App,
+    ) -> Option<Shared<Task<std::result::Result<BufferSemanticTokens, Arc<anyhow::Error>>>>> {
+        None
+    }
+
+    fn supports_inlay_hints(&self, _buffer: &Entity<Buffer>, _cx: &mut App) -> bool {
+        false
+    }
+
+    fn supports_semantic_tokens(&self, _buffer: &Entity<Buffer>, _cx: &mut App) -> bool {
+        false
+    }
+
+    fn document_highlights(
+        &self,
+        _buffer: &Entity<Buffer>,
+        _position: Anchor,
+        _cx: &mut App,
+    ) -> Option<Task<Result<Vec<DocumentHighlight>>>> {
+        None
+    }
+
+    fn definitions(
+        &self,
+        _buffer: &Entity<Buffer>,
+        _position: Anchor,
+        _kind: GotoDefinitionKind,
+        _cx: &mut App,
+    ) -> Option<Task<Result<Option<Vec<LocationLink>>>>> {
+        None
+    }
+
+    fn range_for_rename(
+        &self,
+        _buffer: &Entity<Buffer>,
+        _position: Anchor,
+        _cx: &mut App,
+    ) -> Option<Task<Result<Option<Range<Anchor>>>>> {
+        None
+    }
+
+    fn perform_rename(
+        &self,
+        _buffer: &Entity<Buffer>,
+        _position: Anchor,
+        _new_name: String,
+        _cx: &mut App,
+    ) -> Option<Task<Result<ProjectTransaction>>> {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_reference_query() {
+        assert_eq!(reference_query("Fixes #"), Some("#"));
+        assert_eq!(reference_query("Fixes #12"), Some("#12"));
+        assert_eq!(reference_query("Fix blame (#bla"), Some("#bla"));
+        assert_eq!(reference_query("#12"), None);
+        assert_eq!(reference_query("Fixes a#12"), None);
+        assert_eq!(reference_query("Fixes #12 and"), None);
+    }
+
+    #[test]
+    fn test_reference_at() {
+        let line = "Fix blame (#123), see #7 and a#9";
+        assert_eq!(reference_at(line, 11), Some((11..15, 123)));
+        assert_eq!(reference_at(line, 15), Some((11..15, 123)));
+        assert_eq!(reference_at(line, 23), Some((22..24, 7)));
+        assert_eq!(reference_at(line, 3), None);
+        assert_eq!(reference_at(line, 31), None);
+        assert_eq!(reference_at("#12 is a comment", 1), None);
+        assert_eq!(reference_at("Closes #12abc", 8), None);
+    }
+}
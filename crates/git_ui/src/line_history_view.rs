@@ -0,0 +1,605 @@
+//! LineHistoryView lists the commits that changed a range of lines, each with its hunk.
+
+use anyhow::{Result, anyhow};
+use collections::HashMap;
+use editor::{Addon, Editor, EditorEvent, ExcerptInfo, MultiBuffer, PathKey};
+use git::{line_history::LineHistoryEntry, repository::RepoPath};
+use gpui::{
+    AnyElement, App, AppContext as _, Context, Entity, EventEmitter, FocusHandle, Focusable, Font,
+    IntoElement, Render, SharedString, Task, WeakEntity, Window,
+};
+use language::{Buffer, BufferId, Capability, HighlightedText, Point};
+use project::{ProjectPath, WorktreeId, git_store::Repository};
+use std::{
+    any::{Any, TypeId},
+    ops::RangeInclusive,
+    sync::Arc,
+};
+use time::OffsetDateTime;
+use ui::{Tooltip, prelude::*};
+use workspace::{
+    Item, ItemNavHistory, ToolbarItemLocation, Workspace,
+    item::{ItemEvent, TabContentParams},
+    notifications::DetachAndPromptErr,
+    searchable::SearchableItemHandle,
+};
+
+use crate::{
+    commit_view::{CommitView, GitBlob},
+    patch_view::{build_entry, parse_patch},
+};
+
+pub fn register(workspace: &mut Workspace) {
+    workspace.register_action(|workspace, _: &git::LineHistory, window, cx| {
+        let Some(editor) = workspace.active_item_as::<Editor>(cx) else {
+            return;
+        };
+        let Some(buffer) = editor.read(cx).buffer().read(cx).as_singleton() else {
+            return;
+        };
+        let Some(file) = buffer.read(cx).file() else {
+            return;
+        };
+        let project_path = ProjectPath {
+            worktree_id: file.worktree_id(cx),
+            path: file.path().clone(),
+        };
+        let Some((repository, repo_path)) = workspace
+            .project()
+            .read(cx)
+            .git_store()
+            .read(cx)
+            .repository_and_path_for_project_path(&project_path, cx)
+        else {
+            return;
+        };
+        let selection = editor.update(cx, |editor, cx| {
+            let display_snapshot = editor.display_snapshot(cx);
+            editor.selections.newest::<Point>(&display_snapshot).range()
+        });
+        // A selection of whole lines ends at the start of the line after them.
+        let end_row = if selection.end.column == 0 && selection.end.row > selection.start.row {
+            selection.end.row - 1
+        } else {
+            selection.end.row
+        };
+        let lines = match head_rows(&editor, &buffer, selection.start.row..=end_row, cx) {
+            Some(rows) => rows.start() + 1..=rows.end() + 1,
+            None => {
+                workspace.show_error(&anyhow!("The selected lines aren't committed yet"), cx);
+                return;
+            }
+        };
+
+        LineHistoryView::open(
+            repository,
+            repo_path,
+            lines,
+            project_path.worktree_id,
+            workspace,
+            window,
+            cx,
+        )
+        .detach_and_prompt_err("Failed to load line history", window, cx, |_, _, _| None);
+    });
+}
+
+/// Maps rows of the buffer to the rows they have at HEAD, which is what `git log -L` counts in,
+/// going through the buffer's uncommitted changes. Returns `None` when all the rows are new.
+fn head_rows(
+    editor: &Entity<Editor>,
+    buffer: &Entity<Buffer>,
+    rows: RangeInclusive<u32>,
+    cx: &App,
+) -> Option<RangeInclusive<u32>> {
+    let buffer_id = buffer.read(cx).remote_id();
+    let Some(diff) = editor.read(cx).buffer().read(cx).diff_for(buffer_id) else {
+        return Some(rows);
+    };
+    let diff = diff.read(cx).snapshot(cx);
+    let buffer = buffer.read(cx).snapshot();
+
+    let added_row_count: u32 = diff
+        .hunks_in_row_range(*rows.start()..rows.end() + 1, &buffer)
+        .filter(|hunk| hunk.diff_base_byte_range.is_empty())
+        .map(|hunk| {
+            let start = hunk.range.start.row.max(*rows.start());
+            let end = hunk.range.end.row.min(rows.end() + 1);
+            end.saturating_sub(start)
+        })
+        .sum();
+    if added_row_count >= rows.end() + 1 - rows.start() {
+        return None;
+    }
+
+    let start = diff
+        .buffer_point_to_base_text_point(Point::new(*rows.start(), 0), &buffer)
+        .row;
+    let end_point = if *rows.end() < buffer.max_point().row {
+        Point::new(rows.end() + 1, 0)
+    } else {
+        buffer.max_point()
+    };
+    let end_point = diff.buffer_point_to_base_text_point(end_point, &buffer);
+    let end = if end_point.column == 0 {
+        end_point.row.saturating_sub(1)
+    } else {
+        end_point.row
+    };
+    Some(start..=end.max(start))
+}
+
+pub struct LineHistoryView {
+    editor: Entity<Editor>,
+    repo_path: RepoPath,
+    lines: RangeInclusive<u32>,
+}
+
+/// Shows which commit each excerpt's hunk comes from in its header.
+struct LineHistoryAddon {
+    commits: HashMap<BufferId, LineHistoryEntry>,
+    repo_path: RepoPath,
+    repository: WeakEntity<Repository>,
+    workspace: WeakEntity<Workspace>,
+}
+
+impl Addon for LineHistoryAddon {
+    fn to_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn render_buffer_header_controls(
+        &self,
+        excerpt_info: &ExcerptInfo,
+        _: &Window,
+        _: &App,
+    ) -> Option<AnyElement> {
+        let commit = self.commits.get(&excerpt_info.buffer_id)?;
+        let commit_time = OffsetDateTime::from_unix_timestamp(commit.commit_timestamp)
+            .unwrap_or_else(|_| OffsetDateTime::UNIX_EPOCH);
+        let relative_timestamp = time_format::format_localized_timestamp(
+            commit_time,
+            OffsetDateTime::now_utc(),
+            time::UtcOffset::current_local_offset().unwrap_or(time::UtcOffset::UTC),
+            time_format::TimestampFormat::Relative,
+        );
+        let short_sha: String = commit.sha.chars().take(git::SHORT_SHA_LENGTH).collect();
+        let sha = commit.sha.to_string();
+        let repo_path = self.repo_path.clone();
+        let repository = self.repository.clone();
+        let workspace = self.workspace.clone();
+
+        Some(
+            h_flex()
+                .gap_2()
+                .min_w_0()
+                .child(
+                    Button::new(SharedString::from(format!("line-history-{sha}")), short_sha)
+                        .label_size(LabelSize::Small)
+                        .tooltip(Tooltip::text("View Commit"))
+                        .on_click(move |_, window, cx| {
+                            CommitView::open(
+                                sha.clone(),
+                                repository.clone(),
+                                workspace.clone(),
+                                None,
+                                Some(repo_path.clone()),
+                                window,
+                                cx,
+                            );
+                        }),
+                )
+                .child(
+                    Label::new(commit.subject.clone())
+                        .size(LabelSize::Small)
+                        .truncate(),
+                )
+                .child(
+                    Label::new(format!("{} · {relative_timestamp}", commit.author_name))
+                        .size(LabelSize::Small)
+                        .color(Color::Muted),
+                )
+                .into_any_element(),
+        )
+    }
+}
+
+impl LineHistoryView {
+    /// Opens the commits that changed `lines` of the file, which start at 1 and are counted in
+    /// the file at HEAD. Only the newest commit's hunk starts out expanded.
+    pub fn open(
+        repository: Entity<Repository>,
+        repo_path: RepoPath,
+        lines: RangeInclusive<u32>,
+        worktree_id: WorktreeId,
+        workspace: &Workspace,
+        window: &mut Window,
+        cx: &mut App,
+    ) -> Task<Result<Entity<Self>>> {
+        let project = workspace.project().clone();
+        let languages = project.read(cx).languages().clone();
+        let history = repository.update(cx, |repository, cx| {
+            repository.line_history(repo_path.clone(), lines.clone(), cx)
+        });
+        let workspace = workspace.weak_handle();
+
+        window.spawn(cx, async move |cx| {
+            let history = history.await?;
+            anyhow::ensure!(!history.is_empty(), "no commits changed these lines");
+            let file_name = repo_path
+                .file_name()
+                .unwrap_or(repo_path.as_unix_str())
+                .to_string();
+            let mut entries = Vec::with_capacity(history.len());
+            for commit in history {
+                let Some(file) = parse_patch(&commit.diff).into_iter().next() else {
+                    continue;
+                };
+                let (buffer, diff) = build_entry(&file, &languages, cx).await?;
+                buffer.update(cx, |buffer, cx| {
+                    buffer.file_updated(
+                        Arc::new(GitBlob {
+                            path: repo_path.clone(),
+                            worktree_id,
+                            is_deleted: false,
+                            is_binary: false,
+                            display_name: file_name.clone(),
+                        }),
+                        cx,
+                    )
+                });
+                entries.push((commit, buffer, diff));
+            }
+
+            workspace.update_in(cx, |workspace, window, cx| {
+                let mut commits = HashMap::default();
+                let mut older_buffer_ids = Vec::new();
+                let multibuffer = cx.new(|cx| {
+                    let mut multibuffer = MultiBuffer::new(Capability::ReadOnly);
+                    multibuffer.set_all_diff_hunks_expanded(cx);
+                    for (index, (commit, buffer, diff)) in entries.into_iter().enumerate() {
+                        let max_point = buffer.read(cx).max_point();
+                        let buffer_id = buffer.read(cx).remote_id();
+                        if index > 0 {
+                            older_buffer_ids.push(buffer_id);
+                        }
+                        commits.insert(buffer_id, commit);
+                        multibuffer.set_excerpts_for_path(
+                            PathKey::with_sort_prefix(index as u64, repo_path.as_ref().clone()),
+                            buffer,
+                            [Point::zero()..max_point],
+                            0,
+                            cx,
+                        );
+                        multibuffer.add_diff(diff, cx);
+                    }
+                    multibuffer
+                });
+
+                let addon = LineHistoryAddon {
+                    commits,
+                    repo_path: repo_path.clone(),
+                    repository: repository.downgrade(),
+                    workspace: workspace.weak_handle(),
+                };
+                let line_history_view = cx.new(|cx| {
+                    LineHistoryView::new(
+                        multibuffer,
+                        addon,
+                        older_buffer_ids,
+                        repo_path,
+                        lines,
+                        project.clone(),
+                        window,
+                        cx,
+                    )
+                });
+                workspace.active_pane().update(cx, |pane, cx| {
+                    pane.add_item(
+                        Box::new(line_history_view.clone()),
+                        true,
+                        true,
+                        None,
+                        window,
+                        cx,
+                    );
+                });
+                line_history_view
+            })
+        })
+    }
+
+    fn new(
+        multibuffer: Entity<MultiBuffer>,
+        addon: LineHistoryAddon,
+        older_buffer_ids: Vec<BufferId>,
+        repo_path: RepoPath,
+        lines: RangeInclusive<u32>,
+        project: Entity<project::Project>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Self {
+        let editor = cx.new(|cx| {
+            let mut editor = Editor::for_multibuffer(multibuffer, Some(project), window, cx);
+            editor.set_read_only(true);
+            editor.start_temporary_diff_override();
+            editor.disable_diagnostics(cx);
+            editor.set_expand_all_diff_hunks(cx);
+            editor.set_render_diff_hunk_controls(
+                Arc::new(|_, _, _, _, _, _, _, _| gpui::Empty.into_any_element()),
+                cx,
+            );
+            editor.register_addon(addon);
+            for buffer_id in older_buffer_ids {
+                editor.fold_buffer(buffer_id, cx);
+            }
+            editor
+        });
+
+        Self {
+            editor,
+            repo_path,
+            lines,
+        }
+    }
+
+    fn title(&self) -> SharedString {
+        let file_name = self
+            .repo_path
+            .file_name()
+            .unwrap_or(self.repo_path.as_unix_str());
+        let (start, end) = (self.lines.start(), self.lines.end());
+        if start == end {
+            format!("Line History: {file_name}:{start}").into()
+        } else {
+            format!("Line History: {file_name}:{start}-{end}").into()
+        }
+    }
+}
+
+impl EventEmitter<EditorEvent> for LineHistoryView {}
+
+impl Focusable for LineHistoryView {
+    fn focus_handle(&self, cx: &App) -> FocusHandle {
+        self.editor.focus_handle(cx)
+    }
+}
+
+impl Item for LineHistoryView {
+    type Event = EditorEvent;
+
+    fn tab_icon(&self, _window: &Window, _cx: &App) -> Option<Icon> {
+        Some(Icon::new(IconName::HistoryRerun).color(Color::Muted))
+    }
+
+    fn tab_content(&self, params: TabContentParams, _window: &Window, _cx: &App) -> AnyElement {
+        Label::new(self.title())
+            .color(if params.selected {
+                Color::Default
+            } else {
+                Color::Muted
+            })
+            .into_any_element()
+    }
+
+    fn tab_content_text(&self, _detail: usize, _cx: &App) -> SharedString {
+        self.title()
+    }
+
+    fn tab_tooltip_text(&self, _cx: &App) -> Option<SharedString> {
+        Some(self.repo_path.as_unix_str().to_string().into())
+    }
+
+    fn to_item_events(event: &EditorEvent, f: &mut dyn FnMut(ItemEvent)) {
+        Editor::to_item_events(event, f)
+    }
+
+    fn telemetry_event_text(&self) -> Option<&'static str> {
+        Some("Line History View Opened")
+    }
+
+    fn deactivated(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        self.editor
+            .update(cx, |editor, cx| editor.deactivated(window, cx));
+    }
+
+    fn act_as_type<'a>(
+        &'a self,
+        type_id: TypeId,
+        self_handle: &'a Entity<Self>,
+        _: &'a App,
+    ) -> Option<gpui::AnyEntity> {
+        if type_id == TypeId::of::<Self>() {
+            Some(self_handle.clone().into())
+        } else if type_id == TypeId::of::<Editor>() {
+            Some(self.editor.clone().into())
+        } else {
+            None
+        }
+    }
+
+    fn as_searchable(&self, _: &Entity<Self>, _: &App) -> Option<Box<dyn SearchableItemHandle>> {
+        Some(Box::new(self.editor.clone()))
+    }
+
+    fn set_nav_history(
+        &mut self,
+        nav_history: ItemNavHistory,
+        _: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.editor.update(cx, |editor, _| {
+            editor.set_nav_history(Some(nav_history));
+        });
+    }
+
+    fn navigate(
+        &mut self,
+        data: Arc<dyn Any + Send>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> bool {
+        self.editor
+            .update(cx, |editor, cx| editor.navigate(data, window, cx))
+    }
+
+    fn breadcrumb_location(&self, _: &App) -> ToolbarItemLocation {
+        ToolbarItemLocation::PrimaryLeft
+    }
+
+    fn breadcrumbs(&self, cx: &App) -> Option<(Vec<HighlightedText>, Option<Font>)> {
+        self.editor.breadcrumbs(cx)
+    }
+
+    fn added_to_workspace(
+        &mut self,
+        workspace: &mut Workspace,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.editor.update(cx, |editor, cx| {
+            editor.added_to_workspace(workspace, window, cx)
+        });
+    }
+}
+
+impl Render for LineHistoryView {
+    fn render(&mut self, _window: &mut Window, _cx: &mut Context<Self>) -> impl IntoElement {
+        div().size_full().child(self.editor.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use super::*;
+    use git::repository::repo_path;
+    use gpui::{BorrowAppContext, TestAppContext, VisualTestContext};
+    use project::{FakeFs, Project};
+    use serde_json::json;
+    use settings::SettingsStore;
+    use util::path;
+    use workspace::MultiWorkspace;
+
+    fn init_test(cx: &mut TestAppContext) {
+        cx.update(|cx| {
+            let settings_store = SettingsStore::test(cx);
+            cx.set_global(settings_store);
+            cx.update_global::<SettingsStore, _>(|store, cx| {
+                store.update_user_settings(cx, |settings| {
+                    settings.editor.diff_view_style = Some(settings::DiffViewStyle::Unified);
+                });
+            });
+            theme::init(theme::LoadThemes::JustBase, cx);
+            editor::init(cx);
+        })
+    }
+
+    #[gpui::test]
+    async fn test_line_history_lists_commits_for_selection(cx: &mut TestAppContext) {
+        init_test(cx);
+
+        let fs = FakeFs::new(cx.executor());
+        fs.insert_tree(
+            path!("/project"),
+            json!({
+                ".git": {},
+                "src": { "main.rs": "fn main() {\n    println!(\"hi\");\n}\n" },
+            }),
+        )
+        .await;
+        fs.with_git_state(Path::new(path!("/project/.git")), false, |state| {
+            state.line_histories.insert(
+                repo_path("src/main.rs"),
+                vec![
+                    LineHistoryEntry {
+                        sha: "3a3a3a3a3a".into(),
+                        subject: "Rename the greeting".into(),
+                        commit_timestamp: 1709741400,
+                        author_name: "Joe Schmoe".into(),
+                        diff: concat!(
+                            "diff --git a/src/main.rs b/src/main.rs\n",
+                            "--- a/src/main.rs\n",
+                            "+++ b/src/main.rs\n",
+                            "@@ -2,1 +2,1 @@\n",
+                            "-    println!(\"hello\");\n",
+                            "+    println!(\"hi\");\n",
+                        )
+                        .to_string(),
+                    },
+                    LineHistoryEntry {
+                        sha: "1b1b1b1b1b".into(),
+                        subject: "Add main".into(),
+                        commit_timestamp: 1709740000,
+                        author_name: "Jane Doe".into(),
+                        diff: concat!(
+                            "diff --git a/src/main.rs b/src/main.rs\n",
+                            "--- /dev/null\n",
+                            "+++ b/src/main.rs\n",
+                            "@@ -0,0 +2,1 @@\n",
+                            "+    println!(\"hello\");\n",
+                        )
+                        .to_string(),
+                    },
+                ],
+            );
+        })
+        .unwrap();
+        let project = Project::test(fs.clone(), [Path::new(path!("/project"))], cx).await;
+        let multi_workspace =
+            cx.add_window(|window, cx| MultiWorkspace::test_new(project.clone(), window, cx));
+        let cx = &mut VisualTestContext::from_window(*multi_workspace, cx);
+        let workspace = multi_workspace
+            .update(cx, |workspace, _, _| workspace.workspace().clone())
+            .unwrap();
+        cx.run_until_parked();
+
+        let editor = workspace
+            .update_in(cx, |workspace, window, cx| {
+                workspace.open_abs_path(
+                    path!("/project/src/main.rs").into(),
+                    Default::default(),
+                    window,
+                    cx,
+                )
+            })
+            .await
+            .unwrap()
+            .downcast::<Editor>()
+            .unwrap();
+        editor.update_in(cx, |editor, window, cx| {
+            editor.change_selections(Default::default(), window, cx, |selections| {
+                selections.select_ranges([Point::new(1, 0)..Point::new(2, 0)])
+            });
+        });
+        workspace.update_in(cx, |_, window, cx| {
+            window.dispatch_action(Box::new(git::LineHistory), cx);
+        });
+        cx.run_until_parked();
+
+        let line_history_view = workspace
+            .update(cx, |workspace, cx| {
+                workspace.active_item_as::<LineHistoryView>(cx)
+            })
+            .unwrap();
+        line_history_view.update(cx, |line_history_view, cx| {
+            assert_eq!(line_history_view.lines, 2..=2);
+            assert_eq!(
+                line_history_view.tab_content_text(0, cx),
+                "Line History: main.rs:2"
+            );
+            let text = line_history_view.editor.read(cx).text(cx);
+            assert!(text.contains("println!(\"hi\");"), "{text}");
+            let editor = line_history_view.editor.read(cx);
+            let multibuffer = editor.buffer().read(cx);
+            let buffer_ids = multibuffer.all_buffer_ids();
+            assert_eq!(buffer_ids.len(), 2);
+            for buffer_id in buffer_ids {
+                let buffer = multibuffer.buffer(buffer_id).unwrap();
+                let is_newest = buffer.read(cx).text().contains("hi");
+                assert_eq!(editor.is_buffer_folded(buffer_id, cx), !is_newest);
+            }
+        });
+    }
+}
@@ -0,0 +1,812 @@
+//! PatchView renders the contents of a `.patch` or `.diff` file as a read-only diff.
+
+use anyhow::{Context as _, Result};
+use buffer_diff::BufferDiff;
+use editor::{Editor, EditorEvent, MultiBuffer, PathKey};
+use gpui::{
+    AnyElement, App, AppContext as _, AsyncApp, Context, Entity, EventEmitter, FocusHandle,
+    Focusable, Font, IntoElement, Render, SharedString, Task, WeakEntity, Window,
+};
+use language::{Buffer, Capability, HighlightedText, LanguageRegistry, Point};
+use project::{Project, ProjectPath};
+use std::{
+    any::{Any, TypeId},
+    path::Path,
+    sync::Arc,
+};
+use ui::{Tooltip, prelude::*};
+use util::rel_path::RelPath;
+use workspace::{
+    Item, ItemHandle, ItemNavHistory, ToolbarItemEvent, ToolbarItemLocation, ToolbarItemView,
+    Workspace,
+    item::{ItemEvent, TabContentParams},
+    notifications::DetachAndPromptErr,
+    searchable::SearchableItemHandle,
+};
+
+pub fn register(workspace: &mut Workspace) {
+    workspace.register_action(|workspace, _: &git::ViewPatch, window, cx| {
+        let Some(patch_buffer) = workspace
+            .active_item_as::<Editor>(cx)
+            .and_then(|editor| editor.read(cx).buffer().read(cx).as_singleton())
+            .filter(|buffer| is_patch_file(buffer.read(cx)))
+        else {
+            return;
+        };
+        PatchView::open(patch_buffer, workspace, window, cx).detach_and_prompt_err(
+            "Failed to open patch",
+            window,
+            cx,
+            |_, _, _| None,
+        );
+    });
+}
+
+/// Whether the buffer holds a file that is conventionally a unified diff.
+pub fn is_patch_file(buffer: &Buffer) -> bool {
+    buffer
+        .file()
+        .and_then(|file| file.path().extension())
+        .is_some_and(|extension| matches!(extension, "patch" | "diff"))
+}
+
+pub struct PatchView {
+    editor: Entity<Editor>,
+    patch_buffer: Entity<Buffer>,
+    files: Vec<FilePatch>,
+    project: Entity<Project>,
+}
+
+/// The changes a patch makes to a single file.
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) struct FilePatch {
+    /// `None` when the patch creates the file.
+    old_path: Option<String>,
+    /// `None` when the patch deletes the file.
+    new_path: Option<String>,
+    /// Each hunk's text, starting with its `@@` header line.
+    hunks: Vec<String>,
+}
+
+impl FilePatch {
+    fn path(&self) -> Option<&str> {
+        self.new_path.as_deref().or(self.old_path.as_deref())
+    }
+
+    fn unified_diff(&self) -> String {
+        let old_path = self
+            .old_path
+            .as_ref()
+            .map_or("/dev/null".into(), |path| format!("a/{path}"));
+        let new_path = self
+            .new_path
+            .as_ref()
+            .map_or("/dev/null".into(), |path| format!("b/{path}"));
+        let mut diff = format!("--- {old_path}\n+++ {new_path}\n");
+        for hunk in &self.hunks {
+            diff.push_str(hunk);
+        }
+        diff
+    }
+
+    /// Returns the lines each side of the hunks covers, joined together.
+    fn hunk_texts(&self) -> (String, String) {
+        let mut old_text = String::new();
+        let mut new_text = String::new();
+        for hunk in &self.hunks {
+            let mut previous_marker = None;
+            for line in hunk.split_inclusive('\n').skip(1) {
+                let marker = line.as_bytes().first().copied();
+                match marker {
+                    Some(b' ') => {
+                        old_text.push_str(&line[1..]);
+                        new_text.push_str(&line[1..]);
+                    }
+                    Some(b'-') => old_text.push_str(&line[1..]),
+                    Some(b'+') => new_text.push_str(&line[1..]),
+                    // "\ No newline at end of file" applies to the line before it.
+                    Some(b'\\') => {
+                        if previous_marker != Some(b'+') && old_text.ends_with('\n') {
+                            old_text.pop();
+                        }
+                        if previous_marker != Some(b'-') && new_text.ends_with('\n') {
+                            new_text.pop();
+                        }
+                    }
+                    _ => {
+                        old_text.push_str(line);
+                        new_text.push_str(line);
+                    }
+                }
+                previous_marker = marker;
+            }
+        }
+        (old_text, new_text)
+    }
+}
+
+pub(crate) fn parse_patch(text: &str) -> Vec<FilePatch> {
+    let mut files = Vec::new();
+    let mut current_file: Option<FilePatch> = None;
+    let mut lines = text.split_inclusive('\n').peekable();
+
+    while let Some(line) = lines.next() {
+        if let Some(old_path) = line.strip_prefix("--- ") {
+            let Some(new_path) = lines.next_if(|line| line.starts_with("+++ ")) else {
+                continue;
+            };
+            files.extend(current_file.take());
+            let old_path = parse_patch_path(old_path);
+            let new_path = parse_patch_path(&new_path["+++ ".len()..]);
+            // Git prefixes paths with `a/` and `b/` unless it's told not to, and other tools
+            // leave them as they are, so the prefix is only stripped when both sides have it.
+            let has_prefixes = old_path.as_ref().is_none_or(|path| path.starts_with("a/"))
+                && new_path.as_ref().is_none_or(|path| path.starts_with("b/"));
+            let strip_prefix = |path: Option<String>| match path {
+                Some(path) if has_prefixes => Some(path[2..].to_string()),
+                path => path,
+            };
+            current_file = Some(FilePatch {
+                old_path: strip_prefix(old_path),
+                new_path: strip_prefix(new_path),
+                hunks: Vec::new(),
+            });
+        } else if let Some(file) = current_file.as_mut()
+            && let Some((mut old_remaining, mut new_remaining)) = parse_hunk_header(line)
+        {
+            let mut hunk = line.to_string();
+            while old_remaining > 0 || new_remaining > 0 {
+                let Some(line) = lines.next() else {
+                    break;
+                };
+                match line.as_bytes().first() {
+                    Some(b'-') => old_remaining = old_remaining.saturating_sub(1),
+                    Some(b'+') => new_remaining = new_remaining.saturating_sub(1),
+                    Some(b'\\') => {}
+                    _ => {
+                        old_remaining = old_remaining.saturating_sub(1);
+                        new_remaining = new_remaining.saturating_sub(1);
+                    }
+                }
+                hunk.push_str(line);
+            }
+            if let Some(line) = lines.next_if(|line| line.starts_with('\\')) {
+                hunk.push_str(line);
+            }
+            file.hunks.push(hunk);
+        }
+    }
+
+    files.extend(current_file);
+    files
+}
+
+fn parse_patch_path(path: &str) -> Option<String> {
+    let path = path.trim_end_matches(['\n', '\r']);
+    // Some tools append a timestamp after a tab.
+    let path = path.split('\t').next().unwrap_or(path);
+    if path == "/dev/null" {
+        return None;
+    }
+    Some(path.to_string())
+}
+
+/// Returns how many old and new lines the hunk starting with `line` spans.
+fn parse_hunk_header(line: &str) -> Option<(usize, usize)> {
+    let ranges = line.strip_prefix("@@ -")?.split(" @@").next()?;
+    let (old_range, new_range) = ranges.split_once(" +")?;
+    let line_count = |range: &str| match range.split_once(',') {
+        Some((_, count)) => count.parse().ok(),
+        None => Some(1),
+    };
+    Some((line_count(old_range)?, line_count(new_range)?))
+}
+
+impl PatchView {
+    pub fn open(
+        patch_buffer: Entity<Buffer>,
+        workspace: &Workspace,
+        window: &mut Window,
+        cx: &mut App,
+    ) -> Task<Result<Entity<Self>>> {
+        let project = workspace.project().clone();
+        let languages = project.read(cx).languages().clone();
+        let files = parse_patch(&patch_buffer.read(cx).text());
+        let workspace = workspace.weak_handle();
+
+        window.spawn(cx, async move |cx| {
+            anyhow::ensure!(
+                !files.is_empty(),
+                "the file does not contain a unified diff"
+            );
+            let mut entries = Vec::with_capacity(files.len());
+            for file in &files {
+                entries.push(build_entry(file, &languages, cx).await?);
+            }
+
+            workspace.update_in(cx, |workspace, window, cx| {
+                let multibuffer = cx.new(|cx| {
+                    let mut multibuffer = MultiBuffer::new(Capability::ReadOnly);
+                    multibuffer.set_all_diff_hunks_expanded(cx);
+                    for (index, (file, (buffer, diff))) in files.iter().zip(entries).enumerate() {
+                        let Some(path) = file.path().and_then(|path| RelPath::unix(path).ok())
+                        else {
+                            continue;
+                        };
+                        let max_point = buffer.read(cx).max_point();
+                        multibuffer.set_excerpts_for_path(
+                            PathKey::with_sort_prefix(index as u64, path.into_arc()),
+                            buffer,
+                            [Point::zero()..max_point],
+                            0,
+                            cx,
+                        );
+                        multibuffer.add_diff(diff, cx);
+                    }
+                    multibuffer
+                });
+
+                let patch_view = cx.new(|cx| {
+                    PatchView::new(
+                        multibuffer,
+                        patch_buffer,
+                        files,
+                        project.clone(),
+                        window,
+                        cx,
+                    )
+                });
+                workspace.active_pane().update(cx, |pane, cx| {
+                    pane.add_item(Box::new(patch_view.clone()), true, true, None, window, cx);
+                });
+                patch_view
+            })
+        })
+    }
+
+    fn new(
+        multibuffer: Entity<MultiBuffer>,
+        patch_buffer: Entity<Buffer>,
+        files: Vec<FilePatch>,
+        project: Entity<Project>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Self {
+        let editor = cx.new(|cx| {
+            let mut editor =
+                Editor::for_multibuffer(multibuffer, Some(project.clone()), window, cx);
+            editor.set_read_only(true);
+            editor.start_temporary_diff_override();
+            editor.disable_diagnostics(cx);
+            editor.set_expand_all_diff_hunks(cx);
+            editor.set_render_diff_hunk_controls(
+                Arc::new(|_, _, _, _, _, _, _, _| gpui::Empty.into_any_element()),
+                cx,
+            );
+            editor
+        });
+
+        Self {
+            editor,
+            patch_buffer,
+            files,
+            project,
+        }
+    }
+
+    /// Applies the patch to the files in the project and saves them.
+    ///
+    /// Nothing is written unless every file's hunks apply cleanly.
+    pub fn apply_to_workspace(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let project = self.project.clone();
+        let files = self.files.clone();
+        cx.spawn_in(window, async move |_, cx| {
+            let mut changes = Vec::with_capacity(files.len());
+            for file in &files {
+                let path = file.path().context("patch entry has no path")?;
+                let project_path = project
+                    .read_with(cx, |project, cx| project_path_for(project, path, cx))
+                    .with_context(|| format!("{path} is not in the project"))?;
+                if file.old_path.is_none() {
+                    let exists = project.read_with(cx, |project, cx| {
+                        project.entry_for_path(&project_path, cx).is_some()
+                    });
+                    anyhow::ensure!(!exists, "the patch creates {path}, which already exists");
+                }
+                let buffer = project
+                    .update(cx, |project, cx| {
+                        project.open_buffer(project_path.clone(), cx)
+                    })
+                    .await?;
+                if file.new_path.is_none() {
+                    // Only a file that still holds exactly what the patch removes is deleted.
+                    let (old_text, _) = file.hunk_texts();
+                    let matches = buffer.read_with(cx, |buffer, _| buffer.text() == old_text);
+                    anyhow::ensure!(matches, "the patch does not apply to {path}");
+                    changes.push((project_path, None));
+                    continue;
+                }
+                let base_text = if file.old_path.is_some() {
+                    buffer.read_with(cx, |buffer, _| buffer.text())
+                } else {
+                    String::new()
+                };
+                let new_text = language::apply_diff_patch(&base_text, &file.unified_diff())
+                    .with_context(|| format!("the patch does not apply to {path}"))?;
+                changes.push((project_path, Some((buffer, new_text))));
+            }
+
+            for (project_path, change) in changes {
+                match change {
+                    Some((buffer, new_text)) => {
+                        let diff = buffer
+                            .read_with(cx, |buffer, cx| buffer.diff(new_text, cx))
+                            .await;
+                        buffer.update(cx, |buffer, cx| {
+                            buffer.apply_diff(diff, cx);
+                        });
+                        project
+                            .update(cx, |project, cx| project.save_buffer(buffer, cx))
+                            .await?;
+                    }
+                    None => {
+                        if let Some(task) = project.update(cx, |project, cx| {
+                            project.delete_file(project_path, false, cx)
+                        }) {
+                            task.await?;
+                        }
+                    }
+                }
+            }
+            anyhow::Ok(())
+        })
+        .detach_and_prompt_err("Failed to apply patch", window, cx, |_, _, _| None);
+    }
+
+    fn title(&self, cx: &App) -> SharedString {
+        let file_name = self
+            .patch_buffer
+            .read(cx)
+            .file()
+            .map(|file| file.file_name(cx).to_string())
+            .unwrap_or_else(|| "untitled".into());
+        file_name.into()
+    }
+}
+
+fn project_path_for(project: &Project, path: &str, cx: &App) -> Option<ProjectPath> {
+    project.find_project_path(path, cx).or_else(|| {
+        // The patch creates the file, so it isn't in any worktree yet.
+        let worktree = project.visible_worktrees(cx).next()?;
+        Some(ProjectPath {
+            worktree_id: worktree.read(cx).id(),
+            path: RelPath::unix(path).ok()?.into_arc(),
+        })
+    })
+}
+
+pub(crate) async fn build_entry(
+    file: &FilePatch,
+    languages: &Arc<LanguageRegistry>,
+    cx: &mut AsyncApp,
+) -> Result<(Entity<Buffer>, Entity<BufferDiff>)> {
+    let (old_text, new_text) = file.hunk_texts();
+    let language = match file.path() {
+        Some(path) => languages
+            .load_language_for_file_path(Path::new(path))
+            .await
+            .ok(),
+        None => None,
+    };
+
+    let buffer = cx.new(|cx| {
+        let mut buffer = Buffer::local(new_text, cx);
+        buffer.set_language(language.clone(), cx);
+        buffer.set_capability(Capability::ReadOnly, cx);
+        buffer
+    });
+    let buffer_snapshot = buffer.read_with(cx, |buffer, _| buffer.snapshot());
+    let diff = cx.new(|cx| BufferDiff::new(&buffer_snapshot.text, cx));
+    let update = diff
+        .update(cx, |diff, cx| {
+            diff.update_diff(
+                buffer_snapshot.text.clone(),
+                Some(old_text.into()),
+                Some(true),
+                language.clone(),
+                cx,
+            )
+        })
+        .await;
+    diff.update(cx, |diff, cx| {
+        diff.language_changed(language, Some(languages.clone()), cx);
+        diff.set_snapshot(update, &buffer_snapshot.text, cx)
+    })
+    .await;
+
+    Ok((buffer, diff))
+}
+
+impl EventEmitter<EditorEvent> for PatchView {}
+
+impl Focusable for PatchView {
+    fn focus_handle(&self, cx: &App) -> FocusHandle {
+        self.editor.focus_handle(cx)
+    }
+}
+
+impl Item for PatchView {
+    type Event = EditorEvent;
+
+    fn tab_icon(&self, _window: &Window, _cx: &App) -> Option<Icon> {
+        Some(Icon::new(IconName::Diff).color(Color::Muted))
+    }
+
+    fn tab_content(&self, params: TabContentParams, _window: &Window, cx: &App) -> AnyElement {
+        Label::new(self.title(cx))
+            .color(if params.selected {
+                Color::Default
+            } else {
+                Color::Muted
+            })
+            .into_any_element()
+    }
+
+    fn tab_content_text(&self, _detail: usize, cx: &App) -> SharedString {
+        self.title(cx)
+    }
+
+    fn tab_tooltip_text(&self, cx: &App) -> Option<SharedString> {
+        let file = self.patch_buffer.read(cx).file()?;
+        Some(file.full_path(cx).to_string_lossy().into_owned().into())
+    }
+
+    fn to_item_events(event: &EditorEvent, f: &mut dyn FnMut(ItemEvent)) {
+        Editor::to_item_events(event, f)
+    }
+
+    fn telemetry_event_text(&self) -> Option<&'static str> {
+        Some("Patch View Opened")
+    }
+
+    fn deactivated(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        self.editor
+            .update(cx, |editor, cx| editor.deactivated(window, cx));
+    }
+
+    fn act_as_type<'a>(
+        &'a self,
+        type_id: TypeId,
+        self_handle: &'a Entity<Self>,
+        _: &'a App,
+    ) -> Option<gpui::AnyEntity> {
+        if type_id == TypeId::of::<Self>() {
+            Some(self_handle.clone().into())
+        } else if type_id == TypeId::of::<Editor>() {
+            Some(self.editor.clone().into())
+        } else {
+            None
+        }
+    }
+
+    fn as_searchable(&self, _: &Entity<Self>, _: &App) -> Option<Box<dyn SearchableItemHandle>> {
+        Some(Box::new(self.editor.clone()))
+    }
+
+    fn set_nav_history(
+        &mut self,
+        nav_history: ItemNavHistory,
+        _: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.editor.update(cx, |editor, _| {
+            editor.set_nav_history(Some(nav_history));
+        });
+    }
+
+    fn navigate(
+        &mut self,
+        data: Arc<dyn Any + Send>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> bool {
+        self.editor
+            .update(cx, |editor, cx| editor.navigate(data, window, cx))
+    }
+
+    fn breadcrumb_location(&self, _: &App) -> ToolbarItemLocation {
+        ToolbarItemLocation::PrimaryLeft
+    }
+
+    fn breadcrumbs(&self, cx: &App) -> Option<(Vec<HighlightedText>, Option<Font>)> {
+        self.editor.breadcrumbs(cx)
+    }
+
+    fn added_to_workspace(
+        &mut self,
+        workspace: &mut Workspace,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.editor.update(cx, |editor, cx| {
+            editor.added_to_workspace(workspace, window, cx)
+        });
+    }
+}
+
+impl Render for PatchView {
+    fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        div()
+            .size_full()
+            .on_action(cx.listener(|this, _: &git::ApplyPatch, window, cx| {
+                this.apply_to_workspace(window, cx)
+            }))
+            .child(self.editor.clone())
+    }
+}
+
+enum PatchToolbarTarget {
+    PatchFile,
+    PatchView(WeakEntity<PatchView>),
+}
+
+/// Offers to render patch files as diffs, and to apply the patch shown in a [`PatchView`].
+pub struct PatchViewToolbar {
+    target: Option<PatchToolbarTarget>,
+}
+
+impl PatchViewToolbar {
+    pub fn new() -> Self {
+        Self { target: None }
+    }
+}
+
+impl EventEmitter<ToolbarItemEvent> for PatchViewToolbar {}
+
+impl Render for PatchViewToolbar {
+    fn render(&mut self, _window: &mut Window, _cx: &mut Context<Self>) -> impl IntoElement {
+        match &self.target {
+            None => div(),
+            Some(PatchToolbarTarget::PatchFile) => div().child(
+                Button::new("view_patch", "View as Diff")
+                    .label_size(LabelSize::Small)
+                    .tooltip(Tooltip::for_action_title("View as Diff", &git::ViewPatch))
+                    .on_click(|_, window, cx| {
+                        window.dispatch_action(Box::new(git::ViewPatch), cx);
+                    }),
+            ),
+            Some(PatchToolbarTarget::PatchView(patch_view)) => {
+                let patch_view = patch_view.clone();
+                div().child(
+                    Button::new("apply_patch", "Apply Patch")
+                        .label_size(LabelSize::Small)
+                        .tooltip(Tooltip::for_action_title(
+                            "Apply Patch to Workspace",
+                            &git::ApplyPatch,
+                        ))
+                        .on_click(move |_, window, cx| {
+                            patch_view
+                                .update(cx, |patch_view, cx| {
+                                    patch_view.apply_to_workspace(window, cx)
+                                })
+                                .ok();
+                        }),
+                )
+            }
+        }
+    }
+}
+
+impl ToolbarItemView for PatchViewToolbar {
+    fn set_active_pane_item(
+        &mut self,
+        active_pane_item: Option<&dyn ItemHandle>,
+        _: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> ToolbarItemLocation {
+        self.target = active_pane_item.and_then(|item| {
+            if let Some(patch_view) = item.act_as::<PatchView>(cx) {
+                return Some(PatchToolbarTarget::PatchView(patch_view.downgrade()));
+            }
+            let editor = item.act_as::<Editor>(cx)?;
+            let buffer = editor.read(cx).buffer().read(cx).as_singleton()?;
+            is_patch_file(buffer.read(cx)).then_some(PatchToolbarTarget::PatchFile)
+        });
+        if self.target.is_some() {
+            ToolbarItemLocation::PrimaryRight
+        } else {
+            ToolbarItemLocation::Hidden
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use gpui::{BorrowAppContext, TestAppContext};
+    use project::{FakeFs, Fs};
+    use serde_json::json;
+    use settings::SettingsStore;
+    use unindent::unindent;
+    use util::path;
+    use workspace::MultiWorkspace;
+
+    fn init_test(cx: &mut TestAppContext) {
+        cx.update(|cx| {
+            let settings_store = SettingsStore::test(cx);
+            cx.set_global(settings_store);
+            cx.update_global::<SettingsStore, _>(|store, cx| {
+                store.update_user_settings(cx, |settings| {
+                    settings.editor.diff_view_style = Some(settings::DiffViewStyle::Unified);
+                });
+            });
+            theme::init(theme::LoadThemes::JustBase, cx);
+        });
+    }
+
+    const PATCH: &str = "diff --git a/src/main.rs b/src/main.rs
+index 1111111..2222222 100644
+--- a/src/main.rs
++++ b/src/main.rs
+@@ -1,3 +1,3 @@
+ fn main() {
+-    println!(\"old\");
++    println!(\"new\");
+ }
+diff --git a/notes.txt b/notes.txt
+new file mode 100644
+--- /dev/null
++++ b/notes.txt
+@@ -0,0 +1,1 @@
++hello
+";
+
+    #[test]
+    fn test_parse_patch() {
+        let files = parse_patch(PATCH);
+        assert_eq!(
+            files,
+            vec![
+                FilePatch {
+                    old_path: Some("src/main.rs".into()),
+                    new_path: Some("src/main.rs".into()),
+                    hunks: vec![
+                        "@@ -1,3 +1,3 @@\n fn main() {\n-    println!(\"old\");\n+    println!(\"new\");\n }\n"
+                            .into()
+                    ],
+                },
+                FilePatch {
+                    old_path: None,
+                    new_path: Some("notes.txt".into()),
+                    hunks: vec!["@@ -0,0 +1,1 @@\n+hello\n".into()],
+                },
+            ]
+        );
+        assert_eq!(
+            files[0].hunk_texts(),
+            (
+                "fn main() {\n    println!(\"old\");\n}\n".into(),
+                "fn main() {\n    println!(\"new\");\n}\n".into()
+            )
+        );
+
+        let files = parse_patch(
+            "--- a/notes.txt\n+++ a/notes.txt\n@@ -1 +1 @@\n-old\n\\ No newline at end of file\n+new\n",
+        );
+        assert_eq!(files[0].path(), Some("a/notes.txt"));
+        assert_eq!(files[0].hunk_texts(), ("old".into(), "new\n".into()));
+    }
+
+    #[gpui::test]
+    async fn test_apply_patch_to_workspace(cx: &mut TestAppContext) {
+        init_test(cx);
+
+        let fs = FakeFs::new(cx.executor());
+        fs.insert_tree(
+            path!("/project"),
+            json!({
+                "changes.patch": PATCH,
+                "src": {
+                    "main.rs": "fn main() {\n    println!(\"old\");\n}\n",
+                },
+            }),
+        )
+        .await;
+
+        let project = Project::test(fs.clone(), [path!("/project").as_ref()], cx).await;
+        let (multi_workspace, cx) =
+            cx.add_window_view(|window, cx| MultiWorkspace::test_new(project.clone(), window, cx));
+        let workspace = multi_workspace.read_with(cx, |mw, _| mw.workspace().clone());
+
+        let patch_buffer = project
+            .update(cx, |project, cx| {
+                project.open_local_buffer(path!("/project/changes.patch"), cx)
+            })
+            .await
+            .unwrap();
+        assert!(patch_buffer.read_with(cx, |buffer, _| is_patch_file(buffer)));
+
+        let patch_view = workspace
+            .update_in(cx, |workspace, window, cx| {
+                PatchView::open(patch_buffer, workspace, window, cx)
+            })
+            .await
+            .unwrap();
+        cx.run_until_parked();
+
+        patch_view.read_with(cx, |patch_view, cx| {
+            assert_eq!(patch_view.tab_content_text(0, cx), "changes.patch");
+            let text = patch_view.editor.read(cx).text(cx);
+            assert!(text.contains("println!(\"new\");"), "{text}");
+            assert!(text.contains("hello"), "{text}");
+        });
+
+        patch_view.update_in(cx, |patch_view, window, cx| {
+            patch_view.apply_to_workspace(window, cx)
+        });
+        cx.run_until_parked();
+
+        assert_eq!(
+            fs.load(path!("/project/src/main.rs").as_ref())
+                .await
+                .unwrap(),
+            unindent(
+                "
+                fn main() {
+                    println!(\"new\");
+                }
+                "
+            )
+        );
+        assert_eq!(
+            fs.load(path!("/project/notes.txt").as_ref()).await.unwrap(),
+            "hello\n"
+        );
+    }
+
+    #[gpui::test]
+    async fn test_apply_patch_refuses_conflicts(cx: &mut TestAppContext) {
+        init_test(cx);
+
+        let fs = FakeFs::new(cx.executor());
+        fs.insert_tree(
+            path!("/project"),
+            json!({
+                "create.patch": "--- /dev/null\n+++ b/notes.txt\n@@ -0,0 +1 @@\n+hello\n",
+                "delete.patch": "--- a/notes.txt\n+++ /dev/null\n@@ -1 +0,0 @@\n-hello\n",
+                "notes.txt": "changed\n",
+            }),
+        )
+        .await;
+
+        let project = Project::test(fs.clone(), [path!("/project").as_ref()], cx).await;
+        let (multi_workspace, cx) =
+            cx.add_window_view(|window, cx| MultiWorkspace::test_new(project.clone(), window, cx));
+        let workspace = multi_workspace.read_with(cx, |mw, _| mw.workspace().clone());
+
+        for patch_path in [
+            path!("/project/create.patch"),
+            path!("/project/delete.patch"),
+        ] {
+            let patch_buffer = project
+                .update(cx, |project, cx| project.open_local_buffer(patch_path, cx))
+                .await
+                .unwrap();
+            let patch_view = workspace
+                .update_in(cx, |workspace, window, cx| {
+                    PatchView::open(patch_buffer, workspace, window, cx)
+                })
+                .await
+                .unwrap();
+            patch_view.update_in(cx, |patch_view, window, cx| {
+                patch_view.apply_to_workspace(window, cx)
+            });
+            cx.run_until_parked();
+
+            assert_eq!(
+                fs.load(path!("/project/notes.txt").as_ref()).await.unwrap(),
+                "changed\n"
+            );
+        }
+    }
+}
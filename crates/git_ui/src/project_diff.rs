@@ -1,12 +1,13 @@
 use crate::{
     conflict_view::ConflictAddon,
+    diff_filter::{DiffFilter, DiffFilterBar, DiffFilterEvent},
     git_panel::{GitPanel, GitPanelAddon, GitStatusEntry},
     git_panel_settings::GitPanelSettings,
-    resolve_active_repository,
+    git_status_icon, resolve_active_repository,
 };
 use agent_settings::AgentSettings;
 use anyhow::{Context as _, Result, anyhow};
-use buffer_diff::{BufferDiff, DiffHunkSecondaryStatus};
+use buffer_diff::{BufferDiff, BufferDiffSnapshot, DiffHunkSecondaryStatus};
 use collections::{HashMap, HashSet};
 use editor::{
     Addon, Editor, EditorEvent, EditorSettings, SelectionEffects, SplittableEditor,
@@ -22,10 +23,11 @@ use git::{
 };
 use gpui::{
     Action, AnyElement, App, AppContext as _, AsyncWindowContext, Entity, EventEmitter,
-    FocusHandle, Focusable, Render, Subscription, Task, WeakEntity, actions,
+    FocusHandle, Focusable, MouseButton, Render, Subscription, Task, UniformListScrollHandle,
+    WeakEntity, actions, uniform_list,
 };
-use language::{Anchor, Buffer, BufferId, Capability, OffsetRangeExt};
-use multi_buffer::{MultiBuffer, PathKey};
+use language::{Anchor, Buffer, BufferId, BufferSnapshot, Capability, OffsetRangeExt};
+use multi_buffer::{ExcerptInfo, MultiBuffer, PathKey};
 use project::{
     Project, ProjectPath,
     git_store::{
@@ -36,9 +38,14 @@ use project::{
 use settings::{Settings, SettingsStore};
 use smol::future::yield_now;
 use std::any::{Any, TypeId};
+use std::hash::Hasher as _;
+use std::ops::Range;
 use std::sync::Arc;
 use theme::ActiveTheme;
-use ui::{DiffStat, Divider, KeyBinding, Tooltip, prelude::*, vertical_divider};
+use ui::{
+    Checkbox, DiffStat, Disclosure, Divider, KeyBinding, ListItem, ListItemSpacing, Tooltip,
+    prelude::*, vertical_divider,
+};
 use util::{ResultExt as _, rel_path::RelPath};
 use workspace::{
     CloseActiveItem, ItemNavHistory, SerializableItem, ToolbarItemEvent, ToolbarItemLocation,
@@ -62,6 +69,15 @@ actions!(
         BranchDiff,
         /// Opens a new agent thread with the branch diff for review.
         ReviewDiff,
+        /// Shows or hides the bar that narrows the project diff down by path,
+        /// status, and text.
+        ToggleDiffFilter,
+        /// Shows or hides the list of the files in the project diff.
+        ToggleDiffFileList,
+        /// Collapses every file in the project diff down to its header.
+        CollapseAllDiffFiles,
+        /// Expands every collapsed file in the project diff.
+        ExpandAllDiffFiles,
         LeaderAndFollower,
     ]
 );
@@ -76,10 +92,33 @@ pub struct ProjectDiff {
     focus_handle: FocusHandle,
     pending_scroll: Option<PathKey>,
     review_comment_count: usize,
+    filter_bar: Option<(Entity<DiffFilterBar>, Subscription)>,
+    file_statuses: HashMap<Arc<RelPath>, FileStatus>,
+    /// A hash of every file's text and diff base, which tells when a viewed file has changed.
+    content_hashes: HashMap<Arc<RelPath>, u64>,
+    /// The files marked as viewed, with their content hash at the time.
+    viewed_files: HashMap<Arc<RelPath>, u64>,
+    show_file_list: bool,
+    file_list: Vec<DiffFileEntry>,
+    /// Whether files, their diffs, or their folded or viewed state changed since the file list
+    /// was last built.
+    file_list_is_stale: bool,
+    file_list_scroll_handle: UniformListScrollHandle,
     _task: Task<Result<()>>,
     _subscription: Subscription,
 }
 
+/// A row of the file list, which follows the order of the files in the diff.
+struct DiffFileEntry {
+    path_key: PathKey,
+    buffer_id: BufferId,
+    status: Option<FileStatus>,
+    added: u32,
+    removed: u32,
+    is_folded: bool,
+    is_viewed: bool,
+}
+
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum RefreshReason {
     DiffChanged,
@@ -349,6 +388,7 @@ impl ProjectDiff {
             multibuffer
         });
 
+        let project_diff = cx.weak_entity();
         let editor = cx.new(|cx| {
             let diff_display_editor = SplittableEditor::new(
                 EditorSettings::get_global(cx).diff_view_style,
@@ -371,11 +411,18 @@ impl ProjectDiff {
 
                 match branch_diff.read(cx).diff_base() {
                     DiffBase::Head => {
-                        editor.register_addon(GitPanelAddon {
-                            workspace: workspace.downgrade(),
+                        editor.register_addon(ViewedFileAddon {
+                            project_diff,
+                            git_panel_addon: Some(GitPanelAddon {
+                                workspace: workspace.downgrade(),
+                            }),
                         });
                     }
                     DiffBase::Merge { .. } => {
+                        editor.register_addon(ViewedFileAddon {
+                            project_diff,
+                            git_panel_addon: None,
+                        });
                         editor.register_addon(BranchDiffAddon {
                             branch_diff: branch_diff.clone(),
                         });
@@ -446,6 +493,14 @@ impl ProjectDiff {
             buffer_diff_subscriptions: Default::default(),
             pending_scroll: None,
             review_comment_count: 0,
+            filter_bar: None,
+            file_statuses: HashMap::default(),
+            content_hashes: HashMap::default(),
+            viewed_files: load_viewed_files(&workspace, cx),
+            show_file_list: false,
+            file_list: Vec::new(),
+            file_list_is_stale: true,
+            file_list_scroll_handle: UniformListScrollHandle::new(),
             _task: task,
             _subscription: Subscription::join(
                 branch_diff_subscription,
@@ -458,6 +513,56 @@ impl ProjectDiff {
         self.branch_diff.read(cx).diff_base()
     }
 
+    fn filter(&self, cx: &App) -> DiffFilter {
+        self.filter_bar
+            .as_ref()
+            .map(|(filter_bar, _)| filter_bar.read(cx).filter().clone())
+            .unwrap_or_default()
+    }
+
+    fn toggle_diff_filter(
+        &mut self,
+        _: &ToggleDiffFilter,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        if self.filter_bar.is_some() {
+            self.close_diff_filter(window, cx);
+            return;
+        }
+        let path_style = self.project.read(cx).path_style(cx);
+        let filter_bar = cx.new(|cx| DiffFilterBar::new(path_style, window, cx));
+        let subscription =
+            cx.subscribe_in(
+                &filter_bar,
+                window,
+                |this, _, event, window, cx| match event {
+                    DiffFilterEvent::Changed => this.refresh_for_filter(window, cx),
+                    DiffFilterEvent::Closed => this.close_diff_filter(window, cx),
+                },
+            );
+        window.focus(&filter_bar.focus_handle(cx), cx);
+        self.filter_bar = Some((filter_bar, subscription));
+        cx.notify();
+    }
+
+    fn close_diff_filter(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let was_filtered = !self.filter(cx).is_empty();
+        self.filter_bar = None;
+        if was_filtered {
+            self.refresh_for_filter(window, cx);
+        }
+        window.focus(&self.focus_handle(cx), cx);
+        cx.notify();
+    }
+
+    fn refresh_for_filter(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        self._task = window.spawn(cx, {
+            let this = cx.weak_entity();
+            async |cx| Self::refresh(this, RefreshReason::StatusesChanged, cx).await
+        });
+    }
+
     pub fn move_to_entry(
         &mut self,
         entry: GitStatusEntry,
@@ -471,6 +576,18 @@ impl ProjectDiff {
         let sort_prefix = sort_prefix(repo, &entry.repo_path, entry.status, cx);
         let path_key = PathKey::with_sort_prefix(sort_prefix, entry.repo_path.as_ref().clone());
 
+        // Untracked files may be collapsed, but one picked in the git panel should read like any
+        // other change, with all of its lines shown as added.
+        if entry.status.is_untracked()
+            && let Some(buffer) = self.multibuffer.read(cx).buffer_for_path(&path_key, cx)
+        {
+            let buffer_id = buffer.read(cx).remote_id();
+            self.editor.update(cx, |editor, cx| {
+                editor
+                    .rhs_editor()
+                    .update(cx, |editor, cx| editor.unfold_buffer(buffer_id, cx));
+            });
+        }
         self.move_to_path(path_key, window, cx)
     }
 
@@ -558,24 +675,341 @@ impl ProjectDiff {
             let Some(diff) = snapshot.diff_for_buffer_id(buffer_id) else {
                 continue;
             };
+            let (additions, deletions) = changed_lines(diff, buffer);
+            total_additions += additions;
+            total_deletions += deletions;
+        }
 
-            let base_text = diff.base_text();
+        (total_additions, total_deletions)
+    }
 
-            for hunk in diff.hunks_intersecting_range(Anchor::MIN..Anchor::MAX, buffer) {
-                let added_rows = hunk.range.end.row.saturating_sub(hunk.range.start.row);
-                total_additions += added_rows;
+    fn file_list_entries(&self, cx: &App) -> Vec<DiffFileEntry> {
+        let multibuffer = self.multibuffer.read(cx);
+        let snapshot = multibuffer.snapshot(cx);
+        let editor = self.editor.read(cx).rhs_editor().read(cx);
+        multibuffer
+            .paths()
+            .filter_map(|path_key| {
+                let buffer = multibuffer.buffer_for_path(path_key, cx)?;
+                let buffer = buffer.read(cx);
+                let buffer_id = buffer.remote_id();
+                let (added, removed) = snapshot
+                    .diff_for_buffer_id(buffer_id)
+                    .map(|diff| changed_lines(diff, &buffer.snapshot()))
+                    .unwrap_or_default();
+                Some(DiffFileEntry {
+                    path_key: path_key.clone(),
+                    buffer_id,
+                    status: self.file_statuses.get(&path_key.path).copied(),
+                    added,
+                    removed,
+                    is_folded: editor.is_buffer_folded(buffer_id, cx),
+                    is_viewed: self.is_file_viewed(&path_key.path),
+                })
+            })
+            .collect()
+    }
 
-                let base_start = base_text
-                    .offset_to_point(hunk.diff_base_byte_range.start)
-                    .row;
-                let base_end = base_text.offset_to_point(hunk.diff_base_byte_range.end).row;
-                let deleted_rows = base_end.saturating_sub(base_start);
+    fn toggle_diff_file_list(
+        &mut self,
+        _: &ToggleDiffFileList,
+        _window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.show_file_list = !self.show_file_list;
+        cx.notify();
+    }
 
-                total_deletions += deleted_rows;
-            }
+    fn collapse_all_diff_files(
+        &mut self,
+        _: &CollapseAllDiffFiles,
+        _window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let buffer_ids = self.multibuffer.read(cx).all_buffer_ids();
+        self.editor.update(cx, |editor, cx| {
+            editor
+                .rhs_editor()
+                .update(cx, |editor, cx| editor.fold_buffers(buffer_ids, cx));
+        });
+    }
+
+    fn expand_all_diff_files(
+        &mut self,
+        _: &ExpandAllDiffFiles,
+        _window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let buffer_ids = self.multibuffer.read(cx).all_buffer_ids();
+        self.editor.update(cx, |editor, cx| {
+            editor.rhs_editor().update(cx, |editor, cx| {
+                for buffer_id in buffer_ids {
+                    editor.unfold_buffer(buffer_id, cx);
+                }
+            });
+        });
+    }
+
+    fn toggle_file_folded(&mut self, buffer_id: BufferId, cx: &mut Context<Self>) {
+        self.editor.update(cx, |editor, cx| {
+            editor.rhs_editor().update(cx, |editor, cx| {
+                if editor.is_buffer_folded(buffer_id, cx) {
+                    editor.unfold_buffer(buffer_id, cx);
+                } else {
+                    editor.fold_buffer(buffer_id, cx);
+                }
+            });
+        });
+    }
+
+    fn is_file_viewed(&self, path: &RelPath) -> bool {
+        self.viewed_files
+            .get(path)
+            .is_some_and(|viewed_hash| self.content_hashes.get(path) == Some(viewed_hash))
+    }
+
+    /// Marks a file as viewed, collapsing it until its contents change, or unmarks and expands it.
+    pub fn toggle_file_viewed(&mut self, path_key: &PathKey, cx: &mut Context<Self>) {
+        let Some(buffer) = self.multibuffer.read(cx).buffer_for_path(path_key, cx) else {
+            return;
+        };
+        let buffer_id = buffer.read(cx).remote_id();
+        let is_viewed = !self.is_file_viewed(&path_key.path);
+        if is_viewed {
+            let Some(content_hash) = self.content_hashes.get(&path_key.path).copied() else {
+                return;
+            };
+            self.viewed_files
+                .insert(path_key.path.clone(), content_hash);
+            self.save_viewed_file(&path_key.path, Some(content_hash), cx);
+        } else {
+            self.viewed_files.remove(&path_key.path);
+            self.save_viewed_file(&path_key.path, None, cx);
         }
+        self.file_list_is_stale = true;
+        self.editor.update(cx, |editor, cx| {
+            editor.rhs_editor().update(cx, |editor, cx| {
+                if is_viewed {
+                    editor.fold_buffer(buffer_id, cx);
+                } else {
+                    editor.unfold_buffer(buffer_id, cx);
+                }
+                // The header's checkbox changes even when the file was already folded.
+                cx.notify();
+            });
+        });
+        cx.notify();
+    }
 
-        (total_additions, total_deletions)
+    /// Stores whether a file is viewed, and with which content, in the workspace's database.
+    fn save_viewed_file(&self, path: &RelPath, content_hash: Option<u64>, cx: &mut App) {
+        let Some(workspace_id) = self
+            .workspace
+            .upgrade()
+            .and_then(|workspace| workspace.read(cx).database_id())
+        else {
+            return;
+        };
+        let db = persistence::ProjectDiffDb::global(cx);
+        let path = path.as_unix_str().to_string();
+        cx.background_spawn(async move {
+            match content_hash {
+                Some(content_hash) => {
+                    db.save_viewed_file(workspace_id, path, content_hash as i64)
+                        .await
+                }
+                None => db.remove_viewed_file(workspace_id, path).await,
+            }
+        })
+        .detach_and_log_err(cx);
+    }
+
+    /// How many of the files in the diff are viewed, and how many files there are.
+    pub fn viewed_file_count(&self, cx: &App) -> (usize, usize) {
+        let multibuffer = self.multibuffer.read(cx);
+        let viewed_count = multibuffer
+            .paths()
+            .filter(|path_key| self.is_file_viewed(&path_key.path))
+            .count();
+        (viewed_count, multibuffer.paths().count())
+    }
+
+    fn jump_to_file(&mut self, ix: usize, window: &mut Window, cx: &mut Context<Self>) {
+        let Some(entry) = self.file_list.get(ix) else {
+            return;
+        };
+        let (path_key, buffer_id) = (entry.path_key.clone(), entry.buffer_id);
+        self.editor.update(cx, |editor, cx| {
+            editor
+                .rhs_editor()
+                .update(cx, |editor, cx| editor.unfold_buffer(buffer_id, cx));
+        });
+        self.move_to_path(path_key, window, cx);
+        window.focus(&self.editor.focus_handle(cx), cx);
+    }
+
+    fn render_file_list_rows(
+        &mut self,
+        range: Range<usize>,
+        _window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Vec<AnyElement> {
+        let active_buffer_id = {
+            let editor = self.editor.read(cx).focused_editor().read(cx);
+            let position = editor.selections.newest_anchor().head();
+            editor
+                .buffer()
+                .read(cx)
+                .excerpt_containing(position, cx)
+                .map(|(_, buffer, _)| buffer.read(cx).remote_id())
+        };
+        range
+            .filter_map(|ix| {
+                let entry = self.file_list.get(ix)?;
+                let buffer_id = entry.buffer_id;
+                let file_name = entry
+                    .path_key
+                    .path
+                    .file_name()
+                    .unwrap_or_default()
+                    .to_string();
+                let directory = entry
+                    .path_key
+                    .path
+                    .parent()
+                    .filter(|parent| !parent.is_empty())
+                    .map(|parent| parent.as_unix_str().to_string());
+                Some(
+                    ListItem::new(ix)
+                        .spacing(ListItemSpacing::Dense)
+                        .toggle_state(active_buffer_id == Some(buffer_id))
+                        .start_slot(
+                            Disclosure::new(("project-diff-file-fold", ix), !entry.is_folded)
+                                .on_click(cx.listener(move |this, _, _, cx| {
+                                    this.toggle_file_folded(buffer_id, cx)
+                                })),
+                        )
+                        .child(
+                            h_flex()
+                                .w_full()
+                                .min_w_0()
+                                .gap_1()
+                                .when_some(entry.status, |this, status| {
+                                    this.child(git_status_icon(status))
+                                })
+                                .child(Label::new(file_name).size(LabelSize::Small).truncate())
+                                .when_some(directory, |this, directory| {
+                                    this.child(
+                                        Label::new(directory)
+                                            .size(LabelSize::Small)
+                                            .color(Color::Muted)
+                                            .truncate(),
+                                    )
+                                }),
+                        )
+                        .end_slot(
+                            h_flex()
+                                .gap_1()
+                                .child(DiffStat::new(
+                                    ("project-diff-file-stat", ix),
+                                    entry.added as usize,
+                                    entry.removed as usize,
+                                ))
+                                .child(
+                                    Checkbox::new(
+                                        ("project-diff-file-viewed", ix),
+                                        entry.is_viewed.into(),
+                                    )
+                                    .tooltip(Tooltip::text("Viewed"))
+                                    .on_click({
+                                        let path_key = entry.path_key.clone();
+                                        cx.listener(move |this, _, _, cx| {
+                                            this.toggle_file_viewed(&path_key, cx)
+                                        })
+                                    }),
+                                ),
+                        )
+                        .tooltip(Tooltip::text(entry.path_key.path.as_unix_str().to_string()))
+                        .on_click(
+                            cx.listener(move |this, _, window, cx| {
+                                this.jump_to_file(ix, window, cx)
+                            }),
+                        )
+                        .into_any_element(),
+                )
+            })
+            .collect()
+    }
+
+    fn render_file_list(&self, cx: &mut Context<Self>) -> impl IntoElement {
+        let viewed_count = self
+            .file_list
+            .iter()
+            .filter(|entry| entry.is_viewed)
+            .count();
+        let file_count = match (viewed_count, self.file_list.len()) {
+            (0, 1) => "1 file".to_string(),
+            (0, count) => format!("{count} files"),
+            (viewed_count, count) => format!("{viewed_count}/{count} files viewed"),
+        };
+        v_flex()
+            .w(rems(20.))
+            .h_full()
+            .flex_none()
+            .border_r_1()
+            .border_color(cx.theme().colors().border_variant)
+            .child(
+                h_flex()
+                    .px_2()
+                    .py_1()
+                    .justify_between()
+                    .border_b_1()
+                    .border_color(cx.theme().colors().border_variant)
+                    .child(
+                        Label::new(file_count)
+                            .size(LabelSize::Small)
+                            .color(Color::Muted),
+                    )
+                    .child(
+                        h_flex()
+                            .child(
+                                IconButton::new("collapse-all-diff-files", IconName::ListCollapse)
+                                    .icon_size(IconSize::Small)
+                                    .tooltip(Tooltip::for_action_title(
+                                        "Collapse All Files",
+                                        &CollapseAllDiffFiles,
+                                    ))
+                                    .on_click(cx.listener(|this, _, window, cx| {
+                                        this.collapse_all_diff_files(
+                                            &CollapseAllDiffFiles,
+                                            window,
+                                            cx,
+                                        )
+                                    })),
+                            )
+                            .child(
+                                IconButton::new("expand-all-diff-files", IconName::ExpandVertical)
+                                    .icon_size(IconSize::Small)
+                                    .tooltip(Tooltip::for_action_title(
+                                        "Expand All Files",
+                                        &ExpandAllDiffFiles,
+                                    ))
+                                    .on_click(cx.listener(|this, _, window, cx| {
+                                        this.expand_all_diff_files(&ExpandAllDiffFiles, window, cx)
+                                    })),
+                            ),
+                    ),
+            )
+            .child(
+                uniform_list(
+                    "project-diff-files",
+                    self.file_list.len(),
+                    cx.processor(Self::render_file_list_rows),
+                )
+                .track_scroll(&self.file_list_scroll_handle)
+                .flex_1()
+                .py_1(),
+            )
     }
 
     /// Returns the total count of review comments across all hunks/files.
@@ -679,6 +1113,13 @@ impl ProjectDiff {
                     Self::refresh(this, RefreshReason::EditorSaved, cx).await
                 });
             }
+            EditorEvent::BufferFoldToggled { .. } => {
+                self.file_list_is_stale = true;
+                cx.notify()
+            }
+            EditorEvent::ExcerptsAdded { .. } | EditorEvent::ExcerptsRemoved { .. } => {
+                self.file_list_is_stale = true;
+            }
             _ => {}
         }
         if editor.focus_handle(cx).contains_focused(window, cx)
@@ -706,6 +1147,9 @@ impl ProjectDiff {
         });
         self.buffer_diff_subscriptions
             .insert(path_key.path.clone(), (diff.clone(), subscription));
+        self.file_statuses
+            .insert(path_key.path.clone(), file_status);
+        self.file_list_is_stale = true;
 
         // TODO(split-diff) we shouldn't have a conflict addon when split
         let conflict_addon = self
@@ -718,6 +1162,20 @@ impl ProjectDiff {
 
         let snapshot = buffer.read(cx).snapshot();
         let diff_snapshot = diff.read(cx).snapshot(cx);
+        let filter = self.filter(cx);
+
+        let content_hash = content_hash(&snapshot, &diff_snapshot);
+        self.content_hashes
+            .insert(path_key.path.clone(), content_hash);
+        let viewed_file_changed = self
+            .viewed_files
+            .get(&path_key.path)
+            .is_some_and(|viewed_hash| *viewed_hash != content_hash);
+        if viewed_file_changed {
+            self.viewed_files.remove(&path_key.path);
+            self.save_viewed_file(&path_key.path, None, cx);
+        }
+        let is_viewed = self.is_file_viewed(&path_key.path);
 
         let excerpt_ranges = {
             let diff_hunk_ranges = diff_snapshot
@@ -725,6 +1183,18 @@ impl ProjectDiff {
                     Anchor::min_max_range_for_buffer(snapshot.remote_id()),
                     &snapshot,
                 )
+                .filter(|diff_hunk| {
+                    !filter.has_query()
+                        || filter.matches_hunk(&[
+                            &snapshot
+                                .text_for_range(diff_hunk.buffer_range.clone())
+                                .collect::<String>(),
+                            &diff_snapshot
+                                .base_text()
+                                .text_for_range(diff_hunk.diff_base_byte_range.clone())
+                                .collect::<String>(),
+                        ])
+                })
                 .map(|diff_hunk| diff_hunk.buffer_range.to_point(&snapshot));
             let conflicts = conflict_addon
                 .conflict_set(snapshot.remote_id())
@@ -733,16 +1203,31 @@ impl ProjectDiff {
             let mut conflicts = conflicts
                 .iter()
                 .map(|conflict| conflict.range.to_point(&snapshot))
+                .filter(|range| {
+                    !filter.has_query()
+                        || filter.matches_hunk(&[&snapshot
+                            .text_for_range(range.clone())
+                            .collect::<String>()])
+                })
                 .peekable();
 
             if conflicts.peek().is_some() {
                 conflicts.collect::<Vec<_>>()
             } else {
-                diff_hunk_ranges.collect()
+                diff_hunk_ranges.collect::<Vec<_>>()
             }
         };
 
+        if excerpt_ranges.is_empty() && filter.has_query() {
+            self.file_statuses.remove(&path_key.path);
+            self.editor.update(cx, |editor, cx| {
+                editor.remove_excerpts_for_path(path_key, cx);
+            });
+            return None;
+        }
+
         let mut needs_fold = None;
+        let is_pending_scroll = self.pending_scroll.as_ref() == Some(&path_key);
 
         let (was_empty, is_excerpt_newly_added) = self.editor.update(cx, |editor, cx| {
             let was_empty = editor.rhs_editor().read(cx).buffer().read(cx).is_empty();
@@ -771,9 +1256,14 @@ impl ProjectDiff {
                         },
                     );
                 }
+                if viewed_file_changed {
+                    editor.unfold_buffer(snapshot.remote_id(), cx);
+                }
                 if is_excerpt_newly_added
-                    && (file_status.is_deleted()
+                    && (is_viewed
+                        || file_status.is_deleted()
                         || (file_status.is_untracked()
+                            && !is_pending_scroll
                             && GitPanelSettings::get_global(cx).collapse_untracked_diff))
                 {
                     needs_fold = Some(snapshot.text.remote_id());
@@ -809,10 +1299,13 @@ impl ProjectDiff {
     ) -> Result<()> {
         let mut path_keys = Vec::new();
         let buffers_to_load = this.update(cx, |this, cx| {
-            let (repo, buffers_to_load) = this.branch_diff.update(cx, |branch_diff, cx| {
+            let (repo, mut buffers_to_load) = this.branch_diff.update(cx, |branch_diff, cx| {
                 let load_buffers = branch_diff.load_buffers(cx);
                 (branch_diff.repo().cloned(), load_buffers)
             });
+            let filter = this.filter(cx);
+            buffers_to_load
+                .retain(|entry| filter.matches_file(&entry.repo_path, entry.file_status));
             let mut previous_paths = this
                 .multibuffer
                 .read(cx)
@@ -847,7 +1340,7 @@ impl ProjectDiff {
                         }
                     }
 
-                    this.buffer_diff_subscriptions.remove(&path.path);
+                    this.forget_file(&path.path);
                     editor.remove_excerpts_for_path(path, cx);
                 }
             });
@@ -900,12 +1393,21 @@ impl ProjectDiff {
                 });
             }
             this.pending_scroll.take();
+            this.file_list_is_stale = true;
             cx.notify();
         })?;
 
         Ok(())
     }
 
+    /// Drops what is known about a file that is no longer in the diff.
+    fn forget_file(&mut self, path: &RelPath) {
+        self.buffer_diff_subscriptions.remove(path);
+        self.file_statuses.remove(path);
+        self.content_hashes.remove(path);
+        self.file_list_is_stale = true;
+    }
+
     #[cfg(any(test, feature = "test-support"))]
     pub fn excerpt_paths(&self, cx: &App) -> Vec<std::sync::Arc<util::rel_path::RelPath>> {
         self.multibuffer
@@ -916,6 +1418,55 @@ impl ProjectDiff {
     }
 }
 
+/// The number of lines that the hunks of a diff add and remove.
+fn changed_lines(diff: &BufferDiffSnapshot, buffer: &BufferSnapshot) -> (u32, u32) {
+    let base_text = diff.base_text();
+    let mut additions = 0;
+    let mut deletions = 0;
+    for hunk in diff.hunks_intersecting_range(Anchor::MIN..Anchor::MAX, buffer) {
+        additions += hunk.range.end.row.saturating_sub(hunk.range.start.row);
+        let base_start = base_text
+            .offset_to_point(hunk.diff_base_byte_range.start)
+            .row;
+        let base_end = base_text.offset_to_point(hunk.diff_base_byte_range.end).row;
+        deletions += base_end.saturating_sub(base_start);
+    }
+    (additions, deletions)
+}
+
+/// The files marked as viewed in any project diff of the workspace, which stay viewed when the
+/// diff is reopened.
+fn load_viewed_files(workspace: &Entity<Workspace>, cx: &App) -> HashMap<Arc<RelPath>, u64> {
+    let Some(workspace_id) = workspace.read(cx).database_id() else {
+        return HashMap::default();
+    };
+    persistence::ProjectDiffDb::global(cx)
+        .get_viewed_files(workspace_id)
+        .log_err()
+        .unwrap_or_default()
+        .into_iter()
+        .filter_map(|(path, content_hash)| {
+            let path = RelPath::unix(&path).log_err()?.into_arc();
+            Some((path, content_hash as u64))
+        })
+        .collect()
+}
+
+/// Hashes a file's text and diff base. The hash is stored with viewed files, and `DefaultHasher`
+/// is only stable within a build, so an update can at worst unmark them.
+fn content_hash(buffer: &BufferSnapshot, diff: &BufferDiffSnapshot) -> u64 {
+    let mut hasher = std::hash::DefaultHasher::new();
+    for chunk in buffer.as_rope().chunks() {
+        hasher.write(chunk.as_bytes());
+    }
+    // Keeps text moving between the file and its diff base from hashing the same.
+    hasher.write_u8(0xff);
+    for chunk in diff.base_text().as_rope().chunks() {
+        hasher.write(chunk.as_bytes());
+    }
+    hasher.finish()
+}
+
 fn sort_prefix(repo: &Repository, repo_path: &RepoPath, status: FileStatus, cx: &App) -> u64 {
     let settings = GitPanelSettings::get_global(cx);
 
@@ -1134,19 +1685,24 @@ impl Render for ProjectDiff {
     fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
         let is_empty = self.multibuffer.read(cx).is_empty();
         let is_branch_diff_view = matches!(self.diff_base(cx), DiffBase::Merge { .. });
+        let is_filtered = !self.filter(cx).is_empty();
+        let show_file_list = self.show_file_list && !is_empty;
+        if show_file_list && self.file_list_is_stale {
+            self.file_list = self.file_list_entries(cx);
+            self.file_list_is_stale = false;
+        }
 
-        div()
-            .track_focus(&self.focus_handle)
-            .key_context(if is_empty { "EmptyPane" } else { "GitDiff" })
-            .when(is_branch_diff_view, |this| {
-                this.on_action(cx.listener(Self::review_diff))
-            })
-            .bg(cx.theme().colors().editor_background)
+        let content = div()
             .flex()
+            .flex_1()
+            .min_w_0()
+            .h_full()
             .items_center()
             .justify_center()
-            .size_full()
-            .when(is_empty, |el| {
+            .when(is_empty && is_filtered, |el| {
+                el.child(Label::new("No changes match the filter"))
+            })
+            .when(is_empty && !is_filtered, |el| {
                 let remote_button = if let Some(panel) = self
                     .workspace
                     .upgrade()
@@ -1193,7 +1749,31 @@ impl Render for ProjectDiff {
                         ),
                 )
             })
-            .when(!is_empty, |el| el.child(self.editor.clone()))
+            .when(!is_empty, |el| el.child(self.editor.clone()));
+
+        v_flex()
+            .track_focus(&self.focus_handle)
+            .key_context(if is_empty { "EmptyPane" } else { "GitDiff" })
+            .when(is_branch_diff_view, |this| {
+                this.on_action(cx.listener(Self::review_diff))
+            })
+            .on_action(cx.listener(Self::toggle_diff_filter))
+            .on_action(cx.listener(Self::toggle_diff_file_list))
+            .on_action(cx.listener(Self::collapse_all_diff_files))
+            .on_action(cx.listener(Self::expand_all_diff_files))
+            .bg(cx.theme().colors().editor_background)
+            .size_full()
+            .when_some(self.filter_bar.as_ref(), |this, (filter_bar, _)| {
+                this.child(filter_bar.clone())
+            })
+            .child(
+                h_flex()
+                    .flex_1()
+                    .min_h_0()
+                    .w_full()
+                    .when(show_file_list, |this| this.child(self.render_file_list(cx)))
+                    .child(content),
+            )
     }
 }
 
@@ -1265,6 +1845,7 @@ mod persistence {
 
     use anyhow::Context as _;
     use db::{
+        query,
         sqlez::{domain::Domain, thread_safe_connection::ThreadSafeConnection},
         sqlez_macros::sql,
     };
@@ -1276,18 +1857,31 @@ mod persistence {
     impl Domain for ProjectDiffDb {
         const NAME: &str = stringify!(ProjectDiffDb);
 
-        const MIGRATIONS: &[&str] = &[sql!(
-                CREATE TABLE project_diffs(
-                    workspace_id INTEGER,
-                    item_id INTEGER UNIQUE,
+        const MIGRATIONS: &[&str] = &[
+            sql!(
+                    CREATE TABLE project_diffs(
+                        workspace_id INTEGER,
+                        item_id INTEGER UNIQUE,
 
-                    diff_base TEXT,
+                        diff_base TEXT,
 
-                    PRIMARY KEY(workspace_id, item_id),
-                    FOREIGN KEY(workspace_id) REFERENCES workspaces(workspace_id)
-                    ON DELETE CASCADE
-                ) STRICT;
-        )];
+                        PRIMARY KEY(workspace_id, item_id),
+                        FOREIGN KEY(workspace_id) REFERENCES workspaces(workspace_id)
+                        ON DELETE CASCADE
+                    ) STRICT;
+            ),
+            sql!(
+                    CREATE TABLE project_diff_viewed_files(
+                        workspace_id INTEGER,
+                        path TEXT,
+                        content_hash INTEGER,
+
+                        PRIMARY KEY(workspace_id, path),
+                        FOREIGN KEY(workspace_id) REFERENCES workspaces(workspace_id)
+                        ON DELETE CASCADE
+                    ) STRICT;
+            ),
+        ];
     }
 
     db::static_connection!(ProjectDiffDb, [WorkspaceDb]);
@@ -1332,6 +1926,35 @@ mod persistence {
             };
             serde_json::from_str(&diff_base_str).context("deserializing diff base")
         }
+
+        query! {
+            pub async fn save_viewed_file(
+                workspace_id: WorkspaceId,
+                path: String,
+                content_hash: i64
+            ) -> Result<()> {
+                INSERT OR REPLACE INTO project_diff_viewed_files(workspace_id, path, content_hash)
+                VALUES (?, ?, ?)
+            }
+        }
+
+        query! {
+            pub async fn remove_viewed_file(
+                workspace_id: WorkspaceId,
+                path: String
+            ) -> Result<()> {
+                DELETE FROM project_diff_viewed_files
+                WHERE workspace_id = ? AND path = ?
+            }
+        }
+
+        query! {
+            pub fn get_viewed_files(workspace_id: WorkspaceId) -> Result<Vec<(String, i64)>> {
+                SELECT path, content_hash
+                FROM project_diff_viewed_files
+                WHERE workspace_id = ?
+            }
+        }
     }
 }
 
@@ -1434,6 +2057,9 @@ impl Render for ProjectDiffToolbar {
         let focus_handle = project_diff.focus_handle(cx);
         let button_states = project_diff.read(cx).button_states(cx);
         let review_count = project_diff.read(cx).total_review_comment_count();
+        let is_filter_shown = project_diff.read(cx).filter_bar.is_some();
+        let is_file_list_shown = project_diff.read(cx).show_file_list;
+        let viewed_progress = render_viewed_progress(project_diff.read(cx), cx);
 
         h_group_xl()
             .my_neg_1()
@@ -1496,6 +2122,33 @@ impl Render for ProjectDiffToolbar {
             // support "undo" for staging so we need a way to go back.
             .child(
                 h_group_sm()
+                    .children(viewed_progress)
+                    .child(
+                        IconButton::new("file-list", IconName::ListTree)
+                            .shape(ui::IconButtonShape::Square)
+                            .toggle_state(is_file_list_shown)
+                            .tooltip(Tooltip::for_action_title_in(
+                                "Toggle File List",
+                                &ToggleDiffFileList,
+                                &focus_handle,
+                            ))
+                            .on_click(cx.listener(|this, _, window, cx| {
+                                this.dispatch_action(&ToggleDiffFileList, window, cx)
+                            })),
+                    )
+                    .child(
+                        IconButton::new("filter", IconName::Filter)
+                            .shape(ui::IconButtonShape::Square)
+                            .toggle_state(is_filter_shown)
+                            .tooltip(Tooltip::for_action_title_in(
+                                "Filter Changes",
+                                &ToggleDiffFilter,
+                                &focus_handle,
+                            ))
+                            .on_click(cx.listener(|this, _, window, cx| {
+                                this.dispatch_action(&ToggleDiffFilter, window, cx)
+                            })),
+                    )
                     .child(
                         IconButton::new("up", IconName::ArrowUp)
                             .shape(ui::IconButtonShape::Square)
@@ -1588,6 +2241,15 @@ impl Render for ProjectDiffToolbar {
     }
 }
 
+fn render_viewed_progress(project_diff: &ProjectDiff, cx: &App) -> Option<Label> {
+    let (viewed_count, file_count) = project_diff.viewed_file_count(cx);
+    (viewed_count > 0).then(|| {
+        Label::new(format!("{viewed_count}/{file_count} files viewed"))
+            .size(LabelSize::Small)
+            .color(Color::Muted)
+    })
+}
+
 fn render_send_review_to_agent_button(review_count: usize, focus_handle: &FocusHandle) -> Button {
     Button::new(
         "send-review",
@@ -1666,6 +2328,7 @@ impl Render for BranchDiffToolbar {
         let focus_handle = project_diff.focus_handle(cx);
         let review_count = project_diff.read(cx).total_review_comment_count();
         let (additions, deletions) = project_diff.read(cx).calculate_changed_lines(cx);
+        let viewed_progress = render_viewed_progress(project_diff.read(cx), cx);
 
         let is_multibuffer_empty = project_diff.read(cx).multibuffer.read(cx).is_empty();
         let is_ai_enabled = AgentSettings::get_global(cx).enabled(cx);
@@ -1679,6 +2342,7 @@ impl Render for BranchDiffToolbar {
             .flex_wrap()
             .justify_end()
             .gap_2()
+            .children(viewed_progress)
             .when(!is_multibuffer_empty, |this| {
                 this.child(DiffStat::new(
                     "branch-diff-stat",
@@ -1742,13 +2406,74 @@ impl Addon for BranchDiffAddon {
     }
 }
 
+/// Renders the "Viewed" checkbox on the header of every file in a project diff. Editors render the
+/// header controls of a single addon, so this one also renders the Git panel's controls.
+struct ViewedFileAddon {
+    project_diff: WeakEntity<ProjectDiff>,
+    /// Renders the staging controls, for diffs against HEAD.
+    git_panel_addon: Option<GitPanelAddon>,
+}
+
+impl Addon for ViewedFileAddon {
+    fn to_any(&self) -> &dyn std::any::Any {
+        self
+    }
+
+    fn render_buffer_header_controls(
+        &self,
+        excerpt_info: &ExcerptInfo,
+        window: &Window,
+        cx: &App,
+    ) -> Option<AnyElement> {
+        let git_panel_controls = self
+            .git_panel_addon
+            .as_ref()
+            .and_then(|addon| addon.render_buffer_header_controls(excerpt_info, window, cx));
+        let project_diff = self.project_diff.upgrade()?;
+        let path_key = project_diff
+            .read(cx)
+            .multibuffer
+            .read(cx)
+            .path_for_excerpt(excerpt_info.id)?;
+        let is_viewed = project_diff.read(cx).is_file_viewed(&path_key.path);
+        Some(
+            h_flex()
+                .gap_2()
+                .child(
+                    h_flex()
+                        .id("viewed-file")
+                        .child(
+                            Checkbox::new("viewed-file", is_viewed.into())
+                                .label("Viewed")
+                                .label_size(LabelSize::Small)
+                                .on_click(move |_, _, cx| {
+                                    project_diff.update(cx, |project_diff, cx| {
+                                        project_diff.toggle_file_viewed(&path_key, cx)
+                                    });
+                                    cx.stop_propagation();
+                                }),
+                        )
+                        .on_mouse_down(MouseButton::Left, |_, _, cx| {
+                            // Keeps the click from also toggling the fold of the header.
+                            cx.stop_propagation();
+                        }),
+                )
+                .children(git_panel_controls)
+                .into_any_element(),
+        )
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use collections::HashMap;
     use db::indoc;
     use editor::test::editor_test_context::{EditorTestContext, assert_state_with_diff};
-    use git::status::{TrackedStatus, UnmergedStatus, UnmergedStatusCode};
-    use gpui::TestAppContext;
+    use git::{
+        repository::repo_path,
+        status::{StageStatus, TrackedStatus, UnmergedStatus, UnmergedStatusCode},
+    };
+    use gpui::{TestAppContext, VisualTestContext};
     use project::FakeFs;
     use serde_json::json;
     use settings::{DiffViewStyle, SettingsStore};
@@ -1762,6 +2487,7 @@ mod tests {
     use workspace::MultiWorkspace;
 
     use super::*;
+    use crate::diff_filter::{FILTER_DEBOUNCE, StatusFilter};
 
     #[ctor::ctor]
     fn init_logger() {
@@ -1843,7 +2569,7 @@ mod tests {
     }
 
     #[gpui::test]
-    async fn test_scroll_to_beginning_with_deletion(cx: &mut TestAppContext) {
+    async fn test_stage_and_unstage_single_hunk(cx: &mut TestAppContext) {
         init_test(cx);
 
         let fs = FakeFs::new(cx.executor());
@@ -1851,12 +2577,17 @@ mod tests {
             path!("/project"),
             json!({
                 ".git": {},
-                "bar": "BAR\n",
-                "foo": "FOO\n",
+                "foo.txt": "ONE\ntwo\nthree\nfour\nFIVE\n",
             }),
         )
         .await;
         let project = Project::test(fs.clone(), [path!("/project").as_ref()], cx).await;
+        let original_text = "one\ntwo\nthree\nfour\nfive\n";
+        fs.set_head_and_index_for_repo(
+            path!("/project/.git").as_ref(),
+            &[("foo.txt", original_text.into())],
+        );
+
         let (multi_workspace, cx) =
             cx.add_window_view(|window, cx| MultiWorkspace::test_new(project.clone(), window, cx));
         let workspace = multi_workspace.read_with(cx, |mw, _| mw.workspace().clone());
@@ -1865,14 +2596,68 @@ mod tests {
         });
         cx.run_until_parked();
 
-        fs.set_head_and_index_for_repo(
-            path!("/project/.git").as_ref(),
-            &[("bar", "bar\n".into()), ("foo", "foo\n".into())],
-        );
-        cx.run_until_parked();
-
-        let editor = cx.update_window_entity(&diff, |diff, window, cx| {
-            diff.move_to_path(
+        let editor = diff.read_with(cx, |diff, cx| diff.editor.read(cx).rhs_editor().clone());
+        let index_text = || {
+            let mut index_text = None;
+            fs.with_git_state(path!("/project/.git").as_ref(), false, |state| {
+                index_text = state
+                    .index_contents
+                    .get(&RepoPath::from_rel_path(rel_path("foo.txt")))
+                    .cloned();
+            })
+            .unwrap();
+            index_text
+        };
+
+        editor.update(cx, |editor, cx| {
+            let cursor = editor.selections.newest_anchor().head();
+            editor.stage_or_unstage_diff_hunks(true, vec![cursor..cursor], cx);
+        });
+        cx.run_until_parked();
+        assert_eq!(
+            index_text().as_deref(),
+            Some("ONE\ntwo\nthree\nfour\nfive\n")
+        );
+
+        editor.update(cx, |editor, cx| {
+            let cursor = editor.selections.newest_anchor().head();
+            editor.stage_or_unstage_diff_hunks(false, vec![cursor..cursor], cx);
+        });
+        cx.run_until_parked();
+        assert_eq!(index_text().as_deref(), Some(original_text));
+    }
+
+    #[gpui::test]
+    async fn test_scroll_to_beginning_with_deletion(cx: &mut TestAppContext) {
+        init_test(cx);
+
+        let fs = FakeFs::new(cx.executor());
+        fs.insert_tree(
+            path!("/project"),
+            json!({
+                ".git": {},
+                "bar": "BAR\n",
+                "foo": "FOO\n",
+            }),
+        )
+        .await;
+        let project = Project::test(fs.clone(), [path!("/project").as_ref()], cx).await;
+        let (multi_workspace, cx) =
+            cx.add_window_view(|window, cx| MultiWorkspace::test_new(project.clone(), window, cx));
+        let workspace = multi_workspace.read_with(cx, |mw, _| mw.workspace().clone());
+        let diff = cx.new_window_entity(|window, cx| {
+            ProjectDiff::new(project.clone(), workspace, window, cx)
+        });
+        cx.run_until_parked();
+
+        fs.set_head_and_index_for_repo(
+            path!("/project/.git").as_ref(),
+            &[("bar", "bar\n".into()), ("foo", "foo\n".into())],
+        );
+        cx.run_until_parked();
+
+        let editor = cx.update_window_entity(&diff, |diff, window, cx| {
+            diff.move_to_path(
                 PathKey::with_sort_prefix(TRACKED_SORT_PREFIX, rel_path("foo").into_arc()),
                 window,
                 cx,
@@ -2720,6 +3505,128 @@ mod tests {
         cx.assert_excerpts_with_selections("[EXCERPT]\nˇ# My cool project\nDetails to come.\n");
     }
 
+    #[gpui::test]
+    async fn test_move_to_collapsed_untracked_entry(cx: &mut TestAppContext) {
+        init_test(cx);
+        cx.update(|cx| {
+            cx.update_global::<SettingsStore, _>(|store, cx| {
+                store.update_user_settings(cx, |settings| {
+                    settings
+                        .git_panel
+                        .get_or_insert_default()
+                        .collapse_untracked_diff = Some(true);
+                });
+            });
+        });
+
+        let fs = FakeFs::new(cx.executor());
+        fs.insert_tree(
+            path!("/project"),
+            json!({
+                ".git": {},
+                "new.txt": "one\ntwo\n",
+            }),
+        )
+        .await;
+        let project = Project::test(fs.clone(), [path!("/project").as_ref()], cx).await;
+        let (multi_workspace, cx) =
+            cx.add_window_view(|window, cx| MultiWorkspace::test_new(project.clone(), window, cx));
+        let workspace = multi_workspace.read_with(cx, |mw, _| mw.workspace().clone());
+        let diff = cx.new_window_entity(|window, cx| {
+            ProjectDiff::new(project.clone(), workspace, window, cx)
+        });
+        cx.run_until_parked();
+
+        let buffer = project
+            .update(cx, |project, cx| {
+                project.open_local_buffer(path!("/project/new.txt"), cx)
+            })
+            .await
+            .unwrap();
+        let buffer_id = buffer.read_with(cx, |buffer, _| buffer.remote_id());
+        let editor = diff.read_with(cx, |diff, cx| diff.editor.read(cx).rhs_editor().clone());
+        assert!(editor.read_with(cx, |editor, cx| editor.is_buffer_folded(buffer_id, cx)));
+
+        diff.update_in(cx, |diff, window, cx| {
+            diff.move_to_entry(
+                GitStatusEntry {
+                    repo_path: repo_path("new.txt"),
+                    status: FileStatus::Untracked,
+                    staging: StageStatus::Unstaged,
+                    diff_stat: None,
+                },
+                window,
+                cx,
+            );
+        });
+        cx.run_until_parked();
+
+        assert!(!editor.read_with(cx, |editor, cx| editor.is_buffer_folded(buffer_id, cx)));
+        assert_state_with_diff(
+            &editor,
+            cx,
+            &"
+                + ˇone
+                + two
+            "
+            .unindent(),
+        );
+    }
+
+    #[gpui::test]
+    async fn test_renamed_file_diffs_against_old_path(cx: &mut TestAppContext) {
+        init_test(cx);
+
+        let fs = FakeFs::new(cx.executor());
+        fs.insert_tree(
+            path!("/project"),
+            json!({
+                ".git": {},
+                "new.txt": "one\nTWO\nthree\n",
+            }),
+        )
+        .await;
+        let dot_git = Path::new(path!("/project/.git"));
+        fs.set_head_for_repo(
+            dot_git,
+            &[("old.txt", "one\ntwo\nthree\n".into())],
+            "deadbeef",
+        );
+        fs.set_index_for_repo(dot_git, &[("new.txt", "one\nTWO\nthree\n".into())]);
+        fs.with_git_state(dot_git, true, |state| {
+            state.renames = vec![git::status::FileRename {
+                old_path: repo_path("old.txt"),
+                new_path: repo_path("new.txt"),
+                is_copy: false,
+            }];
+        })
+        .unwrap();
+        let project = Project::test(fs.clone(), [path!("/project").as_ref()], cx).await;
+        let (multi_workspace, cx) =
+            cx.add_window_view(|window, cx| MultiWorkspace::test_new(project.clone(), window, cx));
+        let workspace = multi_workspace.read_with(cx, |mw, _| mw.workspace().clone());
+        let diff = cx.new_window_entity(|window, cx| {
+            ProjectDiff::new(project.clone(), workspace, window, cx)
+        });
+        cx.run_until_parked();
+
+        let paths = diff.read_with(cx, |diff, cx| diff.excerpt_paths(cx));
+        assert_eq!(paths.len(), 1);
+        assert_eq!(*paths[0], *"new.txt");
+        let editor = diff.read_with(cx, |diff, cx| diff.editor.read(cx).rhs_editor().clone());
+        assert_state_with_diff(
+            &editor,
+            cx,
+            &"
+                  ˇone
+                - two
+                + TWO
+                  three
+            "
+            .unindent(),
+        );
+    }
+
     #[gpui::test]
     async fn test_deploy_at_respects_worktree_override(cx: &mut TestAppContext) {
         init_test(cx);
@@ -2808,4 +3715,286 @@ mod tests {
         assert_eq!(paths_b.len(), 1);
         assert_eq!(*paths_b[0], *"b.txt");
     }
+
+    #[gpui::test]
+    async fn test_filter_diff(cx: &mut TestAppContext) {
+        init_test(cx);
+
+        let fs = FakeFs::new(cx.executor());
+        fs.insert_tree(
+            path!("/project"),
+            json!({
+                ".git": {},
+                "src": {
+                    "blame.rs": "fn blame() {}\nfn render_blame() {}\n",
+                    "history.rs": "fn history() {}\nfn render_history() {}\n",
+                },
+                "notes.txt": "todo\n",
+            }),
+        )
+        .await;
+        fs.set_head_and_index_for_repo(
+            Path::new(path!("/project/.git")),
+            &[
+                ("src/blame.rs", "fn blame() {}\n".to_owned()),
+                ("src/history.rs", "fn history() {}\n".to_owned()),
+            ],
+        );
+        let project = Project::test(fs.clone(), [path!("/project").as_ref()], cx).await;
+        let (multi_workspace, cx) =
+            cx.add_window_view(|window, cx| MultiWorkspace::test_new(project.clone(), window, cx));
+        let workspace = multi_workspace.read_with(cx, |mw, _| mw.workspace().clone());
+        let diff = cx.new_window_entity(|window, cx| {
+            ProjectDiff::new(project.clone(), workspace, window, cx)
+        });
+        cx.run_until_parked();
+
+        let excerpt_paths = |cx: &mut VisualTestContext| {
+            diff.read_with(cx, |diff, cx| {
+                let mut paths = diff
+                    .excerpt_paths(cx)
+                    .into_iter()
+                    .map(|path| path.as_unix_str().to_string())
+                    .collect::<Vec<_>>();
+                paths.sort();
+                paths
+            })
+        };
+        assert_eq!(
+            excerpt_paths(cx),
+            ["notes.txt", "src/blame.rs", "src/history.rs"]
+        );
+
+        diff.update_in(cx, |diff, window, cx| {
+            diff.toggle_diff_filter(&ToggleDiffFilter, window, cx)
+        });
+        let filter_bar = diff.read_with(cx, |diff, _| diff.filter_bar.as_ref().unwrap().0.clone());
+        let (path_editor, query_editor) = filter_bar.read_with(cx, |filter_bar, _| {
+            (
+                filter_bar.path_editor.clone(),
+                filter_bar.query_editor.clone(),
+            )
+        });
+        path_editor.update_in(cx, |editor, window, cx| {
+            editor.set_text("src/**", window, cx)
+        });
+        cx.executor().advance_clock(FILTER_DEBOUNCE);
+        cx.run_until_parked();
+        assert_eq!(excerpt_paths(cx), ["src/blame.rs", "src/history.rs"]);
+
+        query_editor.update_in(cx, |editor, window, cx| {
+            editor.set_text("RENDER_BLAME", window, cx)
+        });
+        cx.executor().advance_clock(FILTER_DEBOUNCE);
+        cx.run_until_parked();
+        assert_eq!(excerpt_paths(cx), ["src/blame.rs"]);
+
+        path_editor.update_in(cx, |editor, window, cx| editor.set_text("", window, cx));
+        query_editor.update_in(cx, |editor, window, cx| editor.set_text("", window, cx));
+        filter_bar.update(cx, |filter_bar, cx| {
+            filter_bar.toggle_status(StatusFilter::Untracked, cx)
+        });
+        cx.run_until_parked();
+        assert_eq!(excerpt_paths(cx), ["notes.txt"]);
+
+        diff.update_in(cx, |diff, window, cx| {
+            diff.toggle_diff_filter(&ToggleDiffFilter, window, cx)
+        });
+        cx.run_until_parked();
+        assert!(diff.read_with(cx, |diff, _| diff.filter_bar.is_none()));
+        assert_eq!(
+            excerpt_paths(cx),
+            ["notes.txt", "src/blame.rs", "src/history.rs"]
+        );
+    }
+
+    #[gpui::test]
+    async fn test_file_list(cx: &mut TestAppContext) {
+        init_test(cx);
+
+        let fs = FakeFs::new(cx.executor());
+        fs.insert_tree(
+            path!("/project"),
+            json!({
+                ".git": {},
+                "src": {
+                    "lib.rs": "one\nTWO\nthree\nfour\n",
+                },
+                "README.md": "# Project\n",
+            }),
+        )
+        .await;
+        fs.set_head_and_index_for_repo(
+            Path::new(path!("/project/.git")),
+            &[
+                ("src/lib.rs", "one\ntwo\nthree\n".to_owned()),
+                ("README.md", "# Project\nDetails\n".to_owned()),
+            ],
+        );
+        let project = Project::test(fs.clone(), [path!("/project").as_ref()], cx).await;
+        let (multi_workspace, cx) =
+            cx.add_window_view(|window, cx| MultiWorkspace::test_new(project.clone(), window, cx));
+        let workspace = multi_workspace.read_with(cx, |mw, _| mw.workspace().clone());
+        let diff = cx.new_window_entity(|window, cx| {
+            ProjectDiff::new(project.clone(), workspace, window, cx)
+        });
+        cx.run_until_parked();
+
+        let entries = |cx: &mut VisualTestContext| {
+            diff.read_with(cx, |diff, cx| {
+                diff.file_list_entries(cx)
+                    .into_iter()
+                    .map(|entry| {
+                        (
+                            entry.path_key.path.as_unix_str().to_string(),
+                            entry.added,
+                            entry.removed,
+                            entry.is_folded,
+                        )
+                    })
+                    .collect::<Vec<_>>()
+            })
+        };
+        assert_eq!(
+            entries(cx),
+            [
+                ("README.md".to_string(), 0, 1, false),
+                ("src/lib.rs".to_string(), 2, 1, false),
+            ]
+        );
+
+        diff.update_in(cx, |diff, window, cx| {
+            diff.collapse_all_diff_files(&CollapseAllDiffFiles, window, cx)
+        });
+        assert!(entries(cx).iter().all(|(_, _, _, is_folded)| *is_folded));
+
+        diff.update_in(cx, |diff, window, cx| {
+            diff.file_list = diff.file_list_entries(cx);
+            diff.jump_to_file(1, window, cx);
+        });
+        assert_eq!(
+            entries(cx),
+            [
+                ("README.md".to_string(), 0, 1, true),
+                ("src/lib.rs".to_string(), 2, 1, false),
+            ]
+        );
+        assert_eq!(
+            diff.read_with(cx, |diff, cx| diff.active_path(cx))
+                .map(|project_path| project_path.path.as_unix_str().to_string()),
+            Some("src/lib.rs".to_string())
+        );
+
+        diff.update_in(cx, |diff, window, cx| {
+            diff.expand_all_diff_files(&ExpandAllDiffFiles, window, cx)
+        });
+        assert!(entries(cx).iter().all(|(_, _, _, is_folded)| !*is_folded));
+    }
+
+    #[gpui::test]
+    async fn test_viewed_files(cx: &mut TestAppContext) {
+        init_test(cx);
+
+        let fs = FakeFs::new(cx.executor());
+        fs.insert_tree(
+            path!("/project"),
+            json!({
+                ".git": {},
+                "src": {
+                    "lib.rs": "one\nTWO\nthree\n",
+                },
+                "README.md": "# Project\n",
+            }),
+        )
+        .await;
+        fs.set_head_and_index_for_repo(
+            Path::new(path!("/project/.git")),
+            &[
+                ("src/lib.rs", "one\ntwo\nthree\n".to_owned()),
+                ("README.md", "# Project\nDetails\n".to_owned()),
+            ],
+        );
+        let project = Project::test(fs.clone(), [path!("/project").as_ref()], cx).await;
+        let (multi_workspace, cx) =
+            cx.add_window_view(|window, cx| MultiWorkspace::test_new(project.clone(), window, cx));
+        let workspace = multi_workspace.read_with(cx, |mw, _| mw.workspace().clone());
+        let diff = cx.new_window_entity(|window, cx| {
+            ProjectDiff::new(project.clone(), workspace, window, cx)
+        });
+        cx.run_until_parked();
+
+        let entries = |cx: &mut VisualTestContext| {
+            diff.read_with(cx, |diff, cx| {
+                diff.file_list_entries(cx)
+                    .into_iter()
+                    .map(|entry| {
+                        (
+                            entry.path_key.path.as_unix_str().to_string(),
+                            entry.is_viewed,
+                            entry.is_folded,
+                        )
+                    })
+                    .collect::<Vec<_>>()
+            })
+        };
+        let path_key = diff.read_with(cx, |diff, cx| {
+            diff.file_list_entries(cx)
+                .into_iter()
+                .find(|entry| entry.path_key.path.as_ref() == rel_path("src/lib.rs"))
+                .map(|entry| entry.path_key)
+                .unwrap()
+        });
+        assert_eq!(
+            diff.read_with(cx, |diff, cx| diff.viewed_file_count(cx)),
+            (0, 2)
+        );
+
+        diff.update(cx, |diff, cx| diff.toggle_file_viewed(&path_key, cx));
+        cx.run_until_parked();
+        assert_eq!(
+            entries(cx),
+            [
+                ("README.md".to_string(), false, false),
+                ("src/lib.rs".to_string(), true, true),
+            ]
+        );
+        assert_eq!(
+            diff.read_with(cx, |diff, cx| diff.viewed_file_count(cx)),
+            (1, 2)
+        );
+
+        let buffer = diff.read_with(cx, |diff, cx| {
+            diff.multibuffer
+                .read(cx)
+                .buffer_for_path(&path_key, cx)
+                .unwrap()
+        });
+        buffer.update(cx, |buffer, cx| {
+            buffer.edit([(0..0, "zero\n")], None, cx);
+        });
+        cx.run_until_parked();
+        assert_eq!(
+            entries(cx),
+            [
+                ("README.md".to_string(), false, false),
+                ("src/lib.rs".to_string(), false, false),
+            ]
+        );
+        assert_eq!(
+            diff.read_with(cx, |diff, cx| diff.viewed_file_count(cx)),
+            (0, 2)
+        );
+
+        diff.update(cx, |diff, cx| diff.toggle_file_viewed(&path_key, cx));
+        cx.run_until_parked();
+        diff.update(cx, |diff, cx| diff.toggle_file_viewed(&path_key, cx));
+        cx.run_until_parked();
+        assert_eq!(
+            entries(cx),
+            [
+                ("README.md".to_string(), false, false),
+                ("src/lib.rs".to_string(), false, false),
+            ]
+        );
+    }
 }
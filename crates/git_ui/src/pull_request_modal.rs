@@ -0,0 +1,317 @@
+use std::sync::Arc;
+
+use editor::Editor;
+use git::{CreatePullRequestParams, GitHostingProvider, ParsedGitRemote};
+use gpui::{
+    App, Context, DismissEvent, Entity, EventEmitter, FocusHandle, Focusable, InteractiveElement,
+    IntoElement, ParentElement, Render, Styled, Task, WeakEntity, Window, rems,
+};
+use menu::{Cancel, Confirm};
+use notifications::status_toast::{StatusToast, ToastIcon};
+use project::git_store::Repository;
+use ui::prelude::*;
+use util::ResultExt as _;
+use workspace::{ModalView, Workspace};
+
+/// A branch on a remote that a pull request can be opened for.
+#[derive(Clone)]
+pub(crate) struct PullRequestTarget {
+    pub provider: Arc<dyn GitHostingProvider + Send + Sync + 'static>,
+    pub remote: ParsedGitRemote,
+    pub source_branch: String,
+}
+
+impl PullRequestTarget {
+    /// The page of the hosting provider where a pull request for the branch is filled in.
+    pub fn compare_url(&self) -> Option<String> {
+        self.provider
+            .build_create_pull_request_url(&self.remote, &self.source_branch)
+            .map(|url| url.to_string())
+    }
+}
+
+/// Splits a commit message into the title and the body of a pull request.
+fn split_commit_message(message: &str) -> (&str, &str) {
+    let message = message.trim();
+    match message.split_once('\n') {
+        Some((title, body)) => (title.trim(), body.trim()),
+        None => (message, ""),
+    }
+}
+
+/// Creates a pull request for a branch through the API of its hosting provider, with a title
+/// and body taken from a commit message.
+pub struct PullRequestModal {
+    target: PullRequestTarget,
+    workspace: WeakEntity<Workspace>,
+    title_editor: Entity<Editor>,
+    body_editor: Entity<Editor>,
+    target_branch_editor: Entity<Editor>,
+    error: Option<SharedString>,
+    pending_creation: Option<Task<()>>,
+    _load_default_branch: Task<()>,
+}
+
+impl PullRequestModal {
+    pub(crate) fn new(
+        target: PullRequestTarget,
+        commit_message: &str,
+        repository: Entity<Repository>,
+        workspace: WeakEntity<Workspace>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Self {
+        let (title, body) = split_commit_message(commit_message);
+        let title_editor = cx.new(|cx| {
+            let mut editor = Editor::single_line(window, cx);
+            editor.set_placeholder_text("Title", window, cx);
+            editor.set_text(title, window, cx);
+            editor
+        });
+        let body_editor = cx.new(|cx| {
+            let mut editor = Editor::auto_height(4, 12, window, cx);
+            editor.set_placeholder_text("Description", window, cx);
+            editor.set_text(body, window, cx);
+            editor
+        });
+        let target_branch_editor = cx.new(|cx| {
+            let mut editor = Editor::single_line(window, cx);
+            editor.set_placeholder_text("Branch to merge into", window, cx);
+            editor
+        });
+
+        let default_branch =
+            repository.update(cx, |repository, _| repository.default_branch(false));
+        let load_default_branch = cx.spawn_in(window, async move |this, cx| {
+            let Some(Some(default_branch)) = default_branch
+                .await
+                .ok()
+                .and_then(|result| result.log_err())
+            else {
+                return;
+            };
+            this.update_in(cx, |this, window, cx| {
+                this.target_branch_editor.update(cx, |editor, cx| {
+                    if editor.text(cx).is_empty() {
+                        editor.set_text(default_branch.as_ref(), window, cx);
+                    }
+                });
+            })
+            .log_err();
+        });
+
+        Self {
+            target,
+            workspace,
+            title_editor,
+            body_editor,
+            target_branch_editor,
+            error: None,
+            pending_creation: None,
+            _load_default_branch: load_default_branch,
+        }
+    }
+
+    fn cancel(&mut self, _: &Cancel, _window: &mut Window, cx: &mut Context<Self>) {
+        cx.emit(DismissEvent);
+    }
+
+    fn confirm(&mut self, _: &Confirm, window: &mut Window, cx: &mut Context<Self>) {
+        self.create(window, cx);
+    }
+
+    fn create(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        if self.pending_creation.is_some() {
+            return;
+        }
+        let title = self.title_editor.read(cx).text(cx).trim().to_string();
+        let target_branch = self
+            .target_branch_editor
+            .read(cx)
+            .text(cx)
+            .trim()
+            .to_string();
+        if title.is_empty() {
+            self.error = Some("The pull request needs a title".into());
+            cx.notify();
+            return;
+        }
+        if target_branch.is_empty() {
+            self.error = Some("Choose a branch to merge into".into());
+            cx.notify();
+            return;
+        }
+
+        let params = CreatePullRequestParams {
+            source_branch: self.target.source_branch.clone(),
+            target_branch,
+            title,
+            body: self.body_editor.read(cx).text(cx).trim().to_string(),
+        };
+        let provider = self.target.provider.clone();
+        let remote = self.target.remote.clone();
+        let http_client = cx.http_client();
+        self.error = None;
+        self.pending_creation = Some(cx.spawn_in(window, async move |this, cx| {
+            let result = provider
+                .create_pull_request(&remote, params, http_client)
+                .await;
+            this.update(cx, |this, cx| {
+                this.pending_creation = None;
+                match result {
+                    Ok(pull_request) => {
+                        if let Some(workspace) = this.workspace.upgrade() {
+                            workspace.update(cx, |workspace, cx| {
+                                let url = pull_request.url.clone();
+                                let toast = StatusToast::new(
+                                    format!("Created pull request #{}", pull_request.number),
+                                    cx,
+                                    move |this, _cx| {
+                                        this.icon(
+                                            ToastIcon::new(IconName::GitBranchAlt)
+                                                .color(Color::Muted),
+                                        )
+                                        .action("View Pull Request", move |_, cx| {
+                                            cx.open_url(url.as_str())
+                                        })
+                                        .dismiss_button(true)
+                                    },
+                                );
+                                workspace.toggle_status_toast(toast, cx);
+                            });
+                        }
+                        cx.emit(DismissEvent);
+                    }
+                    Err(error) => {
+                        log::error!("Error while creating pull request {error:?}");
+                        this.error = Some(error.to_string().into());
+                        cx.notify();
+                    }
+                }
+            })
+            .log_err();
+        }));
+        cx.notify();
+    }
+
+    fn open_in_browser(&mut self, cx: &mut Context<Self>) {
+        if let Some(url) = self.target.compare_url() {
+            cx.open_url(&url);
+        }
+        cx.emit(DismissEvent);
+    }
+
+    fn render_field(&self, label: &'static str, editor: &Entity<Editor>, cx: &App) -> Div {
+        v_flex()
+            .gap_1()
+            .child(Label::new(label).size(LabelSize::Small).color(Color::Muted))
+            .child(
+                div()
+                    .px_2()
+                    .py_1()
+                    .border_1()
+                    .border_color(cx.theme().colors().border)
+                    .rounded_md()
+                    .child(editor.clone()),
+            )
+    }
+}
+
+impl EventEmitter<DismissEvent> for PullRequestModal {}
+impl ModalView for PullRequestModal {}
+impl Focusable for PullRequestModal {
+    fn focus_handle(&self, cx: &App) -> FocusHandle {
+        self.title_editor.focus_handle(cx)
+    }
+}
+
+impl Render for PullRequestModal {
+    fn render(&mut self, _: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let is_creating = self.pending_creation.is_some();
+        v_flex()
+            .key_context("PullRequestModal")
+            .on_action(cx.listener(Self::cancel))
+            .on_action(cx.listener(Self::confirm))
+            .elevation_2(cx)
+            .w(rems(34.))
+            .child(
+                h_flex()
+                    .px_3()
+                    .pt_2()
+                    .pb_1()
+                    .w_full()
+                    .gap_1p5()
+                    .child(Icon::new(IconName::GitBranchAlt).size(IconSize::XSmall))
+                    .child(
+                        Headline::new(format!(
+                            "Create Pull Request for {}",
+                            self.target.source_branch
+                        ))
+                        .size(HeadlineSize::XSmall),
+                    ),
+            )
+            .child(
+                v_flex()
+                    .px_3()
+                    .pb_3()
+                    .w_full()
+                    .gap_2()
+                    .child(self.render_field("Title", &self.title_editor, cx))
+                    .child(self.render_field("Description", &self.body_editor, cx))
+                    .child(self.render_field("Merge Into", &self.target_branch_editor, cx))
+                    .when_some(self.error.clone(), |this, error| {
+                        this.child(Label::new(error).size(LabelSize::Small).color(Color::Error))
+                    })
+                    .child(
+                        h_flex()
+                            .gap_1()
+                            .justify_end()
+                            .when(self.target.compare_url().is_some(), |this| {
+                                this.child(
+                                    Button::new(
+                                        "open-pull-request-in-browser",
+                                        format!("Open on {}", self.target.provider.name()),
+                                    )
+                                    .on_click(
+                                        cx.listener(|this, _, _, cx| this.open_in_browser(cx)),
+                                    ),
+                                )
+                            })
+                            .child(
+                                Button::new(
+                                    "create-pull-request",
+                                    if is_creating { "Creating…" } else { "Create" },
+                                )
+                                .style(ButtonStyle::Filled)
+                                .disabled(is_creating)
+                                .on_click(
+                                    cx.listener(|this, _, window, cx| this.create(window, cx)),
+                                ),
+                            ),
+                    ),
+            )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_split_commit_message() {
+        assert_eq!(split_commit_message(""), ("", ""));
+        assert_eq!(
+            split_commit_message("Fix blame for renamed files\n"),
+            ("Fix blame for renamed files", "")
+        );
+        assert_eq!(
+            split_commit_message(
+                "Fix blame for renamed files\n\nBlame now follows renames.\n\nCloses #123\n"
+            ),
+            (
+                "Fix blame for renamed files",
+                "Blame now follows renames.\n\nCloses #123"
+            )
+        );
+    }
+}
@@ -0,0 +1,311 @@
+use std::sync::Arc;
+
+use fuzzy::StringMatchCandidate;
+use git::{GitHostingProvider, ParsedGitRemote, PullRequestSummary};
+use gpui::{App, DismissEvent, Entity, EventEmitter, FocusHandle, Focusable, Task, WeakEntity};
+use picker::{Picker, PickerDelegate};
+use ui::{HighlightedLabel, ListItem, ListItemSpacing, prelude::*};
+use util::ResultExt;
+use workspace::ModalView;
+
+use crate::git_panel::GitPanel;
+
+/// A remote whose hosting provider lists the pull requests opened against it.
+pub(crate) struct PullRequestRemote {
+    /// The name of the remote in the repository, which pull requests are fetched from.
+    pub name: String,
+    pub provider: Arc<dyn GitHostingProvider + Send + Sync + 'static>,
+    pub remote: ParsedGitRemote,
+}
+
+/// Picks one of the open pull requests of a remote to check out into a local branch.
+pub struct PullRequestPicker {
+    picker: Entity<Picker<PullRequestPickerDelegate>>,
+    _load_pull_requests: Task<()>,
+}
+
+impl PullRequestPicker {
+    pub(crate) fn new(
+        remote: PullRequestRemote,
+        git_panel: WeakEntity<GitPanel>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Self {
+        let pull_requests_request = {
+            let provider = remote.provider.clone();
+            let parsed_remote = remote.remote.clone();
+            let http_client = cx.http_client();
+            cx.background_spawn(async move {
+                provider
+                    .list_pull_requests(&parsed_remote, http_client)
+                    .await
+            })
+        };
+        let delegate = PullRequestPickerDelegate {
+            picker: cx.entity().downgrade(),
+            git_panel,
+            remote,
+            all_pull_requests: Vec::new(),
+            matches: Vec::new(),
+            selected_index: 0,
+            load_error: None,
+            loading: true,
+        };
+        let picker = cx.new(|cx| {
+            Picker::uniform_list(delegate, window, cx)
+                .max_height(Some(rems(20.).into()))
+                .show_scrollbar(true)
+        });
+
+        let load_pull_requests = cx.spawn_in(window, async move |this, cx| {
+            let result = pull_requests_request.await;
+            this.update_in(cx, |this, window, cx| {
+                this.picker.update(cx, |picker, cx| {
+                    let delegate = &mut picker.delegate;
+                    delegate.loading = false;
+                    match result {
+                        Ok(pull_requests) => delegate.all_pull_requests = pull_requests,
+                        Err(error) => {
+                            log::error!("Error while listing pull requests {error:?}");
+                            delegate.load_error = Some(error.to_string().into());
+                        }
+                    }
+                    picker.refresh(window, cx);
+                });
+            })
+            .log_err();
+        });
+
+        Self {
+            picker,
+            _load_pull_requests: load_pull_requests,
+        }
+    }
+}
+
+impl EventEmitter<DismissEvent> for PullRequestPicker {}
+
+impl Focusable for PullRequestPicker {
+    fn focus_handle(&self, cx: &App) -> FocusHandle {
+        self.picker.focus_handle(cx)
+    }
+}
+
+impl ModalView for PullRequestPicker {}
+
+impl Render for PullRequestPicker {
+    fn render(&mut self, _window: &mut Window, _cx: &mut Context<Self>) -> impl IntoElement {
+        v_flex()
+            .key_context("PullRequestPicker")
+            .w(rems(34.))
+            .child(self.picker.clone())
+    }
+}
+
+struct PullRequestMatch {
+    pull_request: PullRequestSummary,
+    /// Positions of the matched characters in the title.
+    positions: Vec<usize>,
+}
+
+/// The digits of a query for a pull request number, as in `#12` or `12`.
+fn number_query(query: &str) -> Option<&str> {
+    let number = query.trim().trim_start_matches('#');
+    (!number.is_empty() && number.chars().all(|character| character.is_ascii_digit()))
+        .then_some(number)
+}
+
+pub struct PullRequestPickerDelegate {
+    picker: WeakEntity<PullRequestPicker>,
+    git_panel: WeakEntity<GitPanel>,
+    remote: PullRequestRemote,
+    all_pull_requests: Vec<PullRequestSummary>,
+    matches: Vec<PullRequestMatch>,
+    selected_index: usize,
+    load_error: Option<SharedString>,
+    loading: bool,
+}
+
+impl PickerDelegate for PullRequestPickerDelegate {
+    type ListItem = ListItem;
+
+    fn match_count(&self) -> usize {
+        self.matches.len()
+    }
+
+    fn selected_index(&self) -> usize {
+        self.selected_index
+    }
+
+    fn set_selected_index(
+        &mut self,
+        ix: usize,
+        _window: &mut Window,
+        cx: &mut Context<Picker<Self>>,
+    ) {
+        self.selected_index = ix;
+        cx.notify();
+    }
+
+    fn placeholder_text(&self, _window: &mut Window, _cx: &mut App) -> Arc<str> {
+        format!(
+            "Check out a pull request from {}…",
+            self.remote.provider.name()
+        )
+        .into()
+    }
+
+    fn no_matches_text(&self, _window: &mut Window, _cx: &mut App) -> Option<SharedString> {
+        if self.loading {
+            Some("Loading pull requests…".into())
+        } else if let Some(error) = &self.load_error {
+            Some(format!("Failed to list pull requests: {error}").into())
+        } else {
+            Some("No open pull requests".into())
+        }
+    }
+
+    fn update_matches(
+        &mut self,
+        query: String,
+        window: &mut Window,
+        cx: &mut Context<Picker<Self>>,
+    ) -> Task<()> {
+        let all_pull_requests = self.all_pull_requests.clone();
+        cx.spawn_in(window, async move |picker, cx| {
+            let matches: Vec<PullRequestMatch> = if query.is_empty() {
+                all_pull_requests
+                    .into_iter()
+                    .map(|pull_request| PullRequestMatch {
+                        pull_request,
+                        positions: Vec::new(),
+                    })
+                    .collect()
+            } else if let Some(number) = number_query(&query) {
+                all_pull_requests
+                    .into_iter()
+                    .filter(|pull_request| pull_request.number.to_string().starts_with(number))
+                    .map(|pull_request| PullRequestMatch {
+                        pull_request,
+                        positions: Vec::new(),
+                    })
+                    .collect()
+            } else {
+                let candidates = all_pull_requests
+                    .iter()
+                    .enumerate()
+                    .map(|(ix, pull_request)| StringMatchCandidate::new(ix, &pull_request.title))
+                    .collect::<Vec<_>>();
+                fuzzy::match_strings(
+                    &candidates,
+                    &query,
+                    true,
+                    true,
+                    10000,
+                    &Default::default(),
+                    cx.background_executor().clone(),
+                )
+                .await
+                .into_iter()
+                .filter_map(|string_match| {
+                    Some(PullRequestMatch {
+                        pull_request: all_pull_requests.get(string_match.candidate_id)?.clone(),
+                        positions: string_match.positions,
+                    })
+                })
+                .collect()
+            };
+
+            picker
+                .update(cx, |picker, cx| {
+                    let delegate = &mut picker.delegate;
+                    delegate.matches = matches;
+                    delegate.selected_index = delegate
+                        .selected_index
+                        .min(delegate.matches.len().saturating_sub(1));
+                    cx.notify();
+                })
+                .log_err();
+        })
+    }
+
+    fn confirm(&mut self, _secondary: bool, window: &mut Window, cx: &mut Context<Picker<Self>>) {
+        let Some(selected) = self.matches.get(self.selected_index) else {
+            return;
+        };
+        let number = selected.pull_request.number;
+        let Some(head_ref) = self.remote.provider.pull_request_head_ref(number) else {
+            return;
+        };
+        let remote_name = self.remote.name.clone();
+        self.git_panel
+            .update(cx, |git_panel, cx| {
+                git_panel.checkout_pull_request(remote_name, head_ref, number, window, cx)
+            })
+            .log_err();
+        self.dismissed(window, cx);
+    }
+
+    fn dismissed(&mut self, _window: &mut Window, cx: &mut Context<Picker<Self>>) {
+        self.picker
+            .update(cx, |_, cx| cx.emit(DismissEvent))
+            .log_err();
+    }
+
+    fn render_match(
+        &self,
+        ix: usize,
+        selected: bool,
+        _window: &mut Window,
+        _cx: &mut Context<Picker<Self>>,
+    ) -> Option<Self::ListItem> {
+        let pull_request_match = self.matches.get(ix)?;
+        let pull_request = &pull_request_match.pull_request;
+        let detail = if pull_request.author.is_empty() {
+            pull_request.source_branch.to_string()
+        } else {
+            format!("{} · {}", pull_request.author, pull_request.source_branch)
+        };
+        Some(
+            ListItem::new(ix)
+                .inset(true)
+                .spacing(ListItemSpacing::Sparse)
+                .toggle_state(selected)
+                .child(
+                    v_flex()
+                        .child(
+                            h_flex()
+                                .gap_2()
+                                .child(
+                                    Label::new(format!("#{}", pull_request.number))
+                                        .color(Color::Muted),
+                                )
+                                .child(HighlightedLabel::new(
+                                    pull_request.title.clone(),
+                                    pull_request_match.positions.clone(),
+                                )),
+                        )
+                        .child(
+                            Label::new(detail)
+                                .size(LabelSize::Small)
+                                .color(Color::Muted)
+                                .truncate(),
+                        ),
+                ),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_number_query() {
+        assert_eq!(number_query("#12"), Some("12"));
+        assert_eq!(number_query(" 42 "), Some("42"));
+        assert_eq!(number_query("fix"), None);
+        assert_eq!(number_query("12 fix"), None);
+        assert_eq!(number_query("#"), None);
+    }
+}
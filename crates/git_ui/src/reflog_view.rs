@@ -0,0 +1,626 @@
+//! ReflogView lists the positions HEAD or a branch was at before, so that the repository can be
+//! restored to one of them, such as after a rebase went wrong.
+
+use anyhow::Result;
+use editor::Editor;
+use futures::channel::oneshot;
+use git::repository::{ReflogEntry, ResetMode};
+use gpui::{
+    AnyElement, App, Context, DismissEvent, Entity, EventEmitter, FocusHandle, Focusable,
+    IntoElement, PromptLevel, Render, SharedString, Task, WeakEntity, Window,
+};
+use menu::{Cancel, Confirm};
+use project::git_store::Repository;
+use time::OffsetDateTime;
+use ui::{ContextMenu, PopoverMenu, Tooltip, prelude::*};
+use workspace::{Item, ModalView, Workspace, item::ItemEvent};
+
+use crate::{commit_view::CommitView, resolve_active_repository};
+
+/// Entries listed for a reference; older ones are rarely useful for recovering work.
+const REFLOG_LIMIT: usize = 200;
+
+pub fn register(workspace: &mut Workspace) {
+    workspace.register_action(|workspace, _: &git::OpenReflog, window, cx| {
+        let Some(repository) = resolve_active_repository(workspace, cx) else {
+            return;
+        };
+        ReflogView::open(repository, workspace, window, cx);
+    });
+}
+
+pub struct ReflogView {
+    repository: WeakEntity<Repository>,
+    workspace: WeakEntity<Workspace>,
+    /// `HEAD` or the name of the branch whose reflog is listed.
+    reference: SharedString,
+    branches: Vec<SharedString>,
+    entries: Vec<ReflogEntry>,
+    loading: bool,
+    /// Whether a checkout, reset or branch creation is running.
+    restoring: bool,
+    error: Option<SharedString>,
+    focus_handle: FocusHandle,
+    _task: Option<Task<()>>,
+}
+
+impl ReflogView {
+    fn open(
+        repository: Entity<Repository>,
+        workspace: &mut Workspace,
+        window: &mut Window,
+        cx: &mut Context<Workspace>,
+    ) {
+        let existing = workspace
+            .active_pane()
+            .read(cx)
+            .items()
+            .find_map(|item| item.downcast::<ReflogView>())
+            .filter(|view| view.read(cx).repository == repository.downgrade());
+        if let Some(view) = existing {
+            view.update(cx, |view, cx| view.load(cx));
+            workspace.activate_item(&view, true, true, window, cx);
+            return;
+        }
+
+        let workspace_handle = workspace.weak_handle();
+        let view = cx.new(|cx| ReflogView::new(repository, workspace_handle, cx));
+        workspace.active_pane().update(cx, |pane, cx| {
+            pane.add_item(Box::new(view), true, true, None, window, cx);
+        });
+    }
+
+    fn new(
+        repository: Entity<Repository>,
+        workspace: WeakEntity<Workspace>,
+        cx: &mut Context<Self>,
+    ) -> Self {
+        let mut this = Self {
+            repository: repository.downgrade(),
+            workspace,
+            reference: "HEAD".into(),
+            branches: Vec::new(),
+            entries: Vec::new(),
+            loading: false,
+            restoring: false,
+            error: None,
+            focus_handle: cx.focus_handle(),
+            _task: None,
+        };
+        this.load(cx);
+        this
+    }
+
+    fn set_reference(&mut self, reference: SharedString, cx: &mut Context<Self>) {
+        self.reference = reference;
+        self.load(cx);
+    }
+
+    fn load(&mut self, cx: &mut Context<Self>) {
+        let Some(repository) = self.repository.upgrade() else {
+            return;
+        };
+        let reference = self.reference.to_string();
+        let (reflog, branches) = repository.update(cx, |repository, _| {
+            (
+                repository.reflog(reference, REFLOG_LIMIT),
+                repository.branches(),
+            )
+        });
+        self.loading = true;
+        self._task = Some(cx.spawn(async move |this, cx| {
+            let reflog = reflog.await.map_err(anyhow::Error::from).and_then(|r| r);
+            let branches = branches.await.map_err(anyhow::Error::from).and_then(|r| r);
+            this.update(cx, |this, cx| {
+                this.loading = false;
+                match reflog {
+                    Ok(entries) => {
+                        this.entries = entries;
+                        this.error = None;
+                    }
+                    Err(error) => this.error = Some(error.to_string().into()),
+                }
+                if let Ok(branches) = branches {
+                    this.branches = branches
+                        .iter()
+                        .filter(|branch| !branch.is_remote())
+                        .map(|branch| SharedString::from(branch.name().to_string()))
+                        .collect();
+                }
+                cx.notify();
+            })
+            .ok();
+        }));
+        cx.notify();
+    }
+
+    fn check_out(&mut self, index: usize, cx: &mut Context<Self>) {
+        let (Some(entry), Some(repository)) = (self.entries.get(index), self.repository.upgrade())
+        else {
+            return;
+        };
+        let sha = entry.sha.to_string();
+        let receiver = repository.update(cx, |repository, _| repository.checkout_revision(sha));
+        self.restore(receiver, cx);
+    }
+
+    fn reset_to(&mut self, index: usize, window: &mut Window, cx: &mut Context<Self>) {
+        let Some(entry) = self.entries.get(index) else {
+            return;
+        };
+        let sha = entry.sha.to_string();
+        let confirmation = window.prompt(
+            PromptLevel::Warning,
+            &format!("Reset the current branch to {}?", entry.selector),
+            Some("Uncommitted changes will be lost."),
+            &["Reset", "Cancel"],
+            cx,
+        );
+        cx.spawn(async move |this, cx| {
+            if confirmation.await != Ok(0) {
+                return;
+            }
+            this.update(cx, |this, cx| {
+                let Some(repository) = this.repository.upgrade() else {
+                    return;
+                };
+                let receiver = repository.update(cx, |repository, cx| {
+                    repository.reset(sha, ResetMode::Hard, cx)
+                });
+                this.restore(receiver, cx);
+            })
+            .ok();
+        })
+        .detach();
+    }
+
+    fn create_branch_at(&mut self, index: usize, window: &mut Window, cx: &mut Context<Self>) {
+        let (Some(entry), Some(repository)) = (self.entries.get(index), self.repository.upgrade())
+        else {
+            return;
+        };
+        let sha = entry.sha.clone();
+        let view = cx.weak_entity();
+        self.workspace
+            .update(cx, |workspace, cx| {
+                workspace.toggle_modal(window, cx, |window, cx| {
+                    BranchAtEntryModal::new(repository, view, sha, window, cx)
+                });
+            })
+            .ok();
+    }
+
+    /// Waits for a command that moved HEAD, then lists the reflog again with the new entry.
+    fn restore(&mut self, receiver: oneshot::Receiver<Result<()>>, cx: &mut Context<Self>) {
+        self.restoring = true;
+        self.error = None;
+        self._task = Some(cx.spawn(async move |this, cx| {
+            let result = receiver.await.map_err(anyhow::Error::from).and_then(|r| r);
+            this.update(cx, |this, cx| {
+                this.restoring = false;
+                match result {
+                    Ok(()) => this.load(cx),
+                    Err(error) => this.error = Some(error.to_string().into()),
+                }
+                cx.notify();
+            })
+            .ok();
+        }));
+        cx.notify();
+    }
+
+    fn open_commit(&self, index: usize, window: &mut Window, cx: &mut App) {
+        if let Some(entry) = self.entries.get(index) {
+            CommitView::open(
+                entry.sha.to_string(),
+                self.repository.clone(),
+                self.workspace.clone(),
+                None,
+                None,
+                window,
+                cx,
+            );
+        }
+    }
+
+    fn render_reference_menu(&self, cx: &Context<Self>) -> PopoverMenu<ContextMenu> {
+        let view = cx.weak_entity();
+        let references = std::iter::once(SharedString::from("HEAD"))
+            .chain(self.branches.iter().cloned())
+            .collect::<Vec<_>>();
+        PopoverMenu::new("reflog-reference")
+            .trigger(
+                Button::new("reflog-reference-trigger", self.reference.clone())
+                    .label_size(LabelSize::Small)
+                    .end_icon(Icon::new(IconName::ChevronDown).size(IconSize::XSmall)),
+            )
+            .menu(move |window, cx| {
+                let view = view.clone();
+                let references = references.clone();
+                Some(ContextMenu::build(window, cx, move |mut menu, _, _| {
+                    for reference in references {
+                        let view = view.clone();
+                        menu = menu.entry(reference.clone(), None, move |_, cx| {
+                            view.update(cx, |view, cx| view.set_reference(reference.clone(), cx))
+                                .ok();
+                        });
+                    }
+                    menu
+                }))
+            })
+    }
+
+    fn render_entry(&self, index: usize, entry: &ReflogEntry, cx: &Context<Self>) -> AnyElement {
+        let short_sha: String = entry.sha.chars().take(git::SHORT_SHA_LENGTH).collect();
+        let time = OffsetDateTime::from_unix_timestamp(entry.timestamp)
+            .unwrap_or(OffsetDateTime::UNIX_EPOCH);
+        let relative_time = time_format::format_localized_timestamp(
+            time,
+            OffsetDateTime::now_utc(),
+            time::UtcOffset::current_local_offset().unwrap_or(time::UtcOffset::UTC),
+            time_format::TimestampFormat::Relative,
+        );
+
+        h_flex()
+            .id(("reflog-entry", index))
+            .px_2()
+            .py_1()
+            .gap_2()
+            .border_b_1()
+            .border_color(cx.theme().colors().border_variant)
+            .hover(|style| style.bg(cx.theme().colors().ghost_element_hover))
+            .cursor_pointer()
+            .on_click(cx.listener(move |this, _, window, cx| this.open_commit(index, window, cx)))
+            .child(
+                Label::new(entry.selector.clone())
+                    .size(LabelSize::Small)
+                    .color(Color::Muted)
+                    .buffer_font(cx),
+            )
+            .child(
+                Label::new(short_sha)
+                    .size(LabelSize::Small)
+                    .color(Color::Muted)
+                    .buffer_font(cx),
+            )
+            .child(
+                div().flex_1().min_w_0().child(
+                    Label::new(entry.message.clone())
+                        .size(LabelSize::Small)
+                        .single_line()
+                        .truncate(),
+                ),
+            )
+            .child(
+                Label::new(relative_time)
+                    .size(LabelSize::Small)
+                    .color(Color::Muted),
+            )
+            .child(
+                h_flex()
+                    .gap_0p5()
+                    .child(
+                        IconButton::new(("reflog-check-out", index), IconName::Check)
+                            .icon_size(IconSize::Small)
+                            .disabled(self.restoring)
+                            .tooltip(Tooltip::text("Check Out"))
+                            .on_click(cx.listener(move |this, _, _, cx| {
+                                cx.stop_propagation();
+                                this.check_out(index, cx);
+                            })),
+                    )
+                    .child(
+                        IconButton::new(("reflog-create-branch", index), IconName::GitBranchPlus)
+                            .icon_size(IconSize::Small)
+                            .disabled(self.restoring)
+                            .tooltip(Tooltip::text("Create Branch Here…"))
+                            .on_click(cx.listener(move |this, _, window, cx| {
+                                cx.stop_propagation();
+                                this.create_branch_at(index, window, cx);
+                            })),
+                    )
+                    .child(
+                        IconButton::new(("reflog-reset", index), IconName::Undo)
+                            .icon_size(IconSize::Small)
+                            .disabled(self.restoring)
+                            .tooltip(Tooltip::text("Reset Current Branch Here"))
+                            .on_click(cx.listener(move |this, _, window, cx| {
+                                cx.stop_propagation();
+                                this.reset_to(index, window, cx);
+                            })),
+                    ),
+            )
+            .into_any_element()
+    }
+}
+
+impl EventEmitter<ItemEvent> for ReflogView {}
+
+impl Focusable for ReflogView {
+    fn focus_handle(&self, _cx: &App) -> FocusHandle {
+        self.focus_handle.clone()
+    }
+}
+
+impl Render for ReflogView {
+    fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let entries = self
+            .entries
+            .iter()
+            .enumerate()
+            .map(|(index, entry)| self.render_entry(index, entry, cx))
+            .collect::<Vec<_>>();
+
+        v_flex()
+            .key_context("ReflogView")
+            .track_focus(&self.focus_handle)
+            .size_full()
+            .bg(cx.theme().colors().editor_background)
+            .child(
+                h_flex()
+                    .h(rems_from_px(41.))
+                    .px_2()
+                    .gap_2()
+                    .justify_between()
+                    .border_b_1()
+                    .border_color(cx.theme().colors().border_variant)
+                    .child(
+                        h_flex()
+                            .gap_1()
+                            .child(Label::new("Reflog of").color(Color::Muted))
+                            .child(self.render_reference_menu(cx)),
+                    )
+                    .child(
+                        IconButton::new("reload-reflog", IconName::ArrowCircle)
+                            .icon_size(IconSize::Small)
+                            .disabled(self.loading)
+                            .tooltip(Tooltip::text("Reload"))
+                            .on_click(cx.listener(|this, _, _, cx| this.load(cx))),
+                    ),
+            )
+            .children(self.error.clone().map(|error| {
+                h_flex().px_2().py_1().child(
+                    Label::new(error)
+                        .size(LabelSize::Small)
+                        .color(Color::Error)
+                        .single_line()
+                        .truncate(),
+                )
+            }))
+            .child(
+                v_flex()
+                    .id("reflog-entries")
+                    .flex_1()
+                    .overflow_y_scroll()
+                    .when(entries.is_empty() && !self.loading, |this| {
+                        this.child(
+                            h_flex()
+                                .p_4()
+                                .justify_center()
+                                .child(Label::new("The reflog is empty").color(Color::Muted)),
+                        )
+                    })
+                    .children(entries),
+            )
+    }
+}
+
+impl Item for ReflogView {
+    type Event = ItemEvent;
+
+    fn to_item_events(event: &Self::Event, f: &mut dyn FnMut(ItemEvent)) {
+        f(*event)
+    }
+
+    fn tab_content_text(&self, _detail: usize, _cx: &App) -> SharedString {
+        format!("Reflog: {}", self.reference).into()
+    }
+
+    fn tab_icon(&self, _window: &Window, _cx: &App) -> Option<Icon> {
+        Some(Icon::new(IconName::HistoryRerun))
+    }
+
+    fn telemetry_event_text(&self) -> Option<&'static str> {
+        Some("reflog")
+    }
+
+    fn added_to_workspace(
+        &mut self,
+        _workspace: &mut Workspace,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        window.focus(&self.focus_handle, cx);
+    }
+}
+
+/// Asks for the name of a branch to create at a reflog entry.
+struct BranchAtEntryModal {
+    repository: Entity<Repository>,
+    view: WeakEntity<ReflogView>,
+    sha: SharedString,
+    name_editor: Entity<Editor>,
+}
+
+impl BranchAtEntryModal {
+    fn new(
+        repository: Entity<Repository>,
+        view: WeakEntity<ReflogView>,
+        sha: SharedString,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Self {
+        let name_editor = cx.new(|cx| {
+            let mut editor = Editor::single_line(window, cx);
+            editor.set_placeholder_text("Branch name", window, cx);
+            editor
+        });
+        Self {
+            repository,
+            view,
+            sha,
+            name_editor,
+        }
+    }
+
+    fn cancel(&mut self, _: &Cancel, _window: &mut Window, cx: &mut Context<Self>) {
+        cx.emit(DismissEvent);
+    }
+
+    fn confirm(&mut self, _: &Confirm, _window: &mut Window, cx: &mut Context<Self>) {
+        let name = self.name_editor.read(cx).text(cx).trim().to_string();
+        if name.is_empty() {
+            return;
+        }
+        let sha = self.sha.to_string();
+        let receiver = self.repository.update(cx, |repository, _| {
+            repository.create_branch(name, Some(sha))
+        });
+        self.view
+            .update(cx, |view, cx| view.restore(receiver, cx))
+            .ok();
+        cx.emit(DismissEvent);
+    }
+}
+
+impl EventEmitter<DismissEvent> for BranchAtEntryModal {}
+impl ModalView for BranchAtEntryModal {}
+impl Focusable for BranchAtEntryModal {
+    fn focus_handle(&self, cx: &App) -> FocusHandle {
+        self.name_editor.focus_handle(cx)
+    }
+}
+
+impl Render for BranchAtEntryModal {
+    fn render(&mut self, _: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let short_sha: String = self.sha.chars().take(git::SHORT_SHA_LENGTH).collect();
+        v_flex()
+            .key_context("BranchAtEntryModal")
+            .on_action(cx.listener(Self::cancel))
+            .on_action(cx.listener(Self::confirm))
+            .elevation_2(cx)
+            .w(rems(34.))
+            .child(
+                h_flex()
+                    .px_3()
+                    .pt_2()
+                    .pb_1()
+                    .w_full()
+                    .gap_1p5()
+                    .child(Icon::new(IconName::GitBranchPlus).size(IconSize::XSmall))
+                    .child(
+                        Headline::new(format!("Create Branch at {short_sha}"))
+                            .size(HeadlineSize::XSmall),
+                    ),
+            )
+            .child(div().px_3().pb_3().w_full().child(self.name_editor.clone()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use gpui::{TestAppContext, VisualTestContext};
+    use project::{FakeFs, Project};
+    use serde_json::json;
+    use settings::SettingsStore;
+    use theme::LoadThemes;
+    use util::path;
+    use workspace::MultiWorkspace;
+
+    use super::*;
+
+    fn entry(sha: &str, index: usize, message: &str) -> ReflogEntry {
+        ReflogEntry {
+            sha: sha.to_string().into(),
+            selector: format!("HEAD@{{{index}}}").into(),
+            message: message.to_string().into(),
+            timestamp: 1_700_000_000 - index as i64 * 60,
+        }
+    }
+
+    #[gpui::test]
+    async fn test_restore_from_reflog(cx: &mut TestAppContext) {
+        zlog::init_test();
+        cx.update(|cx| {
+            let settings_store = SettingsStore::test(cx);
+            cx.set_global(settings_store);
+            theme::init(LoadThemes::JustBase, cx);
+            editor::init(cx);
+            crate::init(cx);
+        });
+
+        let fs = FakeFs::new(cx.background_executor.clone());
+        fs.insert_tree(
+            "/root",
+            json!({
+                "project": {
+                    ".git": {},
+                    "file.txt": "content",
+                }
+            }),
+        )
+        .await;
+        fs.with_git_state(Path::new(path!("/root/project/.git")), false, |state| {
+            state.reflogs.insert(
+                "HEAD".to_string(),
+                vec![
+                    entry("ccc", 0, "rebase (finish): returning to refs/heads/main"),
+                    entry("bbb", 1, "rebase (start): checkout origin/main"),
+                    entry("aaa", 2, "commit: Add the feature"),
+                ],
+            );
+        })
+        .unwrap();
+
+        let project = Project::test(fs.clone(), [Path::new(path!("/root/project"))], cx).await;
+        let window_handle =
+            cx.add_window(|window, cx| MultiWorkspace::test_new(project.clone(), window, cx));
+        let workspace = window_handle
+            .read_with(cx, |mw, _| mw.workspace().clone())
+            .unwrap();
+        let cx = &mut VisualTestContext::from_window(window_handle.into(), cx);
+        cx.run_until_parked();
+
+        let repository =
+            project.read_with(cx, |project, cx| project.active_repository(cx).unwrap());
+        workspace.update_in(cx, |workspace, window, cx| {
+            ReflogView::open(repository, workspace, window, cx)
+        });
+        cx.run_until_parked();
+        let view = workspace
+            .read_with(cx, |workspace, cx| {
+                workspace.active_item_as::<ReflogView>(cx)
+            })
+            .unwrap();
+        view.read_with(cx, |view, _| {
+            let shas = view
+                .entries
+                .iter()
+                .map(|entry| entry.sha.as_ref())
+                .collect::<Vec<_>>();
+            assert_eq!(shas, ["ccc", "bbb", "aaa"]);
+        });
+
+        view.update(cx, |view, cx| view.check_out(2, cx));
+        cx.run_until_parked();
+        let head = fs
+            .with_git_state(Path::new(path!("/root/project/.git")), false, |state| {
+                state.refs.get("HEAD").cloned()
+            })
+            .unwrap();
+        assert_eq!(head.as_deref(), Some("aaa"));
+
+        view.update_in(cx, |view, window, cx| view.reset_to(1, window, cx));
+        cx.run_until_parked();
+        cx.simulate_prompt_answer("Reset");
+        cx.run_until_parked();
+        let resets = fs
+            .with_git_state(Path::new(path!("/root/project/.git")), false, |state| {
+                state.resets.clone()
+            })
+            .unwrap();
+        assert_eq!(resets, [("bbb".to_string(), ResetMode::Hard)]);
+    }
+}
@@ -0,0 +1,877 @@
+use editor::Editor;
+use fuzzy::StringMatchCandidate;
+use git::repository::RemoteInfo;
+use gpui::{
+    Action, AnyElement, App, Context, DismissEvent, Entity, EventEmitter, FocusHandle, Focusable,
+    InteractiveElement, IntoElement, ParentElement, PromptLevel, Render, SharedString, Styled,
+    Subscription, Task, WeakEntity, Window, actions, rems,
+};
+use menu::{Cancel, Confirm};
+use picker::{Picker, PickerDelegate};
+use project::git_store::Repository;
+use std::sync::Arc;
+use ui::{HighlightedLabel, KeyBinding, ListItem, ListItemSpacing, Tooltip, prelude::*};
+use util::ResultExt;
+use workspace::notifications::DetachAndPromptErr;
+use workspace::{ModalView, Workspace};
+
+use crate::git_panel::show_error_toast;
+use crate::resolve_active_repository;
+
+actions!(
+    remote_list,
+    [
+        /// Add a remote, named after the query.
+        AddRemote,
+        /// Remove the selected remote.
+        RemoveRemote,
+        /// Rename the selected remote.
+        RenameRemote,
+        /// Change where pushes to the selected remote go.
+        EditPushUrl,
+    ]
+);
+
+pub fn register(workspace: &mut Workspace) {
+    workspace.register_action(|workspace, _: &git::ManageRemotes, window, cx| {
+        let Some(repository) = resolve_active_repository(workspace, cx) else {
+            return;
+        };
+        let weak_workspace = workspace.weak_handle();
+        workspace.toggle_modal(window, cx, |window, cx| {
+            RemoteList::new(repository, weak_workspace, rems(34.), window, cx)
+        });
+    });
+}
+
+pub struct RemoteList {
+    width: Rems,
+    pub picker: Entity<Picker<RemoteListDelegate>>,
+    picker_focus_handle: FocusHandle,
+    _subscriptions: Vec<Subscription>,
+}
+
+impl RemoteList {
+    fn new(
+        repository: Entity<Repository>,
+        workspace: WeakEntity<Workspace>,
+        width: Rems,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Self {
+        let delegate = RemoteListDelegate::new(repository, workspace, cx);
+        let picker = cx.new(|cx| {
+            let mut picker = Picker::uniform_list(delegate, window, cx).show_scrollbar(true);
+            picker.delegate.load_remotes(window, cx);
+            picker
+        });
+        let picker_focus_handle = picker.focus_handle(cx);
+        picker.update(cx, |picker, _| {
+            picker.delegate.focus_handle = picker_focus_handle.clone();
+        });
+        let _subscriptions = vec![cx.subscribe(&picker, |_, _, _, cx| {
+            cx.emit(DismissEvent);
+        })];
+
+        Self {
+            width,
+            picker,
+            picker_focus_handle,
+            _subscriptions,
+        }
+    }
+
+    fn handle_add_remote(&mut self, _: &AddRemote, window: &mut Window, cx: &mut Context<Self>) {
+        self.picker.update(cx, |picker, cx| {
+            let name = picker.delegate.last_query.trim().to_string();
+            picker
+                .delegate
+                .open_edit_modal(RemoteEdit::Add { name }, window, cx);
+        });
+    }
+
+    fn handle_remove_remote(
+        &mut self,
+        _: &RemoveRemote,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.picker.update(cx, |picker, cx| {
+            picker
+                .delegate
+                .remove_remote_at(picker.delegate.selected_index(), window, cx);
+        });
+    }
+
+    fn handle_rename_remote(
+        &mut self,
+        _: &RenameRemote,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.picker.update(cx, |picker, cx| {
+            let Some(remote) = picker.delegate.selected_remote() else {
+                return;
+            };
+            picker
+                .delegate
+                .open_edit_modal(RemoteEdit::Rename { remote }, window, cx);
+        });
+    }
+
+    fn handle_edit_push_url(
+        &mut self,
+        _: &EditPushUrl,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.picker.update(cx, |picker, cx| {
+            let Some(remote) = picker.delegate.selected_remote() else {
+                return;
+            };
+            picker
+                .delegate
+                .open_edit_modal(RemoteEdit::PushUrl { remote }, window, cx);
+        });
+    }
+}
+
+impl ModalView for RemoteList {}
+impl EventEmitter<DismissEvent> for RemoteList {}
+impl Focusable for RemoteList {
+    fn focus_handle(&self, _: &App) -> FocusHandle {
+        self.picker_focus_handle.clone()
+    }
+}
+
+impl Render for RemoteList {
+    fn render(&mut self, _: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        v_flex()
+            .key_context("RemoteList")
+            .w(self.width)
+            .on_action(cx.listener(Self::handle_add_remote))
+            .on_action(cx.listener(Self::handle_remove_remote))
+            .on_action(cx.listener(Self::handle_rename_remote))
+            .on_action(cx.listener(Self::handle_edit_push_url))
+            .child(self.picker.clone())
+    }
+}
+
+#[derive(Debug, Clone)]
+struct RemoteMatch {
+    remote: RemoteInfo,
+    positions: Vec<usize>,
+}
+
+pub struct RemoteListDelegate {
+    matches: Vec<RemoteMatch>,
+    all_remotes: Option<Vec<RemoteInfo>>,
+    repo: Entity<Repository>,
+    workspace: WeakEntity<Workspace>,
+    selected_index: usize,
+    last_query: String,
+    focus_handle: FocusHandle,
+    _load_task: Task<()>,
+}
+
+impl RemoteListDelegate {
+    fn new(
+        repo: Entity<Repository>,
+        workspace: WeakEntity<Workspace>,
+        cx: &mut Context<RemoteList>,
+    ) -> Self {
+        Self {
+            matches: Vec::new(),
+            all_remotes: None,
+            repo,
+            workspace,
+            selected_index: 0,
+            last_query: String::new(),
+            focus_handle: cx.focus_handle(),
+            _load_task: Task::ready(()),
+        }
+    }
+
+    fn load_remotes(&mut self, window: &mut Window, cx: &mut Context<Picker<Self>>) {
+        let remotes = self.repo.update(cx, |repo, _| repo.remote_infos());
+        self._load_task = cx.spawn_in(window, async move |picker, cx| {
+            let Some(remotes) = remotes.await.ok().and_then(|remotes| remotes.log_err()) else {
+                return;
+            };
+            picker
+                .update_in(cx, |picker, window, cx| {
+                    picker.delegate.all_remotes = Some(remotes);
+                    picker.refresh(window, cx);
+                })
+                .ok();
+        });
+    }
+
+    fn selected_remote(&self) -> Option<RemoteInfo> {
+        self.matches
+            .get(self.selected_index)
+            .map(|remote_match| remote_match.remote.clone())
+    }
+
+    fn open_edit_modal(
+        &self,
+        edit: RemoteEdit,
+        window: &mut Window,
+        cx: &mut Context<Picker<Self>>,
+    ) {
+        let repo = self.repo.clone();
+        let workspace = self.workspace.clone();
+        window.defer(cx, move |window, cx| {
+            workspace
+                .clone()
+                .update(cx, |workspace_handle, cx| {
+                    workspace_handle.toggle_modal(window, cx, |window, cx| {
+                        EditRemoteModal::new(repo, edit, workspace, window, cx)
+                    });
+                })
+                .ok();
+        });
+    }
+
+    fn remove_remote_at(&self, ix: usize, window: &mut Window, cx: &mut Context<Picker<Self>>) {
+        let Some(remote) = self
+            .matches
+            .get(ix)
+            .map(|remote_match| remote_match.remote.name.clone())
+        else {
+            return;
+        };
+        let repo = self.repo.clone();
+        let confirmation = window.prompt(
+            PromptLevel::Warning,
+            &format!("Remove remote {remote}?"),
+            Some("Its remote-tracking branches are deleted too. The remote itself is not changed."),
+            &["Remove", "Cancel"],
+            cx,
+        );
+        cx.spawn_in(window, async move |picker, cx| {
+            if confirmation.await != Ok(0) {
+                return Ok(());
+            }
+            repo.update(cx, |repo, _| repo.remove_remote(remote.to_string()))
+                .await??;
+            picker.update_in(cx, |picker, window, cx| {
+                picker.delegate.load_remotes(window, cx);
+            })?;
+            Ok(())
+        })
+        .detach_and_prompt_err("Failed to remove remote", window, cx, |e, _, _| {
+            Some(e.to_string())
+        });
+    }
+
+    /// Makes the remote the one push and pull use when a branch doesn't track a remote of its
+    /// own, or stops it being that if it already is.
+    fn toggle_default_remote(
+        &self,
+        remote: RemoteInfo,
+        window: &mut Window,
+        cx: &mut Context<Picker<Self>>,
+    ) {
+        let default_remote = (!remote.is_default).then(|| remote.name.to_string());
+        let update = self
+            .repo
+            .update(cx, |repo, _| repo.set_default_remote(default_remote));
+        cx.spawn_in(window, async move |picker, cx| {
+            update.await??;
+            picker.update_in(cx, |picker, window, cx| {
+                picker.delegate.load_remotes(window, cx);
+            })?;
+            Ok(())
+        })
+        .detach_and_prompt_err(
+            "Failed to set the default remote",
+            window,
+            cx,
+            |e, _, _| Some(e.to_string()),
+        );
+    }
+}
+
+impl PickerDelegate for RemoteListDelegate {
+    type ListItem = ListItem;
+
+    fn placeholder_text(&self, _window: &mut Window, _cx: &mut App) -> Arc<str> {
+        "Select a remote…".into()
+    }
+
+    fn match_count(&self) -> usize {
+        self.matches.len()
+    }
+
+    fn selected_index(&self) -> usize {
+        self.selected_index
+    }
+
+    fn set_selected_index(
+        &mut self,
+        ix: usize,
+        _window: &mut Window,
+        _: &mut Context<Picker<Self>>,
+    ) {
+        self.selected_index = ix;
+    }
+
+    fn update_matches(
+        &mut self,
+        query: String,
+        window: &mut Window,
+        cx: &mut Context<Picker<Self>>,
+    ) -> Task<()> {
+        let Some(all_remotes) = self.all_remotes.clone() else {
+            return Task::ready(());
+        };
+
+        cx.spawn_in(window, async move |picker, cx| {
+            let matches: Vec<RemoteMatch> = if query.is_empty() {
+                all_remotes
+                    .into_iter()
+                    .map(|remote| RemoteMatch {
+                        remote,
+                        positions: Vec::new(),
+                    })
+                    .collect()
+            } else {
+                let candidates = all_remotes
+                    .iter()
+                    .enumerate()
+                    .map(|(ix, remote)| StringMatchCandidate::new(ix, &remote.name))
+                    .collect::<Vec<StringMatchCandidate>>();
+                fuzzy::match_strings(
+                    &candidates,
+                    &query,
+                    false,
+                    true,
+                    10000,
+                    &Default::default(),
+                    cx.background_executor().clone(),
+                )
+                .await
+                .into_iter()
+                .map(|candidate| RemoteMatch {
+                    remote: all_remotes[candidate.candidate_id].clone(),
+                    positions: candidate.positions,
+                })
+                .collect()
+            };
+
+            picker
+                .update(cx, |picker, _| {
+                    let delegate = &mut picker.delegate;
+                    delegate.matches = matches;
+                    if delegate.matches.is_empty() {
+                        delegate.selected_index = 0;
+                    } else {
+                        delegate.selected_index =
+                            core::cmp::min(delegate.selected_index, delegate.matches.len() - 1);
+                    }
+                    delegate.last_query = query;
+                })
+                .log_err();
+        })
+    }
+
+    fn confirm(&mut self, _secondary: bool, window: &mut Window, cx: &mut Context<Picker<Self>>) {
+        let Some(remote) = self.selected_remote() else {
+            return;
+        };
+        self.toggle_default_remote(remote, window, cx);
+    }
+
+    fn dismissed(&mut self, _: &mut Window, cx: &mut Context<Picker<Self>>) {
+        cx.emit(DismissEvent);
+    }
+
+    fn render_match(
+        &self,
+        ix: usize,
+        selected: bool,
+        _window: &mut Window,
+        cx: &mut Context<Picker<Self>>,
+    ) -> Option<Self::ListItem> {
+        let remote_match = &self.matches[ix];
+        let remote = &remote_match.remote;
+        let focus_handle = self.focus_handle.clone();
+
+        let remove_button = |entry_ix: usize| {
+            IconButton::new(("remove-remote", entry_ix), IconName::Trash)
+                .icon_size(IconSize::Small)
+                .tooltip(move |_, cx| {
+                    Tooltip::for_action_in("Remove Remote", &RemoveRemote, &focus_handle, cx)
+                })
+                .on_click(cx.listener(move |this, _, window, cx| {
+                    this.delegate.remove_remote_at(entry_ix, window, cx);
+                }))
+        };
+
+        let urls = match (&remote.fetch_url, &remote.push_url) {
+            (Some(fetch_url), Some(push_url)) => format!("{fetch_url} (push: {push_url})"),
+            (Some(url), None) | (None, Some(url)) => url.to_string(),
+            (None, None) => String::new(),
+        };
+
+        Some(
+            ListItem::new(format!("remote-{ix}"))
+                .inset(true)
+                .spacing(ListItemSpacing::Sparse)
+                .toggle_state(selected)
+                .child(
+                    h_flex()
+                        .w_full()
+                        .gap_2p5()
+                        .child(
+                            Icon::new(IconName::Server)
+                                .size(IconSize::Small)
+                                .color(Color::Muted),
+                        )
+                        .child(
+                            v_flex()
+                                .min_w_0()
+                                .child(
+                                    h_flex()
+                                        .gap_1()
+                                        .child(
+                                            HighlightedLabel::new(
+                                                remote.name.clone(),
+                                                remote_match.positions.clone(),
+                                            )
+                                            .truncate(),
+                                        )
+                                        .when(remote.is_default, |this| {
+                                            this.child(
+                                                Label::new("default")
+                                                    .size(LabelSize::Small)
+                                                    .color(Color::Accent),
+                                            )
+                                        }),
+                                )
+                                .child(
+                                    Label::new(urls)
+                                        .size(LabelSize::Small)
+                                        .color(Color::Muted)
+                                        .truncate(),
+                                ),
+                        ),
+                )
+                .map(|this| {
+                    if selected {
+                        this.end_slot(remove_button(ix))
+                    } else {
+                        this.end_hover_slot(remove_button(ix))
+                    }
+                }),
+        )
+    }
+
+    fn no_matches_text(&self, _window: &mut Window, _cx: &mut App) -> Option<SharedString> {
+        Some("No remotes found".into())
+    }
+
+    fn render_footer(&self, _: &mut Window, cx: &mut Context<Picker<Self>>) -> Option<AnyElement> {
+        let focus_handle = self.focus_handle.clone();
+        let selected_remote = self.selected_remote();
+
+        Some(
+            h_flex()
+                .w_full()
+                .p_1p5()
+                .gap_0p5()
+                .justify_end()
+                .flex_wrap()
+                .border_t_1()
+                .border_color(cx.theme().colors().border_variant)
+                .child(
+                    Button::new("add-remote", "Add…")
+                        .key_binding(
+                            KeyBinding::for_action_in(&AddRemote, &focus_handle, cx)
+                                .map(|kb| kb.size(rems_from_px(12.))),
+                        )
+                        .on_click(|_, window, cx| {
+                            window.dispatch_action(AddRemote.boxed_clone(), cx)
+                        }),
+                )
+                .when_some(selected_remote, |this, remote| {
+                    this.child(
+                        Button::new("rename-remote", "Rename…")
+                            .key_binding(
+                                KeyBinding::for_action_in(&RenameRemote, &focus_handle, cx)
+                                    .map(|kb| kb.size(rems_from_px(12.))),
+                            )
+                            .on_click(|_, window, cx| {
+                                window.dispatch_action(RenameRemote.boxed_clone(), cx)
+                            }),
+                    )
+                    .child(
+                        Button::new("edit-push-url", "Push URL…")
+                            .key_binding(
+                                KeyBinding::for_action_in(&EditPushUrl, &focus_handle, cx)
+                                    .map(|kb| kb.size(rems_from_px(12.))),
+                            )
+                            .on_click(|_, window, cx| {
+                                window.dispatch_action(EditPushUrl.boxed_clone(), cx)
+                            }),
+                    )
+                    .child(
+                        Button::new(
+                            "toggle-default-remote",
+                            if remote.is_default {
+                                "Unset Default"
+                            } else {
+                                "Set as Default"
+                            },
+                        )
+                        .key_binding(
+                            KeyBinding::for_action_in(&menu::Confirm, &focus_handle, cx)
+                                .map(|kb| kb.size(rems_from_px(12.))),
+                        )
+                        .on_click(|_, window, cx| {
+                            window.dispatch_action(menu::Confirm.boxed_clone(), cx)
+                        }),
+                    )
+                })
+                .into_any(),
+        )
+    }
+}
+
+#[derive(Debug, Clone)]
+enum RemoteEdit {
+    Add { name: String },
+    Rename { remote: RemoteInfo },
+    PushUrl { remote: RemoteInfo },
+}
+
+/// Asks for the name and URL of a new remote, a new name for a remote, or the URL pushes to a
+/// remote go to.
+pub struct EditRemoteModal {
+    repository: Entity<Repository>,
+    workspace: WeakEntity<Workspace>,
+    edit: RemoteEdit,
+    name_editor: Entity<Editor>,
+    url_editor: Entity<Editor>,
+}
+
+impl EditRemoteModal {
+    fn new(
+        repository: Entity<Repository>,
+        edit: RemoteEdit,
+        workspace: WeakEntity<Workspace>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Self {
+        let (name, url, url_placeholder) = match &edit {
+            RemoteEdit::Add { name } => (name.clone(), String::new(), "URL"),
+            RemoteEdit::Rename { remote } => (remote.name.to_string(), String::new(), ""),
+            RemoteEdit::PushUrl { remote } => (
+                String::new(),
+                remote
+                    .push_url
+                    .as_ref()
+                    .map(ToString::to_string)
+                    .unwrap_or_default(),
+                "Push URL (leave empty to push to the fetch URL)",
+            ),
+        };
+        let name_editor = cx.new(|cx| {
+            let mut editor = Editor::single_line(window, cx);
+            editor.set_placeholder_text("Remote name", window, cx);
+            editor.set_text(name, window, cx);
+            editor
+        });
+        let url_editor = cx.new(|cx| {
+            let mut editor = Editor::single_line(window, cx);
+            editor.set_placeholder_text(url_placeholder, window, cx);
+            editor.set_text(url, window, cx);
+            editor
+        });
+        Self {
+            repository,
+            workspace,
+            edit,
+            name_editor,
+            url_editor,
+        }
+    }
+
+    fn cancel(&mut self, _: &Cancel, _window: &mut Window, cx: &mut Context<Self>) {
+        cx.emit(DismissEvent);
+    }
+
+    fn confirm(&mut self, _: &Confirm, _window: &mut Window, cx: &mut Context<Self>) {
+        let name = self.name_editor.read(cx).text(cx).trim().to_string();
+        let url = self.url_editor.read(cx).text(cx).trim().to_string();
+        let update = match &self.edit {
+            RemoteEdit::Add { .. } => {
+                if name.is_empty() || url.is_empty() {
+                    return;
+                }
+                self.repository
+                    .update(cx, |repository, _| repository.create_remote(name, url))
+            }
+            RemoteEdit::Rename { remote } => {
+                if name.is_empty() {
+                    return;
+                }
+                let old_name = remote.name.to_string();
+                self.repository
+                    .update(cx, |repository, _| repository.rename_remote(old_name, name))
+            }
+            RemoteEdit::PushUrl { remote } => {
+                let remote_name = remote.name.to_string();
+                self.repository.update(cx, |repository, _| {
+                    repository.set_remote_push_url(remote_name, (!url.is_empty()).then_some(url))
+                })
+            }
+        };
+        let repository = self.repository.clone();
+        let workspace = self.workspace.clone();
+        cx.spawn(async move |_, cx| match update.await {
+            // Relisting keeps the remotes shown in the git panel's menu up to date.
+            Ok(Ok(())) => {
+                repository
+                    .update(cx, |repository, _| repository.remote_infos())
+                    .await
+                    .ok();
+            }
+            Ok(Err(error)) => {
+                if let Some(workspace) = workspace.upgrade() {
+                    cx.update(|cx| show_error_toast(workspace, "remote", error, cx));
+                }
+            }
+            Err(_) => {}
+        })
+        .detach();
+        cx.emit(DismissEvent);
+    }
+}
+
+impl EventEmitter<DismissEvent> for EditRemoteModal {}
+impl ModalView for EditRemoteModal {}
+impl Focusable for EditRemoteModal {
+    fn focus_handle(&self, cx: &App) -> FocusHandle {
+        match self.edit {
+            RemoteEdit::PushUrl { .. } => self.url_editor.focus_handle(cx),
+            RemoteEdit::Add { .. } | RemoteEdit::Rename { .. } => self.name_editor.focus_handle(cx),
+        }
+    }
+}
+
+impl Render for EditRemoteModal {
+    fn render(&mut self, _: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let title: SharedString = match &self.edit {
+            RemoteEdit::Add { .. } => "Add Remote".into(),
+            RemoteEdit::Rename { remote } => format!("Rename Remote {}", remote.name).into(),
+            RemoteEdit::PushUrl { remote } => format!("Push URL of {}", remote.name).into(),
+        };
+        let show_name = !matches!(self.edit, RemoteEdit::PushUrl { .. });
+        let show_url = !matches!(self.edit, RemoteEdit::Rename { .. });
+
+        v_flex()
+            .key_context("EditRemoteModal")
+            .on_action(cx.listener(Self::cancel))
+            .on_action(cx.listener(Self::confirm))
+            .elevation_2(cx)
+            .w(rems(34.))
+            .child(
+                h_flex()
+                    .px_3()
+                    .pt_2()
+                    .pb_1()
+                    .w_full()
+                    .gap_1p5()
+                    .child(Icon::new(IconName::Server).size(IconSize::XSmall))
+                    .child(Headline::new(title).size(HeadlineSize::XSmall)),
+            )
+            .child(
+                v_flex()
+                    .px_3()
+                    .pb_3()
+                    .w_full()
+                    .gap_2()
+                    .when(show_name, |this| this.child(self.name_editor.clone()))
+                    .when(show_url, |this| this.child(self.url_editor.clone())),
+            )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use super::*;
+    use gpui::{TestAppContext, VisualTestContext};
+    use project::{FakeFs, Project};
+    use serde_json::json;
+    use settings::SettingsStore;
+    use util::path;
+    use workspace::MultiWorkspace;
+
+    fn init_test(cx: &mut TestAppContext) {
+        cx.update(|cx| {
+            let settings_store = SettingsStore::test(cx);
+            cx.set_global(settings_store);
+
+            theme::init(theme::LoadThemes::JustBase, cx);
+            editor::init(cx);
+        })
+    }
+
+    #[gpui::test]
+    async fn test_add_rename_and_remove_remotes(cx: &mut TestAppContext) {
+        init_test(cx);
+
+        let fs = FakeFs::new(cx.executor());
+        fs.insert_tree(path!("/project"), json!({ ".git": {}, "a.txt": "a" }))
+            .await;
+        fs.with_git_state(Path::new(path!("/project/.git")), true, |state| {
+            state.branches.insert("main".to_string());
+            state.current_branch_name = Some("main".to_string());
+            state.remotes.insert(
+                "origin".to_string(),
+                "https://github.com/me/zed.git".to_string(),
+            );
+        })
+        .unwrap();
+        let project = Project::test(fs.clone(), [Path::new(path!("/project"))], cx).await;
+        let multi_workspace =
+            cx.add_window(|window, cx| MultiWorkspace::test_new(project.clone(), window, cx));
+        let cx = &mut VisualTestContext::from_window(*multi_workspace, cx);
+        let workspace = multi_workspace
+            .update(cx, |workspace, _, _| workspace.workspace().clone())
+            .unwrap();
+        cx.run_until_parked();
+        let repository =
+            project.read_with(cx, |project, cx| project.active_repository(cx).unwrap());
+        let open_modal = |edit: RemoteEdit, cx: &mut VisualTestContext| {
+            workspace.update_in(cx, |workspace, window, cx| {
+                let weak_workspace = workspace.weak_handle();
+                let repository = repository.clone();
+                workspace.toggle_modal(window, cx, move |window, cx| {
+                    EditRemoteModal::new(repository, edit, weak_workspace, window, cx)
+                });
+                workspace.active_modal::<EditRemoteModal>(cx).unwrap()
+            })
+        };
+
+        let modal = open_modal(
+            RemoteEdit::Add {
+                name: "upstream".to_string(),
+            },
+            cx,
+        );
+        modal.update_in(cx, |modal, window, cx| {
+            modal.url_editor.update(cx, |editor, cx| {
+                editor.set_text("https://github.com/zed-industries/zed.git", window, cx)
+            });
+            modal.confirm(&Confirm, window, cx);
+        });
+        cx.run_until_parked();
+
+        let remote = |name: &str, cx: &mut VisualTestContext| {
+            repository.read_with(cx, |repository, _| {
+                repository
+                    .cached_remotes()
+                    .unwrap()
+                    .iter()
+                    .find(|remote| remote.name == name)
+                    .cloned()
+            })
+        };
+        let modal = open_modal(
+            RemoteEdit::PushUrl {
+                remote: remote("origin", cx).unwrap(),
+            },
+            cx,
+        );
+        modal.update_in(cx, |modal, window, cx| {
+            modal.url_editor.update(cx, |editor, cx| {
+                editor.set_text("git@github.com:me/zed.git", window, cx)
+            });
+            modal.confirm(&Confirm, window, cx);
+        });
+        cx.run_until_parked();
+        assert_eq!(
+            remote("origin", cx).unwrap().push_url.as_deref(),
+            Some("git@github.com:me/zed.git")
+        );
+
+        let modal = open_modal(
+            RemoteEdit::Rename {
+                remote: remote("upstream", cx).unwrap(),
+            },
+            cx,
+        );
+        modal.update_in(cx, |modal, window, cx| {
+            modal
+                .name_editor
+                .update(cx, |editor, cx| editor.set_text("zed", window, cx));
+            modal.confirm(&Confirm, window, cx);
+        });
+        cx.run_until_parked();
+        assert!(remote("upstream", cx).is_none());
+        assert_eq!(
+            remote("zed", cx).unwrap().fetch_url.as_deref(),
+            Some("https://github.com/zed-industries/zed.git")
+        );
+
+        let remote_list = workspace.update_in(cx, |workspace, window, cx| {
+            let weak_workspace = workspace.weak_handle();
+            let repository = repository.clone();
+            workspace.toggle_modal(window, cx, move |window, cx| {
+                RemoteList::new(repository, weak_workspace, rems(34.), window, cx)
+            });
+            workspace.active_modal::<RemoteList>(cx).unwrap()
+        });
+        cx.run_until_parked();
+
+        // Confirming makes the selected remote the default, and confirming again unsets it.
+        remote_list.update_in(cx, |remote_list, window, cx| {
+            remote_list.picker.update(cx, |picker, cx| {
+                let names = picker
+                    .delegate
+                    .matches
+                    .iter()
+                    .map(|remote_match| remote_match.remote.name.clone())
+                    .collect::<Vec<_>>();
+                assert_eq!(names, ["origin", "zed"]);
+                picker.delegate.set_selected_index(1, window, cx);
+                picker.delegate.confirm(false, window, cx);
+            })
+        });
+        cx.run_until_parked();
+        let default_remote = || {
+            fs.with_git_state(Path::new(path!("/project/.git")), false, |state| {
+                state.default_remote.clone()
+            })
+            .unwrap()
+        };
+        assert_eq!(default_remote().as_deref(), Some("zed"));
+        remote_list.update_in(cx, |remote_list, window, cx| {
+            remote_list.picker.update(cx, |picker, cx| {
+                assert!(picker.delegate.matches[1].remote.is_default);
+                picker.delegate.confirm(false, window, cx);
+            })
+        });
+        cx.run_until_parked();
+        assert_eq!(default_remote(), None);
+
+        remote_list.update_in(cx, |remote_list, window, cx| {
+            remote_list.picker.update(cx, |picker, cx| {
+                picker.delegate.remove_remote_at(0, window, cx);
+            })
+        });
+        cx.run_until_parked();
+        cx.simulate_prompt_answer("Remove");
+        cx.run_until_parked();
+        remote_list.read_with(cx, |remote_list, cx| {
+            let matches = &remote_list.picker.read(cx).delegate.matches;
+            assert_eq!(matches.len(), 1);
+            assert_eq!(matches[0].remote.name, "zed");
+        });
+    }
+}
@@ -33,6 +33,14 @@ pub struct SuccessMessage {
     pub style: SuccessStyle,
 }
 
+/// Whether a push created the branch on the remote.
+pub fn pushed_new_branch(output: &RemoteCommandOutput) -> bool {
+    output
+        .stderr
+        .lines()
+        .any(|line| line.trim_start().starts_with("* [new branch]"))
+}
+
 pub fn format_output(action: &RemoteAction, output: RemoteCommandOutput) -> SuccessMessage {
     match action {
         RemoteAction::Fetch(remote) => {
@@ -306,4 +314,27 @@ mod tests {
             panic!("Expected ToastWithLog variant");
         }
     }
+
+    #[test]
+    fn test_pushed_new_branch() {
+        let new_branch = RemoteCommandOutput {
+            stdout: String::new(),
+            stderr: indoc! { "
+                To http://example.com/test/test.git
+                 * [new branch]      test -> test
+                "}
+            .to_string(),
+        };
+        assert!(pushed_new_branch(&new_branch));
+
+        let existing_branch = RemoteCommandOutput {
+            stdout: String::new(),
+            stderr: indoc! { "
+                To http://example.com/test/test.git
+                   3ef1539..faa6f97  test -> test
+                "}
+            .to_string(),
+        };
+        assert!(!pushed_new_branch(&existing_branch));
+    }
 }
@@ -0,0 +1,323 @@
+//! ResetModal moves the current branch to a commit, after choosing what happens to the changes
+//! of the commits it leaves behind and to the uncommitted ones.
+
+use editor::Editor;
+use git::repository::ResetMode;
+use gpui::{
+    App, Context, DismissEvent, Entity, EventEmitter, FocusHandle, Focusable, InteractiveElement,
+    IntoElement, ParentElement, Render, SharedString, Styled, Subscription, WeakEntity, Window,
+    rems,
+};
+use menu::{Cancel, Confirm};
+use project::git_store::Repository;
+use ui::{ToggleButtonGroup, ToggleButtonGroupStyle, ToggleButtonSimple, prelude::*};
+use workspace::{ModalView, Workspace};
+
+use crate::git_panel::show_error_toast;
+
+/// Resets the current branch to `sha`, such as from a commit in the git graph.
+pub struct ResetModal {
+    repository: Entity<Repository>,
+    workspace: WeakEntity<Workspace>,
+    sha: SharedString,
+    /// The branch that is reset, or `HEAD` when it is detached.
+    branch_name: SharedString,
+    mode: ResetMode,
+    /// Where the branch name is typed to confirm a hard reset.
+    confirmation_editor: Entity<Editor>,
+    focus_handle: FocusHandle,
+    _confirmation_subscription: Subscription,
+}
+
+impl ResetModal {
+    pub fn toggle(
+        workspace: &mut Workspace,
+        repository: Entity<Repository>,
+        sha: String,
+        window: &mut Window,
+        cx: &mut Context<Workspace>,
+    ) {
+        let weak_workspace = workspace.weak_handle();
+        workspace.toggle_modal(window, cx, |window, cx| {
+            ResetModal::new(repository, weak_workspace, sha, window, cx)
+        });
+    }
+
+    fn new(
+        repository: Entity<Repository>,
+        workspace: WeakEntity<Workspace>,
+        sha: String,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Self {
+        let branch_name = repository
+            .read(cx)
+            .branch
+            .as_ref()
+            .map_or(SharedString::from("HEAD"), |branch| {
+                branch.name().to_string().into()
+            });
+        let confirmation_editor = cx.new(|cx| {
+            let mut editor = Editor::single_line(window, cx);
+            editor.set_placeholder_text(&format!("Type {branch_name} to confirm"), window, cx);
+            editor
+        });
+        let confirmation_subscription = cx.observe(&confirmation_editor, |_, _, cx| cx.notify());
+        Self {
+            repository,
+            workspace,
+            sha: sha.into(),
+            branch_name,
+            mode: ResetMode::Mixed,
+            confirmation_editor,
+            focus_handle: cx.focus_handle(),
+            _confirmation_subscription: confirmation_subscription,
+        }
+    }
+
+    fn set_mode(&mut self, mode: ResetMode, window: &mut Window, cx: &mut Context<Self>) {
+        self.mode = mode;
+        if mode == ResetMode::Hard {
+            window.focus(&self.confirmation_editor.focus_handle(cx), cx);
+        } else {
+            window.focus(&self.focus_handle, cx);
+        }
+        cx.notify();
+    }
+
+    /// A hard reset discards work, so it waits for the branch name to be typed.
+    fn can_reset(&self, cx: &App) -> bool {
+        self.mode != ResetMode::Hard
+            || self.confirmation_editor.read(cx).text(cx).trim() == self.branch_name.as_ref()
+    }
+
+    fn explanation(&self) -> String {
+        let short_sha: String = self.sha.chars().take(git::SHORT_SHA_LENGTH).collect();
+        let branch_name = &self.branch_name;
+        match self.mode {
+            ResetMode::Soft => format!(
+                "Moves {branch_name} to {short_sha}. The changes of the commits after it are \
+                kept staged, and uncommitted changes are left as they are."
+            ),
+            ResetMode::Mixed => format!(
+                "Moves {branch_name} to {short_sha}. The changes of the commits after it are \
+                kept as unstaged changes in the working tree, along with anything staged."
+            ),
+            ResetMode::Hard | ResetMode::Merge => format!(
+                "Moves {branch_name} to {short_sha} and discards the changes of the commits \
+                after it, along with all staged and unstaged changes. Commits that no other \
+                branch contains can then only be recovered from the reflog."
+            ),
+        }
+    }
+
+    fn cancel(&mut self, _: &Cancel, _window: &mut Window, cx: &mut Context<Self>) {
+        cx.emit(DismissEvent);
+    }
+
+    fn confirm(&mut self, _: &Confirm, _window: &mut Window, cx: &mut Context<Self>) {
+        if !self.can_reset(cx) {
+            return;
+        }
+        let sha = self.sha.to_string();
+        let mode = self.mode;
+        let reset = self
+            .repository
+            .update(cx, |repository, cx| repository.reset(sha, mode, cx));
+        let workspace = self.workspace.clone();
+        cx.spawn(async move |_, cx| {
+            if let Ok(Err(error)) = reset.await
+                && let Some(workspace) = workspace.upgrade()
+            {
+                cx.update(|cx| show_error_toast(workspace, "reset", error, cx));
+            }
+        })
+        .detach();
+        cx.emit(DismissEvent);
+    }
+}
+
+impl EventEmitter<DismissEvent> for ResetModal {}
+impl ModalView for ResetModal {}
+impl Focusable for ResetModal {
+    fn focus_handle(&self, _cx: &App) -> FocusHandle {
+        self.focus_handle.clone()
+    }
+}
+
+impl Render for ResetModal {
+    fn render(&mut self, _: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let short_sha: String = self.sha.chars().take(git::SHORT_SHA_LENGTH).collect();
+        let is_hard = self.mode == ResetMode::Hard;
+        let modes = [ResetMode::Soft, ResetMode::Mixed, ResetMode::Hard];
+        let selected_index = modes
+            .iter()
+            .position(|mode| *mode == self.mode)
+            .unwrap_or(1);
+
+        v_flex()
+            .key_context("ResetModal")
+            .track_focus(&self.focus_handle)
+            .on_action(cx.listener(Self::cancel))
+            .on_action(cx.listener(Self::confirm))
+            .elevation_2(cx)
+            .w(rems(34.))
+            .child(
+                h_flex()
+                    .px_3()
+                    .pt_2()
+                    .pb_1()
+                    .w_full()
+                    .gap_1p5()
+                    .child(Icon::new(IconName::Undo).size(IconSize::XSmall))
+                    .child(
+                        Headline::new(format!("Reset {} to {short_sha}", self.branch_name))
+                            .size(HeadlineSize::XSmall),
+                    ),
+            )
+            .child(
+                v_flex()
+                    .px_3()
+                    .pb_3()
+                    .w_full()
+                    .gap_2()
+                    .child(
+                        ToggleButtonGroup::single_row(
+                            "reset-mode",
+                            modes.map(|mode| {
+                                let label = match mode {
+                                    ResetMode::Soft => "Soft",
+                                    ResetMode::Mixed => "Mixed",
+                                    ResetMode::Hard | ResetMode::Merge => "Hard",
+                                };
+                                ToggleButtonSimple::new(
+                                    label,
+                                    cx.listener(move |this, _, window, cx| {
+                                        this.set_mode(mode, window, cx)
+                                    }),
+                                )
+                            }),
+                        )
+                        .style(ToggleButtonGroupStyle::Outlined)
+                        .auto_width()
+                        .selected_index(selected_index),
+                    )
+                    .child(
+                        Label::new(self.explanation())
+                            .size(LabelSize::Small)
+                            .color(if is_hard { Color::Error } else { Color::Muted }),
+                    )
+                    .when(is_hard, |this| this.child(self.confirmation_editor.clone()))
+                    .child(
+                        h_flex().justify_end().child(
+                            Button::new("reset-branch", "Reset")
+                                .style(if is_hard {
+                                    ButtonStyle::Tinted(TintColor::Error)
+                                } else {
+                                    ButtonStyle::Filled
+                                })
+                                .disabled(!self.can_reset(cx))
+                                .on_click(cx.listener(|this, _, window, cx| {
+                                    this.confirm(&Confirm, window, cx)
+                                })),
+                        ),
+                    ),
+            )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use super::*;
+    use gpui::{TestAppContext, VisualTestContext};
+    use project::{FakeFs, Project};
+    use serde_json::json;
+    use settings::SettingsStore;
+    use util::path;
+    use workspace::MultiWorkspace;
+
+    fn init_test(cx: &mut TestAppContext) {
+        cx.update(|cx| {
+            let settings_store = SettingsStore::test(cx);
+            cx.set_global(settings_store);
+
+            theme::init(theme::LoadThemes::JustBase, cx);
+            editor::init(cx);
+        })
+    }
+
+    #[gpui::test]
+    async fn test_hard_reset_requires_branch_name(cx: &mut TestAppContext) {
+        init_test(cx);
+
+        let fs = FakeFs::new(cx.executor());
+        fs.insert_tree(path!("/project"), json!({ ".git": {}, "a.txt": "a" }))
+            .await;
+        let dot_git = Path::new(path!("/project/.git"));
+        fs.with_git_state(dot_git, true, |state| {
+            state.branches.insert("main".to_string());
+            state.current_branch_name = Some("main".to_string());
+        })
+        .unwrap();
+        let project = Project::test(fs.clone(), [Path::new(path!("/project"))], cx).await;
+        let multi_workspace =
+            cx.add_window(|window, cx| MultiWorkspace::test_new(project.clone(), window, cx));
+        let cx = &mut VisualTestContext::from_window(*multi_workspace, cx);
+        let workspace = multi_workspace
+            .update(cx, |workspace, _, _| workspace.workspace().clone())
+            .unwrap();
+        cx.run_until_parked();
+
+        let repository =
+            project.read_with(cx, |project, cx| project.active_repository(cx).unwrap());
+        let open_modal = |cx: &mut VisualTestContext| {
+            workspace.update_in(cx, |workspace, window, cx| {
+                ResetModal::toggle(workspace, repository.clone(), "bbb".to_string(), window, cx);
+                workspace.active_modal::<ResetModal>(cx).unwrap()
+            })
+        };
+        let resets = |cx: &mut VisualTestContext| {
+            cx.run_until_parked();
+            fs.with_git_state(dot_git, false, |state| state.resets.clone())
+                .unwrap()
+        };
+
+        let modal = open_modal(cx);
+        modal.update_in(cx, |modal, window, cx| {
+            assert_eq!(modal.branch_name, "main");
+            modal.set_mode(ResetMode::Hard, window, cx);
+            modal.confirm(&Confirm, window, cx);
+            modal
+                .confirmation_editor
+                .update(cx, |editor, cx| editor.set_text("mai", window, cx));
+            modal.confirm(&Confirm, window, cx);
+        });
+        assert!(resets(cx).is_empty());
+
+        modal.update_in(cx, |modal, window, cx| {
+            modal
+                .confirmation_editor
+                .update(cx, |editor, cx| editor.set_text("main", window, cx));
+            modal.confirm(&Confirm, window, cx);
+        });
+        assert_eq!(resets(cx), [("bbb".to_string(), ResetMode::Hard)]);
+        workspace.read_with(cx, |workspace, cx| {
+            assert!(workspace.active_modal::<ResetModal>(cx).is_none());
+        });
+
+        let modal = open_modal(cx);
+        modal.update_in(cx, |modal, window, cx| {
+            modal.set_mode(ResetMode::Soft, window, cx);
+            modal.confirm(&Confirm, window, cx);
+        });
+        assert_eq!(
+            resets(cx),
+            [
+                ("bbb".to_string(), ResetMode::Hard),
+                ("bbb".to_string(), ResetMode::Soft)
+            ]
+        );
+    }
+}
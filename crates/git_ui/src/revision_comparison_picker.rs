@@ -0,0 +1,775 @@
+use std::sync::Arc;
+
+use anyhow::{Context as _, Result};
+use editor::Editor;
+use fuzzy::StringMatchCandidate;
+use git::repository::RepoPath;
+use gpui::{
+    App, AsyncWindowContext, DismissEvent, Entity, EventEmitter, FocusHandle, Focusable,
+    PromptLevel, Task, WeakEntity,
+};
+use language::Buffer;
+use picker::{Picker, PickerDelegate};
+use project::{ProjectPath, git_store::Repository};
+use ui::{HighlightedLabel, ListItem, ListItemSpacing, prelude::*};
+use util::ResultExt;
+use workspace::{ModalView, SaveIntent, Workspace, notifications::DetachAndPromptErr};
+
+use crate::file_diff_view::FileDiffView;
+
+const FILE_HISTORY_LIMIT: usize = 50;
+
+pub fn register(workspace: &mut Workspace) {
+    workspace.register_action(open);
+    workspace.register_action(|workspace, _: &git::RestoreFromRevision, window, cx| {
+        let Some(active_buffer) = active_singleton_buffer(workspace, cx) else {
+            return;
+        };
+        toggle(
+            workspace,
+            active_buffer,
+            RevisionPickerMode::Restore,
+            window,
+            cx,
+        );
+    });
+}
+
+pub fn open(
+    workspace: &mut Workspace,
+    _: &git::CompareWithRevision,
+    window: &mut Window,
+    cx: &mut Context<Workspace>,
+) {
+    let Some(active_buffer) = active_singleton_buffer(workspace, cx) else {
+        return;
+    };
+    toggle(
+        workspace,
+        active_buffer,
+        RevisionPickerMode::Compare,
+        window,
+        cx,
+    );
+}
+
+/// Opens the picker to restore the file at `project_path` to one of its revisions, such as for a
+/// file selected in the project panel.
+pub fn restore_from_revision(
+    workspace: &mut Workspace,
+    project_path: ProjectPath,
+    window: &mut Window,
+    cx: &mut Context<Workspace>,
+) {
+    let open_buffer = workspace
+        .project()
+        .update(cx, |project, cx| project.open_buffer(project_path, cx));
+    cx.spawn_in(window, async move |workspace, cx| {
+        let buffer = open_buffer.await?;
+        workspace.update_in(cx, |workspace, window, cx| {
+            toggle(workspace, buffer, RevisionPickerMode::Restore, window, cx);
+        })
+    })
+    .detach_and_prompt_err("Failed to open file", window, cx, |_, _, _| None);
+}
+
+fn active_singleton_buffer(workspace: &Workspace, cx: &App) -> Option<Entity<Buffer>> {
+    workspace
+        .active_item_as::<Editor>(cx)
+        .and_then(|editor| editor.read(cx).buffer().read(cx).as_singleton())
+}
+
+fn toggle(
+    workspace: &mut Workspace,
+    active_buffer: Entity<Buffer>,
+    mode: RevisionPickerMode,
+    window: &mut Window,
+    cx: &mut Context<Workspace>,
+) {
+    let buffer_id = active_buffer.read(cx).remote_id();
+    let Some((repository, repo_path)) = workspace
+        .project()
+        .read(cx)
+        .git_store()
+        .read(cx)
+        .repository_and_path_for_buffer_id(buffer_id, cx)
+    else {
+        return;
+    };
+    let weak_workspace = workspace.weak_handle();
+    workspace.toggle_modal(window, cx, |window, cx| {
+        RevisionComparisonPicker::new(
+            active_buffer,
+            repository,
+            repo_path,
+            mode,
+            weak_workspace,
+            window,
+            cx,
+        )
+    });
+}
+
+/// What happens to the revision picked in a [`RevisionComparisonPicker`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RevisionPickerMode {
+    /// Opens a diff between the file at the revision and its current content.
+    Compare,
+    /// Previews the diff, then restores the file to the revision or opens the revision's content
+    /// in a new buffer.
+    Restore,
+}
+
+pub struct RevisionComparisonPicker {
+    picker: Entity<Picker<RevisionComparisonDelegate>>,
+    _load_revisions: Task<()>,
+}
+
+impl RevisionComparisonPicker {
+    fn new(
+        active_buffer: Entity<Buffer>,
+        repository: Entity<Repository>,
+        repo_path: RepoPath,
+        mode: RevisionPickerMode,
+        workspace: WeakEntity<Workspace>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Self {
+        // Restoring is scoped to the commits that touched the file.
+        let branches_request = (mode == RevisionPickerMode::Compare)
+            .then(|| repository.update(cx, |repository, _| repository.branches()));
+        let tags_request = (mode == RevisionPickerMode::Compare)
+            .then(|| repository.update(cx, |repository, _| repository.tags()));
+        let file_history_request = repository.update(cx, |repository, _| {
+            repository.file_history_paginated(repo_path.clone(), 0, Some(FILE_HISTORY_LIMIT))
+        });
+
+        let delegate = RevisionComparisonDelegate {
+            picker: cx.entity().downgrade(),
+            workspace,
+            repository,
+            repo_path,
+            active_buffer,
+            mode,
+            all_revisions: Vec::new(),
+            matches: Vec::new(),
+            selected_index: 0,
+        };
+        let picker = cx.new(|cx| {
+            Picker::uniform_list(delegate, window, cx)
+                .max_height(Some(rems(20.).into()))
+                .show_scrollbar(true)
+        });
+
+        let load_revisions =
+            cx.spawn_in(window, async move |this, cx| {
+                let mut revisions = Vec::new();
+                if mode == RevisionPickerMode::Compare {
+                    revisions.push(RevisionCandidate {
+                        revision: "HEAD".into(),
+                        detail: None,
+                    });
+                }
+                if let Some(file_history) = file_history_request
+                    .await
+                    .ok()
+                    .and_then(|result| result.log_err())
+                {
+                    revisions.extend(file_history.entries.into_iter().map(|entry| {
+                        RevisionCandidate {
+                            revision: entry.sha,
+                            detail: Some(entry.subject),
+                        }
+                    }));
+                }
+                if let Some(branches_request) = branches_request
+                    && let Some(branches) = branches_request
+                        .await
+                        .ok()
+                        .and_then(|result| result.log_err())
+                {
+                    revisions.extend(branches.into_iter().map(|branch| {
+                        RevisionCandidate {
+                            revision: branch.name().to_string().into(),
+                            detail: branch
+                                .most_recent_commit
+                                .map(|commit_summary| commit_summary.subject),
+                        }
+                    }));
+                }
+                if let Some(tags_request) = tags_request
+                    && let Some(tags) = tags_request.await.ok().and_then(|result| result.log_err())
+                {
+                    revisions.extend(tags.into_iter().map(|tag| RevisionCandidate {
+                        revision: tag,
+                        detail: Some("Tag".into()),
+                    }));
+                }
+
+                this.update_in(cx, |this, window, cx| {
+                    this.picker.update(cx, |picker, cx| {
+                        picker.delegate.all_revisions = revisions;
+                        picker.refresh(window, cx);
+                    });
+                })
+                .log_err();
+            });
+
+        Self {
+            picker,
+            _load_revisions: load_revisions,
+        }
+    }
+}
+
+impl EventEmitter<DismissEvent> for RevisionComparisonPicker {}
+
+impl Focusable for RevisionComparisonPicker {
+    fn focus_handle(&self, cx: &App) -> FocusHandle {
+        self.picker.focus_handle(cx)
+    }
+}
+
+impl ModalView for RevisionComparisonPicker {}
+
+impl Render for RevisionComparisonPicker {
+    fn render(&mut self, _window: &mut Window, _cx: &mut Context<Self>) -> impl IntoElement {
+        v_flex()
+            .key_context("RevisionComparisonPicker")
+            .w(rems(34.))
+            .child(self.picker.clone())
+    }
+}
+
+#[derive(Clone)]
+struct RevisionCandidate {
+    /// A commit SHA, branch name, or any other revision understood by git.
+    revision: SharedString,
+    detail: Option<SharedString>,
+}
+
+impl RevisionCandidate {
+    fn label(&self) -> SharedString {
+        if is_full_sha(&self.revision) {
+            self.revision
+                .get(..git::SHORT_SHA_LENGTH)
+                .unwrap_or(&self.revision)
+                .to_string()
+                .into()
+        } else {
+            self.revision.clone()
+        }
+    }
+}
+
+fn is_full_sha(revision: &str) -> bool {
+    revision.len() == 40 && revision.chars().all(|char| char.is_ascii_hexdigit())
+}
+
+struct RevisionMatch {
+    candidate: RevisionCandidate,
+    positions: Vec<usize>,
+}
+
+pub struct RevisionComparisonDelegate {
+    picker: WeakEntity<RevisionComparisonPicker>,
+    workspace: WeakEntity<Workspace>,
+    repository: Entity<Repository>,
+    repo_path: RepoPath,
+    active_buffer: Entity<Buffer>,
+    mode: RevisionPickerMode,
+    all_revisions: Vec<RevisionCandidate>,
+    matches: Vec<RevisionMatch>,
+    selected_index: usize,
+}
+
+impl PickerDelegate for RevisionComparisonDelegate {
+    type ListItem = ListItem;
+
+    fn match_count(&self) -> usize {
+        self.matches.len()
+    }
+
+    fn selected_index(&self) -> usize {
+        self.selected_index
+    }
+
+    fn set_selected_index(
+        &mut self,
+        ix: usize,
+        _window: &mut Window,
+        cx: &mut Context<Picker<Self>>,
+    ) {
+        self.selected_index = ix;
+        cx.notify();
+    }
+
+    fn placeholder_text(&self, _window: &mut Window, _cx: &mut App) -> Arc<str> {
+        match self.mode {
+            RevisionPickerMode::Compare => "Compare with revision…".into(),
+            RevisionPickerMode::Restore => "Restore from revision…".into(),
+        }
+    }
+
+    fn no_matches_text(&self, _window: &mut Window, _cx: &mut App) -> Option<SharedString> {
+        Some("No revisions found".into())
+    }
+
+    fn update_matches(
+        &mut self,
+        query: String,
+        window: &mut Window,
+        cx: &mut Context<Picker<Self>>,
+    ) -> Task<()> {
+        let all_revisions = self.all_revisions.clone();
+        cx.spawn_in(window, async move |picker, cx| {
+            let mut matches: Vec<RevisionMatch> = if query.is_empty() {
+                all_revisions
+                    .into_iter()
+                    .map(|candidate| RevisionMatch {
+                        candidate,
+                        positions: Vec::new(),
+                    })
+                    .collect()
+            } else {
+                let candidates = all_revisions
+                    .iter()
+                    .enumerate()
+                    .map(|(ix, candidate)| StringMatchCandidate::new(ix, &candidate.label()))
+                    .collect::<Vec<_>>();
+                fuzzy::match_strings(
+                    &candidates,
+                    &query,
+                    true,
+                    true,
+                    10000,
+                    &Default::default(),
+                    cx.background_executor().clone(),
+                )
+                .await
+                .into_iter()
+                .filter_map(|string_match| {
+                    Some(RevisionMatch {
+                        candidate: all_revisions.get(string_match.candidate_id)?.clone(),
+                        positions: string_match.positions,
+                    })
+                })
+                .collect()
+            };
+
+            // Tags and commits that didn't touch the file aren't listed, so let the query itself
+            // be used as a revision.
+            let query = query.trim();
+            if matches.is_empty() && !query.is_empty() {
+                matches.push(RevisionMatch {
+                    candidate: RevisionCandidate {
+                        revision: query.to_string().into(),
+                        detail: None,
+                    },
+                    positions: Vec::new(),
+                });
+            }
+
+            picker
+                .update(cx, |picker, cx| {
+                    let delegate = &mut picker.delegate;
+                    delegate.matches = matches;
+                    delegate.selected_index = delegate
+                        .selected_index
+                        .min(delegate.matches.len().saturating_sub(1));
+                    cx.notify();
+                })
+                .log_err();
+        })
+    }
+
+    fn confirm(&mut self, _secondary: bool, window: &mut Window, cx: &mut Context<Picker<Self>>) {
+        let Some(selected) = self.matches.get(self.selected_index) else {
+            return;
+        };
+        let revision = selected.candidate.revision.clone();
+        let label = selected.candidate.label();
+        let mode = self.mode;
+        let repo_path = self.repo_path.clone();
+        let active_buffer = self.active_buffer.clone();
+        let workspace = self.workspace.clone();
+        let language = active_buffer.read(cx).language().cloned();
+        let load_text = self.repository.update(cx, |repository, cx| {
+            repository.load_text_at_revision(revision.to_string(), repo_path.clone(), cx)
+        });
+        let error_title = match mode {
+            RevisionPickerMode::Compare => "Failed to open diff",
+            RevisionPickerMode::Restore => "Failed to restore file",
+        };
+        window
+            .spawn(cx, async move |cx| {
+                let text = load_text
+                    .await?
+                    .with_context(|| format!("file does not exist at revision {revision}"))?;
+                let revision_buffer = cx.new(|cx| {
+                    let mut buffer = Buffer::local(text, cx);
+                    buffer.set_language(language, cx);
+                    buffer
+                });
+                let diff_view = cx
+                    .update(|window, cx| {
+                        FileDiffView::open_at_revision(
+                            revision_buffer.clone(),
+                            label.clone(),
+                            active_buffer.clone(),
+                            workspace.clone(),
+                            window,
+                            cx,
+                        )
+                    })?
+                    .await?;
+                if mode == RevisionPickerMode::Restore {
+                    confirm_restore(
+                        label,
+                        revision_buffer,
+                        active_buffer,
+                        repo_path,
+                        diff_view,
+                        workspace,
+                        cx,
+                    )
+                    .await?;
+                }
+                anyhow::Ok(())
+            })
+            .detach_and_prompt_err(error_title, window, cx, |_, _, _| None);
+        self.dismissed(window, cx);
+    }
+
+    fn dismissed(&mut self, _window: &mut Window, cx: &mut Context<Picker<Self>>) {
+        self.picker
+            .update(cx, |_, cx| cx.emit(DismissEvent))
+            .log_err();
+    }
+
+    fn render_match(
+        &self,
+        ix: usize,
+        selected: bool,
+        _window: &mut Window,
+        _cx: &mut Context<Picker<Self>>,
+    ) -> Option<Self::ListItem> {
+        let revision_match = self.matches.get(ix)?;
+        Some(
+            ListItem::new(ix)
+                .inset(true)
+                .spacing(ListItemSpacing::Sparse)
+                .toggle_state(selected)
+                .child(
+                    h_flex()
+                        .gap_2()
+                        .child(HighlightedLabel::new(
+                            revision_match.candidate.label(),
+                            revision_match.positions.clone(),
+                        ))
+                        .when_some(revision_match.candidate.detail.clone(), |this, detail| {
+                            this.child(Label::new(detail).color(Color::Muted).truncate())
+                        }),
+                ),
+        )
+    }
+}
+
+/// Asks, over the diff previewing the revision, whether to restore the file to it or to open the
+/// revision's content in a new untitled buffer instead. The preview is closed either way.
+async fn confirm_restore(
+    label: SharedString,
+    revision_buffer: Entity<Buffer>,
+    active_buffer: Entity<Buffer>,
+    repo_path: RepoPath,
+    diff_view: Entity<FileDiffView>,
+    workspace: WeakEntity<Workspace>,
+    cx: &mut AsyncWindowContext,
+) -> Result<()> {
+    let file_name = repo_path.file_name().unwrap_or_default().to_string();
+    let detail = if active_buffer.read_with(cx, |buffer, _| buffer.is_dirty()) {
+        "Its uncommitted changes and unsaved edits will be lost."
+    } else {
+        "Its uncommitted changes will be lost."
+    };
+    let answer = cx
+        .update(|window, cx| {
+            window.prompt(
+                PromptLevel::Warning,
+                &format!("Restore {file_name} to {label}?"),
+                Some(detail),
+                &["Restore", "Open in New Buffer", "Cancel"],
+                cx,
+            )
+        })?
+        .await;
+
+    match answer {
+        Ok(0) => {
+            // Only the file in the working tree is restored, leaving the index as it was, like
+            // `git restore --worktree` does.
+            let project = workspace.read_with(cx, |workspace, _| workspace.project().clone())?;
+            let text = revision_buffer.read_with(cx, |buffer, _| buffer.text());
+            let diff = active_buffer
+                .read_with(cx, |buffer, cx| buffer.diff(text, cx))
+                .await;
+            active_buffer.update(cx, |buffer, cx| {
+                buffer.apply_diff(diff, cx);
+            });
+            project
+                .update(cx, |project, cx| project.save_buffer(active_buffer, cx))
+                .await?;
+        }
+        Ok(1) => {
+            let project = workspace.read_with(cx, |workspace, _| workspace.project().clone())?;
+            let language = revision_buffer.read_with(cx, |buffer, _| buffer.language().cloned());
+            let new_buffer = project
+                .update(cx, |project, cx| project.create_buffer(language, true, cx))
+                .await?;
+            let text = revision_buffer.read_with(cx, |buffer, _| buffer.text());
+            new_buffer.update(cx, |buffer, cx| buffer.set_text(text, cx));
+            workspace.update_in(cx, |workspace, window, cx| {
+                let editor = cx.new(|cx| Editor::for_buffer(new_buffer, Some(project), window, cx));
+                workspace.add_item_to_active_pane(Box::new(editor), None, true, window, cx);
+            })?;
+        }
+        _ => {}
+    }
+
+    workspace
+        .update_in(cx, |workspace, window, cx| {
+            let diff_view_id = diff_view.entity_id();
+            let pane = workspace
+                .pane_for_item_id(diff_view_id)
+                .unwrap_or_else(|| workspace.active_pane().clone());
+            pane.update(cx, |pane, cx| {
+                pane.close_item_by_id(diff_view_id, SaveIntent::Skip, window, cx)
+            })
+        })?
+        .await?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use gpui::{TestAppContext, VisualTestContext};
+    use project::{FakeFs, Fs, Project};
+    use serde_json::json;
+    use settings::SettingsStore;
+    use util::path;
+    use workspace::{Item as _, MultiWorkspace};
+
+    fn init_test(cx: &mut TestAppContext) {
+        cx.update(|cx| {
+            let settings_store = SettingsStore::test(cx);
+            cx.set_global(settings_store);
+            theme::init(theme::LoadThemes::JustBase, cx);
+            editor::init(cx);
+        });
+    }
+
+    #[gpui::test]
+    async fn test_compare_with_head(cx: &mut TestAppContext) {
+        init_test(cx);
+
+        let fs = FakeFs::new(cx.executor());
+        fs.insert_tree(
+            path!("/project"),
+            json!({
+                ".git": {},
+                "file.rs": "fn main() {\n    changed();\n}\n",
+            }),
+        )
+        .await;
+        fs.set_head_for_repo(
+            path!("/project/.git").as_ref(),
+            &[("file.rs", "fn main() {}\n".into())],
+            "abc1234",
+        );
+        fs.with_git_state(path!("/project/.git").as_ref(), false, |state| {
+            state.tags = vec!["v1.0".to_string()];
+        })
+        .unwrap();
+        let project = Project::test(fs, [path!("/project").as_ref()], cx).await;
+        let multi_workspace =
+            cx.add_window(|window, cx| MultiWorkspace::test_new(project.clone(), window, cx));
+        let cx = &mut VisualTestContext::from_window(*multi_workspace, cx);
+        let workspace = multi_workspace
+            .update(cx, |multi_workspace, _, _| {
+                multi_workspace.workspace().clone()
+            })
+            .unwrap();
+        cx.run_until_parked();
+
+        let active_buffer = project
+            .update(cx, |project, cx| {
+                project.open_local_buffer(path!("/project/file.rs"), cx)
+            })
+            .await
+            .unwrap();
+        let (repository, repo_path) = project.read_with(cx, |project, cx| {
+            project
+                .git_store()
+                .read(cx)
+                .repository_and_path_for_buffer_id(active_buffer.read(cx).remote_id(), cx)
+                .unwrap()
+        });
+
+        let revision_picker = workspace.update_in(cx, |workspace, window, cx| {
+            let weak_workspace = workspace.weak_handle();
+            workspace.toggle_modal(window, cx, |window, cx| {
+                RevisionComparisonPicker::new(
+                    active_buffer,
+                    repository,
+                    repo_path,
+                    RevisionPickerMode::Compare,
+                    weak_workspace,
+                    window,
+                    cx,
+                )
+            });
+            workspace
+                .active_modal::<RevisionComparisonPicker>(cx)
+                .unwrap()
+        });
+        cx.run_until_parked();
+        let picker = revision_picker.read_with(cx, |this, _| this.picker.clone());
+
+        picker
+            .update_in(cx, |picker, window, cx| {
+                picker
+                    .delegate
+                    .update_matches("v1.0".to_string(), window, cx)
+            })
+            .await;
+        picker.read_with(cx, |picker, _| {
+            assert_eq!(picker.delegate.matches[0].candidate.revision, "v1.0");
+        });
+
+        picker
+            .update_in(cx, |picker, window, cx| {
+                picker
+                    .delegate
+                    .update_matches("HEAD".to_string(), window, cx)
+            })
+            .await;
+        picker.update_in(cx, |picker, window, cx| {
+            assert_eq!(picker.delegate.matches[0].candidate.revision, "HEAD");
+            picker.delegate.set_selected_index(0, window, cx);
+            picker.delegate.confirm(false, window, cx);
+        });
+        cx.run_until_parked();
+
+        workspace.read_with(cx, |workspace, cx| {
+            assert!(
+                workspace
+                    .active_modal::<RevisionComparisonPicker>(cx)
+                    .is_none()
+            );
+            let diff_view = workspace.active_item_as::<FileDiffView>(cx).unwrap();
+            assert_eq!(
+                diff_view.read(cx).tab_content_text(0, cx),
+                "file.rs @ HEAD ↔ file.rs"
+            );
+        });
+    }
+
+    #[gpui::test]
+    async fn test_restore_from_revision(cx: &mut TestAppContext) {
+        init_test(cx);
+
+        let fs = FakeFs::new(cx.executor());
+        fs.insert_tree(
+            path!("/project"),
+            json!({
+                ".git": {},
+                "file.rs": "fn main() {\n    changed();\n}\n",
+            }),
+        )
+        .await;
+        fs.with_git_state(path!("/project/.git").as_ref(), false, |state| {
+            state.revision_contents.insert(
+                ("abc1234".to_string(), RepoPath::new("file.rs").unwrap()),
+                "fn main() {\n    old();\n}\n".to_string(),
+            );
+        })
+        .unwrap();
+        let project = Project::test(fs.clone(), [path!("/project").as_ref()], cx).await;
+        let multi_workspace =
+            cx.add_window(|window, cx| MultiWorkspace::test_new(project.clone(), window, cx));
+        let cx = &mut VisualTestContext::from_window(*multi_workspace, cx);
+        let workspace = multi_workspace
+            .update(cx, |multi_workspace, _, _| {
+                multi_workspace.workspace().clone()
+            })
+            .unwrap();
+        cx.run_until_parked();
+
+        let worktree_id = project.read_with(cx, |project, cx| {
+            project.worktrees(cx).next().unwrap().read(cx).id()
+        });
+        let project_path = ProjectPath {
+            worktree_id,
+            path: util::rel_path::rel_path("file.rs").into_arc(),
+        };
+
+        let pick_revision = |revision: &str, cx: &mut VisualTestContext| {
+            workspace.update_in(cx, |workspace, window, cx| {
+                restore_from_revision(workspace, project_path.clone(), window, cx)
+            });
+            cx.run_until_parked();
+            let picker = workspace.read_with(cx, |workspace, cx| {
+                workspace
+                    .active_modal::<RevisionComparisonPicker>(cx)
+                    .unwrap()
+                    .read(cx)
+                    .picker
+                    .clone()
+            });
+            let update_matches = picker.update_in(cx, |picker, window, cx| {
+                picker
+                    .delegate
+                    .update_matches(revision.to_string(), window, cx)
+            });
+            (picker, update_matches)
+        };
+
+        let (picker, update_matches) = pick_revision("abc1234", cx);
+        update_matches.await;
+        picker.update_in(cx, |picker, window, cx| {
+            assert_eq!(picker.delegate.matches[0].candidate.revision, "abc1234");
+            picker.delegate.confirm(false, window, cx);
+        });
+        cx.run_until_parked();
+        workspace.read_with(cx, |workspace, cx| {
+            assert!(workspace.active_item_as::<FileDiffView>(cx).is_some());
+        });
+        cx.simulate_prompt_answer("Restore");
+        cx.run_until_parked();
+
+        assert_eq!(
+            fs.load(path!("/project/file.rs").as_ref()).await.unwrap(),
+            "fn main() {\n    old();\n}\n"
+        );
+        workspace.read_with(cx, |workspace, cx| {
+            assert!(workspace.active_item_as::<FileDiffView>(cx).is_none());
+        });
+
+        let (picker, update_matches) = pick_revision("abc1234", cx);
+        update_matches.await;
+        picker.update_in(cx, |picker, window, cx| {
+            picker.delegate.confirm(false, window, cx);
+        });
+        cx.run_until_parked();
+        cx.simulate_prompt_answer("Open in New Buffer");
+        cx.run_until_parked();
+
+        workspace.read_with(cx, |workspace, cx| {
+            let editor = workspace.active_item_as::<Editor>(cx).unwrap();
+            let buffer = editor.read(cx).buffer().read(cx).as_singleton().unwrap();
+            assert!(buffer.read(cx).file().is_none());
+            assert_eq!(buffer.read(cx).text(), "fn main() {\n    old();\n}\n");
+        });
+    }
+}
@@ -0,0 +1,278 @@
+use editor::Editor;
+use gpui::{
+    App, Context, DismissEvent, Entity, EventEmitter, FocusHandle, Focusable, InteractiveElement,
+    IntoElement, ParentElement, Render, Styled, WeakEntity, Window, rems,
+};
+use menu::Cancel;
+use project::git_store::Repository;
+use ui::{Checkbox, prelude::*};
+use workspace::{ModalView, Workspace};
+
+use crate::git_panel::show_error_toast;
+use crate::resolve_active_repository;
+
+pub fn register(workspace: &mut Workspace) {
+    workspace.register_action(|workspace, _: &git::ConfigureSparseCheckout, window, cx| {
+        let Some(repository) = resolve_active_repository(workspace, cx) else {
+            return;
+        };
+        if repository.read(cx).has_read_sparse_checkout() {
+            SparseCheckoutModal::toggle(workspace, repository, window, cx);
+            return;
+        }
+        // Until the sparse checkout is read, the modal can't tell whether it's enabled.
+        let reload = repository.update(cx, |repository, _| repository.reload_sparse_checkout());
+        cx.spawn_in(window, async move |workspace, cx| {
+            reload.await??;
+            workspace.update_in(cx, |workspace, window, cx| {
+                SparseCheckoutModal::toggle(workspace, repository, window, cx)
+            })
+        })
+        .detach_and_log_err(cx);
+    });
+}
+
+/// Edits the patterns that limit the working tree of a repository, one per line.
+pub struct SparseCheckoutModal {
+    repository: Entity<Repository>,
+    workspace: WeakEntity<Workspace>,
+    patterns_editor: Entity<Editor>,
+    cone: ToggleState,
+    is_enabled: bool,
+}
+
+impl SparseCheckoutModal {
+    fn toggle(
+        workspace: &mut Workspace,
+        repository: Entity<Repository>,
+        window: &mut Window,
+        cx: &mut Context<Workspace>,
+    ) {
+        let weak_workspace = workspace.weak_handle();
+        workspace.toggle_modal(window, cx, |window, cx| {
+            Self::new(repository, weak_workspace, window, cx)
+        });
+    }
+
+    fn new(
+        repository: Entity<Repository>,
+        workspace: WeakEntity<Workspace>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Self {
+        let sparse_checkout = repository.read(cx).sparse_checkout().cloned();
+        let patterns_editor = cx.new(|cx| {
+            let mut editor = Editor::auto_height(4, 16, window, cx);
+            editor.set_placeholder_text("One directory per line, such as crates/git", window, cx);
+            if let Some(sparse_checkout) = &sparse_checkout {
+                editor.set_text(sparse_checkout.patterns.join("\n"), window, cx);
+            }
+            editor
+        });
+        Self {
+            repository,
+            workspace,
+            patterns_editor,
+            cone: sparse_checkout
+                .as_ref()
+                .is_none_or(|sparse_checkout| sparse_checkout.cone)
+                .into(),
+            is_enabled: sparse_checkout.is_some(),
+        }
+    }
+
+    fn cancel(&mut self, _: &Cancel, _window: &mut Window, cx: &mut Context<Self>) {
+        cx.emit(DismissEvent);
+    }
+
+    fn apply(&mut self, cx: &mut Context<Self>) {
+        let patterns = self
+            .patterns_editor
+            .read(cx)
+            .text(cx)
+            .lines()
+            .map(str::trim)
+            .filter(|pattern| !pattern.is_empty() && !pattern.starts_with('#'))
+            .map(ToString::to_string)
+            .collect();
+        self.set_sparse_checkout(Some(patterns), cx);
+    }
+
+    fn set_sparse_checkout(&mut self, patterns: Option<Vec<String>>, cx: &mut Context<Self>) {
+        let cone = self.cone.selected();
+        let update = self.repository.update(cx, |repository, _| {
+            repository.set_sparse_checkout(cone, patterns)
+        });
+        let workspace = self.workspace.clone();
+        cx.spawn(async move |_, cx| {
+            if let Ok(Err(error)) = update.await
+                && let Some(workspace) = workspace.upgrade()
+            {
+                cx.update(|cx| show_error_toast(workspace, "sparse-checkout", error, cx));
+            }
+        })
+        .detach();
+        cx.emit(DismissEvent);
+    }
+}
+
+impl EventEmitter<DismissEvent> for SparseCheckoutModal {}
+impl ModalView for SparseCheckoutModal {}
+impl Focusable for SparseCheckoutModal {
+    fn focus_handle(&self, cx: &App) -> FocusHandle {
+        self.patterns_editor.focus_handle(cx)
+    }
+}
+
+impl Render for SparseCheckoutModal {
+    fn render(&mut self, _: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let excluded_count = self
+            .repository
+            .read(cx)
+            .sparse_checkout()
+            .map_or(0, |sparse_checkout| {
+                sparse_checkout.excluded_directories.len()
+            });
+
+        v_flex()
+            .key_context("SparseCheckoutModal")
+            .on_action(cx.listener(Self::cancel))
+            .elevation_2(cx)
+            .w(rems(34.))
+            .child(
+                h_flex()
+                    .px_3()
+                    .pt_2()
+                    .pb_1()
+                    .w_full()
+                    .gap_1p5()
+                    .child(Icon::new(IconName::Folder).size(IconSize::XSmall))
+                    .child(Headline::new("Sparse Checkout").size(HeadlineSize::XSmall)),
+            )
+            .child(
+                v_flex()
+                    .px_3()
+                    .pb_3()
+                    .w_full()
+                    .gap_2()
+                    .child(self.patterns_editor.clone())
+                    .child(
+                        Checkbox::new("sparse-checkout-cone", self.cone)
+                            .label("Check out whole directories (cone mode)")
+                            .on_click(cx.listener(|this, state: &ToggleState, _, cx| {
+                                this.cone = *state;
+                                cx.notify();
+                            })),
+                    )
+                    .when(excluded_count > 0, |this| {
+                        this.child(
+                            Label::new(format!("{excluded_count} directories aren't checked out"))
+                                .size(LabelSize::Small)
+                                .color(Color::Muted),
+                        )
+                    })
+                    .child(
+                        h_flex()
+                            .gap_1()
+                            .justify_end()
+                            .when(self.is_enabled, |this| {
+                                this.child(
+                                    Button::new("disable-sparse-checkout", "Check Out Everything")
+                                        .on_click(cx.listener(|this, _, _, cx| {
+                                            this.set_sparse_checkout(None, cx)
+                                        })),
+                                )
+                            })
+                            .child(
+                                Button::new("apply-sparse-checkout", "Apply")
+                                    .style(ButtonStyle::Filled)
+                                    .on_click(cx.listener(|this, _, _, cx| this.apply(cx))),
+                            ),
+                    ),
+            )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use super::*;
+    use git::{repository::RepoPath, sparse_checkout::SparseCheckout};
+    use gpui::{TestAppContext, VisualTestContext};
+    use project::{FakeFs, Project};
+    use serde_json::json;
+    use settings::SettingsStore;
+    use util::path;
+    use workspace::MultiWorkspace;
+
+    fn init_test(cx: &mut TestAppContext) {
+        cx.update(|cx| {
+            let settings_store = SettingsStore::test(cx);
+            cx.set_global(settings_store);
+
+            theme::init(theme::LoadThemes::JustBase, cx);
+            editor::init(cx);
+        })
+    }
+
+    #[gpui::test]
+    async fn test_edit_sparse_checkout_patterns(cx: &mut TestAppContext) {
+        init_test(cx);
+
+        let fs = FakeFs::new(cx.executor());
+        fs.insert_tree(path!("/project"), json!({ ".git": {}, "a.txt": "a" }))
+            .await;
+        let dot_git = Path::new(path!("/project/.git"));
+        fs.with_git_state(dot_git, true, |state| {
+            state.branches.insert("main".to_string());
+            state.current_branch_name = Some("main".to_string());
+            state.sparse_checkout = Some(SparseCheckout {
+                cone: true,
+                patterns: vec!["crates/git".to_string()],
+                excluded_directories: vec![
+                    RepoPath::new("crates/editor").unwrap(),
+                    RepoPath::new("docs").unwrap(),
+                ],
+            });
+        })
+        .unwrap();
+        let project = Project::test(fs.clone(), [Path::new(path!("/project"))], cx).await;
+        let multi_workspace =
+            cx.add_window(|window, cx| MultiWorkspace::test_new(project.clone(), window, cx));
+        let cx = &mut VisualTestContext::from_window(*multi_workspace, cx);
+        let workspace = multi_workspace
+            .update(cx, |workspace, _, _| workspace.workspace().clone())
+            .unwrap();
+        cx.run_until_parked();
+
+        workspace.update_in(cx, |workspace, window, cx| {
+            window.dispatch_action(git::ConfigureSparseCheckout.boxed_clone(), cx);
+            workspace.active_modal::<SparseCheckoutModal>(cx).unwrap();
+        });
+        cx.run_until_parked();
+        let modal = workspace.update(cx, |workspace, cx| {
+            workspace.active_modal::<SparseCheckoutModal>(cx).unwrap()
+        });
+        modal.update_in(cx, |modal, window, cx| {
+            assert_eq!(modal.patterns_editor.read(cx).text(cx), "crates/git");
+            modal.patterns_editor.update(cx, |editor, cx| {
+                editor.set_text("crates/git\n# docs are big\ndocs\n", window, cx)
+            });
+            modal.apply(cx);
+        });
+        cx.run_until_parked();
+
+        let sparse_checkout = fs
+            .with_git_state(dot_git, false, |state| state.sparse_checkout.clone())
+            .unwrap();
+        assert_eq!(
+            sparse_checkout,
+            Some(SparseCheckout {
+                cone: true,
+                patterns: vec!["crates/git".to_string(), "docs".to_string()],
+                excluded_directories: vec![RepoPath::new("crates/editor").unwrap()],
+            })
+        );
+    }
+}
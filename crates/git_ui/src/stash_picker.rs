@@ -1,23 +1,38 @@
+use collections::HashMap;
+use editor::Editor;
 use fuzzy::StringMatchCandidate;
 
+use git::Oid;
+use git::repository::{RepoPath, ResetMode, STASH_APPLY_CONFLICTS, StashOptions};
 use git::stash::StashEntry;
+use git::status::DiffTreeType;
 use gpui::{
     Action, AnyElement, App, Context, DismissEvent, Entity, EventEmitter, FocusHandle, Focusable,
-    InteractiveElement, IntoElement, Modifiers, ModifiersChangedEvent, ParentElement, Render,
-    SharedString, Styled, Subscription, Task, WeakEntity, Window, actions, rems,
+    InteractiveElement, IntoElement, Modifiers, ModifiersChangedEvent, ParentElement, PromptLevel,
+    Render, SharedString, Styled, Subscription, Task, WeakEntity, Window, actions, rems,
 };
+use menu::{Cancel, Confirm};
 use picker::{Picker, PickerDelegate};
 use project::git_store::{Repository, RepositoryEvent};
 use std::sync::Arc;
 use time::{OffsetDateTime, UtcOffset};
 use time_format;
-use ui::{HighlightedLabel, KeyBinding, ListItem, ListItemSpacing, Tooltip, prelude::*};
+use ui::{
+    Checkbox, HighlightedLabel, KeyBinding, ListItem, ListItemSpacing, ToggleState, Tooltip,
+    prelude::*,
+};
 use util::ResultExt;
-use workspace::notifications::DetachAndPromptErr;
+use workspace::notifications::simple_message_notification::MessageNotification;
+use workspace::notifications::{DetachAndPromptErr, NotificationId};
 use workspace::{ModalView, Workspace};
 
 use crate::commit_view::CommitView;
+use crate::git_panel::{self, show_error_toast};
 use crate::stash_picker;
+use crate::three_way_diff_view::ThreeWayDiffView;
+
+/// The tree without any files, which git knows without it being stored.
+const EMPTY_TREE_SHA: &str = "4b825dc642cb6eb9a060e54bf8d69288fbee4904";
 
 actions!(
     stash_picker,
@@ -26,9 +41,108 @@ actions!(
         DropStashItem,
         /// Show the diff view of the selected stash entry.
         ShowStashItem,
+        /// Create a branch from the selected stash entry.
+        BranchFromStashItem,
     ]
 );
 
+pub fn register(workspace: &mut Workspace) {
+    workspace.register_action(|workspace, _: &git::StashWithMessage, window, cx| {
+        let Some(repository) = workspace.project().read(cx).active_repository(cx) else {
+            return;
+        };
+        let weak_workspace = workspace.weak_handle();
+        workspace.toggle_modal(window, cx, |window, cx| {
+            StashModal::new(repository, Vec::new(), weak_workspace, window, cx)
+        });
+    });
+}
+
+struct StashConflicts;
+
+/// Reports a failed stash apply or pop. A stash that conflicts is kept and its conflicts are left in
+/// the working tree, so instead of an error the user is offered to resolve them, or to undo the
+/// apply and create a branch from the stash where it applies cleanly.
+pub(crate) fn show_stash_apply_error(
+    workspace: Entity<Workspace>,
+    repository: Entity<Repository>,
+    stash_index: Option<usize>,
+    action: &'static str,
+    error: anyhow::Error,
+    cx: &mut App,
+) {
+    if !format!("{error:#}").contains(STASH_APPLY_CONFLICTS) {
+        show_error_toast(workspace, action, error, cx);
+        return;
+    }
+
+    let stash_name = format!("stash@{{{}}}", stash_index.unwrap_or(0));
+    let weak_workspace = workspace.downgrade();
+    workspace.update(cx, |workspace, cx| {
+        workspace.show_notification(NotificationId::unique::<StashConflicts>(), cx, |cx| {
+            cx.new(|cx| {
+                MessageNotification::new(
+                    format!("Applying {stash_name} stopped on conflicts. The stash was kept."),
+                    cx,
+                )
+                .primary_message("Resolve Conflicts")
+                .primary_on_click({
+                    let repository = repository.clone();
+                    let weak_workspace = weak_workspace.clone();
+                    move |window, cx| {
+                        let conflicted_path = repository
+                            .read(cx)
+                            .cached_status()
+                            .find(|entry| entry.status.is_conflicted())
+                            .map(|entry| entry.repo_path);
+                        match conflicted_path {
+                            Some(repo_path) => ThreeWayDiffView::open_conflict(
+                                repository.clone(),
+                                repo_path,
+                                weak_workspace.clone(),
+                                window,
+                                cx,
+                            )
+                            .detach_and_prompt_err(
+                                "Failed to resolve conflicts",
+                                window,
+                                cx,
+                                |_, _, _| None,
+                            ),
+                            None => window.dispatch_action(Box::new(git_panel::ToggleFocus), cx),
+                        }
+                        cx.emit(DismissEvent);
+                    }
+                })
+                .secondary_message("Create Branch from Stash…")
+                .secondary_on_click({
+                    let repository = repository.clone();
+                    let weak_workspace = weak_workspace.clone();
+                    move |window, cx| {
+                        let repository = repository.clone();
+                        let workspace = weak_workspace.clone();
+                        weak_workspace
+                            .update(cx, |workspace_handle, cx| {
+                                workspace_handle.toggle_modal(window, cx, |window, cx| {
+                                    StashBranchModal::new(
+                                        repository,
+                                        stash_index,
+                                        true,
+                                        workspace,
+                                        window,
+                                        cx,
+                                    )
+                                });
+                            })
+                            .ok();
+                        cx.emit(DismissEvent);
+                    }
+                })
+            })
+        });
+    });
+}
+
 pub fn open(
     workspace: &mut Workspace,
     _: &zed_actions::git::ViewStash,
@@ -101,6 +215,7 @@ impl StashList {
                         });
                         this.picker.update(cx, |this, cx| {
                             this.delegate.all_stash_entries = stash_entries;
+                            this.delegate.load_file_counts(cx);
                             this.refresh(window, cx);
                         });
                     }
@@ -116,6 +231,7 @@ impl StashList {
             this.update_in(cx, |this, window, cx| {
                 this.picker.update(cx, |picker, cx| {
                     picker.delegate.all_stash_entries = Some(stash_entries);
+                    picker.delegate.load_file_counts(cx);
                     picker.refresh(window, cx);
                 })
             })?;
@@ -187,6 +303,19 @@ impl StashList {
         cx.emit(DismissEvent);
     }
 
+    pub fn handle_branch_from_stash(
+        &mut self,
+        _: &BranchFromStashItem,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.picker.update(cx, |picker, cx| {
+            picker
+                .delegate
+                .branch_from_stash_at(picker.delegate.selected_index(), window, cx);
+        });
+    }
+
     pub fn handle_modifiers_changed(
         &mut self,
         ev: &ModifiersChangedEvent,
@@ -214,6 +343,7 @@ impl Render for StashList {
             .on_modifiers_changed(cx.listener(Self::handle_modifiers_changed))
             .on_action(cx.listener(Self::handle_drop_stash))
             .on_action(cx.listener(Self::handle_show_stash))
+            .on_action(cx.listener(Self::handle_branch_from_stash))
             .child(self.picker.clone())
     }
 }
@@ -235,6 +365,9 @@ pub struct StashListDelegate {
     modifiers: Modifiers,
     focus_handle: FocusHandle,
     timezone: UtcOffset,
+    /// The number of files each stash changed, loaded in the background.
+    file_counts: HashMap<Oid, usize>,
+    _file_counts_task: Task<()>,
 }
 
 impl StashListDelegate {
@@ -256,9 +389,63 @@ impl StashListDelegate {
             modifiers: Default::default(),
             focus_handle: cx.focus_handle(),
             timezone,
+            file_counts: HashMap::default(),
+            _file_counts_task: Task::ready(()),
         }
     }
 
+    fn load_file_counts(&mut self, cx: &mut Context<Picker<Self>>) {
+        let (Some(repo), Some(entries)) = (self.repo.clone(), self.all_stash_entries.as_ref())
+        else {
+            return;
+        };
+        let diffs = entries
+            .iter()
+            .filter(|entry| !self.file_counts.contains_key(&entry.oid))
+            .map(|entry| {
+                let oid = entry.oid;
+                // A stash commit's first parent is the commit HEAD was at when it was created.
+                let tracked = DiffTreeType::Since {
+                    base: format!("{oid}^1").into(),
+                    head: oid.to_string().into(),
+                };
+                // Untracked files are stashed in a third parent, which only has those files.
+                let untracked = DiffTreeType::Since {
+                    base: EMPTY_TREE_SHA.into(),
+                    head: format!("{oid}^3").into(),
+                };
+                let diffs = repo.update(cx, |repo, cx| {
+                    (repo.diff_tree(tracked, cx), repo.diff_tree(untracked, cx))
+                });
+                (oid, diffs)
+            })
+            .collect::<Vec<_>>();
+        if diffs.is_empty() {
+            return;
+        }
+
+        self._file_counts_task = cx.spawn(async move |picker, cx| {
+            for (oid, (tracked, untracked)) in diffs {
+                let Some(tracked) = tracked.await.ok().and_then(|diff| diff.log_err()) else {
+                    continue;
+                };
+                // Stashes without untracked files have no third parent.
+                let untracked_count = untracked
+                    .await
+                    .ok()
+                    .and_then(|diff| diff.ok())
+                    .map_or(0, |diff| diff.entries.len());
+                let count = tracked.entries.len() + untracked_count;
+                picker
+                    .update(cx, |picker, cx| {
+                        picker.delegate.file_counts.insert(oid, count);
+                        cx.notify();
+                    })
+                    .ok();
+            }
+        });
+    }
+
     fn format_message(ix: usize, message: &String) -> String {
         format!("#{}: {}", ix, message)
     }
@@ -283,7 +470,17 @@ impl StashListDelegate {
             return;
         };
 
+        let confirmation = window.prompt(
+            PromptLevel::Warning,
+            &format!("Drop stash@{{{stash_index}}}?"),
+            Some(&entry_match.entry.message),
+            &["Drop", "Cancel"],
+            cx,
+        );
         cx.spawn(async move |_, cx| {
+            if confirmation.await != Ok(0) {
+                return Ok(());
+            }
             repo.update(cx, |repo, cx| repo.stash_drop(Some(stash_index), cx))
                 .await??;
             Ok(())
@@ -313,36 +510,64 @@ impl StashListDelegate {
         );
     }
 
-    fn pop_stash(&self, stash_index: usize, window: &mut Window, cx: &mut Context<Picker<Self>>) {
+    fn branch_from_stash_at(&self, ix: usize, window: &mut Window, cx: &mut Context<Picker<Self>>) {
+        let Some(entry_match) = self.matches.get(ix) else {
+            return;
+        };
+        let stash_index = entry_match.entry.index;
         let Some(repo) = self.repo.clone() else {
             return;
         };
-
-        cx.spawn(async move |_, cx| {
-            repo.update(cx, |repo, cx| repo.stash_pop(Some(stash_index), cx))
-                .await?;
-            Ok(())
-        })
-        .detach_and_prompt_err("Failed to pop stash", window, cx, |e, _, _| {
-            Some(e.to_string())
+        let workspace = self.workspace.clone();
+        window.defer(cx, move |window, cx| {
+            workspace
+                .clone()
+                .update(cx, |workspace_handle, cx| {
+                    workspace_handle.toggle_modal(window, cx, |window, cx| {
+                        StashBranchModal::new(repo, Some(stash_index), false, workspace, window, cx)
+                    });
+                })
+                .ok();
         });
+    }
+
+    fn pop_stash(&self, stash_index: usize, _: &mut Window, cx: &mut Context<Picker<Self>>) {
+        let Some(repo) = self.repo.clone() else {
+            return;
+        };
+        let pop = repo.update(cx, |repo, cx| repo.stash_pop(Some(stash_index), cx));
+        self.report_apply_failure(pop, repo, stash_index, "stash pop", cx);
         cx.emit(DismissEvent);
     }
 
-    fn apply_stash(&self, stash_index: usize, window: &mut Window, cx: &mut Context<Picker<Self>>) {
+    fn apply_stash(&self, stash_index: usize, _: &mut Window, cx: &mut Context<Picker<Self>>) {
         let Some(repo) = self.repo.clone() else {
             return;
         };
+        let apply = repo.update(cx, |repo, cx| repo.stash_apply(Some(stash_index), cx));
+        self.report_apply_failure(apply, repo, stash_index, "stash apply", cx);
+        cx.emit(DismissEvent);
+    }
 
+    fn report_apply_failure(
+        &self,
+        apply: Task<anyhow::Result<()>>,
+        repo: Entity<Repository>,
+        stash_index: usize,
+        action: &'static str,
+        cx: &mut Context<Picker<Self>>,
+    ) {
+        let workspace = self.workspace.clone();
         cx.spawn(async move |_, cx| {
-            repo.update(cx, |repo, cx| repo.stash_apply(Some(stash_index), cx))
-                .await?;
-            Ok(())
+            if let Err(error) = apply.await
+                && let Some(workspace) = workspace.upgrade()
+            {
+                cx.update(|cx| {
+                    show_stash_apply_error(workspace, repo, Some(stash_index), action, error, cx)
+                });
+            }
         })
-        .detach_and_prompt_err("Failed to apply stash", window, cx, |e, _, _| {
-            Some(e.to_string())
-        });
-        cx.emit(DismissEvent);
+        .detach();
     }
 }
 
@@ -499,6 +724,26 @@ impl PickerDelegate for StashListDelegate {
                 Label::new(entry_match.formatted_timestamp.clone())
                     .color(Color::Muted)
                     .size(LabelSize::Small),
+            )
+            .when_some(
+                self.file_counts.get(&entry_match.entry.oid),
+                |this, count| {
+                    this.child(
+                        Label::new("•")
+                            .alpha(0.5)
+                            .color(Color::Muted)
+                            .size(LabelSize::Small),
+                    )
+                    .child(
+                        Label::new(if *count == 1 {
+                            "1 file".to_string()
+                        } else {
+                            format!("{count} files")
+                        })
+                        .color(Color::Muted)
+                        .size(LabelSize::Small),
+                    )
+                },
             );
 
         let focus_handle = self.focus_handle.clone();
@@ -560,6 +805,23 @@ impl PickerDelegate for StashListDelegate {
                 .flex_wrap()
                 .border_t_1()
                 .border_color(cx.theme().colors().border_variant)
+                .child(
+                    Button::new("branch-from-stash", "Branch…")
+                        .key_binding(
+                            KeyBinding::for_action_in(
+                                &stash_picker::BranchFromStashItem,
+                                &focus_handle,
+                                cx,
+                            )
+                            .map(|kb| kb.size(rems_from_px(12.))),
+                        )
+                        .on_click(|_, window, cx| {
+                            window.dispatch_action(
+                                stash_picker::BranchFromStashItem.boxed_clone(),
+                                cx,
+                            )
+                        }),
+                )
                 .child(
                     Button::new("drop-stash", "Drop")
                         .key_binding(
@@ -615,16 +877,263 @@ impl PickerDelegate for StashListDelegate {
     }
 }
 
+/// Asks for the message of a new stash, and whether untracked files are stashed too.
+pub struct StashModal {
+    repository: Entity<Repository>,
+    /// The files to stash, or empty to stash every change.
+    paths: Vec<RepoPath>,
+    workspace: WeakEntity<Workspace>,
+    message_editor: Entity<Editor>,
+    include_untracked: bool,
+}
+
+impl StashModal {
+    pub(crate) fn new(
+        repository: Entity<Repository>,
+        paths: Vec<RepoPath>,
+        workspace: WeakEntity<Workspace>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Self {
+        let message_editor = cx.new(|cx| {
+            let mut editor = Editor::single_line(window, cx);
+            editor.set_placeholder_text("Stash message", window, cx);
+            editor
+        });
+        Self {
+            repository,
+            paths,
+            workspace,
+            message_editor,
+            include_untracked: true,
+        }
+    }
+
+    fn cancel(&mut self, _: &Cancel, _window: &mut Window, cx: &mut Context<Self>) {
+        cx.emit(DismissEvent);
+    }
+
+    pub(crate) fn confirm(&mut self, _: &Confirm, _window: &mut Window, cx: &mut Context<Self>) {
+        let message = self.message_editor.read(cx).text(cx).trim().to_string();
+        let paths = self.paths.clone();
+        let options = StashOptions {
+            message: (!message.is_empty()).then_some(message),
+            // Files picked out by name are stashed whether or not they're tracked yet.
+            include_untracked: self.include_untracked || !paths.is_empty(),
+        };
+        let stash = self.repository.update(cx, |repository, cx| {
+            if paths.is_empty() {
+                repository.stash_all(options, cx)
+            } else {
+                repository.stash_entries(paths, options, cx)
+            }
+        });
+        let workspace = self.workspace.clone();
+        cx.spawn(async move |_, cx| {
+            if let Err(error) = stash.await
+                && let Some(workspace) = workspace.upgrade()
+            {
+                cx.update(|cx| show_error_toast(workspace, "stash", error, cx));
+            }
+        })
+        .detach();
+        cx.emit(DismissEvent);
+    }
+}
+
+impl EventEmitter<DismissEvent> for StashModal {}
+impl ModalView for StashModal {}
+impl Focusable for StashModal {
+    fn focus_handle(&self, cx: &App) -> FocusHandle {
+        self.message_editor.focus_handle(cx)
+    }
+}
+
+impl Render for StashModal {
+    fn render(&mut self, _: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let title: SharedString = match self.paths.as_slice() {
+            [] => "Stash Changes".into(),
+            [path] => format!("Stash {}", path.file_name().unwrap_or_default()).into(),
+            paths => format!("Stash {} Files", paths.len()).into(),
+        };
+        v_flex()
+            .key_context("StashModal")
+            .on_action(cx.listener(Self::cancel))
+            .on_action(cx.listener(Self::confirm))
+            .elevation_2(cx)
+            .w(rems(34.))
+            .child(
+                h_flex()
+                    .px_3()
+                    .pt_2()
+                    .pb_1()
+                    .w_full()
+                    .gap_1p5()
+                    .child(Icon::new(IconName::BoxOpen).size(IconSize::XSmall))
+                    .child(Headline::new(title).size(HeadlineSize::XSmall)),
+            )
+            .child(
+                v_flex()
+                    .px_3()
+                    .pb_3()
+                    .w_full()
+                    .gap_2()
+                    .child(self.message_editor.clone())
+                    .when(self.paths.is_empty(), |this| {
+                        this.child(
+                            Checkbox::new("stash-include-untracked", self.include_untracked.into())
+                                .label("Include untracked files")
+                                .label_size(LabelSize::Small)
+                                .on_click(cx.listener(|this, state: &ToggleState, _, cx| {
+                                    this.include_untracked = state.selected();
+                                    cx.notify();
+                                })),
+                        )
+                    }),
+            )
+    }
+}
+
+/// Asks for the name of a branch to create from a stash, at the commit the stash was made on.
+pub struct StashBranchModal {
+    repository: Entity<Repository>,
+    stash_index: Option<usize>,
+    /// Whether a conflicted apply of the stash is undone before the branch is created.
+    undo_conflicted_apply: bool,
+    workspace: WeakEntity<Workspace>,
+    branch_name_editor: Entity<Editor>,
+}
+
+impl StashBranchModal {
+    pub(crate) fn new(
+        repository: Entity<Repository>,
+        stash_index: Option<usize>,
+        undo_conflicted_apply: bool,
+        workspace: WeakEntity<Workspace>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Self {
+        let branch_name_editor = cx.new(|cx| {
+            let mut editor = Editor::single_line(window, cx);
+            editor.set_placeholder_text("Branch name", window, cx);
+            editor
+        });
+        Self {
+            repository,
+            stash_index,
+            undo_conflicted_apply,
+            workspace,
+            branch_name_editor,
+        }
+    }
+
+    fn cancel(&mut self, _: &Cancel, _window: &mut Window, cx: &mut Context<Self>) {
+        cx.emit(DismissEvent);
+    }
+
+    pub(crate) fn confirm(&mut self, _: &Confirm, _window: &mut Window, cx: &mut Context<Self>) {
+        let branch_name = self.branch_name_editor.read(cx).text(cx).trim().to_string();
+        if branch_name.is_empty() {
+            return;
+        }
+        let repository = self.repository.clone();
+        let stash_index = self.stash_index;
+        let undo_conflicted_apply = self.undo_conflicted_apply;
+        let workspace = self.workspace.clone();
+        cx.spawn(async move |_, cx| {
+            let result = async {
+                if undo_conflicted_apply {
+                    repository
+                        .update(cx, |repository, cx| {
+                            repository.reset("HEAD".to_string(), ResetMode::Merge, cx)
+                        })
+                        .await??;
+                }
+                repository
+                    .update(cx, |repository, cx| {
+                        repository.stash_branch(branch_name, stash_index, cx)
+                    })
+                    .await??;
+                anyhow::Ok(())
+            }
+            .await;
+            if let Err(error) = result
+                && let Some(workspace) = workspace.upgrade()
+            {
+                cx.update(|cx| show_error_toast(workspace, "stash branch", error, cx));
+            }
+        })
+        .detach();
+        cx.emit(DismissEvent);
+    }
+}
+
+impl EventEmitter<DismissEvent> for StashBranchModal {}
+impl ModalView for StashBranchModal {}
+impl Focusable for StashBranchModal {
+    fn focus_handle(&self, cx: &App) -> FocusHandle {
+        self.branch_name_editor.focus_handle(cx)
+    }
+}
+
+impl Render for StashBranchModal {
+    fn render(&mut self, _: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let stash_name = format!("stash@{{{}}}", self.stash_index.unwrap_or(0));
+        v_flex()
+            .key_context("StashBranchModal")
+            .on_action(cx.listener(Self::cancel))
+            .on_action(cx.listener(Self::confirm))
+            .elevation_2(cx)
+            .w(rems(34.))
+            .child(
+                h_flex()
+                    .px_3()
+                    .pt_2()
+                    .pb_1()
+                    .w_full()
+                    .gap_1p5()
+                    .child(Icon::new(IconName::GitBranch).size(IconSize::XSmall))
+                    .child(
+                        Headline::new(format!("Create Branch from {stash_name}"))
+                            .size(HeadlineSize::XSmall),
+                    ),
+            )
+            .child(
+                v_flex()
+                    .px_3()
+                    .pb_3()
+                    .w_full()
+                    .gap_2()
+                    .child(self.branch_name_editor.clone())
+                    .when(self.undo_conflicted_apply, |this| {
+                        this.child(
+                            Label::new(
+                                "The conflicted apply is undone first. Other local changes are kept.",
+                            )
+                            .size(LabelSize::Small)
+                            .color(Color::Muted),
+                        )
+                    }),
+            )
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use std::str::FromStr;
+    use std::{path::Path, str::FromStr};
 
     use super::*;
-    use git::{Oid, stash::StashEntry};
+    use git::{
+        Oid,
+        stash::StashEntry,
+        status::{FileStatus, StatusCode},
+    };
     use gpui::{TestAppContext, VisualTestContext, rems};
     use picker::PickerDelegate;
     use project::{FakeFs, Project};
+    use serde_json::json;
     use settings::SettingsStore;
+    use util::path;
     use workspace::MultiWorkspace;
 
     fn init_test(cx: &mut TestAppContext) {
@@ -704,4 +1213,180 @@ mod tests {
             assert!(workspace.active_modal::<StashList>(cx).is_none());
         });
     }
+
+    #[gpui::test]
+    async fn test_stash_with_message_and_drop(cx: &mut TestAppContext) {
+        init_test(cx);
+
+        let fs = FakeFs::new(cx.executor());
+        fs.insert_tree(
+            path!("/project"),
+            json!({
+                ".git": {},
+                "tracked.txt": "changed",
+                "new.txt": "new",
+            }),
+        )
+        .await;
+        fs.set_status_for_repo(
+            Path::new(path!("/project/.git")),
+            &[
+                ("tracked.txt", StatusCode::Modified.worktree()),
+                ("new.txt", FileStatus::Untracked),
+            ],
+        );
+        let project = Project::test(fs.clone(), [Path::new(path!("/project"))], cx).await;
+        let multi_workspace =
+            cx.add_window(|window, cx| MultiWorkspace::test_new(project.clone(), window, cx));
+        let cx = &mut VisualTestContext::from_window(*multi_workspace, cx);
+        let workspace = multi_workspace
+            .update(cx, |workspace, _, _| workspace.workspace().clone())
+            .unwrap();
+        cx.run_until_parked();
+        let repository =
+            project.read_with(cx, |project, cx| project.active_repository(cx).unwrap());
+
+        let modal = workspace.update_in(cx, |workspace, window, cx| {
+            let weak_workspace = workspace.weak_handle();
+            let repository = repository.clone();
+            workspace.toggle_modal(window, cx, move |window, cx| {
+                StashModal::new(repository, Vec::new(), weak_workspace, window, cx)
+            });
+            workspace.active_modal::<StashModal>(cx).unwrap()
+        });
+        modal.update_in(cx, |modal, window, cx| {
+            modal.message_editor.update(cx, |editor, cx| {
+                editor.set_text("Half-done refactor", window, cx)
+            });
+            modal.include_untracked = false;
+            modal.confirm(&Confirm, window, cx);
+        });
+        cx.run_until_parked();
+
+        let pushes = fs
+            .with_git_state(Path::new(path!("/project/.git")), false, |state| {
+                state.stash_pushes.clone()
+            })
+            .unwrap();
+        assert_eq!(
+            pushes,
+            [(
+                vec![RepoPath::new("tracked.txt").unwrap()],
+                StashOptions {
+                    message: Some("Half-done refactor".to_string()),
+                    include_untracked: false,
+                },
+            )]
+        );
+
+        let stash_list = workspace.update_in(cx, |workspace, window, cx| {
+            let weak_workspace = workspace.weak_handle();
+            let repository = repository.clone();
+            workspace.toggle_modal(window, cx, move |window, cx| {
+                StashList::new(Some(repository), weak_workspace, rems(34.), window, cx)
+            });
+            workspace.active_modal::<StashList>(cx).unwrap()
+        });
+        cx.run_until_parked();
+        stash_list.update_in(cx, |stash_list, window, cx| {
+            stash_list.picker.update(cx, |picker, cx| {
+                assert_eq!(picker.delegate.matches.len(), 1);
+                picker.delegate.drop_stash_at(0, window, cx);
+            })
+        });
+        cx.run_until_parked();
+
+        // Dropping waits for the confirmation.
+        let stash_count = || {
+            fs.with_git_state(Path::new(path!("/project/.git")), false, |state| {
+                state.stash_entries.len()
+            })
+            .unwrap()
+        };
+        assert_eq!(stash_count(), 1);
+        cx.simulate_prompt_answer("Drop");
+        cx.run_until_parked();
+        assert_eq!(stash_count(), 0);
+    }
+
+    #[gpui::test]
+    async fn test_conflicting_stash_offers_branch(cx: &mut TestAppContext) {
+        init_test(cx);
+
+        let fs = FakeFs::new(cx.executor());
+        fs.insert_tree(path!("/project"), json!({ ".git": {}, "a.txt": "a" }))
+            .await;
+        let stash = stash_entry(0, "WIP on main", Some("main"));
+        fs.with_git_state(Path::new(path!("/project/.git")), true, |state| {
+            state.branches.insert("main".to_string());
+            state.current_branch_name = Some("main".to_string());
+            state.conflicting_stashes.insert(stash.oid);
+            state.stash_entries.push(stash);
+        })
+        .unwrap();
+        let project = Project::test(fs.clone(), [Path::new(path!("/project"))], cx).await;
+        let multi_workspace =
+            cx.add_window(|window, cx| MultiWorkspace::test_new(project.clone(), window, cx));
+        let cx = &mut VisualTestContext::from_window(*multi_workspace, cx);
+        let workspace = multi_workspace
+            .update(cx, |workspace, _, _| workspace.workspace().clone())
+            .unwrap();
+        cx.run_until_parked();
+        let repository =
+            project.read_with(cx, |project, cx| project.active_repository(cx).unwrap());
+
+        let stash_list = workspace.update_in(cx, |workspace, window, cx| {
+            let weak_workspace = workspace.weak_handle();
+            let repository = repository.clone();
+            workspace.toggle_modal(window, cx, move |window, cx| {
+                StashList::new(Some(repository), weak_workspace, rems(34.), window, cx)
+            });
+            workspace.active_modal::<StashList>(cx).unwrap()
+        });
+        cx.run_until_parked();
+        stash_list.update_in(cx, |stash_list, window, cx| {
+            stash_list
+                .picker
+                .update(cx, |picker, cx| picker.delegate.confirm(false, window, cx))
+        });
+        cx.run_until_parked();
+
+        // The conflict is reported with a way out rather than as a failure, and the stash is kept.
+        workspace.read_with(cx, |workspace, _| {
+            assert!(
+                workspace
+                    .notification_ids()
+                    .contains(&NotificationId::unique::<StashConflicts>())
+            );
+        });
+
+        let modal = workspace.update_in(cx, |workspace, window, cx| {
+            let weak_workspace = workspace.weak_handle();
+            let repository = repository.clone();
+            workspace.toggle_modal(window, cx, move |window, cx| {
+                StashBranchModal::new(repository, Some(0), true, weak_workspace, window, cx)
+            });
+            workspace.active_modal::<StashBranchModal>(cx).unwrap()
+        });
+        modal.update_in(cx, |modal, window, cx| {
+            modal
+                .branch_name_editor
+                .update(cx, |editor, cx| editor.set_text("stashed-work", window, cx));
+            modal.confirm(&Confirm, window, cx);
+        });
+        cx.run_until_parked();
+
+        let (resets, current_branch, stash_count) = fs
+            .with_git_state(Path::new(path!("/project/.git")), false, |state| {
+                (
+                    state.resets.clone(),
+                    state.current_branch_name.clone(),
+                    state.stash_entries.len(),
+                )
+            })
+            .unwrap();
+        assert_eq!(resets, [("HEAD".to_string(), ResetMode::Merge)]);
+        assert_eq!(current_branch.as_deref(), Some("stashed-work"));
+        assert_eq!(stash_count, 0);
+    }
 }
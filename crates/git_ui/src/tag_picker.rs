@@ -0,0 +1,729 @@
+use editor::Editor;
+use fuzzy::StringMatchCandidate;
+use gpui::{
+    Action, AnyElement, App, Context, DismissEvent, Entity, EventEmitter, FocusHandle, Focusable,
+    InteractiveElement, IntoElement, ParentElement, PromptLevel, Render, SharedString, Styled,
+    Subscription, Task, WeakEntity, Window, actions, rems,
+};
+use menu::{Cancel, Confirm};
+use picker::{Picker, PickerDelegate};
+use project::git_store::Repository;
+use std::sync::Arc;
+use ui::{HighlightedLabel, KeyBinding, ListItem, ListItemSpacing, Tooltip, prelude::*};
+use util::ResultExt;
+use workspace::notifications::DetachAndPromptErr;
+use workspace::{ModalView, Workspace};
+
+use crate::git_panel::{GitPanel, show_error_toast};
+use crate::resolve_active_repository;
+
+actions!(
+    tag_picker,
+    [
+        /// Create a tag, named after the query.
+        CreateTag,
+        /// Delete the selected tag.
+        DeleteTag,
+        /// Push the selected tag to a remote.
+        PushTag,
+    ]
+);
+
+pub fn register(workspace: &mut Workspace) {
+    workspace.register_action(|workspace, _: &git::ViewTags, window, cx| {
+        let Some(repository) = resolve_active_repository(workspace, cx) else {
+            return;
+        };
+        let weak_workspace = workspace.weak_handle();
+        workspace.toggle_modal(window, cx, |window, cx| {
+            TagList::new(repository, weak_workspace, rems(34.), window, cx)
+        });
+    });
+}
+
+pub struct TagList {
+    width: Rems,
+    pub picker: Entity<Picker<TagListDelegate>>,
+    picker_focus_handle: FocusHandle,
+    _subscriptions: Vec<Subscription>,
+}
+
+impl TagList {
+    fn new(
+        repository: Entity<Repository>,
+        workspace: WeakEntity<Workspace>,
+        width: Rems,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Self {
+        let delegate = TagListDelegate::new(repository, workspace, cx);
+        let picker = cx.new(|cx| {
+            let mut picker = Picker::uniform_list(delegate, window, cx).show_scrollbar(true);
+            picker.delegate.load_tags(window, cx);
+            picker
+        });
+        let picker_focus_handle = picker.focus_handle(cx);
+        picker.update(cx, |picker, _| {
+            picker.delegate.focus_handle = picker_focus_handle.clone();
+        });
+        let _subscriptions = vec![cx.subscribe(&picker, |_, _, _, cx| {
+            cx.emit(DismissEvent);
+        })];
+
+        Self {
+            width,
+            picker,
+            picker_focus_handle,
+            _subscriptions,
+        }
+    }
+
+    fn handle_create_tag(&mut self, _: &CreateTag, window: &mut Window, cx: &mut Context<Self>) {
+        self.picker.update(cx, |picker, cx| {
+            picker.delegate.create_tag(window, cx);
+        });
+    }
+
+    fn handle_delete_tag(&mut self, _: &DeleteTag, window: &mut Window, cx: &mut Context<Self>) {
+        self.picker.update(cx, |picker, cx| {
+            picker
+                .delegate
+                .delete_tag_at(picker.delegate.selected_index(), window, cx);
+        });
+    }
+
+    fn handle_push_tag(&mut self, _: &PushTag, window: &mut Window, cx: &mut Context<Self>) {
+        self.picker.update(cx, |picker, cx| {
+            picker
+                .delegate
+                .push_tag_at(picker.delegate.selected_index(), window, cx);
+        });
+    }
+}
+
+impl ModalView for TagList {}
+impl EventEmitter<DismissEvent> for TagList {}
+impl Focusable for TagList {
+    fn focus_handle(&self, _: &App) -> FocusHandle {
+        self.picker_focus_handle.clone()
+    }
+}
+
+impl Render for TagList {
+    fn render(&mut self, _: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        v_flex()
+            .key_context("TagList")
+            .w(self.width)
+            .on_action(cx.listener(Self::handle_create_tag))
+            .on_action(cx.listener(Self::handle_delete_tag))
+            .on_action(cx.listener(Self::handle_push_tag))
+            .child(self.picker.clone())
+    }
+}
+
+#[derive(Debug, Clone)]
+struct TagMatch {
+    name: SharedString,
+    positions: Vec<usize>,
+}
+
+pub struct TagListDelegate {
+    matches: Vec<TagMatch>,
+    all_tags: Option<Vec<SharedString>>,
+    repo: Entity<Repository>,
+    workspace: WeakEntity<Workspace>,
+    selected_index: usize,
+    last_query: String,
+    focus_handle: FocusHandle,
+    _load_task: Task<()>,
+}
+
+impl TagListDelegate {
+    fn new(
+        repo: Entity<Repository>,
+        workspace: WeakEntity<Workspace>,
+        cx: &mut Context<TagList>,
+    ) -> Self {
+        Self {
+            matches: Vec::new(),
+            all_tags: None,
+            repo,
+            workspace,
+            selected_index: 0,
+            last_query: String::new(),
+            focus_handle: cx.focus_handle(),
+            _load_task: Task::ready(()),
+        }
+    }
+
+    fn load_tags(&mut self, window: &mut Window, cx: &mut Context<Picker<Self>>) {
+        let tags = self.repo.update(cx, |repo, _| repo.tags());
+        self._load_task = cx.spawn_in(window, async move |picker, cx| {
+            let Some(tags) = tags.await.ok().and_then(|tags| tags.log_err()) else {
+                return;
+            };
+            picker
+                .update_in(cx, |picker, window, cx| {
+                    picker.delegate.all_tags = Some(tags);
+                    picker.refresh(window, cx);
+                })
+                .ok();
+        });
+    }
+
+    fn create_tag(&self, window: &mut Window, cx: &mut Context<Picker<Self>>) {
+        let repo = self.repo.clone();
+        let workspace = self.workspace.clone();
+        let name = self.last_query.trim().to_string();
+        window.defer(cx, move |window, cx| {
+            workspace
+                .clone()
+                .update(cx, |workspace_handle, cx| {
+                    workspace_handle.toggle_modal(window, cx, |window, cx| {
+                        CreateTagModal::new(repo, name, workspace, window, cx)
+                    });
+                })
+                .ok();
+        });
+    }
+
+    fn delete_tag_at(&self, ix: usize, window: &mut Window, cx: &mut Context<Picker<Self>>) {
+        let Some(tag) = self.matches.get(ix).map(|tag| tag.name.clone()) else {
+            return;
+        };
+        let repo = self.repo.clone();
+        let confirmation = window.prompt(
+            PromptLevel::Warning,
+            &format!("Delete tag {tag}?"),
+            Some("Only the local tag is deleted. Remotes it was pushed to keep it."),
+            &["Delete", "Cancel"],
+            cx,
+        );
+        cx.spawn_in(window, async move |picker, cx| {
+            if confirmation.await != Ok(0) {
+                return Ok(());
+            }
+            repo.update(cx, |repo, _| repo.delete_tag(tag.to_string()))
+                .await??;
+            picker.update_in(cx, |picker, window, cx| {
+                picker.delegate.load_tags(window, cx);
+            })?;
+            Ok(())
+        })
+        .detach_and_prompt_err("Failed to delete tag", window, cx, |e, _, _| {
+            Some(e.to_string())
+        });
+    }
+
+    fn push_tag_at(&self, ix: usize, window: &mut Window, cx: &mut Context<Picker<Self>>) {
+        let Some(tag) = self.matches.get(ix).map(|tag| tag.name.clone()) else {
+            return;
+        };
+        let Some(panel) = self
+            .workspace
+            .read_with(cx, |workspace, cx| workspace.panel::<GitPanel>(cx))
+            .ok()
+            .flatten()
+        else {
+            return;
+        };
+        panel.update(cx, |panel, cx| panel.push_tag(tag, window, cx));
+        cx.emit(DismissEvent);
+    }
+
+    /// Checking out a tag detaches HEAD, so the user is told before commits can end up on no
+    /// branch.
+    fn check_out_tag(
+        &self,
+        tag: SharedString,
+        window: &mut Window,
+        cx: &mut Context<Picker<Self>>,
+    ) {
+        let repo = self.repo.clone();
+        let confirmation = window.prompt(
+            PromptLevel::Info,
+            &format!("Check out tag {tag}?"),
+            Some("HEAD will be detached. Create a branch before committing to keep new commits."),
+            &["Check Out", "Cancel"],
+            cx,
+        );
+        cx.spawn_in(window, async move |picker, cx| {
+            if confirmation.await != Ok(0) {
+                return Ok(());
+            }
+            repo.update(cx, |repo, _| {
+                repo.checkout_revision(format!("refs/tags/{tag}"))
+            })
+            .await??;
+            picker.update(cx, |_, cx| cx.emit(DismissEvent))?;
+            Ok(())
+        })
+        .detach_and_prompt_err("Failed to check out tag", window, cx, |e, _, _| {
+            Some(e.to_string())
+        });
+    }
+}
+
+impl PickerDelegate for TagListDelegate {
+    type ListItem = ListItem;
+
+    fn placeholder_text(&self, _window: &mut Window, _cx: &mut App) -> Arc<str> {
+        "Select a tag…".into()
+    }
+
+    fn match_count(&self) -> usize {
+        self.matches.len()
+    }
+
+    fn selected_index(&self) -> usize {
+        self.selected_index
+    }
+
+    fn set_selected_index(
+        &mut self,
+        ix: usize,
+        _window: &mut Window,
+        _: &mut Context<Picker<Self>>,
+    ) {
+        self.selected_index = ix;
+    }
+
+    fn update_matches(
+        &mut self,
+        query: String,
+        window: &mut Window,
+        cx: &mut Context<Picker<Self>>,
+    ) -> Task<()> {
+        let Some(all_tags) = self.all_tags.clone() else {
+            return Task::ready(());
+        };
+
+        cx.spawn_in(window, async move |picker, cx| {
+            let matches: Vec<TagMatch> = if query.is_empty() {
+                all_tags
+                    .into_iter()
+                    .map(|name| TagMatch {
+                        name,
+                        positions: Vec::new(),
+                    })
+                    .collect()
+            } else {
+                let candidates = all_tags
+                    .iter()
+                    .enumerate()
+                    .map(|(ix, name)| StringMatchCandidate::new(ix, name))
+                    .collect::<Vec<StringMatchCandidate>>();
+                fuzzy::match_strings(
+                    &candidates,
+                    &query,
+                    false,
+                    true,
+                    10000,
+                    &Default::default(),
+                    cx.background_executor().clone(),
+                )
+                .await
+                .into_iter()
+                .map(|candidate| TagMatch {
+                    name: all_tags[candidate.candidate_id].clone(),
+                    positions: candidate.positions,
+                })
+                .collect()
+            };
+
+            picker
+                .update(cx, |picker, _| {
+                    let delegate = &mut picker.delegate;
+                    delegate.matches = matches;
+                    if delegate.matches.is_empty() {
+                        delegate.selected_index = 0;
+                    } else {
+                        delegate.selected_index =
+                            core::cmp::min(delegate.selected_index, delegate.matches.len() - 1);
+                    }
+                    delegate.last_query = query;
+                })
+                .log_err();
+        })
+    }
+
+    fn confirm(&mut self, _secondary: bool, window: &mut Window, cx: &mut Context<Picker<Self>>) {
+        let Some(tag) = self
+            .matches
+            .get(self.selected_index())
+            .map(|tag| tag.name.clone())
+        else {
+            return;
+        };
+        self.check_out_tag(tag, window, cx);
+    }
+
+    fn dismissed(&mut self, _: &mut Window, cx: &mut Context<Picker<Self>>) {
+        cx.emit(DismissEvent);
+    }
+
+    fn render_match(
+        &self,
+        ix: usize,
+        selected: bool,
+        _window: &mut Window,
+        cx: &mut Context<Picker<Self>>,
+    ) -> Option<Self::ListItem> {
+        let tag_match = &self.matches[ix];
+        let focus_handle = self.focus_handle.clone();
+
+        let delete_button = |entry_ix: usize| {
+            IconButton::new(("delete-tag", entry_ix), IconName::Trash)
+                .icon_size(IconSize::Small)
+                .tooltip(move |_, cx| {
+                    Tooltip::for_action_in("Delete Tag", &DeleteTag, &focus_handle, cx)
+                })
+                .on_click(cx.listener(move |this, _, window, cx| {
+                    this.delegate.delete_tag_at(entry_ix, window, cx);
+                }))
+        };
+
+        Some(
+            ListItem::new(format!("tag-{ix}"))
+                .inset(true)
+                .spacing(ListItemSpacing::Sparse)
+                .toggle_state(selected)
+                .child(
+                    h_flex()
+                        .w_full()
+                        .gap_2p5()
+                        .child(
+                            Icon::new(IconName::Hash)
+                                .size(IconSize::Small)
+                                .color(Color::Muted),
+                        )
+                        .child(
+                            HighlightedLabel::new(
+                                tag_match.name.clone(),
+                                tag_match.positions.clone(),
+                            )
+                            .truncate(),
+                        ),
+                )
+                .map(|this| {
+                    if selected {
+                        this.end_slot(delete_button(ix))
+                    } else {
+                        this.end_hover_slot(delete_button(ix))
+                    }
+                }),
+        )
+    }
+
+    fn no_matches_text(&self, _window: &mut Window, _cx: &mut App) -> Option<SharedString> {
+        Some("No tags found".into())
+    }
+
+    fn render_footer(&self, _: &mut Window, cx: &mut Context<Picker<Self>>) -> Option<AnyElement> {
+        let focus_handle = self.focus_handle.clone();
+        let has_selection = !self.matches.is_empty();
+
+        Some(
+            h_flex()
+                .w_full()
+                .p_1p5()
+                .gap_0p5()
+                .justify_end()
+                .flex_wrap()
+                .border_t_1()
+                .border_color(cx.theme().colors().border_variant)
+                .child(
+                    Button::new("create-tag", "Create…")
+                        .key_binding(
+                            KeyBinding::for_action_in(&CreateTag, &focus_handle, cx)
+                                .map(|kb| kb.size(rems_from_px(12.))),
+                        )
+                        .on_click(|_, window, cx| {
+                            window.dispatch_action(CreateTag.boxed_clone(), cx)
+                        }),
+                )
+                .when(has_selection, |this| {
+                    this.child(
+                        Button::new("push-tag", "Push…")
+                            .key_binding(
+                                KeyBinding::for_action_in(&PushTag, &focus_handle, cx)
+                                    .map(|kb| kb.size(rems_from_px(12.))),
+                            )
+                            .on_click(|_, window, cx| {
+                                window.dispatch_action(PushTag.boxed_clone(), cx)
+                            }),
+                    )
+                    .child(
+                        Button::new("check-out-tag", "Check Out")
+                            .key_binding(
+                                KeyBinding::for_action_in(&menu::Confirm, &focus_handle, cx)
+                                    .map(|kb| kb.size(rems_from_px(12.))),
+                            )
+                            .on_click(|_, window, cx| {
+                                window.dispatch_action(menu::Confirm.boxed_clone(), cx)
+                            }),
+                    )
+                })
+                .into_any(),
+        )
+    }
+}
+
+/// Asks for the name of a new tag, an optional message that makes it annotated, and the commit
+/// it points at.
+pub struct CreateTagModal {
+    repository: Entity<Repository>,
+    workspace: WeakEntity<Workspace>,
+    name_editor: Entity<Editor>,
+    message_editor: Entity<Editor>,
+    target_editor: Entity<Editor>,
+}
+
+impl CreateTagModal {
+    pub(crate) fn new(
+        repository: Entity<Repository>,
+        name: String,
+        workspace: WeakEntity<Workspace>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Self {
+        let name_editor = cx.new(|cx| {
+            let mut editor = Editor::single_line(window, cx);
+            editor.set_placeholder_text("Tag name", window, cx);
+            editor.set_text(name, window, cx);
+            editor
+        });
+        let message_editor = cx.new(|cx| {
+            let mut editor = Editor::single_line(window, cx);
+            editor.set_placeholder_text("Message (leave empty for a lightweight tag)", window, cx);
+            editor
+        });
+        let target_editor = cx.new(|cx| {
+            let mut editor = Editor::single_line(window, cx);
+            editor.set_placeholder_text("Commit (defaults to HEAD)", window, cx);
+            editor
+        });
+        Self {
+            repository,
+            workspace,
+            name_editor,
+            message_editor,
+            target_editor,
+        }
+    }
+
+    fn cancel(&mut self, _: &Cancel, _window: &mut Window, cx: &mut Context<Self>) {
+        cx.emit(DismissEvent);
+    }
+
+    pub(crate) fn confirm(&mut self, _: &Confirm, _window: &mut Window, cx: &mut Context<Self>) {
+        let name = self.name_editor.read(cx).text(cx).trim().to_string();
+        if name.is_empty() {
+            return;
+        }
+        let message = self.message_editor.read(cx).text(cx).trim().to_string();
+        let target = self.target_editor.read(cx).text(cx).trim().to_string();
+        let create = self.repository.update(cx, |repository, _| {
+            repository.create_tag(
+                name,
+                (!message.is_empty()).then_some(message),
+                (!target.is_empty()).then_some(target),
+            )
+        });
+        let workspace = self.workspace.clone();
+        cx.spawn(async move |_, cx| {
+            if let Ok(Err(error)) = create.await
+                && let Some(workspace) = workspace.upgrade()
+            {
+                cx.update(|cx| show_error_toast(workspace, "tag", error, cx));
+            }
+        })
+        .detach();
+        cx.emit(DismissEvent);
+    }
+}
+
+impl EventEmitter<DismissEvent> for CreateTagModal {}
+impl ModalView for CreateTagModal {}
+impl Focusable for CreateTagModal {
+    fn focus_handle(&self, cx: &App) -> FocusHandle {
+        self.name_editor.focus_handle(cx)
+    }
+}
+
+impl Render for CreateTagModal {
+    fn render(&mut self, _: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        v_flex()
+            .key_context("CreateTagModal")
+            .on_action(cx.listener(Self::cancel))
+            .on_action(cx.listener(Self::confirm))
+            .elevation_2(cx)
+            .w(rems(34.))
+            .child(
+                h_flex()
+                    .px_3()
+                    .pt_2()
+                    .pb_1()
+                    .w_full()
+                    .gap_1p5()
+                    .child(Icon::new(IconName::Hash).size(IconSize::XSmall))
+                    .child(Headline::new("Create Tag").size(HeadlineSize::XSmall)),
+            )
+            .child(
+                v_flex()
+                    .px_3()
+                    .pb_3()
+                    .w_full()
+                    .gap_2()
+                    .child(self.name_editor.clone())
+                    .child(self.message_editor.clone())
+                    .child(self.target_editor.clone()),
+            )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use super::*;
+    use gpui::{TestAppContext, VisualTestContext};
+    use project::{FakeFs, Project};
+    use serde_json::json;
+    use settings::SettingsStore;
+    use util::path;
+    use workspace::MultiWorkspace;
+
+    fn init_test(cx: &mut TestAppContext) {
+        cx.update(|cx| {
+            let settings_store = SettingsStore::test(cx);
+            cx.set_global(settings_store);
+
+            theme::init(theme::LoadThemes::JustBase, cx);
+            editor::init(cx);
+        })
+    }
+
+    #[gpui::test]
+    async fn test_create_check_out_and_delete_tags(cx: &mut TestAppContext) {
+        init_test(cx);
+
+        let fs = FakeFs::new(cx.executor());
+        fs.insert_tree(path!("/project"), json!({ ".git": {}, "a.txt": "a" }))
+            .await;
+        fs.with_git_state(Path::new(path!("/project/.git")), true, |state| {
+            state.branches.insert("main".to_string());
+            state.current_branch_name = Some("main".to_string());
+            state.tags.push("v0.9".to_string());
+        })
+        .unwrap();
+        let project = Project::test(fs.clone(), [Path::new(path!("/project"))], cx).await;
+        let multi_workspace =
+            cx.add_window(|window, cx| MultiWorkspace::test_new(project.clone(), window, cx));
+        let cx = &mut VisualTestContext::from_window(*multi_workspace, cx);
+        let workspace = multi_workspace
+            .update(cx, |workspace, _, _| workspace.workspace().clone())
+            .unwrap();
+        cx.run_until_parked();
+        let repository =
+            project.read_with(cx, |project, cx| project.active_repository(cx).unwrap());
+
+        let modal = workspace.update_in(cx, |workspace, window, cx| {
+            let weak_workspace = workspace.weak_handle();
+            let repository = repository.clone();
+            workspace.toggle_modal(window, cx, move |window, cx| {
+                CreateTagModal::new(repository, "v1.0".to_string(), weak_workspace, window, cx)
+            });
+            workspace.active_modal::<CreateTagModal>(cx).unwrap()
+        });
+        modal.update_in(cx, |modal, window, cx| {
+            modal.message_editor.update(cx, |editor, cx| {
+                editor.set_text("First release", window, cx)
+            });
+            modal
+                .target_editor
+                .update(cx, |editor, cx| editor.set_text("abc123", window, cx));
+            modal.confirm(&Confirm, window, cx);
+        });
+        cx.run_until_parked();
+
+        let (tags, message, target) = fs
+            .with_git_state(Path::new(path!("/project/.git")), false, |state| {
+                (
+                    state.tags.clone(),
+                    state.tag_messages.get("v1.0").cloned(),
+                    state.tag_targets.get("v1.0").cloned(),
+                )
+            })
+            .unwrap();
+        assert_eq!(tags, ["v1.0", "v0.9"]);
+        assert_eq!(message.as_deref(), Some("First release"));
+        assert_eq!(target.as_deref(), Some("abc123"));
+
+        let tag_list = workspace.update_in(cx, |workspace, window, cx| {
+            let weak_workspace = workspace.weak_handle();
+            let repository = repository.clone();
+            workspace.toggle_modal(window, cx, move |window, cx| {
+                TagList::new(repository, weak_workspace, rems(34.), window, cx)
+            });
+            workspace.active_modal::<TagList>(cx).unwrap()
+        });
+        cx.run_until_parked();
+
+        // Checking out a tag waits for the user to accept detaching HEAD.
+        tag_list.update_in(cx, |tag_list, window, cx| {
+            tag_list.picker.update(cx, |picker, cx| {
+                assert_eq!(picker.delegate.matches.len(), 2);
+                picker.delegate.set_selected_index(1, window, cx);
+                picker.delegate.confirm(false, window, cx);
+            })
+        });
+        cx.run_until_parked();
+        let head = || {
+            fs.with_git_state(Path::new(path!("/project/.git")), false, |state| {
+                (
+                    state.refs.get("HEAD").cloned(),
+                    state.current_branch_name.clone(),
+                )
+            })
+            .unwrap()
+        };
+        assert_eq!(head().1.as_deref(), Some("main"));
+        cx.simulate_prompt_answer("Check Out");
+        cx.run_until_parked();
+        assert_eq!(head(), (Some("refs/tags/v0.9".to_string()), None));
+        workspace.update(cx, |workspace, cx| {
+            assert!(workspace.active_modal::<TagList>(cx).is_none());
+        });
+
+        let tag_list = workspace.update_in(cx, |workspace, window, cx| {
+            let weak_workspace = workspace.weak_handle();
+            let repository = repository.clone();
+            workspace.toggle_modal(window, cx, move |window, cx| {
+                TagList::new(repository, weak_workspace, rems(34.), window, cx)
+            });
+            workspace.active_modal::<TagList>(cx).unwrap()
+        });
+        cx.run_until_parked();
+        tag_list.update_in(cx, |tag_list, window, cx| {
+            tag_list.picker.update(cx, |picker, cx| {
+                picker.delegate.delete_tag_at(0, window, cx);
+            })
+        });
+        cx.run_until_parked();
+        cx.simulate_prompt_answer("Delete");
+        cx.run_until_parked();
+
+        let tags = fs
+            .with_git_state(Path::new(path!("/project/.git")), false, |state| {
+                state.tags.clone()
+            })
+            .unwrap();
+        assert_eq!(tags, ["v0.9"]);
+        tag_list.read_with(cx, |tag_list, cx| {
+            let matches = &tag_list.picker.read(cx).delegate.matches;
+            assert_eq!(matches.len(), 1);
+            assert_eq!(matches[0].name, "v0.9");
+        });
+    }
+}
@@ -1,7 +1,7 @@
 //! TextDiffView currently provides a UI for displaying differences between the clipboard and selected text.
 
 use anyhow::Result;
-use buffer_diff::BufferDiff;
+use buffer_diff::{BufferDiff, IgnoreWhitespace};
 use editor::{
     Editor, EditorEvent, EditorSettings, MultiBuffer, SplittableEditor, ToPoint,
     actions::DiffClipboardWithSelectionData,
@@ -9,11 +9,11 @@ use editor::{
 use futures::{FutureExt, select_biased};
 use gpui::{
     AnyElement, App, AppContext as _, AsyncApp, Context, Entity, EventEmitter, FocusHandle,
-    Focusable, IntoElement, Render, Task, Window,
+    Focusable, IntoElement, Render, Task, WeakEntity, Window,
 };
-use language::{self, Buffer, Point};
-use project::Project;
-use settings::Settings;
+use language::{self, Buffer, OffsetRangeExt as _, Point};
+use project::{Project, project_settings::ProjectSettings};
+use settings::{DiffViewStyle, Settings};
 use std::{
     any::{Any, TypeId},
     cmp,
@@ -22,19 +22,32 @@ use std::{
     sync::Arc,
     time::Duration,
 };
-use ui::{Color, Icon, IconName, Label, LabelCommon as _, SharedString};
+use ui::{Tooltip, prelude::*};
 use util::paths::PathExt;
 
 use workspace::{
-    Item, ItemNavHistory, Workspace,
+    Item, ItemHandle, ItemNavHistory, ToolbarItemEvent, ToolbarItemLocation, ToolbarItemView,
+    Workspace,
     item::{ItemEvent, SaveOptions, TabContentParams},
     searchable::SearchableItemHandle,
 };
 
 pub struct TextDiffView {
     diff_editor: Entity<SplittableEditor>,
-    title: SharedString,
-    path: Option<SharedString>,
+    diff: Entity<BufferDiff>,
+    source_buffer: Entity<Buffer>,
+    /// A copy of the source buffer with the compared lines replaced by the clipboard text.
+    clipboard_buffer: Entity<Buffer>,
+    /// The compared lines in `source_buffer`.
+    source_range: Range<language::Anchor>,
+    /// The clipboard text in `clipboard_buffer`.
+    clipboard_range: Range<language::Anchor>,
+    /// Whether the clipboard is the new side of the diff, rather than the old one.
+    swapped: bool,
+    project: Entity<Project>,
+    workspace: Entity<Workspace>,
+    selection_location_title: SharedString,
+    selection_location_path: SharedString,
     buffer_changes_tx: watch::Sender<()>,
     _recalculate_diff_task: Task<Result<()>>,
 }
@@ -120,9 +133,14 @@ impl TextDiffView {
         }
 
         let workspace = workspace.weak_handle();
-        let diff_buffer = cx.new(|cx| BufferDiff::new(&source_buffer_snapshot.text, cx));
+        let ignore_whitespace = ProjectSettings::get_global(cx).git.diff.ignore_whitespace;
+        let diff_buffer = cx.new(|cx| {
+            let mut diff = BufferDiff::new(&source_buffer_snapshot.text, cx);
+            diff.set_ignore_whitespace(ignore_whitespace);
+            diff
+        });
         let clipboard_buffer = build_clipboard_buffer(
-            clipboard_text,
+            clipboard_text.clone(),
             &source_buffer,
             expanded_selection_range.clone(),
             cx,
@@ -137,6 +155,7 @@ impl TextDiffView {
                 let diff_view = cx.new(|cx| {
                     TextDiffView::new(
                         clipboard_buffer,
+                        clipboard_text,
                         source_editor,
                         source_buffer,
                         expanded_selection_range,
@@ -162,6 +181,7 @@ impl TextDiffView {
 
     pub fn new(
         clipboard_buffer: Entity<Buffer>,
+        clipboard_text: String,
         source_editor: Entity<Editor>,
         source_buffer: Entity<Buffer>,
         source_range: Range<Point>,
@@ -171,46 +191,42 @@ impl TextDiffView {
         window: &mut Window,
         cx: &mut Context<Self>,
     ) -> Self {
-        let multibuffer = cx.new(|cx| {
-            let mut multibuffer = MultiBuffer::new(language::Capability::ReadWrite);
-
-            multibuffer.set_excerpts_for_buffer(source_buffer.clone(), [source_range], 0, cx);
-
-            multibuffer.add_diff(diff_buffer.clone(), cx);
-            multibuffer
-        });
-        let diff_editor = cx.new(|cx| {
-            let splittable = SplittableEditor::new(
-                EditorSettings::get_global(cx).diff_view_style,
-                multibuffer,
-                project,
-                workspace,
-                window,
-                cx,
-            );
-            splittable.set_render_diff_hunk_controls(
-                Arc::new(|_, _, _, _, _, _, _, _| gpui::Empty.into_any_element()),
-                cx,
-            );
-            splittable.rhs_editor().update(cx, |editor, cx| {
-                editor.start_temporary_diff_override();
-                editor.disable_diagnostics(cx);
-                editor.set_expand_all_diff_hunks(cx);
-            });
-            splittable
-        });
+        let source_anchor_range = {
+            let source_buffer = source_buffer.read(cx);
+            source_buffer.anchor_before(source_range.start)
+                ..source_buffer.anchor_before(source_range.end)
+        };
+        // Anchored to the clipboard text itself, so that edits mirrored around it keep it in place.
+        let clipboard_anchor_range = {
+            let clipboard_buffer = clipboard_buffer.read(cx);
+            let start = clipboard_buffer.point_to_offset(source_range.start);
+            clipboard_buffer.anchor_after(start)
+                ..clipboard_buffer.anchor_before(start + clipboard_text.len())
+        };
+        let diff_editor = Self::build_diff_editor(
+            &source_buffer,
+            source_anchor_range.clone(),
+            &diff_buffer,
+            project.clone(),
+            workspace.clone(),
+            EditorSettings::get_global(cx).diff_view_style,
+            window,
+            cx,
+        );
 
         let (buffer_changes_tx, mut buffer_changes_rx) = watch::channel(());
 
-        cx.subscribe(&source_buffer, move |this, _, event, _| match event {
-            language::BufferEvent::Edited { .. }
-            | language::BufferEvent::LanguageChanged(_)
-            | language::BufferEvent::Reparsed => {
-                this.buffer_changes_tx.send(()).ok();
-            }
-            _ => {}
-        })
-        .detach();
+        for buffer in [&source_buffer, &clipboard_buffer] {
+            cx.subscribe(buffer, move |this, _, event, _| match event {
+                language::BufferEvent::Edited { .. }
+                | language::BufferEvent::LanguageChanged(_)
+                | language::BufferEvent::Reparsed => {
+                    this.buffer_changes_tx.send(()).ok();
+                }
+                _ => {}
+            })
+            .detach();
+        }
 
         let editor = source_editor.read(cx);
         let title = editor.buffer().read(cx).title(cx).to_string();
@@ -237,10 +253,18 @@ impl TextDiffView {
 
         Self {
             diff_editor,
-            title: format!("Clipboard ↔ {selection_location_title}").into(),
-            path: Some(format!("Clipboard ↔ {selection_location_path}").into()),
+            diff: diff_buffer,
+            source_buffer,
+            clipboard_buffer,
+            source_range: source_anchor_range,
+            clipboard_range: clipboard_anchor_range,
+            swapped: false,
+            project,
+            workspace,
+            selection_location_title: selection_location_title.into(),
+            selection_location_path: selection_location_path.into(),
             buffer_changes_tx,
-            _recalculate_diff_task: cx.spawn(async move |_, cx| {
+            _recalculate_diff_task: cx.spawn(async move |this, cx| {
                 while buffer_changes_rx.recv().await.is_ok() {
                     loop {
                         let mut timer = cx
@@ -255,13 +279,169 @@ impl TextDiffView {
                     }
 
                     log::trace!("start recalculating");
-                    update_diff_buffer(&diff_buffer, &source_buffer, &clipboard_buffer, cx).await?;
+                    let (diff, new_buffer, old_buffer) = this.update(cx, |this, cx| {
+                        this.sync_clipboard_buffer(cx);
+                        let (old_buffer, new_buffer) = this.sides();
+                        (this.diff.clone(), new_buffer.clone(), old_buffer.clone())
+                    })?;
+                    update_diff_buffer(&diff, &new_buffer, &old_buffer, cx).await?;
                     log::trace!("finish recalculating");
                 }
                 Ok(())
             }),
         }
     }
+
+    fn build_diff_editor(
+        buffer: &Entity<Buffer>,
+        range: Range<language::Anchor>,
+        diff: &Entity<BufferDiff>,
+        project: Entity<Project>,
+        workspace: Entity<Workspace>,
+        style: DiffViewStyle,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Entity<SplittableEditor> {
+        let range = range.to_point(&buffer.read(cx).snapshot());
+        let multibuffer = cx.new(|cx| {
+            let mut multibuffer = MultiBuffer::new(language::Capability::ReadWrite);
+
+            multibuffer.set_excerpts_for_buffer(buffer.clone(), [range], 0, cx);
+
+            multibuffer.add_diff(diff.clone(), cx);
+            multibuffer
+        });
+        cx.new(|cx| {
+            let splittable =
+                SplittableEditor::new(style, multibuffer, project, workspace, window, cx);
+            splittable.set_render_diff_hunk_controls(
+                Arc::new(|_, _, _, _, _, _, _, _| gpui::Empty.into_any_element()),
+                cx,
+            );
+            splittable.rhs_editor().update(cx, |editor, cx| {
+                editor.start_temporary_diff_override();
+                editor.disable_diagnostics(cx);
+                editor.set_expand_all_diff_hunks(cx);
+            });
+            splittable
+        })
+    }
+
+    /// The buffers on the old and new side of the diff.
+    fn sides(&self) -> (&Entity<Buffer>, &Entity<Buffer>) {
+        if self.swapped {
+            (&self.source_buffer, &self.clipboard_buffer)
+        } else {
+            (&self.clipboard_buffer, &self.source_buffer)
+        }
+    }
+
+    /// Keeps the text around the compared range in sync with the source buffer, so that only the
+    /// clipboard text differs from it.
+    fn sync_clipboard_buffer(&self, cx: &mut App) {
+        let source_snapshot = self.source_buffer.read(cx).snapshot();
+        let source_range = self.source_range.to_offset(&source_snapshot);
+        let prefix = source_snapshot
+            .text_for_range(0..source_range.start)
+            .collect::<String>();
+        let suffix = source_snapshot
+            .text_for_range(source_range.end..source_snapshot.len())
+            .collect::<String>();
+        self.clipboard_buffer.update(cx, |clipboard_buffer, cx| {
+            let snapshot = clipboard_buffer.snapshot();
+            let clipboard_range = self.clipboard_range.to_offset(&snapshot);
+            let mut edits = Vec::new();
+            if snapshot
+                .text_for_range(0..clipboard_range.start)
+                .collect::<String>()
+                != prefix
+            {
+                edits.push((0..clipboard_range.start, prefix));
+            }
+            if snapshot
+                .text_for_range(clipboard_range.end..snapshot.len())
+                .collect::<String>()
+                != suffix
+            {
+                edits.push((clipboard_range.end..snapshot.len(), suffix));
+            }
+            if !edits.is_empty() {
+                clipboard_buffer.edit(edits, None, cx);
+            }
+        });
+    }
+
+    pub fn ignore_whitespace(&self, cx: &App) -> IgnoreWhitespace {
+        self.diff.read(cx).ignore_whitespace()
+    }
+
+    /// Cycles between showing every whitespace difference, ignoring trailing whitespace, and
+    /// ignoring all of it.
+    pub fn toggle_ignore_whitespace(&mut self, cx: &mut Context<Self>) {
+        self.diff.update(cx, |diff, _| {
+            diff.set_ignore_whitespace(diff.ignore_whitespace().next());
+        });
+        self.buffer_changes_tx.send(()).ok();
+        cx.notify();
+    }
+
+    /// Swaps whether the clipboard or the selection is treated as the old side of the diff.
+    pub fn swap_sides(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        self.swapped = !self.swapped;
+        let style = if self.diff_editor.read(cx).is_split() {
+            DiffViewStyle::Split
+        } else {
+            DiffViewStyle::Unified
+        };
+        let (old_buffer, new_buffer) = self.sides();
+        let (old_buffer, new_buffer) = (old_buffer.clone(), new_buffer.clone());
+        let range = if self.swapped {
+            self.clipboard_range.clone()
+        } else {
+            self.source_range.clone()
+        };
+        let ignore_whitespace = self.diff.read(cx).ignore_whitespace();
+        let new_snapshot = new_buffer.read(cx).snapshot();
+        self.diff = cx.new(|cx| {
+            let mut diff = BufferDiff::new(&new_snapshot.text, cx);
+            diff.set_ignore_whitespace(ignore_whitespace);
+            diff
+        });
+        let was_focused = self
+            .diff_editor
+            .focus_handle(cx)
+            .contains_focused(window, cx);
+        let nav_history = self
+            .diff_editor
+            .read(cx)
+            .rhs_editor()
+            .read(cx)
+            .nav_history()
+            .cloned();
+        self.diff_editor = Self::build_diff_editor(
+            &new_buffer,
+            range,
+            &self.diff,
+            self.project.clone(),
+            self.workspace.clone(),
+            style,
+            window,
+            cx,
+        );
+        if let Some(nav_history) = nav_history {
+            let rhs = self.diff_editor.read(cx).rhs_editor().clone();
+            rhs.update(cx, |editor, _| editor.set_nav_history(Some(nav_history)));
+        }
+        if was_focused {
+            self.diff_editor.focus_handle(cx).focus(window, cx);
+        }
+
+        let diff = self.diff.clone();
+        cx.spawn(async move |_, cx| update_diff_buffer(&diff, &new_buffer, &old_buffer, cx).await)
+            .detach_and_log_err(cx);
+        cx.emit(EditorEvent::TitleChanged);
+        cx.notify();
+    }
 }
 
 fn build_clipboard_buffer(
@@ -286,21 +466,21 @@ fn build_clipboard_buffer(
 
 async fn update_diff_buffer(
     diff: &Entity<BufferDiff>,
-    source_buffer: &Entity<Buffer>,
-    clipboard_buffer: &Entity<Buffer>,
+    buffer: &Entity<Buffer>,
+    base_buffer: &Entity<Buffer>,
     cx: &mut AsyncApp,
 ) -> Result<()> {
-    let source_buffer_snapshot = source_buffer.read_with(cx, |buffer, _| buffer.snapshot());
-    let language = source_buffer_snapshot.language().cloned();
-    let language_registry = source_buffer.read_with(cx, |buffer, _| buffer.language_registry());
+    let buffer_snapshot = buffer.read_with(cx, |buffer, _| buffer.snapshot());
+    let language = buffer_snapshot.language().cloned();
+    let language_registry = buffer.read_with(cx, |buffer, _| buffer.language_registry());
 
-    let base_buffer_snapshot = clipboard_buffer.read_with(cx, |buffer, _| buffer.snapshot());
+    let base_buffer_snapshot = base_buffer.read_with(cx, |buffer, _| buffer.snapshot());
     let base_text = base_buffer_snapshot.text();
 
     let update = diff
         .update(cx, |diff, cx| {
             diff.update_diff(
-                source_buffer_snapshot.text.clone(),
+                buffer_snapshot.text.clone(),
                 Some(Arc::from(base_text.as_str())),
                 Some(true),
                 language.clone(),
@@ -311,7 +491,7 @@ async fn update_diff_buffer(
 
     diff.update(cx, |diff, cx| {
         diff.language_changed(language, language_registry, cx);
-        diff.set_snapshot(update, &source_buffer_snapshot.text, cx)
+        diff.set_snapshot(update, &buffer_snapshot.text, cx)
     })
     .await;
     Ok(())
@@ -343,11 +523,19 @@ impl Item for TextDiffView {
     }
 
     fn tab_content_text(&self, _detail: usize, _: &App) -> SharedString {
-        self.title.clone()
+        if self.swapped {
+            format!("{} ↔ Clipboard", self.selection_location_title).into()
+        } else {
+            format!("Clipboard ↔ {}", self.selection_location_title).into()
+        }
     }
 
     fn tab_tooltip_text(&self, _: &App) -> Option<SharedString> {
-        self.path.clone()
+        Some(if self.swapped {
+            format!("{} ↔ Clipboard", self.selection_location_path).into()
+        } else {
+            format!("Clipboard ↔ {}", self.selection_location_path).into()
+        })
     }
 
     fn to_item_events(event: &EditorEvent, f: &mut dyn FnMut(ItemEvent)) {
@@ -472,8 +660,93 @@ pub fn selection_location_text(editor: &Editor, cx: &App) -> Option<String> {
 }
 
 impl Render for TextDiffView {
-    fn render(&mut self, _: &mut Window, _: &mut Context<Self>) -> impl IntoElement {
-        self.diff_editor.clone()
+    fn render(&mut self, _: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        div()
+            .size_full()
+            .on_action(
+                cx.listener(|this, _: &git::SwapDiffSides, window, cx| this.swap_sides(window, cx)),
+            )
+            .on_action(cx.listener(|this, _: &git::ToggleIgnoreWhitespace, _, cx| {
+                this.toggle_ignore_whitespace(cx)
+            }))
+            .child(self.diff_editor.clone())
+    }
+}
+
+pub struct TextDiffViewToolbar {
+    text_diff_view: Option<WeakEntity<TextDiffView>>,
+}
+
+impl TextDiffViewToolbar {
+    pub fn new() -> Self {
+        Self {
+            text_diff_view: None,
+        }
+    }
+}
+
+impl EventEmitter<ToolbarItemEvent> for TextDiffViewToolbar {}
+
+impl Render for TextDiffViewToolbar {
+    fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let Some(text_diff_view) = self.text_diff_view.clone() else {
+            return div();
+        };
+        let ignore_whitespace = text_diff_view
+            .upgrade()
+            .map_or(IgnoreWhitespace::None, |text_diff_view| {
+                text_diff_view.read(cx).ignore_whitespace(cx)
+            });
+
+        h_flex()
+            .gap_1()
+            .child(
+                IconButton::new("toggle_ignore_whitespace", IconName::Space)
+                    .icon_size(IconSize::Small)
+                    .toggle_state(ignore_whitespace != IgnoreWhitespace::None)
+                    .tooltip(Tooltip::for_action_title(
+                        ignore_whitespace.label(),
+                        &git::ToggleIgnoreWhitespace,
+                    ))
+                    .on_click({
+                        let text_diff_view = text_diff_view.clone();
+                        move |_, _, cx| {
+                            text_diff_view
+                                .update(cx, |text_diff_view, cx| {
+                                    text_diff_view.toggle_ignore_whitespace(cx)
+                                })
+                                .ok();
+                        }
+                    }),
+            )
+            .child(
+                IconButton::new("swap_diff_sides", IconName::ArrowRightLeft)
+                    .icon_size(IconSize::Small)
+                    .tooltip(Tooltip::for_action_title("Swap Sides", &git::SwapDiffSides))
+                    .on_click(move |_, window, cx| {
+                        text_diff_view
+                            .update(cx, |text_diff_view, cx| {
+                                text_diff_view.swap_sides(window, cx)
+                            })
+                            .ok();
+                    }),
+            )
+    }
+}
+
+impl ToolbarItemView for TextDiffViewToolbar {
+    fn set_active_pane_item(
+        &mut self,
+        active_pane_item: Option<&dyn ItemHandle>,
+        _: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> ToolbarItemLocation {
+        if let Some(entity) = active_pane_item.and_then(|item| item.act_as::<TextDiffView>(cx)) {
+            self.text_diff_view = Some(entity.downgrade());
+            return ToolbarItemLocation::PrimaryRight;
+        }
+        self.text_diff_view = None;
+        ToolbarItemLocation::Hidden
     }
 }
 
@@ -770,11 +1043,12 @@ mod tests {
 
         cx.executor().run_until_parked();
 
-        diff_view.read_with(cx, |diff_view, _cx| {
+        diff_view.read_with(cx, |diff_view, cx| {
+            let title = diff_view.tab_content_text(0, cx);
             assert!(
-                diff_view.title.contains("Clipboard"),
+                title.contains("Clipboard"),
                 "diff view should have opened with a clipboard diff title, got: {}",
-                diff_view.title
+                title
             );
         });
     }
@@ -860,13 +1134,328 @@ mod tests {
         cx.executor().run_until_parked();
 
         // Empty selection should diff the full underlying buffer
-        diff_view.read_with(cx, |diff_view, _cx| {
+        diff_view.read_with(cx, |diff_view, cx| {
+            let title = diff_view.tab_content_text(0, cx);
             assert!(
-                diff_view.title.contains("Clipboard"),
+                title.contains("Clipboard"),
                 "diff view should have opened with a clipboard diff title, got: {}",
-                diff_view.title
+                title
+            );
+        });
+    }
+
+    #[gpui::test]
+    async fn test_diff_updates_when_source_buffer_changes(cx: &mut TestAppContext) {
+        init_test(cx);
+
+        let fs = FakeFs::new(cx.executor());
+        fs.insert_tree(
+            path!("/test"),
+            json!({
+                "text.txt": "first\nsecond\nthird\n"
+            }),
+        )
+        .await;
+
+        let project = Project::test(fs, [path!("/test").as_ref()], cx).await;
+
+        let (multi_workspace, cx) =
+            cx.add_window_view(|window, cx| MultiWorkspace::test_new(project.clone(), window, cx));
+        let workspace = multi_workspace.read_with(cx, |mw, _| mw.workspace().clone());
+
+        let buffer = project
+            .update(cx, |project, cx| {
+                project.open_local_buffer(path!("/test/text.txt"), cx)
+            })
+            .await
+            .unwrap();
+
+        let editor = cx.new_window_entity(|window, cx| {
+            let mut editor = Editor::for_buffer(buffer.clone(), None, window, cx);
+            editor.change_selections(Default::default(), window, cx, |s| {
+                s.select_ranges([MultiBufferOffset(6)..MultiBufferOffset(12)])
+            });
+            editor
+        });
+
+        let diff_view = workspace
+            .update_in(cx, |workspace, window, cx| {
+                TextDiffView::open(
+                    &DiffClipboardWithSelectionData {
+                        clipboard_text: "2nd\n".to_string(),
+                        editor,
+                    },
+                    workspace,
+                    window,
+                    cx,
+                )
+            })
+            .unwrap()
+            .await
+            .unwrap();
+        cx.executor().run_until_parked();
+
+        // Edits both outside and inside of the compared range
+        buffer.update(cx, |buffer, cx| {
+            buffer.edit([(Point::new(0, 0)..Point::new(0, 0), "zeroth\n")], None, cx);
+            buffer.edit([(Point::new(2, 6)..Point::new(2, 6), "!")], None, cx);
+        });
+        cx.executor().advance_clock(RECALCULATE_DIFF_DEBOUNCE);
+        cx.executor().run_until_parked();
+
+        assert_state_with_diff(
+            &diff_view.read_with(cx, |diff_view, cx| {
+                diff_view.diff_editor.read(cx).rhs_editor().clone()
+            }),
+            cx,
+            &unindent(
+                "
+                - 2nd
+                + ˇsecond!
+                ",
+            ),
+        );
+    }
+
+    #[gpui::test]
+    async fn test_swap_sides(cx: &mut TestAppContext) {
+        init_test(cx);
+
+        let fs = FakeFs::new(cx.executor());
+        fs.insert_tree(
+            path!("/test"),
+            json!({
+                "text.txt": "first\nsecond\nthird\n"
+            }),
+        )
+        .await;
+
+        let project = Project::test(fs, [path!("/test").as_ref()], cx).await;
+
+        let (multi_workspace, cx) =
+            cx.add_window_view(|window, cx| MultiWorkspace::test_new(project.clone(), window, cx));
+        let workspace = multi_workspace.read_with(cx, |mw, _| mw.workspace().clone());
+
+        let buffer = project
+            .update(cx, |project, cx| {
+                project.open_local_buffer(path!("/test/text.txt"), cx)
+            })
+            .await
+            .unwrap();
+
+        let editor = cx.new_window_entity(|window, cx| {
+            let mut editor = Editor::for_buffer(buffer.clone(), None, window, cx);
+            editor.change_selections(Default::default(), window, cx, |s| {
+                s.select_ranges([MultiBufferOffset(6)..MultiBufferOffset(12)])
+            });
+            editor
+        });
+
+        let diff_view = workspace
+            .update_in(cx, |workspace, window, cx| {
+                TextDiffView::open(
+                    &DiffClipboardWithSelectionData {
+                        clipboard_text: "2nd\n".to_string(),
+                        editor,
+                    },
+                    workspace,
+                    window,
+                    cx,
+                )
+            })
+            .unwrap()
+            .await
+            .unwrap();
+        cx.executor().run_until_parked();
+
+        diff_view.update_in(cx, |diff_view, window, cx| diff_view.swap_sides(window, cx));
+        cx.executor().run_until_parked();
+
+        diff_view.read_with(cx, |diff_view, cx| {
+            let rhs = diff_view.diff_editor.read(cx).rhs_editor().read(cx);
+            assert!(rhs.nav_history().is_some());
+        });
+        assert_state_with_diff(
+            &diff_view.read_with(cx, |diff_view, cx| {
+                diff_view.diff_editor.read(cx).rhs_editor().clone()
+            }),
+            cx,
+            &unindent(
+                "
+                - second
+                + ˇ2nd
+                ",
+            ),
+        );
+        diff_view.read_with(cx, |diff_view, cx| {
+            assert_eq!(
+                diff_view.tab_content_text(0, cx),
+                "text.txt @ L2:1-7 ↔ Clipboard"
             );
         });
+
+        // The source buffer is still the old side after swapping
+        buffer.update(cx, |buffer, cx| {
+            buffer.edit([(Point::new(1, 6)..Point::new(1, 6), "!")], None, cx);
+        });
+        cx.executor().advance_clock(RECALCULATE_DIFF_DEBOUNCE);
+        cx.executor().run_until_parked();
+
+        assert_state_with_diff(
+            &diff_view.read_with(cx, |diff_view, cx| {
+                diff_view.diff_editor.read(cx).rhs_editor().clone()
+            }),
+            cx,
+            &unindent(
+                "
+                - second!
+                + ˇ2nd
+                ",
+            ),
+        );
+    }
+
+    #[gpui::test]
+    async fn test_toggle_ignore_whitespace(cx: &mut TestAppContext) {
+        init_test(cx);
+
+        let fs = FakeFs::new(cx.executor());
+        fs.insert_tree(
+            path!("/test"),
+            json!({
+                "text.txt": "first\nsecond\nthird\n"
+            }),
+        )
+        .await;
+
+        let project = Project::test(fs, [path!("/test").as_ref()], cx).await;
+
+        let (multi_workspace, cx) =
+            cx.add_window_view(|window, cx| MultiWorkspace::test_new(project.clone(), window, cx));
+        let workspace = multi_workspace.read_with(cx, |mw, _| mw.workspace().clone());
+
+        let buffer = project
+            .update(cx, |project, cx| {
+                project.open_local_buffer(path!("/test/text.txt"), cx)
+            })
+            .await
+            .unwrap();
+
+        let editor = cx.new_window_entity(|window, cx| {
+            let mut editor = Editor::for_buffer(buffer.clone(), None, window, cx);
+            editor.change_selections(Default::default(), window, cx, |s| {
+                s.select_ranges([MultiBufferOffset(6)..MultiBufferOffset(12)])
+            });
+            editor
+        });
+
+        let diff_view = workspace
+            .update_in(cx, |workspace, window, cx| {
+                TextDiffView::open(
+                    &DiffClipboardWithSelectionData {
+                        clipboard_text: "second  \n".to_string(),
+                        editor,
+                    },
+                    workspace,
+                    window,
+                    cx,
+                )
+            })
+            .unwrap()
+            .await
+            .unwrap();
+        cx.executor().run_until_parked();
+
+        let rhs_editor = diff_view.read_with(cx, |diff_view, cx| {
+            diff_view.diff_editor.read(cx).rhs_editor().clone()
+        });
+        assert_state_with_diff(&rhs_editor, cx, "- second  \n+ ˇsecond\n");
+
+        diff_view.update(cx, |diff_view, cx| diff_view.toggle_ignore_whitespace(cx));
+        cx.executor().advance_clock(RECALCULATE_DIFF_DEBOUNCE);
+        cx.executor().run_until_parked();
+
+        assert_eq!(
+            diff_view.read_with(cx, |diff_view, cx| diff_view.ignore_whitespace(cx)),
+            IgnoreWhitespace::Trailing
+        );
+        assert_state_with_diff(&rhs_editor, cx, "ˇsecond\n");
+
+        diff_view.update(cx, |diff_view, cx| diff_view.toggle_ignore_whitespace(cx));
+        diff_view.update(cx, |diff_view, cx| diff_view.toggle_ignore_whitespace(cx));
+        cx.executor().advance_clock(RECALCULATE_DIFF_DEBOUNCE);
+        cx.executor().run_until_parked();
+
+        assert_eq!(
+            diff_view.read_with(cx, |diff_view, cx| diff_view.ignore_whitespace(cx)),
+            IgnoreWhitespace::None
+        );
+        assert_state_with_diff(&rhs_editor, cx, "- second  \n+ ˇsecond\n");
+    }
+
+    #[gpui::test]
+    async fn test_diff_highlights_changed_words_within_lines(cx: &mut TestAppContext) {
+        init_test(cx);
+
+        let fs = FakeFs::new(cx.executor());
+        fs.insert_tree(
+            path!("/test"),
+            json!({
+                "text.txt": "let value = compute(first_argument, 2);\n"
+            }),
+        )
+        .await;
+
+        let project = Project::test(fs, [path!("/test").as_ref()], cx).await;
+
+        let (multi_workspace, cx) =
+            cx.add_window_view(|window, cx| MultiWorkspace::test_new(project.clone(), window, cx));
+        let workspace = multi_workspace.read_with(cx, |mw, _| mw.workspace().clone());
+
+        let buffer = project
+            .update(cx, |project, cx| {
+                project.open_local_buffer(path!("/test/text.txt"), cx)
+            })
+            .await
+            .unwrap();
+
+        let editor =
+            cx.new_window_entity(|window, cx| Editor::for_buffer(buffer.clone(), None, window, cx));
+
+        let diff_view = workspace
+            .update_in(cx, |workspace, window, cx| {
+                TextDiffView::open(
+                    &DiffClipboardWithSelectionData {
+                        clipboard_text: "let value = compute(first_argument, 1);\n".to_string(),
+                        editor,
+                    },
+                    workspace,
+                    window,
+                    cx,
+                )
+            })
+            .unwrap()
+            .await
+            .unwrap();
+        cx.executor().run_until_parked();
+
+        let changed_words = diff_view.read_with(cx, |diff_view, cx| {
+            let rhs_editor = diff_view.diff_editor.read(cx).rhs_editor().clone();
+            let snapshot = rhs_editor.read(cx).buffer().read(cx).snapshot(cx);
+            snapshot
+                .diff_hunks()
+                .flat_map(|hunk| hunk.word_diffs)
+                .map(|range| snapshot.text_for_range(range).collect::<String>())
+                .collect::<Vec<_>>()
+        });
+        assert!(
+            changed_words.contains(&"2".to_string()),
+            "expected the changed argument to be highlighted, got {changed_words:?}"
+        );
+        assert!(
+            changed_words.iter().all(|word| !word.contains("compute")),
+            "expected only the changed word to be highlighted, got {changed_words:?}"
+        );
     }
 
     async fn base_test(
@@ -0,0 +1,1250 @@
+//! ThreeWayDiffView compares two buffers that were both derived from a common base buffer, and
+//! builds a merged result by taking each change from the left side, the right side, or both.
+//!
+//! Opened with [`ThreeWayDiffView::open_conflict`], it resolves a conflicted file instead: the
+//! sides are the two versions being merged, and saving writes the result to the file and stages
+//! it. Opened with [`ThreeWayDiffView::open_merge`], it resolves the versions `git mergetool`
+//! hands over, and saving writes the result to the file git reads it back from.
+
+use anyhow::{Context as _, Result};
+use collections::HashSet;
+use editor::{
+    Editor, EditorEvent, RowHighlightOptions,
+    display_map::{BlockContext, BlockPlacement, BlockProperties, BlockStyle, CustomBlockId},
+};
+use git::repository::RepoPath;
+use gpui::{
+    AnyElement, App, AppContext as _, AsyncWindowContext, Context, Entity, EventEmitter,
+    FocusHandle, Focusable, IntoElement, Render, Task, WeakEntity, Window,
+};
+use language::{Bias, Buffer, BufferSnapshot, Point};
+use project::{Project, ProjectPath, git_store::Repository};
+use std::{any::TypeId, ops::Range, path::PathBuf, sync::Arc};
+use ui::prelude::*;
+use workspace::{
+    Item, Workspace,
+    item::{ItemEvent, SaveOptions, TabContentParams},
+};
+
+/// A run of base lines that was changed on at least one side, along with the lines that replace
+/// them on each side.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MergeRegion {
+    pub kind: MergeRegionKind,
+    pub base_rows: Range<u32>,
+    pub left_rows: Range<u32>,
+    pub right_rows: Range<u32>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MergeRegionKind {
+    /// Only the left side changed these lines.
+    Left,
+    /// Only the right side changed these lines.
+    Right,
+    /// Both sides made the same change.
+    Both,
+    /// Both sides changed these lines differently.
+    Conflict,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TakeSide {
+    Left,
+    Right,
+    Both,
+}
+
+/// Groups the line changes each side made to `base` into regions, merging changes from the two
+/// sides whenever they overlap or touch.
+pub fn merge_regions(base: &str, left: &str, right: &str) -> Vec<MergeRegion> {
+    let left_edits = language::line_diff(base, left);
+    let right_edits = language::line_diff(base, right);
+    let mut regions = Vec::new();
+    let mut left_ix = 0;
+    let mut right_ix = 0;
+    let mut left_delta = 0;
+    let mut right_delta = 0;
+
+    loop {
+        let mut base_rows = match (left_edits.get(left_ix), right_edits.get(right_ix)) {
+            (None, None) => break,
+            (Some((left_rows, _)), None) => left_rows.clone(),
+            (None, Some((right_rows, _))) => right_rows.clone(),
+            (Some((left_rows, _)), Some((right_rows, _))) => {
+                if left_rows.start <= right_rows.start {
+                    left_rows.clone()
+                } else {
+                    right_rows.clone()
+                }
+            }
+        };
+        let left_start_ix = left_ix;
+        let right_start_ix = right_ix;
+        loop {
+            let mut grew = false;
+            for (edits, ix) in [(&left_edits, &mut left_ix), (&right_edits, &mut right_ix)] {
+                while let Some((rows, _)) = edits.get(*ix)
+                    && touches(rows, &base_rows)
+                {
+                    base_rows.start = base_rows.start.min(rows.start);
+                    base_rows.end = base_rows.end.max(rows.end);
+                    *ix += 1;
+                    grew = true;
+                }
+            }
+            if !grew {
+                break;
+            }
+        }
+
+        let left_rows = side_rows(
+            &left_edits[left_start_ix..left_ix],
+            &base_rows,
+            &mut left_delta,
+        );
+        let right_rows = side_rows(
+            &right_edits[right_start_ix..right_ix],
+            &base_rows,
+            &mut right_delta,
+        );
+        let kind = match (left_ix > left_start_ix, right_ix > right_start_ix) {
+            (true, false) => MergeRegionKind::Left,
+            (false, true) => MergeRegionKind::Right,
+            _ if rows_text(left, &left_rows) == rows_text(right, &right_rows) => {
+                MergeRegionKind::Both
+            }
+            _ => MergeRegionKind::Conflict,
+        };
+        regions.push(MergeRegion {
+            kind,
+            base_rows,
+            left_rows,
+            right_rows,
+        });
+    }
+    regions
+}
+
+fn touches(rows: &Range<u32>, region_rows: &Range<u32>) -> bool {
+    rows.start <= region_rows.end && region_rows.start <= rows.end
+}
+
+/// Maps `base_rows` onto one side, given that side's edits within the region. `delta` holds the
+/// change in line count from that side's preceding edits, and is advanced past the region.
+fn side_rows(
+    edits: &[(Range<u32>, Range<u32>)],
+    base_rows: &Range<u32>,
+    delta: &mut i64,
+) -> Range<u32> {
+    let start = i64::from(base_rows.start) + *delta;
+    for (old_rows, new_rows) in edits {
+        *delta +=
+            i64::from(new_rows.end - new_rows.start) - i64::from(old_rows.end - old_rows.start);
+    }
+    let end = i64::from(base_rows.end) + *delta;
+    start as u32..end as u32
+}
+
+fn rows_text(text: &str, rows: &Range<u32>) -> String {
+    text.split_inclusive('\n')
+        .skip(rows.start as usize)
+        .take((rows.end - rows.start) as usize)
+        .collect()
+}
+
+fn rows_anchor_range(snapshot: &BufferSnapshot, rows: &Range<u32>) -> Range<language::Anchor> {
+    let point = |row| snapshot.clip_point(Point::new(row, 0), Bias::Left);
+    snapshot.anchor_before(point(rows.start))..snapshot.anchor_after(point(rows.end))
+}
+
+enum LeftChanges {}
+enum RightChanges {}
+
+struct ResultRegion {
+    kind: MergeRegionKind,
+    /// The region's lines in the result buffer.
+    range: Range<language::Anchor>,
+    left_text: String,
+    right_text: String,
+    block_id: Option<CustomBlockId>,
+    resolved: bool,
+}
+
+/// The conflicted file a view resolves.
+enum ConflictTarget {
+    /// A file in a repository, which is staged once it is resolved.
+    Repository {
+        repository: WeakEntity<Repository>,
+        repo_path: RepoPath,
+        project_path: ProjectPath,
+        /// Whether one of the sides deleted the file, so that an empty result resolves the
+        /// conflict by deleting it.
+        deleted_on_one_side: bool,
+    },
+    /// The file `git mergetool` reads the result back from.
+    Output { path: PathBuf },
+}
+
+pub struct ThreeWayDiffView {
+    base_buffer: Entity<Buffer>,
+    left_buffer: Entity<Buffer>,
+    right_buffer: Entity<Buffer>,
+    result_buffer: Entity<Buffer>,
+    left_editor: Entity<Editor>,
+    result_editor: Entity<Editor>,
+    right_editor: Entity<Editor>,
+    regions: Vec<ResultRegion>,
+    conflict: Option<ConflictTarget>,
+    result_written: bool,
+}
+
+impl ThreeWayDiffView {
+    /// Opens a view merging the changes `left_buffer` and `right_buffer` made to `base_buffer`
+    /// into a new, unsaved result buffer.
+    pub fn open(
+        base_buffer: Entity<Buffer>,
+        left_buffer: Entity<Buffer>,
+        right_buffer: Entity<Buffer>,
+        workspace: WeakEntity<Workspace>,
+        window: &mut Window,
+        cx: &mut App,
+    ) -> Task<Result<Entity<Self>>> {
+        window.spawn(cx, async move |cx| {
+            let project = workspace.update(cx, |workspace, _| workspace.project().clone())?;
+            let language = base_buffer.read_with(cx, |buffer, _| buffer.language().cloned());
+            let result_buffer = project
+                .update(cx, |project, cx| project.create_buffer(language, false, cx))
+                .await?;
+            let base_text = base_buffer.read_with(cx, |buffer, _| buffer.text());
+            result_buffer.update(cx, |buffer, cx| {
+                buffer.set_text(base_text, cx);
+            });
+
+            workspace.update_in(cx, |workspace, window, cx| {
+                let view = cx.new(|cx| {
+                    ThreeWayDiffView::new(
+                        base_buffer,
+                        left_buffer,
+                        right_buffer,
+                        result_buffer,
+                        None,
+                        project,
+                        window,
+                        cx,
+                    )
+                });
+                workspace.active_pane().update(cx, |pane, cx| {
+                    pane.add_item(Box::new(view.clone()), true, true, None, window, cx);
+                });
+                view
+            })
+        })
+    }
+
+    /// Opens a view resolving the merge conflicts in `repo_path`, comparing our version on the
+    /// left with the version being merged in on the right. Lines changed on only one side are
+    /// taken automatically.
+    pub fn open_conflict(
+        repository: Entity<Repository>,
+        repo_path: RepoPath,
+        workspace: WeakEntity<Workspace>,
+        window: &mut Window,
+        cx: &mut App,
+    ) -> Task<Result<Entity<Self>>> {
+        let project_path = repository
+            .read(cx)
+            .repo_path_to_project_path(&repo_path, cx);
+        let stages = repository.update(cx, |repository, cx| {
+            repository.load_conflict_stages(repo_path.clone(), cx)
+        });
+        window.spawn(cx, async move |cx| {
+            let project_path = project_path.context("conflicted file is not in the project")?;
+            let stages = stages.await?;
+            let project = workspace.update(cx, |workspace, _| workspace.project().clone())?;
+            let file_buffer = project
+                .update(cx, |project, cx| {
+                    project.open_buffer(project_path.clone(), cx)
+                })
+                .await?;
+            let language = file_buffer.read_with(cx, |buffer, _| buffer.language().cloned());
+
+            let stage_buffer = |text: Option<String>, cx: &mut AsyncWindowContext| {
+                cx.new(|cx| {
+                    let mut buffer = Buffer::local(text.unwrap_or_default(), cx);
+                    buffer.set_language(language.clone(), cx);
+                    buffer
+                })
+            };
+            let deleted_on_one_side = stages.ours.is_none() || stages.theirs.is_none();
+            let base_buffer = stage_buffer(stages.base.clone(), cx);
+            let left_buffer = stage_buffer(stages.ours, cx);
+            let right_buffer = stage_buffer(stages.theirs, cx);
+            let result_buffer = project
+                .update(cx, |project, cx| {
+                    project.create_buffer(language.clone(), false, cx)
+                })
+                .await?;
+            result_buffer.update(cx, |buffer, cx| {
+                buffer.set_text(stages.base.unwrap_or_default(), cx);
+            });
+
+            workspace.update_in(cx, |workspace, window, cx| {
+                let view = cx.new(|cx| {
+                    let mut view = ThreeWayDiffView::new(
+                        base_buffer,
+                        left_buffer,
+                        right_buffer,
+                        result_buffer,
+                        Some(ConflictTarget::Repository {
+                            repository: repository.downgrade(),
+                            repo_path,
+                            project_path,
+                            deleted_on_one_side,
+                        }),
+                        project,
+                        window,
+                        cx,
+                    );
+                    view.resolve_non_conflicting_regions(cx);
+                    view
+                });
+                workspace.active_pane().update(cx, |pane, cx| {
+                    pane.add_item(Box::new(view.clone()), true, true, None, window, cx);
+                });
+                view
+            })
+        })
+    }
+
+    /// Opens a view resolving the conflicts between the `local` and `remote` versions of a file,
+    /// as `git mergetool` hands them over, and writing the result to `output_path`. Lines changed
+    /// on only one side are taken automatically.
+    pub fn open_merge(
+        base_path: PathBuf,
+        local_path: PathBuf,
+        remote_path: PathBuf,
+        output_path: PathBuf,
+        workspace: WeakEntity<Workspace>,
+        window: &mut Window,
+        cx: &mut App,
+    ) -> Task<Result<Entity<Self>>> {
+        window.spawn(cx, async move |cx| {
+            let project = workspace.update(cx, |workspace, _| workspace.project().clone())?;
+            let base_buffer = project
+                .update(cx, |project, cx| project.open_local_buffer(&base_path, cx))
+                .await?;
+            let left_buffer = project
+                .update(cx, |project, cx| project.open_local_buffer(&local_path, cx))
+                .await?;
+            let right_buffer = project
+                .update(cx, |project, cx| {
+                    project.open_local_buffer(&remote_path, cx)
+                })
+                .await?;
+            let language = left_buffer.read_with(cx, |buffer, _| buffer.language().cloned());
+            let result_buffer = project
+                .update(cx, |project, cx| project.create_buffer(language, false, cx))
+                .await?;
+            let base_text = base_buffer.read_with(cx, |buffer, _| buffer.text());
+            result_buffer.update(cx, |buffer, cx| {
+                buffer.set_text(base_text, cx);
+            });
+
+            workspace.update_in(cx, |workspace, window, cx| {
+                let view = cx.new(|cx| {
+                    let mut view = ThreeWayDiffView::new(
+                        base_buffer,
+                        left_buffer,
+                        right_buffer,
+                        result_buffer,
+                        Some(ConflictTarget::Output { path: output_path }),
+                        project,
+                        window,
+                        cx,
+                    );
+                    view.resolve_non_conflicting_regions(cx);
+                    view
+                });
+                workspace.active_pane().update(cx, |pane, cx| {
+                    pane.add_item(Box::new(view.clone()), true, true, None, window, cx);
+                });
+                view
+            })
+        })
+    }
+
+    fn new(
+        base_buffer: Entity<Buffer>,
+        left_buffer: Entity<Buffer>,
+        right_buffer: Entity<Buffer>,
+        result_buffer: Entity<Buffer>,
+        conflict: Option<ConflictTarget>,
+        project: Entity<Project>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Self {
+        let side_editor = |buffer: &Entity<Buffer>, window: &mut Window, cx: &mut Context<Self>| {
+            cx.new(|cx| {
+                let mut editor =
+                    Editor::for_buffer(buffer.clone(), Some(project.clone()), window, cx);
+                editor.set_read_only(true);
+                editor.disable_diagnostics(cx);
+                editor
+            })
+        };
+        let left_editor = side_editor(&left_buffer, window, cx);
+        let right_editor = side_editor(&right_buffer, window, cx);
+        let result_editor = cx.new(|cx| {
+            let mut editor =
+                Editor::for_buffer(result_buffer.clone(), Some(project.clone()), window, cx);
+            editor.disable_diagnostics(cx);
+            editor
+        });
+
+        let base_text = base_buffer.read(cx).text();
+        let left_text = left_buffer.read(cx).text();
+        let right_text = right_buffer.read(cx).text();
+        let merge_regions = merge_regions(&base_text, &left_text, &right_text);
+
+        let options = RowHighlightOptions {
+            include_gutter: true,
+            ..Default::default()
+        };
+        for (editor, left) in [(&left_editor, true), (&right_editor, false)] {
+            editor.update(cx, |editor, cx| {
+                let snapshot = editor.buffer().read(cx).snapshot(cx);
+                let colors = cx.theme().colors();
+                let color = if left {
+                    colors.version_control_conflict_marker_ours
+                } else {
+                    colors.version_control_conflict_marker_theirs
+                };
+                for region in &merge_regions {
+                    let (changed, rows) = if left {
+                        (region.kind != MergeRegionKind::Right, &region.left_rows)
+                    } else {
+                        (region.kind != MergeRegionKind::Left, &region.right_rows)
+                    };
+                    if !changed || rows.is_empty() {
+                        continue;
+                    }
+                    let range = snapshot.anchor_before(Point::new(rows.start, 0))
+                        ..snapshot.anchor_before(Point::new(rows.end - 1, 0));
+                    if left {
+                        editor.highlight_rows::<LeftChanges>(range, color, options, cx);
+                    } else {
+                        editor.highlight_rows::<RightChanges>(range, color, options, cx);
+                    }
+                }
+            });
+        }
+
+        let result_snapshot = result_buffer.read(cx).snapshot();
+        let mut regions = merge_regions
+            .iter()
+            .map(|region| ResultRegion {
+                kind: region.kind,
+                range: rows_anchor_range(&result_snapshot, &region.base_rows),
+                left_text: rows_text(&left_text, &region.left_rows),
+                right_text: rows_text(&right_text, &region.right_rows),
+                block_id: None,
+                resolved: false,
+            })
+            .collect::<Vec<_>>();
+
+        let this = cx.weak_entity();
+        let resolving_conflict = conflict.is_some();
+        let block_ids = result_editor.update(cx, |editor, cx| {
+            let snapshot = editor.buffer().read(cx).snapshot(cx);
+            let blocks = regions
+                .iter()
+                .enumerate()
+                .filter_map(|(ix, region)| {
+                    let anchor = snapshot.as_singleton_anchor(region.range.start)?;
+                    let kind = region.kind;
+                    let this = this.clone();
+                    Some(BlockProperties {
+                        placement: BlockPlacement::Above(anchor),
+                        height: Some(1),
+                        style: BlockStyle::Sticky,
+                        render: Arc::new(move |cx| {
+                            render_region_controls(ix, kind, resolving_conflict, &this, cx)
+                        }),
+                        priority: 0,
+                    })
+                })
+                .collect::<Vec<_>>();
+            editor.insert_blocks(blocks, None, cx)
+        });
+        for (region, block_id) in regions.iter_mut().zip(block_ids) {
+            region.block_id = Some(block_id);
+        }
+
+        Self {
+            base_buffer,
+            left_buffer,
+            right_buffer,
+            result_buffer,
+            left_editor,
+            result_editor,
+            right_editor,
+            regions,
+            conflict,
+            result_written: false,
+        }
+    }
+
+    pub fn result_buffer(&self) -> &Entity<Buffer> {
+        &self.result_buffer
+    }
+
+    /// Whether the resolved result has been saved to the conflicted file, without having been
+    /// edited since.
+    pub fn result_written(&self, cx: &App) -> bool {
+        self.result_written && !self.result_buffer.read(cx).is_dirty()
+    }
+
+    pub fn unresolved_region_count(&self) -> usize {
+        self.regions
+            .iter()
+            .filter(|region| !region.resolved)
+            .count()
+    }
+
+    /// Replaces the region's lines in the result with the text from the given side.
+    pub fn resolve_region(&mut self, ix: usize, side: TakeSide, cx: &mut Context<Self>) {
+        let Some(region) = self.regions.get_mut(ix).filter(|region| !region.resolved) else {
+            return;
+        };
+        region.resolved = true;
+        let text = match side {
+            TakeSide::Left => region.left_text.clone(),
+            TakeSide::Right => region.right_text.clone(),
+            TakeSide::Both => format!("{}{}", region.left_text, region.right_text),
+        };
+        let range = region.range.clone();
+        let block_id = region.block_id.take();
+        self.result_buffer.update(cx, |buffer, cx| {
+            buffer.edit([(range, text)], None, cx);
+        });
+        if let Some(block_id) = block_id {
+            self.result_editor.update(cx, |editor, cx| {
+                editor.remove_blocks(HashSet::from_iter([block_id]), None, cx);
+            });
+        }
+        cx.notify();
+    }
+
+    fn resolve_non_conflicting_regions(&mut self, cx: &mut Context<Self>) {
+        for ix in 0..self.regions.len() {
+            let side = match self.regions[ix].kind {
+                MergeRegionKind::Left | MergeRegionKind::Both => TakeSide::Left,
+                MergeRegionKind::Right => TakeSide::Right,
+                MergeRegionKind::Conflict => continue,
+            };
+            self.resolve_region(ix, side, cx);
+        }
+        // Taking the automatic resolutions shouldn't count as an unsaved change.
+        self.result_buffer.update(cx, |buffer, cx| {
+            let version = buffer.version();
+            buffer.did_save(version, None, cx);
+        });
+    }
+
+    fn side_titles(&self) -> (&'static str, &'static str) {
+        if self.conflict.is_some() {
+            ("Ours", "Theirs")
+        } else {
+            ("Left", "Right")
+        }
+    }
+
+    fn resolve_region_at_cursor(&mut self, side: TakeSide, cx: &mut Context<Self>) {
+        let cursor = self
+            .result_editor
+            .read(cx)
+            .selections
+            .newest_anchor()
+            .head()
+            .text_anchor;
+        let snapshot = self.result_buffer.read(cx).snapshot();
+        let Some(ix) = self.regions.iter().position(|region| {
+            !region.resolved
+                && region.range.start.cmp(&cursor, &snapshot).is_le()
+                && region.range.end.cmp(&cursor, &snapshot).is_ge()
+        }) else {
+            return;
+        };
+        self.resolve_region(ix, side, cx);
+    }
+}
+
+fn render_region_controls(
+    ix: usize,
+    kind: MergeRegionKind,
+    resolving_conflict: bool,
+    view: &WeakEntity<ThreeWayDiffView>,
+    cx: &mut BlockContext,
+) -> AnyElement {
+    let (take_left, take_right) = if resolving_conflict {
+        ("Take Ours", "Take Theirs")
+    } else {
+        ("Take Left", "Take Right")
+    };
+    let label = match kind {
+        MergeRegionKind::Left => "Changed on the left",
+        MergeRegionKind::Right => "Changed on the right",
+        MergeRegionKind::Both => "Changed the same way on both sides",
+        MergeRegionKind::Conflict => "Conflicting changes",
+    };
+    let take_button = |id: &'static str, label: &'static str, side: TakeSide| {
+        let view = view.clone();
+        Button::new((id, ix as u64), label)
+            .label_size(LabelSize::Small)
+            .on_click(move |_, _, cx| {
+                view.update(cx, |view, cx| view.resolve_region(ix, side, cx))
+                    .ok();
+            })
+    };
+
+    h_flex()
+        .id(cx.block_id)
+        .h(cx.line_height)
+        .ml(cx.margins.gutter.width)
+        .gap_1()
+        .bg(cx.theme().colors().editor_background)
+        .child(Label::new(label).size(LabelSize::Small).color(
+            if kind == MergeRegionKind::Conflict {
+                Color::Warning
+            } else {
+                Color::Muted
+            },
+        ))
+        .child(take_button("take-left", take_left, TakeSide::Left))
+        .child(take_button("take-right", take_right, TakeSide::Right))
+        .when(kind == MergeRegionKind::Conflict, |controls| {
+            controls.child(take_button("take-both", "Take Both", TakeSide::Both))
+        })
+        .into_any()
+}
+
+impl EventEmitter<EditorEvent> for ThreeWayDiffView {}
+
+impl Focusable for ThreeWayDiffView {
+    fn focus_handle(&self, cx: &App) -> FocusHandle {
+        self.result_editor.focus_handle(cx)
+    }
+}
+
+impl Item for ThreeWayDiffView {
+    type Event = EditorEvent;
+
+    fn tab_icon(&self, _window: &Window, _cx: &App) -> Option<Icon> {
+        Some(Icon::new(IconName::Diff).color(Color::Muted))
+    }
+
+    fn tab_content(&self, params: TabContentParams, _window: &Window, cx: &App) -> AnyElement {
+        Label::new(self.tab_content_text(params.detail.unwrap_or_default(), cx))
+            .color(if params.selected {
+                Color::Default
+            } else {
+                Color::Muted
+            })
+            .into_any_element()
+    }
+
+    fn tab_content_text(&self, _detail: usize, cx: &App) -> SharedString {
+        match &self.conflict {
+            Some(ConflictTarget::Repository { repo_path, .. }) => {
+                let file_name = repo_path.file_name().unwrap_or_default();
+                return format!("Resolve {file_name}").into();
+            }
+            Some(ConflictTarget::Output { path }) => {
+                let file_name = path.file_name().unwrap_or_default().to_string_lossy();
+                return format!("Resolve {file_name}").into();
+            }
+            None => {}
+        }
+        let file_name = |buffer: &Entity<Buffer>| {
+            buffer
+                .read(cx)
+                .file()
+                .and_then(|file| {
+                    Some(
+                        file.full_path(cx)
+                            .file_name()?
+                            .to_string_lossy()
+                            .to_string(),
+                    )
+                })
+                .unwrap_or_else(|| "untitled".into())
+        };
+        format!(
+            "{} ← {} → {}",
+            file_name(&self.left_buffer),
+            file_name(&self.base_buffer),
+            file_name(&self.right_buffer)
+        )
+        .into()
+    }
+
+    fn to_item_events(event: &EditorEvent, f: &mut dyn FnMut(ItemEvent)) {
+        Editor::to_item_events(event, f)
+    }
+
+    fn telemetry_event_text(&self) -> Option<&'static str> {
+        Some("Three-Way Diff View Opened")
+    }
+
+    fn act_as_type<'a>(
+        &'a self,
+        type_id: TypeId,
+        self_handle: &'a Entity<Self>,
+        _: &'a App,
+    ) -> Option<gpui::AnyEntity> {
+        if type_id == TypeId::of::<Self>() {
+            Some(self_handle.clone().into())
+        } else if type_id == TypeId::of::<Editor>() {
+            Some(self.result_editor.clone().into())
+        } else {
+            None
+        }
+    }
+
+    fn deactivated(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        self.result_editor
+            .update(cx, |editor, cx| editor.deactivated(window, cx));
+    }
+
+    fn is_dirty(&self, cx: &App) -> bool {
+        self.result_buffer.read(cx).is_dirty()
+    }
+
+    fn can_save(&self, _: &App) -> bool {
+        self.conflict.is_some()
+    }
+
+    fn save(
+        &mut self,
+        _options: SaveOptions,
+        project: Entity<Project>,
+        _window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Task<Result<()>> {
+        let Some(conflict) = &self.conflict else {
+            return Task::ready(Ok(()));
+        };
+        let unresolved_region_count = self.unresolved_region_count();
+        if unresolved_region_count > 0 {
+            return Task::ready(Err(anyhow::anyhow!(
+                "{unresolved_region_count} conflicts are still unresolved"
+            )));
+        }
+        let text = self.result_buffer.read(cx).text();
+        let (file_buffer, stage) = match conflict {
+            ConflictTarget::Repository {
+                repository,
+                repo_path,
+                project_path,
+                deleted_on_one_side,
+            } => (
+                project.update(cx, |project, cx| {
+                    project.open_buffer(project_path.clone(), cx)
+                }),
+                Some((
+                    repository.clone(),
+                    repo_path.clone(),
+                    project_path.clone(),
+                    *deleted_on_one_side && text.is_empty(),
+                )),
+            ),
+            ConflictTarget::Output { path } => (
+                project.update(cx, |project, cx| project.open_local_buffer(path, cx)),
+                None,
+            ),
+        };
+        let result_buffer = self.result_buffer.clone();
+        cx.spawn(async move |this, cx| {
+            let file_buffer = file_buffer.await?;
+            if file_buffer.read_with(cx, |buffer, _| buffer.is_dirty()) {
+                anyhow::bail!("the file has unsaved changes, save or discard them first");
+            }
+            match &stage {
+                Some((_, _, project_path, true)) => {
+                    project
+                        .update(cx, |project, cx| {
+                            project.delete_file(project_path.clone(), false, cx)
+                        })
+                        .context("conflicted file is not in the project")?
+                        .await?;
+                }
+                _ => {
+                    file_buffer.update(cx, |buffer, cx| buffer.set_text(text, cx));
+                    project
+                        .update(cx, |project, cx| project.save_buffer(file_buffer, cx))
+                        .await?;
+                }
+            }
+            // Staging a deleted file stages its deletion.
+            if let Some((repository, repo_path, _, _)) = stage {
+                let repository = repository.upgrade().context("repository was closed")?;
+                repository
+                    .update(cx, |repository, cx| {
+                        repository.stage_entries(vec![repo_path], cx)
+                    })
+                    .await?;
+            }
+            result_buffer.update(cx, |buffer, cx| {
+                let version = buffer.version();
+                buffer.did_save(version, None, cx);
+            });
+            this.update(cx, |this, cx| {
+                this.result_written = true;
+                cx.notify();
+            })
+        })
+    }
+
+    fn can_save_as(&self, _: &App) -> bool {
+        true
+    }
+
+    fn save_as(
+        &mut self,
+        project: Entity<Project>,
+        path: ProjectPath,
+        _window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Task<Result<()>> {
+        let result_buffer = self.result_buffer.clone();
+        project.update(cx, |project, cx| {
+            project.save_buffer_as(result_buffer, path, cx)
+        })
+    }
+}
+
+impl Render for ThreeWayDiffView {
+    fn render(&mut self, _: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let border_color = cx.theme().colors().border;
+        let column = |title: SharedString, editor: &Entity<Editor>| {
+            v_flex()
+                .flex_1()
+                .h_full()
+                .min_w_0()
+                .child(
+                    h_flex()
+                        .px_2()
+                        .py_1()
+                        .border_b_1()
+                        .border_color(border_color)
+                        .child(Label::new(title).size(LabelSize::Small).color(Color::Muted)),
+                )
+                .child(div().flex_1().min_h_0().child(editor.clone()))
+        };
+        let unresolved_region_count = self.unresolved_region_count();
+        let (left_title, right_title) = self.side_titles();
+
+        h_flex()
+            .size_full()
+            .on_action(cx.listener(|this, _: &git::TakeLeftSide, _, cx| {
+                this.resolve_region_at_cursor(TakeSide::Left, cx)
+            }))
+            .on_action(cx.listener(|this, _: &git::TakeRightSide, _, cx| {
+                this.resolve_region_at_cursor(TakeSide::Right, cx)
+            }))
+            .on_action(cx.listener(|this, _: &git::TakeBothSides, _, cx| {
+                this.resolve_region_at_cursor(TakeSide::Both, cx)
+            }))
+            .child(column(left_title.into(), &self.left_editor))
+            .child(
+                div()
+                    .flex_1()
+                    .h_full()
+                    .min_w_0()
+                    .border_x_1()
+                    .border_color(border_color)
+                    .child(column(
+                        format!("Result ({unresolved_region_count} unresolved)").into(),
+                        &self.result_editor,
+                    )),
+            )
+            .child(column(right_title.into(), &self.right_editor))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use git::repository::ConflictStages;
+    use gpui::TestAppContext;
+    use project::{FakeFs, Fs as _};
+    use settings::SettingsStore;
+    use std::path::Path;
+    use util::path;
+    use workspace::MultiWorkspace;
+
+    fn init_test(cx: &mut TestAppContext) {
+        cx.update(|cx| {
+            let settings_store = SettingsStore::test(cx);
+            cx.set_global(settings_store);
+            theme::init(theme::LoadThemes::JustBase, cx);
+        });
+    }
+
+    #[test]
+    fn test_merge_regions() {
+        let base = "a\nb\nc\nd\ne\nf\n";
+        let left = "a\nB\nc\nd\ne\nF\n";
+        let right = "a\nb\nc\nD\nD2\ne\nF2\n";
+        assert_eq!(
+            merge_regions(base, left, right),
+            vec![
+                MergeRegion {
+                    kind: MergeRegionKind::Left,
+                    base_rows: 1..2,
+                    left_rows: 1..2,
+                    right_rows: 1..2,
+                },
+                MergeRegion {
+                    kind: MergeRegionKind::Right,
+                    base_rows: 3..4,
+                    left_rows: 3..4,
+                    right_rows: 3..5,
+                },
+                MergeRegion {
+                    kind: MergeRegionKind::Conflict,
+                    base_rows: 5..6,
+                    left_rows: 5..6,
+                    right_rows: 6..7,
+                },
+            ]
+        );
+
+        let same_change = merge_regions("a\nb\n", "a\nc\n", "a\nc\n");
+        assert_eq!(same_change.len(), 1);
+        assert_eq!(same_change[0].kind, MergeRegionKind::Both);
+        assert!(merge_regions("a\n", "a\n", "a\n").is_empty());
+    }
+
+    #[gpui::test]
+    async fn test_resolve_regions(cx: &mut TestAppContext) {
+        init_test(cx);
+
+        let fs = FakeFs::new(cx.executor());
+        fs.insert_tree(
+            path!("/test"),
+            serde_json::json!({
+                "base.txt": "one\ntwo\nthree\nfour\n",
+                "left.txt": "one\nTWO\nthree\nfour left\n",
+                "right.txt": "one\ntwo\nthree\nfour right\n",
+            }),
+        )
+        .await;
+        let project = Project::test(fs, [path!("/test").as_ref()], cx).await;
+        let (multi_workspace, cx) =
+            cx.add_window_view(|window, cx| MultiWorkspace::test_new(project.clone(), window, cx));
+        let workspace = multi_workspace.read_with(cx, |mw, _| mw.workspace().clone());
+
+        let mut buffers = Vec::new();
+        for file_path in [
+            path!("/test/base.txt"),
+            path!("/test/left.txt"),
+            path!("/test/right.txt"),
+        ] {
+            let buffer = project
+                .update(cx, |project, cx| project.open_local_buffer(file_path, cx))
+                .await
+                .unwrap();
+            buffers.push(buffer);
+        }
+        let [base_buffer, left_buffer, right_buffer] = <[_; 3]>::try_from(buffers).unwrap();
+
+        let view = workspace
+            .update_in(cx, |workspace, window, cx| {
+                ThreeWayDiffView::open(
+                    base_buffer,
+                    left_buffer,
+                    right_buffer,
+                    workspace.weak_handle(),
+                    window,
+                    cx,
+                )
+            })
+            .await
+            .unwrap();
+
+        view.read_with(cx, |view, cx| {
+            assert_eq!(view.unresolved_region_count(), 2);
+            assert_eq!(
+                view.tab_content_text(0, cx),
+                "left.txt ← base.txt → right.txt"
+            );
+        });
+
+        view.update(cx, |view, cx| {
+            view.resolve_region(0, TakeSide::Left, cx);
+            view.resolve_region(1, TakeSide::Both, cx);
+        });
+        view.read_with(cx, |view, cx| {
+            assert_eq!(view.unresolved_region_count(), 0);
+            assert_eq!(
+                view.result_buffer().read(cx).text(),
+                "one\nTWO\nthree\nfour left\nfour right\n"
+            );
+        });
+    }
+
+    #[gpui::test]
+    async fn test_resolve_conflicted_file(cx: &mut TestAppContext) {
+        init_test(cx);
+
+        let fs = FakeFs::new(cx.executor());
+        fs.insert_tree(
+            path!("/project"),
+            serde_json::json!({
+                ".git": {},
+                "a.txt": "one\n<<<<<<< HEAD\ntwo ours\n=======\ntwo theirs\n>>>>>>> topic\nthree\n",
+            }),
+        )
+        .await;
+        let repo_path = RepoPath::new("a.txt").unwrap();
+        fs.with_git_state(Path::new(path!("/project/.git")), true, |state| {
+            state.conflict_stages.insert(
+                repo_path.clone(),
+                ConflictStages {
+                    base: Some("one\ntwo\nthree\n".into()),
+                    ours: Some("one\ntwo ours\nthree\n".into()),
+                    theirs: Some("ONE\ntwo theirs\nthree\n".into()),
+                },
+            );
+        })
+        .unwrap();
+        let project = Project::test(fs.clone(), [path!("/project").as_ref()], cx).await;
+        let (multi_workspace, cx) =
+            cx.add_window_view(|window, cx| MultiWorkspace::test_new(project.clone(), window, cx));
+        let workspace = multi_workspace.read_with(cx, |mw, _| mw.workspace().clone());
+        cx.run_until_parked();
+        let repository =
+            project.read_with(cx, |project, cx| project.active_repository(cx).unwrap());
+
+        let view = workspace
+            .update_in(cx, |workspace, window, cx| {
+                ThreeWayDiffView::open_conflict(
+                    repository,
+                    repo_path.clone(),
+                    workspace.weak_handle(),
+                    window,
+                    cx,
+                )
+            })
+            .await
+            .unwrap();
+
+        view.read_with(cx, |view, cx| {
+            assert_eq!(view.tab_content_text(0, cx), "Resolve a.txt");
+            assert_eq!(view.side_titles(), ("Ours", "Theirs"));
+            assert_eq!(view.unresolved_region_count(), 1);
+            assert!(!view.is_dirty(cx));
+        });
+
+        let save = view.update_in(cx, |view, window, cx| {
+            view.save(SaveOptions::default(), project.clone(), window, cx)
+        });
+        assert!(save.await.is_err());
+
+        let conflict_ix = view.read_with(cx, |view, _| {
+            view.regions
+                .iter()
+                .position(|region| !region.resolved)
+                .unwrap()
+        });
+        view.update(cx, |view, cx| {
+            view.resolve_region(conflict_ix, TakeSide::Left, cx)
+        });
+        view.update_in(cx, |view, window, cx| {
+            view.save(SaveOptions::default(), project.clone(), window, cx)
+        })
+        .await
+        .unwrap();
+
+        let resolved_text = "ONE\ntwo ours\nthree\n";
+        assert_eq!(
+            fs.load(path!("/project/a.txt").as_ref()).await.unwrap(),
+            resolved_text
+        );
+        let staged_text = fs
+            .with_git_state(Path::new(path!("/project/.git")), false, |state| {
+                state.index_contents.get(&repo_path).cloned()
+            })
+            .unwrap();
+        assert_eq!(staged_text.as_deref(), Some(resolved_text));
+        view.read_with(cx, |view, cx| assert!(!view.is_dirty(cx)));
+    }
+
+    #[gpui::test]
+    async fn test_resolve_conflict_with_deleted_side(cx: &mut TestAppContext) {
+        init_test(cx);
+
+        let fs = FakeFs::new(cx.executor());
+        fs.insert_tree(
+            path!("/project"),
+            serde_json::json!({
+                ".git": {},
+                "a.txt": "one\ntwo theirs\n",
+            }),
+        )
+        .await;
+        let repo_path = RepoPath::new("a.txt").unwrap();
+        fs.with_git_state(Path::new(path!("/project/.git")), true, |state| {
+            state.conflict_stages.insert(
+                repo_path.clone(),
+                ConflictStages {
+                    base: Some("one\ntwo\n".into()),
+                    ours: None,
+                    theirs: Some("one\ntwo theirs\n".into()),
+                },
+            );
+            state
+                .index_contents
+                .insert(repo_path.clone(), "one\ntwo theirs\n".into());
+        })
+        .unwrap();
+        let project = Project::test(fs.clone(), [path!("/project").as_ref()], cx).await;
+        let (multi_workspace, cx) =
+            cx.add_window_view(|window, cx| MultiWorkspace::test_new(project.clone(), window, cx));
+        let workspace = multi_workspace.read_with(cx, |mw, _| mw.workspace().clone());
+        cx.run_until_parked();
+        let repository =
+            project.read_with(cx, |project, cx| project.active_repository(cx).unwrap());
+
+        let view = workspace
+            .update_in(cx, |workspace, window, cx| {
+                ThreeWayDiffView::open_conflict(
+                    repository,
+                    repo_path.clone(),
+                    workspace.weak_handle(),
+                    window,
+                    cx,
+                )
+            })
+            .await
+            .unwrap();
+        let conflict_ix = view.read_with(cx, |view, _| {
+            view.regions
+                .iter()
+                .position(|region| !region.resolved)
+                .unwrap()
+        });
+        view.update(cx, |view, cx| {
+            view.resolve_region(conflict_ix, TakeSide::Left, cx)
+        });
+
+        // Unsaved edits to the file aren't overwritten.
+        let file_buffer = project
+            .update(cx, |project, cx| {
+                project.open_local_buffer(path!("/project/a.txt"), cx)
+            })
+            .await
+            .unwrap();
+        file_buffer.update(cx, |buffer, cx| buffer.edit([(0..0, "zero\n")], None, cx));
+        let save = view.update_in(cx, |view, window, cx| {
+            view.save(SaveOptions::default(), project.clone(), window, cx)
+        });
+        assert!(save.await.is_err());
+        file_buffer.update(cx, |buffer, cx| buffer.undo(cx));
+        project
+            .update(cx, |project, cx| project.save_buffer(file_buffer, cx))
+            .await
+            .unwrap();
+
+        // Taking the deleting side deletes the file and stages the deletion.
+        view.update_in(cx, |view, window, cx| {
+            view.save(SaveOptions::default(), project.clone(), window, cx)
+        })
+        .await
+        .unwrap();
+        cx.run_until_parked();
+        assert!(!fs.is_file(path!("/project/a.txt").as_ref()).await);
+        let staged_text = fs
+            .with_git_state(Path::new(path!("/project/.git")), false, |state| {
+                state.index_contents.get(&repo_path).cloned()
+            })
+            .unwrap();
+        assert_eq!(staged_text, None);
+    }
+
+    #[gpui::test]
+    async fn test_resolve_merge_tool_files(cx: &mut TestAppContext) {
+        init_test(cx);
+
+        let fs = FakeFs::new(cx.executor());
+        fs.insert_tree(
+            path!("/merge"),
+            serde_json::json!({
+                "a_BASE.txt": "one\ntwo\nthree\n",
+                "a_LOCAL.txt": "one\ntwo ours\nthree\n",
+                "a_REMOTE.txt": "ONE\ntwo theirs\nthree\n",
+                "a.txt": "one\n<<<<<<< HEAD\ntwo ours\n=======\ntwo theirs\n>>>>>>> topic\nthree\n",
+            }),
+        )
+        .await;
+        let project = Project::test(fs.clone(), [], cx).await;
+        let (multi_workspace, cx) =
+            cx.add_window_view(|window, cx| MultiWorkspace::test_new(project.clone(), window, cx));
+        let workspace = multi_workspace.read_with(cx, |mw, _| mw.workspace().clone());
+
+        let view = workspace
+            .update_in(cx, |workspace, window, cx| {
+                ThreeWayDiffView::open_merge(
+                    path!("/merge/a_BASE.txt").into(),
+                    path!("/merge/a_LOCAL.txt").into(),
+                    path!("/merge/a_REMOTE.txt").into(),
+                    path!("/merge/a.txt").into(),
+                    workspace.weak_handle(),
+                    window,
+                    cx,
+                )
+            })
+            .await
+            .unwrap();
+
+        view.read_with(cx, |view, cx| {
+            assert_eq!(view.tab_content_text(0, cx), "Resolve a.txt");
+            assert_eq!(view.unresolved_region_count(), 1);
+            assert!(!view.result_written(cx));
+        });
+
+        let conflict_ix = view.read_with(cx, |view, _| {
+            view.regions
+                .iter()
+                .position(|region| !region.resolved)
+                .unwrap()
+        });
+        view.update(cx, |view, cx| {
+            view.resolve_region(conflict_ix, TakeSide::Right, cx)
+        });
+        view.update_in(cx, |view, window, cx| {
+            view.save(SaveOptions::default(), project.clone(), window, cx)
+        })
+        .await
+        .unwrap();
+
+        assert_eq!(
+            fs.load(path!("/merge/a.txt").as_ref()).await.unwrap(),
+            "ONE\ntwo theirs\nthree\n"
+        );
+        view.read_with(cx, |view, cx| {
+            assert!(view.result_written(cx));
+            assert!(!view.is_dirty(cx));
+        });
+
+        view.update(cx, |view, cx| {
+            view.result_buffer
+                .update(cx, |buffer, cx| buffer.edit([(0..0, "zero\n")], None, cx));
+        });
+        view.read_with(cx, |view, cx| {
+            assert!(!view.result_written(cx));
+        });
+    }
+}
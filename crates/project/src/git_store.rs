@@ -6,6 +6,7 @@ pub mod pending_op;
 use crate::{
     ProjectEnvironment, ProjectItem, ProjectPath,
     buffer_store::{BufferStore, BufferStoreEvent},
+    project_settings::{AutoFetchSettings, ProjectSettings},
     trusted_worktrees::{
         PathTrust, TrustedWorktrees, TrustedWorktreesEvent, TrustedWorktreesStore,
     },
@@ -29,23 +30,33 @@ use futures::{
 };
 use git::{
     BuildPermalinkParams, GitHostingProviderRegistry, Oid, RunHook,
-    blame::Blame,
+    blame::{Blame, BlameOptions},
+    commit::CommitOverview,
+    gitignore::IgnoreRule,
+    index_flags::{FlaggedFile, IndexFlag},
+    line_history::LineHistoryEntry,
     parse_git_remote_url,
+    remote_progress::{RemoteProgress, RemoteProgressDelegate},
     repository::{
-        Branch, CommitDetails, CommitDiff, CommitFile, CommitOptions, DiffType, FetchOptions,
-        GitRepository, GitRepositoryCheckpoint, GraphCommitData, InitialGraphCommitData, LogOrder,
-        LogSource, PushOptions, Remote, RemoteCommandOutput, RepoPath, ResetMode,
-        UpstreamTrackingStatus, Worktree as GitWorktree,
+        BisectMark, BisectStatus, Branch, CommitDetails, CommitDiff, CommitFile, CommitLogEntry,
+        CommitOptions, CommitSignature, CommitSummary, ConflictStages, DiffType, FetchOptions,
+        GitRepository, GitRepositoryCheckpoint, GraphCommitData, InProgressOperation,
+        InProgressOperationKind, InitialGraphCommitData, LogOrder, LogSource, OperationResolution,
+        PullStrategy, PushOptions, RebaseTodoEntry, ReflogEntry, Remote, RemoteCommandOutput,
+        RemoteInfo, RepoPath, ResetMode, SignatureStatus, StashOptions, UpstreamTrackingStatus,
+        Worktree as GitWorktree,
     },
+    sparse_checkout::SparseCheckout,
     stash::{GitStash, StashEntry},
     status::{
-        self, DiffStat, DiffTreeType, FileStatus, GitSummary, StatusCode, TrackedStatus, TreeDiff,
-        TreeDiffStatus, UnmergedStatus, UnmergedStatusCode,
+        self, DiffStat, DiffTreeType, FileRename, FileStatus, GitSummary, StatusCode,
+        TrackedStatus, TreeDiff, TreeDiffStatus, UnmergedStatus, UnmergedStatusCode,
     },
+    submodule::Submodule,
 };
 use gpui::{
-    App, AppContext, AsyncApp, Context, Entity, EventEmitter, SharedString, Subscription, Task,
-    WeakEntity,
+    App, AppContext, AsyncApp, Context, Entity, EventEmitter, Global, SharedString, Subscription,
+    Task, WeakEntity,
 };
 use language::{
     Buffer, BufferEvent, Language, LanguageRegistry,
@@ -56,28 +67,32 @@ use pending_op::{PendingOp, PendingOpId, PendingOps, PendingOpsSummary};
 use postage::stream::Stream as _;
 use rpc::{
     AnyProtoClient, TypedEnvelope,
-    proto::{self, git_reset, split_repository_update},
+    proto::{
+        self, git_bisect_mark, git_reset, git_resolve_in_progress_operation,
+        split_repository_update,
+    },
 };
 use serde::Deserialize;
-use settings::WorktreeId;
+use settings::{Settings as _, SettingsLocation, WorktreeId};
 use smol::future::yield_now;
 use std::{
     cmp::Ordering,
     collections::{BTreeSet, HashSet, VecDeque, hash_map::Entry},
     future::Future,
+    hash::Hasher as _,
     mem,
-    ops::Range,
+    ops::{Range, RangeInclusive},
     path::{Path, PathBuf},
     str::FromStr,
     sync::{
         Arc,
         atomic::{self, AtomicU64},
     },
-    time::Instant,
+    time::{Duration, Instant},
 };
 use sum_tree::{Edit, SumTree, TreeMap};
 use task::Shell;
-use text::{Bias, BufferId};
+use text::{Bias, BufferId, Rope};
 use util::{
     ResultExt, debug_panic,
     paths::{PathStyle, SanitizedPath},
@@ -90,6 +105,10 @@ use worktree::{
 };
 use zeroize::Zeroize;
 
+/// How long statuses must stay put before renames are detected again, since detecting them
+/// diffs the whole working tree against HEAD.
+const RENAMES_DEBOUNCE: Duration = Duration::from_millis(500);
+
 pub struct GitStore {
     state: GitStoreState,
     buffer_store: Entity<BufferStore>,
@@ -102,9 +121,50 @@ pub struct GitStore {
         HashMap<(BufferId, DiffKind), Shared<Task<Result<Entity<BufferDiff>, Arc<anyhow::Error>>>>>,
     diffs: HashMap<BufferId, Entity<BufferGitState>>,
     shared_diffs: HashMap<proto::PeerId, HashMap<BufferId, SharedDiffs>>,
+    blame_cache: HashMap<(RepositoryId, RepoPath), CachedBlame>,
     _subscriptions: Vec<Subscription>,
 }
 
+/// The blame of all of a file, which stays valid until HEAD or the file's content changes. It
+/// outlives the file's buffer, so reopening the file doesn't blame it again.
+struct CachedBlame {
+    buffer_id: BufferId,
+    version: clock::Global,
+    content_hash: u64,
+    head_sha: Option<SharedString>,
+    ignore_revs: Vec<String>,
+    blame: Blame,
+}
+
+impl CachedBlame {
+    fn is_valid(
+        &self,
+        buffer_id: BufferId,
+        version: &clock::Global,
+        content: &Rope,
+        head_sha: &Option<SharedString>,
+        ignore_revs: &[String],
+    ) -> bool {
+        if self.head_sha != *head_sha || self.ignore_revs != ignore_revs {
+            return false;
+        }
+        // Versions of different buffers can't be compared, so a reopened file's content is.
+        if self.buffer_id == buffer_id {
+            self.version == *version
+        } else {
+            self.content_hash == hash_blame_content(content)
+        }
+    }
+}
+
+fn hash_blame_content(content: &Rope) -> u64 {
+    let mut hasher = std::hash::DefaultHasher::new();
+    for chunk in content.chunks() {
+        hasher.write(chunk.as_bytes());
+    }
+    hasher.finish()
+}
+
 #[derive(Default)]
 struct SharedDiffs {
     unstaged: Option<Entity<BufferDiff>>,
@@ -267,6 +327,7 @@ pub struct RepositoryId(pub u64);
 pub struct MergeDetails {
     pub merge_heads_by_conflicted_path: TreeMap<RepoPath, Vec<Option<SharedString>>>,
     pub message: Option<SharedString>,
+    pub operation: Option<InProgressOperation>,
 }
 
 #[derive(Clone)]
@@ -304,6 +365,29 @@ pub struct JobInfo {
     pub message: SharedString,
 }
 
+/// What the most recently run hook printed, collected while it runs.
+#[derive(Clone, Debug)]
+pub struct HookOutput {
+    pub hook: RunHook,
+    pub output: String,
+    pub status: HookStatus,
+}
+
+/// The push, pull, fetch or checkout a local repository is running.
+struct RemoteOperation {
+    id: u64,
+    progress: Option<RemoteProgress>,
+    /// Taken once the operation has been asked to stop.
+    cancel_tx: Option<oneshot::Sender<()>>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum HookStatus {
+    Running,
+    Succeeded,
+    Failed,
+}
+
 struct GraphCommitDataHandler {
     _task: Task<()>,
     commit_data_request: smol::channel::Sender<Oid>,
@@ -346,6 +430,29 @@ pub struct Repository {
     initial_graph_data: HashMap<(LogSource, LogOrder), InitialGitGraphData>,
     graph_commit_data_handler: GraphCommitHandlerState,
     commit_data: HashMap<Oid, CommitDataState>,
+    /// Results of verifying commit signatures, with `None` for unsigned commits.
+    commit_signatures: HashMap<SharedString, Option<CommitSignature>>,
+    hook_output: Option<HookOutput>,
+    remote_operation: Option<RemoteOperation>,
+    latest_remote_operation_id: u64,
+    /// Where the bisect started from this repository stands, until it is reset.
+    bisect: Option<BisectStatus>,
+    /// The branches as of the last time they were listed.
+    cached_branches: Option<Arc<[Branch]>>,
+    /// The remotes as of the last time they were listed.
+    cached_remotes: Option<Arc<[RemoteInfo]>>,
+    /// The sparse checkout as of the last time it was read, which happens whenever HEAD moves,
+    /// or `None` before it was first read.
+    sparse_checkout: Option<Option<SparseCheckout>>,
+    /// The renames and copies between HEAD and the working tree as of the last status update.
+    renames: Vec<FileRename>,
+    /// The HEAD and statuses the renames were last detected for.
+    renames_key: Option<RenamesKey>,
+    _reload_renames: Task<()>,
+    /// The files with index flags as of the last time they were listed, which happens whenever
+    /// HEAD moves.
+    flagged_files: Vec<FlaggedFile>,
+    _auto_fetch: Option<Task<()>>,
 }
 
 impl std::ops::Deref for Repository {
@@ -356,6 +463,19 @@ impl std::ops::Deref for Repository {
     }
 }
 
+/// When each repository was last auto-fetched, by its original repository path.
+#[derive(Default)]
+struct LastAutoFetches(HashMap<Arc<Path>, Instant>);
+
+impl Global for LastAutoFetches {}
+
+/// What renames are detected from: HEAD, and which files differ from it and how.
+#[derive(PartialEq, Eq)]
+struct RenamesKey {
+    head_sha: Option<SharedString>,
+    statuses: Vec<(RepoPath, FileStatus)>,
+}
+
 #[derive(Clone)]
 pub struct LocalRepositoryState {
     pub fs: Arc<dyn Fs>,
@@ -433,6 +553,12 @@ pub enum RepositoryEvent {
     GitWorktreeListChanged,
     PendingOpsChanged { pending_ops: SumTree<PendingOps> },
     GraphEvent((LogSource, LogOrder), GitGraphEvent),
+    HookOutputChanged,
+    RemoteProgressChanged,
+    BisectChanged,
+    SparseCheckoutChanged,
+    RenamesChanged,
+    IndexFlagsChanged,
 }
 
 #[derive(Clone, Debug)]
@@ -465,6 +591,7 @@ enum GitJobKey {
     ReloadBufferDiffBases,
     RefreshStatuses,
     ReloadGitState,
+    ReloadRenames,
 }
 
 impl GitStore {
@@ -533,6 +660,7 @@ impl GitStore {
             loading_diffs: HashMap::default(),
             shared_diffs: HashMap::default(),
             diffs: HashMap::default(),
+            blame_cache: HashMap::default(),
         }
     }
 
@@ -570,6 +698,26 @@ impl GitStore {
         client.add_entity_request_handler(Self::handle_git_diff);
         client.add_entity_request_handler(Self::handle_tree_diff);
         client.add_entity_request_handler(Self::handle_get_blob_content);
+        client.add_entity_request_handler(Self::handle_load_text_at_revision);
+        client.add_entity_request_handler(Self::handle_load_commit_template);
+        client.add_entity_request_handler(Self::handle_recent_commit_authors);
+        client.add_entity_request_handler(Self::handle_verify_commit_signatures);
+        client.add_entity_request_handler(Self::handle_load_conflict_stages);
+        client.add_entity_request_handler(Self::handle_resolve_in_progress_operation);
+        client.add_entity_request_handler(Self::handle_cherry_pick);
+        client.add_entity_request_handler(Self::handle_revert_commit);
+        client.add_entity_request_handler(Self::handle_bisect_start);
+        client.add_entity_request_handler(Self::handle_bisect_mark);
+        client.add_entity_request_handler(Self::handle_bisect_reset);
+        client.add_entity_request_handler(Self::handle_reflog);
+        client.add_entity_request_handler(Self::handle_commit_log);
+        client.add_entity_request_handler(Self::handle_commits_between);
+        client.add_entity_request_handler(Self::handle_checkout_revision);
+        client.add_entity_request_handler(Self::handle_stash_branch);
+        client.add_entity_request_handler(Self::handle_set_branch_upstream);
+        client.add_entity_request_handler(Self::handle_get_tags);
+        client.add_entity_request_handler(Self::handle_create_tag);
+        client.add_entity_request_handler(Self::handle_delete_tag);
         client.add_entity_request_handler(Self::handle_open_unstaged_diff);
         client.add_entity_request_handler(Self::handle_open_uncommitted_diff);
         client.add_entity_message_handler(Self::handle_update_diff_bases);
@@ -1160,51 +1308,133 @@ impl GitStore {
 
     /// Blames a buffer.
     pub fn blame_buffer(
-        &self,
+        &mut self,
         buffer: &Entity<Buffer>,
         version: Option<clock::Global>,
         cx: &mut Context<Self>,
     ) -> Task<Result<Option<Blame>>> {
-        let buffer = buffer.read(cx);
-        let Some((repo, repo_path)) =
-            self.repository_and_path_for_buffer_id(buffer.remote_id(), cx)
-        else {
+        self.blame_buffer_rows(buffer, version, None, cx)
+    }
+
+    /// Blames the given rows of a buffer, or all of it when `rows` is `None`. Blames of whole
+    /// buffers are cached until the buffer or HEAD changes.
+    pub fn blame_buffer_rows(
+        &mut self,
+        buffer: &Entity<Buffer>,
+        version: Option<clock::Global>,
+        rows: Option<Range<u32>>,
+        cx: &mut Context<Self>,
+    ) -> Task<Result<Option<Blame>>> {
+        let buffer_id = buffer.read(cx).remote_id();
+        let Some((repo, repo_path)) = self.repository_and_path_for_buffer_id(buffer_id, cx) else {
             return Task::ready(Err(anyhow!("failed to find a git repository for buffer")));
         };
+        let buffer = buffer.read(cx);
         let content = match &version {
             Some(version) => buffer.rope_for_version(version),
             None => buffer.as_rope().clone(),
         };
         let line_ending = buffer.line_ending();
         let version = version.unwrap_or(buffer.version());
-        let buffer_id = buffer.remote_id();
+        let options = BlameOptions {
+            ignore_revs: blame_ignore_revs(buffer, cx),
+            rows: rows.clone(),
+        };
+        let head_sha = repo
+            .read(cx)
+            .head_commit
+            .as_ref()
+            .map(|commit| commit.sha.clone());
+        let cache_key = (repo.read(cx).id, repo_path.clone());
+        if rows.is_none()
+            && let Some(cached) = self.blame_cache.get_mut(&cache_key)
+            && cached.is_valid(
+                buffer_id,
+                &version,
+                &content,
+                &head_sha,
+                &options.ignore_revs,
+            )
+        {
+            cached.buffer_id = buffer_id;
+            cached.version = version;
+            return Task::ready(Ok(Some(cached.blame.clone())));
+        }
+        let content_hash = rows.is_none().then(|| hash_blame_content(&content));
+        let ignore_revs = options.ignore_revs.clone();
 
         let repo = repo.downgrade();
-        cx.spawn(async move |_, cx| {
+        cx.spawn(async move |this, cx| {
             let repository_state = repo
                 .update(cx, |repo, _| repo.repository_state.clone())?
                 .await
                 .map_err(|err| anyhow::anyhow!(err))?;
-            match repository_state {
+            let blame = match repository_state {
                 RepositoryState::Local(LocalRepositoryState { backend, .. }) => backend
-                    .blame(repo_path.clone(), content, line_ending)
+                    .blame(repo_path.clone(), content, line_ending, options)
                     .await
                     .with_context(|| format!("Failed to blame {:?}", repo_path.as_ref()))
-                    .map(Some),
+                    .map(Some)?,
                 RepositoryState::Remote(RemoteRepositoryState { project_id, client }) => {
                     let response = client
                         .request(proto::BlameBuffer {
                             project_id: project_id.to_proto(),
                             buffer_id: buffer_id.into(),
                             version: serialize_version(&version),
+                            start_row: rows.as_ref().map(|rows| rows.start),
+                            end_row: rows.as_ref().map(|rows| rows.end),
                         })
                         .await?;
-                    Ok(deserialize_blame_buffer_response(response))
+                    deserialize_blame_buffer_response(response)
                 }
+            };
+            if let Some(content_hash) = content_hash
+                && let Some(blame) = &blame
+            {
+                this.update(cx, |this, _| {
+                    // Blames from before HEAD moved can't be used again.
+                    this.blame_cache.retain(|(repo_id, _), cached| {
+                        *repo_id != cache_key.0 || cached.head_sha == head_sha
+                    });
+                    this.blame_cache.insert(
+                        cache_key,
+                        CachedBlame {
+                            buffer_id,
+                            version,
+                            content_hash,
+                            head_sha,
+                            ignore_revs,
+                            blame: blame.clone(),
+                        },
+                    );
+                })?;
             }
+            Ok(blame)
         })
     }
 
+    /// Whether blaming all of the buffer would return a cached blame right away.
+    pub fn has_cached_blame(&self, buffer: &Entity<Buffer>, cx: &App) -> bool {
+        let buffer = buffer.read(cx);
+        let Some((repo, repo_path)) =
+            self.repository_and_path_for_buffer_id(buffer.remote_id(), cx)
+        else {
+            return false;
+        };
+        let repo = repo.read(cx);
+        let Some(cached) = self.blame_cache.get(&(repo.id, repo_path)) else {
+            return false;
+        };
+        let head_sha = repo.head_commit.as_ref().map(|commit| commit.sha.clone());
+        cached.is_valid(
+            buffer.remote_id(),
+            &buffer.version(),
+            buffer.as_rope(),
+            &head_sha,
+            &blame_ignore_revs(buffer, cx),
+        )
+    }
+
     pub fn file_history(
         &self,
         repo: &Entity<Repository>,
@@ -1229,10 +1459,13 @@ impl GitStore {
         cx.spawn(|_: &mut AsyncApp| async move { rx.await? })
     }
 
+    /// Builds a link to `selection` on the remote, pinned to the HEAD commit unless `at_head`,
+    /// which links to the remote's HEAD ref instead.
     pub fn get_permalink_to_line(
         &self,
         buffer: &Entity<Buffer>,
         selection: Range<u32>,
+        at_head: bool,
         cx: &mut App,
     ) -> Task<Result<url::Url>> {
         let Some(file) = File::from_dyn(buffer.read(cx).file()) else {
@@ -1257,7 +1490,7 @@ impl GitStore {
             let file_path = file.worktree.read(cx).absolutize(&file.path);
             return cx.spawn(async move |cx| {
                 let provider_registry = cx.update(GitHostingProviderRegistry::default_global);
-                get_permalink_in_rust_registry_src(provider_registry, file_path, selection)
+                get_permalink_in_rust_registry_src(provider_registry, file_path, selection, at_head)
                     .context("no permalink available")
             });
         };
@@ -1280,7 +1513,11 @@ impl GitStore {
                             .await
                             .with_context(|| format!("remote \"{remote}\" not found"))?;
 
-                        let sha = backend.head_sha().await.context("reading HEAD SHA")?;
+                        let sha = if at_head {
+                            "HEAD".to_string()
+                        } else {
+                            backend.head_sha().await.context("reading HEAD SHA")?
+                        };
 
                         let provider_registry =
                             cx.update(GitHostingProviderRegistry::default_global);
@@ -1303,6 +1540,7 @@ impl GitStore {
                                     start: selection.start as u64,
                                     end: selection.end as u64,
                                 }),
+                                at_head,
                             })
                             .await?;
 
@@ -1870,7 +2108,7 @@ impl GitStore {
             GitStoreState::Local { fs, .. } => {
                 let fs = fs.clone();
                 cx.background_executor()
-                    .spawn(async move { fs.git_clone(&repo, &path).await })
+                    .spawn(async move { fs.git_clone(&repo, &path, None).await })
             }
             GitStoreState::Remote {
                 upstream_client,
@@ -2066,6 +2304,7 @@ impl GitStore {
         let branch_name = envelope.payload.branch_name.into();
         let remote_branch_name = envelope.payload.remote_branch_name.into();
         let remote_name = envelope.payload.remote_name.into();
+        let follow_tags = envelope.payload.follow_tags;
 
         let remote_output = repository_handle
             .update(&mut cx, |repository_handle, cx| {
@@ -2074,6 +2313,7 @@ impl GitStore {
                     remote_branch_name,
                     remote_name,
                     options,
+                    follow_tags,
                     askpass,
                     cx,
                 )
@@ -2103,11 +2343,19 @@ impl GitStore {
 
         let branch_name = envelope.payload.branch_name.map(|name| name.into());
         let remote_name = envelope.payload.remote_name.into();
-        let rebase = envelope.payload.rebase;
+        // Peers that predate `strategy` only send `rebase`.
+        let strategy = match envelope.payload.strategy {
+            Some(_) => Some(match envelope.payload.strategy() {
+                proto::pull::PullStrategy::Merge => PullStrategy::Merge,
+                proto::pull::PullStrategy::Rebase => PullStrategy::Rebase,
+                proto::pull::PullStrategy::FastForwardOnly => PullStrategy::FastForwardOnly,
+            }),
+            None => envelope.payload.rebase.then_some(PullStrategy::Rebase),
+        };
 
         let remote_message = repository_handle
             .update(&mut cx, |repository_handle, cx| {
-                repository_handle.pull(branch_name, remote_name, rebase, askpass, cx)
+                repository_handle.pull(branch_name, remote_name, strategy, askpass, cx)
             })
             .await??;
 
@@ -2178,10 +2426,14 @@ impl GitStore {
             .into_iter()
             .map(|path| RepoPath::new(&path))
             .collect::<Result<Vec<_>>>()?;
+        let options = StashOptions {
+            message: envelope.payload.message,
+            include_untracked: !envelope.payload.exclude_untracked,
+        };
 
         repository_handle
             .update(&mut cx, |repository_handle, cx| {
-                repository_handle.stash_entries(entries, cx)
+                repository_handle.stash_entries(entries, options, cx)
             })
             .await?;
 
@@ -2242,6 +2494,24 @@ impl GitStore {
         Ok(proto::Ack {})
     }
 
+    async fn handle_stash_branch(
+        this: Entity<Self>,
+        envelope: TypedEnvelope<proto::GitStashBranch>,
+        mut cx: AsyncApp,
+    ) -> Result<proto::Ack> {
+        let repository_id = RepositoryId::from_proto(envelope.payload.repository_id);
+        let repository_handle = Self::repository_for_request(&this, repository_id, &mut cx)?;
+        let stash_index = envelope.payload.stash_index.map(|i| i as usize);
+
+        repository_handle
+            .update(&mut cx, |repository_handle, cx| {
+                repository_handle.stash_branch(envelope.payload.branch_name, stash_index, cx)
+            })
+            .await??;
+
+        Ok(proto::Ack {})
+    }
+
     async fn handle_set_index_text(
         this: Entity<Self>,
         envelope: TypedEnvelope<proto::SetIndexText>,
@@ -2301,6 +2571,17 @@ impl GitStore {
         let name = envelope.payload.name.map(SharedString::from);
         let email = envelope.payload.email.map(SharedString::from);
         let options = envelope.payload.options.unwrap_or_default();
+        let only_paths = options
+            .only_paths
+            .iter()
+            .map(|path| RepoPath::from_proto(path))
+            .collect::<Result<Vec<_>>>()?;
+        // Skipping the hooks is up to the host, whose hooks they are.
+        let allow_no_verify = cx.update(|cx| ProjectSettings::get_global(cx).git.allow_no_verify);
+        anyhow::ensure!(
+            !options.no_verify || allow_no_verify,
+            "skipping commit hooks is disabled by the host's `git.allow_no_verify` setting"
+        );
 
         repository_handle
             .update(&mut cx, |repository_handle, cx| {
@@ -2310,6 +2591,8 @@ impl GitStore {
                     CommitOptions {
                         amend: options.amend,
                         signoff: options.signoff,
+                        only_paths,
+                        no_verify: options.no_verify,
                     },
                     askpass,
                     cx,
@@ -2470,10 +2753,84 @@ impl GitStore {
         let repository_id = RepositoryId::from_proto(envelope.payload.repository_id);
         let repository_handle = Self::repository_for_request(&this, repository_id, &mut cx)?;
         let branch_name = envelope.payload.branch_name;
+        let base_branch = envelope.payload.base_branch;
+
+        repository_handle
+            .update(&mut cx, |repository_handle, _| {
+                repository_handle.create_branch(branch_name, base_branch)
+            })
+            .await??;
+
+        Ok(proto::Ack {})
+    }
+
+    async fn handle_set_branch_upstream(
+        this: Entity<Self>,
+        envelope: TypedEnvelope<proto::GitSetBranchUpstream>,
+        mut cx: AsyncApp,
+    ) -> Result<proto::Ack> {
+        let repository_id = RepositoryId::from_proto(envelope.payload.repository_id);
+        let repository_handle = Self::repository_for_request(&this, repository_id, &mut cx)?;
+
+        repository_handle
+            .update(&mut cx, |repository_handle, _| {
+                repository_handle
+                    .set_branch_upstream(envelope.payload.branch_name, envelope.payload.upstream)
+            })
+            .await??;
+
+        Ok(proto::Ack {})
+    }
+
+    async fn handle_get_tags(
+        this: Entity<Self>,
+        envelope: TypedEnvelope<proto::GitGetTags>,
+        mut cx: AsyncApp,
+    ) -> Result<proto::GitTagsResponse> {
+        let repository_id = RepositoryId::from_proto(envelope.payload.repository_id);
+        let repository_handle = Self::repository_for_request(&this, repository_id, &mut cx)?;
+
+        let tags = repository_handle
+            .update(&mut cx, |repository_handle, _| repository_handle.tags())
+            .await??;
+
+        Ok(proto::GitTagsResponse {
+            tags: tags.into_iter().map(|tag| tag.to_string()).collect(),
+        })
+    }
+
+    async fn handle_create_tag(
+        this: Entity<Self>,
+        envelope: TypedEnvelope<proto::GitCreateTag>,
+        mut cx: AsyncApp,
+    ) -> Result<proto::Ack> {
+        let repository_id = RepositoryId::from_proto(envelope.payload.repository_id);
+        let repository_handle = Self::repository_for_request(&this, repository_id, &mut cx)?;
 
         repository_handle
             .update(&mut cx, |repository_handle, _| {
-                repository_handle.create_branch(branch_name, None)
+                repository_handle.create_tag(
+                    envelope.payload.tag_name,
+                    envelope.payload.message,
+                    envelope.payload.target,
+                )
+            })
+            .await??;
+
+        Ok(proto::Ack {})
+    }
+
+    async fn handle_delete_tag(
+        this: Entity<Self>,
+        envelope: TypedEnvelope<proto::GitDeleteTag>,
+        mut cx: AsyncApp,
+    ) -> Result<proto::Ack> {
+        let repository_id = RepositoryId::from_proto(envelope.payload.repository_id);
+        let repository_handle = Self::repository_for_request(&this, repository_id, &mut cx)?;
+
+        repository_handle
+            .update(&mut cx, |repository_handle, _| {
+                repository_handle.delete_tag(envelope.payload.tag_name)
             })
             .await??;
 
@@ -2545,10 +2902,11 @@ impl GitStore {
         let repository_handle = Self::repository_for_request(&this, repository_id, &mut cx)?;
         let is_remote = envelope.payload.is_remote;
         let branch_name = envelope.payload.branch_name;
+        let force = envelope.payload.force;
 
         repository_handle
             .update(&mut cx, |repository_handle, _| {
-                repository_handle.delete_branch(is_remote, branch_name)
+                repository_handle.delete_branch(is_remote, branch_name, force)
             })
             .await??;
 
@@ -2650,12 +3008,125 @@ impl GitStore {
                     commit_timestamp: entry.commit_timestamp,
                     author_name: entry.author_name.to_string(),
                     author_email: entry.author_email.to_string(),
+                    signed: entry.signed,
+                    path: entry.path.map(|path| path.to_proto()),
+                    old_path: entry.old_path.map(|path| path.to_proto()),
                 })
                 .collect(),
             path: file_history.path.to_proto(),
         })
     }
 
+    async fn handle_reflog(
+        this: Entity<Self>,
+        envelope: TypedEnvelope<proto::GitReflog>,
+        mut cx: AsyncApp,
+    ) -> Result<proto::GitReflogResponse> {
+        let repository_id = RepositoryId::from_proto(envelope.payload.repository_id);
+        let repository_handle = Self::repository_for_request(&this, repository_id, &mut cx)?;
+        // Guests choose the limit, so it is capped to keep them from reading unbounded reflogs.
+        const MAX_REFLOG_ENTRIES: usize = 1000;
+        let limit = (envelope.payload.limit as usize).min(MAX_REFLOG_ENTRIES);
+
+        let entries = repository_handle
+            .update(&mut cx, |repository_handle, _| {
+                repository_handle.reflog(envelope.payload.reference, limit)
+            })
+            .await??;
+
+        Ok(proto::GitReflogResponse {
+            entries: entries
+                .into_iter()
+                .map(|entry| proto::git_reflog_response::Entry {
+                    sha: entry.sha.to_string(),
+                    selector: entry.selector.to_string(),
+                    message: entry.message.to_string(),
+                    timestamp: entry.timestamp,
+                })
+                .collect(),
+        })
+    }
+
+    async fn handle_commit_log(
+        this: Entity<Self>,
+        envelope: TypedEnvelope<proto::GitCommitLog>,
+        mut cx: AsyncApp,
+    ) -> Result<proto::GitCommitLogResponse> {
+        let repository_id = RepositoryId::from_proto(envelope.payload.repository_id);
+        let repository_handle = Self::repository_for_request(&this, repository_id, &mut cx)?;
+        // Guests choose the limit, so it is capped like the reflog's.
+        const MAX_COMMIT_LOG_ENTRIES: usize = 1000;
+        let limit = (envelope.payload.limit as usize).min(MAX_COMMIT_LOG_ENTRIES);
+
+        let entries = repository_handle
+            .update(&mut cx, |repository_handle, _| {
+                repository_handle.commit_log(
+                    envelope.payload.query,
+                    envelope.payload.skip as usize,
+                    limit,
+                )
+            })
+            .await??;
+
+        Ok(proto::GitCommitLogResponse {
+            entries: entries
+                .into_iter()
+                .map(|entry| proto::git_commit_log_response::Entry {
+                    sha: entry.sha.to_string(),
+                    subject: entry.subject.to_string(),
+                    commit_timestamp: entry.commit_timestamp,
+                    author_name: entry.author_name.to_string(),
+                    author_email: entry.author_email.to_string(),
+                    refs: entry.refs.iter().map(|name| name.to_string()).collect(),
+                })
+                .collect(),
+        })
+    }
+
+    async fn handle_commits_between(
+        this: Entity<Self>,
+        envelope: TypedEnvelope<proto::GitCommitsBetween>,
+        mut cx: AsyncApp,
+    ) -> Result<proto::GitCommitsBetweenResponse> {
+        let repository_id = RepositoryId::from_proto(envelope.payload.repository_id);
+        let repository_handle = Self::repository_for_request(&this, repository_id, &mut cx)?;
+
+        let commits = repository_handle
+            .update(&mut cx, |repository_handle, _| {
+                repository_handle.commits_between(envelope.payload.base, envelope.payload.head)
+            })
+            .await??;
+
+        Ok(proto::GitCommitsBetweenResponse {
+            commits: commits
+                .into_iter()
+                .map(|commit| proto::git_commits_between_response::Entry {
+                    sha: commit.sha.to_string(),
+                    subject: commit.subject.to_string(),
+                    commit_timestamp: commit.commit_timestamp,
+                    author_name: commit.author_name.to_string(),
+                    has_parent: commit.has_parent,
+                })
+                .collect(),
+        })
+    }
+
+    async fn handle_checkout_revision(
+        this: Entity<Self>,
+        envelope: TypedEnvelope<proto::GitCheckoutRevision>,
+        mut cx: AsyncApp,
+    ) -> Result<proto::Ack> {
+        let repository_id = RepositoryId::from_proto(envelope.payload.repository_id);
+        let repository_handle = Self::repository_for_request(&this, repository_id, &mut cx)?;
+
+        repository_handle
+            .update(&mut cx, |repository_handle, _| {
+                repository_handle.checkout_revision(envelope.payload.revision)
+            })
+            .await??;
+        Ok(proto::Ack {})
+    }
+
     async fn handle_reset(
         this: Entity<Self>,
         envelope: TypedEnvelope<proto::GitReset>,
@@ -2667,6 +3138,8 @@ impl GitStore {
         let mode = match envelope.payload.mode() {
             git_reset::ResetMode::Soft => ResetMode::Soft,
             git_reset::ResetMode::Mixed => ResetMode::Mixed,
+            git_reset::ResetMode::Hard => ResetMode::Hard,
+            git_reset::ResetMode::Merge => ResetMode::Merge,
         };
 
         repository_handle
@@ -2883,52 +3356,255 @@ impl GitStore {
         Ok(proto::GetBlobContentResponse { content })
     }
 
-    async fn handle_open_unstaged_diff(
+    async fn handle_load_text_at_revision(
         this: Entity<Self>,
-        request: TypedEnvelope<proto::OpenUnstagedDiff>,
+        envelope: TypedEnvelope<proto::GitLoadTextAtRevision>,
         mut cx: AsyncApp,
-    ) -> Result<proto::OpenUnstagedDiffResponse> {
-        let buffer_id = BufferId::new(request.payload.buffer_id)?;
-        let diff = this
-            .update(&mut cx, |this, cx| {
-                let buffer = this.buffer_store.read(cx).get(buffer_id)?;
-                Some(this.open_unstaged_diff(buffer, cx))
+    ) -> Result<proto::GitLoadTextAtRevisionResponse> {
+        let repository_id = RepositoryId::from_proto(envelope.payload.repository_id);
+        let repository_handle = Self::repository_for_request(&this, repository_id, &mut cx)?;
+        let path = RepoPath::from_proto(&envelope.payload.path)?;
+        let content = repository_handle
+            .update(&mut cx, |repository_handle, cx| {
+                repository_handle.load_text_at_revision(envelope.payload.revision, path, cx)
             })
-            .context("missing buffer")?
             .await?;
-        this.update(&mut cx, |this, _| {
-            let shared_diffs = this
-                .shared_diffs
-                .entry(request.original_sender_id.unwrap_or(request.sender_id))
-                .or_default();
-            shared_diffs.entry(buffer_id).or_default().unstaged = Some(diff.clone());
-        });
-        let staged_text = diff.read_with(&cx, |diff, cx| diff.base_text_string(cx));
-        Ok(proto::OpenUnstagedDiffResponse { staged_text })
+        Ok(proto::GitLoadTextAtRevisionResponse { content })
     }
 
-    async fn handle_open_uncommitted_diff(
+    async fn handle_load_conflict_stages(
         this: Entity<Self>,
-        request: TypedEnvelope<proto::OpenUncommittedDiff>,
+        envelope: TypedEnvelope<proto::GitLoadConflictStages>,
         mut cx: AsyncApp,
-    ) -> Result<proto::OpenUncommittedDiffResponse> {
-        let buffer_id = BufferId::new(request.payload.buffer_id)?;
-        let diff = this
-            .update(&mut cx, |this, cx| {
-                let buffer = this.buffer_store.read(cx).get(buffer_id)?;
-                Some(this.open_uncommitted_diff(buffer, cx))
+    ) -> Result<proto::GitLoadConflictStagesResponse> {
+        let repository_id = RepositoryId::from_proto(envelope.payload.repository_id);
+        let repository_handle = Self::repository_for_request(&this, repository_id, &mut cx)?;
+        let path = RepoPath::from_proto(&envelope.payload.path)?;
+        let stages = repository_handle
+            .update(&mut cx, |repository_handle, cx| {
+                repository_handle.load_conflict_stages(path, cx)
             })
-            .context("missing buffer")?
             .await?;
-        this.update(&mut cx, |this, _| {
-            let shared_diffs = this
-                .shared_diffs
-                .entry(request.original_sender_id.unwrap_or(request.sender_id))
-                .or_default();
-            shared_diffs.entry(buffer_id).or_default().uncommitted = Some(diff.clone());
-        });
-        Ok(diff.read_with(&cx, |diff, cx| {
-            use proto::open_uncommitted_diff_response::Mode;
+        Ok(proto::GitLoadConflictStagesResponse {
+            base: stages.base,
+            ours: stages.ours,
+            theirs: stages.theirs,
+        })
+    }
+
+    async fn handle_load_commit_template(
+        this: Entity<Self>,
+        envelope: TypedEnvelope<proto::GitLoadCommitTemplate>,
+        mut cx: AsyncApp,
+    ) -> Result<proto::GitLoadCommitTemplateResponse> {
+        let repository_id = RepositoryId::from_proto(envelope.payload.repository_id);
+        let repository_handle = Self::repository_for_request(&this, repository_id, &mut cx)?;
+        // The template is always resolved from the host's own settings and git config, so that
+        // guests can't read arbitrary files on the host.
+        let template = repository_handle
+            .update(&mut cx, |repository_handle, cx| {
+                let configured_path = ProjectSettings::get_global(cx).git.commit_template.clone();
+                repository_handle.load_commit_template(configured_path, cx)
+            })
+            .await?;
+        Ok(proto::GitLoadCommitTemplateResponse { template })
+    }
+
+    async fn handle_recent_commit_authors(
+        this: Entity<Self>,
+        envelope: TypedEnvelope<proto::GitRecentCommitAuthors>,
+        mut cx: AsyncApp,
+    ) -> Result<proto::GitRecentCommitAuthorsResponse> {
+        let repository_id = RepositoryId::from_proto(envelope.payload.repository_id);
+        let repository_handle = Self::repository_for_request(&this, repository_id, &mut cx)?;
+        let authors = repository_handle
+            .update(&mut cx, |repository_handle, cx| {
+                repository_handle.recent_commit_authors(envelope.payload.max_commits as usize, cx)
+            })
+            .await?;
+        Ok(proto::GitRecentCommitAuthorsResponse {
+            authors: authors
+                .into_iter()
+                .map(
+                    |(name, email)| proto::git_recent_commit_authors_response::CommitAuthor {
+                        name: name.to_string(),
+                        email: email.to_string(),
+                    },
+                )
+                .collect(),
+        })
+    }
+
+    async fn handle_verify_commit_signatures(
+        this: Entity<Self>,
+        envelope: TypedEnvelope<proto::GitVerifyCommitSignatures>,
+        mut cx: AsyncApp,
+    ) -> Result<proto::GitVerifyCommitSignaturesResponse> {
+        let repository_id = RepositoryId::from_proto(envelope.payload.repository_id);
+        let repository_handle = Self::repository_for_request(&this, repository_id, &mut cx)?;
+        let shas = envelope.payload.shas.into_iter().map(Into::into).collect();
+        let signatures = repository_handle
+            .update(&mut cx, |repository_handle, cx| {
+                repository_handle.verify_commit_signatures(shas, cx)
+            })
+            .await?;
+        Ok(proto::GitVerifyCommitSignaturesResponse {
+            signatures: signatures
+                .into_iter()
+                .map(|(sha, signature)| commit_signature_to_proto(sha, signature))
+                .collect(),
+        })
+    }
+
+    async fn handle_cherry_pick(
+        this: Entity<Self>,
+        envelope: TypedEnvelope<proto::GitCherryPick>,
+        mut cx: AsyncApp,
+    ) -> Result<proto::GitCherryPickResponse> {
+        let repository_id = RepositoryId::from_proto(envelope.payload.repository_id);
+        let repository_handle = Self::repository_for_request(&this, repository_id, &mut cx)?;
+        let stopped = repository_handle
+            .update(&mut cx, |repository_handle, _| {
+                repository_handle.cherry_pick(envelope.payload.shas, envelope.payload.record_origin)
+            })
+            .await??;
+        Ok(proto::GitCherryPickResponse { stopped })
+    }
+
+    async fn handle_revert_commit(
+        this: Entity<Self>,
+        envelope: TypedEnvelope<proto::GitRevertCommit>,
+        mut cx: AsyncApp,
+    ) -> Result<proto::GitRevertCommitResponse> {
+        let repository_id = RepositoryId::from_proto(envelope.payload.repository_id);
+        let repository_handle = Self::repository_for_request(&this, repository_id, &mut cx)?;
+        let message = repository_handle
+            .update(&mut cx, |repository_handle, _| {
+                repository_handle.revert_commit(envelope.payload.sha, envelope.payload.no_commit)
+            })
+            .await??;
+        Ok(proto::GitRevertCommitResponse { message })
+    }
+
+    async fn handle_bisect_start(
+        this: Entity<Self>,
+        envelope: TypedEnvelope<proto::GitBisectStart>,
+        mut cx: AsyncApp,
+    ) -> Result<proto::GitBisectResponse> {
+        let repository_id = RepositoryId::from_proto(envelope.payload.repository_id);
+        let repository_handle = Self::repository_for_request(&this, repository_id, &mut cx)?;
+        let status = repository_handle
+            .update(&mut cx, |repository_handle, cx| {
+                repository_handle.bisect_start(envelope.payload.bad, envelope.payload.good, cx)
+            })
+            .await??;
+        Ok(bisect_status_to_proto(status))
+    }
+
+    async fn handle_bisect_mark(
+        this: Entity<Self>,
+        envelope: TypedEnvelope<proto::GitBisectMark>,
+        mut cx: AsyncApp,
+    ) -> Result<proto::GitBisectResponse> {
+        let repository_id = RepositoryId::from_proto(envelope.payload.repository_id);
+        let repository_handle = Self::repository_for_request(&this, repository_id, &mut cx)?;
+        let mark = match envelope.payload.mark() {
+            git_bisect_mark::Mark::Good => BisectMark::Good,
+            git_bisect_mark::Mark::Bad => BisectMark::Bad,
+            git_bisect_mark::Mark::Skip => BisectMark::Skip,
+        };
+        let status = repository_handle
+            .update(&mut cx, |repository_handle, cx| {
+                repository_handle.bisect_mark(mark, cx)
+            })
+            .await??;
+        Ok(bisect_status_to_proto(status))
+    }
+
+    async fn handle_bisect_reset(
+        this: Entity<Self>,
+        envelope: TypedEnvelope<proto::GitBisectReset>,
+        mut cx: AsyncApp,
+    ) -> Result<proto::Ack> {
+        let repository_id = RepositoryId::from_proto(envelope.payload.repository_id);
+        let repository_handle = Self::repository_for_request(&this, repository_id, &mut cx)?;
+        repository_handle
+            .update(&mut cx, |repository_handle, cx| {
+                repository_handle.bisect_reset(cx)
+            })
+            .await??;
+        Ok(proto::Ack {})
+    }
+
+    async fn handle_resolve_in_progress_operation(
+        this: Entity<Self>,
+        envelope: TypedEnvelope<proto::GitResolveInProgressOperation>,
+        mut cx: AsyncApp,
+    ) -> Result<proto::GitResolveInProgressOperationResponse> {
+        let repository_id = RepositoryId::from_proto(envelope.payload.repository_id);
+        let repository_handle = Self::repository_for_request(&this, repository_id, &mut cx)?;
+        let kind = proto_to_in_progress_operation_kind(envelope.payload.kind());
+        let resolution = match envelope.payload.resolution() {
+            git_resolve_in_progress_operation::Resolution::Continue => {
+                OperationResolution::Continue
+            }
+            git_resolve_in_progress_operation::Resolution::Skip => OperationResolution::Skip,
+            git_resolve_in_progress_operation::Resolution::Abort => OperationResolution::Abort,
+        };
+        let in_progress = repository_handle
+            .update(&mut cx, |repository_handle, _| {
+                repository_handle.resolve_in_progress_operation(kind, resolution)
+            })
+            .await??;
+        Ok(proto::GitResolveInProgressOperationResponse { in_progress })
+    }
+
+    async fn handle_open_unstaged_diff(
+        this: Entity<Self>,
+        request: TypedEnvelope<proto::OpenUnstagedDiff>,
+        mut cx: AsyncApp,
+    ) -> Result<proto::OpenUnstagedDiffResponse> {
+        let buffer_id = BufferId::new(request.payload.buffer_id)?;
+        let diff = this
+            .update(&mut cx, |this, cx| {
+                let buffer = this.buffer_store.read(cx).get(buffer_id)?;
+                Some(this.open_unstaged_diff(buffer, cx))
+            })
+            .context("missing buffer")?
+            .await?;
+        this.update(&mut cx, |this, _| {
+            let shared_diffs = this
+                .shared_diffs
+                .entry(request.original_sender_id.unwrap_or(request.sender_id))
+                .or_default();
+            shared_diffs.entry(buffer_id).or_default().unstaged = Some(diff.clone());
+        });
+        let staged_text = diff.read_with(&cx, |diff, cx| diff.base_text_string(cx));
+        Ok(proto::OpenUnstagedDiffResponse { staged_text })
+    }
+
+    async fn handle_open_uncommitted_diff(
+        this: Entity<Self>,
+        request: TypedEnvelope<proto::OpenUncommittedDiff>,
+        mut cx: AsyncApp,
+    ) -> Result<proto::OpenUncommittedDiffResponse> {
+        let buffer_id = BufferId::new(request.payload.buffer_id)?;
+        let diff = this
+            .update(&mut cx, |this, cx| {
+                let buffer = this.buffer_store.read(cx).get(buffer_id)?;
+                Some(this.open_uncommitted_diff(buffer, cx))
+            })
+            .context("missing buffer")?
+            .await?;
+        this.update(&mut cx, |this, _| {
+            let shared_diffs = this
+                .shared_diffs
+                .entry(request.original_sender_id.unwrap_or(request.sender_id))
+                .or_default();
+            shared_diffs.entry(buffer_id).or_default().uncommitted = Some(diff.clone());
+        });
+        Ok(diff.read_with(&cx, |diff, cx| {
+            use proto::open_uncommitted_diff_response::Mode;
 
             let unstaged_diff = diff.secondary_diff();
             let index_snapshot = unstaged_diff.and_then(|diff| {
@@ -2994,6 +3670,11 @@ impl GitStore {
     ) -> Result<proto::BlameBufferResponse> {
         let buffer_id = BufferId::new(envelope.payload.buffer_id)?;
         let version = deserialize_version(&envelope.payload.version);
+        let rows = envelope
+            .payload
+            .start_row
+            .zip(envelope.payload.end_row)
+            .map(|(start_row, end_row)| start_row..end_row);
         let buffer = this.read_with(&cx, |this, cx| {
             this.buffer_store.read(cx).get_existing(buffer_id)
         })?;
@@ -3004,7 +3685,7 @@ impl GitStore {
             .await?;
         let blame = this
             .update(&mut cx, |this, cx| {
-                this.blame_buffer(&buffer, Some(version), cx)
+                this.blame_buffer_rows(&buffer, Some(version), rows, cx)
             })
             .await?;
         Ok(serialize_blame_buffer_response(blame))
@@ -3016,6 +3697,7 @@ impl GitStore {
         mut cx: AsyncApp,
     ) -> Result<proto::GetPermalinkToLineResponse> {
         let buffer_id = BufferId::new(envelope.payload.buffer_id)?;
+        let at_head = envelope.payload.at_head;
         // let version = deserialize_version(&envelope.payload.version);
         let selection = {
             let proto_selection = envelope
@@ -3029,7 +3711,7 @@ impl GitStore {
         })?;
         let permalink = this
             .update(&mut cx, |this, cx| {
-                this.get_permalink_to_line(&buffer, selection, cx)
+                this.get_permalink_to_line(&buffer, selection, at_head, cx)
             })
             .await?;
         Ok(proto::GetPermalinkToLineResponse {
@@ -3600,6 +4282,7 @@ impl RepositorySnapshot {
                 .map(|(repo_path, _)| repo_path.to_proto())
                 .collect(),
             merge_message: self.merge.message.as_ref().map(|msg| msg.to_string()),
+            in_progress_operation: self.merge.operation.map(in_progress_operation_to_proto),
             project_id,
             id: self.id.to_proto(),
             abs_path: self.work_directory_abs_path.to_string_lossy().into_owned(),
@@ -3681,6 +4364,7 @@ impl RepositorySnapshot {
                 .map(|(path, _)| path.to_proto())
                 .collect(),
             merge_message: self.merge.message.as_ref().map(|msg| msg.to_string()),
+            in_progress_operation: self.merge.operation.map(in_progress_operation_to_proto),
             project_id,
             id: self.id.to_proto(),
             abs_path: self.work_directory_abs_path.to_string_lossy().into_owned(),
@@ -3731,6 +4415,12 @@ impl RepositorySnapshot {
         self.statuses_by_path.iter().cloned()
     }
 
+    /// Whether HEAD points at a commit rather than a branch, so new commits don't belong to any
+    /// branch.
+    pub fn is_head_detached(&self) -> bool {
+        self.branch.is_none() && self.head_commit.is_some()
+    }
+
     pub fn status_summary(&self) -> GitSummary {
         self.statuses_by_path.summary().item_summary
     }
@@ -3824,6 +4514,7 @@ impl MergeDetails {
     ) -> Result<bool> {
         log::debug!("load merge details");
         self.message = backend.merge_message().await.map(SharedString::from);
+        let operation = backend.in_progress_operation().await;
         let heads = backend
             .revparse_batch(vec![
                 "MERGE_HEAD".into(),
@@ -3839,7 +4530,8 @@ impl MergeDetails {
             .map(|opt| opt.map(SharedString::from))
             .collect::<Vec<_>>();
 
-        let mut conflicts_changed = false;
+        let mut conflicts_changed = operation != self.operation;
+        self.operation = operation;
 
         // Record the merge state for newly conflicted paths
         for path in &current_conflicted_paths {
@@ -3931,15 +4623,46 @@ impl Repository {
             })
             .shared();
 
-        cx.subscribe_self(move |this, event: &RepositoryEvent, _| match event {
+        cx.subscribe_self(move |this, event: &RepositoryEvent, cx| match event {
             RepositoryEvent::BranchChanged => {
                 if this.scan_id > 1 {
                     this.initial_graph_data.clear();
                 }
+                let reload_sparse_checkout = this.reload_sparse_checkout();
+                let reload_flagged_files = this.reload_flagged_files();
+                let reload_cached_branches = this.reload_cached_branches();
+                cx.spawn(async move |_, _| {
+                    if let Ok(result) = reload_sparse_checkout.await {
+                        result.log_err();
+                    }
+                    if let Ok(result) = reload_flagged_files.await {
+                        result.log_err();
+                    }
+                    if let Ok(result) = reload_cached_branches.await {
+                        result.log_err();
+                    }
+                })
+                .detach();
+            }
+            RepositoryEvent::StatusesChanged => {
+                // Renames are detected right away the first time, and once statuses settle after.
+                let debounce = this.renames_key.is_some();
+                this._reload_renames = cx.spawn(async move |this, cx| {
+                    if debounce {
+                        cx.background_executor().timer(RENAMES_DEBOUNCE).await;
+                    }
+                    let Ok(reload) = this.update(cx, |this, _| this.reload_renames()) else {
+                        return;
+                    };
+                    if let Ok(result) = reload.await {
+                        result.log_err();
+                    }
+                });
             }
             _ => {}
         })
         .detach();
+        let auto_fetch = cx.spawn(async move |this, cx| Self::auto_fetch(this, cx).await);
 
         Repository {
             this: cx.weak_entity(),
@@ -3956,7 +4679,20 @@ impl Repository {
             active_jobs: Default::default(),
             initial_graph_data: Default::default(),
             commit_data: Default::default(),
+            commit_signatures: Default::default(),
+            hook_output: None,
+            remote_operation: None,
+            latest_remote_operation_id: 0,
+            bisect: None,
+            cached_branches: None,
+            cached_remotes: None,
+            sparse_checkout: None,
+            renames: Vec::new(),
+            renames_key: None,
+            _reload_renames: Task::ready(()),
+            flagged_files: Vec::new(),
             graph_commit_data_handler: GraphCommitHandlerState::Closed,
+            _auto_fetch: Some(auto_fetch),
         }
     }
 
@@ -3979,6 +4715,18 @@ impl Repository {
         let repository_state = RemoteRepositoryState { project_id, client };
         let job_sender = Self::spawn_remote_git_worker(repository_state.clone(), cx);
         let repository_state = Task::ready(Ok(RepositoryState::Remote(repository_state))).shared();
+        cx.subscribe_self(|this, event: &RepositoryEvent, cx| {
+            if let RepositoryEvent::BranchChanged = event {
+                let reload = this.reload_cached_branches();
+                cx.spawn(async move |_, _| {
+                    if let Ok(result) = reload.await {
+                        result.log_err();
+                    }
+                })
+                .detach();
+            }
+        })
+        .detach();
         Self {
             this: cx.weak_entity(),
             snapshot,
@@ -3994,7 +4742,20 @@ impl Repository {
             job_id: 0,
             initial_graph_data: Default::default(),
             commit_data: Default::default(),
+            commit_signatures: Default::default(),
+            hook_output: None,
+            remote_operation: None,
+            latest_remote_operation_id: 0,
+            bisect: None,
+            cached_branches: None,
+            cached_remotes: None,
+            sparse_checkout: None,
+            renames: Vec::new(),
+            renames_key: None,
+            _reload_renames: Task::ready(()),
+            flagged_files: Vec::new(),
             graph_commit_data_handler: GraphCommitHandlerState::Closed,
+            _auto_fetch: None,
         }
     }
 
@@ -4043,9 +4804,11 @@ impl Repository {
                                         .as_ref()
                                         .is_some_and(|set| set.is_upgradable());
 
+                                    let committed_path = this.committed_path(&repo_path);
                                     Some((
                                         buffer,
                                         repo_path,
+                                        committed_path,
                                         has_unstaged_diff.then(|| diff_state.index_text.clone()),
                                         has_uncommitted_diff.then(|| diff_state.head_text.clone()),
                                     ))
@@ -4058,8 +4821,13 @@ impl Repository {
                 let buffer_diff_base_changes = cx
                     .background_spawn(async move {
                         let mut changes = Vec::new();
-                        for (buffer, repo_path, current_index_text, current_head_text) in
-                            &repo_diff_state_updates
+                        for (
+                            buffer,
+                            repo_path,
+                            committed_path,
+                            current_index_text,
+                            current_head_text,
+                        ) in &repo_diff_state_updates
                         {
                             let index_text = if current_index_text.is_some() {
                                 backend.load_index_text(repo_path.clone()).await
@@ -4067,7 +4835,7 @@ impl Repository {
                                 None
                             };
                             let head_text = if current_head_text.is_some() {
-                                backend.load_committed_text(repo_path.clone()).await
+                                backend.load_committed_text(committed_path.clone()).await
                             } else {
                                 None
                             };
@@ -4439,6 +5207,8 @@ impl Repository {
                             mode: match reset_mode {
                                 ResetMode::Soft => git_reset::ResetMode::Soft.into(),
                                 ResetMode::Mixed => git_reset::ResetMode::Mixed.into(),
+                                ResetMode::Hard => git_reset::ResetMode::Hard.into(),
+                                ResetMode::Merge => git_reset::ResetMode::Merge.into(),
                             },
                         })
                         .await?;
@@ -4449,97 +5219,543 @@ impl Repository {
         })
     }
 
-    pub fn show(&mut self, commit: String) -> oneshot::Receiver<Result<CommitDetails>> {
+    /// Checks out a commit, detaching HEAD from the current branch.
+    pub fn checkout_revision(&mut self, revision: String) -> oneshot::Receiver<Result<()>> {
+        let id = self.id;
+        self.send_job(
+            Some(format!("git switch --detach {revision}").into()),
+            move |git_repo, _cx| async move {
+                match git_repo {
+                    RepositoryState::Local(LocalRepositoryState {
+                        backend,
+                        environment,
+                        ..
+                    }) => backend.checkout_revision(revision, environment).await,
+                    RepositoryState::Remote(RemoteRepositoryState { project_id, client }) => {
+                        client
+                            .request(proto::GitCheckoutRevision {
+                                project_id: project_id.0,
+                                repository_id: id.to_proto(),
+                                revision,
+                            })
+                            .await?;
+                        Ok(())
+                    }
+                }
+            },
+        )
+    }
+
+    /// The most recent positions of `reference`, such as `HEAD` or a branch name, newest first.
+    pub fn reflog(
+        &mut self,
+        reference: String,
+        limit: usize,
+    ) -> oneshot::Receiver<Result<Vec<ReflogEntry>>> {
         let id = self.id;
         self.send_job(None, move |git_repo, _cx| async move {
             match git_repo {
                 RepositoryState::Local(LocalRepositoryState { backend, .. }) => {
-                    backend.show(commit).await
+                    backend.reflog(reference, limit).await
                 }
                 RepositoryState::Remote(RemoteRepositoryState { project_id, client }) => {
-                    let resp = client
-                        .request(proto::GitShow {
+                    let response = client
+                        .request(proto::GitReflog {
                             project_id: project_id.0,
                             repository_id: id.to_proto(),
-                            commit,
+                            reference,
+                            limit: limit as u64,
                         })
                         .await?;
-
-                    Ok(CommitDetails {
-                        sha: resp.sha.into(),
-                        message: resp.message.into(),
-                        commit_timestamp: resp.commit_timestamp,
-                        author_email: resp.author_email.into(),
-                        author_name: resp.author_name.into(),
-                    })
+                    Ok(response
+                        .entries
+                        .into_iter()
+                        .map(|entry| ReflogEntry {
+                            sha: entry.sha.into(),
+                            selector: entry.selector.into(),
+                            message: entry.message.into(),
+                            timestamp: entry.timestamp,
+                        })
+                        .collect())
                 }
             }
         })
     }
 
-    pub fn load_commit_diff(&mut self, commit: String) -> oneshot::Receiver<Result<CommitDiff>> {
+    /// Lists commits reachable from HEAD, newest first. See [`GitRepository::commit_log`].
+    pub fn commit_log(
+        &mut self,
+        query: Option<String>,
+        skip: usize,
+        limit: usize,
+    ) -> oneshot::Receiver<Result<Vec<CommitLogEntry>>> {
         let id = self.id;
-        self.send_job(None, move |git_repo, cx| async move {
+        self.send_job(None, move |git_repo, _cx| async move {
             match git_repo {
                 RepositoryState::Local(LocalRepositoryState { backend, .. }) => {
-                    backend.load_commit(commit, cx).await
+                    backend.commit_log(query, skip, limit).await
                 }
-                RepositoryState::Remote(RemoteRepositoryState {
-                    client, project_id, ..
-                }) => {
+                RepositoryState::Remote(RemoteRepositoryState { project_id, client }) => {
                     let response = client
-                        .request(proto::LoadCommitDiff {
+                        .request(proto::GitCommitLog {
                             project_id: project_id.0,
                             repository_id: id.to_proto(),
-                            commit,
+                            query,
+                            skip: skip as u64,
+                            limit: limit as u64,
                         })
                         .await?;
-                    Ok(CommitDiff {
-                        files: response
-                            .files
-                            .into_iter()
-                            .map(|file| {
-                                Ok(CommitFile {
-                                    path: RepoPath::from_proto(&file.path)?,
-                                    old_text: file.old_text,
-                                    new_text: file.new_text,
-                                    is_binary: file.is_binary,
-                                })
-                            })
-                            .collect::<Result<Vec<_>>>()?,
-                    })
+                    Ok(response
+                        .entries
+                        .into_iter()
+                        .map(|entry| CommitLogEntry {
+                            sha: entry.sha.into(),
+                            subject: entry.subject.into(),
+                            commit_timestamp: entry.commit_timestamp,
+                            author_name: entry.author_name.into(),
+                            author_email: entry.author_email.into(),
+                            refs: entry.refs.into_iter().map(Into::into).collect(),
+                        })
+                        .collect())
                 }
             }
         })
     }
 
-    pub fn file_history(
-        &mut self,
-        path: RepoPath,
-    ) -> oneshot::Receiver<Result<git::repository::FileHistory>> {
-        self.file_history_paginated(path, 0, None)
-    }
-
-    pub fn file_history_paginated(
-        &mut self,
-        path: RepoPath,
-        skip: usize,
-        limit: Option<usize>,
-    ) -> oneshot::Receiver<Result<git::repository::FileHistory>> {
+    pub fn show(&mut self, commit: String) -> oneshot::Receiver<Result<CommitDetails>> {
         let id = self.id;
         self.send_job(None, move |git_repo, _cx| async move {
             match git_repo {
                 RepositoryState::Local(LocalRepositoryState { backend, .. }) => {
-                    backend.file_history_paginated(path, skip, limit).await
+                    backend.show(commit).await
                 }
-                RepositoryState::Remote(RemoteRepositoryState { client, project_id }) => {
-                    let response = client
-                        .request(proto::GitFileHistory {
+                RepositoryState::Remote(RemoteRepositoryState { project_id, client }) => {
+                    let resp = client
+                        .request(proto::GitShow {
                             project_id: project_id.0,
                             repository_id: id.to_proto(),
-                            path: path.to_proto(),
-                            skip: skip as u64,
-                            limit: limit.map(|l| l as u64),
+                            commit,
+                        })
+                        .await?;
+
+                    Ok(CommitDetails {
+                        sha: resp.sha.into(),
+                        message: resp.message.into(),
+                        commit_timestamp: resp.commit_timestamp,
+                        author_email: resp.author_email.into(),
+                        author_name: resp.author_name.into(),
+                    })
+                }
+            }
+        })
+    }
+
+    pub fn search_commits(
+        &mut self,
+        log_source: LogSource,
+        query: String,
+    ) -> oneshot::Receiver<Result<Vec<Oid>>> {
+        self.send_job(None, move |git_repo, _cx| async move {
+            match git_repo {
+                RepositoryState::Local(LocalRepositoryState { backend, .. }) => {
+                    backend.search_commits(log_source, query).await
+                }
+                RepositoryState::Remote(_) => {
+                    anyhow::bail!("searching commits is not supported for collab yet")
+                }
+            }
+        })
+    }
+
+    /// Lists the commits reachable from `head` but not from `base`, newest first.
+    pub fn commits_between(
+        &mut self,
+        base: String,
+        head: String,
+    ) -> oneshot::Receiver<Result<Vec<CommitSummary>>> {
+        let id = self.id;
+        self.send_job(None, move |git_repo, _cx| async move {
+            match git_repo {
+                RepositoryState::Local(LocalRepositoryState { backend, .. }) => {
+                    backend.commits_between(base, head).await
+                }
+                RepositoryState::Remote(RemoteRepositoryState { project_id, client }) => {
+                    let response = client
+                        .request(proto::GitCommitsBetween {
+                            project_id: project_id.0,
+                            repository_id: id.to_proto(),
+                            base,
+                            head,
+                        })
+                        .await?;
+                    Ok(response
+                        .commits
+                        .into_iter()
+                        .map(|commit| CommitSummary {
+                            sha: commit.sha.into(),
+                            subject: commit.subject.into(),
+                            commit_timestamp: commit.commit_timestamp,
+                            author_name: commit.author_name.into(),
+                            has_parent: commit.has_parent,
+                        })
+                        .collect())
+                }
+            }
+        })
+    }
+
+    pub fn rebase_todo(&mut self, base: String) -> oneshot::Receiver<Result<Vec<RebaseTodoEntry>>> {
+        self.send_job(None, move |git_repo, _cx| async move {
+            match git_repo {
+                RepositoryState::Local(LocalRepositoryState { backend, .. }) => {
+                    backend.rebase_todo(base).await
+                }
+                RepositoryState::Remote(_) => {
+                    anyhow::bail!("interactive rebase is not supported for collab yet")
+                }
+            }
+        })
+    }
+
+    /// Runs an interactive rebase onto `base` with the given todo list. Resolves to whether the
+    /// rebase stopped before finishing.
+    pub fn interactive_rebase(
+        &mut self,
+        base: String,
+        todo: Vec<RebaseTodoEntry>,
+    ) -> oneshot::Receiver<Result<bool>> {
+        self.send_job(
+            Some("git rebase --interactive".into()),
+            move |git_repo, _cx| async move {
+                match git_repo {
+                    RepositoryState::Local(LocalRepositoryState {
+                        backend,
+                        environment,
+                        ..
+                    }) => backend.interactive_rebase(base, todo, environment).await,
+                    RepositoryState::Remote(_) => {
+                        anyhow::bail!("interactive rebase is not supported for collab yet")
+                    }
+                }
+            },
+        )
+    }
+
+    /// Cherry-picks `shas` onto the current branch, oldest first. Resolves to whether the
+    /// cherry-pick stopped on conflicts, to be continued or aborted like a rebase.
+    pub fn cherry_pick(
+        &mut self,
+        shas: Vec<String>,
+        record_origin: bool,
+    ) -> oneshot::Receiver<Result<bool>> {
+        let id = self.id;
+        self.send_job(
+            Some("git cherry-pick".into()),
+            move |git_repo, _cx| async move {
+                match git_repo {
+                    RepositoryState::Local(LocalRepositoryState {
+                        backend,
+                        environment,
+                        ..
+                    }) => backend.cherry_pick(shas, record_origin, environment).await,
+                    RepositoryState::Remote(RemoteRepositoryState { project_id, client }) => {
+                        let response = client
+                            .request(proto::GitCherryPick {
+                                project_id: project_id.0,
+                                repository_id: id.to_proto(),
+                                shas,
+                                record_origin,
+                            })
+                            .await?;
+                        Ok(response.stopped)
+                    }
+                }
+            },
+        )
+    }
+
+    /// Reverts `sha` on the current branch, only staging the reverting changes with `no_commit`.
+    /// Resolves to the generated revert message when the revert was left to be committed.
+    pub fn revert_commit(
+        &mut self,
+        sha: String,
+        no_commit: bool,
+    ) -> oneshot::Receiver<Result<Option<String>>> {
+        let id = self.id;
+        self.send_job(
+            Some(format!("git revert {sha}").into()),
+            move |git_repo, _cx| async move {
+                match git_repo {
+                    RepositoryState::Local(LocalRepositoryState {
+                        backend,
+                        environment,
+                        ..
+                    }) => backend.revert_commit(sha, no_commit, environment).await,
+                    RepositoryState::Remote(RemoteRepositoryState { project_id, client }) => {
+                        let response = client
+                            .request(proto::GitRevertCommit {
+                                project_id: project_id.0,
+                                repository_id: id.to_proto(),
+                                sha,
+                                no_commit,
+                            })
+                            .await?;
+                        Ok(response.message)
+                    }
+                }
+            },
+        )
+    }
+
+    pub fn bisect_status(&self) -> Option<&BisectStatus> {
+        self.bisect.as_ref()
+    }
+
+    /// Starts bisecting the commits between the `good` and `bad` revisions, which checks out the
+    /// first commit to test.
+    pub fn bisect_start(
+        &mut self,
+        bad: String,
+        good: String,
+        cx: &mut Context<Self>,
+    ) -> oneshot::Receiver<Result<BisectStatus>> {
+        let id = self.id;
+        let job = self.send_job(
+            Some(format!("git bisect start {bad} {good}").into()),
+            move |git_repo, _cx| async move {
+                match git_repo {
+                    RepositoryState::Local(LocalRepositoryState {
+                        backend,
+                        environment,
+                        ..
+                    }) => backend.bisect_start(bad, good, environment).await,
+                    RepositoryState::Remote(RemoteRepositoryState { project_id, client }) => {
+                        let response = client
+                            .request(proto::GitBisectStart {
+                                project_id: project_id.0,
+                                repository_id: id.to_proto(),
+                                bad,
+                                good,
+                            })
+                            .await?;
+                        Ok(proto_to_bisect_status(response))
+                    }
+                }
+            },
+        );
+        self.track_bisect_status(job, cx)
+    }
+
+    /// Marks the commit being bisected, which checks out the next one to test unless the first
+    /// bad commit was found.
+    pub fn bisect_mark(
+        &mut self,
+        mark: BisectMark,
+        cx: &mut Context<Self>,
+    ) -> oneshot::Receiver<Result<BisectStatus>> {
+        let id = self.id;
+        let job = self.send_job(
+            Some(format!("git bisect {}", mark.command()).into()),
+            move |git_repo, _cx| async move {
+                match git_repo {
+                    RepositoryState::Local(LocalRepositoryState {
+                        backend,
+                        environment,
+                        ..
+                    }) => backend.bisect_mark(mark, environment).await,
+                    RepositoryState::Remote(RemoteRepositoryState { project_id, client }) => {
+                        let mark = match mark {
+                            BisectMark::Good => proto::git_bisect_mark::Mark::Good,
+                            BisectMark::Bad => proto::git_bisect_mark::Mark::Bad,
+                            BisectMark::Skip => proto::git_bisect_mark::Mark::Skip,
+                        };
+                        let response = client
+                            .request(proto::GitBisectMark {
+                                project_id: project_id.0,
+                                repository_id: id.to_proto(),
+                                mark: mark as i32,
+                            })
+                            .await?;
+                        Ok(proto_to_bisect_status(response))
+                    }
+                }
+            },
+        );
+        self.track_bisect_status(job, cx)
+    }
+
+    fn track_bisect_status(
+        &mut self,
+        job: oneshot::Receiver<Result<BisectStatus>>,
+        cx: &mut Context<Self>,
+    ) -> oneshot::Receiver<Result<BisectStatus>> {
+        let (result_tx, result_rx) = oneshot::channel();
+        cx.spawn(async move |this, cx| {
+            let result = match job.await {
+                Ok(result) => result,
+                Err(canceled) => Err(canceled.into()),
+            };
+            if let Ok(status) = &result {
+                this.update(cx, |this, cx| {
+                    this.bisect = Some(status.clone());
+                    cx.emit(RepositoryEvent::BisectChanged);
+                })
+                .ok();
+            }
+            result_tx.send(result).ok();
+        })
+        .detach();
+        result_rx
+    }
+
+    /// Ends the bisect, checking out the branch it was started from.
+    pub fn bisect_reset(&mut self, cx: &mut Context<Self>) -> oneshot::Receiver<Result<()>> {
+        let id = self.id;
+        let job = self.send_job(
+            Some("git bisect reset".into()),
+            move |git_repo, _cx| async move {
+                match git_repo {
+                    RepositoryState::Local(LocalRepositoryState {
+                        backend,
+                        environment,
+                        ..
+                    }) => backend.bisect_reset(environment).await,
+                    RepositoryState::Remote(RemoteRepositoryState { project_id, client }) => {
+                        client
+                            .request(proto::GitBisectReset {
+                                project_id: project_id.0,
+                                repository_id: id.to_proto(),
+                            })
+                            .await?;
+                        Ok(())
+                    }
+                }
+            },
+        );
+        let (result_tx, result_rx) = oneshot::channel();
+        cx.spawn(async move |this, cx| {
+            let result = match job.await {
+                Ok(result) => result,
+                Err(canceled) => Err(canceled.into()),
+            };
+            if result.is_ok() {
+                this.update(cx, |this, cx| {
+                    this.bisect = None;
+                    cx.emit(RepositoryEvent::BisectChanged);
+                })
+                .ok();
+            }
+            result_tx.send(result).ok();
+        })
+        .detach();
+        result_rx
+    }
+
+    /// Continues, skips the current step of, or aborts the operation that stopped. Resolves to
+    /// whether an operation is still in progress afterwards.
+    pub fn resolve_in_progress_operation(
+        &mut self,
+        kind: InProgressOperationKind,
+        resolution: OperationResolution,
+    ) -> oneshot::Receiver<Result<bool>> {
+        let id = self.id;
+        self.send_job(
+            Some(format!("git {} {}", kind.command(), resolution.flag()).into()),
+            move |git_repo, _cx| async move {
+                match git_repo {
+                    RepositoryState::Local(LocalRepositoryState {
+                        backend,
+                        environment,
+                        ..
+                    }) => {
+                        backend
+                            .resolve_in_progress_operation(kind, resolution, environment)
+                            .await
+                    }
+                    RepositoryState::Remote(RemoteRepositoryState { project_id, client }) => {
+                        use proto::git_resolve_in_progress_operation::Resolution;
+                        let response = client
+                            .request(proto::GitResolveInProgressOperation {
+                                project_id: project_id.0,
+                                repository_id: id.to_proto(),
+                                kind: in_progress_operation_kind_to_proto(kind) as i32,
+                                resolution: match resolution {
+                                    OperationResolution::Continue => Resolution::Continue,
+                                    OperationResolution::Skip => Resolution::Skip,
+                                    OperationResolution::Abort => Resolution::Abort,
+                                } as i32,
+                            })
+                            .await?;
+                        Ok(response.in_progress)
+                    }
+                }
+            },
+        )
+    }
+
+    pub fn load_commit_diff(&mut self, commit: String) -> oneshot::Receiver<Result<CommitDiff>> {
+        let id = self.id;
+        self.send_job(None, move |git_repo, cx| async move {
+            match git_repo {
+                RepositoryState::Local(LocalRepositoryState { backend, .. }) => {
+                    backend.load_commit(commit, cx).await
+                }
+                RepositoryState::Remote(RemoteRepositoryState {
+                    client, project_id, ..
+                }) => {
+                    let response = client
+                        .request(proto::LoadCommitDiff {
+                            project_id: project_id.0,
+                            repository_id: id.to_proto(),
+                            commit,
+                        })
+                        .await?;
+                    Ok(CommitDiff {
+                        files: response
+                            .files
+                            .into_iter()
+                            .map(|file| {
+                                Ok(CommitFile {
+                                    path: RepoPath::from_proto(&file.path)?,
+                                    old_text: file.old_text,
+                                    new_text: file.new_text,
+                                    is_binary: file.is_binary,
+                                })
+                            })
+                            .collect::<Result<Vec<_>>>()?,
+                    })
+                }
+            }
+        })
+    }
+
+    pub fn file_history(
+        &mut self,
+        path: RepoPath,
+    ) -> oneshot::Receiver<Result<git::repository::FileHistory>> {
+        self.file_history_paginated(path, 0, None)
+    }
+
+    pub fn file_history_paginated(
+        &mut self,
+        path: RepoPath,
+        skip: usize,
+        limit: Option<usize>,
+    ) -> oneshot::Receiver<Result<git::repository::FileHistory>> {
+        let id = self.id;
+        self.send_job(None, move |git_repo, _cx| async move {
+            match git_repo {
+                RepositoryState::Local(LocalRepositoryState { backend, .. }) => {
+                    backend.file_history_paginated(path, skip, limit).await
+                }
+                RepositoryState::Remote(RemoteRepositoryState { client, project_id }) => {
+                    let response = client
+                        .request(proto::GitFileHistory {
+                            project_id: project_id.0,
+                            repository_id: id.to_proto(),
+                            path: path.to_proto(),
+                            skip: skip as u64,
+                            limit: limit.map(|l| l as u64),
                         })
                         .await?;
                     Ok(git::repository::FileHistory {
@@ -4553,6 +5769,13 @@ impl Repository {
                                 commit_timestamp: entry.commit_timestamp,
                                 author_name: entry.author_name.into(),
                                 author_email: entry.author_email.into(),
+                                signed: entry.signed,
+                                path: entry
+                                    .path
+                                    .and_then(|path| RepoPath::from_proto(&path).log_err()),
+                                old_path: entry
+                                    .old_path
+                                    .and_then(|path| RepoPath::from_proto(&path).log_err()),
                             })
                             .collect(),
                         path: RepoPath::from_proto(&response.path)?,
@@ -4857,12 +6080,20 @@ impl Repository {
     fn stage_or_unstage_entries(
         &mut self,
         stage: bool,
-        entries: Vec<RepoPath>,
+        mut entries: Vec<RepoPath>,
         cx: &mut Context<Self>,
     ) -> Task<anyhow::Result<()>> {
         if entries.is_empty() {
             return Task::ready(Ok(()));
         }
+        // A rename is staged and unstaged as one change, along with the path it moved away from.
+        let moved_away_paths = entries
+            .iter()
+            .filter_map(|path| self.rename_to(path))
+            .filter(|rename| !rename.is_copy && !entries.contains(&rename.old_path))
+            .map(|rename| rename.old_path.clone())
+            .collect::<Vec<_>>();
+        entries.extend(moved_away_paths);
         let Some(git_store) = self.git_store.upgrade() else {
             return Task::ready(Ok(()));
         };
@@ -5105,15 +6336,25 @@ impl Repository {
         })
     }
 
-    pub fn stash_all(&mut self, cx: &mut Context<Self>) -> Task<anyhow::Result<()>> {
-        let to_stash = self.cached_status().map(|entry| entry.repo_path).collect();
+    pub fn stash_all(
+        &mut self,
+        options: StashOptions,
+        cx: &mut Context<Self>,
+    ) -> Task<anyhow::Result<()>> {
+        // Naming untracked paths without `--include-untracked` makes git refuse to stash.
+        let to_stash = self
+            .cached_status()
+            .filter(|entry| options.include_untracked || !entry.status.is_untracked())
+            .map(|entry| entry.repo_path)
+            .collect();
 
-        self.stash_entries(to_stash, cx)
+        self.stash_entries(to_stash, options, cx)
     }
 
     pub fn stash_entries(
         &mut self,
         entries: Vec<RepoPath>,
+        options: StashOptions,
         cx: &mut Context<Self>,
     ) -> Task<anyhow::Result<()>> {
         let id = self.id;
@@ -5126,7 +6367,7 @@ impl Repository {
                             backend,
                             environment,
                             ..
-                        }) => backend.stash_paths(entries, environment).await,
+                        }) => backend.stash_paths(entries, options, environment).await,
                         RepositoryState::Remote(RemoteRepositoryState { project_id, client }) => {
                             client
                                 .request(proto::Stash {
@@ -5136,6 +6377,8 @@ impl Repository {
                                         .into_iter()
                                         .map(|repo_path| repo_path.to_proto())
                                         .collect(),
+                                    message: options.message,
+                                    exclude_untracked: !options.include_untracked,
                                 })
                                 .await?;
                             Ok(())
@@ -5272,9 +6515,86 @@ impl Repository {
         })
     }
 
-    pub fn run_hook(&mut self, hook: RunHook, _cx: &mut App) -> oneshot::Receiver<Result<()>> {
+    /// Creates and checks out a branch at the commit a stash was made on, then applies and drops
+    /// the stash there.
+    pub fn stash_branch(
+        &mut self,
+        branch_name: String,
+        index: Option<usize>,
+        cx: &mut Context<Self>,
+    ) -> oneshot::Receiver<anyhow::Result<()>> {
+        let id = self.id;
+        let updates_tx = self
+            .git_store()
+            .and_then(|git_store| match &git_store.read(cx).state {
+                GitStoreState::Local { downstream, .. } => downstream
+                    .as_ref()
+                    .map(|downstream| downstream.updates_tx.clone()),
+                _ => None,
+            });
+        let this = cx.weak_entity();
+        self.send_job(
+            Some(format!("git stash branch {branch_name}").into()),
+            move |git_repo, mut cx| async move {
+                match git_repo {
+                    RepositoryState::Local(LocalRepositoryState {
+                        backend,
+                        environment,
+                        ..
+                    }) => {
+                        let result = backend.stash_branch(branch_name, index, environment).await;
+                        if result.is_ok()
+                            && let Ok(stash_entries) = backend.stash_entries().await
+                        {
+                            let snapshot = this.update(&mut cx, |this, cx| {
+                                this.snapshot.stash_entries = stash_entries;
+                                cx.emit(RepositoryEvent::StashEntriesChanged);
+                                this.snapshot.clone()
+                            })?;
+                            if let Some(updates_tx) = updates_tx {
+                                updates_tx
+                                    .unbounded_send(DownstreamUpdate::UpdateRepository(snapshot))
+                                    .ok();
+                            }
+                        }
+
+                        result
+                    }
+                    RepositoryState::Remote(RemoteRepositoryState { project_id, client }) => {
+                        client
+                            .request(proto::GitStashBranch {
+                                project_id: project_id.0,
+                                repository_id: id.to_proto(),
+                                branch_name,
+                                stash_index: index.map(|i| i as u64),
+                            })
+                            .await
+                            .context("sending stash branch request")?;
+                        Ok(())
+                    }
+                }
+            },
+        )
+    }
+
+    pub fn hook_output(&self) -> Option<&HookOutput> {
+        self.hook_output.as_ref()
+    }
+
+    pub fn dismiss_hook_output(&mut self, cx: &mut Context<Self>) {
+        if self.hook_output.take().is_some() {
+            cx.emit(RepositoryEvent::HookOutputChanged);
+        }
+    }
+
+    pub fn run_hook(
+        &mut self,
+        hook: RunHook,
+        cx: &mut Context<Self>,
+    ) -> oneshot::Receiver<Result<()>> {
         let id = self.id;
-        self.send_job(
+        let (output_tx, output_rx) = smol::channel::unbounded();
+        let job = self.send_job(
             Some(format!("git hook {}", hook.as_str()).into()),
             move |git_repo, _cx| async move {
                 match git_repo {
@@ -5282,7 +6602,7 @@ impl Repository {
                         backend,
                         environment,
                         ..
-                    }) => backend.run_hook(hook, environment.clone()).await,
+                    }) => backend.run_hook(hook, environment.clone(), output_tx).await,
                     RepositoryState::Remote(RemoteRepositoryState { project_id, client }) => {
                         client
                             .request(proto::RunGitHook {
@@ -5296,7 +6616,52 @@ impl Repository {
                     }
                 }
             },
-        )
+        );
+
+        self.hook_output = Some(HookOutput {
+            hook,
+            output: String::new(),
+            status: HookStatus::Running,
+        });
+        cx.emit(RepositoryEvent::HookOutputChanged);
+
+        let (result_tx, result_rx) = oneshot::channel();
+        cx.spawn(async move |this, cx| {
+            // The output channel closes once the job is done with the hook.
+            while let Ok(line) = output_rx.recv().await {
+                this.update(cx, |this, cx| {
+                    if let Some(hook_output) = &mut this.hook_output {
+                        hook_output.output.push_str(&line);
+                        cx.emit(RepositoryEvent::HookOutputChanged);
+                    }
+                })
+                .ok();
+            }
+            let result = match job.await {
+                Ok(result) => result,
+                Err(canceled) => Err(canceled.into()),
+            };
+            this.update(cx, |this, cx| {
+                if let Some(hook_output) = &mut this.hook_output {
+                    hook_output.status = if result.is_ok() {
+                        HookStatus::Succeeded
+                    } else {
+                        HookStatus::Failed
+                    };
+                    // Hooks run on a remote host don't stream their output, so show the error.
+                    if let Err(error) = &result
+                        && hook_output.output.is_empty()
+                    {
+                        hook_output.output = error.to_string();
+                    }
+                    cx.emit(RepositoryEvent::HookOutputChanged);
+                }
+            })
+            .ok();
+            result_tx.send(result).ok();
+        })
+        .detach();
+        result_rx
     }
 
     pub fn commit(
@@ -5305,16 +6670,18 @@ impl Repository {
         name_and_email: Option<(SharedString, SharedString)>,
         options: CommitOptions,
         askpass: AskPassDelegate,
-        cx: &mut App,
+        cx: &mut Context<Self>,
     ) -> oneshot::Receiver<Result<()>> {
         let id = self.id;
         let askpass_delegates = self.askpass_delegates.clone();
         let askpass_id = util::post_inc(&mut self.latest_askpass_id);
 
-        let rx = self.run_hook(RunHook::PreCommit, cx);
+        let hook = (!options.no_verify).then(|| self.run_hook(RunHook::PreCommit, cx));
 
         self.send_job(Some("git commit".into()), move |git_repo, _cx| async move {
-            rx.await??;
+            if let Some(hook) = hook {
+                hook.await??;
+            }
 
             match git_repo {
                 RepositoryState::Local(LocalRepositoryState {
@@ -5343,6 +6710,12 @@ impl Repository {
                             options: Some(proto::commit::CommitOptions {
                                 amend: options.amend,
                                 signoff: options.signoff,
+                                only_paths: options
+                                    .only_paths
+                                    .iter()
+                                    .map(|path| path.to_proto())
+                                    .collect(),
+                                no_verify: options.no_verify,
                             }),
                             askpass_id,
                         })
@@ -5363,36 +6736,222 @@ impl Repository {
         let askpass_delegates = self.askpass_delegates.clone();
         let askpass_id = util::post_inc(&mut self.latest_askpass_id);
         let id = self.id;
+        let this = self.this.clone();
 
-        self.send_job(Some("git fetch".into()), move |git_repo, cx| async move {
-            match git_repo {
-                RepositoryState::Local(LocalRepositoryState {
-                    backend,
-                    environment,
-                    ..
-                }) => backend.fetch(fetch_options, askpass, environment, cx).await,
-                RepositoryState::Remote(RemoteRepositoryState { project_id, client }) => {
-                    askpass_delegates.lock().insert(askpass_id, askpass);
-                    let _defer = util::defer(|| {
-                        let askpass_delegate = askpass_delegates.lock().remove(&askpass_id);
-                        debug_assert!(askpass_delegate.is_some());
-                    });
+        self.send_job(
+            Some("git fetch".into()),
+            move |git_repo, mut cx| async move {
+                match git_repo {
+                    RepositoryState::Local(LocalRepositoryState {
+                        backend,
+                        environment,
+                        ..
+                    }) => {
+                        let progress = Self::remote_progress_delegate(this, true, &mut cx);
+                        backend
+                            .fetch(fetch_options, askpass, progress, environment, cx)
+                            .await
+                    }
+                    RepositoryState::Remote(RemoteRepositoryState { project_id, client }) => {
+                        askpass_delegates.lock().insert(askpass_id, askpass);
+                        let _defer = util::defer(|| {
+                            let askpass_delegate = askpass_delegates.lock().remove(&askpass_id);
+                            debug_assert!(askpass_delegate.is_some());
+                        });
 
-                    let response = client
-                        .request(proto::Fetch {
-                            project_id: project_id.0,
-                            repository_id: id.to_proto(),
-                            askpass_id,
-                            remote: fetch_options.to_proto(),
+                        let response = client
+                            .request(proto::Fetch {
+                                project_id: project_id.0,
+                                repository_id: id.to_proto(),
+                                askpass_id,
+                                remote: fetch_options.to_proto(),
+                            })
+                            .await?;
+
+                        Ok(RemoteCommandOutput {
+                            stdout: response.stdout,
+                            stderr: response.stderr,
                         })
-                        .await?;
+                    }
+                }
+            },
+        )
+    }
 
-                    Ok(RemoteCommandOutput {
-                        stdout: response.stdout,
-                        stderr: response.stderr,
-                    })
+    /// Fetches a ref of the remote into a local branch, such as the head of a pull request.
+    pub fn fetch_into_branch(
+        &mut self,
+        remote_name: String,
+        remote_ref: String,
+        branch_name: String,
+        askpass: AskPassDelegate,
+        _cx: &mut App,
+    ) -> oneshot::Receiver<Result<RemoteCommandOutput>> {
+        let this = self.this.clone();
+        self.send_job(
+            Some(format!("git fetch {remote_name} {remote_ref}").into()),
+            move |git_repo, mut cx| async move {
+                match git_repo {
+                    RepositoryState::Local(LocalRepositoryState {
+                        backend,
+                        environment,
+                        ..
+                    }) => {
+                        let progress = Self::remote_progress_delegate(this, true, &mut cx);
+                        backend
+                            .fetch_into_branch(
+                                remote_name,
+                                remote_ref,
+                                branch_name,
+                                askpass,
+                                progress,
+                                environment,
+                                cx,
+                            )
+                            .await
+                    }
+                    RepositoryState::Remote(_) => {
+                        anyhow::bail!("fetching into a branch is not supported for collab yet")
+                    }
+                }
+            },
+        )
+    }
+
+    /// Fetches in the background while `git.auto_fetch` is enabled for the worktree of the
+    /// repository. Credentials can't be asked for without interrupting, so each failed fetch
+    /// doubles the wait before the next one.
+    async fn auto_fetch(this: WeakEntity<Self>, cx: &mut AsyncApp) {
+        const MAX_BACKOFF_EXPONENT: u32 = 4;
+
+        let mut consecutive_failures = 0;
+        loop {
+            let Ok(interval) = this.read_with(cx, |this, cx| this.auto_fetch_settings(cx).interval)
+            else {
+                return;
+            };
+            let backoff = 1 << consecutive_failures.min(MAX_BACKOFF_EXPONENT);
+            cx.background_executor()
+                .timer(interval.saturating_mul(backoff))
+                .await;
+
+            let now = cx.background_executor().now();
+            let Ok(fetch) = this.update(cx, |this, cx| {
+                let settings = this.auto_fetch_settings(cx);
+                if !settings.enabled {
+                    return None;
+                }
+                // Linked worktrees, and other projects with the same repository, share its
+                // remote-tracking refs, so only one of them fetches per interval.
+                let last_fetches = &mut cx.default_global::<LastAutoFetches>().0;
+                let repository_path = this.original_repo_abs_path.clone();
+                if last_fetches
+                    .get(&repository_path)
+                    .is_some_and(|last_fetch| now.duration_since(*last_fetch) < settings.interval)
+                {
+                    return None;
+                }
+                last_fetches.insert(repository_path, now);
+                Some(this.fetch_in_background(settings.remotes, cx))
+            }) else {
+                return;
+            };
+            let Some(fetch) = fetch else {
+                consecutive_failures = 0;
+                continue;
+            };
+            match fetch.await {
+                Ok(()) => consecutive_failures = 0,
+                Err(error) => {
+                    consecutive_failures += 1;
+                    log::info!("background fetch failed: {error:#}");
                 }
             }
+        }
+    }
+
+    /// The `git.auto_fetch` settings of the worktree that contains the repository.
+    fn auto_fetch_settings(&self, cx: &App) -> AutoFetchSettings {
+        let worktree = self.git_store.upgrade().and_then(|git_store| {
+            git_store
+                .read(cx)
+                .worktree_store
+                .read(cx)
+                .find_worktree(&self.work_directory_abs_path, cx)
+        });
+        let location = worktree.as_ref().map(|(worktree, path)| SettingsLocation {
+            worktree_id: worktree.read(cx).id(),
+            path,
+        });
+        ProjectSettings::get(location, cx).git.auto_fetch.clone()
+    }
+
+    /// Fetches without asking for credentials, reporting progress or showing a pending job, then
+    /// refreshes the current branch so that its ahead and behind counts are up to date. Fetching
+    /// only updates remote-tracking refs, so it runs outside of the job queue rather than holding
+    /// up the git operations queued behind it, and the working tree is never touched.
+    fn fetch_in_background(
+        &mut self,
+        remotes: Option<Vec<String>>,
+        cx: &mut Context<Self>,
+    ) -> Task<Result<()>> {
+        let updates_tx = self
+            .git_store()
+            .and_then(|git_store| match &git_store.read(cx).state {
+                GitStoreState::Local { downstream, .. } => downstream
+                    .as_ref()
+                    .map(|downstream| downstream.updates_tx.clone()),
+                _ => None,
+            });
+        let repository_state = self.repository_state.clone();
+
+        cx.spawn(async move |this, cx| {
+            // Collab guests leave fetching to the host.
+            let Ok(RepositoryState::Local(LocalRepositoryState {
+                backend,
+                environment,
+                ..
+            })) = repository_state.await
+            else {
+                return Ok(());
+            };
+
+            let fetches = match remotes {
+                Some(remotes) => remotes
+                    .into_iter()
+                    .map(|name| FetchOptions::Remote(Remote { name: name.into() }))
+                    .collect(),
+                None => vec![FetchOptions::All],
+            };
+            for fetch_options in fetches {
+                // Dropping the password channel fails the fetch instead of prompting.
+                let askpass = AskPassDelegate::new(cx, |_, _, _| {});
+                let (progress_tx, _) = smol::channel::unbounded();
+                let (_, cancel_rx) = oneshot::channel();
+                backend
+                    .fetch(
+                        fetch_options,
+                        askpass,
+                        RemoteProgressDelegate::new(progress_tx, cancel_rx),
+                        environment.clone(),
+                        cx.clone(),
+                    )
+                    .await?;
+            }
+
+            let branches = backend.branches().await?;
+            let branch = branches.into_iter().find(|branch| branch.is_head);
+            let snapshot = this.update(cx, |this, cx| {
+                this.snapshot.branch = branch;
+                cx.emit(RepositoryEvent::BranchChanged);
+                this.snapshot.clone()
+            })?;
+            if let Some(updates_tx) = updates_tx {
+                updates_tx
+                    .unbounded_send(DownstreamUpdate::UpdateRepository(snapshot))
+                    .ok();
+            }
+            Ok(())
         })
     }
 
@@ -5402,6 +6961,7 @@ impl Repository {
         remote_branch: SharedString,
         remote: SharedString,
         options: Option<PushOptions>,
+        follow_tags: bool,
         askpass: AskPassDelegate,
         cx: &mut Context<Self>,
     ) -> oneshot::Receiver<Result<RemoteCommandOutput>> {
@@ -5409,12 +6969,16 @@ impl Repository {
         let askpass_id = util::post_inc(&mut self.latest_askpass_id);
         let id = self.id;
 
-        let args = options
+        let mut args = options
             .map(|option| match option {
                 PushOptions::SetUpstream => " --set-upstream",
                 PushOptions::Force => " --force-with-lease",
             })
-            .unwrap_or("");
+            .unwrap_or("")
+            .to_string();
+        if follow_tags {
+            args.push_str(" --follow-tags");
+        }
 
         let updates_tx = self
             .git_store()
@@ -5435,13 +6999,16 @@ impl Repository {
                         environment,
                         ..
                     }) => {
+                        let progress = Self::remote_progress_delegate(this.clone(), true, &mut cx);
                         let result = backend
                             .push(
                                 branch.to_string(),
                                 remote_branch.to_string(),
                                 remote.to_string(),
                                 options,
+                                follow_tags,
                                 askpass,
+                                progress,
                                 environment.clone(),
                                 cx.clone(),
                             )
@@ -5485,6 +7052,7 @@ impl Repository {
                                     }
                                 }
                                     as i32),
+                                follow_tags,
                             })
                             .await?;
 
@@ -5498,69 +7066,277 @@ impl Repository {
         )
     }
 
-    pub fn pull(
+    pub fn pull(
+        &mut self,
+        branch: Option<SharedString>,
+        remote: SharedString,
+        strategy: Option<PullStrategy>,
+        askpass: AskPassDelegate,
+        _cx: &mut App,
+    ) -> oneshot::Receiver<Result<RemoteCommandOutput>> {
+        let askpass_delegates = self.askpass_delegates.clone();
+        let askpass_id = util::post_inc(&mut self.latest_askpass_id);
+        let id = self.id;
+
+        let mut status = "git pull".to_string();
+        if let Some(strategy) = strategy {
+            status.push_str(match strategy {
+                PullStrategy::Merge => " --no-rebase",
+                PullStrategy::Rebase => " --rebase",
+                PullStrategy::FastForwardOnly => " --ff-only",
+            });
+        }
+        status.push_str(&format!(" {}", remote));
+        if let Some(b) = &branch {
+            status.push_str(&format!(" {}", b));
+        }
+        let this = self.this.clone();
+
+        self.send_job(Some(status.into()), move |git_repo, mut cx| async move {
+            match git_repo {
+                RepositoryState::Local(LocalRepositoryState {
+                    backend,
+                    environment,
+                    ..
+                }) => {
+                    let progress = Self::remote_progress_delegate(this, true, &mut cx);
+                    backend
+                        .pull(
+                            branch.as_ref().map(|b| b.to_string()),
+                            remote.to_string(),
+                            strategy,
+                            askpass,
+                            progress,
+                            environment.clone(),
+                            cx,
+                        )
+                        .await
+                }
+                RepositoryState::Remote(RemoteRepositoryState { project_id, client }) => {
+                    askpass_delegates.lock().insert(askpass_id, askpass);
+                    let _defer = util::defer(|| {
+                        let askpass_delegate = askpass_delegates.lock().remove(&askpass_id);
+                        debug_assert!(askpass_delegate.is_some());
+                    });
+                    let response = client
+                        .request(proto::Pull {
+                            project_id: project_id.0,
+                            repository_id: id.to_proto(),
+                            askpass_id,
+                            rebase: strategy == Some(PullStrategy::Rebase),
+                            strategy: strategy.map(|strategy| {
+                                (match strategy {
+                                    PullStrategy::Merge => proto::pull::PullStrategy::Merge,
+                                    PullStrategy::Rebase => proto::pull::PullStrategy::Rebase,
+                                    PullStrategy::FastForwardOnly => {
+                                        proto::pull::PullStrategy::FastForwardOnly
+                                    }
+                                }) as i32
+                            }),
+                            branch_name: branch.as_ref().map(|b| b.to_string()),
+                            remote_name: remote.to_string(),
+                        })
+                        .await?;
+
+                    Ok(RemoteCommandOutput {
+                        stdout: response.stdout,
+                        stderr: response.stderr,
+                    })
+                }
+            }
+        })
+    }
+
+    /// Picks out the paths that git-lfs filters.
+    pub fn lfs_tracked_paths(
+        &mut self,
+        paths: Vec<RepoPath>,
+    ) -> oneshot::Receiver<Result<HashSet<RepoPath>>> {
+        self.send_job(None, move |repo, _cx| async move {
+            match repo {
+                RepositoryState::Local(LocalRepositoryState { backend, .. }) => {
+                    backend.lfs_tracked_paths(paths).await
+                }
+                RepositoryState::Remote(_) => {
+                    anyhow::bail!("listing LFS-tracked paths is not supported for collab yet")
+                }
+            }
+        })
+    }
+
+    pub fn fetch_lfs_objects(
+        &mut self,
+        paths: Vec<RepoPath>,
+        askpass: AskPassDelegate,
+        _cx: &mut App,
+    ) -> oneshot::Receiver<Result<RemoteCommandOutput>> {
+        let this = self.this.clone();
+        self.send_job(
+            Some("git lfs pull".into()),
+            move |git_repo, mut cx| async move {
+                match git_repo {
+                    RepositoryState::Local(LocalRepositoryState {
+                        backend,
+                        environment,
+                        ..
+                    }) => {
+                        let progress = Self::remote_progress_delegate(this, true, &mut cx);
+                        backend
+                            .fetch_lfs_objects(paths, askpass, progress, environment.clone(), cx)
+                            .await
+                    }
+                    RepositoryState::Remote(_) => {
+                        anyhow::bail!("fetching LFS objects is not supported for collab yet")
+                    }
+                }
+            },
+        )
+    }
+
+    /// Lists the submodules directly inside the repository.
+    pub fn submodules(&mut self) -> oneshot::Receiver<Result<Vec<Submodule>>> {
+        self.send_job(None, move |repo, _cx| async move {
+            match repo {
+                RepositoryState::Local(LocalRepositoryState { backend, .. }) => {
+                    backend.submodules().await
+                }
+                RepositoryState::Remote(_) => {
+                    anyhow::bail!("listing submodules is not supported for collab yet")
+                }
+            }
+        })
+    }
+
+    pub fn update_submodules(
         &mut self,
-        branch: Option<SharedString>,
-        remote: SharedString,
-        rebase: bool,
+        paths: Vec<RepoPath>,
+        init: bool,
         askpass: AskPassDelegate,
         _cx: &mut App,
     ) -> oneshot::Receiver<Result<RemoteCommandOutput>> {
-        let askpass_delegates = self.askpass_delegates.clone();
-        let askpass_id = util::post_inc(&mut self.latest_askpass_id);
-        let id = self.id;
-
-        let mut status = "git pull".to_string();
-        if rebase {
-            status.push_str(" --rebase");
-        }
-        status.push_str(&format!(" {}", remote));
-        if let Some(b) = &branch {
-            status.push_str(&format!(" {}", b));
-        }
-
-        self.send_job(Some(status.into()), move |git_repo, cx| async move {
+        let this = self.this.clone();
+        let status = if init {
+            "git submodule update --init"
+        } else {
+            "git submodule update"
+        };
+        self.send_job(Some(status.into()), move |git_repo, mut cx| async move {
             match git_repo {
                 RepositoryState::Local(LocalRepositoryState {
                     backend,
                     environment,
                     ..
                 }) => {
+                    let progress = Self::remote_progress_delegate(this, true, &mut cx);
                     backend
-                        .pull(
-                            branch.as_ref().map(|b| b.to_string()),
-                            remote.to_string(),
-                            rebase,
-                            askpass,
-                            environment.clone(),
-                            cx,
-                        )
+                        .update_submodules(paths, init, askpass, progress, environment.clone(), cx)
                         .await
                 }
-                RepositoryState::Remote(RemoteRepositoryState { project_id, client }) => {
-                    askpass_delegates.lock().insert(askpass_id, askpass);
-                    let _defer = util::defer(|| {
-                        let askpass_delegate = askpass_delegates.lock().remove(&askpass_id);
-                        debug_assert!(askpass_delegate.is_some());
-                    });
-                    let response = client
-                        .request(proto::Pull {
-                            project_id: project_id.0,
-                            repository_id: id.to_proto(),
-                            askpass_id,
-                            rebase,
-                            branch_name: branch.as_ref().map(|b| b.to_string()),
-                            remote_name: remote.to_string(),
-                        })
-                        .await?;
+                RepositoryState::Remote(_) => {
+                    anyhow::bail!("updating submodules is not supported for collab yet")
+                }
+            }
+        })
+    }
 
-                    Ok(RemoteCommandOutput {
-                        stdout: response.stdout,
-                        stderr: response.stderr,
-                    })
+    pub fn sync_submodules(&mut self, paths: Vec<RepoPath>) -> oneshot::Receiver<Result<()>> {
+        self.send_job(
+            Some("git submodule sync".into()),
+            move |repo, _cx| async move {
+                match repo {
+                    RepositoryState::Local(LocalRepositoryState { backend, .. }) => {
+                        backend.sync_submodules(paths).await
+                    }
+                    RepositoryState::Remote(_) => {
+                        anyhow::bail!("syncing submodules is not supported for collab yet")
+                    }
                 }
+            },
+        )
+    }
+
+    /// The progress of the push, pull, fetch or checkout this repository is running, as last
+    /// reported by git. Operations run on a remote host don't report their progress.
+    pub fn remote_progress(&self) -> Option<&RemoteProgress> {
+        self.remote_operation.as_ref()?.progress.as_ref()
+    }
+
+    pub fn can_cancel_remote_operation(&self) -> bool {
+        self.remote_operation
+            .as_ref()
+            .is_some_and(|operation| operation.cancel_tx.is_some())
+    }
+
+    /// Stops the running push, pull or fetch by killing git.
+    pub fn cancel_remote_operation(&mut self, cx: &mut Context<Self>) {
+        if let Some(cancel_tx) = self
+            .remote_operation
+            .as_mut()
+            .and_then(|operation| operation.cancel_tx.take())
+        {
+            cancel_tx.send(()).ok();
+            cx.emit(RepositoryEvent::RemoteProgressChanged);
+        }
+    }
+
+    /// Creates the delegate through which a local push, pull, fetch or checkout reports its
+    /// progress, and keeps the latest report until the operation is done with it.
+    fn remote_progress_delegate(
+        this: WeakEntity<Self>,
+        cancellable: bool,
+        cx: &mut AsyncApp,
+    ) -> RemoteProgressDelegate {
+        let (progress_tx, progress_rx) = smol::channel::unbounded();
+        let (cancel_tx, cancel_rx) = oneshot::channel();
+        let operation_id = this
+            .update(cx, |this, cx| {
+                let operation_id = util::post_inc(&mut this.latest_remote_operation_id);
+                this.remote_operation = Some(RemoteOperation {
+                    id: operation_id,
+                    progress: None,
+                    cancel_tx: cancellable.then_some(cancel_tx),
+                });
+                cx.emit(RepositoryEvent::RemoteProgressChanged);
+                operation_id
+            })
+            .ok();
+        cx.spawn(async move |cx| {
+            // The channel closes once the operation is done with the delegate.
+            while let Ok(progress) = progress_rx.recv().await {
+                this.update(cx, |this, cx| {
+                    if let Some(operation) = this.remote_operation.as_mut()
+                        && Some(operation.id) == operation_id
+                    {
+                        operation.progress = Some(progress);
+                        cx.emit(RepositoryEvent::RemoteProgressChanged);
+                    }
+                })
+                .ok();
             }
+            this.update(cx, |this, cx| {
+                if this
+                    .remote_operation
+                    .as_ref()
+                    .is_some_and(|operation| Some(operation.id) == operation_id)
+                {
+                    this.remote_operation = None;
+                    cx.emit(RepositoryEvent::RemoteProgressChanged);
+                }
+            })
+            .ok();
         })
+        .detach();
+        RemoteProgressDelegate::new(progress_tx, cancel_rx)
+    }
+
+    /// Replaces the index entry of `path` with `content`, or removes it if `content` is `None`.
+    pub fn set_index_text(
+        &mut self,
+        path: RepoPath,
+        content: Option<String>,
+        cx: &mut Context<Self>,
+    ) -> oneshot::Receiver<anyhow::Result<()>> {
+        self.spawn_set_index_text_job(path, content, None, cx)
     }
 
     fn spawn_set_index_text_job(
@@ -5615,88 +7391,406 @@ impl Repository {
                     path.as_unix_str()
                 );
 
-                if let Some(hunk_staging_operation_count) = hunk_staging_operation_count {
-                    let project_path = this
-                        .read_with(&cx, |this, cx| this.repo_path_to_project_path(&path, cx))
-                        .ok()
-                        .flatten();
-                    git_store
-                        .update(&mut cx, |git_store, cx| {
-                            let buffer_id = git_store
-                                .buffer_store
-                                .read(cx)
-                                .get_by_path(&project_path?)?
-                                .read(cx)
-                                .remote_id();
-                            let diff_state = git_store.diffs.get(&buffer_id)?;
-                            diff_state.update(cx, |diff_state, _| {
-                                diff_state.hunk_staging_operation_count_as_of_write =
-                                    hunk_staging_operation_count;
-                            });
-                            Some(())
-                        })
-                        .context("Git store dropped")?;
+                if let Some(hunk_staging_operation_count) = hunk_staging_operation_count {
+                    let project_path = this
+                        .read_with(&cx, |this, cx| this.repo_path_to_project_path(&path, cx))
+                        .ok()
+                        .flatten();
+                    git_store
+                        .update(&mut cx, |git_store, cx| {
+                            let buffer_id = git_store
+                                .buffer_store
+                                .read(cx)
+                                .get_by_path(&project_path?)?
+                                .read(cx)
+                                .remote_id();
+                            let diff_state = git_store.diffs.get(&buffer_id)?;
+                            diff_state.update(cx, |diff_state, _| {
+                                diff_state.hunk_staging_operation_count_as_of_write =
+                                    hunk_staging_operation_count;
+                            });
+                            Some(())
+                        })
+                        .context("Git store dropped")?;
+                }
+                Ok(())
+            },
+        )
+    }
+
+    pub fn create_remote(
+        &mut self,
+        remote_name: String,
+        remote_url: String,
+    ) -> oneshot::Receiver<Result<()>> {
+        let id = self.id;
+        self.send_job(
+            Some(format!("git remote add {remote_name} {remote_url}").into()),
+            move |repo, _cx| async move {
+                match repo {
+                    RepositoryState::Local(LocalRepositoryState { backend, .. }) => {
+                        backend.create_remote(remote_name, remote_url).await
+                    }
+                    RepositoryState::Remote(RemoteRepositoryState { project_id, client }) => {
+                        client
+                            .request(proto::GitCreateRemote {
+                                project_id: project_id.0,
+                                repository_id: id.to_proto(),
+                                remote_name,
+                                remote_url,
+                            })
+                            .await?;
+
+                        Ok(())
+                    }
+                }
+            },
+        )
+    }
+
+    pub fn remove_remote(&mut self, remote_name: String) -> oneshot::Receiver<Result<()>> {
+        let id = self.id;
+        self.send_job(
+            Some(format!("git remove remote {remote_name}").into()),
+            move |repo, _cx| async move {
+                match repo {
+                    RepositoryState::Local(LocalRepositoryState { backend, .. }) => {
+                        backend.remove_remote(remote_name).await
+                    }
+                    RepositoryState::Remote(RemoteRepositoryState { project_id, client }) => {
+                        client
+                            .request(proto::GitRemoveRemote {
+                                project_id: project_id.0,
+                                repository_id: id.to_proto(),
+                                remote_name,
+                            })
+                            .await?;
+
+                        Ok(())
+                    }
+                }
+            },
+        )
+    }
+
+    /// Lists the remotes with their URLs, remembering them for [`Self::cached_remotes`].
+    pub fn remote_infos(&mut self) -> oneshot::Receiver<Result<Vec<RemoteInfo>>> {
+        let this = self.this.clone();
+        self.send_job(None, move |repo, mut cx| async move {
+            let remotes = match repo {
+                RepositoryState::Local(LocalRepositoryState { backend, .. }) => {
+                    backend.remote_infos().await?
+                }
+                RepositoryState::Remote(_) => {
+                    anyhow::bail!("managing remotes is not supported for collab yet")
+                }
+            };
+            this.update(&mut cx, |this, cx| {
+                this.cached_remotes = Some(remotes.clone().into());
+                cx.notify();
+            })
+            .ok();
+
+            Ok(remotes)
+        })
+    }
+
+    /// The remotes as of the last time [`Self::remote_infos`] listed them.
+    pub fn cached_remotes(&self) -> Option<Arc<[RemoteInfo]>> {
+        self.cached_remotes.clone()
+    }
+
+    /// The sparse checkout of the working tree, or `None` when all of HEAD is checked out or
+    /// the sparse checkout hasn't been read yet.
+    pub fn sparse_checkout(&self) -> Option<&SparseCheckout> {
+        self.sparse_checkout.as_ref().and_then(Option::as_ref)
+    }
+
+    /// Whether the sparse checkout was read, so that [`Self::sparse_checkout`] returning `None`
+    /// means that all of HEAD is checked out.
+    pub fn has_read_sparse_checkout(&self) -> bool {
+        self.sparse_checkout.is_some()
+    }
+
+    pub fn reload_sparse_checkout(&mut self) -> oneshot::Receiver<Result<()>> {
+        let this = self.this.clone();
+        self.send_job(None, move |repo, mut cx| async move {
+            // Collab guests don't learn about the host's sparse checkout.
+            let RepositoryState::Local(LocalRepositoryState { backend, .. }) = repo else {
+                return Ok(());
+            };
+            let sparse_checkout = backend.sparse_checkout().await?;
+            this.update(&mut cx, |this, cx| {
+                this.set_cached_sparse_checkout(sparse_checkout, cx)
+            })
+        })
+    }
+
+    /// Limits the working tree to the patterns, or with `None` checks out all of HEAD again.
+    pub fn set_sparse_checkout(
+        &mut self,
+        cone: bool,
+        patterns: Option<Vec<String>>,
+    ) -> oneshot::Receiver<Result<()>> {
+        let this = self.this.clone();
+        let status = if patterns.is_some() {
+            "git sparse-checkout set"
+        } else {
+            "git sparse-checkout disable"
+        };
+        self.send_job(Some(status.into()), move |repo, mut cx| async move {
+            let RepositoryState::Local(LocalRepositoryState { backend, .. }) = repo else {
+                anyhow::bail!("sparse checkouts are not supported for collab yet");
+            };
+            match patterns {
+                Some(patterns) => backend.set_sparse_checkout(cone, patterns).await?,
+                None => backend.disable_sparse_checkout().await?,
+            }
+            let sparse_checkout = backend.sparse_checkout().await?;
+            this.update(&mut cx, |this, cx| {
+                this.set_cached_sparse_checkout(sparse_checkout, cx)
+            })
+        })
+    }
+
+    /// Adds directories left out of a cone-mode sparse checkout to its patterns.
+    pub fn check_out_sparse_directories(
+        &mut self,
+        directories: &[RepoPath],
+    ) -> oneshot::Receiver<Result<()>> {
+        let this = self.this.clone();
+        let directories = directories
+            .iter()
+            .map(|directory| directory.as_unix_str().to_string())
+            .collect::<Vec<_>>();
+        self.send_job(
+            Some("git sparse-checkout add".into()),
+            move |repo, mut cx| async move {
+                let RepositoryState::Local(LocalRepositoryState { backend, .. }) = repo else {
+                    anyhow::bail!("sparse checkouts are not supported for collab yet");
+                };
+                // The patterns are read again rather than taken from the cache, which may not
+                // have been read yet, so that they are never mistaken for a disabled checkout.
+                let Some(sparse_checkout) = backend.sparse_checkout().await? else {
+                    return Ok(());
+                };
+                let mut patterns = sparse_checkout.patterns;
+                patterns.extend(directories);
+                backend.set_sparse_checkout(true, patterns).await?;
+                let sparse_checkout = backend.sparse_checkout().await?;
+                this.update(&mut cx, |this, cx| {
+                    this.set_cached_sparse_checkout(sparse_checkout, cx)
+                })
+            },
+        )
+    }
+
+    fn set_cached_sparse_checkout(
+        &mut self,
+        sparse_checkout: Option<SparseCheckout>,
+        cx: &mut Context<Self>,
+    ) {
+        if self.sparse_checkout.as_ref() != Some(&sparse_checkout) {
+            self.sparse_checkout = Some(sparse_checkout);
+            cx.emit(RepositoryEvent::SparseCheckoutChanged);
+        }
+    }
+
+    /// The renames and copies between HEAD and the working tree that git detected.
+    pub fn renames(&self) -> &[FileRename] {
+        &self.renames
+    }
+
+    /// The rename or copy that `path` was created by.
+    pub fn rename_to(&self, path: &RepoPath) -> Option<&FileRename> {
+        self.renames.iter().find(|rename| &rename.new_path == path)
+    }
+
+    /// The rename that moved `path` away, which shows up as a deletion of it in the statuses.
+    pub fn rename_from(&self, path: &RepoPath) -> Option<&FileRename> {
+        self.renames
+            .iter()
+            .find(|rename| !rename.is_copy && &rename.old_path == path)
+    }
+
+    /// The path whose committed text `path` is compared with, which is the path it was renamed
+    /// or copied from when it has been.
+    fn committed_path(&self, path: &RepoPath) -> RepoPath {
+        self.rename_to(path)
+            .map_or_else(|| path.clone(), |rename| rename.old_path.clone())
+    }
+
+    fn reload_renames(&mut self) -> oneshot::Receiver<Result<()>> {
+        let this = self.this.clone();
+        let key = RenamesKey {
+            head_sha: self
+                .snapshot
+                .head_commit
+                .as_ref()
+                .map(|commit| commit.sha.clone()),
+            statuses: self
+                .snapshot
+                .status()
+                .map(|entry| (entry.repo_path, entry.status))
+                .collect(),
+        };
+        self.send_keyed_job(
+            Some(GitJobKey::ReloadRenames),
+            None,
+            move |repo, mut cx| async move {
+                // Collab guests don't learn about the host's renames.
+                let RepositoryState::Local(LocalRepositoryState { backend, .. }) = repo else {
+                    return Ok(());
+                };
+                let unchanged =
+                    this.read_with(&cx, |this, _| this.renames_key.as_ref() == Some(&key))?;
+                if unchanged {
+                    return Ok(());
                 }
-                Ok(())
+                // Without a HEAD there's nothing to have been renamed, and without a new file
+                // there's nothing to have been renamed or copied to.
+                let any_created = key.statuses.iter().any(|(_, status)| status.is_created());
+                let renames = if key.head_sha.is_some() && any_created {
+                    backend.renames().await?
+                } else {
+                    Vec::new()
+                };
+                this.update(&mut cx, |this, cx| {
+                    this.renames_key = Some(key);
+                    if this.renames != renames {
+                        this.renames = renames;
+                        cx.emit(RepositoryEvent::RenamesChanged);
+                        this.reload_buffer_diff_bases(cx);
+                    }
+                })
             },
         )
     }
 
-    pub fn create_remote(
+    /// The files whose local changes git leaves out of the status because of their index flags.
+    pub fn flagged_files(&self) -> &[FlaggedFile] {
+        &self.flagged_files
+    }
+
+    pub fn flagged_file(&self, path: &RepoPath) -> Option<&FlaggedFile> {
+        self.flagged_files
+            .iter()
+            .find(|flagged_file| &flagged_file.path == path)
+    }
+
+    pub fn reload_flagged_files(&mut self) -> oneshot::Receiver<Result<()>> {
+        let this = self.this.clone();
+        self.send_job(None, move |repo, mut cx| async move {
+            // Collab guests don't learn about the host's index flags.
+            let RepositoryState::Local(LocalRepositoryState { backend, .. }) = repo else {
+                return Ok(());
+            };
+            let flagged_files = backend.flagged_files().await?;
+            this.update(&mut cx, |this, cx| {
+                this.set_cached_flagged_files(flagged_files, cx)
+            })
+        })
+    }
+
+    /// Sets or clears the flag on the index entries of the paths.
+    pub fn set_index_flag(
         &mut self,
-        remote_name: String,
-        remote_url: String,
+        paths: Vec<RepoPath>,
+        flag: IndexFlag,
+        value: bool,
+    ) -> oneshot::Receiver<Result<()>> {
+        let this = self.this.clone();
+        let status = format!("git update-index {}", flag.update_index_option(value));
+        self.send_job(Some(status.into()), move |repo, mut cx| async move {
+            let RepositoryState::Local(LocalRepositoryState { backend, .. }) = repo else {
+                anyhow::bail!("index flags are not supported for collab yet");
+            };
+            backend.set_index_flag(paths, flag, value).await?;
+            let flagged_files = backend.flagged_files().await?;
+            this.update(&mut cx, |this, cx| {
+                this.set_cached_flagged_files(flagged_files, cx)
+            })
+        })
+    }
+
+    fn set_cached_flagged_files(
+        &mut self,
+        flagged_files: Vec<FlaggedFile>,
+        cx: &mut Context<Self>,
+    ) {
+        if self.flagged_files != flagged_files {
+            self.flagged_files = flagged_files;
+            cx.emit(RepositoryEvent::IndexFlagsChanged);
+        }
+    }
+
+    /// The pattern that makes git ignore the path, or `None` when it isn't ignored.
+    pub fn ignore_rule(&mut self, path: RepoPath) -> oneshot::Receiver<Result<Option<IgnoreRule>>> {
+        self.send_job(None, move |repo, _cx| async move {
+            let RepositoryState::Local(LocalRepositoryState { backend, .. }) = repo else {
+                anyhow::bail!("explaining ignored paths is not supported for collab yet");
+            };
+            backend.ignore_rule(path).await
+        })
+    }
+
+    pub fn rename_remote(
+        &mut self,
+        old_name: String,
+        new_name: String,
     ) -> oneshot::Receiver<Result<()>> {
-        let id = self.id;
         self.send_job(
-            Some(format!("git remote add {remote_name} {remote_url}").into()),
+            Some(format!("git remote rename {old_name} {new_name}").into()),
             move |repo, _cx| async move {
                 match repo {
                     RepositoryState::Local(LocalRepositoryState { backend, .. }) => {
-                        backend.create_remote(remote_name, remote_url).await
+                        backend.rename_remote(old_name, new_name).await
                     }
-                    RepositoryState::Remote(RemoteRepositoryState { project_id, client }) => {
-                        client
-                            .request(proto::GitCreateRemote {
-                                project_id: project_id.0,
-                                repository_id: id.to_proto(),
-                                remote_name,
-                                remote_url,
-                            })
-                            .await?;
-
-                        Ok(())
+                    RepositoryState::Remote(_) => {
+                        anyhow::bail!("managing remotes is not supported for collab yet")
                     }
                 }
             },
         )
     }
 
-    pub fn remove_remote(&mut self, remote_name: String) -> oneshot::Receiver<Result<()>> {
-        let id = self.id;
+    pub fn set_remote_push_url(
+        &mut self,
+        remote_name: String,
+        push_url: Option<String>,
+    ) -> oneshot::Receiver<Result<()>> {
         self.send_job(
-            Some(format!("git remove remote {remote_name}").into()),
+            Some(format!("git remote set-url --push {remote_name}").into()),
             move |repo, _cx| async move {
                 match repo {
                     RepositoryState::Local(LocalRepositoryState { backend, .. }) => {
-                        backend.remove_remote(remote_name).await
+                        backend.set_remote_push_url(remote_name, push_url).await
                     }
-                    RepositoryState::Remote(RemoteRepositoryState { project_id, client }) => {
-                        client
-                            .request(proto::GitRemoveRemote {
-                                project_id: project_id.0,
-                                repository_id: id.to_proto(),
-                                remote_name,
-                            })
-                            .await?;
-
-                        Ok(())
+                    RepositoryState::Remote(_) => {
+                        anyhow::bail!("managing remotes is not supported for collab yet")
                     }
                 }
             },
         )
     }
 
+    /// Makes push and pull go to this remote when the branch doesn't name one of its own.
+    pub fn set_default_remote(
+        &mut self,
+        remote_name: Option<String>,
+    ) -> oneshot::Receiver<Result<()>> {
+        self.send_job(None, move |repo, _cx| async move {
+            match repo {
+                RepositoryState::Local(LocalRepositoryState { backend, .. }) => {
+                    backend.set_default_remote(remote_name).await
+                }
+                RepositoryState::Remote(_) => {
+                    anyhow::bail!("managing remotes is not supported for collab yet")
+                }
+            }
+        })
+    }
+
     pub fn get_remotes(
         &mut self,
         branch_name: Option<String>,
@@ -5716,6 +7810,16 @@ impl Repository {
                         None
                     };
 
+                    let remote = match remote {
+                        Some(remote) => Some(remote),
+                        None => backend
+                            .remote_infos()
+                            .await?
+                            .into_iter()
+                            .find(|remote| remote.is_default)
+                            .map(|remote| Remote { name: remote.name }),
+                    };
+
                     match remote {
                         Some(remote) => Ok(vec![remote]),
                         None => backend.get_all_remotes().await,
@@ -5747,10 +7851,11 @@ impl Repository {
 
     pub fn branches(&mut self) -> oneshot::Receiver<Result<Vec<Branch>>> {
         let id = self.id;
-        self.send_job(None, move |repo, _| async move {
-            match repo {
+        let this = self.this.clone();
+        self.send_job(None, move |repo, mut cx| async move {
+            let branches: Vec<Branch> = match repo {
                 RepositoryState::Local(LocalRepositoryState { backend, .. }) => {
-                    backend.branches().await
+                    backend.branches().await?
                 }
                 RepositoryState::Remote(RemoteRepositoryState { project_id, client }) => {
                     let response = client
@@ -5760,18 +7865,36 @@ impl Repository {
                         })
                         .await?;
 
-                    let branches = response
+                    response
                         .branches
                         .into_iter()
                         .map(|branch| proto_to_branch(&branch))
-                        .collect();
-
-                    Ok(branches)
+                        .collect()
                 }
-            }
+            };
+            this.update(&mut cx, |this, _| {
+                this.cached_branches = Some(branches.clone().into());
+            })
+            .ok();
+
+            Ok(branches)
         })
     }
 
+    /// The branches as of the last time [`Self::branches`] listed them, so that they can be shown
+    /// while an up to date list loads.
+    pub fn cached_branches(&self) -> Option<Arc<[Branch]>> {
+        self.cached_branches.clone()
+    }
+
+    /// Forgets the branches listed before the branch changed and lists them again, so that
+    /// [`Self::cached_branches`] is filled in once the repository is first scanned and doesn't
+    /// keep showing a previous head or ahead/behind counts.
+    fn reload_cached_branches(&mut self) -> oneshot::Receiver<Result<Vec<Branch>>> {
+        self.cached_branches = None;
+        self.branches()
+    }
+
     /// If this is a linked worktree (*NOT* the main checkout of a repository),
     /// returns the pathed for the linked worktree.
     ///
@@ -6017,106 +8140,222 @@ impl Repository {
                         ),
                     };
                     let response = client
-                        .request(proto::GitDiff {
+                        .request(proto::GitDiff {
+                            project_id: project_id.0,
+                            repository_id: id.to_proto(),
+                            diff_type: proto_diff_type,
+                            merge_base_ref,
+                        })
+                        .await?;
+
+                    Ok(response.diff)
+                }
+            }
+        })
+    }
+
+    pub fn create_branch(
+        &mut self,
+        branch_name: String,
+        base_branch: Option<String>,
+    ) -> oneshot::Receiver<Result<()>> {
+        let id = self.id;
+        let status_msg = if let Some(ref base) = base_branch {
+            format!("git switch -c {branch_name} {base}").into()
+        } else {
+            format!("git switch -c {branch_name}").into()
+        };
+        self.send_job(Some(status_msg), move |repo, _cx| async move {
+            match repo {
+                RepositoryState::Local(LocalRepositoryState { backend, .. }) => {
+                    backend.create_branch(branch_name, base_branch).await
+                }
+                RepositoryState::Remote(RemoteRepositoryState { project_id, client }) => {
+                    client
+                        .request(proto::GitCreateBranch {
+                            project_id: project_id.0,
+                            repository_id: id.to_proto(),
+                            branch_name,
+                            base_branch,
+                        })
+                        .await?;
+
+                    Ok(())
+                }
+            }
+        })
+    }
+
+    pub fn change_branch(&mut self, branch_name: String) -> oneshot::Receiver<Result<()>> {
+        let id = self.id;
+        let this = self.this.clone();
+        self.send_job(
+            Some(format!("git switch {branch_name}").into()),
+            move |repo, mut cx| async move {
+                match repo {
+                    RepositoryState::Local(LocalRepositoryState { backend, .. }) => {
+                        // Stopping a checkout halfway would leave a mix of both branches behind.
+                        let progress = Self::remote_progress_delegate(this, false, &mut cx);
+                        backend.change_branch(branch_name, progress).await
+                    }
+                    RepositoryState::Remote(RemoteRepositoryState { project_id, client }) => {
+                        client
+                            .request(proto::GitChangeBranch {
+                                project_id: project_id.0,
+                                repository_id: id.to_proto(),
+                                branch_name,
+                            })
+                            .await?;
+
+                        Ok(())
+                    }
+                }
+            },
+        )
+    }
+
+    pub fn delete_branch(
+        &mut self,
+        is_remote: bool,
+        branch_name: String,
+        force: bool,
+    ) -> oneshot::Receiver<Result<()>> {
+        let id = self.id;
+        let flag = match (is_remote, force) {
+            (true, _) => "-dr",
+            (false, false) => "-d",
+            (false, true) => "-D",
+        };
+        self.send_job(
+            Some(format!("git branch {flag} {branch_name}").into()),
+            move |repo, _cx| async move {
+                match repo {
+                    RepositoryState::Local(state) => {
+                        state
+                            .backend
+                            .delete_branch(is_remote, branch_name, force)
+                            .await
+                    }
+                    RepositoryState::Remote(RemoteRepositoryState { project_id, client }) => {
+                        client
+                            .request(proto::GitDeleteBranch {
+                                project_id: project_id.0,
+                                repository_id: id.to_proto(),
+                                is_remote,
+                                branch_name,
+                                force,
+                            })
+                            .await?;
+
+                        Ok(())
+                    }
+                }
+            },
+        )
+    }
+
+    /// Makes `branch_name` track `upstream`, or stop tracking a branch when `upstream` is `None`.
+    pub fn set_branch_upstream(
+        &mut self,
+        branch_name: String,
+        upstream: Option<String>,
+    ) -> oneshot::Receiver<Result<()>> {
+        let id = self.id;
+        let status_msg = match &upstream {
+            Some(upstream) => format!("git branch --set-upstream-to={upstream} {branch_name}"),
+            None => format!("git branch --unset-upstream {branch_name}"),
+        };
+        self.send_job(Some(status_msg.into()), move |repo, _cx| async move {
+            match repo {
+                RepositoryState::Local(state) => {
+                    state
+                        .backend
+                        .set_branch_upstream(branch_name, upstream)
+                        .await
+                }
+                RepositoryState::Remote(RemoteRepositoryState { project_id, client }) => {
+                    client
+                        .request(proto::GitSetBranchUpstream {
+                            project_id: project_id.0,
+                            repository_id: id.to_proto(),
+                            branch_name,
+                            upstream,
+                        })
+                        .await?;
+
+                    Ok(())
+                }
+            }
+        })
+    }
+
+    pub fn tags(&mut self) -> oneshot::Receiver<Result<Vec<SharedString>>> {
+        let id = self.id;
+        self.send_job(None, move |repo, _cx| async move {
+            match repo {
+                RepositoryState::Local(state) => state.backend.tags().await,
+                RepositoryState::Remote(RemoteRepositoryState { project_id, client }) => {
+                    let response = client
+                        .request(proto::GitGetTags {
                             project_id: project_id.0,
                             repository_id: id.to_proto(),
-                            diff_type: proto_diff_type,
-                            merge_base_ref,
                         })
                         .await?;
 
-                    Ok(response.diff)
+                    Ok(response.tags.into_iter().map(SharedString::from).collect())
                 }
             }
         })
     }
 
-    pub fn create_branch(
+    pub fn create_tag(
         &mut self,
-        branch_name: String,
-        base_branch: Option<String>,
+        tag_name: String,
+        message: Option<String>,
+        target: Option<String>,
     ) -> oneshot::Receiver<Result<()>> {
         let id = self.id;
-        let status_msg = if let Some(ref base) = base_branch {
-            format!("git switch -c {branch_name} {base}").into()
-        } else {
-            format!("git switch -c {branch_name}").into()
+        let annotate = if message.is_some() { " -a" } else { "" };
+        let status = match &target {
+            Some(target) => format!("git tag{annotate} {tag_name} {target}"),
+            None => format!("git tag{annotate} {tag_name}"),
         };
-        self.send_job(Some(status_msg), move |repo, _cx| async move {
+        self.send_job(Some(status.into()), move |repo, _cx| async move {
             match repo {
-                RepositoryState::Local(LocalRepositoryState { backend, .. }) => {
-                    backend.create_branch(branch_name, base_branch).await
+                RepositoryState::Local(state) => {
+                    state.backend.create_tag(tag_name, message, target).await
                 }
                 RepositoryState::Remote(RemoteRepositoryState { project_id, client }) => {
                     client
-                        .request(proto::GitCreateBranch {
+                        .request(proto::GitCreateTag {
                             project_id: project_id.0,
                             repository_id: id.to_proto(),
-                            branch_name,
+                            tag_name,
+                            message,
+                            target,
                         })
                         .await?;
-
                     Ok(())
                 }
             }
         })
     }
 
-    pub fn change_branch(&mut self, branch_name: String) -> oneshot::Receiver<Result<()>> {
-        let id = self.id;
-        self.send_job(
-            Some(format!("git switch {branch_name}").into()),
-            move |repo, _cx| async move {
-                match repo {
-                    RepositoryState::Local(LocalRepositoryState { backend, .. }) => {
-                        backend.change_branch(branch_name).await
-                    }
-                    RepositoryState::Remote(RemoteRepositoryState { project_id, client }) => {
-                        client
-                            .request(proto::GitChangeBranch {
-                                project_id: project_id.0,
-                                repository_id: id.to_proto(),
-                                branch_name,
-                            })
-                            .await?;
-
-                        Ok(())
-                    }
-                }
-            },
-        )
-    }
-
-    pub fn delete_branch(
-        &mut self,
-        is_remote: bool,
-        branch_name: String,
-    ) -> oneshot::Receiver<Result<()>> {
+    pub fn delete_tag(&mut self, tag_name: String) -> oneshot::Receiver<Result<()>> {
         let id = self.id;
         self.send_job(
-            Some(
-                format!(
-                    "git branch {} {}",
-                    if is_remote { "-dr" } else { "-d" },
-                    branch_name
-                )
-                .into(),
-            ),
+            Some(format!("git tag -d {tag_name}").into()),
             move |repo, _cx| async move {
                 match repo {
-                    RepositoryState::Local(state) => {
-                        state.backend.delete_branch(is_remote, branch_name).await
-                    }
+                    RepositoryState::Local(state) => state.backend.delete_tag(tag_name).await,
                     RepositoryState::Remote(RemoteRepositoryState { project_id, client }) => {
                         client
-                            .request(proto::GitDeleteBranch {
+                            .request(proto::GitDeleteTag {
                                 project_id: project_id.0,
                                 repository_id: id.to_proto(),
-                                is_remote,
-                                branch_name,
+                                tag_name,
                             })
                             .await?;
-
                         Ok(())
                     }
                 }
@@ -6230,10 +8469,15 @@ impl Repository {
                 .into_iter()
                 .filter_map(|path| Some((RepoPath::from_proto(&path).ok()?, vec![]))),
         );
-        let conflicts_changed =
-            self.snapshot.merge.merge_heads_by_conflicted_path != new_merge_heads;
+        let new_operation = update
+            .in_progress_operation
+            .map(proto_to_in_progress_operation);
+        let conflicts_changed = self.snapshot.merge.merge_heads_by_conflicted_path
+            != new_merge_heads
+            || self.snapshot.merge.operation != new_operation;
         self.snapshot.merge.merge_heads_by_conflicted_path = new_merge_heads;
         self.snapshot.merge.message = update.merge_message.map(SharedString::from);
+        self.snapshot.merge.operation = new_operation;
         let new_stash_entries = GitStash {
             entries: update
                 .stash_entries
@@ -6344,6 +8588,20 @@ impl Repository {
         self.pending_ops = updated;
     }
 
+    /// Recomputes every status right away, such as after an ignore file was edited, rather than
+    /// waiting for the file watcher to report the change.
+    pub fn rescan(&mut self, cx: &mut Context<Self>) {
+        let updates_tx = self
+            .git_store()
+            .and_then(|git_store| match &git_store.read(cx).state {
+                GitStoreState::Local { downstream, .. } => downstream
+                    .as_ref()
+                    .map(|downstream| downstream.updates_tx.clone()),
+                _ => None,
+            });
+        self.schedule_scan(updates_tx, cx);
+    }
+
     fn schedule_scan(
         &mut self,
         updates_tx: Option<mpsc::UnboundedSender<DownstreamUpdate>>,
@@ -6489,10 +8747,11 @@ impl Repository {
         repo_path: RepoPath,
         cx: &App,
     ) -> Task<Result<DiffBasesChange>> {
+        let committed_path = self.committed_path(&repo_path);
         let rx = self.send_job(None, move |state, _| async move {
             match state {
                 RepositoryState::Local(LocalRepositoryState { backend, .. }) => {
-                    let committed_text = backend.load_committed_text(repo_path.clone()).await;
+                    let committed_text = backend.load_committed_text(committed_path).await;
                     let staged_text = backend.load_index_text(repo_path).await;
                     let diff_bases_change = if committed_text == staged_text {
                         DiffBasesChange::SetBoth(committed_text)
@@ -6529,6 +8788,251 @@ impl Repository {
         cx.spawn(|_: &mut AsyncApp| async move { rx.await? })
     }
 
+    /// Blames a file as it was at `revision`, for walking back through the history of its lines.
+    pub fn blame_at_revision(
+        &mut self,
+        revision: String,
+        path: RepoPath,
+        cx: &App,
+    ) -> Task<Result<Blame>> {
+        let rx = self.send_job(None, move |state, _| async move {
+            let RepositoryState::Local(LocalRepositoryState { backend, .. }) = state else {
+                anyhow::bail!("blaming past revisions is not supported for collab yet");
+            };
+            backend.blame_at_revision(revision, path).await
+        });
+        cx.spawn(|_: &mut AsyncApp| async move { rx.await? })
+    }
+
+    pub fn commit_overview(&mut self, commit: String, cx: &App) -> Task<Result<CommitOverview>> {
+        let rx = self.send_job(None, move |state, _| async move {
+            let RepositoryState::Local(LocalRepositoryState { backend, .. }) = state else {
+                anyhow::bail!("commit overviews are not supported for collab yet");
+            };
+            backend.commit_overview(commit).await
+        });
+        cx.spawn(|_: &mut AsyncApp| async move { rx.await? })
+    }
+
+    pub fn compare_revisions(
+        &mut self,
+        base: String,
+        target: String,
+        cx: &App,
+    ) -> Task<Result<CommitDiff>> {
+        let rx = self.send_job(None, move |state, _| async move {
+            let RepositoryState::Local(LocalRepositoryState { backend, .. }) = state else {
+                anyhow::bail!("comparing revisions is not supported for collab yet");
+            };
+            backend.compare_revisions(base, target).await
+        });
+        cx.spawn(|_: &mut AsyncApp| async move { rx.await? })
+    }
+
+    pub fn merge_base(
+        &mut self,
+        first: String,
+        second: String,
+        cx: &App,
+    ) -> Task<Result<Option<String>>> {
+        let rx = self.send_job(None, move |state, _| async move {
+            let RepositoryState::Local(LocalRepositoryState { backend, .. }) = state else {
+                anyhow::bail!("finding merge bases is not supported for collab yet");
+            };
+            backend.merge_base(first, second).await
+        });
+        cx.spawn(|_: &mut AsyncApp| async move { rx.await? })
+    }
+
+    pub fn line_history(
+        &mut self,
+        path: RepoPath,
+        lines: RangeInclusive<u32>,
+        cx: &App,
+    ) -> Task<Result<Vec<LineHistoryEntry>>> {
+        let rx = self.send_job(None, move |state, _| async move {
+            let RepositoryState::Local(LocalRepositoryState { backend, .. }) = state else {
+                anyhow::bail!("line history is not supported for collab yet");
+            };
+            backend.line_history(path, lines).await
+        });
+        cx.spawn(|_: &mut AsyncApp| async move { rx.await? })
+    }
+
+    pub fn load_text_at_revision(
+        &mut self,
+        revision: String,
+        path: RepoPath,
+        cx: &App,
+    ) -> Task<Result<Option<String>>> {
+        let repository_id = self.snapshot.id;
+        let rx = self.send_job(None, move |state, _| async move {
+            match state {
+                RepositoryState::Local(LocalRepositoryState { backend, .. }) => {
+                    backend.load_text_at_revision(revision, path).await
+                }
+                RepositoryState::Remote(RemoteRepositoryState { client, project_id }) => {
+                    let response = client
+                        .request(proto::GitLoadTextAtRevision {
+                            project_id: project_id.to_proto(),
+                            repository_id: repository_id.to_proto(),
+                            revision,
+                            path: path.to_proto(),
+                        })
+                        .await?;
+                    Ok(response.content)
+                }
+            }
+        });
+        cx.spawn(|_: &mut AsyncApp| async move { rx.await? })
+    }
+
+    pub fn load_conflict_stages(
+        &mut self,
+        path: RepoPath,
+        cx: &App,
+    ) -> Task<Result<ConflictStages>> {
+        let repository_id = self.snapshot.id;
+        let rx = self.send_job(None, move |state, _| async move {
+            match state {
+                RepositoryState::Local(LocalRepositoryState { backend, .. }) => {
+                    backend.load_conflict_stages(path).await
+                }
+                RepositoryState::Remote(RemoteRepositoryState { client, project_id }) => {
+                    let response = client
+                        .request(proto::GitLoadConflictStages {
+                            project_id: project_id.to_proto(),
+                            repository_id: repository_id.to_proto(),
+                            path: path.to_proto(),
+                        })
+                        .await?;
+                    Ok(ConflictStages {
+                        base: response.base,
+                        ours: response.ours,
+                        theirs: response.theirs,
+                    })
+                }
+            }
+        });
+        cx.spawn(|_: &mut AsyncApp| async move { rx.await? })
+    }
+
+    pub fn load_commit_template(
+        &mut self,
+        configured_path: Option<String>,
+        cx: &App,
+    ) -> Task<Result<Option<String>>> {
+        let repository_id = self.snapshot.id;
+        let rx = self.send_job(None, move |state, _| async move {
+            match state {
+                RepositoryState::Local(LocalRepositoryState { backend, .. }) => {
+                    backend.load_commit_template(configured_path).await
+                }
+                // The host uses the template from its own settings.
+                RepositoryState::Remote(RemoteRepositoryState { client, project_id }) => {
+                    let response = client
+                        .request(proto::GitLoadCommitTemplate {
+                            project_id: project_id.to_proto(),
+                            repository_id: repository_id.to_proto(),
+                        })
+                        .await?;
+                    Ok(response.template)
+                }
+            }
+        });
+        cx.spawn(|_: &mut AsyncApp| async move { rx.await? })
+    }
+
+    pub fn recent_commit_authors(
+        &mut self,
+        max_commits: usize,
+        cx: &App,
+    ) -> Task<Result<Vec<(SharedString, SharedString)>>> {
+        let repository_id = self.snapshot.id;
+        let rx = self.send_job(None, move |state, _| async move {
+            match state {
+                RepositoryState::Local(LocalRepositoryState { backend, .. }) => {
+                    backend.recent_commit_authors(max_commits).await
+                }
+                RepositoryState::Remote(RemoteRepositoryState { client, project_id }) => {
+                    let response = client
+                        .request(proto::GitRecentCommitAuthors {
+                            project_id: project_id.to_proto(),
+                            repository_id: repository_id.to_proto(),
+                            max_commits: max_commits as u64,
+                        })
+                        .await?;
+                    Ok(response
+                        .authors
+                        .into_iter()
+                        .map(|author| (author.name.into(), author.email.into()))
+                        .collect())
+                }
+            }
+        });
+        cx.spawn(|_: &mut AsyncApp| async move { rx.await? })
+    }
+
+    /// Verifies the signatures of the given commits in the background, reusing earlier results.
+    /// Unsigned commits are left out of the result.
+    pub fn verify_commit_signatures(
+        &mut self,
+        shas: Vec<SharedString>,
+        cx: &mut Context<Self>,
+    ) -> Task<Result<HashMap<SharedString, CommitSignature>>> {
+        let mut signatures = HashMap::default();
+        let mut uncached_shas = Vec::new();
+        for sha in shas {
+            match self.commit_signatures.get(&sha) {
+                Some(Some(signature)) => {
+                    signatures.insert(sha, signature.clone());
+                }
+                Some(None) => {}
+                None => uncached_shas.push(sha),
+            }
+        }
+        if uncached_shas.is_empty() {
+            return Task::ready(Ok(signatures));
+        }
+
+        let repository_id = self.snapshot.id;
+        let requested_shas = uncached_shas.clone();
+        let rx = self.send_job(None, move |state, _| async move {
+            match state {
+                RepositoryState::Local(LocalRepositoryState { backend, .. }) => {
+                    backend.verify_commit_signatures(requested_shas).await
+                }
+                RepositoryState::Remote(RemoteRepositoryState { client, project_id }) => {
+                    let response = client
+                        .request(proto::GitVerifyCommitSignatures {
+                            project_id: project_id.to_proto(),
+                            repository_id: repository_id.to_proto(),
+                            shas: requested_shas.iter().map(ToString::to_string).collect(),
+                        })
+                        .await?;
+                    Ok(response
+                        .signatures
+                        .into_iter()
+                        .map(proto_to_commit_signature)
+                        .collect())
+                }
+            }
+        });
+        cx.spawn(async move |this, cx| {
+            let mut verified = rx.await??;
+            this.update(cx, |this, _| {
+                for sha in uncached_shas {
+                    let signature = verified.remove(&sha);
+                    if let Some(signature) = &signature {
+                        signatures.insert(sha.clone(), signature.clone());
+                    }
+                    this.commit_signatures.insert(sha, signature);
+                }
+            })?;
+            Ok(signatures)
+        })
+    }
+
     fn load_blob_content(&mut self, oid: Oid, cx: &App) -> Task<Result<String>> {
         let repository_id = self.snapshot.id;
         let rx = self.send_job(None, move |state, _| async move {
@@ -6863,6 +9367,7 @@ fn get_permalink_in_rust_registry_src(
     provider_registry: Arc<GitHostingProviderRegistry>,
     path: PathBuf,
     selection: Range<u32>,
+    at_head: bool,
 ) -> Result<url::Url> {
     #[derive(Deserialize)]
     struct CargoVcsGit {
@@ -6897,10 +9402,16 @@ fn get_permalink_in_rust_registry_src(
     let (provider, remote) = parse_git_remote_url(provider_registry, &manifest.package.repository)
         .context("parsing package.repository field of manifest")?;
     let path = PathBuf::from(cargo_vcs_info.path_in_vcs).join(path.strip_prefix(dir).unwrap());
+    // The published crate's commit, unless the link should follow the repository's default branch.
+    let sha = if at_head {
+        "HEAD"
+    } else {
+        &cargo_vcs_info.git.sha1
+    };
     let permalink = provider.build_permalink(
         remote,
         BuildPermalinkParams::new(
-            &cargo_vcs_info.git.sha1,
+            sha,
             &RepoPath::from_rel_path(
                 &RelPath::new(&path, PathStyle::local()).context("invalid path")?,
             ),
@@ -6910,6 +9421,19 @@ fn get_permalink_in_rust_registry_src(
     Ok(permalink)
 }
 
+/// The revisions to blame past, as configured for the worktree of the buffer's file.
+fn blame_ignore_revs(buffer: &Buffer, cx: &App) -> Vec<String> {
+    let location = buffer.file().map(|file| SettingsLocation {
+        worktree_id: file.worktree_id(cx),
+        path: file.path(),
+    });
+    ProjectSettings::get(location, cx)
+        .git
+        .blame
+        .ignore_revs
+        .clone()
+}
+
 fn serialize_blame_buffer_response(blame: Option<git::blame::Blame>) -> proto::BlameBufferResponse {
     let Some(blame) = blame else {
         return proto::BlameBufferResponse {
@@ -6936,6 +9460,9 @@ fn serialize_blame_buffer_response(blame: Option<git::blame::Blame>) -> proto::B
             summary: entry.summary,
             previous: entry.previous,
             filename: entry.filename,
+            ignored_revision: entry
+                .ignored_revision
+                .map(|revision| revision.as_bytes().into()),
         })
         .collect::<Vec<_>>();
 
@@ -6976,6 +9503,9 @@ fn deserialize_blame_buffer_response(
                 summary: entry.summary,
                 previous: entry.previous,
                 filename: entry.filename,
+                ignored_revision: entry
+                    .ignored_revision
+                    .and_then(|revision| git::Oid::from_bytes(&revision).ok()),
             })
         })
         .collect::<Vec<_>>();
@@ -7071,6 +9601,123 @@ fn proto_to_branch(proto: &proto::Branch) -> git::repository::Branch {
     }
 }
 
+fn in_progress_operation_kind_to_proto(
+    kind: InProgressOperationKind,
+) -> proto::in_progress_operation::Kind {
+    use proto::in_progress_operation::Kind;
+    match kind {
+        InProgressOperationKind::Merge => Kind::Merge,
+        InProgressOperationKind::CherryPick => Kind::CherryPick,
+        InProgressOperationKind::Revert => Kind::Revert,
+        InProgressOperationKind::Rebase => Kind::Rebase,
+    }
+}
+
+fn proto_to_in_progress_operation_kind(
+    kind: proto::in_progress_operation::Kind,
+) -> InProgressOperationKind {
+    use proto::in_progress_operation::Kind;
+    match kind {
+        Kind::Merge => InProgressOperationKind::Merge,
+        Kind::CherryPick => InProgressOperationKind::CherryPick,
+        Kind::Revert => InProgressOperationKind::Revert,
+        Kind::Rebase => InProgressOperationKind::Rebase,
+    }
+}
+
+fn in_progress_operation_to_proto(operation: InProgressOperation) -> proto::InProgressOperation {
+    proto::InProgressOperation {
+        kind: in_progress_operation_kind_to_proto(operation.kind) as i32,
+        step: operation.progress.map(|(step, _)| step as u64),
+        total: operation.progress.map(|(_, total)| total as u64),
+    }
+}
+
+fn proto_to_in_progress_operation(operation: proto::InProgressOperation) -> InProgressOperation {
+    InProgressOperation {
+        kind: proto_to_in_progress_operation_kind(operation.kind()),
+        progress: operation
+            .step
+            .zip(operation.total)
+            .map(|(step, total)| (step as usize, total as usize)),
+    }
+}
+
+fn bisect_status_to_proto(status: BisectStatus) -> proto::GitBisectResponse {
+    match status {
+        BisectStatus::Testing {
+            sha,
+            subject,
+            remaining_steps,
+        } => proto::GitBisectResponse {
+            sha,
+            subject,
+            remaining_steps: remaining_steps as u64,
+            found: false,
+        },
+        BisectStatus::Found { sha, subject } => proto::GitBisectResponse {
+            sha,
+            subject,
+            remaining_steps: 0,
+            found: true,
+        },
+    }
+}
+
+fn proto_to_bisect_status(response: proto::GitBisectResponse) -> BisectStatus {
+    if response.found {
+        BisectStatus::Found {
+            sha: response.sha,
+            subject: response.subject,
+        }
+    } else {
+        BisectStatus::Testing {
+            sha: response.sha,
+            subject: response.subject,
+            remaining_steps: response.remaining_steps as usize,
+        }
+    }
+}
+
+fn commit_signature_to_proto(
+    sha: SharedString,
+    signature: CommitSignature,
+) -> proto::git_verify_commit_signatures_response::CommitSignature {
+    use proto::git_verify_commit_signatures_response::commit_signature::Status;
+    let status = match signature.status {
+        SignatureStatus::Verified => Status::Verified,
+        SignatureStatus::Unverified => Status::Unverified,
+        SignatureStatus::UnknownKey => Status::UnknownKey,
+        SignatureStatus::Expired => Status::Expired,
+    };
+    proto::git_verify_commit_signatures_response::CommitSignature {
+        sha: sha.to_string(),
+        status: status as i32,
+        signer: signature.signer.map(|signer| signer.to_string()),
+        key: signature.key.map(|key| key.to_string()),
+    }
+}
+
+fn proto_to_commit_signature(
+    proto: proto::git_verify_commit_signatures_response::CommitSignature,
+) -> (SharedString, CommitSignature) {
+    use proto::git_verify_commit_signatures_response::commit_signature::Status;
+    let status = match proto.status() {
+        Status::Verified => SignatureStatus::Verified,
+        Status::Unverified => SignatureStatus::Unverified,
+        Status::UnknownKey => SignatureStatus::UnknownKey,
+        Status::Expired => SignatureStatus::Expired,
+    };
+    (
+        proto.sha.into(),
+        CommitSignature {
+            status,
+            signer: proto.signer.map(Into::into),
+            key: proto.key.map(Into::into),
+        },
+    )
+}
+
 fn commit_details_to_proto(commit: &CommitDetails) -> proto::GitCommitDetails {
     proto::GitCommitDetails {
         sha: commit.sha.to_string(),
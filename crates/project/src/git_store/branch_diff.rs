@@ -70,7 +70,9 @@ impl BranchDiff {
                     }
                     GitStoreEvent::RepositoryUpdated(
                         event_repo_id,
-                        RepositoryEvent::StatusesChanged | RepositoryEvent::BranchChanged,
+                        RepositoryEvent::StatusesChanged
+                        | RepositoryEvent::BranchChanged
+                        | RepositoryEvent::RenamesChanged,
                         _,
                     ) => this
                         .repo
@@ -304,6 +306,12 @@ impl BranchDiff {
                 if !status.has_changes() {
                     continue;
                 }
+                // The path a file was renamed from is shown as part of the file's own diff.
+                if matches!(self.diff_base, DiffBase::Head)
+                    && repo.read(cx).rename_from(&item.repo_path).is_some()
+                {
+                    continue;
+                }
 
                 let Some(project_path) =
                     repo.read(cx).repo_path_to_project_path(&item.repo_path, cx)
@@ -4925,14 +4925,26 @@ impl Project {
         })
     }
 
+    pub fn blame_buffer_rows(
+        &self,
+        buffer: &Entity<Buffer>,
+        rows: Range<u32>,
+        cx: &mut App,
+    ) -> Task<Result<Option<Blame>>> {
+        self.git_store.update(cx, |git_store, cx| {
+            git_store.blame_buffer_rows(buffer, None, Some(rows), cx)
+        })
+    }
+
     pub fn get_permalink_to_line(
         &self,
         buffer: &Entity<Buffer>,
         selection: Range<u32>,
+        at_head: bool,
         cx: &mut App,
     ) -> Task<Result<url::Url>> {
         self.git_store.update(cx, |git_store, cx| {
-            git_store.get_permalink_to_line(buffer, selection, cx)
+            git_store.get_permalink_to_line(buffer, selection, at_head, cx)
         })
     }
 
@@ -1,4 +1,5 @@
 use anyhow::Context as _;
+use buffer_diff::IgnoreWhitespace;
 use collections::HashMap;
 use context_server::ContextServerCommand;
 use dap::adapters::DebugAdapterName;
@@ -455,6 +456,8 @@ pub struct GitSettings {
     ///
     /// Default: file_name_first
     pub path_style: GitPathStyle,
+    /// Settings for diffs shown in comparison views.
+    pub diff: GitDiffSettings,
     /// Directory where git worktrees are created, relative to the repository
     /// working directory. When the resolved directory is outside the project
     /// root, the project's directory name is automatically appended so that
@@ -462,6 +465,45 @@ pub struct GitSettings {
     ///
     /// Default: ../worktrees
     pub worktree_directory: String,
+    /// Path to a commit message template, overriding the `commit.template` git config.
+    ///
+    /// Default: None
+    pub commit_template: Option<String>,
+    /// Whether committing without running the pre-commit hook is offered.
+    ///
+    /// Default: false
+    pub allow_no_verify: bool,
+    /// How pulling integrates upstream changes, overriding the `pull.rebase` and `pull.ff` git
+    /// config.
+    ///
+    /// Default: None
+    pub pull_strategy: Option<settings::PullStrategy>,
+    /// The branch that the current branch is compared with through its merge base, overriding
+    /// the default branch of the repository's remote.
+    ///
+    /// Default: None
+    pub default_base_branch: Option<String>,
+    /// Settings for fetching from remotes in the background.
+    pub auto_fetch: AutoFetchSettings,
+}
+
+/// Background fetches run at most this often, however short the configured interval is.
+const MIN_AUTO_FETCH_INTERVAL_SECONDS: u64 = 30;
+
+#[derive(Clone, Debug)]
+pub struct AutoFetchSettings {
+    /// Whether to fetch from remotes in the background.
+    ///
+    /// Default: false
+    pub enabled: bool,
+    /// How long to wait between background fetches.
+    ///
+    /// Default: 5 minutes
+    pub interval: Duration,
+    /// The remotes to fetch from, or every remote when `None`.
+    ///
+    /// Default: None
+    pub remotes: Option<Vec<String>>,
 }
 
 #[derive(Clone, Copy, Debug)]
@@ -519,12 +561,20 @@ pub struct InlineBlameSettings {
     pub show_commit_summary: bool,
 }
 
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Debug)]
 pub struct BlameSettings {
     /// Whether to show the avatar of the author of the commit.
     ///
     /// Default: true
     pub show_avatar: bool,
+    /// Whether to color the blame column by the age of each line's commit.
+    ///
+    /// Default: false
+    pub heat_map: bool,
+    /// Revisions for blame to look past, in addition to those in `blame.ignoreRevsFile`.
+    ///
+    /// Default: []
+    pub ignore_revs: Vec<String>,
 }
 
 impl GitSettings {
@@ -555,6 +605,18 @@ impl Default for BranchPickerSettings {
     }
 }
 
+#[derive(Clone, Copy, Debug, Default)]
+pub struct GitDiffSettings {
+    /// Which whitespace differences to ignore when comparing files.
+    ///
+    /// Default: none
+    pub ignore_whitespace: IgnoreWhitespace,
+    /// Where the file comparison picker opens diffs.
+    ///
+    /// Default: active_pane
+    pub placement: settings::DiffPlacement,
+}
+
 #[derive(Clone, Debug)]
 pub struct DiagnosticsSettings {
     /// Whether to show the project diagnostics button in the status bar.
@@ -638,9 +700,11 @@ impl Settings for ProjectSettings {
                 }
             },
             blame: {
-                let blame = git.blame.unwrap();
+                let blame = git.blame.clone().unwrap();
                 BlameSettings {
                     show_avatar: blame.show_avatar.unwrap(),
+                    heat_map: blame.heat_map.unwrap(),
+                    ignore_revs: blame.ignore_revs.unwrap(),
                 }
             },
             branch_picker: {
@@ -651,10 +715,38 @@ impl Settings for ProjectSettings {
             },
             hunk_style: git.hunk_style.unwrap(),
             path_style: git.path_style.unwrap().into(),
+            diff: {
+                let diff = git.diff.unwrap();
+                GitDiffSettings {
+                    ignore_whitespace: match diff.ignore_whitespace.unwrap() {
+                        settings::IgnoreWhitespaceSetting::None => IgnoreWhitespace::None,
+                        settings::IgnoreWhitespaceSetting::Trailing => IgnoreWhitespace::Trailing,
+                        settings::IgnoreWhitespaceSetting::All => IgnoreWhitespace::All,
+                    },
+                    placement: diff.placement.unwrap(),
+                }
+            },
             worktree_directory: git
                 .worktree_directory
                 .clone()
                 .unwrap_or_else(|| DEFAULT_WORKTREE_DIRECTORY.to_string()),
+            commit_template: git.commit_template.clone(),
+            allow_no_verify: git.allow_no_verify.unwrap(),
+            pull_strategy: git.pull_strategy,
+            default_base_branch: git.default_base_branch.clone(),
+            auto_fetch: {
+                let auto_fetch = git.auto_fetch.clone().unwrap();
+                AutoFetchSettings {
+                    enabled: auto_fetch.enabled.unwrap(),
+                    interval: Duration::from_secs(
+                        auto_fetch
+                            .interval_seconds
+                            .unwrap()
+                            .max(MIN_AUTO_FETCH_INTERVAL_SECONDS),
+                    ),
+                    remotes: auto_fetch.remotes,
+                }
+            },
         };
         Self {
             context_servers: project
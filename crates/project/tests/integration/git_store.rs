@@ -1648,3 +1648,270 @@ mod resolve_worktree_tests {
         }
     }
 }
+
+mod auto_fetch {
+    use std::{path::Path, time::Duration};
+
+    use fs::FakeFs;
+    use git::repository::{FetchOptions, Remote};
+    use gpui::{TestAppContext, UpdateGlobal};
+    use project::Project;
+    use serde_json::json;
+    use settings::SettingsStore;
+    use util::path;
+
+    const INTERVAL: Duration = Duration::from_secs(300);
+
+    fn init_test(cx: &mut gpui::TestAppContext) {
+        zlog::init_test();
+
+        cx.update(|cx| {
+            let settings_store = SettingsStore::test(cx);
+            cx.set_global(settings_store);
+        });
+    }
+
+    #[gpui::test]
+    async fn test_auto_fetch_backs_off_after_failures(cx: &mut TestAppContext) {
+        init_test(cx);
+        let fs = FakeFs::new(cx.background_executor.clone());
+        fs.insert_tree(
+            path!("/root"),
+            json!({
+                ".git": {},
+                "file.txt": "content",
+            }),
+        )
+        .await;
+
+        let _project = Project::test(fs.clone(), [path!("/root").as_ref()], cx).await;
+        cx.executor().run_until_parked();
+
+        let fetch_count = || {
+            fs.with_git_state(Path::new(path!("/root/.git")), false, |state| {
+                state.fetches.len()
+            })
+            .unwrap()
+        };
+
+        cx.executor().advance_clock(INTERVAL);
+        cx.executor().run_until_parked();
+        assert_eq!(fetch_count(), 0, "auto fetch is disabled by default");
+
+        cx.update(|cx| {
+            SettingsStore::update_global(cx, |store, cx| {
+                store.update_user_settings(cx, |settings| {
+                    let auto_fetch = settings
+                        .git
+                        .get_or_insert_default()
+                        .auto_fetch
+                        .get_or_insert_default();
+                    auto_fetch.enabled = Some(true);
+                    auto_fetch.remotes = Some(vec!["origin".to_string()]);
+                })
+            });
+        });
+        cx.executor().advance_clock(INTERVAL);
+        cx.executor().run_until_parked();
+        fs.with_git_state(Path::new(path!("/root/.git")), false, |state| {
+            assert_eq!(
+                state.fetches,
+                [FetchOptions::Remote(Remote {
+                    name: "origin".into()
+                })]
+            );
+            state.simulated_fetch_error = Some("Authentication failed".to_string());
+        })
+        .unwrap();
+
+        cx.executor().advance_clock(INTERVAL);
+        cx.executor().run_until_parked();
+        assert_eq!(fetch_count(), 2);
+
+        // After a failure, the next fetch waits twice as long.
+        cx.executor().advance_clock(INTERVAL);
+        cx.executor().run_until_parked();
+        assert_eq!(fetch_count(), 2);
+        cx.executor().advance_clock(INTERVAL);
+        cx.executor().run_until_parked();
+        assert_eq!(fetch_count(), 3);
+
+        fs.with_git_state(Path::new(path!("/root/.git")), false, |state| {
+            state.simulated_fetch_error = None;
+        })
+        .unwrap();
+        cx.executor().advance_clock(INTERVAL * 4);
+        cx.executor().run_until_parked();
+        assert_eq!(fetch_count(), 4);
+
+        // A successful fetch goes back to the configured interval.
+        cx.executor().advance_clock(INTERVAL);
+        cx.executor().run_until_parked();
+        assert_eq!(fetch_count(), 5);
+    }
+
+    #[gpui::test]
+    async fn test_auto_fetch_once_per_repository(cx: &mut TestAppContext) {
+        init_test(cx);
+        cx.update(|cx| {
+            SettingsStore::update_global(cx, |store, cx| {
+                store.update_user_settings(cx, |settings| {
+                    let auto_fetch = settings
+                        .git
+                        .get_or_insert_default()
+                        .auto_fetch
+                        .get_or_insert_default();
+                    auto_fetch.enabled = Some(true);
+                })
+            });
+        });
+        let fs = FakeFs::new(cx.background_executor.clone());
+        fs.insert_tree(
+            path!("/root"),
+            json!({
+                ".git": {},
+                "file.txt": "content",
+            }),
+        )
+        .await;
+
+        let _project_a = Project::test(fs.clone(), [path!("/root").as_ref()], cx).await;
+        let _project_b = Project::test(fs.clone(), [path!("/root").as_ref()], cx).await;
+        cx.executor().run_until_parked();
+
+        cx.executor().advance_clock(INTERVAL);
+        cx.executor().run_until_parked();
+        fs.with_git_state(Path::new(path!("/root/.git")), false, |state| {
+            assert_eq!(state.fetches, [FetchOptions::All]);
+        })
+        .unwrap();
+    }
+}
+
+mod cached_branches {
+    use std::path::Path;
+
+    use fs::FakeFs;
+    use gpui::TestAppContext;
+    use project::Project;
+    use serde_json::json;
+    use settings::SettingsStore;
+    use util::path;
+
+    fn init_test(cx: &mut gpui::TestAppContext) {
+        zlog::init_test();
+
+        cx.update(|cx| {
+            let settings_store = SettingsStore::test(cx);
+            cx.set_global(settings_store);
+        });
+    }
+
+    #[gpui::test]
+    async fn test_cached_branches_follow_branch_changes(cx: &mut TestAppContext) {
+        init_test(cx);
+        let fs = FakeFs::new(cx.background_executor.clone());
+        fs.insert_tree(
+            path!("/root"),
+            json!({
+                ".git": {},
+                "file.txt": "content",
+            }),
+        )
+        .await;
+        fs.insert_branches(Path::new(path!("/root/.git")), &["main", "feature"]);
+
+        let project = Project::test(fs.clone(), [path!("/root").as_ref()], cx).await;
+        cx.executor().run_until_parked();
+
+        let repository = project.read_with(cx, |project, cx| {
+            project.repositories(cx).values().next().unwrap().clone()
+        });
+        let head_branch = |cx: &mut TestAppContext| {
+            repository.read_with(cx, |repository, _| {
+                let branches = repository
+                    .cached_branches()
+                    .expect("branches are listed once the repository is scanned");
+                assert_eq!(branches.len(), 2);
+                branches
+                    .iter()
+                    .find(|branch| branch.is_head)
+                    .map(|branch| branch.name().to_string())
+            })
+        };
+        assert_eq!(head_branch(cx).as_deref(), Some("main"));
+
+        fs.set_branch_name(Path::new(path!("/root/.git")), Some("feature"));
+        cx.executor().run_until_parked();
+        assert_eq!(head_branch(cx).as_deref(), Some("feature"));
+    }
+}
+
+mod permalinks {
+    use std::path::Path;
+
+    use fs::FakeFs;
+    use git::GitHostingProviderRegistry;
+    use gpui::TestAppContext;
+    use project::Project;
+    use serde_json::json;
+    use settings::SettingsStore;
+    use util::path;
+
+    fn init_test(cx: &mut gpui::TestAppContext) {
+        zlog::init_test();
+
+        cx.update(|cx| {
+            let settings_store = SettingsStore::test(cx);
+            cx.set_global(settings_store);
+            GitHostingProviderRegistry::default_global(cx);
+            git_hosting_providers::init(cx);
+        });
+    }
+
+    #[gpui::test]
+    async fn test_permalink_at_head(cx: &mut TestAppContext) {
+        init_test(cx);
+        let fs = FakeFs::new(cx.background_executor.clone());
+        fs.insert_tree(
+            path!("/root"),
+            json!({
+                ".git": {},
+                "src": {
+                    "main.rs": "fn main() {\n}\n",
+                },
+            }),
+        )
+        .await;
+        let dot_git = Path::new(path!("/root/.git"));
+        fs.set_head_for_repo(
+            dot_git,
+            &[("src/main.rs", "fn main() {\n}\n".into())],
+            "a1b2c3d4",
+        );
+        fs.set_remote_for_repo(dot_git, "origin", "git@github.com:zed-industries/zed.git");
+
+        let project = Project::test(fs.clone(), [path!("/root").as_ref()], cx).await;
+        let buffer = project
+            .update(cx, |project, cx| {
+                project.open_local_buffer(path!("/root/src/main.rs"), cx)
+            })
+            .await
+            .unwrap();
+        cx.executor().run_until_parked();
+
+        let mut permalink = |at_head| {
+            project.update(cx, |project, cx| {
+                project.get_permalink_to_line(&buffer, 0..1, at_head, cx)
+            })
+        };
+        assert_eq!(
+            permalink(false).await.unwrap().as_str(),
+            "https://github.com/zed-industries/zed/blob/a1b2c3d4/src/main.rs#L1-L2"
+        );
+        assert_eq!(
+            permalink(true).await.unwrap().as_str(),
+            "https://github.com/zed-industries/zed/blob/HEAD/src/main.rs#L1-L2"
+        );
+    }
+}
@@ -16,9 +16,10 @@ use editor::{
 use feature_flags::{FeatureFlagAppExt, ProjectPanelUndoRedoFeatureFlag};
 use file_icons::FileIcons;
 use git;
+use git::repository::RepoPath;
 use git::status::GitSummary;
 use git_ui;
-use git_ui::file_diff_view::FileDiffView;
+use git_ui::{directory_diff_view::DirectoryDiffView, file_diff_view::FileDiffView};
 use gpui::{
     Action, AnyElement, App, AsyncWindowContext, Bounds, ClipboardEntry as GpuiClipboardEntry,
     ClipboardItem, Context, CursorStyle, DismissEvent, Div, DragMoveEvent, Entity, EventEmitter,
@@ -35,7 +36,7 @@ use notifications::status_toast::{StatusToast, ToastIcon};
 use project::{
     Entry, EntryKind, Fs, GitEntry, GitEntryRef, GitTraversal, Project, ProjectEntryId,
     ProjectPath, Worktree, WorktreeId,
-    git_store::{GitStoreEvent, RepositoryEvent, git_traversal::ChildEntriesGitIter},
+    git_store::{GitStoreEvent, Repository, RepositoryEvent, git_traversal::ChildEntriesGitIter},
     project_settings::GoToDiagnosticSeverityFilter,
 };
 use project_panel_settings::ProjectPanelSettings;
@@ -62,7 +63,7 @@ use theme::ThemeSettings;
 use ui::{
     Color, ContextMenu, ContextMenuEntry, DecoratedIcon, Divider, Icon, IconDecoration,
     IconDecorationKind, IndentGuideColors, IndentGuideLayout, Indicator, KeyBinding, Label,
-    LabelSize, ListItem, ListItemSpacing, ScrollAxes, ScrollableHandle, Scrollbars,
+    LabelSize, ListItem, ListItemSpacing, PopoverMenu, ScrollAxes, ScrollableHandle, Scrollbars,
     StickyCandidate, Tooltip, WithScrollbar, prelude::*, v_flex,
 };
 use util::{
@@ -394,8 +395,12 @@ actions!(
         SelectNextDirectory,
         /// Selects the previous directory.
         SelectPrevDirectory,
+        /// Opens a picker to compare the selected file with another file.
+        CompareWith,
         /// Opens a diff view to compare two marked files.
         CompareMarkedFiles,
+        /// Opens a view listing the files that differ between two marked folders.
+        CompareMarkedDirectories,
         /// Undoes the last file operation.
         Undo,
     ]
@@ -664,6 +669,11 @@ impl ProjectPanel {
                         this.update_visible_entries(None, false, false, window, cx);
                         cx.notify();
                     }
+                    GitStoreEvent::RepositoryUpdated(
+                        _,
+                        RepositoryEvent::SparseCheckoutChanged,
+                        _,
+                    ) => cx.notify(),
                     _ => {}
                 },
             )
@@ -1108,9 +1118,14 @@ impl ProjectPanel {
             let should_hide_rename = is_root
                 && (cfg!(target_os = "windows")
                     || (settings.hide_root && visible_worktrees_count == 1));
-            let should_show_compare = !is_dir && self.file_abs_paths_to_diff(cx).is_some();
-
-            let has_git_repo = !is_dir && {
+            let should_show_compare = !is_dir
+                && self.marked_entries.len() == 2
+                && self.file_abs_paths_to_diff(cx).is_some();
+            let should_show_compare_directories =
+                is_dir && self.marked_abs_paths_to_diff(true, cx).is_some();
+
+            let is_ignored = entry.is_ignored;
+            let in_git_repo = {
                 let project_path = project::ProjectPath {
                     worktree_id,
                     path: entry.path.clone(),
@@ -1121,6 +1136,20 @@ impl ProjectPanel {
                     .repository_and_path_for_project_path(&project_path, cx)
                     .is_some()
             };
+            let has_git_repo = !is_dir && in_git_repo;
+            let has_git_directory = is_dir && in_git_repo;
+
+            let gitignore_choices =
+                self.selected_untracked_repo_path(cx)
+                    .map(|(_, repo_path, is_dir)| {
+                        let extension = (!is_dir)
+                            .then(|| repo_path.extension())
+                            .flatten()
+                            .map(|extension| SharedString::from(extension.to_string()));
+                        let has_directory =
+                            !is_dir && repo_path.parent().is_some_and(|parent| !parent.is_empty());
+                        (extension, has_directory)
+                    });
 
             let has_pasteable_content = self.has_pasteable_content(cx);
             let entity = cx.entity();
@@ -1154,9 +1183,21 @@ impl ProjectPanel {
                             .when(is_foldable, |menu| {
                                 menu.action("Fold Directory", Box::new(FoldDirectory))
                             })
-                            .when(should_show_compare, |menu| {
+                            .when(!is_dir, |menu| {
                                 menu.separator()
-                                    .action("Compare Marked Files", Box::new(CompareMarkedFiles))
+                                    .action("Compare with…", Box::new(CompareWith))
+                                    .when(should_show_compare, |menu| {
+                                        menu.action(
+                                            "Compare Selected Files",
+                                            Box::new(CompareMarkedFiles),
+                                        )
+                                    })
+                            })
+                            .when(should_show_compare_directories, |menu| {
+                                menu.separator().action(
+                                    "Compare Marked Folders",
+                                    Box::new(CompareMarkedDirectories),
+                                )
                             })
                             .separator()
                             .action("Cut", Box::new(Cut))
@@ -1187,9 +1228,35 @@ impl ProjectPanel {
                                     Box::new(git::RestoreFile { skip_prompt: false }),
                                 )
                             })
+                            .when_some(gitignore_choices, |menu, (extension, has_directory)| {
+                                let focus_handle = self.focus_handle.clone();
+                                menu.separator()
+                                    .submenu("Add to .gitignore", move |menu, _, _| {
+                                        git_ui::gitignore::gitignore_menu_entries(
+                                            menu,
+                                            focus_handle.clone(),
+                                            extension.clone(),
+                                            has_directory,
+                                        )
+                                    })
+                            })
+                            .when(is_ignored, |menu| {
+                                menu.separator()
+                                    .action("Explain Why Ignored", Box::new(git::ExplainIgnore))
+                            })
                             .when(has_git_repo, |menu| {
                                 menu.separator()
                                     .action("View File History", Box::new(git::FileHistory))
+                                    .action(
+                                        "Restore from Revision…",
+                                        Box::new(git::RestoreFromRevision),
+                                    )
+                            })
+                            .when(has_git_directory, |menu| {
+                                menu.separator().action(
+                                    "Compare Revisions…",
+                                    Box::new(git::CompareDirectoryRevisions),
+                                )
                             })
                             .when(!should_hide_rename, |menu| {
                                 menu.separator().action("Rename", Box::new(Rename))
@@ -2207,6 +2274,77 @@ impl ProjectPanel {
         self.remove(false, action.skip_prompt, window, cx);
     }
 
+    /// The repository and path of the selected entry when git tracks neither it nor anything in
+    /// it, and whether it's a directory.
+    fn selected_untracked_repo_path(
+        &self,
+        cx: &App,
+    ) -> Option<(Entity<Repository>, RepoPath, bool)> {
+        let selection = self.selection?;
+        let (_worktree, entry) = self.selected_sub_entry(cx)?;
+        let is_dir = entry.is_dir();
+        let git_summary = self
+            .state
+            .visible_entries
+            .iter()
+            .flat_map(|visible| visible.entries.iter())
+            .find(|git_entry| git_entry.id == selection.entry_id)?
+            .git_summary;
+        if git_summary.untracked == 0 || git_summary.untracked != git_summary.count {
+            return None;
+        }
+
+        let project = self.project.read(cx);
+        let project_path = project.path_for_entry(selection.entry_id, cx)?;
+        let (repository, repo_path) = project
+            .git_store()
+            .read(cx)
+            .repository_and_path_for_project_path(&project_path, cx)?;
+        Some((repository, repo_path, is_dir))
+    }
+
+    fn add_to_gitignore(
+        &mut self,
+        action: &git::AddToGitignore,
+        _window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let Some((repository, repo_path, is_dir)) = self.selected_untracked_repo_path(cx) else {
+            return;
+        };
+        git_ui::gitignore::add_to_gitignore(
+            self.project.clone(),
+            repository,
+            repo_path,
+            is_dir,
+            action.pattern,
+            cx,
+        )
+        .detach_and_log_err(cx);
+    }
+
+    fn explain_ignore(
+        &mut self,
+        _: &git::ExplainIgnore,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        maybe!({
+            let selection = self.selection?;
+            let project = self.project.read(cx);
+            let project_path = project.path_for_entry(selection.entry_id, cx)?;
+            let (repository, repo_path) = project
+                .git_store()
+                .read(cx)
+                .repository_and_path_for_project_path(&project_path, cx)?;
+            self.workspace
+                .update(cx, |workspace, cx| {
+                    git_ui::gitignore::explain_ignore(workspace, repository, repo_path, window, cx);
+                })
+                .ok()
+        });
+    }
+
     fn restore_file(
         &mut self,
         action: &git::RestoreFile,
@@ -3450,6 +3588,14 @@ impl ProjectPanel {
     }
 
     fn file_abs_paths_to_diff(&self, cx: &Context<Self>) -> Option<(PathBuf, PathBuf)> {
+        self.marked_abs_paths_to_diff(false, cx)
+    }
+
+    fn marked_abs_paths_to_diff(
+        &self,
+        directories: bool,
+        cx: &Context<Self>,
+    ) -> Option<(PathBuf, PathBuf)> {
         let mut selections_abs_path = self
             .marked_entries
             .iter()
@@ -3457,7 +3603,7 @@ impl ProjectPanel {
                 let project = self.project.read(cx);
                 let worktree = project.worktree_for_id(entry.worktree_id, cx)?;
                 let entry = worktree.read(cx).entry_for_id(entry.entry_id)?;
-                if !entry.is_file() {
+                if entry.is_dir() != directories {
                     return None;
                 }
                 Some(worktree.read(cx).absolutize(&entry.path))
@@ -3469,6 +3615,85 @@ impl ProjectPanel {
         Some((previous_to_last, last_path))
     }
 
+    fn compare_with(&mut self, _: &CompareWith, window: &mut Window, cx: &mut Context<Self>) {
+        let Some((worktree, entry)) = self.selected_sub_entry(cx) else {
+            return;
+        };
+        if entry.is_dir() {
+            return;
+        }
+        let project_path = ProjectPath {
+            worktree_id: worktree.read(cx).id(),
+            path: entry.path.clone(),
+        };
+        self.workspace
+            .update(cx, |workspace, cx| {
+                git_ui::file_comparison_picker::open_against_path(
+                    workspace,
+                    project_path,
+                    window,
+                    cx,
+                )
+            })
+            .ok();
+    }
+
+    fn restore_from_revision(
+        &mut self,
+        _: &git::RestoreFromRevision,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let Some((worktree, entry)) = self.selected_sub_entry(cx) else {
+            return;
+        };
+        if entry.is_dir() {
+            return;
+        }
+        let project_path = ProjectPath {
+            worktree_id: worktree.read(cx).id(),
+            path: entry.path.clone(),
+        };
+        self.workspace
+            .update(cx, |workspace, cx| {
+                git_ui::revision_comparison_picker::restore_from_revision(
+                    workspace,
+                    project_path,
+                    window,
+                    cx,
+                )
+            })
+            .ok();
+    }
+
+    fn compare_directory_revisions(
+        &mut self,
+        _: &git::CompareDirectoryRevisions,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let Some((worktree, entry)) = self.selected_sub_entry(cx) else {
+            return;
+        };
+        if !entry.is_dir() {
+            return;
+        }
+        let project_path = ProjectPath {
+            worktree_id: worktree.read(cx).id(),
+            path: entry.path.clone(),
+        };
+        self.workspace
+            .update(cx, |workspace, cx| {
+                git_ui::branch_comparison_picker::compare_directory_revisions(
+                    workspace,
+                    project_path,
+                    window,
+                    cx,
+                )
+            })
+            .ok();
+    }
+
     fn compare_marked_files(
         &mut self,
         _: &CompareMarkedFiles,
@@ -3486,6 +3711,28 @@ impl ProjectPanel {
         }
     }
 
+    fn compare_marked_directories(
+        &mut self,
+        _: &CompareMarkedDirectories,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        if let Some((old_path, new_path)) = self.marked_abs_paths_to_diff(true, cx) {
+            self.workspace
+                .update(cx, |workspace, cx| {
+                    DirectoryDiffView::open(
+                        old_path,
+                        new_path,
+                        workspace.weak_handle(),
+                        window,
+                        cx,
+                    )
+                    .detach_and_log_err(cx);
+                })
+                .ok();
+        }
+    }
+
     fn open_system(&mut self, _: &OpenWithSystem, _: &mut Window, cx: &mut Context<Self>) {
         if let Some((worktree, entry)) = self.selected_entry(cx) {
             let abs_path = worktree.absolutize(&entry.path);
@@ -5219,6 +5466,90 @@ impl ProjectPanel {
         false
     }
 
+    /// The subdirectories of a directory that its repository's sparse checkout leaves out.
+    fn sparse_excluded_directories(
+        &self,
+        project_path: &ProjectPath,
+        cx: &App,
+    ) -> Option<(Entity<Repository>, Vec<RepoPath>)> {
+        let (repository, repo_path) = self
+            .project
+            .read(cx)
+            .git_store()
+            .read(cx)
+            .repository_and_path_for_project_path(project_path, cx)?;
+        let directories = repository
+            .read(cx)
+            .sparse_checkout()?
+            .excluded_directories
+            .iter()
+            .filter(|directory| directory.parent() == Some(&*repo_path))
+            .cloned()
+            .collect::<Vec<_>>();
+        (!directories.is_empty()).then_some((repository, directories))
+    }
+
+    fn render_sparse_excluded_directories(
+        entry_id: ProjectEntryId,
+        repository: Entity<Repository>,
+        directories: Vec<RepoPath>,
+    ) -> impl IntoElement {
+        let label = format!("{} not checked out", directories.len());
+        PopoverMenu::new(SharedString::from(format!(
+            "sparse-excluded-{}",
+            entry_id.to_usize()
+        )))
+        .trigger_with_tooltip(
+            Button::new("sparse-excluded-trigger", label)
+                .label_size(LabelSize::XSmall)
+                .color(Color::Disabled),
+            Tooltip::text("Directories left out of the sparse checkout"),
+        )
+        .menu(move |window, cx| {
+            let repository = repository.clone();
+            let directories = directories.clone();
+            Some(ContextMenu::build(window, cx, move |mut menu, _, _| {
+                for directory in &directories {
+                    let name = directory.file_name().unwrap_or_default();
+                    let repository = repository.clone();
+                    let directory = directory.clone();
+                    menu = menu.entry(format!("Check Out {name}"), None, move |_, cx| {
+                        Self::check_out_sparse_directories(
+                            &repository,
+                            std::slice::from_ref(&directory),
+                            cx,
+                        );
+                    });
+                }
+                if directories.len() > 1 {
+                    let repository = repository.clone();
+                    let directories = directories.clone();
+                    menu = menu.separator().entry("Check Out All", None, move |_, cx| {
+                        Self::check_out_sparse_directories(&repository, &directories, cx);
+                    });
+                }
+                menu
+            }))
+        })
+        .anchor(gpui::Corner::TopRight)
+    }
+
+    fn check_out_sparse_directories(
+        repository: &Entity<Repository>,
+        directories: &[RepoPath],
+        cx: &mut App,
+    ) {
+        let check_out = repository.update(cx, |repository, _| {
+            repository.check_out_sparse_directories(directories)
+        });
+        cx.spawn(async move |_| {
+            if let Ok(result) = check_out.await {
+                result.log_err();
+            }
+        })
+        .detach();
+    }
+
     fn render_entry(
         &self,
         entry_id: ProjectEntryId,
@@ -5356,6 +5687,18 @@ impl ProjectPanel {
             .git_status_indicator
             .then(|| git_status_indicator(details.git_status))
             .flatten();
+        let sparse_excluded_directories = kind
+            .is_dir()
+            .then(|| {
+                self.sparse_excluded_directories(
+                    &ProjectPath {
+                        worktree_id,
+                        path: details.path.clone(),
+                    },
+                    cx,
+                )
+            })
+            .flatten();
 
         let id: ElementId = if is_sticky {
             SharedString::from(format!("project_panel_sticky_item_{}", entry_id.to_usize())).into()
@@ -5702,7 +6045,8 @@ impl ProjectPanel {
                     .when(
                         canonical_path.is_some()
                             || diagnostic_count.is_some()
-                            || git_indicator.is_some(),
+                            || git_indicator.is_some()
+                            || sparse_excluded_directories.is_some(),
                         |this| {
                             let symlink_element = canonical_path.map(|path| {
                                 div()
@@ -5759,6 +6103,16 @@ impl ProjectPanel {
 
                                         this.child(git_indicator)
                                     })
+                                    .when_some(
+                                        sparse_excluded_directories,
+                                        |this, (repository, directories)| {
+                                            this.child(Self::render_sparse_excluded_directories(
+                                                entry_id,
+                                                repository,
+                                                directories,
+                                            ))
+                                        },
+                                    )
                                     .when_some(symlink_element, |this, el| this.child(el))
                                     .into_any_element(),
                             )
@@ -6628,7 +6982,11 @@ impl Render for ProjectPanel {
                 .on_action(cx.listener(Self::unfold_directory))
                 .on_action(cx.listener(Self::fold_directory))
                 .on_action(cx.listener(Self::remove_from_project))
+                .on_action(cx.listener(Self::compare_with))
                 .on_action(cx.listener(Self::compare_marked_files))
+                .on_action(cx.listener(Self::compare_marked_directories))
+                .on_action(cx.listener(Self::restore_from_revision))
+                .on_action(cx.listener(Self::compare_directory_revisions))
                 .when(cx.has_flag::<ProjectPanelUndoRedoFeatureFlag>(), |el| {
                     el.on_action(cx.listener(Self::undo))
                 })
@@ -6642,6 +7000,8 @@ impl Render for ProjectPanel {
                         .on_action(cx.listener(Self::paste))
                         .on_action(cx.listener(Self::duplicate))
                         .on_action(cx.listener(Self::restore_file))
+                        .on_action(cx.listener(Self::add_to_gitignore))
+                        .on_action(cx.listener(Self::explain_ignore))
                         .when(!project.is_remote(), |el| {
                             el.on_action(cx.listener(Self::trash))
                         })
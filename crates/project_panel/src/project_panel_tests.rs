@@ -9282,6 +9282,62 @@ async fn test_compare_selected_files(cx: &mut gpui::TestAppContext) {
     });
 }
 
+#[gpui::test]
+async fn test_compare_marked_directories(cx: &mut gpui::TestAppContext) {
+    init_test_with_editor(cx);
+
+    let fs = FakeFs::new(cx.executor());
+    fs.insert_tree(
+        "/root",
+        json!({
+            "dir1": {
+                "same.txt": "same",
+                "changed.txt": "old",
+            },
+            "dir2": {
+                "same.txt": "same",
+                "changed.txt": "new",
+                "added.txt": "added",
+            },
+        }),
+    )
+    .await;
+
+    let project = Project::test(fs.clone(), ["/root".as_ref()], cx).await;
+    let window = cx.add_window(|window, cx| MultiWorkspace::test_new(project.clone(), window, cx));
+    let workspace = window
+        .read_with(cx, |mw, _| mw.workspace().clone())
+        .unwrap();
+    let cx = &mut VisualTestContext::from_window(window.into(), cx);
+    let panel = workspace.update_in(cx, ProjectPanel::new);
+    cx.run_until_parked();
+
+    select_path_with_mark(&panel, "root/dir1", cx);
+    select_path_with_mark(&panel, "root/dir2", cx);
+
+    panel.update_in(cx, |panel, window, cx| {
+        panel.compare_marked_directories(&CompareMarkedDirectories, window, cx);
+    });
+    cx.executor().run_until_parked();
+
+    workspace.update_in(cx, |workspace, _, cx| {
+        let diff_view = workspace
+            .active_item_as::<DirectoryDiffView>(cx)
+            .expect("Open item should be a DirectoryDiffView");
+        assert_eq!(diff_view.tab_content_text(0, cx), "dir1 ↔ dir2");
+        let paths = diff_view
+            .read(cx)
+            .entries()
+            .iter()
+            .map(|entry| entry.path.clone())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            paths,
+            vec![PathBuf::from("added.txt"), PathBuf::from("changed.txt")]
+        );
+    });
+}
+
 #[gpui::test]
 async fn test_compare_files_context_menu(cx: &mut gpui::TestAppContext) {
     init_test_with_editor(cx);
@@ -337,6 +337,39 @@ messages!(
     (GetTreeDiffResponse, Background),
     (GetBlobContent, Background),
     (GetBlobContentResponse, Background),
+    (GitLoadTextAtRevision, Background),
+    (GitLoadTextAtRevisionResponse, Background),
+    (GitLoadConflictStages, Background),
+    (GitLoadConflictStagesResponse, Background),
+    (GitLoadCommitTemplate, Background),
+    (GitLoadCommitTemplateResponse, Background),
+    (GitRecentCommitAuthors, Background),
+    (GitRecentCommitAuthorsResponse, Background),
+    (GitVerifyCommitSignatures, Background),
+    (GitVerifyCommitSignaturesResponse, Background),
+    (GitResolveInProgressOperation, Background),
+    (GitResolveInProgressOperationResponse, Background),
+    (GitCherryPick, Background),
+    (GitCherryPickResponse, Background),
+    (GitRevertCommit, Background),
+    (GitRevertCommitResponse, Background),
+    (GitBisectStart, Background),
+    (GitBisectMark, Background),
+    (GitBisectResponse, Background),
+    (GitBisectReset, Background),
+    (GitReflog, Background),
+    (GitReflogResponse, Background),
+    (GitCommitLog, Background),
+    (GitCommitLogResponse, Background),
+    (GitCommitsBetween, Background),
+    (GitCommitsBetweenResponse, Background),
+    (GitCheckoutRevision, Background),
+    (GitStashBranch, Background),
+    (GitSetBranchUpstream, Background),
+    (GitGetTags, Background),
+    (GitTagsResponse, Background),
+    (GitCreateTag, Background),
+    (GitDeleteTag, Background),
     (GitClone, Background),
     (GitCloneResponse, Background),
     (ToggleLspLogs, Background),
@@ -549,6 +582,29 @@ request_messages!(
     (PullWorkspaceDiagnostics, Ack),
     (GetDefaultBranch, GetDefaultBranchResponse),
     (GetBlobContent, GetBlobContentResponse),
+    (GitLoadTextAtRevision, GitLoadTextAtRevisionResponse),
+    (GitLoadConflictStages, GitLoadConflictStagesResponse),
+    (GitLoadCommitTemplate, GitLoadCommitTemplateResponse),
+    (GitRecentCommitAuthors, GitRecentCommitAuthorsResponse),
+    (GitVerifyCommitSignatures, GitVerifyCommitSignaturesResponse),
+    (
+        GitResolveInProgressOperation,
+        GitResolveInProgressOperationResponse
+    ),
+    (GitCherryPick, GitCherryPickResponse),
+    (GitRevertCommit, GitRevertCommitResponse),
+    (GitBisectStart, GitBisectResponse),
+    (GitBisectMark, GitBisectResponse),
+    (GitBisectReset, Ack),
+    (GitReflog, GitReflogResponse),
+    (GitCommitLog, GitCommitLogResponse),
+    (GitCommitsBetween, GitCommitsBetweenResponse),
+    (GitCheckoutRevision, Ack),
+    (GitStashBranch, Ack),
+    (GitSetBranchUpstream, Ack),
+    (GitGetTags, GitTagsResponse),
+    (GitCreateTag, Ack),
+    (GitDeleteTag, Ack),
     (GetTreeDiff, GetTreeDiffResponse),
     (GitClone, GitCloneResponse),
     (ToggleLspLogs, Ack),
@@ -742,6 +798,26 @@ entity_messages!(
     GetDefaultBranch,
     GetTreeDiff,
     GetBlobContent,
+    GitLoadTextAtRevision,
+    GitLoadConflictStages,
+    GitLoadCommitTemplate,
+    GitRecentCommitAuthors,
+    GitVerifyCommitSignatures,
+    GitResolveInProgressOperation,
+    GitCherryPick,
+    GitRevertCommit,
+    GitBisectStart,
+    GitBisectMark,
+    GitBisectReset,
+    GitReflog,
+    GitCommitLog,
+    GitCommitsBetween,
+    GitCheckoutRevision,
+    GitStashBranch,
+    GitSetBranchUpstream,
+    GitGetTags,
+    GitCreateTag,
+    GitDeleteTag,
     GitClone,
     GetAgentServerCommand,
     GetContextServerCommand,
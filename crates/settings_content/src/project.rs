@@ -497,6 +497,8 @@ pub struct GitSettings {
     ///
     /// Default: file_name_first
     pub path_style: Option<GitPathStyle>,
+    /// Settings for diffs shown in comparison views.
+    pub diff: Option<GitDiffSettingsContent>,
     /// Directory where git worktrees are created, relative to the repository
     /// working directory.
     ///
@@ -518,6 +520,36 @@ pub struct GitSettings {
     ///
     /// Default: ../worktrees
     pub worktree_directory: Option<String>,
+    /// Path to a commit message template that pre-populates the commit
+    /// message editor, relative to the repository working directory.
+    /// Lines starting with `#` are stripped when committing.
+    ///
+    /// When unset, the `commit.template` git config is used.
+    ///
+    /// Default: null
+    pub commit_template: Option<String>,
+    /// Whether to offer committing without running the pre-commit hook
+    /// (`git commit --no-verify`) in the commit menu.
+    ///
+    /// Default: false
+    pub allow_no_verify: Option<bool>,
+    /// How pulling integrates the upstream changes into the current branch.
+    /// Set it in a project's settings to pick a strategy for its repositories.
+    ///
+    /// When unset, the `pull.rebase` and `pull.ff` git config is used.
+    ///
+    /// Default: null
+    pub pull_strategy: Option<PullStrategy>,
+    /// The branch that "Diff Against Merge Base" compares the current branch
+    /// with. Set it in a project's settings to pick a branch for its repositories.
+    ///
+    /// When unset, the default branch of the repository's remote is used, or
+    /// `main` without one.
+    ///
+    /// Default: null
+    pub default_base_branch: Option<String>,
+    /// Settings for fetching from remotes in the background.
+    pub auto_fetch: Option<AutoFetchSettingsContent>,
 }
 
 #[with_fallible_options]
@@ -591,13 +623,104 @@ pub struct InlineBlameSettings {
 }
 
 #[with_fallible_options]
-#[derive(Clone, Copy, Debug, PartialEq, Default, Serialize, Deserialize, JsonSchema, MergeFrom)]
+#[derive(Clone, Debug, PartialEq, Default, Serialize, Deserialize, JsonSchema, MergeFrom)]
 #[serde(rename_all = "snake_case")]
 pub struct BlameSettings {
     /// Whether to show the avatar of the author of the commit.
     ///
     /// Default: true
     pub show_avatar: Option<bool>,
+    /// Whether to color the blame column by the age of each line's commit,
+    /// from the oldest to the newest commit in the file.
+    ///
+    /// Default: false
+    pub heat_map: Option<bool>,
+    /// Revisions for blame to look past, such as commits that only reformatted code,
+    /// in addition to those in the repository's `blame.ignoreRevsFile`.
+    ///
+    /// Default: []
+    pub ignore_revs: Option<Vec<String>>,
+}
+
+#[with_fallible_options]
+#[derive(Clone, PartialEq, Debug, Default, Serialize, Deserialize, JsonSchema, MergeFrom)]
+#[serde(rename_all = "snake_case")]
+pub struct AutoFetchSettingsContent {
+    /// Whether to fetch from remotes in the background, keeping the ahead and
+    /// behind counts of branches current.
+    ///
+    /// Default: false
+    pub enabled: Option<bool>,
+    /// How many seconds to wait between background fetches.
+    ///
+    /// Default: 300
+    pub interval_seconds: Option<u64>,
+    /// The remotes to fetch from. When unset, every remote is fetched.
+    ///
+    /// Default: null
+    pub remotes: Option<Vec<String>>,
+}
+
+#[with_fallible_options]
+#[derive(Clone, Copy, PartialEq, Debug, Default, Serialize, Deserialize, JsonSchema, MergeFrom)]
+#[serde(rename_all = "snake_case")]
+pub struct GitDiffSettingsContent {
+    /// Which whitespace differences to ignore when comparing files.
+    ///
+    /// Default: none
+    pub ignore_whitespace: Option<IgnoreWhitespaceSetting>,
+    /// Where the file comparison picker opens diffs. The secondary confirm
+    /// opens them in the other placement.
+    ///
+    /// Default: active_pane
+    pub placement: Option<DiffPlacement>,
+}
+
+#[derive(
+    Clone,
+    Copy,
+    PartialEq,
+    Debug,
+    Default,
+    Serialize,
+    Deserialize,
+    JsonSchema,
+    MergeFrom,
+    strum::VariantArray,
+    strum::VariantNames,
+)]
+#[serde(rename_all = "snake_case")]
+pub enum IgnoreWhitespaceSetting {
+    /// Show every whitespace difference.
+    #[default]
+    None,
+    /// Ignore whitespace at the end of lines.
+    Trailing,
+    /// Ignore all whitespace differences, including indentation changes.
+    All,
+}
+
+#[derive(
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    Debug,
+    Default,
+    Serialize,
+    Deserialize,
+    JsonSchema,
+    MergeFrom,
+    strum::VariantArray,
+    strum::VariantNames,
+)]
+#[serde(rename_all = "snake_case")]
+pub enum DiffPlacement {
+    /// Open the diff in the active pane.
+    #[default]
+    ActivePane,
+    /// Open the diff in a new pane split to the right of the active one.
+    Split,
 }
 
 #[with_fallible_options]
@@ -610,6 +733,28 @@ pub struct BranchPickerSettingsContent {
     pub show_author_name: Option<bool>,
 }
 
+#[derive(
+    Clone,
+    Copy,
+    PartialEq,
+    Debug,
+    Serialize,
+    Deserialize,
+    JsonSchema,
+    MergeFrom,
+    strum::VariantArray,
+    strum::VariantNames,
+)]
+#[serde(rename_all = "snake_case")]
+pub enum PullStrategy {
+    /// Merge the upstream changes, creating a merge commit when the branches diverged.
+    Merge,
+    /// Rebase local commits onto the upstream changes.
+    Rebase,
+    /// Only update the branch when it can be fast-forwarded.
+    FastForwardOnly,
+}
+
 #[derive(
     Clone,
     Copy,
@@ -773,7 +918,8 @@ pub enum DiagnosticSeverityContent {
 pub struct GitHostingProviderConfig {
     /// The type of the provider.
     ///
-    /// Must be one of `github`, `gitlab`, `bitbucket`, `gitea`, `forgejo`, or `source_hut`.
+    /// Must be one of `github`, `gitlab`, `bitbucket`, `gitea`, `forgejo`, `source_hut`, or
+    /// `custom`.
     pub provider: GitHostingProviderKind,
 
     /// The base URL for the provider (e.g., "https://code.corp.big.com").
@@ -781,6 +927,43 @@ pub struct GitHostingProviderConfig {
 
     /// The display name for the provider (e.g., "BigCorp GitHub").
     pub name: String,
+
+    /// The URL templates of a `custom` provider, ignored by the other providers.
+    pub url_templates: Option<GitHostingUrlTemplates>,
+}
+
+/// The URLs of a `custom` Git hosting provider.
+///
+/// URL templates may contain `{base_url}`, `{owner}` and `{repo}`, as well as `{sha}`, `{path}`
+/// and `{branch}` where they apply. Line templates may contain `{line}`, or `{start_line}` and
+/// `{end_line}`.
+#[with_fallible_options]
+#[derive(Debug, Default, PartialEq, Clone, Serialize, Deserialize, JsonSchema, MergeFrom)]
+pub struct GitHostingUrlTemplates {
+    /// The URL of a commit.
+    ///
+    /// Default: "{base_url}/{owner}/{repo}/commit/{sha}"
+    pub commit: Option<String>,
+
+    /// The URL of a file at a commit.
+    ///
+    /// Default: "{base_url}/{owner}/{repo}/blob/{sha}/{path}"
+    pub file: Option<String>,
+
+    /// The URL of a branch.
+    ///
+    /// Default: "{base_url}/{owner}/{repo}/tree/{branch}"
+    pub branch: Option<String>,
+
+    /// The URL fragment of a single selected line.
+    ///
+    /// Default: "L{line}"
+    pub line: Option<String>,
+
+    /// The URL fragment of several selected lines.
+    ///
+    /// Default: "L{start_line}-L{end_line}"
+    pub line_range: Option<String>,
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema, MergeFrom)]
@@ -792,4 +975,5 @@ pub enum GitHostingProviderKind {
     Gitea,
     Forgejo,
     SourceHut,
+    Custom,
 }
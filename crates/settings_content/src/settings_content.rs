@@ -586,6 +586,11 @@ pub struct GitPanelSettingsContent {
     ///
     /// Default: false
     pub starts_open: Option<bool>,
+
+    /// Length of the commit message's first line above which the commit editor shows a warning.
+    ///
+    /// Default: 72
+    pub max_subject_length: Option<usize>,
 }
 
 #[derive(
@@ -97,6 +97,12 @@ pub struct TitleBarSettingsContent {
     ///
     /// Default: true
     pub show_branch_name: Option<bool>,
+    /// Whether to show the checks and review state of the branch's pull request beside the
+    /// branch name. Requires a `GITHUB_TOKEN` for GitHub, or a `GH_ENTERPRISE_TOKEN` and `GH_HOST`
+    /// for GitHub Enterprise.
+    ///
+    /// Default: true
+    pub show_pull_request_status: Option<bool>,
     /// Whether to show the project host and name in the titlebar.
     ///
     /// Default: true
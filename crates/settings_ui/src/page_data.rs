@@ -3497,7 +3497,7 @@ fn window_and_layout_page() -> SettingsPage {
         ]
     }
 
-    fn title_bar_section() -> [SettingsPageItem; 10] {
+    fn title_bar_section() -> [SettingsPageItem; 11] {
         [
             SettingsPageItem::SectionHeader("Title Bar"),
             SettingsPageItem::SettingItem(SettingItem {
@@ -3544,6 +3544,28 @@ fn window_and_layout_page() -> SettingsPage {
                 metadata: None,
                 files: USER,
             }),
+            SettingsPageItem::SettingItem(SettingItem {
+                title: "Show Pull Request Status",
+                description: "Show the checks and review state of the branch's pull request beside the branch name.",
+                field: Box::new(SettingField {
+                    json_path: Some("title_bar.show_pull_request_status"),
+                    pick: |settings_content| {
+                        settings_content
+                            .title_bar
+                            .as_ref()?
+                            .show_pull_request_status
+                            .as_ref()
+                    },
+                    write: |settings_content, value| {
+                        settings_content
+                            .title_bar
+                            .get_or_insert_default()
+                            .show_pull_request_status = value;
+                    },
+                }),
+                metadata: None,
+                files: USER,
+            }),
             SettingsPageItem::SettingItem(SettingItem {
                 title: "Show Project Items",
                 description: "Show the project host and name in the titlebar.",
@@ -5224,7 +5246,7 @@ fn panels_page() -> SettingsPage {
         ]
     }
 
-    fn git_panel_section() -> [SettingsPageItem; 14] {
+    fn git_panel_section() -> [SettingsPageItem; 15] {
         [
             SettingsPageItem::SectionHeader("Git Panel"),
             SettingsPageItem::SettingItem(SettingItem {
@@ -5442,6 +5464,28 @@ fn panels_page() -> SettingsPage {
                 metadata: None,
                 files: USER,
             }),
+            SettingsPageItem::SettingItem(SettingItem {
+                title: "Max Subject Length",
+                description: "Length of the commit message's first line above which the commit editor shows a warning.",
+                field: Box::new(SettingField {
+                    json_path: Some("git_panel.max_subject_length"),
+                    pick: |settings_content| {
+                        settings_content
+                            .git_panel
+                            .as_ref()?
+                            .max_subject_length
+                            .as_ref()
+                    },
+                    write: |settings_content, value| {
+                        settings_content
+                            .git_panel
+                            .get_or_insert_default()
+                            .max_subject_length = value;
+                    },
+                }),
+                metadata: None,
+                files: USER,
+            }),
             SettingsPageItem::SettingItem(SettingItem {
                 title: "Scroll Bar",
                 description: "How and when the scrollbar should be displayed.",
@@ -6932,7 +6976,7 @@ fn version_control_page() -> SettingsPage {
         ]
     }
 
-    fn git_blame_view_section() -> [SettingsPageItem; 2] {
+    fn git_blame_view_section() -> [SettingsPageItem; 3] {
         [
             SettingsPageItem::SectionHeader("Git Blame View"),
             SettingsPageItem::SettingItem(SettingItem {
@@ -6961,6 +7005,32 @@ fn version_control_page() -> SettingsPage {
                 metadata: None,
                 files: USER,
             }),
+            SettingsPageItem::SettingItem(SettingItem {
+                title: "Heat Map",
+                description: "Color the blame column by the age of each line's commit.",
+                field: Box::new(SettingField {
+                    json_path: Some("git.blame.heat_map"),
+                    pick: |settings_content| {
+                        settings_content
+                            .git
+                            .as_ref()?
+                            .blame
+                            .as_ref()?
+                            .heat_map
+                            .as_ref()
+                    },
+                    write: |settings_content, value| {
+                        settings_content
+                            .git
+                            .get_or_insert_default()
+                            .blame
+                            .get_or_insert_default()
+                            .heat_map = value;
+                    },
+                }),
+                metadata: None,
+                files: USER,
+            }),
         ]
     }
 
@@ -7028,6 +7098,64 @@ fn version_control_page() -> SettingsPage {
         ]
     }
 
+    fn git_diff_section() -> [SettingsPageItem; 3] {
+        [
+            SettingsPageItem::SectionHeader("Diffs"),
+            SettingsPageItem::SettingItem(SettingItem {
+                title: "Ignore Whitespace",
+                description: "Which whitespace differences to ignore when comparing files.",
+                field: Box::new(SettingField {
+                    json_path: Some("git.diff.ignore_whitespace"),
+                    pick: |settings_content| {
+                        settings_content
+                            .git
+                            .as_ref()?
+                            .diff
+                            .as_ref()?
+                            .ignore_whitespace
+                            .as_ref()
+                    },
+                    write: |settings_content, value| {
+                        settings_content
+                            .git
+                            .get_or_insert_default()
+                            .diff
+                            .get_or_insert_default()
+                            .ignore_whitespace = value;
+                    },
+                }),
+                metadata: None,
+                files: USER,
+            }),
+            SettingsPageItem::SettingItem(SettingItem {
+                title: "Placement",
+                description: "Where the file comparison picker opens diffs.",
+                field: Box::new(SettingField {
+                    json_path: Some("git.diff.placement"),
+                    pick: |settings_content| {
+                        settings_content
+                            .git
+                            .as_ref()?
+                            .diff
+                            .as_ref()?
+                            .placement
+                            .as_ref()
+                    },
+                    write: |settings_content, value| {
+                        settings_content
+                            .git
+                            .get_or_insert_default()
+                            .diff
+                            .get_or_insert_default()
+                            .placement = value;
+                    },
+                }),
+                metadata: None,
+                files: USER,
+            }),
+        ]
+    }
+
     SettingsPage {
         title: "Version Control",
         items: concat_sections![
@@ -7037,6 +7165,7 @@ fn version_control_page() -> SettingsPage {
             git_blame_view_section(),
             branch_picker_section(),
             git_hunks_section(),
+            git_diff_section(),
         ],
     }
 }
@@ -0,0 +1,236 @@
+use std::time::Duration;
+
+use git::{
+    CheckStatus, GitHostingProviderRegistry, PullRequestStatus, ReviewState,
+    repository::UpstreamTracking,
+};
+use gpui::{App, Context, Entity, IntoElement, Render, Subscription, Task, Window};
+use project::{
+    Project,
+    git_store::{GitStoreEvent, Repository},
+};
+use settings::{Settings as _, SettingsStore};
+use ui::{ButtonLike, ContextMenu, ContextMenuEntry, PopoverMenu, Tooltip, prelude::*};
+use util::ResultExt as _;
+
+use crate::title_bar_settings::TitleBarSettings;
+
+const POLL_INTERVAL: Duration = Duration::from_secs(120);
+
+/// A branch as it is known on its remote.
+#[derive(Clone, Debug, PartialEq)]
+struct TrackedBranch {
+    remote_url: String,
+    /// The repository pull requests from the branch are opened against: the `upstream` remote
+    /// when the branch lives on a fork of it, and the branch's own remote otherwise.
+    base_remote_url: String,
+    branch: String,
+}
+
+impl TrackedBranch {
+    fn for_repository(repository: &Repository) -> Option<Self> {
+        let upstream = repository
+            .branch
+            .as_ref()?
+            .upstream
+            .as_ref()
+            .filter(|upstream| matches!(upstream.tracking, UpstreamTracking::Tracked(_)))?;
+        let remote_origin = repository.remote_origin_url.as_ref();
+        let remote_upstream = repository.remote_upstream_url.as_ref();
+        let remote_url = match upstream.remote_name() {
+            Some("upstream") => remote_upstream,
+            _ => remote_origin,
+        }
+        .or(remote_origin)
+        .or(remote_upstream)?;
+        let base_remote_url = remote_upstream.unwrap_or(remote_url);
+        Some(Self {
+            remote_url: remote_url.clone(),
+            base_remote_url: base_remote_url.clone(),
+            branch: upstream.branch_name()?.to_string(),
+        })
+    }
+}
+
+/// Shows the checks and review state of the pull request of the active branch, polling its
+/// hosting provider while the branch stays the same.
+pub struct PullRequestStatusIndicator {
+    project: Entity<Project>,
+    tracked_branch: Option<TrackedBranch>,
+    status: Option<PullRequestStatus>,
+    _poll_status: Task<()>,
+    _git_store_subscription: Subscription,
+    _settings_subscription: Subscription,
+}
+
+impl PullRequestStatusIndicator {
+    pub fn new(project: Entity<Project>, cx: &mut Context<Self>) -> Self {
+        let git_store = project.read(cx).git_store().clone();
+        let git_store_subscription = cx.subscribe(&git_store, |this, _, event, cx| match event {
+            GitStoreEvent::ActiveRepositoryChanged(_)
+            | GitStoreEvent::RepositoryUpdated(_, _, true) => this.track_active_branch(cx),
+            _ => {}
+        });
+        let settings_subscription =
+            cx.observe_global::<SettingsStore>(|this, cx| this.track_active_branch(cx));
+        let mut this = Self {
+            project,
+            tracked_branch: None,
+            status: None,
+            _poll_status: Task::ready(()),
+            _git_store_subscription: git_store_subscription,
+            _settings_subscription: settings_subscription,
+        };
+        this.track_active_branch(cx);
+        this
+    }
+
+    fn track_active_branch(&mut self, cx: &mut Context<Self>) {
+        // Nothing is polled while the status isn't shown.
+        let tracked_branch = self
+            .project
+            .read(cx)
+            .active_repository(cx)
+            .filter(|_| TitleBarSettings::get_global(cx).show_pull_request_status)
+            .and_then(|repository| TrackedBranch::for_repository(repository.read(cx)));
+        if tracked_branch == self.tracked_branch {
+            return;
+        }
+        self.tracked_branch = tracked_branch.clone();
+        self.status = None;
+        cx.notify();
+
+        let Some(tracked_branch) = tracked_branch else {
+            self._poll_status = Task::ready(());
+            return;
+        };
+        let provider_registry = GitHostingProviderRegistry::global(cx);
+        let Some((provider, head_remote)) =
+            git::parse_git_remote_url(provider_registry.clone(), &tracked_branch.remote_url)
+        else {
+            self._poll_status = Task::ready(());
+            return;
+        };
+        let base_remote =
+            git::parse_git_remote_url(provider_registry, &tracked_branch.base_remote_url)
+                .filter(|(base_provider, _)| base_provider.name() == provider.name())
+                .map_or(head_remote.clone(), |(_, base_remote)| base_remote);
+        let http_client = cx.http_client();
+        self._poll_status = cx.spawn(async move |this, cx| {
+            loop {
+                let status = provider
+                    .pull_request_status(
+                        &base_remote,
+                        &head_remote.owner,
+                        &tracked_branch.branch,
+                        http_client.clone(),
+                    )
+                    .await;
+                let updated = this.update(cx, |this, cx| {
+                    this.status = status.log_err().flatten();
+                    cx.notify();
+                });
+                if updated.is_err() {
+                    break;
+                }
+                cx.background_executor().timer(POLL_INTERVAL).await;
+            }
+        });
+    }
+
+    fn build_menu(
+        status: PullRequestStatus,
+        window: &mut Window,
+        cx: &mut App,
+    ) -> Entity<ContextMenu> {
+        ContextMenu::build(window, cx, move |menu, _, _| {
+            let url = status.url.clone();
+            let mut menu = menu
+                .header(format!("#{} {}", status.number, status.title))
+                .entry("Open Pull Request", None, move |_, cx| {
+                    cx.open_url(url.as_str())
+                });
+            let failing_checks = status.failing_checks().cloned().collect::<Vec<_>>();
+            if !failing_checks.is_empty() {
+                menu = menu.separator().header("Failing Checks");
+                for check in failing_checks {
+                    let mut entry = ContextMenuEntry::new(check.name)
+                        .icon(IconName::XCircle)
+                        .icon_color(Color::Error);
+                    if let Some(url) = check.url {
+                        entry = entry.handler(move |_, cx| cx.open_url(url.as_str()));
+                    }
+                    menu = menu.item(entry);
+                }
+            }
+            menu
+        })
+    }
+}
+
+fn checks_indicator(status: CheckStatus) -> (IconName, Color, &'static str) {
+    match status {
+        CheckStatus::Success => (IconName::Check, Color::Success, "Checks passed"),
+        CheckStatus::Pending => (IconName::CountdownTimer, Color::Warning, "Checks running"),
+        CheckStatus::Failure => (IconName::XCircle, Color::Error, "Checks failed"),
+    }
+}
+
+fn review_indicator(review: ReviewState) -> (IconName, Color, &'static str) {
+    match review {
+        ReviewState::Approved => (IconName::UserCheck, Color::Success, "Approved"),
+        ReviewState::ChangesRequested => {
+            (IconName::UserRoundPen, Color::Warning, "Changes requested")
+        }
+    }
+}
+
+impl Render for PullRequestStatusIndicator {
+    fn render(&mut self, _window: &mut Window, _cx: &mut Context<Self>) -> impl IntoElement {
+        let Some(status) = self.status.clone() else {
+            return div().into_any_element();
+        };
+        let checks = status.checks_status().map(checks_indicator);
+        let review = status.review.map(review_indicator);
+        let summary = checks
+            .iter()
+            .chain(review.iter())
+            .map(|(_, _, description)| *description)
+            .collect::<Vec<_>>()
+            .join(", ");
+        let number_label = format!("#{}", status.number);
+        let tooltip_title: SharedString = format!("Pull Request #{}", status.number).into();
+        let tooltip_meta: SharedString = if summary.is_empty() {
+            status.title.clone()
+        } else {
+            format!("{}\n{summary}", status.title).into()
+        };
+
+        PopoverMenu::new("pull-request-status-menu")
+            .menu(move |window, cx| Some(Self::build_menu(status.clone(), window, cx)))
+            .trigger_with_tooltip(
+                ButtonLike::new("pull-request-status-trigger").child(
+                    h_flex()
+                        .gap_0p5()
+                        .child(
+                            Icon::new(IconName::PullRequest)
+                                .size(IconSize::XSmall)
+                                .color(Color::Muted),
+                        )
+                        .child(
+                            Label::new(number_label)
+                                .size(LabelSize::Small)
+                                .color(Color::Muted),
+                        )
+                        .children(checks.into_iter().chain(review).map(|(icon, color, _)| {
+                            Icon::new(icon).size(IconSize::XSmall).color(color)
+                        })),
+                ),
+                move |_window, cx| {
+                    Tooltip::with_meta(tooltip_title.clone(), None, tooltip_meta.clone(), cx)
+                },
+            )
+            .anchor(gpui::Corner::TopLeft)
+            .into_any_element()
+    }
+}
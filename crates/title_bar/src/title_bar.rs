@@ -2,6 +2,7 @@ mod application_menu;
 pub mod collab;
 mod onboarding_banner;
 mod plan_chip;
+mod pull_request_status;
 mod title_bar_settings;
 mod update_version;
 
@@ -10,6 +11,7 @@ mod stories;
 
 use crate::application_menu::{ApplicationMenu, show_menus};
 use crate::plan_chip::PlanChip;
+use crate::pull_request_status::PullRequestStatusIndicator;
 pub use platform_title_bar::{
     self, DraggedWindowTab, MergeAllWindows, MoveTabToNewWindow, PlatformTitleBar,
     ShowNextWindowTab, ShowPreviousWindowTab,
@@ -156,6 +158,7 @@ pub struct TitleBar {
     _subscriptions: Vec<Subscription>,
     banner: Entity<OnboardingBanner>,
     update_version: Entity<UpdateVersion>,
+    pull_request_status: Entity<PullRequestStatusIndicator>,
     screen_share_popover_handle: PopoverMenuHandle<ContextMenu>,
     _diagnostics_subscription: Option<gpui::Subscription>,
 }
@@ -236,6 +239,11 @@ impl Render for TitleBar {
                                         ))
                                     },
                                 )
+                                .when(
+                                    title_bar_settings.show_branch_name
+                                        && title_bar_settings.show_pull_request_status,
+                                    |title_bar| title_bar.child(self.pull_request_status.clone()),
+                                )
                         })
                 })
                 .on_mouse_down(MouseButton::Left, |_, _, cx| cx.stop_propagation())
@@ -398,6 +406,7 @@ impl TitleBar {
         });
 
         let update_version = cx.new(|cx| UpdateVersion::new(cx));
+        let pull_request_status = cx.new(|cx| PullRequestStatusIndicator::new(project.clone(), cx));
         let platform_titlebar = cx.new(|cx| {
             let mut titlebar = PlatformTitleBar::new(id, cx);
             if let Some(mw) = multi_workspace.clone() {
@@ -417,6 +426,7 @@ impl TitleBar {
             _subscriptions: subscriptions,
             banner,
             update_version,
+            pull_request_status,
             screen_share_popover_handle: PopoverMenuHandle::default(),
             _diagnostics_subscription: None,
         };
@@ -863,7 +873,7 @@ impl TitleBar {
     ) -> Option<impl IntoElement> {
         let workspace = self.workspace.upgrade()?;
 
-        let (branch_name, icon_info) = {
+        let (branch_name, icon_info, head_detached) = {
             let repo = repository.read(cx);
 
             let branch_name = repo
@@ -895,7 +905,7 @@ impl TitleBar {
                 (IconName::GitBranch, Color::Muted)
             };
 
-            (branch_name, icon_info)
+            (branch_name, icon_info, repo.is_head_detached())
         };
 
         let branch_name = branch_name?;
@@ -940,17 +950,23 @@ impl TitleBar {
                                         ),
                                     )
                                 })
-                                .child(
-                                    Label::new(branch_name)
-                                        .size(LabelSize::Small)
-                                        .color(Color::Muted),
-                                ),
+                                .child(Label::new(branch_name).size(LabelSize::Small).color(
+                                    if head_detached {
+                                        Color::Warning
+                                    } else {
+                                        Color::Muted
+                                    },
+                                )),
                         ),
                     move |_window, cx| {
                         Tooltip::with_meta(
                             "Git Switcher",
                             Some(&zed_actions::git::Branch),
-                            "Worktrees, Branches, and Stashes",
+                            if head_detached {
+                                "Detached HEAD: new commits won't belong to a branch"
+                            } else {
+                                "Worktrees, Branches, and Stashes"
+                            },
                             cx,
                         )
                     },
@@ -7,6 +7,7 @@ pub struct TitleBarSettings {
     pub show_onboarding_banner: bool,
     pub show_user_picture: bool,
     pub show_branch_name: bool,
+    pub show_pull_request_status: bool,
     pub show_project_items: bool,
     pub show_sign_in: bool,
     pub show_user_menu: bool,
@@ -22,6 +23,7 @@ impl Settings for TitleBarSettings {
             show_onboarding_banner: content.show_onboarding_banner.unwrap(),
             show_user_picture: content.show_user_picture.unwrap(),
             show_branch_name: content.show_branch_name.unwrap(),
+            show_pull_request_status: content.show_pull_request_status.unwrap(),
             show_project_items: content.show_project_items.unwrap(),
             show_sign_in: content.show_sign_in.unwrap(),
             show_user_menu: content.show_user_menu.unwrap(),
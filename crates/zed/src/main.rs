@@ -837,6 +837,10 @@ fn main() {
             .chunks(2)
             .any(|pair| Path::new(&pair[0]).is_dir() || Path::new(&pair[1]).is_dir());
 
+        if !args.merge.is_empty() {
+            log::error!("--merge is only handled by an instance of Zed that is already running");
+        }
+
         let diff_paths: Vec<[String; 2]> = args
             .diff
             .chunks(2)
@@ -1611,6 +1615,12 @@ struct Args {
     #[arg(long, action = clap::ArgAction::Append, num_args = 2, value_names = ["OLD_PATH", "NEW_PATH"])]
     diff: Vec<String>,
 
+    /// Resolve a merge conflict between the local and remote versions of a file, as done by
+    /// `git mergetool`. Only handled when another instance of Zed is already running, which
+    /// this one hands the merge to and waits on.
+    #[arg(long, num_args = 4, value_names = ["BASE", "LOCAL", "REMOTE", "OUTPUT"], conflicts_with = "diff")]
+    merge: Vec<String>,
+
     /// Sets a custom directory for all user data (e.g., database, extensions, logs).
     ///
     /// This overrides the default platform-specific data directory location.
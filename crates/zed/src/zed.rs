@@ -30,8 +30,12 @@ use futures::FutureExt as _;
 use futures::future::Either;
 use futures::{StreamExt, channel::mpsc, select_biased};
 use git_ui::commit_view::CommitViewToolbar;
+use git_ui::conflict_view::ConflictToolbar;
+use git_ui::file_diff_view::FileDiffViewToolbar;
 use git_ui::git_panel::GitPanel;
+use git_ui::patch_view::PatchViewToolbar;
 use git_ui::project_diff::{BranchDiffToolbar, ProjectDiffToolbar};
+use git_ui::text_diff_view::TextDiffViewToolbar;
 use gpui::{
     Action, App, AppContext as _, AsyncWindowContext, Context, DismissEvent, Element, Entity,
     Focusable, KeyBinding, ParentElement, PathPromptOptions, PromptLevel, ReadGlobal, SharedString,
@@ -1259,6 +1263,14 @@ fn initialize_pane(
             toolbar.add_item(branch_diff_toolbar, window, cx);
             let commit_view_toolbar = cx.new(|_| CommitViewToolbar::new());
             toolbar.add_item(commit_view_toolbar, window, cx);
+            let file_diff_view_toolbar = cx.new(|_| FileDiffViewToolbar::new());
+            toolbar.add_item(file_diff_view_toolbar, window, cx);
+            let text_diff_view_toolbar = cx.new(|_| TextDiffViewToolbar::new());
+            toolbar.add_item(text_diff_view_toolbar, window, cx);
+            let conflict_toolbar = cx.new(|_| ConflictToolbar::new());
+            toolbar.add_item(conflict_toolbar, window, cx);
+            let patch_view_toolbar = cx.new(|_| PatchViewToolbar::new());
+            toolbar.add_item(patch_view_toolbar, window, cx);
             let agent_diff_toolbar = cx.new(AgentDiffToolbar::new);
             toolbar.add_item(agent_diff_toolbar, window, cx);
             let basedpyright_banner = cx.new(|cx| BasedPyrightBanner::new(workspace, cx));
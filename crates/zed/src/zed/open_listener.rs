@@ -13,7 +13,10 @@ use futures::channel::{mpsc, oneshot};
 use futures::future;
 
 use futures::{FutureExt, SinkExt, StreamExt};
-use git_ui::{file_diff_view::FileDiffView, multi_diff_view::MultiDiffView};
+use git_ui::{
+    file_diff_view::FileDiffView, multi_diff_view::MultiDiffView,
+    three_way_diff_view::ThreeWayDiffView,
+};
 use gpui::{App, AsyncApp, Global, WindowHandle};
 use onboarding::FIRST_OPEN;
 use onboarding::show_onboarding_view;
@@ -131,6 +134,8 @@ impl OpenRequest {
                 this.kind = Some(OpenRequestKind::Setting {
                     setting_path: Some(setting_path.to_string()),
                 });
+            } else if let Some(diff_path) = url.strip_prefix("zed://diff") {
+                this.parse_diff_url(diff_path)?
             } else if let Some(clone_path) = url.strip_prefix("zed://git/clone") {
                 this.parse_git_clone_url(clone_path)?
             } else if let Some(commit_path) = url.strip_prefix("zed://git/commit/") {
@@ -175,6 +180,40 @@ impl OpenRequest {
         });
     }
 
+    fn parse_diff_url(&mut self, diff_path: &str) -> Result<()> {
+        // Format: /?left=<path>&right=<path> or ?left=<path>&right=<path>
+        anyhow::ensure!(
+            diff_path.is_empty() || diff_path.starts_with(['/', '?']),
+            "invalid diff url: unknown path zed://diff{diff_path}"
+        );
+        let diff_path = diff_path.strip_prefix('/').unwrap_or(diff_path);
+
+        let query = diff_path
+            .strip_prefix('?')
+            .context("invalid diff url: missing query string")?;
+
+        let path_parameter = |name: &str| {
+            url::form_urlencoded::parse(query.as_bytes())
+                .find_map(|(key, value)| (key == name).then_some(value))
+                .filter(|path| !path.is_empty())
+                .map(|path| path.into_owned())
+                .with_context(|| format!("invalid diff url: missing {name} query parameter"))
+        };
+        let left = path_parameter("left")?;
+        let right = path_parameter("right")?;
+        // There is no working directory to resolve relative paths against.
+        for path in [&left, &right] {
+            anyhow::ensure!(
+                Path::new(path).is_absolute(),
+                "invalid diff url: {path} is not an absolute path"
+            );
+        }
+
+        self.diff_paths.push([left, right]);
+
+        Ok(())
+    }
+
     fn parse_git_clone_url(&mut self, clone_path: &str) -> Result<()> {
         // Format: /?repo=<url> or ?repo=<url>
         let clone_path = clone_path.strip_prefix('/').unwrap_or(clone_path);
@@ -407,6 +446,7 @@ pub async fn handle_cli_connection(
                 paths,
                 diff_paths,
                 diff_all,
+                merge_paths,
                 wait,
                 wsl,
                 open_new_workspace,
@@ -414,6 +454,44 @@ pub async fn handle_cli_connection(
                 env,
                 user_data_dir: _,
             } => {
+                if let Some(merge_paths) = merge_paths {
+                    let open_options = workspace::OpenOptions {
+                        open_new_workspace,
+                        env,
+                        ..Default::default()
+                    };
+                    let status = match open_merge_tool(
+                        merge_paths,
+                        open_options,
+                        &responses,
+                        app_state.clone(),
+                        cx,
+                    )
+                    .await
+                    {
+                        Ok(true) => 0,
+                        Ok(false) => {
+                            responses
+                                .send(CliResponse::Stderr {
+                                    message: "merge was closed without saving the result"
+                                        .to_string(),
+                                })
+                                .log_err();
+                            1
+                        }
+                        Err(error) => {
+                            responses
+                                .send(CliResponse::Stderr {
+                                    message: format!("error opening merge: {error}"),
+                                })
+                                .log_err();
+                            1
+                        }
+                    };
+                    responses.send(CliResponse::Exit { status }).log_err();
+                    return;
+                }
+
                 if !urls.is_empty() {
                     cx.update(|cx| {
                         match OpenRequest::parse(
@@ -624,13 +702,17 @@ async fn open_local_workspace(
     let mut item_release_futures = Vec::new();
     let mut subscriptions = Vec::new();
     // If --wait flag is used with no paths, or a directory, then wait until
-    // the entire workspace is closed.
+    // the entire workspace is closed. A directory opened alongside diffs only
+    // gives them a workspace, so the diffs are waited on instead, which lets
+    // tools such as `git difftool` continue once a diff is closed.
     if open_options.wait {
         let mut wait_for_window_close = paths_with_position.is_empty() && diff_paths.is_empty();
-        for path_with_position in &paths_with_position {
-            if app_state.fs.is_dir(&path_with_position.path).await {
-                wait_for_window_close = true;
-                break;
+        if diff_paths.is_empty() {
+            for path_with_position in &paths_with_position {
+                if app_state.fs.is_dir(&path_with_position.path).await {
+                    wait_for_window_close = true;
+                    break;
+                }
             }
         }
 
@@ -700,6 +782,61 @@ async fn open_local_workspace(
     errored
 }
 
+/// Opens the three-way merge of a `git mergetool` invocation and waits until it is closed,
+/// returning whether the result was saved to the output file.
+async fn open_merge_tool(
+    merge_paths: [String; 4],
+    open_options: workspace::OpenOptions,
+    responses: &IpcSender<CliResponse>,
+    app_state: Arc<AppState>,
+    cx: &mut AsyncApp,
+) -> Result<bool> {
+    let [base_path, local_path, remote_path, output_path] = merge_paths.map(PathBuf::from);
+    let (multi_workspace, _) =
+        open_paths_with_positions(&[], &[], false, app_state, open_options, cx).await?;
+    let workspace = multi_workspace.read_with(cx, |multi_workspace, _| {
+        multi_workspace.workspace().downgrade()
+    })?;
+    let merge_view = multi_workspace
+        .update(cx, |_, window, cx| {
+            ThreeWayDiffView::open_merge(
+                base_path,
+                local_path,
+                remote_path,
+                output_path,
+                workspace,
+                window,
+                cx,
+            )
+        })?
+        .await?;
+
+    let (release_tx, release_rx) = oneshot::channel();
+    let _subscription = merge_view.update(cx, |_, cx| {
+        cx.on_release(move |merge_view, cx| {
+            release_tx.send(merge_view.result_written(cx)).ok();
+        })
+    });
+    // Holding on to the view would keep it alive after its tab is closed.
+    drop(merge_view);
+
+    let release = release_rx.fuse();
+    futures::pin_mut!(release);
+    let background = cx.background_executor().clone();
+    loop {
+        // git waits on the CLI, so stop waiting once it is gone.
+        let mut timer = background.timer(Duration::from_secs(1)).fuse();
+        futures::select_biased! {
+            result_written = release => return Ok(result_written.unwrap_or(false)),
+            _ = timer => {
+                if responses.send(CliResponse::Ping).is_err() {
+                    return Ok(false);
+                }
+            }
+        }
+    }
+}
+
 pub async fn derive_paths_with_position(
     fs: &dyn Fs,
     path_strings: impl IntoIterator<Item = impl AsRef<str>>,
@@ -990,6 +1127,66 @@ mod tests {
         );
     }
 
+    #[gpui::test]
+    fn test_parse_diff_url(cx: &mut TestAppContext) {
+        let _app_state = init_test(cx);
+
+        let url = format!(
+            "zed://diff?left={}&right={}",
+            urlencoding::encode(path!("/tmp/old file.rs")),
+            urlencoding::encode(path!("/tmp/new.rs"))
+        );
+        let request = cx.update(|cx| {
+            OpenRequest::parse(
+                RawOpenRequest {
+                    urls: vec![url],
+                    ..Default::default()
+                },
+                cx,
+            )
+            .unwrap()
+        });
+        assert!(request.kind.is_none());
+        assert!(request.open_paths.is_empty());
+        assert_eq!(
+            request.diff_paths,
+            vec![[
+                path!("/tmp/old file.rs").to_string(),
+                path!("/tmp/new.rs").to_string()
+            ]]
+        );
+
+        for (url, error) in [
+            ("zed://diff", "missing query string"),
+            (
+                "zed://diffs?left=/tmp/old.rs&right=/tmp/new.rs",
+                "unknown path",
+            ),
+            (
+                "zed://diff?left=/tmp/old.rs",
+                "missing right query parameter",
+            ),
+            (
+                "zed://diff?left=old.rs&right=new.rs",
+                "is not an absolute path",
+            ),
+        ] {
+            let result = cx.update(|cx| {
+                OpenRequest::parse(
+                    RawOpenRequest {
+                        urls: vec![url.into()],
+                        ..Default::default()
+                    },
+                    cx,
+                )
+            });
+            assert!(
+                result.unwrap_err().to_string().contains(error),
+                "unexpected error for {url}"
+            );
+        }
+    }
+
     #[gpui::test]
     async fn test_open_workspace_with_directory(cx: &mut TestAppContext) {
         let app_state = init_test(cx);
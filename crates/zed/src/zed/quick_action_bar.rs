@@ -123,6 +123,7 @@ impl Render for QuickActionBar {
         let inline_diagnostics_enabled = editor_value.show_inline_diagnostics();
         let git_blame_inline_enabled = editor_value.git_blame_inline_enabled();
         let show_git_blame_gutter = editor_value.show_git_blame_gutter();
+        let git_blame_heat_map_enabled = editor_value.git_blame_heat_map_enabled(cx);
         let auto_signature_help_enabled = editor_value.auto_signature_help_enabled(cx);
         let show_line_numbers = editor_value.line_numbers_enabled(cx);
         let has_edit_prediction_provider = editor_value.edit_prediction_provider().is_some();
@@ -608,6 +609,27 @@ impl Render for QuickActionBar {
                                 },
                             );
 
+                            menu = menu.toggleable_entry(
+                                "Git Blame Heat Map",
+                                git_blame_heat_map_enabled,
+                                IconPosition::Start,
+                                Some(editor::actions::ToggleGitBlameHeatMap.boxed_clone()),
+                                {
+                                    let editor = editor.clone();
+                                    move |window, cx| {
+                                        editor
+                                            .update(cx, |editor, cx| {
+                                                editor.toggle_git_blame_heat_map(
+                                                    &editor::actions::ToggleGitBlameHeatMap,
+                                                    window,
+                                                    cx,
+                                                )
+                                            })
+                                            .ok();
+                                    }
+                                },
+                            );
+
                             menu = menu.separator();
 
                             menu = menu.toggleable_entry(
@@ -4,7 +4,7 @@ use anyhow::Context;
 use cli::{CliRequest, CliResponse, IpcHandshake, ipc::IpcOneShotServer};
 use parking_lot::Mutex;
 use release_channel::app_identifier;
-use util::ResultExt;
+use util::{ResultExt, maybe};
 use windows::{
     Win32::{
         Foundation::{CloseHandle, ERROR_ALREADY_EXISTS, GENERIC_WRITE, GetLastError, HANDLE},
@@ -151,11 +151,29 @@ fn send_args_to_instance(args: &Args) -> anyhow::Result<()> {
             }
         }
 
+        let merge_paths = match args.merge.as_slice() {
+            [base, local, remote, output] => maybe!({
+                let canonicalize = |path: &String| {
+                    std::fs::canonicalize(path)
+                        .log_err()
+                        .map(|path| path.to_string_lossy().into_owned())
+                };
+                Some([
+                    canonicalize(base)?,
+                    canonicalize(local)?,
+                    canonicalize(remote)?,
+                    canonicalize(output)?,
+                ])
+            }),
+            _ => None,
+        };
+
         CliRequest::Open {
             paths,
             urls,
             diff_paths,
             diff_all: false,
+            merge_paths,
             wait: false,
             wsl: args.wsl.clone(),
             open_new_workspace: None,
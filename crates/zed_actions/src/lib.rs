@@ -275,7 +275,11 @@ pub mod git {
             /// Opens the git worktree selector.
             Worktree,
             /// Creates a pull request for the current branch.
-            CreatePullRequest
+            CreatePullRequest,
+            /// Opens the current branch on the web page of its remote.
+            OpenBranchOnRemote,
+            /// Checks out an open pull request of the remote into a local branch.
+            CheckoutPullRequest
         ]
     );
 }